@@ -0,0 +1,43 @@
+//! Example: Backtest keeper strategies against recorded events
+//!
+//! This example replays an indexer export (JSON array or JSON lines) through
+//! the protocol model and compares keeper strategies with different profit
+//! thresholds.
+//!
+//! ```bash
+//! cargo run --example backtest -- events.json
+//! ```
+
+use stellarlend_client::backtest::{
+    load_recorded_events, Backtester, CloseFactorStrategy, KeeperStrategy, ModelParams,
+};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::env::args()
+        .nth(1)
+        .ok_or("usage: backtest <events.json>")?;
+    let events = load_recorded_events(&std::fs::read_to_string(path)?)?;
+    println!(
+        "=== StellarLend Keeper Backtest ({} events) ===\n",
+        events.len()
+    );
+
+    let mut strategies: Vec<Box<dyn KeeperStrategy>> = vec![
+        Box::new(CloseFactorStrategy::new(0)),
+        Box::new(CloseFactorStrategy::new(1_000)),
+        Box::new(CloseFactorStrategy::new(10_000)),
+    ];
+    let reports = Backtester::new(ModelParams::default()).run_batch(&events, &mut strategies);
+
+    for report in reports {
+        println!("{}", report.strategy);
+        println!("  liquidations:       {}", report.liquidations.len());
+        println!("  debt repaid:        {}", report.total_debt_repaid);
+        println!("  collateral seized:  {}", report.total_collateral_seized);
+        println!("  profit:             {}", report.total_profit);
+        println!("  bad debt:           {}", report.bad_debt);
+        println!("  still liquidatable: {}\n", report.open_liquidatable);
+    }
+
+    Ok(())
+}
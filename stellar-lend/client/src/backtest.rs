@@ -0,0 +1,969 @@
+//! Keeper strategy backtesting.
+//!
+//! Replays protocol events recorded by the indexer through a pure-Rust model
+//! of the contract's interest and liquidation math, so keeper strategies can
+//! be evaluated against historical data before they are deployed. Nothing
+//! here talks to the network.
+//!
+//! # Model
+//!
+//! The model follows one market: deposits of `collateral_asset` and borrows of
+//! `debt_asset` (`None` = native XLM), mirroring the contract:
+//!
+//! - Borrow interest accrues linearly on the principal at the kinked
+//!   utilization rate (`interest_rate::calculate_borrow_rate`), per position
+//!   since its last update.
+//! - A position is liquidatable when `collateral * 10000 / debt` falls below
+//!   the liquidation threshold. A liquidation repays at most `close_factor`
+//!   of the debt (interest first), and seizes the repaid value plus the
+//!   liquidation incentive, capped at the collateral. Debt left without
+//!   collateral is written off as bad debt.
+//! - Collateral is converted to debt terms with the last recorded prices of
+//!   both assets; without prices, one unit of each is worth the same.
+//!
+//! Events for other assets, and events the model does not understand, are
+//! counted as skipped.
+//!
+//! # Replay
+//!
+//! Events are replayed in ledger order. After each event the strategy
+//! inspects the model and may liquidate; its liquidations change the model,
+//! so recorded liquidations by other keepers are clamped to what is left of
+//! the position. [`Backtester::run_batch`] replays the same history once per
+//! strategy, each against a fresh model.
+//!
+//! ```rust,no_run
+//! use stellarlend_client::backtest::{
+//!     load_recorded_events, Backtester, CloseFactorStrategy, ModelParams,
+//! };
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let events = load_recorded_events(&std::fs::read_to_string("events.json")?)?;
+//! let report = Backtester::new(ModelParams::default()).run(&events, &mut CloseFactorStrategy::new(0));
+//! println!("profit: {}", report.total_profit);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{BlockchainError, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+/// Basis point scale (100% = 10000)
+pub const BPS_SCALE: i128 = 10_000;
+
+/// Interest rate model parameters (basis points), as in the contract's
+/// `InterestRateConfig`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateModel {
+    /// Rate at 0% utilization
+    pub base_rate_bps: i128,
+    /// Utilization where the jump multiplier starts
+    pub kink_utilization_bps: i128,
+    /// Rate added between 0% utilization and the kink
+    pub multiplier_bps: i128,
+    /// Rate added between the kink and 100% utilization
+    pub jump_multiplier_bps: i128,
+    /// Minimum rate
+    pub rate_floor_bps: i128,
+    /// Maximum rate
+    pub rate_ceiling_bps: i128,
+    /// Emergency adjustment added to the rate
+    pub emergency_adjustment_bps: i128,
+}
+
+impl Default for RateModel {
+    fn default() -> Self {
+        Self {
+            base_rate_bps: 100,
+            kink_utilization_bps: 8_000,
+            multiplier_bps: 2_000,
+            jump_multiplier_bps: 10_000,
+            rate_floor_bps: 50,
+            rate_ceiling_bps: 10_000,
+            emergency_adjustment_bps: 0,
+        }
+    }
+}
+
+impl RateModel {
+    /// Annual borrow rate (basis points) at a utilization (basis points)
+    pub fn borrow_rate(&self, utilization_bps: i128) -> i128 {
+        let utilization = utilization_bps.clamp(0, BPS_SCALE);
+        let rate = if utilization <= self.kink_utilization_bps {
+            let increase = if self.kink_utilization_bps > 0 {
+                utilization * self.multiplier_bps / self.kink_utilization_bps
+            } else {
+                0
+            };
+            self.base_rate_bps + increase
+        } else {
+            let rate_at_kink = self.base_rate_bps + self.multiplier_bps;
+            let span = BPS_SCALE - self.kink_utilization_bps;
+            if span > 0 {
+                rate_at_kink
+                    + (utilization - self.kink_utilization_bps) * self.jump_multiplier_bps / span
+            } else {
+                rate_at_kink
+            }
+        };
+        (rate + self.emergency_adjustment_bps)
+            .max(self.rate_floor_bps)
+            .min(self.rate_ceiling_bps)
+    }
+}
+
+/// Model configuration; the defaults match a freshly initialized contract
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelParams {
+    /// Interest rate model
+    pub rates: RateModel,
+    /// Collateral ratio (basis points) below which positions are liquidatable
+    pub liquidation_threshold: i128,
+    /// Largest share of the debt (basis points) repaid per liquidation
+    pub close_factor: i128,
+    /// Liquidator bonus (basis points) on the repaid value
+    pub liquidation_incentive: i128,
+    /// Year length used to annualize rates
+    pub seconds_per_year: u64,
+    /// Collateral asset followed by the model (`None` = native XLM)
+    pub collateral_asset: Option<String>,
+    /// Debt asset followed by the model (`None` = native XLM)
+    pub debt_asset: Option<String>,
+}
+
+impl Default for ModelParams {
+    fn default() -> Self {
+        Self {
+            rates: RateModel::default(),
+            liquidation_threshold: 10_500,
+            close_factor: 5_000,
+            liquidation_incentive: 1_000,
+            seconds_per_year: 365 * 24 * 60 * 60,
+            collateral_asset: None,
+            debt_asset: None,
+        }
+    }
+}
+
+/// Event as recorded by the indexer
+///
+/// `event_name` is the event's topic (`deposit_event`, `borrow`, ...) and
+/// `event_data` its decoded fields. The ledger timestamp is read from
+/// `timestamp`, falling back to `event_data.timestamp`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    /// Event topic
+    pub event_name: String,
+    /// Ledger the event was emitted in
+    #[serde(default)]
+    pub block_number: u64,
+    /// Position of the event within its transaction
+    #[serde(default)]
+    pub log_index: u32,
+    /// Ledger timestamp
+    #[serde(default)]
+    pub timestamp: Option<u64>,
+    /// Decoded event fields
+    pub event_data: Value,
+}
+
+impl RecordedEvent {
+    fn ledger_time(&self) -> u64 {
+        self.timestamp
+            .or_else(|| json_u64(&self.event_data["timestamp"]))
+            .unwrap_or(0)
+    }
+}
+
+/// Parse recorded events from a JSON array or JSON lines export
+pub fn load_recorded_events(input: &str) -> Result<Vec<RecordedEvent>> {
+    let trimmed = input.trim_start();
+    if trimmed.starts_with('[') {
+        return Ok(serde_json::from_str(trimmed)?);
+    }
+    trimmed
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).map_err(BlockchainError::from))
+        .collect()
+}
+
+/// Reasons the model rejects a liquidation
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ModelError {
+    /// Amount is not positive
+    #[error("invalid amount")]
+    InvalidAmount,
+    /// Position is above the liquidation threshold or has no debt
+    #[error("position is not liquidatable")]
+    NotLiquidatable,
+    /// Amount exceeds the close factor
+    #[error("amount exceeds the close factor")]
+    ExceedsCloseFactor,
+    /// Arithmetic overflow
+    #[error("arithmetic overflow")]
+    Overflow,
+}
+
+/// Modelled borrower position
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ModelPosition {
+    /// Collateral balance
+    pub collateral: i128,
+    /// Borrowed principal
+    pub debt: i128,
+    /// Accrued, unpaid interest
+    pub borrow_interest: i128,
+    /// Timestamp interest was last accrued
+    pub last_accrual_time: u64,
+}
+
+impl ModelPosition {
+    /// Principal plus accrued interest
+    pub fn total_debt(&self) -> i128 {
+        self.debt.saturating_add(self.borrow_interest)
+    }
+
+    fn repay(&mut self, amount: i128) {
+        let interest = amount.min(self.borrow_interest);
+        self.borrow_interest -= interest;
+        self.debt = (self.debt - (amount - interest)).max(0);
+    }
+}
+
+/// Result of a liquidation applied to the model
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LiquidationOutcome {
+    /// Liquidated borrower
+    pub borrower: String,
+    /// Timestamp of the liquidation
+    pub timestamp: u64,
+    /// Debt repaid by the liquidator
+    pub debt_repaid: i128,
+    /// Collateral received by the liquidator
+    pub collateral_seized: i128,
+    /// Value of the seized collateral minus the repaid debt, in debt terms
+    pub profit: i128,
+    /// Debt written off because the collateral ran out
+    pub bad_debt: i128,
+}
+
+/// Pure-Rust model of the protocol state
+#[derive(Debug, Clone)]
+pub struct ProtocolModel {
+    params: ModelParams,
+    positions: BTreeMap<String, ModelPosition>,
+    prices: BTreeMap<String, i128>,
+    total_deposits: i128,
+    total_borrows: i128,
+    bad_debt: i128,
+    now: u64,
+}
+
+impl ProtocolModel {
+    /// Create an empty model
+    pub fn new(params: ModelParams) -> Self {
+        Self {
+            params,
+            positions: BTreeMap::new(),
+            prices: BTreeMap::new(),
+            total_deposits: 0,
+            total_borrows: 0,
+            bad_debt: 0,
+            now: 0,
+        }
+    }
+
+    /// Model configuration
+    pub fn params(&self) -> &ModelParams {
+        &self.params
+    }
+
+    /// Current model time
+    pub fn now(&self) -> u64 {
+        self.now
+    }
+
+    /// All positions, by borrower
+    pub fn positions(&self) -> &BTreeMap<String, ModelPosition> {
+        &self.positions
+    }
+
+    /// Debt written off so far
+    pub fn bad_debt(&self) -> i128 {
+        self.bad_debt
+    }
+
+    /// Utilization (basis points) of the modelled market
+    pub fn utilization(&self) -> i128 {
+        if self.total_deposits <= 0 {
+            return 0;
+        }
+        (self.total_borrows.saturating_mul(BPS_SCALE) / self.total_deposits).min(BPS_SCALE)
+    }
+
+    /// Current annual borrow rate (basis points)
+    pub fn borrow_rate(&self) -> i128 {
+        self.params.rates.borrow_rate(self.utilization())
+    }
+
+    /// A borrower's position with interest accrued to the model time
+    pub fn position(&self, borrower: &str) -> Option<ModelPosition> {
+        let mut position = self.positions.get(borrower)?.clone();
+        self.accrue(&mut position);
+        Some(position)
+    }
+
+    /// Collateral converted to debt terms at the last recorded prices
+    pub fn collateral_value(&self, collateral: i128) -> i128 {
+        match self.prices_of_market() {
+            Some((collateral_price, debt_price)) => {
+                collateral.saturating_mul(collateral_price) / debt_price
+            }
+            None => collateral,
+        }
+    }
+
+    /// Collateral ratio (basis points) of a position, `None` without debt
+    pub fn collateral_ratio(&self, borrower: &str) -> Option<i128> {
+        let position = self.position(borrower)?;
+        let debt = position.total_debt();
+        (debt > 0).then(|| {
+            self.collateral_value(position.collateral)
+                .saturating_mul(BPS_SCALE)
+                / debt
+        })
+    }
+
+    /// Borrowers whose collateral ratio is below the liquidation threshold
+    pub fn liquidatable(&self) -> Vec<String> {
+        self.positions
+            .keys()
+            .filter(|borrower| {
+                self.collateral_ratio(borrower)
+                    .is_some_and(|ratio| ratio < self.params.liquidation_threshold)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Largest debt amount a single liquidation of the borrower may repay
+    pub fn max_liquidatable(&self, borrower: &str) -> i128 {
+        self.position(borrower)
+            .map(|position| {
+                position
+                    .total_debt()
+                    .saturating_mul(self.params.close_factor)
+                    / BPS_SCALE
+            })
+            .unwrap_or(0)
+    }
+
+    /// Liquidate a position as the contract would
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - Amount is not positive
+    /// * `NotLiquidatable` - The position is healthy or has no debt
+    /// * `ExceedsCloseFactor` - Amount exceeds the close factor
+    pub fn liquidate(
+        &mut self,
+        borrower: &str,
+        debt_amount: i128,
+    ) -> std::result::Result<LiquidationOutcome, ModelError> {
+        if debt_amount <= 0 {
+            return Err(ModelError::InvalidAmount);
+        }
+        let position = self.position(borrower).ok_or(ModelError::NotLiquidatable)?;
+        let total_debt = position.total_debt();
+        let ratio = self
+            .collateral_ratio(borrower)
+            .ok_or(ModelError::NotLiquidatable)?;
+        if ratio >= self.params.liquidation_threshold {
+            return Err(ModelError::NotLiquidatable);
+        }
+        if debt_amount > self.max_liquidatable(borrower) {
+            return Err(ModelError::ExceedsCloseFactor);
+        }
+
+        let repaid = debt_amount.min(total_debt);
+        let seized = self.seized_for(repaid, self.params.liquidation_incentive)?;
+        Ok(self.apply_liquidation(borrower, position, repaid, seized))
+    }
+
+    /// Apply a recorded event
+    ///
+    /// Returns false for events the model skips.
+    pub fn apply(&mut self, event: &RecordedEvent) -> bool {
+        self.now = self.now.max(event.ledger_time());
+        let data = &event.event_data;
+        let name = event_kind(&event.event_name);
+        let asset = asset_of(&data["asset"]);
+        let user = data["user"].as_str().map(String::from);
+        let amount = json_i128(&data["amount"]);
+
+        match (name.as_str(), user, amount) {
+            ("deposit", Some(user), Some(amount)) if asset == self.params.collateral_asset => {
+                self.update(&user, |position| position.collateral += amount);
+                self.total_deposits += amount;
+                true
+            }
+            ("withdrawal" | "withdraw", Some(user), Some(amount))
+                if asset == self.params.collateral_asset =>
+            {
+                let amount = self.update(&user, |position| {
+                    let amount = amount.min(position.collateral);
+                    position.collateral -= amount;
+                    amount
+                });
+                self.total_deposits = (self.total_deposits - amount).max(0);
+                true
+            }
+            ("borrow", Some(user), Some(amount)) if asset == self.params.debt_asset => {
+                self.update(&user, |position| position.debt += amount);
+                self.total_borrows += amount;
+                true
+            }
+            ("repay", Some(user), Some(amount)) if asset == self.params.debt_asset => {
+                let principal = self.update(&user, |position| {
+                    let debt_before = position.debt;
+                    position.repay(amount.min(position.total_debt()));
+                    debt_before - position.debt
+                });
+                self.total_borrows = (self.total_borrows - principal).max(0);
+                true
+            }
+            ("liquidation" | "liquidation_auction_bid", _, _) => {
+                self.apply_recorded_liquidation(data)
+            }
+            ("price_updated" | "price_update", _, _) => {
+                match (data["asset"].as_str(), json_i128(&data["price"])) {
+                    (Some(asset), Some(price)) if price > 0 => {
+                        self.prices.insert(asset.to_string(), price);
+                        true
+                    }
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    fn apply_recorded_liquidation(&mut self, data: &Value) -> bool {
+        let (Some(borrower), Some(repaid), Some(seized)) = (
+            data["borrower"].as_str(),
+            json_i128(&data["debt_liquidated"]),
+            json_i128(&data["collateral_seized"]),
+        ) else {
+            return false;
+        };
+        let Some(position) = self.position(borrower) else {
+            return false;
+        };
+        // Another keeper's liquidation, clamped to what the strategy left over
+        let repaid = repaid.min(position.total_debt());
+        let seized = seized.min(position.collateral);
+        self.apply_liquidation(borrower, position, repaid, seized);
+        true
+    }
+
+    fn apply_liquidation(
+        &mut self,
+        borrower: &str,
+        mut position: ModelPosition,
+        repaid: i128,
+        seized: i128,
+    ) -> LiquidationOutcome {
+        let principal_before = position.debt;
+        position.repay(repaid);
+        let seized = seized.min(position.collateral);
+        position.collateral -= seized;
+        let mut principal_released = principal_before - position.debt;
+
+        let mut bad_debt = 0;
+        if position.collateral == 0 && position.total_debt() > 0 {
+            bad_debt = position.total_debt();
+            principal_released += position.debt;
+            position.debt = 0;
+            position.borrow_interest = 0;
+        }
+        self.bad_debt += bad_debt;
+        self.total_borrows = (self.total_borrows - principal_released).max(0);
+        self.total_deposits = (self.total_deposits - seized).max(0);
+        self.positions.insert(borrower.to_string(), position);
+
+        LiquidationOutcome {
+            borrower: borrower.to_string(),
+            timestamp: self.now,
+            debt_repaid: repaid,
+            collateral_seized: seized,
+            profit: self.collateral_value(seized) - repaid,
+            bad_debt,
+        }
+    }
+
+    /// Collateral seized for `repaid` debt with an incentive, before the
+    /// collateral cap
+    fn seized_for(
+        &self,
+        repaid: i128,
+        incentive_bps: i128,
+    ) -> std::result::Result<i128, ModelError> {
+        let in_collateral = match self.prices_of_market() {
+            Some((collateral_price, debt_price)) => {
+                repaid.checked_mul(debt_price).ok_or(ModelError::Overflow)? / collateral_price
+            }
+            None => repaid,
+        };
+        in_collateral
+            .checked_mul(BPS_SCALE + incentive_bps)
+            .map(|value| value / BPS_SCALE)
+            .ok_or(ModelError::Overflow)
+    }
+
+    /// Accrue a stored position and apply `change` to it
+    fn update<T>(&mut self, user: &str, change: impl FnOnce(&mut ModelPosition) -> T) -> T {
+        let mut position = self.position(user).unwrap_or(ModelPosition {
+            last_accrual_time: self.now,
+            ..Default::default()
+        });
+        let result = change(&mut position);
+        self.positions.insert(user.to_string(), position);
+        result
+    }
+
+    fn accrue(&self, position: &mut ModelPosition) {
+        if position.debt > 0 && self.now > position.last_accrual_time {
+            let elapsed = (self.now - position.last_accrual_time) as i128;
+            let interest = position
+                .debt
+                .saturating_mul(self.borrow_rate())
+                .saturating_mul(elapsed)
+                / (BPS_SCALE * self.params.seconds_per_year as i128);
+            position.borrow_interest = position.borrow_interest.saturating_add(interest);
+        }
+        position.last_accrual_time = position.last_accrual_time.max(self.now);
+    }
+
+    /// Collateral and debt prices, when both assets differ and are priced
+    fn prices_of_market(&self) -> Option<(i128, i128)> {
+        if self.params.collateral_asset == self.params.debt_asset {
+            return None;
+        }
+        let price = |asset: &Option<String>| self.prices.get(asset.as_ref()?).copied();
+        Some((
+            price(&self.params.collateral_asset)?,
+            price(&self.params.debt_asset)?,
+        ))
+    }
+}
+
+/// Liquidation a strategy wants to perform
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LiquidationRequest {
+    /// Borrower to liquidate
+    pub borrower: String,
+    /// Debt amount to repay
+    pub debt_amount: i128,
+}
+
+/// Keeper strategy evaluated by the backtester
+pub trait KeeperStrategy {
+    /// Name used in reports
+    fn name(&self) -> String;
+
+    /// Decide which liquidations to attempt, called after every replayed event
+    fn on_step(&mut self, model: &ProtocolModel) -> Vec<LiquidationRequest>;
+}
+
+/// Liquidates every liquidatable position at the close factor, when the
+/// expected profit reaches `min_profit`
+#[derive(Debug, Clone)]
+pub struct CloseFactorStrategy {
+    /// Smallest profit (in debt terms) worth a transaction
+    pub min_profit: i128,
+}
+
+impl CloseFactorStrategy {
+    /// Create the strategy
+    pub fn new(min_profit: i128) -> Self {
+        Self { min_profit }
+    }
+}
+
+impl KeeperStrategy for CloseFactorStrategy {
+    fn name(&self) -> String {
+        format!("close_factor(min_profit={})", self.min_profit)
+    }
+
+    fn on_step(&mut self, model: &ProtocolModel) -> Vec<LiquidationRequest> {
+        model
+            .liquidatable()
+            .into_iter()
+            .filter_map(|borrower| {
+                let debt_amount = model.max_liquidatable(&borrower);
+                let mut preview = model.clone();
+                let outcome = preview.liquidate(&borrower, debt_amount).ok()?;
+                (outcome.profit >= self.min_profit).then_some(LiquidationRequest {
+                    borrower,
+                    debt_amount,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Outcome of replaying a history with one strategy
+#[derive(Debug, Clone, Serialize)]
+pub struct BacktestReport {
+    /// Strategy name
+    pub strategy: String,
+    /// Events applied to the model
+    pub events_replayed: usize,
+    /// Events the model skipped
+    pub events_skipped: usize,
+    /// Liquidations the strategy performed
+    pub liquidations: Vec<LiquidationOutcome>,
+    /// Liquidations the model rejected, with the reason
+    pub rejected: Vec<(LiquidationRequest, ModelError)>,
+    /// Debt repaid by the strategy
+    pub total_debt_repaid: i128,
+    /// Collateral seized by the strategy
+    pub total_collateral_seized: i128,
+    /// Strategy profit in debt terms
+    pub total_profit: i128,
+    /// Protocol bad debt at the end of the replay
+    pub bad_debt: i128,
+    /// Positions still liquidatable at the end of the replay
+    pub open_liquidatable: usize,
+}
+
+/// Replays recorded events against strategies
+#[derive(Debug, Clone)]
+pub struct Backtester {
+    params: ModelParams,
+}
+
+impl Backtester {
+    /// Create a backtester with the given model configuration
+    pub fn new(params: ModelParams) -> Self {
+        Self { params }
+    }
+
+    /// Replay the events with one strategy
+    pub fn run(
+        &self,
+        events: &[RecordedEvent],
+        strategy: &mut dyn KeeperStrategy,
+    ) -> BacktestReport {
+        let mut ordered: Vec<&RecordedEvent> = events.iter().collect();
+        ordered.sort_by_key(|event| (event.block_number, event.ledger_time(), event.log_index));
+
+        let mut model = ProtocolModel::new(self.params.clone());
+        let mut report = BacktestReport {
+            strategy: strategy.name(),
+            events_replayed: 0,
+            events_skipped: 0,
+            liquidations: Vec::new(),
+            rejected: Vec::new(),
+            total_debt_repaid: 0,
+            total_collateral_seized: 0,
+            total_profit: 0,
+            bad_debt: 0,
+            open_liquidatable: 0,
+        };
+
+        for event in ordered {
+            if model.apply(event) {
+                report.events_replayed += 1;
+            } else {
+                report.events_skipped += 1;
+            }
+
+            for request in strategy.on_step(&model) {
+                match model.liquidate(&request.borrower, request.debt_amount) {
+                    Ok(outcome) => {
+                        report.total_debt_repaid += outcome.debt_repaid;
+                        report.total_collateral_seized += outcome.collateral_seized;
+                        report.total_profit += outcome.profit;
+                        report.liquidations.push(outcome);
+                    }
+                    Err(error) => report.rejected.push((request, error)),
+                }
+            }
+        }
+
+        report.bad_debt = model.bad_debt();
+        report.open_liquidatable = model.liquidatable().len();
+        report
+    }
+
+    /// Replay the same events once per strategy
+    pub fn run_batch(
+        &self,
+        events: &[RecordedEvent],
+        strategies: &mut [Box<dyn KeeperStrategy>],
+    ) -> Vec<BacktestReport> {
+        strategies
+            .iter_mut()
+            .map(|strategy| self.run(events, strategy.as_mut()))
+            .collect()
+    }
+}
+
+/// Event topic without the `_event` suffix, in snake case
+fn event_kind(name: &str) -> String {
+    let mut kind = String::with_capacity(name.len());
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                kind.push('_');
+            }
+            kind.push(c.to_ascii_lowercase());
+        } else {
+            kind.push(c);
+        }
+    }
+    kind.strip_suffix("_event")
+        .map(String::from)
+        .unwrap_or(kind)
+}
+
+/// Asset field: `null` or `"native"` is native XLM
+fn asset_of(value: &Value) -> Option<String> {
+    value
+        .as_str()
+        .filter(|asset| !asset.eq_ignore_ascii_case("native"))
+        .map(String::from)
+}
+
+/// Amounts are exported as numbers or, for `i128`, as strings
+fn json_i128(value: &Value) -> Option<i128> {
+    match value {
+        Value::Number(n) => n.as_i64().map(i128::from),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn json_u64(value: &Value) -> Option<u64> {
+    match value {
+        Value::Number(n) => n.as_u64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const YEAR: u64 = 365 * 24 * 60 * 60;
+
+    fn event(name: &str, block: u64, data: Value) -> RecordedEvent {
+        RecordedEvent {
+            event_name: name.to_string(),
+            block_number: block,
+            log_index: 0,
+            timestamp: None,
+            event_data: data,
+        }
+    }
+
+    fn op(name: &str, block: u64, user: &str, amount: i128, timestamp: u64) -> RecordedEvent {
+        event(
+            name,
+            block,
+            json!({"user": user, "asset": null, "amount": amount.to_string(), "timestamp": timestamp}),
+        )
+    }
+
+    /// Alice at the minimum collateral ratio, Bob comfortably collateralized
+    fn history() -> Vec<RecordedEvent> {
+        vec![
+            op("deposit_event", 1, "alice", 11_000, 0),
+            op("borrow_event", 2, "alice", 10_000, 0),
+            op("deposit_event", 3, "bob", 100_000, 0),
+            op("borrow_event", 4, "bob", 10_000, 0),
+            // A year later Alice's interest has pushed her below 105%
+            op("repay_event", 5, "bob", 1, YEAR),
+        ]
+    }
+
+    #[test]
+    fn test_borrow_rate_matches_contract_kink_model() {
+        let rates = RateModel::default();
+        assert_eq!(rates.borrow_rate(0), 100);
+        assert_eq!(rates.borrow_rate(4_000), 1_100);
+        assert_eq!(rates.borrow_rate(8_000), 2_100);
+        assert_eq!(rates.borrow_rate(9_000), 7_100);
+        assert_eq!(rates.borrow_rate(10_000), 10_000);
+    }
+
+    #[test]
+    fn test_interest_accrues_at_utilization_rate() {
+        let mut model = ProtocolModel::new(ModelParams::default());
+        for event in &history()[..2] {
+            assert!(model.apply(event));
+        }
+        // 10_000 / 11_000 utilization = 9_090 bps → 100 + 2_000 + 1_090 * 10_000 / 2_000
+        assert_eq!(model.borrow_rate(), 7_550);
+
+        model.now = YEAR;
+        let position = model.position("alice").unwrap();
+        assert_eq!(position.borrow_interest, 7_550);
+        assert_eq!(model.liquidatable(), vec!["alice".to_string()]);
+    }
+
+    #[test]
+    fn test_liquidation_follows_close_factor_and_incentive() {
+        let mut model = ProtocolModel::new(ModelParams::default());
+        for event in &history() {
+            model.apply(event);
+        }
+        let debt = model.position("alice").unwrap().total_debt();
+        let max = model.max_liquidatable("alice");
+        assert_eq!(max, debt / 2);
+
+        assert_eq!(
+            model.liquidate("alice", max + 1),
+            Err(ModelError::ExceedsCloseFactor)
+        );
+        assert_eq!(
+            model.liquidate("bob", 100),
+            Err(ModelError::NotLiquidatable)
+        );
+        assert_eq!(model.liquidate("alice", 0), Err(ModelError::InvalidAmount));
+
+        let outcome = model.liquidate("alice", max).unwrap();
+        assert_eq!(outcome.debt_repaid, max);
+        assert_eq!(outcome.collateral_seized, max * 11 / 10);
+        assert_eq!(outcome.profit, outcome.collateral_seized - max);
+        assert_eq!(model.position("alice").unwrap().total_debt(), debt - max);
+    }
+
+    #[test]
+    fn test_price_drop_makes_position_liquidatable() {
+        let params = ModelParams {
+            collateral_asset: Some("CCOLLATERAL".to_string()),
+            debt_asset: Some("CDEBT".to_string()),
+            ..ModelParams::default()
+        };
+        let mut model = ProtocolModel::new(params);
+        let events = [
+            event(
+                "price_updated_event",
+                1,
+                json!({"asset": "CCOLLATERAL", "price": 200}),
+            ),
+            event(
+                "price_updated_event",
+                1,
+                json!({"asset": "CDEBT", "price": 100}),
+            ),
+            event(
+                "deposit",
+                2,
+                json!({"user": "carol", "asset": "CCOLLATERAL", "amount": 1_000}),
+            ),
+            event(
+                "borrow",
+                3,
+                json!({"user": "carol", "asset": "CDEBT", "amount": 1_800}),
+            ),
+            // Native deposits are outside the modelled market
+            op("deposit", 3, "carol", 1_000, 0),
+        ];
+        let applied: Vec<bool> = events.iter().map(|event| model.apply(event)).collect();
+        assert_eq!(applied, vec![true, true, true, true, false]);
+        assert_eq!(model.collateral_ratio("carol"), Some(11_111));
+        assert!(model.liquidatable().is_empty());
+
+        model.apply(&event(
+            "PriceUpdated",
+            4,
+            json!({"asset": "CCOLLATERAL", "price": 180}),
+        ));
+        assert_eq!(model.collateral_ratio("carol"), Some(10_000));
+        let outcome = model.liquidate("carol", 900).unwrap();
+        // 900 debt = 500 collateral at 180/100, plus 10%
+        assert_eq!(outcome.collateral_seized, 550);
+        assert_eq!(outcome.profit, 90);
+    }
+
+    #[test]
+    fn test_exhausted_collateral_books_bad_debt() {
+        let params = ModelParams {
+            close_factor: BPS_SCALE,
+            ..ModelParams::default()
+        };
+        let mut model = ProtocolModel::new(params);
+        model.apply(&op("deposit", 1, "dave", 1_000, 0));
+        model.apply(&op("borrow", 2, "dave", 1_000, 0));
+
+        let outcome = model.liquidate("dave", 1_000).unwrap();
+        // 1_100 would be seized; only 1_000 exists, and no debt remains
+        assert_eq!(outcome.collateral_seized, 1_000);
+        assert_eq!(outcome.bad_debt, 0);
+        assert_eq!(model.position("dave").unwrap(), ModelPosition::default());
+
+        model.apply(&op("deposit", 3, "erin", 1_000, 0));
+        model.apply(&op("borrow", 4, "erin", 1_000, 0));
+        let outcome = model.liquidate("erin", 950).unwrap();
+        assert_eq!(outcome.collateral_seized, 1_000);
+        assert_eq!(outcome.bad_debt, 50);
+        assert_eq!(model.bad_debt(), 50);
+    }
+
+    #[test]
+    fn test_backtest_runs_strategies_in_batch() {
+        let mut events = history();
+        // A competing keeper liquidates Alice later; it is clamped to what is left
+        events.push(event(
+            "liquidation_event",
+            6,
+            json!({"borrower": "alice", "debt_liquidated": "100000", "collateral_seized": "100000"}),
+        ));
+        events.push(event("admin_action_event", 7, json!({})));
+        events.reverse();
+
+        let backtester = Backtester::new(ModelParams::default());
+        let mut strategies: Vec<Box<dyn KeeperStrategy>> = vec![
+            Box::new(CloseFactorStrategy::new(0)),
+            Box::new(CloseFactorStrategy::new(i128::MAX)),
+        ];
+        let reports = backtester.run_batch(&events, &mut strategies);
+        assert_eq!(reports.len(), 2);
+
+        let eager = &reports[0];
+        assert_eq!(eager.events_replayed, 6);
+        assert_eq!(eager.events_skipped, 1);
+        assert!(!eager.liquidations.is_empty());
+        assert!(eager.total_profit > 0);
+        assert_eq!(eager.liquidations[0].borrower, "alice");
+        assert!(eager.rejected.is_empty());
+
+        let idle = &reports[1];
+        assert!(idle.liquidations.is_empty());
+        assert_eq!(idle.total_profit, 0);
+        assert_eq!(idle.open_liquidatable, 0);
+        assert!(serde_json::to_string(idle).is_ok());
+    }
+
+    #[test]
+    fn test_load_recorded_events() {
+        let array = r#"[{"event_name": "deposit_event", "block_number": 1, "event_data": {"user": "a", "amount": 5}}]"#;
+        let lines = "{\"event_name\": \"borrow\", \"event_data\": {}}\n\n{\"event_name\": \"repay\", \"timestamp\": 7, \"event_data\": {}}\n";
+        assert_eq!(load_recorded_events(array).unwrap().len(), 1);
+        let events = load_recorded_events(lines).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].ledger_time(), 7);
+        assert!(load_recorded_events("not json").is_err());
+
+        assert_eq!(event_kind("DepositEvent"), "deposit");
+        assert_eq!(event_kind("price_updated_event"), "price_updated");
+    }
+}
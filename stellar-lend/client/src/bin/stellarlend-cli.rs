@@ -0,0 +1,374 @@
+//! StellarLend command line interface.
+//!
+//! Signs and submits protocol operations (deposit, borrow, repay, withdraw,
+//! liquidate) and admin/config calls against a deployed StellarLend contract,
+//! and runs read-only views through simulation. Every command prints a single
+//! JSON object on stdout; failures print `{"error": "..."}` and exit with
+//! status 1.
+//!
+//! ```text
+//! stellarlend-cli --contract C... --keyfile ./operator.key deposit --asset native --amount 1000
+//! stellarlend-cli --contract C... user-report --user G...
+//! stellarlend-cli --contract C... --keyfile ./hot-wallet.key watchtower --config watchtower.json
+//! ```
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
+use stellar_xdr::curr::ScVal;
+use stellarlend_client::invoke::{
+    address_arg, asset_arg, i128_arg, option_i128_arg, symbol_arg, xdr_arg,
+};
+use stellarlend_client::watchtower::{AuditLog, ContractGuard, Watchtower, WatchtowerConfig};
+use stellarlend_client::{
+    BlockchainClient, BlockchainConfig, BlockchainError, ContractInvoker, InvocationOutput,
+    Keypair, Network, Result,
+};
+
+#[derive(Parser)]
+#[command(
+    name = "stellarlend-cli",
+    version,
+    about = "Interact with StellarLend contracts"
+)]
+struct Cli {
+    /// Network to connect to
+    #[arg(
+        long,
+        value_enum,
+        default_value = "testnet",
+        env = "STELLARLEND_NETWORK"
+    )]
+    network: NetworkArg,
+
+    /// Horizon URL (overrides the network default)
+    #[arg(long, env = "STELLARLEND_HORIZON_URL")]
+    horizon_url: Option<String>,
+
+    /// Soroban RPC URL (overrides the network default)
+    #[arg(long, env = "STELLARLEND_RPC_URL")]
+    rpc_url: Option<String>,
+
+    /// Network passphrase (required for custom networks)
+    #[arg(long, env = "STELLARLEND_NETWORK_PASSPHRASE")]
+    network_passphrase: Option<String>,
+
+    /// StellarLend contract id (C...)
+    #[arg(long, env = "STELLARLEND_CONTRACT_ID")]
+    contract: String,
+
+    /// Keyfile holding the signer's secret seed (plain text or {"secret_key": "S..."})
+    #[arg(long, env = "STELLARLEND_KEYFILE")]
+    keyfile: Option<PathBuf>,
+
+    /// Simulate the call without submitting it
+    #[arg(long, global = true)]
+    simulate: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum NetworkArg {
+    Testnet,
+    Mainnet,
+    Futurenet,
+    Custom,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Deposit collateral
+    Deposit(AssetAmount),
+    /// Borrow against deposited collateral
+    Borrow(AssetAmount),
+    /// Repay borrowed assets
+    Repay(AssetAmount),
+    /// Withdraw collateral
+    Withdraw(AssetAmount),
+    /// Liquidate an undercollateralized position
+    Liquidate {
+        /// Borrower being liquidated (G...)
+        #[arg(long)]
+        borrower: String,
+        /// Debt asset repaid (`native` or a token contract id)
+        #[arg(long, default_value = "native")]
+        debt_asset: String,
+        /// Collateral asset seized (`native` or a token contract id)
+        #[arg(long, default_value = "native")]
+        collateral_asset: String,
+        /// Debt amount to repay
+        #[arg(long)]
+        amount: i128,
+    },
+    /// Admin and configuration operations (the keyfile must hold the admin key)
+    #[command(subcommand)]
+    Admin(AdminCommand),
+    /// Show a user's position report
+    UserReport {
+        /// User address (defaults to the keyfile account)
+        #[arg(long)]
+        user: Option<String>,
+    },
+    /// Show the protocol report
+    ProtocolReport,
+    /// Call any contract function with base64 XDR `ScVal` arguments
+    Invoke {
+        /// Function name
+        function: String,
+        /// Arguments, in order
+        #[arg(long = "arg")]
+        args: Vec<String>,
+    },
+    /// Guard positions until interrupted, signing protective actions with the keyfile
+    Watchtower {
+        /// JSON file listing the guarded accounts, triggers and actions
+        #[arg(long)]
+        config: PathBuf,
+        /// JSON lines audit log (appended to)
+        #[arg(long, default_value = "watchtower-audit.jsonl")]
+        audit_log: PathBuf,
+    },
+}
+
+#[derive(Args)]
+struct AssetAmount {
+    /// Asset (`native` or a token contract id)
+    #[arg(long, default_value = "native")]
+    asset: String,
+    /// Amount in the asset's smallest unit
+    #[arg(long)]
+    amount: i128,
+}
+
+#[derive(Subcommand)]
+enum AdminCommand {
+    /// Pause or resume a single operation (e.g. `pause_borrow`)
+    Pause {
+        /// Operation switch
+        operation: String,
+        /// Resume the operation instead of pausing it
+        #[arg(long)]
+        resume: bool,
+    },
+    /// Enable or disable the emergency pause
+    EmergencyPause {
+        /// Disable the emergency pause instead of enabling it
+        #[arg(long)]
+        off: bool,
+    },
+    /// Update risk parameters (basis points); omitted values are unchanged
+    SetRiskParams {
+        /// Minimum collateral ratio
+        #[arg(long)]
+        min_collateral_ratio: Option<i128>,
+        /// Liquidation threshold
+        #[arg(long)]
+        liquidation_threshold: Option<i128>,
+        /// Close factor
+        #[arg(long)]
+        close_factor: Option<i128>,
+        /// Liquidation incentive
+        #[arg(long)]
+        liquidation_incentive: Option<i128>,
+    },
+}
+
+/// Source account for simulations without a keyfile
+const SIMULATION_SOURCE: [u8; 32] = [0; 32];
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("warn")),
+        )
+        .init();
+
+    let cli = Cli::parse();
+    let result = match &cli.command {
+        Command::Watchtower { config, audit_log } => {
+            run_watchtower(&cli, config, audit_log.clone()).await
+        }
+        _ => run(cli)
+            .await
+            .map(|output| serde_json::to_value(output).unwrap_or_default()),
+    };
+    match result {
+        Ok(output) => {
+            print_json(&output);
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            print_json(&serde_json::json!({ "error": error.to_string() }));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<InvocationOutput> {
+    let client = BlockchainClient::new(Arc::new(config(&cli)?))?;
+    let invoker = ContractInvoker::new(&client, &cli.contract)?;
+    let keypair = cli.keyfile.as_ref().map(Keypair::from_file).transpose()?;
+    let signer = || {
+        keypair.as_ref().ok_or_else(|| {
+            BlockchainError::ConfigError("A keyfile is required for this command".to_string())
+        })
+    };
+
+    let (function, args, read_only) = match &cli.command {
+        Command::Deposit(op) => ("deposit_collateral", user_op(signer()?, op)?, false),
+        Command::Borrow(op) => ("borrow_asset", user_op(signer()?, op)?, false),
+        Command::Repay(op) => ("repay_debt", user_op(signer()?, op)?, false),
+        Command::Withdraw(op) => ("withdraw_collateral", user_op(signer()?, op)?, false),
+        Command::Liquidate {
+            borrower,
+            debt_asset,
+            collateral_asset,
+            amount,
+        } => (
+            "liquidate",
+            vec![
+                address_arg(&signer()?.public_key())?,
+                address_arg(borrower)?,
+                asset_arg(debt_asset)?,
+                asset_arg(collateral_asset)?,
+                i128_arg(*amount),
+            ],
+            false,
+        ),
+        Command::Admin(admin) => {
+            let caller = address_arg(&signer()?.public_key())?;
+            match admin {
+                AdminCommand::Pause { operation, resume } => (
+                    "set_pause_switch",
+                    vec![caller, symbol_arg(operation)?, ScVal::Bool(!resume)],
+                    false,
+                ),
+                AdminCommand::EmergencyPause { off } => (
+                    "set_emergency_pause",
+                    vec![caller, ScVal::Bool(!off)],
+                    false,
+                ),
+                AdminCommand::SetRiskParams {
+                    min_collateral_ratio,
+                    liquidation_threshold,
+                    close_factor,
+                    liquidation_incentive,
+                } => (
+                    "set_risk_params",
+                    vec![
+                        caller,
+                        option_i128_arg(*min_collateral_ratio),
+                        option_i128_arg(*liquidation_threshold),
+                        option_i128_arg(*close_factor),
+                        option_i128_arg(*liquidation_incentive),
+                    ],
+                    false,
+                ),
+            }
+        }
+        Command::UserReport { user } => {
+            let user = match user {
+                Some(user) => user.clone(),
+                None => signer()?.public_key(),
+            };
+            ("get_user_report", vec![address_arg(&user)?], true)
+        }
+        Command::ProtocolReport => ("get_protocol_report", vec![], true),
+        Command::Invoke { function, args } => (
+            function.as_str(),
+            args.iter().map(|arg| xdr_arg(arg)).collect::<Result<_>>()?,
+            false,
+        ),
+        Command::Watchtower { .. } => unreachable!("handled by run_watchtower"),
+    };
+
+    if read_only || cli.simulate {
+        let source = match &keypair {
+            Some(keypair) => keypair.public_key(),
+            None => stellar_strkey::ed25519::PublicKey(SIMULATION_SOURCE).to_string(),
+        };
+        invoker.simulate(&source, function, args).await
+    } else {
+        invoker.invoke(signer()?, function, args).await
+    }
+}
+
+async fn run_watchtower(
+    cli: &Cli,
+    config_path: &Path,
+    audit_log: PathBuf,
+) -> Result<serde_json::Value> {
+    let client = BlockchainClient::new(Arc::new(config(cli)?))?;
+    let keyfile = cli.keyfile.as_ref().ok_or_else(|| {
+        BlockchainError::ConfigError("A keyfile is required for this command".to_string())
+    })?;
+    let guard = ContractGuard::new(
+        ContractInvoker::new(&client, &cli.contract)?,
+        Keypair::from_file(keyfile)?,
+    );
+    let watchtower_config = WatchtowerConfig::from_file(config_path)?;
+    let accounts = watchtower_config.accounts.len();
+    let mut watchtower = Watchtower::new(
+        watchtower_config,
+        &guard,
+        &guard,
+        AuditLog::new(audit_log.clone()),
+    )?;
+    let rounds = watchtower
+        .run_until(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+    Ok(serde_json::json!({
+        "status": "stopped",
+        "accounts": accounts,
+        "rounds": rounds,
+        "audit_log": audit_log,
+    }))
+}
+
+fn config(cli: &Cli) -> Result<BlockchainConfig> {
+    let network = match cli.network {
+        NetworkArg::Testnet => Network::Testnet,
+        NetworkArg::Mainnet => Network::Mainnet,
+        NetworkArg::Futurenet => Network::Futurenet,
+        NetworkArg::Custom => Network::Custom,
+    };
+    if network == Network::Custom && cli.network_passphrase.is_none() {
+        return Err(BlockchainError::ConfigError(
+            "--network-passphrase is required for custom networks".to_string(),
+        ));
+    }
+    let defaults = BlockchainConfig::new(network);
+    let mut config = BlockchainConfig::custom(
+        cli.horizon_url.clone().unwrap_or(defaults.horizon_url),
+        cli.rpc_url.clone().unwrap_or(defaults.soroban_rpc_url),
+        cli.network_passphrase
+            .clone()
+            .unwrap_or(defaults.network_passphrase),
+    )?;
+    config.network = network;
+    config.validate()?;
+    Ok(config)
+}
+
+fn user_op(keypair: &Keypair, op: &AssetAmount) -> Result<Vec<ScVal>> {
+    Ok(vec![
+        address_arg(&keypair.public_key())?,
+        asset_arg(&op.asset)?,
+        i128_arg(op.amount),
+    ])
+}
+
+fn print_json(value: &serde_json::Value) {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+    );
+}
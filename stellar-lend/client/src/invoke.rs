@@ -0,0 +1,513 @@
+//! Contract invocation for the CLI.
+//!
+//! Builds `InvokeHostFunction` transactions against a StellarLend contract,
+//! prepares them through Soroban RPC simulation (footprint, resource fee and
+//! authorization entries), signs them with a [`Keypair`] and submits them.
+//! Read-only calls are simulated only and never submitted.
+//!
+//! Only invocations authorized by the signing account itself are supported:
+//! simulations requiring another address's signature are rejected before
+//! submission.
+
+use crate::config::BlockchainConfig;
+use crate::error::{BlockchainError, Result};
+use crate::keys::Keypair;
+use crate::monitor::{MonitorOptions, MonitorResult};
+use crate::soroban_rpc::SimulateTransactionResult;
+use crate::BlockchainClient;
+use serde::Serialize;
+use std::str::FromStr;
+use stellar_xdr::curr::{
+    HostFunction, Int128Parts, InvokeContractArgs, InvokeHostFunctionOp, Limits, Memo,
+    MuxedAccount, Operation, OperationBody, Preconditions, ReadXdr, ScAddress, ScSymbol, ScVal,
+    SequenceNumber, SorobanAuthorizationEntry, SorobanCredentials, SorobanTransactionData,
+    Transaction, TransactionEnvelope, TransactionExt, TransactionV1Envelope, Uint256, WriteXdr,
+};
+
+/// Base fee (in stroops) before the simulated resource fee is added
+pub const BASE_FEE: u32 = 100;
+
+/// Parse a Stellar account (`G...`) or contract (`C...`) address argument
+pub fn address_arg(value: &str) -> Result<ScVal> {
+    ScAddress::from_str(value.trim())
+        .map(ScVal::Address)
+        .map_err(|_| BlockchainError::Generic(format!("Invalid address: {}", value)))
+}
+
+/// Parse an asset argument: `native` for XLM, otherwise a token contract address
+///
+/// Encodes the contracts' `Option<Address>` asset parameter.
+pub fn asset_arg(value: &str) -> Result<ScVal> {
+    if value.eq_ignore_ascii_case("native") {
+        Ok(ScVal::Void)
+    } else {
+        address_arg(value)
+    }
+}
+
+/// Encode an `i128` argument
+pub fn i128_arg(value: i128) -> ScVal {
+    ScVal::I128(Int128Parts {
+        hi: (value >> 64) as i64,
+        lo: value as u64,
+    })
+}
+
+/// Encode an `Option<i128>` argument
+pub fn option_i128_arg(value: Option<i128>) -> ScVal {
+    value.map(i128_arg).unwrap_or(ScVal::Void)
+}
+
+/// Encode a `Symbol` argument (up to 32 characters from `[a-zA-Z0-9_]`)
+pub fn symbol_arg(value: &str) -> Result<ScVal> {
+    let invalid = || BlockchainError::Generic(format!("Invalid symbol: {}", value));
+    if !value.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(invalid());
+    }
+    ScSymbol::try_from(value)
+        .map(ScVal::Symbol)
+        .map_err(|_| invalid())
+}
+
+/// Parse a base64 XDR `ScVal` argument, for parameters without a dedicated
+/// parser
+pub fn xdr_arg(value: &str) -> Result<ScVal> {
+    ScVal::from_xdr_base64(value.trim(), Limits::none())
+        .map_err(|e| BlockchainError::Generic(format!("Invalid ScVal XDR: {}", e)))
+}
+
+/// Outcome of a contract call, printed as JSON by the CLI
+#[derive(Debug, Clone, Serialize)]
+pub struct InvocationOutput {
+    /// Contract function called
+    pub function: String,
+    /// `simulated`, `success`, `failed` or `timeout`
+    pub status: String,
+    /// Hash of the submitted transaction (None for simulations)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_hash: Option<String>,
+    /// Ledger the transaction was included in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ledger: Option<u64>,
+    /// Return value, as simulated before submission
+    pub result: Option<serde_json::Value>,
+    /// Failure reason
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Builds, signs and submits calls to one contract
+pub struct ContractInvoker<'a> {
+    client: &'a BlockchainClient,
+    contract: ScAddress,
+}
+
+impl<'a> ContractInvoker<'a> {
+    /// Create an invoker for a contract (`C...`)
+    pub fn new(client: &'a BlockchainClient, contract_id: &str) -> Result<Self> {
+        let contract = ScAddress::from_str(contract_id.trim()).map_err(|_| {
+            BlockchainError::ConfigError(format!("Invalid contract id: {}", contract_id))
+        })?;
+        if !matches!(contract, ScAddress::Contract(_)) {
+            return Err(BlockchainError::ConfigError(format!(
+                "Not a contract id: {}",
+                contract_id
+            )));
+        }
+        Ok(Self { client, contract })
+    }
+
+    /// Simulate a call without submitting it
+    ///
+    /// `source` is the account the simulation runs as; any existing account
+    /// works for read-only functions.
+    pub async fn simulate(
+        &self,
+        source: &str,
+        function: &str,
+        args: Vec<ScVal>,
+    ) -> Result<InvocationOutput> {
+        let source = parse_account(source)?;
+        let transaction = build_invocation(source, 0, &self.contract, function, args)?;
+        let simulation = self.simulate_transaction(&transaction).await?;
+        Ok(InvocationOutput {
+            function: function.to_string(),
+            status: "simulated".to_string(),
+            transaction_hash: None,
+            ledger: None,
+            result: simulated_result(&simulation)?,
+            error: None,
+        })
+    }
+
+    /// Simulate, sign and submit a call, waiting for its confirmation
+    pub async fn invoke(
+        &self,
+        keypair: &Keypair,
+        function: &str,
+        args: Vec<ScVal>,
+    ) -> Result<InvocationOutput> {
+        let account = self.client.get_account(&keypair.public_key()).await?;
+        let sequence: i64 = account.sequence.parse().map_err(|_| {
+            BlockchainError::InvalidResponse(format!(
+                "Invalid account sequence: {}",
+                account.sequence
+            ))
+        })?;
+
+        let transaction = build_invocation(
+            keypair.muxed_account(),
+            sequence + 1,
+            &self.contract,
+            function,
+            args,
+        )?;
+        let simulation = self.simulate_transaction(&transaction).await?;
+        let result = simulated_result(&simulation)?;
+        let transaction = assemble_transaction(transaction, &simulation)?;
+
+        let config = self.client.config();
+        let envelope = sign_envelope(keypair, transaction, config)?;
+        let hash = self
+            .client
+            .soroban_rpc()
+            .send_transaction(&envelope)
+            .await?;
+
+        let options = MonitorOptions::from_config(config).with_soroban_rpc();
+        let mut output = InvocationOutput {
+            function: function.to_string(),
+            status: "success".to_string(),
+            transaction_hash: Some(hash.clone()),
+            ledger: None,
+            result,
+            error: None,
+        };
+        match self.client.monitor_transaction(&hash, options).await? {
+            MonitorResult::SorobanSuccess(invocation) => output.ledger = Some(invocation.ledger),
+            MonitorResult::Success(details) => output.ledger = details.ledger,
+            MonitorResult::Failed(reason) => {
+                output.status = "failed".to_string();
+                output.result = None;
+                output.error = Some(reason);
+            }
+            MonitorResult::Timeout => {
+                output.status = "timeout".to_string();
+                output.result = None;
+            }
+        }
+        Ok(output)
+    }
+
+    async fn simulate_transaction(
+        &self,
+        transaction: &Transaction,
+    ) -> Result<SimulateTransactionResult> {
+        let envelope = TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: transaction.clone(),
+            signatures: Default::default(),
+        });
+        let xdr = envelope
+            .to_xdr_base64(Limits::none())
+            .map_err(|e| BlockchainError::InvalidTransaction(e.to_string()))?;
+        let simulation = self.client.simulate_soroban_transaction(&xdr).await?;
+        if !simulation.success {
+            return Err(BlockchainError::InvalidTransaction(format!(
+                "Simulation failed: {}",
+                simulation.error.as_deref().unwrap_or("unknown error")
+            )));
+        }
+        Ok(simulation)
+    }
+}
+
+/// Build an unprepared `InvokeHostFunction` transaction
+pub fn build_invocation(
+    source: MuxedAccount,
+    sequence: i64,
+    contract: &ScAddress,
+    function: &str,
+    args: Vec<ScVal>,
+) -> Result<Transaction> {
+    let function_name = ScSymbol::try_from(function)
+        .map_err(|_| BlockchainError::Generic(format!("Invalid function name: {}", function)))?;
+    let args = args
+        .try_into()
+        .map_err(|_| BlockchainError::Generic("Too many arguments".to_string()))?;
+    let operation = Operation {
+        source_account: None,
+        body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+            host_function: HostFunction::InvokeContract(InvokeContractArgs {
+                contract_address: contract.clone(),
+                function_name,
+                args,
+            }),
+            auth: Default::default(),
+        }),
+    };
+
+    Ok(Transaction {
+        source_account: source,
+        fee: BASE_FEE,
+        seq_num: SequenceNumber(sequence),
+        cond: Preconditions::None,
+        memo: Memo::None,
+        operations: vec![operation]
+            .try_into()
+            .map_err(|_| BlockchainError::Generic("Invalid operation list".to_string()))?,
+        ext: TransactionExt::V0,
+    })
+}
+
+/// Apply a simulation to a transaction: resource footprint, resource fee and
+/// authorization entries
+///
+/// # Errors
+/// Fails if the invocation needs a signature from an address other than the
+/// transaction source.
+pub fn assemble_transaction(
+    mut transaction: Transaction,
+    simulation: &SimulateTransactionResult,
+) -> Result<Transaction> {
+    let data =
+        SorobanTransactionData::from_xdr_base64(&simulation.transaction_data, Limits::none())
+            .map_err(|e| {
+                BlockchainError::InvalidResponse(format!("Invalid transactionData: {}", e))
+            })?;
+    let resource_fee: u32 = simulation.min_resource_fee.parse().map_err(|_| {
+        BlockchainError::InvalidResponse(format!(
+            "Invalid resource fee: {}",
+            simulation.min_resource_fee
+        ))
+    })?;
+
+    let auth = simulation
+        .auth
+        .iter()
+        .map(|entry| {
+            let entry = SorobanAuthorizationEntry::from_xdr_base64(entry, Limits::none())
+                .map_err(|e| BlockchainError::InvalidResponse(format!("Invalid auth: {}", e)))?;
+            if !matches!(entry.credentials, SorobanCredentials::SourceAccount) {
+                return Err(BlockchainError::InvalidTransaction(
+                    "Invocation requires authorization from another account".to_string(),
+                ));
+            }
+            Ok(entry)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut operations = transaction.operations.to_vec();
+    if let Some(Operation {
+        body: OperationBody::InvokeHostFunction(op),
+        ..
+    }) = operations.first_mut()
+    {
+        op.auth = auth
+            .try_into()
+            .map_err(|_| BlockchainError::InvalidResponse("Too many auth entries".to_string()))?;
+    }
+    transaction.operations = operations
+        .try_into()
+        .map_err(|_| BlockchainError::Generic("Invalid operation list".to_string()))?;
+    transaction.fee = transaction
+        .fee
+        .checked_add(resource_fee)
+        .ok_or_else(|| BlockchainError::InvalidTransaction("Fee overflow".to_string()))?;
+    transaction.ext = TransactionExt::V1(data);
+    Ok(transaction)
+}
+
+/// Sign a transaction and encode its envelope as base64 XDR
+pub fn sign_envelope(
+    keypair: &Keypair,
+    transaction: Transaction,
+    config: &BlockchainConfig,
+) -> Result<String> {
+    let signature = keypair.sign_transaction(&transaction, &config.network_passphrase)?;
+    TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx: transaction,
+        signatures: vec![signature]
+            .try_into()
+            .map_err(|_| BlockchainError::Generic("Invalid signature list".to_string()))?,
+    })
+    .to_xdr_base64(Limits::none())
+    .map_err(|e| BlockchainError::InvalidTransaction(e.to_string()))
+}
+
+fn parse_account(account: &str) -> Result<MuxedAccount> {
+    let key = stellar_strkey::ed25519::PublicKey::from_string(account.trim())
+        .map_err(|_| BlockchainError::Generic(format!("Invalid account: {}", account)))?;
+    Ok(MuxedAccount::Ed25519(Uint256(key.0)))
+}
+
+fn simulated_result(simulation: &SimulateTransactionResult) -> Result<Option<serde_json::Value>> {
+    simulation
+        .result_xdr
+        .as_deref()
+        .map(|xdr| {
+            let value = ScVal::from_xdr_base64(xdr, Limits::none())
+                .map_err(|e| BlockchainError::InvalidResponse(format!("Invalid result: {}", e)))?;
+            Ok(serde_json::to_value(value)?)
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stellar_xdr::curr::{
+        LedgerFootprint, SorobanAddressCredentials, SorobanAuthorizedFunction,
+        SorobanAuthorizedInvocation, SorobanResources, SorobanTransactionDataExt,
+    };
+
+    fn contract_id() -> String {
+        stellar_strkey::Contract([3u8; 32]).to_string()
+    }
+
+    fn keypair() -> Keypair {
+        Keypair::from_secret(&stellar_strkey::ed25519::PrivateKey([9u8; 32]).to_string()).unwrap()
+    }
+
+    fn contract() -> ScAddress {
+        ScAddress::from_str(&contract_id()).unwrap()
+    }
+
+    fn transaction_data() -> String {
+        SorobanTransactionData {
+            ext: SorobanTransactionDataExt::V0,
+            resources: SorobanResources {
+                footprint: LedgerFootprint {
+                    read_only: Default::default(),
+                    read_write: Default::default(),
+                },
+                instructions: 1_000,
+                disk_read_bytes: 0,
+                write_bytes: 0,
+            },
+            resource_fee: 5_000,
+        }
+        .to_xdr_base64(Limits::none())
+        .unwrap()
+    }
+
+    fn auth_entry(credentials: SorobanCredentials) -> String {
+        SorobanAuthorizationEntry {
+            credentials,
+            root_invocation: SorobanAuthorizedInvocation {
+                function: SorobanAuthorizedFunction::ContractFn(InvokeContractArgs {
+                    contract_address: contract(),
+                    function_name: ScSymbol::try_from("deposit_collateral").unwrap(),
+                    args: Default::default(),
+                }),
+                sub_invocations: Default::default(),
+            },
+        }
+        .to_xdr_base64(Limits::none())
+        .unwrap()
+    }
+
+    fn simulation(auth: Vec<String>) -> SimulateTransactionResult {
+        SimulateTransactionResult {
+            result_xdr: Some(ScVal::Void.to_xdr_base64(Limits::none()).unwrap()),
+            transaction_data: transaction_data(),
+            min_resource_fee: "5000".to_string(),
+            events: None,
+            success: true,
+            error: None,
+            auth,
+        }
+    }
+
+    #[test]
+    fn test_argument_encoding() {
+        assert_eq!(asset_arg("native").unwrap(), ScVal::Void);
+        assert!(matches!(
+            asset_arg(&contract_id()).unwrap(),
+            ScVal::Address(ScAddress::Contract(_))
+        ));
+        assert!(matches!(
+            address_arg(&keypair().public_key()).unwrap(),
+            ScVal::Address(ScAddress::Account(_))
+        ));
+        assert!(address_arg("GBAD").is_err());
+
+        assert_eq!(i128_arg(-1), ScVal::from(-1i128));
+        assert_eq!(i128_arg(i128::MAX), ScVal::from(i128::MAX));
+        assert_eq!(option_i128_arg(None), ScVal::Void);
+        assert!(symbol_arg("pause_borrow").is_ok());
+        assert!(symbol_arg("not a symbol").is_err());
+
+        let xdr = ScVal::U32(7).to_xdr_base64(Limits::none()).unwrap();
+        assert_eq!(xdr_arg(&xdr).unwrap(), ScVal::U32(7));
+    }
+
+    #[test]
+    fn test_assemble_applies_simulation() {
+        let keypair = keypair();
+        let transaction = build_invocation(
+            keypair.muxed_account(),
+            42,
+            &contract(),
+            "deposit_collateral",
+            vec![
+                address_arg(&keypair.public_key()).unwrap(),
+                ScVal::Void,
+                i128_arg(1_000),
+            ],
+        )
+        .unwrap();
+        assert_eq!(transaction.fee, BASE_FEE);
+
+        let simulation = simulation(vec![auth_entry(SorobanCredentials::SourceAccount)]);
+        let assembled = assemble_transaction(transaction, &simulation).unwrap();
+        assert_eq!(assembled.fee, BASE_FEE + 5_000);
+        assert_eq!(assembled.seq_num, SequenceNumber(42));
+        assert!(matches!(assembled.ext, TransactionExt::V1(_)));
+        let OperationBody::InvokeHostFunction(op) = &assembled.operations[0].body else {
+            panic!("expected an invocation");
+        };
+        assert_eq!(op.auth.len(), 1);
+
+        let envelope = sign_envelope(&keypair, assembled, &BlockchainConfig::testnet()).unwrap();
+        let TransactionEnvelope::Tx(envelope) =
+            TransactionEnvelope::from_xdr_base64(envelope, Limits::none()).unwrap()
+        else {
+            panic!("expected a v1 envelope");
+        };
+        assert_eq!(envelope.signatures.len(), 1);
+    }
+
+    #[test]
+    fn test_foreign_authorization_rejected() {
+        let transaction = build_invocation(
+            keypair().muxed_account(),
+            1,
+            &contract(),
+            "set_emergency_pause",
+            vec![],
+        )
+        .unwrap();
+        let credentials = SorobanCredentials::Address(SorobanAddressCredentials {
+            address: contract(),
+            nonce: 0,
+            signature_expiration_ledger: 0,
+            signature: ScVal::Void,
+        });
+
+        assert!(
+            assemble_transaction(transaction, &simulation(vec![auth_entry(credentials)])).is_err()
+        );
+    }
+
+    #[test]
+    fn test_simulated_result_decoding() {
+        let mut simulation = simulation(vec![]);
+        assert_eq!(
+            simulated_result(&simulation).unwrap(),
+            Some(serde_json::json!("void"))
+        );
+
+        simulation.result_xdr = None;
+        assert_eq!(simulated_result(&simulation).unwrap(), None);
+    }
+}
@@ -0,0 +1,189 @@
+//! Keyfile loading and transaction signing for the CLI.
+//!
+//! A keyfile holds a single Stellar secret seed (`S...`), either as plain text
+//! or as JSON of the form `{"secret_key": "S..."}`. Keyfiles should be readable
+//! by their owner only; the seed is never printed or logged.
+
+use crate::error::{BlockchainError, Result};
+use ed25519_dalek::{Signer, SigningKey};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use stellar_strkey::ed25519;
+use stellar_xdr::curr::{
+    DecoratedSignature, Hash, Limits, MuxedAccount, Signature, SignatureHint, Transaction,
+    TransactionSignaturePayload, TransactionSignaturePayloadTaggedTransaction, Uint256, WriteXdr,
+};
+
+/// JSON keyfile layout
+#[derive(Deserialize)]
+struct KeyFile {
+    secret_key: String,
+}
+
+/// Ed25519 keypair used to sign transactions
+pub struct Keypair {
+    signing_key: SigningKey,
+}
+
+impl Keypair {
+    /// Create a keypair from a secret seed (`S...`)
+    pub fn from_secret(secret: &str) -> Result<Self> {
+        let seed = ed25519::PrivateKey::from_string(secret.trim()).map_err(|_| {
+            BlockchainError::ConfigError("Invalid secret key in keyfile".to_string())
+        })?;
+        Ok(Self {
+            signing_key: SigningKey::from_bytes(&seed.0),
+        })
+    }
+
+    /// Load a keypair from a plain text or JSON keyfile
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            BlockchainError::ConfigError(format!("Cannot read keyfile {}: {}", path.display(), e))
+        })?;
+        let contents = contents.trim();
+        if contents.starts_with('{') {
+            let key_file: KeyFile = serde_json::from_str(contents)?;
+            Self::from_secret(&key_file.secret_key)
+        } else {
+            Self::from_secret(contents)
+        }
+    }
+
+    /// Raw public key bytes
+    pub fn public_key_bytes(&self) -> [u8; 32] {
+        self.signing_key.verifying_key().to_bytes()
+    }
+
+    /// Public key as a Stellar account id (`G...`)
+    pub fn public_key(&self) -> String {
+        ed25519::PublicKey(self.public_key_bytes()).to_string()
+    }
+
+    /// Account the keypair signs for, as a transaction source
+    pub fn muxed_account(&self) -> MuxedAccount {
+        MuxedAccount::Ed25519(Uint256(self.public_key_bytes()))
+    }
+
+    /// Sign a transaction for the given network
+    ///
+    /// The signature covers the transaction together with the network id
+    /// (SHA-256 of the network passphrase), so it cannot be replayed on
+    /// another network.
+    pub fn sign_transaction(
+        &self,
+        transaction: &Transaction,
+        network_passphrase: &str,
+    ) -> Result<DecoratedSignature> {
+        let hash = transaction_hash(transaction, network_passphrase)?;
+        let signature = self.signing_key.sign(&hash);
+
+        let public_key = self.public_key_bytes();
+        let mut hint = [0u8; 4];
+        hint.copy_from_slice(&public_key[28..]);
+        Ok(DecoratedSignature {
+            hint: SignatureHint(hint),
+            signature: Signature(signature.to_bytes().to_vec().try_into().map_err(|_| {
+                BlockchainError::InvalidTransaction("Invalid signature length".to_string())
+            })?),
+        })
+    }
+}
+
+/// Hash of a transaction as signed on the given network
+pub fn transaction_hash(transaction: &Transaction, network_passphrase: &str) -> Result<[u8; 32]> {
+    let payload = TransactionSignaturePayload {
+        network_id: Hash(Sha256::digest(network_passphrase.as_bytes()).into()),
+        tagged_transaction: TransactionSignaturePayloadTaggedTransaction::Tx(transaction.clone()),
+    };
+    let bytes = payload
+        .to_xdr(Limits::none())
+        .map_err(|e| BlockchainError::InvalidTransaction(e.to_string()))?;
+    Ok(Sha256::digest(bytes).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Verifier, VerifyingKey};
+    use stellar_xdr::curr::{Memo, Preconditions, SequenceNumber, TransactionExt};
+
+    fn secret() -> String {
+        ed25519::PrivateKey([7u8; 32]).to_string()
+    }
+
+    fn empty_transaction(keypair: &Keypair) -> Transaction {
+        Transaction {
+            source_account: keypair.muxed_account(),
+            fee: 100,
+            seq_num: SequenceNumber(1),
+            cond: Preconditions::None,
+            memo: Memo::None,
+            operations: Default::default(),
+            ext: TransactionExt::V0,
+        }
+    }
+
+    #[test]
+    fn test_public_key_round_trip() {
+        let keypair = Keypair::from_secret(&secret()).unwrap();
+        let public_key = keypair.public_key();
+        assert!(public_key.starts_with('G'));
+        assert_eq!(
+            ed25519::PublicKey::from_string(&public_key).unwrap().0,
+            keypair.public_key_bytes()
+        );
+    }
+
+    #[test]
+    fn test_invalid_secret_rejected() {
+        assert!(Keypair::from_secret("not-a-secret").is_err());
+        assert!(
+            Keypair::from_secret(&Keypair::from_secret(&secret()).unwrap().public_key()).is_err()
+        );
+    }
+
+    #[test]
+    fn test_keyfile_formats() {
+        let dir = std::env::temp_dir();
+        let plain = dir.join(format!("stellarlend-key-{}.txt", std::process::id()));
+        let json = dir.join(format!("stellarlend-key-{}.json", std::process::id()));
+        std::fs::write(&plain, format!("{}\n", secret())).unwrap();
+        std::fs::write(&json, format!("{{\"secret_key\": \"{}\"}}", secret())).unwrap();
+
+        let expected = Keypair::from_secret(&secret()).unwrap().public_key();
+        assert_eq!(Keypair::from_file(&plain).unwrap().public_key(), expected);
+        assert_eq!(Keypair::from_file(&json).unwrap().public_key(), expected);
+
+        std::fs::remove_file(plain).unwrap();
+        std::fs::remove_file(json).unwrap();
+        assert!(Keypair::from_file(dir.join("stellarlend-missing-key")).is_err());
+    }
+
+    #[test]
+    fn test_signature_verifies_against_network_hash() {
+        let keypair = Keypair::from_secret(&secret()).unwrap();
+        let transaction = empty_transaction(&keypair);
+        let passphrase = "Test SDF Network ; September 2015";
+
+        let signature = keypair.sign_transaction(&transaction, passphrase).unwrap();
+        assert_eq!(signature.hint.0, keypair.public_key_bytes()[28..]);
+
+        let verifying_key = VerifyingKey::from_bytes(&keypair.public_key_bytes()).unwrap();
+        let raw: [u8; 64] = signature.signature.0.to_vec().try_into().unwrap();
+        let hash = transaction_hash(&transaction, passphrase).unwrap();
+        assert!(verifying_key
+            .verify(&hash, &ed25519_dalek::Signature::from_bytes(&raw))
+            .is_ok());
+
+        // Signing for another network yields a different payload
+        let other = transaction_hash(
+            &transaction,
+            "Public Global Stellar Network ; September 2015",
+        )
+        .unwrap();
+        assert_ne!(hash, other);
+    }
+}
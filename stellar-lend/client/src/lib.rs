@@ -13,6 +13,9 @@
 //! - **Error Handling**: Comprehensive error types with detailed error messages
 //! - **Retry Logic**: Exponential backoff for transient network errors
 //! - **Network Support**: Testnet, Mainnet, Futurenet, and custom networks
+//! - **Backtesting**: Replay indexed protocol events to evaluate keeper strategies offline
+//! - **Watchtower** (feature `cli`): Guard your own positions with audited, automatic protective actions
+//! - **CLI** (feature `cli`): `stellarlend-cli` binary with keyfile signing and JSON output
 //!
 //! # Quick Start
 //!
@@ -102,19 +105,30 @@
 #![warn(rustdoc::broken_intra_doc_links)]
 
 // Re-export main types and modules
+pub mod backtest;
 pub mod config;
 pub mod error;
 pub mod horizon;
+#[cfg(feature = "cli")]
+pub mod invoke;
+#[cfg(feature = "cli")]
+pub mod keys;
 pub mod monitor;
 pub mod retry;
 pub mod soroban_rpc;
 pub mod transaction;
 pub mod types;
+#[cfg(feature = "cli")]
+pub mod watchtower;
 
 // Re-export commonly used types
 pub use config::{BlockchainConfig, Network};
 pub use error::{BlockchainError, Result};
 pub use horizon::HorizonClient;
+#[cfg(feature = "cli")]
+pub use invoke::{ContractInvoker, InvocationOutput};
+#[cfg(feature = "cli")]
+pub use keys::Keypair;
 pub use monitor::{MonitorOptions, MonitorResult, TransactionMonitor};
 pub use retry::RetryStrategy;
 pub use soroban_rpc::{InvokeContractParams, SimulateTransactionResult, SorobanRpcClient};
@@ -78,6 +78,9 @@ pub struct SimulateTransactionResult {
     pub success: bool,
     /// Error message if simulation failed
     pub error: Option<String>,
+    /// Authorization entries (base64 XDR) the invocation requires
+    #[serde(default)]
+    pub auth: Vec<String>,
 }
 
 /// Contract invocation parameters
@@ -207,14 +210,16 @@ impl SorobanRpcClient {
 
         let result_xdr = result["results"][0]["xdr"].as_str().map(|s| s.to_string());
 
-        let transaction_data = result["transactionData"]
-            .as_str()
-            .ok_or_else(|| {
-                BlockchainError::InvalidResponse(
+        // Failed simulations carry no footprint; keep the error for the caller
+        let transaction_data = match result["transactionData"].as_str() {
+            Some(data) => data.to_string(),
+            None if !success => String::new(),
+            None => {
+                return Err(BlockchainError::InvalidResponse(
                     "Missing transactionData in simulation".to_string(),
-                )
-            })?
-            .to_string();
+                ))
+            }
+        };
 
         let min_resource_fee = result["minResourceFee"].as_str().unwrap_or("0").to_string();
 
@@ -224,6 +229,15 @@ impl SorobanRpcClient {
                 .collect()
         });
 
+        let auth = result["results"][0]["auth"]
+            .as_array()
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|a| a.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default();
+
         debug!(
             "Transaction simulation completed. Success: {}, Fee: {}",
             success, min_resource_fee
@@ -236,6 +250,7 @@ impl SorobanRpcClient {
             events,
             success,
             error,
+            auth,
         })
     }
 
@@ -257,6 +272,13 @@ impl SorobanRpcClient {
             .to_string();
 
         let status = result["status"].as_str().unwrap_or("PENDING");
+        if status == "ERROR" {
+            return Err(BlockchainError::TransactionSubmissionError(format!(
+                "{} rejected: {}",
+                hash,
+                result["errorResultXdr"].as_str().unwrap_or("unknown error")
+            )));
+        }
 
         info!("Transaction sent: {} (status: {})", hash, status);
 
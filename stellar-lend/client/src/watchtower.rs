@@ -0,0 +1,626 @@
+//! Watchtower: automatic protection for a user's own positions.
+//!
+//! Polls the health factor of each guarded account and, once it falls below
+//! the account's trigger, submits a protective action signed by a hot wallet
+//! the user controls. Every decision is appended to an audit log, one JSON
+//! object per line.
+//!
+//! # Protective actions
+//!
+//! Both actions only need the hot wallet's signature, so the watchtower
+//! never holds the guarded account's key:
+//!
+//! - [`ProtectiveAction::Repay`] repays debt from the hot wallet's own
+//!   balance through `repay_debt_for`.
+//! - [`ProtectiveAction::Hedge`] triggers the hedge instruction the user
+//!   signed in advance (`set_hedge_instruction`) through `execute_hedge`,
+//!   with the hot wallet as keeper. The instruction's own trigger, cooldown
+//!   and limits still apply on chain.
+//!
+//! After an action is attempted, the account is left alone for
+//! `cooldown_secs` whatever the outcome, so a failing action cannot drain
+//! the hot wallet in fees.
+//!
+//! # Health source
+//!
+//! [`ContractGuard`] reads the health factor (basis points,
+//! `collateral * 10000 / debt`) from a simulated `get_user_report`.
+//! Implement [`HealthSource`] to read it from an indexer instead.
+//!
+//! ```rust,no_run
+//! use stellarlend_client::watchtower::{AuditLog, ContractGuard, Watchtower, WatchtowerConfig};
+//! use stellarlend_client::{BlockchainClient, BlockchainConfig, ContractInvoker, Keypair};
+//! use std::sync::Arc;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = BlockchainClient::new(Arc::new(BlockchainConfig::testnet()))?;
+//! let guard = ContractGuard::new(
+//!     ContractInvoker::new(&client, "C...")?,
+//!     Keypair::from_file("hot-wallet.key")?,
+//! );
+//! let config = WatchtowerConfig::from_file("watchtower.json")?;
+//! let mut watchtower = Watchtower::new(config, &guard, &guard, AuditLog::new("audit.jsonl"))?;
+//! watchtower.run_until(async { tokio::signal::ctrl_c().await.ok(); }).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{BlockchainError, Result};
+use crate::invoke::{address_arg, asset_arg, i128_arg, ContractInvoker, InvocationOutput};
+use crate::keys::Keypair;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use stellar_xdr::curr::ScVal;
+use tracing::{info, warn};
+
+/// Protective action submitted when an account's trigger is crossed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProtectiveAction {
+    /// Repay debt from the hot wallet (`repay_debt_for`)
+    Repay {
+        /// Debt asset (`native` or a token contract id)
+        asset: String,
+        /// Amount in the asset's smallest unit
+        amount: i128,
+    },
+    /// Execute the user's signed hedge instruction (`execute_hedge`)
+    Hedge,
+}
+
+impl ProtectiveAction {
+    /// Contract function the action calls
+    pub fn function(&self) -> &'static str {
+        match self {
+            Self::Repay { .. } => "repay_debt_for",
+            Self::Hedge => "execute_hedge",
+        }
+    }
+
+    /// Arguments of the call, signed by `hot_wallet` on behalf of `user`
+    pub fn args(&self, hot_wallet: &str, user: &str) -> Result<Vec<ScVal>> {
+        Ok(match self {
+            Self::Repay { asset, amount } => vec![
+                address_arg(hot_wallet)?,
+                address_arg(user)?,
+                asset_arg(asset)?,
+                i128_arg(*amount),
+            ],
+            Self::Hedge => vec![address_arg(hot_wallet)?, address_arg(user)?],
+        })
+    }
+}
+
+/// An account guarded by the watchtower
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuardedAccount {
+    /// Account address (G...)
+    pub user: String,
+    /// Health factor (basis points) below which the action is submitted
+    pub trigger_health_factor: i128,
+    /// Action submitted when the trigger is crossed
+    pub action: ProtectiveAction,
+}
+
+/// Watchtower configuration, usually loaded from a JSON file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchtowerConfig {
+    /// Accounts to guard
+    pub accounts: Vec<GuardedAccount>,
+    /// Seconds between two rounds of checks
+    #[serde(default = "default_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+    /// Seconds an account is left alone after an action was attempted
+    #[serde(default = "default_cooldown_secs")]
+    pub cooldown_secs: u64,
+}
+
+fn default_poll_interval_secs() -> u64 {
+    60
+}
+
+fn default_cooldown_secs() -> u64 {
+    600
+}
+
+impl WatchtowerConfig {
+    /// Load and validate a JSON configuration file
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            BlockchainError::ConfigError(format!("Cannot read {}: {}", path.display(), e))
+        })?;
+        let config: Self = serde_json::from_str(&contents)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check the configuration is usable
+    pub fn validate(&self) -> Result<()> {
+        if self.accounts.is_empty() {
+            return Err(BlockchainError::ConfigError(
+                "No accounts to guard".to_string(),
+            ));
+        }
+        if self.poll_interval_secs == 0 {
+            return Err(BlockchainError::ConfigError(
+                "poll_interval_secs must be positive".to_string(),
+            ));
+        }
+        for account in &self.accounts {
+            if account.trigger_health_factor <= 0 {
+                return Err(BlockchainError::ConfigError(format!(
+                    "Invalid trigger for {}",
+                    account.user
+                )));
+            }
+            if let ProtectiveAction::Repay { amount, .. } = account.action {
+                if amount <= 0 {
+                    return Err(BlockchainError::ConfigError(format!(
+                        "Invalid repay amount for {}",
+                        account.user
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads an account's current health factor
+#[async_trait]
+pub trait HealthSource: Send + Sync {
+    /// Health factor in basis points (`i128::MAX` without debt)
+    async fn health_factor(&self, user: &str) -> Result<i128>;
+}
+
+/// Submits protective actions
+#[async_trait]
+pub trait ActionExecutor: Send + Sync {
+    /// Submit `action` for `user` and wait for its outcome
+    async fn execute(&self, user: &str, action: &ProtectiveAction) -> Result<InvocationOutput>;
+}
+
+/// Health source and executor backed by the contract, signing with a hot
+/// wallet
+pub struct ContractGuard<'a> {
+    invoker: ContractInvoker<'a>,
+    hot_wallet: Keypair,
+}
+
+impl<'a> ContractGuard<'a> {
+    /// Guard positions on the invoker's contract, signing with `hot_wallet`
+    pub fn new(invoker: ContractInvoker<'a>, hot_wallet: Keypair) -> Self {
+        Self {
+            invoker,
+            hot_wallet,
+        }
+    }
+}
+
+#[async_trait]
+impl HealthSource for ContractGuard<'_> {
+    async fn health_factor(&self, user: &str) -> Result<i128> {
+        let output = self
+            .invoker
+            .simulate(
+                &self.hot_wallet.public_key(),
+                "get_user_report",
+                vec![address_arg(user)?],
+            )
+            .await?;
+        let report = output.result.ok_or_else(|| {
+            BlockchainError::InvalidResponse("get_user_report returned nothing".to_string())
+        })?;
+        health_factor_from_report(report)
+    }
+}
+
+#[async_trait]
+impl ActionExecutor for ContractGuard<'_> {
+    async fn execute(&self, user: &str, action: &ProtectiveAction) -> Result<InvocationOutput> {
+        let args = action.args(&self.hot_wallet.public_key(), user)?;
+        self.invoker
+            .invoke(&self.hot_wallet, action.function(), args)
+            .await
+    }
+}
+
+/// Extract `metrics.health_factor` from a simulated `UserReport`
+pub fn health_factor_from_report(report: serde_json::Value) -> Result<i128> {
+    let invalid = || BlockchainError::InvalidResponse("Unexpected user report".to_string());
+    let report: ScVal = serde_json::from_value(report)?;
+    let metrics = map_field(&report, "metrics").ok_or_else(invalid)?;
+    match map_field(metrics, "health_factor") {
+        Some(ScVal::I128(parts)) => Ok(((parts.hi as i128) << 64) | parts.lo as i128),
+        _ => Err(invalid()),
+    }
+}
+
+fn map_field<'v>(value: &'v ScVal, name: &str) -> Option<&'v ScVal> {
+    let ScVal::Map(Some(map)) = value else {
+        return None;
+    };
+    map.iter()
+        .find(
+            |entry| matches!(&entry.key, ScVal::Symbol(key) if key.to_utf8_string_lossy() == name),
+        )
+        .map(|entry| &entry.val)
+}
+
+/// What the watchtower decided for an account in one round
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditEvent {
+    /// Health factor at or above the trigger
+    Healthy,
+    /// Below the trigger, but an action was attempted within the cooldown
+    CoolingDown,
+    /// Below the trigger; the action was submitted (see `status`)
+    ActionSubmitted,
+    /// Below the trigger; the action could not be submitted
+    ActionFailed,
+    /// The health factor could not be read
+    CheckFailed,
+}
+
+/// One audit log line
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// When the decision was made
+    pub timestamp: DateTime<Utc>,
+    /// Guarded account
+    pub user: String,
+    /// What happened
+    pub event: AuditEvent,
+    /// Health factor read this round
+    pub health_factor: Option<i128>,
+    /// Account trigger
+    pub trigger_health_factor: i128,
+    /// Contract function called, for attempted actions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<String>,
+    /// Outcome of a submitted action (`success`, `failed` or `timeout`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// Hash of the submitted transaction
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_hash: Option<String>,
+    /// Failure reason
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Append-only JSON lines audit log
+pub struct AuditLog {
+    path: PathBuf,
+}
+
+impl AuditLog {
+    /// Log to `path`, which is created if missing and never truncated
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Append an entry and mirror it to tracing
+    pub fn record(&self, entry: &AuditEntry) -> Result<()> {
+        let line = serde_json::to_string(entry)?;
+        match entry.event {
+            AuditEvent::ActionFailed | AuditEvent::CheckFailed => warn!("watchtower: {}", line),
+            _ => info!("watchtower: {}", line),
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| audit_error(&self.path, e))?;
+        writeln!(file, "{}", line).map_err(|e| audit_error(&self.path, e))
+    }
+}
+
+fn audit_error(path: &Path, error: std::io::Error) -> BlockchainError {
+    BlockchainError::Generic(format!(
+        "Cannot write audit log {}: {}",
+        path.display(),
+        error
+    ))
+}
+
+/// Guards accounts by polling their health and submitting protective actions
+pub struct Watchtower<'a, S: HealthSource, E: ActionExecutor> {
+    config: WatchtowerConfig,
+    source: &'a S,
+    executor: &'a E,
+    audit: AuditLog,
+    last_action: HashMap<String, DateTime<Utc>>,
+}
+
+impl<'a, S: HealthSource, E: ActionExecutor> Watchtower<'a, S, E> {
+    /// Create a watchtower; fails if the configuration is invalid
+    pub fn new(
+        config: WatchtowerConfig,
+        source: &'a S,
+        executor: &'a E,
+        audit: AuditLog,
+    ) -> Result<Self> {
+        config.validate()?;
+        Ok(Self {
+            config,
+            source,
+            executor,
+            audit,
+            last_action: HashMap::new(),
+        })
+    }
+
+    /// Check every account once, submitting actions where needed
+    ///
+    /// # Errors
+    /// Only fails if the audit log cannot be written; failed checks and
+    /// actions are recorded and the round continues.
+    pub async fn check_once(&mut self) -> Result<Vec<AuditEntry>> {
+        let mut entries = Vec::with_capacity(self.config.accounts.len());
+        for account in &self.config.accounts {
+            let now = Utc::now();
+            let mut entry = AuditEntry {
+                timestamp: now,
+                user: account.user.clone(),
+                event: AuditEvent::Healthy,
+                health_factor: None,
+                trigger_health_factor: account.trigger_health_factor,
+                action: None,
+                status: None,
+                transaction_hash: None,
+                error: None,
+            };
+
+            match self.source.health_factor(&account.user).await {
+                Err(error) => {
+                    entry.event = AuditEvent::CheckFailed;
+                    entry.error = Some(error.to_string());
+                }
+                Ok(health_factor) => {
+                    entry.health_factor = Some(health_factor);
+                    if health_factor < account.trigger_health_factor {
+                        let cooling_down = self.last_action.get(&account.user).is_some_and(|at| {
+                            (now - *at).num_seconds() < self.config.cooldown_secs as i64
+                        });
+                        if cooling_down {
+                            entry.event = AuditEvent::CoolingDown;
+                        } else {
+                            self.last_action.insert(account.user.clone(), now);
+                            entry.action = Some(account.action.function().to_string());
+                            match self.executor.execute(&account.user, &account.action).await {
+                                Ok(output) => {
+                                    entry.event = AuditEvent::ActionSubmitted;
+                                    entry.status = Some(output.status);
+                                    entry.transaction_hash = output.transaction_hash;
+                                    entry.error = output.error;
+                                }
+                                Err(error) => {
+                                    entry.event = AuditEvent::ActionFailed;
+                                    entry.error = Some(error.to_string());
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            self.audit.record(&entry)?;
+            entries.push(entry);
+        }
+        Ok(entries)
+    }
+
+    /// Run rounds every `poll_interval_secs` until `shutdown` completes
+    ///
+    /// # Returns
+    /// The number of rounds run
+    pub async fn run_until(&mut self, shutdown: impl Future<Output = ()>) -> Result<u64> {
+        let interval = Duration::from_secs(self.config.poll_interval_secs);
+        tokio::pin!(shutdown);
+        let mut rounds = 0;
+        loop {
+            tokio::select! {
+                _ = &mut shutdown => return Ok(rounds),
+                result = self.check_once() => {
+                    result?;
+                    rounds += 1;
+                }
+            }
+            tokio::select! {
+                _ = &mut shutdown => return Ok(rounds),
+                _ = tokio::time::sleep(interval) => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use stellar_xdr::curr::{ScMap, ScMapEntry, ScSymbol};
+
+    const USER: &str = "GAAQEAYEAUDAOCAJBIFQYDIOB4IBCEQTCQKRMFYYDENBWHA5DYPSABOV";
+
+    struct FixedHealth(Result<i128>);
+
+    #[async_trait]
+    impl HealthSource for FixedHealth {
+        async fn health_factor(&self, _user: &str) -> Result<i128> {
+            match &self.0 {
+                Ok(value) => Ok(*value),
+                Err(error) => Err(BlockchainError::Generic(error.to_string())),
+            }
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingExecutor {
+        calls: Mutex<Vec<(String, ProtectiveAction)>>,
+    }
+
+    #[async_trait]
+    impl ActionExecutor for RecordingExecutor {
+        async fn execute(&self, user: &str, action: &ProtectiveAction) -> Result<InvocationOutput> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push((user.to_string(), action.clone()));
+            Ok(InvocationOutput {
+                function: action.function().to_string(),
+                status: "success".to_string(),
+                transaction_hash: Some("abc".to_string()),
+                ledger: Some(7),
+                result: None,
+                error: None,
+            })
+        }
+    }
+
+    fn report_map(fields: Vec<(&str, ScVal)>) -> ScVal {
+        let entries: Vec<ScMapEntry> = fields
+            .into_iter()
+            .map(|(key, val)| ScMapEntry {
+                key: ScVal::Symbol(ScSymbol::try_from(key).unwrap()),
+                val,
+            })
+            .collect();
+        ScVal::Map(Some(ScMap(entries.try_into().unwrap())))
+    }
+
+    fn config() -> WatchtowerConfig {
+        serde_json::from_value(serde_json::json!({
+            "accounts": [{
+                "user": USER,
+                "trigger_health_factor": 11_000,
+                "action": {"repay": {"asset": "native", "amount": 5_000}}
+            }]
+        }))
+        .unwrap()
+    }
+
+    fn audit_path(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("watchtower-{}-{}.jsonl", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn test_config_defaults_and_validation() {
+        let config = config();
+        assert_eq!(config.poll_interval_secs, 60);
+        assert_eq!(config.cooldown_secs, 600);
+        assert!(config.validate().is_ok());
+
+        let mut invalid = config.clone();
+        invalid.accounts[0].action = ProtectiveAction::Repay {
+            asset: "native".to_string(),
+            amount: 0,
+        };
+        assert!(invalid.validate().is_err());
+        invalid.accounts.clear();
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_action_arguments() {
+        let hedge: ProtectiveAction = serde_json::from_str("\"hedge\"").unwrap();
+        assert_eq!(hedge, ProtectiveAction::Hedge);
+        assert_eq!(hedge.function(), "execute_hedge");
+        assert_eq!(hedge.args(USER, USER).unwrap().len(), 2);
+
+        let repay = config().accounts[0].action.clone();
+        assert_eq!(repay.function(), "repay_debt_for");
+        let args = repay.args(USER, USER).unwrap();
+        assert_eq!(args[2], ScVal::Void);
+        assert_eq!(args[3], i128_arg(5_000));
+        assert!(repay.args("GBAD", USER).is_err());
+    }
+
+    #[test]
+    fn test_health_factor_from_report() {
+        let report = report_map(vec![
+            (
+                "metrics",
+                report_map(vec![
+                    ("debt", i128_arg(100)),
+                    ("health_factor", i128_arg(10_500)),
+                ]),
+            ),
+            ("timestamp", ScVal::U64(1)),
+        ]);
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(health_factor_from_report(json).unwrap(), 10_500);
+
+        let no_debt = report_map(vec![(
+            "metrics",
+            report_map(vec![("health_factor", i128_arg(i128::MAX))]),
+        )]);
+        let json = serde_json::to_value(&no_debt).unwrap();
+        assert_eq!(health_factor_from_report(json).unwrap(), i128::MAX);
+
+        let json = serde_json::to_value(ScVal::Void).unwrap();
+        assert!(health_factor_from_report(json).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_triggers_action_once_per_cooldown() {
+        let path = audit_path("trigger");
+        let source = FixedHealth(Ok(10_200));
+        let executor = RecordingExecutor::default();
+        let mut watchtower =
+            Watchtower::new(config(), &source, &executor, AuditLog::new(&path)).unwrap();
+
+        let first = watchtower.check_once().await.unwrap();
+        assert_eq!(first[0].event, AuditEvent::ActionSubmitted);
+        assert_eq!(first[0].action.as_deref(), Some("repay_debt_for"));
+        assert_eq!(first[0].transaction_hash.as_deref(), Some("abc"));
+
+        let second = watchtower.check_once().await.unwrap();
+        assert_eq!(second[0].event, AuditEvent::CoolingDown);
+        assert_eq!(executor.calls.lock().unwrap().len(), 1);
+
+        let log = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<AuditEntry> = log
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines, [first, second].concat());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_healthy_and_failed_checks_are_audited() {
+        let path = audit_path("healthy");
+        let executor = RecordingExecutor::default();
+
+        let healthy = FixedHealth(Ok(11_000));
+        let mut watchtower =
+            Watchtower::new(config(), &healthy, &executor, AuditLog::new(&path)).unwrap();
+        let entries = watchtower.check_once().await.unwrap();
+        assert_eq!(entries[0].event, AuditEvent::Healthy);
+        assert_eq!(entries[0].health_factor, Some(11_000));
+
+        let failing = FixedHealth(Err(BlockchainError::Generic("rpc down".to_string())));
+        let mut watchtower =
+            Watchtower::new(config(), &failing, &executor, AuditLog::new(&path)).unwrap();
+        let entries = watchtower.check_once().await.unwrap();
+        assert_eq!(entries[0].event, AuditEvent::CheckFailed);
+        assert!(entries[0].error.as_deref().unwrap().contains("rpc down"));
+
+        assert!(executor.calls.lock().unwrap().is_empty());
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 2);
+        std::fs::remove_file(path).unwrap();
+    }
+}
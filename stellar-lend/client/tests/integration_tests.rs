@@ -5,8 +5,8 @@
 use std::sync::Arc;
 use std::time::Duration;
 use stellarlend_client::{
-    BlockchainClient, BlockchainConfig, MonitorOptions, MonitorResult, Network, SubmitOptions,
-    TransactionStatus,
+    BlockchainClient, BlockchainConfig, BlockchainError, MonitorOptions, MonitorResult, Network,
+    SubmitOptions, TransactionStatus,
 };
 use wiremock::{
     matchers::{method, path},
@@ -234,6 +234,58 @@ async fn test_soroban_rpc_simulate_transaction() {
     assert_eq!(simulation.transaction_data, "tx_data");
 }
 
+#[tokio::test]
+async fn test_soroban_rpc_simulate_transaction_auth_and_failure() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": {
+                "results": [{"xdr": "result_xdr", "auth": ["auth_entry"]}],
+                "transactionData": "tx_data",
+                "minResourceFee": "1000",
+                "error": null
+            }
+        })))
+        .up_to_n_times(1)
+        .mount(&mock_server)
+        .await;
+    // Failed simulations carry no transactionData
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "result": {
+                "error": "HostError: Error(Contract, #1)",
+                "latestLedger": 100
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let config = create_test_config("http://horizon.test".to_string(), mock_server.uri());
+    let client = BlockchainClient::new(config).unwrap();
+
+    let simulation = client
+        .simulate_soroban_transaction("test_xdr")
+        .await
+        .unwrap();
+    assert_eq!(simulation.auth, vec!["auth_entry".to_string()]);
+
+    let simulation = client
+        .simulate_soroban_transaction("test_xdr")
+        .await
+        .unwrap();
+    assert!(!simulation.success);
+    assert_eq!(
+        simulation.error.as_deref(),
+        Some("HostError: Error(Contract, #1)")
+    );
+    assert!(simulation.transaction_data.is_empty());
+}
+
 #[tokio::test]
 async fn test_soroban_rpc_send_transaction() {
     let mock_server = MockServer::start().await;
@@ -266,6 +318,33 @@ async fn test_soroban_rpc_send_transaction() {
     assert_eq!(hash, "soroban_tx_hash");
 }
 
+#[tokio::test]
+async fn test_soroban_rpc_send_transaction_rejected() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": {
+                "hash": "soroban_tx_hash",
+                "status": "ERROR",
+                "errorResultXdr": "error_xdr"
+            }
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let config = create_test_config("http://horizon.test".to_string(), mock_server.uri());
+    let client = BlockchainClient::new(config).unwrap();
+
+    let result = client.soroban_rpc().send_transaction("test_xdr").await;
+    assert!(matches!(
+        result,
+        Err(BlockchainError::TransactionSubmissionError(message)) if message.contains("error_xdr")
+    ));
+}
+
 #[tokio::test]
 async fn test_transaction_monitoring_success() {
     let mock_server = MockServer::start().await;
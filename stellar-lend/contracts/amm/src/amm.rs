@@ -8,13 +8,58 @@
 //! contracts. Each protocol has its own configuration including fee tiers,
 //! supported token pairs, and swap limits.
 //!
+//! ## Access Control
+//! The super admin initializes the contract, replaces settings wholesale and
+//! appoints managers. Managers can register, enable or disable protocols and
+//! tune slippage limits and per-pair auto-swap thresholds without holding the
+//! super admin key.
+//!
+//! ## Quotes
+//! [`get_swap_quote`] reads a registered pool's reserves through
+//! [`AmmPoolInterface`] and prices a swap on the constant-product curve,
+//! returning the expected output, fee and price impact without executing
+//! anything. Auto-swaps route through [`quote_auto_swap`], which picks the
+//! protocol quoting the most output and bounds the swap by that quote.
+//!
+//! ## Price Impact Guard
+//! When `max_price_impact_bps` is set, every swap is priced on its pool
+//! before it executes and compared against the lending oracle configured with
+//! [`set_price_oracle`]. A swap whose pool output deviates from the
+//! oracle-implied output by more than the limit is rejected with a
+//! `swap_rejected` event, so a pool pushed off-price (for example by a
+//! sandwiching trade) cannot fill it. Auto-swaps and route hops go through
+//! the same check.
+//!
+//! ## Routing
+//! [`execute_route_swap`] executes a route of [`SwapHop`]s in order, feeding
+//! each hop's output into the next. The route's slippage tolerance is a budget
+//! for the whole route: each hop may slip by whatever the earlier hops left
+//! of it, so slippage compounding across hops cannot exceed the tolerance.
+//! [`find_swap_route`] is a best-effort router that tries direct pools, then
+//! routes through one intermediate token, and returns the route quoting the
+//! most output.
+//!
+//! ## LP Positions
+//! Liquidity added through the contract is tracked per user as an
+//! [`LpPosition`] for each protocol and pair; removals reduce it. Users who
+//! opt in with [`set_auto_compound`] let any keeper call [`auto_compound`],
+//! which claims the position's pool fees and rewards and re-adds them as
+//! liquidity. The keeper is paid a configurable share of the new LP tokens,
+//! credited to its own position in the same pool.
+//!
+//! ## Pair Statistics
+//! Each swap updates cumulative volume, fees and slippage for its token pair,
+//! keyed by the pair in canonical order so both directions share one entry.
+//! Routers and analytics read them with [`get_pair_stats`].
+//!
 //! ## Callback Validation
 //! Uses nonce-based replay protection: each user has an incrementing nonce
 //! stored on-chain. Callbacks must present the expected nonce to be accepted.
 
 #![allow(unused)]
 use soroban_sdk::{
-    contracterror, contractevent, contracttype, Address, Env, IntoVal, Map, Symbol, Val, Vec, I256,
+    contractclient, contracterror, contractevent, contracttype, Address, Env, IntoVal, Map, Symbol,
+    Val, Vec, I256,
 };
 
 /// Errors that can occur during AMM operations
@@ -50,6 +95,64 @@ pub enum AmmError {
     MaxInputExceeded = 13,
     /// Contract has already been initialized
     AlreadyInitialized = 14,
+    /// AMM integration has not been configured
+    NotConfigured = 15,
+    /// No pool could be queried for a quote
+    QuoteUnavailable = 16,
+    /// Route is empty, too long or its hops do not connect
+    InvalidRoute = 17,
+    /// No registered pools connect the tokens
+    NoRouteFound = 18,
+    /// Pool price deviates from the oracle price beyond the limit
+    PriceImpactTooHigh = 19,
+    /// Oracle price unavailable for the price impact guard
+    PriceUnavailable = 20,
+    /// The user has not opted in to auto-compounding
+    AutoCompoundDisabled = 21,
+    /// The user holds no tracked position in the pool
+    LpPositionNotFound = 22,
+    /// The position has no claimable fees
+    NothingToCompound = 23,
+    /// The pool's fees could not be claimed
+    FeeClaimFailed = 24,
+    /// No order exists with the id
+    OrderNotFound = 25,
+    /// The order is not open
+    OrderNotOpen = 26,
+    /// The order is past its expiry
+    OrderExpired = 27,
+    /// The quote does not meet the order's price
+    PriceNotMet = 28,
+    /// Native XLM needs its asset contract, which is not configured
+    NativeAssetNotSet = 29,
+    /// No DCA schedule exists with the id
+    DcaNotFound = 30,
+    /// The DCA schedule is not active
+    DcaNotActive = 31,
+    /// The DCA schedule's next tranche is not due yet
+    TrancheNotDue = 32,
+}
+
+/// Maximum number of hops in a swap route
+pub const MAX_ROUTE_HOPS: u32 = 3;
+
+/// Read interface a registered AMM pool exposes for quoting
+#[contractclient(name = "AmmPoolClient")]
+pub trait AmmPoolInterface {
+    /// Current reserves of the pool's tokens, in the order of the
+    /// registered [`TokenPair`] (`token_a`, `token_b`)
+    fn get_reserves(env: Env) -> (i128, i128);
+
+    /// Claim the fees and rewards `lp_amount` LP tokens earned since
+    /// `since`, paid to the caller in (`token_a`, `token_b`) order
+    fn claim_fees(env: Env, lp_amount: i128, since: u64) -> (i128, i128);
+}
+
+/// Read interface of the lending oracle used by the price impact guard
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracleInterface {
+    /// Current price of an asset; prices share one scale across assets
+    fn get_price(env: Env, asset: Address) -> i128;
 }
 
 /// Storage keys for AMM-related data
@@ -69,6 +172,35 @@ pub enum AmmDataKey {
     CallbackNonces(Address),
     /// Admin address
     Admin,
+    /// Scoped AMM managers: Manager(Address) -> bool
+    Manager(Address),
+    /// Cumulative swap statistics per canonically ordered pair: PairStats
+    PairStats(Option<Address>, Option<Address>),
+    /// Auto-swap threshold override per (source, target) pair: i128
+    AutoSwapThreshold(Option<Address>, Option<Address>),
+    /// Oracle for the price impact guard: PriceOracleConfig
+    PriceOracle,
+    /// Tracked LP positions per user: Vec<LpPosition>
+    LpPositions(Address),
+    /// Auto-compound opt-in per user: bool
+    AutoCompound(Address),
+    /// Keeper share of auto-compounded LP tokens (in basis points): i128
+    CompoundKeeperFee,
+    /// Asset contract of native XLM for escrowed transfers: Address
+    NativeAsset,
+}
+
+/// Maximum keeper share of auto-compounded LP tokens (in basis points)
+pub const MAX_COMPOUND_KEEPER_FEE_BPS: i128 = 1_000;
+
+/// Oracle used by the price impact guard
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PriceOracleConfig {
+    /// Lending oracle contract address
+    pub oracle: Address,
+    /// Asset address the oracle prices native XLM under
+    pub native_asset: Address,
 }
 
 /// AMM protocol configuration
@@ -117,6 +249,9 @@ pub struct AmmSettings {
     pub liquidity_enabled: bool,
     /// Auto-swap threshold for collateral optimization
     pub auto_swap_threshold: i128,
+    /// Maximum deviation of a swap's pool price from the oracle price (in
+    /// basis points, 0 disables the guard)
+    pub max_price_impact_bps: i128,
 }
 
 /// Swap operation parameters
@@ -211,6 +346,26 @@ pub struct LiquidityRecord {
     pub timestamp: u64,
 }
 
+/// Liquidity a user holds in a pool through the contract
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct LpPosition {
+    /// AMM protocol address
+    pub protocol: Address,
+    /// First token of the pair (None for native XLM)
+    pub token_a: Option<Address>,
+    /// Second token of the pair (None for native XLM)
+    pub token_b: Option<Address>,
+    /// LP tokens held
+    pub lp_amount: i128,
+    /// When the position was opened
+    pub opened_at: u64,
+    /// When liquidity was last added
+    pub last_added_at: u64,
+    /// When fees were last compounded (opening time if never)
+    pub last_compounded_at: u64,
+}
+
 /// AMM callback data for validation
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
@@ -227,6 +382,117 @@ pub struct AmmCallbackData {
     pub deadline: u64,
 }
 
+/// Read-only health snapshot of a protocol's pool for a token pair
+///
+/// Lets lending flows check whether a swap can go through before committing
+/// to it, instead of failing mid-operation.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PoolHealth {
+    /// Protocol contract address
+    pub protocol: Address,
+    /// Pool serving the pair (None if the protocol does not support it)
+    pub pool_address: Option<Address>,
+    /// Whether the protocol is enabled
+    pub protocol_enabled: bool,
+    /// Whether swap operations are enabled
+    pub swap_enabled: bool,
+    /// Fee tier (in basis points)
+    pub fee_tier: i128,
+    /// Minimum swap amount
+    pub min_swap_amount: i128,
+    /// Maximum swap amount
+    pub max_swap_amount: i128,
+    /// Whether swaps on this pool can currently be executed
+    pub healthy: bool,
+}
+
+/// Expected outcome of a swap, priced from the pool's reserves without executing it
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwapQuote {
+    /// Protocol contract address
+    pub protocol: Address,
+    /// Pool serving the pair
+    pub pool_address: Address,
+    /// Amount swapped in
+    pub amount_in: i128,
+    /// Expected amount out after the fee and price impact
+    pub amount_out: i128,
+    /// Fee charged on the input (in units of the input token)
+    pub fee: i128,
+    /// Shortfall of `amount_out` against the pool's spot price after the
+    /// fee (in basis points)
+    pub price_impact_bps: i128,
+}
+
+/// One leg of a multi-hop swap route
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwapHop {
+    /// AMM protocol executing this hop
+    pub protocol: Address,
+    /// Input token address (None for native XLM)
+    pub token_in: Option<Address>,
+    /// Output token address (None for native XLM)
+    pub token_out: Option<Address>,
+}
+
+/// Multi-hop swap parameters
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RouteSwapParams {
+    /// Hops executed in order; each hop swaps the previous hop's output
+    pub hops: Vec<SwapHop>,
+    /// Amount of the first hop's input token to swap
+    pub amount_in: i128,
+    /// Minimum amount of the last hop's output token to receive
+    pub min_amount_out: i128,
+    /// Slippage tolerance for the whole route (in basis points)
+    pub slippage_tolerance: i128,
+    /// Deadline for the swap (timestamp)
+    pub deadline: u64,
+}
+
+/// Route returned by the router
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwapRoute {
+    /// Hops to execute in order
+    pub hops: Vec<SwapHop>,
+    /// Expected output of the last hop
+    pub expected_amount_out: i128,
+}
+
+/// Cumulative swap statistics for a token pair
+///
+/// Tokens are kept in canonical order (`token_a <= token_b`), so swaps in
+/// either direction accumulate into the same entry.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PairStats {
+    /// First token of the pair (None for native XLM)
+    pub token_a: Option<Address>,
+    /// Second token of the pair (None for native XLM)
+    pub token_b: Option<Address>,
+    /// Number of swaps executed
+    pub swap_count: u64,
+    /// Cumulative amount of token A swapped in or out
+    pub volume_a: i128,
+    /// Cumulative amount of token B swapped in or out
+    pub volume_b: i128,
+    /// Fees paid on swaps from token A
+    pub fees_a: i128,
+    /// Fees paid on swaps from token B
+    pub fees_b: i128,
+    /// Sum of per-swap slippage (in basis points)
+    pub total_slippage_bps: i128,
+    /// Average slippage per swap (in basis points)
+    pub avg_slippage_bps: i128,
+    /// Timestamp of the last swap
+    pub last_swap: u64,
+}
+
 /// Execute a swap operation through AMM
 ///
 /// Performs token swaps using configured AMM protocols with slippage protection
@@ -268,6 +534,9 @@ pub fn execute_swap(env: &Env, user: Address, params: SwapParams) -> Result<i128
     // Validate token pair is supported
     validate_token_pair(env, &protocol_config, &params.token_in, &params.token_out)?;
 
+    // Reject swaps on pools priced away from the oracle
+    check_price_impact(env, &user, &params)?;
+
     // Generate callback nonce for validation
     let nonce = generate_callback_nonce(env, &user);
 
@@ -364,6 +633,14 @@ pub fn add_liquidity(env: &Env, user: Address, params: LiquidityParams) -> Resul
 
     // Record liquidity operation
     record_liquidity_operation(env, &user, Symbol::new(env, "add"), &params, lp_tokens)?;
+    adjust_lp_position(
+        env,
+        &user,
+        &params.protocol,
+        &params.token_a,
+        &params.token_b,
+        lp_tokens,
+    )?;
 
     // Emit events
     emit_liquidity_added_event(env, &user, &params, lp_tokens);
@@ -474,6 +751,7 @@ pub fn remove_liquidity(
 
     // Record liquidity operation
     record_liquidity_operation(env, &user, Symbol::new(env, "remove"), &params, lp_tokens)?;
+    adjust_lp_position(env, &user, &protocol, &token_a, &token_b, -lp_tokens)?;
 
     // Emit events
     emit_liquidity_removed_event(env, &user, &params, lp_tokens);
@@ -488,6 +766,174 @@ pub fn remove_liquidity(
     Ok((amount_a, amount_b))
 }
 
+/// Get a user's tracked LP positions
+pub fn get_lp_positions(env: &Env, user: &Address) -> Vec<LpPosition> {
+    env.storage()
+        .persistent()
+        .get::<AmmDataKey, Vec<LpPosition>>(&AmmDataKey::LpPositions(user.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Opt in to or out of keeper auto-compounding
+pub fn set_auto_compound(env: &Env, user: Address, enabled: bool) -> Result<(), AmmError> {
+    user.require_auth();
+    let key = AmmDataKey::AutoCompound(user);
+    if enabled {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+    Ok(())
+}
+
+/// Check whether a user has opted in to auto-compounding
+pub fn is_auto_compound_enabled(env: &Env, user: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get::<AmmDataKey, bool>(&AmmDataKey::AutoCompound(user.clone()))
+        .unwrap_or(false)
+}
+
+/// Set the keeper share of auto-compounded LP tokens (admin or manager)
+pub fn set_compound_keeper_fee(env: &Env, caller: Address, fee_bps: i128) -> Result<(), AmmError> {
+    require_manager_or_admin(env, &caller)?;
+    if !(0..=MAX_COMPOUND_KEEPER_FEE_BPS).contains(&fee_bps) {
+        return Err(AmmError::InvalidSwapParams);
+    }
+    env.storage()
+        .persistent()
+        .set(&AmmDataKey::CompoundKeeperFee, &fee_bps);
+    Ok(())
+}
+
+/// Get the keeper share of auto-compounded LP tokens (in basis points)
+pub fn get_compound_keeper_fee(env: &Env) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<AmmDataKey, i128>(&AmmDataKey::CompoundKeeperFee)
+        .unwrap_or(0)
+}
+
+/// Compound a user's pool fees back into liquidity
+///
+/// Claims the fees and rewards the user's position earned since it was last
+/// compounded and re-adds them to the pool. The keeper's share of the new LP
+/// tokens is moved to the keeper's position in the same pool.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `keeper` - The keeper triggering the compound
+/// * `user` - The position owner, who must have opted in
+/// * `protocol` - AMM protocol address
+/// * `token_a` - First token of the pair (None for native XLM)
+/// * `token_b` - Second token of the pair (None for native XLM)
+///
+/// # Returns
+/// Returns the LP tokens added to the user's position
+///
+/// # Errors
+/// * `AmmError::AutoCompoundDisabled` - The user has not opted in
+/// * `AmmError::LpPositionNotFound` - The user holds no position in the pool
+/// * `AmmError::FeeClaimFailed` - The pool's fees could not be claimed
+/// * `AmmError::NothingToCompound` - The claim returned nothing to re-add
+///
+/// # Events
+/// Emits the add_liquidity events, then lp_compounded
+pub fn auto_compound(
+    env: &Env,
+    keeper: Address,
+    user: Address,
+    protocol: Address,
+    token_a: Option<Address>,
+    token_b: Option<Address>,
+) -> Result<i128, AmmError> {
+    keeper.require_auth();
+    if !is_auto_compound_enabled(env, &user) {
+        return Err(AmmError::AutoCompoundDisabled);
+    }
+    check_liquidity_enabled(env)?;
+
+    let position = get_lp_positions(env, &user)
+        .iter()
+        .find(|p| p.protocol == protocol && same_pair(&p.token_a, &p.token_b, &token_a, &token_b))
+        .ok_or(AmmError::LpPositionNotFound)?;
+    let config = get_amm_protocol_config(env, &protocol)?;
+    let pair = find_token_pair(&config, &token_a, &token_b).ok_or(AmmError::InvalidTokenPair)?;
+
+    let (fees_a, fees_b) = match AmmPoolClient::new(env, &pair.pool_address)
+        .try_claim_fees(&position.lp_amount, &position.last_compounded_at)
+    {
+        Ok(Ok(fees)) => fees,
+        _ => return Err(AmmError::FeeClaimFailed),
+    };
+    if fees_a <= 0 || fees_b <= 0 {
+        return Err(AmmError::NothingToCompound);
+    }
+
+    let lp_added = add_liquidity(
+        env,
+        user.clone(),
+        LiquidityParams {
+            protocol: protocol.clone(),
+            token_a: pair.token_a.clone(),
+            token_b: pair.token_b.clone(),
+            amount_a: fees_a,
+            amount_b: fees_b,
+            min_amount_a: 0,
+            min_amount_b: 0,
+            deadline: env.ledger().timestamp(),
+        },
+    )?;
+    let keeper_lp = lp_added
+        .checked_mul(get_compound_keeper_fee(env))
+        .map(|v| v / 10_000)
+        .ok_or(AmmError::Overflow)?;
+    if keeper_lp > 0 {
+        adjust_lp_position(
+            env,
+            &user,
+            &protocol,
+            &pair.token_a,
+            &pair.token_b,
+            -keeper_lp,
+        )?;
+        adjust_lp_position(
+            env,
+            &keeper,
+            &protocol,
+            &pair.token_a,
+            &pair.token_b,
+            keeper_lp,
+        )?;
+    }
+
+    let mut positions = get_lp_positions(env, &user);
+    for i in 0..positions.len() {
+        let mut p = positions.get(i).unwrap();
+        if p.protocol == protocol && same_pair(&p.token_a, &p.token_b, &token_a, &token_b) {
+            p.last_compounded_at = env.ledger().timestamp();
+            positions.set(i, p);
+        }
+    }
+    env.storage()
+        .persistent()
+        .set(&AmmDataKey::LpPositions(user.clone()), &positions);
+
+    let user_lp = lp_added - keeper_lp;
+    LpCompoundedEvent {
+        user,
+        keeper,
+        protocol,
+        fees_a,
+        fees_b,
+        lp_added: user_lp,
+        keeper_lp,
+    }
+    .publish(env);
+
+    Ok(user_lp)
+}
+
 /// Validate AMM callback
 ///
 /// Validates callbacks from AMM protocols to ensure they are legitimate
@@ -572,13 +1018,22 @@ pub fn auto_swap_for_collateral(
         return Err(AmmError::SwapPaused);
     }
 
-    // Check if amount meets threshold
-    if amount < settings.auto_swap_threshold {
+    // Check if amount meets the pair's threshold
+    if amount < get_auto_swap_threshold(env, &None, &target_token)? {
         return Err(AmmError::InvalidSwapParams);
     }
 
-    // Find best AMM protocol for this swap
-    let best_protocol = find_best_amm_protocol(env, &None, &target_token, amount)?;
+    // Route to the protocol quoting the most output and bound the swap by
+    // its quote; pools that cannot quote fall back to the first supporting
+    // protocol at the default slippage
+    let (best_protocol, expected_out) = match quote_auto_swap(env, target_token.clone(), amount) {
+        Ok(quote) => (quote.protocol, quote.amount_out),
+        Err(AmmError::QuoteUnavailable) => (
+            find_best_amm_protocol(env, &None, &target_token, amount)?,
+            amount,
+        ),
+        Err(e) => return Err(e),
+    };
 
     // Create swap parameters with default slippage
     let params = SwapParams {
@@ -586,7 +1041,10 @@ pub fn auto_swap_for_collateral(
         token_in: None, // Assume swapping from native XLM
         token_out: target_token,
         amount_in: amount,
-        min_amount_out: calculate_min_output_with_slippage(amount, settings.default_slippage)?,
+        min_amount_out: calculate_min_output_with_slippage(
+            expected_out,
+            settings.default_slippage,
+        )?,
         slippage_tolerance: settings.default_slippage,
         deadline: env
             .ledger()
@@ -601,8 +1059,444 @@ pub fn auto_swap_for_collateral(
     Ok(amount_out)
 }
 
+/// Quote a swap on a protocol's pool without executing it
+///
+/// Reads the pool's reserves and prices the swap on the constant-product
+/// curve after the protocol's fee tier.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `protocol` - AMM protocol address
+/// * `token_in` - Input token address (None for native XLM)
+/// * `token_out` - Output token address (None for native XLM)
+/// * `amount_in` - Amount to swap
+///
+/// # Errors
+/// * `AmmError::InvalidSwapParams` - Amount is not positive
+/// * `AmmError::InvalidTokenPair` - The protocol does not support the pair
+/// * `AmmError::UnsupportedProtocol` - The protocol is unknown or disabled
+/// * `AmmError::QuoteUnavailable` - The pool could not be queried
+/// * `AmmError::InsufficientLiquidity` - The pool holds no reserves
+pub fn get_swap_quote(
+    env: &Env,
+    protocol: Address,
+    token_in: Option<Address>,
+    token_out: Option<Address>,
+    amount_in: i128,
+) -> Result<SwapQuote, AmmError> {
+    if amount_in <= 0 {
+        return Err(AmmError::InvalidSwapParams);
+    }
+    if token_in == token_out {
+        return Err(AmmError::InvalidTokenPair);
+    }
+    let config = get_amm_protocol_config(env, &protocol)?;
+    if !config.enabled {
+        return Err(AmmError::UnsupportedProtocol);
+    }
+    let pair = find_token_pair(&config, &token_in, &token_out).ok_or(AmmError::InvalidTokenPair)?;
+
+    let (reserve_a, reserve_b) =
+        match AmmPoolClient::new(env, &pair.pool_address).try_get_reserves() {
+            Ok(Ok(reserves)) => reserves,
+            _ => return Err(AmmError::QuoteUnavailable),
+        };
+    let (reserve_in, reserve_out) = if pair.token_a == token_in {
+        (reserve_a, reserve_b)
+    } else {
+        (reserve_b, reserve_a)
+    };
+    if reserve_in <= 0 || reserve_out <= 0 {
+        return Err(AmmError::InsufficientLiquidity);
+    }
+
+    let fee = calculate_swap_fees(&config, amount_in)?;
+    let net_in = amount_in - fee;
+    // Constant product: out = reserve_out * net_in / (reserve_in + net_in)
+    let amount_out = reserve_out
+        .checked_mul(net_in)
+        .and_then(|v| v.checked_div(reserve_in.checked_add(net_in)?))
+        .ok_or(AmmError::Overflow)?;
+    let spot_out = reserve_out
+        .checked_mul(net_in)
+        .and_then(|v| v.checked_div(reserve_in))
+        .ok_or(AmmError::Overflow)?;
+    let price_impact_bps = if spot_out > 0 {
+        (spot_out - amount_out)
+            .checked_mul(10_000)
+            .map(|v| v / spot_out)
+            .ok_or(AmmError::Overflow)?
+    } else {
+        0
+    };
+
+    Ok(SwapQuote {
+        protocol,
+        pool_address: pair.pool_address,
+        amount_in,
+        amount_out,
+        fee,
+        price_impact_bps,
+    })
+}
+
+/// Quote an auto-swap from native XLM on the best enabled protocol
+///
+/// Quotes every enabled protocol supporting the pair and returns the quote
+/// with the most output. Protocols whose pool cannot be queried are skipped.
+///
+/// # Errors
+/// * `AmmError::UnsupportedProtocol` - No enabled protocol supports the pair
+/// * `AmmError::QuoteUnavailable` - No supporting pool could be queried
+pub fn quote_auto_swap(
+    env: &Env,
+    target_token: Option<Address>,
+    amount: i128,
+) -> Result<SwapQuote, AmmError> {
+    let protocols = get_amm_protocols(env)?;
+    let mut supported = false;
+    let mut best: Option<SwapQuote> = None;
+    for (protocol_addr, config) in protocols.iter() {
+        if !config.enabled || find_token_pair(&config, &None, &target_token).is_none() {
+            continue;
+        }
+        supported = true;
+        match get_swap_quote(env, protocol_addr, None, target_token.clone(), amount) {
+            Ok(quote) => {
+                let better = match &best {
+                    Some(current) => quote.amount_out > current.amount_out,
+                    None => true,
+                };
+                if better {
+                    best = Some(quote);
+                }
+            }
+            Err(AmmError::QuoteUnavailable) | Err(AmmError::InsufficientLiquidity) => {}
+            Err(e) => return Err(e),
+        }
+    }
+    match best {
+        Some(quote) => Ok(quote),
+        None if supported => Err(AmmError::QuoteUnavailable),
+        None => Err(AmmError::UnsupportedProtocol),
+    }
+}
+
+/// Execute a multi-hop swap
+///
+/// Executes each hop through [`execute_swap`], swapping the previous hop's
+/// output. Every hop is bounded by its expected output less the slippage the
+/// route has left, and the route fails if its compounded slippage exceeds
+/// `slippage_tolerance` or the final output falls below `min_amount_out`.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `user` - The user performing the swap
+/// * `params` - Route parameters including hops, amounts and slippage
+///
+/// # Returns
+/// Returns the amount of the last hop's output token received
+///
+/// # Errors
+/// * `AmmError::InvalidRoute` - The route is empty, too long, cyclic or disconnected
+/// * `AmmError::MinOutputNotMet` - A hop or the route fell short of its minimum
+/// * `AmmError::SlippageExceeded` - The compounded slippage exceeds the tolerance
+///
+/// # Events
+/// Emits swap_executed and amm_operation for every hop, then route_executed
+pub fn execute_route_swap(
+    env: &Env,
+    user: Address,
+    params: RouteSwapParams,
+) -> Result<i128, AmmError> {
+    validate_route_params(env, &params)?;
+
+    let floor_bps = 10_000 - params.slippage_tolerance;
+    // Share of the ideal output kept so far (in basis points)
+    let mut retained_bps = 10_000i128;
+    let mut amount = params.amount_in;
+    for hop in params.hops.iter() {
+        let expected = expected_swap_output(env, &hop, amount)?;
+        // Ceiling keeps the hop within the budget the route has left
+        let hop_floor_bps = floor_bps
+            .checked_mul(10_000)
+            .map(|v| (v + retained_bps - 1) / retained_bps)
+            .ok_or(AmmError::Overflow)?;
+        let hop_tolerance = (10_000 - hop_floor_bps).max(0);
+
+        let amount_out = execute_swap(
+            env,
+            user.clone(),
+            SwapParams {
+                protocol: hop.protocol,
+                token_in: hop.token_in,
+                token_out: hop.token_out,
+                amount_in: amount,
+                min_amount_out: calculate_min_output_with_slippage(expected, hop_tolerance)?,
+                slippage_tolerance: hop_tolerance,
+                deadline: params.deadline,
+            },
+        )?;
+
+        retained_bps = retained_bps
+            .checked_mul(amount_out.min(expected))
+            .and_then(|v| v.checked_div(expected))
+            .ok_or(AmmError::Overflow)?;
+        amount = amount_out;
+    }
+
+    let slippage_bps = 10_000 - retained_bps;
+    if slippage_bps > params.slippage_tolerance {
+        return Err(AmmError::SlippageExceeded);
+    }
+    if amount < params.min_amount_out {
+        return Err(AmmError::MinOutputNotMet);
+    }
+
+    emit_route_executed_event(env, &user, &params, amount, slippage_bps);
+
+    Ok(amount)
+}
+
+/// Find a swap route between two tokens
+///
+/// Best-effort router over the enabled protocols: tries every direct pool,
+/// then every route through one intermediate token, and returns the route
+/// quoting the most output. Direct routes win ties. Hops whose pool cannot be
+/// quoted are priced at par.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `token_in` - Input token address (None for native XLM)
+/// * `token_out` - Output token address (None for native XLM)
+/// * `amount_in` - Amount to swap
+///
+/// # Errors
+/// * `AmmError::NoRouteFound` - No enabled pools connect the tokens
+pub fn find_swap_route(
+    env: &Env,
+    token_in: Option<Address>,
+    token_out: Option<Address>,
+    amount_in: i128,
+) -> Result<SwapRoute, AmmError> {
+    if amount_in <= 0 {
+        return Err(AmmError::InvalidSwapParams);
+    }
+    if token_in == token_out {
+        return Err(AmmError::InvalidTokenPair);
+    }
+    let protocols = get_amm_protocols(env)?;
+    let mut best: Option<SwapRoute> = None;
+
+    // Direct routes
+    for (protocol, config) in protocols.iter() {
+        if !config.enabled || find_token_pair(&config, &token_in, &token_out).is_none() {
+            continue;
+        }
+        let hops = Vec::from_array(
+            env,
+            [SwapHop {
+                protocol,
+                token_in: token_in.clone(),
+                token_out: token_out.clone(),
+            }],
+        );
+        consider_route(env, &mut best, hops, amount_in);
+    }
+
+    // Routes through one intermediate token
+    for (first, first_config) in protocols.iter() {
+        if !first_config.enabled {
+            continue;
+        }
+        for pair in first_config.supported_pairs.iter() {
+            let intermediate = if pair.token_a == token_in {
+                pair.token_b
+            } else if pair.token_b == token_in {
+                pair.token_a
+            } else {
+                continue;
+            };
+            if intermediate == token_out {
+                continue;
+            }
+            for (second, second_config) in protocols.iter() {
+                if !second_config.enabled
+                    || find_token_pair(&second_config, &intermediate, &token_out).is_none()
+                {
+                    continue;
+                }
+                let hops = Vec::from_array(
+                    env,
+                    [
+                        SwapHop {
+                            protocol: first.clone(),
+                            token_in: token_in.clone(),
+                            token_out: intermediate.clone(),
+                        },
+                        SwapHop {
+                            protocol: second,
+                            token_in: intermediate.clone(),
+                            token_out: token_out.clone(),
+                        },
+                    ],
+                );
+                consider_route(env, &mut best, hops, amount_in);
+            }
+        }
+    }
+
+    best.ok_or(AmmError::NoRouteFound)
+}
+
 // Helper functions
 
+/// Keep `hops` as the best route if it quotes more than the current best
+///
+/// Routes that cannot be quoted or executed at their hop amounts are skipped.
+fn consider_route(env: &Env, best: &mut Option<SwapRoute>, hops: Vec<SwapHop>, amount_in: i128) {
+    let mut amount = amount_in;
+    for hop in hops.iter() {
+        amount = match expected_swap_output(env, &hop, amount) {
+            Ok(out) if out > 0 => out,
+            _ => return,
+        };
+    }
+    let better = match best {
+        Some(current) => amount > current.expected_amount_out,
+        None => true,
+    };
+    if better {
+        *best = Some(SwapRoute {
+            hops,
+            expected_amount_out: amount,
+        });
+    }
+}
+
+/// Expected output of a hop: its pool quote, or par when the pool cannot be quoted
+pub(crate) fn expected_swap_output(
+    env: &Env,
+    hop: &SwapHop,
+    amount_in: i128,
+) -> Result<i128, AmmError> {
+    let config = get_amm_protocol_config(env, &hop.protocol)?;
+    if amount_in < config.min_swap_amount {
+        return Err(AmmError::InvalidSwapParams);
+    }
+    if amount_in > config.max_swap_amount {
+        return Err(AmmError::MaxInputExceeded);
+    }
+    match get_swap_quote(
+        env,
+        hop.protocol.clone(),
+        hop.token_in.clone(),
+        hop.token_out.clone(),
+        amount_in,
+    ) {
+        Ok(quote) => Ok(quote.amount_out),
+        Err(AmmError::QuoteUnavailable) => Ok(amount_in),
+        Err(e) => Err(e),
+    }
+}
+
+/// Validate multi-hop swap parameters
+fn validate_route_params(env: &Env, params: &RouteSwapParams) -> Result<(), AmmError> {
+    if params.hops.is_empty() || params.hops.len() > MAX_ROUTE_HOPS {
+        return Err(AmmError::InvalidRoute);
+    }
+    if params.amount_in <= 0 || params.min_amount_out <= 0 || params.slippage_tolerance < 0 {
+        return Err(AmmError::InvalidSwapParams);
+    }
+    let settings = get_amm_settings(env)?;
+    if params.slippage_tolerance > settings.max_slippage {
+        return Err(AmmError::SlippageExceeded);
+    }
+
+    let mut previous: Option<SwapHop> = None;
+    for hop in params.hops.iter() {
+        if let Some(prev) = previous {
+            if prev.token_out != hop.token_in {
+                return Err(AmmError::InvalidRoute);
+            }
+        }
+        previous = Some(hop);
+    }
+    let first = params.hops.first().ok_or(AmmError::InvalidRoute)?;
+    if params.hops.len() > 1 && previous.map(|last| last.token_out) == Some(first.token_in) {
+        return Err(AmmError::InvalidRoute);
+    }
+    Ok(())
+}
+
+/// Compare a swap's pool output against the oracle-implied output
+///
+/// Prices the swap on its pool and rejects it when the output deviates from
+/// what the oracle prices imply for the same input after the pool fee by
+/// more than `max_price_impact_bps`. A no-op while the limit is 0.
+///
+/// # Errors
+/// * `AmmError::PriceImpactTooHigh` - The deviation exceeds the limit
+/// * `AmmError::PriceUnavailable` - No oracle is configured or it has no price
+/// * `AmmError::QuoteUnavailable` - The pool could not be priced
+fn check_price_impact(env: &Env, user: &Address, params: &SwapParams) -> Result<(), AmmError> {
+    let settings = get_amm_settings(env)?;
+    if settings.max_price_impact_bps == 0 {
+        return Ok(());
+    }
+    let oracle = get_price_oracle(env).ok_or(AmmError::PriceUnavailable)?;
+
+    let quote = get_swap_quote(
+        env,
+        params.protocol.clone(),
+        params.token_in.clone(),
+        params.token_out.clone(),
+        params.amount_in,
+    )?;
+    let price_in = get_oracle_price(env, &oracle, &params.token_in)?;
+    let price_out = get_oracle_price(env, &oracle, &params.token_out)?;
+    let oracle_out = (params.amount_in - quote.fee)
+        .checked_mul(price_in)
+        .and_then(|v| v.checked_div(price_out))
+        .ok_or(AmmError::Overflow)?;
+    if oracle_out <= 0 {
+        return Err(AmmError::PriceUnavailable);
+    }
+
+    let deviation_bps = (quote.amount_out - oracle_out)
+        .abs()
+        .checked_mul(10_000)
+        .map(|v| v / oracle_out)
+        .ok_or(AmmError::Overflow)?;
+    if deviation_bps > settings.max_price_impact_bps {
+        SwapRejectedEvent {
+            user: user.clone(),
+            protocol: params.protocol.clone(),
+            token_in: params.token_in.clone(),
+            token_out: params.token_out.clone(),
+            amount_in: params.amount_in,
+            deviation_bps,
+            max_price_impact_bps: settings.max_price_impact_bps,
+        }
+        .publish(env);
+        return Err(AmmError::PriceImpactTooHigh);
+    }
+    Ok(())
+}
+
+/// Oracle price of a token, with native XLM priced under the configured asset
+fn get_oracle_price(
+    env: &Env,
+    oracle: &PriceOracleConfig,
+    token: &Option<Address>,
+) -> Result<i128, AmmError> {
+    let asset = token.clone().unwrap_or_else(|| oracle.native_asset.clone());
+    match PriceOracleClient::new(env, &oracle.oracle).try_get_price(&asset) {
+        Ok(Ok(price)) if price > 0 => Ok(price),
+        _ => Err(AmmError::PriceUnavailable),
+    }
+}
+
 /// Validate swap parameters
 fn validate_swap_params(env: &Env, params: &SwapParams) -> Result<(), AmmError> {
     if params.amount_in <= 0 {
@@ -686,6 +1580,59 @@ pub fn get_amm_settings(env: &Env) -> Result<AmmSettings, AmmError> {
         .ok_or(AmmError::InvalidSwapParams)
 }
 
+/// Get the maximum slippage tolerance (in basis points)
+pub fn get_max_slippage(env: &Env) -> Result<i128, AmmError> {
+    Ok(get_amm_settings(env)?.max_slippage)
+}
+
+/// Get the addresses of all enabled AMM protocols
+pub fn get_supported_protocols(env: &Env) -> Vec<Address> {
+    let mut supported = Vec::new(env);
+    if let Ok(protocols) = get_amm_protocols(env) {
+        for (protocol_addr, config) in protocols.iter() {
+            if config.enabled {
+                supported.push_back(protocol_addr);
+            }
+        }
+    }
+    supported
+}
+
+/// Get the health of a protocol's pool for a token pair
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `protocol` - AMM protocol address
+/// * `token_a` - First token address (None for native XLM)
+/// * `token_b` - Second token address (None for native XLM)
+///
+/// # Returns
+/// Returns the pool health snapshot; an unsupported pair yields an
+/// unhealthy snapshot without a pool address
+pub fn get_pool_health(
+    env: &Env,
+    protocol: Address,
+    token_a: Option<Address>,
+    token_b: Option<Address>,
+) -> Result<PoolHealth, AmmError> {
+    let settings = get_amm_settings(env)?;
+    let config = get_amm_protocol_config(env, &protocol)?;
+
+    let pool_address = find_token_pair(&config, &token_a, &token_b).map(|pair| pair.pool_address);
+
+    let healthy = config.enabled && settings.swap_enabled && pool_address.is_some();
+    Ok(PoolHealth {
+        protocol,
+        pool_address,
+        protocol_enabled: config.enabled,
+        swap_enabled: settings.swap_enabled,
+        fee_tier: config.fee_tier,
+        min_swap_amount: config.min_swap_amount,
+        max_swap_amount: config.max_swap_amount,
+        healthy,
+    })
+}
+
 /// Validate token pair is supported by protocol
 fn validate_token_pair(
     env: &Env,
@@ -693,14 +1640,88 @@ fn validate_token_pair(
     token_a: &Option<Address>,
     token_b: &Option<Address>,
 ) -> Result<(), AmmError> {
-    for pair in protocol_config.supported_pairs.iter() {
-        if (pair.token_a == *token_a && pair.token_b == *token_b)
+    find_token_pair(protocol_config, token_a, token_b)
+        .map(|_| ())
+        .ok_or(AmmError::InvalidTokenPair)
+}
+
+/// Find the protocol's pool for a token pair, in either order
+fn find_token_pair(
+    protocol_config: &AmmProtocolConfig,
+    token_a: &Option<Address>,
+    token_b: &Option<Address>,
+) -> Option<TokenPair> {
+    protocol_config.supported_pairs.iter().find(|pair| {
+        (pair.token_a == *token_a && pair.token_b == *token_b)
             || (pair.token_a == *token_b && pair.token_b == *token_a)
-        {
-            return Ok(());
+    })
+}
+
+/// Whether two token pairs are the same, in either order
+fn same_pair(
+    a1: &Option<Address>,
+    b1: &Option<Address>,
+    a2: &Option<Address>,
+    b2: &Option<Address>,
+) -> bool {
+    (a1 == a2 && b1 == b2) || (a1 == b2 && b1 == a2)
+}
+
+/// Add `delta` LP tokens to a user's tracked position in a pool
+///
+/// Opens the position on first add. Removals reduce it down to zero and drop
+/// it; LP tokens acquired outside the contract are not tracked.
+fn adjust_lp_position(
+    env: &Env,
+    user: &Address,
+    protocol: &Address,
+    token_a: &Option<Address>,
+    token_b: &Option<Address>,
+    delta: i128,
+) -> Result<(), AmmError> {
+    let key = AmmDataKey::LpPositions(user.clone());
+    let mut positions = get_lp_positions(env, user);
+    let now = env.ledger().timestamp();
+
+    let index = positions.iter().position(|p| {
+        p.protocol == *protocol && same_pair(&p.token_a, &p.token_b, token_a, token_b)
+    });
+    match index {
+        Some(i) => {
+            let i = i as u32;
+            let mut position = positions.get(i).unwrap();
+            position.lp_amount = position
+                .lp_amount
+                .checked_add(delta)
+                .ok_or(AmmError::Overflow)?
+                .max(0);
+            if delta > 0 {
+                position.last_added_at = now;
+            }
+            if position.lp_amount == 0 {
+                positions.remove(i);
+            } else {
+                positions.set(i, position);
+            }
         }
+        None if delta > 0 => positions.push_back(LpPosition {
+            protocol: protocol.clone(),
+            token_a: token_a.clone(),
+            token_b: token_b.clone(),
+            lp_amount: delta,
+            opened_at: now,
+            last_added_at: now,
+            last_compounded_at: now,
+        }),
+        None => return Ok(()),
+    }
+
+    if positions.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &positions);
     }
-    Err(AmmError::InvalidTokenPair)
+    Ok(())
 }
 
 /// Generate callback nonce for validation
@@ -899,9 +1920,65 @@ fn record_swap(
     }
 
     env.storage().persistent().set(&history_key, &history);
+
+    update_pair_stats(env, params, amount_out, fees_paid)
+}
+
+/// Fold a swap into its pair's cumulative statistics
+fn update_pair_stats(
+    env: &Env,
+    params: &SwapParams,
+    amount_out: i128,
+    fees_paid: i128,
+) -> Result<(), AmmError> {
+    let mut stats = get_pair_stats(env, params.token_in.clone(), params.token_out.clone());
+    let (volume_in, volume_out, fees) = if params.token_in == stats.token_a {
+        (&mut stats.volume_a, &mut stats.volume_b, &mut stats.fees_a)
+    } else {
+        (&mut stats.volume_b, &mut stats.volume_a, &mut stats.fees_b)
+    };
+    *volume_in = volume_in
+        .checked_add(params.amount_in)
+        .ok_or(AmmError::Overflow)?;
+    *volume_out = volume_out
+        .checked_add(amount_out)
+        .ok_or(AmmError::Overflow)?;
+    *fees = fees.checked_add(fees_paid).ok_or(AmmError::Overflow)?;
+
+    let slippage_bps = calculate_realized_slippage(params.amount_in, amount_out)?;
+    stats.swap_count += 1;
+    stats.total_slippage_bps = stats
+        .total_slippage_bps
+        .checked_add(slippage_bps)
+        .ok_or(AmmError::Overflow)?;
+    stats.avg_slippage_bps = stats.total_slippage_bps / stats.swap_count as i128;
+    stats.last_swap = env.ledger().timestamp();
+
+    env.storage().persistent().set(
+        &AmmDataKey::PairStats(stats.token_a.clone(), stats.token_b.clone()),
+        &stats,
+    );
     Ok(())
 }
 
+/// Realized slippage of a swap against the router's 1:1 reference rate
+/// (in basis points, never negative)
+pub(crate) fn calculate_realized_slippage(
+    amount_in: i128,
+    amount_out: i128,
+) -> Result<i128, AmmError> {
+    if amount_in <= 0 {
+        return Err(AmmError::InvalidSwapParams);
+    }
+    if amount_out >= amount_in {
+        return Ok(0);
+    }
+    (amount_in - amount_out)
+        .checked_mul(10_000)
+        .map(|v| v / amount_in)
+        .ok_or(AmmError::Overflow)
+}
+
 /// Record liquidity operation
 fn record_liquidity_operation(
     env: &Env,
@@ -953,6 +2030,30 @@ pub struct SwapExecutedEvent {
     pub effective_price: i128,
 }
 
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct SwapRejectedEvent {
+    pub user: Address,
+    pub protocol: Address,
+    pub token_in: Option<Address>,
+    pub token_out: Option<Address>,
+    pub amount_in: i128,
+    pub deviation_bps: i128,
+    pub max_price_impact_bps: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct RouteExecutedEvent {
+    pub user: Address,
+    pub token_in: Option<Address>,
+    pub token_out: Option<Address>,
+    pub hops: u32,
+    pub amount_in: i128,
+    pub amount_out: i128,
+    pub slippage_bps: i128,
+}
+
 #[contractevent]
 #[derive(Clone, Debug)]
 pub struct LiquidityAddedEvent {
@@ -990,6 +2091,26 @@ pub struct CallbackValidatedEvent {
     pub nonce: u64,
 }
 
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct LpCompoundedEvent {
+    pub user: Address,
+    pub keeper: Address,
+    pub protocol: Address,
+    pub fees_a: i128,
+    pub fees_b: i128,
+    pub lp_added: i128,
+    pub keeper_lp: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ManagerUpdatedEvent {
+    pub admin: Address,
+    pub manager: Address,
+    pub granted: bool,
+}
+
 /// Emit swap executed event
 fn emit_swap_executed_event(
     env: &Env,
@@ -1008,6 +2129,28 @@ fn emit_swap_executed_event(
     .publish(env);
 }
 
+/// Emit route executed event
+fn emit_route_executed_event(
+    env: &Env,
+    user: &Address,
+    params: &RouteSwapParams,
+    amount_out: i128,
+    slippage_bps: i128,
+) {
+    let first = params.hops.first();
+    let last = params.hops.last();
+    RouteExecutedEvent {
+        user: user.clone(),
+        token_in: first.and_then(|hop| hop.token_in),
+        token_out: last.and_then(|hop| hop.token_out),
+        hops: params.hops.len(),
+        amount_in: params.amount_in,
+        amount_out,
+        slippage_bps,
+    }
+    .publish(env);
+}
+
 /// Emit liquidity added event
 fn emit_liquidity_added_event(
     env: &Env,
@@ -1094,6 +2237,7 @@ pub fn initialize_amm_settings(
         swap_enabled: true,
         liquidity_enabled: true,
         auto_swap_threshold,
+        max_price_impact_bps: 0,
     };
 
     let settings_key = AmmDataKey::AmmSettings;
@@ -1107,14 +2251,14 @@ pub fn initialize_amm_settings(
     Ok(())
 }
 
-/// Add AMM protocol (admin only)
+/// Add or replace an AMM protocol (admin or manager)
 pub fn add_amm_protocol(
     env: &Env,
-    admin: Address,
+    caller: Address,
     protocol_config: AmmProtocolConfig,
 ) -> Result<(), AmmError> {
-    // Check admin authorization
-    require_admin(env, &admin)?;
+    // Check manager authorization
+    require_manager_or_admin(env, &caller)?;
 
     let protocols_key = AmmDataKey::AmmProtocols;
     let mut protocols = env
@@ -1138,6 +2282,10 @@ pub fn update_amm_settings(
     // Check admin authorization
     require_admin(env, &admin)?;
 
+    if settings.max_price_impact_bps < 0 || settings.max_price_impact_bps > 10_000 {
+        return Err(AmmError::InvalidSwapParams);
+    }
+
     let settings_key = AmmDataKey::AmmSettings;
     env.storage().persistent().set(&settings_key, &settings);
 
@@ -1159,6 +2307,192 @@ fn require_admin(env: &Env, caller: &Address) -> Result<(), AmmError> {
     Ok(())
 }
 
+/// Check if caller is a manager or the admin
+pub(crate) fn require_manager_or_admin(env: &Env, caller: &Address) -> Result<(), AmmError> {
+    caller.require_auth();
+    if is_amm_manager(env, caller) {
+        return Ok(());
+    }
+    require_admin(env, caller)
+}
+
+/// Enable or disable a registered AMM protocol (admin or manager)
+pub fn set_protocol_enabled(
+    env: &Env,
+    caller: Address,
+    protocol: Address,
+    enabled: bool,
+) -> Result<(), AmmError> {
+    require_manager_or_admin(env, &caller)?;
+
+    let protocols_key = AmmDataKey::AmmProtocols;
+    let mut protocols = get_amm_protocols(env)?;
+    let mut config = protocols
+        .get(protocol.clone())
+        .ok_or(AmmError::UnsupportedProtocol)?;
+    config.enabled = enabled;
+    protocols.set(protocol, config);
+    env.storage().persistent().set(&protocols_key, &protocols);
+
+    Ok(())
+}
+
+/// Tune the default and maximum slippage tolerances (admin or manager)
+///
+/// # Errors
+/// * `AmmError::InvalidSwapParams` - Unless `0 <= default_slippage <= max_slippage <= 10_000`
+pub fn set_slippage_limits(
+    env: &Env,
+    caller: Address,
+    default_slippage: i128,
+    max_slippage: i128,
+) -> Result<(), AmmError> {
+    require_manager_or_admin(env, &caller)?;
+
+    if default_slippage < 0 || default_slippage > max_slippage || max_slippage > 10_000 {
+        return Err(AmmError::InvalidSwapParams);
+    }
+
+    let settings_key = AmmDataKey::AmmSettings;
+    let mut settings = get_amm_settings(env)?;
+    settings.default_slippage = default_slippage;
+    settings.max_slippage = max_slippage;
+    env.storage().persistent().set(&settings_key, &settings);
+
+    Ok(())
+}
+
+/// Set or clear the auto-swap threshold for a (source, target) pair (admin or manager)
+///
+/// Dust thresholds differ between assets of different value and decimals, so
+/// a pair override replaces the global `auto_swap_threshold`. `None` removes
+/// the override.
+///
+/// # Errors
+/// * `AmmError::InvalidTokenPair` - Source and target are the same token
+/// * `AmmError::InvalidSwapParams` - Threshold is negative
+pub fn set_auto_swap_threshold(
+    env: &Env,
+    caller: Address,
+    source: Option<Address>,
+    target: Option<Address>,
+    threshold: Option<i128>,
+) -> Result<(), AmmError> {
+    require_manager_or_admin(env, &caller)?;
+
+    if source == target {
+        return Err(AmmError::InvalidTokenPair);
+    }
+
+    let key = AmmDataKey::AutoSwapThreshold(source, target);
+    match threshold {
+        Some(threshold) if threshold < 0 => return Err(AmmError::InvalidSwapParams),
+        Some(threshold) => env.storage().persistent().set(&key, &threshold),
+        None => env.storage().persistent().remove(&key),
+    }
+
+    Ok(())
+}
+
+/// Get the auto-swap threshold for a (source, target) pair
+///
+/// Falls back to the global `auto_swap_threshold` when the pair has no override.
+pub fn get_auto_swap_threshold(
+    env: &Env,
+    source: &Option<Address>,
+    target: &Option<Address>,
+) -> Result<i128, AmmError> {
+    let key = AmmDataKey::AutoSwapThreshold(source.clone(), target.clone());
+    match env.storage().persistent().get::<AmmDataKey, i128>(&key) {
+        Some(threshold) => Ok(threshold),
+        None => Ok(get_amm_settings(env)?.auto_swap_threshold),
+    }
+}
+
+/// Appoint or remove an AMM manager (admin only)
+pub fn set_amm_manager(
+    env: &Env,
+    admin: Address,
+    manager: Address,
+    granted: bool,
+) -> Result<(), AmmError> {
+    admin.require_auth();
+    require_admin(env, &admin)?;
+
+    let manager_key = AmmDataKey::Manager(manager.clone());
+    if granted {
+        env.storage().persistent().set(&manager_key, &true);
+    } else {
+        env.storage().persistent().remove(&manager_key);
+    }
+
+    ManagerUpdatedEvent {
+        admin,
+        manager,
+        granted,
+    }
+    .publish(env);
+
+    Ok(())
+}
+
+/// Set or clear the oracle used by the price impact guard (admin only)
+pub fn set_price_oracle(
+    env: &Env,
+    admin: Address,
+    oracle: Option<PriceOracleConfig>,
+) -> Result<(), AmmError> {
+    admin.require_auth();
+    require_admin(env, &admin)?;
+
+    let key = AmmDataKey::PriceOracle;
+    match oracle {
+        Some(oracle) => env.storage().persistent().set(&key, &oracle),
+        None => env.storage().persistent().remove(&key),
+    }
+    Ok(())
+}
+
+/// Get the oracle used by the price impact guard
+pub fn get_price_oracle(env: &Env) -> Option<PriceOracleConfig> {
+    env.storage()
+        .persistent()
+        .get::<AmmDataKey, PriceOracleConfig>(&AmmDataKey::PriceOracle)
+}
+
+/// Set the asset contract of native XLM used for escrowed transfers (admin only)
+pub fn set_native_asset(env: &Env, admin: Address, asset: Address) -> Result<(), AmmError> {
+    admin.require_auth();
+    require_admin(env, &admin)?;
+    env.storage()
+        .persistent()
+        .set(&AmmDataKey::NativeAsset, &asset);
+    Ok(())
+}
+
+/// Get the asset contract of native XLM
+pub fn get_native_asset(env: &Env) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get::<AmmDataKey, Address>(&AmmDataKey::NativeAsset)
+}
+
+/// Token contract to transfer for a token (None resolves to native XLM)
+pub(crate) fn token_address(env: &Env, token: &Option<Address>) -> Result<Address, AmmError> {
+    match token {
+        Some(token) => Ok(token.clone()),
+        None => get_native_asset(env).ok_or(AmmError::NativeAssetNotSet),
+    }
+}
+
+/// Check if an address is an AMM manager
+pub fn is_amm_manager(env: &Env, account: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get::<AmmDataKey, bool>(&AmmDataKey::Manager(account.clone()))
+        .unwrap_or(false)
+}
+
 // Public query functions for analytics
 
 /// Get swap history
@@ -1196,6 +2530,33 @@ pub fn get_swap_history(
     Ok(filtered_history)
 }
 
+/// Get cumulative swap statistics for a token pair
+///
+/// The pair is order-independent; the returned stats list the tokens in
+/// canonical order. Pairs without swaps return zeroed stats.
+pub fn get_pair_stats(env: &Env, token_a: Option<Address>, token_b: Option<Address>) -> PairStats {
+    let (token_a, token_b) = if token_a <= token_b {
+        (token_a, token_b)
+    } else {
+        (token_b, token_a)
+    };
+    env.storage()
+        .persistent()
+        .get::<AmmDataKey, PairStats>(&AmmDataKey::PairStats(token_a.clone(), token_b.clone()))
+        .unwrap_or(PairStats {
+            token_a,
+            token_b,
+            swap_count: 0,
+            volume_a: 0,
+            volume_b: 0,
+            fees_a: 0,
+            fees_b: 0,
+            total_slippage_bps: 0,
+            avg_slippage_bps: 0,
+            last_swap: 0,
+        })
+}
+
 /// Get liquidity history
 pub fn get_liquidity_history(
     env: &Env,
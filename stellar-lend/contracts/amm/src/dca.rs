@@ -0,0 +1,380 @@
+//! # DCA Schedules
+//!
+//! A dollar-cost-average schedule swaps a fixed `amount_per_interval` of one
+//! token for another once per interval, `total_intervals` times. The whole
+//! budget is escrowed when the schedule is created; keepers trigger each
+//! tranche once it is due, and the output is paid to the owner.
+//!
+//! ## Tranches
+//! Each tranche goes through [`execute_swap`] at the default slippage,
+//! bounded by the pool's quote for the tranche (par when the pool cannot be
+//! quoted). The first tranche is due on creation; each later one is due
+//! `interval_secs` after the previous tranche ran, so missed intervals are
+//! not bunched together.
+//!
+//! ## Owner Controls
+//! The owner may pause and resume a schedule, or cancel it to refund the
+//! budget of the tranches that have not run.
+//!
+//! ## Storage Layout
+//! - `Schedule(id)` — the schedule, kept after it ends for history
+//! - `NextScheduleId` — id assigned to the next schedule
+//! - `UserSchedules(user)` — ids of a user's schedules, oldest first
+
+use crate::amm::{
+    calculate_min_output_with_slippage, execute_swap, expected_swap_output, get_amm_settings,
+    token_address, AmmError, SwapHop, SwapParams,
+};
+use soroban_sdk::{contractevent, contracttype, token, Address, Env, Vec};
+
+/// Storage keys for DCA schedules
+#[contracttype]
+#[derive(Clone)]
+pub enum DcaDataKey {
+    /// Schedule by id: DcaSchedule
+    Schedule(u64),
+    /// Id of the next schedule: u64
+    NextScheduleId,
+    /// Ids of a user's schedules: Vec<u64>
+    UserSchedules(Address),
+}
+
+/// DCA schedule status
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DcaStatus {
+    Active,
+    Paused,
+    Cancelled,
+    Completed,
+}
+
+/// A recurring swap schedule and its escrowed budget
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DcaSchedule {
+    /// Schedule id
+    pub id: u64,
+    /// Schedule owner
+    pub owner: Address,
+    /// Input token (None for native XLM)
+    pub token_in: Option<Address>,
+    /// Output token (None for native XLM)
+    pub token_out: Option<Address>,
+    /// Input swapped per tranche
+    pub amount_per_interval: i128,
+    /// Minimum time between tranches (seconds)
+    pub interval_secs: u64,
+    /// Number of tranches in the schedule
+    pub total_intervals: u32,
+    /// Tranches executed so far
+    pub executed_intervals: u32,
+    /// Earliest timestamp the next tranche can run at
+    pub next_execution: u64,
+    /// Current status
+    pub status: DcaStatus,
+    /// Output paid to the owner across tranches
+    pub total_out: i128,
+    /// When the schedule was created
+    pub created_at: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct DcaScheduledEvent {
+    pub schedule_id: u64,
+    pub owner: Address,
+    pub amount_per_interval: i128,
+    pub interval_secs: u64,
+    pub total_intervals: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct DcaTrancheExecutedEvent {
+    pub schedule_id: u64,
+    pub keeper: Address,
+    pub protocol: Address,
+    pub tranche: u32,
+    pub amount_in: i128,
+    pub amount_out: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct DcaStatusChangedEvent {
+    pub schedule_id: u64,
+    pub status: DcaStatus,
+    pub refunded: i128,
+}
+
+/// Create a DCA schedule, escrowing its whole budget from the owner
+///
+/// # Returns
+/// Returns the schedule id
+///
+/// # Errors
+/// * `AmmError::InvalidSwapParams` - Amount, interval or tranche count is not positive
+/// * `AmmError::InvalidTokenPair` - Input and output tokens are the same
+/// * `AmmError::NativeAssetNotSet` - Native XLM is used before its asset is set
+pub fn schedule_dca(
+    env: &Env,
+    user: Address,
+    token_in: Option<Address>,
+    token_out: Option<Address>,
+    amount_per_interval: i128,
+    interval_secs: u64,
+    total_intervals: u32,
+) -> Result<u64, AmmError> {
+    user.require_auth();
+    if amount_per_interval <= 0 || interval_secs == 0 || total_intervals == 0 {
+        return Err(AmmError::InvalidSwapParams);
+    }
+    if token_in == token_out {
+        return Err(AmmError::InvalidTokenPair);
+    }
+    let budget = amount_per_interval
+        .checked_mul(total_intervals as i128)
+        .ok_or(AmmError::Overflow)?;
+    token_address(env, &token_out)?;
+    token::Client::new(env, &token_address(env, &token_in)?).transfer(
+        &user,
+        env.current_contract_address(),
+        &budget,
+    );
+
+    let id = env
+        .storage()
+        .persistent()
+        .get::<DcaDataKey, u64>(&DcaDataKey::NextScheduleId)
+        .unwrap_or(1);
+    env.storage()
+        .persistent()
+        .set(&DcaDataKey::NextScheduleId, &(id + 1));
+
+    let now = env.ledger().timestamp();
+    let schedule = DcaSchedule {
+        id,
+        owner: user.clone(),
+        token_in,
+        token_out,
+        amount_per_interval,
+        interval_secs,
+        total_intervals,
+        executed_intervals: 0,
+        next_execution: now,
+        status: DcaStatus::Active,
+        total_out: 0,
+        created_at: now,
+    };
+    env.storage()
+        .persistent()
+        .set(&DcaDataKey::Schedule(id), &schedule);
+
+    let user_key = DcaDataKey::UserSchedules(user.clone());
+    let mut schedules = env
+        .storage()
+        .persistent()
+        .get::<DcaDataKey, Vec<u64>>(&user_key)
+        .unwrap_or_else(|| Vec::new(env));
+    schedules.push_back(id);
+    env.storage().persistent().set(&user_key, &schedules);
+
+    DcaScheduledEvent {
+        schedule_id: id,
+        owner: user,
+        amount_per_interval,
+        interval_secs,
+        total_intervals,
+    }
+    .publish(env);
+
+    Ok(id)
+}
+
+/// Run the next due tranche of a schedule through `protocol` (keeper)
+///
+/// # Returns
+/// Returns the output paid to the owner for the tranche
+///
+/// # Errors
+/// * `AmmError::DcaNotFound` - No schedule exists with the id
+/// * `AmmError::DcaNotActive` - The schedule is paused, cancelled or complete
+/// * `AmmError::TrancheNotDue` - The interval since the last tranche has not passed
+pub fn execute_dca_tranche(
+    env: &Env,
+    keeper: Address,
+    schedule_id: u64,
+    protocol: Address,
+) -> Result<i128, AmmError> {
+    keeper.require_auth();
+    let mut schedule = get_schedule(env, schedule_id)?;
+    if schedule.status != DcaStatus::Active {
+        return Err(AmmError::DcaNotActive);
+    }
+    let now = env.ledger().timestamp();
+    if now < schedule.next_execution {
+        return Err(AmmError::TrancheNotDue);
+    }
+
+    let hop = SwapHop {
+        protocol: protocol.clone(),
+        token_in: schedule.token_in.clone(),
+        token_out: schedule.token_out.clone(),
+    };
+    let expected = expected_swap_output(env, &hop, schedule.amount_per_interval)?;
+    let slippage = get_amm_settings(env)?.default_slippage;
+    let amount_out = execute_swap(
+        env,
+        schedule.owner.clone(),
+        SwapParams {
+            protocol: protocol.clone(),
+            token_in: schedule.token_in.clone(),
+            token_out: schedule.token_out.clone(),
+            amount_in: schedule.amount_per_interval,
+            min_amount_out: calculate_min_output_with_slippage(expected, slippage)?,
+            slippage_tolerance: slippage,
+            deadline: now,
+        },
+    )?;
+    token::Client::new(env, &token_address(env, &schedule.token_out)?).transfer(
+        &env.current_contract_address(),
+        &schedule.owner,
+        &amount_out,
+    );
+
+    schedule.executed_intervals += 1;
+    schedule.total_out = schedule
+        .total_out
+        .checked_add(amount_out)
+        .ok_or(AmmError::Overflow)?;
+    schedule.next_execution = now.saturating_add(schedule.interval_secs);
+    if schedule.executed_intervals == schedule.total_intervals {
+        schedule.status = DcaStatus::Completed;
+    }
+    save_schedule(env, &schedule);
+
+    DcaTrancheExecutedEvent {
+        schedule_id,
+        keeper,
+        protocol,
+        tranche: schedule.executed_intervals,
+        amount_in: schedule.amount_per_interval,
+        amount_out,
+    }
+    .publish(env);
+    if schedule.status == DcaStatus::Completed {
+        emit_status_changed(env, &schedule, 0);
+    }
+
+    Ok(amount_out)
+}
+
+/// Pause or resume a schedule (owner only)
+///
+/// # Errors
+/// * `AmmError::Unauthorized` - Caller is not the schedule's owner
+/// * `AmmError::DcaNotActive` - The schedule is cancelled or complete
+pub fn set_dca_paused(
+    env: &Env,
+    user: Address,
+    schedule_id: u64,
+    paused: bool,
+) -> Result<(), AmmError> {
+    user.require_auth();
+    let mut schedule = get_owned_schedule(env, &user, schedule_id)?;
+    schedule.status = match (schedule.status, paused) {
+        (DcaStatus::Active, true) | (DcaStatus::Paused, true) => DcaStatus::Paused,
+        (DcaStatus::Active, false) | (DcaStatus::Paused, false) => DcaStatus::Active,
+        _ => return Err(AmmError::DcaNotActive),
+    };
+    save_schedule(env, &schedule);
+    emit_status_changed(env, &schedule, 0);
+    Ok(())
+}
+
+/// Cancel a schedule and refund the budget of its remaining tranches (owner only)
+///
+/// # Returns
+/// Returns the refunded amount
+///
+/// # Errors
+/// * `AmmError::Unauthorized` - Caller is not the schedule's owner
+/// * `AmmError::DcaNotActive` - The schedule is already cancelled or complete
+pub fn cancel_dca(env: &Env, user: Address, schedule_id: u64) -> Result<i128, AmmError> {
+    user.require_auth();
+    let mut schedule = get_owned_schedule(env, &user, schedule_id)?;
+    if !matches!(schedule.status, DcaStatus::Active | DcaStatus::Paused) {
+        return Err(AmmError::DcaNotActive);
+    }
+
+    let remaining = (schedule.total_intervals - schedule.executed_intervals) as i128;
+    let refund = schedule
+        .amount_per_interval
+        .checked_mul(remaining)
+        .ok_or(AmmError::Overflow)?;
+    token::Client::new(env, &token_address(env, &schedule.token_in)?).transfer(
+        &env.current_contract_address(),
+        &user,
+        &refund,
+    );
+
+    schedule.status = DcaStatus::Cancelled;
+    save_schedule(env, &schedule);
+    emit_status_changed(env, &schedule, refund);
+    Ok(refund)
+}
+
+/// Get a schedule by id
+pub fn get_dca_schedule(env: &Env, schedule_id: u64) -> Option<DcaSchedule> {
+    env.storage()
+        .persistent()
+        .get(&DcaDataKey::Schedule(schedule_id))
+}
+
+/// Get a user's schedules in any status, newest first
+pub fn get_user_dca_schedules(env: &Env, user: Address) -> Vec<DcaSchedule> {
+    let ids = env
+        .storage()
+        .persistent()
+        .get::<DcaDataKey, Vec<u64>>(&DcaDataKey::UserSchedules(user))
+        .unwrap_or_else(|| Vec::new(env));
+    let mut schedules = Vec::new(env);
+    for id in ids.iter().rev() {
+        if let Some(schedule) = get_dca_schedule(env, id) {
+            schedules.push_back(schedule);
+        }
+    }
+    schedules
+}
+
+fn get_schedule(env: &Env, schedule_id: u64) -> Result<DcaSchedule, AmmError> {
+    get_dca_schedule(env, schedule_id).ok_or(AmmError::DcaNotFound)
+}
+
+fn get_owned_schedule(
+    env: &Env,
+    user: &Address,
+    schedule_id: u64,
+) -> Result<DcaSchedule, AmmError> {
+    let schedule = get_schedule(env, schedule_id)?;
+    if schedule.owner != *user {
+        return Err(AmmError::Unauthorized);
+    }
+    Ok(schedule)
+}
+
+fn save_schedule(env: &Env, schedule: &DcaSchedule) {
+    env.storage()
+        .persistent()
+        .set(&DcaDataKey::Schedule(schedule.id), schedule);
+}
+
+fn emit_status_changed(env: &Env, schedule: &DcaSchedule, refunded: i128) {
+    DcaStatusChangedEvent {
+        schedule_id: schedule.id,
+        status: schedule.status,
+        refunded,
+    }
+    .publish(env);
+}
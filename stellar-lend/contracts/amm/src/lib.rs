@@ -6,21 +6,37 @@
 //! ## Features
 //! - Multi-protocol AMM support with pluggable protocol configs
 //! - Slippage protection with configurable tolerances
+//! - Price impact guard rejecting swaps priced away from the lending oracle
+//! - Multi-hop routing with a slippage budget shared across hops
 //! - Auto-swap for collateral optimization during lending operations
 //! - Callback validation with nonce-based replay protection
 //! - Swap and liquidity operation history for analytics
+//! - Tracked LP positions with opt-in, keeper-run fee auto-compounding
+//! - Escrowed limit orders filled by keepers for a bounty
+//! - Escrowed DCA schedules swapped in keeper-triggered tranches
+//! - Read API (max slippage, enabled protocols, pool health) for pre-swap checks
+//! - Read-only swap quotes priced from pool reserves
+//! - Per-pair volume, fee and slippage statistics for routing decisions
 
 #![no_std]
 #![allow(clippy::too_many_arguments)]
 use soroban_sdk::{contract, contractimpl, Address, Env, Map};
 
 pub mod amm;
+pub mod dca;
+pub mod orders;
 pub use crate::amm::{
-    add_amm_protocol, add_liquidity, auto_swap_for_collateral, execute_swap,
-    initialize_amm_settings, remove_liquidity, update_amm_settings, validate_amm_callback,
-    AmmCallbackData, AmmError, AmmProtocolConfig, AmmSettings, LiquidityParams, SwapParams,
-    TokenPair,
+    add_amm_protocol, add_liquidity, auto_compound, auto_swap_for_collateral, execute_route_swap,
+    execute_swap, find_swap_route, get_pair_stats, initialize_amm_settings, remove_liquidity,
+    set_amm_manager, set_auto_compound, set_auto_swap_threshold, set_compound_keeper_fee,
+    set_price_oracle, set_protocol_enabled, set_slippage_limits, update_amm_settings,
+    validate_amm_callback, AmmCallbackData, AmmError, AmmPoolClient, AmmPoolInterface,
+    AmmProtocolConfig, AmmSettings, LiquidityParams, LpPosition, PairStats, PoolHealth,
+    PriceOracleClient, PriceOracleConfig, PriceOracleInterface, RouteSwapParams, SwapHop,
+    SwapParams, SwapQuote, SwapRoute, TokenPair,
 };
+pub use crate::dca::{DcaSchedule, DcaStatus};
+pub use crate::orders::{LimitOrder, OrderStatus};
 
 #[contract]
 pub struct AmmContract;
@@ -55,22 +71,119 @@ impl AmmContract {
         )
     }
 
-    /// Add AMM protocol (admin only)
+    /// Add AMM protocol (admin or manager)
     ///
     /// Registers a new AMM protocol for swap and liquidity operations.
     ///
     /// # Arguments
-    /// * `admin` - The admin address
+    /// * `caller` - The admin or a manager address
     /// * `protocol_config` - Configuration for the AMM protocol
     ///
     /// # Returns
     /// Returns Ok(()) on success
     pub fn add_amm_protocol(
         env: Env,
-        admin: Address,
+        caller: Address,
         protocol_config: AmmProtocolConfig,
     ) -> Result<(), AmmError> {
-        add_amm_protocol(&env, admin, protocol_config)
+        add_amm_protocol(&env, caller, protocol_config)
+    }
+
+    /// Enable or disable an AMM protocol (admin or manager)
+    ///
+    /// # Arguments
+    /// * `caller` - The admin or a manager address
+    /// * `protocol` - The registered protocol address
+    /// * `enabled` - Whether the protocol accepts swaps
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn set_protocol_enabled(
+        env: Env,
+        caller: Address,
+        protocol: Address,
+        enabled: bool,
+    ) -> Result<(), AmmError> {
+        set_protocol_enabled(&env, caller, protocol, enabled)
+    }
+
+    /// Tune slippage limits (admin or manager)
+    ///
+    /// # Arguments
+    /// * `caller` - The admin or a manager address
+    /// * `default_slippage` - Default slippage tolerance in basis points
+    /// * `max_slippage` - Maximum allowed slippage in basis points
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn set_slippage_limits(
+        env: Env,
+        caller: Address,
+        default_slippage: i128,
+        max_slippage: i128,
+    ) -> Result<(), AmmError> {
+        set_slippage_limits(&env, caller, default_slippage, max_slippage)
+    }
+
+    /// Set or clear the auto-swap threshold for a pair (admin or manager)
+    ///
+    /// # Arguments
+    /// * `caller` - The admin or a manager address
+    /// * `source` - Token swapped from (None for native XLM)
+    /// * `target` - Token swapped to (None for native XLM)
+    /// * `threshold` - Minimum auto-swap amount; None falls back to the global threshold
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn set_auto_swap_threshold(
+        env: Env,
+        caller: Address,
+        source: Option<Address>,
+        target: Option<Address>,
+        threshold: Option<i128>,
+    ) -> Result<(), AmmError> {
+        set_auto_swap_threshold(&env, caller, source, target, threshold)
+    }
+
+    /// Get the auto-swap threshold for a pair
+    ///
+    /// # Returns
+    /// Returns the pair override, or the global threshold if none is set
+    pub fn get_auto_swap_threshold(
+        env: Env,
+        source: Option<Address>,
+        target: Option<Address>,
+    ) -> Result<i128, AmmError> {
+        amm::get_auto_swap_threshold(&env, &source, &target)
+    }
+
+    /// Appoint or remove an AMM manager (admin only)
+    ///
+    /// Managers can add, enable and disable protocols and tune slippage
+    /// limits without holding the admin key.
+    ///
+    /// # Arguments
+    /// * `admin` - The admin address
+    /// * `manager` - The manager address
+    /// * `granted` - Whether to appoint (true) or remove (false) the manager
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    ///
+    /// # Events
+    /// Emits a `manager_updated` event
+    pub fn set_amm_manager(
+        env: Env,
+        admin: Address,
+        manager: Address,
+        granted: bool,
+    ) -> Result<(), AmmError> {
+        set_amm_manager(&env, admin, manager, granted)
+    }
+
+    /// Check if an address is an AMM manager
+    pub fn is_amm_manager(env: Env, account: Address) -> bool {
+        amm::is_amm_manager(&env, &account)
     }
 
     /// Update AMM settings (admin only)
@@ -91,6 +204,31 @@ impl AmmContract {
         update_amm_settings(&env, admin, settings)
     }
 
+    /// Set or clear the price impact guard's oracle (admin only)
+    ///
+    /// While `max_price_impact_bps` is set, swaps are rejected when their
+    /// pool price deviates from this oracle's prices beyond the limit.
+    ///
+    /// # Arguments
+    /// * `admin` - The admin address
+    /// * `oracle` - Oracle address and the asset it prices native XLM under,
+    ///   or None to clear
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn set_price_oracle(
+        env: Env,
+        admin: Address,
+        oracle: Option<PriceOracleConfig>,
+    ) -> Result<(), AmmError> {
+        set_price_oracle(&env, admin, oracle)
+    }
+
+    /// Get the price impact guard's oracle
+    pub fn get_price_oracle(env: Env) -> Option<PriceOracleConfig> {
+        amm::get_price_oracle(&env)
+    }
+
     /// Execute swap through AMM
     ///
     /// Performs token swaps using configured AMM protocols with slippage protection.
@@ -107,10 +245,56 @@ impl AmmContract {
     /// Emits the following events:
     /// - `swap_executed`: Swap transaction details
     /// - `amm_operation`: AMM operation tracking
+    /// - `swap_rejected`: Pool price deviated from the oracle beyond the limit
     pub fn execute_swap(env: Env, user: Address, params: SwapParams) -> Result<i128, AmmError> {
         execute_swap(&env, user, params)
     }
 
+    /// Execute a multi-hop swap
+    ///
+    /// Swaps through each hop in order, feeding each hop's output into the
+    /// next. The slippage tolerance is a budget for the whole route.
+    ///
+    /// # Arguments
+    /// * `user` - The user performing the swap
+    /// * `params` - Route parameters including hops, amounts and slippage tolerance
+    ///
+    /// # Returns
+    /// Returns the amount of the last hop's output token received
+    ///
+    /// # Events
+    /// Emits the following events:
+    /// - `swap_executed` and `amm_operation`: For every hop
+    /// - `route_executed`: Route summary with the compounded slippage
+    pub fn execute_route_swap(
+        env: Env,
+        user: Address,
+        params: RouteSwapParams,
+    ) -> Result<i128, AmmError> {
+        execute_route_swap(&env, user, params)
+    }
+
+    /// Find a swap route
+    ///
+    /// Tries direct pools, then routes through one intermediate token, and
+    /// returns the route quoting the most output.
+    ///
+    /// # Arguments
+    /// * `token_in` - Input token address (None for native XLM)
+    /// * `token_out` - Output token address (None for native XLM)
+    /// * `amount_in` - Amount to swap
+    ///
+    /// # Returns
+    /// Returns the route and its expected output
+    pub fn find_swap_route(
+        env: Env,
+        token_in: Option<Address>,
+        token_out: Option<Address>,
+        amount_in: i128,
+    ) -> Result<SwapRoute, AmmError> {
+        find_swap_route(&env, token_in, token_out, amount_in)
+    }
+
     /// Add liquidity to AMM pool
     ///
     /// Adds liquidity to AMM pools for earning fees and supporting protocol operations.
@@ -180,6 +364,265 @@ impl AmmContract {
         )
     }
 
+    /// Get a user's tracked LP positions
+    ///
+    /// # Arguments
+    /// * `user` - The position owner
+    ///
+    /// # Returns
+    /// Returns one position per protocol and pair
+    pub fn get_lp_positions(env: Env, user: Address) -> soroban_sdk::Vec<amm::LpPosition> {
+        amm::get_lp_positions(&env, &user)
+    }
+
+    /// Opt in to or out of keeper auto-compounding
+    ///
+    /// # Arguments
+    /// * `user` - The position owner
+    /// * `enabled` - Whether keepers may compound the user's positions
+    pub fn set_auto_compound(env: Env, user: Address, enabled: bool) -> Result<(), AmmError> {
+        set_auto_compound(&env, user, enabled)
+    }
+
+    /// Check whether a user has opted in to auto-compounding
+    pub fn is_auto_compound_enabled(env: Env, user: Address) -> bool {
+        amm::is_auto_compound_enabled(&env, &user)
+    }
+
+    /// Set the keeper share of auto-compounded LP tokens (admin or manager)
+    ///
+    /// # Arguments
+    /// * `caller` - The admin or a manager
+    /// * `fee_bps` - Keeper share in basis points, at most 10%
+    pub fn set_compound_keeper_fee(
+        env: Env,
+        caller: Address,
+        fee_bps: i128,
+    ) -> Result<(), AmmError> {
+        set_compound_keeper_fee(&env, caller, fee_bps)
+    }
+
+    /// Get the keeper share of auto-compounded LP tokens (in basis points)
+    pub fn get_compound_keeper_fee(env: Env) -> i128 {
+        amm::get_compound_keeper_fee(&env)
+    }
+
+    /// Compound a user's pool fees back into liquidity
+    ///
+    /// Claims the fees and rewards of the user's position and re-adds them as
+    /// liquidity. Callable by any keeper once the user has opted in; the
+    /// keeper is paid a share of the new LP tokens.
+    ///
+    /// # Arguments
+    /// * `keeper` - The keeper triggering the compound
+    /// * `user` - The position owner
+    /// * `protocol` - AMM protocol address
+    /// * `token_a` - First token of the pair (None for native XLM)
+    /// * `token_b` - Second token of the pair (None for native XLM)
+    ///
+    /// # Returns
+    /// Returns the LP tokens added to the user's position
+    ///
+    /// # Events
+    /// Emits the following events:
+    /// - `liquidity_added` and `amm_operation`: The re-added liquidity
+    /// - `lp_compounded`: Claimed fees and the user's and keeper's LP shares
+    pub fn auto_compound(
+        env: Env,
+        keeper: Address,
+        user: Address,
+        protocol: Address,
+        token_a: Option<Address>,
+        token_b: Option<Address>,
+    ) -> Result<i128, AmmError> {
+        auto_compound(&env, keeper, user, protocol, token_a, token_b)
+    }
+
+    /// Set the asset contract of native XLM (admin only)
+    ///
+    /// Escrowed limit orders transfer native XLM through this contract.
+    pub fn set_native_asset(env: Env, admin: Address, asset: Address) -> Result<(), AmmError> {
+        amm::set_native_asset(&env, admin, asset)
+    }
+
+    /// Get the asset contract of native XLM
+    pub fn get_native_asset(env: Env) -> Option<Address> {
+        amm::get_native_asset(&env)
+    }
+
+    /// Place a limit order
+    ///
+    /// Escrows `amount_in` from the owner until the order is filled, cancelled
+    /// or expired.
+    ///
+    /// # Arguments
+    /// * `owner` - The order owner
+    /// * `token_in` - Input token address (None for native XLM)
+    /// * `token_out` - Output token address (None for native XLM)
+    /// * `amount_in` - Amount to swap
+    /// * `min_price` - Minimum `token_out` per `token_in`, scaled by 10^7
+    /// * `expiry` - Last timestamp the order can be filled at
+    ///
+    /// # Returns
+    /// Returns the order id
+    pub fn place_limit_order(
+        env: Env,
+        owner: Address,
+        token_in: Option<Address>,
+        token_out: Option<Address>,
+        amount_in: i128,
+        min_price: i128,
+        expiry: u64,
+    ) -> Result<u64, AmmError> {
+        orders::place_limit_order(
+            &env, owner, token_in, token_out, amount_in, min_price, expiry,
+        )
+    }
+
+    /// Fill a limit order (keeper)
+    ///
+    /// Swaps the order's escrow through `protocol` when its quote meets the
+    /// order's price. The keeper earns the configured bounty out of the output.
+    ///
+    /// # Arguments
+    /// * `keeper` - The keeper filling the order
+    /// * `order_id` - The order to fill
+    /// * `protocol` - AMM protocol to swap through
+    ///
+    /// # Returns
+    /// Returns the output paid to the order owner
+    pub fn execute_limit_order(
+        env: Env,
+        keeper: Address,
+        order_id: u64,
+        protocol: Address,
+    ) -> Result<i128, AmmError> {
+        orders::execute_limit_order(&env, keeper, order_id, protocol)
+    }
+
+    /// Cancel an open limit order and refund its escrow (owner only)
+    pub fn cancel_limit_order(env: Env, owner: Address, order_id: u64) -> Result<(), AmmError> {
+        orders::cancel_limit_order(&env, owner, order_id)
+    }
+
+    /// Close a limit order past its expiry and refund its escrow to the owner
+    pub fn expire_limit_order(env: Env, order_id: u64) -> Result<(), AmmError> {
+        orders::expire_limit_order(&env, order_id)
+    }
+
+    /// Get a limit order by id
+    pub fn get_limit_order(env: Env, order_id: u64) -> Option<LimitOrder> {
+        orders::get_limit_order(&env, order_id)
+    }
+
+    /// Get up to `limit` open limit orders, oldest first
+    pub fn get_open_orders(env: Env, limit: u32) -> soroban_sdk::Vec<LimitOrder> {
+        orders::get_open_orders(&env, limit)
+    }
+
+    /// Get up to `limit` of a user's limit orders in any status, newest first
+    pub fn get_user_orders(env: Env, user: Address, limit: u32) -> soroban_sdk::Vec<LimitOrder> {
+        orders::get_user_orders(&env, user, limit)
+    }
+
+    /// Set the keeper bounty on limit order fills (admin or manager)
+    ///
+    /// # Arguments
+    /// * `caller` - The admin or a manager
+    /// * `bounty_bps` - Keeper share of a fill's output in basis points, at most 5%
+    pub fn set_keeper_bounty(env: Env, caller: Address, bounty_bps: i128) -> Result<(), AmmError> {
+        orders::set_keeper_bounty(&env, caller, bounty_bps)
+    }
+
+    /// Get the keeper bounty on limit order fills (in basis points)
+    pub fn get_keeper_bounty(env: Env) -> i128 {
+        orders::get_keeper_bounty(&env)
+    }
+
+    /// Schedule a DCA
+    ///
+    /// Escrows `amount_per_interval * total_intervals` from the user and
+    /// swaps `amount_per_interval` once per interval as keepers trigger it.
+    ///
+    /// # Arguments
+    /// * `user` - The schedule owner
+    /// * `token_in` - Input token address (None for native XLM)
+    /// * `token_out` - Output token address (None for native XLM)
+    /// * `amount_per_interval` - Amount swapped per tranche
+    /// * `interval_secs` - Minimum time between tranches
+    /// * `total_intervals` - Number of tranches
+    ///
+    /// # Returns
+    /// Returns the schedule id
+    pub fn schedule_dca(
+        env: Env,
+        user: Address,
+        token_in: Option<Address>,
+        token_out: Option<Address>,
+        amount_per_interval: i128,
+        interval_secs: u64,
+        total_intervals: u32,
+    ) -> Result<u64, AmmError> {
+        dca::schedule_dca(
+            &env,
+            user,
+            token_in,
+            token_out,
+            amount_per_interval,
+            interval_secs,
+            total_intervals,
+        )
+    }
+
+    /// Run a schedule's next due tranche (keeper)
+    ///
+    /// # Arguments
+    /// * `keeper` - The keeper triggering the tranche
+    /// * `schedule_id` - The schedule
+    /// * `protocol` - AMM protocol to swap through
+    ///
+    /// # Returns
+    /// Returns the output paid to the schedule owner
+    ///
+    /// # Events
+    /// Emits the swap events and `dca_tranche_executed`
+    pub fn execute_dca_tranche(
+        env: Env,
+        keeper: Address,
+        schedule_id: u64,
+        protocol: Address,
+    ) -> Result<i128, AmmError> {
+        dca::execute_dca_tranche(&env, keeper, schedule_id, protocol)
+    }
+
+    /// Pause or resume a DCA schedule (owner only)
+    pub fn set_dca_paused(
+        env: Env,
+        user: Address,
+        schedule_id: u64,
+        paused: bool,
+    ) -> Result<(), AmmError> {
+        dca::set_dca_paused(&env, user, schedule_id, paused)
+    }
+
+    /// Cancel a DCA schedule and refund its unspent budget (owner only)
+    ///
+    /// # Returns
+    /// Returns the refunded amount
+    pub fn cancel_dca(env: Env, user: Address, schedule_id: u64) -> Result<i128, AmmError> {
+        dca::cancel_dca(&env, user, schedule_id)
+    }
+
+    /// Get a DCA schedule by id
+    pub fn get_dca_schedule(env: Env, schedule_id: u64) -> Option<DcaSchedule> {
+        dca::get_dca_schedule(&env, schedule_id)
+    }
+
+    /// Get a user's DCA schedules in any status, newest first
+    pub fn get_user_dca_schedules(env: Env, user: Address) -> soroban_sdk::Vec<DcaSchedule> {
+        dca::get_user_dca_schedules(&env, user)
+    }
+
     /// Validate AMM callback
     ///
     /// Validates callbacks from AMM protocols to ensure they are legitimate
@@ -246,6 +689,90 @@ impl AmmContract {
         amm::get_amm_protocols(&env).ok()
     }
 
+    /// Get maximum slippage
+    ///
+    /// Returns the maximum slippage tolerance so callers can bound their
+    /// swaps before submitting them.
+    ///
+    /// # Returns
+    /// Returns the maximum slippage in basis points
+    pub fn get_max_slippage(env: Env) -> Result<i128, AmmError> {
+        amm::get_max_slippage(&env)
+    }
+
+    /// Get enabled AMM protocols
+    ///
+    /// Returns the addresses of all protocols currently accepting swaps.
+    ///
+    /// # Returns
+    /// Returns a vector of enabled protocol addresses
+    pub fn get_supported_protocols(env: Env) -> soroban_sdk::Vec<Address> {
+        amm::get_supported_protocols(&env)
+    }
+
+    /// Get pool health
+    ///
+    /// Returns whether a protocol's pool for a token pair can currently
+    /// execute swaps, along with its fee tier and swap limits.
+    ///
+    /// # Arguments
+    /// * `protocol` - AMM protocol address
+    /// * `token_a` - First token address (None for native XLM)
+    /// * `token_b` - Second token address (None for native XLM)
+    ///
+    /// # Returns
+    /// Returns the pool health snapshot
+    pub fn get_pool_health(
+        env: Env,
+        protocol: Address,
+        token_a: Option<Address>,
+        token_b: Option<Address>,
+    ) -> Result<PoolHealth, AmmError> {
+        amm::get_pool_health(&env, protocol, token_a, token_b)
+    }
+
+    /// Get a swap quote
+    ///
+    /// Reads the pool's reserves and returns the expected output, fee and
+    /// price impact of a swap without executing it.
+    ///
+    /// # Arguments
+    /// * `protocol` - AMM protocol address
+    /// * `token_in` - Input token address (None for native XLM)
+    /// * `token_out` - Output token address (None for native XLM)
+    /// * `amount_in` - Amount to swap
+    ///
+    /// # Returns
+    /// Returns the quote
+    pub fn get_swap_quote(
+        env: Env,
+        protocol: Address,
+        token_in: Option<Address>,
+        token_out: Option<Address>,
+        amount_in: i128,
+    ) -> Result<SwapQuote, AmmError> {
+        amm::get_swap_quote(&env, protocol, token_in, token_out, amount_in)
+    }
+
+    /// Quote an auto-swap for collateral
+    ///
+    /// Returns the best quote across enabled protocols for swapping native
+    /// XLM into the target token, as `auto_swap_for_collateral` would route it.
+    ///
+    /// # Arguments
+    /// * `target_token` - The token to swap to (None for native XLM)
+    /// * `amount` - Amount to swap
+    ///
+    /// # Returns
+    /// Returns the quote
+    pub fn quote_auto_swap(
+        env: Env,
+        target_token: Option<Address>,
+        amount: i128,
+    ) -> Result<SwapQuote, AmmError> {
+        amm::quote_auto_swap(&env, target_token, amount)
+    }
+
     /// Get swap history
     ///
     /// Returns recent swap operations for analytics.
@@ -264,6 +791,25 @@ impl AmmContract {
         amm::get_swap_history(&env, user, limit).ok()
     }
 
+    /// Get pair statistics
+    ///
+    /// Returns cumulative volume, fees and average slippage for a token pair,
+    /// regardless of the order the tokens are given in.
+    ///
+    /// # Arguments
+    /// * `token_a` - First token address (None for native XLM)
+    /// * `token_b` - Second token address (None for native XLM)
+    ///
+    /// # Returns
+    /// Returns the pair statistics (zeroed if the pair has no swaps)
+    pub fn get_pair_stats(
+        env: Env,
+        token_a: Option<Address>,
+        token_b: Option<Address>,
+    ) -> PairStats {
+        amm::get_pair_stats(&env, token_a, token_b)
+    }
+
     /// Get liquidity history
     ///
     /// Returns recent liquidity operations for analytics.
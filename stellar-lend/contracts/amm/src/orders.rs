@@ -0,0 +1,435 @@
+//! # Limit Orders
+//!
+//! Users place limit orders that swap `amount_in` of one token for another
+//! once the AMM quotes at least `min_price`. The input is escrowed in the
+//! contract when the order is placed, so a keeper can fill it later without
+//! the owner's signature.
+//!
+//! ## Lifecycle
+//! - **Open** — placed and escrowed; listed by [`get_open_orders`]
+//! - **Filled** — a keeper called [`execute_limit_order`] while the quote
+//!   met the price; the owner receives the output less the keeper bounty,
+//!   paid from the output the pool delivers to the contract
+//! - **Cancelled** — the owner withdrew the order and its escrow
+//! - **Expired** — anyone may close an order past its expiry, refunding the
+//!   escrow to the owner
+//!
+//! ## Prices
+//! `min_price` is the amount of `token_out` per unit of `token_in`, scaled by
+//! [`ORDER_PRICE_SCALE`]. The owner's minimum output is
+//! `amount_in * min_price / ORDER_PRICE_SCALE` (rounded up) after the keeper
+//! bounty, so fills never pay the owner below the limit.
+//!
+//! ## Storage Layout
+//! - `Order(id)` — the order, kept after it closes for history
+//! - `NextOrderId` — id assigned to the next order
+//! - `OpenOrders` — ids of open orders
+//! - `UserOrders(user)` — ids of a user's orders, oldest first
+//! - `KeeperBounty` — keeper share of a fill's output (in basis points)
+
+use crate::amm::{
+    execute_swap, get_amm_settings, get_swap_quote, require_manager_or_admin, token_address,
+    AmmError, SwapParams,
+};
+use soroban_sdk::{contractevent, contracttype, token, Address, Env, Vec};
+
+/// Price scale for `min_price` (7 decimals)
+pub const ORDER_PRICE_SCALE: i128 = 10_000_000;
+
+/// Maximum keeper bounty on a fill (in basis points)
+pub const MAX_KEEPER_BOUNTY_BPS: i128 = 500;
+
+/// Storage keys for limit orders
+#[contracttype]
+#[derive(Clone)]
+pub enum OrderDataKey {
+    /// Order by id: LimitOrder
+    Order(u64),
+    /// Id of the next order: u64
+    NextOrderId,
+    /// Ids of open orders: Vec<u64>
+    OpenOrders,
+    /// Ids of a user's orders: Vec<u64>
+    UserOrders(Address),
+    /// Keeper share of a fill's output (in basis points): i128
+    KeeperBounty,
+}
+
+/// Limit order status
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OrderStatus {
+    Open,
+    Filled,
+    Cancelled,
+    Expired,
+}
+
+/// A limit order and its escrowed input
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct LimitOrder {
+    /// Order id
+    pub id: u64,
+    /// Order owner
+    pub owner: Address,
+    /// Input token (None for native XLM)
+    pub token_in: Option<Address>,
+    /// Output token (None for native XLM)
+    pub token_out: Option<Address>,
+    /// Escrowed input amount
+    pub amount_in: i128,
+    /// Minimum `token_out` per `token_in`, scaled by [`ORDER_PRICE_SCALE`]
+    pub min_price: i128,
+    /// Last timestamp the order can be filled at
+    pub expiry: u64,
+    /// Current status
+    pub status: OrderStatus,
+    /// When the order was placed
+    pub created_at: u64,
+    /// When the order was filled, cancelled or expired (0 while open)
+    pub closed_at: u64,
+    /// Output paid to the owner (0 unless filled)
+    pub amount_out: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct OrderPlacedEvent {
+    pub order_id: u64,
+    pub owner: Address,
+    pub token_in: Option<Address>,
+    pub token_out: Option<Address>,
+    pub amount_in: i128,
+    pub min_price: i128,
+    pub expiry: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct OrderExecutedEvent {
+    pub order_id: u64,
+    pub keeper: Address,
+    pub protocol: Address,
+    pub amount_out: i128,
+    pub bounty: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct OrderClosedEvent {
+    pub order_id: u64,
+    pub status: OrderStatus,
+    pub refunded: i128,
+}
+
+/// Place a limit order, escrowing `amount_in` from the owner
+///
+/// # Errors
+/// * `AmmError::InvalidSwapParams` - Amount or price is not positive, or the
+///   expiry has passed
+/// * `AmmError::InvalidTokenPair` - Input and output tokens are the same
+/// * `AmmError::NativeAssetNotSet` - Native XLM is used before its asset is set
+pub fn place_limit_order(
+    env: &Env,
+    owner: Address,
+    token_in: Option<Address>,
+    token_out: Option<Address>,
+    amount_in: i128,
+    min_price: i128,
+    expiry: u64,
+) -> Result<u64, AmmError> {
+    owner.require_auth();
+    if amount_in <= 0 || min_price <= 0 || expiry <= env.ledger().timestamp() {
+        return Err(AmmError::InvalidSwapParams);
+    }
+    if token_in == token_out {
+        return Err(AmmError::InvalidTokenPair);
+    }
+    token_address(env, &token_out)?;
+    token::Client::new(env, &token_address(env, &token_in)?).transfer(
+        &owner,
+        env.current_contract_address(),
+        &amount_in,
+    );
+
+    let id = env
+        .storage()
+        .persistent()
+        .get::<OrderDataKey, u64>(&OrderDataKey::NextOrderId)
+        .unwrap_or(1);
+    env.storage()
+        .persistent()
+        .set(&OrderDataKey::NextOrderId, &(id + 1));
+
+    let order = LimitOrder {
+        id,
+        owner: owner.clone(),
+        token_in: token_in.clone(),
+        token_out: token_out.clone(),
+        amount_in,
+        min_price,
+        expiry,
+        status: OrderStatus::Open,
+        created_at: env.ledger().timestamp(),
+        closed_at: 0,
+        amount_out: 0,
+    };
+    env.storage()
+        .persistent()
+        .set(&OrderDataKey::Order(id), &order);
+
+    let mut open = get_open_order_ids(env);
+    open.push_back(id);
+    env.storage()
+        .persistent()
+        .set(&OrderDataKey::OpenOrders, &open);
+    let user_key = OrderDataKey::UserOrders(owner.clone());
+    let mut user_orders = env
+        .storage()
+        .persistent()
+        .get::<OrderDataKey, Vec<u64>>(&user_key)
+        .unwrap_or_else(|| Vec::new(env));
+    user_orders.push_back(id);
+    env.storage().persistent().set(&user_key, &user_orders);
+
+    OrderPlacedEvent {
+        order_id: id,
+        owner,
+        token_in,
+        token_out,
+        amount_in,
+        min_price,
+        expiry,
+    }
+    .publish(env);
+
+    Ok(id)
+}
+
+/// Fill an open order through `protocol` when its quote meets the price
+///
+/// Swaps the escrow through [`execute_swap`], pays the keeper its bounty out
+/// of the output and the owner the rest.
+///
+/// # Returns
+/// Returns the output paid to the owner
+///
+/// # Errors
+/// * `AmmError::OrderNotFound` / `AmmError::OrderNotOpen` - No open order with the id
+/// * `AmmError::OrderExpired` - The order is past its expiry
+/// * `AmmError::PriceNotMet` - The quote pays the owner less than the limit
+pub fn execute_limit_order(
+    env: &Env,
+    keeper: Address,
+    order_id: u64,
+    protocol: Address,
+) -> Result<i128, AmmError> {
+    keeper.require_auth();
+    let mut order = get_open_order(env, order_id)?;
+    let now = env.ledger().timestamp();
+    if now > order.expiry {
+        return Err(AmmError::OrderExpired);
+    }
+
+    // Gross the owner's minimum up by the bounty so the limit holds after it
+    let bounty_bps = get_keeper_bounty(env);
+    let owner_min = ceil_div(
+        order
+            .amount_in
+            .checked_mul(order.min_price)
+            .ok_or(AmmError::Overflow)?,
+        ORDER_PRICE_SCALE,
+    );
+    let min_amount_out = ceil_div(
+        owner_min.checked_mul(10_000).ok_or(AmmError::Overflow)?,
+        10_000 - bounty_bps,
+    );
+    let quote = get_swap_quote(
+        env,
+        protocol.clone(),
+        order.token_in.clone(),
+        order.token_out.clone(),
+        order.amount_in,
+    )?;
+    if quote.amount_out < min_amount_out {
+        return Err(AmmError::PriceNotMet);
+    }
+
+    let amount_out = execute_swap(
+        env,
+        order.owner.clone(),
+        SwapParams {
+            protocol: protocol.clone(),
+            token_in: order.token_in.clone(),
+            token_out: order.token_out.clone(),
+            amount_in: order.amount_in,
+            min_amount_out,
+            slippage_tolerance: get_amm_settings(env)?.default_slippage,
+            deadline: now,
+        },
+    )?;
+    let bounty = amount_out
+        .checked_mul(bounty_bps)
+        .map(|v| v / 10_000)
+        .ok_or(AmmError::Overflow)?;
+    let owner_out = amount_out - bounty;
+
+    let token_out = token::Client::new(env, &token_address(env, &order.token_out)?);
+    if bounty > 0 {
+        token_out.transfer(&env.current_contract_address(), &keeper, &bounty);
+    }
+    token_out.transfer(&env.current_contract_address(), &order.owner, &owner_out);
+
+    order.amount_out = owner_out;
+    close_order(env, &mut order, OrderStatus::Filled);
+    OrderExecutedEvent {
+        order_id,
+        keeper,
+        protocol,
+        amount_out: owner_out,
+        bounty,
+    }
+    .publish(env);
+
+    Ok(owner_out)
+}
+
+/// Cancel an open order and refund its escrow (owner only)
+///
+/// # Errors
+/// * `AmmError::Unauthorized` - Caller is not the order's owner
+pub fn cancel_limit_order(env: &Env, owner: Address, order_id: u64) -> Result<(), AmmError> {
+    owner.require_auth();
+    let mut order = get_open_order(env, order_id)?;
+    if order.owner != owner {
+        return Err(AmmError::Unauthorized);
+    }
+    refund_and_close(env, &mut order, OrderStatus::Cancelled)
+}
+
+/// Close an order past its expiry and refund its escrow to the owner
+///
+/// Callable by anyone.
+///
+/// # Errors
+/// * `AmmError::OrderNotOpen` - The order is still fillable or already closed
+pub fn expire_limit_order(env: &Env, order_id: u64) -> Result<(), AmmError> {
+    let mut order = get_open_order(env, order_id)?;
+    if env.ledger().timestamp() <= order.expiry {
+        return Err(AmmError::OrderNotOpen);
+    }
+    refund_and_close(env, &mut order, OrderStatus::Expired)
+}
+
+/// Get an order by id
+pub fn get_limit_order(env: &Env, order_id: u64) -> Option<LimitOrder> {
+    env.storage()
+        .persistent()
+        .get(&OrderDataKey::Order(order_id))
+}
+
+/// Get up to `limit` open orders, oldest first
+pub fn get_open_orders(env: &Env, limit: u32) -> Vec<LimitOrder> {
+    let mut orders = Vec::new(env);
+    for id in get_open_order_ids(env).iter().take(limit as usize) {
+        if let Some(order) = get_limit_order(env, id) {
+            orders.push_back(order);
+        }
+    }
+    orders
+}
+
+/// Get up to `limit` of a user's orders in any status, newest first
+pub fn get_user_orders(env: &Env, user: Address, limit: u32) -> Vec<LimitOrder> {
+    let ids = env
+        .storage()
+        .persistent()
+        .get::<OrderDataKey, Vec<u64>>(&OrderDataKey::UserOrders(user))
+        .unwrap_or_else(|| Vec::new(env));
+    let mut orders = Vec::new(env);
+    for id in ids.iter().rev().take(limit as usize) {
+        if let Some(order) = get_limit_order(env, id) {
+            orders.push_back(order);
+        }
+    }
+    orders
+}
+
+/// Set the keeper bounty on fills (admin or manager)
+///
+/// # Errors
+/// * `AmmError::InvalidSwapParams` - Bounty outside 0..=[`MAX_KEEPER_BOUNTY_BPS`]
+pub fn set_keeper_bounty(env: &Env, caller: Address, bounty_bps: i128) -> Result<(), AmmError> {
+    require_manager_or_admin(env, &caller)?;
+    if !(0..=MAX_KEEPER_BOUNTY_BPS).contains(&bounty_bps) {
+        return Err(AmmError::InvalidSwapParams);
+    }
+    env.storage()
+        .persistent()
+        .set(&OrderDataKey::KeeperBounty, &bounty_bps);
+    Ok(())
+}
+
+/// Get the keeper bounty on fills (in basis points)
+pub fn get_keeper_bounty(env: &Env) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&OrderDataKey::KeeperBounty)
+        .unwrap_or(0)
+}
+
+fn get_open_order(env: &Env, order_id: u64) -> Result<LimitOrder, AmmError> {
+    let order = get_limit_order(env, order_id).ok_or(AmmError::OrderNotFound)?;
+    if order.status != OrderStatus::Open {
+        return Err(AmmError::OrderNotOpen);
+    }
+    Ok(order)
+}
+
+fn get_open_order_ids(env: &Env) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&OrderDataKey::OpenOrders)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn refund_and_close(
+    env: &Env,
+    order: &mut LimitOrder,
+    status: OrderStatus,
+) -> Result<(), AmmError> {
+    token::Client::new(env, &token_address(env, &order.token_in)?).transfer(
+        &env.current_contract_address(),
+        &order.owner,
+        &order.amount_in,
+    );
+    close_order(env, order, status);
+    OrderClosedEvent {
+        order_id: order.id,
+        status,
+        refunded: order.amount_in,
+    }
+    .publish(env);
+    Ok(())
+}
+
+fn close_order(env: &Env, order: &mut LimitOrder, status: OrderStatus) {
+    order.status = status;
+    order.closed_at = env.ledger().timestamp();
+    env.storage()
+        .persistent()
+        .set(&OrderDataKey::Order(order.id), order);
+
+    let mut open = get_open_order_ids(env);
+    if let Some(index) = open.first_index_of(order.id) {
+        open.remove(index);
+    }
+    env.storage()
+        .persistent()
+        .set(&OrderDataKey::OpenOrders, &open);
+}
+
+fn ceil_div(a: i128, b: i128) -> i128 {
+    (a + b - 1) / b
+}
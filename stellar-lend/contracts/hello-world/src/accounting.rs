@@ -0,0 +1,206 @@
+//! # Accounting Currency
+//!
+//! Expresses every valuation in one configurable numeraire, so a deployment
+//! reports consistently in USD or XLM instead of mixing oracle quote units
+//! and raw native amounts.
+//!
+//! Oracle prices are quoted in the oracle's quote currency (typically USD)
+//! with per-feed decimals. A price is normalized by:
+//! 1. Rescaling it from its feed decimals (the SEP-40 source's
+//!    `price_decimals`, else the pushed feed's `decimals`, else
+//!    `ACCOUNTING_DECIMALS`) to `ACCOUNTING_DECIMALS`.
+//! 2. If the numeraire is an asset (e.g. native XLM), dividing by that
+//!    asset's rescaled price, so one unit of the numeraire is worth exactly
+//!    `ACCOUNTING_SCALE`.
+//!
+//! Native XLM (`None`) is priced through its configured asset contract.
+
+use soroban_sdk::{contracterror, contracttype, Address, Env, Symbol};
+
+use crate::deposit::token_address;
+use crate::events::{emit_admin_action, AdminActionEvent};
+use crate::oracle::{get_price, get_sep40_source, OracleDataKey, OracleError, PriceFeed};
+
+/// Decimals of normalized prices and accounting values
+pub const ACCOUNTING_DECIMALS: u32 = 8;
+
+/// One unit of the numeraire at `ACCOUNTING_DECIMALS`
+pub const ACCOUNTING_SCALE: i128 = 100_000_000;
+
+/// Errors that can occur in accounting currency operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AccountingError {
+    /// Caller is not the admin
+    Unauthorized = 1,
+    /// No usable price for the asset or the numeraire
+    PriceUnavailable = 2,
+    /// Native XLM is used but its asset contract is not configured
+    NativeAssetNotSet = 3,
+    /// Arithmetic overflow occurred
+    Overflow = 4,
+}
+
+/// Numeraire all valuations are expressed in
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccountingCurrency {
+    /// Display code of the numeraire (e.g. "USD", "XLM")
+    pub code: Symbol,
+    /// Asset whose price is the numeraire; None for the oracle quote currency
+    pub numeraire_asset: Option<Address>,
+}
+
+/// Storage keys for accounting data
+#[contracttype]
+#[derive(Clone)]
+pub enum AccountingDataKey {
+    /// Configured accounting currency (absent = oracle quote currency, USD)
+    /// Value type: AccountingCurrency
+    Currency,
+}
+
+/// Get the accounting currency
+pub fn get_accounting_currency(env: &Env) -> AccountingCurrency {
+    env.storage()
+        .persistent()
+        .get(&AccountingDataKey::Currency)
+        .unwrap_or(AccountingCurrency {
+            code: Symbol::new(env, "USD"),
+            numeraire_asset: None,
+        })
+}
+
+/// Set the accounting currency (admin only)
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not admin
+/// * `PriceUnavailable` - The numeraire asset has no current price
+pub fn set_accounting_currency(
+    env: &Env,
+    caller: Address,
+    currency: AccountingCurrency,
+) -> Result<(), AccountingError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| AccountingError::Unauthorized)?;
+    if let Some(numeraire) = &currency.numeraire_asset {
+        quote_price(env, numeraire, get_price(env, numeraire))?;
+    }
+    env.storage()
+        .persistent()
+        .set(&AccountingDataKey::Currency, &currency);
+
+    emit_admin_action(
+        env,
+        AdminActionEvent {
+            actor: caller,
+            action: Symbol::new(env, "set_accounting_currency"),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Price of one whole unit of `asset` in the accounting currency
+///
+/// `raw_price` supplies the oracle price in the feed's own decimals (spot,
+/// TWAP, ...); the numeraire is always converted at its spot price.
+pub fn price_in_accounting(
+    env: &Env,
+    asset: &Option<Address>,
+    raw_price: impl FnOnce(&Address) -> Result<i128, OracleError>,
+) -> Result<i128, AccountingError> {
+    let asset = token_address(env, asset).ok_or(AccountingError::NativeAssetNotSet)?;
+    let numeraire = get_accounting_currency(env).numeraire_asset;
+    if numeraire.as_ref() == Some(&asset) {
+        return Ok(ACCOUNTING_SCALE);
+    }
+
+    let price = quote_price(env, &asset, raw_price(&asset))?;
+    match numeraire {
+        None => Ok(price),
+        Some(numeraire) => {
+            let numeraire_price = quote_price(env, &numeraire, get_price(env, &numeraire))?;
+            mul_div(price, ACCOUNTING_SCALE, numeraire_price)
+        }
+    }
+}
+
+/// Spot price of one whole unit of `asset` in the accounting currency
+pub fn get_normalized_price(env: &Env, asset: &Option<Address>) -> Result<i128, AccountingError> {
+    price_in_accounting(env, asset, |a| get_price(env, a))
+}
+
+/// Value `amount` of `asset` in the accounting currency
+pub fn to_accounting_value(
+    env: &Env,
+    asset: &Option<Address>,
+    amount: i128,
+) -> Result<i128, AccountingError> {
+    mul_div(amount, get_normalized_price(env, asset)?, ACCOUNTING_SCALE)
+}
+
+/// Amount of `asset` worth `value` in the accounting currency
+pub fn from_accounting_value(
+    env: &Env,
+    asset: &Option<Address>,
+    value: i128,
+) -> Result<i128, AccountingError> {
+    mul_div(value, ACCOUNTING_SCALE, get_normalized_price(env, asset)?)
+}
+
+/// Convert `amount` of `from` into the equally valued amount of `to`
+pub fn convert_amount(
+    env: &Env,
+    from: &Option<Address>,
+    to: &Option<Address>,
+    amount: i128,
+) -> Result<i128, AccountingError> {
+    if from == to {
+        return Ok(amount);
+    }
+    from_accounting_value(env, to, to_accounting_value(env, from, amount)?)
+}
+
+/// Rescale an oracle price to `ACCOUNTING_DECIMALS`
+fn quote_price(
+    env: &Env,
+    asset: &Address,
+    raw_price: Result<i128, OracleError>,
+) -> Result<i128, AccountingError> {
+    let raw_price = raw_price.map_err(|_| AccountingError::PriceUnavailable)?;
+    let price =
+        crate::oracle::rescale_price(raw_price, feed_decimals(env, asset), ACCOUNTING_DECIMALS)
+            .map_err(|_| AccountingError::Overflow)?;
+    if price <= 0 {
+        return Err(AccountingError::PriceUnavailable);
+    }
+    Ok(price)
+}
+
+/// Decimals of the price returned by the oracle for an asset (SEP-40 source,
+/// then primary feed, then fallback feed)
+fn feed_decimals(env: &Env, asset: &Address) -> u32 {
+    if let Some(source) = get_sep40_source(env, asset) {
+        return source.price_decimals;
+    }
+    env.storage()
+        .persistent()
+        .get::<OracleDataKey, PriceFeed>(&OracleDataKey::PriceFeed(asset.clone()))
+        .or_else(|| {
+            env.storage()
+                .persistent()
+                .get::<OracleDataKey, PriceFeed>(&OracleDataKey::FallbackFeed(asset.clone()))
+        })
+        .map(|feed| feed.decimals)
+        .unwrap_or(ACCOUNTING_DECIMALS)
+}
+
+fn mul_div(a: i128, b: i128, c: i128) -> Result<i128, AccountingError> {
+    if c == 0 {
+        return Err(AccountingError::PriceUnavailable);
+    }
+    a.checked_mul(b)
+        .map(|x| x / c)
+        .ok_or(AccountingError::Overflow)
+}
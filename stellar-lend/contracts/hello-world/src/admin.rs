@@ -5,11 +5,27 @@
 //!
 //! ## Features
 //! - **Super Admin**: A single address with ultimate authority over the protocol.
-//! - **Roles**: Optional multi-admin functionality via specific roles (e.g., "oracle_admin").
+//! - **Roles**: Operational roles that can run a slice of the admin's duties without
+//!   holding the super-admin key. The super admin always passes every role check.
 //! - **Events**: Emits events for critical admin actions (admin changes, role grants/revocations).
+//! - **Dead-man Switch**: Optional inactivity guard. Every successful admin check (or an
+//!   explicit `admin_heartbeat`) refreshes the admin's last activity. If the admin stays
+//!   inactive for the configured period, a pre-approved backup admin may take over and
+//!   guardian recovery may also replace the super admin.
 
 use soroban_sdk::{contracterror, contracttype, Address, Env, IntoVal, Symbol, Val, Vec};
 
+/// Edits risk parameters, borrow caps, reserve factors and the exit exemption list
+pub const RISK_ADMIN: &str = "risk_admin";
+/// Pushes price updates for any asset, like a registered primary oracle
+pub const ORACLE_FEEDER: &str = "oracle_feeder";
+/// Flips pause switches, the emergency pause and per-market liquidation pauses
+pub const PAUSER: &str = "pauser";
+/// Moves protocol reserves to the treasury and funds the subsidy budget
+pub const TREASURER: &str = "treasurer";
+/// Tunes close factors, liquidation pricing, auctions and bad debt socialization
+pub const LIQUIDATION_ADMIN: &str = "liquidation_admin";
+
 /// Errors that can occur during admin operations
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -21,6 +37,10 @@ pub enum AdminError {
     InvalidParameter = 2,
     /// Admin has already been set
     AdminAlreadySet = 3,
+    /// Dead-man switch has not been configured
+    DeadManSwitchNotConfigured = 4,
+    /// Admin is still active; the dead-man switch has not triggered
+    DeadManSwitchNotTriggered = 5,
 }
 
 /// Storage keys for Admin and Roles
@@ -32,8 +52,27 @@ pub enum AdminDataKey {
     Admin,
     /// Specific role assigned to an address: Role(RoleName, Address) -> bool
     Role(Symbol, Address),
+    /// Timestamp of the super admin's last administrative action: u64
+    LastAdminActivity,
+    /// Dead-man switch configuration: DeadManSwitchConfig
+    DeadManSwitch,
+    /// Current holders of a role, in grant order: Vec<Address>
+    RoleMembers(Symbol),
+}
+
+/// Dead-man switch configuration
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeadManSwitchConfig {
+    /// Seconds of admin inactivity after which the switch triggers
+    pub inactivity_period: u64,
+    /// Backup admin allowed to take over once the switch triggers
+    pub backup_admin: Option<Address>,
 }
 
+/// Minimum dead-man switch inactivity period (7 days)
+pub const MIN_INACTIVITY_PERIOD: u64 = 7 * 24 * 60 * 60;
+
 /// Check if the super admin is set
 pub fn has_admin(env: &Env) -> bool {
     env.storage().persistent().has(&AdminDataKey::Admin)
@@ -64,6 +103,7 @@ pub fn set_admin(env: &Env, new_admin: Address, caller: Option<Address>) -> Resu
     env.storage()
         .persistent()
         .set(&AdminDataKey::Admin, &new_admin);
+    record_admin_activity(env);
 
     // Emit event
     let topics = (Symbol::new(env, "admin_changed"),);
@@ -80,12 +120,14 @@ pub fn set_admin(env: &Env, new_admin: Address, caller: Option<Address>) -> Resu
     Ok(())
 }
 
-/// Require that the caller is the super admin
+/// Require that the caller is the super admin, authorizing the call
 pub fn require_admin(env: &Env, caller: &Address) -> Result<(), AdminError> {
+    caller.require_auth();
     let admin = get_admin(env).ok_or(AdminError::Unauthorized)?;
     if admin != *caller {
         return Err(AdminError::Unauthorized);
     }
+    record_admin_activity(env);
     Ok(())
 }
 
@@ -99,6 +141,13 @@ pub fn grant_role(
     require_admin(env, &caller)?;
 
     let key = AdminDataKey::Role(role.clone(), account.clone());
+    if !env.storage().persistent().has(&key) {
+        let mut members = get_role_members(env, role.clone());
+        members.push_back(account.clone());
+        env.storage()
+            .persistent()
+            .set(&AdminDataKey::RoleMembers(role.clone()), &members);
+    }
     env.storage().persistent().set(&key, &true);
 
     // Emit event
@@ -127,6 +176,13 @@ pub fn revoke_role(
 
     let key = AdminDataKey::Role(role.clone(), account.clone());
     env.storage().persistent().remove(&key);
+    let mut members = get_role_members(env, role.clone());
+    if let Some(index) = members.first_index_of(&account) {
+        members.remove(index);
+        env.storage()
+            .persistent()
+            .set(&AdminDataKey::RoleMembers(role.clone()), &members);
+    }
 
     // Emit event
     let topics = (
@@ -144,20 +200,29 @@ pub fn revoke_role(
 }
 
 /// Check if an address has a specific role
-#[allow(dead_code)]
 pub fn has_role(env: &Env, role: Symbol, account: Address) -> bool {
     let key = AdminDataKey::Role(role, account);
     env.storage().persistent().get(&key).unwrap_or(false)
 }
 
-/// Require that the caller is either the super admin or has the required role
-#[allow(dead_code)]
+/// Get the current holders of a role, in grant order
+pub fn get_role_members(env: &Env, role: Symbol) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&AdminDataKey::RoleMembers(role))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Require that the caller is either the super admin or has the required role,
+/// authorizing the call
 pub fn require_role_or_admin(
     env: &Env,
     caller: &Address,
     required_role: Symbol,
 ) -> Result<(), AdminError> {
+    caller.require_auth();
     if get_admin(env).map(|a| a == *caller).unwrap_or(false) {
+        record_admin_activity(env);
         return Ok(());
     }
 
@@ -167,3 +232,113 @@ pub fn require_role_or_admin(
 
     Err(AdminError::Unauthorized)
 }
+
+/// Require that the caller is the super admin or holds one of the operational
+/// roles ([`RISK_ADMIN`], [`PAUSER`], ...)
+pub fn require_role(env: &Env, caller: &Address, role: &str) -> Result<(), AdminError> {
+    require_role_or_admin(env, caller, Symbol::new(env, role))
+}
+
+/// Record the current ledger time as the admin's last activity
+fn record_admin_activity(env: &Env) {
+    env.storage()
+        .persistent()
+        .set(&AdminDataKey::LastAdminActivity, &env.ledger().timestamp());
+}
+
+/// Explicit admin heartbeat that resets the dead-man switch timer
+pub fn admin_heartbeat(env: &Env, caller: Address) -> Result<(), AdminError> {
+    require_admin(env, &caller)
+}
+
+/// Configure or disable the dead-man switch (admin only)
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `caller` - The super admin
+/// * `config` - The switch configuration (None to disable)
+pub fn configure_dead_man_switch(
+    env: &Env,
+    caller: Address,
+    config: Option<DeadManSwitchConfig>,
+) -> Result<(), AdminError> {
+    require_admin(env, &caller)?;
+
+    match config {
+        Some(config) => {
+            if config.inactivity_period < MIN_INACTIVITY_PERIOD {
+                return Err(AdminError::InvalidParameter);
+            }
+            if config.backup_admin.as_ref() == Some(&caller) {
+                return Err(AdminError::InvalidParameter);
+            }
+            env.storage()
+                .persistent()
+                .set(&AdminDataKey::DeadManSwitch, &config);
+        }
+        None => env
+            .storage()
+            .persistent()
+            .remove(&AdminDataKey::DeadManSwitch),
+    }
+
+    let topics = (Symbol::new(env, "dead_man_switch_set"), caller);
+    env.events().publish(topics, env.ledger().timestamp());
+
+    Ok(())
+}
+
+/// Get the dead-man switch configuration
+pub fn get_dead_man_switch(env: &Env) -> Option<DeadManSwitchConfig> {
+    env.storage().persistent().get(&AdminDataKey::DeadManSwitch)
+}
+
+/// Get the timestamp of the admin's last recorded activity
+pub fn get_last_admin_activity(env: &Env) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&AdminDataKey::LastAdminActivity)
+}
+
+/// Whether the dead-man switch is configured and the admin has been inactive
+/// for at least the configured period
+pub fn is_admin_inactive(env: &Env) -> bool {
+    let config = match get_dead_man_switch(env) {
+        Some(config) => config,
+        None => return false,
+    };
+    let last_active = get_last_admin_activity(env).unwrap_or(0);
+    env.ledger().timestamp().saturating_sub(last_active) >= config.inactivity_period
+}
+
+/// Let the pre-approved backup admin take over after admin inactivity
+///
+/// # Errors
+/// * `DeadManSwitchNotConfigured` - No switch or no backup admin configured
+/// * `Unauthorized` - Caller is not the configured backup admin
+/// * `DeadManSwitchNotTriggered` - The admin is still active
+pub fn claim_admin_on_inactivity(env: &Env, backup: Address) -> Result<(), AdminError> {
+    backup.require_auth();
+
+    let config = get_dead_man_switch(env).ok_or(AdminError::DeadManSwitchNotConfigured)?;
+    let backup_admin = config
+        .backup_admin
+        .ok_or(AdminError::DeadManSwitchNotConfigured)?;
+    if backup_admin != backup {
+        return Err(AdminError::Unauthorized);
+    }
+    if !is_admin_inactive(env) {
+        return Err(AdminError::DeadManSwitchNotTriggered);
+    }
+
+    let previous = get_admin(env);
+    env.storage()
+        .persistent()
+        .set(&AdminDataKey::Admin, &backup);
+    record_admin_activity(env);
+
+    let topics = (Symbol::new(env, "backup_admin_activated"),);
+    env.events().publish(topics, (previous, backup));
+
+    Ok(())
+}
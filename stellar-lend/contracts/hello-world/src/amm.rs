@@ -1,6 +1,20 @@
-use soroban_sdk::{Address, Env};
+//! # AMM Integration
+//!
+//! Wraps the `stellarlend_amm` library for in-contract swaps and provides a
+//! read-only client for a deployed AMM contract.
+//!
+//! ## AMM Read Client
+//! Lending flows that may auto-swap first query the registered AMM contract
+//! through [`AmmReader`] for its maximum slippage, enabled protocols and pool
+//! health, and run [`preflight_amm_swap`] so that a swap that cannot succeed
+//! is rejected up front instead of failing mid-operation.
+//!
+//! ## Storage Layout
+//! - `AmmContract` — address of the deployed AMM contract (set by the admin)
+
+use soroban_sdk::{contracttype, Address, Env, Vec};
 use stellarlend_amm::{
-    AmmError, AmmProtocolConfig, LiquidityParams, SwapParams,
+    AmmContractClient, AmmError, AmmProtocolConfig, LiquidityParams, PoolHealth, SwapParams,
 };
 
 /// Set AMM pool configuration (admin only)
@@ -20,7 +34,34 @@ pub fn set_amm_pool(
 
 /// Execute swap through AMM
 pub fn amm_swap(env: Env, user: Address, params: SwapParams) -> Result<i128, AmmError> {
-    stellarlend_amm::execute_swap(&env, user, params)
+    swap(&env, user, params)
+}
+
+/// Execute a swap and book its fee to the protocol financials
+///
+/// Used by direct swaps, debt swaps and leverage unwinds.
+pub(crate) fn swap(env: &Env, user: Address, params: SwapParams) -> Result<i128, AmmError> {
+    let token_in = params.token_in.clone();
+    let token_out = params.token_out.clone();
+    let fees_before = pair_fees_in(env, &token_in, &token_out);
+    let received = stellarlend_amm::execute_swap(env, user, params)?;
+    crate::financials::record(
+        env,
+        crate::financials::FinancialItem::AmmFees,
+        &token_in,
+        pair_fees_in(env, &token_in, &token_out) - fees_before,
+    );
+    Ok(received)
+}
+
+/// Cumulative swap fees of a pair paid in `token_in`
+fn pair_fees_in(env: &Env, token_in: &Option<Address>, token_out: &Option<Address>) -> i128 {
+    let stats = stellarlend_amm::get_pair_stats(env, token_in.clone(), token_out.clone());
+    if stats.token_a == *token_in {
+        stats.fees_a
+    } else {
+        stats.fees_b
+    }
 }
 
 /// Add liquidity to AMM pool
@@ -73,3 +114,127 @@ pub fn initialize_amm(
         auto_swap_threshold,
     )
 }
+
+/// Storage keys for the AMM integration
+#[contracttype]
+#[derive(Clone)]
+pub enum AmmIntegrationDataKey {
+    /// Address of the deployed AMM contract
+    /// Value type: Address
+    AmmContract,
+}
+
+/// Typed read-only client for the deployed AMM contract
+///
+/// Maps AMM contract errors through unchanged; a failed or malformed
+/// cross-contract call surfaces as `AmmError::InvalidAmmProtocol`.
+pub struct AmmReader<'a> {
+    client: AmmContractClient<'a>,
+}
+
+impl<'a> AmmReader<'a> {
+    /// Client for the registered AMM contract
+    pub fn load(env: &'a Env) -> Result<Self, AmmError> {
+        let amm = get_amm_contract(env).ok_or(AmmError::NotConfigured)?;
+        Ok(Self {
+            client: AmmContractClient::new(env, &amm),
+        })
+    }
+
+    /// Maximum slippage tolerance (in basis points)
+    pub fn max_slippage(&self) -> Result<i128, AmmError> {
+        match self.client.try_get_max_slippage() {
+            Ok(Ok(max_slippage)) => Ok(max_slippage),
+            Err(Ok(err)) => Err(err),
+            _ => Err(AmmError::InvalidAmmProtocol),
+        }
+    }
+
+    /// Addresses of the enabled AMM protocols
+    pub fn supported_protocols(&self) -> Result<Vec<Address>, AmmError> {
+        match self.client.try_get_supported_protocols() {
+            Ok(Ok(protocols)) => Ok(protocols),
+            _ => Err(AmmError::InvalidAmmProtocol),
+        }
+    }
+
+    /// Health of a protocol's pool for a token pair
+    pub fn pool_health(
+        &self,
+        protocol: &Address,
+        token_a: &Option<Address>,
+        token_b: &Option<Address>,
+    ) -> Result<PoolHealth, AmmError> {
+        match self.client.try_get_pool_health(protocol, token_a, token_b) {
+            Ok(Ok(health)) => Ok(health),
+            Err(Ok(err)) => Err(err),
+            _ => Err(AmmError::InvalidAmmProtocol),
+        }
+    }
+}
+
+/// Register the deployed AMM contract to query (admin only)
+pub fn set_amm_contract(env: &Env, caller: Address, amm: Address) -> Result<(), AmmError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| AmmError::Unauthorized)?;
+    env.storage()
+        .persistent()
+        .set(&AmmIntegrationDataKey::AmmContract, &amm);
+    Ok(())
+}
+
+/// Get the registered AMM contract, if any
+pub fn get_amm_contract(env: &Env) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&AmmIntegrationDataKey::AmmContract)
+}
+
+/// Check that a swap would be accepted by the AMM contract
+///
+/// Applies the same limits the AMM enforces on `execute_swap`, using only
+/// read calls, so a lending flow can decide whether to auto-swap.
+///
+/// # Returns
+/// Returns the pool health snapshot when the swap is viable
+///
+/// # Errors
+/// * `AmmError::NotConfigured` - If no AMM contract is registered
+/// * `AmmError::SlippageExceeded` - If `slippage` is above the AMM maximum
+/// * `AmmError::UnsupportedProtocol` - If the protocol is unknown or disabled
+/// * `AmmError::SwapPaused` - If AMM swaps are paused
+/// * `AmmError::InvalidTokenPair` - If the protocol has no pool for the pair
+/// * `AmmError::InvalidSwapParams` - If `amount` is below the minimum swap
+/// * `AmmError::MaxInputExceeded` - If `amount` is above the maximum swap
+pub fn preflight_amm_swap(
+    env: &Env,
+    protocol: Address,
+    token_in: Option<Address>,
+    token_out: Option<Address>,
+    amount: i128,
+    slippage: i128,
+) -> Result<PoolHealth, AmmError> {
+    let reader = AmmReader::load(env)?;
+
+    if slippage > reader.max_slippage()? {
+        return Err(AmmError::SlippageExceeded);
+    }
+
+    let health = reader.pool_health(&protocol, &token_in, &token_out)?;
+    if !health.protocol_enabled {
+        return Err(AmmError::UnsupportedProtocol);
+    }
+    if !health.swap_enabled {
+        return Err(AmmError::SwapPaused);
+    }
+    if health.pool_address.is_none() {
+        return Err(AmmError::InvalidTokenPair);
+    }
+    if amount < health.min_swap_amount {
+        return Err(AmmError::InvalidSwapParams);
+    }
+    if amount > health.max_swap_amount {
+        return Err(AmmError::MaxInputExceeded);
+    }
+
+    Ok(health)
+}
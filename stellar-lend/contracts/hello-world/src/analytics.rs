@@ -6,6 +6,15 @@
 //! - **Protocol metrics**: TVL, utilization, average borrow rate, total users/transactions
 //! - **User metrics**: collateral, debt, health factor, risk level, activity score
 //! - **Activity feed**: bounded log of recent protocol operations (max 10,000 entries)
+//! - **Asset markets**: per-asset supplied, borrowed, reserves and utilization
+//!   from the deposit module's asset ledgers
+//!
+//! ## Protocol Totals
+//! `ProtocolMetrics` keeps the legacy aggregate, which adds raw amounts of
+//! all assets together. The protocol report also values every asset ledger
+//! at its oracle price in the accounting currency and derives protocol-wide
+//! supplied and borrowed value, and utilization, from those values. Assets
+//! without a usable price are listed but left out of the totals.
 //!
 //! ## Health Factor
 //! `health_factor = (collateral * 10000) / debt`
@@ -25,8 +34,8 @@
 use soroban_sdk::{contracterror, contracttype, Address, Env, Map, Symbol, Vec};
 
 use crate::deposit::{
-    DepositDataKey, Position, ProtocolAnalytics as DepositProtocolAnalytics,
-    UserAnalytics as DepositUserAnalytics,
+    get_asset_ledger, get_ledger_assets, DepositDataKey, Position,
+    ProtocolAnalytics as DepositProtocolAnalytics, UserAnalytics as DepositUserAnalytics,
 };
 
 /// Errors that can occur during analytics operations.
@@ -131,12 +140,44 @@ pub struct ActivityEntry {
     pub metadata: Map<Symbol, i128>,
 }
 
+/// Per-asset market metrics.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct AssetMetrics {
+    /// Asset address (None for native XLM)
+    pub asset: Option<Address>,
+    /// Collateral supplied in the asset's units
+    pub supplied: i128,
+    /// Debt principal borrowed in the asset's units
+    pub borrowed: i128,
+    /// Protocol reserve balance in the asset's units
+    pub reserves: i128,
+    /// Utilization rate in basis points (borrowed / supplied * 10000)
+    pub utilization_rate: i128,
+    /// Whether the asset has a usable oracle price
+    pub priced: bool,
+    /// Supplied value in the accounting currency (0 when unpriced)
+    pub supplied_value: i128,
+    /// Borrowed value in the accounting currency (0 when unpriced)
+    pub borrowed_value: i128,
+}
+
 /// Protocol-level analytics report.
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
 pub struct ProtocolReport {
     /// Current protocol metrics
     pub metrics: ProtocolMetrics,
+    /// Per-asset markets, in the order assets first saw activity
+    pub assets: Vec<AssetMetrics>,
+    /// Supplied value of priced assets in the accounting currency
+    pub total_supplied_value: i128,
+    /// Borrowed value of priced assets in the accounting currency
+    pub total_borrowed_value: i128,
+    /// Value-weighted utilization rate in basis points
+    pub value_utilization_rate: i128,
+    /// Protocol-wide daily borrow limit status
+    pub daily_borrow: crate::risk_management::DailyBorrowStatus,
     /// Report generation timestamp
     pub timestamp: u64,
 }
@@ -642,15 +683,89 @@ pub fn get_activity_by_type(
 /// A `ProtocolReport` containing fresh metrics and the current timestamp.
 pub fn generate_protocol_report(env: &Env) -> Result<ProtocolReport, AnalyticsError> {
     let metrics = update_protocol_metrics(env)?;
+    let assets = get_asset_metrics(env)?;
+    let (total_supplied_value, total_borrowed_value) = sum_values(&assets)?;
 
     let report = ProtocolReport {
         metrics,
+        assets,
+        total_supplied_value,
+        total_borrowed_value,
+        value_utilization_rate: utilization_bps(total_borrowed_value, total_supplied_value)?,
+        daily_borrow: crate::risk_management::get_global_daily_borrow_status(env),
         timestamp: env.ledger().timestamp(),
     };
 
     Ok(report)
 }
 
+/// Get the market metrics of every asset with a ledger.
+///
+/// Amounts come from the asset ledgers; values use the spot price in the
+/// accounting currency.
+///
+/// # Returns
+/// One `AssetMetrics` per asset, in the order assets first saw activity.
+pub fn get_asset_metrics(env: &Env) -> Result<Vec<AssetMetrics>, AnalyticsError> {
+    let mut metrics = Vec::new(env);
+    for asset in get_ledger_assets(env).iter() {
+        let ledger = get_asset_ledger(env, &asset);
+        let values = crate::accounting::to_accounting_value(env, &asset, ledger.supplied)
+            .and_then(|supplied| {
+                crate::accounting::to_accounting_value(env, &asset, ledger.borrowed)
+                    .map(|borrowed| (supplied, borrowed))
+            })
+            .ok();
+        let (supplied_value, borrowed_value) = values.unwrap_or((0, 0));
+
+        metrics.push_back(AssetMetrics {
+            asset,
+            supplied: ledger.supplied,
+            borrowed: ledger.borrowed,
+            reserves: ledger.reserves,
+            utilization_rate: utilization_bps(ledger.borrowed, ledger.supplied)?,
+            priced: values.is_some(),
+            supplied_value,
+            borrowed_value,
+        });
+    }
+    Ok(metrics)
+}
+
+/// Supplied and borrowed value of priced assets in the accounting currency
+///
+/// # Returns
+/// `(total_supplied_value, total_borrowed_value, value_utilization_rate)`
+pub(crate) fn get_value_totals(env: &Env) -> Result<(i128, i128, i128), AnalyticsError> {
+    let (supplied, borrowed) = sum_values(&get_asset_metrics(env)?)?;
+    Ok((supplied, borrowed, utilization_bps(borrowed, supplied)?))
+}
+
+fn sum_values(assets: &Vec<AssetMetrics>) -> Result<(i128, i128), AnalyticsError> {
+    let mut supplied: i128 = 0;
+    let mut borrowed: i128 = 0;
+    for asset in assets.iter() {
+        supplied = supplied
+            .checked_add(asset.supplied_value)
+            .ok_or(AnalyticsError::Overflow)?;
+        borrowed = borrowed
+            .checked_add(asset.borrowed_value)
+            .ok_or(AnalyticsError::Overflow)?;
+    }
+    Ok((supplied, borrowed))
+}
+
+/// `borrowed * 10000 / supplied`, or 0 with nothing supplied
+fn utilization_bps(borrowed: i128, supplied: i128) -> Result<i128, AnalyticsError> {
+    if supplied <= 0 {
+        return Ok(0);
+    }
+    borrowed
+        .checked_mul(BASIS_POINTS)
+        .map(|scaled| scaled / supplied)
+        .ok_or(AnalyticsError::Overflow)
+}
+
 /// Generate a comprehensive user analytics report.
 ///
 /// Includes the user's computed metrics, current position, and the 10 most
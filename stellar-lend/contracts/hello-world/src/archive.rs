@@ -0,0 +1,228 @@
+//! # Idle Position Archive
+//!
+//! Reclaims hot-path storage from accounts that have gone quiet. Any keeper
+//! can sweep positions untouched for [`ARCHIVE_IDLE_SECONDS`]: their
+//! `Position`, `CollateralBalance` and `SupplyShares` entries are folded into
+//! a single compact [`ArchivedPosition`]. Nothing is deleted; the archived
+//! shares stay in the supply pool and keep earning interest.
+//!
+//! Only debt-free positions are archived, so liquidations and interest
+//! accrual never need to look at the archive.
+//!
+//! ## Restoring
+//! The user restores the position with one call to [`restore_position`]
+//! before interacting again. Restoring merges the archive into anything the
+//! user has done since, so acting before restoring never loses funds; the
+//! archived collateral just does not count until it is restored.
+//!
+//! Isolated position accounts cannot authorize, so their owner restores them
+//! through `positions::restore_isolated_position` instead.
+
+use soroban_sdk::{contracterror, contracttype, Address, Env, Symbol, Vec};
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::events::{
+    emit_position_archived, emit_position_restored, PositionArchivedEvent, PositionRestoredEvent,
+};
+
+/// Time a position must go untouched before it can be archived (2 years)
+pub const ARCHIVE_IDLE_SECONDS: u64 = 2 * 365 * 86_400;
+
+/// Maximum number of accounts swept per call
+pub const MAX_ARCHIVE_BATCH: u32 = 50;
+
+/// Errors that can occur in position archive operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ArchiveError {
+    /// More than `MAX_ARCHIVE_BATCH` accounts in one sweep
+    BatchTooLarge = 1,
+    /// The account has no archived position
+    NotArchived = 2,
+    /// Overflow while merging balances
+    Overflow = 3,
+}
+
+/// Storage keys for the position archive
+#[contracttype]
+#[derive(Clone)]
+pub enum ArchiveDataKey {
+    /// Archived position of an account
+    /// Value type: ArchivedPosition
+    Archived(Address),
+}
+
+/// Cold-storage copy of an idle position
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArchivedPosition {
+    /// `Position::collateral` when archived
+    pub collateral: i128,
+    /// `CollateralBalance` when archived
+    pub collateral_balance: i128,
+    /// Supply shares held when archived
+    pub supply_shares: i128,
+    /// Last time the position was touched
+    pub last_active: u64,
+    pub archived_at: u64,
+}
+
+/// Archive the idle, debt-free positions among `users`
+///
+/// Callable by anyone; accounts that are not eligible are skipped.
+///
+/// # Returns
+/// The number of positions archived
+///
+/// # Errors
+/// * `BatchTooLarge` - More than `MAX_ARCHIVE_BATCH` accounts
+pub fn archive_idle_positions(
+    env: &Env,
+    keeper: Address,
+    users: Vec<Address>,
+) -> Result<u32, ArchiveError> {
+    keeper.require_auth();
+    if users.len() > MAX_ARCHIVE_BATCH {
+        return Err(ArchiveError::BatchTooLarge);
+    }
+
+    let mut archived = 0;
+    for user in users.iter() {
+        if archive_if_idle(env, &keeper, &user)? {
+            archived += 1;
+        }
+    }
+    if archived > 0 {
+        crate::keeper_pool::reimburse(env, &keeper, Symbol::new(env, "archive"));
+    }
+    Ok(archived)
+}
+
+fn archive_if_idle(env: &Env, keeper: &Address, user: &Address) -> Result<bool, ArchiveError> {
+    let storage = env.storage().persistent();
+    let position_key = DepositDataKey::Position(user.clone());
+    let Some(position) = storage.get::<DepositDataKey, Position>(&position_key) else {
+        return Ok(false);
+    };
+    let now = env.ledger().timestamp();
+    if position.debt != 0
+        || position.borrow_interest != 0
+        || now.saturating_sub(position.last_accrual_time) < ARCHIVE_IDLE_SECONDS
+    {
+        return Ok(false);
+    }
+
+    let balance_key = DepositDataKey::CollateralBalance(user.clone());
+    let shares_key = DepositDataKey::SupplyShares(user.clone());
+    let collateral_balance: i128 = storage.get(&balance_key).unwrap_or(0);
+    let supply_shares: i128 = storage.get(&shares_key).unwrap_or(0);
+
+    // A position opened after an earlier archive and left idle adds to it
+    let archive = match get_archived_position(env, user) {
+        Some(existing) => ArchivedPosition {
+            collateral: add(existing.collateral, position.collateral)?,
+            collateral_balance: add(existing.collateral_balance, collateral_balance)?,
+            supply_shares: add(existing.supply_shares, supply_shares)?,
+            last_active: position.last_accrual_time,
+            archived_at: now,
+        },
+        None => ArchivedPosition {
+            collateral: position.collateral,
+            collateral_balance,
+            supply_shares,
+            last_active: position.last_accrual_time,
+            archived_at: now,
+        },
+    };
+
+    storage.set(&ArchiveDataKey::Archived(user.clone()), &archive);
+    storage.remove(&position_key);
+    storage.remove(&balance_key);
+    storage.remove(&shares_key);
+
+    emit_position_archived(
+        env,
+        PositionArchivedEvent {
+            user: user.clone(),
+            keeper: keeper.clone(),
+            collateral: archive.collateral,
+            supply_shares: archive.supply_shares,
+            timestamp: now,
+        },
+    );
+    Ok(true)
+}
+
+/// Move an archived position back into the hot-path entries
+///
+/// Merges with any position opened since the archive.
+///
+/// # Returns
+/// The restored position
+///
+/// # Errors
+/// * `NotArchived` - The account has no archived position
+pub fn restore_position(env: &Env, user: Address) -> Result<Position, ArchiveError> {
+    user.require_auth();
+    restore_archived(env, &user)
+}
+
+/// Restore an archived position without an authorization check
+///
+/// Used for accounts that cannot authorize themselves, once the caller has
+/// authorized on their behalf.
+pub(crate) fn restore_archived(env: &Env, user: &Address) -> Result<Position, ArchiveError> {
+    let archive = get_archived_position(env, user).ok_or(ArchiveError::NotArchived)?;
+    let storage = env.storage().persistent();
+    let now = env.ledger().timestamp();
+
+    let position_key = DepositDataKey::Position(user.clone());
+    let mut position = storage
+        .get::<DepositDataKey, Position>(&position_key)
+        .unwrap_or(Position {
+            collateral: 0,
+            debt: 0,
+            borrow_interest: 0,
+            last_accrual_time: now,
+        });
+    position.collateral = add(position.collateral, archive.collateral)?;
+
+    let balance_key = DepositDataKey::CollateralBalance(user.clone());
+    let shares_key = DepositDataKey::SupplyShares(user.clone());
+    let collateral_balance = add(
+        storage.get(&balance_key).unwrap_or(0),
+        archive.collateral_balance,
+    )?;
+    let supply_shares = add(storage.get(&shares_key).unwrap_or(0), archive.supply_shares)?;
+
+    storage.set(&position_key, &position);
+    storage.set(&balance_key, &collateral_balance);
+    if supply_shares > 0 {
+        storage.set(&shares_key, &supply_shares);
+    }
+    storage.remove(&ArchiveDataKey::Archived(user.clone()));
+
+    emit_position_restored(
+        env,
+        PositionRestoredEvent {
+            user: user.clone(),
+            collateral: archive.collateral,
+            supply_shares: archive.supply_shares,
+            timestamp: now,
+        },
+    );
+    crate::deposit::emit_position_updated_event(env, user, &position);
+    Ok(position)
+}
+
+/// Get an account's archived position, if any
+pub fn get_archived_position(env: &Env, user: &Address) -> Option<ArchivedPosition> {
+    env.storage()
+        .persistent()
+        .get(&ArchiveDataKey::Archived(user.clone()))
+}
+
+fn add(a: i128, b: i128) -> Result<i128, ArchiveError> {
+    a.checked_add(b).ok_or(ArchiveError::Overflow)
+}
@@ -0,0 +1,344 @@
+//! # Automation Jobs
+//!
+//! Interest accrual, oracle failover checks and external tasks such as AMM
+//! DCA tranches need regular pokes. The admin registers each as a job with a
+//! minimum interval and a native XLM reward; any keeper may run a due job
+//! with [`execute_job`] and is paid the reward from the keeper pool (see
+//! `keeper_pool.rs`). A job still runs when the pool cannot cover its reward.
+//!
+//! ## Targets
+//! - `AccrueInterest` — advances the global borrow index
+//! - `RefreshOracleFailover(asset)` — walks the asset's oracle failover chain
+//! - `Contract(address, function)` — calls another contract with the job's
+//!   stored arguments; a failing call fails the job
+//!
+//! ## Pause Controls
+//! The admin can pause a single job or all automation. Paused jobs keep
+//! their schedule and run again once resumed.
+//!
+//! ## Storage Layout
+//! - `Job(id)` — the job and its last execution
+//! - `JobIds` — ids of registered jobs, oldest first
+//! - `NextJobId` — id assigned to the next job
+//! - `Paused` — whether all automation is paused
+
+use soroban_sdk::{contracterror, contracttype, Address, Env, Symbol, Val, Vec};
+
+use crate::events::{emit_admin_action, emit_job_executed, AdminActionEvent, JobExecutedEvent};
+
+/// Maximum number of registered jobs
+pub const MAX_AUTOMATION_JOBS: u32 = 50;
+
+/// Errors that can occur in automation operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AutomationError {
+    /// Caller is not the admin
+    Unauthorized = 1,
+    /// Interval is zero, reward is negative or the target is this contract
+    InvalidJob = 2,
+    /// No job exists with the id
+    JobNotFound = 3,
+    /// The job is paused
+    JobPaused = 4,
+    /// All automation is paused
+    AutomationPaused = 5,
+    /// The job's interval has not passed since its last execution
+    JobNotDue = 6,
+    /// `MAX_AUTOMATION_JOBS` jobs are already registered
+    TooManyJobs = 7,
+    /// The job's target failed
+    JobFailed = 8,
+}
+
+/// Storage keys for automation jobs
+#[contracttype]
+#[derive(Clone)]
+pub enum AutomationDataKey {
+    /// A registered job
+    /// Value type: AutomationJob
+    Job(u32),
+    /// Ids of registered jobs, oldest first
+    /// Value type: Vec<u32>
+    JobIds,
+    /// Id assigned to the next job
+    /// Value type: u32
+    NextJobId,
+    /// Whether all automation is paused
+    /// Value type: bool
+    Paused,
+}
+
+/// What a job runs
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum JobTarget {
+    /// Advance the global borrow index
+    AccrueInterest,
+    /// Walk an asset's oracle failover chain
+    RefreshOracleFailover(Address),
+    /// Call `function` on another contract with the job's arguments
+    Contract(Address, Symbol),
+}
+
+/// A registered automation job
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AutomationJob {
+    pub id: u32,
+    pub target: JobTarget,
+    /// Arguments of a `Contract` target (ignored otherwise)
+    pub args: Vec<Val>,
+    /// Minimum seconds between executions
+    pub interval: u64,
+    /// Native XLM paid to the keeper per execution
+    pub reward: i128,
+    pub paused: bool,
+    /// Timestamp of the latest execution (0 if never run)
+    pub last_executed: u64,
+    /// Keeper of the latest execution
+    pub last_keeper: Option<Address>,
+    /// Number of executions
+    pub executions: u32,
+}
+
+/// Register a job (admin only)
+///
+/// # Returns
+/// The job id
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not the admin
+/// * `InvalidJob` - Interval is zero, reward is negative or the target is this contract
+/// * `TooManyJobs` - `MAX_AUTOMATION_JOBS` jobs are already registered
+pub fn register_job(
+    env: &Env,
+    caller: Address,
+    target: JobTarget,
+    args: Vec<Val>,
+    interval: u64,
+    reward: i128,
+) -> Result<u32, AutomationError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| AutomationError::Unauthorized)?;
+    if interval == 0 || reward < 0 {
+        return Err(AutomationError::InvalidJob);
+    }
+    if let JobTarget::Contract(ref contract, _) = target {
+        if *contract == env.current_contract_address() {
+            return Err(AutomationError::InvalidJob);
+        }
+    }
+    let mut ids = get_job_ids(env);
+    if ids.len() >= MAX_AUTOMATION_JOBS {
+        return Err(AutomationError::TooManyJobs);
+    }
+
+    let id = env
+        .storage()
+        .persistent()
+        .get(&AutomationDataKey::NextJobId)
+        .unwrap_or(0u32);
+    env.storage()
+        .persistent()
+        .set(&AutomationDataKey::NextJobId, &(id + 1));
+    let job = AutomationJob {
+        id,
+        target,
+        args,
+        interval,
+        reward,
+        paused: false,
+        last_executed: 0,
+        last_keeper: None,
+        executions: 0,
+    };
+    save_job(env, &job);
+    ids.push_back(id);
+    env.storage()
+        .persistent()
+        .set(&AutomationDataKey::JobIds, &ids);
+
+    emit_admin(env, caller, "register_job");
+    Ok(id)
+}
+
+/// Remove a job (admin only)
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not the admin
+/// * `JobNotFound` - No job exists with the id
+pub fn remove_job(env: &Env, caller: Address, job_id: u32) -> Result<(), AutomationError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| AutomationError::Unauthorized)?;
+    let mut ids = get_job_ids(env);
+    let index = ids
+        .first_index_of(job_id)
+        .ok_or(AutomationError::JobNotFound)?;
+    ids.remove(index);
+    env.storage()
+        .persistent()
+        .set(&AutomationDataKey::JobIds, &ids);
+    env.storage()
+        .persistent()
+        .remove(&AutomationDataKey::Job(job_id));
+
+    emit_admin(env, caller, "remove_job");
+    Ok(())
+}
+
+/// Pause or resume a job (admin only)
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not the admin
+/// * `JobNotFound` - No job exists with the id
+pub fn set_job_paused(
+    env: &Env,
+    caller: Address,
+    job_id: u32,
+    paused: bool,
+) -> Result<(), AutomationError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| AutomationError::Unauthorized)?;
+    let mut job = get_job(env, job_id).ok_or(AutomationError::JobNotFound)?;
+    job.paused = paused;
+    save_job(env, &job);
+
+    emit_admin(env, caller, "set_job_paused");
+    Ok(())
+}
+
+/// Pause or resume all automation (admin only)
+pub fn set_automation_paused(
+    env: &Env,
+    caller: Address,
+    paused: bool,
+) -> Result<(), AutomationError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| AutomationError::Unauthorized)?;
+    env.storage()
+        .persistent()
+        .set(&AutomationDataKey::Paused, &paused);
+
+    emit_admin(env, caller, "set_automation_paused");
+    Ok(())
+}
+
+/// Whether all automation is paused
+pub fn is_automation_paused(env: &Env) -> bool {
+    env.storage()
+        .persistent()
+        .get(&AutomationDataKey::Paused)
+        .unwrap_or(false)
+}
+
+/// Run a due job and pay the keeper its reward
+///
+/// # Returns
+/// The reward paid (0 when the keeper pool cannot cover it)
+///
+/// # Errors
+/// * `JobNotFound` - No job exists with the id
+/// * `AutomationPaused` / `JobPaused` - Automation or the job is paused
+/// * `JobNotDue` - The job's interval has not passed since its last execution
+/// * `JobFailed` - The job's target failed
+pub fn execute_job(env: &Env, keeper: Address, job_id: u32) -> Result<i128, AutomationError> {
+    keeper.require_auth();
+    let mut job = get_job(env, job_id).ok_or(AutomationError::JobNotFound)?;
+    if is_automation_paused(env) {
+        return Err(AutomationError::AutomationPaused);
+    }
+    if job.paused {
+        return Err(AutomationError::JobPaused);
+    }
+    let now = env.ledger().timestamp();
+    if job.executions > 0 && now < job.last_executed.saturating_add(job.interval) {
+        return Err(AutomationError::JobNotDue);
+    }
+
+    run_target(env, &job)?;
+
+    job.last_executed = now;
+    job.last_keeper = Some(keeper.clone());
+    job.executions = job.executions.saturating_add(1);
+    save_job(env, &job);
+
+    let reward = crate::keeper_pool::pay_from_pool(
+        env,
+        &keeper,
+        job.reward,
+        Symbol::new(env, "execute_job"),
+    );
+    emit_job_executed(
+        env,
+        JobExecutedEvent {
+            job_id,
+            keeper,
+            reward,
+            timestamp: now,
+        },
+    );
+    Ok(reward)
+}
+
+/// Get a job by id
+pub fn get_job(env: &Env, job_id: u32) -> Option<AutomationJob> {
+    env.storage()
+        .persistent()
+        .get(&AutomationDataKey::Job(job_id))
+}
+
+/// Get all registered jobs, oldest first
+pub fn get_jobs(env: &Env) -> Vec<AutomationJob> {
+    let mut jobs = Vec::new(env);
+    for id in get_job_ids(env).iter() {
+        if let Some(job) = get_job(env, id) {
+            jobs.push_back(job);
+        }
+    }
+    jobs
+}
+
+fn run_target(env: &Env, job: &AutomationJob) -> Result<(), AutomationError> {
+    match &job.target {
+        JobTarget::AccrueInterest => crate::interest_rate::accrue_borrow_index(env)
+            .map(|_| ())
+            .map_err(|_| AutomationError::JobFailed),
+        JobTarget::RefreshOracleFailover(asset) => {
+            crate::oracle::refresh_failover_state(env, asset)
+                .map(|_| ())
+                .map_err(|_| AutomationError::JobFailed)
+        }
+        JobTarget::Contract(contract, function) => {
+            match env.try_invoke_contract::<Val, soroban_sdk::Error>(
+                contract,
+                function,
+                job.args.clone(),
+            ) {
+                Ok(Ok(_)) => Ok(()),
+                _ => Err(AutomationError::JobFailed),
+            }
+        }
+    }
+}
+
+fn get_job_ids(env: &Env) -> Vec<u32> {
+    env.storage()
+        .persistent()
+        .get(&AutomationDataKey::JobIds)
+        .unwrap_or(Vec::new(env))
+}
+
+fn save_job(env: &Env, job: &AutomationJob) {
+    env.storage()
+        .persistent()
+        .set(&AutomationDataKey::Job(job.id), job);
+}
+
+fn emit_admin(env: &Env, caller: Address, action: &str) {
+    emit_admin_action(
+        env,
+        AdminActionEvent {
+            actor: caller,
+            action: Symbol::new(env, action),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+}
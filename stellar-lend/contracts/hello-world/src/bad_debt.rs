@@ -0,0 +1,343 @@
+//! # Bad Debt Module
+//!
+//! Tracks debt left behind by insolvent positions and resolves it against an
+//! insurance reserve and, failing that, the suppliers.
+//!
+//! ## Shortfall Recording
+//! When a liquidation seizes a borrower's last collateral while debt remains,
+//! the remaining principal and interest can never be repaid. The position's
+//! debt is written off and the amount is booked as a shortfall against the
+//! debt asset, so the loss stays visible instead of sitting in an
+//! unliquidatable position.
+//!
+//! ## Insolvency Flagging
+//! Every position update checks whether the position's collateral is worth
+//! less than its debt (true insolvency, below even a 100% collateral ratio).
+//! Such positions are flagged and `insolvency_detected` is emitted so the
+//! bad debt process can start right away; flagged positions cannot borrow.
+//! The flag is lifted by the first update that leaves the position solvent,
+//! including a liquidation that writes its remaining debt off.
+//!
+//! ## Insurance Reserve
+//! A slice of accrued borrow interest (`insurance_factor_bps`, 0 by default)
+//! is diverted from the supply pool into the insurance reserve at accrual
+//! time. The reserve is protocol-wide because the core lending pool is a
+//! single supply pool.
+//!
+//! ## Socialization
+//! `socialize_bad_debt` (admin/governance) resolves an asset's shortfall:
+//! 1. The insurance reserve covers as much as it holds.
+//! 2. The remainder is written off the supply pool's underlying, so every
+//!    supplier loses in proportion to their shares through the exchange rate.
+//!
+//! ## Storage Layout
+//! - `Shortfall(asset)` — unresolved bad debt per debt asset
+//! - `InsuranceReserve` — interest set aside to cover bad debt
+//! - `InsuranceFactor` — share of accrued interest diverted to insurance (bps)
+//! - `TotalCovered(asset)` / `TotalSocialized(asset)` — cumulative resolutions
+//! - `Insolvent(user)` — flag on positions found insolvent
+//!
+//! ## Invariants
+//! - Shortfalls and the insurance reserve are never negative.
+//! - The insurance factor plus the supply reserve factor never exceed 100%.
+//! - Socialization never drives the supply pool's underlying below zero.
+
+use crate::deposit::Position;
+use crate::events::{
+    emit_admin_action, emit_bad_debt_recorded, emit_bad_debt_socialized, emit_insolvency_detected,
+    AdminActionEvent, BadDebtRecordedEvent, BadDebtSocializedEvent, InsolvencyDetectedEvent,
+};
+use soroban_sdk::{contracterror, contracttype, Address, Env, Symbol};
+
+/// Maximum share of accrued interest diverted to insurance (bps)
+pub const MAX_INSURANCE_FACTOR_BPS: i128 = 5_000;
+
+/// Errors that can occur during bad debt operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum BadDebtError {
+    /// Caller is not authorized (not admin)
+    Unauthorized = 1,
+    /// Insurance factor outside 0 - MAX_INSURANCE_FACTOR_BPS
+    InvalidInsuranceFactor = 2,
+    /// The asset has no outstanding bad debt
+    NoBadDebt = 3,
+    /// Arithmetic overflow occurred
+    Overflow = 4,
+}
+
+/// Storage keys for bad debt data
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum BadDebtDataKey {
+    /// Unresolved bad debt per debt asset: Shortfall(asset) -> i128
+    Shortfall(Option<Address>),
+    /// Interest set aside to cover bad debt: InsuranceReserve -> i128
+    InsuranceReserve,
+    /// Share of accrued interest diverted to insurance: InsuranceFactor -> i128 (bps)
+    InsuranceFactor,
+    /// Cumulative bad debt covered by insurance: TotalCovered(asset) -> i128
+    TotalCovered(Option<Address>),
+    /// Cumulative bad debt written off the suppliers: TotalSocialized(asset) -> i128
+    TotalSocialized(Option<Address>),
+    /// Flag on a position found insolvent: Insolvent(user) -> InsolvencyRecord
+    Insolvent(Address),
+}
+
+/// Outcome of resolving an asset's bad debt
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadDebtResolution {
+    /// Amount paid out of the insurance reserve
+    pub covered_by_insurance: i128,
+    /// Amount written off the supply pool
+    pub socialized: i128,
+}
+
+/// Position state when it was flagged insolvent
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InsolvencyRecord {
+    /// Collateral value at detection
+    pub collateral: i128,
+    /// Debt value (principal plus interest) at detection
+    pub debt: i128,
+    /// Timestamp of detection
+    pub detected_at: u64,
+}
+
+/// Get the unresolved bad debt for an asset
+pub fn get_bad_debt(env: &Env, asset: Option<Address>) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&BadDebtDataKey::Shortfall(asset))
+        .unwrap_or(0)
+}
+
+/// Get the insurance reserve balance
+pub fn get_insurance_reserve(env: &Env) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&BadDebtDataKey::InsuranceReserve)
+        .unwrap_or(0)
+}
+
+/// Get the share of accrued interest diverted to insurance (bps)
+pub fn get_insurance_factor(env: &Env) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&BadDebtDataKey::InsuranceFactor)
+        .unwrap_or(0)
+}
+
+/// Get the cumulative (covered by insurance, socialized) amounts for an asset
+pub fn get_bad_debt_totals(env: &Env, asset: Option<Address>) -> (i128, i128) {
+    let storage = env.storage().persistent();
+    (
+        storage
+            .get(&BadDebtDataKey::TotalCovered(asset.clone()))
+            .unwrap_or(0),
+        storage
+            .get(&BadDebtDataKey::TotalSocialized(asset))
+            .unwrap_or(0),
+    )
+}
+
+/// Set the share of accrued interest diverted to insurance (admin only)
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not the admin or risk admin
+/// * `InvalidInsuranceFactor` - Factor is negative or above `MAX_INSURANCE_FACTOR_BPS`
+pub fn set_insurance_factor(
+    env: &Env,
+    caller: Address,
+    factor_bps: i128,
+) -> Result<(), BadDebtError> {
+    crate::admin::require_role(env, &caller, crate::admin::RISK_ADMIN)
+        .map_err(|_| BadDebtError::Unauthorized)?;
+
+    if !(0..=MAX_INSURANCE_FACTOR_BPS).contains(&factor_bps) {
+        return Err(BadDebtError::InvalidInsuranceFactor);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&BadDebtDataKey::InsuranceFactor, &factor_bps);
+
+    emit_admin_action(
+        env,
+        AdminActionEvent {
+            actor: caller,
+            action: Symbol::new(env, "set_insurance_factor"),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(())
+}
+
+/// Divert the insurance slice of newly accrued interest
+///
+/// Called during interest accrual before the suppliers' share is credited.
+/// Returns the amount added to the insurance reserve.
+pub(crate) fn accrue_insurance(env: &Env, interest: i128) -> i128 {
+    if interest <= 0 {
+        return 0;
+    }
+    let slice = interest.saturating_mul(get_insurance_factor(env)) / 10_000;
+    if slice <= 0 {
+        return 0;
+    }
+    env.storage().persistent().set(
+        &BadDebtDataKey::InsuranceReserve,
+        &get_insurance_reserve(env).saturating_add(slice),
+    );
+    slice
+}
+
+/// Book debt that can no longer be repaid as a shortfall
+///
+/// Called by liquidation once a borrower's collateral is exhausted.
+pub(crate) fn record_bad_debt(env: &Env, borrower: &Address, asset: Option<Address>, amount: i128) {
+    if amount <= 0 {
+        return;
+    }
+    let shortfall = get_bad_debt(env, asset.clone()).saturating_add(amount);
+    env.storage()
+        .persistent()
+        .set(&BadDebtDataKey::Shortfall(asset.clone()), &shortfall);
+
+    emit_bad_debt_recorded(
+        env,
+        BadDebtRecordedEvent {
+            borrower: borrower.clone(),
+            asset,
+            amount,
+            total_shortfall: shortfall,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+}
+
+/// Get the insolvency flag of a position, if it is flagged
+pub fn get_insolvency(env: &Env, user: &Address) -> Option<InsolvencyRecord> {
+    env.storage()
+        .persistent()
+        .get(&BadDebtDataKey::Insolvent(user.clone()))
+}
+
+/// Check whether a position is flagged insolvent
+pub fn is_insolvent(env: &Env, user: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&BadDebtDataKey::Insolvent(user.clone()))
+}
+
+/// Flag a position whose collateral is worth less than its debt, or lift
+/// the flag once it is solvent again
+///
+/// Called after every position update. `insolvency_detected` is emitted
+/// when a position is first flagged.
+pub(crate) fn check_insolvency(env: &Env, user: &Address, position: &Position) {
+    let key = BadDebtDataKey::Insolvent(user.clone());
+    let debt = position.debt.saturating_add(position.borrow_interest);
+    if debt <= position.collateral {
+        if env.storage().persistent().has(&key) {
+            env.storage().persistent().remove(&key);
+        }
+        return;
+    }
+    if env.storage().persistent().has(&key) {
+        return;
+    }
+
+    let record = InsolvencyRecord {
+        collateral: position.collateral,
+        debt,
+        detected_at: env.ledger().timestamp(),
+    };
+    env.storage().persistent().set(&key, &record);
+
+    emit_insolvency_detected(
+        env,
+        InsolvencyDetectedEvent {
+            user: user.clone(),
+            collateral: record.collateral,
+            debt: record.debt,
+            timestamp: record.detected_at,
+        },
+    );
+}
+
+/// Resolve an asset's bad debt (admin/governance only)
+///
+/// Covers the shortfall from the insurance reserve first and writes the
+/// remainder off the supply pool, spreading the loss across suppliers in
+/// proportion to their shares. Any loss exceeding the pool's underlying stays
+/// recorded as a shortfall.
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not the admin or liquidation admin
+/// * `NoBadDebt` - The asset has no outstanding shortfall
+/// * `Overflow` - Cumulative totals overflowed
+pub fn socialize_bad_debt(
+    env: &Env,
+    caller: Address,
+    asset: Option<Address>,
+) -> Result<BadDebtResolution, BadDebtError> {
+    crate::admin::require_role(env, &caller, crate::admin::LIQUIDATION_ADMIN)
+        .map_err(|_| BadDebtError::Unauthorized)?;
+
+    let shortfall = get_bad_debt(env, asset.clone());
+    if shortfall <= 0 {
+        return Err(BadDebtError::NoBadDebt);
+    }
+
+    let insurance = get_insurance_reserve(env);
+    let covered_by_insurance = shortfall.min(insurance);
+    let socialized = crate::deposit::write_off_supply(env, shortfall - covered_by_insurance);
+
+    let (total_covered, total_socialized) = get_bad_debt_totals(env, asset.clone());
+    let total_covered = total_covered
+        .checked_add(covered_by_insurance)
+        .ok_or(BadDebtError::Overflow)?;
+    let total_socialized = total_socialized
+        .checked_add(socialized)
+        .ok_or(BadDebtError::Overflow)?;
+
+    let storage = env.storage().persistent();
+    storage.set(
+        &BadDebtDataKey::InsuranceReserve,
+        &(insurance - covered_by_insurance),
+    );
+    let remaining = shortfall - covered_by_insurance - socialized;
+    if remaining > 0 {
+        storage.set(&BadDebtDataKey::Shortfall(asset.clone()), &remaining);
+    } else {
+        storage.remove(&BadDebtDataKey::Shortfall(asset.clone()));
+    }
+    storage.set(&BadDebtDataKey::TotalCovered(asset.clone()), &total_covered);
+    storage.set(
+        &BadDebtDataKey::TotalSocialized(asset.clone()),
+        &total_socialized,
+    );
+
+    emit_bad_debt_socialized(
+        env,
+        BadDebtSocializedEvent {
+            caller,
+            asset,
+            shortfall,
+            covered_by_insurance,
+            socialized,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(BadDebtResolution {
+        covered_by_insurance,
+        socialized,
+    })
+}
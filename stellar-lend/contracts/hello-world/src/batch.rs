@@ -0,0 +1,137 @@
+//! # Batch Operations
+//!
+//! Lets a user run several core operations in one transaction, e.g.
+//! deposit + borrow or repay + withdraw.
+//!
+//! Actions run in order through the regular entrypoint logic, so every
+//! check (pauses, caps, collateral ratios, flash loan lock) applies to each
+//! step exactly as if it had been submitted on its own. Each step takes and
+//! releases its own reentrancy guard, so steps compose without the batch
+//! holding a lock of its own.
+//!
+//! The user authorizes the batch once and the steps do not ask again, so a
+//! single signature, or a single `__check_auth` call for a contract
+//! account, covers the whole batch.
+//!
+//! Batches are all-or-nothing: the first failing action aborts the batch
+//! and the whole invocation, including earlier actions, is rolled back. A
+//! single `BatchExecutedEvent` summarizes a successful batch in addition to
+//! the per-operation events.
+
+use soroban_sdk::{contracterror, contracttype, Address, Env, Vec};
+
+use crate::borrow::{borrow_asset, BorrowError};
+use crate::deposit::{deposit_collateral, DepositError};
+use crate::events::{emit_batch_executed, BatchExecutedEvent};
+use crate::repay::{repay_debt, RepayError};
+use crate::withdraw::{withdraw_collateral, WithdrawError};
+
+/// Maximum actions in one batch
+pub const MAX_BATCH_ACTIONS: u32 = 10;
+
+/// Errors that can occur in batch execution
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum BatchError {
+    /// The batch has no actions
+    EmptyBatch = 1,
+    /// The batch has more than `MAX_BATCH_ACTIONS` actions
+    TooManyActions = 2,
+    /// A deposit action failed
+    DepositFailed = 3,
+    /// A borrow action failed
+    BorrowFailed = 4,
+    /// A repay action failed
+    RepayFailed = 5,
+    /// A withdraw action failed
+    WithdrawFailed = 6,
+    /// Reentrancy detected
+    Reentrancy = 7,
+}
+
+/// A single step of a batch (asset, amount); None is native XLM
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProtocolAction {
+    Deposit(Option<Address>, i128),
+    Borrow(Option<Address>, i128),
+    Repay(Option<Address>, i128),
+    Withdraw(Option<Address>, i128),
+}
+
+/// Execute `actions` for `user` in order, all-or-nothing
+///
+/// # Returns
+/// One result per action: the new collateral balance for deposits and
+/// withdrawals, the total debt for borrows and the remaining debt for repays
+///
+/// # Errors
+/// * `EmptyBatch` / `TooManyActions` - Batch size out of range
+/// * `DepositFailed` / `BorrowFailed` / `RepayFailed` / `WithdrawFailed` -
+///   The named action failed; nothing in the batch is applied
+/// * `Reentrancy` - Called while another operation is in progress
+pub fn execute_batch(
+    env: &Env,
+    user: Address,
+    actions: Vec<ProtocolAction>,
+) -> Result<Vec<i128>, BatchError> {
+    if actions.is_empty() {
+        return Err(BatchError::EmptyBatch);
+    }
+    if actions.len() > MAX_BATCH_ACTIONS {
+        return Err(BatchError::TooManyActions);
+    }
+    user.require_auth();
+
+    let mut results = Vec::new(env);
+    let mut event = BatchExecutedEvent {
+        user: user.clone(),
+        actions: actions.len(),
+        deposited: 0,
+        borrowed: 0,
+        repaid: 0,
+        withdrawn: 0,
+        timestamp: env.ledger().timestamp(),
+    };
+    for action in actions.iter() {
+        let result = match action {
+            ProtocolAction::Deposit(asset, amount) => {
+                event.deposited = event.deposited.saturating_add(amount);
+                deposit_collateral(env, user.clone(), asset, amount).map_err(|e| match e {
+                    DepositError::Reentrancy => BatchError::Reentrancy,
+                    _ => BatchError::DepositFailed,
+                })?
+            }
+            ProtocolAction::Borrow(asset, amount) => {
+                event.borrowed = event.borrowed.saturating_add(amount);
+                borrow_asset(env, user.clone(), asset, amount).map_err(|e| match e {
+                    BorrowError::Reentrancy => BatchError::Reentrancy,
+                    _ => BatchError::BorrowFailed,
+                })?
+            }
+            ProtocolAction::Repay(asset, amount) => {
+                let (remaining_debt, interest_paid, principal_paid) =
+                    repay_debt(env, user.clone(), asset, amount).map_err(|e| match e {
+                        RepayError::Reentrancy => BatchError::Reentrancy,
+                        _ => BatchError::RepayFailed,
+                    })?;
+                event.repaid = event
+                    .repaid
+                    .saturating_add(interest_paid.saturating_add(principal_paid));
+                remaining_debt
+            }
+            ProtocolAction::Withdraw(asset, amount) => {
+                event.withdrawn = event.withdrawn.saturating_add(amount);
+                withdraw_collateral(env, user.clone(), asset, amount).map_err(|e| match e {
+                    WithdrawError::Reentrancy => BatchError::Reentrancy,
+                    _ => BatchError::WithdrawFailed,
+                })?
+            }
+        };
+        results.push_back(result);
+    }
+
+    emit_batch_executed(env, event);
+    Ok(results)
+}
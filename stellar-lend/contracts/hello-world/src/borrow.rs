@@ -15,7 +15,17 @@
 //! ## Invariants
 //! - A user must have collateral deposited before borrowing.
 //! - The collateral ratio must remain at or above the minimum after the borrow.
+//! - Borrowed volume must stay within the global and per-asset daily caps, if set.
 //! - Borrow amount must not exceed the maximum borrowable given current collateral.
+//! - Borrows above the risk hook threshold must be approved by the external
+//!   risk engine, if one is configured.
+//! - Positions flagged insolvent cannot borrow.
+//! - Assets whose price missed its oracle heartbeat cannot be borrowed until
+//!   the next fresh update.
+//! - Assets whose price circuit breaker tripped cannot be borrowed until it
+//!   is reset.
+//! - Stable-rate borrows require the stable rate mode to be enabled; see
+//!   the `stable_rate` module.
 
 #![allow(unused)]
 use soroban_sdk::{contracterror, Address, Env, IntoVal, Map, Symbol, Val, Vec};
@@ -26,6 +36,8 @@ use crate::deposit::{
     AssetParams, DepositDataKey, Position, ProtocolAnalytics, UserAnalytics,
 };
 use crate::events::{emit_borrow, BorrowEvent};
+use crate::operations::{record_operation, OperationKind};
+use crate::stable_rate::RateMode;
 
 /// Errors that can occur during borrow operations
 #[contracterror]
@@ -50,6 +62,28 @@ pub enum BorrowError {
     MaxBorrowExceeded = 8,
     /// Asset is not enabled for borrowing
     AssetNotEnabled = 9,
+    /// Called while a flash loan is outstanding
+    FlashLoanInProgress = 10,
+    /// Borrow would exceed the global or per-asset daily borrow cap
+    DailyBorrowCapExceeded = 11,
+    /// Borrow would exceed the asset's borrow cap
+    BorrowCapExceeded = 12,
+    /// Borrow would eat into the liquidity reserved by the utilization buffer
+    UtilizationBufferExceeded = 13,
+    /// Asset price is disputed by its oracle feeders
+    PriceDisputed = 14,
+    /// The external risk engine rejected the borrow
+    RiskCheckRejected = 15,
+    /// The position is flagged insolvent
+    PositionInsolvent = 16,
+    /// The compliance controls rejected the borrow
+    ComplianceRejected = 17,
+    /// Stable-rate borrowing is not enabled
+    StableRateDisabled = 18,
+    /// Asset price missed its oracle heartbeat
+    PriceDegraded = 19,
+    /// Asset's price circuit breaker is tripped
+    CircuitBreakerTripped = 20,
 }
 
 /// Minimum collateral ratio (in basis points, e.g., 15000 = 150%)
@@ -67,6 +101,8 @@ pub enum BorrowError {
 /// Uses the current borrow rate based on protocol utilization
 fn calculate_accrued_interest(
     env: &Env,
+    user: &Address,
+    asset: &Option<Address>,
     principal: i128,
     last_accrual_time: u64,
     current_time: u64,
@@ -75,38 +111,56 @@ fn calculate_accrued_interest(
         return Ok(0);
     }
 
-    if current_time <= last_accrual_time {
-        return Ok(0);
-    }
-
-    // Get current borrow rate (in basis points)
-    let rate_bps =
-        crate::interest_rate::calculate_borrow_rate(env).map_err(|_| BorrowError::Overflow)?;
-
-    // Calculate interest using the dynamic rate
-    crate::interest_rate::calculate_accrued_interest(
+    // Dynamic borrow rate, capped per user class with the gap absorbed by the subsidy budget
+    crate::interest_rate::calculate_user_accrued_interest(
+        env,
+        user,
+        asset,
         principal,
         last_accrual_time,
         current_time,
-        rate_bps,
     )
     .map_err(|_| BorrowError::Overflow)
 }
 
 /// Accrue interest on a position
-/// Updates the position's borrow_interest and last_accrual_time
-fn accrue_interest(env: &Env, position: &mut Position) -> Result<(), BorrowError> {
+/// Updates the position's borrow_interest and last_accrual_time, and
+/// snapshots the borrow index for the next accrual
+fn accrue_interest(
+    env: &Env,
+    user: &Address,
+    asset: &Option<Address>,
+    position: &mut Position,
+) -> Result<(), BorrowError> {
     let current_time = env.ledger().timestamp();
 
     if position.debt == 0 {
         position.borrow_interest = 0;
         position.last_accrual_time = current_time;
+        crate::interest_rate::checkpoint_user_borrow_index(env, user)
+            .map_err(|_| BorrowError::Overflow)?;
         return Ok(());
     }
 
     // Calculate new interest accrued using dynamic rate
-    let new_interest =
-        calculate_accrued_interest(env, position.debt, position.last_accrual_time, current_time)?;
+    let new_interest = calculate_accrued_interest(
+        env,
+        user,
+        asset,
+        position.debt,
+        position.last_accrual_time,
+        current_time,
+    )?;
+
+    // An open subsidy campaign on the debt asset absorbs part of it
+    let new_interest = crate::subsidy_campaign::apply_campaign_subsidy(
+        env,
+        asset,
+        position.debt,
+        position.last_accrual_time,
+        current_time,
+        new_interest,
+    );
 
     // Add to existing interest
     position.borrow_interest = position
@@ -114,8 +168,13 @@ fn accrue_interest(env: &Env, position: &mut Position) -> Result<(), BorrowError
         .checked_add(new_interest)
         .ok_or(BorrowError::Overflow)?;
 
+    // Pass the suppliers' share of the new interest to the supply pool
+    crate::deposit::accrue_supply_interest(env, asset, new_interest);
+
     // Update last accrual time
     position.last_accrual_time = current_time;
+    crate::interest_rate::checkpoint_user_borrow_index(env, user)
+        .map_err(|_| BorrowError::Overflow)?;
 
     Ok(())
 }
@@ -235,21 +294,57 @@ fn validate_collateral_ratio_after_borrow(
     Ok(())
 }
 
-/// Borrow assets from the protocol
+/// Borrow assets from the protocol at the variable rate
 pub fn borrow_asset(
     env: &Env,
     user: Address,
     asset: Option<Address>,
     amount: i128,
+) -> Result<i128, BorrowError> {
+    borrow_asset_with_rate_mode(env, user, asset, amount, RateMode::Variable)
+}
+
+/// Borrow assets from the protocol in the chosen rate mode
+///
+/// Stable borrows lock the current stable rate for the borrowed amount,
+/// blended with any stable debt the user already has.
+pub fn borrow_asset_with_rate_mode(
+    env: &Env,
+    user: Address,
+    asset: Option<Address>,
+    amount: i128,
+    rate_mode: RateMode,
+) -> Result<i128, BorrowError> {
+    borrow_asset_to(env, user.clone(), user, asset, amount, rate_mode)
+}
+
+/// Borrow against `user`'s position and pay the proceeds to `recipient`
+pub(crate) fn borrow_asset_to(
+    env: &Env,
+    user: Address,
+    recipient: Address,
+    asset: Option<Address>,
+    amount: i128,
+    rate_mode: RateMode,
 ) -> Result<i128, BorrowError> {
     // Validate amount
     if amount <= 0 {
         return Err(BorrowError::InvalidAmount);
     }
 
+    // Stable borrows need the stable rate mode to be enabled
+    if rate_mode == RateMode::Stable && !crate::stable_rate::get_stable_rate_config(env).enabled {
+        return Err(BorrowError::StableRateDisabled);
+    }
+
     // Check for reentrancy
     let _guard = crate::reentrancy::ReentrancyGuard::new(env).map_err(|_| BorrowError::Reentrancy)?;
 
+    // Flash loan receivers may not borrow while the loan is outstanding
+    if crate::reentrancy::is_flash_loan_locked(env) {
+        return Err(BorrowError::FlashLoanInProgress);
+    }
+
     // Check if borrows are paused
     let pause_switches_key = DepositDataKey::PauseSwitches;
     if let Some(pause_map) = env
@@ -274,6 +369,20 @@ pub fn borrow_asset(
             return Err(BorrowError::InvalidAsset);
         }
 
+        // Disagreeing oracle feeders block new borrows until resolved
+        if crate::oracle::is_price_disputed(env, asset_addr) {
+            return Err(BorrowError::PriceDisputed);
+        }
+
+        // A missed heartbeat blocks new borrows until the next fresh update
+        if crate::oracle::check_price_heartbeat(env, asset_addr) {
+            return Err(BorrowError::PriceDegraded);
+        }
+
+        if crate::oracle::is_circuit_breaker_tripped(env, asset_addr) {
+            return Err(BorrowError::CircuitBreakerTripped);
+        }
+
         // Check asset parameters
         let asset_params_key = DepositDataKey::AssetParams(asset_addr.clone());
         if let Some(params) = env
@@ -301,8 +410,13 @@ pub fn borrow_asset(
             last_accrual_time: timestamp,
         });
 
+    // Positions found insolvent are restricted until they are solvent again
+    if crate::bad_debt::is_insolvent(env, &user) {
+        return Err(BorrowError::PositionInsolvent);
+    }
+
     // Accrue interest on existing debt before borrowing
-    accrue_interest(env, &mut position)?;
+    accrue_interest(env, &user, &asset, &mut position)?;
 
     // Get current collateral balance
     let collateral_key = DepositDataKey::CollateralBalance(user.clone());
@@ -382,19 +496,67 @@ pub fn borrow_asset(
         .checked_div(10000)
         .ok_or(BorrowError::Overflow)?;
 
+    // Protocol origination fee, accrued to the fee collector
+    let origination_fee =
+        crate::fees::charge_fee(env, crate::fees::FeeKind::Origination, &user, &asset, amount)
+            .map_err(|_| BorrowError::Overflow)?;
+
     // Amount user actually receives
-    let receive_amount = amount.checked_sub(fee_amount).ok_or(BorrowError::Overflow)?;
+    let receive_amount = amount
+        .checked_sub(fee_amount)
+        .and_then(|amount| amount.checked_sub(origination_fee))
+        .ok_or(BorrowError::Overflow)?;
 
     if receive_amount <= 0 {
         return Err(BorrowError::InvalidAmount);
     }
 
+    // Enforce the per-asset borrow cap and utilization buffer
+    crate::cross_asset::check_borrow_cap(env, asset.clone(), amount).map_err(|e| {
+        if e == crate::cross_asset::CrossAssetError::UtilizationBufferExceeded {
+            BorrowError::UtilizationBufferExceeded
+        } else {
+            BorrowError::BorrowCapExceeded
+        }
+    })?;
+
+    // Enforce daily borrow volume circuit breakers
+    crate::risk_management::record_daily_borrow(env, asset.clone(), amount).map_err(|e| {
+        if e == crate::risk_management::RiskManagementError::DailyBorrowCapExceeded {
+            BorrowError::DailyBorrowCapExceeded
+        } else {
+            BorrowError::Overflow
+        }
+    })?;
+
+    // Regulated deployments gate borrows on allowlists, caps and their hook
+    crate::compliance::check_operation(env, &user, Symbol::new(env, "borrow"), &asset, amount)
+        .map_err(|_| BorrowError::ComplianceRejected)?;
+
+    // Large borrows may be vetoed by the external risk engine
+    crate::risk_hook::check_operation(env, &user, Symbol::new(env, "borrow"), &asset, amount)
+        .map_err(|_| BorrowError::RiskCheckRejected)?;
+
+    // Track the stable share of the debt apart from the variable share
+    crate::stable_rate::record_borrow(env, &user, &asset, position.debt, amount, rate_mode)
+        .map_err(|_| BorrowError::Overflow)?;
+
     // Update position
     position.debt = new_debt;
     position.last_accrual_time = timestamp;
     env.storage().persistent().set(&position_key, &position);
+    crate::snapshot::index_user(env, &user);
+    crate::cross_asset::record_borrow_change(env, asset.clone(), amount);
+    crate::deposit::update_asset_ledger(env, &asset, 0, amount);
+    crate::rewards::record_balance_change(
+        env,
+        &user,
+        &asset,
+        crate::rewards::RewardSide::Borrow,
+        amount,
+    );
 
-    // Handle asset transfer - contract sends tokens to user
+    // Handle asset transfer - contract sends tokens to the recipient
     if let Some(ref asset_addr) = asset {
         // Skip actual token transfers in unit tests to avoid Storage error with non-existent contracts
         #[cfg(not(test))]
@@ -409,10 +571,12 @@ pub fn borrow_asset(
 
             token_client.transfer(
                 &env.current_contract_address(),
-                &user,
+                &recipient,
                 &receive_amount,
             );
         }
+        #[cfg(test)]
+        let _ = (asset_addr, &recipient);
 
         // Credit fee to protocol reserve
         if fee_amount > 0 {
@@ -426,6 +590,12 @@ pub fn borrow_asset(
                 &reserve_key,
                 &(current_reserve.checked_add(fee_amount).ok_or(BorrowError::Overflow)?),
             );
+            crate::financials::record(
+                env,
+                crate::financials::FinancialItem::ProtocolFees,
+                &asset,
+                fee_amount,
+            );
         }
     }
 
@@ -450,6 +620,8 @@ pub fn borrow_asset(
     })?;
 
     // Emit borrow event
+    let operation_id =
+        record_operation(env, OperationKind::Borrow, &user, &asset, amount, timestamp);
     emit_borrow(
         env,
         BorrowEvent {
@@ -457,6 +629,7 @@ pub fn borrow_asset(
             asset: asset.clone(),
             amount,
             timestamp,
+            operation_id,
         },
     );
 
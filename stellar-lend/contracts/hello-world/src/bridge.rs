@@ -1,5 +1,7 @@
 #![allow(dead_code)]
-use soroban_sdk::{contracterror, contracttype, symbol_short, Address, Env, Map, Symbol};
+use soroban_sdk::{contracterror, contracttype, symbol_short, Address, Env, Map, Symbol, Vec};
+
+use crate::storage::{GovernanceDataKey, GuardianConfig};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -22,12 +24,295 @@ pub enum BridgeError {
     InvalidFee = 5,
     InvalidAmount = 6,
     AssetNotSupported = 7,
+    InvalidBatchSize = 8,
+    InvalidValidatorSet = 9,
+    NotValidator = 10,
+    AlreadyAttested = 11,
+    MessageNotFound = 12,
+    MessageNotPending = 13,
+    AttestationRequired = 14,
+    InvalidChallengeWindow = 15,
+    TransferNotFound = 16,
+    TransferNotPending = 17,
+    ChallengeWindowOpen = 18,
+    ChallengeWindowClosed = 19,
+    RateLimitExceeded = 20,
+    OutflowsPaused = 21,
+    InvalidRateLimit = 22,
+    TransferNotExpired = 23,
+    TooManyPendingTransfers = 24,
+    TooManyRemoteCollateralEntries = 25,
+    InvalidRemoteCollateralConfig = 26,
+}
+
+/// Processing state of an inbound bridge message
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BridgeMessageStatus {
+    /// Waiting in the queue
+    Pending,
+    /// Applied to the user's position
+    Processed,
+    /// Could not be applied; skipped so later messages are not blocked
+    Failed,
+}
+
+/// What an inbound message asks the protocol to do
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BridgeMessageKind {
+    /// Credit a deposit bridged in to the user's collateral
+    Deposit,
+    /// Record the amount the user has locked as collateral on the remote network
+    RemoteCollateral,
+}
+
+/// Attested inbound message from a remote network
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BridgeMessage {
+    /// Position in the network's queue (assigned on enqueue, starting at 0)
+    pub nonce: u64,
+    /// What the message does
+    pub kind: BridgeMessageKind,
+    /// User the message is for
+    pub user: Address,
+    /// Asset concerned (None for native XLM)
+    pub asset: Option<Address>,
+    /// Deposit: gross amount bridged in, before the bridge fee.
+    /// RemoteCollateral: total amount locked remotely (0 releases it)
+    pub amount: i128,
+    /// Ledger timestamp at which the message was enqueued
+    pub enqueued_at: u64,
+    /// Processing state
+    pub status: BridgeMessageStatus,
+}
+
+/// Queue cursors for a bridge: messages in `[head, tail)` are pending
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BridgeQueueState {
+    /// Nonce of the next message to process
+    pub head: u64,
+    /// Nonce that will be assigned to the next enqueued message
+    pub tail: u64,
+}
+
+/// Outcome of a `process_bridge_messages` call
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BridgeProcessResult {
+    /// Messages applied successfully
+    pub processed: u32,
+    /// Messages marked as failed and skipped
+    pub failed: u32,
+    /// Messages still pending after this call
+    pub remaining: u64,
+}
+
+/// Breakdown of the bridge fee on an amount
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BridgeFeeQuote {
+    /// Gross amount the fee is taken from
+    pub amount: i128,
+    /// Total fee deducted
+    pub total_fee: i128,
+    /// Part of the fee kept by the protocol
+    pub protocol_fee: i128,
+    /// Part of the fee owed to the relayers carrying the transfer
+    pub relayer_fee: i128,
+    /// Amount left after the fee
+    pub net_amount: i128,
+}
+
+/// Validators whose attestations inbound messages of a bridge need
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BridgeValidatorSet {
+    /// Addresses allowed to attest messages
+    pub validators: Vec<Address>,
+    /// Attestations a message needs before it is applied
+    pub threshold: u32,
+}
+
+/// Lifecycle of an outbound bridge transfer
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BridgeTransferStatus {
+    /// Collateral withdrawn; waiting for the relayer to carry it out
+    Pending,
+    /// Carried out to the remote network by the relayer
+    Completed,
+    /// Not completed in time; the collateral was returned to the user
+    Refunded,
+    /// Vetoed by a guardian; the collateral was returned to the user
+    Vetoed,
+}
+
+/// Outbound transfer created by `bridge_withdraw`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BridgeTransfer {
+    /// Transfer id (starting at 1)
+    pub id: u64,
+    /// Remote network ID
+    pub network_id: u32,
+    /// User whose collateral was withdrawn
+    pub user: Address,
+    /// Asset withdrawn (None for native XLM)
+    pub asset: Option<Address>,
+    /// Fee breakdown fixed when the transfer was requested
+    pub quote: BridgeFeeQuote,
+    /// Ledger timestamp of the request
+    pub created_at: u64,
+    /// Timestamp from which the relayer can complete the transfer
+    pub challenge_ends_at: u64,
+    /// Timestamp from which the user can refund an uncompleted transfer
+    pub refundable_at: u64,
+    /// Lifecycle state
+    pub status: BridgeTransferStatus,
+}
+
+/// Collateral a user has locked on a remote network, as last attested
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RemoteCollateral {
+    /// Network the collateral is locked on
+    pub network_id: u32,
+    /// Local asset the collateral is valued as (None for native XLM)
+    pub asset: Option<Address>,
+    /// Amount locked
+    pub amount: i128,
+    /// Timestamp at which the bridge reported the amount
+    pub attested_at: u64,
+}
+
+/// How remote collateral counts toward local borrow capacity
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RemoteCollateralConfig {
+    /// Haircut applied on top of the asset's liquidation threshold (bps)
+    pub haircut_bps: i128,
+    /// Attestations older than this (seconds) no longer count
+    pub max_age: u64,
+}
+
+/// Amount bridged out within a single epoch
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BridgeOutflowWindow {
+    /// Timestamp at which the epoch started
+    pub window_start: u64,
+    /// Gross amount bridged out in this epoch
+    pub amount: i128,
+}
+
+/// Outflow cap usage in the current epoch
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BridgeUtilization {
+    /// Configured cap (None = unlimited)
+    pub cap: Option<i128>,
+    /// Gross amount bridged out in the current epoch
+    pub used: i128,
+    /// Amount left under the cap (None = unlimited)
+    pub remaining: Option<i128>,
+    /// `used` as a share of the cap in basis points (0 without a cap)
+    pub utilization_bps: i128,
+    /// Timestamp at which the current epoch ends and usage resets
+    pub window_end: u64,
+}
+
+/// Storage keys for the inbound message queues, fee split, verification and
+/// rate limits
+#[contracttype]
+#[derive(Clone)]
+pub enum BridgeDataKey {
+    /// Queue cursors per network id
+    Queue(u32),
+    /// Message by (network id, nonce)
+    Message(u32, u64),
+    /// Share of the bridge fee owed to relayers per network id (bps)
+    RelayerShare(u32),
+    /// Validator set per network id
+    /// Value type: BridgeValidatorSet
+    Validators(u32),
+    /// Validators that attested a message, by (network id, nonce)
+    /// Value type: Vec<Address>
+    Attestations(u32, u64),
+    /// Challenge window for outbound transfers per network id (seconds)
+    /// Value type: u64
+    ChallengeWindow(u32),
+    /// Next transfer id to assign
+    /// Value type: u64
+    NextTransferId,
+    /// Outbound transfer by id
+    /// Value type: BridgeTransfer
+    Transfer(u64),
+    /// Ids of pending transfers per network id
+    /// Value type: Vec<u64>
+    PendingTransfers(u32),
+    /// Ids of a user's most recent transfers, oldest first
+    /// Value type: Vec<u64>
+    UserTransfers(Address),
+    /// Collateral a user has locked on remote networks
+    /// Value type: Vec<RemoteCollateral>
+    RemoteCollateral(Address),
+    /// Recognition of remote collateral (absent = not recognized)
+    /// Value type: RemoteCollateralConfig
+    RemoteCollateralConfig,
+    /// Cap on the gross amount bridged out per epoch per network id, all
+    /// assets combined (absent = unlimited)
+    /// Value type: i128
+    OutflowCap(u32),
+    /// Cap on the amount of an asset bridged out per epoch, by (network id,
+    /// asset) (absent = unlimited)
+    /// Value type: i128
+    AssetOutflowCap(u32, Option<Address>),
+    /// Amount bridged out in the current epoch per network id
+    /// Value type: BridgeOutflowWindow
+    Outflow(u32),
+    /// Amount of an asset bridged out in the current epoch
+    /// Value type: BridgeOutflowWindow
+    AssetOutflow(u32, Option<Address>),
+    /// Whether withdrawals through a bridge are paused per network id
+    /// Value type: bool
+    OutflowsPaused(u32),
 }
 
 // Storage keys
 const ADMIN: Symbol = symbol_short!("admin");
 const BRIDGES: Symbol = symbol_short!("bridges");
 
+/// Maximum number of messages processed in a single call
+pub const MAX_MESSAGES_PER_BATCH: u32 = 50;
+
+/// Maximum number of validators per bridge
+pub const MAX_BRIDGE_VALIDATORS: u32 = 20;
+
+/// Longest challenge window a bridge may hold transfers for (7 days)
+pub const MAX_CHALLENGE_WINDOW: u64 = 7 * 24 * 60 * 60;
+
+/// Time after the challenge window before a user can refund a transfer the
+/// relayer has not completed (24 hours)
+pub const BRIDGE_TRANSFER_TIMEOUT: u64 = 24 * 60 * 60;
+
+/// Maximum number of pending transfers per bridge
+pub const MAX_PENDING_TRANSFERS: u32 = 200;
+
+/// Number of transfers kept in each user's history
+pub const MAX_USER_TRANSFER_HISTORY: u32 = 50;
+
+/// Maximum number of (network, asset) remote collateral entries per user
+pub const MAX_REMOTE_COLLATERAL_ENTRIES: u32 = 10;
+
+/// Longest a remote collateral attestation may be configured to stay valid (7 days)
+pub const MAX_REMOTE_COLLATERAL_AGE: u64 = 7 * 24 * 60 * 60;
+
+/// Length of the epoch outflow caps apply to (24 hours, UTC-aligned)
+pub const BRIDGE_EPOCH_SECONDS: u64 = 24 * 60 * 60;
+
 fn require_admin(env: &Env, caller: &Address) -> Result<(), BridgeError> {
     let admin: Address = env
         .storage()
@@ -125,9 +410,85 @@ pub fn set_bridge_fee(
     Ok(())
 }
 
+/// Get the share of a bridge's fee owed to relayers (bps, 0 if unset)
+pub fn get_bridge_relayer_share(env: &Env, network_id: u32) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&BridgeDataKey::RelayerShare(network_id))
+        .unwrap_or(0)
+}
+
+/// Set the share of a bridge's fee owed to relayers
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `caller` - Admin address for authorization
+/// * `network_id` - ID of the remote network
+/// * `share_bps` - Relayer share of the fee in basis points
+pub fn set_bridge_relayer_share(
+    env: &Env,
+    caller: Address,
+    network_id: u32,
+    share_bps: i128,
+) -> Result<(), BridgeError> {
+    require_admin(env, &caller)?;
+
+    if !(0..=10000).contains(&share_bps) {
+        return Err(BridgeError::InvalidFee);
+    }
+    get_bridge_config(env, network_id)?;
+
+    env.storage()
+        .persistent()
+        .set(&BridgeDataKey::RelayerShare(network_id), &share_bps);
+    Ok(())
+}
+
+/// Quote the bridge fee on an amount before bridging it
+///
+/// Uses the same split as `bridge_deposit`, `bridge_withdraw` and message
+/// processing; exit-exempt accounts pay nothing on withdrawal.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `network_id` - Remote network ID
+/// * `asset` - Asset to bridge
+/// * `amount` - Gross amount to bridge
+pub fn quote_bridge_fee(
+    env: &Env,
+    network_id: u32,
+    asset: Option<Address>,
+    amount: i128,
+) -> Result<BridgeFeeQuote, BridgeError> {
+    if amount <= 0 {
+        return Err(BridgeError::InvalidAmount);
+    }
+
+    let config = get_bridge_config(env, network_id)?;
+    crate::cross_asset::get_asset_config_by_address(env, asset)
+        .map_err(|_| BridgeError::AssetNotSupported)?;
+
+    Ok(fee_quote(env, network_id, &config, amount))
+}
+
+/// Split the fee on `amount` between the protocol and the relayers
+fn fee_quote(env: &Env, network_id: u32, config: &BridgeConfig, amount: i128) -> BridgeFeeQuote {
+    let total_fee = (amount * config.fee_bps) / 10000;
+    let relayer_fee = (total_fee * get_bridge_relayer_share(env, network_id)) / 10000;
+    BridgeFeeQuote {
+        amount,
+        total_fee,
+        protocol_fee: total_fee - relayer_fee,
+        relayer_fee,
+        net_amount: amount - total_fee,
+    }
+}
+
 /// Initiate deposit to bridge
 ///
-/// Moves user assets into the lending protocol from a bridge.
+/// Moves user assets into the lending protocol from a bridge. Once the bridge
+/// has a validator set, deposits must arrive as attested queue messages
+/// instead and this returns `AttestationRequired`.
 /// 
 /// # Arguments
 /// * `env` - The contract environment
@@ -150,22 +511,37 @@ pub fn bridge_deposit(
     if !config.is_active {
         return Err(BridgeError::BridgeNotActive);
     }
+    if get_bridge_validators(env, network_id).is_some() {
+        return Err(BridgeError::AttestationRequired);
+    }
     
     // Ensure asset is configured in the protocol
     crate::cross_asset::get_asset_config_by_address(env, asset.clone())
         .map_err(|_| BridgeError::AssetNotSupported)?;
         
     // Calculate and deduct fee
-    let fee = (amount * config.fee_bps) / 10000;
-    let deposit_amount = amount - fee;
+    let quote = fee_quote(env, network_id, &config, amount);
+    let deposit_amount = quote.net_amount;
 
     // Simulate cross chain bridging by wrapping standard deposit
-    crate::cross_asset::cross_asset_deposit(env, user.clone(), asset, deposit_amount)
+    crate::cross_asset::cross_asset_deposit(env, user.clone(), asset.clone(), deposit_amount)
         .map_err(|_| BridgeError::InvalidAmount)?;
+    crate::financials::record(
+        env,
+        crate::financials::FinancialItem::BridgeFees,
+        &asset,
+        quote.protocol_fee,
+    );
 
     env.events().publish(
         (symbol_short!("bridge"), symbol_short!("deposit"), network_id),
-        (user, deposit_amount, fee),
+        (
+            user,
+            deposit_amount,
+            quote.total_fee,
+            quote.protocol_fee,
+            quote.relayer_fee,
+        ),
     );
 
     Ok(deposit_amount)
@@ -174,6 +550,15 @@ pub fn bridge_deposit(
 /// Initiate withdrawal through a bridge
 ///
 /// Withdraws lending collateral and initiates a bridge transfer to remote chain.
+/// Accounts on the exit exemption list pay no bridge fee. The gross amount
+/// counts toward the bridge's outflow caps; a withdrawal that would exceed one
+/// is rejected, and one that fills a cap pauses outflows until the admin
+/// resumes them.
+///
+/// The withdrawal becomes a pending [`BridgeTransfer`]. The bridge's relayer
+/// completes it once the challenge window (if any) has passed, and a guardian
+/// may veto it until then. If the relayer has not completed it
+/// `BRIDGE_TRANSFER_TIMEOUT` after the window, the user can refund it.
 /// 
 /// # Arguments
 /// * `env` - The contract environment
@@ -196,19 +581,1046 @@ pub fn bridge_withdraw(
     if !config.is_active {
         return Err(BridgeError::BridgeNotActive);
     }
+    record_outflow(env, network_id, &asset, amount)?;
 
     // Attempt internal withdrawal
     crate::cross_asset::cross_asset_withdraw(env, user.clone(), asset.clone(), amount)
         .map_err(|_| BridgeError::InvalidAmount)?;
 
-    // Calculate and deduct fee for the withdrawal execution
-    let fee = (amount * config.fee_bps) / 10000;
-    let withdraw_amount = amount - fee;
+    // Calculate and deduct fee for the withdrawal execution; protocol-owned
+    // accounts on the exit exemption list pay none
+    let mut quote = fee_quote(env, network_id, &config, amount);
+    if crate::risk_management::use_exit_exemption(
+        env,
+        &user,
+        Symbol::new(env, "bridge_withdraw"),
+        quote.total_fee,
+    ) {
+        quote = BridgeFeeQuote {
+            amount,
+            total_fee: 0,
+            protocol_fee: 0,
+            relayer_fee: 0,
+            net_amount: amount,
+        };
+    }
+    let withdraw_amount = quote.net_amount;
+
+    let mut pending = get_pending_transfer_ids(env, network_id);
+    if pending.len() >= MAX_PENDING_TRANSFERS {
+        return Err(BridgeError::TooManyPendingTransfers);
+    }
+    let id: u64 = env
+        .storage()
+        .persistent()
+        .get(&BridgeDataKey::NextTransferId)
+        .unwrap_or(1);
+    let now = env.ledger().timestamp();
+    let challenge_ends_at = now + get_bridge_challenge_window(env, network_id);
+    let transfer = BridgeTransfer {
+        id,
+        network_id,
+        user: user.clone(),
+        asset,
+        quote,
+        created_at: now,
+        challenge_ends_at,
+        refundable_at: challenge_ends_at + BRIDGE_TRANSFER_TIMEOUT,
+        status: BridgeTransferStatus::Pending,
+    };
+    env.storage()
+        .persistent()
+        .set(&BridgeDataKey::Transfer(id), &transfer);
+    env.storage()
+        .persistent()
+        .set(&BridgeDataKey::NextTransferId, &(id + 1));
+
+    pending.push_back(id);
+    env.storage()
+        .persistent()
+        .set(&BridgeDataKey::PendingTransfers(network_id), &pending);
+    let mut history = get_user_transfer_ids(env, &user);
+    if history.len() >= MAX_USER_TRANSFER_HISTORY {
+        history.pop_front();
+    }
+    history.push_back(id);
+    env.storage()
+        .persistent()
+        .set(&BridgeDataKey::UserTransfers(user.clone()), &history);
 
     env.events().publish(
-        (symbol_short!("bridge"), symbol_short!("withdraw"), network_id),
-        (user, withdraw_amount, fee),
+        (
+            symbol_short!("bridge"),
+            symbol_short!("xfer_pend"),
+            network_id,
+        ),
+        (id, user, withdraw_amount, challenge_ends_at),
     );
 
     Ok(withdraw_amount)
 }
+
+/// Record the protocol's fee and emit the withdrawal to the remote network
+fn release_withdrawal(
+    env: &Env,
+    network_id: u32,
+    user: Address,
+    asset: &Option<Address>,
+    quote: &BridgeFeeQuote,
+) {
+    crate::financials::record(
+        env,
+        crate::financials::FinancialItem::BridgeFees,
+        asset,
+        quote.protocol_fee,
+    );
+
+    env.events().publish(
+        (symbol_short!("bridge"), symbol_short!("withdraw"), network_id),
+        (
+            user,
+            quote.net_amount,
+            quote.total_fee,
+            quote.protocol_fee,
+            quote.relayer_fee,
+        ),
+    );
+}
+
+/// Get the challenge window transfers of a bridge are held for (0 if unset)
+pub fn get_bridge_challenge_window(env: &Env, network_id: u32) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&BridgeDataKey::ChallengeWindow(network_id))
+        .unwrap_or(0)
+}
+
+/// Set how long transfers through a bridge wait before they can be completed
+///
+/// Zero lets the relayer complete transfers immediately. Transfers already
+/// pending keep the window they were created with.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `caller` - Admin address for authorization
+/// * `network_id` - ID of the remote network
+/// * `window` - Challenge window in seconds (at most `MAX_CHALLENGE_WINDOW`)
+pub fn set_bridge_challenge_window(
+    env: &Env,
+    caller: Address,
+    network_id: u32,
+    window: u64,
+) -> Result<(), BridgeError> {
+    require_admin(env, &caller)?;
+
+    if window > MAX_CHALLENGE_WINDOW {
+        return Err(BridgeError::InvalidChallengeWindow);
+    }
+    get_bridge_config(env, network_id)?;
+
+    env.storage()
+        .persistent()
+        .set(&BridgeDataKey::ChallengeWindow(network_id), &window);
+    Ok(())
+}
+
+/// Get an outbound transfer by id
+pub fn get_bridge_transfer(env: &Env, id: u64) -> Option<BridgeTransfer> {
+    env.storage().persistent().get(&BridgeDataKey::Transfer(id))
+}
+
+fn get_pending_transfer_ids(env: &Env, network_id: u32) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&BridgeDataKey::PendingTransfers(network_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn get_user_transfer_ids(env: &Env, user: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&BridgeDataKey::UserTransfers(user.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn load_transfers(env: &Env, ids: Vec<u64>) -> Vec<BridgeTransfer> {
+    let mut transfers = Vec::new(env);
+    for id in ids.iter() {
+        if let Some(transfer) = get_bridge_transfer(env, id) {
+            transfers.push_back(transfer);
+        }
+    }
+    transfers
+}
+
+/// List a bridge's pending transfers, oldest first
+pub fn list_pending_transfers(env: &Env, network_id: u32) -> Vec<BridgeTransfer> {
+    load_transfers(env, get_pending_transfer_ids(env, network_id))
+}
+
+/// List a user's most recent transfers (up to `MAX_USER_TRANSFER_HISTORY`),
+/// oldest first, in every state
+pub fn get_user_transfers(env: &Env, user: Address) -> Vec<BridgeTransfer> {
+    load_transfers(env, get_user_transfer_ids(env, &user))
+}
+
+/// Load a pending transfer
+fn pending_transfer(env: &Env, id: u64) -> Result<BridgeTransfer, BridgeError> {
+    let transfer = get_bridge_transfer(env, id).ok_or(BridgeError::TransferNotFound)?;
+    if transfer.status != BridgeTransferStatus::Pending {
+        return Err(BridgeError::TransferNotPending);
+    }
+    Ok(transfer)
+}
+
+/// Store a transfer that left the pending state and drop it from the list
+fn close_transfer(env: &Env, transfer: &BridgeTransfer) {
+    env.storage()
+        .persistent()
+        .set(&BridgeDataKey::Transfer(transfer.id), transfer);
+
+    let mut pending = get_pending_transfer_ids(env, transfer.network_id);
+    if let Some(index) = pending.first_index_of(transfer.id) {
+        pending.remove(index);
+    }
+    env.storage().persistent().set(
+        &BridgeDataKey::PendingTransfers(transfer.network_id),
+        &pending,
+    );
+}
+
+/// Complete a pending transfer once its challenge window has ended
+///
+/// Called by the bridge's relayer (its registered bridge contract) after
+/// carrying the transfer out. The fee recorded is the one quoted at request
+/// time.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `relayer` - The registered bridge contract (must authorize)
+/// * `id` - The transfer to complete
+///
+/// # Returns
+/// The net amount carried out to the remote network
+///
+/// # Errors
+/// * `TransferNotFound` - No transfer with this id
+/// * `TransferNotPending` - Already completed, refunded or vetoed
+/// * `NotAuthorized` - Caller is not the transfer's bridge contract
+/// * `ChallengeWindowOpen` - The challenge window has not ended yet
+pub fn complete_bridge_transfer(env: &Env, relayer: Address, id: u64) -> Result<i128, BridgeError> {
+    let mut transfer = pending_transfer(env, id)?;
+    let config = get_bridge_config(env, transfer.network_id)?;
+    if relayer != config.bridge_address {
+        return Err(BridgeError::NotAuthorized);
+    }
+    relayer.require_auth();
+    if env.ledger().timestamp() < transfer.challenge_ends_at {
+        return Err(BridgeError::ChallengeWindowOpen);
+    }
+
+    transfer.status = BridgeTransferStatus::Completed;
+    close_transfer(env, &transfer);
+
+    release_withdrawal(
+        env,
+        transfer.network_id,
+        transfer.user,
+        &transfer.asset,
+        &transfer.quote,
+    );
+    Ok(transfer.quote.net_amount)
+}
+
+/// Refund a transfer the relayer did not complete in time
+///
+/// The gross amount goes back to the user's collateral and no fee is taken.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `user` - The transfer's user (must authorize)
+/// * `id` - The transfer to refund
+///
+/// # Errors
+/// * `TransferNotFound` - No transfer with this id
+/// * `TransferNotPending` - Already completed, refunded or vetoed
+/// * `NotAuthorized` - Caller is not the transfer's user
+/// * `TransferNotExpired` - `refundable_at` has not been reached
+pub fn refund_bridge_transfer(env: &Env, user: Address, id: u64) -> Result<i128, BridgeError> {
+    let mut transfer = pending_transfer(env, id)?;
+    if user != transfer.user {
+        return Err(BridgeError::NotAuthorized);
+    }
+    user.require_auth();
+    if env.ledger().timestamp() < transfer.refundable_at {
+        return Err(BridgeError::TransferNotExpired);
+    }
+
+    transfer.status = BridgeTransferStatus::Refunded;
+    close_transfer(env, &transfer);
+    crate::cross_asset::restore_collateral(
+        env,
+        user.clone(),
+        transfer.asset.clone(),
+        transfer.quote.amount,
+    );
+
+    env.events().publish(
+        (
+            symbol_short!("bridge"),
+            symbol_short!("xfer_rfnd"),
+            transfer.network_id,
+        ),
+        (id, user, transfer.quote.amount),
+    );
+    Ok(transfer.quote.amount)
+}
+
+/// Veto a pending transfer during its challenge window (guardians only)
+///
+/// The gross amount goes back to the user's collateral and no fee is taken.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `guardian` - A governance guardian (must authorize)
+/// * `id` - The transfer to veto
+///
+/// # Errors
+/// * `NotAuthorized` - Caller is not a guardian
+/// * `TransferNotFound` - No transfer with this id
+/// * `TransferNotPending` - Already completed, refunded or vetoed
+/// * `ChallengeWindowClosed` - The challenge window has ended
+pub fn veto_bridge_transfer(env: &Env, guardian: Address, id: u64) -> Result<(), BridgeError> {
+    let is_guardian = env
+        .storage()
+        .instance()
+        .get::<GovernanceDataKey, GuardianConfig>(&GovernanceDataKey::GuardianConfig)
+        .map(|config| config.guardians.contains(&guardian))
+        .unwrap_or(false);
+    if !is_guardian {
+        return Err(BridgeError::NotAuthorized);
+    }
+    guardian.require_auth();
+
+    let mut transfer = pending_transfer(env, id)?;
+    if env.ledger().timestamp() >= transfer.challenge_ends_at {
+        return Err(BridgeError::ChallengeWindowClosed);
+    }
+
+    transfer.status = BridgeTransferStatus::Vetoed;
+    close_transfer(env, &transfer);
+    crate::cross_asset::restore_collateral(
+        env,
+        transfer.user.clone(),
+        transfer.asset.clone(),
+        transfer.quote.amount,
+    );
+
+    env.events().publish(
+        (
+            symbol_short!("bridge"),
+            symbol_short!("xfer_veto"),
+            transfer.network_id,
+        ),
+        (id, guardian, transfer.user, transfer.quote.amount),
+    );
+    Ok(())
+}
+
+/// Get the validator set of a bridge, if one is registered
+pub fn get_bridge_validators(env: &Env, network_id: u32) -> Option<BridgeValidatorSet> {
+    env.storage()
+        .persistent()
+        .get(&BridgeDataKey::Validators(network_id))
+}
+
+/// Register the validators that must attest a bridge's inbound messages
+///
+/// Replaces any previous set. Attestations already recorded only count while
+/// their validator stays in the set. An empty list with a zero threshold
+/// removes the set, leaving the bridge contract's authorization as the only
+/// check.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `caller` - Admin address for authorization
+/// * `network_id` - ID of the remote network
+/// * `validators` - Distinct validator addresses (at most `MAX_BRIDGE_VALIDATORS`)
+/// * `threshold` - Attestations required per message (1..=validators)
+pub fn register_bridge_validators(
+    env: &Env,
+    caller: Address,
+    network_id: u32,
+    validators: Vec<Address>,
+    threshold: u32,
+) -> Result<(), BridgeError> {
+    require_admin(env, &caller)?;
+    get_bridge_config(env, network_id)?;
+
+    let key = BridgeDataKey::Validators(network_id);
+    if validators.is_empty() && threshold == 0 {
+        env.storage().persistent().remove(&key);
+        return Ok(());
+    }
+    if threshold == 0 || threshold > validators.len() || validators.len() > MAX_BRIDGE_VALIDATORS {
+        return Err(BridgeError::InvalidValidatorSet);
+    }
+    for (i, validator) in validators.iter().enumerate() {
+        if validators.first_index_of(&validator) != Some(i as u32) {
+            return Err(BridgeError::InvalidValidatorSet);
+        }
+    }
+
+    let set = BridgeValidatorSet {
+        validators,
+        threshold,
+    };
+    env.storage().persistent().set(&key, &set);
+
+    env.events().publish(
+        (
+            symbol_short!("bridge"),
+            symbol_short!("vals_set"),
+            network_id,
+        ),
+        (set.validators.len(), threshold),
+    );
+    Ok(())
+}
+
+/// Get the validators that attested an inbound message
+pub fn get_bridge_attestations(env: &Env, network_id: u32, nonce: u64) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&BridgeDataKey::Attestations(network_id, nonce))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Record a validator's attestation of a pending inbound message
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `validator` - A validator of the bridge (must authorize)
+/// * `network_id` - Remote network ID
+/// * `nonce` - Nonce of the message being attested
+///
+/// # Returns
+/// Attestations from current validators the message now has
+///
+/// # Errors
+/// * `NotValidator` - No validator set, or the caller is not in it
+/// * `MessageNotFound` - No message with this nonce
+/// * `MessageNotPending` - The message was already processed or failed
+/// * `AlreadyAttested` - The validator already attested this message
+pub fn attest_bridge_message(
+    env: &Env,
+    validator: Address,
+    network_id: u32,
+    nonce: u64,
+) -> Result<u32, BridgeError> {
+    let set = get_bridge_validators(env, network_id).ok_or(BridgeError::NotValidator)?;
+    if !set.validators.contains(&validator) {
+        return Err(BridgeError::NotValidator);
+    }
+    validator.require_auth();
+
+    let message = get_bridge_message(env, network_id, nonce).ok_or(BridgeError::MessageNotFound)?;
+    if message.status != BridgeMessageStatus::Pending {
+        return Err(BridgeError::MessageNotPending);
+    }
+
+    let mut attestations = get_bridge_attestations(env, network_id, nonce);
+    if attestations.contains(&validator) {
+        return Err(BridgeError::AlreadyAttested);
+    }
+    attestations.push_back(validator.clone());
+    env.storage().persistent().set(
+        &BridgeDataKey::Attestations(network_id, nonce),
+        &attestations,
+    );
+
+    let count = attestation_count(env, &set, network_id, nonce);
+    env.events().publish(
+        (
+            symbol_short!("bridge"),
+            symbol_short!("msg_att"),
+            network_id,
+        ),
+        (nonce, validator, count),
+    );
+    Ok(count)
+}
+
+/// Attestations of a message from validators still in `set`
+fn attestation_count(env: &Env, set: &BridgeValidatorSet, network_id: u32, nonce: u64) -> u32 {
+    get_bridge_attestations(env, network_id, nonce)
+        .iter()
+        .filter(|validator| set.validators.contains(validator))
+        .count() as u32
+}
+
+/// Get the queue cursors for a bridge (zeroed if nothing was ever enqueued)
+pub fn get_bridge_queue_state(env: &Env, network_id: u32) -> BridgeQueueState {
+    env.storage()
+        .persistent()
+        .get(&BridgeDataKey::Queue(network_id))
+        .unwrap_or(BridgeQueueState { head: 0, tail: 0 })
+}
+
+/// Get an inbound message by nonce
+pub fn get_bridge_message(env: &Env, network_id: u32, nonce: u64) -> Option<BridgeMessage> {
+    env.storage()
+        .persistent()
+        .get(&BridgeDataKey::Message(network_id, nonce))
+}
+
+/// Enqueue an attested inbound message
+///
+/// Only the registered bridge contract for `network_id` may enqueue; its
+/// authorization is the attestation. Messages receive consecutive nonces and
+/// are applied strictly in that order by [`process_bridge_messages`].
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `caller` - The bridge contract address (must authorize)
+/// * `network_id` - Remote network ID
+/// * `user` - Recipient of the deposit
+/// * `asset` - Asset to credit
+/// * `amount` - Gross amount bridged in
+///
+/// # Returns
+/// The nonce assigned to the message
+pub fn enqueue_bridge_message(
+    env: &Env,
+    caller: Address,
+    network_id: u32,
+    user: Address,
+    asset: Option<Address>,
+    amount: i128,
+) -> Result<u64, BridgeError> {
+    if amount <= 0 {
+        return Err(BridgeError::InvalidAmount);
+    }
+    enqueue(
+        env,
+        caller,
+        network_id,
+        BridgeMessageKind::Deposit,
+        user,
+        asset,
+        amount,
+    )
+}
+
+/// Enqueue an attested report of collateral a user has locked remotely
+///
+/// Goes through the same queue, validator attestations and ordering as
+/// deposits. Once processed, the amount replaces the user's previous report
+/// for the same network and asset; see [`get_remote_collateral`].
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `caller` - The bridge contract address (must authorize)
+/// * `network_id` - Remote network ID
+/// * `user` - User whose collateral is locked
+/// * `asset` - Local asset the collateral is valued as
+/// * `amount` - Total amount locked (0 when released)
+///
+/// # Returns
+/// The nonce assigned to the message
+pub fn enqueue_remote_collateral(
+    env: &Env,
+    caller: Address,
+    network_id: u32,
+    user: Address,
+    asset: Option<Address>,
+    amount: i128,
+) -> Result<u64, BridgeError> {
+    if amount < 0 {
+        return Err(BridgeError::InvalidAmount);
+    }
+    enqueue(
+        env,
+        caller,
+        network_id,
+        BridgeMessageKind::RemoteCollateral,
+        user,
+        asset,
+        amount,
+    )
+}
+
+fn enqueue(
+    env: &Env,
+    caller: Address,
+    network_id: u32,
+    kind: BridgeMessageKind,
+    user: Address,
+    asset: Option<Address>,
+    amount: i128,
+) -> Result<u64, BridgeError> {
+    let config = get_bridge_config(env, network_id)?;
+    if caller != config.bridge_address {
+        return Err(BridgeError::NotAuthorized);
+    }
+    caller.require_auth();
+
+    if !config.is_active {
+        return Err(BridgeError::BridgeNotActive);
+    }
+
+    let mut queue = get_bridge_queue_state(env, network_id);
+    let nonce = queue.tail;
+    let message = BridgeMessage {
+        nonce,
+        kind,
+        user: user.clone(),
+        asset,
+        amount,
+        enqueued_at: env.ledger().timestamp(),
+        status: BridgeMessageStatus::Pending,
+    };
+    env.storage()
+        .persistent()
+        .set(&BridgeDataKey::Message(network_id, nonce), &message);
+
+    queue.tail += 1;
+    env.storage()
+        .persistent()
+        .set(&BridgeDataKey::Queue(network_id), &queue);
+
+    env.events().publish(
+        (
+            symbol_short!("bridge"),
+            symbol_short!("msg_queue"),
+            network_id,
+        ),
+        (nonce, user, amount),
+    );
+
+    Ok(nonce)
+}
+
+/// Process up to `max` pending inbound messages in nonce order
+///
+/// Each deposit is credited to the recipient's cross-asset position net of the
+/// bridge fee, and the processed event carries the fee breakdown. Remote
+/// collateral reports update the user's [`RemoteCollateral`] entry. A message that cannot be applied (e.g. the asset is no longer
+/// supported or a supply cap is hit) is marked `Failed` and skipped, so one bad
+/// message never blocks the rest of the queue; every outcome is emitted as an
+/// event and reflected in the returned counts. When the bridge has a validator
+/// set, processing stops at the first message short of the attestation
+/// threshold and resumes once it is attested. Anyone may call this.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `network_id` - Remote network ID
+/// * `max` - Maximum number of messages to process (1..=`MAX_MESSAGES_PER_BATCH`)
+///
+/// # Errors
+/// * `BridgeNotFound` - No bridge is registered for `network_id`
+/// * `BridgeNotActive` - The bridge is disabled; the queue is left untouched
+/// * `InvalidBatchSize` - `max` is zero or above `MAX_MESSAGES_PER_BATCH`
+pub fn process_bridge_messages(
+    env: &Env,
+    network_id: u32,
+    max: u32,
+) -> Result<BridgeProcessResult, BridgeError> {
+    if max == 0 || max > MAX_MESSAGES_PER_BATCH {
+        return Err(BridgeError::InvalidBatchSize);
+    }
+
+    let config = get_bridge_config(env, network_id)?;
+    if !config.is_active {
+        return Err(BridgeError::BridgeNotActive);
+    }
+
+    let validators = get_bridge_validators(env, network_id);
+    let mut queue = get_bridge_queue_state(env, network_id);
+    let mut processed: u32 = 0;
+    let mut failed: u32 = 0;
+
+    while queue.head < queue.tail && processed + failed < max {
+        let key = BridgeDataKey::Message(network_id, queue.head);
+        let mut message: BridgeMessage = match env.storage().persistent().get(&key) {
+            Some(message) => message,
+            None => break,
+        };
+        if let Some(set) = &validators {
+            if attestation_count(env, set, network_id, queue.head) < set.threshold {
+                break;
+            }
+        }
+
+        let applied = match message.kind {
+            BridgeMessageKind::Deposit => {
+                let quote = fee_quote(env, network_id, &config, message.amount);
+                let credit = quote.net_amount;
+                let result = crate::cross_asset::credit_collateral(
+                    env,
+                    message.user.clone(),
+                    message.asset.clone(),
+                    credit,
+                );
+                if result.is_ok() {
+                    env.events().publish(
+                        (
+                            symbol_short!("bridge"),
+                            symbol_short!("msg_done"),
+                            network_id,
+                        ),
+                        (
+                            message.nonce,
+                            message.user.clone(),
+                            credit,
+                            quote.total_fee,
+                            quote.protocol_fee,
+                            quote.relayer_fee,
+                        ),
+                    );
+                }
+                result.is_ok()
+            }
+            BridgeMessageKind::RemoteCollateral => {
+                let result = record_remote_collateral(env, network_id, &message);
+                if result.is_ok() {
+                    env.events().publish(
+                        (symbol_short!("bridge"), symbol_short!("rc_set"), network_id),
+                        (message.nonce, message.user.clone(), message.amount),
+                    );
+                }
+                result.is_ok()
+            }
+        };
+
+        if applied {
+            message.status = BridgeMessageStatus::Processed;
+            processed += 1;
+        } else {
+            message.status = BridgeMessageStatus::Failed;
+            failed += 1;
+            env.events().publish(
+                (
+                    symbol_short!("bridge"),
+                    symbol_short!("msg_fail"),
+                    network_id,
+                ),
+                (message.nonce, message.user.clone(), message.amount),
+            );
+        }
+
+        env.storage().persistent().set(&key, &message);
+        queue.head += 1;
+    }
+
+    env.storage()
+        .persistent()
+        .set(&BridgeDataKey::Queue(network_id), &queue);
+
+    Ok(BridgeProcessResult {
+        processed,
+        failed,
+        remaining: queue.tail - queue.head,
+    })
+}
+
+/// Start of the epoch containing the current ledger timestamp
+fn current_epoch_start(env: &Env) -> u64 {
+    let now = env.ledger().timestamp();
+    now - now % BRIDGE_EPOCH_SECONDS
+}
+
+fn utilization(env: &Env, cap_key: BridgeDataKey, usage_key: BridgeDataKey) -> BridgeUtilization {
+    let epoch_start = current_epoch_start(env);
+    let cap = env
+        .storage()
+        .persistent()
+        .get::<BridgeDataKey, i128>(&cap_key);
+    let used = env
+        .storage()
+        .persistent()
+        .get::<BridgeDataKey, BridgeOutflowWindow>(&usage_key)
+        .filter(|window| window.window_start == epoch_start)
+        .map(|window| window.amount)
+        .unwrap_or(0);
+    BridgeUtilization {
+        cap,
+        used,
+        remaining: cap.map(|cap| cap.saturating_sub(used).max(0)),
+        utilization_bps: match cap {
+            Some(cap) if cap > 0 => used.saturating_mul(10000) / cap,
+            Some(_) => 10000,
+            None => 0,
+        },
+        window_end: epoch_start + BRIDGE_EPOCH_SECONDS,
+    }
+}
+
+/// Outflow cap usage of a bridge in the current epoch, all assets combined
+pub fn get_bridge_utilization(env: &Env, network_id: u32) -> BridgeUtilization {
+    utilization(
+        env,
+        BridgeDataKey::OutflowCap(network_id),
+        BridgeDataKey::Outflow(network_id),
+    )
+}
+
+/// Outflow cap usage of one asset through a bridge in the current epoch
+pub fn get_bridge_asset_utilization(
+    env: &Env,
+    network_id: u32,
+    asset: Option<Address>,
+) -> BridgeUtilization {
+    utilization(
+        env,
+        BridgeDataKey::AssetOutflowCap(network_id, asset.clone()),
+        BridgeDataKey::AssetOutflow(network_id, asset),
+    )
+}
+
+/// Set or clear the per-epoch outflow cap of a bridge, all assets combined
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `caller` - Admin address for authorization
+/// * `network_id` - ID of the remote network
+/// * `cap` - Maximum gross amount bridged out per epoch (None to disable)
+pub fn set_bridge_outflow_cap(
+    env: &Env,
+    caller: Address,
+    network_id: u32,
+    cap: Option<i128>,
+) -> Result<(), BridgeError> {
+    require_admin(env, &caller)?;
+    get_bridge_config(env, network_id)?;
+    set_cap(env, BridgeDataKey::OutflowCap(network_id), cap)
+}
+
+/// Set or clear the per-epoch outflow cap of one asset through a bridge
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `caller` - Admin address for authorization
+/// * `network_id` - ID of the remote network
+/// * `asset` - The asset (None for native XLM)
+/// * `cap` - Maximum amount bridged out per epoch (None to disable)
+pub fn set_bridge_asset_outflow_cap(
+    env: &Env,
+    caller: Address,
+    network_id: u32,
+    asset: Option<Address>,
+    cap: Option<i128>,
+) -> Result<(), BridgeError> {
+    require_admin(env, &caller)?;
+    get_bridge_config(env, network_id)?;
+    set_cap(env, BridgeDataKey::AssetOutflowCap(network_id, asset), cap)
+}
+
+fn set_cap(env: &Env, key: BridgeDataKey, cap: Option<i128>) -> Result<(), BridgeError> {
+    match cap {
+        Some(cap) if cap < 0 => Err(BridgeError::InvalidRateLimit),
+        Some(cap) => {
+            env.storage().persistent().set(&key, &cap);
+            Ok(())
+        }
+        None => {
+            env.storage().persistent().remove(&key);
+            Ok(())
+        }
+    }
+}
+
+/// Whether withdrawals through a bridge are paused
+pub fn is_bridge_outflow_paused(env: &Env, network_id: u32) -> bool {
+    env.storage()
+        .persistent()
+        .get(&BridgeDataKey::OutflowsPaused(network_id))
+        .unwrap_or(false)
+}
+
+/// Pause or resume withdrawals through a bridge
+///
+/// Resuming after an automatic pause does not reset the epoch's usage; raise
+/// the cap as well to let more through before the epoch ends.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `caller` - Admin address for authorization
+/// * `network_id` - ID of the remote network
+/// * `paused` - Whether outflows are paused
+pub fn set_bridge_outflows_paused(
+    env: &Env,
+    caller: Address,
+    network_id: u32,
+    paused: bool,
+) -> Result<(), BridgeError> {
+    require_admin(env, &caller)?;
+    get_bridge_config(env, network_id)?;
+
+    env.storage()
+        .persistent()
+        .set(&BridgeDataKey::OutflowsPaused(network_id), &paused);
+    env.events().publish(
+        (
+            symbol_short!("bridge"),
+            symbol_short!("out_pause"),
+            network_id,
+        ),
+        (caller, paused),
+    );
+    Ok(())
+}
+
+/// Check a withdrawal against the bridge's outflow caps and record it
+///
+/// Both counters are updated only if neither cap is exceeded. A withdrawal
+/// that fills a cap pauses the bridge's outflows, so a compromised bridge
+/// cannot drain a full cap every epoch without an admin looking at it.
+///
+/// # Errors
+/// * `OutflowsPaused` - Withdrawals through the bridge are paused
+/// * `RateLimitExceeded` - The withdrawal would exceed an outflow cap
+fn record_outflow(
+    env: &Env,
+    network_id: u32,
+    asset: &Option<Address>,
+    amount: i128,
+) -> Result<(), BridgeError> {
+    if is_bridge_outflow_paused(env, network_id) {
+        return Err(BridgeError::OutflowsPaused);
+    }
+
+    let window_start = current_epoch_start(env);
+    let network = get_bridge_utilization(env, network_id);
+    let per_asset = get_bridge_asset_utilization(env, network_id, asset.clone());
+    let new_network = network
+        .used
+        .checked_add(amount)
+        .ok_or(BridgeError::InvalidAmount)?;
+    let new_asset = per_asset
+        .used
+        .checked_add(amount)
+        .ok_or(BridgeError::InvalidAmount)?;
+
+    if network.cap.is_some_and(|cap| new_network > cap)
+        || per_asset.cap.is_some_and(|cap| new_asset > cap)
+    {
+        return Err(BridgeError::RateLimitExceeded);
+    }
+
+    env.storage().persistent().set(
+        &BridgeDataKey::Outflow(network_id),
+        &BridgeOutflowWindow {
+            window_start,
+            amount: new_network,
+        },
+    );
+    env.storage().persistent().set(
+        &BridgeDataKey::AssetOutflow(network_id, asset.clone()),
+        &BridgeOutflowWindow {
+            window_start,
+            amount: new_asset,
+        },
+    );
+
+    if network.cap == Some(new_network) || per_asset.cap == Some(new_asset) {
+        env.storage()
+            .persistent()
+            .set(&BridgeDataKey::OutflowsPaused(network_id), &true);
+        env.events().publish(
+            (
+                symbol_short!("bridge"),
+                symbol_short!("rl_pause"),
+                network_id,
+            ),
+            (asset.clone(), new_network, new_asset),
+        );
+    }
+    Ok(())
+}
+
+/// Get the collateral a user has locked on remote networks, stale or not
+pub fn get_remote_collateral(env: &Env, user: Address) -> Vec<RemoteCollateral> {
+    env.storage()
+        .persistent()
+        .get(&BridgeDataKey::RemoteCollateral(user))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Get how remote collateral is recognized, if it is
+pub fn get_remote_collateral_config(env: &Env) -> Option<RemoteCollateralConfig> {
+    env.storage()
+        .persistent()
+        .get(&BridgeDataKey::RemoteCollateralConfig)
+}
+
+/// Configure how attested remote collateral counts toward borrow capacity
+///
+/// Remote collateral is weighted by the local asset's liquidation threshold
+/// and then reduced by `haircut_bps`. An attestation older than `max_age`
+/// stops counting until the bridge reports the collateral again.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `caller` - Admin address for authorization
+/// * `haircut_bps` - Haircut in basis points (10000 counts nothing)
+/// * `max_age` - Seconds an attestation stays valid (1..=`MAX_REMOTE_COLLATERAL_AGE`)
+pub fn set_remote_collateral_config(
+    env: &Env,
+    caller: Address,
+    haircut_bps: i128,
+    max_age: u64,
+) -> Result<(), BridgeError> {
+    require_admin(env, &caller)?;
+
+    if !(0..=10000).contains(&haircut_bps) || max_age == 0 || max_age > MAX_REMOTE_COLLATERAL_AGE {
+        return Err(BridgeError::InvalidRemoteCollateralConfig);
+    }
+
+    env.storage().persistent().set(
+        &BridgeDataKey::RemoteCollateralConfig,
+        &RemoteCollateralConfig {
+            haircut_bps,
+            max_age,
+        },
+    );
+    env.events().publish(
+        (symbol_short!("bridge"), symbol_short!("rc_config")),
+        (caller, haircut_bps, max_age),
+    );
+    Ok(())
+}
+
+/// Replace the user's remote collateral entry for the message's network and
+/// asset, dropping it when the reported amount is zero
+fn record_remote_collateral(
+    env: &Env,
+    network_id: u32,
+    message: &BridgeMessage,
+) -> Result<(), BridgeError> {
+    let mut entries = get_remote_collateral(env, message.user.clone());
+    let existing = entries
+        .iter()
+        .position(|entry| entry.network_id == network_id && entry.asset == message.asset);
+    if let Some(index) = existing {
+        entries.remove(index as u32);
+    }
+
+    if message.amount > 0 {
+        if entries.len() >= MAX_REMOTE_COLLATERAL_ENTRIES {
+            return Err(BridgeError::TooManyRemoteCollateralEntries);
+        }
+        entries.push_back(RemoteCollateral {
+            network_id,
+            asset: message.asset.clone(),
+            amount: message.amount,
+            attested_at: message.enqueued_at,
+        });
+    }
+
+    env.storage().persistent().set(
+        &BridgeDataKey::RemoteCollateral(message.user.clone()),
+        &entries,
+    );
+    Ok(())
+}
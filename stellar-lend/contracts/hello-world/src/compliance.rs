@@ -0,0 +1,260 @@
+//! # Compliance Controls
+//!
+//! Optional controls for regulated deployments, checked by deposits and
+//! borrows before they change any state. With nothing configured every
+//! operation passes.
+//!
+//! - **Denylist**: denied accounts can neither deposit nor borrow.
+//! - **Allowlist**: when the configuration requires it, only allowed
+//!   accounts can deposit or borrow (KYC gating).
+//! - **Position caps**: per-account limits on the collateral balance and
+//!   on the debt (principal plus interest) after the operation.
+//! - **Compliance hook**: an external contract implementing
+//!   [`ComplianceHookInterface`] asked `check_allowed(user, op, asset, amount)`
+//!   for every deposit and borrow that passes the checks above. The hook
+//!   fails closed: a trap, an error or `false` rejects the operation.
+//!
+//! Repayments and withdrawals are never gated, so a denied account can
+//! always unwind its position.
+
+use soroban_sdk::{contractclient, contracterror, contracttype, Address, Env, Symbol};
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::events::{emit_admin_action, AdminActionEvent};
+
+/// Errors that can occur in compliance operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ComplianceError {
+    /// Caller is not the admin
+    Unauthorized = 1,
+    /// Caps must not be negative
+    InvalidCap = 2,
+    /// The account is denied, or not allowed while the allowlist is required
+    NotAllowed = 3,
+    /// The account's collateral would exceed its supply cap
+    SupplyCapExceeded = 4,
+    /// The account's debt would exceed its borrow cap
+    BorrowCapExceeded = 5,
+    /// The compliance hook vetoed the operation or failed
+    HookRejected = 6,
+}
+
+/// Interface an external compliance contract implements
+#[contractclient(name = "ComplianceHookClient")]
+pub trait ComplianceHookInterface {
+    /// Return true to allow `user` to run `op` (`deposit` or `borrow`)
+    fn check_allowed(
+        env: Env,
+        user: Address,
+        op: Symbol,
+        asset: Option<Address>,
+        amount: i128,
+    ) -> bool;
+}
+
+/// Deployment-wide compliance settings
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ComplianceConfig {
+    /// Only allowlisted accounts can deposit or borrow
+    pub allowlist_required: bool,
+    /// External contract consulted for every deposit and borrow
+    pub hook: Option<Address>,
+}
+
+/// Listing of an account
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ComplianceStatus {
+    Allowed,
+    Denied,
+}
+
+/// Per-account position limits (0 = unlimited)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UserPositionCaps {
+    /// Largest collateral balance after a deposit
+    pub max_supply: i128,
+    /// Largest debt (principal plus interest) after a borrow
+    pub max_borrow: i128,
+}
+
+/// Storage keys for compliance data
+#[contracttype]
+#[derive(Clone)]
+pub enum ComplianceDataKey {
+    /// Deployment-wide settings (absent = no allowlist, no hook)
+    /// Value type: ComplianceConfig
+    Config,
+    /// An account's allowlist or denylist entry
+    /// Value type: ComplianceStatus
+    Status(Address),
+    /// An account's position caps
+    /// Value type: UserPositionCaps
+    Caps(Address),
+}
+
+/// Get the compliance settings
+pub fn get_compliance_config(env: &Env) -> ComplianceConfig {
+    env.storage()
+        .persistent()
+        .get(&ComplianceDataKey::Config)
+        .unwrap_or(ComplianceConfig {
+            allowlist_required: false,
+            hook: None,
+        })
+}
+
+/// Set the compliance settings (admin only)
+pub fn set_compliance_config(
+    env: &Env,
+    caller: Address,
+    config: ComplianceConfig,
+) -> Result<(), ComplianceError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| ComplianceError::Unauthorized)?;
+    env.storage()
+        .persistent()
+        .set(&ComplianceDataKey::Config, &config);
+    emit_admin_action(
+        env,
+        AdminActionEvent {
+            actor: caller,
+            action: Symbol::new(env, "set_compliance_config"),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Get an account's allowlist or denylist entry
+pub fn get_compliance_status(env: &Env, user: &Address) -> Option<ComplianceStatus> {
+    env.storage()
+        .persistent()
+        .get(&ComplianceDataKey::Status(user.clone()))
+}
+
+/// Allow, deny or unlist an account (admin only)
+pub fn set_compliance_status(
+    env: &Env,
+    caller: Address,
+    user: Address,
+    status: Option<ComplianceStatus>,
+) -> Result<(), ComplianceError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| ComplianceError::Unauthorized)?;
+    let key = ComplianceDataKey::Status(user);
+    match status {
+        Some(status) => env.storage().persistent().set(&key, &status),
+        None => env.storage().persistent().remove(&key),
+    }
+    emit_admin_action(
+        env,
+        AdminActionEvent {
+            actor: caller,
+            action: Symbol::new(env, "set_compliance_status"),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Get an account's position caps
+pub fn get_user_position_caps(env: &Env, user: &Address) -> Option<UserPositionCaps> {
+    env.storage()
+        .persistent()
+        .get(&ComplianceDataKey::Caps(user.clone()))
+}
+
+/// Set or clear an account's position caps (admin only)
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not admin
+/// * `InvalidCap` - A cap is negative
+pub fn set_user_position_caps(
+    env: &Env,
+    caller: Address,
+    user: Address,
+    caps: Option<UserPositionCaps>,
+) -> Result<(), ComplianceError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| ComplianceError::Unauthorized)?;
+    let key = ComplianceDataKey::Caps(user);
+    match caps {
+        Some(caps) => {
+            if caps.max_supply < 0 || caps.max_borrow < 0 {
+                return Err(ComplianceError::InvalidCap);
+            }
+            env.storage().persistent().set(&key, &caps);
+        }
+        None => env.storage().persistent().remove(&key),
+    }
+    emit_admin_action(
+        env,
+        AdminActionEvent {
+            actor: caller,
+            action: Symbol::new(env, "set_user_position_caps"),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Check a deposit or borrow against the compliance controls
+///
+/// `operation` is `deposit` or `borrow`; the matching cap is checked against
+/// the account's balance after adding `amount`.
+///
+/// # Errors
+/// * `NotAllowed` - The account is denied or not allowlisted
+/// * `SupplyCapExceeded` / `BorrowCapExceeded` - The account's cap is exceeded
+/// * `HookRejected` - The compliance hook vetoed the operation or failed
+pub(crate) fn check_operation(
+    env: &Env,
+    user: &Address,
+    operation: Symbol,
+    asset: &Option<Address>,
+    amount: i128,
+) -> Result<(), ComplianceError> {
+    let config = get_compliance_config(env);
+    match get_compliance_status(env, user) {
+        Some(ComplianceStatus::Denied) => return Err(ComplianceError::NotAllowed),
+        None if config.allowlist_required => return Err(ComplianceError::NotAllowed),
+        _ => {}
+    }
+
+    if let Some(caps) = get_user_position_caps(env, user) {
+        if operation == Symbol::new(env, "deposit") {
+            let collateral = env
+                .storage()
+                .persistent()
+                .get::<DepositDataKey, i128>(&DepositDataKey::CollateralBalance(user.clone()))
+                .unwrap_or(0);
+            if caps.max_supply > 0 && collateral.saturating_add(amount) > caps.max_supply {
+                return Err(ComplianceError::SupplyCapExceeded);
+            }
+        } else {
+            let debt = env
+                .storage()
+                .persistent()
+                .get::<DepositDataKey, Position>(&DepositDataKey::Position(user.clone()))
+                .map(|p| p.debt.saturating_add(p.borrow_interest))
+                .unwrap_or(0);
+            if caps.max_borrow > 0 && debt.saturating_add(amount) > caps.max_borrow {
+                return Err(ComplianceError::BorrowCapExceeded);
+            }
+        }
+    }
+
+    if let Some(hook) = config.hook {
+        let allowed = matches!(
+            ComplianceHookClient::new(env, &hook)
+                .try_check_allowed(user, &operation, asset, &amount),
+            Ok(Ok(true))
+        );
+        if !allowed {
+            return Err(ComplianceError::HookRejected);
+        }
+    }
+    Ok(())
+}
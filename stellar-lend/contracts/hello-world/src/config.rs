@@ -4,6 +4,7 @@
 //! Allows the admin to set, get, backup, and restore configuration parameters.
 
 use crate::risk_management::require_admin;
+use crate::storage::{self, CONFIG_NAMESPACE};
 use soroban_sdk::{contracterror, contracttype, Address, Env, Symbol, Val, Vec};
 
 /// Errors that can occur during configuration operations
@@ -38,7 +39,7 @@ pub fn config_set(env: &Env, caller: Address, key: Symbol, value: Val) -> Result
     require_admin(env, &caller).map_err(|_| ConfigError::Unauthorized)?;
 
     let storage_key = ConfigDataKey::ConfigKey(key);
-    env.storage().persistent().set(&storage_key, &value);
+    storage::write(env, &CONFIG_NAMESPACE, &storage_key, &value);
 
     // Consider emitting an event for configuration update, though not explicitly requested, good practice.
     // let topics = (Symbol::new(env, "config_updated"), caller);
@@ -57,7 +58,7 @@ pub fn config_set(env: &Env, caller: Address, key: Symbol, value: Val) -> Result
 /// Returns Some(value) if the key exists, None otherwise
 pub fn config_get(env: &Env, key: Symbol) -> Option<Val> {
     let storage_key = ConfigDataKey::ConfigKey(key);
-    env.storage().persistent().get(&storage_key)
+    storage::read(env, &CONFIG_NAMESPACE, &storage_key)
 }
 
 /// Backup configuration parameters (admin only)
@@ -104,7 +105,7 @@ pub fn config_restore(
 
     for (key, value) in backup.iter() {
         let storage_key = ConfigDataKey::ConfigKey(key);
-        env.storage().persistent().set(&storage_key, &value);
+        storage::write(env, &CONFIG_NAMESPACE, &storage_key, &value);
     }
 
     Ok(())
@@ -0,0 +1,135 @@
+//! # Protocol Constants
+//!
+//! Central registry for protocol-wide constants that were previously
+//! duplicated across modules.
+//!
+//! Fixed scales (basis points, price precision) are compile-time constants.
+//! Time- and deployment-dependent values live in [`NetworkConstants`], which
+//! defaults to mainnet values and may be overridden once at initialization via
+//! `initialize_with_constants`. This lets testnet deployments run with
+//! accelerated time (e.g. a one-day "year") or tighter default caps without
+//! code changes.
+//!
+//! ## Invariants
+//! - Overrides can only be written during initialization.
+//! - `seconds_per_year` and `price_staleness_seconds` are always positive.
+//! - Default caps are non-negative (0 = unlimited).
+
+use crate::risk_management::RiskManagementError;
+use crate::storage::{self, CONSTANTS_NAMESPACE};
+use soroban_sdk::{contracttype, Env};
+
+/// 100% expressed in basis points
+pub const BPS_SCALE: i128 = 10_000;
+
+/// Fixed-point scale of cross-asset prices (7 decimals)
+pub const PRICE_SCALE: i128 = 10_000_000;
+
+/// Seconds in one day
+pub const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Seconds per year used to annualize rates on mainnet
+pub const DEFAULT_SECONDS_PER_YEAR: u64 = 365 * SECONDS_PER_DAY;
+
+/// Maximum age of a cross-asset price before positions are rejected
+pub const DEFAULT_PRICE_STALENESS_SECONDS: u64 = 3_600;
+
+/// Supply cap applied to assets without their own configuration (0 = unlimited)
+pub const DEFAULT_SUPPLY_CAP: i128 = 0;
+
+/// Borrow cap applied to assets without their own configuration (0 = unlimited)
+pub const DEFAULT_BORROW_CAP: i128 = 0;
+
+/// Storage keys for the constants registry
+#[contracttype]
+#[derive(Clone)]
+pub enum ConstantsDataKey {
+    /// Per-network overrides set at initialization
+    /// Value type: NetworkConstants
+    NetworkConstants,
+}
+
+/// Deployment-specific values that may differ between networks
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NetworkConstants {
+    /// Seconds per year used to annualize interest rates
+    pub seconds_per_year: u64,
+    /// Maximum age of a cross-asset price in seconds
+    pub price_staleness_seconds: u64,
+    /// Supply cap for assets without their own configuration (0 = unlimited)
+    pub default_supply_cap: i128,
+    /// Borrow cap for assets without their own configuration (0 = unlimited)
+    pub default_borrow_cap: i128,
+}
+
+impl NetworkConstants {
+    /// Mainnet values, used when no override was provided
+    pub fn mainnet() -> Self {
+        NetworkConstants {
+            seconds_per_year: DEFAULT_SECONDS_PER_YEAR,
+            price_staleness_seconds: DEFAULT_PRICE_STALENESS_SECONDS,
+            default_supply_cap: DEFAULT_SUPPLY_CAP,
+            default_borrow_cap: DEFAULT_BORROW_CAP,
+        }
+    }
+}
+
+/// Get the active network constants
+pub fn get_network_constants(env: &Env) -> NetworkConstants {
+    storage::read(
+        env,
+        &CONSTANTS_NAMESPACE,
+        &ConstantsDataKey::NetworkConstants,
+    )
+    .unwrap_or_else(NetworkConstants::mainnet)
+}
+
+/// Store per-network overrides
+///
+/// Only called from contract initialization; there is no entrypoint to
+/// change the values afterwards.
+///
+/// # Errors
+/// * `InvalidParameter` - A duration is zero or a default cap is negative
+pub(crate) fn set_network_constants(
+    env: &Env,
+    constants: &NetworkConstants,
+) -> Result<(), RiskManagementError> {
+    if constants.seconds_per_year == 0
+        || constants.price_staleness_seconds == 0
+        || constants.default_supply_cap < 0
+        || constants.default_borrow_cap < 0
+    {
+        return Err(RiskManagementError::InvalidParameter);
+    }
+
+    storage::write(
+        env,
+        &CONSTANTS_NAMESPACE,
+        &ConstantsDataKey::NetworkConstants,
+        constants,
+    );
+
+    Ok(())
+}
+
+/// Seconds per year used to annualize interest rates
+pub fn seconds_per_year(env: &Env) -> u64 {
+    get_network_constants(env).seconds_per_year
+}
+
+/// Maximum age of a cross-asset price in seconds
+pub fn price_staleness_seconds(env: &Env) -> u64 {
+    get_network_constants(env).price_staleness_seconds
+}
+
+/// Supply cap for assets without their own configuration (0 = unlimited)
+pub fn default_supply_cap(env: &Env) -> i128 {
+    get_network_constants(env).default_supply_cap
+}
+
+/// Borrow cap for assets without their own configuration (0 = unlimited)
+pub fn default_borrow_cap(env: &Env) -> i128 {
+    get_network_constants(env).default_borrow_cap
+}
@@ -8,17 +8,86 @@
 //! - Oracle-based price feeds for cross-asset value calculation
 //! - Unified position summary with health factor across all assets
 //! - Supply and borrow cap enforcement per asset
+//! - Per-asset kinked interest rate models with time-based debt accrual
+//! - Portfolio margin netting for correlated collateral/debt pairs
+//! - E-mode categories with boosted parameters for correlated assets
+//! - Isolation mode for newly listed risky collateral
+//! - Utilization buffers that keep part of each asset's supply unborrowed
+//! - Withdrawals limited to each asset's available liquidity, so
+//!   utilization never exceeds 100%
 //!
 //! ## Health Factor
 //! Computed as `weighted_collateral_value / weighted_debt_value * 10000`.
 //! A health factor below 10,000 (1.0x) makes the position liquidatable.
 //!
+//! ## Interest Accrual
+//! Each asset may carry an [`AssetRateModel`], or select another curve
+//! through `interest_rate::set_asset_rate_curve`. The borrow rate is derived
+//! from the asset's utilization (`total borrows / total supplies`) by
+//! `interest_rate::calculate_rate`, the same dispatch the core interest rate
+//! module uses. Interest accrues as simple
+//! interest on `debt_principal` since `last_updated` and is settled into
+//! `accrued_interest` whenever the position is touched. Assets without a rate
+//! model accrue no interest.
+//!
+//! ## Netting
+//! Governance can register [`NettingPair`]s whose collateral and debt move
+//! together (e.g. USDC debt against USDC-yield collateral). For each pair the
+//! overlapping value `min(collateral_value, debt_value)` is offset, and
+//! `netting_factor_bps` of it is removed from the weighted debt. The benefit is
+//! bounded by `MAX_NETTING_FACTOR_BPS` and by each pair's `max_benefit_value`.
+//!
+//! ## E-Mode
+//! Admin-defined [`EModeCategory`]s group correlated assets (e.g. stablecoins)
+//! under a higher collateral factor and liquidation threshold. A user who opts
+//! into a category via [`set_user_emode`] has the category parameters applied
+//! to every asset in it; while in e-mode they may only borrow category assets.
+//!
+//! ## Isolation Mode
+//! Assets flagged `is_isolated` can only be used as a user's sole collateral.
+//! While a user holds isolated collateral they cannot deposit any other
+//! collateral, and the total debt value borrowed against each isolated asset
+//! (across all users) is bounded by its `isolated_debt_ceiling`. Isolated debt
+//! is tracked in USD at borrow time and released on repayment.
+//!
+//! ## Remote Collateral
+//! Collateral a user has locked on another network, reported through the
+//! bridge message queue, adds to their weighted collateral at the asset's
+//! liquidation threshold less a protocol haircut (see
+//! [`crate::bridge::set_remote_collateral_config`]). Reports older than the
+//! configured maximum age stop counting until they are refreshed.
+//!
+//! ## Unit Conversions
+//! Position valuations use the listed `price` (7 decimals): an amount is
+//! worth `amount * price / PRICE_SCALE`, ratios are `a * 10000 / b` and
+//! factors apply as `value * bps / 10000`, all rounding toward zero.
+//! [`value_of`], [`amount_of`], [`apply_bps`] and [`ratio_bps`] are the only
+//! implementations, and [`to_value`] / [`from_value`] expose them for listed
+//! assets so integrators round exactly like the contract.
+//!
+//! ## Caps
+//! `max_supply` and `max_borrow` bound the protocol-wide totals of an asset,
+//! which include both cross-asset positions and the core
+//! `deposit_collateral`/`borrow_asset` flows. An optional per-asset
+//! utilization buffer additionally rejects borrows that would push total
+//! borrows above `total_supply * (10000 - buffer_bps) / 10000`, so that some
+//! liquidity always remains available for withdrawals.
+//!
 //! ## Invariants
 //! - Withdrawals and borrows are rejected if they would lower health factor below 1.0.
-//! - Prices must not be stale (> 1 hour old) for position calculations.
+//! - Prices must not be stale (> 1 hour old by default, see
+//!   [`crate::constants`]) for position calculations.
+//! - Price updates are checked by the asset's oracle circuit breaker, and
+//!   assets whose breaker tripped cannot be borrowed until it is reset.
 
 #![allow(dead_code)]
-use soroban_sdk::{contracterror, contracttype, symbol_short, Address, Env, Map, Symbol, Vec};
+use crate::constants::{
+    default_borrow_cap, default_supply_cap, price_staleness_seconds, seconds_per_year, BPS_SCALE,
+    PRICE_SCALE,
+};
+use soroban_sdk::{
+    contracterror, contracttype, panic_with_error, symbol_short, Address, Env, Map, Symbol, Vec,
+};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -45,6 +114,11 @@ pub struct AssetConfig {
     pub price: i128,
     /// Last price update timestamp
     pub price_updated_at: u64,
+    /// Whether the asset can only be used as a user's sole collateral
+    pub is_isolated: bool,
+    /// Maximum total debt value in USD (7 decimals) borrowed against this
+    /// asset in isolation mode (0 = unlimited)
+    pub isolated_debt_ceiling: i128,
 }
 
 /// User position across a single asset
@@ -79,6 +153,41 @@ pub struct UserPositionSummary {
     pub is_liquidatable: bool,
     /// Maximum additional borrow capacity in USD
     pub borrow_capacity: i128,
+    /// Weighted debt removed by netting pairs in USD
+    pub netting_benefit: i128,
+    /// Weighted value of attested collateral on other networks in USD,
+    /// included in `weighted_collateral_value`
+    pub remote_collateral_value: i128,
+    /// Isolated collateral asset when the user is in isolation mode
+    pub isolated_asset: Option<AssetKey>,
+}
+
+/// E-mode category: correlated assets sharing boosted risk parameters
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EModeCategory {
+    /// Human-readable label (e.g. "stables")
+    pub label: Symbol,
+    /// Collateral factor (LTV) applied to category assets in basis points
+    pub collateral_factor: i128,
+    /// Liquidation threshold applied to category assets in basis points
+    pub liquidation_threshold: i128,
+    /// Assets belonging to the category
+    pub assets: Vec<AssetKey>,
+}
+
+/// Correlated collateral/debt pair eligible for margin netting
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NettingPair {
+    /// Asset held as collateral
+    pub collateral_asset: AssetKey,
+    /// Asset borrowed against it
+    pub debt_asset: AssetKey,
+    /// Share of the offsetting value removed from weighted debt (basis points)
+    pub netting_factor_bps: i128,
+    /// Maximum benefit per user in USD (7 decimals)
+    pub max_benefit_value: i128,
 }
 
 #[contracttype]
@@ -108,10 +217,60 @@ pub enum CrossAssetError {
     BorrowCapExceeded = 7,
     /// Price is zero or negative
     InvalidPrice = 8,
-    /// Asset price is older than the staleness threshold (1 hour)
+    /// Asset price is older than the staleness threshold (1 hour by default)
     PriceStale = 9,
     /// Caller is not authorized (not admin)
     NotAuthorized = 10,
+    /// Rate model parameters are out of range
+    InvalidRateModel = 11,
+    /// Netting pair parameters are invalid or the pair does not exist
+    InvalidNettingPair = 12,
+    /// E-mode category is invalid, unknown, or excludes a borrowed asset
+    InvalidEMode = 13,
+    /// Isolated collateral cannot be combined with other collateral
+    IsolationModeViolation = 14,
+    /// Borrow would exceed the isolated asset's debt ceiling
+    IsolatedDebtCeilingExceeded = 15,
+    /// Borrow would eat into the liquidity reserved by the utilization buffer
+    UtilizationBufferExceeded = 16,
+    /// Withdrawal would take supply that outstanding borrows have claim on
+    InsufficientLiquidity = 17,
+    /// Asset's price circuit breaker is tripped
+    CircuitBreakerTripped = 18,
+}
+
+/// Remaining headroom under an asset's supply and borrow caps
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RemainingCaps {
+    /// Current total supply across cross-asset and core positions
+    pub total_supply: i128,
+    /// Current total borrows across cross-asset and core positions
+    pub total_borrow: i128,
+    /// Amount that can still be supplied (`i128::MAX` if uncapped)
+    pub remaining_supply: i128,
+    /// Amount that can still be borrowed under the borrow cap and
+    /// utilization buffer (`i128::MAX` if both are disabled)
+    pub remaining_borrow: i128,
+    /// Utilization buffer in basis points (0 = disabled)
+    pub utilization_buffer_bps: i128,
+}
+
+/// Per-asset interest rate model for cross-asset borrows.
+///
+/// Rate below kink = `base_rate + utilization / kink * multiplier`.
+/// Rate above kink = `base_rate + multiplier + (utilization - kink) / (10000 - kink) * jump_multiplier`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetRateModel {
+    /// Annual borrow rate at 0% utilization (basis points)
+    pub base_rate_bps: i128,
+    /// Utilization at which the jump multiplier takes over (basis points)
+    pub kink_utilization_bps: i128,
+    /// Annual rate added between 0% utilization and the kink (basis points)
+    pub multiplier_bps: i128,
+    /// Annual rate added between the kink and 100% utilization (basis points)
+    pub jump_multiplier_bps: i128,
 }
 
 /// Admin address authorized for protocol management
@@ -132,6 +291,33 @@ const TOTAL_BORROWS: Symbol = symbol_short!("borrows");
 /// Storage key for the global list of registered assets: Vec<AssetKey>
 const ASSET_LIST: Symbol = symbol_short!("assets");
 
+/// Storage key for the map of per-asset rate models: Map<AssetKey, AssetRateModel>
+const RATE_MODELS: Symbol = symbol_short!("rates");
+
+/// Storage key for the list of netting pairs: Vec<NettingPair>
+const NETTING_PAIRS: Symbol = symbol_short!("netting");
+
+/// Hard cap on any pair's netting factor (basis points)
+pub const MAX_NETTING_FACTOR_BPS: i128 = 5_000;
+
+/// Maximum number of netting pairs
+pub const MAX_NETTING_PAIRS: u32 = 20;
+
+/// Storage key for e-mode categories: Map<u32, EModeCategory>
+const EMODE_CATEGORIES: Symbol = symbol_short!("emodes");
+
+/// Storage key for users' selected e-mode category: Map<Address, u32>
+const USER_EMODES: Symbol = symbol_short!("user_emod");
+
+/// Storage key for debt value borrowed against isolated assets: Map<AssetKey, i128>
+const ISOLATED_DEBT: Symbol = symbol_short!("iso_debt");
+
+/// Storage key for per-asset utilization buffers: Map<AssetKey, i128>
+const UTILIZATION_BUFFERS: Symbol = symbol_short!("util_buf");
+
+/// Maximum utilization buffer (basis points)
+pub const MAX_UTILIZATION_BUFFER_BPS: i128 = 5_000;
+
 /// Initialize the cross-asset lending module.
 ///
 /// Sets the admin address. Can only be called once; subsequent calls return
@@ -175,7 +361,8 @@ fn require_admin(env: &Env) -> Result<(), CrossAssetError> {
 ///
 /// # Errors
 /// * `NotAuthorized` - Caller is not the admin
-/// * `AssetNotConfigured` - A basis-point field is out of [0, 10000]
+/// * `AssetNotConfigured` - A basis-point field is out of [0, 10000] or the
+///   isolated debt ceiling is negative
 /// * `InvalidPrice` - Price is zero or negative
 pub fn initialize_asset(
     env: &Env,
@@ -184,6 +371,17 @@ pub fn initialize_asset(
 ) -> Result<(), CrossAssetError> {
     require_admin(env)?;
 
+    apply_asset_listing(env, asset, config)
+}
+
+/// Validate and register an asset without an authorization check
+///
+/// Shared by [`initialize_asset`] and governance execution.
+pub(crate) fn apply_asset_listing(
+    env: &Env,
+    asset: Option<Address>,
+    config: AssetConfig,
+) -> Result<(), CrossAssetError> {
     require_valid_config(&config)?;
 
     let asset_key = AssetKey::from_option(asset.clone());
@@ -286,6 +484,7 @@ pub fn update_asset_config(
 /// Update the oracle price for an asset.
 ///
 /// Records the new price and the current ledger timestamp for staleness checks.
+/// A token's move from its previous price is checked by its circuit breaker.
 ///
 /// # Arguments
 /// * `env` - The contract environment
@@ -307,8 +506,11 @@ pub fn update_asset_price(
         return Err(CrossAssetError::InvalidPrice);
     }
 
-    let asset_key = AssetKey::from_option(asset);
+    let asset_key = AssetKey::from_option(asset.clone());
     let mut config = get_asset_config(env, &asset_key)?;
+    if let Some(asset) = asset {
+        crate::oracle::check_price_move(env, &asset, config.price, config.price_updated_at, price);
+    }
     config.price = price;
     config.price_updated_at = env.ledger().timestamp();
 
@@ -349,6 +551,17 @@ pub fn get_user_asset_position(env: &Env, user: &Address, asset: Option<Address>
     })
 }
 
+/// A user's outstanding debt in one asset, including interest accrued since
+/// the position was last updated
+pub(crate) fn get_user_asset_debt(env: &Env, user: &Address, asset: Option<Address>) -> i128 {
+    let asset_key = AssetKey::from_option(asset.clone());
+    let position = get_user_asset_position(env, user, asset);
+    position
+        .debt_principal
+        .saturating_add(position.accrued_interest)
+        .saturating_add(pending_interest(env, &asset_key, &position))
+}
+
 /// Update user's position for a specific asset
 ///
 /// # Arguments
@@ -371,13 +584,18 @@ fn set_user_asset_position(
 
     positions.set(key, position);
     env.storage().persistent().set(&USER_POSITIONS, &positions);
+    crate::snapshot::index_user(env, user);
 }
 
 /// Calculate a unified position summary across all registered assets.
 ///
 /// Iterates over all configured assets, aggregates collateral and debt values
 /// weighted by their respective factors, and computes the health factor.
-/// Prices older than 1 hour are rejected.
+/// Debt includes interest accrued since each position was last updated, and
+/// weighted debt is reduced by any netting pair benefit. Reports the isolated
+/// collateral asset when the user is in isolation mode.
+/// Prices older than the network's staleness threshold (1 hour by default)
+/// are rejected.
 ///
 /// # Arguments
 /// * `env` - The contract environment
@@ -387,7 +605,7 @@ fn set_user_asset_position(
 /// [`UserPositionSummary`] with health factor, liquidation status, and borrow capacity.
 ///
 /// # Errors
-/// * `PriceStale` - Any asset with a non-zero position has a price older than 1 hour
+/// * `PriceStale` - Any asset with a non-zero position has a stale price
 pub fn get_user_position_summary(
     env: &Env,
     user: &Address,
@@ -408,6 +626,10 @@ pub fn get_user_position_summary(
     let mut weighted_collateral_value: i128 = 0;
     let mut total_debt_value: i128 = 0;
     let mut weighted_debt_value: i128 = 0;
+    let mut collateral_values: Map<AssetKey, i128> = Map::new(env);
+    let mut debt_values: Map<AssetKey, i128> = Map::new(env);
+    let emode = get_emode_category(env, get_user_emode(env, user));
+    let mut isolated_asset: Option<AssetKey> = None;
 
     for i in 0..asset_list.len() {
         let asset_key = asset_list.get(i).unwrap();
@@ -420,33 +642,49 @@ pub fn get_user_position_summary(
                 continue;
             }
 
-            let current_time = env.ledger().timestamp();
-            if current_time > config.price_updated_at
-                && current_time - config.price_updated_at > 3600
-            {
-                return Err(CrossAssetError::PriceStale);
+            require_fresh_price(env, &config)?;
+
+            if config.is_isolated && position.collateral > 0 {
+                isolated_asset = Some(asset_key.clone());
             }
 
-            let collateral_value = (position.collateral * config.price) / 10_000_000;
+            let collateral_value = value_of(position.collateral, config.price);
             total_collateral_value += collateral_value;
 
+            let liquidation_threshold = match &emode {
+                Some(category) if category.assets.contains(&asset_key) => {
+                    category.liquidation_threshold
+                }
+                _ => config.liquidation_threshold,
+            };
+
             if config.can_collateralize {
-                weighted_collateral_value +=
-                    (collateral_value * config.liquidation_threshold) / 10_000;
+                weighted_collateral_value += apply_bps(collateral_value, liquidation_threshold);
+                collateral_values.set(asset_key.clone(), collateral_value);
             }
 
-            let total_debt = position.debt_principal + position.accrued_interest;
-            let debt_value = (total_debt * config.price) / 10_000_000;
+            let total_debt = position.debt_principal
+                + position.accrued_interest
+                + pending_interest(env, &asset_key, &position);
+            let debt_value = value_of(total_debt, config.price);
             total_debt_value += debt_value;
 
             weighted_debt_value += debt_value;
+            debt_values.set(asset_key.clone(), debt_value);
         }
     }
 
+    let remote_collateral_value = remote_collateral_value(env, user, &configs)?;
+    weighted_collateral_value += remote_collateral_value;
+
+    let netting_benefit =
+        compute_netting_benefit(env, &collateral_values, &debt_values).min(weighted_debt_value);
+    weighted_debt_value -= netting_benefit;
+
     // Calculate health factor (weighted_collateral / weighted_debt * 10000)
     // Health factor of 1.0 = 10000, below 1.0 can be liquidated
     let health_factor = if weighted_debt_value > 0 {
-        (weighted_collateral_value * 10_000) / weighted_debt_value
+        ratio_bps(weighted_collateral_value, weighted_debt_value)
     } else {
         i128::MAX // No debt = infinite health
     };
@@ -469,13 +707,60 @@ pub fn get_user_position_summary(
         health_factor,
         is_liquidatable,
         borrow_capacity,
+        netting_benefit,
+        remote_collateral_value,
+        isolated_asset,
     })
 }
 
+/// Weighted value of the collateral a user has locked on other networks
+///
+/// Each entry attested through the bridge counts at the local asset's
+/// liquidation threshold less the protocol's remote collateral haircut.
+/// Nothing counts until a remote collateral configuration is set, and an
+/// entry whose attestation is older than its maximum age is de-recognized
+/// until the bridge reports it again. Entries for assets not listed locally
+/// or not enabled as collateral are ignored.
+///
+/// # Errors
+/// * `PriceStale` - A recognized entry's asset has a stale price
+fn remote_collateral_value(
+    env: &Env,
+    user: &Address,
+    configs: &Map<AssetKey, AssetConfig>,
+) -> Result<i128, CrossAssetError> {
+    let Some(remote) = crate::bridge::get_remote_collateral_config(env) else {
+        return Ok(0);
+    };
+    let now = env.ledger().timestamp();
+
+    let mut value = 0;
+    for entry in crate::bridge::get_remote_collateral(env, user.clone()).iter() {
+        if now.saturating_sub(entry.attested_at) > remote.max_age {
+            continue;
+        }
+        let Some(config) = configs.get(AssetKey::from_option(entry.asset)) else {
+            continue;
+        };
+        if !config.can_collateralize {
+            continue;
+        }
+        require_fresh_price(env, &config)?;
+
+        let weighted = apply_bps(
+            value_of(entry.amount, config.price),
+            config.liquidation_threshold,
+        );
+        value += apply_bps(weighted, BPS_SCALE - remote.haircut_bps);
+    }
+    Ok(value)
+}
+
 /// Deposit collateral for a specific asset.
 ///
-/// Requires user authorization. Validates the asset is enabled for collateral
-/// and that the deposit does not exceed the supply cap.
+/// Requires user authorization. Validates the asset is enabled for collateral,
+/// that the deposit does not exceed the supply cap, and that it does not mix
+/// isolated collateral with other collateral.
 ///
 /// # Arguments
 /// * `env` - The contract environment
@@ -490,6 +775,7 @@ pub fn get_user_position_summary(
 /// * `AssetNotConfigured` - Asset is not registered
 /// * `AssetDisabled` - Asset is not enabled for collateral
 /// * `SupplyCapExceeded` - Deposit would exceed the asset's supply cap
+/// * `IsolationModeViolation` - Deposit would combine isolated collateral with other collateral
 pub fn cross_asset_deposit(
     env: &Env,
     user: Address,
@@ -498,6 +784,22 @@ pub fn cross_asset_deposit(
 ) -> Result<AssetPosition, CrossAssetError> {
     user.require_auth();
 
+    credit_collateral(env, user, asset, amount)
+}
+
+/// Credit collateral to a user's position without requiring their signature.
+///
+/// Used for deposits that were authorized elsewhere, such as attested inbound
+/// bridge messages. All checks are performed before any state is written.
+///
+/// # Errors
+/// Same as [`cross_asset_deposit`].
+pub(crate) fn credit_collateral(
+    env: &Env,
+    user: Address,
+    asset: Option<Address>,
+    amount: i128,
+) -> Result<AssetPosition, CrossAssetError> {
     let asset_key = AssetKey::from_option(asset.clone());
     let config = get_asset_config(env, &asset_key)?;
 
@@ -505,14 +807,12 @@ pub fn cross_asset_deposit(
         return Err(CrossAssetError::AssetDisabled);
     }
 
-    if config.max_supply > 0 {
-        let total_supply = get_total_supply(env, &asset_key);
-        if total_supply + amount > config.max_supply {
-            return Err(CrossAssetError::SupplyCapExceeded);
-        }
-    }
+    require_supply_cap(env, &asset_key, &config, amount)?;
+
+    require_isolation_compatible(env, &user, &asset_key, &config)?;
 
     let mut position = get_user_asset_position(env, &user, asset.clone());
+    accrue_position_interest(env, &asset_key, &mut position);
 
     position.collateral += amount;
     position.last_updated = env.ledger().timestamp();
@@ -523,11 +823,29 @@ pub fn cross_asset_deposit(
     Ok(position)
 }
 
+/// Return collateral taken by an operation that was later reversed.
+///
+/// Skips the listing, supply cap and isolation checks: the collateral was in
+/// the position moments ago, so it goes back unconditionally (e.g. a bridged
+/// withdrawal vetoed during its challenge window).
+pub(crate) fn restore_collateral(env: &Env, user: Address, asset: Option<Address>, amount: i128) {
+    let asset_key = AssetKey::from_option(asset.clone());
+    let mut position = get_user_asset_position(env, &user, asset.clone());
+    accrue_position_interest(env, &asset_key, &mut position);
+
+    position.collateral += amount;
+    position.last_updated = env.ledger().timestamp();
+
+    set_user_asset_position(env, &user, asset, position);
+    update_total_supply(env, &asset_key, amount);
+}
+
 /// Withdraw collateral for a specific asset.
 ///
-/// Requires user authorization. Checks that the user has sufficient collateral
-/// and that the withdrawal does not bring the health factor below 1.0. If the
-/// health check fails, the withdrawal is rolled back.
+/// Requires user authorization. Checks that the user has sufficient collateral,
+/// that the asset's unborrowed supply covers the withdrawal, and that the
+/// withdrawal does not bring the health factor below 1.0. If the health check
+/// fails, the withdrawal is rolled back.
 ///
 /// # Arguments
 /// * `env` - The contract environment
@@ -540,6 +858,7 @@ pub fn cross_asset_deposit(
 ///
 /// # Errors
 /// * `InsufficientCollateral` - User's collateral balance is below `amount`
+/// * `InsufficientLiquidity` - Withdrawal exceeds the asset's available liquidity
 /// * `UnhealthyPosition` - Withdrawal would drop health factor below 1.0
 /// * `PriceStale` - Stale price prevents health factor calculation
 pub fn cross_asset_withdraw(
@@ -553,10 +872,12 @@ pub fn cross_asset_withdraw(
     let asset_key = AssetKey::from_option(asset.clone());
 
     let mut position = get_user_asset_position(env, &user, asset.clone());
+    accrue_position_interest(env, &asset_key, &mut position);
 
     if position.collateral < amount {
         return Err(CrossAssetError::InsufficientCollateral);
     }
+    check_withdraw_liquidity(env, asset.clone(), amount)?;
 
     position.collateral -= amount;
     position.last_updated = env.ledger().timestamp();
@@ -578,9 +899,11 @@ pub fn cross_asset_withdraw(
 
 /// Borrow a specific asset against cross-asset collateral.
 ///
-/// Requires user authorization. Validates the asset is enabled for borrowing,
-/// checks the borrow cap, and verifies the post-borrow health factor stays
-/// above 1.0. If the health check fails, the borrow is rolled back.
+/// Requires user authorization. Settles interest accrued on the existing debt,
+/// validates the asset is enabled for borrowing, checks the borrow cap and,
+/// for users in isolation mode, the isolated debt ceiling, and verifies the
+/// post-borrow health factor stays above 1.0. If the health check fails, the
+/// borrow is rolled back.
 ///
 /// # Arguments
 /// * `env` - The contract environment
@@ -594,7 +917,10 @@ pub fn cross_asset_withdraw(
 /// # Errors
 /// * `AssetNotConfigured` - Asset is not registered
 /// * `AssetDisabled` - Asset is not enabled for borrowing
+/// * `CircuitBreakerTripped` - The asset's price circuit breaker is tripped
 /// * `BorrowCapExceeded` - Borrow would exceed the asset's borrow cap
+/// * `UtilizationBufferExceeded` - Borrow would breach the utilization buffer
+/// * `IsolatedDebtCeilingExceeded` - Borrow would exceed the isolated collateral's debt ceiling
 /// * `ExceedsBorrowCapacity` - Health factor would drop below 1.0
 /// * `PriceStale` - Stale price prevents health factor calculation
 pub fn cross_asset_borrow(
@@ -605,6 +931,21 @@ pub fn cross_asset_borrow(
 ) -> Result<AssetPosition, CrossAssetError> {
     user.require_auth();
 
+    apply_borrow(env, &user, asset, amount)
+}
+
+/// Borrow against a user's collateral without an authorization check.
+///
+/// Used by flows that already hold the user's signature, such as debt swaps.
+///
+/// # Errors
+/// Same as [`cross_asset_borrow`].
+pub(crate) fn apply_borrow(
+    env: &Env,
+    user: &Address,
+    asset: Option<Address>,
+    amount: i128,
+) -> Result<AssetPosition, CrossAssetError> {
     let asset_key = AssetKey::from_option(asset.clone());
     let config = get_asset_config(env, &asset_key)?;
 
@@ -612,28 +953,50 @@ pub fn cross_asset_borrow(
         return Err(CrossAssetError::AssetDisabled);
     }
 
-    if config.max_borrow > 0 {
-        let total_borrow = get_total_borrow(env, &asset_key);
-        if total_borrow + amount > config.max_borrow {
-            return Err(CrossAssetError::BorrowCapExceeded);
+    if let Some(asset) = &asset {
+        if crate::oracle::is_circuit_breaker_tripped(env, asset) {
+            return Err(CrossAssetError::CircuitBreakerTripped);
         }
     }
 
-    let mut position = get_user_asset_position(env, &user, asset.clone());
+    if let Some(category) = get_emode_category(env, get_user_emode(env, user)) {
+        if !category.assets.contains(&asset_key) {
+            return Err(CrossAssetError::InvalidEMode);
+        }
+    }
+
+    require_borrow_cap(env, &asset_key, &config, amount)?;
+
+    let mut position = get_user_asset_position(env, user, asset.clone());
+    accrue_position_interest(env, &asset_key, &mut position);
 
     position.debt_principal += amount;
     position.last_updated = env.ledger().timestamp();
 
-    set_user_asset_position(env, &user, asset.clone(), position.clone());
+    set_user_asset_position(env, user, asset.clone(), position.clone());
 
-    let summary = get_user_position_summary(env, &user)?;
+    let summary = get_user_position_summary(env, user)?;
 
     if summary.health_factor < 10_000 {
         position.debt_principal -= amount;
-        set_user_asset_position(env, &user, asset, position);
+        set_user_asset_position(env, user, asset, position);
         return Err(CrossAssetError::ExceedsBorrowCapacity);
     }
 
+    if let Some(isolated_key) = summary.isolated_asset {
+        let isolated_config = get_asset_config(env, &isolated_key)?;
+        let borrow_value = value_of(amount, config.price);
+        let isolated_debt = get_isolated_debt_for(env, &isolated_key);
+        if isolated_config.isolated_debt_ceiling > 0
+            && isolated_debt + borrow_value > isolated_config.isolated_debt_ceiling
+        {
+            position.debt_principal -= amount;
+            set_user_asset_position(env, user, asset, position);
+            return Err(CrossAssetError::IsolatedDebtCeilingExceeded);
+        }
+        update_isolated_debt(env, &isolated_key, borrow_value);
+    }
+
     update_total_borrow(env, &asset_key, amount);
 
     Ok(position)
@@ -641,8 +1004,10 @@ pub fn cross_asset_borrow(
 
 /// Repay debt for a specific asset.
 ///
-/// Requires user authorization. Repayment is capped at the total outstanding
-/// debt (principal + accrued interest). Interest is paid first, then principal.
+/// Requires user authorization. Interest accrued since the last update is
+/// settled first, then repayment is capped at the total outstanding debt
+/// (principal + accrued interest). Interest is paid first, then principal.
+/// Repayments by users in isolation mode release isolated debt.
 ///
 /// # Arguments
 /// * `env` - The contract environment
@@ -660,10 +1025,23 @@ pub fn cross_asset_repay(
 ) -> Result<AssetPosition, CrossAssetError> {
     user.require_auth();
 
+    apply_repay(env, &user, asset, amount)
+}
+
+/// Repay a user's debt without an authorization check.
+///
+/// Used by flows that already hold the user's signature, such as debt swaps.
+pub(crate) fn apply_repay(
+    env: &Env,
+    user: &Address,
+    asset: Option<Address>,
+    amount: i128,
+) -> Result<AssetPosition, CrossAssetError> {
     let asset_key = AssetKey::from_option(asset.clone());
 
-    // Get current position
-    let mut position = get_user_asset_position(env, &user, asset.clone());
+    // Get current position and settle outstanding interest
+    let mut position = get_user_asset_position(env, user, asset.clone());
+    accrue_position_interest(env, &asset_key, &mut position);
 
     let total_debt = position.debt_principal + position.accrued_interest;
     let repay_amount = amount.min(total_debt);
@@ -680,12 +1058,80 @@ pub fn cross_asset_repay(
     position.last_updated = env.ledger().timestamp();
 
     // Update storage
-    set_user_asset_position(env, &user, asset, position.clone());
+    set_user_asset_position(env, user, asset, position.clone());
     update_total_borrow(env, &asset_key, -repay_amount);
 
+    if let Some(isolated_key) = find_isolated_collateral(env, user) {
+        let config = get_asset_config(env, &asset_key)?;
+        update_isolated_debt(env, &isolated_key, -value_of(repay_amount, config.price));
+    }
+
     Ok(position)
 }
 
+/// Value an amount of a listed asset in USD (7 decimals), as positions are valued
+///
+/// # Errors
+/// * `AssetNotConfigured` - The asset is not listed
+/// * `PriceStale` - The listed price is older than the staleness threshold
+pub fn to_value(env: &Env, asset: Option<Address>, amount: i128) -> Result<i128, CrossAssetError> {
+    let config = get_asset_config(env, &AssetKey::from_option(asset))?;
+    require_fresh_price(env, &config)?;
+    Ok(value_of(amount, config.price))
+}
+
+/// Amount of a listed asset worth `value` in USD (7 decimals), rounded down
+///
+/// # Errors
+/// * `AssetNotConfigured` - The asset is not listed
+/// * `PriceStale` - The listed price is older than the staleness threshold
+pub fn from_value(env: &Env, asset: Option<Address>, value: i128) -> Result<i128, CrossAssetError> {
+    let config = get_asset_config(env, &AssetKey::from_option(asset))?;
+    require_fresh_price(env, &config)?;
+    Ok(amount_of(value, config.price))
+}
+
+/// Listed price of an asset (7 decimals)
+///
+/// # Errors
+/// * `AssetNotConfigured` - The asset is not listed
+/// * `PriceStale` - The listed price is older than the staleness threshold
+pub(crate) fn get_fresh_price(env: &Env, asset: Option<Address>) -> Result<i128, CrossAssetError> {
+    let config = get_asset_config(env, &AssetKey::from_option(asset))?;
+    require_fresh_price(env, &config)?;
+    Ok(config.price)
+}
+
+/// Value of `amount` at `price` (7 decimals), rounded toward zero
+pub fn value_of(amount: i128, price: i128) -> i128 {
+    (amount * price) / PRICE_SCALE
+}
+
+/// Amount worth `value` at `price` (7 decimals), rounded toward zero
+pub fn amount_of(value: i128, price: i128) -> i128 {
+    (value * PRICE_SCALE) / price
+}
+
+/// `amount` scaled by a basis point factor, rounded toward zero
+pub fn apply_bps(amount: i128, bps: i128) -> i128 {
+    (amount * bps) / BPS_SCALE
+}
+
+/// `numerator / denominator` in basis points, rounded toward zero
+pub fn ratio_bps(numerator: i128, denominator: i128) -> i128 {
+    (numerator * BPS_SCALE) / denominator
+}
+
+fn require_fresh_price(env: &Env, config: &AssetConfig) -> Result<(), CrossAssetError> {
+    let current_time = env.ledger().timestamp();
+    if current_time > config.price_updated_at
+        && current_time - config.price_updated_at > price_staleness_seconds(env)
+    {
+        return Err(CrossAssetError::PriceStale);
+    }
+    Ok(())
+}
+
 /// Return the list of all registered asset keys.
 ///
 /// Returns an empty vector if no assets have been configured.
@@ -715,27 +1161,820 @@ pub fn get_asset_config_by_address(
     get_asset_config(env, &asset_key)
 }
 
-// Helper functions
+/// Set the interest rate model for a registered asset (admin only).
+///
+/// Outstanding positions accrue at the new rate from their last update
+/// onwards, so callers should expect already-elapsed time to be priced
+/// with the new model the next time each position is touched.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `asset` - Asset to configure (`None` for XLM)
+/// * `model` - Rate model parameters
+///
+/// # Errors
+/// * `NotAuthorized` - Caller is not the admin
+/// * `AssetNotConfigured` - Asset has not been initialized
+/// * `InvalidRateModel` - Kink outside (0, 10000] or a negative rate component
+pub fn set_asset_rate_model(
+    env: &Env,
+    asset: Option<Address>,
+    model: AssetRateModel,
+) -> Result<(), CrossAssetError> {
+    require_admin(env)?;
 
-fn get_asset_config(env: &Env, asset_key: &AssetKey) -> Result<AssetConfig, CrossAssetError> {
-    let configs: Map<AssetKey, AssetConfig> = env
+    let asset_key = AssetKey::from_option(asset);
+    get_asset_config(env, &asset_key)?;
+
+    if model.kink_utilization_bps <= 0
+        || model.kink_utilization_bps > 10_000
+        || model.base_rate_bps < 0
+        || model.multiplier_bps < 0
+        || model.jump_multiplier_bps < 0
+    {
+        return Err(CrossAssetError::InvalidRateModel);
+    }
+
+    let mut models: Map<AssetKey, AssetRateModel> = env
         .storage()
         .persistent()
-        .get(&ASSET_CONFIGS)
+        .get(&RATE_MODELS)
         .unwrap_or(Map::new(env));
 
-    configs
-        .get(asset_key.clone())
-        .ok_or(CrossAssetError::AssetNotConfigured)
+    models.set(asset_key, model);
+    env.storage().persistent().set(&RATE_MODELS, &models);
+
+    Ok(())
 }
 
-fn require_valid_config(config: &AssetConfig) -> Result<(), CrossAssetError> {
-    require_valid_basis_points(config.collateral_factor)?;
-    require_valid_basis_points(config.liquidation_threshold)?;
-    require_valid_basis_points(config.reserve_factor)?;
+/// Get the interest rate model for an asset, if one has been set.
+pub fn get_asset_rate_model(env: &Env, asset: Option<Address>) -> Option<AssetRateModel> {
+    get_rate_model(env, &AssetKey::from_option(asset))
+}
 
-    if config.price <= 0 {
-        return Err(CrossAssetError::InvalidPrice);
+/// Get the current annual borrow rate for an asset in basis points.
+///
+/// Returns 0 for assets without a rate model.
+pub fn get_asset_borrow_rate(env: &Env, asset: Option<Address>) -> i128 {
+    borrow_rate_for(env, &AssetKey::from_option(asset))
+}
+
+/// Register or update a netting pair (admin/governance only).
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `collateral_asset` - Collateral side of the pair (`None` for XLM)
+/// * `debt_asset` - Debt side of the pair (`None` for XLM)
+/// * `netting_factor_bps` - Share of the offset removed from weighted debt
+/// * `max_benefit_value` - Per-user cap on the benefit in USD (7 decimals)
+///
+/// # Errors
+/// * `NotAuthorized` - Caller is not the admin
+/// * `AssetNotConfigured` - Either asset has not been initialized
+/// * `InvalidNettingPair` - Factor outside (0, `MAX_NETTING_FACTOR_BPS`],
+///   non-positive cap, or the pair limit has been reached
+pub fn set_netting_pair(
+    env: &Env,
+    collateral_asset: Option<Address>,
+    debt_asset: Option<Address>,
+    netting_factor_bps: i128,
+    max_benefit_value: i128,
+) -> Result<(), CrossAssetError> {
+    require_admin(env)?;
+
+    let collateral_key = AssetKey::from_option(collateral_asset);
+    let debt_key = AssetKey::from_option(debt_asset);
+    get_asset_config(env, &collateral_key)?;
+    get_asset_config(env, &debt_key)?;
+
+    if netting_factor_bps <= 0
+        || netting_factor_bps > MAX_NETTING_FACTOR_BPS
+        || max_benefit_value <= 0
+    {
+        return Err(CrossAssetError::InvalidNettingPair);
+    }
+
+    let pair = NettingPair {
+        collateral_asset: collateral_key,
+        debt_asset: debt_key,
+        netting_factor_bps,
+        max_benefit_value,
+    };
+
+    let mut pairs = get_netting_pairs(env);
+    match find_netting_pair(&pairs, &pair.collateral_asset, &pair.debt_asset) {
+        Some(index) => pairs.set(index, pair),
+        None => {
+            if pairs.len() >= MAX_NETTING_PAIRS {
+                return Err(CrossAssetError::InvalidNettingPair);
+            }
+            pairs.push_back(pair);
+        }
+    }
+    env.storage().persistent().set(&NETTING_PAIRS, &pairs);
+
+    Ok(())
+}
+
+/// Remove a netting pair (admin/governance only).
+///
+/// # Errors
+/// * `NotAuthorized` - Caller is not the admin
+/// * `InvalidNettingPair` - The pair is not registered
+pub fn remove_netting_pair(
+    env: &Env,
+    collateral_asset: Option<Address>,
+    debt_asset: Option<Address>,
+) -> Result<(), CrossAssetError> {
+    require_admin(env)?;
+
+    let mut pairs = get_netting_pairs(env);
+    let index = find_netting_pair(
+        &pairs,
+        &AssetKey::from_option(collateral_asset),
+        &AssetKey::from_option(debt_asset),
+    )
+    .ok_or(CrossAssetError::InvalidNettingPair)?;
+
+    pairs.remove(index);
+    env.storage().persistent().set(&NETTING_PAIRS, &pairs);
+
+    Ok(())
+}
+
+/// Return all registered netting pairs.
+pub fn get_netting_pairs(env: &Env) -> Vec<NettingPair> {
+    env.storage()
+        .persistent()
+        .get(&NETTING_PAIRS)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Define or update an e-mode category (admin only).
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `category_id` - Category identifier (0 is reserved for "no e-mode")
+/// * `category` - Category parameters and member assets
+///
+/// # Errors
+/// * `NotAuthorized` - Caller is not the admin
+/// * `AssetNotConfigured` - A factor is outside [0, 10000] or a member asset is unknown
+/// * `InvalidEMode` - Id is 0, no assets, or threshold below collateral factor
+pub fn set_emode_category(
+    env: &Env,
+    category_id: u32,
+    category: EModeCategory,
+) -> Result<(), CrossAssetError> {
+    require_admin(env)?;
+
+    if category_id == 0 || category.assets.is_empty() {
+        return Err(CrossAssetError::InvalidEMode);
+    }
+    require_valid_basis_points(category.collateral_factor)?;
+    require_valid_basis_points(category.liquidation_threshold)?;
+    if category.liquidation_threshold < category.collateral_factor {
+        return Err(CrossAssetError::InvalidEMode);
+    }
+    for asset_key in category.assets.iter() {
+        get_asset_config(env, &asset_key)?;
+    }
+
+    let mut categories: Map<u32, EModeCategory> = env
+        .storage()
+        .persistent()
+        .get(&EMODE_CATEGORIES)
+        .unwrap_or(Map::new(env));
+    categories.set(category_id, category);
+    env.storage()
+        .persistent()
+        .set(&EMODE_CATEGORIES, &categories);
+
+    Ok(())
+}
+
+/// Get an e-mode category by id (`None` for id 0 or unknown ids).
+pub fn get_emode_category(env: &Env, category_id: u32) -> Option<EModeCategory> {
+    if category_id == 0 {
+        return None;
+    }
+    let categories: Map<u32, EModeCategory> = env
+        .storage()
+        .persistent()
+        .get(&EMODE_CATEGORIES)
+        .unwrap_or(Map::new(env));
+    categories.get(category_id)
+}
+
+/// Get the e-mode category a user has opted into (0 if none).
+pub fn get_user_emode(env: &Env, user: &Address) -> u32 {
+    let user_emodes: Map<Address, u32> = env
+        .storage()
+        .persistent()
+        .get(&USER_EMODES)
+        .unwrap_or(Map::new(env));
+    user_emodes.get(user.clone()).unwrap_or(0)
+}
+
+/// Opt a user's position into an e-mode category, or out of e-mode with 0.
+///
+/// Entering a category requires every asset the user currently borrows to be
+/// a member. The switch is rejected if it would leave the position with a
+/// health factor below 1.0 (e.g. when leaving e-mode).
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `user` - User switching category (must authorize)
+/// * `category_id` - Category to enter, or 0 to leave e-mode
+///
+/// # Errors
+/// * `InvalidEMode` - Unknown category or a borrowed asset is outside it
+/// * `UnhealthyPosition` - The switch would make the position liquidatable
+/// * `PriceStale` - Stale price prevents health factor calculation
+pub fn set_user_emode(env: &Env, user: Address, category_id: u32) -> Result<(), CrossAssetError> {
+    user.require_auth();
+
+    if category_id != 0 {
+        let category = get_emode_category(env, category_id).ok_or(CrossAssetError::InvalidEMode)?;
+        for asset_key in get_asset_list(env).iter() {
+            let position = get_user_asset_position(env, &user, asset_key.to_option());
+            let has_debt = position.debt_principal > 0 || position.accrued_interest > 0;
+            if has_debt && !category.assets.contains(&asset_key) {
+                return Err(CrossAssetError::InvalidEMode);
+            }
+        }
+    }
+
+    let previous = get_user_emode(env, &user);
+    store_user_emode(env, &user, category_id);
+
+    let summary = get_user_position_summary(env, &user)?;
+    if summary.weighted_debt_value > 0 && summary.health_factor < 10_000 {
+        store_user_emode(env, &user, previous);
+        return Err(CrossAssetError::UnhealthyPosition);
+    }
+
+    Ok(())
+}
+
+fn store_user_emode(env: &Env, user: &Address, category_id: u32) {
+    let mut user_emodes: Map<Address, u32> = env
+        .storage()
+        .persistent()
+        .get(&USER_EMODES)
+        .unwrap_or(Map::new(env));
+    if category_id == 0 {
+        user_emodes.remove(user.clone());
+    } else {
+        user_emodes.set(user.clone(), category_id);
+    }
+    env.storage().persistent().set(&USER_EMODES, &user_emodes);
+}
+
+// Helper functions
+
+/// Configure isolation mode for a registered asset (admin only).
+///
+/// Changing the flag does not affect existing positions; the deposit checks
+/// apply to subsequent deposits only.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `asset` - Asset to configure (`None` for XLM)
+/// * `is_isolated` - Whether the asset may only be used as sole collateral
+/// * `debt_ceiling` - Maximum debt value in USD borrowed against it (0 = unlimited)
+///
+/// # Errors
+/// * `NotAuthorized` - Caller is not the admin
+/// * `AssetNotConfigured` - Asset has not been initialized or the ceiling is negative
+pub fn set_asset_isolation(
+    env: &Env,
+    asset: Option<Address>,
+    is_isolated: bool,
+    debt_ceiling: i128,
+) -> Result<(), CrossAssetError> {
+    require_admin(env)?;
+
+    if debt_ceiling < 0 {
+        return Err(CrossAssetError::AssetNotConfigured);
+    }
+
+    let asset_key = AssetKey::from_option(asset);
+    let mut config = get_asset_config(env, &asset_key)?;
+    config.is_isolated = is_isolated;
+    config.isolated_debt_ceiling = debt_ceiling;
+
+    let mut configs: Map<AssetKey, AssetConfig> = env
+        .storage()
+        .persistent()
+        .get(&ASSET_CONFIGS)
+        .unwrap_or(Map::new(env));
+
+    configs.set(asset_key, config);
+    env.storage().persistent().set(&ASSET_CONFIGS, &configs);
+
+    Ok(())
+}
+
+/// Set the reserve factor of a registered asset.
+///
+/// Called by `reserve::apply_reserve_factor`, which authorizes the caller and
+/// bounds the factor.
+///
+/// # Errors
+/// * `AssetNotConfigured` - Asset has not been initialized
+pub(crate) fn set_asset_reserve_factor(
+    env: &Env,
+    asset: Option<Address>,
+    reserve_factor: i128,
+) -> Result<(), CrossAssetError> {
+    let asset_key = AssetKey::from_option(asset);
+    let mut config = get_asset_config(env, &asset_key)?;
+    config.reserve_factor = reserve_factor;
+
+    let mut configs: Map<AssetKey, AssetConfig> = env
+        .storage()
+        .persistent()
+        .get(&ASSET_CONFIGS)
+        .unwrap_or(Map::new(env));
+
+    configs.set(asset_key, config);
+    env.storage().persistent().set(&ASSET_CONFIGS, &configs);
+
+    Ok(())
+}
+
+/// Total debt value in USD currently borrowed against an isolated asset.
+pub fn get_isolated_debt(env: &Env, asset: Option<Address>) -> i128 {
+    get_isolated_debt_for(env, &AssetKey::from_option(asset))
+}
+
+/// Find the isolated asset a user holds as collateral, if any.
+fn find_isolated_collateral(env: &Env, user: &Address) -> Option<AssetKey> {
+    let asset_list = get_asset_list(env);
+    for i in 0..asset_list.len() {
+        let asset_key = asset_list.get(i).unwrap();
+        let is_isolated = get_asset_config(env, &asset_key)
+            .map(|config| config.is_isolated)
+            .unwrap_or(false);
+        if is_isolated && get_user_asset_position(env, user, asset_key.to_option()).collateral > 0 {
+            return Some(asset_key);
+        }
+    }
+    None
+}
+
+/// Reject deposits that would mix isolated collateral with other collateral.
+///
+/// An isolated asset may only be deposited when the user holds no other
+/// collateral; any other asset may only be deposited when the user holds no
+/// isolated collateral.
+fn require_isolation_compatible(
+    env: &Env,
+    user: &Address,
+    asset_key: &AssetKey,
+    config: &AssetConfig,
+) -> Result<(), CrossAssetError> {
+    let asset_list = get_asset_list(env);
+    for i in 0..asset_list.len() {
+        let other_key = asset_list.get(i).unwrap();
+        if other_key == *asset_key {
+            continue;
+        }
+        let position = get_user_asset_position(env, user, other_key.to_option());
+        if position.collateral == 0 {
+            continue;
+        }
+        let other_isolated = get_asset_config(env, &other_key)
+            .map(|other| other.is_isolated)
+            .unwrap_or(false);
+        if config.is_isolated || other_isolated {
+            return Err(CrossAssetError::IsolationModeViolation);
+        }
+    }
+    Ok(())
+}
+
+fn get_isolated_debt_for(env: &Env, asset_key: &AssetKey) -> i128 {
+    let debts: Map<AssetKey, i128> = env
+        .storage()
+        .persistent()
+        .get(&ISOLATED_DEBT)
+        .unwrap_or(Map::new(env));
+
+    debts.get(asset_key.clone()).unwrap_or(0)
+}
+
+/// Adjust the isolated debt for an asset, flooring at zero since repayments
+/// are valued at the current price rather than the price at borrow time.
+fn update_isolated_debt(env: &Env, asset_key: &AssetKey, delta: i128) {
+    let mut debts: Map<AssetKey, i128> = env
+        .storage()
+        .persistent()
+        .get(&ISOLATED_DEBT)
+        .unwrap_or(Map::new(env));
+
+    let current = debts.get(asset_key.clone()).unwrap_or(0);
+    debts.set(asset_key.clone(), (current + delta).max(0));
+    env.storage().persistent().set(&ISOLATED_DEBT, &debts);
+}
+
+/// Set the utilization buffer for a registered asset (admin only).
+///
+/// Borrows are rejected once total borrows would exceed
+/// `total_supply * (10000 - buffer_bps) / 10000`. A buffer of 0 disables the
+/// check.
+///
+/// # Errors
+/// * `NotAuthorized` - Caller is not the admin
+/// * `AssetNotConfigured` - Asset has not been initialized or the buffer is
+///   outside [0, `MAX_UTILIZATION_BUFFER_BPS`]
+pub fn set_utilization_buffer(
+    env: &Env,
+    asset: Option<Address>,
+    buffer_bps: i128,
+) -> Result<(), CrossAssetError> {
+    require_admin(env)?;
+
+    if !(0..=MAX_UTILIZATION_BUFFER_BPS).contains(&buffer_bps) {
+        return Err(CrossAssetError::AssetNotConfigured);
+    }
+
+    let asset_key = AssetKey::from_option(asset);
+    get_asset_config(env, &asset_key)?;
+
+    let mut buffers: Map<AssetKey, i128> = env
+        .storage()
+        .persistent()
+        .get(&UTILIZATION_BUFFERS)
+        .unwrap_or(Map::new(env));
+    buffers.set(asset_key, buffer_bps);
+    env.storage()
+        .persistent()
+        .set(&UTILIZATION_BUFFERS, &buffers);
+
+    Ok(())
+}
+
+/// Get the utilization buffer for an asset in basis points (0 if unset).
+pub fn get_utilization_buffer(env: &Env, asset: Option<Address>) -> i128 {
+    get_utilization_buffer_for(env, &AssetKey::from_option(asset))
+}
+
+/// Report the remaining headroom under an asset's caps.
+///
+/// # Errors
+/// * `AssetNotConfigured` - Asset has not been initialized
+pub fn get_remaining_caps(
+    env: &Env,
+    asset: Option<Address>,
+) -> Result<RemainingCaps, CrossAssetError> {
+    let asset_key = AssetKey::from_option(asset);
+    let config = get_asset_config(env, &asset_key)?;
+
+    let total_supply = get_total_supply(env, &asset_key);
+    let total_borrow = get_total_borrow(env, &asset_key);
+    let utilization_buffer_bps = get_utilization_buffer_for(env, &asset_key);
+
+    let remaining_supply = if config.max_supply > 0 {
+        (config.max_supply - total_supply).max(0)
+    } else {
+        i128::MAX
+    };
+
+    let mut remaining_borrow = if config.max_borrow > 0 {
+        (config.max_borrow - total_borrow).max(0)
+    } else {
+        i128::MAX
+    };
+    if utilization_buffer_bps > 0 {
+        let buffered =
+            (max_buffered_borrow(total_supply, utilization_buffer_bps) - total_borrow).max(0);
+        remaining_borrow = remaining_borrow.min(buffered);
+    }
+
+    Ok(RemainingCaps {
+        total_supply,
+        total_borrow,
+        remaining_supply,
+        remaining_borrow,
+        utilization_buffer_bps,
+    })
+}
+
+/// Enforce the supply cap for a deposit made outside this module.
+///
+/// Assets without a cross-asset configuration fall back to the network's
+/// default supply cap.
+pub(crate) fn check_supply_cap(
+    env: &Env,
+    asset: Option<Address>,
+    amount: i128,
+) -> Result<(), CrossAssetError> {
+    let asset_key = AssetKey::from_option(asset);
+    match get_asset_config(env, &asset_key) {
+        Ok(config) => require_supply_cap(env, &asset_key, &config, amount),
+        Err(_) => {
+            let cap = default_supply_cap(env);
+            if cap > 0 && get_total_supply(env, &asset_key) + amount > cap {
+                return Err(CrossAssetError::SupplyCapExceeded);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Enforce the borrow cap and utilization buffer for a borrow made outside
+/// this module.
+///
+/// Assets without a cross-asset configuration fall back to the network's
+/// default borrow cap.
+pub(crate) fn check_borrow_cap(
+    env: &Env,
+    asset: Option<Address>,
+    amount: i128,
+) -> Result<(), CrossAssetError> {
+    let asset_key = AssetKey::from_option(asset);
+    match get_asset_config(env, &asset_key) {
+        Ok(config) => require_borrow_cap(env, &asset_key, &config, amount),
+        Err(_) => {
+            let cap = default_borrow_cap(env);
+            if cap > 0 && get_total_borrow(env, &asset_key) + amount > cap {
+                return Err(CrossAssetError::BorrowCapExceeded);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Supply of an asset not lent out: total supply minus total borrows,
+/// floored at zero
+pub fn get_available_liquidity(env: &Env, asset: Option<Address>) -> i128 {
+    let asset_key = AssetKey::from_option(asset);
+    (get_total_supply(env, &asset_key) - get_total_borrow(env, &asset_key)).max(0)
+}
+
+/// Enforce that a withdrawal leaves the supply outstanding borrows have
+/// claim on, so utilization never rises above 100%.
+pub(crate) fn check_withdraw_liquidity(
+    env: &Env,
+    asset: Option<Address>,
+    amount: i128,
+) -> Result<(), CrossAssetError> {
+    match withdraw_liquidity_limit(env, asset) {
+        Some(limit) if amount > limit => Err(CrossAssetError::InsufficientLiquidity),
+        _ => Ok(()),
+    }
+}
+
+/// Most a withdrawal of the asset can take, or None while nothing is
+/// borrowed and withdrawals cannot raise utilization
+pub(crate) fn withdraw_liquidity_limit(env: &Env, asset: Option<Address>) -> Option<i128> {
+    let asset_key = AssetKey::from_option(asset.clone());
+    if get_total_borrow(env, &asset_key) == 0 {
+        return None;
+    }
+    Some(get_available_liquidity(env, asset))
+}
+
+/// Record a supply change made outside this module, flooring the total at zero.
+pub(crate) fn record_supply_change(env: &Env, asset: Option<Address>, delta: i128) {
+    let asset_key = AssetKey::from_option(asset);
+    let current = get_total_supply(env, &asset_key);
+    update_total_supply(env, &asset_key, delta.max(-current));
+}
+
+/// Record a borrow change made outside this module, flooring the total at zero.
+pub(crate) fn record_borrow_change(env: &Env, asset: Option<Address>, delta: i128) {
+    let asset_key = AssetKey::from_option(asset);
+    let current = get_total_borrow(env, &asset_key);
+    update_total_borrow(env, &asset_key, delta.max(-current));
+}
+
+/// Recorded total supply and total borrows of every asset with a total
+pub(crate) fn get_recorded_totals(env: &Env) -> (Map<AssetKey, i128>, Map<AssetKey, i128>) {
+    let supplies = env
+        .storage()
+        .persistent()
+        .get(&TOTAL_SUPPLIES)
+        .unwrap_or(Map::new(env));
+    let borrows = env
+        .storage()
+        .persistent()
+        .get(&TOTAL_BORROWS)
+        .unwrap_or(Map::new(env));
+    (supplies, borrows)
+}
+
+/// Collateral and debt (principal plus settled interest) of all
+/// cross-asset positions, summed per asset
+pub(crate) fn get_position_totals(env: &Env) -> Map<AssetKey, (i128, i128)> {
+    let positions: Map<UserAssetKey, AssetPosition> = env
+        .storage()
+        .persistent()
+        .get(&USER_POSITIONS)
+        .unwrap_or(Map::new(env));
+
+    let mut totals: Map<AssetKey, (i128, i128)> = Map::new(env);
+    for (key, position) in positions.iter() {
+        let (collateral, debt) = totals.get(key.asset.clone()).unwrap_or((0, 0));
+        totals.set(
+            key.asset,
+            (
+                collateral.saturating_add(position.collateral),
+                debt.saturating_add(position.debt_principal)
+                    .saturating_add(position.accrued_interest),
+            ),
+        );
+    }
+    totals
+}
+
+fn require_supply_cap(
+    env: &Env,
+    asset_key: &AssetKey,
+    config: &AssetConfig,
+    amount: i128,
+) -> Result<(), CrossAssetError> {
+    if config.max_supply > 0 && get_total_supply(env, asset_key) + amount > config.max_supply {
+        return Err(CrossAssetError::SupplyCapExceeded);
+    }
+    Ok(())
+}
+
+fn require_borrow_cap(
+    env: &Env,
+    asset_key: &AssetKey,
+    config: &AssetConfig,
+    amount: i128,
+) -> Result<(), CrossAssetError> {
+    let total_borrow = get_total_borrow(env, asset_key);
+    if config.max_borrow > 0 && total_borrow + amount > config.max_borrow {
+        return Err(CrossAssetError::BorrowCapExceeded);
+    }
+
+    let buffer_bps = get_utilization_buffer_for(env, asset_key);
+    if buffer_bps > 0 {
+        let total_supply = get_total_supply(env, asset_key);
+        if total_borrow + amount > max_buffered_borrow(total_supply, buffer_bps) {
+            return Err(CrossAssetError::UtilizationBufferExceeded);
+        }
+    }
+    Ok(())
+}
+
+/// Maximum total borrows that leave `buffer_bps` of supply unborrowed
+fn max_buffered_borrow(total_supply: i128, buffer_bps: i128) -> i128 {
+    total_supply * (10_000 - buffer_bps) / 10_000
+}
+
+fn get_utilization_buffer_for(env: &Env, asset_key: &AssetKey) -> i128 {
+    let buffers: Map<AssetKey, i128> = env
+        .storage()
+        .persistent()
+        .get(&UTILIZATION_BUFFERS)
+        .unwrap_or(Map::new(env));
+
+    buffers.get(asset_key.clone()).unwrap_or(0)
+}
+
+fn find_netting_pair(
+    pairs: &Vec<NettingPair>,
+    collateral_asset: &AssetKey,
+    debt_asset: &AssetKey,
+) -> Option<u32> {
+    for i in 0..pairs.len() {
+        let pair = pairs.get(i).unwrap();
+        if &pair.collateral_asset == collateral_asset && &pair.debt_asset == debt_asset {
+            return Some(i);
+        }
+    }
+    None
+}
+
+/// Total weighted debt offset by netting pairs for the given per-asset values.
+///
+/// Each unit of collateral and debt value is consumed by at most one pair, in
+/// registration order, so overlapping pairs cannot net the same exposure twice.
+fn compute_netting_benefit(
+    env: &Env,
+    collateral_values: &Map<AssetKey, i128>,
+    debt_values: &Map<AssetKey, i128>,
+) -> i128 {
+    let mut remaining_collateral = collateral_values.clone();
+    let mut remaining_debt = debt_values.clone();
+    let mut benefit: i128 = 0;
+
+    for pair in get_netting_pairs(env).iter() {
+        let collateral = remaining_collateral
+            .get(pair.collateral_asset.clone())
+            .unwrap_or(0);
+        let debt = remaining_debt.get(pair.debt_asset.clone()).unwrap_or(0);
+        let offset = collateral.min(debt);
+        if offset <= 0 {
+            continue;
+        }
+
+        remaining_collateral.set(pair.collateral_asset.clone(), collateral - offset);
+        remaining_debt.set(pair.debt_asset.clone(), debt - offset);
+
+        let pair_benefit = (offset * pair.netting_factor_bps / 10_000).min(pair.max_benefit_value);
+        benefit += pair_benefit;
+    }
+
+    benefit
+}
+
+fn get_rate_model(env: &Env, asset_key: &AssetKey) -> Option<AssetRateModel> {
+    let models: Map<AssetKey, AssetRateModel> = env
+        .storage()
+        .persistent()
+        .get(&RATE_MODELS)
+        .unwrap_or(Map::new(env));
+
+    models.get(asset_key.clone())
+}
+
+fn borrow_rate_for(env: &Env, asset_key: &AssetKey) -> i128 {
+    let asset = asset_key.to_option();
+    let curve = match crate::interest_rate::get_effective_rate_curve(env, &asset) {
+        Some(curve) => curve,
+        None => return 0,
+    };
+
+    crate::interest_rate::calculate_rate(env, &asset, &curve, asset_utilization(env, asset_key))
+        .unwrap_or_else(|_| panic_with_error!(env, CrossAssetError::InvalidRateModel))
+}
+
+/// Utilization of an asset (`total borrows / total supplies`, basis points)
+fn asset_utilization(env: &Env, asset_key: &AssetKey) -> i128 {
+    let total_supply = get_total_supply(env, asset_key);
+    let total_borrow = get_total_borrow(env, asset_key);
+    if total_supply > 0 {
+        (total_borrow.max(0) * 10_000 / total_supply).min(10_000)
+    } else {
+        0
+    }
+}
+
+/// Interest owed on `position.debt_principal` since `position.last_updated`
+/// that has not yet been settled into `accrued_interest`.
+fn pending_interest(env: &Env, asset_key: &AssetKey, position: &AssetPosition) -> i128 {
+    let now = env.ledger().timestamp();
+    if position.debt_principal <= 0 || now <= position.last_updated {
+        return 0;
+    }
+
+    let rate_bps = borrow_rate_for(env, asset_key);
+    if rate_bps <= 0 {
+        return 0;
+    }
+
+    let elapsed = (now - position.last_updated) as i128;
+    position
+        .debt_principal
+        .saturating_mul(rate_bps)
+        .saturating_mul(elapsed)
+        / (BPS_SCALE * seconds_per_year(env) as i128)
+}
+
+/// Settle pending interest into the position and the asset's total borrows.
+fn accrue_position_interest(env: &Env, asset_key: &AssetKey, position: &mut AssetPosition) {
+    let interest = pending_interest(env, asset_key, position);
+    let asset = asset_key.to_option();
+    crate::interest_rate::checkpoint_dynamic_kink(env, &asset, asset_utilization(env, asset_key))
+        .unwrap_or_else(|_| panic_with_error!(env, CrossAssetError::InvalidRateModel));
+    if interest > 0 {
+        position.accrued_interest += interest;
+        update_total_borrow(env, asset_key, interest);
+        crate::reserve::accrue_protocol_reserve(env, &asset, interest);
+    }
+    position.last_updated = env.ledger().timestamp();
+}
+
+fn get_asset_config(env: &Env, asset_key: &AssetKey) -> Result<AssetConfig, CrossAssetError> {
+    let configs: Map<AssetKey, AssetConfig> = env
+        .storage()
+        .persistent()
+        .get(&ASSET_CONFIGS)
+        .unwrap_or(Map::new(env));
+
+    configs
+        .get(asset_key.clone())
+        .ok_or(CrossAssetError::AssetNotConfigured)
+}
+
+pub(crate) fn require_valid_config(config: &AssetConfig) -> Result<(), CrossAssetError> {
+    require_valid_basis_points(config.collateral_factor)?;
+    require_valid_basis_points(config.liquidation_threshold)?;
+    require_valid_basis_points(config.reserve_factor)?;
+
+    if config.price <= 0 {
+        return Err(CrossAssetError::InvalidPrice);
+    }
+
+    if config.isolated_debt_ceiling < 0 {
+        return Err(CrossAssetError::AssetNotConfigured);
     }
 
     // Liquidation threshold must be >= collateral factor (LTV)
@@ -0,0 +1,325 @@
+//! # Debt Swap
+//!
+//! `swap_debt` refinances part of a user's cross-asset debt from one asset
+//! into another in a single call:
+//!
+//! 1. The protocol flash-lends `amount` of the old asset and repays the
+//!    user's debt in that asset with it.
+//! 2. The user borrows enough of the new asset that swapping it through the
+//!    AMM covers the flash amount plus the flash loan fee. The amount is
+//!    priced from the listed oracle prices of both assets.
+//! 3. The swap proceeds settle the flash loan; the fee goes to the old
+//!    asset's protocol reserve (or the fee collector).
+//!
+//! Debt is tracked per asset in the cross-asset positions, so after the swap
+//! the old asset's debt is `amount` lower and the new asset's debt is
+//! `borrowed` higher. Health is the cross-asset position health factor,
+//! which values every asset at its price.
+//!
+//! The fee is the user's current flash loan fee for the old asset
+//! (`get_asset_flash_loan_fee_bps`); like other flash loan fees it goes to the
+//! fee collector while protocol fees are switched on.
+//! The position must not be liquidatable before the swap, and the new borrow
+//! runs through the regular cross-asset borrow checks (caps, e-mode,
+//! isolation, circuit breaker, borrow capacity). A failing step, or a swap
+//! returning less than the flash amount plus fee, rolls back the whole call.
+
+use soroban_sdk::{contracterror, contracttype, token, Address, Env};
+use stellarlend_amm::SwapParams;
+
+use crate::cross_asset::{self, CrossAssetError};
+use crate::deposit::{token_address, DepositDataKey};
+use crate::events::{emit_debt_swapped, DebtSwappedEvent};
+use crate::risk_management::HEALTH_FACTOR_SCALE;
+
+/// Basis points scale
+const BPS_SCALE: i128 = 10_000;
+
+/// Seconds the refinancing swap stays valid
+const SWAP_DEADLINE_SECONDS: u64 = 300;
+
+/// Errors that can occur when swapping debt
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum DebtSwapError {
+    /// Amount must be greater than zero
+    InvalidAmount = 1,
+    /// The old and new debt assets are the same
+    SameAsset = 2,
+    /// Slippage tolerance out of range or below the AMM's slippage, or the
+    /// swap returned less than the flash amount plus fee
+    SlippageExceeded = 3,
+    /// No enabled AMM protocol supports the pair
+    UnsupportedPair = 4,
+    /// The user's debt is smaller than the amount to swap
+    InsufficientDebt = 5,
+    /// The protocol does not hold enough of the new asset to lend
+    InsufficientLiquidity = 6,
+    /// The position is liquidatable before or after the swap
+    HealthCheckFailed = 7,
+    /// The AMM rejected the swap
+    SwapFailed = 8,
+    /// Borrowing the new asset failed
+    BorrowFailed = 9,
+    /// Repaying the old asset failed
+    RepayFailed = 10,
+    /// The native asset address is not configured
+    InvalidAsset = 11,
+    /// Overflow occurred during calculation
+    Overflow = 12,
+    /// An asset is not listed or its price is stale
+    PriceUnavailable = 13,
+}
+
+/// Outcome of a debt swap
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DebtSwapResult {
+    /// Old asset debt repaid
+    pub repaid: i128,
+    /// New asset borrowed
+    pub borrowed: i128,
+    /// Flash loan fee paid in the old asset
+    pub flash_fee: i128,
+    /// Health factor before the swap (scaled by `HEALTH_FACTOR_SCALE`)
+    pub health_before: i128,
+    /// Health factor after the swap
+    pub health_after: i128,
+}
+
+/// Move `amount` of debt from `from_debt_asset` to `to_debt_asset`
+///
+/// # Arguments
+/// * `user` - The position owner (must authorize)
+/// * `from_debt_asset` - Asset whose debt is repaid (None for native XLM)
+/// * `to_debt_asset` - Asset the new debt is opened in
+/// * `amount` - Debt repaid in `from_debt_asset`
+/// * `max_slippage_bps` - Largest swap loss accepted
+///
+/// # Errors
+/// * `InvalidAmount` / `SameAsset` / `SlippageExceeded` - Invalid input, or
+///   the AMM's swap slippage exceeds `max_slippage_bps`
+/// * `InsufficientDebt` - The user owes less than `amount` of the old asset
+/// * `InsufficientLiquidity` - The protocol cannot lend the new asset
+/// * `HealthCheckFailed` - The position is liquidatable before or after
+/// * `PriceUnavailable` - An asset is not listed or its price is stale
+/// * `RepayFailed` / `BorrowFailed` / `SwapFailed` - A step failed
+pub fn swap_debt(
+    env: &Env,
+    user: Address,
+    from_debt_asset: Option<Address>,
+    to_debt_asset: Option<Address>,
+    amount: i128,
+    max_slippage_bps: i128,
+) -> Result<DebtSwapResult, DebtSwapError> {
+    if amount <= 0 {
+        return Err(DebtSwapError::InvalidAmount);
+    }
+    if from_debt_asset == to_debt_asset {
+        return Err(DebtSwapError::SameAsset);
+    }
+    if !(0..BPS_SCALE).contains(&max_slippage_bps) {
+        return Err(DebtSwapError::SlippageExceeded);
+    }
+    let slippage = amm_slippage(env, max_slippage_bps)?;
+    let protocol = find_protocol(env, &to_debt_asset, &from_debt_asset)?;
+    user.require_auth();
+
+    let health_before = health_factor(env, &user)?;
+    if health_before < HEALTH_FACTOR_SCALE {
+        return Err(DebtSwapError::HealthCheckFailed);
+    }
+    if cross_asset::get_user_asset_debt(env, &user, from_debt_asset.clone()) < amount {
+        return Err(DebtSwapError::InsufficientDebt);
+    }
+
+    let flash_fee = mul_div_ceil(
+        amount,
+        crate::flash_loan::get_asset_flash_loan_fee_bps(env, &user, &from_debt_asset),
+        BPS_SCALE,
+    )?;
+    let owed = amount
+        .checked_add(flash_fee)
+        .ok_or(DebtSwapError::Overflow)?;
+
+    // The new asset worth the flash amount plus fee at the listed prices,
+    // grossed up for the AMM's slippage
+    let from_price = cross_asset::get_fresh_price(env, from_debt_asset.clone())
+        .map_err(|_| DebtSwapError::PriceUnavailable)?;
+    let to_price = cross_asset::get_fresh_price(env, to_debt_asset.clone())
+        .map_err(|_| DebtSwapError::PriceUnavailable)?;
+    let borrowed = mul_div_ceil(
+        mul_div_ceil(owed, from_price, to_price)?,
+        BPS_SCALE,
+        BPS_SCALE - slippage,
+    )?;
+
+    let this = env.current_contract_address();
+    let from_token = token::Client::new(
+        env,
+        &token_address(env, &from_debt_asset).ok_or(DebtSwapError::InvalidAsset)?,
+    );
+    let to_token = token::Client::new(
+        env,
+        &token_address(env, &to_debt_asset).ok_or(DebtSwapError::InvalidAsset)?,
+    );
+    if to_token.balance(&this) < borrowed {
+        return Err(DebtSwapError::InsufficientLiquidity);
+    }
+
+    // Repay the old asset's debt with the flash amount, then open the new
+    // asset's debt and lend it to the user for the swap
+    cross_asset::apply_repay(env, &user, from_debt_asset.clone(), amount)
+        .map_err(|_| DebtSwapError::RepayFailed)?;
+    cross_asset::apply_borrow(env, &user, to_debt_asset.clone(), borrowed).map_err(
+        |err| match err {
+            CrossAssetError::ExceedsBorrowCapacity => DebtSwapError::HealthCheckFailed,
+            _ => DebtSwapError::BorrowFailed,
+        },
+    )?;
+    to_token.transfer(&this, &user, &borrowed);
+    let received = swap(
+        env,
+        &user,
+        protocol,
+        &to_debt_asset,
+        &from_debt_asset,
+        borrowed,
+        owed,
+        slippage,
+    )?;
+    if received < owed {
+        return Err(DebtSwapError::SlippageExceeded);
+    }
+
+    // Settle the flash loan and credit the fee to the collector or reserve
+    from_token.transfer_from(&this, &user, &this, &owed);
+    if flash_fee > 0 && crate::fees::is_fee_switch_on(env) {
+        crate::fees::accrue_fee(
+            env,
+            crate::fees::FeeKind::FlashLoan,
+            &user,
+            &from_debt_asset,
+            flash_fee,
+        )
+        .map_err(|_| DebtSwapError::Overflow)?;
+    } else if flash_fee > 0 {
+        let reserve_key = DepositDataKey::ProtocolReserve(from_debt_asset.clone());
+        let reserve = env
+            .storage()
+            .persistent()
+            .get::<DepositDataKey, i128>(&reserve_key)
+            .unwrap_or(0);
+        env.storage().persistent().set(
+            &reserve_key,
+            &reserve
+                .checked_add(flash_fee)
+                .ok_or(DebtSwapError::Overflow)?,
+        );
+        crate::financials::record(
+            env,
+            crate::financials::FinancialItem::FlashLoanFees,
+            &from_debt_asset,
+            flash_fee,
+        );
+    }
+
+    let health_after = health_factor(env, &user)?;
+    if health_after < HEALTH_FACTOR_SCALE {
+        return Err(DebtSwapError::HealthCheckFailed);
+    }
+
+    emit_debt_swapped(
+        env,
+        DebtSwappedEvent {
+            user,
+            from_asset: from_debt_asset,
+            to_asset: to_debt_asset,
+            repaid: amount,
+            borrowed,
+            flash_fee,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(DebtSwapResult {
+        repaid: amount,
+        borrowed,
+        flash_fee,
+        health_before,
+        health_after,
+    })
+}
+
+/// First enabled AMM protocol supporting the pair
+fn find_protocol(
+    env: &Env,
+    token_in: &Option<Address>,
+    token_out: &Option<Address>,
+) -> Result<Address, DebtSwapError> {
+    let protocols =
+        stellarlend_amm::amm::get_amm_protocols(env).map_err(|_| DebtSwapError::SwapFailed)?;
+    protocols
+        .iter()
+        .find(|(_, config)| {
+            config.enabled
+                && config.supported_pairs.iter().any(|pair| {
+                    (pair.token_a == *token_in && pair.token_b == *token_out)
+                        || (pair.token_a == *token_out && pair.token_b == *token_in)
+                })
+        })
+        .map(|(protocol, _)| protocol)
+        .ok_or(DebtSwapError::UnsupportedPair)
+}
+
+/// Swap the new asset into the old one through `protocol`
+#[allow(clippy::too_many_arguments)]
+fn swap(
+    env: &Env,
+    user: &Address,
+    protocol: Address,
+    token_in: &Option<Address>,
+    token_out: &Option<Address>,
+    amount_in: i128,
+    min_amount_out: i128,
+    slippage: i128,
+) -> Result<i128, DebtSwapError> {
+    let params = SwapParams {
+        protocol,
+        token_in: token_in.clone(),
+        token_out: token_out.clone(),
+        amount_in,
+        min_amount_out,
+        slippage_tolerance: slippage,
+        deadline: env.ledger().timestamp() + SWAP_DEADLINE_SECONDS,
+    };
+    crate::amm::swap(env, user.clone(), params).map_err(|_| DebtSwapError::SwapFailed)
+}
+
+/// Slippage the AMM applies, rejected up front when it exceeds the caller's
+/// tolerance
+fn amm_slippage(env: &Env, max_slippage_bps: i128) -> Result<i128, DebtSwapError> {
+    let settings =
+        stellarlend_amm::amm::get_amm_settings(env).map_err(|_| DebtSwapError::SwapFailed)?;
+    if settings.default_slippage > max_slippage_bps {
+        return Err(DebtSwapError::SlippageExceeded);
+    }
+    Ok(settings.default_slippage)
+}
+
+/// Health factor of a user's cross-asset position, every asset valued at its
+/// listed price
+fn health_factor(env: &Env, user: &Address) -> Result<i128, DebtSwapError> {
+    cross_asset::get_user_position_summary(env, user)
+        .map(|summary| summary.health_factor)
+        .map_err(|_| DebtSwapError::PriceUnavailable)
+}
+
+fn mul_div_ceil(a: i128, b: i128, c: i128) -> Result<i128, DebtSwapError> {
+    let product = a.checked_mul(b).ok_or(DebtSwapError::Overflow)?;
+    if c <= 0 {
+        return Err(DebtSwapError::Overflow);
+    }
+    Ok((product + c - 1) / c)
+}
@@ -10,10 +10,13 @@
 //!
 //! ## Storage Layout
 //! - `CollateralBalance(user)` — per-user collateral amount
+//! - `SupplyShares(user)` — per-user interest-bearing supply shares
+//! - `SupplyPool` — total shares and the underlying they redeem for
 //! - `Position(user)` — per-user position (collateral, debt, interest)
 //! - `AssetParams(asset)` — per-asset deposit parameters
 //! - `PauseSwitches` — operation pause flags
 //! - `ProtocolAnalytics` — aggregate protocol metrics
+//! - `AssetLedger(asset)` — per-asset supplied, borrowed and reserves
 //! - `UserAnalytics(user)` — per-user activity metrics
 //! - `ActivityLog` — bounded activity history (max 1000 entries)
 //!
@@ -21,14 +24,32 @@
 //! - Deposit amount must be strictly positive.
 //! - Deposits are rejected when the protocol or deposit operation is paused.
 //! - Token transfers use `transfer_from`, requiring prior user approval.
+//!
+//! ## Supply Shares
+//! Deposits mint shares at the current exchange rate
+//! (`total_underlying / total_shares`). As borrow interest accrues, the
+//! suppliers' portion (net of the reserve factor) is added to
+//! `total_underlying`, so the exchange rate only grows and each share redeems
+//! for more of the underlying asset. A user's `CollateralBalance` is refreshed
+//! to the value of their shares whenever they deposit or withdraw.
+//!
+//! ## Asset Ledger
+//! `ProtocolAnalytics` adds raw amounts of every asset into one total, so it
+//! cannot back utilization or valuation once more than one asset is listed.
+//! Each asset therefore also keeps an [`AssetLedger`] of supplied collateral
+//! and borrowed principal, updated wherever core deposits, withdrawals,
+//! borrows, repayments and liquidations move them. Protocol-wide totals are
+//! derived from the ledgers at oracle prices in the analytics report.
 
 #![allow(unused)]
 use soroban_sdk::{contracterror, contracttype, Address, Env, IntoVal, Map, Symbol, Val, Vec};
 
 use crate::events::{
-    emit_analytics_updated, emit_deposit, emit_position_updated, emit_user_activity_tracked,
-    AnalyticsUpdatedEvent, DepositEvent, PositionUpdatedEvent, UserActivityTrackedEvent,
+    emit_analytics_updated, emit_deposit, emit_on_behalf, emit_position_updated,
+    emit_user_activity_tracked, AnalyticsUpdatedEvent, DepositEvent, OnBehalfEvent,
+    PositionUpdatedEvent, UserActivityTrackedEvent,
 };
+use crate::operations::{record_operation, OperationKind};
 
 /// Errors that can occur during deposit operations
 #[contracterror]
@@ -49,6 +70,12 @@ pub enum DepositError {
     Overflow = 6,
     /// Reentrancy detected
     Reentrancy = 7,
+    /// Called while a flash loan is outstanding
+    FlashLoanInProgress = 8,
+    /// Deposit would exceed the asset's supply cap
+    SupplyCapExceeded = 9,
+    /// The compliance controls rejected the deposit
+    ComplianceRejected = 10,
 }
 
 /// Storage keys for deposit-related data
@@ -82,6 +109,18 @@ pub enum DepositDataKey {
     ProtocolReserve(Option<Address>),
     /// Native asset (XLM) contract address
     NativeAssetAddress,
+    /// Interest-bearing supply shares held by a user
+    /// Value type: i128
+    SupplyShares(Address),
+    /// Aggregate supply share accounting
+    /// Value type: SupplyPool
+    SupplyPool,
+    /// Per-asset supply and borrow aggregates
+    /// Value type: AssetLedger
+    AssetLedger(Option<Address>),
+    /// Assets with a ledger, in first-seen order
+    /// Value type: Vec<Option<Address>>
+    LedgerAssets,
 }
 
 /// Asset parameters for collateral
@@ -174,6 +213,35 @@ pub struct ProtocolAnalytics {
     pub total_value_locked: i128,
 }
 
+/// Aggregates of one asset's core market
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct AssetLedger {
+    pub asset: Option<Address>,
+    /// Collateral supplied, net of withdrawals and seizures
+    pub supplied: i128,
+    /// Debt principal borrowed, net of repayments and write-offs
+    pub borrowed: i128,
+    /// Protocol reserve balance
+    pub reserves: i128,
+    /// Borrow index at the last update (scaled by 1e18)
+    pub last_index: i128,
+    pub last_updated: u64,
+}
+
+/// Aggregate supply share accounting
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SupplyPool {
+    /// Total shares outstanding
+    pub total_shares: i128,
+    /// Underlying owed to all share holders, including accrued interest
+    pub total_underlying: i128,
+}
+
+/// Fixed-point scale of the supply exchange rate (1e9 = 1 underlying per share)
+pub const EXCHANGE_RATE_SCALE: i128 = 1_000_000_000;
+
 /// Deposit collateral function
 ///
 /// Allows users to deposit assets as collateral in the protocol.
@@ -193,6 +261,7 @@ pub struct ProtocolAnalytics {
 /// * `DepositError::InsufficientBalance` - If user doesn't have enough balance
 /// * `DepositError::DepositPaused` - If deposits are paused
 /// * `DepositError::AssetNotEnabled` - If asset is not enabled for deposits
+/// * `DepositError::SupplyCapExceeded` - If the deposit would exceed the asset's supply cap
 /// * `DepositError::Overflow` - If calculation overflow occurs
 ///
 /// # Security
@@ -208,6 +277,58 @@ pub fn deposit_collateral(
     user: Address,
     asset: Option<Address>,
     amount: i128,
+) -> Result<i128, DepositError> {
+    deposit_collateral_from(env, user.clone(), user, asset, amount)
+}
+
+/// Deposit collateral funded by a third party
+///
+/// `payer` supplies the tokens and must authorize the call; the collateral is
+/// credited to `user`, whose authorization is not required.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `payer` - The address supplying the tokens
+/// * `user` - The address whose position is credited
+/// * `asset` - The address of the asset contract to deposit (None for native XLM)
+/// * `amount` - The amount to deposit
+///
+/// # Returns
+/// Returns the updated collateral balance of `user`
+///
+/// # Errors
+/// Same as [`deposit_collateral`], with `InsufficientBalance` checked against
+/// the payer
+pub fn deposit_collateral_for(
+    env: &Env,
+    payer: Address,
+    user: Address,
+    asset: Option<Address>,
+    amount: i128,
+) -> Result<i128, DepositError> {
+    payer.require_auth();
+    let balance = deposit_collateral_from(env, payer.clone(), user.clone(), asset.clone(), amount)?;
+    emit_on_behalf(
+        env,
+        OnBehalfEvent {
+            payer,
+            user,
+            operation: Symbol::new(env, "deposit"),
+            asset,
+            amount,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(balance)
+}
+
+/// Deposit `amount` pulled from `payer` into `user`'s position
+pub(crate) fn deposit_collateral_from(
+    env: &Env,
+    payer: Address,
+    user: Address,
+    asset: Option<Address>,
+    amount: i128,
 ) -> Result<i128, DepositError> {
     // Validate amount
     if amount <= 0 {
@@ -217,6 +338,11 @@ pub fn deposit_collateral(
     // Check for reentrancy
     let _guard = crate::reentrancy::ReentrancyGuard::new(env).map_err(|_| DepositError::Reentrancy)?;
 
+    // Flash loan receivers may not deposit while the loan is outstanding
+    if crate::reentrancy::is_flash_loan_locked(env) {
+        return Err(DepositError::FlashLoanInProgress);
+    }
+
     // Check if deposits are paused
     // Note: The risk management system provides pause functionality through the public API.
     // This check maintains backward compatibility with the old pause switch system.
@@ -238,6 +364,17 @@ pub fn deposit_collateral(
     // We access the risk management storage directly to check pause status
     check_risk_management_pause(env)?;
 
+    // Enforce the per-asset supply cap shared with cross-asset positions
+    crate::cross_asset::check_supply_cap(env, asset.clone(), amount)
+        .map_err(|_| DepositError::SupplyCapExceeded)?;
+
+    // Regulated deployments gate deposits on the credited account
+    crate::compliance::check_operation(env, &user, Symbol::new(env, "deposit"), &asset, amount)
+        .map_err(|_| DepositError::ComplianceRejected)?;
+
+    // Settle borrow interest at the current utilization before it changes
+    crate::interest_rate::accrue_borrow_index(env).map_err(|_| DepositError::Overflow)?;
+
     // Get current timestamp
     let timestamp = env.ledger().timestamp();
 
@@ -265,22 +402,22 @@ pub fn deposit_collateral(
             }
         }
 
-        // Transfer tokens from user to contract using token contract
+        // Transfer tokens from payer to contract using token contract
         // Use the token contract's transfer_from method
         let token_client = soroban_sdk::token::Client::new(env, asset_addr);
 
-        // Check user balance
-        let user_balance = token_client.balance(&user);
-        if user_balance < amount {
+        // Check payer balance
+        let payer_balance = token_client.balance(&payer);
+        if payer_balance < amount {
             return Err(DepositError::InsufficientBalance);
         }
 
-        // Transfer tokens from user to contract
-        // The user must have approved the contract to spend their tokens
-        // transfer_from requires: spender (contract), from (user), to (contract), amount
+        // Transfer tokens from payer to contract
+        // The payer must have approved the contract to spend their tokens
+        // transfer_from requires: spender (contract), from (payer), to (contract), amount
         token_client.transfer_from(
             &env.current_contract_address(), // spender (this contract)
-            &user,                           // from (user)
+            &payer,                          // from (payer)
             &env.current_contract_address(), // to (this contract)
             &amount,
         );
@@ -290,6 +427,10 @@ pub fn deposit_collateral(
         // This is a placeholder for native asset handling
     }
 
+    // Mint supply shares at the current exchange rate
+    sync_legacy_collateral(env, &user)?;
+    mint_supply_shares(env, &user, amount)?;
+
     // Get or create user position
     let position_key = DepositDataKey::Position(user.clone());
     #[allow(clippy::unnecessary_lazy_evaluations)]
@@ -312,10 +453,12 @@ pub fn deposit_collateral(
         .get::<DepositDataKey, i128>(&collateral_key)
         .unwrap_or(0);
 
-    // Check for overflow
+    // Check for overflow; the balance also picks up any interest earned by
+    // previously minted shares
     let new_collateral = current_collateral
         .checked_add(amount)
-        .ok_or(DepositError::Overflow)?;
+        .ok_or(DepositError::Overflow)?
+        .max(get_underlying_balance(env, &user));
 
     // Update storage
     env.storage()
@@ -327,6 +470,19 @@ pub fn deposit_collateral(
     position.last_accrual_time = timestamp;
     env.storage().persistent().set(&position_key, &position);
 
+    // Make the position discoverable by snapshot exports
+    crate::snapshot::index_user(env, &user);
+
+    crate::cross_asset::record_supply_change(env, asset.clone(), amount);
+    update_asset_ledger(env, &asset, amount, 0);
+    crate::rewards::record_balance_change(
+        env,
+        &user,
+        &asset,
+        crate::rewards::RewardSide::Supply,
+        amount,
+    );
+
     // Update user analytics
     update_user_analytics(env, &user, amount, timestamp, true)?;
 
@@ -344,6 +500,14 @@ pub fn deposit_collateral(
     )?;
 
     // Emit deposit event
+    let operation_id = record_operation(
+        env,
+        OperationKind::Deposit,
+        &user,
+        &asset,
+        amount,
+        timestamp,
+    );
     emit_deposit(
         env,
         DepositEvent {
@@ -351,6 +515,7 @@ pub fn deposit_collateral(
             asset: asset.clone(),
             amount,
             timestamp,
+            operation_id,
         },
     );
 
@@ -366,6 +531,188 @@ pub fn deposit_collateral(
     Ok(new_collateral)
 }
 
+/// Get aggregate supply share accounting
+pub fn get_supply_pool(env: &Env) -> SupplyPool {
+    env.storage()
+        .persistent()
+        .get(&DepositDataKey::SupplyPool)
+        .unwrap_or(SupplyPool {
+            total_shares: 0,
+            total_underlying: 0,
+        })
+}
+
+/// Get the supply shares held by a user
+pub fn get_supply_shares(env: &Env, user: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DepositDataKey::SupplyShares(user.clone()))
+        .unwrap_or(0)
+}
+
+/// Get the current exchange rate of one share, scaled by `EXCHANGE_RATE_SCALE`
+///
+/// Starts at `EXCHANGE_RATE_SCALE` (1:1) and grows as borrow interest accrues.
+pub fn get_exchange_rate(env: &Env) -> i128 {
+    let pool = get_supply_pool(env);
+    if pool.total_shares == 0 {
+        return EXCHANGE_RATE_SCALE;
+    }
+    pool.total_underlying
+        .saturating_mul(EXCHANGE_RATE_SCALE)
+        .checked_div(pool.total_shares)
+        .unwrap_or(EXCHANGE_RATE_SCALE)
+}
+
+/// Get the underlying amount a user's shares currently redeem for
+pub fn get_underlying_balance(env: &Env, user: &Address) -> i128 {
+    let shares = get_supply_shares(env, user);
+    let pool = get_supply_pool(env);
+    if shares == 0 || pool.total_shares == 0 {
+        return 0;
+    }
+    shares
+        .saturating_mul(pool.total_underlying)
+        .checked_div(pool.total_shares)
+        .unwrap_or(0)
+}
+
+/// Mint shares for `amount` of underlying at the current exchange rate
+///
+/// Rounds down so existing holders are never diluted.
+fn mint_supply_shares(env: &Env, user: &Address, amount: i128) -> Result<i128, DepositError> {
+    let mut pool = get_supply_pool(env);
+    let shares = if pool.total_shares == 0 || pool.total_underlying == 0 {
+        amount
+    } else {
+        amount
+            .checked_mul(pool.total_shares)
+            .ok_or(DepositError::Overflow)?
+            .checked_div(pool.total_underlying)
+            .ok_or(DepositError::Overflow)?
+    };
+
+    pool.total_shares = pool
+        .total_shares
+        .checked_add(shares)
+        .ok_or(DepositError::Overflow)?;
+    pool.total_underlying = pool
+        .total_underlying
+        .checked_add(amount)
+        .ok_or(DepositError::Overflow)?;
+    env.storage()
+        .persistent()
+        .set(&DepositDataKey::SupplyPool, &pool);
+
+    let user_shares = get_supply_shares(env, user)
+        .checked_add(shares)
+        .ok_or(DepositError::Overflow)?;
+    env.storage()
+        .persistent()
+        .set(&DepositDataKey::SupplyShares(user.clone()), &user_shares);
+
+    Ok(shares)
+}
+
+/// Burn the shares backing `amount` of underlying leaving the pool
+///
+/// Rounds the burned shares up (so redemptions never over-withdraw) and caps
+/// them at the user's holdings.
+pub(crate) fn redeem_supply_shares(
+    env: &Env,
+    user: &Address,
+    amount: i128,
+) -> Result<i128, DepositError> {
+    let mut pool = get_supply_pool(env);
+    let user_shares = get_supply_shares(env, user);
+    if amount <= 0 || user_shares == 0 || pool.total_underlying == 0 {
+        return Ok(0);
+    }
+
+    let numerator = amount
+        .checked_mul(pool.total_shares)
+        .ok_or(DepositError::Overflow)?;
+    let mut shares = numerator / pool.total_underlying;
+    if numerator % pool.total_underlying != 0 {
+        shares += 1;
+    }
+    let shares = shares.min(user_shares);
+
+    pool.total_shares -= shares;
+    pool.total_underlying = pool.total_underlying.saturating_sub(amount).max(0);
+    env.storage()
+        .persistent()
+        .set(&DepositDataKey::SupplyPool, &pool);
+    env.storage().persistent().set(
+        &DepositDataKey::SupplyShares(user.clone()),
+        &(user_shares - shares),
+    );
+
+    Ok(shares)
+}
+
+/// Split newly accrued borrow interest between reserves and suppliers
+///
+/// The reserve factor portion of `asset` is booked to the protocol reserve.
+/// The insurance slice goes to the bad debt insurance reserve and the rest
+/// is credited to the supply pool.
+pub(crate) fn accrue_supply_interest(env: &Env, asset: &Option<Address>, interest: i128) {
+    if interest <= 0 {
+        return;
+    }
+    let reserve = crate::reserve::accrue_protocol_reserve(env, asset, interest);
+    let mut pool = get_supply_pool(env);
+    if pool.total_shares == 0 {
+        return;
+    }
+    let insurance = crate::bad_debt::accrue_insurance(env, interest);
+    pool.total_underlying = pool
+        .total_underlying
+        .saturating_add(interest - reserve - insurance);
+    env.storage()
+        .persistent()
+        .set(&DepositDataKey::SupplyPool, &pool);
+}
+
+/// Write a bad debt loss off the supply pool
+///
+/// Lowers the underlying without touching shares, so every supplier absorbs
+/// the loss in proportion to their shares. Returns the amount written off,
+/// which is capped at the pool's underlying.
+pub(crate) fn write_off_supply(env: &Env, loss: i128) -> i128 {
+    if loss <= 0 {
+        return 0;
+    }
+    let mut pool = get_supply_pool(env);
+    let written_off = loss.min(pool.total_underlying);
+    if written_off <= 0 {
+        return 0;
+    }
+    pool.total_underlying -= written_off;
+    env.storage()
+        .persistent()
+        .set(&DepositDataKey::SupplyPool, &pool);
+    written_off
+}
+
+/// Mint shares for collateral recorded before share accounting existed
+///
+/// A no-op once the user holds shares.
+pub(crate) fn sync_legacy_collateral(env: &Env, user: &Address) -> Result<(), DepositError> {
+    if get_supply_shares(env, user) > 0 {
+        return Ok(());
+    }
+    let legacy = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, i128>(&DepositDataKey::CollateralBalance(user.clone()))
+        .unwrap_or(0);
+    if legacy > 0 {
+        mint_supply_shares(env, user, legacy)?;
+    }
+    Ok(())
+}
+
 /// Set the native asset address (admin only).
 /// Required for deposit/borrow/repay with asset = None. Must be called before using None as asset.
 pub fn set_native_asset_address(
@@ -387,6 +734,21 @@ pub fn set_native_asset_address(
     Ok(())
 }
 
+/// Get the configured native asset address, if set
+pub(crate) fn get_native_asset_address(env: &Env) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get::<DepositDataKey, Address>(&DepositDataKey::NativeAssetAddress)
+}
+
+/// Token contract moved for an asset (the configured native asset for None)
+pub(crate) fn token_address(env: &Env, asset: &Option<Address>) -> Option<Address> {
+    match asset {
+        Some(asset) => Some(asset.clone()),
+        None => get_native_asset_address(env),
+    }
+}
+
 /// Update user analytics after deposit
 pub fn update_user_analytics(
     env: &Env,
@@ -467,6 +829,73 @@ pub fn update_protocol_analytics(
     Ok(())
 }
 
+/// Apply supply and borrow deltas to an asset's ledger, flooring at zero,
+/// and refresh the day's metric snapshot
+pub(crate) fn update_asset_ledger(
+    env: &Env,
+    asset: &Option<Address>,
+    supplied_delta: i128,
+    borrowed_delta: i128,
+) {
+    let key = DepositDataKey::AssetLedger(asset.clone());
+    let mut ledger: AssetLedger = match env.storage().persistent().get(&key) {
+        Some(ledger) => ledger,
+        None => {
+            let mut assets = get_ledger_assets(env);
+            assets.push_back(asset.clone());
+            env.storage()
+                .persistent()
+                .set(&DepositDataKey::LedgerAssets, &assets);
+            empty_ledger(asset)
+        }
+    };
+
+    ledger.supplied = ledger.supplied.saturating_add(supplied_delta).max(0);
+    ledger.borrowed = ledger.borrowed.saturating_add(borrowed_delta).max(0);
+    ledger.reserves = get_reserve_balance(env, asset);
+    ledger.last_index = crate::interest_rate::get_borrow_index(env).unwrap_or(ledger.last_index);
+    ledger.last_updated = env.ledger().timestamp();
+    env.storage().persistent().set(&key, &ledger);
+    crate::metric_history::record_daily_metrics(env);
+}
+
+/// Get an asset's ledger with its current reserve balance
+pub fn get_asset_ledger(env: &Env, asset: &Option<Address>) -> AssetLedger {
+    let mut ledger = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, AssetLedger>(&DepositDataKey::AssetLedger(asset.clone()))
+        .unwrap_or_else(|| empty_ledger(asset));
+    ledger.reserves = get_reserve_balance(env, asset);
+    ledger
+}
+
+/// Get the assets with a ledger, in first-seen order
+pub fn get_ledger_assets(env: &Env) -> Vec<Option<Address>> {
+    env.storage()
+        .persistent()
+        .get(&DepositDataKey::LedgerAssets)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn get_reserve_balance(env: &Env, asset: &Option<Address>) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DepositDataKey::ProtocolReserve(asset.clone()))
+        .unwrap_or(0)
+}
+
+fn empty_ledger(asset: &Option<Address>) -> AssetLedger {
+    AssetLedger {
+        asset: asset.clone(),
+        supplied: 0,
+        borrowed: 0,
+        reserves: 0,
+        last_index: 0,
+        last_updated: 0,
+    }
+}
+
 /// Add entry to activity log
 pub fn add_activity_log(
     env: &Env,
@@ -503,7 +932,9 @@ pub fn add_activity_log(
     Ok(())
 }
 
-/// Emit position updated event
+/// Emit position updated event, plus a health notification if the position
+/// crossed a notification threshold, flag the position if it became
+/// insolvent, and sync the user's receipt tokens
 pub fn emit_position_updated_event(env: &Env, user: &Address, position: &Position) {
     emit_position_updated(
         env,
@@ -513,6 +944,10 @@ pub fn emit_position_updated_event(env: &Env, user: &Address, position: &Positio
             debt: position.debt,
         },
     );
+    crate::notifications::check_health_thresholds(env, user, position);
+    crate::bad_debt::check_insolvency(env, user, position);
+    crate::tokenization::sync_position(env, user, position);
+    crate::liquidate::sync_borrower_index(env, user, position);
 }
 
 /// Emit analytics updated event
@@ -37,4 +37,8 @@ pub enum GovernanceError {
     Unauthorized = 131,
     AlreadyInitialized = 132,
     NotInitialized = 133,
+    InvalidAmount = 134,
+    InsufficientVotingDeposit = 135,
+    VotesNotYetDetermined = 136,
+    UnknownProposalKind = 137,
 }
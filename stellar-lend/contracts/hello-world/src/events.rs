@@ -1,7 +1,10 @@
 #![allow(unused_variables)]
 
-use soroban_sdk::{contractevent, Address, Env, String, Symbol, Vec};
+use soroban_sdk::{contractevent, Address, BytesN, Env, String, Symbol, Vec};
 
+use crate::fees::FeeKind;
+use crate::invariants::InvariantViolation;
+use crate::oracle::PriceSource;
 use crate::types::{AssetStatus, ProposalType, VoteType};
 
 // ============================================================================
@@ -15,6 +18,7 @@ pub struct DepositEvent {
     pub asset: Option<Address>,
     pub amount: i128,
     pub timestamp: u64,
+    pub operation_id: u64,
 }
 
 #[contractevent]
@@ -24,6 +28,7 @@ pub struct WithdrawalEvent {
     pub asset: Option<Address>,
     pub amount: i128,
     pub timestamp: u64,
+    pub operation_id: u64,
 }
 
 #[contractevent]
@@ -33,6 +38,7 @@ pub struct BorrowEvent {
     pub asset: Option<Address>,
     pub amount: i128,
     pub timestamp: u64,
+    pub operation_id: u64,
 }
 
 #[contractevent]
@@ -42,6 +48,18 @@ pub struct RepayEvent {
     pub asset: Option<Address>,
     pub amount: i128,
     pub timestamp: u64,
+    pub operation_id: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct OnBehalfEvent {
+    pub payer: Address,
+    pub user: Address,
+    pub operation: Symbol,
+    pub asset: Option<Address>,
+    pub amount: i128,
+    pub timestamp: u64,
 }
 
 #[contractevent]
@@ -59,66 +77,612 @@ pub struct LiquidationEvent {
 
 #[contractevent]
 #[derive(Clone, Debug)]
-pub struct FlashLoanInitiatedEvent {
+pub struct LiquidationSwapEvent {
+    pub liquidator: Address,
+    pub borrower: Address,
+    pub debt_asset: Option<Address>,
+    pub collateral_asset: Option<Address>,
+    pub collateral_swapped: i128,
+    pub amount_out: i128,
+    pub surplus: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent(topics = ["liquidation_auction_started"])]
+#[derive(Clone, Debug)]
+pub struct LiquidationAuctionStartedEvent {
+    pub borrower: Address,
+    pub starter: Address,
+    pub debt_asset: Option<Address>,
+    pub collateral_asset: Option<Address>,
+    pub duration: u64,
+    pub max_incentive_bps: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct LiquidationAuctionBidEvent {
+    pub borrower: Address,
+    pub liquidator: Address,
+    pub debt_liquidated: i128,
+    pub collateral_seized: i128,
+    pub incentive_bps: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct LiquidationAuctionClosedEvent {
+    pub borrower: Address,
+    pub caller: Address,
+    pub cancelled: bool,
+    pub debt_repaid: i128,
+    pub collateral_seized: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct BadDebtRecordedEvent {
+    pub borrower: Address,
+    pub asset: Option<Address>,
+    pub amount: i128,
+    pub total_shortfall: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent(topics = ["insolvency_detected"])]
+#[derive(Clone, Debug)]
+pub struct InsolvencyDetectedEvent {
+    pub user: Address,
+    pub collateral: i128,
+    pub debt: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct BadDebtSocializedEvent {
+    pub caller: Address,
+    pub asset: Option<Address>,
+    pub shortfall: i128,
+    pub covered_by_insurance: i128,
+    pub socialized: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct FlashLoanInitiatedEvent {
+    pub user: Address,
+    pub asset: Address,
+    pub amount: i128,
+    pub fee: i128,
+    pub callback: Address,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct FlashLoanRepaidEvent {
+    pub user: Address,
+    pub asset: Address,
+    pub amount: i128,
+    pub fee: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct AdminActionEvent {
+    pub actor: Address,
+    pub action: Symbol,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct PriceUpdatedEvent {
+    pub actor: Address,
+    pub asset: Address,
+    pub price: i128,
+    pub decimals: u32,
+    pub oracle: Address,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct OracleBondPostedEvent {
+    pub updater: Address,
+    pub amount: i128,
+    pub total_bond: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct OracleBondWithdrawnEvent {
+    pub updater: Address,
+    pub amount: i128,
+    pub total_bond: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct OracleBondSlashedEvent {
+    pub actor: Address,
+    pub updater: Address,
+    pub amount: i128,
+    pub recipient: Address,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct PriceDisputedEvent {
+    pub asset: Address,
+    pub min_price: i128,
+    pub max_price: i128,
+    pub deviation_bps: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct PriceDisputeResolvedEvent {
+    pub actor: Address,
+    pub asset: Address,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct OracleFailoverEvent {
+    pub asset: Address,
+    pub from: PriceSource,
+    pub to: PriceSource,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct PriceStaleEvent {
+    pub asset: Address,
+    pub last_price: i128,
+    pub last_updated: u64,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct CircuitBreakerTrippedEvent {
+    pub asset: Address,
+    pub previous_price: i128,
+    pub price: i128,
+    pub move_bps: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct CircuitBreakerResetEvent {
+    pub asset: Address,
+    pub actor: Address,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct PriceOverrideSetEvent {
+    pub asset: Address,
+    pub actor: Address,
+    pub price: i128,
+    pub reference_price: i128,
+    pub expires_at: u64,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct PriceOverrideClearedEvent {
+    pub asset: Address,
+    pub actor: Address,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct FeeAccruedEvent {
+    pub kind: FeeKind,
+    pub user: Address,
+    pub asset: Option<Address>,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct FeesClaimedEvent {
+    pub collector: Address,
+    pub asset: Option<Address>,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct RewardsFundedEvent {
+    pub funder: Address,
+    pub amount: i128,
+    pub budget: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct RewardsClaimedEvent {
+    pub user: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct StakedEvent {
+    pub staker: Address,
+    pub amount: i128,
+    pub shares: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct StakeCooldownStartedEvent {
+    pub staker: Address,
+    pub unstake_from: u64,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct UnstakedEvent {
+    pub staker: Address,
+    pub amount: i128,
+    pub shares: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct SafetyModuleSlashedEvent {
+    pub caller: Address,
+    pub asset: Option<Address>,
+    pub shortfall: i128,
+    pub amount: i128,
+    pub recipient: Address,
+    pub total_staked: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct StakingFeesClaimedEvent {
+    pub staker: Address,
+    pub asset: Option<Address>,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct PositionArchivedEvent {
+    pub user: Address,
+    pub keeper: Address,
+    pub collateral: i128,
+    pub supply_shares: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct PositionRestoredEvent {
+    pub user: Address,
+    pub collateral: i128,
+    pub supply_shares: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct KeeperReimbursedEvent {
+    pub keeper: Address,
+    pub operation: Symbol,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct JobExecutedEvent {
+    pub job_id: u32,
+    pub keeper: Address,
+    pub reward: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct HedgeExecutedEvent {
+    pub user: Address,
+    pub keeper: Address,
+    pub collateral_sold: i128,
+    pub proceeds: i128,
+    pub debt_repaid: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct UpgradeScheduledEvent {
+    pub actor: Address,
+    pub wasm_hash: BytesN<32>,
+    pub eta: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ContractUpgradedEvent {
+    pub wasm_hash: BytesN<32>,
+    /// Storage layout version of the code being replaced
+    pub previous_version: u32,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct StorageMigratedEvent {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct SubsidyCampaignCreatedEvent {
+    pub id: u64,
+    pub asset: Option<Address>,
+    pub discount_bps: i128,
+    pub start: u64,
+    pub end: u64,
+    pub budget: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct SubsidyCampaignClosedEvent {
+    pub id: u64,
+    pub asset: Option<Address>,
+    pub spent: i128,
+    pub refunded: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct StableBorrowEvent {
+    pub user: Address,
+    pub asset: Option<Address>,
+    pub amount: i128,
+    pub stable_principal: i128,
+    pub rate_bps: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct StableRateRebalancedEvent {
+    pub user: Address,
+    pub caller: Address,
+    pub stable_principal: i128,
+    pub old_rate_bps: i128,
+    pub new_rate_bps: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ReservesClaimedEvent {
+    pub actor: Address,
+    pub asset: Option<Address>,
+    pub to: Address,
+    pub amount: i128,
+    pub remaining: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct InvariantReportEvent {
+    pub holds: bool,
+    pub markets: u32,
+    pub violations: Vec<InvariantViolation>,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct RiskParamsUpdatedEvent {
+    pub actor: Address,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct PauseStateChangedEvent {
+    pub actor: Address,
+    pub operation: Symbol,
+    pub paused: bool,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct LiquidationPauseUpdatedEvent {
+    pub actor: Address,
+    pub asset: Option<Address>,
+    pub expires_at: u64,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ExitExemptionUpdatedEvent {
+    pub account: Address,
+    pub exempt: bool,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ExitExemptionUsedEvent {
+    pub account: Address,
+    pub operation: Symbol,
+    pub fee_waived: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent(topics = ["interest_accrued"])]
+#[derive(Clone, Debug)]
+pub struct InterestAccruedEvent {
     pub user: Address,
-    pub asset: Address,
-    pub amount: i128,
-    pub fee: i128,
-    pub callback: Address,
+    pub principal: i128,
+    pub accrued: i128,
+    pub borrow_rate_bps: i128,
+    pub period_start: u64,
     pub timestamp: u64,
 }
 
 #[contractevent]
 #[derive(Clone, Debug)]
-pub struct FlashLoanRepaidEvent {
+pub struct PositionUpdatedEvent {
     pub user: Address,
-    pub asset: Address,
-    pub amount: i128,
-    pub fee: i128,
+    pub collateral: i128,
+    pub debt: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct BatchExecutedEvent {
+    pub user: Address,
+    pub actions: u32,
+    pub deposited: i128,
+    pub borrowed: i128,
+    pub repaid: i128,
+    pub withdrawn: i128,
     pub timestamp: u64,
 }
 
 #[contractevent]
 #[derive(Clone, Debug)]
-pub struct AdminActionEvent {
-    pub actor: Address,
-    pub action: Symbol,
+pub struct PositionOperatorSetEvent {
+    pub user: Address,
+    pub operator: Address,
+    pub permissions: u32,
     pub timestamp: u64,
 }
 
 #[contractevent]
 #[derive(Clone, Debug)]
-pub struct PriceUpdatedEvent {
-    pub actor: Address,
-    pub asset: Address,
-    pub price: i128,
-    pub decimals: u32,
-    pub oracle: Address,
+pub struct OperatorActionEvent {
+    pub operator: Address,
+    pub user: Address,
+    pub operation: Symbol,
+    pub asset: Option<Address>,
+    pub amount: i128,
     pub timestamp: u64,
 }
 
 #[contractevent]
 #[derive(Clone, Debug)]
-pub struct RiskParamsUpdatedEvent {
-    pub actor: Address,
+pub struct PositionOpenedEvent {
+    pub position_id: u64,
+    pub owner: Address,
+    pub account: Address,
     pub timestamp: u64,
 }
 
 #[contractevent]
 #[derive(Clone, Debug)]
-pub struct PauseStateChangedEvent {
-    pub actor: Address,
+pub struct PositionTransferredEvent {
+    pub position_id: u64,
+    pub from: Address,
+    pub to: Address,
+    pub timestamp: u64,
+}
+
+#[contractevent(topics = ["health_warning"])]
+#[derive(Clone, Debug)]
+pub struct HealthWarningEvent {
+    pub user: Address,
+    pub previous_health_factor: i128,
+    pub health_factor: i128,
+    pub contact_hint: Option<BytesN<32>>,
+    pub timestamp: u64,
+}
+
+#[contractevent(topics = ["health_critical"])]
+#[derive(Clone, Debug)]
+pub struct HealthCriticalEvent {
+    pub user: Address,
+    pub previous_health_factor: i128,
+    pub health_factor: i128,
+    pub contact_hint: Option<BytesN<32>>,
+    pub timestamp: u64,
+}
+
+#[contractevent(topics = ["liquidation_imminent"])]
+#[derive(Clone, Debug)]
+pub struct LiquidationImminentEvent {
+    pub user: Address,
+    pub previous_health_factor: i128,
+    pub health_factor: i128,
+    pub contact_hint: Option<BytesN<32>>,
+    pub timestamp: u64,
+}
+
+#[contractevent(topics = ["risk_check"])]
+#[derive(Clone, Debug)]
+pub struct RiskCheckEvent {
+    pub user: Address,
+    pub engine: Address,
     pub operation: Symbol,
-    pub paused: bool,
+    pub asset: Option<Address>,
+    pub amount: i128,
+    pub engine_failed: bool,
     pub timestamp: u64,
 }
 
 #[contractevent]
 #[derive(Clone, Debug)]
-pub struct PositionUpdatedEvent {
+pub struct DebtSwappedEvent {
     pub user: Address,
-    pub collateral: i128,
-    pub debt: i128,
+    pub from_asset: Option<Address>,
+    pub to_asset: Option<Address>,
+    pub repaid: i128,
+    pub borrowed: i128,
+    pub flash_fee: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct ContactHintUpdatedEvent {
+    pub user: Address,
+    pub contact_hint: Option<BytesN<32>>,
+    pub timestamp: u64,
 }
 
 #[contractevent]
@@ -246,6 +810,22 @@ pub struct VoteCastEvent {
     pub timestamp: u64,
 }
 
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct DelegateChangedEvent {
+    pub delegator: Address,
+    pub from_delegate: Address,
+    pub to_delegate: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct DelegateVotesChangedEvent {
+    pub delegate: Address,
+    pub previous_votes: i128,
+    pub new_votes: i128,
+}
+
 #[contractevent]
 #[derive(Clone, Debug)]
 pub struct ProposalQueuedEvent {
@@ -395,10 +975,42 @@ pub fn emit_repay(e: &Env, event: RepayEvent) {
     event.publish(e);
 }
 
+pub fn emit_on_behalf(e: &Env, event: OnBehalfEvent) {
+    event.publish(e);
+}
+
 pub fn emit_liquidation(e: &Env, event: LiquidationEvent) {
     event.publish(e);
 }
 
+pub fn emit_liquidation_swap(e: &Env, event: LiquidationSwapEvent) {
+    event.publish(e);
+}
+
+pub fn emit_liquidation_auction_started(e: &Env, event: LiquidationAuctionStartedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_liquidation_auction_bid(e: &Env, event: LiquidationAuctionBidEvent) {
+    event.publish(e);
+}
+
+pub fn emit_liquidation_auction_closed(e: &Env, event: LiquidationAuctionClosedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_bad_debt_recorded(e: &Env, event: BadDebtRecordedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_insolvency_detected(e: &Env, event: InsolvencyDetectedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_bad_debt_socialized(e: &Env, event: BadDebtSocializedEvent) {
+    event.publish(e);
+}
+
 pub fn emit_flash_loan_initiated(e: &Env, event: FlashLoanInitiatedEvent) {
     event.publish(e);
 }
@@ -415,6 +1027,142 @@ pub fn emit_price_updated(e: &Env, event: PriceUpdatedEvent) {
     event.publish(e);
 }
 
+pub fn emit_oracle_bond_posted(e: &Env, event: OracleBondPostedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_oracle_bond_withdrawn(e: &Env, event: OracleBondWithdrawnEvent) {
+    event.publish(e);
+}
+
+pub fn emit_oracle_bond_slashed(e: &Env, event: OracleBondSlashedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_price_disputed(e: &Env, event: PriceDisputedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_price_dispute_resolved(e: &Env, event: PriceDisputeResolvedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_oracle_failover(e: &Env, event: OracleFailoverEvent) {
+    event.publish(e);
+}
+
+pub fn emit_price_stale(e: &Env, event: PriceStaleEvent) {
+    event.publish(e);
+}
+
+pub fn emit_circuit_breaker_tripped(e: &Env, event: CircuitBreakerTrippedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_circuit_breaker_reset(e: &Env, event: CircuitBreakerResetEvent) {
+    event.publish(e);
+}
+
+pub fn emit_price_override_set(e: &Env, event: PriceOverrideSetEvent) {
+    event.publish(e);
+}
+
+pub fn emit_price_override_cleared(e: &Env, event: PriceOverrideClearedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_fee_accrued(e: &Env, event: FeeAccruedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_fees_claimed(e: &Env, event: FeesClaimedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_rewards_funded(e: &Env, event: RewardsFundedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_rewards_claimed(e: &Env, event: RewardsClaimedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_staked(e: &Env, event: StakedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_stake_cooldown_started(e: &Env, event: StakeCooldownStartedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_unstaked(e: &Env, event: UnstakedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_safety_module_slashed(e: &Env, event: SafetyModuleSlashedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_staking_fees_claimed(e: &Env, event: StakingFeesClaimedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_position_archived(e: &Env, event: PositionArchivedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_position_restored(e: &Env, event: PositionRestoredEvent) {
+    event.publish(e);
+}
+
+pub fn emit_keeper_reimbursed(e: &Env, event: KeeperReimbursedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_job_executed(e: &Env, event: JobExecutedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_hedge_executed(e: &Env, event: HedgeExecutedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_upgrade_scheduled(e: &Env, event: UpgradeScheduledEvent) {
+    event.publish(e);
+}
+
+pub fn emit_contract_upgraded(e: &Env, event: ContractUpgradedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_storage_migrated(e: &Env, event: StorageMigratedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_subsidy_campaign_created(e: &Env, event: SubsidyCampaignCreatedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_subsidy_campaign_closed(e: &Env, event: SubsidyCampaignClosedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_stable_borrow(e: &Env, event: StableBorrowEvent) {
+    event.publish(e);
+}
+
+pub fn emit_stable_rate_rebalanced(e: &Env, event: StableRateRebalancedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_reserves_claimed(e: &Env, event: ReservesClaimedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_invariant_report(e: &Env, event: InvariantReportEvent) {
+    event.publish(e);
+}
+
 pub fn emit_risk_params_updated(e: &Env, event: RiskParamsUpdatedEvent) {
     event.publish(e);
 }
@@ -423,10 +1171,70 @@ pub fn emit_pause_state_changed(e: &Env, event: PauseStateChangedEvent) {
     event.publish(e);
 }
 
+pub fn emit_liquidation_pause_updated(e: &Env, event: LiquidationPauseUpdatedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_exit_exemption_updated(e: &Env, event: ExitExemptionUpdatedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_exit_exemption_used(e: &Env, event: ExitExemptionUsedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_interest_accrued(e: &Env, event: InterestAccruedEvent) {
+    event.publish(e);
+}
+
 pub fn emit_position_updated(e: &Env, event: PositionUpdatedEvent) {
     event.publish(e);
 }
 
+pub fn emit_batch_executed(e: &Env, event: BatchExecutedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_position_operator_set(e: &Env, event: PositionOperatorSetEvent) {
+    event.publish(e);
+}
+
+pub fn emit_operator_action(e: &Env, event: OperatorActionEvent) {
+    event.publish(e);
+}
+
+pub fn emit_position_opened(e: &Env, event: PositionOpenedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_position_transferred(e: &Env, event: PositionTransferredEvent) {
+    event.publish(e);
+}
+
+pub fn emit_health_warning(e: &Env, event: HealthWarningEvent) {
+    event.publish(e);
+}
+
+pub fn emit_health_critical(e: &Env, event: HealthCriticalEvent) {
+    event.publish(e);
+}
+
+pub fn emit_liquidation_imminent(e: &Env, event: LiquidationImminentEvent) {
+    event.publish(e);
+}
+
+pub fn emit_risk_check(e: &Env, event: RiskCheckEvent) {
+    event.publish(e);
+}
+
+pub fn emit_debt_swapped(e: &Env, event: DebtSwappedEvent) {
+    event.publish(e);
+}
+
+pub fn emit_contact_hint_updated(e: &Env, event: ContactHintUpdatedEvent) {
+    event.publish(e);
+}
+
 pub fn emit_analytics_updated(e: &Env, event: AnalyticsUpdatedEvent) {
     event.publish(e);
 }
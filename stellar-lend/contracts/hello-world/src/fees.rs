@@ -0,0 +1,349 @@
+//! # Protocol Fees Module
+//!
+//! Per-asset protocol fees routed to a settable fee collector.
+//!
+//! ## Fee Kinds
+//! - **Origination**: charged on borrows, withheld from the amount sent to
+//!   the borrower (on top of the asset's reserve-bound `borrow_fee_bps`).
+//! - **Flash loan**: an asset's `flash_loan_fee_bps` replaces the global
+//!   flash loan base fee; integrator and volume discounts still apply.
+//! - **Withdrawal**: charged on collateral withdrawals, withheld from the
+//!   amount sent to the user.
+//!
+//! ## Fee Switch
+//! Fees are only charged while the admin has turned the fee switch on, which
+//! requires a fee collector. With the switch off, borrows and withdrawals are
+//! free of protocol fees and flash loan fees go to the protocol reserve as
+//! before; with it on, flash loan fees accrue to the collector instead.
+//!
+//! ## Accrual and Claims
+//! Fees stay in the contract and accrue per asset (`AccruedFees(asset)`),
+//! emitting a `fee_accrued_event` each time. While the safety module has
+//! stakers, its `fee_share_bps` of each fee goes to them instead (see the
+//! `safety_module` module). The collector claims an asset's
+//! accrued fees with [`claim_fees`]; anyone may [`sweep_fees`] for a list of
+//! assets, which pays everything accrued to the current collector.
+
+use soroban_sdk::{contracterror, contracttype, token, Address, Env, Symbol, Vec};
+
+use crate::deposit::token_address;
+use crate::events::{
+    emit_admin_action, emit_fee_accrued, emit_fees_claimed, AdminActionEvent, FeeAccruedEvent,
+    FeesClaimedEvent,
+};
+use crate::financials::FinancialItem;
+
+/// Basis points scale
+const BPS_SCALE: i128 = 10_000;
+
+/// Maximum fee of any kind: 10%
+pub const MAX_FEE_BPS: i128 = 1_000;
+
+/// Errors that can occur in fee operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum FeesError {
+    /// Caller is not the admin (or not the fee collector for claims)
+    Unauthorized = 1,
+    /// Fee is negative or above `MAX_FEE_BPS`
+    InvalidFee = 2,
+    /// No fee collector is set
+    CollectorNotSet = 3,
+    /// No fees accrued for the asset
+    NothingToClaim = 4,
+    /// The native asset address is not configured
+    InvalidAsset = 5,
+    /// Overflow occurred during calculation
+    Overflow = 6,
+}
+
+/// Storage keys for protocol fees
+#[contracttype]
+#[derive(Clone)]
+pub enum FeesDataKey {
+    /// Fee rates of an asset
+    /// Value type: FeeConfig
+    FeeConfig(Option<Address>),
+    /// Address fees are paid out to
+    /// Value type: Address
+    FeeCollector,
+    /// Whether protocol fees are charged
+    /// Value type: bool
+    FeeSwitch,
+    /// Fees accrued and not yet claimed for an asset
+    /// Value type: i128
+    AccruedFees(Option<Address>),
+}
+
+/// Operation a fee is charged on
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FeeKind {
+    Origination,
+    FlashLoan,
+    Withdrawal,
+}
+
+/// Fee rates of an asset, in basis points
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FeeConfig {
+    /// Fee on borrowed amounts
+    pub origination_fee_bps: i128,
+    /// Flash loan base fee (None uses the global flash loan fee)
+    pub flash_loan_fee_bps: Option<i128>,
+    /// Fee on withdrawn collateral
+    pub withdrawal_fee_bps: i128,
+}
+
+/// Get the fee rates of an asset (all zero when unset)
+pub fn get_fee_config(env: &Env, asset: &Option<Address>) -> FeeConfig {
+    env.storage()
+        .persistent()
+        .get::<FeesDataKey, FeeConfig>(&FeesDataKey::FeeConfig(asset.clone()))
+        .unwrap_or_default()
+}
+
+/// Set the fee rates of an asset (admin only)
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not the admin
+/// * `InvalidFee` - A rate is negative or above `MAX_FEE_BPS`
+pub fn set_fee_config(
+    env: &Env,
+    caller: Address,
+    asset: Option<Address>,
+    config: FeeConfig,
+) -> Result<(), FeesError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| FeesError::Unauthorized)?;
+
+    let rates = [
+        config.origination_fee_bps,
+        config.flash_loan_fee_bps.unwrap_or(0),
+        config.withdrawal_fee_bps,
+    ];
+    if rates.iter().any(|bps| !(0..=MAX_FEE_BPS).contains(bps)) {
+        return Err(FeesError::InvalidFee);
+    }
+    env.storage()
+        .persistent()
+        .set(&FeesDataKey::FeeConfig(asset), &config);
+
+    emit_admin_action(
+        env,
+        AdminActionEvent {
+            actor: caller,
+            action: Symbol::new(env, "set_fee_config"),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Get the fee collector
+pub fn get_fee_collector(env: &Env) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get::<FeesDataKey, Address>(&FeesDataKey::FeeCollector)
+}
+
+/// Set the fee collector (admin only)
+///
+/// Fees accrued before the change are paid to the new collector.
+pub fn set_fee_collector(env: &Env, caller: Address, collector: Address) -> Result<(), FeesError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| FeesError::Unauthorized)?;
+    env.storage()
+        .persistent()
+        .set(&FeesDataKey::FeeCollector, &collector);
+
+    emit_admin_action(
+        env,
+        AdminActionEvent {
+            actor: caller,
+            action: Symbol::new(env, "set_fee_collector"),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Whether protocol fees are charged
+pub fn is_fee_switch_on(env: &Env) -> bool {
+    env.storage()
+        .persistent()
+        .get::<FeesDataKey, bool>(&FeesDataKey::FeeSwitch)
+        .unwrap_or(false)
+}
+
+/// Turn protocol fees on or off (admin only)
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not the admin
+/// * `CollectorNotSet` - Turning fees on without a fee collector
+pub fn set_fee_switch(env: &Env, caller: Address, enabled: bool) -> Result<(), FeesError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| FeesError::Unauthorized)?;
+    if enabled && get_fee_collector(env).is_none() {
+        return Err(FeesError::CollectorNotSet);
+    }
+    env.storage()
+        .persistent()
+        .set(&FeesDataKey::FeeSwitch, &enabled);
+
+    emit_admin_action(
+        env,
+        AdminActionEvent {
+            actor: caller,
+            action: Symbol::new(env, "set_fee_switch"),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Get the fees accrued and not yet claimed for an asset
+pub fn get_accrued_fees(env: &Env, asset: &Option<Address>) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<FeesDataKey, i128>(&FeesDataKey::AccruedFees(asset.clone()))
+        .unwrap_or(0)
+}
+
+/// Rate of a fee kind for an asset, if fees are switched on and the asset
+/// sets one
+pub(crate) fn get_fee_bps(env: &Env, kind: FeeKind, asset: &Option<Address>) -> Option<i128> {
+    if !is_fee_switch_on(env) {
+        return None;
+    }
+    let config = get_fee_config(env, asset);
+    match kind {
+        FeeKind::Origination => Some(config.origination_fee_bps),
+        FeeKind::FlashLoan => config.flash_loan_fee_bps,
+        FeeKind::Withdrawal => Some(config.withdrawal_fee_bps),
+    }
+}
+
+/// Charge the fee of `kind` on `amount` and accrue it
+///
+/// # Returns
+/// The fee withheld (0 with the switch off)
+pub(crate) fn charge_fee(
+    env: &Env,
+    kind: FeeKind,
+    user: &Address,
+    asset: &Option<Address>,
+    amount: i128,
+) -> Result<i128, FeesError> {
+    let fee_bps = get_fee_bps(env, kind, asset).unwrap_or(0);
+    let fee = amount.checked_mul(fee_bps).ok_or(FeesError::Overflow)? / BPS_SCALE;
+    if fee > 0 {
+        accrue_fee(env, kind, user, asset, fee)?;
+    }
+    Ok(fee)
+}
+
+/// Accrue a fee already held by the contract to the collector, less the
+/// safety module stakers' share
+pub(crate) fn accrue_fee(
+    env: &Env,
+    kind: FeeKind,
+    user: &Address,
+    asset: &Option<Address>,
+    fee: i128,
+) -> Result<(), FeesError> {
+    // Stakers in the safety module take their share first
+    let staker_share = crate::safety_module::distribute_fees(env, asset, fee);
+    let accrued = get_accrued_fees(env, asset)
+        .checked_add(fee - staker_share)
+        .ok_or(FeesError::Overflow)?;
+    env.storage()
+        .persistent()
+        .set(&FeesDataKey::AccruedFees(asset.clone()), &accrued);
+    let item = match kind {
+        FeeKind::FlashLoan => FinancialItem::FlashLoanFees,
+        FeeKind::Origination | FeeKind::Withdrawal => FinancialItem::ProtocolFees,
+    };
+    crate::financials::record(env, item, asset, fee);
+
+    emit_fee_accrued(
+        env,
+        FeeAccruedEvent {
+            kind,
+            user: user.clone(),
+            asset: asset.clone(),
+            amount: fee,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Pay an asset's accrued fees to the collector (collector only)
+///
+/// # Returns
+/// The amount paid out
+///
+/// # Errors
+/// * `CollectorNotSet` - No fee collector is set
+/// * `Unauthorized` - Caller is not the fee collector
+/// * `NothingToClaim` - No fees accrued for the asset
+pub fn claim_fees(env: &Env, caller: Address, asset: Option<Address>) -> Result<i128, FeesError> {
+    let collector = get_fee_collector(env).ok_or(FeesError::CollectorNotSet)?;
+    if caller != collector {
+        return Err(FeesError::Unauthorized);
+    }
+    caller.require_auth();
+
+    let amount = pay_out(env, &collector, &asset)?;
+    if amount == 0 {
+        return Err(FeesError::NothingToClaim);
+    }
+    Ok(amount)
+}
+
+/// Pay the accrued fees of `assets` to the collector (anyone may call)
+///
+/// Assets without accrued fees are skipped.
+///
+/// # Returns
+/// The amount paid out per asset, in the order given
+///
+/// # Errors
+/// * `CollectorNotSet` - No fee collector is set
+pub fn sweep_fees(env: &Env, assets: Vec<Option<Address>>) -> Result<Vec<i128>, FeesError> {
+    let collector = get_fee_collector(env).ok_or(FeesError::CollectorNotSet)?;
+    let mut amounts = Vec::new(env);
+    for asset in assets.iter() {
+        amounts.push_back(pay_out(env, &collector, &asset)?);
+    }
+    Ok(amounts)
+}
+
+/// Transfer an asset's accrued fees to the collector and reset them
+fn pay_out(env: &Env, collector: &Address, asset: &Option<Address>) -> Result<i128, FeesError> {
+    let amount = get_accrued_fees(env, asset);
+    if amount == 0 {
+        return Ok(0);
+    }
+
+    let token_address = token_address(env, asset).ok_or(FeesError::InvalidAsset)?;
+    env.storage()
+        .persistent()
+        .set(&FeesDataKey::AccruedFees(asset.clone()), &0i128);
+    token::Client::new(env, &token_address).transfer(
+        &env.current_contract_address(),
+        collector,
+        &amount,
+    );
+
+    emit_fees_claimed(
+        env,
+        FeesClaimedEvent {
+            collector: collector.clone(),
+            asset: asset.clone(),
+            amount,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(amount)
+}
@@ -0,0 +1,171 @@
+//! # Protocol Financials
+//!
+//! Revenue and expense statement kept as counters updated where the money
+//! moves, so treasury reporting is a single read instead of an event replay.
+//!
+//! Counters are bucketed per period of [`FINANCIAL_PERIOD_SECONDS`] and per
+//! asset (None is native XLM), in the asset's own units:
+//! - Revenue: the reserve share of borrower interest, flash loan fees, AMM
+//!   swap fees, the protocol share of bridge fees, and origination and
+//!   withdrawal fees.
+//! - Expenses: liquidity mining rewards paid out, borrow APR subsidies
+//!   drawn from the subsidy budget, and keeper reimbursements from the
+//!   keeper sponsorship pool.
+//!
+//! `net_revenue` is revenue less expenses for the asset in the period.
+
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+/// Length of a reporting period (30 days)
+pub const FINANCIAL_PERIOD_SECONDS: u64 = 30 * 86_400;
+
+/// Storage keys for protocol financials
+#[contracttype]
+#[derive(Clone)]
+pub enum FinancialsDataKey {
+    /// Counters of an asset in a period
+    /// Value type: AssetFinancials
+    Statement(u64, Option<Address>),
+    /// Assets with counters in a period, in first-seen order
+    /// Value type: Vec<Option<Address>>
+    PeriodAssets(u64),
+}
+
+/// Revenue or expense line of the statement
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FinancialItem {
+    InterestCollected,
+    FlashLoanFees,
+    AmmFees,
+    BridgeFees,
+    ProtocolFees,
+    RewardsPaid,
+    SubsidiesPaid,
+    KeeperReimbursements,
+}
+
+/// Revenue and expenses of one asset in a period
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetFinancials {
+    pub asset: Option<Address>,
+    /// Reserve share of borrower interest
+    pub interest_collected: i128,
+    pub flash_loan_fees: i128,
+    pub amm_fees: i128,
+    /// Protocol share of bridge fees (relayer share excluded)
+    pub bridge_fees: i128,
+    /// Origination, borrow and withdrawal fees
+    pub protocol_fees: i128,
+    pub rewards_paid: i128,
+    pub subsidies_paid: i128,
+    pub keeper_reimbursements: i128,
+    /// Revenue less expenses
+    pub net_revenue: i128,
+}
+
+/// Revenue and expense statement of a period
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FinancialStatement {
+    pub period: u64,
+    /// First second of the period
+    pub start: u64,
+    /// First second after the period
+    pub end: u64,
+    pub assets: Vec<AssetFinancials>,
+}
+
+/// Index of the current reporting period
+pub fn get_financial_period(env: &Env) -> u64 {
+    env.ledger().timestamp() / FINANCIAL_PERIOD_SECONDS
+}
+
+/// Book `amount` of `asset` to a statement line of the current period
+///
+/// Called wherever revenue is earned or an expense is paid; non-positive
+/// amounts are ignored.
+pub(crate) fn record(env: &Env, item: FinancialItem, asset: &Option<Address>, amount: i128) {
+    if amount <= 0 {
+        return;
+    }
+    let period = get_financial_period(env);
+    let key = FinancialsDataKey::Statement(period, asset.clone());
+    let mut entry = match env.storage().persistent().get(&key) {
+        Some(entry) => entry,
+        None => {
+            let assets_key = FinancialsDataKey::PeriodAssets(period);
+            let mut assets: Vec<Option<Address>> = env
+                .storage()
+                .persistent()
+                .get(&assets_key)
+                .unwrap_or_else(|| Vec::new(env));
+            assets.push_back(asset.clone());
+            env.storage().persistent().set(&assets_key, &assets);
+            empty_financials(asset)
+        }
+    };
+
+    let (line, expense) = match item {
+        FinancialItem::InterestCollected => (&mut entry.interest_collected, false),
+        FinancialItem::FlashLoanFees => (&mut entry.flash_loan_fees, false),
+        FinancialItem::AmmFees => (&mut entry.amm_fees, false),
+        FinancialItem::BridgeFees => (&mut entry.bridge_fees, false),
+        FinancialItem::ProtocolFees => (&mut entry.protocol_fees, false),
+        FinancialItem::RewardsPaid => (&mut entry.rewards_paid, true),
+        FinancialItem::SubsidiesPaid => (&mut entry.subsidies_paid, true),
+        FinancialItem::KeeperReimbursements => (&mut entry.keeper_reimbursements, true),
+    };
+    *line = line.saturating_add(amount);
+    entry.net_revenue = if expense {
+        entry.net_revenue.saturating_sub(amount)
+    } else {
+        entry.net_revenue.saturating_add(amount)
+    };
+    env.storage().persistent().set(&key, &entry);
+}
+
+/// Get the revenue and expense statement of a period
+///
+/// Assets appear in the order they first had activity in the period; a
+/// period without activity has no assets.
+pub fn get_financials(env: &Env, period: u64) -> FinancialStatement {
+    let mut assets = Vec::new(env);
+    let period_assets: Vec<Option<Address>> = env
+        .storage()
+        .persistent()
+        .get(&FinancialsDataKey::PeriodAssets(period))
+        .unwrap_or_else(|| Vec::new(env));
+    for asset in period_assets.iter() {
+        let entry = env
+            .storage()
+            .persistent()
+            .get(&FinancialsDataKey::Statement(period, asset.clone()))
+            .unwrap_or_else(|| empty_financials(&asset));
+        assets.push_back(entry);
+    }
+
+    let start = period.saturating_mul(FINANCIAL_PERIOD_SECONDS);
+    FinancialStatement {
+        period,
+        start,
+        end: start.saturating_add(FINANCIAL_PERIOD_SECONDS),
+        assets,
+    }
+}
+
+fn empty_financials(asset: &Option<Address>) -> AssetFinancials {
+    AssetFinancials {
+        asset: asset.clone(),
+        interest_collected: 0,
+        flash_loan_fees: 0,
+        amm_fees: 0,
+        bridge_fees: 0,
+        protocol_fees: 0,
+        rewards_paid: 0,
+        subsidies_paid: 0,
+        keeper_reimbursements: 0,
+        net_revenue: 0,
+    }
+}
@@ -5,27 +5,60 @@
 //! Flash loans allow users to borrow assets without collateral, provided the loan
 //! (principal + fee) is repaid within the same transaction via a callback contract.
 //!
+//! ## Receiver Callback
+//! `execute_flash_loan` transfers the principal to the receiver contract and
+//! invokes its [`FlashLoanReceiver::execute_operation`] with the fee and the
+//! initiator's opaque `params`. The receiver repays by transferring principal
+//! plus fee back to the protocol before returning `true`. The protocol checks
+//! its own balance afterwards; a failed or `false` callback, or a short
+//! repayment, returns an error and reverts the whole loan.
+//!
+//! ## Internal Flash Loans
+//! Protocol operations such as `liquidate_with_flash_loan` take flash loans
+//! without a receiver contract through `begin_internal_flash_loan` and
+//! `settle_internal_flash_loan`. They share the checks, fee, volume tracking,
+//! lock and events of receiver flash loans.
+//!
 //! ## Fee Structure
 //! - Default fee: 9 basis points (0.09%) of the borrowed amount.
 //! - Fee is configurable by the admin.
+//! - Registered integrators pay `integrator_fee_bps` instead of the base fee.
+//! - High-volume users pay a reduced fee once their flash loan volume in the
+//!   current epoch reaches a configured tier threshold (`volume_tiers`).
+//! - The lowest applicable fee is charged. Per-user volume counters reset at
+//!   the start of each epoch (`epoch_duration` seconds).
+//! - The integrator fee, volume tiers and epoch length are stored apart from
+//!   `FlashLoanConfig` (under `FeeTiers`), so configs stored before fee tiers
+//!   existed still decode; until tiers are configured the defaults apply.
+//! - With protocol fees switched on, an asset's `flash_loan_fee_bps` replaces
+//!   the base fee and fees accrue to the fee collector instead of the reserve
+//!   (see the fees module).
 //!
 //! ## Reentrancy Protection
 //! An active flash loan is recorded per (user, asset) pair. A second flash loan
 //! for the same pair is rejected until the first is repaid, preventing reentrancy.
 //!
+//! While any flash loan is outstanding, an operation-scoped lock (see
+//! `reentrancy::enter_flash_loan`) is held. Deposit, borrow and withdraw reject
+//! calls with `FlashLoanInProgress` until the loan is repaid, so a receiver
+//! cannot use borrowed funds to manipulate its own or another position.
+//!
 //! ## Invariants
 //! - The borrowed amount must be within configured min/max limits.
 //! - The contract must have sufficient liquidity to fund the loan.
-//! - Repayment must cover principal + fee in full.
+//! - Repayment must cover principal + fee in full before the call returns.
 
 #![allow(unused)]
 use crate::events::{
     emit_flash_loan_initiated, emit_flash_loan_repaid, FlashLoanInitiatedEvent,
     FlashLoanRepaidEvent,
 };
-use soroban_sdk::{contracterror, contracttype, Address, Env, IntoVal, Map, Symbol, Val, Vec};
+use soroban_sdk::{
+    contractclient, contracterror, contracttype, Address, Bytes, Env, Map, Symbol, Vec,
+};
 
 use crate::deposit::DepositDataKey;
+use crate::fees::FeeKind;
 
 /// Errors that can occur during flash loan operations
 #[contracterror]
@@ -69,6 +102,15 @@ pub enum FlashLoanDataKey {
     FlashLoanConfig,
     /// Pause switches specifically for flash loan operations: Map<Symbol, bool>
     PauseSwitches,
+    /// Registered integrator eligible for the integrator fee
+    /// Value type: bool
+    Integrator(Address),
+    /// Per-user flash loan volume in the current fee epoch
+    /// Value type: FlashLoanEpochVolume
+    EpochVolume(Address),
+    /// Integrator fee, volume tiers and epoch length
+    /// Value type: FlashLoanFeeTiers
+    FeeTiers,
 }
 
 /// Flash loan record
@@ -97,6 +139,38 @@ pub struct FlashLoanConfig {
     pub min_amount: i128,
 }
 
+/// Flash loan fee discounts for integrators and high-volume users
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlashLoanFeeTiers {
+    /// Fee in basis points for registered integrators
+    pub integrator_fee_bps: i128,
+    /// Volume-based fee tiers, applied to a user's volume in the current epoch
+    pub volume_tiers: Vec<FlashLoanFeeTier>,
+    /// Length of a volume epoch in seconds
+    pub epoch_duration: u64,
+}
+
+/// Volume-based flash loan fee tier
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlashLoanFeeTier {
+    /// Minimum flash loan volume in the current epoch to qualify
+    pub min_epoch_volume: i128,
+    /// Fee in basis points for qualifying users
+    pub fee_bps: i128,
+}
+
+/// Per-user flash loan volume counter
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlashLoanEpochVolume {
+    /// Epoch index (`timestamp / epoch_duration`) the volume belongs to
+    pub epoch: u64,
+    /// Total amount borrowed via flash loans in this epoch
+    pub volume: i128,
+}
+
 /// Default flash loan fee: 9 basis points (0.09%)
 const DEFAULT_FLASH_LOAN_FEE_BPS: i128 = 9;
 
@@ -106,6 +180,15 @@ const DEFAULT_MAX_FLASH_LOAN_AMOUNT: i128 = i128::MAX;
 /// Default minimum flash loan amount
 const DEFAULT_MIN_FLASH_LOAN_AMOUNT: i128 = 1;
 
+/// Default integrator fee: 5 basis points (0.05%)
+const DEFAULT_INTEGRATOR_FEE_BPS: i128 = 5;
+
+/// Default fee epoch length: 1 day
+const DEFAULT_FEE_EPOCH_DURATION: u64 = 86_400;
+
+/// Maximum number of volume fee tiers
+const MAX_VOLUME_TIERS: u32 = 10;
+
 /// Get default flash loan configuration
 fn get_default_config() -> FlashLoanConfig {
     FlashLoanConfig {
@@ -124,13 +207,111 @@ fn get_flash_loan_config(env: &Env) -> FlashLoanConfig {
         .unwrap_or_else(get_default_config)
 }
 
-/// Calculate flash loan fee
-fn calculate_flash_loan_fee(env: &Env, amount: i128) -> Result<i128, FlashLoanError> {
+/// Get the flash loan fee tiers, or the defaults if never configured
+pub fn get_flash_loan_fee_tiers(env: &Env) -> FlashLoanFeeTiers {
+    env.storage()
+        .persistent()
+        .get::<FlashLoanDataKey, FlashLoanFeeTiers>(&FlashLoanDataKey::FeeTiers)
+        .unwrap_or_else(|| FlashLoanFeeTiers {
+            integrator_fee_bps: DEFAULT_INTEGRATOR_FEE_BPS,
+            volume_tiers: Vec::new(env),
+            epoch_duration: DEFAULT_FEE_EPOCH_DURATION,
+        })
+}
+
+/// Current fee epoch index
+fn current_epoch(env: &Env, tiers: &FlashLoanFeeTiers) -> u64 {
+    env.ledger().timestamp() / tiers.epoch_duration.max(1)
+}
+
+/// Get a user's flash loan volume in the current epoch
+///
+/// Counters from earlier epochs are treated as zero.
+pub fn get_flash_loan_epoch_volume(env: &Env, user: &Address) -> i128 {
+    let tiers = get_flash_loan_fee_tiers(env);
+    let epoch = current_epoch(env, &tiers);
+    env.storage()
+        .persistent()
+        .get::<FlashLoanDataKey, FlashLoanEpochVolume>(&FlashLoanDataKey::EpochVolume(user.clone()))
+        .filter(|v| v.epoch == epoch)
+        .map(|v| v.volume)
+        .unwrap_or(0)
+}
+
+/// Add to a user's flash loan volume, resetting the counter on a new epoch
+fn record_epoch_volume(env: &Env, user: &Address, amount: i128) -> Result<(), FlashLoanError> {
+    let tiers = get_flash_loan_fee_tiers(env);
+    let volume = get_flash_loan_epoch_volume(env, user)
+        .checked_add(amount)
+        .ok_or(FlashLoanError::Overflow)?;
+    env.storage().persistent().set(
+        &FlashLoanDataKey::EpochVolume(user.clone()),
+        &FlashLoanEpochVolume {
+            epoch: current_epoch(env, &tiers),
+            volume,
+        },
+    );
+    Ok(())
+}
+
+/// Check whether an address is a registered flash loan integrator
+pub fn is_flash_loan_integrator(env: &Env, user: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get::<FlashLoanDataKey, bool>(&FlashLoanDataKey::Integrator(user.clone()))
+        .unwrap_or(false)
+}
+
+/// Get the fee in basis points a user would pay for their next flash loan
+///
+/// Returns the lowest of the base fee, the integrator fee (if registered) and
+/// the best volume tier reached in the current epoch.
+pub fn get_flash_loan_fee_bps(env: &Env, user: &Address) -> i128 {
+    discounted_fee_bps(env, user, get_flash_loan_config(env).fee_bps)
+}
+
+/// Get the fee in basis points a user would pay to flash-borrow an asset
+///
+/// Like [`get_flash_loan_fee_bps`], with the asset's protocol flash loan fee
+/// (see the fees module) replacing the base fee when set.
+pub fn get_asset_flash_loan_fee_bps(env: &Env, user: &Address, asset: &Option<Address>) -> i128 {
     let config = get_flash_loan_config(env);
+    let base_bps =
+        crate::fees::get_fee_bps(env, FeeKind::FlashLoan, asset).unwrap_or(config.fee_bps);
+    discounted_fee_bps(env, user, base_bps)
+}
+
+/// Lowest of `base_bps` and the user's integrator and volume tier fees
+fn discounted_fee_bps(env: &Env, user: &Address, base_bps: i128) -> i128 {
+    let tiers = get_flash_loan_fee_tiers(env);
+    let mut fee_bps = base_bps;
+
+    if is_flash_loan_integrator(env, user) {
+        fee_bps = fee_bps.min(tiers.integrator_fee_bps);
+    }
+
+    let volume = get_flash_loan_epoch_volume(env, user);
+    for tier in tiers.volume_tiers.iter() {
+        if volume >= tier.min_epoch_volume {
+            fee_bps = fee_bps.min(tier.fee_bps);
+        }
+    }
+
+    fee_bps
+}
+
+/// Calculate flash loan fee
+fn calculate_flash_loan_fee(
+    env: &Env,
+    user: &Address,
+    asset: &Address,
+    amount: i128,
+) -> Result<i128, FlashLoanError> {
+    let fee_bps = get_asset_flash_loan_fee_bps(env, user, &Some(asset.clone()));
 
     // Fee = amount * fee_bps / 10000
     amount
-        .checked_mul(config.fee_bps)
+        .checked_mul(fee_bps)
         .ok_or(FlashLoanError::Overflow)?
         .checked_div(10000)
         .ok_or(FlashLoanError::Overflow)
@@ -170,20 +351,43 @@ fn clear_flash_loan(env: &Env, user: &Address, asset: &Address) {
     env.storage().persistent().remove(&loan_key);
 }
 
+/// Interface implemented by flash loan receivers
+///
+/// The protocol transfers `amount` of `asset` to the receiver and then calls
+/// `execute_operation` in the same invocation. Before returning `true`, the
+/// receiver must transfer `amount + fee` back to the protocol; otherwise the
+/// whole flash loan reverts.
+#[contractclient(name = "FlashLoanReceiverClient")]
+pub trait FlashLoanReceiver {
+    /// Use the borrowed funds and repay principal plus fee
+    ///
+    /// # Arguments
+    /// * `asset` - The borrowed asset
+    /// * `amount` - The principal transferred to the receiver
+    /// * `fee` - The fee owed on top of the principal
+    /// * `params` - Opaque data forwarded from the initiator
+    ///
+    /// # Returns
+    /// `true` if the operation succeeded
+    fn execute_operation(env: Env, asset: Address, amount: i128, fee: i128, params: Bytes) -> bool;
+}
+
 /// Execute flash loan
 ///
-/// Allows users to borrow assets without collateral for a single transaction.
-/// The loan must be repaid (with fee) within the same transaction via callback.
+/// Lends `amount` of `asset` to the `callback` receiver without collateral,
+/// invokes its `execute_operation` and verifies that principal plus fee was
+/// returned before the invocation ends. Any failure reverts the whole loan.
 ///
 /// # Arguments
 /// * `env` - The Soroban environment
-/// * `user` - The address borrowing the flash loan
+/// * `user` - The address initiating the flash loan (determines the fee tier)
 /// * `asset` - The address of the asset contract to borrow
 /// * `amount` - The amount to borrow
-/// * `callback` - The callback contract address that will handle repayment
+/// * `callback` - The receiver contract implementing [`FlashLoanReceiver`]
+/// * `params` - Opaque data forwarded to the receiver
 ///
 /// # Returns
-/// Returns the total amount to repay (principal + fee)
+/// Returns the total amount repaid (principal + fee)
 ///
 /// # Errors
 /// * `FlashLoanError::InvalidAmount` - If amount is zero, negative, or outside limits
@@ -192,6 +396,8 @@ fn clear_flash_loan(env: &Env, user: &Address, asset: &Address) {
 /// * `FlashLoanError::FlashLoanPaused` - If flash loans are paused
 /// * `FlashLoanError::Reentrancy` - If flash loan is already active for this user/asset
 /// * `FlashLoanError::InvalidCallback` - If callback address is invalid
+/// * `FlashLoanError::CallbackFailed` - If the receiver call failed or returned `false`
+/// * `FlashLoanError::InsufficientRepayment` - If principal plus fee was not returned
 /// * `FlashLoanError::Overflow` - If calculation overflow occurs
 pub fn execute_flash_loan(
     env: &Env,
@@ -199,65 +405,37 @@ pub fn execute_flash_loan(
     asset: Address,
     amount: i128,
     callback: Address,
+    params: Bytes,
 ) -> Result<i128, FlashLoanError> {
-    // Validate amount
-    if amount <= 0 {
-        return Err(FlashLoanError::InvalidAmount);
-    }
-
-    // Check if flash loans are paused
-    let pause_key = FlashLoanDataKey::PauseSwitches;
-    if let Some(pause_map) = env
-        .storage()
-        .persistent()
-        .get::<FlashLoanDataKey, Map<Symbol, bool>>(&pause_key)
-    {
-        if let Some(paused) = pause_map.get(Symbol::new(env, "pause_flash_loan")) {
-            if paused {
-                return Err(FlashLoanError::FlashLoanPaused);
-            }
-        }
-    }
-
-    // Validate asset address
-    if asset == env.current_contract_address() {
-        return Err(FlashLoanError::InvalidAsset);
-    }
+    user.require_auth();
 
     // Validate callback address
     if callback == env.current_contract_address() {
         return Err(FlashLoanError::InvalidCallback);
     }
 
-    // Check configuration limits
-    let config = get_flash_loan_config(env);
-    if amount < config.min_amount || amount > config.max_amount {
-        return Err(FlashLoanError::InvalidAmount);
-    }
-
-    // Check for reentrancy (active flash loan)
-    if is_flash_loan_active(env, &user, &asset) {
-        return Err(FlashLoanError::Reentrancy);
-    }
-
-    // Calculate fee
-    let fee = calculate_flash_loan_fee(env, amount)?;
+    let fee = validate_flash_loan(env, &user, &asset, amount)?;
     let total_repayment = amount.checked_add(fee).ok_or(FlashLoanError::Overflow)?;
 
     // Check contract balance
     let token_client = soroban_sdk::token::Client::new(env, &asset);
-    let contract_balance = token_client.balance(&env.current_contract_address());
-    if contract_balance < amount {
+    let balance_before = token_client.balance(&env.current_contract_address());
+    if balance_before < amount {
         return Err(FlashLoanError::InsufficientLiquidity);
     }
+    let required_balance = balance_before
+        .checked_add(fee)
+        .ok_or(FlashLoanError::Overflow)?;
 
     // Record flash loan before transfer
     record_flash_loan(env, &user, &asset, amount, fee, &callback);
+    record_epoch_volume(env, &user, amount)?;
+    crate::reentrancy::enter_flash_loan(env);
 
-    // Transfer tokens to user
+    // Transfer tokens to the receiver
     token_client.transfer(
         &env.current_contract_address(), // from (this contract)
-        &user,                           // to (user)
+        &callback,                       // to (receiver)
         &amount,
     );
 
@@ -274,70 +452,164 @@ pub fn execute_flash_loan(
         },
     );
 
-    // Note: In a real implementation, we would call the callback here
-    // For Soroban, the callback would need to be invoked by the user
-    // The repayment check happens when the user calls repay_flash_loan
+    // Hand control to the receiver; it must return principal plus fee
+    let receiver = FlashLoanReceiverClient::new(env, &callback);
+    match receiver.try_execute_operation(&asset, &amount, &fee, &params) {
+        Ok(Ok(true)) => {}
+        _ => return Err(FlashLoanError::CallbackFailed),
+    }
+
+    // Verify repayment from the contract's balance
+    if token_client.balance(&env.current_contract_address()) < required_balance {
+        return Err(FlashLoanError::InsufficientRepayment);
+    }
+
+    credit_flash_loan_fee(env, &user, &asset, fee)?;
+
+    // Clear flash loan record and release the operation lock
+    clear_flash_loan(env, &user, &asset);
+    crate::reentrancy::exit_flash_loan(env);
+
+    // Emit flash loan repaid event
+    emit_flash_loan_repaid(
+        env,
+        FlashLoanRepaidEvent {
+            user,
+            asset,
+            amount,
+            fee,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
 
     Ok(total_repayment)
 }
 
-/// Repay flash loan
-///
-/// Must be called within the same transaction as the flash loan.
-/// Validates that the full amount (principal + fee) is repaid.
+/// Open a flash loan the protocol takes out internally, without a receiver
 ///
-/// # Arguments
-/// * `env` - The Soroban environment
-/// * `user` - The address repaying the flash loan
-/// * `asset` - The address of the asset contract
-/// * `amount` - The amount being repaid (should equal principal + fee)
+/// Applies the pause, limit, liquidity and reentrancy checks of
+/// [`execute_flash_loan`], records the loan and takes the flash loan lock.
+/// The principal is not transferred: the caller uses it inside the protocol
+/// and must bring principal plus fee into the contract before calling
+/// [`settle_internal_flash_loan`]. Any failure in between reverts the loan.
 ///
 /// # Returns
-/// Returns success if repayment is valid
-pub fn repay_flash_loan(
+/// Returns the fee owed on top of the principal
+pub(crate) fn begin_internal_flash_loan(
     env: &Env,
-    user: Address,
-    asset: Address,
+    user: &Address,
+    asset: &Address,
+    amount: i128,
+) -> Result<i128, FlashLoanError> {
+    let fee = validate_flash_loan(env, user, asset, amount)?;
+    let balance =
+        soroban_sdk::token::Client::new(env, asset).balance(&env.current_contract_address());
+    if balance < amount {
+        return Err(FlashLoanError::InsufficientLiquidity);
+    }
+
+    let this = env.current_contract_address();
+    record_flash_loan(env, user, asset, amount, fee, &this);
+    record_epoch_volume(env, user, amount)?;
+    crate::reentrancy::enter_flash_loan(env);
+    emit_flash_loan_initiated(
+        env,
+        FlashLoanInitiatedEvent {
+            user: user.clone(),
+            asset: asset.clone(),
+            amount,
+            fee,
+            callback: this,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(fee)
+}
+
+/// Close an internal flash loan once principal plus fee is back in the contract
+pub(crate) fn settle_internal_flash_loan(
+    env: &Env,
+    user: &Address,
+    asset: &Address,
     amount: i128,
+    fee: i128,
 ) -> Result<(), FlashLoanError> {
-    // Get active flash loan record
-    let loan_key = FlashLoanDataKey::ActiveFlashLoan(user.clone(), asset.clone());
-    let record = env
+    credit_flash_loan_fee(env, user, asset, fee)?;
+    clear_flash_loan(env, user, asset);
+    crate::reentrancy::exit_flash_loan(env);
+    emit_flash_loan_repaid(
+        env,
+        FlashLoanRepaidEvent {
+            user: user.clone(),
+            asset: asset.clone(),
+            amount,
+            fee,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Checks shared by receiver and internal flash loans
+///
+/// # Returns
+/// Returns the fee owed on top of the principal
+fn validate_flash_loan(
+    env: &Env,
+    user: &Address,
+    asset: &Address,
+    amount: i128,
+) -> Result<i128, FlashLoanError> {
+    // Validate amount
+    if amount <= 0 {
+        return Err(FlashLoanError::InvalidAmount);
+    }
+
+    // Check if flash loans are paused
+    let pause_key = FlashLoanDataKey::PauseSwitches;
+    if let Some(pause_map) = env
         .storage()
         .persistent()
-        .get::<FlashLoanDataKey, FlashLoanRecord>(&loan_key)
-        .ok_or(FlashLoanError::NotRepaid)?;
-
-    // Calculate required repayment
-    let required_repayment = record
-        .amount
-        .checked_add(record.fee)
-        .ok_or(FlashLoanError::Overflow)?;
+        .get::<FlashLoanDataKey, Map<Symbol, bool>>(&pause_key)
+    {
+        if let Some(paused) = pause_map.get(Symbol::new(env, "pause_flash_loan")) {
+            if paused {
+                return Err(FlashLoanError::FlashLoanPaused);
+            }
+        }
+    }
 
-    // Validate repayment amount
-    if amount < required_repayment {
-        return Err(FlashLoanError::InsufficientRepayment);
+    // Validate asset address
+    if *asset == env.current_contract_address() {
+        return Err(FlashLoanError::InvalidAsset);
     }
 
-    // Transfer tokens from user to contract
-    let token_client = soroban_sdk::token::Client::new(env, &asset);
+    // Check configuration limits
+    let config = get_flash_loan_config(env);
+    if amount < config.min_amount || amount > config.max_amount {
+        return Err(FlashLoanError::InvalidAmount);
+    }
 
-    // Check user balance
-    let user_balance = token_client.balance(&user);
-    if user_balance < required_repayment {
-        return Err(FlashLoanError::InsufficientRepayment);
+    // Check for reentrancy (active flash loan)
+    if is_flash_loan_active(env, user, asset) {
+        return Err(FlashLoanError::Reentrancy);
     }
 
-    // Transfer repayment (user must have approved the contract)
-    token_client.transfer_from(
-        &env.current_contract_address(), // spender (this contract)
-        &user,                           // from (user)
-        &env.current_contract_address(), // to (this contract)
-        &required_repayment,
-    );
+    calculate_flash_loan_fee(env, user, asset, amount)
+}
 
-    // Credit fee to protocol reserve
-    if record.fee > 0 {
+/// Credit a flash loan fee to the fee collector while protocol fees are on,
+/// otherwise to the protocol reserve
+fn credit_flash_loan_fee(
+    env: &Env,
+    user: &Address,
+    asset: &Address,
+    fee: i128,
+) -> Result<(), FlashLoanError> {
+    if fee > 0 && crate::fees::is_fee_switch_on(env) {
+        crate::fees::accrue_fee(env, FeeKind::FlashLoan, user, &Some(asset.clone()), fee)
+            .map_err(|_| FlashLoanError::Overflow)?;
+    } else if fee > 0 {
         let reserve_key = DepositDataKey::ProtocolReserve(Some(asset.clone()));
         let current_reserve = env
             .storage()
@@ -346,25 +618,17 @@ pub fn repay_flash_loan(
             .unwrap_or(0);
         env.storage().persistent().set(
             &reserve_key,
-            &(current_reserve.checked_add(record.fee).ok_or(FlashLoanError::Overflow)?),
+            &(current_reserve
+                .checked_add(fee)
+                .ok_or(FlashLoanError::Overflow)?),
+        );
+        crate::financials::record(
+            env,
+            crate::financials::FinancialItem::FlashLoanFees,
+            &Some(asset.clone()),
+            fee,
         );
     }
-
-    // Clear flash loan record
-    clear_flash_loan(env, &user, &asset);
-
-    // Emit flash loan repaid event
-    emit_flash_loan_repaid(
-        env,
-        FlashLoanRepaidEvent {
-            user: user.clone(),
-            asset: asset.clone(),
-            amount: record.amount,
-            fee: record.fee,
-            timestamp: env.ledger().timestamp(),
-        },
-    );
-
     Ok(())
 }
 
@@ -421,3 +685,64 @@ pub fn configure_flash_loan(
 
     Ok(())
 }
+
+/// Configure the flash loan integrator fee, volume tiers and epoch length
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `caller` - The address calling this function (must be admin)
+/// * `tiers` - The new fee tiers
+pub fn configure_flash_loan_fee_tiers(
+    env: &Env,
+    caller: Address,
+    tiers: FlashLoanFeeTiers,
+) -> Result<(), FlashLoanError> {
+    // Check authorization
+    crate::admin::require_admin(env, &caller).map_err(|_| FlashLoanError::InvalidCallback)?;
+
+    // Validate tiers
+    if !(0..=10000).contains(&tiers.integrator_fee_bps) || tiers.epoch_duration == 0 {
+        return Err(FlashLoanError::InvalidAmount);
+    }
+
+    if tiers.volume_tiers.len() > MAX_VOLUME_TIERS {
+        return Err(FlashLoanError::InvalidAmount);
+    }
+    for tier in tiers.volume_tiers.iter() {
+        if tier.min_epoch_volume < 0 || !(0..=10000).contains(&tier.fee_bps) {
+            return Err(FlashLoanError::InvalidAmount);
+        }
+    }
+
+    env.storage()
+        .persistent()
+        .set(&FlashLoanDataKey::FeeTiers, &tiers);
+
+    Ok(())
+}
+
+/// Register or deregister a flash loan integrator
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `caller` - The address calling this function (must be admin)
+/// * `integrator` - The integrator address
+/// * `enabled` - Whether the integrator fee applies
+pub fn set_flash_loan_integrator(
+    env: &Env,
+    caller: Address,
+    integrator: Address,
+    enabled: bool,
+) -> Result<(), FlashLoanError> {
+    // Check authorization
+    crate::admin::require_admin(env, &caller).map_err(|_| FlashLoanError::InvalidCallback)?;
+
+    let key = FlashLoanDataKey::Integrator(integrator);
+    if enabled {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+
+    Ok(())
+}
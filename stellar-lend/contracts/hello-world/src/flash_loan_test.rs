@@ -1,18 +1,22 @@
 //! # Flash Loan Test Suite
 //!
 //! Comprehensive tests for flash loan functionality including:
-//! - Successful flash loan execution and repayment
+//! - Successful flash loan execution with in-call repayment by the receiver
 //! - Fee calculation and validation
-//! - Unpaid loan revert scenarios
-//! - Callback validation
+//! - Unpaid, short and rejected loan revert scenarios
+//! - Callback validation and parameter forwarding
 //! - Admin fee configuration (set_fee_bps)
 //! - Security assumptions (reentrancy, pause, limits)
 
-use soroban_sdk::{testutils::Address as _, token, Address, Env, Map, Symbol};
+use soroban_sdk::{testutils::Address as _, token, Address, Bytes, Env, Map, Symbol};
+use stellarlend_testutils::{
+    register_mock_receiver, MockFlashLoanReceiverClient, ReceiverBehavior,
+};
 
+use crate::deposit::DepositDataKey;
 use crate::flash_loan::{
-    configure_flash_loan, execute_flash_loan, repay_flash_loan, set_flash_loan_fee,
-    FlashLoanConfig, FlashLoanDataKey, FlashLoanError,
+    configure_flash_loan, execute_flash_loan, set_flash_loan_fee, FlashLoanConfig,
+    FlashLoanDataKey, FlashLoanError,
 };
 use crate::HelloContract;
 
@@ -44,6 +48,21 @@ fn setup_with_balance(balance: i128) -> (Env, Address, Address, Address, Address
     (env, contract_id, admin, user, token_address)
 }
 
+/// Extra tokens held by receivers to pay loan fees
+const FEE_BUDGET: i128 = 100_000;
+
+/// Register a mock receiver funded with `FEE_BUDGET`
+fn setup_receiver(
+    env: &Env,
+    contract_id: &Address,
+    token_address: &Address,
+    behavior: ReceiverBehavior,
+) -> Address {
+    let receiver = register_mock_receiver(env, contract_id, behavior);
+    token::StellarAssetClient::new(env, token_address).mint(&receiver, &FEE_BUDGET);
+    receiver
+}
+
 // ============================================================================
 // SUCCESS CASES
 // ============================================================================
@@ -52,7 +71,7 @@ fn setup_with_balance(balance: i128) -> (Env, Address, Address, Address, Address
 #[test]
 fn test_flash_loan_success() {
     let (env, contract_id, _admin, user, token_address) = setup_with_balance(10_000_000);
-    let callback = Address::generate(&env);
+    let callback = setup_receiver(&env, &contract_id, &token_address, ReceiverBehavior::Repay);
 
     let result = env.as_contract(&contract_id, || {
         execute_flash_loan(
@@ -61,6 +80,7 @@ fn test_flash_loan_success() {
             token_address.clone(),
             1_000_000,
             callback,
+            Bytes::new(&env),
         )
     });
 
@@ -68,13 +88,12 @@ fn test_flash_loan_success() {
     assert_eq!(result.unwrap(), 1_000_900); // 1M + 900 fee
 }
 
-/// Test successful repayment
+/// Test repayment is collected within the same call
 #[test]
 fn test_flash_loan_repayment_success() {
     let (env, contract_id, _admin, user, token_address) = setup_with_balance(10_000_000);
-    let callback = Address::generate(&env);
-    let token_client = token::StellarAssetClient::new(&env, &token_address);
-    let token_std_client = token::TokenClient::new(&env, &token_address);
+    let callback = setup_receiver(&env, &contract_id, &token_address, ReceiverBehavior::Repay);
+    let token_client = token::TokenClient::new(&env, &token_address);
 
     let total = env.as_contract(&contract_id, || {
         execute_flash_loan(
@@ -82,19 +101,29 @@ fn test_flash_loan_repayment_success() {
             user.clone(),
             token_address.clone(),
             1_000_000,
-            callback,
+            callback.clone(),
+            Bytes::new(&env),
         )
         .unwrap()
     });
 
-    token_client.mint(&user, &(total * 2));
-    token_std_client.approve(&user, &contract_id, &total, &99999);
+    assert_eq!(total, 1_000_900);
+    assert_eq!(token_client.balance(&contract_id), 10_000_900);
+    assert_eq!(token_client.balance(&callback), FEE_BUDGET - 900);
 
-    let result = env.as_contract(&contract_id, || {
-        repay_flash_loan(&env, user.clone(), token_address.clone(), total)
+    // Loan record cleared and fee credited to the protocol reserve
+    env.as_contract(&contract_id, || {
+        let key = FlashLoanDataKey::ActiveFlashLoan(user.clone(), token_address.clone());
+        assert!(!env.storage().persistent().has(&key));
+        let reserve = env
+            .storage()
+            .persistent()
+            .get::<DepositDataKey, i128>(&DepositDataKey::ProtocolReserve(Some(
+                token_address.clone(),
+            )))
+            .unwrap();
+        assert_eq!(reserve, 900);
     });
-
-    assert!(result.is_ok());
 }
 
 // ============================================================================
@@ -105,7 +134,7 @@ fn test_flash_loan_repayment_success() {
 #[test]
 fn test_default_fee_calculation() {
     let (env, contract_id, _admin, user, token_address) = setup_with_balance(100_000_000);
-    let callback = Address::generate(&env);
+    let callback = setup_receiver(&env, &contract_id, &token_address, ReceiverBehavior::Repay);
 
     let cases = [(1_000_000_i128, 900_i128), (10_000_000_i128, 9_000_i128)];
 
@@ -117,17 +146,12 @@ fn test_default_fee_calculation() {
                 token_address.clone(),
                 amount,
                 callback.clone(),
+                Bytes::new(&env),
             )
             .unwrap()
         });
 
         assert_eq!(total, amount + expected_fee);
-
-        // Clear for next test
-        env.as_contract(&contract_id, || {
-            let key = FlashLoanDataKey::ActiveFlashLoan(user.clone(), token_address.clone());
-            env.storage().persistent().remove(&key);
-        });
     }
 }
 
@@ -135,7 +159,7 @@ fn test_default_fee_calculation() {
 #[test]
 fn test_custom_fee_calculation() {
     let (env, contract_id, admin, user, token_address) = setup_with_balance(10_000_000);
-    let callback = Address::generate(&env);
+    let callback = setup_receiver(&env, &contract_id, &token_address, ReceiverBehavior::Repay);
 
     env.as_contract(&contract_id, || {
         set_flash_loan_fee(&env, admin, 50).unwrap(); // 0.5%
@@ -148,6 +172,7 @@ fn test_custom_fee_calculation() {
             token_address.clone(),
             1_000_000,
             callback,
+            Bytes::new(&env),
         )
         .unwrap()
     });
@@ -159,7 +184,7 @@ fn test_custom_fee_calculation() {
 #[test]
 fn test_zero_fee() {
     let (env, contract_id, admin, user, token_address) = setup_with_balance(10_000_000);
-    let callback = Address::generate(&env);
+    let callback = setup_receiver(&env, &contract_id, &token_address, ReceiverBehavior::Repay);
 
     env.as_contract(&contract_id, || {
         set_flash_loan_fee(&env, admin, 0).unwrap();
@@ -172,6 +197,7 @@ fn test_zero_fee() {
             token_address.clone(),
             1_000_000,
             callback,
+            Bytes::new(&env),
         )
         .unwrap()
     });
@@ -183,70 +209,89 @@ fn test_zero_fee() {
 // UNPAID LOAN REVERT TESTS
 // ============================================================================
 
-/// Test unpaid loan error
+/// Test receiver that keeps the funds
 #[test]
 fn test_unpaid_loan_revert() {
     let (env, contract_id, _admin, user, token_address) = setup_with_balance(10_000_000);
+    let callback = setup_receiver(&env, &contract_id, &token_address, ReceiverBehavior::Keep);
 
     let result = env.as_contract(&contract_id, || {
-        repay_flash_loan(&env, user.clone(), token_address.clone(), 1_000_000)
+        execute_flash_loan(
+            &env,
+            user.clone(),
+            token_address.clone(),
+            1_000_000,
+            callback,
+            Bytes::new(&env),
+        )
     });
 
-    assert_eq!(result.unwrap_err(), FlashLoanError::NotRepaid);
+    assert_eq!(result.unwrap_err(), FlashLoanError::InsufficientRepayment);
 }
 
-/// Test insufficient repayment
+/// Test receiver that returns the principal but not the fee
 #[test]
 fn test_insufficient_repayment() {
     let (env, contract_id, _admin, user, token_address) = setup_with_balance(10_000_000);
-    let callback = Address::generate(&env);
-    let token_client = token::StellarAssetClient::new(&env, &token_address);
-    let token_std_client = token::TokenClient::new(&env, &token_address);
+    let callback = setup_receiver(
+        &env,
+        &contract_id,
+        &token_address,
+        ReceiverBehavior::RepayPrincipal,
+    );
 
-    let total = env.as_contract(&contract_id, || {
+    let result = env.as_contract(&contract_id, || {
         execute_flash_loan(
             &env,
             user.clone(),
             token_address.clone(),
             1_000_000,
             callback,
+            Bytes::new(&env),
         )
-        .unwrap()
     });
 
-    let insufficient = total - 100;
-    token_client.mint(&user, &(insufficient * 2));
-    token_std_client.approve(&user, &contract_id, &insufficient, &99999);
+    assert_eq!(result.unwrap_err(), FlashLoanError::InsufficientRepayment);
+}
+
+/// Test receiver that reports failure
+#[test]
+fn test_receiver_rejects_operation() {
+    let (env, contract_id, _admin, user, token_address) = setup_with_balance(10_000_000);
+    let callback = setup_receiver(&env, &contract_id, &token_address, ReceiverBehavior::Reject);
 
     let result = env.as_contract(&contract_id, || {
-        repay_flash_loan(&env, user.clone(), token_address.clone(), insufficient)
+        execute_flash_loan(
+            &env,
+            user.clone(),
+            token_address.clone(),
+            1_000_000,
+            callback,
+            Bytes::new(&env),
+        )
     });
 
-    assert_eq!(result.unwrap_err(), FlashLoanError::InsufficientRepayment);
+    assert_eq!(result.unwrap_err(), FlashLoanError::CallbackFailed);
 }
 
-/// Test user insufficient balance
+/// Test callback address without a receiver contract
 #[test]
-fn test_insufficient_user_balance() {
+fn test_callback_not_a_receiver() {
     let (env, contract_id, _admin, user, token_address) = setup_with_balance(10_000_000);
     let callback = Address::generate(&env);
 
-    let total = env.as_contract(&contract_id, || {
+    let result = env.as_contract(&contract_id, || {
         execute_flash_loan(
             &env,
             user.clone(),
             token_address.clone(),
             1_000_000,
             callback,
+            Bytes::new(&env),
         )
-        .unwrap()
-    });
-
-    let result = env.as_contract(&contract_id, || {
-        repay_flash_loan(&env, user.clone(), token_address.clone(), total)
     });
 
-    assert_eq!(result.unwrap_err(), FlashLoanError::InsufficientRepayment);
+    assert_eq!(result.unwrap_err(), FlashLoanError::CallbackFailed);
 }
 
 // ============================================================================
@@ -265,17 +310,19 @@ fn test_invalid_callback_self() {
             token_address.clone(),
             1_000_000,
             contract_id.clone(),
+            Bytes::new(&env),
         )
     });
 
     assert_eq!(result.unwrap_err(), FlashLoanError::InvalidCallback);
 }
 
-/// Test valid callback
+/// Test valid callback receives the initiator's params
 #[test]
 fn test_valid_callback() {
     let (env, contract_id, _admin, user, token_address) = setup_with_balance(10_000_000);
-    let callback = Address::generate(&env);
+    let callback = setup_receiver(&env, &contract_id, &token_address, ReceiverBehavior::Repay);
+    let params = Bytes::from_slice(&env, b"swap:route-1");
 
     let result = env.as_contract(&contract_id, || {
         execute_flash_loan(
@@ -284,21 +331,15 @@ fn test_valid_callback() {
             token_address.clone(),
             1_000_000,
             callback.clone(),
+            params.clone(),
         )
     });
 
     assert!(result.is_ok());
-
-    // Verify callback stored
-    env.as_contract(&contract_id, || {
-        let key = FlashLoanDataKey::ActiveFlashLoan(user.clone(), token_address.clone());
-        let record = env
-            .storage()
-            .persistent()
-            .get::<FlashLoanDataKey, crate::flash_loan::FlashLoanRecord>(&key)
-            .unwrap();
-        assert_eq!(record.callback, callback);
-    });
+    assert_eq!(
+        MockFlashLoanReceiverClient::new(&env, &callback).last_params(),
+        Some(params)
+    );
 }
 
 // ============================================================================
@@ -369,17 +410,19 @@ fn test_set_fee_bps_maximum() {
 #[test]
 fn test_reentrancy_protection() {
     let (env, contract_id, _admin, user, token_address) = setup_with_balance(20_000_000);
-    let callback = Address::generate(&env);
+    let callback = setup_receiver(&env, &contract_id, &token_address, ReceiverBehavior::Repay);
 
+    // A loan for the same (user, asset) pair is still outstanding
     env.as_contract(&contract_id, || {
-        execute_flash_loan(
-            &env,
-            user.clone(),
-            token_address.clone(),
-            1_000_000,
-            callback.clone(),
-        )
-        .unwrap();
+        env.storage().persistent().set(
+            &FlashLoanDataKey::ActiveFlashLoan(user.clone(), token_address.clone()),
+            &crate::flash_loan::FlashLoanRecord {
+                amount: 1_000_000,
+                fee: 900,
+                timestamp: env.ledger().timestamp(),
+                callback: callback.clone(),
+            },
+        );
     });
 
     let result = env.as_contract(&contract_id, || {
@@ -389,6 +432,7 @@ fn test_reentrancy_protection() {
             token_address.clone(),
             1_000_000,
             callback,
+            Bytes::new(&env),
         )
     });
 
@@ -415,6 +459,7 @@ fn test_pause_flash_loan() {
             token_address.clone(),
             1_000_000,
             callback,
+            Bytes::new(&env),
         )
     });
 
@@ -434,6 +479,7 @@ fn test_insufficient_liquidity() {
             token_address.clone(),
             1_000_000,
             callback,
+            Bytes::new(&env),
         )
     });
 
@@ -447,7 +493,14 @@ fn test_invalid_amount_zero() {
     let callback = Address::generate(&env);
 
     let result = env.as_contract(&contract_id, || {
-        execute_flash_loan(&env, user.clone(), token_address.clone(), 0, callback)
+        execute_flash_loan(
+            &env,
+            user.clone(),
+            token_address.clone(),
+            0,
+            callback,
+            Bytes::new(&env),
+        )
     });
 
     assert_eq!(result.unwrap_err(), FlashLoanError::InvalidAmount);
@@ -466,6 +519,7 @@ fn test_invalid_amount_negative() {
             token_address.clone(),
             -1_000_000,
             callback,
+            Bytes::new(&env),
         )
     });
 
@@ -479,7 +533,14 @@ fn test_invalid_asset() {
     let callback = Address::generate(&env);
 
     let result = env.as_contract(&contract_id, || {
-        execute_flash_loan(&env, user.clone(), contract_id.clone(), 1_000_000, callback)
+        execute_flash_loan(
+            &env,
+            user.clone(),
+            contract_id.clone(),
+            1_000_000,
+            callback,
+            Bytes::new(&env),
+        )
     });
 
     assert_eq!(result.unwrap_err(), FlashLoanError::InvalidAsset);
@@ -489,7 +550,7 @@ fn test_invalid_asset() {
 #[test]
 fn test_configuration_limits() {
     let (env, contract_id, admin, user, token_address) = setup_with_balance(100_000_000);
-    let callback = Address::generate(&env);
+    let callback = setup_receiver(&env, &contract_id, &token_address, ReceiverBehavior::Repay);
 
     env.as_contract(&contract_id, || {
         let config = FlashLoanConfig {
@@ -508,6 +569,7 @@ fn test_configuration_limits() {
             token_address.clone(),
             500,
             callback.clone(),
+            Bytes::new(&env),
         )
     });
     assert_eq!(result.unwrap_err(), FlashLoanError::InvalidAmount);
@@ -520,6 +582,7 @@ fn test_configuration_limits() {
             token_address.clone(),
             20_000_000,
             callback.clone(),
+            Bytes::new(&env),
         )
     });
     assert_eq!(result.unwrap_err(), FlashLoanError::InvalidAmount);
@@ -532,6 +595,7 @@ fn test_configuration_limits() {
             token_address.clone(),
             5_000_000,
             callback,
+            Bytes::new(&env),
         )
     });
     assert!(result.is_ok());
@@ -1,22 +1,22 @@
 #![allow(unused_variables)]
 
-use soroban_sdk::{token::TokenClient, Address, Env, String, Vec};
+use soroban_sdk::{token::TokenClient, vec, Address, Env, IntoVal, Map, String, Symbol, Vec};
 
 use crate::errors::GovernanceError;
 use crate::storage::{GovernanceDataKey, GuardianConfig};
 
 use crate::events::{
-    GovernanceInitializedEvent, GuardianAddedEvent, GuardianRemovedEvent, ProposalApprovedEvent,
-    ProposalCancelledEvent, ProposalCreatedEvent, ProposalExecutedEvent, ProposalFailedEvent,
-    ProposalQueuedEvent, RecoveryApprovedEvent, RecoveryExecutedEvent, RecoveryStartedEvent,
-    VoteCastEvent,
+    DelegateChangedEvent, DelegateVotesChangedEvent, GovernanceInitializedEvent,
+    GuardianAddedEvent, GuardianRemovedEvent, ProposalApprovedEvent, ProposalCancelledEvent,
+    ProposalCreatedEvent, ProposalExecutedEvent, ProposalFailedEvent, ProposalQueuedEvent,
+    RecoveryApprovedEvent, RecoveryExecutedEvent, RecoveryStartedEvent, VoteCastEvent,
 };
 
 use crate::types::{
     GovernanceConfig, MultisigConfig, Proposal, ProposalOutcome, ProposalStatus, ProposalType,
-    RecoveryRequest, VoteInfo, VoteType, BASIS_POINTS_SCALE, DEFAULT_EXECUTION_DELAY,
-    DEFAULT_QUORUM_BPS, DEFAULT_RECOVERY_PERIOD, DEFAULT_TIMELOCK_DURATION, DEFAULT_VOTING_PERIOD,
-    DEFAULT_VOTING_THRESHOLD,
+    RecoveryRequest, VoteCheckpoint, VoteInfo, VoteType, VotingStrategy, BASIS_POINTS_SCALE,
+    DEFAULT_EXECUTION_DELAY, DEFAULT_QUORUM_BPS, DEFAULT_RECOVERY_PERIOD,
+    DEFAULT_TIMELOCK_DURATION, DEFAULT_VOTING_PERIOD, DEFAULT_VOTING_THRESHOLD,
 };
 
 // ========================================================================
@@ -48,6 +48,9 @@ pub fn initialize(
         vote_token,
         timelock_duration: timelock_duration.unwrap_or(DEFAULT_TIMELOCK_DURATION),
         default_voting_threshold: default_voting_threshold.unwrap_or(DEFAULT_VOTING_THRESHOLD),
+        voting_strategy: VotingStrategy::TokenWeighted,
+        strategy_overrides: Map::new(env),
+        strategy_quorums: Map::new(env),
     };
 
     if config.quorum_bps > 10000 {
@@ -116,9 +119,10 @@ pub fn create_proposal(
         .get(&GovernanceDataKey::Config)
         .ok_or(GovernanceError::NotInitialized)?;
 
+    validate_proposal_type(env, &proposal_type)?;
+
     if config.proposal_threshold > 0 {
-        let token_client = TokenClient::new(env, &config.vote_token);
-        let balance = token_client.balance(&proposer);
+        let balance = voting_power(env, &config, &proposer);
 
         if balance < config.proposal_threshold {
             return Err(GovernanceError::InsufficientProposalPower);
@@ -132,6 +136,7 @@ pub fn create_proposal(
         .unwrap_or(0);
 
     let now = env.ledger().timestamp();
+    let voting_strategy = strategy_for(env, &config, &proposal_type);
 
     let proposal = Proposal {
         id: next_id,
@@ -148,6 +153,7 @@ pub fn create_proposal(
         abstain_votes: 0,
         total_voting_power: 0,
         created_at: now,
+        voting_strategy,
     };
 
     env.storage()
@@ -183,6 +189,68 @@ pub fn create_proposal(
 // Voting
 // ========================================================================
 
+/// Current voting power of an account
+///
+/// Vote token balance plus vested, unclaimed tokens in the vesting contract;
+/// the vesting contract itself has no voting power, so locked tokens never
+/// vote. With checkpointed voting enabled, only deposited votes delegated to
+/// the account count.
+fn voting_power(env: &Env, config: &GovernanceConfig, account: &Address) -> i128 {
+    if is_checkpointed_voting(env) {
+        return get_current_votes(env, account);
+    }
+
+    let vesting: Option<Address> = env
+        .storage()
+        .instance()
+        .get(&GovernanceDataKey::VestingContract);
+    if vesting.as_ref() == Some(account) {
+        return 0;
+    }
+
+    let balance = TokenClient::new(env, &config.vote_token).balance(account);
+    let vested = vesting.map_or(0, |vesting| {
+        env.invoke_contract::<i128>(
+            &vesting,
+            &Symbol::new(env, "releasable"),
+            vec![env, account.into_val(env)],
+        )
+    });
+    balance.saturating_add(vested)
+}
+
+/// Register the token lock contract holding unvested vote tokens (admin only)
+pub fn set_vesting_contract(
+    env: &Env,
+    caller: Address,
+    vesting_contract: Address,
+) -> Result<(), GovernanceError> {
+    caller.require_auth();
+
+    let admin: Address = env
+        .storage()
+        .instance()
+        .get(&GovernanceDataKey::Admin)
+        .ok_or(GovernanceError::NotInitialized)?;
+
+    if caller != admin {
+        return Err(GovernanceError::Unauthorized);
+    }
+
+    env.storage()
+        .instance()
+        .set(&GovernanceDataKey::VestingContract, &vesting_contract);
+
+    Ok(())
+}
+
+/// Get the registered token lock contract
+pub fn get_vesting_contract(env: &Env) -> Option<Address> {
+    env.storage()
+        .instance()
+        .get(&GovernanceDataKey::VestingContract)
+}
+
 pub fn vote(
     env: &Env,
     voter: Address,
@@ -218,12 +286,18 @@ pub fn vote(
         return Err(GovernanceError::AlreadyVoted);
     }
 
-    let token_client = TokenClient::new(env, &config.vote_token);
-    let voting_power = token_client.balance(&voter);
+    // Checkpointed votes are taken as of before the proposal was created, so
+    // tokens moved afterwards cannot vote twice
+    let voting_power = if is_checkpointed_voting(env) {
+        get_prior_votes(env, &voter, proposal.created_at.saturating_sub(1))?
+    } else {
+        voting_power(env, &config, &voter)
+    };
 
     if voting_power == 0 {
         return Err(GovernanceError::NoVotingPower);
     }
+    let voting_power = strategy_votes(proposal.voting_strategy, voting_power);
 
     match vote_type {
         VoteType::For => proposal.for_votes += voting_power,
@@ -258,6 +332,432 @@ pub fn vote(
     Ok(())
 }
 
+// ========================================================================
+// Voting Strategies
+// ========================================================================
+
+/// Proposal kinds, in `ProposalType` variant order, for strategy overrides
+const PROPOSAL_KINDS: [&str; 12] = [
+    "min_collateral_ratio",
+    "risk_params",
+    "pause_switch",
+    "emergency_pause",
+    "reserve_factor",
+    "exit_exemption",
+    "risk_engine",
+    "interest_rate_config",
+    "list_asset",
+    "generic_action",
+    "upgrade_contract",
+    "subsidy_campaign",
+];
+
+/// Name of a proposal type's kind, as used for strategy overrides
+pub fn proposal_kind(env: &Env, proposal_type: &ProposalType) -> Symbol {
+    let index = match proposal_type {
+        ProposalType::MinCollateralRatio(_) => 0,
+        ProposalType::RiskParams(..) => 1,
+        ProposalType::PauseSwitch(..) => 2,
+        ProposalType::EmergencyPause(_) => 3,
+        ProposalType::ReserveFactor(..) => 4,
+        ProposalType::ExitExemption(..) => 5,
+        ProposalType::RiskEngine(..) => 6,
+        ProposalType::InterestRateConfig(_) => 7,
+        ProposalType::ListAsset(_) => 8,
+        ProposalType::GenericAction(_) => 9,
+        ProposalType::UpgradeContract(_) => 10,
+        ProposalType::SubsidyCampaign(_) => 11,
+    };
+    Symbol::new(env, PROPOSAL_KINDS[index])
+}
+
+fn strategy_for(
+    env: &Env,
+    config: &GovernanceConfig,
+    proposal_type: &ProposalType,
+) -> VotingStrategy {
+    config
+        .strategy_overrides
+        .get(proposal_kind(env, proposal_type))
+        .unwrap_or(config.voting_strategy)
+}
+
+/// Votes counted for `voting_power` under `strategy`
+fn strategy_votes(strategy: VotingStrategy, voting_power: i128) -> i128 {
+    match strategy {
+        VotingStrategy::TokenWeighted => voting_power,
+        VotingStrategy::Quadratic => integer_sqrt(voting_power),
+        VotingStrategy::OneAddressOneVote => 1,
+    }
+}
+
+/// Floor of the square root of a non-negative value
+fn integer_sqrt(value: i128) -> i128 {
+    if value < 2 {
+        return value.max(0);
+    }
+    let mut x = value;
+    let mut y = (x + 1) / 2;
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+/// Set the voting strategy of a proposal kind, or the default with `None`
+/// (admin only)
+///
+/// Applies to proposals created afterwards; existing proposals keep the
+/// strategy they were created with.
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not the governance admin
+/// * `UnknownProposalKind` - `kind` is not a name from [`proposal_kind`]
+pub fn set_voting_strategy(
+    env: &Env,
+    caller: Address,
+    kind: Option<Symbol>,
+    strategy: VotingStrategy,
+) -> Result<(), GovernanceError> {
+    let mut config = admin_config(env, &caller)?;
+    match kind {
+        Some(kind) => {
+            if !PROPOSAL_KINDS
+                .iter()
+                .any(|name| Symbol::new(env, name) == kind)
+            {
+                return Err(GovernanceError::UnknownProposalKind);
+            }
+            config.strategy_overrides.set(kind, strategy);
+        }
+        None => config.voting_strategy = strategy,
+    }
+    env.storage()
+        .instance()
+        .set(&GovernanceDataKey::Config, &config);
+    Ok(())
+}
+
+/// Set the minimum votes cast, in the strategy's units, for proposals voted
+/// with `strategy` to reach quorum (admin only)
+///
+/// Token amounts for token-weighted, square roots of token amounts for
+/// quadratic and voters for one-address-one-vote; 0 removes the minimum.
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not the governance admin
+/// * `InvalidQuorum` - `min_votes` is negative
+pub fn set_strategy_quorum(
+    env: &Env,
+    caller: Address,
+    strategy: VotingStrategy,
+    min_votes: i128,
+) -> Result<(), GovernanceError> {
+    let mut config = admin_config(env, &caller)?;
+    if min_votes < 0 {
+        return Err(GovernanceError::InvalidQuorum);
+    }
+    if min_votes == 0 {
+        config.strategy_quorums.remove(strategy);
+    } else {
+        config.strategy_quorums.set(strategy, min_votes);
+    }
+    env.storage()
+        .instance()
+        .set(&GovernanceDataKey::Config, &config);
+    Ok(())
+}
+
+/// Voting strategy new proposals of this type are created with
+pub fn get_voting_strategy(
+    env: &Env,
+    proposal_type: &ProposalType,
+) -> Result<VotingStrategy, GovernanceError> {
+    let config = get_config(env).ok_or(GovernanceError::NotInitialized)?;
+    Ok(strategy_for(env, &config, proposal_type))
+}
+
+/// Authorize the governance admin and load the config
+fn admin_config(env: &Env, caller: &Address) -> Result<GovernanceConfig, GovernanceError> {
+    caller.require_auth();
+
+    let admin: Address = env
+        .storage()
+        .instance()
+        .get(&GovernanceDataKey::Admin)
+        .ok_or(GovernanceError::NotInitialized)?;
+
+    if *caller != admin {
+        return Err(GovernanceError::Unauthorized);
+    }
+
+    get_config(env).ok_or(GovernanceError::NotInitialized)
+}
+
+// ========================================================================
+// Delegation
+// ========================================================================
+
+/// Switch between live-balance and checkpointed voting (admin only)
+///
+/// Checkpointed voting counts only vote tokens deposited with
+/// [`deposit_votes`], snapshotted before each proposal's creation.
+pub fn set_checkpointed_voting(
+    env: &Env,
+    caller: Address,
+    enabled: bool,
+) -> Result<(), GovernanceError> {
+    caller.require_auth();
+
+    let admin: Address = env
+        .storage()
+        .instance()
+        .get(&GovernanceDataKey::Admin)
+        .ok_or(GovernanceError::NotInitialized)?;
+
+    if caller != admin {
+        return Err(GovernanceError::Unauthorized);
+    }
+
+    env.storage()
+        .instance()
+        .set(&GovernanceDataKey::CheckpointedVoting, &enabled);
+
+    Ok(())
+}
+
+/// Whether votes come from checkpointed deposits
+pub fn is_checkpointed_voting(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&GovernanceDataKey::CheckpointedVoting)
+        .unwrap_or(false)
+}
+
+/// Lock vote tokens in governance, adding their votes to the account's delegate
+pub fn deposit_votes(env: &Env, account: Address, amount: i128) -> Result<(), GovernanceError> {
+    account.require_auth();
+    if amount <= 0 {
+        return Err(GovernanceError::InvalidAmount);
+    }
+
+    let config: GovernanceConfig = env
+        .storage()
+        .instance()
+        .get(&GovernanceDataKey::Config)
+        .ok_or(GovernanceError::NotInitialized)?;
+    TokenClient::new(env, &config.vote_token).transfer(
+        &account,
+        &env.current_contract_address(),
+        &amount,
+    );
+
+    let deposit = get_voting_deposit(env, &account)
+        .checked_add(amount)
+        .ok_or(GovernanceError::MathOverflow)?;
+    env.storage()
+        .persistent()
+        .set(&GovernanceDataKey::VotingDeposit(account.clone()), &deposit);
+    move_votes(env, None, Some(&get_delegate(env, &account)), amount)
+}
+
+/// Return deposited vote tokens, removing their votes from the delegate
+pub fn withdraw_votes(env: &Env, account: Address, amount: i128) -> Result<(), GovernanceError> {
+    account.require_auth();
+    if amount <= 0 {
+        return Err(GovernanceError::InvalidAmount);
+    }
+
+    let deposit = get_voting_deposit(env, &account);
+    if amount > deposit {
+        return Err(GovernanceError::InsufficientVotingDeposit);
+    }
+    let config: GovernanceConfig = env
+        .storage()
+        .instance()
+        .get(&GovernanceDataKey::Config)
+        .ok_or(GovernanceError::NotInitialized)?;
+
+    env.storage().persistent().set(
+        &GovernanceDataKey::VotingDeposit(account.clone()),
+        &(deposit - amount),
+    );
+    move_votes(env, Some(&get_delegate(env, &account)), None, amount)?;
+
+    TokenClient::new(env, &config.vote_token).transfer(
+        &env.current_contract_address(),
+        &account,
+        &amount,
+    );
+    Ok(())
+}
+
+/// Vote through `delegatee` with the delegator's deposit
+///
+/// Delegating to oneself takes the votes back.
+pub fn delegate(env: &Env, delegator: Address, delegatee: Address) -> Result<(), GovernanceError> {
+    delegator.require_auth();
+
+    let current = get_delegate(env, &delegator);
+    if current == delegatee {
+        return Ok(());
+    }
+    if delegatee == delegator {
+        env.storage()
+            .persistent()
+            .remove(&GovernanceDataKey::Delegate(delegator.clone()));
+    } else {
+        env.storage()
+            .persistent()
+            .set(&GovernanceDataKey::Delegate(delegator.clone()), &delegatee);
+    }
+
+    DelegateChangedEvent {
+        delegator: delegator.clone(),
+        from_delegate: current.clone(),
+        to_delegate: delegatee.clone(),
+    }
+    .publish(env);
+
+    move_votes(
+        env,
+        Some(&current),
+        Some(&delegatee),
+        get_voting_deposit(env, &delegator),
+    )
+}
+
+/// Account that votes with an account's deposit (itself unless delegated)
+pub fn get_delegate(env: &Env, account: &Address) -> Address {
+    env.storage()
+        .persistent()
+        .get(&GovernanceDataKey::Delegate(account.clone()))
+        .unwrap_or(account.clone())
+}
+
+/// Vote tokens an account has deposited
+pub fn get_voting_deposit(env: &Env, account: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&GovernanceDataKey::VotingDeposit(account.clone()))
+        .unwrap_or(0)
+}
+
+/// Votes currently delegated to an account
+pub fn get_current_votes(env: &Env, account: &Address) -> i128 {
+    let count = checkpoint_count(env, account);
+    if count == 0 {
+        return 0;
+    }
+    checkpoint(env, account, count - 1).votes
+}
+
+/// Votes delegated to an account at the end of `timestamp`
+///
+/// # Errors
+/// * `VotesNotYetDetermined` - `timestamp` is not in the past
+pub fn get_prior_votes(
+    env: &Env,
+    account: &Address,
+    timestamp: u64,
+) -> Result<i128, GovernanceError> {
+    if timestamp >= env.ledger().timestamp() {
+        return Err(GovernanceError::VotesNotYetDetermined);
+    }
+
+    // Binary search for the last checkpoint at or before `timestamp`
+    let count = checkpoint_count(env, account);
+    if count == 0 || checkpoint(env, account, 0).timestamp > timestamp {
+        return Ok(0);
+    }
+    let (mut low, mut high) = (0, count - 1);
+    while low < high {
+        let mid = high - (high - low) / 2;
+        if checkpoint(env, account, mid).timestamp <= timestamp {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    Ok(checkpoint(env, account, low).votes)
+}
+
+fn checkpoint_count(env: &Env, account: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&GovernanceDataKey::CheckpointCount(account.clone()))
+        .unwrap_or(0)
+}
+
+fn checkpoint(env: &Env, account: &Address, index: u32) -> VoteCheckpoint {
+    env.storage()
+        .persistent()
+        .get(&GovernanceDataKey::Checkpoint(account.clone(), index))
+        .unwrap_or(VoteCheckpoint {
+            timestamp: 0,
+            votes: 0,
+        })
+}
+
+/// Move `amount` votes between delegates, checkpointing both
+///
+/// `None` mints votes (deposit) or burns them (withdrawal).
+fn move_votes(
+    env: &Env,
+    from: Option<&Address>,
+    to: Option<&Address>,
+    amount: i128,
+) -> Result<(), GovernanceError> {
+    if amount == 0 || from == to {
+        return Ok(());
+    }
+    if let Some(from) = from {
+        let previous = get_current_votes(env, from);
+        let votes = previous
+            .checked_sub(amount)
+            .ok_or(GovernanceError::MathOverflow)?;
+        write_checkpoint(env, from, previous, votes);
+    }
+    if let Some(to) = to {
+        let previous = get_current_votes(env, to);
+        let votes = previous
+            .checked_add(amount)
+            .ok_or(GovernanceError::MathOverflow)?;
+        write_checkpoint(env, to, previous, votes);
+    }
+    Ok(())
+}
+
+/// Record a delegate's new votes, overwriting a checkpoint from this ledger
+fn write_checkpoint(env: &Env, delegate: &Address, previous_votes: i128, new_votes: i128) {
+    let now = env.ledger().timestamp();
+    let count = checkpoint_count(env, delegate);
+    let index = if count > 0 && checkpoint(env, delegate, count - 1).timestamp == now {
+        count - 1
+    } else {
+        env.storage().persistent().set(
+            &GovernanceDataKey::CheckpointCount(delegate.clone()),
+            &(count + 1),
+        );
+        count
+    };
+    env.storage().persistent().set(
+        &GovernanceDataKey::Checkpoint(delegate.clone(), index),
+        &VoteCheckpoint {
+            timestamp: now,
+            votes: new_votes,
+        },
+    );
+
+    DelegateVotesChangedEvent {
+        delegate: delegate.clone(),
+        previous_votes,
+        new_votes,
+    }
+    .publish(env);
+}
+
 // ========================================================================
 // Queue Proposal
 // ========================================================================
@@ -305,8 +805,14 @@ pub fn queue_proposal(
         return Err(GovernanceError::ProposalExpired);
     }
 
+    // Votes are in the proposal's strategy units, and so is its quorum
     let total_votes = proposal.for_votes + proposal.against_votes + proposal.abstain_votes;
-    let quorum_required = (total_votes * config.quorum_bps as i128) / BASIS_POINTS_SCALE;
+    let strategy_quorum = config
+        .strategy_quorums
+        .get(proposal.voting_strategy)
+        .unwrap_or(0);
+    let quorum_required =
+        ((total_votes * config.quorum_bps as i128) / BASIS_POINTS_SCALE).max(strategy_quorum);
     let quorum_reached = total_votes >= quorum_required;
 
     let threshold_votes =
@@ -424,14 +930,134 @@ pub fn execute_proposal(
     Ok(())
 }
 
-fn execute_proposal_type(_env: &Env, proposal_type: &ProposalType) -> Result<(), GovernanceError> {
+/// Apply a passed proposal's payload with governance authority
+///
+/// Parameter changes go through the same validation as the admin setters;
+/// any failure aborts the execution.
+fn execute_proposal_type(env: &Env, proposal_type: &ProposalType) -> Result<(), GovernanceError> {
+    let governance = env.current_contract_address();
     match proposal_type {
-        ProposalType::MinCollateralRatio(_)
-        | ProposalType::RiskParams(_, _, _, _)
-        | ProposalType::PauseSwitch(_, _)
+        ProposalType::MinCollateralRatio(min_collateral_ratio) => {
+            crate::risk_params::set_risk_params(env, Some(*min_collateral_ratio), None, None, None)
+                .map_err(|_| GovernanceError::ExecutionFailed)
+        }
+        ProposalType::RiskParams(
+            min_collateral_ratio,
+            liquidation_threshold,
+            close_factor,
+            liquidation_incentive,
+        ) => crate::risk_params::set_risk_params(
+            env,
+            *min_collateral_ratio,
+            *liquidation_threshold,
+            *close_factor,
+            *liquidation_incentive,
+        )
+        .map_err(|_| GovernanceError::ExecutionFailed),
+        ProposalType::PauseSwitch(operation, paused) => {
+            crate::risk_management::apply_pause_switch(env, &governance, operation.clone(), *paused)
+                .map_err(|_| GovernanceError::ExecutionFailed)
+        }
+        ProposalType::EmergencyPause(paused) => {
+            crate::risk_management::apply_emergency_pause(env, &governance, *paused);
+            Ok(())
+        }
+        ProposalType::ReserveFactor(asset, reserve_factor_bps) => {
+            crate::reserve::apply_reserve_factor(env, asset.clone(), *reserve_factor_bps)
+                .map_err(|_| GovernanceError::ExecutionFailed)
+        }
+        ProposalType::ExitExemption(account, exempt) => {
+            crate::risk_management::apply_exit_exemption(env, account.clone(), *exempt)
+                .map_err(|_| GovernanceError::ExecutionFailed)
+        }
+        ProposalType::RiskEngine(engine, whitelisted) => {
+            crate::risk_hook::apply_risk_engine_whitelist(env, engine.clone(), *whitelisted);
+            Ok(())
+        }
+        ProposalType::InterestRateConfig(update) => {
+            crate::interest_rate::apply_interest_rate_config(env, update)
+                .map_err(|_| GovernanceError::ExecutionFailed)
+        }
+        ProposalType::ListAsset(config) => {
+            crate::cross_asset::apply_asset_listing(env, config.asset.clone(), config.clone())
+                .map_err(|_| GovernanceError::ExecutionFailed)
+        }
+        ProposalType::GenericAction(_) => Err(GovernanceError::InvalidAction),
+        ProposalType::UpgradeContract(wasm_hash) => {
+            crate::upgrade::apply_upgrade(env, wasm_hash.clone());
+            Ok(())
+        }
+        ProposalType::SubsidyCampaign(terms) => {
+            crate::subsidy_campaign::apply_subsidy_campaign(env, terms.clone())
+                .map(|_| ())
+                .map_err(|_| GovernanceError::ExecutionFailed)
+        }
+    }
+}
+
+/// Reject malformed payloads when a proposal is created
+///
+/// Only checks what does not depend on protocol state at execution time
+/// (ranges and listing status); execution re-validates everything.
+fn validate_proposal_type(env: &Env, proposal_type: &ProposalType) -> Result<(), GovernanceError> {
+    let valid = match proposal_type {
+        ProposalType::MinCollateralRatio(min_collateral_ratio) => {
+            crate::risk_params::validate_risk_params_bounds(
+                Some(*min_collateral_ratio),
+                None,
+                None,
+                None,
+            )
+            .is_ok()
+        }
+        ProposalType::RiskParams(
+            min_collateral_ratio,
+            liquidation_threshold,
+            close_factor,
+            liquidation_incentive,
+        ) => {
+            [
+                min_collateral_ratio,
+                liquidation_threshold,
+                close_factor,
+                liquidation_incentive,
+            ]
+            .iter()
+            .any(|param| param.is_some())
+                && crate::risk_params::validate_risk_params_bounds(
+                    *min_collateral_ratio,
+                    *liquidation_threshold,
+                    *close_factor,
+                    *liquidation_incentive,
+                )
+                .is_ok()
+        }
+        ProposalType::ReserveFactor(_, reserve_factor_bps) => {
+            (0..=crate::reserve::MAX_RESERVE_FACTOR_BPS).contains(reserve_factor_bps)
+        }
+        ProposalType::InterestRateConfig(update) => {
+            crate::interest_rate::validate_interest_rate_update(update).is_ok()
+        }
+        ProposalType::SubsidyCampaign(terms) => {
+            crate::subsidy_campaign::validate_terms(terms).is_ok()
+        }
+        ProposalType::ListAsset(config) => {
+            crate::cross_asset::require_valid_config(config).is_ok()
+                && crate::cross_asset::get_asset_config_by_address(env, config.asset.clone())
+                    .is_err()
+        }
+        ProposalType::PauseSwitch(_, _)
         | ProposalType::EmergencyPause(_)
-        | ProposalType::GenericAction(_) => Ok(()),
+        | ProposalType::ExitExemption(_, _)
+        | ProposalType::RiskEngine(_, _)
+        | ProposalType::UpgradeContract(_) => true,
+        ProposalType::GenericAction(_) => false,
+    };
+
+    if !valid {
+        return Err(GovernanceError::InvalidAction);
     }
+    Ok(())
 }
 
 // ========================================================================
@@ -0,0 +1,367 @@
+//! # Position Hedging Hooks
+//!
+//! Lets a borrower sign a standing hedge instruction that keepers execute
+//! when the position weakens: once the health factor falls below the
+//! user's trigger, a keeper calls [`execute_hedge`] to sell a fixed share of
+//! the collateral through the AMM into the hedge asset (typically a stable
+//! asset the user owes) and repay debt with the proceeds.
+//!
+//! The keeper cannot choose anything: the assets, the share sold, the
+//! slippage bound, the cooldown between executions, the number of
+//! executions and the expiry all come from the instruction the user signed.
+//! An execution:
+//!
+//! 1. Fronts the minimum swap proceeds of the hedge asset from the
+//!    protocol's liquidity and repays the user's debt with them, so the
+//!    collateral withdrawal below passes the ratio check even on a tight
+//!    position.
+//! 2. Withdraws the share of collateral and swaps it into the hedge asset.
+//! 3. Takes the fronted amount back from the user; any proceeds above the
+//!    slippage bound stay with the user.
+//!
+//! Repaying and settling pull the hedge asset from the user through an
+//! allowance, so the user approves the contract for the hedge asset when
+//! setting the instruction. A failing step rolls back the whole execution.
+
+use soroban_sdk::{contracterror, contracttype, token, Address, Env};
+use stellarlend_amm::SwapParams;
+
+use crate::deposit::{token_address, DepositDataKey, Position};
+use crate::events::{emit_hedge_executed, HedgeExecutedEvent};
+use crate::fees::FeeKind;
+use crate::risk_management::{calculate_health_factor, HEALTH_FACTOR_SCALE};
+
+/// Basis points scale
+const BPS_SCALE: i128 = 10_000;
+
+/// Seconds the hedge swap stays valid
+const SWAP_DEADLINE_SECONDS: u64 = 300;
+
+/// Errors that can occur in hedging operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum HedgeError {
+    /// The instruction's assets, trigger, share, slippage, limit or expiry is invalid
+    InvalidInstruction = 1,
+    /// The user has no hedge instruction
+    NoInstruction = 2,
+    /// The instruction has expired
+    Expired = 3,
+    /// The instruction has been executed `max_executions` times
+    ExecutionLimitReached = 4,
+    /// The cooldown since the last execution has not elapsed
+    CooldownActive = 5,
+    /// The health factor is not below the trigger
+    NotTriggered = 6,
+    /// The AMM's slippage exceeds the instruction's bound, or the swap lost more
+    SlippageExceeded = 7,
+    /// No enabled AMM protocol supports the pair
+    UnsupportedPair = 8,
+    /// The protocol does not hold enough of the hedge asset to front
+    InsufficientLiquidity = 9,
+    /// The user has no collateral or debt to hedge
+    NothingToHedge = 10,
+    /// Withdrawing the collateral failed
+    WithdrawFailed = 11,
+    /// Repaying the debt failed
+    RepayFailed = 12,
+    /// The AMM rejected the swap
+    SwapFailed = 13,
+    /// The native asset address is not configured
+    InvalidAsset = 14,
+    /// Overflow occurred during calculation
+    Overflow = 15,
+}
+
+/// Storage keys for hedging hooks
+#[contracttype]
+#[derive(Clone)]
+pub enum HedgeDataKey {
+    /// A user's hedge instruction and execution state
+    /// Value type: HedgeHook
+    Hook(Address),
+}
+
+/// Hedge a user signs for keepers to execute
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HedgeInstruction {
+    /// Collateral asset sold (None for native XLM)
+    pub collateral_asset: Option<Address>,
+    /// Asset bought and repaid; must differ from the collateral asset
+    pub hedge_asset: Option<Address>,
+    /// Execute while the health factor is below this (above `HEALTH_FACTOR_SCALE`)
+    pub trigger_health_factor: i128,
+    /// Share of the collateral sold per execution, in basis points
+    pub hedge_bps: i128,
+    /// Largest swap loss accepted, in basis points
+    pub max_slippage_bps: i128,
+    /// Minimum seconds between executions
+    pub cooldown: u64,
+    /// Maximum number of executions
+    pub max_executions: u32,
+    /// Timestamp after which the instruction can no longer execute
+    pub expires_at: u64,
+}
+
+/// A hedge instruction and its execution state
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HedgeHook {
+    pub instruction: HedgeInstruction,
+    pub executions: u32,
+    /// Timestamp of the last execution (0 if never executed)
+    pub last_executed: u64,
+}
+
+/// Outcome of a hedge execution
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HedgeResult {
+    /// Collateral withdrawn and sold
+    pub collateral_sold: i128,
+    /// Hedge asset received from the swap
+    pub proceeds: i128,
+    /// Debt repaid with the proceeds
+    pub debt_repaid: i128,
+    pub health_before: i128,
+    pub health_after: i128,
+}
+
+/// Get a user's hedge instruction and execution state
+pub fn get_hedge_hook(env: &Env, user: &Address) -> Option<HedgeHook> {
+    env.storage()
+        .persistent()
+        .get(&HedgeDataKey::Hook(user.clone()))
+}
+
+/// Set (or replace) the caller's hedge instruction
+///
+/// Replacing an instruction resets its execution count.
+///
+/// # Errors
+/// * `InvalidInstruction` - Same assets, trigger at or below
+///   `HEALTH_FACTOR_SCALE`, share or slippage out of range, no executions
+///   allowed, or already expired
+pub fn set_hedge_instruction(
+    env: &Env,
+    user: Address,
+    instruction: HedgeInstruction,
+) -> Result<(), HedgeError> {
+    user.require_auth();
+    if instruction.collateral_asset == instruction.hedge_asset
+        || instruction.trigger_health_factor <= HEALTH_FACTOR_SCALE
+        || instruction.hedge_bps <= 0
+        || instruction.hedge_bps > BPS_SCALE
+        || !(0..BPS_SCALE).contains(&instruction.max_slippage_bps)
+        || instruction.max_executions == 0
+        || instruction.expires_at <= env.ledger().timestamp()
+    {
+        return Err(HedgeError::InvalidInstruction);
+    }
+    env.storage().persistent().set(
+        &HedgeDataKey::Hook(user),
+        &HedgeHook {
+            instruction,
+            executions: 0,
+            last_executed: 0,
+        },
+    );
+    Ok(())
+}
+
+/// Remove the caller's hedge instruction
+pub fn cancel_hedge_instruction(env: &Env, user: Address) -> Result<(), HedgeError> {
+    user.require_auth();
+    let key = HedgeDataKey::Hook(user);
+    if !env.storage().persistent().has(&key) {
+        return Err(HedgeError::NoInstruction);
+    }
+    env.storage().persistent().remove(&key);
+    Ok(())
+}
+
+/// Execute a user's hedge instruction (keeper)
+///
+/// # Errors
+/// * `NoInstruction` / `Expired` / `ExecutionLimitReached` / `CooldownActive`
+///   - The instruction cannot execute now
+/// * `NotTriggered` - The health factor is at or above the trigger
+/// * `SlippageExceeded` / `UnsupportedPair` / `SwapFailed` - The swap failed
+/// * `InsufficientLiquidity` - The protocol cannot front the repayment
+/// * `WithdrawFailed` / `RepayFailed` - A step failed
+pub fn execute_hedge(env: &Env, keeper: Address, user: Address) -> Result<HedgeResult, HedgeError> {
+    keeper.require_auth();
+    let mut hook = get_hedge_hook(env, &user).ok_or(HedgeError::NoInstruction)?;
+    let instruction = hook.instruction.clone();
+    let now = env.ledger().timestamp();
+    if now > instruction.expires_at {
+        return Err(HedgeError::Expired);
+    }
+    if hook.executions >= instruction.max_executions {
+        return Err(HedgeError::ExecutionLimitReached);
+    }
+    if hook.executions > 0 && now < hook.last_executed.saturating_add(instruction.cooldown) {
+        return Err(HedgeError::CooldownActive);
+    }
+    let health_before = health_factor(env, &user)?;
+    if health_before >= instruction.trigger_health_factor {
+        return Err(HedgeError::NotTriggered);
+    }
+
+    let (collateral, debt) = position_of(env, &user);
+    let sell = mul_div(collateral, instruction.hedge_bps, BPS_SCALE)?;
+    if sell <= 0 || debt <= 0 {
+        return Err(HedgeError::NothingToHedge);
+    }
+    let slippage = amm_slippage(env, instruction.max_slippage_bps)?;
+    // The withdrawal fee is withheld before the collateral reaches the swap
+    let fee_bps = crate::fees::get_fee_bps(env, FeeKind::Withdrawal, &instruction.collateral_asset)
+        .unwrap_or(0);
+    let swap_in = sell - mul_div(sell, fee_bps, BPS_SCALE)?;
+    let min_out = mul_div(swap_in, BPS_SCALE - instruction.max_slippage_bps, BPS_SCALE)?;
+    let repay = min_out.min(debt);
+    if repay <= 0 {
+        return Err(HedgeError::NothingToHedge);
+    }
+
+    // Front the minimum proceeds and repay with them
+    let this = env.current_contract_address();
+    let hedge_token = token::Client::new(
+        env,
+        &token_address(env, &instruction.hedge_asset).ok_or(HedgeError::InvalidAsset)?,
+    );
+    if hedge_token.balance(&this) < repay {
+        return Err(HedgeError::InsufficientLiquidity);
+    }
+    hedge_token.transfer(&this, &user, &repay);
+    crate::repay::repay_debt(env, user.clone(), instruction.hedge_asset.clone(), repay)
+        .map_err(|_| HedgeError::RepayFailed)?;
+
+    // Sell the collateral share into the hedge asset
+    crate::withdraw::withdraw_collateral(
+        env,
+        user.clone(),
+        instruction.collateral_asset.clone(),
+        sell,
+    )
+    .map_err(|_| HedgeError::WithdrawFailed)?;
+    let proceeds = swap(
+        env,
+        &user,
+        &instruction.collateral_asset,
+        &instruction.hedge_asset,
+        swap_in,
+        min_out,
+        slippage,
+    )?;
+    if proceeds < min_out {
+        return Err(HedgeError::SlippageExceeded);
+    }
+
+    // Take the fronted amount back out of the proceeds
+    hedge_token.transfer_from(&this, &user, &this, &repay);
+
+    hook.executions += 1;
+    hook.last_executed = now;
+    env.storage()
+        .persistent()
+        .set(&HedgeDataKey::Hook(user.clone()), &hook);
+
+    let health_after = health_factor(env, &user)?;
+    emit_hedge_executed(
+        env,
+        HedgeExecutedEvent {
+            user,
+            keeper,
+            collateral_sold: sell,
+            proceeds,
+            debt_repaid: repay,
+            timestamp: now,
+        },
+    );
+
+    Ok(HedgeResult {
+        collateral_sold: sell,
+        proceeds,
+        debt_repaid: repay,
+        health_before,
+        health_after,
+    })
+}
+
+/// Swap collateral into the hedge asset through the first enabled AMM
+/// protocol supporting the pair
+fn swap(
+    env: &Env,
+    user: &Address,
+    token_in: &Option<Address>,
+    token_out: &Option<Address>,
+    amount_in: i128,
+    min_amount_out: i128,
+    slippage: i128,
+) -> Result<i128, HedgeError> {
+    let protocols =
+        stellarlend_amm::amm::get_amm_protocols(env).map_err(|_| HedgeError::SwapFailed)?;
+    let protocol = protocols
+        .iter()
+        .find(|(_, config)| {
+            config.enabled
+                && config.supported_pairs.iter().any(|pair| {
+                    (pair.token_a == *token_in && pair.token_b == *token_out)
+                        || (pair.token_a == *token_out && pair.token_b == *token_in)
+                })
+        })
+        .map(|(protocol, _)| protocol)
+        .ok_or(HedgeError::UnsupportedPair)?;
+    let params = SwapParams {
+        protocol,
+        token_in: token_in.clone(),
+        token_out: token_out.clone(),
+        amount_in,
+        min_amount_out,
+        slippage_tolerance: slippage,
+        deadline: env.ledger().timestamp() + SWAP_DEADLINE_SECONDS,
+    };
+    crate::amm::swap(env, user.clone(), params).map_err(|_| HedgeError::SwapFailed)
+}
+
+/// Slippage the AMM applies, rejected when it exceeds the instruction's bound
+fn amm_slippage(env: &Env, max_slippage_bps: i128) -> Result<i128, HedgeError> {
+    let settings =
+        stellarlend_amm::amm::get_amm_settings(env).map_err(|_| HedgeError::SwapFailed)?;
+    if settings.default_slippage > max_slippage_bps {
+        return Err(HedgeError::SlippageExceeded);
+    }
+    Ok(settings.default_slippage)
+}
+
+/// Collateral balance and debt (principal plus interest) of a user
+fn position_of(env: &Env, user: &Address) -> (i128, i128) {
+    let collateral = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, i128>(&DepositDataKey::CollateralBalance(user.clone()))
+        .unwrap_or(0);
+    let debt = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, Position>(&DepositDataKey::Position(user.clone()))
+        .map(|p| p.debt.saturating_add(p.borrow_interest))
+        .unwrap_or(0);
+    (collateral, debt)
+}
+
+/// Health factor of a user's position at face value
+fn health_factor(env: &Env, user: &Address) -> Result<i128, HedgeError> {
+    let (collateral, debt) = position_of(env, user);
+    calculate_health_factor(env, collateral, debt).map_err(|_| HedgeError::Overflow)
+}
+
+fn mul_div(a: i128, b: i128, c: i128) -> Result<i128, HedgeError> {
+    a.checked_mul(b)
+        .ok_or(HedgeError::Overflow)?
+        .checked_div(c)
+        .ok_or(HedgeError::Overflow)
+}
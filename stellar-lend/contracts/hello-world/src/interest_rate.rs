@@ -17,14 +17,61 @@
 //! - Rate floor: 0.5%, Rate ceiling: 100%
 //! - Spread: 2%
 //!
+//! ## Rate Curves
+//! Each asset can select a [`RateCurve`] for its market, overriding the
+//! kinked cross-asset [`AssetRateModel`](crate::cross_asset::AssetRateModel):
+//! - **Linear**: `rate = base_rate + utilization * slope`
+//! - **Kinked**: the piecewise linear model above, without floor, ceiling or
+//!   spread
+//! - **Two-slope**: the kinked shape with a kink that drifts by
+//!   `kink_speed_bps` per day between `min_kink_bps` and `max_kink_bps`:
+//!   down while utilization is above it (steepening rates until borrowers
+//!   repay) and up while utilization is below it. A newly selected curve
+//!   starts at the midpoint of its range.
+//! - **Fixed**: the same rate at any utilization, for fixed-rate stablecoin
+//!   markets
+//!
+//! Every curve is evaluated by [`calculate_rate`] and validated by
+//! [`validate_rate_curve`]; the core model goes through the same kinked
+//! math.
+//!
+//! ## Rate Projections
+//! [`project_rates`] evaluates the model of an asset at hypothetical
+//! utilizations without touching pool state, so governance UIs can chart the
+//! full borrow and supply curves of a configuration before it is voted on.
+//!
 //! ## Emergency Adjustment
 //! Admin can apply a positive or negative emergency adjustment to the calculated rate,
 //! bounded to ±100%.
+//!
+//! ## User Class Rate Caps
+//! Admin can assign borrowers to a user class (e.g. RWA-permissioned borrowers)
+//! and cap the borrow APR for that class. At accrual, capped borrowers pay at
+//! most the class cap; the difference to the market rate is drawn from the
+//! treasury subsidy budget (see `reserve.rs`). When the budget runs out the
+//! uncovered portion is charged at the market rate.
+//!
+//! ## Borrow Index
+//! Interest compounds into a global borrow index (scaled by 1e18) that is
+//! advanced at the current borrow rate on every state-changing call, before
+//! utilization or rate parameters change. Each borrower stores the index at
+//! their last accrual; their interest since then is
+//! `debt * (index / snapshot - 1)`. Positions without a snapshot (opened
+//! before the index existed) accrue once by elapsed time and are then
+//! snapshotted.
+//!
+//! ## Interest Accrued Events
+//! Every accrual on a borrower's position is added to a per-user checkpoint.
+//! When a position is touched and at least `INTEREST_EVENT_INTERVAL` seconds
+//! have passed since the last `interest_accrued` event, the pending amount is
+//! emitted with the principal and current borrow rate. Amounts are never
+//! dropped, so summing the events reproduces the interest charged.
 
 #![allow(unused)]
-use soroban_sdk::{contracterror, contracttype, Address, Env, IntoVal};
+use soroban_sdk::{contracterror, contracttype, Address, Env, IntoVal, Symbol, Vec};
 
-use crate::deposit::{DepositDataKey, ProtocolAnalytics};
+use crate::deposit::{DepositDataKey, Position, ProtocolAnalytics};
+use crate::events::{emit_interest_accrued, InterestAccruedEvent};
 
 /// Errors that can occur during interest rate operations
 #[contracterror]
@@ -58,6 +105,115 @@ pub enum InterestRateDataKey {
     Admin,
     /// Placeholder for emergency rate adjustment status
     EmergencyRateAdjustment,
+    /// User class assigned to a borrower
+    /// Value type: Symbol
+    UserClass(Address),
+    /// Borrow APR cap for a user class (in basis points)
+    /// Value type: i128
+    ClassRateCap(Symbol),
+    /// Interest accrued since the last `interest_accrued` event for a user
+    /// Value type: InterestCheckpoint
+    InterestCheckpoint(Address),
+    /// Global borrow index and the time it was last advanced
+    /// Value type: BorrowIndex
+    BorrowIndex,
+    /// Borrow index at a borrower's last accrual
+    /// Value type: i128
+    UserBorrowIndex(Address),
+    /// Rate curve selected for an asset's market
+    /// Value type: RateCurve
+    AssetRateCurve(Option<Address>),
+    /// Current kink of an asset's two-slope curve
+    /// Value type: DynamicKink
+    DynamicKink(Option<Address>),
+}
+
+/// Borrow rate curve an asset's market can select
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RateCurve {
+    /// Rate grows linearly with utilization
+    Linear(LinearCurve),
+    /// Piecewise linear rate with a fixed kink
+    Kinked(KinkedCurve),
+    /// Piecewise linear rate with a kink that drifts against utilization
+    TwoSlope(TwoSlopeCurve),
+    /// Annual rate (basis points) at any utilization
+    Fixed(i128),
+}
+
+/// `rate = base_rate + utilization * slope / 10000`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LinearCurve {
+    /// Annual rate at 0% utilization (basis points)
+    pub base_rate_bps: i128,
+    /// Annual rate added between 0% and 100% utilization (basis points)
+    pub slope_bps: i128,
+}
+
+/// Kinked curve, as in the core model
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KinkedCurve {
+    /// Annual rate at 0% utilization (basis points)
+    pub base_rate_bps: i128,
+    /// Utilization where the jump multiplier takes over (basis points)
+    pub kink_utilization_bps: i128,
+    /// Annual rate added between 0% utilization and the kink (basis points)
+    pub multiplier_bps: i128,
+    /// Annual rate added between the kink and 100% utilization (basis points)
+    pub jump_multiplier_bps: i128,
+}
+
+/// Kinked curve whose kink drifts against utilization
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TwoSlopeCurve {
+    /// Annual rate at 0% utilization (basis points)
+    pub base_rate_bps: i128,
+    /// Annual rate added between 0% utilization and the kink (basis points)
+    pub slope1_bps: i128,
+    /// Annual rate added between the kink and 100% utilization (basis points)
+    pub slope2_bps: i128,
+    /// Lowest kink (basis points of utilization)
+    pub min_kink_bps: i128,
+    /// Highest kink (basis points of utilization)
+    pub max_kink_bps: i128,
+    /// Largest kink move per day (basis points of utilization)
+    pub kink_speed_bps: i128,
+}
+
+/// Current kink of a two-slope curve
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DynamicKink {
+    /// Kink (basis points of utilization)
+    pub kink_bps: i128,
+    /// Timestamp the kink was last moved
+    pub last_update: u64,
+}
+
+/// Global borrow index state
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BorrowIndex {
+    /// Cumulative borrow growth factor, scaled by `BORROW_INDEX_SCALE`
+    pub index: i128,
+    /// Timestamp the index was last advanced
+    pub last_update: u64,
+}
+
+/// Interest accrued by a borrower since the last `interest_accrued` event
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct InterestCheckpoint {
+    /// Start of the period covered by `pending`
+    pub period_start: u64,
+    /// Interest accrued but not yet reported
+    pub pending: i128,
+    /// Timestamp of the last emitted event (0 if none)
+    pub last_emitted: u64,
 }
 
 /// Interest rate configuration parameters
@@ -90,9 +246,45 @@ pub struct InterestRateConfig {
     pub last_update: u64,
 }
 
+/// Changes to the interest rate model; `None` keeps the current value
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct InterestRateConfigUpdate {
+    pub base_rate_bps: Option<i128>,
+    pub kink_utilization_bps: Option<i128>,
+    pub multiplier_bps: Option<i128>,
+    pub jump_multiplier_bps: Option<i128>,
+    pub rate_floor_bps: Option<i128>,
+    pub rate_ceiling_bps: Option<i128>,
+    pub spread_bps: Option<i128>,
+}
+
+/// Modelled rates at a hypothetical utilization
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RateProjection {
+    /// Hypothetical utilization (basis points)
+    pub utilization_bps: i128,
+    /// Annual borrow rate at that utilization (basis points)
+    pub borrow_rate_bps: i128,
+    /// Annual supply rate at that utilization (basis points)
+    pub supply_rate_bps: i128,
+}
+
+/// Highest rate or slope a rate curve can use (basis points, 1000% a year)
+pub const MAX_CURVE_RATE_BPS: i128 = 100_000;
+
+/// Maximum number of utilizations `project_rates` evaluates per call
+pub const MAX_RATE_PROJECTION_POINTS: u32 = 101;
+
 /// Constants for validation
-const BASIS_POINTS_SCALE: i128 = 10_000; // 100% = 10,000 basis points
-const SECONDS_PER_YEAR: u64 = 365 * 86400; // 31,536,000 seconds
+const BASIS_POINTS_SCALE: i128 = crate::constants::BPS_SCALE;
+
+/// Scale of the borrow index (1.0 = 1e18)
+pub const BORROW_INDEX_SCALE: i128 = 1_000_000_000_000_000_000;
+
+/// Minimum interval between `interest_accrued` events per user (1 day)
+pub const INTEREST_EVENT_INTERVAL: u64 = crate::constants::SECONDS_PER_DAY;
 
 /// Default interest rate configuration
 fn get_default_config() -> InterestRateConfig {
@@ -175,50 +367,21 @@ pub fn calculate_utilization(env: &Env) -> Result<i128, InterestRateError> {
 pub fn calculate_borrow_rate(env: &Env) -> Result<i128, InterestRateError> {
     let config = get_interest_rate_config(env).ok_or(InterestRateError::InvalidParameter)?;
     let utilization = calculate_utilization(env)?;
+    borrow_rate_at(&config, utilization)
+}
 
-    let mut rate = config.base_rate_bps;
-
-    if utilization <= config.kink_utilization_bps {
-        // Below kink: linear increase
-        if config.kink_utilization_bps > 0 {
-            let rate_increase = utilization
-                .checked_mul(config.multiplier_bps)
-                .ok_or(InterestRateError::Overflow)?
-                .checked_div(config.kink_utilization_bps)
-                .ok_or(InterestRateError::DivisionByZero)?;
-            rate = rate
-                .checked_add(rate_increase)
-                .ok_or(InterestRateError::Overflow)?;
-        }
-    } else {
-        // Above kink: steeper increase
-        let rate_at_kink = config
-            .base_rate_bps
-            .checked_add(config.multiplier_bps)
-            .ok_or(InterestRateError::Overflow)?;
-
-        let utilization_above_kink = utilization
-            .checked_sub(config.kink_utilization_bps)
-            .ok_or(InterestRateError::Overflow)?;
-
-        let max_utilization_above_kink = BASIS_POINTS_SCALE
-            .checked_sub(config.kink_utilization_bps)
-            .ok_or(InterestRateError::Overflow)?;
-
-        if max_utilization_above_kink > 0 {
-            let additional_rate = utilization_above_kink
-                .checked_mul(config.jump_multiplier_bps)
-                .ok_or(InterestRateError::Overflow)?
-                .checked_div(max_utilization_above_kink)
-                .ok_or(InterestRateError::DivisionByZero)?;
-
-            rate = rate_at_kink
-                .checked_add(additional_rate)
-                .ok_or(InterestRateError::Overflow)?;
-        } else {
-            rate = rate_at_kink;
-        }
-    }
+/// Borrow rate of `config` at a given utilization
+fn borrow_rate_at(
+    config: &InterestRateConfig,
+    utilization: i128,
+) -> Result<i128, InterestRateError> {
+    let mut rate = kinked_rate(
+        config.base_rate_bps,
+        config.kink_utilization_bps,
+        config.multiplier_bps,
+        config.jump_multiplier_bps,
+        utilization,
+    )?;
 
     // Apply emergency adjustment
     rate = rate
@@ -231,12 +394,280 @@ pub fn calculate_borrow_rate(env: &Env) -> Result<i128, InterestRateError> {
     Ok(rate)
 }
 
+/// Piecewise linear rate shared by the core model and kinked curves
+///
+/// Below kink: `base + utilization / kink * multiplier`
+/// Above kink: `base + multiplier + (utilization - kink) / (10000 - kink) * jump_multiplier`
+fn kinked_rate(
+    base_rate_bps: i128,
+    kink_bps: i128,
+    multiplier_bps: i128,
+    jump_multiplier_bps: i128,
+    utilization: i128,
+) -> Result<i128, InterestRateError> {
+    if utilization <= kink_bps {
+        // Below kink: linear increase
+        if kink_bps <= 0 {
+            return Ok(base_rate_bps);
+        }
+        let rate_increase = utilization
+            .checked_mul(multiplier_bps)
+            .ok_or(InterestRateError::Overflow)?
+            .checked_div(kink_bps)
+            .ok_or(InterestRateError::DivisionByZero)?;
+        return base_rate_bps
+            .checked_add(rate_increase)
+            .ok_or(InterestRateError::Overflow);
+    }
+
+    // Above kink: steeper increase
+    let rate_at_kink = base_rate_bps
+        .checked_add(multiplier_bps)
+        .ok_or(InterestRateError::Overflow)?;
+    let utilization_above_kink = utilization
+        .checked_sub(kink_bps)
+        .ok_or(InterestRateError::Overflow)?;
+    let max_utilization_above_kink = BASIS_POINTS_SCALE
+        .checked_sub(kink_bps)
+        .ok_or(InterestRateError::Overflow)?;
+    if max_utilization_above_kink <= 0 {
+        return Ok(rate_at_kink);
+    }
+    let additional_rate = utilization_above_kink
+        .checked_mul(jump_multiplier_bps)
+        .ok_or(InterestRateError::Overflow)?
+        .checked_div(max_utilization_above_kink)
+        .ok_or(InterestRateError::DivisionByZero)?;
+    rate_at_kink
+        .checked_add(additional_rate)
+        .ok_or(InterestRateError::Overflow)
+}
+
+/// Borrow rate of an asset's curve at the asset's current utilization
+///
+/// Two-slope curves are evaluated at their kink drifted up to now; see
+/// [`checkpoint_dynamic_kink`] to persist it.
+pub fn calculate_rate(
+    env: &Env,
+    asset: &Option<Address>,
+    curve: &RateCurve,
+    utilization: i128,
+) -> Result<i128, InterestRateError> {
+    let kink_bps = match curve {
+        RateCurve::TwoSlope(two_slope) => {
+            current_dynamic_kink(env, asset, two_slope, utilization)?.kink_bps
+        }
+        _ => 0,
+    };
+    curve_rate(curve, utilization, kink_bps)
+}
+
+/// Borrow rate of `curve` at a utilization, with `kink_bps` as the kink of a
+/// two-slope curve
+fn curve_rate(
+    curve: &RateCurve,
+    utilization: i128,
+    kink_bps: i128,
+) -> Result<i128, InterestRateError> {
+    let utilization = utilization.clamp(0, BASIS_POINTS_SCALE);
+    match curve {
+        RateCurve::Linear(linear) => utilization
+            .checked_mul(linear.slope_bps)
+            .map(|increase| increase / BASIS_POINTS_SCALE)
+            .and_then(|increase| linear.base_rate_bps.checked_add(increase))
+            .ok_or(InterestRateError::Overflow),
+        RateCurve::Kinked(kinked) => kinked_rate(
+            kinked.base_rate_bps,
+            kinked.kink_utilization_bps,
+            kinked.multiplier_bps,
+            kinked.jump_multiplier_bps,
+            utilization,
+        ),
+        RateCurve::TwoSlope(two_slope) => kinked_rate(
+            two_slope.base_rate_bps,
+            kink_bps,
+            two_slope.slope1_bps,
+            two_slope.slope2_bps,
+            utilization,
+        ),
+        RateCurve::Fixed(rate_bps) => Ok(*rate_bps),
+    }
+}
+
+/// Check a rate curve's parameters against their ranges
+///
+/// Rates and slopes must lie in `0..=MAX_CURVE_RATE_BPS`; a kinked curve's
+/// kink in `(0, 10000]`; a two-slope curve's kink range in `(0, 10000)` with
+/// `min_kink_bps <= max_kink_bps` and a speed in `0..=10000`.
+pub fn validate_rate_curve(curve: &RateCurve) -> Result<(), InterestRateError> {
+    let rate = |value: i128| (0..=MAX_CURVE_RATE_BPS).contains(&value);
+    let valid = match curve {
+        RateCurve::Linear(linear) => rate(linear.base_rate_bps) && rate(linear.slope_bps),
+        RateCurve::Kinked(kinked) => {
+            rate(kinked.base_rate_bps)
+                && rate(kinked.multiplier_bps)
+                && rate(kinked.jump_multiplier_bps)
+                && kinked.kink_utilization_bps > 0
+                && kinked.kink_utilization_bps <= BASIS_POINTS_SCALE
+        }
+        RateCurve::TwoSlope(two_slope) => {
+            rate(two_slope.base_rate_bps)
+                && rate(two_slope.slope1_bps)
+                && rate(two_slope.slope2_bps)
+                && two_slope.min_kink_bps > 0
+                && two_slope.min_kink_bps <= two_slope.max_kink_bps
+                && two_slope.max_kink_bps < BASIS_POINTS_SCALE
+                && (0..=BASIS_POINTS_SCALE).contains(&two_slope.kink_speed_bps)
+        }
+        RateCurve::Fixed(rate_bps) => rate(*rate_bps),
+    };
+    if !valid {
+        return Err(InterestRateError::InvalidParameter);
+    }
+    Ok(())
+}
+
+/// Select or clear the rate curve of an asset's market (admin only)
+///
+/// Replaces the asset's cross-asset rate model while set. Outstanding
+/// positions are priced with the new curve from their last update onwards,
+/// and a two-slope curve's kink restarts at the midpoint of its range.
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not admin
+/// * `InvalidParameter` - The curve fails [`validate_rate_curve`]
+pub fn set_asset_rate_curve(
+    env: &Env,
+    caller: Address,
+    asset: Option<Address>,
+    curve: Option<RateCurve>,
+) -> Result<(), InterestRateError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| InterestRateError::Unauthorized)?;
+
+    let key = InterestRateDataKey::AssetRateCurve(asset.clone());
+    let kink_key = InterestRateDataKey::DynamicKink(asset.clone());
+    if let Some(curve) = &curve {
+        validate_rate_curve(curve)?;
+        env.storage().persistent().set(&key, curve);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+    // A two-slope curve starts drifting from the midpoint of its range
+    if let Some(RateCurve::TwoSlope(two_slope)) = &curve {
+        let kink = DynamicKink {
+            kink_bps: (two_slope.min_kink_bps + two_slope.max_kink_bps) / 2,
+            last_update: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&kink_key, &kink);
+    } else {
+        env.storage().persistent().remove(&kink_key);
+    }
+
+    let topics = (Symbol::new(env, "rate_curve_updated"), caller);
+    env.events().publish(topics, (asset, curve));
+
+    Ok(())
+}
+
+/// Get the rate curve selected for an asset's market, if any
+pub fn get_asset_rate_curve(env: &Env, asset: &Option<Address>) -> Option<RateCurve> {
+    env.storage()
+        .persistent()
+        .get(&InterestRateDataKey::AssetRateCurve(asset.clone()))
+}
+
+/// Get the curve an asset's market is priced with: its selected curve, else
+/// its cross-asset rate model as a kinked curve
+pub fn get_effective_rate_curve(env: &Env, asset: &Option<Address>) -> Option<RateCurve> {
+    get_asset_rate_curve(env, asset).or_else(|| {
+        crate::cross_asset::get_asset_rate_model(env, asset.clone()).map(|model| {
+            RateCurve::Kinked(KinkedCurve {
+                base_rate_bps: model.base_rate_bps,
+                kink_utilization_bps: model.kink_utilization_bps,
+                multiplier_bps: model.multiplier_bps,
+                jump_multiplier_bps: model.jump_multiplier_bps,
+            })
+        })
+    })
+}
+
+/// Kink of an asset's two-slope curve as last persisted, within the curve's
+/// range (the midpoint before the first checkpoint)
+fn stored_dynamic_kink(env: &Env, asset: &Option<Address>, curve: &TwoSlopeCurve) -> DynamicKink {
+    let mut state = env
+        .storage()
+        .persistent()
+        .get::<InterestRateDataKey, DynamicKink>(&InterestRateDataKey::DynamicKink(asset.clone()))
+        .unwrap_or(DynamicKink {
+            kink_bps: (curve.min_kink_bps + curve.max_kink_bps) / 2,
+            last_update: env.ledger().timestamp(),
+        });
+    state.kink_bps = state.kink_bps.clamp(curve.min_kink_bps, curve.max_kink_bps);
+    state
+}
+
+/// Kink of an asset's two-slope curve, drifted up to now without persisting
+fn current_dynamic_kink(
+    env: &Env,
+    asset: &Option<Address>,
+    curve: &TwoSlopeCurve,
+    utilization: i128,
+) -> Result<DynamicKink, InterestRateError> {
+    let now = env.ledger().timestamp();
+    let mut state = stored_dynamic_kink(env, asset, curve);
+    let mut kink = state.kink_bps;
+
+    if now > state.last_update {
+        let step = curve
+            .kink_speed_bps
+            .checked_mul((now - state.last_update) as i128)
+            .ok_or(InterestRateError::Overflow)?
+            / crate::constants::SECONDS_PER_DAY as i128;
+        if utilization > kink {
+            kink = kink.saturating_sub(step).max(curve.min_kink_bps);
+        } else if utilization < kink {
+            kink = kink.saturating_add(step).min(curve.max_kink_bps);
+        }
+        // Keep accumulating time until it amounts to a whole step
+        if step > 0 || utilization == kink {
+            state.last_update = now;
+        }
+    }
+    state.kink_bps = kink;
+    Ok(state)
+}
+
+/// Persist the drifted kink of an asset's two-slope curve
+///
+/// Call when the asset's interest is settled, before its utilization
+/// changes, so the elapsed drift follows the utilization that held.
+pub fn checkpoint_dynamic_kink(
+    env: &Env,
+    asset: &Option<Address>,
+    utilization: i128,
+) -> Result<(), InterestRateError> {
+    if let Some(RateCurve::TwoSlope(curve)) = get_asset_rate_curve(env, asset) {
+        let state = current_dynamic_kink(env, asset, &curve, utilization)?;
+        env.storage()
+            .persistent()
+            .set(&InterestRateDataKey::DynamicKink(asset.clone()), &state);
+    }
+    Ok(())
+}
+
 /// Calculate supply interest rate
 /// Supply rate = borrow rate - spread
 pub fn calculate_supply_rate(env: &Env) -> Result<i128, InterestRateError> {
     let config = get_interest_rate_config(env).ok_or(InterestRateError::InvalidParameter)?;
     let borrow_rate = calculate_borrow_rate(env)?;
+    supply_rate_at(&config, borrow_rate)
+}
 
+/// Supply rate of `config` for a given borrow rate
+fn supply_rate_at(
+    config: &InterestRateConfig,
+    borrow_rate: i128,
+) -> Result<i128, InterestRateError> {
     // Supply rate = borrow rate - spread
     let supply_rate = borrow_rate
         .checked_sub(config.spread_bps)
@@ -248,6 +679,9 @@ pub fn calculate_supply_rate(env: &Env) -> Result<i128, InterestRateError> {
 
 /// Calculate accrued interest using dynamic rate
 ///
+/// Annualizes with the mainnet year length; use
+/// [`calculate_accrued_interest_for_year`] to honor network overrides.
+///
 /// # Arguments
 /// * `principal` - The principal amount
 /// * `last_accrual_time` - Last time interest was accrued
@@ -261,6 +695,33 @@ pub fn calculate_accrued_interest(
     last_accrual_time: u64,
     current_time: u64,
     rate_bps: i128,
+) -> Result<i128, InterestRateError> {
+    calculate_accrued_interest_for_year(
+        principal,
+        last_accrual_time,
+        current_time,
+        rate_bps,
+        crate::constants::DEFAULT_SECONDS_PER_YEAR,
+    )
+}
+
+/// Calculate accrued interest with an explicit year length
+///
+/// # Arguments
+/// * `principal` - The principal amount
+/// * `last_accrual_time` - Last time interest was accrued
+/// * `current_time` - Current timestamp
+/// * `rate_bps` - Interest rate in basis points (annual)
+/// * `seconds_per_year` - Length of a year in seconds (shorter on accelerated testnets)
+///
+/// # Returns
+/// Accrued interest amount
+pub fn calculate_accrued_interest_for_year(
+    principal: i128,
+    last_accrual_time: u64,
+    current_time: u64,
+    rate_bps: i128,
+    seconds_per_year: u64,
 ) -> Result<i128, InterestRateError> {
     if principal == 0 {
         return Ok(0);
@@ -270,28 +731,18 @@ pub fn calculate_accrued_interest(
         return Ok(0);
     }
 
-    // Calculate time elapsed in seconds
-    let time_elapsed = current_time
-        .checked_sub(last_accrual_time)
-        .ok_or(InterestRateError::Overflow)?;
-
-    // Calculate interest: principal * (rate / 10000) * (time_elapsed / seconds_per_year)
-    // To avoid precision loss: principal * rate * time_elapsed / (10000 * seconds_per_year)
-    let denominator = BASIS_POINTS_SCALE
-        .checked_mul(SECONDS_PER_YEAR as i128)
-        .ok_or(InterestRateError::Overflow)?;
-
-    let numerator = principal
-        .checked_mul(rate_bps)
-        .ok_or(InterestRateError::Overflow)?
-        .checked_mul(time_elapsed as i128)
-        .ok_or(InterestRateError::Overflow)?;
-
-    let interest = numerator
-        .checked_div(denominator)
-        .ok_or(InterestRateError::DivisionByZero)?;
+    if seconds_per_year == 0 {
+        return Err(InterestRateError::DivisionByZero);
+    }
 
-    Ok(interest)
+    // principal * rate * time_elapsed / (10000 * seconds_per_year), shared with the lending contract
+    stellarlend_math::simple_interest(
+        principal,
+        rate_bps,
+        current_time - last_accrual_time,
+        seconds_per_year,
+    )
+    .ok_or(InterestRateError::Overflow)
 }
 
 /// Update interest rate configuration parameters
@@ -321,67 +772,100 @@ pub fn update_interest_rate_config(
     // Check authorization
     crate::admin::require_admin(env, &caller).map_err(|_| InterestRateError::Unauthorized)?;
 
-    let config_key = InterestRateDataKey::InterestRateConfig;
+    apply_interest_rate_config(
+        env,
+        &InterestRateConfigUpdate {
+            base_rate_bps,
+            kink_utilization_bps,
+            multiplier_bps,
+            jump_multiplier_bps,
+            rate_floor_bps,
+            rate_ceiling_bps,
+            spread_bps,
+        },
+    )
+}
+
+/// Update the interest rate model without an authorization check
+///
+/// Shared by [`update_interest_rate_config`] and governance execution.
+/// Settles the borrow index at the old rate before switching.
+pub(crate) fn apply_interest_rate_config(
+    env: &Env,
+    update: &InterestRateConfigUpdate,
+) -> Result<(), InterestRateError> {
+    validate_interest_rate_update(update)?;
     let mut config = get_interest_rate_config(env).ok_or(InterestRateError::InvalidParameter)?;
 
-    // Update parameters with validation
-    if let Some(rate) = base_rate_bps {
-        if !(0..=BASIS_POINTS_SCALE).contains(&rate) {
-            return Err(InterestRateError::InvalidParameter);
-        }
+    // Settle the index at the old rate
+    accrue_borrow_index(env)?;
+
+    if let Some(rate) = update.base_rate_bps {
         config.base_rate_bps = rate;
     }
-
-    if let Some(kink) = kink_utilization_bps {
-        if kink <= 0 || kink >= BASIS_POINTS_SCALE {
-            return Err(InterestRateError::InvalidParameter);
-        }
+    if let Some(kink) = update.kink_utilization_bps {
         config.kink_utilization_bps = kink;
     }
-
-    if let Some(mult) = multiplier_bps {
-        if mult < 0 {
-            return Err(InterestRateError::InvalidParameter);
-        }
+    if let Some(mult) = update.multiplier_bps {
         config.multiplier_bps = mult;
     }
-
-    if let Some(jump) = jump_multiplier_bps {
-        if jump < 0 {
-            return Err(InterestRateError::InvalidParameter);
-        }
+    if let Some(jump) = update.jump_multiplier_bps {
         config.jump_multiplier_bps = jump;
     }
-
-    if let Some(floor) = rate_floor_bps {
-        if !(0..=BASIS_POINTS_SCALE).contains(&floor) {
-            return Err(InterestRateError::InvalidParameter);
-        }
+    if let Some(floor) = update.rate_floor_bps {
         if floor > config.rate_ceiling_bps {
             return Err(InterestRateError::InvalidParameter);
         }
         config.rate_floor_bps = floor;
     }
-
-    if let Some(ceiling) = rate_ceiling_bps {
-        if !(0..=BASIS_POINTS_SCALE).contains(&ceiling) {
-            return Err(InterestRateError::InvalidParameter);
-        }
+    if let Some(ceiling) = update.rate_ceiling_bps {
         if ceiling < config.rate_floor_bps {
             return Err(InterestRateError::InvalidParameter);
         }
         config.rate_ceiling_bps = ceiling;
     }
-
-    if let Some(spread) = spread_bps {
-        if !(0..=BASIS_POINTS_SCALE).contains(&spread) {
-            return Err(InterestRateError::InvalidParameter);
-        }
+    if let Some(spread) = update.spread_bps {
         config.spread_bps = spread;
     }
 
     config.last_update = env.ledger().timestamp();
-    env.storage().persistent().set(&config_key, &config);
+    env.storage()
+        .persistent()
+        .set(&InterestRateDataKey::InterestRateConfig, &config);
+
+    Ok(())
+}
+
+/// Check each provided interest rate parameter against its range
+///
+/// The floor/ceiling ordering against the current model is checked when the
+/// update is applied.
+pub(crate) fn validate_interest_rate_update(
+    update: &InterestRateConfigUpdate,
+) -> Result<(), InterestRateError> {
+    let out_of_bps_range =
+        |value: Option<i128>| matches!(value, Some(v) if !(0..=BASIS_POINTS_SCALE).contains(&v));
+    let negative = |value: Option<i128>| matches!(value, Some(v) if v < 0);
+    let bad_kink = matches!(
+        update.kink_utilization_bps,
+        Some(kink) if kink <= 0 || kink >= BASIS_POINTS_SCALE
+    );
+
+    if out_of_bps_range(update.base_rate_bps)
+        || bad_kink
+        || negative(update.multiplier_bps)
+        || negative(update.jump_multiplier_bps)
+        || out_of_bps_range(update.rate_floor_bps)
+        || out_of_bps_range(update.rate_ceiling_bps)
+        || out_of_bps_range(update.spread_bps)
+    {
+        return Err(InterestRateError::InvalidParameter);
+    }
+    if let (Some(floor), Some(ceiling)) = (update.rate_floor_bps, update.rate_ceiling_bps) {
+        if floor > ceiling {
+            return Err(InterestRateError::InvalidParameter);
+        }
+    }
 
     Ok(())
 }
@@ -408,6 +892,9 @@ pub fn set_emergency_rate_adjustment(
     let config_key = InterestRateDataKey::InterestRateConfig;
     let mut config = get_interest_rate_config(env).ok_or(InterestRateError::InvalidParameter)?;
 
+    // Settle the index at the old rate
+    accrue_borrow_index(env)?;
+
     config.emergency_adjustment_bps = adjustment_bps;
     config.last_update = env.ledger().timestamp();
 
@@ -430,3 +917,497 @@ pub fn get_current_supply_rate(env: &Env) -> Result<i128, InterestRateError> {
 pub fn get_current_utilization(env: &Env) -> Result<i128, InterestRateError> {
     calculate_utilization(env)
 }
+
+/// Model borrow and supply rates for each of `utilizations` (basis points)
+///
+/// Assets with a rate curve (selected, or their cross-asset
+/// [`AssetRateModel`](crate::cross_asset::AssetRateModel)) are projected on
+/// that curve, two-slope curves at their current kink, with suppliers
+/// earning the borrow interest net of the asset's reserve factor. Other
+/// assets use the core rate model,
+/// including its floor, ceiling, emergency adjustment and spread. Nothing is
+/// read from or written to pool balances, so the curve of a configuration can
+/// be charted at any utilization.
+///
+/// # Errors
+/// * `InvalidParameter` - A utilization outside 0..=10000, more than
+///   `MAX_RATE_PROJECTION_POINTS` utilizations, or the rate model is not
+///   initialized
+pub fn project_rates(
+    env: &Env,
+    asset: Option<Address>,
+    utilizations: Vec<i128>,
+) -> Result<Vec<RateProjection>, InterestRateError> {
+    if utilizations.len() > MAX_RATE_PROJECTION_POINTS {
+        return Err(InterestRateError::InvalidParameter);
+    }
+    let config = get_interest_rate_config(env).ok_or(InterestRateError::InvalidParameter)?;
+    let curve = get_effective_rate_curve(env, &asset);
+    let kink_bps = match &curve {
+        Some(RateCurve::TwoSlope(two_slope)) => {
+            stored_dynamic_kink(env, &asset, two_slope).kink_bps
+        }
+        _ => 0,
+    };
+    let reserve_factor = crate::reserve::get_reserve_factor(env, asset);
+
+    let mut projections = Vec::new(env);
+    for utilization in utilizations.iter() {
+        if !(0..=BASIS_POINTS_SCALE).contains(&utilization) {
+            return Err(InterestRateError::InvalidParameter);
+        }
+        let (borrow_rate, supply_rate) = match &curve {
+            Some(curve) => {
+                let borrow_rate = curve_rate(curve, utilization, kink_bps)?;
+                let supply_rate = borrow_rate
+                    .checked_mul(utilization)
+                    .and_then(|rate| rate.checked_mul(BASIS_POINTS_SCALE - reserve_factor))
+                    .ok_or(InterestRateError::Overflow)?
+                    / (BASIS_POINTS_SCALE * BASIS_POINTS_SCALE);
+                (borrow_rate, supply_rate)
+            }
+            None => {
+                let borrow_rate = borrow_rate_at(&config, utilization)?;
+                (borrow_rate, supply_rate_at(&config, borrow_rate)?)
+            }
+        };
+        projections.push_back(RateProjection {
+            utilization_bps: utilization,
+            borrow_rate_bps: borrow_rate,
+            supply_rate_bps: supply_rate,
+        });
+    }
+    Ok(projections)
+}
+
+/// Assign or clear a borrower's user class
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `caller` - The caller address (must be admin)
+/// * `user` - The borrower address
+/// * `class` - The user class (None to clear)
+pub fn set_user_class(
+    env: &Env,
+    caller: Address,
+    user: Address,
+    class: Option<Symbol>,
+) -> Result<(), InterestRateError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| InterestRateError::Unauthorized)?;
+
+    let key = InterestRateDataKey::UserClass(user.clone());
+    match class.clone() {
+        Some(class) => env.storage().persistent().set(&key, &class),
+        None => env.storage().persistent().remove(&key),
+    }
+
+    let topics = (Symbol::new(env, "user_class_updated"), caller);
+    env.events().publish(topics, (user, class));
+
+    Ok(())
+}
+
+/// Set or clear the borrow APR cap for a user class
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `caller` - The caller address (must be admin)
+/// * `class` - The user class
+/// * `cap_bps` - Annual borrow rate cap in basis points (None to clear)
+pub fn set_class_rate_cap(
+    env: &Env,
+    caller: Address,
+    class: Symbol,
+    cap_bps: Option<i128>,
+) -> Result<(), InterestRateError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| InterestRateError::Unauthorized)?;
+
+    let key = InterestRateDataKey::ClassRateCap(class.clone());
+    match cap_bps {
+        Some(cap) => {
+            if !(0..=BASIS_POINTS_SCALE).contains(&cap) {
+                return Err(InterestRateError::InvalidParameter);
+            }
+            env.storage().persistent().set(&key, &cap);
+        }
+        None => env.storage().persistent().remove(&key),
+    }
+
+    let topics = (Symbol::new(env, "class_rate_cap_updated"), caller);
+    env.events().publish(topics, (class, cap_bps));
+
+    Ok(())
+}
+
+/// Get the user class assigned to a borrower
+pub fn get_user_class(env: &Env, user: &Address) -> Option<Symbol> {
+    env.storage()
+        .persistent()
+        .get(&InterestRateDataKey::UserClass(user.clone()))
+}
+
+/// Get the borrow APR cap for a user class (in basis points)
+pub fn get_class_rate_cap(env: &Env, class: &Symbol) -> Option<i128> {
+    env.storage()
+        .persistent()
+        .get(&InterestRateDataKey::ClassRateCap(class.clone()))
+}
+
+/// Get the borrow APR cap that applies to a borrower, if any
+pub fn get_user_rate_cap(env: &Env, user: &Address) -> Option<i128> {
+    get_user_class(env, user).and_then(|class| get_class_rate_cap(env, &class))
+}
+
+/// Calculate interest accrued by a borrower, honoring user class rate caps
+///
+/// Interest on the variable share of the principal is first computed at the
+/// market borrow rate. If the borrower's class cap is lower, the difference
+/// is drawn from the debt asset's subsidy budget and only the remainder is
+/// charged to the borrower. The stable share accrues at its locked rate (see
+/// `stable_rate`). The charged amount is recorded for `interest_accrued`
+/// reporting.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `user` - The borrower address
+/// * `asset` - The debt asset, whose subsidy budget absorbs capped interest
+/// * `principal` - The principal amount
+/// * `last_accrual_time` - Last time interest was accrued
+/// * `current_time` - Current timestamp
+///
+/// # Returns
+/// Interest amount charged to the borrower
+pub fn calculate_user_accrued_interest(
+    env: &Env,
+    user: &Address,
+    asset: &Option<Address>,
+    principal: i128,
+    last_accrual_time: u64,
+    current_time: u64,
+) -> Result<i128, InterestRateError> {
+    let (stable_principal, stable_interest) = crate::stable_rate::stable_interest(
+        env,
+        user,
+        principal,
+        last_accrual_time,
+        current_time,
+        true,
+    )?;
+    let index = accrue_borrow_index(env)?;
+    let (market_rate, market_interest, subsidy) = split_user_interest(
+        env,
+        user,
+        principal - stable_principal,
+        last_accrual_time,
+        current_time,
+        index,
+    )?;
+
+    let covered = crate::reserve::draw_subsidy(env, asset.clone(), subsidy);
+    let charged = market_interest
+        .checked_sub(covered)
+        .and_then(|v| v.checked_add(stable_interest))
+        .ok_or(InterestRateError::Overflow)?;
+
+    record_interest_accrued(
+        env,
+        user,
+        principal,
+        charged,
+        market_rate,
+        last_accrual_time,
+    )?;
+    Ok(charged)
+}
+
+/// Preview the interest a borrower would be charged, without side effects
+///
+/// Mirrors `calculate_user_accrued_interest`, including the class rate cap
+/// and the debt asset's remaining subsidy budget, but draws no subsidy and
+/// records no checkpoint. Use it from views and eligibility checks that do
+/// not persist the accrual.
+pub fn preview_user_accrued_interest(
+    env: &Env,
+    user: &Address,
+    asset: &Option<Address>,
+    principal: i128,
+    last_accrual_time: u64,
+    current_time: u64,
+) -> Result<i128, InterestRateError> {
+    let (stable_principal, stable_interest) = crate::stable_rate::stable_interest(
+        env,
+        user,
+        principal,
+        last_accrual_time,
+        current_time,
+        false,
+    )?;
+    let index = get_borrow_index(env)?;
+    let (_, market_interest, subsidy) = split_user_interest(
+        env,
+        user,
+        principal - stable_principal,
+        last_accrual_time,
+        current_time,
+        index,
+    )?;
+
+    let covered = subsidy
+        .min(crate::reserve::get_subsidy_budget(env, asset.clone()))
+        .max(0);
+    market_interest
+        .checked_sub(covered)
+        .and_then(|v| v.checked_add(stable_interest))
+        .ok_or(InterestRateError::Overflow)
+}
+
+/// Market rate, interest at the market rate, and the share of it above the
+/// borrower's class rate cap (0 if uncapped)
+///
+/// Market interest follows the borrow index from the borrower's snapshot to
+/// `index`, or elapsed time at the current rate if there is no snapshot.
+fn split_user_interest(
+    env: &Env,
+    user: &Address,
+    principal: i128,
+    last_accrual_time: u64,
+    current_time: u64,
+    index: i128,
+) -> Result<(i128, i128, i128), InterestRateError> {
+    let market_rate = calculate_borrow_rate(env)?;
+    let seconds_per_year = crate::constants::seconds_per_year(env);
+
+    if let Some(snapshot) = get_user_borrow_index(env, user) {
+        let market_interest = index_interest(principal, snapshot, index)?;
+        // The cap applies as a share of the market rate over the period
+        let subsidy = match get_user_rate_cap(env, user) {
+            Some(cap) if cap < market_rate && market_rate > 0 => market_interest
+                .checked_mul(market_rate - cap.max(0))
+                .ok_or(InterestRateError::Overflow)?
+                .checked_div(market_rate)
+                .ok_or(InterestRateError::DivisionByZero)?,
+            _ => 0,
+        };
+        return Ok((market_rate, market_interest, subsidy));
+    }
+
+    let market_interest = calculate_accrued_interest_for_year(
+        principal,
+        last_accrual_time,
+        current_time,
+        market_rate,
+        seconds_per_year,
+    )?;
+
+    let subsidy = match get_user_rate_cap(env, user) {
+        Some(cap) if cap < market_rate => {
+            let capped_interest = calculate_accrued_interest_for_year(
+                principal,
+                last_accrual_time,
+                current_time,
+                cap,
+                seconds_per_year,
+            )?;
+            market_interest
+                .checked_sub(capped_interest)
+                .ok_or(InterestRateError::Overflow)?
+        }
+        _ => 0,
+    };
+
+    Ok((market_rate, market_interest, subsidy))
+}
+
+/// Interest on `principal` as the index grows from `from_index` to `to_index`
+fn index_interest(
+    principal: i128,
+    from_index: i128,
+    to_index: i128,
+) -> Result<i128, InterestRateError> {
+    if principal == 0 || to_index <= from_index {
+        return Ok(0);
+    }
+    principal
+        .checked_mul(to_index - from_index)
+        .ok_or(InterestRateError::Overflow)?
+        .checked_div(from_index)
+        .ok_or(InterestRateError::DivisionByZero)
+}
+
+/// Borrow index state advanced to the current ledger time without persisting
+///
+/// The index grows by `rate * elapsed / seconds_per_year` at the current
+/// borrow rate. Without a rate configuration the index does not grow.
+fn current_borrow_index(env: &Env) -> Result<BorrowIndex, InterestRateError> {
+    let now = env.ledger().timestamp();
+    let mut state = env
+        .storage()
+        .persistent()
+        .get::<InterestRateDataKey, BorrowIndex>(&InterestRateDataKey::BorrowIndex)
+        .unwrap_or(BorrowIndex {
+            index: BORROW_INDEX_SCALE,
+            last_update: now,
+        });
+
+    if now <= state.last_update || get_interest_rate_config(env).is_none() {
+        state.last_update = state.last_update.max(now);
+        return Ok(state);
+    }
+
+    let growth = calculate_accrued_interest_for_year(
+        state.index,
+        state.last_update,
+        now,
+        calculate_borrow_rate(env)?,
+        crate::constants::seconds_per_year(env),
+    )?;
+    state.index = state
+        .index
+        .checked_add(growth)
+        .ok_or(InterestRateError::Overflow)?;
+    state.last_update = now;
+    Ok(state)
+}
+
+/// Get the global borrow index at the current ledger time
+pub fn get_borrow_index(env: &Env) -> Result<i128, InterestRateError> {
+    Ok(current_borrow_index(env)?.index)
+}
+
+/// Advance and persist the global borrow index
+///
+/// Call before anything that changes utilization or the rate configuration,
+/// so elapsed time is charged at the rate that was in effect.
+///
+/// # Returns
+/// The current borrow index
+pub fn accrue_borrow_index(env: &Env) -> Result<i128, InterestRateError> {
+    let state = current_borrow_index(env)?;
+    env.storage()
+        .persistent()
+        .set(&InterestRateDataKey::BorrowIndex, &state);
+    Ok(state.index)
+}
+
+/// Get the borrow index at a borrower's last accrual
+pub fn get_user_borrow_index(env: &Env, user: &Address) -> Option<i128> {
+    env.storage()
+        .persistent()
+        .get::<InterestRateDataKey, i128>(&InterestRateDataKey::UserBorrowIndex(user.clone()))
+}
+
+/// Snapshot the current borrow index for a borrower
+///
+/// Called after a position's interest has been accrued, so the next accrual
+/// starts from here.
+pub fn checkpoint_user_borrow_index(env: &Env, user: &Address) -> Result<(), InterestRateError> {
+    let index = accrue_borrow_index(env)?;
+    env.storage()
+        .persistent()
+        .set(&InterestRateDataKey::UserBorrowIndex(user.clone()), &index);
+    Ok(())
+}
+
+/// Get a borrower's debt including interest accrued up to now
+///
+/// Principal plus recorded interest plus the pending interest since the last
+/// accrual, net of any class rate cap subsidy from the native budget (legacy
+/// positions are valued in the native asset). Nothing is persisted.
+pub fn get_accrued_debt(env: &Env, user: &Address) -> Result<i128, InterestRateError> {
+    let Some(position) = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, Position>(&DepositDataKey::Position(user.clone()))
+    else {
+        return Ok(0);
+    };
+
+    let pending_interest = if position.debt > 0 {
+        preview_user_accrued_interest(
+            env,
+            user,
+            &None,
+            position.debt,
+            position.last_accrual_time,
+            env.ledger().timestamp(),
+        )?
+    } else {
+        0
+    };
+    position
+        .debt
+        .checked_add(position.borrow_interest)
+        .and_then(|d| d.checked_add(pending_interest))
+        .ok_or(InterestRateError::Overflow)
+}
+
+/// Add accrued interest to a borrower's checkpoint and emit an
+/// `interest_accrued` event if the reporting interval has elapsed
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `user` - The borrower address
+/// * `principal` - Principal the interest accrued on
+/// * `accrued` - Interest charged in this accrual
+/// * `rate_bps` - Current borrow rate in basis points
+/// * `accrued_since` - Start of this accrual, used to open a new reporting period
+pub fn record_interest_accrued(
+    env: &Env,
+    user: &Address,
+    principal: i128,
+    accrued: i128,
+    rate_bps: i128,
+    accrued_since: u64,
+) -> Result<(), InterestRateError> {
+    let now = env.ledger().timestamp();
+    let key = InterestRateDataKey::InterestCheckpoint(user.clone());
+    let mut checkpoint = get_interest_checkpoint(env, user);
+
+    if checkpoint.last_emitted == 0 && checkpoint.pending == 0 {
+        checkpoint.period_start = accrued_since;
+    }
+
+    checkpoint.pending = checkpoint
+        .pending
+        .checked_add(accrued)
+        .ok_or(InterestRateError::Overflow)?;
+
+    let interval_elapsed = checkpoint.last_emitted == 0
+        || now.saturating_sub(checkpoint.last_emitted) >= INTEREST_EVENT_INTERVAL;
+
+    if interval_elapsed && checkpoint.pending > 0 {
+        emit_interest_accrued(
+            env,
+            InterestAccruedEvent {
+                user: user.clone(),
+                principal,
+                accrued: checkpoint.pending,
+                borrow_rate_bps: rate_bps,
+                period_start: checkpoint.period_start,
+                timestamp: now,
+            },
+        );
+        checkpoint = InterestCheckpoint {
+            period_start: now,
+            pending: 0,
+            last_emitted: now,
+        };
+    }
+
+    env.storage().persistent().set(&key, &checkpoint);
+    Ok(())
+}
+
+/// Get a borrower's interest reporting checkpoint
+pub fn get_interest_checkpoint(env: &Env, user: &Address) -> InterestCheckpoint {
+    env.storage()
+        .persistent()
+        .get::<InterestRateDataKey, InterestCheckpoint>(&InterestRateDataKey::InterestCheckpoint(
+            user.clone(),
+        ))
+        .unwrap_or(InterestCheckpoint {
+            period_start: 0,
+            pending: 0,
+            last_emitted: 0,
+        })
+}
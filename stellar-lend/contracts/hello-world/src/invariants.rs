@@ -0,0 +1,155 @@
+//! # Protocol Invariants
+//!
+//! Recomputes the protocol's aggregate accounting from its parts so
+//! watchdogs can detect drift with a single call. Anyone may run the check;
+//! it only reads state and emits an [`InvariantReportEvent`].
+//!
+//! Checked per market (every listed asset and every asset with a recorded
+//! total supply or total borrows):
+//! - Total supply covers the collateral of cross-asset positions (the
+//!   remainder is core collateral, which is not tracked per asset).
+//! - Total borrows cover the principal and settled interest of cross-asset
+//!   positions.
+//! - The protocol reserve balance is not negative.
+//!
+//! Checked across markets: total borrows equal the debt of all cross-asset
+//! positions plus the debt principal of core borrowers. Core borrowers are
+//! read from the liquidation borrower index, so the sum is only checked
+//! while the index holds at most [`MAX_INVARIANT_BORROWERS`] borrowers;
+//! `debt_checked` in the report says whether it ran.
+
+use crate::cross_asset::{get_asset_list, get_position_totals, get_recorded_totals, AssetKey};
+use crate::deposit::DepositDataKey;
+use crate::events::{emit_invariant_report, InvariantReportEvent};
+use crate::liquidate::{get_borrower_count, get_indexed_debt};
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+/// Largest borrower index the cross-market debt sum is checked against
+pub const MAX_INVARIANT_BORROWERS: u32 = 200;
+
+/// Recorded totals of one market against the positions in it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MarketInvariant {
+    pub asset: Option<Address>,
+    pub total_supply: i128,
+    /// Collateral of cross-asset positions
+    pub position_collateral: i128,
+    pub total_borrows: i128,
+    /// Principal and settled interest of cross-asset positions
+    pub position_debt: i128,
+    /// Protocol reserve balance
+    pub reserve_balance: i128,
+}
+
+/// Invariant that failed
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum InvariantViolation {
+    /// Total supply is below the collateral of cross-asset positions
+    SupplyBelowCollateral(Option<Address>),
+    /// Total borrows are below the debt of cross-asset positions
+    BorrowsBelowDebt(Option<Address>),
+    /// Total borrows across markets differ from the debt of all positions
+    DebtMismatch,
+    /// Protocol reserve balance is negative
+    NegativeReserve(Option<Address>),
+}
+
+/// Result of an invariant check
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvariantReport {
+    pub markets: Vec<MarketInvariant>,
+    /// Total borrows across markets
+    pub total_borrows: i128,
+    /// Debt principal of core borrowers in the borrower index
+    pub core_debt: i128,
+    pub borrowers_checked: u32,
+    /// Whether the cross-market debt sum was checked
+    pub debt_checked: bool,
+    pub violations: Vec<InvariantViolation>,
+    /// True when no invariant failed
+    pub holds: bool,
+    pub checked_at: u64,
+}
+
+/// Recompute the aggregate sums, emit the report and return it
+pub fn assert_invariants(env: &Env) -> InvariantReport {
+    let (supplies, borrows) = get_recorded_totals(env);
+    let position_totals = get_position_totals(env);
+
+    let mut keys: Vec<AssetKey> = get_asset_list(env);
+    for key in supplies.keys().iter().chain(borrows.keys().iter()) {
+        if !keys.contains(&key) {
+            keys.push_back(key);
+        }
+    }
+
+    let mut markets = Vec::new(env);
+    let mut violations = Vec::new(env);
+    let mut total_borrows: i128 = 0;
+    let mut position_debt_total: i128 = 0;
+    for key in keys.iter() {
+        let asset = key.to_option();
+        let (position_collateral, position_debt) =
+            position_totals.get(key.clone()).unwrap_or((0, 0));
+        let market = MarketInvariant {
+            asset: asset.clone(),
+            total_supply: supplies.get(key.clone()).unwrap_or(0),
+            position_collateral,
+            total_borrows: borrows.get(key).unwrap_or(0),
+            position_debt,
+            reserve_balance: env
+                .storage()
+                .persistent()
+                .get(&DepositDataKey::ProtocolReserve(asset.clone()))
+                .unwrap_or(0),
+        };
+
+        if market.total_supply < market.position_collateral {
+            violations.push_back(InvariantViolation::SupplyBelowCollateral(asset.clone()));
+        }
+        if market.total_borrows < market.position_debt {
+            violations.push_back(InvariantViolation::BorrowsBelowDebt(asset.clone()));
+        }
+        if market.reserve_balance < 0 {
+            violations.push_back(InvariantViolation::NegativeReserve(asset));
+        }
+        total_borrows = total_borrows.saturating_add(market.total_borrows);
+        position_debt_total = position_debt_total.saturating_add(market.position_debt);
+        markets.push_back(market);
+    }
+
+    let borrower_count = get_borrower_count(env);
+    let debt_checked = borrower_count <= MAX_INVARIANT_BORROWERS;
+    let (core_debt, borrowers_checked) = if debt_checked {
+        (get_indexed_debt(env, borrower_count), borrower_count)
+    } else {
+        (0, 0)
+    };
+    if debt_checked && total_borrows != position_debt_total.saturating_add(core_debt) {
+        violations.push_back(InvariantViolation::DebtMismatch);
+    }
+
+    let report = InvariantReport {
+        markets,
+        total_borrows,
+        core_debt,
+        borrowers_checked,
+        debt_checked,
+        holds: violations.is_empty(),
+        violations,
+        checked_at: env.ledger().timestamp(),
+    };
+    emit_invariant_report(
+        env,
+        InvariantReportEvent {
+            holds: report.holds,
+            markets: report.markets.len(),
+            violations: report.violations.clone(),
+            timestamp: report.checked_at,
+        },
+    );
+    report
+}
@@ -0,0 +1,326 @@
+//! # Keeper Sponsorship Pool
+//!
+//! Pays whitelisted keepers a fixed amount of native XLM per maintenance
+//! call, so TTL bumps, rate pokes and archive sweeps keep happening without
+//! relying on altruism.
+//!
+//! The treasury (or anyone) tops the pool up with [`fund_keeper_pool`]. Each
+//! sponsored call pays `reward_per_call` while the keeper's payouts for the
+//! current day stay within `daily_cap` and the pool can cover it; otherwise
+//! the call still runs, unpaid. Unsponsored keepers may call the operations
+//! too, they are just never paid.
+//!
+//! ## Sponsored Operations
+//! - [`bump_ttl`] — extends the contract instance and users' position entries
+//! - [`poke_rates`] — advances the global borrow index and refreshes the
+//!   day's metric snapshot
+//! - `archive_idle_positions` — paid when at least one position is archived
+//!
+//! Automation jobs (see `automation.rs`) pay their own per-job reward from
+//! the same pool.
+
+use soroban_sdk::{contracterror, contracttype, token, Address, Env, Symbol, Vec};
+
+use crate::deposit::{get_native_asset_address, DepositDataKey};
+use crate::events::{
+    emit_admin_action, emit_keeper_reimbursed, AdminActionEvent, KeeperReimbursedEvent,
+};
+
+/// Length of the daily cap window
+pub const KEEPER_DAY_SECONDS: u64 = 86_400;
+
+/// Maximum number of users per TTL bump
+pub const MAX_TTL_BUMP_BATCH: u32 = 50;
+
+/// Entries with less TTL left than this (ledgers, ~30 days) are extended
+pub const KEEPER_TTL_THRESHOLD: u32 = 518_400;
+
+/// TTL entries are extended to (ledgers, ~180 days)
+pub const KEEPER_TTL_EXTEND_TO: u32 = 3_110_400;
+
+/// Errors that can occur in keeper pool operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum KeeperPoolError {
+    /// Caller is not the admin
+    Unauthorized = 1,
+    /// Amount is not positive
+    InvalidAmount = 2,
+    /// Reward is not positive or exceeds the daily cap
+    InvalidConfig = 3,
+    /// The native asset address is not configured
+    NativeAssetNotSet = 4,
+    /// More than `MAX_TTL_BUMP_BATCH` users in one bump
+    BatchTooLarge = 5,
+    /// The borrow index could not be advanced
+    RateUpdateFailed = 6,
+    /// Overflow occurred during calculation
+    Overflow = 7,
+}
+
+/// Storage keys for the keeper pool
+#[contracttype]
+#[derive(Clone)]
+pub enum KeeperPoolDataKey {
+    /// Payout settings
+    /// Value type: KeeperPoolConfig
+    Config,
+    /// Native XLM available for payouts
+    /// Value type: i128
+    Balance,
+    /// Whether a keeper is paid for its calls
+    /// Value type: bool
+    Sponsored(Address),
+    /// Amount paid to a keeper on a day (`timestamp / KEEPER_DAY_SECONDS`)
+    /// Value type: i128
+    Paid(Address, u64),
+}
+
+/// Keeper payout settings, in native XLM
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeeperPoolConfig {
+    /// Paid per sponsored call
+    pub reward_per_call: i128,
+    /// Maximum paid to one keeper per day
+    pub daily_cap: i128,
+}
+
+/// Get the payout settings (None until configured)
+pub fn get_keeper_pool_config(env: &Env) -> Option<KeeperPoolConfig> {
+    env.storage().persistent().get(&KeeperPoolDataKey::Config)
+}
+
+/// Set the payout settings (admin only)
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not the admin
+/// * `InvalidConfig` - Reward is not positive or exceeds the daily cap
+pub fn set_keeper_pool_config(
+    env: &Env,
+    caller: Address,
+    config: KeeperPoolConfig,
+) -> Result<(), KeeperPoolError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| KeeperPoolError::Unauthorized)?;
+    if config.reward_per_call <= 0 || config.daily_cap < config.reward_per_call {
+        return Err(KeeperPoolError::InvalidConfig);
+    }
+    env.storage()
+        .persistent()
+        .set(&KeeperPoolDataKey::Config, &config);
+
+    emit_admin_action(
+        env,
+        AdminActionEvent {
+            actor: caller,
+            action: Symbol::new(env, "set_keeper_pool_config"),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Whether a keeper is paid for its calls
+pub fn is_sponsored_keeper(env: &Env, keeper: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&KeeperPoolDataKey::Sponsored(keeper.clone()))
+        .unwrap_or(false)
+}
+
+/// Add or remove a keeper from the sponsorship whitelist (admin only)
+pub fn set_sponsored_keeper(
+    env: &Env,
+    caller: Address,
+    keeper: Address,
+    sponsored: bool,
+) -> Result<(), KeeperPoolError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| KeeperPoolError::Unauthorized)?;
+    let key = KeeperPoolDataKey::Sponsored(keeper);
+    if sponsored {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+
+    emit_admin_action(
+        env,
+        AdminActionEvent {
+            actor: caller,
+            action: Symbol::new(env, "set_sponsored_keeper"),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Get the native XLM available for payouts
+pub fn get_keeper_pool_balance(env: &Env) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&KeeperPoolDataKey::Balance)
+        .unwrap_or(0)
+}
+
+/// Get the amount paid to a keeper today
+pub fn get_keeper_paid_today(env: &Env, keeper: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&KeeperPoolDataKey::Paid(keeper.clone(), current_day(env)))
+        .unwrap_or(0)
+}
+
+/// Transfer native XLM from `funder` into the pool
+///
+/// # Returns
+/// The new pool balance
+///
+/// # Errors
+/// * `InvalidAmount` - Amount is not positive
+/// * `NativeAssetNotSet` - The native asset address is not configured
+pub fn fund_keeper_pool(env: &Env, funder: Address, amount: i128) -> Result<i128, KeeperPoolError> {
+    funder.require_auth();
+    if amount <= 0 {
+        return Err(KeeperPoolError::InvalidAmount);
+    }
+    let native = get_native_asset_address(env).ok_or(KeeperPoolError::NativeAssetNotSet)?;
+    token::Client::new(env, &native).transfer(&funder, &env.current_contract_address(), &amount);
+    let balance = get_keeper_pool_balance(env)
+        .checked_add(amount)
+        .ok_or(KeeperPoolError::Overflow)?;
+    env.storage()
+        .persistent()
+        .set(&KeeperPoolDataKey::Balance, &balance);
+    Ok(balance)
+}
+
+/// Pay a sponsored keeper for one call of `operation`
+///
+/// Pays nothing when the keeper is not sponsored, the pool is not
+/// configured, or the payout would exceed the keeper's daily cap or the pool
+/// balance; never fails the operation itself.
+///
+/// # Returns
+/// The amount paid
+pub(crate) fn reimburse(env: &Env, keeper: &Address, operation: Symbol) -> i128 {
+    if !is_sponsored_keeper(env, keeper) {
+        return 0;
+    }
+    let Some(config) = get_keeper_pool_config(env) else {
+        return 0;
+    };
+    let paid_today = get_keeper_paid_today(env, keeper);
+    if paid_today.saturating_add(config.reward_per_call) > config.daily_cap {
+        return 0;
+    }
+
+    let paid = pay_from_pool(env, keeper, config.reward_per_call, operation);
+    if paid > 0 {
+        env.storage().persistent().set(
+            &KeeperPoolDataKey::Paid(keeper.clone(), current_day(env)),
+            &(paid_today + paid),
+        );
+    }
+    paid
+}
+
+/// Pay `amount` of native XLM from the pool to a keeper for `operation`
+///
+/// Pays nothing when the native asset is not configured or the pool cannot
+/// cover the amount. Used for sponsored calls and automation job rewards.
+///
+/// # Returns
+/// The amount paid
+pub(crate) fn pay_from_pool(env: &Env, keeper: &Address, amount: i128, operation: Symbol) -> i128 {
+    let Some(native) = get_native_asset_address(env) else {
+        return 0;
+    };
+    let balance = get_keeper_pool_balance(env);
+    if amount <= 0 || amount > balance {
+        return 0;
+    }
+
+    env.storage()
+        .persistent()
+        .set(&KeeperPoolDataKey::Balance, &(balance - amount));
+    token::Client::new(env, &native).transfer(&env.current_contract_address(), keeper, &amount);
+    crate::financials::record(
+        env,
+        crate::financials::FinancialItem::KeeperReimbursements,
+        &None,
+        amount,
+    );
+
+    emit_keeper_reimbursed(
+        env,
+        KeeperReimbursedEvent {
+            keeper: keeper.clone(),
+            operation,
+            amount,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    amount
+}
+
+/// Extend the TTL of the contract instance and of `users`' position entries
+///
+/// Entries that do not exist are skipped.
+///
+/// # Returns
+/// The number of users with a position
+///
+/// # Errors
+/// * `BatchTooLarge` - More than `MAX_TTL_BUMP_BATCH` users
+pub fn bump_ttl(env: &Env, keeper: Address, users: Vec<Address>) -> Result<u32, KeeperPoolError> {
+    keeper.require_auth();
+    if users.len() > MAX_TTL_BUMP_BATCH {
+        return Err(KeeperPoolError::BatchTooLarge);
+    }
+
+    env.storage()
+        .instance()
+        .extend_ttl(KEEPER_TTL_THRESHOLD, KEEPER_TTL_EXTEND_TO);
+    let storage = env.storage().persistent();
+    let mut bumped = 0;
+    for user in users.iter() {
+        let keys = [
+            DepositDataKey::Position(user.clone()),
+            DepositDataKey::CollateralBalance(user.clone()),
+            DepositDataKey::SupplyShares(user.clone()),
+        ];
+        if !storage.has(&keys[0]) {
+            continue;
+        }
+        for key in keys.iter() {
+            if storage.has(key) {
+                storage.extend_ttl(key, KEEPER_TTL_THRESHOLD, KEEPER_TTL_EXTEND_TO);
+            }
+        }
+        bumped += 1;
+    }
+
+    reimburse(env, &keeper, Symbol::new(env, "bump_ttl"));
+    Ok(bumped)
+}
+
+/// Advance the global borrow index to now and refresh the day's metrics
+///
+/// # Returns
+/// The current borrow index
+///
+/// # Errors
+/// * `RateUpdateFailed` - The interest rate model is not initialized
+pub fn poke_rates(env: &Env, keeper: Address) -> Result<i128, KeeperPoolError> {
+    keeper.require_auth();
+    let index = crate::interest_rate::accrue_borrow_index(env)
+        .map_err(|_| KeeperPoolError::RateUpdateFailed)?;
+    crate::metric_history::record_daily_metrics(env);
+    reimburse(env, &keeper, Symbol::new(env, "poke_rates"));
+    Ok(index)
+}
+
+fn current_day(env: &Env) -> u64 {
+    env.ledger().timestamp() / KEEPER_DAY_SECONDS
+}
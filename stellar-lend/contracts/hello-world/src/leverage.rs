@@ -0,0 +1,433 @@
+//! # Leveraged Positions
+//!
+//! One-call helpers that loop the core operations to open or unwind a
+//! leveraged position.
+//!
+//! `open_leveraged_position` deposits the user's initial collateral, then
+//! repeats borrow → swap → deposit until total collateral reaches
+//! `initial × target_leverage`, the borrow capacity left by the minimum
+//! collateral ratio runs out, or the loop limit is hit. Swaps use the AMM's
+//! `auto_swap_for_collateral`, which swaps from native XLM, so the borrow
+//! asset must be native whenever it differs from the collateral asset.
+//!
+//! `close_leveraged_position` does the reverse: it repeatedly withdraws the
+//! collateral the ratio allows, swaps it to the borrow asset and repays,
+//! then withdraws the remaining collateral once the debt is cleared.
+//!
+//! Every step goes through the regular entrypoint logic, so pauses, caps
+//! and ratio checks apply as usual. The helpers are all-or-nothing: a
+//! failing step, or a swap returning less than `max_slippage_bps` allows,
+//! rolls back the whole call.
+
+use soroban_sdk::{contracterror, contracttype, Address, Env};
+use stellarlend_amm::SwapParams;
+
+use crate::deposit::{deposit_collateral, AssetParams, DepositDataKey, Position};
+use crate::repay::repay_debt;
+use crate::withdraw::withdraw_collateral;
+
+/// Leverage scale; 10000 = 1x
+pub const LEVERAGE_SCALE: i128 = 10_000;
+
+/// Maximum target leverage (5x)
+pub const MAX_LEVERAGE_BPS: i128 = 50_000;
+
+/// Maximum borrow → swap → deposit (or withdraw → swap → repay) rounds per call
+pub const MAX_LEVERAGE_LOOPS: u32 = 10;
+
+/// Seconds a loop swap stays valid
+const SWAP_DEADLINE_SECONDS: u64 = 300;
+
+/// Errors that can occur in leveraged position operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum LeverageError {
+    /// Amount must be greater than zero
+    InvalidAmount = 1,
+    /// Target leverage must be above 1x and at most `MAX_LEVERAGE_BPS`
+    InvalidLeverage = 2,
+    /// Slippage tolerance out of range or below the AMM's slippage, or a swap
+    /// lost more than allowed
+    SlippageExceeded = 3,
+    /// The assets cannot be swapped: a non-native borrow asset when opening,
+    /// or no AMM protocol supporting the pair when closing
+    UnsupportedPair = 4,
+    /// The AMM rejected a swap
+    SwapFailed = 5,
+    /// A deposit step failed
+    DepositFailed = 6,
+    /// A borrow step failed
+    BorrowFailed = 7,
+    /// A withdraw step failed
+    WithdrawFailed = 8,
+    /// A repay step failed
+    RepayFailed = 9,
+    /// The position cannot be unwound: no collateral can be withdrawn, or the
+    /// loop limit was reached with debt outstanding
+    UnwindFailed = 10,
+    /// Overflow occurred during calculation
+    Overflow = 11,
+}
+
+/// Position after a leverage helper ran
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LeverageResult {
+    pub collateral: i128,
+    /// Principal plus accrued interest
+    pub debt: i128,
+    /// Collateral over equity (collateral - debt), scaled by `LEVERAGE_SCALE`
+    pub leverage_bps: i128,
+    /// Loop rounds executed
+    pub loops: u32,
+}
+
+/// Open (or extend) a leveraged position
+///
+/// # Arguments
+/// * `user` - The position owner (must authorize)
+/// * `collateral_asset` - Asset deposited as collateral (None for native XLM)
+/// * `borrow_asset` - Asset borrowed and swapped into collateral
+/// * `initial_collateral` - Collateral the user deposits up front
+/// * `target_leverage_bps` - Target collateral / initial collateral (20000 = 2x)
+/// * `max_slippage_bps` - Largest swap loss accepted per round
+///
+/// # Errors
+/// * `InvalidAmount` / `InvalidLeverage` / `SlippageExceeded` - Invalid input,
+///   or the AMM's swap slippage exceeds `max_slippage_bps`
+/// * `UnsupportedPair` - Swapping would be needed from a non-native asset
+/// * `DepositFailed` / `BorrowFailed` / `SwapFailed` - A step failed
+pub fn open_leveraged_position(
+    env: &Env,
+    user: Address,
+    collateral_asset: Option<Address>,
+    borrow_asset: Option<Address>,
+    initial_collateral: i128,
+    target_leverage_bps: i128,
+    max_slippage_bps: i128,
+) -> Result<LeverageResult, LeverageError> {
+    if initial_collateral <= 0 {
+        return Err(LeverageError::InvalidAmount);
+    }
+    if target_leverage_bps <= LEVERAGE_SCALE || target_leverage_bps > MAX_LEVERAGE_BPS {
+        return Err(LeverageError::InvalidLeverage);
+    }
+    validate_slippage(max_slippage_bps)?;
+    let swap_needed = collateral_asset != borrow_asset;
+    if swap_needed {
+        if borrow_asset.is_some() {
+            return Err(LeverageError::UnsupportedPair);
+        }
+        amm_slippage(env, max_slippage_bps)?;
+    }
+    user.require_auth();
+
+    deposit(env, &user, &collateral_asset, initial_collateral)?;
+    let target_added = mul_div(
+        initial_collateral,
+        target_leverage_bps - LEVERAGE_SCALE,
+        LEVERAGE_SCALE,
+    )?;
+
+    let mut added = 0i128;
+    let mut loops = 0u32;
+    while loops < MAX_LEVERAGE_LOOPS && added < target_added {
+        let step = (target_added - added).min(borrow_capacity(env, &user, &borrow_asset)?);
+        if step <= 0 {
+            break;
+        }
+        if swap_needed && step < auto_swap_threshold(env, &collateral_asset) {
+            break;
+        }
+
+        crate::borrow::borrow_asset(env, user.clone(), borrow_asset.clone(), step)
+            .map_err(|_| LeverageError::BorrowFailed)?;
+        let borrowed = borrowed_after_fee(env, &borrow_asset, step)?;
+        let received = if swap_needed {
+            let received = stellarlend_amm::auto_swap_for_collateral(
+                env,
+                user.clone(),
+                collateral_asset.clone(),
+                borrowed,
+            )
+            .map_err(|_| LeverageError::SwapFailed)?;
+            check_slippage(borrowed, received, max_slippage_bps)?;
+            received
+        } else {
+            borrowed
+        };
+        deposit(env, &user, &collateral_asset, received)?;
+
+        added = added.checked_add(received).ok_or(LeverageError::Overflow)?;
+        loops += 1;
+    }
+
+    summarize(env, &user, loops)
+}
+
+/// Unwind a leveraged position: repay all debt from collateral and withdraw
+/// the rest
+///
+/// # Arguments
+/// * `user` - The position owner (must authorize)
+/// * `collateral_asset` - Asset held as collateral
+/// * `borrow_asset` - Asset owed
+/// * `max_slippage_bps` - Largest swap loss accepted per round
+///
+/// # Errors
+/// * `SlippageExceeded` - Invalid tolerance, or a swap lost more than allowed
+/// * `UnsupportedPair` - No AMM protocol swaps collateral into the borrow asset
+/// * `UnwindFailed` - Debt cannot be cleared within `MAX_LEVERAGE_LOOPS`
+/// * `WithdrawFailed` / `RepayFailed` / `SwapFailed` - A step failed
+pub fn close_leveraged_position(
+    env: &Env,
+    user: Address,
+    collateral_asset: Option<Address>,
+    borrow_asset: Option<Address>,
+    max_slippage_bps: i128,
+) -> Result<LeverageResult, LeverageError> {
+    validate_slippage(max_slippage_bps)?;
+    let swap_needed = collateral_asset != borrow_asset;
+    if swap_needed {
+        amm_slippage(env, max_slippage_bps)?;
+    }
+    user.require_auth();
+
+    let mut loops = 0u32;
+    loop {
+        let (collateral, debt) = position_of(env, &user);
+        if debt <= 0 {
+            if collateral > 0 {
+                withdraw_collateral(env, user.clone(), collateral_asset.clone(), collateral)
+                    .map_err(|_| LeverageError::WithdrawFailed)?;
+            }
+            break;
+        }
+        if loops >= MAX_LEVERAGE_LOOPS {
+            return Err(LeverageError::UnwindFailed);
+        }
+
+        // Collateral needed to cover the debt after the worst accepted slippage
+        let needed = if swap_needed {
+            mul_div_ceil(debt, LEVERAGE_SCALE, LEVERAGE_SCALE - max_slippage_bps)?
+        } else {
+            debt
+        };
+        let step = needed
+            .min(withdraw_capacity(env, &user, &collateral_asset)?)
+            .min(collateral);
+        if step <= 0 {
+            return Err(LeverageError::UnwindFailed);
+        }
+
+        withdraw_collateral(env, user.clone(), collateral_asset.clone(), step)
+            .map_err(|_| LeverageError::WithdrawFailed)?;
+        let received = if swap_needed {
+            swap_to_borrow_asset(
+                env,
+                &user,
+                &collateral_asset,
+                &borrow_asset,
+                step,
+                max_slippage_bps,
+            )?
+        } else {
+            step
+        };
+        repay_debt(env, user.clone(), borrow_asset.clone(), received.min(debt))
+            .map_err(|_| LeverageError::RepayFailed)?;
+        loops += 1;
+    }
+
+    summarize(env, &user, loops)
+}
+
+fn validate_slippage(max_slippage_bps: i128) -> Result<(), LeverageError> {
+    if !(0..LEVERAGE_SCALE).contains(&max_slippage_bps) {
+        return Err(LeverageError::SlippageExceeded);
+    }
+    Ok(())
+}
+
+fn check_slippage(
+    amount_in: i128,
+    received: i128,
+    max_slippage_bps: i128,
+) -> Result<(), LeverageError> {
+    let min_out = mul_div(amount_in, LEVERAGE_SCALE - max_slippage_bps, LEVERAGE_SCALE)?;
+    if received < min_out {
+        return Err(LeverageError::SlippageExceeded);
+    }
+    Ok(())
+}
+
+fn deposit(
+    env: &Env,
+    user: &Address,
+    asset: &Option<Address>,
+    amount: i128,
+) -> Result<(), LeverageError> {
+    deposit_collateral(env, user.clone(), asset.clone(), amount)
+        .map(|_| ())
+        .map_err(|_| LeverageError::DepositFailed)
+}
+
+/// Swap collateral back into the borrow asset through the first enabled AMM
+/// protocol supporting the pair
+fn swap_to_borrow_asset(
+    env: &Env,
+    user: &Address,
+    collateral_asset: &Option<Address>,
+    borrow_asset: &Option<Address>,
+    amount: i128,
+    max_slippage_bps: i128,
+) -> Result<i128, LeverageError> {
+    let protocols =
+        stellarlend_amm::amm::get_amm_protocols(env).map_err(|_| LeverageError::SwapFailed)?;
+    let protocol = protocols
+        .iter()
+        .find(|(_, config)| {
+            config.enabled
+                && config.supported_pairs.iter().any(|pair| {
+                    (pair.token_a == *collateral_asset && pair.token_b == *borrow_asset)
+                        || (pair.token_a == *borrow_asset && pair.token_b == *collateral_asset)
+                })
+        })
+        .map(|(protocol, _)| protocol)
+        .ok_or(LeverageError::UnsupportedPair)?;
+    let params = SwapParams {
+        protocol,
+        token_in: collateral_asset.clone(),
+        token_out: borrow_asset.clone(),
+        amount_in: amount,
+        min_amount_out: mul_div(amount, LEVERAGE_SCALE - max_slippage_bps, LEVERAGE_SCALE)?,
+        slippage_tolerance: amm_slippage(env, max_slippage_bps)?,
+        deadline: env.ledger().timestamp() + SWAP_DEADLINE_SECONDS,
+    };
+    let received =
+        crate::amm::swap(env, user.clone(), params).map_err(|_| LeverageError::SwapFailed)?;
+    check_slippage(amount, received, max_slippage_bps)?;
+    Ok(received)
+}
+
+/// Slippage the AMM applies to loop swaps, rejected up front when it exceeds
+/// the caller's tolerance
+fn amm_slippage(env: &Env, max_slippage_bps: i128) -> Result<i128, LeverageError> {
+    let settings =
+        stellarlend_amm::amm::get_amm_settings(env).map_err(|_| LeverageError::SwapFailed)?;
+    if settings.default_slippage > max_slippage_bps {
+        return Err(LeverageError::SlippageExceeded);
+    }
+    Ok(settings.default_slippage)
+}
+
+/// Smallest amount the AMM auto-swaps from native XLM into `target`
+fn auto_swap_threshold(env: &Env, target: &Option<Address>) -> i128 {
+    stellarlend_amm::amm::get_auto_swap_threshold(env, &None, target).unwrap_or(0)
+}
+
+/// Collateral balance and debt (principal plus interest) of a user
+fn position_of(env: &Env, user: &Address) -> (i128, i128) {
+    let collateral = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, i128>(&DepositDataKey::CollateralBalance(user.clone()))
+        .unwrap_or(0);
+    let debt = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, Position>(&DepositDataKey::Position(user.clone()))
+        .map(|p| p.debt.saturating_add(p.borrow_interest))
+        .unwrap_or(0);
+    (collateral, debt)
+}
+
+fn asset_params(env: &Env, asset: &Option<Address>) -> Option<AssetParams> {
+    asset.as_ref().and_then(|asset| {
+        env.storage()
+            .persistent()
+            .get::<DepositDataKey, AssetParams>(&DepositDataKey::AssetParams(asset.clone()))
+    })
+}
+
+/// Collateral factor the core operations apply for an asset
+fn collateral_factor(env: &Env, asset: &Option<Address>) -> i128 {
+    asset_params(env, asset)
+        .map(|params| params.collateral_factor)
+        .unwrap_or(LEVERAGE_SCALE)
+}
+
+/// Amount the user receives when borrowing `amount` (after the borrow fee)
+fn borrowed_after_fee(
+    env: &Env,
+    asset: &Option<Address>,
+    amount: i128,
+) -> Result<i128, LeverageError> {
+    let fee_bps = asset_params(env, asset)
+        .map(|params| params.borrow_fee_bps)
+        .unwrap_or(0);
+    Ok(amount - mul_div(amount, fee_bps, LEVERAGE_SCALE)?)
+}
+
+fn min_collateral_ratio(env: &Env) -> i128 {
+    crate::risk_params::get_min_collateral_ratio(env).unwrap_or(15_000)
+}
+
+/// Amount that can still be borrowed without breaking the minimum ratio
+fn borrow_capacity(
+    env: &Env,
+    user: &Address,
+    asset: &Option<Address>,
+) -> Result<i128, LeverageError> {
+    let (collateral, debt) = position_of(env, user);
+    let collateral_value = mul_div(collateral, collateral_factor(env, asset), LEVERAGE_SCALE)?;
+    let max_debt = mul_div(collateral_value, LEVERAGE_SCALE, min_collateral_ratio(env))?;
+    Ok((max_debt - debt).max(0))
+}
+
+/// Collateral that can be withdrawn without breaking the minimum ratio
+fn withdraw_capacity(
+    env: &Env,
+    user: &Address,
+    asset: &Option<Address>,
+) -> Result<i128, LeverageError> {
+    let (collateral, debt) = position_of(env, user);
+    let required_value = mul_div_ceil(debt, min_collateral_ratio(env), LEVERAGE_SCALE)?;
+    let required = mul_div_ceil(
+        required_value,
+        LEVERAGE_SCALE,
+        collateral_factor(env, asset),
+    )?;
+    Ok((collateral - required).max(0))
+}
+
+fn summarize(env: &Env, user: &Address, loops: u32) -> Result<LeverageResult, LeverageError> {
+    let (collateral, debt) = position_of(env, user);
+    let equity = collateral - debt;
+    let leverage_bps = if equity > 0 {
+        mul_div(collateral, LEVERAGE_SCALE, equity)?
+    } else {
+        0
+    };
+    Ok(LeverageResult {
+        collateral,
+        debt,
+        leverage_bps,
+        loops,
+    })
+}
+
+fn mul_div(a: i128, b: i128, c: i128) -> Result<i128, LeverageError> {
+    a.checked_mul(b)
+        .and_then(|x| x.checked_div(c))
+        .ok_or(LeverageError::Overflow)
+}
+
+fn mul_div_ceil(a: i128, b: i128, c: i128) -> Result<i128, LeverageError> {
+    let product = a.checked_mul(b).ok_or(LeverageError::Overflow)?;
+    if c <= 0 {
+        return Err(LeverageError::Overflow);
+    }
+    Ok((product + c - 1) / c)
+}
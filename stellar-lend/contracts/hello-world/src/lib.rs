@@ -1,7 +1,8 @@
-use soroban_sdk::{contract, contractimpl, Address, Env, Map, Symbol, Vec};
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Map, Symbol, Val, Vec};
 
 pub mod analytics;
 pub mod borrow;
+pub mod constants;
 pub mod cross_asset;
 pub mod deposit;
 pub mod events;
@@ -12,6 +13,7 @@ pub mod liquidate;
 pub mod oracle;
 pub mod repay;
 pub mod risk_management;
+pub mod snapshot;
 pub mod withdraw;
 
 #[cfg(test)]
@@ -47,6 +49,7 @@ mod reserve;
 mod risk_management;
 mod risk_params;
 mod withdraw;
+mod operations;
 pub mod recovery;
 pub mod multisig;
 
@@ -78,7 +81,8 @@ mod cross_asset;
 use cross_asset::{
     get_asset_config_by_address, get_asset_list, get_user_asset_position,
     get_user_position_summary, initialize_asset, update_asset_config,
-    update_asset_price, AssetConfig, AssetKey, AssetPosition, CrossAssetError, UserPositionSummary,
+    update_asset_price, AssetConfig, AssetKey, AssetPosition, AssetRateModel, CrossAssetError,
+    UserPositionSummary,
 };
 
 mod oracle;
@@ -92,7 +96,7 @@ use config::{config_backup, config_get, config_restore, config_set, ConfigError}
 
 mod flash_loan;
 use flash_loan::{
-    configure_flash_loan, execute_flash_loan, repay_flash_loan, set_flash_loan_fee, FlashLoanConfig,
+    configure_flash_loan, execute_flash_loan, set_flash_loan_fee, FlashLoanConfig, FlashLoanError,
 };
 
 mod bridge;
@@ -102,6 +106,11 @@ use bridge::{
     set_bridge_fee, BridgeConfig, BridgeError,
 };
 
+mod amm;
+use amm::{amm_swap, initialize_amm, set_amm_pool};
+use stellarlend_amm::{AmmError, AmmProtocolConfig, PoolHealth, SwapParams};
+
+mod bad_debt;
 mod liquidate;
 use liquidate::liquidate;
 
@@ -115,7 +124,35 @@ use interest_rate::{
     InterestRateError,
 };
 
+mod accounting;
+mod archive;
+mod automation;
+mod batch;
+mod compliance;
+mod debt_swap;
+mod fees;
+mod financials;
 mod governance;
+mod hedging;
+mod invariants;
+mod keeper_pool;
+mod leverage;
+mod metric_history;
+mod notifications;
+mod operators;
+mod param_bundle;
+mod positions;
+mod rewards;
+mod risk_hook;
+mod safety_module;
+mod stable_rate;
+mod storage;
+mod subsidy_campaign;
+mod tokenization;
+mod upgrade;
+
+#[cfg(any(test, feature = "sim"))]
+pub mod sim;
 
 use storage::GuardianConfig;
 
@@ -173,9 +210,37 @@ impl HelloContract {
                 RiskManagementError::Unauthorized
             }
         })?;
+        upgrade::init_storage_version(&env);
         Ok(())
     }
 
+    /// Initialize the contract with per-network constant overrides
+    ///
+    /// Same as `initialize`, but first records deployment-specific values such
+    /// as the year length used for interest accrual, so testnets can run with
+    /// accelerated time. The overrides cannot be changed afterwards.
+    ///
+    /// # Arguments
+    /// * `admin` - The admin address
+    /// * `constants` - Network constants replacing the mainnet defaults
+    pub fn initialize_with_constants(
+        env: Env,
+        admin: Address,
+        constants: crate::constants::NetworkConstants,
+    ) -> Result<(), RiskManagementError> {
+        if crate::admin::has_admin(&env) {
+            return Err(RiskManagementError::Unauthorized);
+        }
+
+        crate::constants::set_network_constants(&env, &constants)?;
+        Self::initialize(env, admin)
+    }
+
+    /// Get the active network constants
+    pub fn get_network_constants(env: Env) -> crate::constants::NetworkConstants {
+        crate::constants::get_network_constants(&env)
+    }
+
     /// Transfer super admin rights
     ///
     /// # Arguments
@@ -190,6 +255,9 @@ impl HelloContract {
     }
 
     /// Grant a role to an address (admin only)
+    ///
+    /// Operational roles enforced by the protocol: `risk_admin`,
+    /// `oracle_feeder`, `pauser`, `treasurer` and `liquidation_admin`.
     pub fn grant_role(
         env: Env,
         caller: Address,
@@ -209,13 +277,23 @@ impl HelloContract {
         crate::admin::revoke_role(&env, caller, role, account)
     }
 
+    /// Check whether an address holds a role
+    pub fn has_role(env: Env, role: Symbol, account: Address) -> bool {
+        crate::admin::has_role(&env, role, account)
+    }
+
+    /// Get the current holders of a role, in grant order
+    pub fn get_role_members(env: Env, role: Symbol) -> Vec<Address> {
+        crate::admin::get_role_members(&env, role)
+    }
+
     /// Deposit collateral into the protocol
     ///
     /// Allows users to deposit assets as collateral in the protocol.
     /// Supports multiple asset types including XLM (native) and token contracts (USDC, etc.).
     ///
     /// # Arguments
-    /// * `user` - The address of the user depositing collateral
+    /// * `user` - The address of the user depositing collateral (must authorize)
     /// * `asset` - The address of the asset contract to deposit (None for native XLM)
     /// * `amount` - The amount to deposit
     ///
@@ -234,9 +312,30 @@ impl HelloContract {
         asset: Option<Address>,
         amount: i128,
     ) -> Result<i128, crate::deposit::DepositError> {
+        user.require_auth();
         deposit::deposit_collateral(&env, user, asset, amount)
     }
 
+    /// Deposit collateral into another user's position
+    ///
+    /// # Arguments
+    /// * `payer` - The address supplying the tokens (must authorize)
+    /// * `user` - The address whose position is credited (no authorization needed)
+    /// * `asset` - The address of the asset contract to deposit (None for native XLM)
+    /// * `amount` - The amount to deposit
+    ///
+    /// # Returns
+    /// Returns the updated collateral balance of `user`
+    pub fn deposit_collateral_for(
+        env: Env,
+        payer: Address,
+        user: Address,
+        asset: Option<Address>,
+        amount: i128,
+    ) -> Result<i128, crate::deposit::DepositError> {
+        deposit::deposit_collateral_for(&env, payer, user, asset, amount)
+    }
+
     /// Set native asset address (admin only). Required before using asset = None for deposit/borrow/repay.
     pub fn set_native_asset_address(
         env: Env,
@@ -246,12 +345,32 @@ impl HelloContract {
         deposit::set_native_asset_address(&env, caller, native_asset)
     }
 
-    /// Set risk parameters (admin only)
+    /// Get the supply exchange rate
+    ///
+    /// # Returns
+    /// Underlying per share, scaled by `deposit::EXCHANGE_RATE_SCALE` (1e9 = 1:1)
+    pub fn get_exchange_rate(env: Env) -> i128 {
+        deposit::get_exchange_rate(&env)
+    }
+
+    /// Get the supply shares held by a user
+    pub fn get_supply_shares(env: Env, user: Address) -> i128 {
+        deposit::get_supply_shares(&env, &user)
+    }
+
+    /// Get the underlying amount a user's supply shares redeem for
+    ///
+    /// Includes the user's share of borrow interest accrued so far.
+    pub fn get_underlying_balance(env: Env, user: Address) -> i128 {
+        deposit::get_underlying_balance(&env, &user)
+    }
+
+    /// Set risk parameters (admin or risk admin)
     ///
     /// Updates risk parameters with validation and change limits.
     ///
     /// # Arguments
-    /// * `caller` - The caller address (must be admin)
+    /// * `caller` - The caller address (must be admin or hold `risk_admin`)
     /// * `min_collateral_ratio` - Optional new minimum collateral ratio (in basis points)
     /// * `liquidation_threshold` - Optional new liquidation threshold (in basis points)
     /// * `close_factor` - Optional new close factor (in basis points)
@@ -267,7 +386,7 @@ impl HelloContract {
         close_factor: Option<i128>,
         liquidation_incentive: Option<i128>,
     ) -> Result<(), RiskManagementError> {
-        require_admin(&env, &caller)?;
+        risk_management::require_role(&env, &caller, admin::RISK_ADMIN)?;
         check_emergency_pause(&env)?;
         risk_params::set_risk_params(
             &env,
@@ -285,6 +404,32 @@ impl HelloContract {
         })
     }
 
+    /// Export the live protocol parameters as a bundle
+    ///
+    /// # Arguments
+    /// * `assets` - Assets whose caps are included (None for native XLM)
+    pub fn get_parameter_bundle(
+        env: Env,
+        assets: Vec<Option<Address>>,
+    ) -> Result<param_bundle::ParameterBundle, param_bundle::ParameterBundleError> {
+        param_bundle::get_parameter_bundle(&env, assets)
+    }
+
+    /// Apply only the differences between a parameter bundle and the live
+    /// parameters (admin only)
+    ///
+    /// All changes are applied atomically through the regular setters.
+    ///
+    /// # Returns
+    /// One entry per changed value
+    pub fn apply_parameter_bundle(
+        env: Env,
+        caller: Address,
+        bundle: param_bundle::ParameterBundle,
+    ) -> Result<Vec<param_bundle::ParameterChange>, param_bundle::ParameterBundleError> {
+        param_bundle::apply_parameter_bundle(&env, caller, bundle)
+    }
+
 
     pub fn set_guardians(
     env: Env,
@@ -366,6 +511,7 @@ pub fn ms_execute(
         asset: Option<Address>,
         amount: i128,
     ) -> Result<i128, crate::borrow::BorrowError> {
+        user.require_auth();
         borrow::borrow_asset(&env, user, asset, amount)
     }
 
@@ -376,9 +522,351 @@ pub fn ms_execute(
         asset: Option<Address>,
         amount: i128,
     ) -> Result<(i128, i128, i128), crate::repay::RepayError> {
+        user.require_auth();
         repay::repay_debt(&env, user, asset, amount)
     }
 
+    /// Repay another user's debt
+    ///
+    /// # Arguments
+    /// * `payer` - The address supplying the tokens (must authorize)
+    /// * `user` - The address whose debt is repaid (no authorization needed)
+    /// * `asset` - The address of the asset contract to repay (None for native XLM)
+    /// * `amount` - The amount to repay
+    ///
+    /// # Returns
+    /// Returns (remaining_debt, interest_paid, principal_paid) for `user`
+    pub fn repay_debt_for(
+        env: Env,
+        payer: Address,
+        user: Address,
+        asset: Option<Address>,
+        amount: i128,
+    ) -> Result<(i128, i128, i128), crate::repay::RepayError> {
+        repay::repay_debt_for(&env, payer, user, asset, amount)
+    }
+
+    /// Execute deposit, borrow, repay and withdraw actions in order, all-or-nothing
+    ///
+    /// # Arguments
+    /// * `user` - The user performing every action
+    /// * `actions` - Up to 10 actions, run in order
+    ///
+    /// # Returns
+    /// One result per action (collateral balance, total debt or remaining debt)
+    pub fn execute_batch(
+        env: Env,
+        user: Address,
+        actions: Vec<batch::ProtocolAction>,
+    ) -> Result<Vec<i128>, batch::BatchError> {
+        batch::execute_batch(&env, user, actions)
+    }
+
+    /// Set the permissions of an operator over the caller's position
+    ///
+    /// # Arguments
+    /// * `user` - The position owner (must authorize)
+    /// * `operator` - The address being authorized, e.g. a vault contract
+    /// * `permissions` - Bitmask of deposit (1), withdraw (2), borrow (4) and
+    ///   repay (8) permissions; zero revokes the operator
+    pub fn set_position_operator(
+        env: Env,
+        user: Address,
+        operator: Address,
+        permissions: u32,
+    ) -> Result<(), operators::OperatorError> {
+        operators::set_position_operator(&env, user, operator, permissions)
+    }
+
+    /// Get the permissions of an operator over a user's position (0 if none)
+    pub fn get_position_operator(env: Env, user: Address, operator: Address) -> u32 {
+        operators::get_position_operator(&env, &user, &operator)
+    }
+
+    /// Run a deposit, withdraw, borrow or repay on a user's position as their operator
+    ///
+    /// Deposits and repayments are pulled from the user's wallet; withdrawals
+    /// and borrows are paid to the user.
+    ///
+    /// # Arguments
+    /// * `operator` - The operator (must authorize and hold the action's permission)
+    /// * `user` - The position owner (no authorization needed)
+    /// * `action` - The action to run
+    ///
+    /// # Returns
+    /// The collateral balance, total debt or remaining debt after the action
+    pub fn execute_as_operator(
+        env: Env,
+        operator: Address,
+        user: Address,
+        action: batch::ProtocolAction,
+    ) -> Result<i128, operators::OperatorError> {
+        operators::execute_as_operator(&env, operator, user, action)
+    }
+
+    /// Open an empty isolated position (sub-account)
+    ///
+    /// # Arguments
+    /// * `owner` - The position owner (must authorize)
+    ///
+    /// # Returns
+    /// The position id
+    pub fn open_position(env: Env, owner: Address) -> Result<u64, positions::PositionError> {
+        positions::open_position(&env, owner)
+    }
+
+    /// Transfer a whole isolated position, collateral and debt included
+    ///
+    /// # Arguments
+    /// * `owner` - The current owner (must authorize)
+    /// * `position_id` - The position to transfer
+    /// * `to` - The new owner
+    pub fn transfer_position(
+        env: Env,
+        owner: Address,
+        position_id: u64,
+        to: Address,
+    ) -> Result<(), positions::PositionError> {
+        positions::transfer_position(&env, owner, position_id, to)
+    }
+
+    /// Run a deposit, withdraw, borrow or repay on an isolated position
+    ///
+    /// # Arguments
+    /// * `owner` - The position owner (must authorize); pays deposits and
+    ///   repayments and receives withdrawals and borrows
+    /// * `position_id` - The position to act on
+    /// * `action` - The action to run
+    ///
+    /// # Returns
+    /// The position's collateral balance, total debt or remaining debt after the action
+    pub fn execute_on_position(
+        env: Env,
+        owner: Address,
+        position_id: u64,
+        action: batch::ProtocolAction,
+    ) -> Result<i128, positions::PositionError> {
+        positions::execute_on_position(&env, owner, position_id, action)
+    }
+
+    /// Restore an isolated position's archived account
+    ///
+    /// # Arguments
+    /// * `owner` - The position owner (must authorize)
+    /// * `position_id` - The position to restore
+    ///
+    /// # Returns
+    /// The restored position
+    pub fn restore_isolated_position(
+        env: Env,
+        owner: Address,
+        position_id: u64,
+    ) -> Result<deposit::Position, positions::PositionError> {
+        positions::restore_isolated_position(&env, owner, position_id)
+    }
+
+    /// Get the owner of an isolated position
+    pub fn get_position_owner(env: Env, position_id: u64) -> Option<Address> {
+        positions::get_position_owner(&env, position_id)
+    }
+
+    /// Get the ids of the isolated positions a user owns, oldest first
+    pub fn get_user_positions(env: Env, owner: Address) -> Vec<u64> {
+        positions::get_user_positions(&env, &owner)
+    }
+
+    /// Get the account address an isolated position lives at
+    ///
+    /// Health factor, liquidation and position views take this address.
+    pub fn get_position_account(env: Env, position_id: u64) -> Address {
+        positions::get_position_account(&env, position_id)
+    }
+
+    /// Open a leveraged position by looping borrow → swap → deposit
+    ///
+    /// # Arguments
+    /// * `user` - The position owner
+    /// * `collateral_asset` - Asset held as collateral (None for native XLM)
+    /// * `borrow_asset` - Asset borrowed; must be native XLM unless equal to
+    ///   the collateral asset
+    /// * `initial_collateral` - Collateral deposited up front
+    /// * `target_leverage_bps` - Target collateral / initial collateral (20000 = 2x, max 5x)
+    /// * `max_slippage_bps` - Largest swap loss accepted per round
+    ///
+    /// # Returns
+    /// The resulting collateral, debt, leverage and number of rounds
+    pub fn open_leveraged_position(
+        env: Env,
+        user: Address,
+        collateral_asset: Option<Address>,
+        borrow_asset: Option<Address>,
+        initial_collateral: i128,
+        target_leverage_bps: i128,
+        max_slippage_bps: i128,
+    ) -> Result<leverage::LeverageResult, leverage::LeverageError> {
+        leverage::open_leveraged_position(
+            &env,
+            user,
+            collateral_asset,
+            borrow_asset,
+            initial_collateral,
+            target_leverage_bps,
+            max_slippage_bps,
+        )
+    }
+
+    /// Unwind a leveraged position by looping withdraw → swap → repay, then
+    /// withdraw the remaining collateral
+    ///
+    /// # Arguments
+    /// * `user` - The position owner
+    /// * `collateral_asset` - Asset held as collateral
+    /// * `borrow_asset` - Asset owed
+    /// * `max_slippage_bps` - Largest swap loss accepted per round
+    pub fn close_leveraged_position(
+        env: Env,
+        user: Address,
+        collateral_asset: Option<Address>,
+        borrow_asset: Option<Address>,
+        max_slippage_bps: i128,
+    ) -> Result<leverage::LeverageResult, leverage::LeverageError> {
+        leverage::close_leveraged_position(
+            &env,
+            user,
+            collateral_asset,
+            borrow_asset,
+            max_slippage_bps,
+        )
+    }
+
+    /// Move cross-asset debt from one asset to another via an internal flash
+    /// loan and an AMM swap
+    ///
+    /// # Arguments
+    /// * `user` - The position owner
+    /// * `from_debt_asset` - Asset whose debt is repaid (None for native XLM)
+    /// * `to_debt_asset` - Asset the new debt is opened in
+    /// * `amount` - Debt repaid in `from_debt_asset`
+    /// * `max_slippage_bps` - Largest swap loss accepted
+    ///
+    /// # Returns
+    /// The amounts repaid, borrowed and paid as flash fee, and the health
+    /// factor before and after
+    pub fn swap_debt(
+        env: Env,
+        user: Address,
+        from_debt_asset: Option<Address>,
+        to_debt_asset: Option<Address>,
+        amount: i128,
+        max_slippage_bps: i128,
+    ) -> Result<debt_swap::DebtSwapResult, debt_swap::DebtSwapError> {
+        debt_swap::swap_debt(
+            &env,
+            user,
+            from_debt_asset,
+            to_debt_asset,
+            amount,
+            max_slippage_bps,
+        )
+    }
+
+    /// Deposit collateral and return the operation id
+    ///
+    /// # Arguments
+    /// * `user` - The depositor (must authorize)
+    /// * `asset` - The asset to deposit (None for native XLM)
+    /// * `amount` - The amount to deposit
+    ///
+    /// # Returns
+    /// (updated collateral balance, operation id)
+    pub fn deposit_collateral_with_id(
+        env: Env,
+        user: Address,
+        asset: Option<Address>,
+        amount: i128,
+    ) -> Result<(i128, u64), deposit::DepositError> {
+        user.require_auth();
+        let balance = deposit::deposit_collateral(&env, user.clone(), asset, amount)?;
+        Ok((balance, operations::get_last_operation_id(&env, &user)))
+    }
+
+    /// Withdraw collateral and return the operation id
+    ///
+    /// # Arguments
+    /// * `user` - The withdrawer (must authorize)
+    /// * `asset` - The asset to withdraw (None for native XLM)
+    /// * `amount` - The amount to withdraw
+    ///
+    /// # Returns
+    /// (remaining collateral balance, operation id)
+    pub fn withdraw_collateral_with_id(
+        env: Env,
+        user: Address,
+        asset: Option<Address>,
+        amount: i128,
+    ) -> Result<(i128, u64), withdraw::WithdrawError> {
+        user.require_auth();
+        let balance = withdraw::withdraw_collateral(&env, user.clone(), asset, amount)?;
+        Ok((balance, operations::get_last_operation_id(&env, &user)))
+    }
+
+    /// Borrow at the variable rate and return the operation id
+    ///
+    /// # Arguments
+    /// * `user` - The borrower (must authorize)
+    /// * `asset` - The asset to borrow (None for native XLM)
+    /// * `amount` - The amount to borrow
+    ///
+    /// # Returns
+    /// (total debt, operation id)
+    pub fn borrow_asset_with_id(
+        env: Env,
+        user: Address,
+        asset: Option<Address>,
+        amount: i128,
+    ) -> Result<(i128, u64), borrow::BorrowError> {
+        user.require_auth();
+        let debt = borrow::borrow_asset(&env, user.clone(), asset, amount)?;
+        Ok((debt, operations::get_last_operation_id(&env, &user)))
+    }
+
+    /// Repay debt and return the operation id
+    ///
+    /// # Arguments
+    /// * `user` - The borrower (must authorize)
+    /// * `asset` - The asset to repay (None for native XLM)
+    /// * `amount` - The amount to repay
+    ///
+    /// # Returns
+    /// (remaining debt, interest paid, principal paid, operation id)
+    pub fn repay_debt_with_id(
+        env: Env,
+        user: Address,
+        asset: Option<Address>,
+        amount: i128,
+    ) -> Result<(i128, i128, i128, u64), repay::RepayError> {
+        user.require_auth();
+        let (remaining, interest, principal) =
+            repay::repay_debt(&env, user.clone(), asset, amount)?;
+        Ok((
+            remaining,
+            interest,
+            principal,
+            operations::get_last_operation_id(&env, &user),
+        ))
+    }
+
+    /// Get the receipt (including the operation id) of a user's latest
+    /// deposit, withdrawal, borrow or repayment
+    pub fn get_last_operation(env: Env, user: Address) -> Option<operations::OperationReceipt> {
+        operations::get_last_operation(&env, &user)
+    }
+
+    /// Get the number of operations recorded so far (the latest operation id)
+    pub fn get_operation_count(env: Env) -> u64 {
+        operations::get_operation_count(&env)
+    }
+
     /// Liquidate an undercollateralized position
     pub fn liquidate(
         env: Env,
@@ -420,6 +908,70 @@ pub fn ms_execute(
         risk_params::get_close_factor(&env).map_err(|_| RiskManagementError::InvalidParameter)
     }
 
+    /// Set position size tiers for the close factor (admin or liquidation admin)
+    ///
+    /// Large positions can be given a smaller close factor so they are
+    /// unwound over several liquidations instead of one.
+    ///
+    /// # Arguments
+    /// * `caller` - The caller address (must be admin or hold `liquidation_admin`)
+    /// * `tiers` - Size tiers sorted by increasing `min_debt` (empty to clear)
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn set_close_factor_tiers(
+        env: Env,
+        caller: Address,
+        tiers: soroban_sdk::Vec<risk_params::CloseFactorTier>,
+    ) -> Result<(), RiskManagementError> {
+        risk_management::require_role(&env, &caller, admin::LIQUIDATION_ADMIN)?;
+        risk_params::set_close_factor_tiers(&env, tiers).map_err(|e| match e {
+            RiskParamsError::InvalidCloseFactor => RiskManagementError::InvalidCloseFactor,
+            _ => RiskManagementError::InvalidParameter,
+        })
+    }
+
+    /// Get configured close factor size tiers
+    pub fn get_close_factor_tiers(env: Env) -> soroban_sdk::Vec<risk_params::CloseFactorTier> {
+        risk_params::get_close_factor_tiers(&env)
+    }
+
+    /// Get the close factor applied to a position of the given debt size
+    ///
+    /// # Arguments
+    /// * `debt_value` - Total debt of the position (in base units)
+    ///
+    /// # Returns
+    /// Returns the close factor in basis points
+    pub fn get_close_factor_for_debt(
+        env: Env,
+        debt_value: i128,
+    ) -> Result<i128, RiskManagementError> {
+        risk_params::get_close_factor_for_debt(&env, debt_value)
+            .map_err(|_| RiskManagementError::InvalidParameter)
+    }
+
+    /// Get the close factor for a position with the given collateral and debt
+    ///
+    /// Applies the health factor tiers and the position size tiers the same
+    /// way liquidations do, so other contracts can liquidate under the
+    /// close factor this contract would use.
+    ///
+    /// # Arguments
+    /// * `collateral_value` - Collateral value of the position
+    /// * `debt_value` - Total debt of the position
+    ///
+    /// # Returns
+    /// Returns the close factor in basis points (0 if the health tiers do not
+    /// consider the position liquidatable)
+    pub fn get_close_factor_for_position(
+        env: Env,
+        collateral_value: i128,
+        debt_value: i128,
+    ) -> Result<i128, RiskManagementError> {
+        risk_management::get_close_factor_for_health(&env, collateral_value, debt_value)
+    }
+
     /// Get liquidation incentive
     ///
     /// # Returns
@@ -438,6 +990,29 @@ pub fn ms_execute(
         interest_rate::calculate_supply_rate(&env).unwrap_or(0)
     }
 
+    /// Model borrow and supply rates at hypothetical utilizations
+    ///
+    /// # Arguments
+    /// * `asset` - Asset whose rate model is projected (None for native XLM)
+    /// * `hypothetical_utilizations` - Utilizations in basis points (0-10000)
+    pub fn project_rates(
+        env: Env,
+        asset: Option<Address>,
+        hypothetical_utilizations: Vec<i128>,
+    ) -> Result<Vec<interest_rate::RateProjection>, InterestRateError> {
+        interest_rate::project_rates(&env, asset, hypothetical_utilizations)
+    }
+
+    /// Get the global borrow index (scaled by 1e18) at the current time
+    pub fn get_borrow_index(env: Env) -> Result<i128, InterestRateError> {
+        interest_rate::get_borrow_index(&env)
+    }
+
+    /// Get a user's debt including interest accrued up to now
+    pub fn get_accrued_debt(env: Env, user: Address) -> Result<i128, InterestRateError> {
+        interest_rate::get_accrued_debt(&env, &user)
+    }
+
     /// Update interest rate model configuration (admin only)
     #[allow(clippy::too_many_arguments)]
     pub fn update_interest_rate_config(
@@ -467,7 +1042,7 @@ pub fn ms_execute(
         collateral_value: i128,
         debt_value: i128,
     ) -> Result<bool, RiskManagementError> {
-        can_be_liquidated(&env, collateral_value, debt_value).map_err(|_| RiskManagementError::InvalidParameter)
+        can_be_liquidated(&env, collateral_value, debt_value, None).map_err(|_| RiskManagementError::InvalidParameter)
     }
 
     /// Manual emergency interest rate adjustment (admin only)
@@ -492,37 +1067,253 @@ pub fn ms_execute(
         get_liquidation_incentive_amount(&env, liquidated_amount).map_err(|_| RiskManagementError::Overflow)
     }
 
-    /// Refresh analytics for a user
-    pub fn refresh_user_analytics(_env: Env, _user: Address) -> Result<(), RiskManagementError> {
-        Ok(())
+    /// Liquidate a position and swap the seized collateral to the debt asset
+    /// through the AMM; the proceeds repay the debt and the surplus goes to
+    /// the liquidator
+    ///
+    /// # Arguments
+    /// * `liquidator` - The liquidator
+    /// * `borrower` - The borrower being liquidated
+    /// * `debt_asset` - The debt asset to repay (None for native XLM)
+    /// * `collateral_asset` - The collateral asset seized and swapped
+    /// * `amount` - The amount of debt to liquidate
+    /// * `min_out` - Least debt asset the swap must return
+    pub fn liquidate_and_swap(
+        env: Env,
+        liquidator: Address,
+        borrower: Address,
+        debt_asset: Option<Address>,
+        collateral_asset: Option<Address>,
+        amount: i128,
+        min_out: i128,
+    ) -> Result<liquidate::SwapLiquidationResult, liquidate::LiquidationError> {
+        liquidate::liquidate_and_swap(
+            &env,
+            liquidator,
+            borrower,
+            debt_asset,
+            collateral_asset,
+            amount,
+            min_out,
+        )
     }
 
-    /// Claim accumulated protocol reserves (admin only)
-    pub fn claim_reserves(env: Env, caller: Address, asset: Option<Address>, to: Address, amount: i128) -> Result<(), RiskManagementError> {
-        require_admin(&env, &caller)?;
-        
-        let reserve_key = DepositDataKey::ProtocolReserve(asset.clone());
-        let mut reserve_balance = env.storage().persistent()
-            .get::<DepositDataKey, i128>(&reserve_key)
-            .unwrap_or(0);
-            
-        if amount > reserve_balance {
-            return Err(RiskManagementError::InvalidParameter);
-        }
-        
-        if let Some(_asset_addr) = asset {
-            #[cfg(not(test))]
-            {
-                let token_client = soroban_sdk::token::Client::new(&env, &_asset_addr);
-                token_client.transfer(&env.current_contract_address(), &to, &amount);
-            }
-        }
-        
-        reserve_balance -= amount;
-        env.storage().persistent().set(&reserve_key, &reserve_balance);
+    /// Liquidate a position with an internal flash loan of the debt asset,
+    /// selling just enough seized collateral through the AMM to repay the
+    /// loan plus fee; the rest goes to the liquidator
+    ///
+    /// # Arguments
+    /// * `liquidator` - The liquidator
+    /// * `borrower` - The borrower being liquidated
+    /// * `debt_asset` - The debt asset to repay (None for native XLM)
+    /// * `collateral_asset` - The collateral asset seized
+    /// * `amount` - The amount of debt to liquidate
+    pub fn liquidate_with_flash_loan(
+        env: Env,
+        liquidator: Address,
+        borrower: Address,
+        debt_asset: Option<Address>,
+        collateral_asset: Option<Address>,
+        amount: i128,
+    ) -> Result<liquidate::FlashLiquidationResult, liquidate::LiquidationError> {
+        liquidate::liquidate_with_flash_loan(
+            &env,
+            liquidator,
+            borrower,
+            debt_asset,
+            collateral_asset,
+            amount,
+        )
+    }
+
+    /// Set the Dutch-auction ramp duration and maximum incentive (admin or liquidation admin)
+    pub fn set_auction_config(
+        env: Env,
+        caller: Address,
+        config: liquidate::AuctionConfig,
+    ) -> Result<(), liquidate::LiquidationError> {
+        liquidate::set_auction_config(&env, caller, config)
+    }
+
+    /// Get the Dutch-auction ramp parameters
+    pub fn get_auction_config(env: Env) -> liquidate::AuctionConfig {
+        liquidate::get_auction_config(&env)
+    }
+
+    /// Start a Dutch auction for an undercollateralized position
+    pub fn start_auction(
+        env: Env,
+        starter: Address,
+        borrower: Address,
+        debt_asset: Option<Address>,
+        collateral_asset: Option<Address>,
+    ) -> Result<liquidate::LiquidationAuction, liquidate::LiquidationError> {
+        liquidate::start_auction(&env, starter, borrower, debt_asset, collateral_asset)
+    }
+
+    /// Bid on an open auction at the current collateral discount
+    ///
+    /// # Returns
+    /// Returns a tuple (debt_liquidated, collateral_seized, incentive_amount)
+    pub fn bid(
+        env: Env,
+        liquidator: Address,
+        borrower: Address,
+        debt_amount: i128,
+    ) -> Result<(i128, i128, i128), liquidate::LiquidationError> {
+        liquidate::bid(&env, liquidator, borrower, debt_amount)
+    }
+
+    /// Close an auction once the position is healthy or the ramp has finished
+    pub fn settle_auction(
+        env: Env,
+        caller: Address,
+        borrower: Address,
+    ) -> Result<liquidate::AuctionRecord, liquidate::LiquidationError> {
+        liquidate::settle_auction(&env, caller, borrower)
+    }
+
+    /// Cancel an open auction (admin or liquidation admin)
+    pub fn cancel_auction(
+        env: Env,
+        caller: Address,
+        borrower: Address,
+    ) -> Result<liquidate::AuctionRecord, liquidate::LiquidationError> {
+        liquidate::cancel_auction(&env, caller, borrower)
+    }
+
+    /// Get the open auction for a borrower, if any
+    pub fn get_auction(env: Env, borrower: Address) -> Option<liquidate::LiquidationAuction> {
+        liquidate::get_auction(&env, &borrower)
+    }
+
+    /// Get the closed auctions for a borrower, oldest first
+    pub fn get_auction_history(env: Env, borrower: Address) -> Vec<liquidate::AuctionRecord> {
+        liquidate::get_auction_history(&env, &borrower)
+    }
+
+    /// Get the cumulative liquidation volume and incentive earned by a liquidator
+    pub fn get_liquidator_stats(env: Env, liquidator: Address) -> liquidate::LiquidatorStats {
+        liquidate::get_liquidator_stats(&env, &liquidator)
+    }
+
+    /// Get the top liquidators by debt repaid (at most 20)
+    pub fn get_liquidator_leaderboard(env: Env, limit: u32) -> Vec<liquidate::LeaderboardEntry> {
+        liquidate::get_liquidator_leaderboard(&env, limit)
+    }
+
+    /// Get archived liquidation receipts starting at `start_id`, oldest first (at most 50)
+    pub fn get_liquidation_receipts(
+        env: Env,
+        start_id: u64,
+        limit: u32,
+    ) -> Vec<liquidate::LiquidationReceipt> {
+        liquidate::get_liquidation_receipts(&env, start_id, limit)
+    }
+
+    /// Get an archived liquidation receipt by id, if it has not been pruned
+    pub fn get_liquidation_receipt(env: Env, id: u64) -> Option<liquidate::LiquidationReceipt> {
+        liquidate::get_liquidation_receipt(&env, id)
+    }
+
+    /// Number of liquidation receipts ever archived (the next receipt id)
+    pub fn get_liquidation_receipt_count(env: Env) -> u64 {
+        liquidate::get_liquidation_receipt_count(&env)
+    }
+
+    /// Get the liquidatable borrowers among `limit` borrower index slots from `offset` (at most 50)
+    pub fn get_liquidatable_positions(
+        env: Env,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<liquidate::LiquidatablePosition>, liquidate::LiquidationError> {
+        liquidate::get_liquidatable_positions(&env, limit, offset)
+    }
+
+    /// Number of borrowers with outstanding debt
+    pub fn get_borrower_count(env: Env) -> u32 {
+        liquidate::get_borrower_count(&env)
+    }
+
+    /// Set the share of accrued interest diverted to the insurance reserve (admin or risk admin)
+    pub fn set_insurance_factor(
+        env: Env,
+        caller: Address,
+        factor_bps: i128,
+    ) -> Result<(), bad_debt::BadDebtError> {
+        bad_debt::set_insurance_factor(&env, caller, factor_bps)
+    }
+
+    /// Get the share of accrued interest diverted to the insurance reserve (bps)
+    pub fn get_insurance_factor(env: Env) -> i128 {
+        bad_debt::get_insurance_factor(&env)
+    }
+
+    /// Get the insurance reserve balance
+    pub fn get_insurance_reserve(env: Env) -> i128 {
+        bad_debt::get_insurance_reserve(&env)
+    }
+
+    /// Get the unresolved bad debt for an asset
+    pub fn get_bad_debt(env: Env, asset: Option<Address>) -> i128 {
+        bad_debt::get_bad_debt(&env, asset)
+    }
+
+    /// Cover an asset's bad debt from insurance and socialize the rest (admin or liquidation admin)
+    pub fn socialize_bad_debt(
+        env: Env,
+        caller: Address,
+        asset: Option<Address>,
+    ) -> Result<bad_debt::BadDebtResolution, bad_debt::BadDebtError> {
+        bad_debt::socialize_bad_debt(&env, caller, asset)
+    }
+
+    /// Get a position's insolvency flag, set when its collateral was found
+    /// worth less than its debt
+    pub fn get_insolvency(env: Env, user: Address) -> Option<bad_debt::InsolvencyRecord> {
+        bad_debt::get_insolvency(&env, &user)
+    }
+
+    /// Refresh analytics for a user
+    pub fn refresh_user_analytics(_env: Env, _user: Address) -> Result<(), RiskManagementError> {
         Ok(())
     }
 
+    /// Claim accumulated protocol reserves (admin or treasurer)
+    pub fn claim_reserves(env: Env, caller: Address, asset: Option<Address>, to: Address, amount: i128) -> Result<(), RiskManagementError> {
+        reserve::claim_protocol_reserves(&env, caller, asset, to, amount)
+            .map(|_| ())
+            .map_err(|e| match e {
+                reserve::ReserveError::Unauthorized => RiskManagementError::Unauthorized,
+                _ => RiskManagementError::InvalidParameter,
+            })
+    }
+
+    /// Set the share of borrower interest booked to reserves for an asset (admin or risk admin)
+    ///
+    /// # Arguments
+    /// * `caller` - The admin or a risk admin
+    /// * `asset` - The asset (None for native)
+    /// * `reserve_factor_bps` - Reserve factor in basis points (0-5000)
+    pub fn set_reserve_factor(
+        env: Env,
+        caller: Address,
+        asset: Option<Address>,
+        reserve_factor_bps: i128,
+    ) -> Result<(), reserve::ReserveError> {
+        reserve::set_reserve_factor(&env, caller, asset, reserve_factor_bps)
+    }
+
+    /// Get the reserve factor for an asset (in basis points)
+    pub fn get_reserve_factor(env: Env, asset: Option<Address>) -> i128 {
+        reserve::get_reserve_factor(&env, asset)
+    }
+
+    /// Get lifetime reserve accounting (interest booked, claimed) for an asset
+    pub fn get_reserve_account(env: Env, asset: Option<Address>) -> reserve::ReserveAccount {
+        reserve::get_reserve_account(&env, asset)
+    }
+
     /// Get current protocol reserve balance for an asset
     pub fn get_reserve_balance(env: Env, asset: Option<Address>) -> i128 {
         let reserve_key = DepositDataKey::ProtocolReserve(asset);
@@ -537,7 +1328,8 @@ pub fn ms_execute(
     /// into a single [`ProtocolReport`] snapshot.
     ///
     /// # Returns
-    /// A `ProtocolReport` containing current protocol metrics and timestamp.
+    /// A `ProtocolReport` containing current protocol metrics, per-asset markets,
+    /// protocol totals valued at oracle prices, and timestamp.
     ///
     /// # Errors
     /// Returns `AnalyticsError` if protocol data is not initialized or computation overflows.
@@ -545,6 +1337,14 @@ pub fn ms_execute(
         generate_protocol_report(&env)
     }
 
+    /// Get the supply, borrow and reserve aggregates of an asset
+    ///
+    /// # Arguments
+    /// * `asset` - The asset address (None for native XLM)
+    pub fn get_asset_ledger(env: Env, asset: Option<Address>) -> deposit::AssetLedger {
+        deposit::get_asset_ledger(&env, &asset)
+    }
+
     /// Generate a comprehensive report for a specific user.
     ///
     /// Includes the user's position, health factor, risk level, activity history,
@@ -650,6 +1450,309 @@ pub fn ms_execute(
         oracle::set_fallback_oracle(&env, caller, asset, fallback_oracle).expect("Oracle error")
     }
 
+    /// Register or remove the SEP-40 oracle that prices an asset by pull (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The caller address (must be admin)
+    /// * `asset` - The asset address
+    /// * `source` - SEP-40 oracle, feed asset and target decimals; None removes it
+    pub fn set_sep40_source(
+        env: Env,
+        caller: Address,
+        asset: Address,
+        source: Option<oracle::Sep40Source>,
+    ) -> Result<(), oracle::OracleError> {
+        oracle::set_sep40_source(&env, caller, asset, source)
+    }
+
+    /// Get the SEP-40 oracle registered for an asset
+    pub fn get_sep40_source(env: Env, asset: Address) -> Option<oracle::Sep40Source> {
+        oracle::get_sep40_source(&env, &asset)
+    }
+
+    /// Set or remove an asset's explicit failover chain (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The caller address (must be admin)
+    /// * `asset` - The asset address
+    /// * `policy` - Fallback and TWAP steps tried after the primary source;
+    ///   None restores the implicit resolution order
+    pub fn set_failover_policy(
+        env: Env,
+        caller: Address,
+        asset: Address,
+        policy: Option<oracle::FailoverPolicy>,
+    ) -> Result<(), oracle::OracleError> {
+        oracle::set_failover_policy(&env, caller, asset, policy)
+    }
+
+    /// Get an asset's failover policy
+    pub fn get_failover_policy(env: Env, asset: Address) -> Option<oracle::FailoverPolicy> {
+        oracle::get_failover_policy(&env, &asset)
+    }
+
+    /// Get the source currently pricing an asset under its failover policy
+    pub fn get_failover_state(env: Env, asset: Address) -> Option<oracle::FailoverState> {
+        oracle::get_failover_state(&env, &asset)
+    }
+
+    /// Re-walk an asset's failover chain and record the source that prices it
+    pub fn refresh_failover_state(
+        env: Env,
+        asset: Address,
+    ) -> Result<oracle::FailoverState, oracle::OracleError> {
+        oracle::refresh_failover_state(&env, &asset)
+    }
+
+    /// Pin an asset to a manual price during an oracle outage (admin or
+    /// guardian)
+    ///
+    /// # Arguments
+    /// * `caller` - The admin or a governance guardian
+    /// * `asset` - The asset address
+    /// * `price` - Override price, bounded relative to the last oracle price
+    /// * `expires_at` - Mandatory expiry (at most 24 hours ahead)
+    pub fn set_price_override(
+        env: Env,
+        caller: Address,
+        asset: Address,
+        price: i128,
+        expires_at: u64,
+    ) -> Result<oracle::PriceOverride, oracle::OracleError> {
+        oracle::set_price_override(&env, caller, asset, price, expires_at)
+    }
+
+    /// Remove an asset's emergency price override (admin or guardian)
+    pub fn clear_price_override(
+        env: Env,
+        caller: Address,
+        asset: Address,
+    ) -> Result<(), oracle::OracleError> {
+        oracle::clear_price_override(&env, caller, asset)
+    }
+
+    /// Get an asset's active emergency price override
+    pub fn get_price_override(env: Env, asset: Address) -> Option<oracle::PriceOverride> {
+        oracle::get_price_override(&env, &asset)
+    }
+
+    /// Set or remove heartbeat monitoring of an asset's price (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The admin
+    /// * `asset` - The asset address
+    /// * `config` - Heartbeat and liquidation safety buffer, or None to remove
+    pub fn set_price_heartbeat(
+        env: Env,
+        caller: Address,
+        asset: Address,
+        config: Option<oracle::HeartbeatConfig>,
+    ) -> Result<(), oracle::OracleError> {
+        oracle::set_price_heartbeat(&env, caller, asset, config)
+    }
+
+    /// Get the heartbeat monitoring of an asset's price
+    pub fn get_price_heartbeat(env: Env, asset: Address) -> Option<oracle::HeartbeatConfig> {
+        oracle::get_price_heartbeat(&env, &asset)
+    }
+
+    /// Check an asset's heartbeat, recording degraded mode if it was missed
+    ///
+    /// # Returns
+    /// Whether the asset is degraded
+    pub fn check_price_heartbeat(env: Env, asset: Address) -> bool {
+        oracle::check_price_heartbeat(&env, &asset)
+    }
+
+    /// Get the recorded degraded mode of an asset
+    pub fn get_degraded_state(env: Env, asset: Address) -> Option<oracle::DegradedState> {
+        oracle::get_degraded_state(&env, &asset)
+    }
+
+    /// Set or remove the price circuit breaker of an asset (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The admin
+    /// * `asset` - The asset address
+    /// * `config` - Largest price move and window, or None to remove
+    pub fn set_circuit_breaker(
+        env: Env,
+        caller: Address,
+        asset: Address,
+        config: Option<oracle::CircuitBreakerConfig>,
+    ) -> Result<(), oracle::OracleError> {
+        oracle::set_circuit_breaker(&env, caller, asset, config)
+    }
+
+    /// Get the price circuit breaker of an asset
+    pub fn get_circuit_breaker(env: Env, asset: Address) -> Option<oracle::CircuitBreakerConfig> {
+        oracle::get_circuit_breaker(&env, &asset)
+    }
+
+    /// Get the price move that tripped an asset's circuit breaker
+    pub fn get_circuit_breaker_trip(
+        env: Env,
+        asset: Address,
+    ) -> Option<oracle::CircuitBreakerTrip> {
+        oracle::get_circuit_breaker_trip(&env, &asset)
+    }
+
+    /// Reset a tripped circuit breaker so borrows resume (admin or guardian)
+    pub fn reset_circuit_breaker(
+        env: Env,
+        caller: Address,
+        asset: Address,
+    ) -> Result<(), oracle::OracleError> {
+        oracle::reset_circuit_breaker(&env, caller, asset)
+    }
+
+    /// Configure the bond and rate-limit policy for price updaters (admin only)
+    pub fn set_updater_policy(
+        env: Env,
+        caller: Address,
+        policy: oracle::UpdaterPolicy,
+    ) -> Result<(), oracle::OracleError> {
+        oracle::set_updater_policy(&env, caller, policy)
+    }
+
+    /// Get the updater bond and rate-limit policy
+    pub fn get_updater_policy(env: Env) -> Option<oracle::UpdaterPolicy> {
+        oracle::get_updater_policy(&env)
+    }
+
+    /// Lock bond tokens so an updater may push prices
+    ///
+    /// # Returns
+    /// The updater's total bond
+    pub fn post_oracle_bond(
+        env: Env,
+        updater: Address,
+        amount: i128,
+    ) -> Result<i128, oracle::OracleError> {
+        oracle::post_oracle_bond(&env, updater, amount)
+    }
+
+    /// Withdraw bond tokens after the post-push lock period
+    ///
+    /// # Returns
+    /// The updater's remaining bond
+    pub fn withdraw_oracle_bond(
+        env: Env,
+        updater: Address,
+        amount: i128,
+    ) -> Result<i128, oracle::OracleError> {
+        oracle::withdraw_oracle_bond(&env, updater, amount)
+    }
+
+    /// Slash a manipulating updater's bond (admin/governance only)
+    ///
+    /// # Arguments
+    /// * `caller` - The caller address (must be admin)
+    /// * `updater` - The updater being slashed
+    /// * `amount` - Maximum amount to slash
+    /// * `recipient` - Receiver of the slashed tokens
+    ///
+    /// # Returns
+    /// The amount actually slashed
+    pub fn slash_oracle_bond(
+        env: Env,
+        caller: Address,
+        updater: Address,
+        amount: i128,
+        recipient: Address,
+    ) -> Result<i128, oracle::OracleError> {
+        oracle::slash_oracle_bond(&env, caller, updater, amount, recipient)
+    }
+
+    /// Get the bond currently locked by an updater
+    pub fn get_oracle_bond(env: Env, updater: Address) -> i128 {
+        oracle::get_oracle_bond(&env, &updater)
+    }
+
+    /// Get the time-weighted average price of an asset
+    ///
+    /// # Arguments
+    /// * `asset` - The asset address
+    /// * `window_secs` - Length of the trailing averaging window
+    pub fn get_twap(
+        env: Env,
+        asset: Address,
+        window_secs: u64,
+    ) -> Result<i128, oracle::OracleError> {
+        oracle::get_twap(&env, &asset, window_secs)
+    }
+
+    /// Get the recorded price observations for an asset, oldest first
+    pub fn get_price_history(env: Env, asset: Address) -> Vec<oracle::PriceObservation> {
+        oracle::get_price_history(&env, &asset)
+    }
+
+    /// Set the number of price observations kept per asset (admin only)
+    pub fn set_price_history_capacity(
+        env: Env,
+        caller: Address,
+        capacity: u32,
+    ) -> Result<(), oracle::OracleError> {
+        oracle::set_price_history_capacity(&env, caller, capacity)
+    }
+
+    /// Get the number of price observations kept per asset
+    pub fn get_price_history_capacity(env: Env) -> u32 {
+        oracle::get_price_history_capacity(&env)
+    }
+
+    /// Register an independent price feeder for an asset (admin only)
+    pub fn add_price_feeder(
+        env: Env,
+        caller: Address,
+        asset: Address,
+        feeder: Address,
+    ) -> Result<(), oracle::OracleError> {
+        oracle::add_price_feeder(&env, caller, asset, feeder)
+    }
+
+    /// Remove a price feeder for an asset (admin only)
+    pub fn remove_price_feeder(
+        env: Env,
+        caller: Address,
+        asset: Address,
+        feeder: Address,
+    ) -> Result<(), oracle::OracleError> {
+        oracle::remove_price_feeder(&env, caller, asset, feeder)
+    }
+
+    /// Get the feeders registered for an asset
+    pub fn get_price_feeders(env: Env, asset: Address) -> Vec<Address> {
+        oracle::get_price_feeders(&env, &asset)
+    }
+
+    /// Submit a price as a registered feeder
+    ///
+    /// # Returns
+    /// The median of the fresh feeder prices
+    pub fn submit_feeder_price(
+        env: Env,
+        feeder: Address,
+        asset: Address,
+        price: i128,
+    ) -> Result<i128, oracle::OracleError> {
+        oracle::submit_feeder_price(&env, feeder, asset, price)
+    }
+
+    /// Get the open dispute over an asset's feeder prices, if any
+    pub fn get_price_dispute(env: Env, asset: Address) -> Option<oracle::PriceDispute> {
+        oracle::get_price_dispute(&env, &asset)
+    }
+
+    /// Resolve a price dispute so borrows of the asset resume (admin only)
+    pub fn resolve_price_dispute(
+        env: Env,
+        caller: Address,
+        asset: Address,
+    ) -> Result<(), oracle::OracleError> {
+        oracle::resolve_price_dispute(&env, caller, asset)
+    }
+
     /// Get recent activity from analytics
     pub fn get_recent_activity(env: Env, limit: u32, offset: u32) -> Result<Vec<crate::analytics::ActivityEntry>, crate::analytics::AnalyticsError> {
         analytics::get_recent_activity(&env, limit, offset)
@@ -677,7 +1780,7 @@ pub fn ms_execute(
         risk_management::set_risk_params(&env, admin, min_collateral_ratio, liquidation_threshold, close_factor, liquidation_incentive)
     }
 
-    /// Set a pause switch for an operation (admin only)
+    /// Set a pause switch for an operation (admin or pauser)
     pub fn set_pause_switch(env: Env, admin: Address, operation: Symbol, paused: bool) -> Result<(), RiskManagementError> {
         risk_management::set_pause_switch(&env, admin, operation, paused)
     }
@@ -692,7 +1795,7 @@ pub fn ms_execute(
         risk_management::is_emergency_paused(&env)
     }
 
-    /// Set emergency pause (admin only)
+    /// Set emergency pause (admin or pauser)
     pub fn set_emergency_pause(env: Env, admin: Address, paused: bool) -> Result<(), RiskManagementError> {
         risk_management::set_emergency_pause(&env, admin, paused)
     }
@@ -738,21 +1841,87 @@ pub fn ms_execute(
         amm_swap(env, user, params)
     }
 
-    /// Register a bridge 
-    ///
-    /// # Arguments
-    /// * `caller` - Admin address for authorization
-    /// * `network_id` - ID of the remote network
-    /// * `bridge` - Address of the bridge contract
-    /// * `fee_bps` - Fee in basis points
-    pub fn register_bridge(
+    /// Get cumulative swap statistics for a token pair (order-independent)
+    pub fn get_amm_pair_stats(
         env: Env,
-        caller: Address,
-        network_id: u32,
-        bridge: Address,
-        fee_bps: i128,
-    ) -> Result<(), BridgeError> {
-        bridge::register_bridge(&env, caller, network_id, bridge, fee_bps)
+        token_a: Option<Address>,
+        token_b: Option<Address>,
+    ) -> stellarlend_amm::PairStats {
+        stellarlend_amm::get_pair_stats(&env, token_a, token_b)
+    }
+
+    /// Register the deployed AMM contract queried before auto-swaps (admin only)
+    pub fn set_amm_contract(env: Env, caller: Address, amm: Address) -> Result<(), AmmError> {
+        amm::set_amm_contract(&env, caller, amm)
+    }
+
+    /// Get the registered AMM contract, if any
+    pub fn get_amm_contract(env: Env) -> Option<Address> {
+        amm::get_amm_contract(&env)
+    }
+
+    /// Get the maximum slippage (in basis points) of the registered AMM contract
+    pub fn get_amm_max_slippage(env: Env) -> Result<i128, AmmError> {
+        amm::AmmReader::load(&env)?.max_slippage()
+    }
+
+    /// Get the enabled protocols of the registered AMM contract
+    pub fn get_amm_supported_protocols(env: Env) -> Result<Vec<Address>, AmmError> {
+        amm::AmmReader::load(&env)?.supported_protocols()
+    }
+
+    /// Get the health of an AMM pool for a token pair
+    ///
+    /// # Arguments
+    /// * `protocol` - AMM protocol address
+    /// * `token_a` - First token address (None for native XLM)
+    /// * `token_b` - Second token address (None for native XLM)
+    pub fn get_amm_pool_health(
+        env: Env,
+        protocol: Address,
+        token_a: Option<Address>,
+        token_b: Option<Address>,
+    ) -> Result<PoolHealth, AmmError> {
+        amm::AmmReader::load(&env)?.pool_health(&protocol, &token_a, &token_b)
+    }
+
+    /// Check that a swap would be accepted by the registered AMM contract
+    ///
+    /// # Arguments
+    /// * `protocol` - AMM protocol address
+    /// * `token_in` - Input token address (None for native XLM)
+    /// * `token_out` - Output token address (None for native XLM)
+    /// * `amount` - Amount to swap
+    /// * `slippage` - Intended slippage tolerance (in basis points)
+    ///
+    /// # Returns
+    /// The pool health snapshot when the swap is viable
+    pub fn preflight_amm_swap(
+        env: Env,
+        protocol: Address,
+        token_in: Option<Address>,
+        token_out: Option<Address>,
+        amount: i128,
+        slippage: i128,
+    ) -> Result<PoolHealth, AmmError> {
+        amm::preflight_amm_swap(&env, protocol, token_in, token_out, amount, slippage)
+    }
+
+    /// Register a bridge 
+    ///
+    /// # Arguments
+    /// * `caller` - Admin address for authorization
+    /// * `network_id` - ID of the remote network
+    /// * `bridge` - Address of the bridge contract
+    /// * `fee_bps` - Fee in basis points
+    pub fn register_bridge(
+        env: Env,
+        caller: Address,
+        network_id: u32,
+        bridge: Address,
+        fee_bps: i128,
+    ) -> Result<(), BridgeError> {
+        bridge::register_bridge(&env, caller, network_id, bridge, fee_bps)
     }
 
     /// Set bridge fee
@@ -814,6 +1983,283 @@ pub fn ms_execute(
         bridge::get_bridge_config(&env, network_id)
     }
 
+    /// Enqueue an attested inbound bridge message (bridge contract only)
+    ///
+    /// # Arguments
+    /// * `caller` - The registered bridge contract for `network_id`
+    /// * `network_id` - Remote network ID
+    /// * `user` - Recipient of the deposit
+    /// * `asset` - Asset to credit
+    /// * `amount` - Gross amount bridged in
+    ///
+    /// # Returns
+    /// The nonce assigned to the message
+    pub fn enqueue_bridge_message(
+        env: Env,
+        caller: Address,
+        network_id: u32,
+        user: Address,
+        asset: Option<Address>,
+        amount: i128,
+    ) -> Result<u64, BridgeError> {
+        bridge::enqueue_bridge_message(&env, caller, network_id, user, asset, amount)
+    }
+
+    /// Process pending inbound bridge messages in order
+    ///
+    /// Failed messages are marked and skipped; see `BridgeProcessResult`.
+    ///
+    /// # Arguments
+    /// * `network_id` - Remote network ID
+    /// * `max` - Maximum number of messages to process
+    pub fn process_bridge_messages(
+        env: Env,
+        network_id: u32,
+        max: u32,
+    ) -> Result<bridge::BridgeProcessResult, BridgeError> {
+        bridge::process_bridge_messages(&env, network_id, max)
+    }
+
+    /// Get the inbound queue cursors for a bridge
+    pub fn get_bridge_queue_state(env: Env, network_id: u32) -> bridge::BridgeQueueState {
+        bridge::get_bridge_queue_state(&env, network_id)
+    }
+
+    /// Get an inbound bridge message by nonce
+    pub fn get_bridge_message(
+        env: Env,
+        network_id: u32,
+        nonce: u64,
+    ) -> Option<bridge::BridgeMessage> {
+        bridge::get_bridge_message(&env, network_id, nonce)
+    }
+
+    /// Set the share of a bridge's fee owed to relayers (admin only)
+    pub fn set_bridge_relayer_share(
+        env: Env,
+        caller: Address,
+        network_id: u32,
+        share_bps: i128,
+    ) -> Result<(), BridgeError> {
+        bridge::set_bridge_relayer_share(&env, caller, network_id, share_bps)
+    }
+
+    /// Get the share of a bridge's fee owed to relayers (bps)
+    pub fn get_bridge_relayer_share(env: Env, network_id: u32) -> i128 {
+        bridge::get_bridge_relayer_share(&env, network_id)
+    }
+
+    /// Quote the total, protocol and relayer fee on a bridge transfer
+    pub fn quote_bridge_fee(
+        env: Env,
+        network_id: u32,
+        asset: Option<Address>,
+        amount: i128,
+    ) -> Result<bridge::BridgeFeeQuote, BridgeError> {
+        bridge::quote_bridge_fee(&env, network_id, asset, amount)
+    }
+
+    /// Register the validators that must attest a bridge's messages (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - Admin address for authorization
+    /// * `network_id` - ID of the remote network
+    /// * `validators` - Validator addresses; empty with a zero threshold removes the set
+    /// * `threshold` - Attestations required per message
+    pub fn register_bridge_validators(
+        env: Env,
+        caller: Address,
+        network_id: u32,
+        validators: soroban_sdk::Vec<Address>,
+        threshold: u32,
+    ) -> Result<(), BridgeError> {
+        bridge::register_bridge_validators(&env, caller, network_id, validators, threshold)
+    }
+
+    /// Get the validator set of a bridge
+    pub fn get_bridge_validators(env: Env, network_id: u32) -> Option<bridge::BridgeValidatorSet> {
+        bridge::get_bridge_validators(&env, network_id)
+    }
+
+    /// Attest a pending inbound bridge message (validators only)
+    ///
+    /// # Returns
+    /// Attestations from current validators the message now has
+    pub fn attest_bridge_message(
+        env: Env,
+        validator: Address,
+        network_id: u32,
+        nonce: u64,
+    ) -> Result<u32, BridgeError> {
+        bridge::attest_bridge_message(&env, validator, network_id, nonce)
+    }
+
+    /// Get the validators that attested an inbound bridge message
+    pub fn get_bridge_attestations(
+        env: Env,
+        network_id: u32,
+        nonce: u64,
+    ) -> soroban_sdk::Vec<Address> {
+        bridge::get_bridge_attestations(&env, network_id, nonce)
+    }
+
+    /// Set the challenge window bridged withdrawals are held for (admin only)
+    pub fn set_bridge_challenge_window(
+        env: Env,
+        caller: Address,
+        network_id: u32,
+        window: u64,
+    ) -> Result<(), BridgeError> {
+        bridge::set_bridge_challenge_window(&env, caller, network_id, window)
+    }
+
+    /// Get the challenge window of a bridge in seconds
+    pub fn get_bridge_challenge_window(env: Env, network_id: u32) -> u64 {
+        bridge::get_bridge_challenge_window(&env, network_id)
+    }
+
+    /// Get an outbound bridge transfer by id
+    pub fn get_bridge_transfer(env: Env, id: u64) -> Option<bridge::BridgeTransfer> {
+        bridge::get_bridge_transfer(&env, id)
+    }
+
+    /// List a bridge's pending outbound transfers
+    pub fn list_pending_transfers(
+        env: Env,
+        network_id: u32,
+    ) -> soroban_sdk::Vec<bridge::BridgeTransfer> {
+        bridge::list_pending_transfers(&env, network_id)
+    }
+
+    /// List a user's most recent outbound bridge transfers
+    pub fn get_user_transfers(env: Env, user: Address) -> soroban_sdk::Vec<bridge::BridgeTransfer> {
+        bridge::get_user_transfers(&env, user)
+    }
+
+    /// Complete a pending transfer after its challenge window (relayer only)
+    ///
+    /// # Arguments
+    /// * `relayer` - The registered bridge contract of the transfer's network
+    /// * `id` - The transfer to complete
+    ///
+    /// # Returns
+    /// The net amount carried out
+    pub fn complete_bridge_transfer(
+        env: Env,
+        relayer: Address,
+        id: u64,
+    ) -> Result<i128, BridgeError> {
+        bridge::complete_bridge_transfer(&env, relayer, id)
+    }
+
+    /// Refund a transfer the relayer did not complete in time (transfer's user only)
+    ///
+    /// # Returns
+    /// The gross amount returned to the user's collateral
+    pub fn refund_bridge_transfer(env: Env, user: Address, id: u64) -> Result<i128, BridgeError> {
+        bridge::refund_bridge_transfer(&env, user, id)
+    }
+
+    /// Veto a pending transfer during its challenge window (guardians only)
+    pub fn veto_bridge_transfer(env: Env, guardian: Address, id: u64) -> Result<(), BridgeError> {
+        bridge::veto_bridge_transfer(&env, guardian, id)
+    }
+
+    /// Set or clear a bridge's per-epoch outflow cap, all assets combined (admin only)
+    pub fn set_bridge_outflow_cap(
+        env: Env,
+        caller: Address,
+        network_id: u32,
+        cap: Option<i128>,
+    ) -> Result<(), BridgeError> {
+        bridge::set_bridge_outflow_cap(&env, caller, network_id, cap)
+    }
+
+    /// Set or clear the per-epoch outflow cap of one asset through a bridge (admin only)
+    pub fn set_bridge_asset_outflow_cap(
+        env: Env,
+        caller: Address,
+        network_id: u32,
+        asset: Option<Address>,
+        cap: Option<i128>,
+    ) -> Result<(), BridgeError> {
+        bridge::set_bridge_asset_outflow_cap(&env, caller, network_id, asset, cap)
+    }
+
+    /// Pause or resume withdrawals through a bridge (admin only)
+    pub fn set_bridge_outflows_paused(
+        env: Env,
+        caller: Address,
+        network_id: u32,
+        paused: bool,
+    ) -> Result<(), BridgeError> {
+        bridge::set_bridge_outflows_paused(&env, caller, network_id, paused)
+    }
+
+    /// Whether withdrawals through a bridge are paused
+    pub fn is_bridge_outflow_paused(env: Env, network_id: u32) -> bool {
+        bridge::is_bridge_outflow_paused(&env, network_id)
+    }
+
+    /// Get a bridge's outflow cap usage in the current epoch
+    pub fn get_bridge_utilization(env: Env, network_id: u32) -> bridge::BridgeUtilization {
+        bridge::get_bridge_utilization(&env, network_id)
+    }
+
+    /// Get one asset's outflow cap usage through a bridge in the current epoch
+    pub fn get_bridge_asset_utilization(
+        env: Env,
+        network_id: u32,
+        asset: Option<Address>,
+    ) -> bridge::BridgeUtilization {
+        bridge::get_bridge_asset_utilization(&env, network_id, asset)
+    }
+
+    /// Enqueue an attested report of collateral locked remotely (bridge contract only)
+    ///
+    /// # Arguments
+    /// * `caller` - The registered bridge contract for `network_id`
+    /// * `network_id` - Remote network ID
+    /// * `user` - User whose collateral is locked
+    /// * `asset` - Local asset the collateral is valued as
+    /// * `amount` - Total amount locked (0 when released)
+    ///
+    /// # Returns
+    /// The nonce assigned to the message
+    pub fn enqueue_remote_collateral(
+        env: Env,
+        caller: Address,
+        network_id: u32,
+        user: Address,
+        asset: Option<Address>,
+        amount: i128,
+    ) -> Result<u64, BridgeError> {
+        bridge::enqueue_remote_collateral(&env, caller, network_id, user, asset, amount)
+    }
+
+    /// Get the collateral a user has locked on remote networks
+    pub fn get_remote_collateral(
+        env: Env,
+        user: Address,
+    ) -> soroban_sdk::Vec<bridge::RemoteCollateral> {
+        bridge::get_remote_collateral(&env, user)
+    }
+
+    /// Configure the haircut and maximum age of remote collateral (admin only)
+    pub fn set_remote_collateral_config(
+        env: Env,
+        caller: Address,
+        haircut_bps: i128,
+        max_age: u64,
+    ) -> Result<(), BridgeError> {
+        bridge::set_remote_collateral_config(&env, caller, haircut_bps, max_age)
+    }
+
+    /// Get how remote collateral is recognized, if it is
+    pub fn get_remote_collateral_config(env: Env) -> Option<bridge::RemoteCollateralConfig> {
+        bridge::get_remote_collateral_config(&env)
+    }
+
     /// Set a configuration value (admin only)
     ///
     /// # Arguments
@@ -1112,90 +2558,327 @@ pub fn ms_execute(
         get_user_position_summary(&env, &user)
     }
 
-    // ============================================================================
-    // Governance Entrypoints
-    // ============================================================================
-
-    /// Initialize governance module
-    ///
-    /// Sets up the governance system with voting token and parameters.
-    /// Must be called after contract initialization.
+    /// Set the interest rate model for a cross-asset market (admin only)
     ///
     /// # Arguments
-    /// * `admin` - The admin address
-    /// * `vote_token` - Token contract address used for voting
-    /// * `voting_period` - Optional voting duration in seconds
-    /// * `execution_delay` - Optional delay before execution in seconds
-    /// * `quorum_bps` - Optional quorum requirement in basis points
-    /// * `proposal_threshold` - Optional minimum tokens to create proposal
-    /// * `timelock_duration` - Optional timelock duration in seconds
-    /// * `default_voting_threshold` - Optional default voting threshold in basis points
+    /// * `asset` - Asset address (None for XLM)
+    /// * `model` - Base rate, kink and multipliers in basis points
     ///
     /// # Returns
     /// Returns Ok(()) on success
-    pub fn gov_initialize(
+    pub fn set_asset_rate_model(
         env: Env,
-        admin: Address,
-        vote_token: Address,
-        voting_period: Option<u64>,
-        execution_delay: Option<u64>,
-        quorum_bps: Option<u32>,
-        proposal_threshold: Option<i128>,
-        timelock_duration: Option<u64>,
-        default_voting_threshold: Option<i128>,
-    ) -> Result<(), errors::GovernanceError> {
-        governance::initialize(
-            &env,
-            admin,
-            vote_token,
-            voting_period,
-            execution_delay,
-            quorum_bps,
-            proposal_threshold,
-            timelock_duration,
-            default_voting_threshold,
-        )
+        asset: Option<Address>,
+        model: AssetRateModel,
+    ) -> Result<(), CrossAssetError> {
+        cross_asset::set_asset_rate_model(&env, asset, model)
     }
 
-    /// Create a new governance proposal
+    /// Get the interest rate model for a cross-asset market, if set
     ///
     /// # Arguments
-    /// * `proposer` - Address creating the proposal
-    /// * `proposal_type` - Type of proposal (parameter change, pause, etc.)
-    /// * `description` - Description of the proposal
-    /// * `voting_threshold` - Optional custom voting threshold
-    ///
-    /// # Returns
-    /// Returns the new proposal ID
-    pub fn gov_create_proposal(
-        env: Env,
-        proposer: Address,
-        proposal_type: ProposalType,
-        description: String,
-        voting_threshold: Option<i128>,
-    ) -> Result<u64, errors::GovernanceError> {
-        governance::create_proposal(&env, proposer, proposal_type, description, voting_threshold)
+    /// * `asset` - Asset address (None for XLM)
+    pub fn get_asset_rate_model(env: Env, asset: Option<Address>) -> Option<AssetRateModel> {
+        cross_asset::get_asset_rate_model(&env, asset)
     }
 
-    /// Cast a vote on a proposal
+    /// Get the current annual borrow rate for a cross-asset market
     ///
     /// # Arguments
-    /// * `voter` - Address casting the vote
-    /// * `proposal_id` - ID of the proposal to vote on
-    /// * `vote_type` - Vote choice (For, Against, Abstain)
+    /// * `asset` - Asset address (None for XLM)
     ///
     /// # Returns
-    /// Returns Ok(()) on success
-    pub fn gov_vote(
-        env: Env,
-        voter: Address,
-        proposal_id: u64,
-        vote_type: VoteType,
-    ) -> Result<(), errors::GovernanceError> {
-        governance::vote(&env, voter, proposal_id, vote_type)
+    /// Borrow rate in basis points (0 if the asset has no rate model)
+    pub fn get_asset_borrow_rate(env: Env, asset: Option<Address>) -> i128 {
+        cross_asset::get_asset_borrow_rate(&env, asset)
     }
 
-    /// Queue a successful proposal for execution
+    /// Select or clear the rate curve of an asset's market (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - Admin address
+    /// * `asset` - Asset address (None for XLM)
+    /// * `curve` - Linear, kinked, two-slope or fixed curve (None to fall back
+    ///   to the asset's rate model)
+    pub fn set_asset_rate_curve(
+        env: Env,
+        caller: Address,
+        asset: Option<Address>,
+        curve: Option<interest_rate::RateCurve>,
+    ) -> Result<(), InterestRateError> {
+        interest_rate::set_asset_rate_curve(&env, caller, asset, curve)
+    }
+
+    /// Get the rate curve selected for an asset's market, if any
+    pub fn get_asset_rate_curve(
+        env: Env,
+        asset: Option<Address>,
+    ) -> Option<interest_rate::RateCurve> {
+        interest_rate::get_asset_rate_curve(&env, &asset)
+    }
+
+    /// Register or update a cross-asset netting pair (admin only)
+    ///
+    /// Offsetting collateral and debt in a correlated pair reduce the
+    /// weighted debt used for the health factor, subject to caps.
+    ///
+    /// # Arguments
+    /// * `collateral_asset` - Collateral side of the pair (None for XLM)
+    /// * `debt_asset` - Debt side of the pair (None for XLM)
+    /// * `netting_factor_bps` - Share of the offset removed from weighted debt
+    /// * `max_benefit_value` - Per-user cap on the benefit in USD (7 decimals)
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn set_netting_pair(
+        env: Env,
+        collateral_asset: Option<Address>,
+        debt_asset: Option<Address>,
+        netting_factor_bps: i128,
+        max_benefit_value: i128,
+    ) -> Result<(), CrossAssetError> {
+        cross_asset::set_netting_pair(
+            &env,
+            collateral_asset,
+            debt_asset,
+            netting_factor_bps,
+            max_benefit_value,
+        )
+    }
+
+    /// Remove a cross-asset netting pair (admin only)
+    pub fn remove_netting_pair(
+        env: Env,
+        collateral_asset: Option<Address>,
+        debt_asset: Option<Address>,
+    ) -> Result<(), CrossAssetError> {
+        cross_asset::remove_netting_pair(&env, collateral_asset, debt_asset)
+    }
+
+    /// Get all cross-asset netting pairs
+    pub fn get_netting_pairs(env: Env) -> soroban_sdk::Vec<cross_asset::NettingPair> {
+        cross_asset::get_netting_pairs(&env)
+    }
+
+    /// Define or update an e-mode category (admin only)
+    ///
+    /// # Arguments
+    /// * `category_id` - Category identifier (must be non-zero)
+    /// * `category` - Boosted parameters and member assets
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn set_emode_category(
+        env: Env,
+        category_id: u32,
+        category: cross_asset::EModeCategory,
+    ) -> Result<(), CrossAssetError> {
+        cross_asset::set_emode_category(&env, category_id, category)
+    }
+
+    /// Get an e-mode category by id
+    pub fn get_emode_category(env: Env, category_id: u32) -> Option<cross_asset::EModeCategory> {
+        cross_asset::get_emode_category(&env, category_id)
+    }
+
+    /// Opt a position into an e-mode category (0 to leave e-mode)
+    ///
+    /// # Arguments
+    /// * `user` - User address (must authorize)
+    /// * `category_id` - Category to enter, or 0 to leave
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn set_user_emode(
+        env: Env,
+        user: Address,
+        category_id: u32,
+    ) -> Result<(), CrossAssetError> {
+        cross_asset::set_user_emode(&env, user, category_id)
+    }
+
+    /// Get the e-mode category a user has opted into (0 if none)
+    pub fn get_user_emode(env: Env, user: Address) -> u32 {
+        cross_asset::get_user_emode(&env, &user)
+    }
+
+    /// Configure isolation mode for an asset (admin only)
+    ///
+    /// # Arguments
+    /// * `asset` - Asset to configure (None for XLM)
+    /// * `is_isolated` - Whether the asset may only be used as sole collateral
+    /// * `debt_ceiling` - Maximum debt value in USD borrowed against it (0 = unlimited)
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn set_asset_isolation(
+        env: Env,
+        asset: Option<Address>,
+        is_isolated: bool,
+        debt_ceiling: i128,
+    ) -> Result<(), CrossAssetError> {
+        cross_asset::set_asset_isolation(&env, asset, is_isolated, debt_ceiling)
+    }
+
+    /// Get the debt value in USD borrowed against an isolated asset
+    pub fn get_isolated_debt(env: Env, asset: Option<Address>) -> i128 {
+        cross_asset::get_isolated_debt(&env, asset)
+    }
+
+    /// Set the utilization buffer for an asset (admin only)
+    ///
+    /// # Arguments
+    /// * `asset` - Asset to configure (None for XLM)
+    /// * `buffer_bps` - Share of supply kept unborrowed in basis points (0 = disabled)
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn ca_set_utilization_buffer(
+        env: Env,
+        asset: Option<Address>,
+        buffer_bps: i128,
+    ) -> Result<(), CrossAssetError> {
+        cross_asset::set_utilization_buffer(&env, asset, buffer_bps)
+    }
+
+    /// Get remaining supply and borrow headroom for an asset
+    ///
+    /// Reflects the supply cap, borrow cap and utilization buffer across both
+    /// cross-asset and core positions.
+    pub fn ca_get_remaining_caps(
+        env: Env,
+        asset: Option<Address>,
+    ) -> Result<cross_asset::RemainingCaps, CrossAssetError> {
+        cross_asset::get_remaining_caps(&env, asset)
+    }
+
+    /// Get an asset's supply not lent out (total supply minus total borrows)
+    pub fn get_available_liquidity(env: Env, asset: Option<Address>) -> i128 {
+        cross_asset::get_available_liquidity(&env, asset)
+    }
+
+    /// Get the largest collateral withdrawal a user can make now
+    ///
+    /// Bounded by the user's collateral, the minimum collateral ratio and the
+    /// asset's available liquidity.
+    pub fn get_max_withdraw(env: Env, user: Address, asset: Option<Address>) -> i128 {
+        withdraw::get_max_withdraw(&env, &user, asset)
+    }
+
+    /// Value an amount of a listed asset in USD (7 decimals)
+    ///
+    /// Uses the same listed price and rounding as position valuation.
+    pub fn to_value(
+        env: Env,
+        asset: Option<Address>,
+        amount: i128,
+    ) -> Result<i128, CrossAssetError> {
+        cross_asset::to_value(&env, asset, amount)
+    }
+
+    /// Amount of a listed asset worth a USD value (7 decimals), rounded down
+    pub fn from_value(
+        env: Env,
+        asset: Option<Address>,
+        value: i128,
+    ) -> Result<i128, CrossAssetError> {
+        cross_asset::from_value(&env, asset, value)
+    }
+
+    /// Scale an amount by a basis point factor, rounding as the contract does
+    pub fn apply_bps(_env: Env, amount: i128, bps: i128) -> i128 {
+        cross_asset::apply_bps(amount, bps)
+    }
+
+    /// Ratio of two values in basis points (non-zero denominator), rounding as the contract does
+    pub fn ratio_bps(_env: Env, numerator: i128, denominator: i128) -> i128 {
+        cross_asset::ratio_bps(numerator, denominator)
+    }
+
+    // ============================================================================
+    // Governance Entrypoints
+    // ============================================================================
+
+    /// Initialize governance module
+    ///
+    /// Sets up the governance system with voting token and parameters.
+    /// Must be called after contract initialization.
+    ///
+    /// # Arguments
+    /// * `admin` - The admin address
+    /// * `vote_token` - Token contract address used for voting
+    /// * `voting_period` - Optional voting duration in seconds
+    /// * `execution_delay` - Optional delay before execution in seconds
+    /// * `quorum_bps` - Optional quorum requirement in basis points
+    /// * `proposal_threshold` - Optional minimum tokens to create proposal
+    /// * `timelock_duration` - Optional timelock duration in seconds
+    /// * `default_voting_threshold` - Optional default voting threshold in basis points
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn gov_initialize(
+        env: Env,
+        admin: Address,
+        vote_token: Address,
+        voting_period: Option<u64>,
+        execution_delay: Option<u64>,
+        quorum_bps: Option<u32>,
+        proposal_threshold: Option<i128>,
+        timelock_duration: Option<u64>,
+        default_voting_threshold: Option<i128>,
+    ) -> Result<(), errors::GovernanceError> {
+        governance::initialize(
+            &env,
+            admin,
+            vote_token,
+            voting_period,
+            execution_delay,
+            quorum_bps,
+            proposal_threshold,
+            timelock_duration,
+            default_voting_threshold,
+        )
+    }
+
+    /// Create a new governance proposal
+    ///
+    /// # Arguments
+    /// * `proposer` - Address creating the proposal
+    /// * `proposal_type` - Type of proposal (parameter change, pause, etc.)
+    /// * `description` - Description of the proposal
+    /// * `voting_threshold` - Optional custom voting threshold
+    ///
+    /// # Returns
+    /// Returns the new proposal ID
+    pub fn gov_create_proposal(
+        env: Env,
+        proposer: Address,
+        proposal_type: ProposalType,
+        description: String,
+        voting_threshold: Option<i128>,
+    ) -> Result<u64, errors::GovernanceError> {
+        governance::create_proposal(&env, proposer, proposal_type, description, voting_threshold)
+    }
+
+    /// Cast a vote on a proposal
+    ///
+    /// # Arguments
+    /// * `voter` - Address casting the vote
+    /// * `proposal_id` - ID of the proposal to vote on
+    /// * `vote_type` - Vote choice (For, Against, Abstain)
+    ///
+    /// # Returns
+    /// Returns Ok(()) on success
+    pub fn gov_vote(
+        env: Env,
+        voter: Address,
+        proposal_id: u64,
+        vote_type: VoteType,
+    ) -> Result<(), errors::GovernanceError> {
+        governance::vote(&env, voter, proposal_id, vote_type)
+    }
+
+    /// Queue a successful proposal for execution
     ///
     /// # Arguments
     /// * `caller` - Address queuing the proposal
@@ -1279,6 +2962,125 @@ pub fn ms_execute(
         governance::set_multisig_config(&env, caller, admins, threshold)
     }
 
+    /// Register the vesting contract holding locked vote tokens
+    ///
+    /// Its balance cannot vote; vested, unclaimed grants count towards
+    /// their beneficiaries' voting power.
+    ///
+    /// # Arguments
+    /// * `caller` - Caller address (must be admin)
+    /// * `vesting_contract` - Vesting contract address
+    pub fn gov_set_vesting_contract(
+        env: Env,
+        caller: Address,
+        vesting_contract: Address,
+    ) -> Result<(), errors::GovernanceError> {
+        governance::set_vesting_contract(&env, caller, vesting_contract)
+    }
+
+    /// Get the registered vesting contract
+    pub fn gov_get_vesting_contract(env: Env) -> Option<Address> {
+        governance::get_vesting_contract(&env)
+    }
+
+    /// Switch between live-balance and checkpointed voting (admin only)
+    pub fn gov_set_checkpointed_voting(
+        env: Env,
+        caller: Address,
+        enabled: bool,
+    ) -> Result<(), errors::GovernanceError> {
+        governance::set_checkpointed_voting(&env, caller, enabled)
+    }
+
+    /// Whether votes come from checkpointed deposits
+    pub fn gov_is_checkpointed_voting(env: Env) -> bool {
+        governance::is_checkpointed_voting(&env)
+    }
+
+    /// Deposit vote tokens, adding their votes to the account's delegate
+    pub fn gov_deposit_votes(
+        env: Env,
+        account: Address,
+        amount: i128,
+    ) -> Result<(), errors::GovernanceError> {
+        governance::deposit_votes(&env, account, amount)
+    }
+
+    /// Withdraw deposited vote tokens
+    pub fn gov_withdraw_votes(
+        env: Env,
+        account: Address,
+        amount: i128,
+    ) -> Result<(), errors::GovernanceError> {
+        governance::withdraw_votes(&env, account, amount)
+    }
+
+    /// Delegate the votes of the delegator's deposit (to itself to undelegate)
+    pub fn gov_delegate(
+        env: Env,
+        delegator: Address,
+        delegatee: Address,
+    ) -> Result<(), errors::GovernanceError> {
+        governance::delegate(&env, delegator, delegatee)
+    }
+
+    /// Get the account voting with an account's deposit
+    pub fn gov_get_delegate(env: Env, account: Address) -> Address {
+        governance::get_delegate(&env, &account)
+    }
+
+    /// Get the vote tokens an account has deposited
+    pub fn gov_get_voting_deposit(env: Env, account: Address) -> i128 {
+        governance::get_voting_deposit(&env, &account)
+    }
+
+    /// Get the votes currently delegated to an account
+    pub fn gov_get_current_votes(env: Env, account: Address) -> i128 {
+        governance::get_current_votes(&env, &account)
+    }
+
+    /// Get the votes delegated to an account at the end of a past timestamp
+    pub fn gov_get_prior_votes(
+        env: Env,
+        account: Address,
+        timestamp: u64,
+    ) -> Result<i128, errors::GovernanceError> {
+        governance::get_prior_votes(&env, &account, timestamp)
+    }
+
+    /// Set the voting strategy of a proposal kind, or the default with `None`
+    /// (admin only)
+    ///
+    /// # Arguments
+    /// * `kind` - Proposal kind name (e.g. "min_collateral_ratio", "list_asset")
+    /// * `strategy` - Token-weighted, quadratic or one-address-one-vote
+    pub fn gov_set_voting_strategy(
+        env: Env,
+        caller: Address,
+        kind: Option<Symbol>,
+        strategy: types::VotingStrategy,
+    ) -> Result<(), errors::GovernanceError> {
+        governance::set_voting_strategy(&env, caller, kind, strategy)
+    }
+
+    /// Set the minimum votes cast for quorum under a strategy (admin only)
+    pub fn gov_set_strategy_quorum(
+        env: Env,
+        caller: Address,
+        strategy: types::VotingStrategy,
+        min_votes: i128,
+    ) -> Result<(), errors::GovernanceError> {
+        governance::set_strategy_quorum(&env, caller, strategy, min_votes)
+    }
+
+    /// Get the voting strategy new proposals of this type are created with
+    pub fn gov_get_voting_strategy(
+        env: Env,
+        proposal_type: ProposalType,
+    ) -> Result<types::VotingStrategy, errors::GovernanceError> {
+        governance::get_voting_strategy(&env, &proposal_type)
+    }
+
     /// Add a guardian
     ///
     /// # Arguments
@@ -1486,6 +3288,1289 @@ pub fn ms_execute(
     ) -> Result<i128, BridgeError> {
         bridge_withdraw(&env, user, network_id, asset, amount)
     }
+    // ============================================================================
+    // Per-Market Liquidation Pause
+    // ============================================================================
+
+    /// Pause liquidations for a single market (admin or pauser)
+    ///
+    /// The pause expires automatically after `duration_seconds`, bounded by
+    /// `MAX_LIQUIDATION_PAUSE_SECONDS`.
+    ///
+    /// # Arguments
+    /// * `caller` - The caller address (must be admin or pauser)
+    /// * `asset` - The market to pause (None for native XLM)
+    /// * `duration_seconds` - How long the pause lasts
+    ///
+    /// # Returns
+    /// Returns the timestamp at which the pause expires
+    pub fn pause_asset_liquidations(
+        env: Env,
+        caller: Address,
+        asset: Option<Address>,
+        duration_seconds: u64,
+    ) -> Result<u64, RiskManagementError> {
+        risk_management::pause_asset_liquidations(&env, caller, asset, duration_seconds)
+    }
+
+    /// Lift a per-market liquidation pause early (admin or pauser)
+    pub fn unpause_asset_liquidations(
+        env: Env,
+        caller: Address,
+        asset: Option<Address>,
+    ) -> Result<(), RiskManagementError> {
+        risk_management::unpause_asset_liquidations(&env, caller, asset)
+    }
+
+    /// Get the expiry of the active liquidation pause for a market, if any
+    pub fn get_liquidation_pause_expiry(env: Env, asset: Option<Address>) -> Option<u64> {
+        risk_management::get_asset_liquidation_pause_expiry(&env, asset)
+    }
+
+    // ============================================================================
+    // State Snapshot Exports
+    // ============================================================================
+
+    /// Export global protocol state for audits and indexer snapshots
+    pub fn export_protocol_snapshot(env: Env) -> snapshot::ProtocolSnapshot {
+        snapshot::export_protocol_snapshot(&env)
+    }
+
+    /// Export a page of user positions in deterministic index order
+    ///
+    /// # Arguments
+    /// * `offset` - Index of the first user to return
+    /// * `limit` - Maximum number of entries (capped at 50)
+    pub fn export_user_positions(
+        env: Env,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<snapshot::UserSnapshotEntry> {
+        snapshot::export_user_positions(&env, offset, limit)
+    }
+
+    /// Export a page of cross-asset market configurations in registration order
+    ///
+    /// # Arguments
+    /// * `offset` - Index of the first asset to return
+    /// * `limit` - Maximum number of entries (capped at 50)
+    pub fn export_asset_configs(
+        env: Env,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<snapshot::AssetSnapshotEntry> {
+        snapshot::export_asset_configs(&env, offset, limit)
+    }
+
+    /// Export a page of asset ledgers, with reserves and accrued fees, in first-seen order
+    ///
+    /// # Arguments
+    /// * `offset` - Index of the first asset to return
+    /// * `limit` - Maximum number of entries (capped at 50)
+    pub fn export_asset_ledgers(
+        env: Env,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<snapshot::AssetLedgerSnapshotEntry> {
+        snapshot::export_asset_ledgers(&env, offset, limit)
+    }
+
+    // ============================================================================
+    // Borrow APR Caps per User Class
+    // ============================================================================
+
+    /// Assign or clear a borrower's user class (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The admin address
+    /// * `user` - The borrower address
+    /// * `class` - The user class (None to clear)
+    pub fn set_user_class(
+        env: Env,
+        caller: Address,
+        user: Address,
+        class: Option<Symbol>,
+    ) -> Result<(), InterestRateError> {
+        interest_rate::set_user_class(&env, caller, user, class)
+    }
+
+    /// Set or clear the borrow APR cap for a user class (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The admin address
+    /// * `class` - The user class
+    /// * `cap_bps` - Annual borrow rate cap in basis points (None to clear)
+    pub fn set_class_rate_cap(
+        env: Env,
+        caller: Address,
+        class: Symbol,
+        cap_bps: Option<i128>,
+    ) -> Result<(), InterestRateError> {
+        interest_rate::set_class_rate_cap(&env, caller, class, cap_bps)
+    }
+
+    /// Get the borrow APR cap that applies to a borrower, if any
+    pub fn get_user_rate_cap(env: Env, user: Address) -> Option<i128> {
+        interest_rate::get_user_rate_cap(&env, &user)
+    }
+
+    /// Earmark an asset's reserves as its borrow APR subsidy budget (admin or treasurer)
+    ///
+    /// # Returns
+    /// The asset's new subsidy budget
+    pub fn allocate_subsidy_budget(
+        env: Env,
+        caller: Address,
+        asset: Option<Address>,
+        amount: i128,
+    ) -> Result<i128, reserve::ReserveError> {
+        reserve::allocate_subsidy_budget(&env, caller, asset, amount)
+    }
+
+    /// Get an asset's remaining borrow APR subsidy budget
+    pub fn get_subsidy_budget(env: Env, asset: Option<Address>) -> i128 {
+        reserve::get_subsidy_budget(&env, asset)
+    }
+
+    // ============================================================================
+    // Flash Loans
+    // ============================================================================
+
+    /// Execute a flash loan through a receiver contract
+    ///
+    /// Transfers `amount` to `callback`, invokes its `execute_operation` and
+    /// verifies principal plus fee was returned before this call ends.
+    ///
+    /// # Arguments
+    /// * `user` - The address initiating the loan (determines the fee tier)
+    /// * `asset` - The asset to borrow
+    /// * `amount` - The amount to borrow
+    /// * `callback` - The receiver contract implementing `FlashLoanReceiver`
+    /// * `params` - Opaque data forwarded to the receiver
+    ///
+    /// # Returns
+    /// The total amount repaid (principal + fee)
+    pub fn execute_flash_loan(
+        env: Env,
+        user: Address,
+        asset: Address,
+        amount: i128,
+        callback: Address,
+        params: soroban_sdk::Bytes,
+    ) -> Result<i128, FlashLoanError> {
+        execute_flash_loan(&env, user, asset, amount, callback, params)
+    }
+
+    /// Set the base flash loan fee in basis points (admin only)
+    pub fn set_flash_loan_fee(
+        env: Env,
+        caller: Address,
+        fee_bps: i128,
+    ) -> Result<(), FlashLoanError> {
+        set_flash_loan_fee(&env, caller, fee_bps)
+    }
+
+    /// Update the flash loan configuration (admin only)
+    pub fn configure_flash_loan(
+        env: Env,
+        caller: Address,
+        config: FlashLoanConfig,
+    ) -> Result<(), FlashLoanError> {
+        configure_flash_loan(&env, caller, config)
+    }
+
+    // ============================================================================
+    // Flash Loan Fee Tiers
+    // ============================================================================
+
+    /// Register or deregister a flash loan integrator (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The admin address
+    /// * `integrator` - The integrator address
+    /// * `enabled` - Whether the integrator fee applies
+    pub fn set_flash_loan_integrator(
+        env: Env,
+        caller: Address,
+        integrator: Address,
+        enabled: bool,
+    ) -> Result<(), flash_loan::FlashLoanError> {
+        flash_loan::set_flash_loan_integrator(&env, caller, integrator, enabled)
+    }
+
+    /// Set the flash loan integrator fee, volume tiers and epoch length (admin only)
+    pub fn configure_flash_loan_fee_tiers(
+        env: Env,
+        caller: Address,
+        tiers: flash_loan::FlashLoanFeeTiers,
+    ) -> Result<(), flash_loan::FlashLoanError> {
+        flash_loan::configure_flash_loan_fee_tiers(&env, caller, tiers)
+    }
+
+    /// Get the flash loan integrator fee, volume tiers and epoch length
+    pub fn get_flash_loan_fee_tiers(env: Env) -> flash_loan::FlashLoanFeeTiers {
+        flash_loan::get_flash_loan_fee_tiers(&env)
+    }
+
+    /// Get the flash loan fee (in basis points) a user would pay next
+    pub fn get_flash_loan_fee_bps(env: Env, user: Address) -> i128 {
+        flash_loan::get_flash_loan_fee_bps(&env, &user)
+    }
+
+    /// Get a user's flash loan volume in the current fee epoch
+    pub fn get_flash_loan_epoch_volume(env: Env, user: Address) -> i128 {
+        flash_loan::get_flash_loan_epoch_volume(&env, &user)
+    }
+
+    // ============================================================================
+    // Protocol Fees
+    // ============================================================================
+
+    /// Set an asset's origination, flash loan and withdrawal fees (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The admin address
+    /// * `asset` - The asset (None for native XLM)
+    /// * `config` - Fee rates in basis points (each at most 10%)
+    pub fn set_asset_fees(
+        env: Env,
+        caller: Address,
+        asset: Option<Address>,
+        config: fees::FeeConfig,
+    ) -> Result<(), fees::FeesError> {
+        fees::set_fee_config(&env, caller, asset, config)
+    }
+
+    /// Get an asset's protocol fee rates
+    pub fn get_asset_fees(env: Env, asset: Option<Address>) -> fees::FeeConfig {
+        fees::get_fee_config(&env, &asset)
+    }
+
+    /// Set the address protocol fees are paid to (admin only)
+    pub fn set_fee_collector(
+        env: Env,
+        caller: Address,
+        collector: Address,
+    ) -> Result<(), fees::FeesError> {
+        fees::set_fee_collector(&env, caller, collector)
+    }
+
+    /// Get the fee collector
+    pub fn get_fee_collector(env: Env) -> Option<Address> {
+        fees::get_fee_collector(&env)
+    }
+
+    /// Turn protocol fees on or off (admin only; needs a fee collector)
+    pub fn set_fee_switch(env: Env, caller: Address, enabled: bool) -> Result<(), fees::FeesError> {
+        fees::set_fee_switch(&env, caller, enabled)
+    }
+
+    /// Check whether protocol fees are charged
+    pub fn is_fee_switch_on(env: Env) -> bool {
+        fees::is_fee_switch_on(&env)
+    }
+
+    /// Get an asset's accrued, unclaimed protocol fees
+    pub fn get_accrued_fees(env: Env, asset: Option<Address>) -> i128 {
+        fees::get_accrued_fees(&env, &asset)
+    }
+
+    /// Claim an asset's accrued fees (fee collector only)
+    pub fn claim_fees(
+        env: Env,
+        caller: Address,
+        asset: Option<Address>,
+    ) -> Result<i128, fees::FeesError> {
+        fees::claim_fees(&env, caller, asset)
+    }
+
+    /// Pay the accrued fees of several assets to the fee collector (anyone)
+    ///
+    /// # Returns
+    /// The amount paid out per asset
+    pub fn sweep_fees(
+        env: Env,
+        assets: Vec<Option<Address>>,
+    ) -> Result<Vec<i128>, fees::FeesError> {
+        fees::sweep_fees(&env, assets)
+    }
+
+    // ============================================================================
+    // Liquidity Mining Rewards
+    // ============================================================================
+
+    /// Set the token rewards are paid in (admin only, once)
+    pub fn set_reward_token(
+        env: Env,
+        caller: Address,
+        token: Address,
+    ) -> Result<(), rewards::RewardsError> {
+        rewards::set_reward_token(&env, caller, token)
+    }
+
+    /// Get the reward token
+    pub fn get_reward_token(env: Env) -> Option<Address> {
+        rewards::get_reward_token(&env)
+    }
+
+    /// Transfer reward tokens from the admin into the reward budget
+    ///
+    /// # Returns
+    /// The new budget
+    pub fn fund_rewards(
+        env: Env,
+        caller: Address,
+        amount: i128,
+    ) -> Result<i128, rewards::RewardsError> {
+        rewards::fund_rewards(&env, caller, amount)
+    }
+
+    /// Get the funded rewards not yet emitted
+    pub fn get_reward_budget(env: Env) -> i128 {
+        rewards::get_reward_budget(&env)
+    }
+
+    /// Set the reward tokens emitted per second to an asset's suppliers or
+    /// borrowers (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The admin address
+    /// * `asset` - The asset (None for native XLM)
+    /// * `side` - Supply or borrow side
+    /// * `rate_per_second` - Emission rate (0 stops emissions)
+    pub fn set_emission_rate(
+        env: Env,
+        caller: Address,
+        asset: Option<Address>,
+        side: rewards::RewardSide,
+        rate_per_second: i128,
+    ) -> Result<(), rewards::RewardsError> {
+        rewards::set_emission_rate(&env, caller, asset, side, rate_per_second)
+    }
+
+    /// Get the emission rate of an asset's supply or borrow side
+    pub fn get_emission_rate(env: Env, asset: Option<Address>, side: rewards::RewardSide) -> i128 {
+        rewards::get_emission_rate(&env, &asset, side)
+    }
+
+    /// Get the reward index of an asset's supply or borrow side
+    pub fn get_reward_index(
+        env: Env,
+        asset: Option<Address>,
+        side: rewards::RewardSide,
+    ) -> rewards::RewardIndex {
+        rewards::get_market_index(&env, &asset, side)
+    }
+
+    /// Get the rewards a user could claim now
+    pub fn get_pending_rewards(env: Env, user: Address) -> i128 {
+        rewards::get_pending_rewards(&env, &user)
+    }
+
+    /// Claim all of a user's accrued rewards
+    ///
+    /// # Returns
+    /// The amount claimed
+    pub fn claim_rewards(env: Env, user: Address) -> Result<i128, rewards::RewardsError> {
+        rewards::claim_rewards(&env, user)
+    }
+
+    // ============================================================================
+    // Safety Module
+    // ============================================================================
+
+    /// Set the token staked in the safety module (admin only, once)
+    pub fn set_staking_token(
+        env: Env,
+        caller: Address,
+        token: Address,
+    ) -> Result<(), safety_module::SafetyModuleError> {
+        safety_module::set_staking_token(&env, caller, token)
+    }
+
+    /// Get the token staked in the safety module
+    pub fn get_staking_token(env: Env) -> Option<Address> {
+        safety_module::get_staking_token(&env)
+    }
+
+    /// Set the cooldown, unstake window, slashing cap and staker fee share
+    /// (admin only)
+    pub fn set_safety_module_config(
+        env: Env,
+        caller: Address,
+        config: safety_module::SafetyModuleConfig,
+    ) -> Result<(), safety_module::SafetyModuleError> {
+        safety_module::set_config(&env, caller, config)
+    }
+
+    /// Get the safety module parameters
+    pub fn get_safety_module_config(env: Env) -> safety_module::SafetyModuleConfig {
+        safety_module::get_config(&env)
+    }
+
+    /// Get the safety module pool totals
+    pub fn get_safety_module_state(env: Env) -> safety_module::SafetyModuleState {
+        safety_module::get_state(&env)
+    }
+
+    /// Get a staker's shares, stake value and cooldown
+    pub fn get_stake(env: Env, staker: Address) -> safety_module::StakeInfo {
+        safety_module::get_stake(&env, &staker)
+    }
+
+    /// Get a staker's claimable fees in an asset
+    pub fn get_pending_staking_fees(env: Env, staker: Address, asset: Option<Address>) -> i128 {
+        safety_module::get_pending_fees(&env, &staker, &asset)
+    }
+
+    /// Stake tokens into the safety module
+    ///
+    /// # Returns
+    /// The shares minted
+    pub fn stake(
+        env: Env,
+        staker: Address,
+        amount: i128,
+    ) -> Result<i128, safety_module::SafetyModuleError> {
+        safety_module::stake(&env, staker, amount)
+    }
+
+    /// Start the cooldown before unstaking
+    pub fn start_unstake_cooldown(
+        env: Env,
+        staker: Address,
+    ) -> Result<(), safety_module::SafetyModuleError> {
+        safety_module::start_cooldown(&env, staker)
+    }
+
+    /// Withdraw staked tokens within the unstake window after the cooldown
+    ///
+    /// # Returns
+    /// The shares burned
+    pub fn unstake(
+        env: Env,
+        staker: Address,
+        amount: i128,
+    ) -> Result<i128, safety_module::SafetyModuleError> {
+        safety_module::unstake(&env, staker, amount)
+    }
+
+    /// Claim a staker's share of protocol fees in an asset
+    pub fn claim_staking_fees(
+        env: Env,
+        staker: Address,
+        asset: Option<Address>,
+    ) -> Result<i128, safety_module::SafetyModuleError> {
+        safety_module::claim_staking_fees(&env, staker, asset)
+    }
+
+    /// Slash the safety module to cover an asset's shortfall
+    /// (admin/governance only, at most `max_slash_bps` of the pool)
+    pub fn slash_safety_module(
+        env: Env,
+        caller: Address,
+        asset: Option<Address>,
+        amount: i128,
+        recipient: Address,
+    ) -> Result<(), safety_module::SafetyModuleError> {
+        safety_module::slash(&env, caller, asset, amount, recipient)
+    }
+
+    // ============================================================================
+    // Position Archive
+    // ============================================================================
+
+    /// Archive the idle, debt-free positions among `users` (keeper, at most 50)
+    ///
+    /// # Returns
+    /// The number of positions archived
+    pub fn archive_idle_positions(
+        env: Env,
+        keeper: Address,
+        users: Vec<Address>,
+    ) -> Result<u32, archive::ArchiveError> {
+        archive::archive_idle_positions(&env, keeper, users)
+    }
+
+    /// Restore the caller's archived position
+    pub fn restore_position(
+        env: Env,
+        user: Address,
+    ) -> Result<deposit::Position, archive::ArchiveError> {
+        archive::restore_position(&env, user)
+    }
+
+    /// Get an account's archived position, if any
+    pub fn get_archived_position(env: Env, user: Address) -> Option<archive::ArchivedPosition> {
+        archive::get_archived_position(&env, &user)
+    }
+
+    // ============================================================================
+    // Position Hedging Hooks
+    // ============================================================================
+
+    /// Set (or replace) the caller's hedge instruction for keepers to execute
+    ///
+    /// # Arguments
+    /// * `user` - The position owner (must authorize)
+    /// * `instruction` - Assets, health factor trigger, share sold, slippage
+    ///   bound, cooldown, execution limit and expiry
+    pub fn set_hedge_instruction(
+        env: Env,
+        user: Address,
+        instruction: hedging::HedgeInstruction,
+    ) -> Result<(), hedging::HedgeError> {
+        hedging::set_hedge_instruction(&env, user, instruction)
+    }
+
+    /// Remove the caller's hedge instruction
+    pub fn cancel_hedge_instruction(env: Env, user: Address) -> Result<(), hedging::HedgeError> {
+        hedging::cancel_hedge_instruction(&env, user)
+    }
+
+    /// Execute a user's hedge instruction once its health factor trigger is hit (keeper)
+    ///
+    /// # Returns
+    /// Collateral sold, swap proceeds, debt repaid and the health factor before and after
+    pub fn execute_hedge(
+        env: Env,
+        keeper: Address,
+        user: Address,
+    ) -> Result<hedging::HedgeResult, hedging::HedgeError> {
+        hedging::execute_hedge(&env, keeper, user)
+    }
+
+    /// Get a user's hedge instruction and execution state
+    pub fn get_hedge_hook(env: Env, user: Address) -> Option<hedging::HedgeHook> {
+        hedging::get_hedge_hook(&env, &user)
+    }
+
+    // ============================================================================
+    // Keeper Sponsorship Pool
+    // ============================================================================
+
+    /// Set the native XLM paid per sponsored keeper call and per-keeper daily cap (admin only)
+    pub fn set_keeper_pool_config(
+        env: Env,
+        caller: Address,
+        config: keeper_pool::KeeperPoolConfig,
+    ) -> Result<(), keeper_pool::KeeperPoolError> {
+        keeper_pool::set_keeper_pool_config(&env, caller, config)
+    }
+
+    /// Add or remove a keeper from the sponsorship whitelist (admin only)
+    pub fn set_sponsored_keeper(
+        env: Env,
+        caller: Address,
+        keeper: Address,
+        sponsored: bool,
+    ) -> Result<(), keeper_pool::KeeperPoolError> {
+        keeper_pool::set_sponsored_keeper(&env, caller, keeper, sponsored)
+    }
+
+    /// Transfer native XLM from `funder` (e.g. the treasury) into the keeper pool
+    ///
+    /// # Returns
+    /// The new pool balance
+    pub fn fund_keeper_pool(
+        env: Env,
+        funder: Address,
+        amount: i128,
+    ) -> Result<i128, keeper_pool::KeeperPoolError> {
+        keeper_pool::fund_keeper_pool(&env, funder, amount)
+    }
+
+    /// Extend the TTL of the contract instance and `users`' positions (keeper, at most 50)
+    ///
+    /// # Returns
+    /// The number of users with a position
+    pub fn bump_ttl(
+        env: Env,
+        keeper: Address,
+        users: Vec<Address>,
+    ) -> Result<u32, keeper_pool::KeeperPoolError> {
+        keeper_pool::bump_ttl(&env, keeper, users)
+    }
+
+    /// Advance the global borrow index to now and refresh the day's metrics (keeper)
+    pub fn poke_rates(env: Env, keeper: Address) -> Result<i128, keeper_pool::KeeperPoolError> {
+        keeper_pool::poke_rates(&env, keeper)
+    }
+
+    /// Get the keeper payout settings, if configured
+    pub fn get_keeper_pool_config(env: Env) -> Option<keeper_pool::KeeperPoolConfig> {
+        keeper_pool::get_keeper_pool_config(&env)
+    }
+
+    /// Get the native XLM available for keeper payouts
+    pub fn get_keeper_pool_balance(env: Env) -> i128 {
+        keeper_pool::get_keeper_pool_balance(&env)
+    }
+
+    /// Whether a keeper is paid for its calls
+    pub fn is_sponsored_keeper(env: Env, keeper: Address) -> bool {
+        keeper_pool::is_sponsored_keeper(&env, &keeper)
+    }
+
+    /// Get the amount paid to a keeper today
+    pub fn get_keeper_paid_today(env: Env, keeper: Address) -> i128 {
+        keeper_pool::get_keeper_paid_today(&env, &keeper)
+    }
+
+    // ============================================================================
+    // Automation Jobs
+    // ============================================================================
+
+    /// Register a job run by keepers at most once per `interval` seconds,
+    /// paying `reward` native XLM from the keeper pool (admin only)
+    ///
+    /// # Returns
+    /// The job id
+    pub fn register_job(
+        env: Env,
+        caller: Address,
+        target: automation::JobTarget,
+        args: Vec<Val>,
+        interval: u64,
+        reward: i128,
+    ) -> Result<u32, automation::AutomationError> {
+        automation::register_job(&env, caller, target, args, interval, reward)
+    }
+
+    /// Remove a job (admin only)
+    pub fn remove_job(
+        env: Env,
+        caller: Address,
+        job_id: u32,
+    ) -> Result<(), automation::AutomationError> {
+        automation::remove_job(&env, caller, job_id)
+    }
+
+    /// Pause or resume a job (admin only)
+    pub fn set_job_paused(
+        env: Env,
+        caller: Address,
+        job_id: u32,
+        paused: bool,
+    ) -> Result<(), automation::AutomationError> {
+        automation::set_job_paused(&env, caller, job_id, paused)
+    }
+
+    /// Pause or resume all automation jobs (admin only)
+    pub fn set_automation_paused(
+        env: Env,
+        caller: Address,
+        paused: bool,
+    ) -> Result<(), automation::AutomationError> {
+        automation::set_automation_paused(&env, caller, paused)
+    }
+
+    /// Run a due job (keeper)
+    ///
+    /// # Returns
+    /// The reward paid (0 when the keeper pool cannot cover it)
+    pub fn execute_job(
+        env: Env,
+        keeper: Address,
+        job_id: u32,
+    ) -> Result<i128, automation::AutomationError> {
+        automation::execute_job(&env, keeper, job_id)
+    }
+
+    /// Get a job and its last execution
+    pub fn get_job(env: Env, job_id: u32) -> Option<automation::AutomationJob> {
+        automation::get_job(&env, job_id)
+    }
+
+    /// Get all registered jobs, oldest first
+    pub fn get_jobs(env: Env) -> Vec<automation::AutomationJob> {
+        automation::get_jobs(&env)
+    }
+
+    /// Whether all automation jobs are paused
+    pub fn is_automation_paused(env: Env) -> bool {
+        automation::is_automation_paused(&env)
+    }
+
+    // ============================================================================
+    // Protocol Financials
+    // ============================================================================
+
+    /// Get the revenue and expense statement of a 30-day period
+    ///
+    /// # Arguments
+    /// * `period` - Period index (`timestamp / 2_592_000`)
+    ///
+    /// # Returns
+    /// Per-asset interest, fees, rewards, subsidies and keeper payouts, and net revenue
+    pub fn get_financials(env: Env, period: u64) -> financials::FinancialStatement {
+        financials::get_financials(&env, period)
+    }
+
+    /// Get the index of the current financial reporting period
+    pub fn get_financial_period(env: Env) -> u64 {
+        financials::get_financial_period(&env)
+    }
+
+    // ============================================================================
+    // Protocol Invariants
+    // ============================================================================
+
+    /// Recompute aggregate totals against positions and reserves (anyone)
+    ///
+    /// # Returns
+    /// Per-market totals, the cross-market debt check and any violations;
+    /// also emitted as an invariant report event
+    pub fn assert_invariants(env: Env) -> invariants::InvariantReport {
+        invariants::assert_invariants(&env)
+    }
+
+    // ============================================================================
+    // Metric History
+    // ============================================================================
+
+    /// Get a metric for every recorded day in a range (at most 90 days)
+    ///
+    /// # Arguments
+    /// * `metric` - TVL, borrows, utilization, borrow rate or supply rate
+    /// * `from_day` - First day (`timestamp / 86_400`)
+    /// * `to_day` - Last day, inclusive
+    ///
+    /// # Returns
+    /// One point per day with a snapshot, in day order
+    pub fn get_metric_history(
+        env: Env,
+        metric: metric_history::Metric,
+        from_day: u64,
+        to_day: u64,
+    ) -> Result<Vec<metric_history::MetricPoint>, metric_history::MetricHistoryError> {
+        metric_history::get_metric_history(&env, metric, from_day, to_day)
+    }
+
+    /// Get all metrics recorded for a day, if still kept
+    pub fn get_daily_metrics(env: Env, day: u64) -> Option<metric_history::DailyMetrics> {
+        metric_history::get_daily_metrics(&env, day)
+    }
+
+    /// Get the index of the current metric day
+    pub fn get_metric_day(env: Env) -> u64 {
+        metric_history::get_metric_day(&env)
+    }
+
+    // ============================================================================
+    // Daily Borrow Limits
+    // ============================================================================
+
+    /// Set or clear the protocol-wide daily borrow cap (admin or risk admin)
+    ///
+    /// # Arguments
+    /// * `caller` - The admin or a risk admin
+    /// * `cap` - Maximum volume borrowable per day (None to disable)
+    pub fn set_global_daily_borrow_cap(
+        env: Env,
+        caller: Address,
+        cap: Option<i128>,
+    ) -> Result<(), RiskManagementError> {
+        risk_management::set_global_daily_borrow_cap(&env, caller, cap)
+    }
+
+    /// Set or clear the daily borrow cap for an asset (admin or risk admin)
+    ///
+    /// # Arguments
+    /// * `caller` - The admin or a risk admin
+    /// * `asset` - The asset (None for native XLM)
+    /// * `cap` - Maximum volume borrowable per day (None to disable)
+    pub fn set_asset_daily_borrow_cap(
+        env: Env,
+        caller: Address,
+        asset: Option<Address>,
+        cap: Option<i128>,
+    ) -> Result<(), RiskManagementError> {
+        risk_management::set_asset_daily_borrow_cap(&env, caller, asset, cap)
+    }
+
+    /// Get the protocol-wide daily borrow limit status
+    pub fn get_global_daily_borrow_status(env: Env) -> risk_management::DailyBorrowStatus {
+        risk_management::get_global_daily_borrow_status(&env)
+    }
+
+    /// Get the daily borrow limit status for an asset
+    pub fn get_asset_daily_borrow_status(
+        env: Env,
+        asset: Option<Address>,
+    ) -> risk_management::DailyBorrowStatus {
+        risk_management::get_asset_daily_borrow_status(&env, asset)
+    }
+
+    // ============================================================================
+    // Receipt Tokens
+    // ============================================================================
+
+    /// Register the slToken and debtToken contracts (admin only)
+    ///
+    /// Both must be SEP-41 receipt tokens administered by this contract.
+    /// Pass `None` for a token to stop issuing it.
+    pub fn set_receipt_tokens(
+        env: Env,
+        caller: Address,
+        tokens: tokenization::ReceiptTokens,
+    ) -> Result<(), tokenization::TokenizationError> {
+        tokenization::set_receipt_tokens(&env, caller, tokens)
+    }
+
+    /// Get the registered receipt tokens
+    pub fn get_receipt_tokens(env: Env) -> tokenization::ReceiptTokens {
+        tokenization::get_receipt_tokens(&env)
+    }
+
+    // ============================================================================
+    // Borrower Notifications
+    // ============================================================================
+
+    /// Register or replace the caller's contact hint for liquidation warnings
+    ///
+    /// The hint (hash of an encrypted contact, or a webhook id) is attached to
+    /// health threshold events so opted-in notification services can reach the
+    /// borrower.
+    ///
+    /// # Arguments
+    /// * `user` - The borrower
+    /// * `hint` - 32-byte contact hint
+    pub fn set_contact_hint(env: Env, user: Address, hint: BytesN<32>) {
+        notifications::set_contact_hint(&env, user, hint)
+    }
+
+    /// Remove the caller's contact hint
+    pub fn clear_contact_hint(env: Env, user: Address) {
+        notifications::clear_contact_hint(&env, user)
+    }
+
+    /// Get a borrower's contact hint, if registered
+    pub fn get_contact_hint(env: Env, user: Address) -> Option<BytesN<32>> {
+        notifications::get_contact_hint(&env, &user)
+    }
+
+    /// Set the health factor thresholds for notification events (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The admin address
+    /// * `thresholds` - Warning, critical and liquidation imminent health factors
+    pub fn set_health_notify_thresholds(
+        env: Env,
+        caller: Address,
+        thresholds: notifications::HealthNotificationThresholds,
+    ) -> Result<(), notifications::NotificationError> {
+        notifications::set_health_notification_thresholds(&env, caller, thresholds)
+    }
+
+    /// Get the health factor thresholds for notification events
+    pub fn get_health_notify_thresholds(env: Env) -> notifications::HealthNotificationThresholds {
+        notifications::get_health_notification_thresholds(&env)
+    }
+
+    // ============================================================================
+    // Exit Exemptions
+    // ============================================================================
+
+    /// Add or remove a protocol-owned account on the exit exemption list (admin or risk admin)
+    ///
+    /// Exempt accounts pay no exit fees and bypass withdrawal rate limits.
+    ///
+    /// # Arguments
+    /// * `caller` - The admin or a risk admin
+    /// * `account` - Vault strategy, treasury or other protocol-owned account
+    /// * `exempt` - Whether the account is exempt
+    pub fn set_exit_exemption(
+        env: Env,
+        caller: Address,
+        account: Address,
+        exempt: bool,
+    ) -> Result<(), RiskManagementError> {
+        risk_management::set_exit_exemption(&env, caller, account, exempt)
+    }
+
+    /// Get the accounts on the exit exemption list
+    pub fn get_exit_exemptions(env: Env) -> Vec<Address> {
+        risk_management::get_exit_exemptions(&env)
+    }
+
+    /// Set or clear the external risk engine hook (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The admin address
+    /// * `config` - Governance-whitelisted engine, per-operation value
+    ///   thresholds and failure policy; None disables the hook
+    pub fn set_risk_hook(
+        env: Env,
+        caller: Address,
+        config: Option<risk_hook::RiskHookConfig>,
+    ) -> Result<(), risk_hook::RiskHookError> {
+        risk_hook::set_risk_hook(&env, caller, config)
+    }
+
+    /// Get the active external risk engine hook, if any
+    pub fn get_risk_hook(env: Env) -> Option<risk_hook::RiskHookConfig> {
+        risk_hook::get_risk_hook(&env)
+    }
+
+    /// Whether governance has whitelisted a risk engine
+    pub fn is_risk_engine_whitelisted(env: Env, engine: Address) -> bool {
+        risk_hook::is_risk_engine_whitelisted(&env, &engine)
+    }
+
+    /// Set health factor tiers for the dynamic close factor (admin or liquidation admin)
+    ///
+    /// # Arguments
+    /// * `caller` - The admin or a liquidation admin
+    /// * `tiers` - Health tiers, most severe first; empty restores the base close factor
+    pub fn set_health_close_factor_tiers(
+        env: Env,
+        caller: Address,
+        tiers: Vec<risk_management::HealthCloseFactorTier>,
+    ) -> Result<(), RiskManagementError> {
+        risk_management::set_health_close_factor_tiers(&env, caller, tiers)
+    }
+
+    /// Get the configured health factor close factor tiers
+    pub fn get_health_close_factor_tiers(
+        env: Env,
+    ) -> Vec<risk_management::HealthCloseFactorTier> {
+        risk_management::get_health_close_factor_tiers(&env)
+    }
+
+    /// Set the price source for liquidation checks: spot or TWAP (admin or liquidation admin)
+    pub fn set_liquidation_price_source(
+        env: Env,
+        caller: Address,
+        source: risk_management::LiquidationPriceSource,
+    ) -> Result<(), RiskManagementError> {
+        risk_management::set_liquidation_price_source(&env, caller, source)
+    }
+
+    /// Set the liquidation hysteresis band in basis points, at most 100 (admin or liquidation admin)
+    pub fn set_liquidation_hysteresis(
+        env: Env,
+        caller: Address,
+        band_bps: i128,
+    ) -> Result<(), RiskManagementError> {
+        risk_management::set_liquidation_hysteresis(&env, caller, band_bps)
+    }
+
+    /// Get the liquidation hysteresis band in basis points
+    pub fn get_liquidation_hysteresis(env: Env) -> i128 {
+        risk_management::get_liquidation_hysteresis(&env)
+    }
+
+    /// Get the price source used for liquidation checks
+    pub fn get_liquidation_price_source(env: Env) -> risk_management::LiquidationPriceSource {
+        risk_management::get_liquidation_price_source(&env)
+    }
+
+    /// Get the close factor that applies to a borrower's position right now
+    ///
+    /// # Returns
+    /// Close factor in basis points (0 if the position is not liquidatable)
+    pub fn get_dynamic_close_factor(
+        env: Env,
+        borrower: Address,
+    ) -> Result<i128, RiskManagementError> {
+        risk_management::get_dynamic_close_factor(&env, &borrower)
+    }
+
+    // ============================================================================
+    // Accounting Currency
+    // ============================================================================
+
+    /// Set the accounting currency all valuations are expressed in (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The admin address
+    /// * `currency` - Display code and numeraire asset (None for the oracle quote currency)
+    pub fn set_accounting_currency(
+        env: Env,
+        caller: Address,
+        currency: accounting::AccountingCurrency,
+    ) -> Result<(), accounting::AccountingError> {
+        accounting::set_accounting_currency(&env, caller, currency)
+    }
+
+    /// Get the accounting currency
+    pub fn get_accounting_currency(env: Env) -> accounting::AccountingCurrency {
+        accounting::get_accounting_currency(&env)
+    }
+
+    /// Get an asset's spot price in the accounting currency (8 decimals)
+    pub fn get_normalized_price(
+        env: Env,
+        asset: Option<Address>,
+    ) -> Result<i128, accounting::AccountingError> {
+        accounting::get_normalized_price(&env, &asset)
+    }
+
+    /// Value an amount of an asset in the accounting currency (8 decimals)
+    pub fn to_accounting_value(
+        env: Env,
+        asset: Option<Address>,
+        amount: i128,
+    ) -> Result<i128, accounting::AccountingError> {
+        accounting::to_accounting_value(&env, &asset, amount)
+    }
+
+    /// Convert an amount of one asset into the equally valued amount of another
+    pub fn convert_amount(
+        env: Env,
+        from: Option<Address>,
+        to: Option<Address>,
+        amount: i128,
+    ) -> Result<i128, accounting::AccountingError> {
+        accounting::convert_amount(&env, &from, &to, amount)
+    }
+
+    // ============================================================================
+    // Compliance Controls
+    // ============================================================================
+
+    /// Set the allowlist requirement and external compliance hook (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The admin address
+    /// * `config` - Whether deposits and borrows need an allowlist entry, and
+    ///   the contract asked `check_allowed(user, op, asset, amount)` (None for no hook)
+    pub fn set_compliance_config(
+        env: Env,
+        caller: Address,
+        config: compliance::ComplianceConfig,
+    ) -> Result<(), compliance::ComplianceError> {
+        compliance::set_compliance_config(&env, caller, config)
+    }
+
+    /// Get the compliance settings
+    pub fn get_compliance_config(env: Env) -> compliance::ComplianceConfig {
+        compliance::get_compliance_config(&env)
+    }
+
+    /// Allow, deny or unlist an account (admin only)
+    pub fn set_compliance_status(
+        env: Env,
+        caller: Address,
+        user: Address,
+        status: Option<compliance::ComplianceStatus>,
+    ) -> Result<(), compliance::ComplianceError> {
+        compliance::set_compliance_status(&env, caller, user, status)
+    }
+
+    /// Get an account's allowlist or denylist entry
+    pub fn get_compliance_status(env: Env, user: Address) -> Option<compliance::ComplianceStatus> {
+        compliance::get_compliance_status(&env, &user)
+    }
+
+    /// Set or clear an account's collateral and debt caps (admin only)
+    pub fn set_user_position_caps(
+        env: Env,
+        caller: Address,
+        user: Address,
+        caps: Option<compliance::UserPositionCaps>,
+    ) -> Result<(), compliance::ComplianceError> {
+        compliance::set_user_position_caps(&env, caller, user, caps)
+    }
+
+    /// Get an account's collateral and debt caps
+    pub fn get_user_position_caps(env: Env, user: Address) -> Option<compliance::UserPositionCaps> {
+        compliance::get_user_position_caps(&env, &user)
+    }
+
+    // ============================================================================
+    // Interest Subsidy Campaigns
+    // ============================================================================
+
+    /// Close an asset's subsidy campaign once its window ended or its budget ran out
+    ///
+    /// Campaigns are opened by `SubsidyCampaign` governance proposals.
+    ///
+    /// # Returns
+    /// The unspent budget returned to the asset's protocol reserve
+    pub fn close_subsidy_campaign(
+        env: Env,
+        asset: Option<Address>,
+    ) -> Result<i128, subsidy_campaign::SubsidyCampaignError> {
+        subsidy_campaign::close_subsidy_campaign(&env, asset)
+    }
+
+    /// Get a subsidy campaign by id
+    pub fn get_subsidy_campaign(env: Env, id: u64) -> Option<subsidy_campaign::SubsidyCampaign> {
+        subsidy_campaign::get_subsidy_campaign(&env, id)
+    }
+
+    /// Get the open subsidy campaign on an asset, if any
+    pub fn get_asset_subsidy_campaign(
+        env: Env,
+        asset: Option<Address>,
+    ) -> Option<subsidy_campaign::SubsidyCampaign> {
+        subsidy_campaign::get_asset_subsidy_campaign(&env, &asset)
+    }
+
+    // ============================================================================
+    // Stable Rate Borrowing
+    // ============================================================================
+
+    /// Borrow assets in the chosen rate mode
+    ///
+    /// # Arguments
+    /// * `user` - The borrower
+    /// * `asset` - The asset to borrow (None for native XLM)
+    /// * `amount` - The amount to borrow
+    /// * `rate_mode` - `Variable` follows the market rate; `Stable` locks the
+    ///   current stable rate
+    ///
+    /// # Returns
+    /// The user's total debt after the borrow
+    pub fn borrow_asset_with_rate_mode(
+        env: Env,
+        user: Address,
+        asset: Option<Address>,
+        amount: i128,
+        rate_mode: stable_rate::RateMode,
+    ) -> Result<i128, crate::borrow::BorrowError> {
+        user.require_auth();
+        borrow::borrow_asset_with_rate_mode(&env, user, asset, amount, rate_mode)
+    }
+
+    /// Enable stable borrows and set the stable premium and rebalance threshold (admin only)
+    pub fn set_stable_rate_config(
+        env: Env,
+        caller: Address,
+        config: stable_rate::StableRateConfig,
+    ) -> Result<(), stable_rate::StableRateError> {
+        stable_rate::set_stable_rate_config(&env, caller, config)
+    }
+
+    /// Get the stable rate settings
+    pub fn get_stable_rate_config(env: Env) -> stable_rate::StableRateConfig {
+        stable_rate::get_stable_rate_config(&env)
+    }
+
+    /// Get the rate a stable borrow would lock now
+    pub fn get_stable_borrow_rate(env: Env) -> Result<i128, stable_rate::StableRateError> {
+        stable_rate::get_stable_borrow_rate(&env)
+    }
+
+    /// Get a user's principal split into variable and stable debt
+    pub fn get_debt_by_mode(env: Env, user: Address) -> stable_rate::DebtByMode {
+        stable_rate::get_debt_by_mode(&env, &user)
+    }
+
+    /// Reset a user's locked stable rate to the current stable rate
+    ///
+    /// Anyone can call this once the two rates differ by more than the
+    /// rebalance threshold.
+    ///
+    /// # Returns
+    /// The new locked rate
+    pub fn rebalance_stable_rate(
+        env: Env,
+        caller: Address,
+        user: Address,
+    ) -> Result<i128, stable_rate::StableRateError> {
+        caller.require_auth();
+        stable_rate::rebalance_stable_rate(&env, caller, user)
+    }
+
+    // ============================================================================
+    // Contract Upgrades
+    // ============================================================================
+
+    /// Schedule a Wasm upgrade, applicable once the upgrade timelock elapses (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The super admin
+    /// * `wasm_hash` - Hash of the already-uploaded Wasm
+    ///
+    /// # Returns
+    /// The earliest time the upgrade can be applied
+    pub fn schedule_upgrade(
+        env: Env,
+        caller: Address,
+        wasm_hash: BytesN<32>,
+    ) -> Result<u64, upgrade::UpgradeError> {
+        upgrade::schedule_upgrade(&env, caller, wasm_hash)
+    }
+
+    /// Cancel the scheduled Wasm upgrade (admin only)
+    pub fn cancel_upgrade(env: Env, caller: Address) -> Result<(), upgrade::UpgradeError> {
+        upgrade::cancel_upgrade(&env, caller)
+    }
+
+    /// Apply the scheduled Wasm upgrade after its timelock (admin only)
+    ///
+    /// Governance can upgrade without scheduling through an
+    /// `UpgradeContract` proposal. Call `migrate` afterwards if the new
+    /// code expects a newer storage layout.
+    pub fn upgrade_wasm(
+        env: Env,
+        caller: Address,
+        wasm_hash: BytesN<32>,
+    ) -> Result<(), upgrade::UpgradeError> {
+        upgrade::upgrade_wasm(&env, caller, wasm_hash)
+    }
+
+    /// Bring persistent storage up to the layout the running code expects (admin only)
+    ///
+    /// # Returns
+    /// The new storage version
+    pub fn migrate(env: Env, caller: Address) -> Result<u32, upgrade::UpgradeError> {
+        upgrade::migrate(&env, caller)
+    }
+
+    /// Get the storage layout version of the code and of persistent storage
+    pub fn get_contract_version(env: Env) -> upgrade::ContractVersion {
+        upgrade::get_contract_version(&env)
+    }
+
+    /// Get the scheduled Wasm upgrade, if any
+    pub fn get_pending_upgrade(env: Env) -> Option<upgrade::PendingUpgrade> {
+        upgrade::get_pending_upgrade(&env)
+    }
+
+    // ============================================================================
+    // Dead-man Switch
+    // ============================================================================
+
+    /// Admin heartbeat that resets the dead-man switch timer
+    pub fn admin_heartbeat(env: Env, caller: Address) -> Result<(), crate::admin::AdminError> {
+        crate::admin::admin_heartbeat(&env, caller)
+    }
+
+    /// Configure or disable the dead-man switch (admin only)
+    ///
+    /// # Arguments
+    /// * `caller` - The super admin
+    /// * `config` - Inactivity period and optional backup admin (None to disable)
+    pub fn configure_dead_man_switch(
+        env: Env,
+        caller: Address,
+        config: Option<crate::admin::DeadManSwitchConfig>,
+    ) -> Result<(), crate::admin::AdminError> {
+        crate::admin::configure_dead_man_switch(&env, caller, config)
+    }
+
+    /// Take over as super admin after the admin has been inactive (backup admin only)
+    pub fn claim_admin_on_inactivity(
+        env: Env,
+        backup: Address,
+    ) -> Result<(), crate::admin::AdminError> {
+        crate::admin::claim_admin_on_inactivity(&env, backup)
+    }
+
+    /// Whether the dead-man switch has triggered
+    pub fn is_admin_inactive(env: Env) -> bool {
+        crate::admin::is_admin_inactive(&env)
+    }
+
+    /// Timestamp of the admin's last recorded activity
+    pub fn get_last_admin_activity(env: Env) -> Option<u64> {
+        crate::admin::get_last_admin_activity(&env)
+    }
+
 }
 
 #[cfg(test)]
@@ -6,7 +6,8 @@
 //! collateral plus a liquidation incentive (bonus). This module uses the risk
 //! management system to determine:
 //! - Whether a position is eligible for liquidation (below liquidation threshold)
-//! - The maximum liquidatable amount (controlled by the close factor)
+//! - The maximum liquidatable amount (controlled by the close factor, which
+//!   may scale with how far the position is below the liquidation threshold)
 //! - The liquidation incentive awarded to the liquidator
 //!
 //! ## Cross-Asset Liquidation
@@ -19,24 +20,82 @@
 //! - Liquidation amount cannot exceed the close factor percentage of total debt.
 //! - Collateral seized cannot exceed the borrower's available collateral.
 //! - Interest is accrued on the borrower's position before liquidation.
+//! - Liquidations are rejected while either market involved has an active
+//!   (non-expired) per-market liquidation pause.
+//! - A liquidation that seizes the borrower's last collateral writes off the
+//!   remaining debt as bad debt (see `bad_debt.rs`).
+//! - An asset whose price missed its oracle heartbeat is valued at its last
+//!   good price less the safety buffer as collateral, and plus the buffer as
+//!   debt, unless an emergency price override is active.
+//!
+//! ## Dutch-Auction Liquidation
+//! As an alternative to the fixed incentive, anyone can `start_auction` on a
+//! liquidatable position. The collateral discount offered to bidders ramps
+//! linearly from 0% to the configured maximum incentive over the auction
+//! duration and stays at the maximum afterwards, so positions are cleared at
+//! the smallest discount a liquidator will accept. While an auction is open,
+//! fixed-incentive `liquidate` is rejected for that borrower. Auctions are
+//! closed with `settle_auction` once the position is healthy or the duration
+//! has elapsed, or by the admin with `cancel_auction`; closed auctions are
+//! kept in a bounded per-borrower history.
+//!
+//! ## Liquidator Statistics
+//! Every liquidation and auction bid adds to the liquidator's cumulative
+//! volume and incentive earned. The top `MAX_LEADERBOARD_SIZE` liquidators by
+//! debt repaid are kept ranked for keeper rewards and external analytics.
+//!
+//! ## Liquidation Archive
+//! Every liquidation and auction bid stores a compact `LiquidationReceipt`
+//! (parties, assets, amounts, accounting currency prices and timestamp)
+//! under a sequential id. The newest `MAX_ARCHIVED_RECEIPTS` are kept and
+//! can be read in pages, so disputes and analytics do not depend on event
+//! retention.
+//!
+//! ## Swap Liquidation
+//! `liquidate_and_swap` lets a liquidator without inventory of either asset
+//! clear a position: the seized collateral is swapped to the debt asset
+//! through the first enabled AMM protocol supporting the pair, the proceeds
+//! repay the debt and the surplus stays with the liquidator. The call reverts
+//! if the swap returns less than `min_out` or less than the debt repaid.
+//!
+//! `liquidate_with_flash_loan` goes one step further for liquidators without
+//! any inventory: the repaid debt is funded by an internal flash loan, just
+//! enough seized collateral is sold to repay the loan plus its fee, and the
+//! rest of the collateral and of the sale proceeds go to the liquidator. Any
+//! failing step reverts the whole call, including the loan.
+//!
+//! ## Borrower Index
+//! Every borrower with outstanding debt is kept in an on-chain index, updated
+//! whenever a position changes. `get_liquidatable_positions` pages through the
+//! index and returns the borrowers currently below the liquidation threshold
+//! with their debt and collateral breakdown, so liquidation bots can work
+//! against the contract instead of scanning events.
 
 #![allow(unused)]
-use crate::events::{emit_liquidation, LiquidationEvent};
-use soroban_sdk::{contracterror, Address, Env, IntoVal, Map, Symbol, Val, Vec};
+use crate::events::{
+    emit_liquidation, emit_liquidation_auction_bid, emit_liquidation_auction_closed,
+    emit_liquidation_auction_started, emit_liquidation_swap, LiquidationAuctionBidEvent,
+    LiquidationAuctionClosedEvent, LiquidationAuctionStartedEvent, LiquidationEvent,
+    LiquidationSwapEvent,
+};
+use soroban_sdk::{contracterror, contracttype, Address, Env, IntoVal, Map, Symbol, Val, Vec};
 
+use crate::accounting::{price_in_accounting, ACCOUNTING_SCALE};
 use crate::deposit::{
     add_activity_log, emit_analytics_updated_event, emit_position_updated_event,
-    emit_user_activity_tracked_event, update_protocol_analytics, AssetParams, DepositDataKey,
-    Position, ProtocolAnalytics, UserAnalytics,
+    emit_user_activity_tracked_event, token_address, update_protocol_analytics, AssetParams,
+    DepositDataKey, Position, ProtocolAnalytics, UserAnalytics,
 };
-use crate::oracle::get_price;
+use crate::oracle::{get_degraded_price, get_price, get_price_override, get_twap};
 use crate::risk_management::{
-    is_emergency_paused, is_operation_paused, require_operation_not_paused, RiskManagementError,
+    get_liquidation_price_source, is_asset_liquidation_paused, is_emergency_paused,
+    is_operation_paused, require_operation_not_paused, LiquidationPriceSource, RiskManagementError,
 };
 use crate::risk_params::{
     can_be_liquidated, get_close_factor, get_liquidation_incentive,
     get_liquidation_incentive_amount, get_max_liquidatable_amount,
 };
+use crate::storage::{self, LIQUIDATION_NAMESPACE};
 
 /// Errors that can occur during liquidation operations
 #[contracterror]
@@ -65,6 +124,228 @@ pub enum LiquidationError {
     PriceNotAvailable = 10,
     /// Liquidation would leave position undercollateralized
     InsufficientLiquidation = 11,
+    /// No auction is running for the borrower
+    AuctionNotFound = 12,
+    /// An auction is already running for the borrower
+    AuctionActive = 13,
+    /// Auction cannot be settled while the position is liquidatable and the
+    /// ramp has not finished
+    AuctionNotSettleable = 14,
+    /// Caller is not the admin
+    Unauthorized = 15,
+    /// Auction duration or maximum incentive out of range
+    InvalidAuctionConfig = 16,
+    /// No enabled AMM protocol supports the pair, or the AMM rejected the swap
+    SwapFailed = 17,
+    /// The swap returned less than the minimum or than the debt repaid
+    SlippageExceeded = 18,
+    /// The internal flash loan could not be taken out
+    FlashLoanFailed = 19,
+    /// The seized collateral cannot cover the flash loan and its fee
+    FlashLoanNotCovered = 20,
+}
+
+/// Default auction ramp duration in seconds
+pub const DEFAULT_AUCTION_DURATION: u64 = 3_600;
+
+/// Default maximum auction incentive (10%), used when risk params are unset
+pub const DEFAULT_AUCTION_MAX_INCENTIVE_BPS: i128 = 1_000;
+
+/// Upper bound for the auction incentive (50%)
+pub const MAX_AUCTION_INCENTIVE_BPS: i128 = 5_000;
+
+/// Closed auctions kept per borrower; the oldest entry is dropped first
+pub const MAX_AUCTION_HISTORY: u32 = 20;
+
+/// Liquidators ranked on the leaderboard
+pub const MAX_LEADERBOARD_SIZE: u32 = 20;
+
+/// Liquidation receipts kept in the archive; the oldest is pruned first
+pub const MAX_ARCHIVED_RECEIPTS: u64 = 1_000;
+
+/// Seconds the collateral swap of `liquidate_and_swap` stays valid
+const SWAP_DEADLINE_SECONDS: u64 = 300;
+
+/// Receipts returned per archive page
+pub const MAX_RECEIPT_PAGE_SIZE: u32 = 50;
+
+/// Borrower index slots scanned per liquidatable positions page
+pub const MAX_BORROWER_PAGE_SIZE: u32 = 50;
+
+/// Storage keys for liquidation auctions
+#[contracttype]
+#[derive(Clone)]
+pub enum LiquidationDataKey {
+    /// Auction ramp parameters
+    /// Value type: AuctionConfig
+    AuctionConfig,
+    /// Open auction for a borrower
+    /// Value type: LiquidationAuction
+    Auction(Address),
+    /// Closed auctions for a borrower, oldest first
+    /// Value type: Vec<AuctionRecord>
+    AuctionHistory(Address),
+    /// Cumulative liquidation activity of a liquidator
+    /// Value type: LiquidatorStats
+    LiquidatorStats(Address),
+    /// Top liquidators by debt repaid, highest first
+    /// Value type: Vec<Address>
+    LiquidatorLeaderboard,
+    /// Archived liquidation receipt by id
+    /// Value type: LiquidationReceipt
+    Receipt(u64),
+    /// Receipts ever archived; the next receipt id
+    /// Value type: u64
+    ReceiptCount,
+    /// Borrower with outstanding debt at an index slot
+    /// Value type: Address
+    Borrower(u32),
+    /// Index slot of a borrower with outstanding debt
+    /// Value type: u32
+    BorrowerSlot(Address),
+    /// Borrowers in the index
+    /// Value type: u32
+    BorrowerCount,
+}
+
+/// Dutch-auction ramp parameters
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuctionConfig {
+    /// Seconds for the discount to ramp from 0 to `max_incentive_bps`
+    pub duration: u64,
+    /// Collateral discount reached at the end of the ramp (basis points)
+    pub max_incentive_bps: i128,
+}
+
+/// An open liquidation auction
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LiquidationAuction {
+    pub borrower: Address,
+    /// Address that started the auction
+    pub starter: Address,
+    pub debt_asset: Option<Address>,
+    pub collateral_asset: Option<Address>,
+    pub start_time: u64,
+    /// Ramp parameters captured when the auction started
+    pub duration: u64,
+    pub max_incentive_bps: i128,
+    /// Debt repaid by bidders so far
+    pub debt_repaid: i128,
+    /// Collateral paid out to bidders so far
+    pub collateral_seized: i128,
+}
+
+/// How an auction was closed
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AuctionOutcome {
+    Settled,
+    Cancelled,
+}
+
+/// A closed auction in the borrower's history
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuctionRecord {
+    pub auction: LiquidationAuction,
+    pub outcome: AuctionOutcome,
+    pub closed_at: u64,
+}
+
+/// Cumulative liquidation activity of a liquidator
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LiquidatorStats {
+    /// Liquidations and auction bids executed
+    pub liquidation_count: u32,
+    /// Debt repaid on behalf of borrowers
+    pub debt_repaid: i128,
+    /// Collateral received, including the incentive
+    pub collateral_seized: i128,
+    /// Incentive earned, in debt asset terms
+    pub incentive_earned: i128,
+    /// Timestamp of the latest liquidation
+    pub last_liquidation: u64,
+}
+
+/// A ranked liquidator on the leaderboard
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LeaderboardEntry {
+    pub liquidator: Address,
+    pub stats: LiquidatorStats,
+}
+
+/// Archived record of a single liquidation or auction bid
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LiquidationReceipt {
+    pub id: u64,
+    pub borrower: Address,
+    pub liquidator: Address,
+    pub debt_asset: Option<Address>,
+    pub collateral_asset: Option<Address>,
+    pub debt_repaid: i128,
+    /// Collateral paid to the liquidator, including the incentive
+    pub collateral_seized: i128,
+    /// Incentive in debt asset terms
+    pub incentive_amount: i128,
+    /// Debt asset price in the accounting currency (8 decimals)
+    pub debt_price: i128,
+    /// Collateral asset price in the accounting currency (8 decimals)
+    pub collateral_price: i128,
+    pub timestamp: u64,
+}
+
+/// Outcome of a liquidation settled by swapping the seized collateral
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapLiquidationResult {
+    pub debt_liquidated: i128,
+    /// Collateral seized, including the incentive, and swapped
+    pub collateral_seized: i128,
+    /// Incentive in debt asset terms
+    pub incentive_amount: i128,
+    /// Debt asset received for the seized collateral
+    pub amount_out: i128,
+    /// Proceeds left to the liquidator after repaying the debt
+    pub surplus: i128,
+}
+
+/// Outcome of a liquidation funded by an internal flash loan
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FlashLiquidationResult {
+    pub debt_liquidated: i128,
+    /// Collateral seized, including the incentive
+    pub collateral_seized: i128,
+    /// Incentive in debt asset terms
+    pub incentive_amount: i128,
+    /// Flash loan fee paid in the debt asset
+    pub flash_fee: i128,
+    /// Collateral sold to repay the flash loan and fee
+    pub collateral_sold: i128,
+    /// Debt asset the sale returned
+    pub amount_out: i128,
+    /// Sale proceeds left to the liquidator after repaying the flash loan
+    pub surplus: i128,
+}
+
+/// A borrower below the liquidation threshold
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LiquidatablePosition {
+    pub borrower: Address,
+    /// Collateral balance
+    pub collateral: i128,
+    /// Debt principal
+    pub debt: i128,
+    /// Interest owed, including interest not yet accrued to the position
+    pub interest: i128,
+    /// Collateral to total debt ratio (basis points)
+    pub health_factor: i128,
 }
 
 /// Annual interest rate in basis points (e.g., 500 = 5% per year)
@@ -76,6 +357,8 @@ pub enum LiquidationError {
 /// Uses the current borrow rate based on protocol utilization
 fn calculate_accrued_interest(
     env: &Env,
+    user: &Address,
+    asset: &Option<Address>,
     principal: i128,
     last_accrual_time: u64,
     current_time: u64,
@@ -84,37 +367,54 @@ fn calculate_accrued_interest(
         return Ok(0);
     }
 
-    if current_time <= last_accrual_time {
-        return Ok(0);
-    }
-
-    // Get current borrow rate (in basis points)
-    let rate_bps =
-        crate::interest_rate::calculate_borrow_rate(env).map_err(|_| LiquidationError::Overflow)?;
-
-    // Calculate interest using the dynamic rate
-    crate::interest_rate::calculate_accrued_interest(
+    // Dynamic borrow rate, capped per user class with the gap absorbed by the subsidy budget
+    crate::interest_rate::calculate_user_accrued_interest(
+        env,
+        user,
+        asset,
         principal,
         last_accrual_time,
         current_time,
-        rate_bps,
     )
     .map_err(|_| LiquidationError::Overflow)
 }
 
 /// Accrue interest on a position
-fn accrue_interest(env: &Env, position: &mut Position) -> Result<(), LiquidationError> {
+fn accrue_interest(
+    env: &Env,
+    user: &Address,
+    asset: &Option<Address>,
+    position: &mut Position,
+) -> Result<(), LiquidationError> {
     let current_time = env.ledger().timestamp();
 
     if position.debt == 0 {
         position.borrow_interest = 0;
         position.last_accrual_time = current_time;
+        crate::interest_rate::checkpoint_user_borrow_index(env, user)
+            .map_err(|_| LiquidationError::Overflow)?;
         return Ok(());
     }
 
     // Calculate new interest accrued using dynamic rate
-    let new_interest =
-        calculate_accrued_interest(env, position.debt, position.last_accrual_time, current_time)?;
+    let new_interest = calculate_accrued_interest(
+        env,
+        user,
+        asset,
+        position.debt,
+        position.last_accrual_time,
+        current_time,
+    )?;
+
+    // An open subsidy campaign on the debt asset absorbs part of it
+    let new_interest = crate::subsidy_campaign::apply_campaign_subsidy(
+        env,
+        asset,
+        position.debt,
+        position.last_accrual_time,
+        current_time,
+        new_interest,
+    );
 
     // Add to existing interest
     position.borrow_interest = position
@@ -122,19 +422,53 @@ fn accrue_interest(env: &Env, position: &mut Position) -> Result<(), Liquidation
         .checked_add(new_interest)
         .ok_or(LiquidationError::Overflow)?;
 
+    // Pass the suppliers' share of the new interest to the supply pool
+    crate::deposit::accrue_supply_interest(env, asset, new_interest);
+
     // Update last accrual time
     position.last_accrual_time = current_time;
+    crate::interest_rate::checkpoint_user_borrow_index(env, user)
+        .map_err(|_| LiquidationError::Overflow)?;
 
     Ok(())
 }
 
-/// Get asset price from oracle
-/// Returns price in base units (scaled by decimals)
-/// Falls back to default price if oracle doesn't have a price set
-fn get_asset_price(env: &Env, asset: &Address) -> i128 {
-    // Try to get price from oracle, but fallback to default if not available
-    // This allows liquidation to work even when prices aren't set up in tests
-    get_price(env, asset).unwrap_or(1_00000000i128) // Default: 1 XLM with 8 decimals
+/// Get an asset's price in the accounting currency
+/// Uses the TWAP when configured as the liquidation price source, falling
+/// back to spot for assets without price history; an active emergency
+/// price override always applies, and a degraded asset is priced at its
+/// buffered last good price
+/// Falls back to one accounting unit when no price is available (so
+/// liquidation still works before oracles are configured, e.g. in tests)
+fn get_asset_price(env: &Env, asset: &Option<Address>, as_collateral: bool) -> i128 {
+    let source = get_liquidation_price_source(env);
+    price_in_accounting(env, asset, |asset| {
+        let overridden = get_price_override(env, asset).is_some();
+        if !overridden {
+            if let Some(price) = get_degraded_price(env, asset, as_collateral) {
+                return Ok(price);
+            }
+        }
+        match source {
+            // An emergency override replaces the TWAP of the failed feed
+            LiquidationPriceSource::Twap(_) if overridden => get_price(env, asset),
+            LiquidationPriceSource::Twap(window) => {
+                get_twap(env, asset, window).or_else(|_| get_price(env, asset))
+            }
+            LiquidationPriceSource::Spot => get_price(env, asset),
+        }
+    })
+    .unwrap_or(ACCOUNTING_SCALE)
+}
+
+/// Price of the asset being repaid
+fn get_debt_price(env: &Env, asset: &Option<Address>) -> i128 {
+    get_asset_price(env, asset, false)
+}
+
+/// Price of the asset being seized
+fn get_collateral_price(env: &Env, asset: &Option<Address>) -> i128 {
+    get_asset_price(env, asset, true)
 }
 
 /// Calculate collateral value in debt asset terms
@@ -156,6 +490,26 @@ fn calculate_collateral_value(
         .ok_or(LiquidationError::Overflow)
 }
 
+/// Value the borrower's collateral balance in debt asset terms
+fn collateral_value_in_debt_terms(
+    env: &Env,
+    collateral_balance: i128,
+    debt_asset: &Option<Address>,
+    collateral_asset: &Option<Address>,
+) -> Result<i128, LiquidationError> {
+    // Native on both sides needs no conversion; otherwise convert through
+    // accounting currency prices (native XLM is priced like any token)
+    if debt_asset.is_none() && collateral_asset.is_none() {
+        // Both are native XLM - no price conversion needed
+        return Ok(collateral_balance);
+    }
+
+    let debt_price = get_debt_price(env, debt_asset);
+    let collateral_price = get_collateral_price(env, collateral_asset);
+
+    calculate_collateral_value(collateral_balance, collateral_price, debt_price)
+}
+
 /// Calculate debt value
 /// Returns debt_value = debt_amount + interest
 fn calculate_debt_value(debt: i128, interest: i128) -> Result<i128, LiquidationError> {
@@ -198,6 +552,7 @@ fn calculate_debt_value(debt: i128, interest: i128) -> Result<i128, LiquidationE
 /// * Updates debt and collateral balances
 /// * Emits events for tracking
 /// * Updates analytics
+/// * Rejected while a liquidation auction is open for the borrower
 pub fn liquidate(
     env: &Env,
     liquidator: Address,
@@ -205,6 +560,311 @@ pub fn liquidate(
     debt_asset: Option<Address>,
     collateral_asset: Option<Address>,
     debt_amount: i128,
+) -> Result<(i128, i128, i128), LiquidationError> {
+    // An open auction determines the price for this borrower
+    if get_auction(env, &borrower).is_some() {
+        return Err(LiquidationError::AuctionActive);
+    }
+
+    execute_liquidation(
+        env,
+        liquidator,
+        borrower,
+        debt_asset,
+        collateral_asset,
+        debt_amount,
+        None,
+        false,
+    )
+}
+
+/// Liquidate a position and swap the seized collateral to the debt asset
+///
+/// The swap proceeds repay the debt, so the liquidator needs no inventory of
+/// either asset; whatever the swap returns beyond the debt repaid is left to
+/// the liquidator.
+///
+/// # Arguments
+/// * `liquidator` - The liquidator (must authorize)
+/// * `borrower` - The borrower being liquidated
+/// * `debt_asset` - The debt asset to repay (None for native XLM)
+/// * `collateral_asset` - The collateral asset seized and swapped
+/// * `debt_amount` - The amount of debt to liquidate
+/// * `min_out` - Least debt asset the swap must return
+///
+/// # Errors
+/// * `LiquidationError::InvalidCollateralAsset` - Debt and collateral are the same asset
+/// * `LiquidationError::InvalidAmount` - `min_out` is negative
+/// * `LiquidationError::SwapFailed` - No AMM protocol supports the pair, or the swap failed
+/// * `LiquidationError::SlippageExceeded` - The swap returned less than
+///   `min_out` or than the debt repaid
+/// * Any error of `liquidate`
+pub fn liquidate_and_swap(
+    env: &Env,
+    liquidator: Address,
+    borrower: Address,
+    debt_asset: Option<Address>,
+    collateral_asset: Option<Address>,
+    debt_amount: i128,
+    min_out: i128,
+) -> Result<SwapLiquidationResult, LiquidationError> {
+    if debt_asset == collateral_asset {
+        return Err(LiquidationError::InvalidCollateralAsset);
+    }
+    if min_out < 0 {
+        return Err(LiquidationError::InvalidAmount);
+    }
+    if get_auction(env, &borrower).is_some() {
+        return Err(LiquidationError::AuctionActive);
+    }
+    liquidator.require_auth();
+
+    let (debt_liquidated, collateral_seized, incentive_amount) = execute_liquidation(
+        env,
+        liquidator.clone(),
+        borrower.clone(),
+        debt_asset.clone(),
+        collateral_asset.clone(),
+        debt_amount,
+        None,
+        true,
+    )?;
+    let amount_out = swap_collateral(
+        env,
+        &liquidator,
+        &collateral_asset,
+        &debt_asset,
+        collateral_seized,
+        min_out.max(debt_liquidated),
+    )?;
+
+    // The proceeds repay the debt; the surplus stays with the liquidator
+    if let Some(ref debt_addr) = debt_asset {
+        soroban_sdk::token::Client::new(env, debt_addr).transfer_from(
+            &env.current_contract_address(),
+            &liquidator,
+            &env.current_contract_address(),
+            &debt_liquidated,
+        );
+    }
+    let surplus = amount_out
+        .checked_sub(debt_liquidated)
+        .ok_or(LiquidationError::Overflow)?;
+
+    emit_liquidation_swap(
+        env,
+        LiquidationSwapEvent {
+            liquidator,
+            borrower,
+            debt_asset,
+            collateral_asset,
+            collateral_swapped: collateral_seized,
+            amount_out,
+            surplus,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(SwapLiquidationResult {
+        debt_liquidated,
+        collateral_seized,
+        incentive_amount,
+        amount_out,
+        surplus,
+    })
+}
+
+/// Liquidate a position with an internal flash loan of the debt asset
+///
+/// The flash loan repays the debt; just enough of the seized collateral is
+/// sold through the AMM to repay the loan plus its fee. The unsold
+/// collateral and any proceeds beyond the loan stay with the liquidator.
+///
+/// # Arguments
+/// * `liquidator` - The liquidator (must authorize)
+/// * `borrower` - The borrower being liquidated
+/// * `debt_asset` - The debt asset to repay (None for native XLM)
+/// * `collateral_asset` - The collateral asset seized
+/// * `debt_amount` - The amount of debt to liquidate
+///
+/// # Errors
+/// * `LiquidationError::InvalidCollateralAsset` - Debt and collateral are the same asset
+/// * `LiquidationError::InvalidDebtAsset` - Native XLM debt without a native asset address
+/// * `LiquidationError::FlashLoanFailed` - The flash loan was refused (paused,
+///   outside limits, already active or not enough liquidity)
+/// * `LiquidationError::FlashLoanNotCovered` - The seized collateral is worth
+///   less than the flash loan plus fee after slippage
+/// * `LiquidationError::SwapFailed` / `LiquidationError::SlippageExceeded` - The sale failed
+/// * Any error of `liquidate`
+pub fn liquidate_with_flash_loan(
+    env: &Env,
+    liquidator: Address,
+    borrower: Address,
+    debt_asset: Option<Address>,
+    collateral_asset: Option<Address>,
+    debt_amount: i128,
+) -> Result<FlashLiquidationResult, LiquidationError> {
+    if debt_asset == collateral_asset {
+        return Err(LiquidationError::InvalidCollateralAsset);
+    }
+    if get_auction(env, &borrower).is_some() {
+        return Err(LiquidationError::AuctionActive);
+    }
+    liquidator.require_auth();
+
+    let loan_asset = token_address(env, &debt_asset).ok_or(LiquidationError::InvalidDebtAsset)?;
+    let flash_fee =
+        crate::flash_loan::begin_internal_flash_loan(env, &liquidator, &loan_asset, debt_amount)
+            .map_err(|_| LiquidationError::FlashLoanFailed)?;
+
+    // The loan stays in the contract as the borrower's repayment
+    let (debt_liquidated, collateral_seized, incentive_amount) = execute_liquidation(
+        env,
+        liquidator.clone(),
+        borrower.clone(),
+        debt_asset.clone(),
+        collateral_asset.clone(),
+        debt_amount,
+        None,
+        true,
+    )?;
+
+    // Sell the collateral worth the loan plus fee, grossed up for slippage
+    let owed = debt_amount
+        .checked_add(flash_fee)
+        .ok_or(LiquidationError::Overflow)?;
+    let slippage = stellarlend_amm::amm::get_amm_settings(env)
+        .map_err(|_| LiquidationError::SwapFailed)?
+        .default_slippage;
+    let owed_in_collateral = mul_div_ceil(
+        owed,
+        get_debt_price(env, &debt_asset),
+        get_collateral_price(env, &collateral_asset),
+    )?;
+    let collateral_sold = mul_div_ceil(owed_in_collateral, 10_000, 10_000 - slippage)?;
+    if collateral_sold > collateral_seized {
+        return Err(LiquidationError::FlashLoanNotCovered);
+    }
+    let amount_out = swap_collateral(
+        env,
+        &liquidator,
+        &collateral_asset,
+        &debt_asset,
+        collateral_sold,
+        owed,
+    )?;
+
+    // Repay the loan from the proceeds
+    soroban_sdk::token::Client::new(env, &loan_asset).transfer_from(
+        &env.current_contract_address(),
+        &liquidator,
+        &env.current_contract_address(),
+        &owed,
+    );
+    crate::flash_loan::settle_internal_flash_loan(
+        env,
+        &liquidator,
+        &loan_asset,
+        debt_amount,
+        flash_fee,
+    )
+    .map_err(|_| LiquidationError::FlashLoanFailed)?;
+
+    let surplus = amount_out
+        .checked_sub(owed)
+        .ok_or(LiquidationError::Overflow)?;
+    emit_liquidation_swap(
+        env,
+        LiquidationSwapEvent {
+            liquidator,
+            borrower,
+            debt_asset,
+            collateral_asset,
+            collateral_swapped: collateral_sold,
+            amount_out,
+            surplus,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(FlashLiquidationResult {
+        debt_liquidated,
+        collateral_seized,
+        incentive_amount,
+        flash_fee,
+        collateral_sold,
+        amount_out,
+        surplus,
+    })
+}
+
+/// `a * b / c`, rounded up
+fn mul_div_ceil(a: i128, b: i128, c: i128) -> Result<i128, LiquidationError> {
+    if c <= 0 {
+        return Err(LiquidationError::PriceNotAvailable);
+    }
+    let product = a.checked_mul(b).ok_or(LiquidationError::Overflow)?;
+    Ok((product + c - 1) / c)
+}
+
+/// Swap seized collateral for the liquidator through the first enabled AMM
+/// protocol supporting the pair
+fn swap_collateral(
+    env: &Env,
+    liquidator: &Address,
+    collateral_asset: &Option<Address>,
+    debt_asset: &Option<Address>,
+    amount_in: i128,
+    min_amount_out: i128,
+) -> Result<i128, LiquidationError> {
+    let protocols =
+        stellarlend_amm::amm::get_amm_protocols(env).map_err(|_| LiquidationError::SwapFailed)?;
+    let protocol = protocols
+        .iter()
+        .find(|(_, config)| {
+            config.enabled
+                && config.supported_pairs.iter().any(|pair| {
+                    (pair.token_a == *collateral_asset && pair.token_b == *debt_asset)
+                        || (pair.token_a == *debt_asset && pair.token_b == *collateral_asset)
+                })
+        })
+        .map(|(protocol, _)| protocol)
+        .ok_or(LiquidationError::SwapFailed)?;
+    let slippage = stellarlend_amm::amm::get_amm_settings(env)
+        .map_err(|_| LiquidationError::SwapFailed)?
+        .default_slippage;
+    let params = stellarlend_amm::SwapParams {
+        protocol,
+        token_in: collateral_asset.clone(),
+        token_out: debt_asset.clone(),
+        amount_in,
+        min_amount_out,
+        slippage_tolerance: slippage,
+        deadline: env.ledger().timestamp() + SWAP_DEADLINE_SECONDS,
+    };
+    crate::amm::swap(env, liquidator.clone(), params).map_err(|e| match e {
+        stellarlend_amm::AmmError::MinOutputNotMet => LiquidationError::SlippageExceeded,
+        _ => LiquidationError::SwapFailed,
+    })
+}
+
+/// Shared liquidation path for fixed-incentive liquidations, auction bids and
+/// swap liquidations
+///
+/// `incentive_override` replaces the risk params incentive (in basis points)
+/// when set, which is how auction bids apply the current ramp discount. With
+/// `defer_repayment` the debt asset is not collected here; the caller
+/// collects it once the seized collateral has been swapped.
+#[allow(clippy::too_many_arguments)]
+fn execute_liquidation(
+    env: &Env,
+    liquidator: Address,
+    borrower: Address,
+    debt_asset: Option<Address>,
+    collateral_asset: Option<Address>,
+    debt_amount: i128,
+    incentive_override: Option<i128>,
+    defer_repayment: bool,
 ) -> Result<(i128, i128, i128), LiquidationError> {
     // Validate amount
     if debt_amount <= 0 {
@@ -238,6 +898,13 @@ pub fn liquidate(
         }
     }
 
+    // Check per-market liquidation pauses (auto-expiring)
+    if is_asset_liquidation_paused(env, debt_asset.clone())
+        || is_asset_liquidation_paused(env, collateral_asset.clone())
+    {
+        return Err(LiquidationError::LiquidationPaused);
+    }
+
     // Get current timestamp
     let timestamp = env.ledger().timestamp();
 
@@ -250,7 +917,7 @@ pub fn liquidate(
         .ok_or(LiquidationError::NotLiquidatable)?;
 
     // Accrue interest before liquidation
-    accrue_interest(env, &mut position)?;
+    accrue_interest(env, &borrower, &debt_asset, &mut position)?;
 
     // Get collateral balance
     let collateral_key = DepositDataKey::CollateralBalance(borrower.clone());
@@ -264,42 +931,24 @@ pub fn liquidate(
     let total_debt = calculate_debt_value(position.debt, position.borrow_interest)?;
 
     // Get asset prices and calculate collateral value
-    // For native XLM (None), both assets are the same, so use 1:1 ratio
-    // For token assets, use oracle prices to convert between assets
-    let collateral_value = if debt_asset.is_none() && collateral_asset.is_none() {
-        // Both are native XLM - no price conversion needed
-        collateral_balance
-    } else {
-        // Need to convert between different assets using prices
-        let debt_price = if let Some(ref debt_addr) = debt_asset {
-            get_asset_price(env, debt_addr)
-        } else {
-            // Default price for native XLM (1:1, no decimals)
-            1i128
-        };
-
-        let collateral_price = if let Some(ref collateral_addr) = collateral_asset {
-            get_asset_price(env, collateral_addr)
-        } else {
-            // Default price for native XLM (1:1, no decimals)
-            1i128
-        };
-
-        // Calculate collateral value in debt asset terms
-        calculate_collateral_value(collateral_balance, collateral_price, debt_price)?
-    };
+    let collateral_value =
+        collateral_value_in_debt_terms(env, collateral_balance, &debt_asset, &collateral_asset)?;
 
     // Check if position can be liquidated
-    let can_liquidate = can_be_liquidated(env, collateral_value, total_debt)
+    let can_liquidate = can_be_liquidated(env, collateral_value, total_debt, Some(&borrower))
         .map_err(|_| LiquidationError::NotLiquidatable)?;
 
     if !can_liquidate {
         return Err(LiquidationError::NotLiquidatable);
     }
+    crate::risk_management::mark_liquidatable(env, &borrower);
 
-    // Get maximum liquidatable amount (close factor)
-    let max_liquidatable =
-        get_max_liquidatable_amount(env, total_debt).map_err(|_| LiquidationError::Overflow)?;
+    // Get maximum liquidatable amount (close factor scaled by health factor severity)
+    let close_factor =
+        crate::risk_management::get_close_factor_for_health(env, collateral_value, total_debt)
+            .map_err(|_| LiquidationError::Overflow)?;
+    let max_liquidatable = stellarlend_math::close_amount(total_debt, close_factor)
+        .ok_or(LiquidationError::Overflow)?;
 
     // Validate liquidation amount doesn't exceed close factor
     if debt_amount > max_liquidatable {
@@ -314,32 +963,34 @@ pub fn liquidate(
     };
 
     // Calculate liquidation incentive
-    let incentive_bps = get_liquidation_incentive(env).map_err(|_| LiquidationError::Overflow)?;
-    let incentive_amount = get_liquidation_incentive_amount(env, actual_debt_liquidated)
-        .map_err(|_| LiquidationError::Overflow)?;
+    let (incentive_bps, incentive_amount) = match incentive_override {
+        Some(bps) => (
+            bps,
+            actual_debt_liquidated
+                .checked_mul(bps)
+                .ok_or(LiquidationError::Overflow)?
+                .checked_div(10000)
+                .ok_or(LiquidationError::Overflow)?,
+        ),
+        None => (
+            get_liquidation_incentive(env).map_err(|_| LiquidationError::Overflow)?,
+            get_liquidation_incentive_amount(env, actual_debt_liquidated)
+                .map_err(|_| LiquidationError::Overflow)?,
+        ),
+    };
 
     // Calculate collateral to seize
     // Liquidator repays debt_liquidated amount of debt asset
     // Liquidator receives collateral worth debt_liquidated (in debt terms) + incentive
     // collateral_seized = (debt_liquidated * debt_price / collateral_price) * (1 + incentive_bps / 10000)
     // First, convert debt amount to collateral terms: debt_liquidated * debt_price / collateral_price
+    let debt_price = get_debt_price(env, &debt_asset);
+    let collateral_price = get_collateral_price(env, &collateral_asset);
     let collateral_value_liquidated = if debt_asset.is_none() && collateral_asset.is_none() {
         // Both are native XLM - no price conversion needed
         actual_debt_liquidated
     } else {
         // Need to convert between different assets using prices
-        let debt_price = if let Some(ref debt_addr) = debt_asset {
-            get_asset_price(env, debt_addr)
-        } else {
-            1i128 // Native XLM
-        };
-
-        let collateral_price = if let Some(ref collateral_addr) = collateral_asset {
-            get_asset_price(env, collateral_addr)
-        } else {
-            1i128 // Native XLM
-        };
-
         actual_debt_liquidated
             .checked_mul(debt_price)
             .ok_or(LiquidationError::Overflow)?
@@ -361,8 +1012,9 @@ pub fn liquidate(
         collateral_seized
     };
 
-    // Check liquidator has sufficient balance to repay debt
-    if let Some(ref debt_addr) = debt_asset {
+    // Check liquidator has sufficient balance to repay debt (a deferred
+    // repayment is collected from the swap proceeds by the caller)
+    if let Some(debt_addr) = debt_asset.as_ref().filter(|_| !defer_repayment) {
         let token_client = soroban_sdk::token::Client::new(env, debt_addr);
         let liquidator_balance = token_client.balance(&liquidator);
         if liquidator_balance < actual_debt_liquidated {
@@ -420,13 +1072,62 @@ pub fn liquidate(
     let new_collateral_balance = collateral_balance
         .checked_sub(actual_collateral_seized)
         .ok_or(LiquidationError::Overflow)?;
+    crate::deposit::sync_legacy_collateral(env, &borrower)
+        .and_then(|_| {
+            crate::deposit::redeem_supply_shares(env, &borrower, actual_collateral_seized)
+        })
+        .map_err(|_| LiquidationError::Overflow)?;
     env.storage()
         .persistent()
         .set(&collateral_key, &new_collateral_balance);
 
+    // Release the liquidated debt and seized collateral from the asset totals
+    crate::cross_asset::record_borrow_change(env, debt_asset.clone(), -principal_to_pay);
+    crate::cross_asset::record_supply_change(
+        env,
+        collateral_asset.clone(),
+        -actual_collateral_seized,
+    );
+    crate::deposit::update_asset_ledger(env, &debt_asset, 0, -principal_to_pay);
+    crate::deposit::update_asset_ledger(env, &collateral_asset, -actual_collateral_seized, 0);
+    crate::rewards::record_balance_change(
+        env,
+        &borrower,
+        &debt_asset,
+        crate::rewards::RewardSide::Borrow,
+        -principal_to_pay,
+    );
+    crate::rewards::record_balance_change(
+        env,
+        &borrower,
+        &collateral_asset,
+        crate::rewards::RewardSide::Supply,
+        -actual_collateral_seized,
+    );
+
     // Update position collateral
     position.collateral = new_collateral_balance;
 
+    // With the collateral exhausted the remaining debt can never be repaid,
+    // so write it off the position and book it as bad debt
+    if new_collateral_balance == 0 {
+        let remaining_debt = position.debt.saturating_add(position.borrow_interest);
+        if remaining_debt > 0 {
+            crate::bad_debt::record_bad_debt(env, &borrower, debt_asset.clone(), remaining_debt);
+            crate::cross_asset::record_borrow_change(env, debt_asset.clone(), -position.debt);
+            crate::deposit::update_asset_ledger(env, &debt_asset, 0, -position.debt);
+            crate::rewards::record_balance_change(
+                env,
+                &borrower,
+                &debt_asset,
+                crate::rewards::RewardSide::Borrow,
+                -position.debt,
+            );
+            position.debt = 0;
+            position.borrow_interest = 0;
+        }
+    }
+
     // Save updated position
     env.storage().persistent().set(&position_key, &position);
 
@@ -439,6 +1140,30 @@ pub fn liquidate(
         actual_collateral_seized,
         timestamp,
     )?;
+    record_liquidator_stats(
+        env,
+        &liquidator,
+        actual_debt_liquidated,
+        actual_collateral_seized,
+        incentive_amount,
+        timestamp,
+    )?;
+    archive_receipt(
+        env,
+        LiquidationReceipt {
+            id: 0,
+            borrower: borrower.clone(),
+            liquidator: liquidator.clone(),
+            debt_asset: debt_asset.clone(),
+            collateral_asset: collateral_asset.clone(),
+            debt_repaid: actual_debt_liquidated,
+            collateral_seized: actual_collateral_seized,
+            incentive_amount,
+            debt_price,
+            collateral_price,
+            timestamp,
+        },
+    );
 
     // Add to activity log
     add_activity_log(
@@ -583,3 +1308,708 @@ fn update_liquidation_analytics(
 
     Ok(())
 }
+
+/// Get the auction ramp parameters
+///
+/// Defaults to a one-hour ramp up to the risk params liquidation incentive.
+pub fn get_auction_config(env: &Env) -> AuctionConfig {
+    storage::read(
+        env,
+        &LIQUIDATION_NAMESPACE,
+        &LiquidationDataKey::AuctionConfig,
+    )
+    .unwrap_or_else(|| AuctionConfig {
+        duration: DEFAULT_AUCTION_DURATION,
+        max_incentive_bps: get_liquidation_incentive(env)
+            .unwrap_or(DEFAULT_AUCTION_MAX_INCENTIVE_BPS),
+    })
+}
+
+/// Set the auction ramp parameters (admin only)
+///
+/// Only affects auctions started afterwards; open auctions keep the
+/// parameters captured at start.
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not the admin or liquidation admin
+/// * `InvalidAuctionConfig` - Duration is zero or the incentive is outside (0, 50%]
+pub fn set_auction_config(
+    env: &Env,
+    caller: Address,
+    config: AuctionConfig,
+) -> Result<(), LiquidationError> {
+    crate::admin::require_role(env, &caller, crate::admin::LIQUIDATION_ADMIN)
+        .map_err(|_| LiquidationError::Unauthorized)?;
+
+    if config.duration == 0
+        || config.max_incentive_bps <= 0
+        || config.max_incentive_bps > MAX_AUCTION_INCENTIVE_BPS
+    {
+        return Err(LiquidationError::InvalidAuctionConfig);
+    }
+
+    storage::write(
+        env,
+        &LIQUIDATION_NAMESPACE,
+        &LiquidationDataKey::AuctionConfig,
+        &config,
+    );
+
+    Ok(())
+}
+
+/// Get the open auction for a borrower, if any
+pub fn get_auction(env: &Env, borrower: &Address) -> Option<LiquidationAuction> {
+    storage::read(
+        env,
+        &LIQUIDATION_NAMESPACE,
+        &LiquidationDataKey::Auction(borrower.clone()),
+    )
+}
+
+/// Get the closed auctions for a borrower, oldest first
+pub fn get_auction_history(env: &Env, borrower: &Address) -> Vec<AuctionRecord> {
+    storage::read(
+        env,
+        &LIQUIDATION_NAMESPACE,
+        &LiquidationDataKey::AuctionHistory(borrower.clone()),
+    )
+    .unwrap_or(Vec::new(env))
+}
+
+/// Current collateral discount offered by an auction (basis points)
+///
+/// Ramps linearly from 0 at `start_time` to `max_incentive_bps` after
+/// `duration` seconds and stays there.
+pub fn current_auction_incentive(env: &Env, auction: &LiquidationAuction) -> i128 {
+    let elapsed = env.ledger().timestamp().saturating_sub(auction.start_time);
+    if elapsed >= auction.duration {
+        return auction.max_incentive_bps;
+    }
+
+    auction.max_incentive_bps * elapsed as i128 / auction.duration as i128
+}
+
+/// Start a Dutch auction for an undercollateralized position
+///
+/// Anyone may start an auction. The ramp parameters are captured from the
+/// current [`AuctionConfig`].
+///
+/// # Errors
+/// * `LiquidationPaused` - Liquidations are paused for either market
+/// * `InvalidDebtAsset` / `InvalidCollateralAsset` - Asset is the contract itself
+/// * `AuctionActive` - An auction is already open for the borrower
+/// * `NotLiquidatable` - Position is above the liquidation threshold
+pub fn start_auction(
+    env: &Env,
+    starter: Address,
+    borrower: Address,
+    debt_asset: Option<Address>,
+    collateral_asset: Option<Address>,
+) -> Result<LiquidationAuction, LiquidationError> {
+    require_liquidations_enabled(env, &debt_asset, &collateral_asset)?;
+
+    if get_auction(env, &borrower).is_some() {
+        return Err(LiquidationError::AuctionActive);
+    }
+
+    if !is_position_liquidatable(env, &borrower, &debt_asset, &collateral_asset)? {
+        return Err(LiquidationError::NotLiquidatable);
+    }
+
+    let config = get_auction_config(env);
+    let auction = LiquidationAuction {
+        borrower: borrower.clone(),
+        starter: starter.clone(),
+        debt_asset,
+        collateral_asset,
+        start_time: env.ledger().timestamp(),
+        duration: config.duration,
+        max_incentive_bps: config.max_incentive_bps,
+        debt_repaid: 0,
+        collateral_seized: 0,
+    };
+
+    storage::write(
+        env,
+        &LIQUIDATION_NAMESPACE,
+        &LiquidationDataKey::Auction(borrower.clone()),
+        &auction,
+    );
+
+    emit_liquidation_auction_started(
+        env,
+        LiquidationAuctionStartedEvent {
+            borrower,
+            starter,
+            debt_asset: auction.debt_asset.clone(),
+            collateral_asset: auction.collateral_asset.clone(),
+            duration: auction.duration,
+            max_incentive_bps: auction.max_incentive_bps,
+            timestamp: auction.start_time,
+        },
+    );
+
+    Ok(auction)
+}
+
+/// Bid on an open auction at the current discount
+///
+/// Repays `debt_amount` of the borrower's debt and receives collateral worth
+/// the repaid debt plus the current ramp discount. Close factor, pause and
+/// balance checks are the same as for [`liquidate`].
+///
+/// # Returns
+/// Returns a tuple (debt_liquidated, collateral_seized, incentive_amount)
+///
+/// # Errors
+/// * `AuctionNotFound` - No auction is open for the borrower
+/// * Any error returned by the liquidation path
+pub fn bid(
+    env: &Env,
+    liquidator: Address,
+    borrower: Address,
+    debt_amount: i128,
+) -> Result<(i128, i128, i128), LiquidationError> {
+    liquidator.require_auth();
+
+    let mut auction = get_auction(env, &borrower).ok_or(LiquidationError::AuctionNotFound)?;
+    let incentive_bps = current_auction_incentive(env, &auction);
+
+    let (debt_liquidated, collateral_seized, incentive_amount) = execute_liquidation(
+        env,
+        liquidator.clone(),
+        borrower.clone(),
+        auction.debt_asset.clone(),
+        auction.collateral_asset.clone(),
+        debt_amount,
+        Some(incentive_bps),
+        false,
+    )?;
+
+    auction.debt_repaid = auction
+        .debt_repaid
+        .checked_add(debt_liquidated)
+        .ok_or(LiquidationError::Overflow)?;
+    auction.collateral_seized = auction
+        .collateral_seized
+        .checked_add(collateral_seized)
+        .ok_or(LiquidationError::Overflow)?;
+    storage::write(
+        env,
+        &LIQUIDATION_NAMESPACE,
+        &LiquidationDataKey::Auction(borrower.clone()),
+        &auction,
+    );
+
+    emit_liquidation_auction_bid(
+        env,
+        LiquidationAuctionBidEvent {
+            borrower,
+            liquidator,
+            debt_liquidated,
+            collateral_seized,
+            incentive_bps,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok((debt_liquidated, collateral_seized, incentive_amount))
+}
+
+/// Close an auction that has run its course
+///
+/// Anyone may settle once the position is no longer liquidatable or the ramp
+/// has reached its maximum discount. The auction moves to the borrower's
+/// history.
+///
+/// # Errors
+/// * `AuctionNotFound` - No auction is open for the borrower
+/// * `AuctionNotSettleable` - Position is still liquidatable and the ramp is running
+pub fn settle_auction(
+    env: &Env,
+    caller: Address,
+    borrower: Address,
+) -> Result<AuctionRecord, LiquidationError> {
+    let auction = get_auction(env, &borrower).ok_or(LiquidationError::AuctionNotFound)?;
+
+    let elapsed = env.ledger().timestamp().saturating_sub(auction.start_time);
+    if elapsed < auction.duration
+        && is_position_liquidatable(
+            env,
+            &borrower,
+            &auction.debt_asset,
+            &auction.collateral_asset,
+        )?
+    {
+        return Err(LiquidationError::AuctionNotSettleable);
+    }
+
+    Ok(close_auction(env, caller, auction, AuctionOutcome::Settled))
+}
+
+/// Cancel an open auction (admin only)
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not the admin or liquidation admin
+/// * `AuctionNotFound` - No auction is open for the borrower
+pub fn cancel_auction(
+    env: &Env,
+    caller: Address,
+    borrower: Address,
+) -> Result<AuctionRecord, LiquidationError> {
+    crate::admin::require_role(env, &caller, crate::admin::LIQUIDATION_ADMIN)
+        .map_err(|_| LiquidationError::Unauthorized)?;
+    let auction = get_auction(env, &borrower).ok_or(LiquidationError::AuctionNotFound)?;
+
+    Ok(close_auction(
+        env,
+        caller,
+        auction,
+        AuctionOutcome::Cancelled,
+    ))
+}
+
+/// Remove an open auction and append it to the borrower's history
+fn close_auction(
+    env: &Env,
+    caller: Address,
+    auction: LiquidationAuction,
+    outcome: AuctionOutcome,
+) -> AuctionRecord {
+    let borrower = auction.borrower.clone();
+    let timestamp = env.ledger().timestamp();
+
+    storage::remove(
+        env,
+        &LIQUIDATION_NAMESPACE,
+        &LiquidationDataKey::Auction(borrower.clone()),
+    );
+
+    let record = AuctionRecord {
+        auction,
+        outcome,
+        closed_at: timestamp,
+    };
+
+    let mut history = get_auction_history(env, &borrower);
+    if history.len() >= MAX_AUCTION_HISTORY {
+        history.remove(0);
+    }
+    history.push_back(record.clone());
+    storage::write(
+        env,
+        &LIQUIDATION_NAMESPACE,
+        &LiquidationDataKey::AuctionHistory(borrower.clone()),
+        &history,
+    );
+
+    emit_liquidation_auction_closed(
+        env,
+        LiquidationAuctionClosedEvent {
+            borrower,
+            caller,
+            cancelled: outcome == AuctionOutcome::Cancelled,
+            debt_repaid: record.auction.debt_repaid,
+            collateral_seized: record.auction.collateral_seized,
+            timestamp,
+        },
+    );
+
+    record
+}
+
+/// Reject auctions while liquidations are paused or an asset is invalid
+fn require_liquidations_enabled(
+    env: &Env,
+    debt_asset: &Option<Address>,
+    collateral_asset: &Option<Address>,
+) -> Result<(), LiquidationError> {
+    if is_emergency_paused(env) {
+        return Err(LiquidationError::LiquidationPaused);
+    }
+
+    require_operation_not_paused(env, Symbol::new(env, "pause_liquidate"))
+        .map_err(|_| LiquidationError::LiquidationPaused)?;
+
+    if debt_asset.as_ref() == Some(&env.current_contract_address()) {
+        return Err(LiquidationError::InvalidDebtAsset);
+    }
+    if collateral_asset.as_ref() == Some(&env.current_contract_address()) {
+        return Err(LiquidationError::InvalidCollateralAsset);
+    }
+
+    if is_asset_liquidation_paused(env, debt_asset.clone())
+        || is_asset_liquidation_paused(env, collateral_asset.clone())
+    {
+        return Err(LiquidationError::LiquidationPaused);
+    }
+
+    Ok(())
+}
+
+/// Whether a position is currently below the liquidation threshold
+///
+/// Pending interest is included in the debt but nothing is persisted.
+fn is_position_liquidatable(
+    env: &Env,
+    borrower: &Address,
+    debt_asset: &Option<Address>,
+    collateral_asset: &Option<Address>,
+) -> Result<bool, LiquidationError> {
+    let (collateral_balance, total_debt) = match position_balances(env, borrower)? {
+        Some((collateral_balance, debt, interest)) => {
+            (collateral_balance, calculate_debt_value(debt, interest)?)
+        }
+        None => return Ok(false),
+    };
+    let collateral_value =
+        collateral_value_in_debt_terms(env, collateral_balance, debt_asset, collateral_asset)?;
+
+    can_be_liquidated(env, collateral_value, total_debt, Some(borrower))
+        .map_err(|_| LiquidationError::NotLiquidatable)
+}
+
+/// A borrower's collateral balance, debt principal and interest owed
+///
+/// Interest includes what has not been accrued to the position yet. Returns
+/// `None` if the borrower has no position.
+fn position_balances(
+    env: &Env,
+    borrower: &Address,
+) -> Result<Option<(i128, i128, i128)>, LiquidationError> {
+    let position = match env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, Position>(&DepositDataKey::Position(borrower.clone()))
+    {
+        Some(position) => position,
+        None => return Ok(None),
+    };
+
+    let pending_interest = if position.debt > 0 {
+        crate::interest_rate::preview_user_accrued_interest(
+            env,
+            borrower,
+            &None,
+            position.debt,
+            position.last_accrual_time,
+            env.ledger().timestamp(),
+        )
+        .map_err(|_| LiquidationError::Overflow)?
+    } else {
+        0
+    };
+    let interest = position
+        .borrow_interest
+        .checked_add(pending_interest)
+        .ok_or(LiquidationError::Overflow)?;
+
+    let collateral_balance = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, i128>(&DepositDataKey::CollateralBalance(borrower.clone()))
+        .unwrap_or(0);
+
+    Ok(Some((collateral_balance, position.debt, interest)))
+}
+
+/// Number of borrowers with outstanding debt
+pub fn get_borrower_count(env: &Env) -> u32 {
+    storage::read(
+        env,
+        &LIQUIDATION_NAMESPACE,
+        &LiquidationDataKey::BorrowerCount,
+    )
+    .unwrap_or(0)
+}
+
+/// Debt principal of the borrowers in the first `limit` borrower index slots
+pub(crate) fn get_indexed_debt(env: &Env, limit: u32) -> i128 {
+    let mut total: i128 = 0;
+    for slot in 0..get_borrower_count(env).min(limit) {
+        let borrower: Address = match storage::read(
+            env,
+            &LIQUIDATION_NAMESPACE,
+            &LiquidationDataKey::Borrower(slot),
+        ) {
+            Some(borrower) => borrower,
+            None => continue,
+        };
+        if let Some(position) = env
+            .storage()
+            .persistent()
+            .get::<DepositDataKey, Position>(&DepositDataKey::Position(borrower))
+        {
+            total = total.saturating_add(position.debt);
+        }
+    }
+    total
+}
+
+/// Get the liquidatable borrowers among `limit` borrower index slots from `offset`
+///
+/// Positions are valued in native terms, as `liquidate` does for native debt
+/// and collateral. The scan is bounded by `MAX_BORROWER_PAGE_SIZE` slots;
+/// continue from `offset + limit` until it reaches `get_borrower_count`. The
+/// index is compacted when a borrower repays in full, so a borrower may move
+/// between pages while a bot is paging.
+pub fn get_liquidatable_positions(
+    env: &Env,
+    limit: u32,
+    offset: u32,
+) -> Result<Vec<LiquidatablePosition>, LiquidationError> {
+    let count = get_borrower_count(env);
+    let end = offset
+        .saturating_add(limit.min(MAX_BORROWER_PAGE_SIZE))
+        .min(count);
+    let mut positions = Vec::new(env);
+    for slot in offset..end {
+        let borrower: Address = match storage::read(
+            env,
+            &LIQUIDATION_NAMESPACE,
+            &LiquidationDataKey::Borrower(slot),
+        ) {
+            Some(borrower) => borrower,
+            None => continue,
+        };
+        let (collateral, debt, interest) = match position_balances(env, &borrower)? {
+            Some(balances) => balances,
+            None => continue,
+        };
+        let total_debt = calculate_debt_value(debt, interest)?;
+        if !can_be_liquidated(env, collateral, total_debt, Some(&borrower))
+            .map_err(|_| LiquidationError::NotLiquidatable)?
+        {
+            continue;
+        }
+        let health_factor = collateral
+            .checked_mul(10_000)
+            .and_then(|value| value.checked_div(total_debt))
+            .ok_or(LiquidationError::Overflow)?;
+        positions.push_back(LiquidatablePosition {
+            borrower,
+            collateral,
+            debt,
+            interest,
+            health_factor,
+        });
+    }
+    Ok(positions)
+}
+
+/// Add or remove a borrower from the index after their position changes
+///
+/// Borrowers with debt principal or interest are indexed. Removal moves the
+/// last borrower into the freed slot so the index stays dense.
+pub(crate) fn sync_borrower_index(env: &Env, borrower: &Address, position: &Position) {
+    let slot_key = LiquidationDataKey::BorrowerSlot(borrower.clone());
+    let slot: Option<u32> = storage::read(env, &LIQUIDATION_NAMESPACE, &slot_key);
+    let has_debt = position.debt > 0 || position.borrow_interest > 0;
+    let count = get_borrower_count(env);
+
+    match (has_debt, slot) {
+        (true, None) => {
+            storage::write(
+                env,
+                &LIQUIDATION_NAMESPACE,
+                &LiquidationDataKey::Borrower(count),
+                borrower,
+            );
+            storage::write(env, &LIQUIDATION_NAMESPACE, &slot_key, &count);
+            storage::write(
+                env,
+                &LIQUIDATION_NAMESPACE,
+                &LiquidationDataKey::BorrowerCount,
+                &(count + 1),
+            );
+        }
+        (false, Some(slot)) => {
+            let last = count - 1;
+            if slot != last {
+                let moved: Address = storage::read(
+                    env,
+                    &LIQUIDATION_NAMESPACE,
+                    &LiquidationDataKey::Borrower(last),
+                )
+                .unwrap();
+                storage::write(
+                    env,
+                    &LIQUIDATION_NAMESPACE,
+                    &LiquidationDataKey::Borrower(slot),
+                    &moved,
+                );
+                storage::write(
+                    env,
+                    &LIQUIDATION_NAMESPACE,
+                    &LiquidationDataKey::BorrowerSlot(moved),
+                    &slot,
+                );
+            }
+            storage::remove(
+                env,
+                &LIQUIDATION_NAMESPACE,
+                &LiquidationDataKey::Borrower(last),
+            );
+            storage::remove(env, &LIQUIDATION_NAMESPACE, &slot_key);
+            storage::write(
+                env,
+                &LIQUIDATION_NAMESPACE,
+                &LiquidationDataKey::BorrowerCount,
+                &last,
+            );
+        }
+        _ => {}
+    }
+}
+
+/// Get the cumulative liquidation activity of a liquidator
+pub fn get_liquidator_stats(env: &Env, liquidator: &Address) -> LiquidatorStats {
+    storage::read(
+        env,
+        &LIQUIDATION_NAMESPACE,
+        &LiquidationDataKey::LiquidatorStats(liquidator.clone()),
+    )
+    .unwrap_or_default()
+}
+
+/// Get the top liquidators by debt repaid, highest first
+///
+/// Returns at most `limit` entries, bounded by `MAX_LEADERBOARD_SIZE`.
+pub fn get_liquidator_leaderboard(env: &Env, limit: u32) -> Vec<LeaderboardEntry> {
+    let mut entries = Vec::new(env);
+    for liquidator in get_leaderboard(env).iter().take(limit as usize) {
+        let stats = get_liquidator_stats(env, &liquidator);
+        entries.push_back(LeaderboardEntry { liquidator, stats });
+    }
+    entries
+}
+
+fn get_leaderboard(env: &Env) -> Vec<Address> {
+    storage::read(
+        env,
+        &LIQUIDATION_NAMESPACE,
+        &LiquidationDataKey::LiquidatorLeaderboard,
+    )
+    .unwrap_or(Vec::new(env))
+}
+
+/// Add a liquidation to the liquidator's stats and re-rank the leaderboard
+fn record_liquidator_stats(
+    env: &Env,
+    liquidator: &Address,
+    debt_repaid: i128,
+    collateral_seized: i128,
+    incentive_earned: i128,
+    timestamp: u64,
+) -> Result<(), LiquidationError> {
+    let mut stats = get_liquidator_stats(env, liquidator);
+    stats.liquidation_count = stats.liquidation_count.saturating_add(1);
+    stats.debt_repaid = stats
+        .debt_repaid
+        .checked_add(debt_repaid)
+        .ok_or(LiquidationError::Overflow)?;
+    stats.collateral_seized = stats
+        .collateral_seized
+        .checked_add(collateral_seized)
+        .ok_or(LiquidationError::Overflow)?;
+    stats.incentive_earned = stats
+        .incentive_earned
+        .checked_add(incentive_earned)
+        .ok_or(LiquidationError::Overflow)?;
+    stats.last_liquidation = timestamp;
+    storage::write(
+        env,
+        &LIQUIDATION_NAMESPACE,
+        &LiquidationDataKey::LiquidatorStats(liquidator.clone()),
+        &stats,
+    );
+
+    // Re-insert the liquidator at its rank; ties keep the earlier entry ahead
+    let mut leaderboard = get_leaderboard(env);
+    if let Some(index) = leaderboard.first_index_of(liquidator) {
+        leaderboard.remove(index);
+    }
+    let mut rank = leaderboard.len();
+    for (i, other) in leaderboard.iter().enumerate() {
+        if get_liquidator_stats(env, &other).debt_repaid < stats.debt_repaid {
+            rank = i as u32;
+            break;
+        }
+    }
+    if rank < MAX_LEADERBOARD_SIZE {
+        leaderboard.insert(rank, liquidator.clone());
+        if leaderboard.len() > MAX_LEADERBOARD_SIZE {
+            leaderboard.pop_back();
+        }
+        storage::write(
+            env,
+            &LIQUIDATION_NAMESPACE,
+            &LiquidationDataKey::LiquidatorLeaderboard,
+            &leaderboard,
+        );
+    }
+
+    Ok(())
+}
+
+/// Number of liquidation receipts ever archived (the next receipt id)
+pub fn get_liquidation_receipt_count(env: &Env) -> u64 {
+    storage::read(
+        env,
+        &LIQUIDATION_NAMESPACE,
+        &LiquidationDataKey::ReceiptCount,
+    )
+    .unwrap_or(0)
+}
+
+/// Get an archived liquidation receipt, if it has not been pruned
+pub fn get_liquidation_receipt(env: &Env, id: u64) -> Option<LiquidationReceipt> {
+    storage::read(
+        env,
+        &LIQUIDATION_NAMESPACE,
+        &LiquidationDataKey::Receipt(id),
+    )
+}
+
+/// Get archived liquidation receipts with ids from `start_id`, oldest first
+///
+/// Pruned ids are skipped. Returns at most `limit` receipts, bounded by
+/// `MAX_RECEIPT_PAGE_SIZE`; continue from the last id + 1.
+pub fn get_liquidation_receipts(env: &Env, start_id: u64, limit: u32) -> Vec<LiquidationReceipt> {
+    let count = get_liquidation_receipt_count(env);
+    let first = start_id.max(count.saturating_sub(MAX_ARCHIVED_RECEIPTS));
+    let limit = limit.min(MAX_RECEIPT_PAGE_SIZE) as u64;
+    let mut receipts = Vec::new(env);
+    for id in first..count.min(first.saturating_add(limit)) {
+        if let Some(receipt) = get_liquidation_receipt(env, id) {
+            receipts.push_back(receipt);
+        }
+    }
+    receipts
+}
+
+/// Store a receipt under the next id and prune the oldest beyond the bound
+fn archive_receipt(env: &Env, mut receipt: LiquidationReceipt) {
+    let id = get_liquidation_receipt_count(env);
+    receipt.id = id;
+    storage::write(
+        env,
+        &LIQUIDATION_NAMESPACE,
+        &LiquidationDataKey::Receipt(id),
+        &receipt,
+    );
+    storage::write(
+        env,
+        &LIQUIDATION_NAMESPACE,
+        &LiquidationDataKey::ReceiptCount,
+        &(id + 1),
+    );
+    if id >= MAX_ARCHIVED_RECEIPTS {
+        storage::remove(
+            env,
+            &LIQUIDATION_NAMESPACE,
+            &LiquidationDataKey::Receipt(id - MAX_ARCHIVED_RECEIPTS),
+        );
+    }
+}
@@ -0,0 +1,155 @@
+//! # Metric History
+//!
+//! Daily snapshots of protocol metrics kept on-chain, so dashboards can chart
+//! trends without an indexer.
+//!
+//! Each day (`timestamp / METRIC_DAY_SECONDS`) has one [`DailyMetrics`]
+//! bucket holding the day's latest values:
+//! - TVL and borrows: supplied and borrowed value of priced assets in the
+//!   accounting currency (see `analytics.rs`)
+//! - Utilization: value-weighted, in basis points
+//! - Borrow and supply rates of the core rate model, in basis points
+//!
+//! A bucket is refreshed on every change to the asset ledgers (deposits,
+//! withdrawals, borrows, repayments, liquidations) and on every keeper
+//! `poke_rates`, so quiet days still get a bucket while a keeper runs. Days
+//! without either have no bucket.
+//!
+//! ## Storage Layout
+//! Buckets live in a ring buffer of [`METRIC_HISTORY_DAYS`] slots indexed by
+//! `day % METRIC_HISTORY_DAYS`. A slot is overwritten when its day comes
+//! round again, so history older than the buffer is dropped; each bucket
+//! stores its day and stale slots are never returned.
+
+use soroban_sdk::{contracterror, contracttype, Env, Vec};
+
+/// Length of a metric bucket
+pub const METRIC_DAY_SECONDS: u64 = 86_400;
+
+/// Number of daily buckets kept
+pub const METRIC_HISTORY_DAYS: u64 = 90;
+
+/// Errors that can occur reading metric history
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum MetricHistoryError {
+    /// `from_day` is after `to_day`, or the range is longer than the history
+    InvalidRange = 1,
+}
+
+/// Storage keys for metric history
+#[contracttype]
+#[derive(Clone)]
+pub enum MetricHistoryDataKey {
+    /// Ring buffer slot (`day % METRIC_HISTORY_DAYS`)
+    /// Value type: DailyMetrics
+    Slot(u64),
+}
+
+/// Metric of a history series
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Metric {
+    Tvl,
+    Borrows,
+    Utilization,
+    BorrowRate,
+    SupplyRate,
+}
+
+/// Latest protocol metrics of a day
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DailyMetrics {
+    pub day: u64,
+    /// Supplied value in the accounting currency
+    pub tvl: i128,
+    /// Borrowed value in the accounting currency
+    pub borrows: i128,
+    pub utilization_bps: i128,
+    pub borrow_rate_bps: i128,
+    pub supply_rate_bps: i128,
+    /// Timestamp of the last refresh
+    pub updated_at: u64,
+}
+
+/// Value of a metric on a day
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MetricPoint {
+    pub day: u64,
+    pub value: i128,
+}
+
+/// Index of the current day
+pub fn get_metric_day(env: &Env) -> u64 {
+    env.ledger().timestamp() / METRIC_DAY_SECONDS
+}
+
+/// Refresh the current day's bucket with the latest metrics
+///
+/// Metrics that cannot be computed (no prices, no rate model) are recorded
+/// as zero rather than failing the state change that triggered the refresh.
+pub(crate) fn record_daily_metrics(env: &Env) {
+    let day = get_metric_day(env);
+    let (tvl, borrows, utilization_bps) =
+        crate::analytics::get_value_totals(env).unwrap_or((0, 0, 0));
+    let metrics = DailyMetrics {
+        day,
+        tvl,
+        borrows,
+        utilization_bps,
+        borrow_rate_bps: crate::interest_rate::get_current_borrow_rate(env).unwrap_or(0),
+        supply_rate_bps: crate::interest_rate::get_current_supply_rate(env).unwrap_or(0),
+        updated_at: env.ledger().timestamp(),
+    };
+    env.storage().persistent().set(
+        &MetricHistoryDataKey::Slot(day % METRIC_HISTORY_DAYS),
+        &metrics,
+    );
+}
+
+/// Get a day's bucket, if it is still in the ring buffer
+pub fn get_daily_metrics(env: &Env, day: u64) -> Option<DailyMetrics> {
+    env.storage()
+        .persistent()
+        .get::<MetricHistoryDataKey, DailyMetrics>(&MetricHistoryDataKey::Slot(
+            day % METRIC_HISTORY_DAYS,
+        ))
+        .filter(|metrics| metrics.day == day)
+}
+
+/// Get a metric for every day with a bucket from `from_day` to `to_day`
+///
+/// # Returns
+/// One point per day with a bucket, in day order
+///
+/// # Errors
+/// * `InvalidRange` - `from_day > to_day`, or more than
+///   `METRIC_HISTORY_DAYS` days requested
+pub fn get_metric_history(
+    env: &Env,
+    metric: Metric,
+    from_day: u64,
+    to_day: u64,
+) -> Result<Vec<MetricPoint>, MetricHistoryError> {
+    if from_day > to_day || to_day - from_day >= METRIC_HISTORY_DAYS {
+        return Err(MetricHistoryError::InvalidRange);
+    }
+
+    let mut points = Vec::new(env);
+    for day in from_day..=to_day {
+        if let Some(metrics) = get_daily_metrics(env, day) {
+            let value = match metric {
+                Metric::Tvl => metrics.tvl,
+                Metric::Borrows => metrics.borrows,
+                Metric::Utilization => metrics.utilization_bps,
+                Metric::BorrowRate => metrics.borrow_rate_bps,
+                Metric::SupplyRate => metrics.supply_rate_bps,
+            };
+            points.push_back(MetricPoint { day, value });
+        }
+    }
+    Ok(points)
+}
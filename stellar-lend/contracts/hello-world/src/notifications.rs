@@ -0,0 +1,245 @@
+//! # Borrower Notifications
+//!
+//! Opt-in contact hints that let off-chain notification services warn
+//! borrowers before liquidation.
+//!
+//! A borrower may register a 32-byte hint: the hash of an encrypted contact,
+//! or a webhook id issued by a notification service they subscribed to. Only
+//! the hint is stored; resolving it to an actual contact happens off-chain,
+//! so nothing readable about the borrower is published.
+//!
+//! ## Threshold Crossings
+//! Three admin-configurable health factor thresholds split positions into
+//! bands: warning, critical and liquidation imminent. Whenever a position
+//! update moves a borrower into a different band below the warning
+//! threshold, the band's event (`health_warning`, `health_critical` or
+//! `liquidation_imminent`) is emitted with the previous and new health
+//! factor and the contact hint (if any), so a service can match the event to
+//! its subscriber without recomputing the position. Updates that stay within
+//! a band, or return the position to health, emit nothing.
+
+use soroban_sdk::{contracterror, contracttype, Address, BytesN, Env, Symbol};
+
+use crate::deposit::Position;
+use crate::events::{
+    emit_admin_action, emit_contact_hint_updated, emit_health_critical, emit_health_warning,
+    emit_liquidation_imminent, AdminActionEvent, ContactHintUpdatedEvent, HealthCriticalEvent,
+    HealthWarningEvent, LiquidationImminentEvent,
+};
+use crate::risk_management::{calculate_health_factor, HEALTH_FACTOR_SCALE};
+
+/// Default warning threshold (1.1x the liquidation threshold)
+pub const HEALTH_WARNING_FACTOR: i128 = HEALTH_FACTOR_SCALE * 11 / 10;
+
+/// Default critical threshold (1.05x the liquidation threshold)
+pub const HEALTH_CRITICAL_FACTOR: i128 = HEALTH_FACTOR_SCALE * 105 / 100;
+
+/// Default liquidation imminent threshold (1.01x the liquidation threshold)
+pub const LIQUIDATION_IMMINENT_FACTOR: i128 = HEALTH_FACTOR_SCALE * 101 / 100;
+
+/// Errors that can occur in notification operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum NotificationError {
+    /// Caller is not the admin
+    Unauthorized = 1,
+    /// Thresholds are not strictly decreasing or fall below liquidation
+    InvalidThresholds = 2,
+}
+
+/// Health factor thresholds for notification events (scaled by
+/// `HEALTH_FACTOR_SCALE`)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HealthNotificationThresholds {
+    pub warning: i128,
+    pub critical: i128,
+    pub liquidation_imminent: i128,
+}
+
+/// Storage keys for notification data
+#[contracttype]
+#[derive(Clone)]
+pub enum NotificationDataKey {
+    /// Contact hint registered by a borrower
+    /// Value type: BytesN<32>
+    ContactHint(Address),
+    /// Configured notification thresholds
+    /// Value type: HealthNotificationThresholds
+    Thresholds,
+    /// Health factor after a borrower's last position update (absent = no debt)
+    /// Value type: i128
+    LastHealthFactor(Address),
+}
+
+/// Register or replace the caller's contact hint
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `user` - The borrower (must authorize)
+/// * `hint` - Hash of an encrypted contact, or a notification service webhook id
+pub fn set_contact_hint(env: &Env, user: Address, hint: BytesN<32>) {
+    user.require_auth();
+    env.storage()
+        .persistent()
+        .set(&NotificationDataKey::ContactHint(user.clone()), &hint);
+    emit_hint_updated(env, user, Some(hint));
+}
+
+/// Remove the caller's contact hint, opting out of notifications
+pub fn clear_contact_hint(env: &Env, user: Address) {
+    user.require_auth();
+    env.storage()
+        .persistent()
+        .remove(&NotificationDataKey::ContactHint(user.clone()));
+    emit_hint_updated(env, user, None);
+}
+
+/// Get a borrower's contact hint, if registered
+pub fn get_contact_hint(env: &Env, user: &Address) -> Option<BytesN<32>> {
+    env.storage()
+        .persistent()
+        .get(&NotificationDataKey::ContactHint(user.clone()))
+}
+
+/// Get the notification thresholds
+pub fn get_health_notification_thresholds(env: &Env) -> HealthNotificationThresholds {
+    env.storage()
+        .persistent()
+        .get(&NotificationDataKey::Thresholds)
+        .unwrap_or(HealthNotificationThresholds {
+            warning: HEALTH_WARNING_FACTOR,
+            critical: HEALTH_CRITICAL_FACTOR,
+            liquidation_imminent: LIQUIDATION_IMMINENT_FACTOR,
+        })
+}
+
+/// Set the notification thresholds (admin only)
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not admin
+/// * `InvalidThresholds` - Not `warning > critical > liquidation_imminent >=
+///   HEALTH_FACTOR_SCALE`
+pub fn set_health_notification_thresholds(
+    env: &Env,
+    caller: Address,
+    thresholds: HealthNotificationThresholds,
+) -> Result<(), NotificationError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| NotificationError::Unauthorized)?;
+    if thresholds.warning <= thresholds.critical
+        || thresholds.critical <= thresholds.liquidation_imminent
+        || thresholds.liquidation_imminent < HEALTH_FACTOR_SCALE
+    {
+        return Err(NotificationError::InvalidThresholds);
+    }
+    env.storage()
+        .persistent()
+        .set(&NotificationDataKey::Thresholds, &thresholds);
+
+    emit_admin_action(
+        env,
+        AdminActionEvent {
+            actor: caller,
+            action: Symbol::new(env, "set_health_notify_thresholds"),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Get a borrower's health factor as of their last position update
+/// (`i128::MAX` without debt)
+pub fn get_last_health_factor(env: &Env, user: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&NotificationDataKey::LastHealthFactor(user.clone()))
+        .unwrap_or(i128::MAX)
+}
+
+/// Emit a threshold-crossing event if an update moved a position into a
+/// different notification band
+///
+/// Called after every position update. Positions whose health factor cannot
+/// be computed are skipped.
+pub(crate) fn check_health_thresholds(env: &Env, user: &Address, position: &Position) {
+    let debt_value = position.debt.saturating_add(position.borrow_interest);
+    let Ok(health_factor) = calculate_health_factor(env, position.collateral, debt_value) else {
+        return;
+    };
+
+    let previous_health_factor = get_last_health_factor(env, user);
+    let key = NotificationDataKey::LastHealthFactor(user.clone());
+    if health_factor == i128::MAX {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &health_factor);
+    }
+
+    let thresholds = get_health_notification_thresholds(env);
+    let band = band_of(&thresholds, health_factor);
+    if band == 0 || band == band_of(&thresholds, previous_health_factor) {
+        return;
+    }
+
+    let contact_hint = get_contact_hint(env, user);
+    let timestamp = env.ledger().timestamp();
+    let user = user.clone();
+    match band {
+        1 => emit_health_warning(
+            env,
+            HealthWarningEvent {
+                user,
+                previous_health_factor,
+                health_factor,
+                contact_hint,
+                timestamp,
+            },
+        ),
+        2 => emit_health_critical(
+            env,
+            HealthCriticalEvent {
+                user,
+                previous_health_factor,
+                health_factor,
+                contact_hint,
+                timestamp,
+            },
+        ),
+        _ => emit_liquidation_imminent(
+            env,
+            LiquidationImminentEvent {
+                user,
+                previous_health_factor,
+                health_factor,
+                contact_hint,
+                timestamp,
+            },
+        ),
+    }
+}
+
+/// Notification band of a health factor: 0 healthy, 1 warning, 2 critical,
+/// 3 liquidation imminent
+fn band_of(thresholds: &HealthNotificationThresholds, health_factor: i128) -> u32 {
+    if health_factor < thresholds.liquidation_imminent {
+        3
+    } else if health_factor < thresholds.critical {
+        2
+    } else if health_factor < thresholds.warning {
+        1
+    } else {
+        0
+    }
+}
+
+fn emit_hint_updated(env: &Env, user: Address, contact_hint: Option<BytesN<32>>) {
+    emit_contact_hint_updated(
+        env,
+        ContactHintUpdatedEvent {
+            user,
+            contact_hint,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+}
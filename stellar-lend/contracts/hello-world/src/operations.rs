@@ -0,0 +1,109 @@
+//! # Operation Ids
+//!
+//! Assigns every deposit, withdrawal, borrow and repayment a protocol-wide,
+//! monotonically increasing operation id so integrators can reconcile their
+//! off-chain records with on-chain operations exactly.
+//!
+//! The id is embedded in the operation's event (`operation_id`) and the
+//! user's latest operation is kept as an [`OperationReceipt`]. The
+//! `deposit_collateral_with_id`, `withdraw_collateral_with_id`,
+//! `borrow_asset_with_id` and `repay_debt_with_id` entrypoints return the id
+//! to the caller alongside the usual result; the plain entrypoints keep their
+//! return values for compatibility.
+//!
+//! ## Storage Layout
+//! - `NextOperationId` — id assigned to the next operation (ids start at 1)
+//! - `LastOperation(user)` — receipt of the user's latest operation
+//!
+//! ## Invariants
+//! - Ids are never reused; a failed operation reverts its id assignment.
+//! - Ids strictly increase in execution order across all users and kinds.
+
+use soroban_sdk::{contracttype, Address, Env};
+
+/// Storage keys for operation ids
+#[contracttype]
+#[derive(Clone)]
+pub enum OperationDataKey {
+    /// Id assigned to the next operation
+    /// Value type: u64
+    NextOperationId,
+    /// Receipt of a user's latest operation
+    /// Value type: OperationReceipt
+    LastOperation(Address),
+}
+
+/// Kind of a core lending operation
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OperationKind {
+    Deposit,
+    Withdraw,
+    Borrow,
+    Repay,
+}
+
+/// Record of a completed operation
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperationReceipt {
+    pub operation_id: u64,
+    pub kind: OperationKind,
+    pub user: Address,
+    pub asset: Option<Address>,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+/// Number of operations recorded so far (also the latest assigned id)
+pub fn get_operation_count(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get::<OperationDataKey, u64>(&OperationDataKey::NextOperationId)
+        .unwrap_or(1)
+        - 1
+}
+
+/// Get the receipt of a user's latest operation
+pub fn get_last_operation(env: &Env, user: &Address) -> Option<OperationReceipt> {
+    env.storage()
+        .persistent()
+        .get(&OperationDataKey::LastOperation(user.clone()))
+}
+
+/// Id of a user's latest operation, 0 if they have none
+pub(crate) fn get_last_operation_id(env: &Env, user: &Address) -> u64 {
+    get_last_operation(env, user)
+        .map(|receipt| receipt.operation_id)
+        .unwrap_or(0)
+}
+
+/// Assign the next operation id and store the user's receipt
+///
+/// Called by the operation modules once all checks have passed, right before
+/// the operation's event is emitted.
+pub(crate) fn record_operation(
+    env: &Env,
+    kind: OperationKind,
+    user: &Address,
+    asset: &Option<Address>,
+    amount: i128,
+    timestamp: u64,
+) -> u64 {
+    let operation_id = get_operation_count(env) + 1;
+    env.storage()
+        .persistent()
+        .set(&OperationDataKey::NextOperationId, &(operation_id + 1));
+    env.storage().persistent().set(
+        &OperationDataKey::LastOperation(user.clone()),
+        &OperationReceipt {
+            operation_id,
+            kind,
+            user: user.clone(),
+            asset: asset.clone(),
+            amount,
+            timestamp,
+        },
+    );
+    operation_id
+}
@@ -0,0 +1,198 @@
+//! # Position Operators
+//!
+//! A user can authorize an operator (an automated vault, a keeper contract
+//! or another account) to manage their position with scoped permissions.
+//! The permissions are a bitmask of `PERMISSION_*` flags set with
+//! [`set_position_operator`]; zero revokes the operator.
+//!
+//! Operators act through [`execute_as_operator`], which runs the action
+//! through the regular deposit, withdraw, borrow or repay logic for the user.
+//! Only the operator authorizes the call. Funds never leave the user's
+//! control: deposits and repayments are pulled from the user's own wallet
+//! (against the user's token allowance to this contract), and withdrawals
+//! and borrows are always paid to the user, never to the operator.
+//!
+//! ## Storage Layout
+//! - `Permissions(user, operator)` — the operator's permission bitmask
+
+use soroban_sdk::{contracterror, contracttype, Address, Env, Symbol};
+
+use crate::batch::ProtocolAction;
+use crate::borrow::{borrow_asset, BorrowError};
+use crate::deposit::{deposit_collateral, DepositError};
+use crate::events::{
+    emit_operator_action, emit_position_operator_set, OperatorActionEvent, PositionOperatorSetEvent,
+};
+use crate::repay::{repay_debt, RepayError};
+use crate::withdraw::{withdraw_collateral, WithdrawError};
+
+/// Operator may deposit the user's tokens as collateral
+pub const PERMISSION_DEPOSIT: u32 = 1;
+/// Operator may withdraw collateral to the user
+pub const PERMISSION_WITHDRAW: u32 = 1 << 1;
+/// Operator may borrow against the position, paid to the user
+pub const PERMISSION_BORROW: u32 = 1 << 2;
+/// Operator may repay the user's debt from the user's tokens
+pub const PERMISSION_REPAY: u32 = 1 << 3;
+/// Every permission flag
+pub const PERMISSION_ALL: u32 =
+    PERMISSION_DEPOSIT | PERMISSION_WITHDRAW | PERMISSION_BORROW | PERMISSION_REPAY;
+
+/// Errors that can occur in operator operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum OperatorError {
+    /// The operator lacks the permission for the action
+    Unauthorized = 1,
+    /// The bitmask has bits outside `PERMISSION_ALL`
+    InvalidPermissions = 2,
+    /// The operator is the user or this contract
+    InvalidOperator = 3,
+    /// The deposit failed
+    DepositFailed = 4,
+    /// The borrow failed
+    BorrowFailed = 5,
+    /// The repayment failed
+    RepayFailed = 6,
+    /// The withdrawal failed
+    WithdrawFailed = 7,
+    /// Reentrancy detected
+    Reentrancy = 8,
+}
+
+/// Storage keys for position operators
+#[contracttype]
+#[derive(Clone)]
+pub enum OperatorDataKey {
+    /// Permissions granted by a user to an operator
+    /// Value type: u32
+    Permissions(Address, Address),
+}
+
+/// Set the permissions of `operator` over `user`'s position (user only)
+///
+/// # Arguments
+/// * `user` - The position owner (must authorize)
+/// * `operator` - The address being authorized
+/// * `permissions` - Bitmask of `PERMISSION_*` flags; zero revokes the operator
+///
+/// # Errors
+/// * `InvalidPermissions` - The bitmask has bits outside `PERMISSION_ALL`
+/// * `InvalidOperator` - The operator is the user or this contract
+pub fn set_position_operator(
+    env: &Env,
+    user: Address,
+    operator: Address,
+    permissions: u32,
+) -> Result<(), OperatorError> {
+    user.require_auth();
+    if permissions & !PERMISSION_ALL != 0 {
+        return Err(OperatorError::InvalidPermissions);
+    }
+    if operator == user || operator == env.current_contract_address() {
+        return Err(OperatorError::InvalidOperator);
+    }
+
+    let key = OperatorDataKey::Permissions(user.clone(), operator.clone());
+    if permissions == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &permissions);
+    }
+
+    emit_position_operator_set(
+        env,
+        PositionOperatorSetEvent {
+            user,
+            operator,
+            permissions,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Get the permissions of `operator` over `user`'s position (0 if none)
+pub fn get_position_operator(env: &Env, user: &Address, operator: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&OperatorDataKey::Permissions(
+            user.clone(),
+            operator.clone(),
+        ))
+        .unwrap_or(0)
+}
+
+/// Run `action` on `user`'s position as `operator`
+///
+/// # Returns
+/// The new collateral balance for deposits and withdrawals, the total debt
+/// for borrows and the remaining debt for repays
+///
+/// # Errors
+/// * `Unauthorized` - The operator lacks the permission for the action
+/// * `DepositFailed` / `BorrowFailed` / `RepayFailed` / `WithdrawFailed` -
+///   The action failed
+/// * `Reentrancy` - Called while another operation is in progress
+pub fn execute_as_operator(
+    env: &Env,
+    operator: Address,
+    user: Address,
+    action: ProtocolAction,
+) -> Result<i128, OperatorError> {
+    operator.require_auth();
+    let (permission, operation, asset, amount) = match &action {
+        ProtocolAction::Deposit(asset, amount) => (PERMISSION_DEPOSIT, "deposit", asset, *amount),
+        ProtocolAction::Withdraw(asset, amount) => {
+            (PERMISSION_WITHDRAW, "withdraw", asset, *amount)
+        }
+        ProtocolAction::Borrow(asset, amount) => (PERMISSION_BORROW, "borrow", asset, *amount),
+        ProtocolAction::Repay(asset, amount) => (PERMISSION_REPAY, "repay", asset, *amount),
+    };
+    if get_position_operator(env, &user, &operator) & permission == 0 {
+        return Err(OperatorError::Unauthorized);
+    }
+    let asset = asset.clone();
+
+    let result = match action {
+        ProtocolAction::Deposit(asset, amount) => {
+            deposit_collateral(env, user.clone(), asset, amount).map_err(|e| match e {
+                DepositError::Reentrancy => OperatorError::Reentrancy,
+                _ => OperatorError::DepositFailed,
+            })?
+        }
+        ProtocolAction::Withdraw(asset, amount) => {
+            withdraw_collateral(env, user.clone(), asset, amount).map_err(|e| match e {
+                WithdrawError::Reentrancy => OperatorError::Reentrancy,
+                _ => OperatorError::WithdrawFailed,
+            })?
+        }
+        ProtocolAction::Borrow(asset, amount) => borrow_asset(env, user.clone(), asset, amount)
+            .map_err(|e| match e {
+                BorrowError::Reentrancy => OperatorError::Reentrancy,
+                _ => OperatorError::BorrowFailed,
+            })?,
+        ProtocolAction::Repay(asset, amount) => {
+            repay_debt(env, user.clone(), asset, amount)
+                .map_err(|e| match e {
+                    RepayError::Reentrancy => OperatorError::Reentrancy,
+                    _ => OperatorError::RepayFailed,
+                })?
+                .0
+        }
+    };
+
+    emit_operator_action(
+        env,
+        OperatorActionEvent {
+            operator,
+            user,
+            operation: Symbol::new(env, operation),
+            asset,
+            amount,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(result)
+}
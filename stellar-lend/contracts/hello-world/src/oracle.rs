@@ -5,21 +5,97 @@
 //!
 //! ## Price Resolution Order
 //! 1. **Cache**: returns a cached price if the TTL has not expired.
-//! 2. **Primary feed**: reads the on-chain `PriceFeed` entry; rejects if stale.
-//! 3. **Fallback oracle**: if the primary is stale or missing, queries a
+//! 2. **SEP-40 pull**: if a [`Sep40Source`] is registered for the asset, pulls
+//!    `lastprice` from that oracle contract (e.g. Reflector); failed, missing
+//!    or stale pulls fall through to the pushed feeds.
+//! 3. **Primary feed**: reads the on-chain `PriceFeed` entry; rejects if stale.
+//! 4. **Fallback oracle**: if the primary is stale or missing, queries a
 //!    configured fallback oracle address.
 //!
 //! ## Safety
 //! - Price deviation between consecutive updates is bounded (default ±5%).
 //! - Staleness threshold defaults to 1 hour; configurable by admin.
 //! - Sanity-check bounds on min/max price are enforced on every update.
-//! - Only the admin or the designated oracle address may submit price updates.
+//! - Only the admin, the designated oracle address or an `oracle_feeder` role
+//!   holder may submit price updates.
+//!
+//! ## Updater Accountability
+//! Once an [`UpdaterPolicy`] is configured, non-admin updaters must hold at
+//! least `min_bond` of the bond token (posted via [`post_oracle_bond`]) and may
+//! push a given asset at most once per `min_update_interval`. Governance can
+//! slash the bond of an updater whose prices are later proven manipulated.
+//! Bonds stay locked for `bond_lock_seconds` after an updater's last push so
+//! that slashing remains possible during the dispute window.
+//!
+//! ## Price History and TWAP
+//! Every primary-feed update is also appended to a per-asset ring buffer of
+//! [`PriceObservation`]s holding the latest `capacity` entries (admin
+//! configurable, default 24). [`get_twap`] weights each observation by how
+//! long it was the current price within the requested window, so a single
+//! manipulated update moves the average only in proportion to its duration.
+//!
+//! ## Multi-Feeder Aggregation
+//! The admin may register up to `MAX_PRICE_FEEDERS` independent feeders for
+//! an asset. Once an asset has feeders, [`get_price`] returns the median of
+//! their fresh submissions instead of the single primary feed. If the fresh
+//! submissions spread further apart than `max_deviation_bps`, the asset's
+//! price is marked disputed and new borrows of the asset are refused until
+//! the admin resolves the dispute.
+//!
+//! ## Failover Policies
+//! An asset with a [`FailoverPolicy`] skips the implicit order above and
+//! walks an explicit chain instead: primary (feeders, SEP-40 pull or the
+//! pushed feed) → fallback oracle → TWAP → frozen. Disabled steps are
+//! skipped, and a frozen asset cannot be priced until a source recovers.
+//! The step that priced the asset is stored as its [`FailoverState`]; every
+//! change of step emits an `oracle_failover_event`, so integrators can tell
+//! which source priced a given operation. Failover assets are not served
+//! from the price cache.
+//!
+//! ## Emergency Price Overrides
+//! When feeds fail, the admin or a governance guardian may pin an asset to a
+//! manual [`PriceOverride`] so liquidations and withdrawals keep working.
+//! An override must expire within `MAX_PRICE_OVERRIDE_DURATION` and stay
+//! within `MAX_PRICE_OVERRIDE_DEVIATION_BPS` of the last pushed primary
+//! price. While active it takes precedence over every other source,
+//! including a TWAP liquidation price; once expired the regular resolution
+//! applies again.
+//!
+//! ## Heartbeat Monitoring
+//! The admin may give an asset a [`HeartbeatConfig`]. When no price update
+//! (pushed primary feed or feeder median) has arrived within the heartbeat,
+//! the asset is degraded: new borrows of it are refused and liquidations
+//! value it at its last good price moved against the borrower by the safety
+//! buffer (collateral lower, debt higher). The first check that finds the
+//! asset degraded records a [`DegradedState`] and emits a
+//! `price_stale_event`; the next fresh update ends degraded mode.
+//!
+//! ## Circuit Breaker
+//! An asset with a [`CircuitBreakerConfig`] trips its breaker when a new
+//! price moves more than `max_move_bps` from the previous one and the
+//! previous one is at most `window_secs` old. Both oracle updates and
+//! cross-asset price updates are checked. A tripped breaker pauses new
+//! borrows of the asset (core and cross-asset) and emits a
+//! `circuit_breaker_tripped_event`; the price update itself still applies.
+//! The admin or a governance guardian resets the breaker.
 
 #![allow(unused)]
+use crate::admin::ORACLE_FEEDER;
 use crate::deposit::DepositDataKey;
-use crate::events::{emit_price_updated, PriceUpdatedEvent};
+use crate::events::{
+    emit_circuit_breaker_reset, emit_circuit_breaker_tripped, emit_oracle_bond_posted,
+    emit_oracle_bond_slashed, emit_oracle_bond_withdrawn, emit_oracle_failover,
+    emit_price_dispute_resolved, emit_price_disputed, emit_price_override_cleared,
+    emit_price_override_set, emit_price_stale, emit_price_updated, CircuitBreakerResetEvent,
+    CircuitBreakerTrippedEvent, OracleBondPostedEvent, OracleBondSlashedEvent,
+    OracleBondWithdrawnEvent, OracleFailoverEvent, PriceDisputeResolvedEvent, PriceDisputedEvent,
+    PriceOverrideClearedEvent, PriceOverrideSetEvent, PriceStaleEvent, PriceUpdatedEvent,
+};
 use crate::risk_management::get_admin;
-use soroban_sdk::{contracterror, contracttype, Address, Env, IntoVal, Map, Symbol, Val, Vec};
+use crate::storage::{GovernanceDataKey, GuardianConfig};
+use soroban_sdk::{
+    contractclient, contracterror, contracttype, Address, Env, IntoVal, Map, Symbol, Val, Vec,
+};
 
 /// Errors that can occur during oracle operations
 #[contracterror]
@@ -44,6 +120,34 @@ pub enum OracleError {
     AssetNotSupported = 8,
     /// Fallback oracle not configured
     FallbackNotConfigured = 9,
+    /// Updater pushed this asset again before the minimum interval elapsed
+    UpdateRateLimited = 10,
+    /// Updater bond is below the required minimum or the amount is invalid
+    InsufficientBond = 11,
+    /// Bond cannot be withdrawn until the lock period after the last push ends
+    BondLocked = 12,
+    /// No updater policy has been configured
+    BondNotConfigured = 13,
+    /// No price history is recorded for the asset
+    InsufficientHistory = 14,
+    /// History capacity or TWAP window is out of range
+    InvalidHistoryConfig = 15,
+    /// Asset already has the maximum number of feeders
+    TooManyFeeders = 16,
+    /// Asset price is not under dispute
+    NotDisputed = 17,
+    /// Every source in the asset's failover policy failed
+    PriceFrozen = 18,
+    /// Override price too far from the last oracle price
+    OverrideOutOfBounds = 19,
+    /// Override expiry is in the past or too far in the future
+    InvalidOverrideExpiry = 20,
+    /// Heartbeat is zero or the safety buffer is out of range
+    InvalidHeartbeatConfig = 21,
+    /// Circuit breaker move limit or window is out of range
+    InvalidCircuitBreakerConfig = 22,
+    /// Asset's circuit breaker is not tripped
+    CircuitBreakerNotTripped = 23,
 }
 
 /// Storage keys for oracle-related data
@@ -65,6 +169,60 @@ pub enum OracleDataKey {
     OracleConfig,
     /// Pause switches specifically for oracle updates: Map<Symbol, bool>
     PauseSwitches,
+    /// Bond and rate-limit policy for non-admin updaters
+    /// Value type: UpdaterPolicy
+    UpdaterPolicy,
+    /// Bond currently locked by an updater
+    /// Value type: i128
+    UpdaterBond(Address),
+    /// Sum of all updater bonds held by the contract
+    /// Value type: i128
+    TotalUpdaterBonds,
+    /// Timestamp of an updater's last push for an asset (updater, asset)
+    /// Value type: u64
+    UpdaterLastPush(Address, Address),
+    /// Timestamp of an updater's last push for any asset
+    /// Value type: u64
+    UpdaterLastActivity(Address),
+    /// Ring buffer of recent primary-feed observations for an asset
+    /// Value type: PriceHistory
+    PriceHistory(Address),
+    /// Number of observations kept per asset
+    /// Value type: u32
+    PriceHistoryCapacity,
+    /// Feeders registered for an asset
+    /// Value type: Vec<Address>
+    Feeders(Address),
+    /// Latest submission of a feeder for an asset (asset, feeder)
+    /// Value type: PriceObservation
+    FeederPrice(Address, Address),
+    /// Open dispute over an asset's feeder prices
+    /// Value type: PriceDispute
+    PriceDispute(Address),
+    /// SEP-40 oracle that prices an asset by pull
+    /// Value type: Sep40Source
+    Sep40Source(Address),
+    /// Explicit failover chain for an asset
+    /// Value type: FailoverPolicy
+    FailoverPolicy(Address),
+    /// Source currently pricing an asset under its failover policy
+    /// Value type: FailoverState
+    FailoverState(Address),
+    /// Manual emergency price for an asset
+    /// Value type: PriceOverride
+    PriceOverride(Address),
+    /// Heartbeat monitoring of an asset's price updates
+    /// Value type: HeartbeatConfig
+    PriceHeartbeat(Address),
+    /// Degraded mode entered after a missed heartbeat
+    /// Value type: DegradedState
+    PriceDegraded(Address),
+    /// Price move limit that pauses borrows of an asset
+    /// Value type: CircuitBreakerConfig
+    CircuitBreaker(Address),
+    /// Price move that tripped an asset's circuit breaker
+    /// Value type: CircuitBreakerTrip
+    CircuitBreakerTrip(Address),
 }
 
 /// Price feed data structure
@@ -109,6 +267,221 @@ pub struct OracleConfig {
     pub max_price: i128,
 }
 
+/// Bond and rate-limit policy applied to non-admin price updaters
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct UpdaterPolicy {
+    /// Token in which updater bonds are posted
+    pub bond_token: Address,
+    /// Minimum bond required to push prices (0 = no bond required)
+    pub min_bond: i128,
+    /// Minimum seconds between pushes from one updater for the same asset
+    pub min_update_interval: u64,
+    /// Seconds after an updater's last push before its bond can be withdrawn
+    pub bond_lock_seconds: u64,
+}
+
+/// Single historical price observation
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PriceObservation {
+    /// Observed price
+    pub price: i128,
+    /// Timestamp of the update that produced this price
+    pub timestamp: u64,
+}
+
+/// Fixed-capacity ring buffer of price observations
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PriceHistory {
+    /// Observations in slot order; grows to `capacity`, then wraps
+    pub observations: Vec<PriceObservation>,
+    /// Slot the next observation is written to
+    pub next: u32,
+    /// Capacity the buffer was written with
+    pub capacity: u32,
+}
+
+/// SEP-40 asset identifier
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Sep40Asset {
+    /// Stellar asset contract address
+    Stellar(Address),
+    /// Off-chain asset ticker
+    Other(Symbol),
+}
+
+/// SEP-40 price record
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sep40PriceData {
+    /// Price scaled by the oracle's `decimals()`
+    pub price: i128,
+    /// Timestamp the price was recorded
+    pub timestamp: u64,
+}
+
+/// Subset of the SEP-40 oracle interface used for price pulls
+#[contractclient(name = "Sep40OracleClient")]
+pub trait Sep40Oracle {
+    /// Decimals of all prices reported by the oracle
+    fn decimals(env: Env) -> u32;
+    /// Most recent price record for an asset
+    fn lastprice(env: Env, asset: Sep40Asset) -> Option<Sep40PriceData>;
+}
+
+/// SEP-40 oracle registered to price an asset
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sep40Source {
+    /// SEP-40 oracle contract
+    pub oracle: Address,
+    /// Identifier of the asset in that oracle
+    pub feed_asset: Sep40Asset,
+    /// Decimals pulled prices are rescaled to (should match pushed feeds)
+    pub price_decimals: u32,
+}
+
+/// Maximum decimals a SEP-40 price can be rescaled to
+const MAX_SEP40_PRICE_DECIMALS: u32 = 18;
+
+/// Feeder disagreement that disputed an asset's price
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PriceDispute {
+    /// Lowest fresh feeder price when the dispute was raised
+    pub min_price: i128,
+    /// Highest fresh feeder price when the dispute was raised
+    pub max_price: i128,
+    /// Spread between them in basis points of `min_price`
+    pub deviation_bps: i128,
+    /// Timestamp the dispute was raised
+    pub flagged_at: u64,
+}
+
+/// Step of a failover chain
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PriceSource {
+    /// Feeder median, SEP-40 pull or pushed primary feed
+    Primary,
+    /// Fallback oracle feed
+    Fallback,
+    /// Time-weighted average of the primary feed history
+    Twap,
+    /// No source could price the asset
+    Frozen,
+}
+
+/// Explicit failover chain for an asset
+///
+/// The primary source is always tried first and the asset freezes when every
+/// enabled step fails.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FailoverPolicy {
+    /// Try the fallback oracle after the primary
+    pub use_fallback: bool,
+    /// TWAP window tried after the fallback (0 disables the TWAP step); the
+    /// window must contain at least one observation
+    pub twap_window_secs: u64,
+}
+
+/// Source pricing an asset under its failover policy
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FailoverState {
+    pub source: PriceSource,
+    /// Timestamp the asset moved to this source
+    pub since: u64,
+}
+
+/// Manual price pinned to an asset during an oracle outage
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PriceOverride {
+    /// Price returned while the override is active
+    pub price: i128,
+    /// Last primary feed price the override was bounded against
+    pub reference_price: i128,
+    /// Timestamp after which the override no longer applies
+    pub expires_at: u64,
+    /// Admin or guardian that set the override
+    pub set_by: Address,
+    /// Timestamp the override was set
+    pub set_at: u64,
+}
+
+/// Heartbeat monitoring of an asset's price updates
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct HeartbeatConfig {
+    /// Longest gap between price updates before the asset is degraded
+    pub heartbeat_secs: u64,
+    /// Buffer applied to the last good price during liquidations, in basis
+    /// points (at most `MAX_HEARTBEAT_SAFETY_BUFFER_BPS`)
+    pub safety_buffer_bps: i128,
+}
+
+/// Degraded mode of an asset whose heartbeat was missed
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DegradedState {
+    /// Last good price, or 0 if the asset was never priced
+    pub last_price: i128,
+    /// Timestamp of the last good price
+    pub last_updated: u64,
+    /// Timestamp the missed heartbeat was recorded
+    pub since: u64,
+}
+
+/// Price move limit that pauses borrows of an asset
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct CircuitBreakerConfig {
+    /// Largest move from the previous price, in basis points
+    pub max_move_bps: i128,
+    /// Moves are only checked against a previous price at most this old
+    pub window_secs: u64,
+}
+
+/// Price move that tripped an asset's circuit breaker
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct CircuitBreakerTrip {
+    /// Price before the move
+    pub previous_price: i128,
+    /// Price that tripped the breaker
+    pub price: i128,
+    /// Size of the move in basis points of `previous_price`
+    pub move_bps: i128,
+    /// Timestamp the breaker tripped
+    pub tripped_at: u64,
+}
+
+/// Longest window a circuit breaker compares prices over (24 hours)
+pub const MAX_CIRCUIT_BREAKER_WINDOW: u64 = 86_400;
+
+/// Largest safety buffer on a degraded asset's last good price (50%)
+pub const MAX_HEARTBEAT_SAFETY_BUFFER_BPS: i128 = 5_000;
+
+/// Longest an emergency price override may stay active (24 hours)
+pub const MAX_PRICE_OVERRIDE_DURATION: u64 = 86_400;
+
+/// Largest distance of an override from the last oracle price (20%)
+pub const MAX_PRICE_OVERRIDE_DEVIATION_BPS: i128 = 2_000;
+
+/// Maximum number of feeders per asset
+pub const MAX_PRICE_FEEDERS: u32 = 7;
+
+/// Default number of observations kept per asset
+pub const DEFAULT_PRICE_HISTORY_CAPACITY: u32 = 24;
+
+/// Maximum number of observations kept per asset
+pub const MAX_PRICE_HISTORY_CAPACITY: u32 = 144;
+
 /// Default configuration values
 const DEFAULT_MAX_DEVIATION_BPS: i128 = 500; // 5%
 const DEFAULT_MAX_STALENESS_SECONDS: u64 = 3600; // 1 hour
@@ -128,7 +501,7 @@ fn get_default_config() -> OracleConfig {
 }
 
 /// Get oracle configuration
-fn get_oracle_config(env: &Env) -> OracleConfig {
+pub(crate) fn get_oracle_config(env: &Env) -> OracleConfig {
     let config_key = OracleDataKey::OracleConfig;
     env.storage()
         .persistent()
@@ -245,7 +618,7 @@ fn cache_price(env: &Env, asset: &Address, price: i128) {
 ///
 /// # Arguments
 /// * `env` - The Soroban environment
-/// * `caller` - The address calling this function (must be admin or oracle)
+/// * `caller` - The address calling this function (must be admin, oracle or oracle feeder)
 /// * `asset` - The asset address
 /// * `price` - The new price
 /// * `decimals` - Price decimals
@@ -262,17 +635,8 @@ pub fn update_price_feed(
     oracle: Address,
 ) -> Result<i128, OracleError> {
     // Check if oracle updates are paused
-    let pause_key = OracleDataKey::PauseSwitches;
-    if let Some(pause_map) = env
-        .storage()
-        .persistent()
-        .get::<OracleDataKey, Map<Symbol, bool>>(&pause_key)
-    {
-        if let Some(paused) = pause_map.get(Symbol::new(env, "pause_oracle")) {
-            if paused {
-                return Err(OracleError::OraclePaused);
-            }
-        }
+    if is_oracle_paused(env) {
+        return Err(OracleError::OraclePaused);
     }
 
     // Validate caller authorization
@@ -282,8 +646,10 @@ pub fn update_price_feed(
 
     let is_primary = primary.map(|p| p == caller).unwrap_or(false);
     let is_fallback = fallback.map(|f| f == caller).unwrap_or(false);
+    // Oracle feeders push to the primary feed of any asset
+    let is_feeder = crate::admin::has_role(env, Symbol::new(env, ORACLE_FEEDER), caller.clone());
 
-    if !is_admin && !is_primary && !is_fallback {
+    if !is_admin && !is_primary && !is_fallback && !is_feeder {
         return Err(OracleError::Unauthorized);
     }
 
@@ -295,8 +661,14 @@ pub fn update_price_feed(
     // Validate price
     validate_price(env, price)?;
 
+    // Bonded, rate-limited updaters (admin pushes are exempt)
+    if !is_admin {
+        require_updater_allowed(env, &caller, &asset)?;
+    }
+
     // Determine target storage key and get current feed for deviation check
-    let feed_key = if is_fallback && !is_primary && !is_admin {
+    let is_fallback_feed = is_fallback && !is_primary && !is_admin && !is_feeder;
+    let feed_key = if is_fallback_feed {
         OracleDataKey::FallbackFeed(asset.clone())
     } else {
         OracleDataKey::PriceFeed(asset.clone())
@@ -332,9 +704,18 @@ pub fn update_price_feed(
         env.storage().persistent().set(&primary_key, &oracle);
     }
 
+    // Only the primary feed contributes to the TWAP history
+    if !is_fallback_feed {
+        record_observation(env, &asset, price, timestamp);
+    }
+
     // Update cache
     cache_price(env, &asset, price);
 
+    if !is_admin {
+        record_updater_push(env, &caller, &asset, timestamp);
+    }
+
     // Emit price update event
     emit_price_updated(
         env,
@@ -353,18 +734,41 @@ pub fn update_price_feed(
 
 /// Get price for an asset with fallback support
 ///
+/// Assets with registered feeders are priced at the median of the fresh
+/// feeder submissions; the primary feed, cache and fallback are not used.
+///
 /// # Arguments
 /// * `env` - The Soroban environment
 /// * `asset` - The asset address
 ///
 /// # Returns
 /// Returns the current price, using cache or fallback if needed
+///
+/// An active emergency price override takes precedence over every source.
 pub fn get_price(env: &Env, asset: &Address) -> Result<i128, OracleError> {
+    if let Some(price_override) = get_price_override(env, asset) {
+        return Ok(price_override.price);
+    }
+
+    if let Some(policy) = get_failover_policy(env, asset) {
+        return get_failover_price(env, asset, &policy);
+    }
+
+    if !get_price_feeders(env, asset).is_empty() {
+        return get_aggregated_price(env, asset);
+    }
+
     // Try cache first
     if let Some(cached_price) = get_cached_price(env, asset) {
         return Ok(cached_price);
     }
 
+    // Pull from a registered SEP-40 oracle, falling back to pushed feeds
+    if let Ok(price) = pull_sep40_price(env, asset) {
+        cache_price(env, asset, price);
+        return Ok(price);
+    }
+
     // Get primary price feed
     let feed_key = OracleDataKey::PriceFeed(asset.clone());
     if let Some(feed) = env
@@ -492,6 +896,13 @@ pub fn configure_oracle(
     // Check authorization
     crate::admin::require_admin(env, &caller).map_err(|_| OracleError::Unauthorized)?;
 
+    apply_oracle_config(env, config)
+}
+
+/// Validate and store the oracle configuration without an authorization check
+///
+/// Shared by [`configure_oracle`] and parameter bundles.
+pub(crate) fn apply_oracle_config(env: &Env, config: OracleConfig) -> Result<(), OracleError> {
     // Validate configuration
     if config.max_deviation_bps <= 0 || config.max_deviation_bps > 10000 {
         return Err(OracleError::InvalidPrice);
@@ -507,3 +918,1350 @@ pub fn configure_oracle(
 
     Ok(())
 }
+
+/// Get the updater bond and rate-limit policy, if configured
+pub fn get_updater_policy(env: &Env) -> Option<UpdaterPolicy> {
+    env.storage()
+        .persistent()
+        .get::<OracleDataKey, UpdaterPolicy>(&OracleDataKey::UpdaterPolicy)
+}
+
+/// Get the bond currently locked by an updater
+pub fn get_oracle_bond(env: &Env, updater: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<OracleDataKey, i128>(&OracleDataKey::UpdaterBond(updater.clone()))
+        .unwrap_or(0)
+}
+
+/// Configure the bond and rate-limit policy for price updaters
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `caller` - The address calling this function (must be admin)
+/// * `policy` - The new updater policy
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not the admin
+/// * `InsufficientBond` - Minimum bond is negative
+/// * `InvalidOracle` - Bond token changed while bonds are outstanding
+pub fn set_updater_policy(
+    env: &Env,
+    caller: Address,
+    policy: UpdaterPolicy,
+) -> Result<(), OracleError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| OracleError::Unauthorized)?;
+
+    if policy.min_bond < 0 {
+        return Err(OracleError::InsufficientBond);
+    }
+
+    if let Some(current) = get_updater_policy(env) {
+        if current.bond_token != policy.bond_token && get_total_bonds(env) > 0 {
+            return Err(OracleError::InvalidOracle);
+        }
+    }
+
+    env.storage()
+        .persistent()
+        .set(&OracleDataKey::UpdaterPolicy, &policy);
+
+    Ok(())
+}
+
+/// Lock bond tokens for an updater
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `updater` - The updater posting the bond (must authorize)
+/// * `amount` - Amount of the bond token to lock
+///
+/// # Returns
+/// The updater's total bond after posting
+pub fn post_oracle_bond(env: &Env, updater: Address, amount: i128) -> Result<i128, OracleError> {
+    updater.require_auth();
+
+    let policy = get_updater_policy(env).ok_or(OracleError::BondNotConfigured)?;
+    if amount <= 0 {
+        return Err(OracleError::InsufficientBond);
+    }
+
+    let token_client = soroban_sdk::token::Client::new(env, &policy.bond_token);
+    token_client.transfer(&updater, &env.current_contract_address(), &amount);
+
+    let total_bond = get_oracle_bond(env, &updater)
+        .checked_add(amount)
+        .ok_or(OracleError::Overflow)?;
+    set_bond(env, &updater, total_bond, amount);
+
+    emit_oracle_bond_posted(
+        env,
+        OracleBondPostedEvent {
+            updater,
+            amount,
+            total_bond,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(total_bond)
+}
+
+/// Withdraw bond tokens once the lock period after the last push has passed
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `updater` - The updater withdrawing (must authorize)
+/// * `amount` - Amount of the bond token to release
+///
+/// # Returns
+/// The updater's remaining bond
+pub fn withdraw_oracle_bond(
+    env: &Env,
+    updater: Address,
+    amount: i128,
+) -> Result<i128, OracleError> {
+    updater.require_auth();
+
+    let policy = get_updater_policy(env).ok_or(OracleError::BondNotConfigured)?;
+    let bond = get_oracle_bond(env, &updater);
+    if amount <= 0 || amount > bond {
+        return Err(OracleError::InsufficientBond);
+    }
+
+    let last_push = env
+        .storage()
+        .persistent()
+        .get::<OracleDataKey, u64>(&OracleDataKey::UpdaterLastActivity(updater.clone()));
+    if let Some(last_push) = last_push {
+        if env.ledger().timestamp() < last_push.saturating_add(policy.bond_lock_seconds) {
+            return Err(OracleError::BondLocked);
+        }
+    }
+
+    let remaining = bond - amount;
+    set_bond(env, &updater, remaining, -amount);
+
+    let token_client = soroban_sdk::token::Client::new(env, &policy.bond_token);
+    token_client.transfer(&env.current_contract_address(), &updater, &amount);
+
+    emit_oracle_bond_withdrawn(
+        env,
+        OracleBondWithdrawnEvent {
+            updater,
+            amount,
+            total_bond: remaining,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(remaining)
+}
+
+/// Slash an updater's bond after its prices were proven manipulated
+///
+/// The slashed amount is capped at the updater's bond and transferred to
+/// `recipient` (e.g. the treasury or affected users' compensation pool).
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `caller` - The address calling this function (must be admin/governance)
+/// * `updater` - The updater being slashed
+/// * `amount` - Maximum amount to slash
+/// * `recipient` - Receiver of the slashed tokens
+///
+/// # Returns
+/// The amount actually slashed
+pub fn slash_oracle_bond(
+    env: &Env,
+    caller: Address,
+    updater: Address,
+    amount: i128,
+    recipient: Address,
+) -> Result<i128, OracleError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| OracleError::Unauthorized)?;
+
+    let policy = get_updater_policy(env).ok_or(OracleError::BondNotConfigured)?;
+    if amount <= 0 {
+        return Err(OracleError::InsufficientBond);
+    }
+
+    let bond = get_oracle_bond(env, &updater);
+    let slashed = amount.min(bond);
+    if slashed == 0 {
+        return Ok(0);
+    }
+
+    set_bond(env, &updater, bond - slashed, -slashed);
+
+    let token_client = soroban_sdk::token::Client::new(env, &policy.bond_token);
+    token_client.transfer(&env.current_contract_address(), &recipient, &slashed);
+
+    emit_oracle_bond_slashed(
+        env,
+        OracleBondSlashedEvent {
+            actor: caller,
+            updater,
+            amount: slashed,
+            recipient,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(slashed)
+}
+
+/// Enforce the updater policy for a non-admin price push
+fn require_updater_allowed(
+    env: &Env,
+    updater: &Address,
+    asset: &Address,
+) -> Result<(), OracleError> {
+    let Some(policy) = get_updater_policy(env) else {
+        return Ok(());
+    };
+
+    if get_oracle_bond(env, updater) < policy.min_bond {
+        return Err(OracleError::InsufficientBond);
+    }
+
+    let push_key = OracleDataKey::UpdaterLastPush(updater.clone(), asset.clone());
+    let last_push = env
+        .storage()
+        .persistent()
+        .get::<OracleDataKey, u64>(&push_key);
+    if let Some(last_push) = last_push {
+        if env.ledger().timestamp() < last_push.saturating_add(policy.min_update_interval) {
+            return Err(OracleError::UpdateRateLimited);
+        }
+    }
+
+    Ok(())
+}
+
+/// Record a successful push for rate limiting and bond locking
+fn record_updater_push(env: &Env, updater: &Address, asset: &Address, timestamp: u64) {
+    env.storage().persistent().set(
+        &OracleDataKey::UpdaterLastPush(updater.clone(), asset.clone()),
+        &timestamp,
+    );
+    env.storage().persistent().set(
+        &OracleDataKey::UpdaterLastActivity(updater.clone()),
+        &timestamp,
+    );
+}
+
+fn get_total_bonds(env: &Env) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<OracleDataKey, i128>(&OracleDataKey::TotalUpdaterBonds)
+        .unwrap_or(0)
+}
+
+/// Store an updater's bond and adjust the total by `delta`
+fn set_bond(env: &Env, updater: &Address, bond: i128, delta: i128) {
+    env.storage()
+        .persistent()
+        .set(&OracleDataKey::UpdaterBond(updater.clone()), &bond);
+    env.storage().persistent().set(
+        &OracleDataKey::TotalUpdaterBonds,
+        &(get_total_bonds(env) + delta),
+    );
+}
+
+/// Get the number of observations kept per asset
+pub fn get_price_history_capacity(env: &Env) -> u32 {
+    env.storage()
+        .persistent()
+        .get::<OracleDataKey, u32>(&OracleDataKey::PriceHistoryCapacity)
+        .unwrap_or(DEFAULT_PRICE_HISTORY_CAPACITY)
+}
+
+/// Set the number of observations kept per asset (admin only)
+///
+/// Existing buffers are resized lazily on their next update, keeping the
+/// most recent observations.
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not the admin
+/// * `InvalidHistoryConfig` - Capacity is zero or above `MAX_PRICE_HISTORY_CAPACITY`
+pub fn set_price_history_capacity(
+    env: &Env,
+    caller: Address,
+    capacity: u32,
+) -> Result<(), OracleError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| OracleError::Unauthorized)?;
+
+    if capacity == 0 || capacity > MAX_PRICE_HISTORY_CAPACITY {
+        return Err(OracleError::InvalidHistoryConfig);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&OracleDataKey::PriceHistoryCapacity, &capacity);
+
+    Ok(())
+}
+
+/// Get the recorded observations for an asset, oldest first
+pub fn get_price_history(env: &Env, asset: &Address) -> Vec<PriceObservation> {
+    match env
+        .storage()
+        .persistent()
+        .get::<OracleDataKey, PriceHistory>(&OracleDataKey::PriceHistory(asset.clone()))
+    {
+        Some(history) => ordered_observations(env, &history),
+        None => Vec::new(env),
+    }
+}
+
+/// Get the time-weighted average price of an asset over a trailing window
+///
+/// Each observation is weighted by how long it was the latest price within
+/// `[now - window_secs, now]`. If the history does not reach back to the
+/// start of the window, the average covers the recorded part only.
+///
+/// # Errors
+/// * `InvalidHistoryConfig` - `window_secs` is zero
+/// * `InsufficientHistory` - No observations are recorded for the asset
+/// * `StalePrice` - The latest observation is older than the staleness limit
+pub fn get_twap(env: &Env, asset: &Address, window_secs: u64) -> Result<i128, OracleError> {
+    if window_secs == 0 {
+        return Err(OracleError::InvalidHistoryConfig);
+    }
+
+    let observations = get_price_history(env, asset);
+    let latest = observations
+        .last()
+        .ok_or(OracleError::InsufficientHistory)?;
+    if is_price_stale(env, latest.timestamp) {
+        return Err(OracleError::StalePrice);
+    }
+
+    time_weighted_average(env, &observations, window_secs)
+}
+
+/// Time-weighted average of non-empty, ordered observations over a window
+fn time_weighted_average(
+    env: &Env,
+    observations: &Vec<PriceObservation>,
+    window_secs: u64,
+) -> Result<i128, OracleError> {
+    let latest = observations
+        .last()
+        .ok_or(OracleError::InsufficientHistory)?;
+    let now = env.ledger().timestamp();
+    let window_start = now.saturating_sub(window_secs);
+    let mut weighted: i128 = 0;
+    let mut total_time: u64 = 0;
+    for i in 0..observations.len() {
+        let observation = observations.get(i).unwrap();
+        let end = match observations.get(i + 1) {
+            Some(next) => next.timestamp,
+            None => now,
+        };
+        let start = observation.timestamp.max(window_start);
+        if end <= start {
+            continue;
+        }
+        let duration = end - start;
+        weighted = observation
+            .price
+            .checked_mul(duration as i128)
+            .and_then(|v| v.checked_add(weighted))
+            .ok_or(OracleError::Overflow)?;
+        total_time += duration;
+    }
+
+    // All observations were made in the current ledger
+    if total_time == 0 {
+        return Ok(latest.price);
+    }
+
+    Ok(weighted / total_time as i128)
+}
+
+/// Append an observation to an asset's ring buffer
+///
+/// A fresh observation also ends the asset's degraded mode, if any, and is
+/// checked against the previous one by the asset's circuit breaker.
+pub(crate) fn record_observation(env: &Env, asset: &Address, price: i128, timestamp: u64) {
+    if let Some(previous) = get_price_history(env, asset).last() {
+        check_price_move(env, asset, previous.price, previous.timestamp, price);
+    }
+
+    let key = OracleDataKey::PriceHistory(asset.clone());
+    let capacity = get_price_history_capacity(env);
+    let mut history = match env
+        .storage()
+        .persistent()
+        .get::<OracleDataKey, PriceHistory>(&key)
+    {
+        Some(history) if history.capacity == capacity => history,
+        // Capacity changed: keep the most recent observations in order
+        Some(history) => {
+            let ordered = ordered_observations(env, &history);
+            let skip = ordered.len().saturating_sub(capacity);
+            let observations = ordered.slice(skip..);
+            PriceHistory {
+                next: observations.len() % capacity,
+                observations,
+                capacity,
+            }
+        }
+        None => PriceHistory {
+            observations: Vec::new(env),
+            next: 0,
+            capacity,
+        },
+    };
+
+    let observation = PriceObservation { price, timestamp };
+    let len = history.observations.len();
+    let latest_slot = if len > 0 {
+        Some((history.next + len - 1) % len)
+    } else {
+        None
+    };
+
+    match latest_slot {
+        // Several updates in one ledger collapse into the last one
+        Some(slot) if history.observations.get(slot).unwrap().timestamp == timestamp => {
+            history.observations.set(slot, observation);
+        }
+        _ => {
+            if len < capacity {
+                history.observations.push_back(observation);
+            } else {
+                history.observations.set(history.next, observation);
+            }
+            history.next = (history.next + 1) % capacity;
+        }
+    }
+
+    env.storage().persistent().set(&key, &history);
+    env.storage()
+        .persistent()
+        .remove(&OracleDataKey::PriceDegraded(asset.clone()));
+}
+
+/// Observations of a ring buffer, oldest first
+fn ordered_observations(env: &Env, history: &PriceHistory) -> Vec<PriceObservation> {
+    let len = history.observations.len();
+    let mut ordered = Vec::new(env);
+    if len == 0 {
+        return ordered;
+    }
+    // Before the buffer wraps `next == len`, so the oldest slot is 0
+    let start = history.next % len;
+    for i in 0..len {
+        ordered.push_back(history.observations.get((start + i) % len).unwrap());
+    }
+    ordered
+}
+
+/// Get the feeders registered for an asset
+pub fn get_price_feeders(env: &Env, asset: &Address) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get::<OracleDataKey, Vec<Address>>(&OracleDataKey::Feeders(asset.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Register a price feeder for an asset (admin only)
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not the admin
+/// * `InvalidOracle` - Feeder is already registered or is this contract
+/// * `TooManyFeeders` - Asset already has `MAX_PRICE_FEEDERS` feeders
+pub fn add_price_feeder(
+    env: &Env,
+    caller: Address,
+    asset: Address,
+    feeder: Address,
+) -> Result<(), OracleError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| OracleError::Unauthorized)?;
+
+    let mut feeders = get_price_feeders(env, &asset);
+    if feeder == env.current_contract_address() || feeders.contains(&feeder) {
+        return Err(OracleError::InvalidOracle);
+    }
+    if feeders.len() >= MAX_PRICE_FEEDERS {
+        return Err(OracleError::TooManyFeeders);
+    }
+
+    feeders.push_back(feeder);
+    env.storage()
+        .persistent()
+        .set(&OracleDataKey::Feeders(asset), &feeders);
+
+    Ok(())
+}
+
+/// Remove a price feeder and its last submission (admin only)
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not the admin
+/// * `InvalidOracle` - Feeder is not registered for the asset
+pub fn remove_price_feeder(
+    env: &Env,
+    caller: Address,
+    asset: Address,
+    feeder: Address,
+) -> Result<(), OracleError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| OracleError::Unauthorized)?;
+
+    let mut feeders = get_price_feeders(env, &asset);
+    let index = feeders
+        .first_index_of(&feeder)
+        .ok_or(OracleError::InvalidOracle)?;
+    feeders.remove(index);
+
+    env.storage()
+        .persistent()
+        .remove(&OracleDataKey::FeederPrice(asset.clone(), feeder));
+    env.storage()
+        .persistent()
+        .set(&OracleDataKey::Feeders(asset), &feeders);
+
+    Ok(())
+}
+
+/// Submit a feeder price for an asset
+///
+/// Stores the submission, then compares all fresh submissions: a spread above
+/// `max_deviation_bps` disputes the asset's price. While undisputed, the
+/// median is also recorded in the asset's TWAP history.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `feeder` - A registered feeder for the asset (must authorize)
+/// * `asset` - The asset address
+/// * `price` - The submitted price
+///
+/// # Returns
+/// The median of the fresh feeder prices after this submission
+///
+/// # Errors
+/// * `OraclePaused` - Oracle updates are paused
+/// * `Unauthorized` - Feeder is not registered for the asset
+/// * `InvalidPrice` - Price fails the sanity bounds
+pub fn submit_feeder_price(
+    env: &Env,
+    feeder: Address,
+    asset: Address,
+    price: i128,
+) -> Result<i128, OracleError> {
+    feeder.require_auth();
+
+    if is_oracle_paused(env) {
+        return Err(OracleError::OraclePaused);
+    }
+    if !get_price_feeders(env, &asset).contains(&feeder) {
+        return Err(OracleError::Unauthorized);
+    }
+    validate_price(env, price)?;
+
+    let timestamp = env.ledger().timestamp();
+    env.storage().persistent().set(
+        &OracleDataKey::FeederPrice(asset.clone(), feeder),
+        &PriceObservation { price, timestamp },
+    );
+
+    // Contains at least the submission just stored
+    let prices = fresh_feeder_prices(env, &asset);
+    let min_price = prices.first().unwrap();
+    let max_price = prices.last().unwrap();
+    let deviation_bps = (max_price - min_price)
+        .checked_mul(10_000)
+        .ok_or(OracleError::Overflow)?
+        / min_price;
+
+    if deviation_bps > get_oracle_config(env).max_deviation_bps && !is_price_disputed(env, &asset) {
+        env.storage().persistent().set(
+            &OracleDataKey::PriceDispute(asset.clone()),
+            &PriceDispute {
+                min_price,
+                max_price,
+                deviation_bps,
+                flagged_at: timestamp,
+            },
+        );
+        emit_price_disputed(
+            env,
+            PriceDisputedEvent {
+                asset: asset.clone(),
+                min_price,
+                max_price,
+                deviation_bps,
+                timestamp,
+            },
+        );
+    }
+
+    let median = median_price(&prices);
+    if !is_price_disputed(env, &asset) {
+        record_observation(env, &asset, median, timestamp);
+    }
+
+    Ok(median)
+}
+
+/// Median of the fresh feeder submissions for an asset
+///
+/// # Errors
+/// * `StalePrice` - No feeder has a fresh submission
+pub fn get_aggregated_price(env: &Env, asset: &Address) -> Result<i128, OracleError> {
+    let prices = fresh_feeder_prices(env, asset);
+    if prices.is_empty() {
+        return Err(OracleError::StalePrice);
+    }
+    Ok(median_price(&prices))
+}
+
+/// Get the open dispute over an asset's price, if any
+pub fn get_price_dispute(env: &Env, asset: &Address) -> Option<PriceDispute> {
+    env.storage()
+        .persistent()
+        .get::<OracleDataKey, PriceDispute>(&OracleDataKey::PriceDispute(asset.clone()))
+}
+
+/// Whether an asset's price is under dispute
+pub fn is_price_disputed(env: &Env, asset: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&OracleDataKey::PriceDispute(asset.clone()))
+}
+
+/// Close a price dispute so borrows of the asset resume (admin only)
+///
+/// The admin should remove or correct the deviating feeder first; a fresh
+/// submission that still disagrees raises a new dispute.
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not the admin
+/// * `NotDisputed` - The asset has no open dispute
+pub fn resolve_price_dispute(
+    env: &Env,
+    caller: Address,
+    asset: Address,
+) -> Result<(), OracleError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| OracleError::Unauthorized)?;
+
+    if !is_price_disputed(env, &asset) {
+        return Err(OracleError::NotDisputed);
+    }
+    env.storage()
+        .persistent()
+        .remove(&OracleDataKey::PriceDispute(asset.clone()));
+
+    emit_price_dispute_resolved(
+        env,
+        PriceDisputeResolvedEvent {
+            actor: caller,
+            asset,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(())
+}
+
+/// Whether oracle price submissions are paused
+fn is_oracle_paused(env: &Env) -> bool {
+    env.storage()
+        .persistent()
+        .get::<OracleDataKey, Map<Symbol, bool>>(&OracleDataKey::PauseSwitches)
+        .and_then(|switches| switches.get(Symbol::new(env, "pause_oracle")))
+        .unwrap_or(false)
+}
+
+/// Fresh feeder prices for an asset, sorted ascending
+fn fresh_feeder_prices(env: &Env, asset: &Address) -> Vec<i128> {
+    let mut prices: Vec<i128> = Vec::new(env);
+    for feeder in get_price_feeders(env, asset).iter() {
+        let Some(observation) = env
+            .storage()
+            .persistent()
+            .get::<OracleDataKey, PriceObservation>(&OracleDataKey::FeederPrice(
+                asset.clone(),
+                feeder,
+            ))
+        else {
+            continue;
+        };
+        if is_price_stale(env, observation.timestamp) {
+            continue;
+        }
+        // Insertion sort; there are at most `MAX_PRICE_FEEDERS` prices
+        let mut index = 0;
+        while index < prices.len() && prices.get(index).unwrap() < observation.price {
+            index += 1;
+        }
+        prices.insert(index, observation.price);
+    }
+    prices
+}
+
+/// Median of a non-empty ascending price list
+fn median_price(prices: &Vec<i128>) -> i128 {
+    let mid = prices.len() / 2;
+    let upper = prices.get(mid).unwrap();
+    if prices.len() % 2 == 1 {
+        return upper;
+    }
+    let lower = prices.get(mid - 1).unwrap();
+    lower + (upper - lower) / 2
+}
+
+/// Get the SEP-40 oracle registered for an asset
+pub fn get_sep40_source(env: &Env, asset: &Address) -> Option<Sep40Source> {
+    env.storage()
+        .persistent()
+        .get::<OracleDataKey, Sep40Source>(&OracleDataKey::Sep40Source(asset.clone()))
+}
+
+/// Register or remove the SEP-40 oracle that prices an asset (admin only)
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `caller` - The address calling this function (must be admin)
+/// * `asset` - The asset address
+/// * `source` - The SEP-40 oracle and feed to pull from, or None to remove
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not the admin
+/// * `InvalidOracle` - Oracle is this contract or `price_decimals` is above 18
+pub fn set_sep40_source(
+    env: &Env,
+    caller: Address,
+    asset: Address,
+    source: Option<Sep40Source>,
+) -> Result<(), OracleError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| OracleError::Unauthorized)?;
+
+    let key = OracleDataKey::Sep40Source(asset.clone());
+    match source {
+        Some(source) => {
+            if source.oracle == env.current_contract_address()
+                || source.price_decimals > MAX_SEP40_PRICE_DECIMALS
+            {
+                return Err(OracleError::InvalidOracle);
+            }
+            env.storage().persistent().set(&key, &source);
+        }
+        None => env.storage().persistent().remove(&key),
+    }
+
+    // Drop prices cached from the previous source
+    env.storage()
+        .persistent()
+        .remove(&OracleDataKey::PriceCache(asset));
+
+    Ok(())
+}
+
+/// Pull an asset's price from its registered SEP-40 oracle
+///
+/// The oracle's price is rescaled from its `decimals()` to the source's
+/// `price_decimals` and checked against the staleness and sanity limits.
+///
+/// # Errors
+/// * `AssetNotSupported` - No SEP-40 oracle is registered for the asset
+/// * `InvalidOracle` - The oracle call failed or returned no price
+/// * `StalePrice` - The oracle's price is older than the staleness limit
+pub fn pull_sep40_price(env: &Env, asset: &Address) -> Result<i128, OracleError> {
+    let source = get_sep40_source(env, asset).ok_or(OracleError::AssetNotSupported)?;
+    let client = Sep40OracleClient::new(env, &source.oracle);
+
+    let data = match client.try_lastprice(&source.feed_asset) {
+        Ok(Ok(Some(data))) => data,
+        _ => return Err(OracleError::InvalidOracle),
+    };
+    let decimals = match client.try_decimals() {
+        Ok(Ok(decimals)) => decimals,
+        _ => return Err(OracleError::InvalidOracle),
+    };
+    if is_price_stale(env, data.timestamp) {
+        return Err(OracleError::StalePrice);
+    }
+
+    let price = rescale_price(data.price, decimals, source.price_decimals)?;
+    validate_price(env, price)?;
+    Ok(price)
+}
+
+/// Rescale a price between decimal precisions
+pub(crate) fn rescale_price(
+    price: i128,
+    from_decimals: u32,
+    to_decimals: u32,
+) -> Result<i128, OracleError> {
+    if from_decimals >= to_decimals {
+        let factor = 10i128
+            .checked_pow(from_decimals - to_decimals)
+            .ok_or(OracleError::Overflow)?;
+        Ok(price / factor)
+    } else {
+        10i128
+            .checked_pow(to_decimals - from_decimals)
+            .and_then(|factor| price.checked_mul(factor))
+            .ok_or(OracleError::Overflow)
+    }
+}
+
+/// Get the failover policy of an asset, if any
+pub fn get_failover_policy(env: &Env, asset: &Address) -> Option<FailoverPolicy> {
+    env.storage()
+        .persistent()
+        .get::<OracleDataKey, FailoverPolicy>(&OracleDataKey::FailoverPolicy(asset.clone()))
+}
+
+/// Get the source currently pricing an asset under its failover policy
+///
+/// Assets whose policy has not priced anything yet report the primary source.
+pub fn get_failover_state(env: &Env, asset: &Address) -> Option<FailoverState> {
+    get_failover_policy(env, asset)?;
+    Some(
+        env.storage()
+            .persistent()
+            .get::<OracleDataKey, FailoverState>(&OracleDataKey::FailoverState(asset.clone()))
+            .unwrap_or(FailoverState {
+                source: PriceSource::Primary,
+                since: 0,
+            }),
+    )
+}
+
+/// Set or remove the failover policy of an asset (admin only)
+///
+/// Removing the policy restores the implicit resolution order and clears the
+/// asset's failover state.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `caller` - The address calling this function (must be admin)
+/// * `asset` - The asset address
+/// * `policy` - The failover chain, or None to remove it
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not the admin
+/// * `FallbackNotConfigured` - The policy uses a fallback the asset lacks
+pub fn set_failover_policy(
+    env: &Env,
+    caller: Address,
+    asset: Address,
+    policy: Option<FailoverPolicy>,
+) -> Result<(), OracleError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| OracleError::Unauthorized)?;
+
+    let key = OracleDataKey::FailoverPolicy(asset.clone());
+    match policy {
+        Some(policy) => {
+            if policy.use_fallback && get_fallback_oracle(env, &asset).is_none() {
+                return Err(OracleError::FallbackNotConfigured);
+            }
+            env.storage().persistent().set(&key, &policy);
+        }
+        None => {
+            env.storage().persistent().remove(&key);
+            env.storage()
+                .persistent()
+                .remove(&OracleDataKey::FailoverState(asset));
+        }
+    }
+
+    Ok(())
+}
+
+/// Walk an asset's failover chain and record the resulting source
+///
+/// A failing operation rolls back the state change together with the rest of
+/// the call, so a freeze hit while pricing a reverted operation is only
+/// recorded once this is called directly.
+///
+/// # Errors
+/// * `AssetNotSupported` - The asset has no failover policy
+pub fn refresh_failover_state(env: &Env, asset: &Address) -> Result<FailoverState, OracleError> {
+    let policy = get_failover_policy(env, asset).ok_or(OracleError::AssetNotSupported)?;
+    resolve_failover(env, asset, &policy);
+    get_failover_state(env, asset).ok_or(OracleError::AssetNotSupported)
+}
+
+/// Price an asset through its failover chain
+fn get_failover_price(
+    env: &Env,
+    asset: &Address,
+    policy: &FailoverPolicy,
+) -> Result<i128, OracleError> {
+    resolve_failover(env, asset, policy).ok_or(OracleError::PriceFrozen)
+}
+
+/// Walk an asset's failover chain, recording the step used; None when the
+/// asset is frozen
+fn resolve_failover(env: &Env, asset: &Address, policy: &FailoverPolicy) -> Option<i128> {
+    let mut resolved = get_primary_price(env, asset)
+        .ok()
+        .map(|price| (PriceSource::Primary, price));
+    if resolved.is_none() && policy.use_fallback {
+        resolved = get_fallback_price(env, asset)
+            .ok()
+            .map(|price| (PriceSource::Fallback, price));
+    }
+    if resolved.is_none() && policy.twap_window_secs > 0 {
+        resolved = get_failover_twap(env, asset, policy.twap_window_secs)
+            .ok()
+            .map(|price| (PriceSource::Twap, price));
+    }
+    let source = resolved
+        .map(|(source, _)| source)
+        .unwrap_or(PriceSource::Frozen);
+
+    let previous = get_failover_state(env, asset)
+        .map(|state| state.source)
+        .unwrap_or(PriceSource::Primary);
+    if source != previous {
+        let timestamp = env.ledger().timestamp();
+        env.storage().persistent().set(
+            &OracleDataKey::FailoverState(asset.clone()),
+            &FailoverState {
+                source,
+                since: timestamp,
+            },
+        );
+        emit_oracle_failover(
+            env,
+            OracleFailoverEvent {
+                asset: asset.clone(),
+                from: previous,
+                to: source,
+                timestamp,
+            },
+        );
+    }
+
+    resolved.map(|(_, price)| price)
+}
+
+/// Price from the primary source: the feeder median when the asset has
+/// feeders, otherwise a SEP-40 pull or the pushed primary feed
+fn get_primary_price(env: &Env, asset: &Address) -> Result<i128, OracleError> {
+    if !get_price_feeders(env, asset).is_empty() {
+        return get_aggregated_price(env, asset);
+    }
+    if let Ok(price) = pull_sep40_price(env, asset) {
+        return Ok(price);
+    }
+
+    let feed = env
+        .storage()
+        .persistent()
+        .get::<OracleDataKey, PriceFeed>(&OracleDataKey::PriceFeed(asset.clone()))
+        .ok_or(OracleError::AssetNotSupported)?;
+    if is_price_stale(env, feed.last_updated) {
+        return Err(OracleError::StalePrice);
+    }
+    Ok(feed.price)
+}
+
+/// TWAP over the policy window, which must contain an observation
+fn get_failover_twap(env: &Env, asset: &Address, window_secs: u64) -> Result<i128, OracleError> {
+    let observations = get_price_history(env, asset);
+    let latest = observations
+        .last()
+        .ok_or(OracleError::InsufficientHistory)?;
+    if env.ledger().timestamp().saturating_sub(latest.timestamp) > window_secs {
+        return Err(OracleError::InsufficientHistory);
+    }
+    time_weighted_average(env, &observations, window_secs)
+}
+
+/// Get the active emergency price override of an asset
+///
+/// Expired overrides are not returned.
+pub fn get_price_override(env: &Env, asset: &Address) -> Option<PriceOverride> {
+    env.storage()
+        .persistent()
+        .get::<OracleDataKey, PriceOverride>(&OracleDataKey::PriceOverride(asset.clone()))
+        .filter(|price_override| env.ledger().timestamp() < price_override.expires_at)
+}
+
+/// Pin an asset to a manual price until `expires_at` (admin or guardian)
+///
+/// The price must lie within `MAX_PRICE_OVERRIDE_DEVIATION_BPS` of the last
+/// primary feed price, stale or not. Setting a new override replaces the
+/// current one; the bound is always taken against the oracle price, so
+/// repeated overrides cannot walk the price away from it.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `caller` - The admin or a governance guardian
+/// * `asset` - The asset address
+/// * `price` - The override price
+/// * `expires_at` - Timestamp the override stops applying
+///
+/// # Errors
+/// * `Unauthorized` - Caller is neither the admin nor a guardian
+/// * `InvalidPrice` - The price fails the configured sanity bounds
+/// * `InvalidOverrideExpiry` - Expiry not within `MAX_PRICE_OVERRIDE_DURATION`
+/// * `AssetNotSupported` - The asset never had a primary price
+/// * `OverrideOutOfBounds` - The price is too far from the last oracle price
+pub fn set_price_override(
+    env: &Env,
+    caller: Address,
+    asset: Address,
+    price: i128,
+    expires_at: u64,
+) -> Result<PriceOverride, OracleError> {
+    require_override_authority(env, &caller)?;
+    validate_price(env, price)?;
+
+    let now = env.ledger().timestamp();
+    if expires_at <= now || expires_at - now > MAX_PRICE_OVERRIDE_DURATION {
+        return Err(OracleError::InvalidOverrideExpiry);
+    }
+
+    let reference_price = env
+        .storage()
+        .persistent()
+        .get::<OracleDataKey, PriceFeed>(&OracleDataKey::PriceFeed(asset.clone()))
+        .ok_or(OracleError::AssetNotSupported)?
+        .price;
+    let deviation = (price - reference_price)
+        .abs()
+        .checked_mul(10_000)
+        .ok_or(OracleError::Overflow)?;
+    let bound = reference_price
+        .checked_mul(MAX_PRICE_OVERRIDE_DEVIATION_BPS)
+        .ok_or(OracleError::Overflow)?;
+    if deviation > bound {
+        return Err(OracleError::OverrideOutOfBounds);
+    }
+
+    let price_override = PriceOverride {
+        price,
+        reference_price,
+        expires_at,
+        set_by: caller.clone(),
+        set_at: now,
+    };
+    env.storage().persistent().set(
+        &OracleDataKey::PriceOverride(asset.clone()),
+        &price_override,
+    );
+
+    emit_price_override_set(
+        env,
+        PriceOverrideSetEvent {
+            asset,
+            actor: caller,
+            price,
+            reference_price,
+            expires_at,
+            timestamp: now,
+        },
+    );
+
+    Ok(price_override)
+}
+
+/// Remove an asset's emergency price override before it expires (admin or
+/// guardian)
+///
+/// # Errors
+/// * `Unauthorized` - Caller is neither the admin nor a guardian
+pub fn clear_price_override(env: &Env, caller: Address, asset: Address) -> Result<(), OracleError> {
+    require_override_authority(env, &caller)?;
+
+    let key = OracleDataKey::PriceOverride(asset.clone());
+    if env.storage().persistent().has(&key) {
+        env.storage().persistent().remove(&key);
+        emit_price_override_cleared(
+            env,
+            PriceOverrideClearedEvent {
+                asset,
+                actor: caller,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    Ok(())
+}
+
+/// The admin or any governance guardian, authorizing the call
+fn require_override_authority(env: &Env, caller: &Address) -> Result<(), OracleError> {
+    let is_guardian = env
+        .storage()
+        .instance()
+        .get::<GovernanceDataKey, GuardianConfig>(&GovernanceDataKey::GuardianConfig)
+        .map(|config| config.guardians.contains(caller))
+        .unwrap_or(false);
+    if is_guardian {
+        caller.require_auth();
+        return Ok(());
+    }
+    crate::admin::require_admin(env, caller).map_err(|_| OracleError::Unauthorized)
+}
+
+/// Get the heartbeat monitoring of an asset, if any
+pub fn get_price_heartbeat(env: &Env, asset: &Address) -> Option<HeartbeatConfig> {
+    env.storage()
+        .persistent()
+        .get::<OracleDataKey, HeartbeatConfig>(&OracleDataKey::PriceHeartbeat(asset.clone()))
+}
+
+/// Set or remove the heartbeat monitoring of an asset (admin only)
+///
+/// Removing the heartbeat also ends the asset's degraded mode.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `caller` - The address calling this function (must be admin)
+/// * `asset` - The asset address
+/// * `config` - The heartbeat and safety buffer, or None to remove them
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not the admin
+/// * `InvalidHeartbeatConfig` - Zero heartbeat or buffer out of range
+pub fn set_price_heartbeat(
+    env: &Env,
+    caller: Address,
+    asset: Address,
+    config: Option<HeartbeatConfig>,
+) -> Result<(), OracleError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| OracleError::Unauthorized)?;
+
+    let key = OracleDataKey::PriceHeartbeat(asset.clone());
+    match config {
+        Some(config) => {
+            if config.heartbeat_secs == 0
+                || !(0..=MAX_HEARTBEAT_SAFETY_BUFFER_BPS).contains(&config.safety_buffer_bps)
+            {
+                return Err(OracleError::InvalidHeartbeatConfig);
+            }
+            env.storage().persistent().set(&key, &config);
+        }
+        None => {
+            env.storage().persistent().remove(&key);
+            env.storage()
+                .persistent()
+                .remove(&OracleDataKey::PriceDegraded(asset));
+        }
+    }
+
+    Ok(())
+}
+
+/// Get the recorded degraded mode of an asset, if any
+///
+/// Only reflects missed heartbeats already recorded by
+/// [`check_price_heartbeat`].
+pub fn get_degraded_state(env: &Env, asset: &Address) -> Option<DegradedState> {
+    env.storage()
+        .persistent()
+        .get::<OracleDataKey, DegradedState>(&OracleDataKey::PriceDegraded(asset.clone()))
+}
+
+/// Whether an asset has missed its heartbeat
+///
+/// Assets without a heartbeat are never degraded; monitored assets that were
+/// never priced are.
+pub fn is_price_degraded(env: &Env, asset: &Address) -> bool {
+    match get_price_heartbeat(env, asset) {
+        Some(config) => get_price_history(env, asset).last().map_or(true, |latest| {
+            env.ledger().timestamp().saturating_sub(latest.timestamp) > config.heartbeat_secs
+        }),
+        None => false,
+    }
+}
+
+/// Check an asset's heartbeat, recording degraded mode when it is missed
+///
+/// Entering degraded mode emits a `price_stale_event`. As with failover
+/// state, a failing operation rolls the record back, so a missed heartbeat
+/// found by a refused borrow is only recorded once this is called directly.
+///
+/// # Returns
+/// Whether the asset is degraded
+pub fn check_price_heartbeat(env: &Env, asset: &Address) -> bool {
+    let degraded = is_price_degraded(env, asset);
+    if degraded && get_degraded_state(env, asset).is_none() {
+        let latest = get_price_history(env, asset).last();
+        let state = DegradedState {
+            last_price: latest.as_ref().map_or(0, |latest| latest.price),
+            last_updated: latest.map_or(0, |latest| latest.timestamp),
+            since: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&OracleDataKey::PriceDegraded(asset.clone()), &state);
+        emit_price_stale(
+            env,
+            PriceStaleEvent {
+                asset: asset.clone(),
+                last_price: state.last_price,
+                last_updated: state.last_updated,
+                timestamp: state.since,
+            },
+        );
+    }
+    degraded
+}
+
+/// Liquidation price of a degraded asset: its last good price moved
+/// against the borrower by the safety buffer
+///
+/// Collateral is valued lower and debt higher. Returns None when the asset
+/// is not degraded or was never priced.
+pub fn get_degraded_price(env: &Env, asset: &Address, as_collateral: bool) -> Option<i128> {
+    if !check_price_heartbeat(env, asset) {
+        return None;
+    }
+    let config = get_price_heartbeat(env, asset)?;
+    let last_price = get_price_history(env, asset).last()?.price;
+    let factor = if as_collateral {
+        10_000 - config.safety_buffer_bps
+    } else {
+        10_000 + config.safety_buffer_bps
+    };
+    last_price.checked_mul(factor).map(|price| price / 10_000)
+}
+
+/// Get the circuit breaker of an asset, if any
+pub fn get_circuit_breaker(env: &Env, asset: &Address) -> Option<CircuitBreakerConfig> {
+    env.storage()
+        .persistent()
+        .get::<OracleDataKey, CircuitBreakerConfig>(&OracleDataKey::CircuitBreaker(asset.clone()))
+}
+
+/// Set or remove the circuit breaker of an asset (admin only)
+///
+/// Removing the breaker does not reset a tripped one.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `caller` - The address calling this function (must be admin)
+/// * `asset` - The asset address
+/// * `config` - The move limit and window, or None to remove them
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not the admin
+/// * `InvalidCircuitBreakerConfig` - Move limit outside 1..=10000 or window
+///   outside 1..=`MAX_CIRCUIT_BREAKER_WINDOW`
+pub fn set_circuit_breaker(
+    env: &Env,
+    caller: Address,
+    asset: Address,
+    config: Option<CircuitBreakerConfig>,
+) -> Result<(), OracleError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| OracleError::Unauthorized)?;
+
+    let key = OracleDataKey::CircuitBreaker(asset);
+    match config {
+        Some(config) => {
+            if !(1..=10_000).contains(&config.max_move_bps)
+                || config.window_secs == 0
+                || config.window_secs > MAX_CIRCUIT_BREAKER_WINDOW
+            {
+                return Err(OracleError::InvalidCircuitBreakerConfig);
+            }
+            env.storage().persistent().set(&key, &config);
+        }
+        None => env.storage().persistent().remove(&key),
+    }
+
+    Ok(())
+}
+
+/// Get the move that tripped an asset's circuit breaker, if tripped
+pub fn get_circuit_breaker_trip(env: &Env, asset: &Address) -> Option<CircuitBreakerTrip> {
+    env.storage()
+        .persistent()
+        .get::<OracleDataKey, CircuitBreakerTrip>(&OracleDataKey::CircuitBreakerTrip(asset.clone()))
+}
+
+/// Whether an asset's circuit breaker is tripped, pausing its borrows
+pub fn is_circuit_breaker_tripped(env: &Env, asset: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&OracleDataKey::CircuitBreakerTrip(asset.clone()))
+}
+
+/// Reset a tripped circuit breaker so borrows of the asset resume (admin or
+/// guardian)
+///
+/// # Errors
+/// * `Unauthorized` - Caller is neither the admin nor a guardian
+/// * `CircuitBreakerNotTripped` - The asset's breaker is not tripped
+pub fn reset_circuit_breaker(
+    env: &Env,
+    caller: Address,
+    asset: Address,
+) -> Result<(), OracleError> {
+    require_override_authority(env, &caller)?;
+
+    let key = OracleDataKey::CircuitBreakerTrip(asset.clone());
+    if !env.storage().persistent().has(&key) {
+        return Err(OracleError::CircuitBreakerNotTripped);
+    }
+    env.storage().persistent().remove(&key);
+
+    emit_circuit_breaker_reset(
+        env,
+        CircuitBreakerResetEvent {
+            asset,
+            actor: caller,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(())
+}
+
+/// Trip an asset's circuit breaker if `price` moved too far from a recent
+/// previous price
+pub(crate) fn check_price_move(
+    env: &Env,
+    asset: &Address,
+    previous_price: i128,
+    previous_at: u64,
+    price: i128,
+) {
+    let config = match get_circuit_breaker(env, asset) {
+        Some(config) => config,
+        None => return,
+    };
+    let now = env.ledger().timestamp();
+    if previous_price <= 0
+        || now.saturating_sub(previous_at) > config.window_secs
+        || is_circuit_breaker_tripped(env, asset)
+    {
+        return;
+    }
+
+    let move_bps = (price - previous_price).abs().saturating_mul(10_000) / previous_price;
+    if move_bps <= config.max_move_bps {
+        return;
+    }
+
+    let trip = CircuitBreakerTrip {
+        previous_price,
+        price,
+        move_bps,
+        tripped_at: now,
+    };
+    env.storage()
+        .persistent()
+        .set(&OracleDataKey::CircuitBreakerTrip(asset.clone()), &trip);
+    emit_circuit_breaker_tripped(
+        env,
+        CircuitBreakerTrippedEvent {
+            asset: asset.clone(),
+            previous_price,
+            price,
+            move_bps,
+            timestamp: now,
+        },
+    );
+}
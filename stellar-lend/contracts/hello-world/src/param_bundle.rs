@@ -0,0 +1,519 @@
+//! # Parameter Bundles
+//!
+//! Export and apply the protocol's tunable parameters as one typed bundle:
+//! risk parameters, the interest rate model, the oracle configuration and
+//! borrow/supply caps. A parameter set tested on one deployment can be read
+//! with `get_parameter_bundle` and promoted to another with a single
+//! `apply_parameter_bundle` call.
+//!
+//! Applying a bundle compares it with the live values and only writes the
+//! differences, with the same validation as the individual admin
+//! entrypoints; in particular risk parameters may still move at most 10% per
+//! update. The admin authorizes the bundle once. The call is atomic: if any
+//! change is rejected nothing is applied. It returns one [`ParameterChange`]
+//! per modified value.
+
+use soroban_sdk::{contracterror, contracttype, Address, Env, Symbol, Vec};
+
+use crate::cross_asset::CrossAssetError;
+use crate::events::{emit_admin_action, AdminActionEvent};
+use crate::interest_rate::InterestRateConfigUpdate;
+use crate::oracle::OracleConfig;
+use crate::risk_params::RiskParamsError;
+
+/// Errors that can occur when exporting or applying a parameter bundle
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ParameterBundleError {
+    /// Caller is not the admin
+    Unauthorized = 1,
+    /// Risk parameters or the interest rate model are not initialized
+    NotInitialized = 2,
+    /// A risk parameter is out of range
+    InvalidRiskParams = 3,
+    /// A risk parameter moves by more than the allowed change per update
+    ParameterChangeTooLarge = 4,
+    /// An interest rate parameter is out of range
+    InvalidInterestRate = 5,
+    /// The oracle configuration is invalid
+    InvalidOracleConfig = 6,
+    /// A cap is negative
+    InvalidCap = 7,
+    /// A capped asset is not registered with the cross-asset module
+    AssetNotConfigured = 8,
+}
+
+/// Risk parameters (basis points)
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RiskParamsBundle {
+    pub min_collateral_ratio: i128,
+    pub liquidation_threshold: i128,
+    pub close_factor: i128,
+    pub liquidation_incentive: i128,
+}
+
+/// Kink interest rate model (basis points)
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct InterestRateBundle {
+    pub base_rate_bps: i128,
+    pub kink_utilization_bps: i128,
+    pub multiplier_bps: i128,
+    pub jump_multiplier_bps: i128,
+    pub rate_floor_bps: i128,
+    pub rate_ceiling_bps: i128,
+    pub spread_bps: i128,
+}
+
+/// Caps of a single asset
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct AssetCapsBundle {
+    /// The asset (None for native XLM)
+    pub asset: Option<Address>,
+    /// Cross-asset supply cap (0 = unlimited)
+    pub max_supply: i128,
+    /// Cross-asset borrow cap (0 = unlimited)
+    pub max_borrow: i128,
+    /// Daily borrow cap (None = unlimited)
+    pub daily_borrow_cap: Option<i128>,
+}
+
+/// Borrow and supply caps
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct CapsBundle {
+    /// Protocol-wide daily borrow cap (None = unlimited)
+    pub global_daily_borrow_cap: Option<i128>,
+    /// Per-asset caps; assets not listed are left unchanged
+    pub assets: Vec<AssetCapsBundle>,
+}
+
+/// Full set of tunable protocol parameters
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParameterBundle {
+    pub risk: RiskParamsBundle,
+    pub interest: InterestRateBundle,
+    pub oracle: OracleConfig,
+    pub caps: CapsBundle,
+}
+
+/// A value modified by `apply_parameter_bundle`
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParameterChange {
+    /// Parameter name (e.g. `min_collateral_ratio`)
+    pub parameter: Symbol,
+    /// Asset for per-asset caps
+    pub asset: Option<Address>,
+    /// Previous value (None = unset / unlimited)
+    pub old_value: Option<i128>,
+    /// New value (None = unset / unlimited)
+    pub new_value: Option<i128>,
+}
+
+/// Export the live parameters, including the caps of `assets`
+///
+/// # Errors
+/// * `NotInitialized` - Risk parameters or the rate model are missing
+/// * `AssetNotConfigured` - An asset is not registered for cross-asset caps
+pub fn get_parameter_bundle(
+    env: &Env,
+    assets: Vec<Option<Address>>,
+) -> Result<ParameterBundle, ParameterBundleError> {
+    let risk =
+        crate::risk_params::get_risk_params(env).ok_or(ParameterBundleError::NotInitialized)?;
+    let rates = crate::interest_rate::get_interest_rate_config(env)
+        .ok_or(ParameterBundleError::NotInitialized)?;
+
+    let mut asset_caps = Vec::new(env);
+    for asset in assets.iter() {
+        asset_caps.push_back(get_asset_caps(env, &asset)?);
+    }
+
+    Ok(ParameterBundle {
+        risk: RiskParamsBundle {
+            min_collateral_ratio: risk.min_collateral_ratio,
+            liquidation_threshold: risk.liquidation_threshold,
+            close_factor: risk.close_factor,
+            liquidation_incentive: risk.liquidation_incentive,
+        },
+        interest: InterestRateBundle {
+            base_rate_bps: rates.base_rate_bps,
+            kink_utilization_bps: rates.kink_utilization_bps,
+            multiplier_bps: rates.multiplier_bps,
+            jump_multiplier_bps: rates.jump_multiplier_bps,
+            rate_floor_bps: rates.rate_floor_bps,
+            rate_ceiling_bps: rates.rate_ceiling_bps,
+            spread_bps: rates.spread_bps,
+        },
+        oracle: crate::oracle::get_oracle_config(env),
+        caps: CapsBundle {
+            global_daily_borrow_cap: crate::risk_management::get_global_daily_borrow_status(env)
+                .cap,
+            assets: asset_caps,
+        },
+    })
+}
+
+/// Apply the differences between `bundle` and the live parameters (admin only)
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `caller` - The caller address (must be admin)
+/// * `bundle` - The target parameter set
+///
+/// # Returns
+/// The values that changed, in application order (empty if none did)
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not the admin (or not the cross-asset admin
+///   when supply/borrow caps change)
+/// * `InvalidRiskParams` / `ParameterChangeTooLarge` / `InvalidInterestRate` /
+///   `InvalidOracleConfig` / `InvalidCap` - A value was rejected
+/// * `AssetNotConfigured` - A capped asset is not registered
+pub fn apply_parameter_bundle(
+    env: &Env,
+    caller: Address,
+    bundle: ParameterBundle,
+) -> Result<Vec<ParameterChange>, ParameterBundleError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| ParameterBundleError::Unauthorized)?;
+
+    let mut assets = Vec::new(env);
+    for caps in bundle.caps.assets.iter() {
+        if caps.max_supply < 0
+            || caps.max_borrow < 0
+            || caps.daily_borrow_cap.is_some_and(|cap| cap < 0)
+        {
+            return Err(ParameterBundleError::InvalidCap);
+        }
+        assets.push_back(caps.asset);
+    }
+    let current = get_parameter_bundle(env, assets)?;
+    let mut changes = Vec::new(env);
+
+    apply_risk_params(env, &mut changes, &current.risk, &bundle.risk)?;
+    apply_interest_rates(env, &mut changes, &current.interest, &bundle.interest)?;
+    apply_oracle_config(env, &mut changes, &current.oracle, bundle.oracle)?;
+
+    if record(
+        env,
+        &mut changes,
+        "global_daily_borrow_cap",
+        &None,
+        current.caps.global_daily_borrow_cap,
+        bundle.caps.global_daily_borrow_cap,
+    ) {
+        crate::risk_management::apply_global_daily_borrow_cap(
+            env,
+            bundle.caps.global_daily_borrow_cap,
+        )
+        .map_err(|_| ParameterBundleError::InvalidCap)?;
+    }
+    for (old, new) in current.caps.assets.iter().zip(bundle.caps.assets.iter()) {
+        apply_asset_caps(env, &mut changes, &old, &new)?;
+    }
+
+    if !changes.is_empty() {
+        emit_admin_action(
+            env,
+            AdminActionEvent {
+                actor: caller,
+                action: Symbol::new(env, "apply_param_bundle"),
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    Ok(changes)
+}
+
+fn apply_risk_params(
+    env: &Env,
+    changes: &mut Vec<ParameterChange>,
+    old: &RiskParamsBundle,
+    new: &RiskParamsBundle,
+) -> Result<(), ParameterBundleError> {
+    let min_collateral_ratio = changed(
+        env,
+        changes,
+        "min_collateral_ratio",
+        old.min_collateral_ratio,
+        new.min_collateral_ratio,
+    );
+    let liquidation_threshold = changed(
+        env,
+        changes,
+        "liquidation_threshold",
+        old.liquidation_threshold,
+        new.liquidation_threshold,
+    );
+    let close_factor = changed(
+        env,
+        changes,
+        "close_factor",
+        old.close_factor,
+        new.close_factor,
+    );
+    let liquidation_incentive = changed(
+        env,
+        changes,
+        "liquidation_incentive",
+        old.liquidation_incentive,
+        new.liquidation_incentive,
+    );
+    if min_collateral_ratio.is_none()
+        && liquidation_threshold.is_none()
+        && close_factor.is_none()
+        && liquidation_incentive.is_none()
+    {
+        return Ok(());
+    }
+
+    crate::risk_params::set_risk_params(
+        env,
+        min_collateral_ratio,
+        liquidation_threshold,
+        close_factor,
+        liquidation_incentive,
+    )
+    .map_err(|e| match e {
+        RiskParamsError::ParameterChangeTooLarge => ParameterBundleError::ParameterChangeTooLarge,
+        _ => ParameterBundleError::InvalidRiskParams,
+    })
+}
+
+fn apply_interest_rates(
+    env: &Env,
+    changes: &mut Vec<ParameterChange>,
+    old: &InterestRateBundle,
+    new: &InterestRateBundle,
+) -> Result<(), ParameterBundleError> {
+    let base_rate = changed(
+        env,
+        changes,
+        "base_rate_bps",
+        old.base_rate_bps,
+        new.base_rate_bps,
+    );
+    let kink = changed(
+        env,
+        changes,
+        "kink_utilization_bps",
+        old.kink_utilization_bps,
+        new.kink_utilization_bps,
+    );
+    let multiplier = changed(
+        env,
+        changes,
+        "multiplier_bps",
+        old.multiplier_bps,
+        new.multiplier_bps,
+    );
+    let jump = changed(
+        env,
+        changes,
+        "jump_multiplier_bps",
+        old.jump_multiplier_bps,
+        new.jump_multiplier_bps,
+    );
+    let floor = changed(
+        env,
+        changes,
+        "rate_floor_bps",
+        old.rate_floor_bps,
+        new.rate_floor_bps,
+    );
+    let ceiling = changed(
+        env,
+        changes,
+        "rate_ceiling_bps",
+        old.rate_ceiling_bps,
+        new.rate_ceiling_bps,
+    );
+    let spread = changed(env, changes, "spread_bps", old.spread_bps, new.spread_bps);
+
+    if [base_rate, kink, multiplier, jump, floor, ceiling, spread]
+        .iter()
+        .all(Option::is_none)
+    {
+        return Ok(());
+    }
+
+    // The setter checks a new floor against the stored ceiling, so a floor
+    // raised above the old ceiling needs the ceiling raised first
+    let mut ceiling = ceiling;
+    if ceiling.is_some() && new.rate_floor_bps > old.rate_ceiling_bps {
+        crate::interest_rate::apply_interest_rate_config(
+            env,
+            &InterestRateConfigUpdate {
+                base_rate_bps: None,
+                kink_utilization_bps: None,
+                multiplier_bps: None,
+                jump_multiplier_bps: None,
+                rate_floor_bps: None,
+                rate_ceiling_bps: ceiling.take(),
+                spread_bps: None,
+            },
+        )
+        .map_err(|_| ParameterBundleError::InvalidInterestRate)?;
+    }
+    crate::interest_rate::apply_interest_rate_config(
+        env,
+        &InterestRateConfigUpdate {
+            base_rate_bps: base_rate,
+            kink_utilization_bps: kink,
+            multiplier_bps: multiplier,
+            jump_multiplier_bps: jump,
+            rate_floor_bps: floor,
+            rate_ceiling_bps: ceiling,
+            spread_bps: spread,
+        },
+    )
+    .map_err(|_| ParameterBundleError::InvalidInterestRate)
+}
+
+fn apply_oracle_config(
+    env: &Env,
+    changes: &mut Vec<ParameterChange>,
+    old: &OracleConfig,
+    new: OracleConfig,
+) -> Result<(), ParameterBundleError> {
+    if *old == new {
+        return Ok(());
+    }
+    changed(
+        env,
+        changes,
+        "max_deviation_bps",
+        old.max_deviation_bps,
+        new.max_deviation_bps,
+    );
+    changed(
+        env,
+        changes,
+        "max_staleness_seconds",
+        old.max_staleness_seconds as i128,
+        new.max_staleness_seconds as i128,
+    );
+    changed(
+        env,
+        changes,
+        "cache_ttl_seconds",
+        old.cache_ttl_seconds as i128,
+        new.cache_ttl_seconds as i128,
+    );
+    changed(env, changes, "min_price", old.min_price, new.min_price);
+    changed(env, changes, "max_price", old.max_price, new.max_price);
+    crate::oracle::apply_oracle_config(env, new)
+        .map_err(|_| ParameterBundleError::InvalidOracleConfig)
+}
+
+fn apply_asset_caps(
+    env: &Env,
+    changes: &mut Vec<ParameterChange>,
+    old: &AssetCapsBundle,
+    new: &AssetCapsBundle,
+) -> Result<(), ParameterBundleError> {
+    let asset = &new.asset;
+    let max_supply = record(
+        env,
+        changes,
+        "max_supply",
+        asset,
+        Some(old.max_supply),
+        Some(new.max_supply),
+    );
+    let max_borrow = record(
+        env,
+        changes,
+        "max_borrow",
+        asset,
+        Some(old.max_borrow),
+        Some(new.max_borrow),
+    );
+    if max_supply || max_borrow {
+        crate::cross_asset::update_asset_config(
+            env,
+            asset.clone(),
+            None,
+            None,
+            max_supply.then_some(new.max_supply),
+            max_borrow.then_some(new.max_borrow),
+            None,
+            None,
+        )
+        .map_err(|e| match e {
+            CrossAssetError::NotAuthorized => ParameterBundleError::Unauthorized,
+            CrossAssetError::AssetNotConfigured => ParameterBundleError::AssetNotConfigured,
+            _ => ParameterBundleError::InvalidCap,
+        })?;
+    }
+
+    if record(
+        env,
+        changes,
+        "daily_borrow_cap",
+        asset,
+        old.daily_borrow_cap,
+        new.daily_borrow_cap,
+    ) {
+        crate::risk_management::apply_asset_daily_borrow_cap(
+            env,
+            asset.clone(),
+            new.daily_borrow_cap,
+        )
+        .map_err(|_| ParameterBundleError::InvalidCap)?;
+    }
+    Ok(())
+}
+
+fn get_asset_caps(
+    env: &Env,
+    asset: &Option<Address>,
+) -> Result<AssetCapsBundle, ParameterBundleError> {
+    let config = crate::cross_asset::get_asset_config_by_address(env, asset.clone())
+        .map_err(|_| ParameterBundleError::AssetNotConfigured)?;
+    Ok(AssetCapsBundle {
+        asset: asset.clone(),
+        max_supply: config.max_supply,
+        max_borrow: config.max_borrow,
+        daily_borrow_cap: crate::risk_management::get_asset_daily_borrow_status(env, asset.clone())
+            .cap,
+    })
+}
+
+/// Record a protocol-wide value change; returns the new value if it changed
+fn changed(
+    env: &Env,
+    changes: &mut Vec<ParameterChange>,
+    parameter: &str,
+    old: i128,
+    new: i128,
+) -> Option<i128> {
+    record(env, changes, parameter, &None, Some(old), Some(new)).then_some(new)
+}
+
+/// Record a change if `old` and `new` differ; returns whether they did
+fn record(
+    env: &Env,
+    changes: &mut Vec<ParameterChange>,
+    parameter: &str,
+    asset: &Option<Address>,
+    old: Option<i128>,
+    new: Option<i128>,
+) -> bool {
+    if old == new {
+        return false;
+    }
+    changes.push_back(ParameterChange {
+        parameter: Symbol::new(env, parameter),
+        asset: asset.clone(),
+        old_value: old,
+        new_value: new,
+    });
+    true
+}
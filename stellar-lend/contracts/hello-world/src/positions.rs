@@ -0,0 +1,291 @@
+//! # Isolated Positions
+//!
+//! Besides the position held at their own address, a user can open any
+//! number of isolated positions (sub-accounts), each identified by a
+//! position id. Collateral and debt in one position never back another, and
+//! a whole position can be transferred to another address, e.g. to sell a
+//! leveraged position.
+//!
+//! ## Position Accounts
+//! Each position lives at its own account address, derived from this
+//! contract and the position id (see [`get_position_account`]). No contract
+//! is ever deployed there, so nobody can act as the account: only the
+//! position's owner can operate it, through [`execute_on_position`]. Since
+//! the account is an ordinary position address, health factors,
+//! liquidations, interest and every other position view work on it
+//! unchanged. Compliance controls also apply to the account, so under a
+//! required allowlist the account must be allowed like any other.
+//!
+//! Deposits and repayments are pulled from the owner; withdrawals and
+//! borrows are paid to the owner. An archived position account is likewise
+//! restored by its owner, through [`restore_isolated_position`].
+//!
+//! ## Transfers
+//! The account is derived from the id alone, so [`transfer_position`] only
+//! changes the owner; the collateral, debt and history stay in place.
+//!
+//! ## Storage Layout
+//! - `NextPositionId` — id assigned to the next position
+//! - `Owner(id)` — the position's owner
+//! - `UserPositions(owner)` — ids of the positions a user owns, oldest first
+
+use soroban_sdk::{contracterror, contracttype, Address, Bytes, Env, Vec};
+
+use crate::batch::ProtocolAction;
+use crate::borrow::{borrow_asset_to, BorrowError};
+use crate::deposit::{deposit_collateral_from, DepositError, Position};
+use crate::events::{
+    emit_position_opened, emit_position_transferred, PositionOpenedEvent, PositionTransferredEvent,
+};
+use crate::repay::{repay_debt_from, RepayError};
+use crate::stable_rate::RateMode;
+use crate::withdraw::{withdraw_collateral_to, WithdrawError};
+
+/// Maximum number of isolated positions a user can own
+pub const MAX_POSITIONS_PER_USER: u32 = 20;
+
+/// Errors that can occur in isolated position operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum PositionError {
+    /// No position exists with the id
+    PositionNotFound = 1,
+    /// Caller does not own the position
+    NotPositionOwner = 2,
+    /// The owner already has `MAX_POSITIONS_PER_USER` positions
+    TooManyPositions = 3,
+    /// The recipient is the current owner or this contract
+    InvalidRecipient = 4,
+    /// The deposit failed
+    DepositFailed = 5,
+    /// The borrow failed
+    BorrowFailed = 6,
+    /// The repayment failed
+    RepayFailed = 7,
+    /// The withdrawal failed
+    WithdrawFailed = 8,
+    /// Reentrancy detected
+    Reentrancy = 9,
+    /// The position account has no archived position, or restoring it failed
+    RestoreFailed = 10,
+}
+
+/// Storage keys for isolated positions
+#[contracttype]
+#[derive(Clone)]
+pub enum PositionDataKey {
+    /// Id assigned to the next position
+    /// Value type: u64
+    NextPositionId,
+    /// Owner of a position
+    /// Value type: Address
+    Owner(u64),
+    /// Ids of the positions a user owns, oldest first
+    /// Value type: Vec<u64>
+    UserPositions(Address),
+}
+
+/// Open an empty isolated position
+///
+/// # Returns
+/// The position id
+///
+/// # Errors
+/// * `TooManyPositions` - The owner already has `MAX_POSITIONS_PER_USER` positions
+pub fn open_position(env: &Env, owner: Address) -> Result<u64, PositionError> {
+    owner.require_auth();
+    let mut ids = get_user_positions(env, &owner);
+    if ids.len() >= MAX_POSITIONS_PER_USER {
+        return Err(PositionError::TooManyPositions);
+    }
+
+    let id = env
+        .storage()
+        .persistent()
+        .get(&PositionDataKey::NextPositionId)
+        .unwrap_or(1u64);
+    env.storage()
+        .persistent()
+        .set(&PositionDataKey::NextPositionId, &(id + 1));
+    env.storage()
+        .persistent()
+        .set(&PositionDataKey::Owner(id), &owner);
+    ids.push_back(id);
+    set_user_positions(env, &owner, &ids);
+    crate::snapshot::index_user(env, &get_position_account(env, id));
+
+    emit_position_opened(
+        env,
+        PositionOpenedEvent {
+            position_id: id,
+            owner,
+            account: get_position_account(env, id),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(id)
+}
+
+/// Transfer a whole position, collateral and debt included, to `to`
+///
+/// # Errors
+/// * `PositionNotFound` - No position exists with the id
+/// * `NotPositionOwner` - Caller does not own the position
+/// * `InvalidRecipient` - The recipient is the current owner or this contract
+/// * `TooManyPositions` - The recipient already has `MAX_POSITIONS_PER_USER` positions
+pub fn transfer_position(
+    env: &Env,
+    owner: Address,
+    position_id: u64,
+    to: Address,
+) -> Result<(), PositionError> {
+    owner.require_auth();
+    require_owner(env, &owner, position_id)?;
+    if to == owner || to == env.current_contract_address() {
+        return Err(PositionError::InvalidRecipient);
+    }
+    let mut to_ids = get_user_positions(env, &to);
+    if to_ids.len() >= MAX_POSITIONS_PER_USER {
+        return Err(PositionError::TooManyPositions);
+    }
+
+    let mut owner_ids = get_user_positions(env, &owner);
+    if let Some(index) = owner_ids.first_index_of(position_id) {
+        owner_ids.remove(index);
+    }
+    set_user_positions(env, &owner, &owner_ids);
+    to_ids.push_back(position_id);
+    set_user_positions(env, &to, &to_ids);
+    env.storage()
+        .persistent()
+        .set(&PositionDataKey::Owner(position_id), &to);
+
+    emit_position_transferred(
+        env,
+        PositionTransferredEvent {
+            position_id,
+            from: owner,
+            to,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Run `action` on an isolated position (owner only)
+///
+/// # Returns
+/// The position's new collateral balance for deposits and withdrawals, its
+/// total debt for borrows and its remaining debt for repays
+///
+/// # Errors
+/// * `PositionNotFound` - No position exists with the id
+/// * `NotPositionOwner` - Caller does not own the position
+/// * `DepositFailed` / `BorrowFailed` / `RepayFailed` / `WithdrawFailed` -
+///   The action failed
+/// * `Reentrancy` - Called while another operation is in progress
+pub fn execute_on_position(
+    env: &Env,
+    owner: Address,
+    position_id: u64,
+    action: ProtocolAction,
+) -> Result<i128, PositionError> {
+    owner.require_auth();
+    require_owner(env, &owner, position_id)?;
+    let account = get_position_account(env, position_id);
+
+    let result = match action {
+        ProtocolAction::Deposit(asset, amount) => {
+            deposit_collateral_from(env, owner, account, asset, amount).map_err(|e| match e {
+                DepositError::Reentrancy => PositionError::Reentrancy,
+                _ => PositionError::DepositFailed,
+            })?
+        }
+        ProtocolAction::Withdraw(asset, amount) => {
+            withdraw_collateral_to(env, account, owner, asset, amount).map_err(|e| match e {
+                WithdrawError::Reentrancy => PositionError::Reentrancy,
+                _ => PositionError::WithdrawFailed,
+            })?
+        }
+        ProtocolAction::Borrow(asset, amount) => {
+            borrow_asset_to(env, account, owner, asset, amount, RateMode::Variable).map_err(
+                |e| match e {
+                    BorrowError::Reentrancy => PositionError::Reentrancy,
+                    _ => PositionError::BorrowFailed,
+                },
+            )?
+        }
+        ProtocolAction::Repay(asset, amount) => {
+            repay_debt_from(env, owner, account, asset, amount)
+                .map_err(|e| match e {
+                    RepayError::Reentrancy => PositionError::Reentrancy,
+                    _ => PositionError::RepayFailed,
+                })?
+                .0
+        }
+    };
+    Ok(result)
+}
+
+/// Restore an isolated position's account from the idle position archive
+///
+/// # Returns
+/// The restored position
+///
+/// # Errors
+/// * `PositionNotFound` - No position exists with the id
+/// * `NotPositionOwner` - Caller does not own the position
+/// * `RestoreFailed` - The position account has no archived position, or
+///   restoring it failed
+pub fn restore_isolated_position(
+    env: &Env,
+    owner: Address,
+    position_id: u64,
+) -> Result<Position, PositionError> {
+    owner.require_auth();
+    require_owner(env, &owner, position_id)?;
+    let account = get_position_account(env, position_id);
+    crate::archive::restore_archived(env, &account).map_err(|_| PositionError::RestoreFailed)
+}
+
+/// Get the owner of a position
+pub fn get_position_owner(env: &Env, position_id: u64) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&PositionDataKey::Owner(position_id))
+}
+
+/// Get the ids of the positions a user owns, oldest first
+pub fn get_user_positions(env: &Env, owner: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&PositionDataKey::UserPositions(owner.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Get the account address a position lives at
+///
+/// The address is derived from this contract and the id, whether or not
+/// the position has been opened.
+pub fn get_position_account(env: &Env, position_id: u64) -> Address {
+    let mut salt = Bytes::from_slice(env, b"position");
+    salt.extend_from_array(&position_id.to_be_bytes());
+    env.deployer()
+        .with_current_contract(env.crypto().sha256(&salt).to_bytes())
+        .deployed_address()
+}
+
+fn require_owner(env: &Env, owner: &Address, position_id: u64) -> Result<(), PositionError> {
+    match get_position_owner(env, position_id) {
+        None => Err(PositionError::PositionNotFound),
+        Some(current) if current != *owner => Err(PositionError::NotPositionOwner),
+        Some(_) => Ok(()),
+    }
+}
+
+fn set_user_positions(env: &Env, owner: &Address, ids: &Vec<u64>) {
+    env.storage()
+        .persistent()
+        .set(&PositionDataKey::UserPositions(owner.clone()), ids);
+}
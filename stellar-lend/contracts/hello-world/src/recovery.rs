@@ -277,6 +277,16 @@ pub fn execute_recovery(env: &Env, executor: Address) -> Result<(), GovernanceEr
         .persistent()
         .set(&GovernanceDataKey::MultisigAdmins, &new_admins);
 
+    // Dead-man switch: once the super admin has been inactive for the configured
+    // period, guardian recovery may also replace the super admin.
+    if crate::admin::is_admin_inactive(env)
+        && crate::admin::get_admin(env) == Some(recovery.old_admin.clone())
+    {
+        env.storage()
+            .persistent()
+            .set(&crate::admin::AdminDataKey::Admin, &recovery.new_admin);
+    }
+
     env.storage()
         .persistent()
         .remove(&GovernanceDataKey::RecoveryRequest);
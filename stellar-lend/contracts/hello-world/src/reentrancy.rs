@@ -22,3 +22,31 @@ impl<'a> Drop for ReentrancyGuard<'a> {
         self.env.storage().temporary().remove(&key);
     }
 }
+
+/// Temporary-storage counter of outstanding flash loans; present while any loan is unrepaid
+const FLASH_LOAN_LOCK: &str = "FLASH_LOAN_LOCK";
+
+/// Take the flash loan lock (one hold per outstanding loan)
+pub fn enter_flash_loan(env: &Env) {
+    let key = Symbol::new(env, FLASH_LOAN_LOCK);
+    let active: u32 = env.storage().temporary().get(&key).unwrap_or(0);
+    env.storage().temporary().set(&key, &(active + 1));
+}
+
+/// Release one hold on the flash loan lock
+pub fn exit_flash_loan(env: &Env) {
+    let key = Symbol::new(env, FLASH_LOAN_LOCK);
+    let active: u32 = env.storage().temporary().get(&key).unwrap_or(0);
+    if active <= 1 {
+        env.storage().temporary().remove(&key);
+    } else {
+        env.storage().temporary().set(&key, &(active - 1));
+    }
+}
+
+/// Whether any flash loan is currently outstanding
+pub fn is_flash_loan_locked(env: &Env) -> bool {
+    env.storage()
+        .temporary()
+        .has(&Symbol::new(env, FLASH_LOAN_LOCK))
+}
@@ -19,10 +19,11 @@ use soroban_sdk::{contracterror, Address, Env, IntoVal, Map, Symbol, Val, Vec};
 
 use crate::deposit::{
     add_activity_log, emit_analytics_updated_event, emit_position_updated_event,
-    emit_user_activity_tracked_event, update_protocol_analytics, update_user_analytics, Activity,
-    DepositDataKey, Position, ProtocolAnalytics, UserAnalytics,
+    emit_user_activity_tracked_event, get_native_asset_address, update_protocol_analytics,
+    update_user_analytics, Activity, DepositDataKey, Position, ProtocolAnalytics, UserAnalytics,
 };
-use crate::events::{emit_repay, RepayEvent};
+use crate::events::{emit_on_behalf, emit_repay, OnBehalfEvent, RepayEvent};
+use crate::operations::{record_operation, OperationKind};
 
 /// Errors that can occur during repay operations
 #[contracterror]
@@ -51,6 +52,7 @@ pub enum RepayError {
 ///
 /// # Arguments
 /// * `env` - The Soroban environment
+/// * `asset` - The debt asset, whose subsidy budget absorbs capped interest
 /// * `principal` - The principal amount to calculate interest on
 /// * `last_accrual_time` - The timestamp of the last interest accrual
 /// * `current_time` - The current ledger timestamp
@@ -59,6 +61,8 @@ pub enum RepayError {
 /// * `Result<i128, RepayError>` - The accrued interest amount or an error
 fn calculate_accrued_interest(
     env: &Env,
+    user: &Address,
+    asset: &Option<Address>,
     principal: i128,
     last_accrual_time: u64,
     current_time: u64,
@@ -66,62 +70,74 @@ fn calculate_accrued_interest(
     if principal == 0 {
         return Ok(0);
     }
-    if current_time <= last_accrual_time {
-        return Ok(0);
-    }
-    let rate_bps =
-        crate::interest_rate::calculate_borrow_rate(env).map_err(|_| RepayError::Overflow)?;
-    crate::interest_rate::calculate_accrued_interest(
+
+    // Dynamic borrow rate, capped per user class with the gap absorbed by the subsidy budget
+    crate::interest_rate::calculate_user_accrued_interest(
+        env,
+        user,
+        asset,
         principal,
         last_accrual_time,
         current_time,
-        rate_bps,
     )
     .map_err(|_| RepayError::Overflow)
 }
 
 /// Accrue interest on a position
 ///
-/// Updates the position's borrow_interest and last_accrual_time based on elapsed time
-/// and the current interest rate.
+/// Updates the position's borrow_interest from the growth of the borrow index
+/// since the user's snapshot, then snapshots the current index.
 ///
 /// # Arguments
 /// * `env` - The Soroban environment
+/// * `asset` - The debt asset, whose reserve factor applies to the interest
 /// * `position` - A mutable reference to the user's position
 ///
 /// # Returns
 /// * `Result<(), RepayError>` - Success or an error
-fn accrue_interest(env: &Env, position: &mut Position) -> Result<(), RepayError> {
+fn accrue_interest(
+    env: &Env,
+    user: &Address,
+    asset: &Option<Address>,
+    position: &mut Position,
+) -> Result<(), RepayError> {
     let current_time = env.ledger().timestamp();
     if position.debt == 0 {
         position.borrow_interest = 0;
         position.last_accrual_time = current_time;
+        crate::interest_rate::checkpoint_user_borrow_index(env, user)
+            .map_err(|_| RepayError::Overflow)?;
         return Ok(());
     }
-    let new_interest =
-        calculate_accrued_interest(env, position.debt, position.last_accrual_time, current_time)?;
+    let new_interest = calculate_accrued_interest(
+        env,
+        user,
+        asset,
+        position.debt,
+        position.last_accrual_time,
+        current_time,
+    )?;
+    // An open subsidy campaign on the debt asset absorbs part of it
+    let new_interest = crate::subsidy_campaign::apply_campaign_subsidy(
+        env,
+        asset,
+        position.debt,
+        position.last_accrual_time,
+        current_time,
+        new_interest,
+    );
     position.borrow_interest = position
         .borrow_interest
         .checked_add(new_interest)
         .ok_or(RepayError::Overflow)?;
+    // Pass the suppliers' share of the new interest to the supply pool
+    crate::deposit::accrue_supply_interest(env, asset, new_interest);
     position.last_accrual_time = current_time;
+    crate::interest_rate::checkpoint_user_borrow_index(env, user)
+        .map_err(|_| RepayError::Overflow)?;
     Ok(())
 }
 
-/// Helper function to get the native asset contract address from storage
-///
-/// # Arguments
-/// * `env` - The Soroban environment
-///
-/// # Returns
-/// * `Result<Address, RepayError>` - The native asset address or an error if not configured
-fn get_native_asset_address(env: &Env) -> Result<Address, RepayError> {
-    env.storage()
-        .persistent()
-        .get::<DepositDataKey, Address>(&DepositDataKey::NativeAssetAddress)
-        .ok_or(RepayError::InvalidAsset)
-}
-
 /// Repay debt function
 ///
 /// Allows users to repay their borrowed assets, reducing debt and accrued interest.
@@ -159,6 +175,58 @@ pub fn repay_debt(
     user: Address,
     asset: Option<Address>,
     amount: i128,
+) -> Result<(i128, i128, i128), RepayError> {
+    repay_debt_from(env, user.clone(), user, asset, amount)
+}
+
+/// Repay another user's debt
+///
+/// `payer` supplies the tokens and must authorize the call; the debt repaid
+/// belongs to `user`, whose authorization is not required.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `payer` - The address supplying the tokens
+/// * `user` - The address whose debt is repaid
+/// * `asset` - The address of the asset contract to repay (None for native XLM)
+/// * `amount` - The amount to repay
+///
+/// # Returns
+/// Returns a tuple (remaining_debt, interest_paid, principal_paid) for `user`
+///
+/// # Errors
+/// Same as [`repay_debt`], with `InsufficientBalance` checked against the payer
+pub fn repay_debt_for(
+    env: &Env,
+    payer: Address,
+    user: Address,
+    asset: Option<Address>,
+    amount: i128,
+) -> Result<(i128, i128, i128), RepayError> {
+    payer.require_auth();
+    let (remaining_debt, interest_paid, principal_paid) =
+        repay_debt_from(env, payer.clone(), user.clone(), asset.clone(), amount)?;
+    emit_on_behalf(
+        env,
+        OnBehalfEvent {
+            payer,
+            user,
+            operation: Symbol::new(env, "repay"),
+            asset,
+            amount: interest_paid + principal_paid,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok((remaining_debt, interest_paid, principal_paid))
+}
+
+/// Repay `user`'s debt with tokens pulled from `payer`
+pub(crate) fn repay_debt_from(
+    env: &Env,
+    payer: Address,
+    user: Address,
+    asset: Option<Address>,
+    amount: i128,
 ) -> Result<(i128, i128, i128), RepayError> {
     if amount <= 0 {
         return Err(RepayError::InvalidAmount);
@@ -197,23 +265,8 @@ pub fn repay_debt(
             }
             addr.clone()
         }
-        None => get_native_asset_address(env)?,
+        None => get_native_asset_address(env).ok_or(RepayError::InvalidAsset)?,
     };
-    let reserve_factor = if let Some(asset_addr) = asset.as_ref() {
-        let params_key = DepositDataKey::AssetParams(asset_addr.clone());
-        if let Some(params) = env
-            .storage()
-            .persistent()
-            .get::<DepositDataKey, crate::deposit::AssetParams>(&params_key)
-        {
-            1000 // Default 10%
-        } else {
-            1000
-        }
-    } else {
-        1000
-    };
-
     let position_key = DepositDataKey::Position(user.clone());
     let mut position = env
         .storage()
@@ -225,7 +278,7 @@ pub fn repay_debt(
         return Err(RepayError::NoDebt);
     }
 
-    accrue_interest(env, &mut position)?;
+    accrue_interest(env, &user, &asset, &mut position)?;
 
     let total_debt = position
         .debt
@@ -241,13 +294,13 @@ pub fn repay_debt(
         #[cfg(not(test))]
         {
             let token_client = soroban_sdk::token::Client::new(env, asset_addr);
-            let user_balance = token_client.balance(&user);
-            if user_balance < repay_amount {
+            let payer_balance = token_client.balance(&payer);
+            if payer_balance < repay_amount {
                 return Err(RepayError::InsufficientBalance);
             }
             token_client.transfer_from(
                 &env.current_contract_address(),
-                &user,
+                &payer,
                 &env.current_contract_address(),
                 &repay_amount,
             );
@@ -262,21 +315,21 @@ pub fn repay_debt(
     let principal_paid = repay_amount
         .checked_sub(interest_paid)
         .ok_or(RepayError::Overflow)?;
-    // Handle asset transfer - user pays the contract
+    // Handle asset transfer - payer pays the contract
     // We use the determined asset_addr (either token or native)
     let token_client = soroban_sdk::token::Client::new(env, &asset_addr);
 
-    // Check user balance
-    let user_balance = token_client.balance(&user);
-    if user_balance < repay_amount {
+    // Check payer balance
+    let payer_balance = token_client.balance(&payer);
+    if payer_balance < repay_amount {
         return Err(RepayError::InsufficientBalance);
     }
 
-    // Transfer tokens from user to contract
-    // The user must have approved the contract to spend their tokens
+    // Transfer tokens from payer to contract
+    // The payer must have approved the contract to spend their tokens
     token_client.transfer_from(
         &env.current_contract_address(), // spender (this contract)
-        &user,                           // from (user)
+        &payer,                          // from (payer)
         &env.current_contract_address(), // to (this contract)
         &repay_amount,
     );
@@ -309,29 +362,17 @@ pub fn repay_debt(
     position.last_accrual_time = timestamp;
 
     env.storage().persistent().set(&position_key, &position);
+    crate::snapshot::index_user(env, &user);
 
-    if interest_paid > 0 {
-        let reserve_amount = interest_paid
-            .checked_mul(reserve_factor)
-            .ok_or(RepayError::Overflow)?
-            .checked_div(10000)
-            .ok_or(RepayError::Overflow)?;
-        if reserve_amount > 0 {
-            let reserve_key = DepositDataKey::ProtocolReserve(asset.clone());
-            let current_reserve = env
-                .storage()
-                .persistent()
-                .get::<DepositDataKey, i128>(&reserve_key)
-                .unwrap_or(0);
-            env.storage().persistent().set(
-                &reserve_key,
-                &(current_reserve
-                    .checked_add(reserve_amount)
-                    .ok_or(RepayError::Overflow)?),
-            );
-        }
-    }
-
+    crate::cross_asset::record_borrow_change(env, asset.clone(), -principal_paid);
+    crate::deposit::update_asset_ledger(env, &asset, 0, -principal_paid);
+    crate::rewards::record_balance_change(
+        env,
+        &user,
+        &asset,
+        crate::rewards::RewardSide::Borrow,
+        -principal_paid,
+    );
     update_user_analytics_repay(env, &user, repay_amount, timestamp)?;
     update_protocol_analytics_repay(env, repay_amount)?;
     add_activity_log(
@@ -343,6 +384,14 @@ pub fn repay_debt(
         timestamp,
     )
     .map_err(|e| RepayError::Overflow)?;
+    let operation_id = record_operation(
+        env,
+        OperationKind::Repay,
+        &user,
+        &asset,
+        repay_amount,
+        timestamp,
+    );
     log_repay(
         env,
         RepayEvent {
@@ -350,6 +399,7 @@ pub fn repay_debt(
             asset: asset.clone(),
             amount: repay_amount,
             timestamp,
+            operation_id,
         },
     );
 
@@ -375,6 +425,7 @@ pub fn repay_debt(
             asset: asset.clone(),
             amount: repay_amount,
             timestamp,
+            operation_id,
         },
     );
 
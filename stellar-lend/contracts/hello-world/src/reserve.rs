@@ -14,14 +14,20 @@
 //! - Example: 1000 bps (10%) means 10% of interest goes to reserves, 90% to lenders
 //! - Configurable per asset by admin
 //! - Range: 0 - 5000 bps (0% - 50%)
+//! - Cross-asset markets keep it in `AssetConfig::reserve_factor`; other
+//!   assets use `ReserveFactor(asset)`
+//! - Settable by the admin, a `risk_admin` role holder, or an executed
+//!   governance proposal
 //!
 //! ### Reserve Accrual
-//! - Reserves accrue automatically when interest is calculated during repayment
+//! - Reserves accrue automatically whenever borrower interest accrues
 //! - Formula: `reserve_amount = total_interest * reserve_factor / 10000`
-//! - Tracked separately per asset in persistent storage
+//! - Booked to `DepositDataKey::ProtocolReserve(asset)`, the balance paid out
+//!   by `claim_reserves`, with lifetime totals in `ReserveAccount(asset)`
 //!
 //! ### Treasury Withdrawal
-//! - Admin can withdraw accrued reserves to a treasury address
+//! - Admin or a `treasurer` role holder can withdraw accrued reserves to a
+//!   treasury address
 //! - Withdrawals are bounded by the actual reserve balance
 //! - Cannot withdraw user funds (collateral or principal)
 //! - All withdrawals are logged via events
@@ -30,6 +36,18 @@
 //! - `ReserveBalance(asset)` — accumulated reserve per asset
 //! - `ReserveFactor(asset)` — reserve factor per asset (basis points)
 //! - `TreasuryAddress` — destination address for reserve withdrawals
+//! - `SubsidyBudget(asset)` — reserves earmarked to absorb capped borrow APRs
+//! - `SubsidySpent(asset)` — cumulative subsidy drawn from the budget
+//! - `ReserveAccount(asset)` — interest booked and reserves claimed per asset
+//!
+//! ### Borrow APR Subsidies
+//! - Admin earmarks part of an asset's reserve balance as that asset's
+//!   subsidy budget
+//! - When a user class has a borrow APR cap below the market rate, the interest
+//!   difference is drawn from the debt asset's budget at accrual time
+//! - Once the budget is exhausted, capped borrowers pay the market rate again
+//! - Governance subsidy campaigns draw their own budgets from an asset's
+//!   protocol reserve (see `subsidy_campaign.rs`)
 //!
 //! ## Security Invariants
 //! - Reserve factor must be between 0 and 5000 bps (0% - 50%)
@@ -41,7 +59,9 @@
 #![allow(unused)]
 use soroban_sdk::{contracterror, contracttype, Address, Env, Symbol};
 
+use crate::admin::{RISK_ADMIN, TREASURER};
 use crate::deposit::DepositDataKey;
+use crate::events::{emit_reserves_claimed, ReservesClaimedEvent};
 
 /// Maximum allowed reserve factor (50% = 5000 basis points)
 /// This ensures that at least 50% of interest always goes to lenders
@@ -90,6 +110,28 @@ pub enum ReserveDataKey {
     /// Treasury address: TreasuryAddress -> Address
     /// Destination for reserve withdrawals
     TreasuryAddress,
+    /// Subsidy budget per asset: SubsidyBudget(asset) -> i128
+    /// Reserves earmarked to absorb the gap between market and capped borrow APRs
+    SubsidyBudget(Option<Address>),
+    /// Cumulative subsidy spent per asset: SubsidySpent(asset) -> i128
+    SubsidySpent(Option<Address>),
+    /// Per-asset reserve accounting: ReserveAccount(asset) -> ReserveAccount
+    ReserveAccount(Option<Address>),
+}
+
+/// Lifetime protocol reserve accounting for an asset
+///
+/// The claimable balance itself is `DepositDataKey::ProtocolReserve(asset)`,
+/// which also receives borrow and flash loan fees.
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReserveAccount {
+    /// Borrower interest booked to the reserve by the reserve factor
+    pub interest_accrued: i128,
+    /// Reserves claimed to treasury addresses
+    pub claimed: i128,
+    /// Timestamp of the last claim (0 if never claimed)
+    pub last_claimed_at: u64,
 }
 
 /// Initialize reserve configuration for an asset
@@ -144,12 +186,12 @@ pub fn initialize_reserve_config(
 ///
 /// # Arguments
 /// * `env` - The Soroban environment
-/// * `caller` - The caller address (must be admin)
+/// * `caller` - The caller address (must be admin or risk admin)
 /// * `asset` - The asset address (None for native asset)
 /// * `reserve_factor_bps` - New reserve factor in basis points (0-5000)
 ///
 /// # Errors
-/// * `ReserveError::Unauthorized` - If caller is not admin
+/// * `ReserveError::Unauthorized` - If caller is not admin or risk admin
 /// * `ReserveError::InvalidReserveFactor` - If reserve factor > MAX_RESERVE_FACTOR_BPS
 ///
 /// # Security
@@ -164,29 +206,44 @@ pub fn set_reserve_factor(
 ) -> Result<(), ReserveError> {
     // Require admin authorization
     caller.require_auth();
-    require_admin(env, &caller)?;
+    require_role(env, &caller, RISK_ADMIN)?;
 
-    // Validate reserve factor
+    apply_reserve_factor(env, asset.clone(), reserve_factor_bps)?;
+
+    // Emit event
+    let topics = (Symbol::new(env, "reserve_factor_updated"), caller);
+    env.events().publish(topics, (asset, reserve_factor_bps));
+
+    Ok(())
+}
+
+/// Validate and store a reserve factor without an authorization check
+///
+/// Shared by [`set_reserve_factor`] and governance execution. Updates the
+/// asset's cross-asset `AssetConfig` when one is registered.
+pub(crate) fn apply_reserve_factor(
+    env: &Env,
+    asset: Option<Address>,
+    reserve_factor_bps: i128,
+) -> Result<(), ReserveError> {
     if reserve_factor_bps < 0 || reserve_factor_bps > MAX_RESERVE_FACTOR_BPS {
         return Err(ReserveError::InvalidReserveFactor);
     }
 
-    // Update reserve factor
     let factor_key = ReserveDataKey::ReserveFactor(asset.clone());
     env.storage()
         .persistent()
         .set(&factor_key, &reserve_factor_bps);
-
-    // Emit event
-    let topics = (Symbol::new(env, "reserve_factor_updated"), caller);
-    env.events().publish(topics, (asset, reserve_factor_bps));
+    // Unregistered assets only use the key above
+    let _ = crate::cross_asset::set_asset_reserve_factor(env, asset, reserve_factor_bps);
 
     Ok(())
 }
 
 /// Get the reserve factor for an asset
 ///
-/// Returns the current reserve factor, or the default if not explicitly set.
+/// Returns the asset's `AssetConfig::reserve_factor` if it is a registered
+/// cross-asset market, otherwise the stored factor or the default.
 ///
 /// # Arguments
 /// * `env` - The Soroban environment
@@ -195,6 +252,9 @@ pub fn set_reserve_factor(
 /// # Returns
 /// Reserve factor in basis points (0-5000)
 pub fn get_reserve_factor(env: &Env, asset: Option<Address>) -> i128 {
+    if let Ok(config) = crate::cross_asset::get_asset_config_by_address(env, asset.clone()) {
+        return config.reserve_factor;
+    }
     let factor_key = ReserveDataKey::ReserveFactor(asset);
     env.storage()
         .persistent()
@@ -202,6 +262,124 @@ pub fn get_reserve_factor(env: &Env, asset: Option<Address>) -> i128 {
         .unwrap_or(DEFAULT_RESERVE_FACTOR_BPS)
 }
 
+/// Book the reserve factor share of newly accrued borrower interest
+///
+/// Adds `interest * reserve_factor / 10000` to
+/// `DepositDataKey::ProtocolReserve(asset)` and the asset's lifetime
+/// accounting. Called wherever borrower interest accrues.
+///
+/// # Returns
+/// The amount booked to the reserve
+pub(crate) fn accrue_protocol_reserve(env: &Env, asset: &Option<Address>, interest: i128) -> i128 {
+    if interest <= 0 {
+        return 0;
+    }
+    let reserve_amount =
+        interest.saturating_mul(get_reserve_factor(env, asset.clone())) / BASIS_POINTS_SCALE;
+    if reserve_amount <= 0 {
+        return 0;
+    }
+
+    let reserve_key = DepositDataKey::ProtocolReserve(asset.clone());
+    let balance: i128 = env.storage().persistent().get(&reserve_key).unwrap_or(0);
+    env.storage()
+        .persistent()
+        .set(&reserve_key, &balance.saturating_add(reserve_amount));
+
+    let mut account = get_reserve_account(env, asset.clone());
+    account.interest_accrued = account.interest_accrued.saturating_add(reserve_amount);
+    env.storage()
+        .persistent()
+        .set(&ReserveDataKey::ReserveAccount(asset.clone()), &account);
+    crate::financials::record(
+        env,
+        crate::financials::FinancialItem::InterestCollected,
+        asset,
+        reserve_amount,
+    );
+
+    reserve_amount
+}
+
+/// Get the lifetime reserve accounting for an asset
+pub fn get_reserve_account(env: &Env, asset: Option<Address>) -> ReserveAccount {
+    env.storage()
+        .persistent()
+        .get(&ReserveDataKey::ReserveAccount(asset))
+        .unwrap_or_default()
+}
+
+/// Claim protocol reserves to an address (admin only)
+///
+/// Pays out of `DepositDataKey::ProtocolReserve(asset)` and records the claim
+/// in the asset's accounting.
+///
+/// # Returns
+/// The remaining reserve balance
+///
+/// # Errors
+/// * `ReserveError::Unauthorized` - If caller is not admin or treasurer
+/// * `ReserveError::InvalidAmount` - If amount <= 0
+/// * `ReserveError::InsufficientReserve` - If amount > reserve balance
+/// * `ReserveError::InvalidTreasury` - If `to` is the contract itself
+pub fn claim_protocol_reserves(
+    env: &Env,
+    caller: Address,
+    asset: Option<Address>,
+    to: Address,
+    amount: i128,
+) -> Result<i128, ReserveError> {
+    crate::admin::require_role(env, &caller, TREASURER).map_err(|_| ReserveError::Unauthorized)?;
+
+    if amount <= 0 {
+        return Err(ReserveError::InvalidAmount);
+    }
+    if to == env.current_contract_address() {
+        return Err(ReserveError::InvalidTreasury);
+    }
+
+    let reserve_key = DepositDataKey::ProtocolReserve(asset.clone());
+    let balance: i128 = env.storage().persistent().get(&reserve_key).unwrap_or(0);
+    if amount > balance {
+        return Err(ReserveError::InsufficientReserve);
+    }
+    let remaining = balance - amount;
+    env.storage().persistent().set(&reserve_key, &remaining);
+
+    let now = env.ledger().timestamp();
+    let mut account = get_reserve_account(env, asset.clone());
+    account.claimed = account
+        .claimed
+        .checked_add(amount)
+        .ok_or(ReserveError::Overflow)?;
+    account.last_claimed_at = now;
+    env.storage()
+        .persistent()
+        .set(&ReserveDataKey::ReserveAccount(asset.clone()), &account);
+
+    #[cfg(not(test))]
+    {
+        if let Some(ref asset_addr) = asset {
+            let token_client = soroban_sdk::token::Client::new(env, asset_addr);
+            token_client.transfer(&env.current_contract_address(), &to, &amount);
+        }
+    }
+
+    emit_reserves_claimed(
+        env,
+        ReservesClaimedEvent {
+            actor: caller,
+            asset,
+            to,
+            amount,
+            remaining,
+            timestamp: now,
+        },
+    );
+
+    Ok(remaining)
+}
+
 /// Accrue reserves from interest payment
 ///
 /// Called internally when interest is paid during repayment. Calculates the
@@ -347,7 +525,7 @@ pub fn get_treasury_address(env: &Env) -> Option<Address> {
 ///
 /// # Arguments
 /// * `env` - The Soroban environment
-/// * `caller` - The caller address (must be admin)
+/// * `caller` - The caller address (must be admin or treasurer)
 /// * `asset` - The asset address (None for native asset)
 /// * `amount` - Amount to withdraw
 ///
@@ -355,7 +533,7 @@ pub fn get_treasury_address(env: &Env) -> Option<Address> {
 /// Actual amount withdrawn
 ///
 /// # Errors
-/// * `ReserveError::Unauthorized` - If caller is not admin
+/// * `ReserveError::Unauthorized` - If caller is not admin or treasurer
 /// * `ReserveError::TreasuryNotSet` - If treasury address not configured
 /// * `ReserveError::InvalidAmount` - If amount <= 0
 /// * `ReserveError::InsufficientReserve` - If amount > reserve balance
@@ -376,7 +554,7 @@ pub fn withdraw_reserve_to_treasury(
 ) -> Result<i128, ReserveError> {
     // Require admin authorization
     caller.require_auth();
-    require_admin(env, &caller)?;
+    require_role(env, &caller, TREASURER)?;
 
     // Validate amount
     if amount <= 0 {
@@ -446,6 +624,14 @@ fn require_admin(env: &Env, caller: &Address) -> Result<(), ReserveError> {
     Ok(())
 }
 
+/// Require that the caller is the admin or holds an operational `role`
+fn require_role(env: &Env, caller: &Address, role: &str) -> Result<(), ReserveError> {
+    if crate::admin::has_role(env, Symbol::new(env, role), caller.clone()) {
+        return Ok(());
+    }
+    require_admin(env, caller)
+}
+
 /// Get reserve statistics for an asset
 ///
 /// Returns comprehensive reserve information for reporting and analytics.
@@ -463,3 +649,119 @@ pub fn get_reserve_stats(env: &Env, asset: Option<Address>) -> (i128, i128, Opti
 
     (balance, factor, treasury)
 }
+
+/// Earmark an asset's reserves as its borrow APR subsidy budget (admin only)
+///
+/// Moves `amount` from the asset's reserve balance into its subsidy budget.
+/// The budget absorbs the interest difference on debt in that asset for
+/// borrowers whose user class has a rate cap below the market rate.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `caller` - The caller address (must be admin or treasurer)
+/// * `asset` - The asset address (None for native asset)
+/// * `amount` - Amount of reserves to earmark
+///
+/// # Returns
+/// The asset's new subsidy budget
+///
+/// # Errors
+/// * `ReserveError::Unauthorized` - If caller is not admin or treasurer
+/// * `ReserveError::InvalidAmount` - If amount <= 0
+/// * `ReserveError::InsufficientReserve` - If amount > the asset's reserve balance
+/// * `ReserveError::Overflow` - If arithmetic overflow occurs
+pub fn allocate_subsidy_budget(
+    env: &Env,
+    caller: Address,
+    asset: Option<Address>,
+    amount: i128,
+) -> Result<i128, ReserveError> {
+    crate::admin::require_role(env, &caller, TREASURER).map_err(|_| ReserveError::Unauthorized)?;
+
+    if amount <= 0 {
+        return Err(ReserveError::InvalidAmount);
+    }
+
+    let balance_key = ReserveDataKey::ReserveBalance(asset.clone());
+    let reserve_balance: i128 = env.storage().persistent().get(&balance_key).unwrap_or(0);
+    if amount > reserve_balance {
+        return Err(ReserveError::InsufficientReserve);
+    }
+
+    let new_reserve = reserve_balance
+        .checked_sub(amount)
+        .ok_or(ReserveError::Overflow)?;
+    let new_budget = get_subsidy_budget(env, asset.clone())
+        .checked_add(amount)
+        .ok_or(ReserveError::Overflow)?;
+
+    env.storage().persistent().set(&balance_key, &new_reserve);
+    env.storage()
+        .persistent()
+        .set(&ReserveDataKey::SubsidyBudget(asset.clone()), &new_budget);
+
+    let topics = (Symbol::new(env, "subsidy_budget_allocated"), caller);
+    env.events().publish(topics, (asset, amount, new_budget));
+
+    Ok(new_budget)
+}
+
+/// Draw from an asset's subsidy budget
+///
+/// Called internally during interest accrual on debt in `asset`. Draws at
+/// most the remaining budget and returns the amount actually covered.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `asset` - The debt asset (None for native asset)
+/// * `amount` - Interest difference to be absorbed
+///
+/// # Returns
+/// The amount covered by the budget (may be less than requested)
+pub fn draw_subsidy(env: &Env, asset: Option<Address>, amount: i128) -> i128 {
+    if amount <= 0 {
+        return 0;
+    }
+
+    let budget = get_subsidy_budget(env, asset.clone());
+    let drawn = amount.min(budget);
+    if drawn <= 0 {
+        return 0;
+    }
+
+    let spent = get_subsidy_spent(env, asset.clone()).saturating_add(drawn);
+    env.storage().persistent().set(
+        &ReserveDataKey::SubsidyBudget(asset.clone()),
+        &(budget - drawn),
+    );
+    env.storage()
+        .persistent()
+        .set(&ReserveDataKey::SubsidySpent(asset.clone()), &spent);
+    crate::financials::record(
+        env,
+        crate::financials::FinancialItem::SubsidiesPaid,
+        &asset,
+        drawn,
+    );
+
+    let topics = (Symbol::new(env, "subsidy_drawn"),);
+    env.events().publish(topics, (asset, drawn, budget - drawn));
+
+    drawn
+}
+
+/// Get an asset's remaining borrow APR subsidy budget
+pub fn get_subsidy_budget(env: &Env, asset: Option<Address>) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&ReserveDataKey::SubsidyBudget(asset))
+        .unwrap_or(0)
+}
+
+/// Get the cumulative subsidy drawn from an asset's budget
+pub fn get_subsidy_spent(env: &Env, asset: Option<Address>) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&ReserveDataKey::SubsidySpent(asset))
+        .unwrap_or(0)
+}
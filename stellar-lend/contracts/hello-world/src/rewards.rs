@@ -0,0 +1,455 @@
+//! # Rewards Module
+//!
+//! Liquidity mining for suppliers and borrowers, paid in a single reward
+//! token.
+//!
+//! ## Emissions
+//! The admin sets the reward token once, funds a reward budget with
+//! [`fund_rewards`] and sets an emission rate (reward tokens per second) for
+//! each asset and side ([`RewardSide::Supply`] or [`RewardSide::Borrow`]).
+//! Emissions are paid out of the budget and stop once it is exhausted; time
+//! during which a market has no participants emits nothing.
+//!
+//! ## Accrual
+//! Each market keeps a reward index, the cumulative reward per unit of
+//! balance (scaled by `REWARD_INDEX_SCALE`), advanced before every balance
+//! change. A user's rewards since their last update are
+//! `balance * (index - user_index)`; settled rewards are held until
+//! [`claim_rewards`] transfers them.
+//!
+//! Balances are tracked per user, asset and side as deposits, withdrawals,
+//! borrows, repayments and liquidations move them, so a market earns from
+//! the moment its balances change after this module is deployed.
+
+use soroban_sdk::{contracterror, contracttype, token, Address, Env, Symbol, Vec};
+
+use crate::events::{
+    emit_admin_action, emit_rewards_claimed, emit_rewards_funded, AdminActionEvent,
+    RewardsClaimedEvent, RewardsFundedEvent,
+};
+
+/// Scale of market reward indices (1.0 = 1e18)
+pub const REWARD_INDEX_SCALE: i128 = 1_000_000_000_000_000_000;
+
+/// Errors that can occur in reward operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum RewardsError {
+    /// Caller is not the admin
+    Unauthorized = 1,
+    /// Amount or emission rate is negative or zero where not allowed
+    InvalidAmount = 2,
+    /// The reward token has not been set
+    RewardTokenNotSet = 3,
+    /// The reward token is already set
+    RewardTokenAlreadySet = 4,
+    /// The user has no rewards to claim
+    NothingToClaim = 5,
+    /// Overflow occurred during calculation
+    Overflow = 6,
+}
+
+/// Side of a market that earns rewards
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RewardSide {
+    Supply,
+    Borrow,
+}
+
+/// Storage keys for rewards
+#[contracttype]
+#[derive(Clone)]
+pub enum RewardsDataKey {
+    /// Token rewards are paid in
+    /// Value type: Address
+    RewardToken,
+    /// Funded rewards not yet emitted
+    /// Value type: i128
+    RewardBudget,
+    /// Reward tokens emitted per second to a market (asset, side)
+    /// Value type: i128
+    EmissionRate(Option<Address>, RewardSide),
+    /// Reward index of a market (asset, side)
+    /// Value type: RewardIndex
+    MarketIndex(Option<Address>, RewardSide),
+    /// Sum of user balances in a market (asset, side)
+    /// Value type: i128
+    MarketTotal(Option<Address>, RewardSide),
+    /// A user's position in a market (user, asset, side)
+    /// Value type: UserReward
+    UserReward(Address, Option<Address>, RewardSide),
+    /// Markets a user has a position in
+    /// Value type: Vec<RewardMarket>
+    UserMarkets(Address),
+    /// Rewards settled for a user and not yet claimed
+    /// Value type: i128
+    Accrued(Address),
+}
+
+/// An (asset, side) market
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RewardMarket {
+    pub asset: Option<Address>,
+    pub side: RewardSide,
+}
+
+/// Reward index of a market
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RewardIndex {
+    /// Cumulative reward per unit of balance, scaled by `REWARD_INDEX_SCALE`
+    pub index: i128,
+    /// Timestamp the index was last advanced
+    pub last_update: u64,
+}
+
+/// A user's position in a market
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct UserReward {
+    /// Balance earning rewards
+    pub balance: i128,
+    /// Market index at the user's last update
+    pub index: i128,
+}
+
+/// Get the reward token
+pub fn get_reward_token(env: &Env) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get::<RewardsDataKey, Address>(&RewardsDataKey::RewardToken)
+}
+
+/// Set the reward token (admin only, once)
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not the admin
+/// * `RewardTokenAlreadySet` - A reward token is already set
+pub fn set_reward_token(env: &Env, caller: Address, token: Address) -> Result<(), RewardsError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| RewardsError::Unauthorized)?;
+    if get_reward_token(env).is_some() {
+        return Err(RewardsError::RewardTokenAlreadySet);
+    }
+    env.storage()
+        .persistent()
+        .set(&RewardsDataKey::RewardToken, &token);
+
+    emit_admin_action(
+        env,
+        AdminActionEvent {
+            actor: caller,
+            action: Symbol::new(env, "set_reward_token"),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Get the funded rewards not yet emitted
+pub fn get_reward_budget(env: &Env) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<RewardsDataKey, i128>(&RewardsDataKey::RewardBudget)
+        .unwrap_or(0)
+}
+
+/// Transfer reward tokens from the admin into the reward budget
+///
+/// # Returns
+/// The new budget
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not the admin
+/// * `InvalidAmount` - Amount is not positive
+/// * `RewardTokenNotSet` - No reward token is set
+pub fn fund_rewards(env: &Env, caller: Address, amount: i128) -> Result<i128, RewardsError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| RewardsError::Unauthorized)?;
+    if amount <= 0 {
+        return Err(RewardsError::InvalidAmount);
+    }
+    let reward_token = get_reward_token(env).ok_or(RewardsError::RewardTokenNotSet)?;
+
+    token::Client::new(env, &reward_token).transfer(
+        &caller,
+        &env.current_contract_address(),
+        &amount,
+    );
+    let budget = get_reward_budget(env)
+        .checked_add(amount)
+        .ok_or(RewardsError::Overflow)?;
+    env.storage()
+        .persistent()
+        .set(&RewardsDataKey::RewardBudget, &budget);
+
+    emit_rewards_funded(
+        env,
+        RewardsFundedEvent {
+            funder: caller,
+            amount,
+            budget,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(budget)
+}
+
+/// Get the emission rate of a market (reward tokens per second)
+pub fn get_emission_rate(env: &Env, asset: &Option<Address>, side: RewardSide) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<RewardsDataKey, i128>(&RewardsDataKey::EmissionRate(asset.clone(), side))
+        .unwrap_or(0)
+}
+
+/// Set the emission rate of a market (admin only)
+///
+/// Rewards up to now are emitted at the previous rate first.
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not the admin
+/// * `InvalidAmount` - Rate is negative
+pub fn set_emission_rate(
+    env: &Env,
+    caller: Address,
+    asset: Option<Address>,
+    side: RewardSide,
+    rate_per_second: i128,
+) -> Result<(), RewardsError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| RewardsError::Unauthorized)?;
+    if rate_per_second < 0 {
+        return Err(RewardsError::InvalidAmount);
+    }
+
+    accrue_market(env, &asset, side);
+    env.storage()
+        .persistent()
+        .set(&RewardsDataKey::EmissionRate(asset, side), &rate_per_second);
+
+    emit_admin_action(
+        env,
+        AdminActionEvent {
+            actor: caller,
+            action: Symbol::new(env, "set_emission_rate"),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Get the reward index of a market, advanced to now
+pub fn get_market_index(env: &Env, asset: &Option<Address>, side: RewardSide) -> RewardIndex {
+    let (index, _) = projected_index(env, asset, side);
+    index
+}
+
+/// Get the rewards a user could claim now
+pub fn get_pending_rewards(env: &Env, user: &Address) -> i128 {
+    let mut pending = get_accrued(env, user);
+    for market in get_user_markets(env, user).iter() {
+        let (index, _) = projected_index(env, &market.asset, market.side);
+        let position = get_user_reward(env, user, &market.asset, market.side);
+        pending = pending.saturating_add(earned(&position, index.index));
+    }
+    pending
+}
+
+/// Settle and transfer all of a user's rewards
+///
+/// # Returns
+/// The amount claimed
+///
+/// # Errors
+/// * `RewardTokenNotSet` - No reward token is set
+/// * `NothingToClaim` - The user has no rewards
+pub fn claim_rewards(env: &Env, user: Address) -> Result<i128, RewardsError> {
+    user.require_auth();
+    let reward_token = get_reward_token(env).ok_or(RewardsError::RewardTokenNotSet)?;
+
+    for market in get_user_markets(env, &user).iter() {
+        settle_user(env, &user, &market.asset, market.side);
+    }
+    let amount = get_accrued(env, &user);
+    if amount <= 0 {
+        return Err(RewardsError::NothingToClaim);
+    }
+    env.storage()
+        .persistent()
+        .remove(&RewardsDataKey::Accrued(user.clone()));
+    token::Client::new(env, &reward_token).transfer(
+        &env.current_contract_address(),
+        &user,
+        &amount,
+    );
+    crate::financials::record(
+        env,
+        crate::financials::FinancialItem::RewardsPaid,
+        &Some(reward_token.clone()),
+        amount,
+    );
+
+    emit_rewards_claimed(
+        env,
+        RewardsClaimedEvent {
+            user,
+            token: reward_token,
+            amount,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(amount)
+}
+
+/// Settle a user's rewards in a market and apply a balance change
+///
+/// Called by deposit, withdraw, borrow, repay and liquidation after they
+/// change the user's supplied or borrowed balance. Decreases below zero
+/// clamp the balance at zero.
+pub(crate) fn record_balance_change(
+    env: &Env,
+    user: &Address,
+    asset: &Option<Address>,
+    side: RewardSide,
+    delta: i128,
+) {
+    if delta == 0 {
+        return;
+    }
+    let mut position = settle_user(env, user, asset, side);
+    let new_balance = position.balance.saturating_add(delta).max(0);
+    let applied = new_balance - position.balance;
+    position.balance = new_balance;
+
+    let key = RewardsDataKey::UserReward(user.clone(), asset.clone(), side);
+    env.storage().persistent().set(&key, &position);
+    let total_key = RewardsDataKey::MarketTotal(asset.clone(), side);
+    let total = get_market_total(env, asset, side)
+        .saturating_add(applied)
+        .max(0);
+    env.storage().persistent().set(&total_key, &total);
+
+    let market = RewardMarket {
+        asset: asset.clone(),
+        side,
+    };
+    let mut markets = get_user_markets(env, user);
+    if !markets.contains(&market) {
+        markets.push_back(market);
+        env.storage()
+            .persistent()
+            .set(&RewardsDataKey::UserMarkets(user.clone()), &markets);
+    }
+}
+
+/// Advance a market's index and settle the user's rewards into `Accrued`
+fn settle_user(env: &Env, user: &Address, asset: &Option<Address>, side: RewardSide) -> UserReward {
+    let index = accrue_market(env, asset, side);
+    let mut position = get_user_reward(env, user, asset, side);
+    let reward = earned(&position, index.index);
+    if reward > 0 {
+        let accrued = get_accrued(env, user).saturating_add(reward);
+        env.storage()
+            .persistent()
+            .set(&RewardsDataKey::Accrued(user.clone()), &accrued);
+    }
+    if position.index != index.index {
+        position.index = index.index;
+        env.storage().persistent().set(
+            &RewardsDataKey::UserReward(user.clone(), asset.clone(), side),
+            &position,
+        );
+    }
+    position
+}
+
+/// Advance a market's index to now, drawing emissions from the budget
+fn accrue_market(env: &Env, asset: &Option<Address>, side: RewardSide) -> RewardIndex {
+    let (index, emitted) = projected_index(env, asset, side);
+    env.storage()
+        .persistent()
+        .set(&RewardsDataKey::MarketIndex(asset.clone(), side), &index);
+    if emitted > 0 {
+        env.storage().persistent().set(
+            &RewardsDataKey::RewardBudget,
+            &(get_reward_budget(env) - emitted),
+        );
+    }
+    index
+}
+
+/// A market's index advanced to now, and the rewards emitted to get there
+fn projected_index(env: &Env, asset: &Option<Address>, side: RewardSide) -> (RewardIndex, i128) {
+    let now = env.ledger().timestamp();
+    let mut index = env
+        .storage()
+        .persistent()
+        .get::<RewardsDataKey, RewardIndex>(&RewardsDataKey::MarketIndex(asset.clone(), side))
+        .unwrap_or(RewardIndex {
+            index: 0,
+            last_update: now,
+        });
+    let total = get_market_total(env, asset, side);
+    let elapsed = now.saturating_sub(index.last_update) as i128;
+    index.last_update = now;
+    if total <= 0 || elapsed == 0 {
+        return (index, 0);
+    }
+
+    let emitted = get_emission_rate(env, asset, side)
+        .saturating_mul(elapsed)
+        .min(get_reward_budget(env));
+    if emitted <= 0 {
+        return (index, 0);
+    }
+    index.index = index
+        .index
+        .saturating_add(emitted.saturating_mul(REWARD_INDEX_SCALE) / total);
+    (index, emitted)
+}
+
+/// Rewards earned by a position up to `index`
+fn earned(position: &UserReward, index: i128) -> i128 {
+    position.balance.saturating_mul(index - position.index) / REWARD_INDEX_SCALE
+}
+
+fn get_market_total(env: &Env, asset: &Option<Address>, side: RewardSide) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<RewardsDataKey, i128>(&RewardsDataKey::MarketTotal(asset.clone(), side))
+        .unwrap_or(0)
+}
+
+fn get_user_reward(
+    env: &Env,
+    user: &Address,
+    asset: &Option<Address>,
+    side: RewardSide,
+) -> UserReward {
+    env.storage()
+        .persistent()
+        .get::<RewardsDataKey, UserReward>(&RewardsDataKey::UserReward(
+            user.clone(),
+            asset.clone(),
+            side,
+        ))
+        .unwrap_or(UserReward {
+            balance: 0,
+            index: 0,
+        })
+}
+
+fn get_user_markets(env: &Env, user: &Address) -> Vec<RewardMarket> {
+    env.storage()
+        .persistent()
+        .get::<RewardsDataKey, Vec<RewardMarket>>(&RewardsDataKey::UserMarkets(user.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+fn get_accrued(env: &Env, user: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<RewardsDataKey, i128>(&RewardsDataKey::Accrued(user.clone()))
+        .unwrap_or(0)
+}
@@ -0,0 +1,245 @@
+//! # External Risk Engine Hook
+//!
+//! Lets an external contract veto large borrows and withdrawals, so risk
+//! engines (heuristics, off-chain models publishing on-chain scores) can be
+//! plugged in without changing the core operations.
+//!
+//! Engines must first be whitelisted by governance (`ProposalType::RiskEngine`).
+//! The admin then points the hook at a whitelisted engine with per-operation
+//! value thresholds; only operations whose value in the accounting currency
+//! reaches the threshold are sent to the engine. Removing an engine from the
+//! whitelist disables the hook immediately.
+//!
+//! ## Bounded Calls
+//! The engine is consulted at most once per operation, with a single
+//! fixed-size [`RiskCheckRequest`]. Any failure (the engine traps, returns an
+//! error or an unexpected value) is resolved by the configured
+//! [`RiskHookFailPolicy`]: fail open lets the operation proceed, fail closed
+//! rejects it. Soroban has no per-call budget, so an engine that exhausts the
+//! transaction's resource limits still aborts the operation; engines must be
+//! reviewed for bounded cost before governance whitelists them.
+//!
+//! Operations the hook lets through emit a `risk_check` event recording
+//! whether the engine approved them or failed open; rejections surface as
+//! the operation's error.
+
+use soroban_sdk::{contractclient, contracterror, contracttype, Address, Env, Symbol};
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::events::{emit_admin_action, emit_risk_check, AdminActionEvent, RiskCheckEvent};
+
+/// Errors that can occur in risk hook operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum RiskHookError {
+    /// Caller is not the admin
+    Unauthorized = 1,
+    /// Engine is not whitelisted by governance
+    EngineNotWhitelisted = 2,
+    /// Thresholds must not be negative
+    InvalidThreshold = 3,
+    /// The engine rejected the operation, or failed under a fail-closed policy
+    Rejected = 4,
+}
+
+/// Interface an external risk engine implements
+#[contractclient(name = "RiskEngineClient")]
+pub trait RiskEngineInterface {
+    /// Return true to approve the operation
+    fn check_risk(env: Env, request: RiskCheckRequest) -> bool;
+}
+
+/// Operation sent to the risk engine
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RiskCheckRequest {
+    pub user: Address,
+    /// `borrow` or `withdraw`
+    pub operation: Symbol,
+    pub asset: Option<Address>,
+    pub amount: i128,
+    /// Value of `amount` in the accounting currency (None if unpriced)
+    pub value: Option<i128>,
+    /// Collateral balance before the operation
+    pub collateral: i128,
+    /// Debt (principal plus interest) before the operation
+    pub debt: i128,
+}
+
+/// How an engine failure is resolved
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RiskHookFailPolicy {
+    /// Proceed as if the engine approved
+    FailOpen,
+    /// Reject the operation
+    FailClosed,
+}
+
+/// Active risk hook
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RiskHookConfig {
+    /// Whitelisted engine contract
+    pub engine: Address,
+    /// Smallest borrow value (accounting currency) sent to the engine
+    pub borrow_threshold: i128,
+    /// Smallest withdrawal value (accounting currency) sent to the engine
+    pub withdraw_threshold: i128,
+    pub fail_policy: RiskHookFailPolicy,
+}
+
+/// Storage keys for risk hook data
+#[contracttype]
+#[derive(Clone)]
+pub enum RiskHookDataKey {
+    /// Active hook (absent = no external checks)
+    /// Value type: RiskHookConfig
+    Config,
+    /// Engine approved by governance
+    /// Value type: bool
+    Whitelisted(Address),
+}
+
+/// Whether governance has whitelisted an engine
+pub fn is_risk_engine_whitelisted(env: &Env, engine: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&RiskHookDataKey::Whitelisted(engine.clone()))
+        .unwrap_or(false)
+}
+
+/// Add or remove an engine from the whitelist without an authorization check
+///
+/// Called by governance proposal execution.
+pub(crate) fn apply_risk_engine_whitelist(env: &Env, engine: Address, whitelisted: bool) {
+    let key = RiskHookDataKey::Whitelisted(engine);
+    if whitelisted {
+        env.storage().persistent().set(&key, &true);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// Get the active risk hook, if any
+pub fn get_risk_hook(env: &Env) -> Option<RiskHookConfig> {
+    env.storage().persistent().get(&RiskHookDataKey::Config)
+}
+
+/// Set or clear the risk hook (admin only)
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not admin
+/// * `EngineNotWhitelisted` - The engine is not whitelisted by governance
+/// * `InvalidThreshold` - A threshold is negative
+pub fn set_risk_hook(
+    env: &Env,
+    caller: Address,
+    config: Option<RiskHookConfig>,
+) -> Result<(), RiskHookError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| RiskHookError::Unauthorized)?;
+    match &config {
+        Some(config) => {
+            if !is_risk_engine_whitelisted(env, &config.engine) {
+                return Err(RiskHookError::EngineNotWhitelisted);
+            }
+            if config.borrow_threshold < 0 || config.withdraw_threshold < 0 {
+                return Err(RiskHookError::InvalidThreshold);
+            }
+            env.storage()
+                .persistent()
+                .set(&RiskHookDataKey::Config, config);
+        }
+        None => env.storage().persistent().remove(&RiskHookDataKey::Config),
+    }
+
+    emit_admin_action(
+        env,
+        AdminActionEvent {
+            actor: caller,
+            action: Symbol::new(env, "set_risk_hook"),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Consult the risk engine before a borrow or withdrawal
+///
+/// Does nothing without an active hook, when its engine is no longer
+/// whitelisted, or when the operation's value is below the threshold.
+/// Operations that cannot be valued are always sent to the engine.
+///
+/// # Errors
+/// * `Rejected` - The engine rejected the operation, or failed under a
+///   fail-closed policy
+pub(crate) fn check_operation(
+    env: &Env,
+    user: &Address,
+    operation: Symbol,
+    asset: &Option<Address>,
+    amount: i128,
+) -> Result<(), RiskHookError> {
+    let Some(config) = get_risk_hook(env) else {
+        return Ok(());
+    };
+    if !is_risk_engine_whitelisted(env, &config.engine) {
+        return Ok(());
+    }
+
+    let threshold = if operation == Symbol::new(env, "borrow") {
+        config.borrow_threshold
+    } else {
+        config.withdraw_threshold
+    };
+    let value = crate::accounting::to_accounting_value(env, asset, amount).ok();
+    if value.is_some_and(|value| value < threshold) {
+        return Ok(());
+    }
+
+    let collateral = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, i128>(&DepositDataKey::CollateralBalance(user.clone()))
+        .unwrap_or(0);
+    let debt = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, Position>(&DepositDataKey::Position(user.clone()))
+        .map(|p| p.debt.saturating_add(p.borrow_interest))
+        .unwrap_or(0);
+    let request = RiskCheckRequest {
+        user: user.clone(),
+        operation: operation.clone(),
+        asset: asset.clone(),
+        amount,
+        value,
+        collateral,
+        debt,
+    };
+
+    let (approved, engine_failed) =
+        match RiskEngineClient::new(env, &config.engine).try_check_risk(&request) {
+            Ok(Ok(approved)) => (approved, false),
+            _ => (config.fail_policy == RiskHookFailPolicy::FailOpen, true),
+        };
+
+    if !approved {
+        return Err(RiskHookError::Rejected);
+    }
+
+    emit_risk_check(
+        env,
+        RiskCheckEvent {
+            user: user.clone(),
+            engine: config.engine,
+            operation,
+            asset: asset.clone(),
+            amount,
+            engine_failed,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
@@ -11,16 +11,47 @@
 //! ## Pause Controls
 //! - Per-operation pause switches (deposit, withdraw, borrow, repay, liquidate)
 //! - Global emergency pause that halts all operations immediately
+//! - Per-market liquidation pause that expires automatically after a bounded duration
+//!
+//! ## Daily Borrow Limits
+//! - Optional global and per-asset caps on borrowed volume per UTC-aligned
+//!   ledger-time window (`DAILY_BORROW_WINDOW_SECONDS`)
+//! - Act as a circuit breaker against exploit-driven draining; counters reset
+//!   automatically when a new window starts
+//!
+//! ## Exit Exemptions
+//! - A governance-managed list of protocol-owned accounts (vault strategies,
+//!   treasury) that pay no exit fees and bypass withdrawal rate limits
+//! - Every waived fee or bypassed limit emits `ExitExemptionUsedEvent`
+//!
+//! ## Dynamic Close Factor
+//! - Optional health factor tiers scale the close factor with how far a
+//!   position is below the liquidation threshold (e.g. 25% closable at
+//!   HF 0.95-1.0, 100% below 0.8). Without tiers the base close factor (and
+//!   any position size tier) applies.
+//! - Position size tiers still cap the close factor for large debts.
+//!
+//! ## Liquidation Hysteresis
+//! - Once a liquidation finds a position liquidatable, it stays liquidatable
+//!   for the rest of the ledger until its collateral ratio clears the
+//!   liquidation threshold plus a small configurable band
+//! - Keeps positions floating at the threshold (rounding, oracle jitter)
+//!   from flapping between liquidatable and safe between partial
+//!   liquidations in the same ledger
 //!
 //! ## Safety
 //! - Parameter changes are limited to ±10% per update to prevent drastic shifts.
 //! - Min collateral ratio must always be ≥ liquidation threshold.
-//! - Only the admin address can modify risk parameters.
+//! - Only the admin address, or a holder of the matching operational role
+//!   (`RISK_ADMIN`, `PAUSER`, `LIQUIDATION_ADMIN`), can modify risk parameters.
 
 #![allow(unused)]
+use crate::admin::{LIQUIDATION_ADMIN, PAUSER, RISK_ADMIN};
 use crate::events::{
-    emit_admin_action, emit_pause_state_changed, emit_risk_params_updated, AdminActionEvent,
-    PauseStateChangedEvent, RiskParamsUpdatedEvent,
+    emit_admin_action, emit_exit_exemption_updated, emit_exit_exemption_used,
+    emit_liquidation_pause_updated, emit_pause_state_changed, emit_risk_params_updated,
+    AdminActionEvent, ExitExemptionUpdatedEvent, ExitExemptionUsedEvent,
+    LiquidationPauseUpdatedEvent, PauseStateChangedEvent, RiskParamsUpdatedEvent,
 };
 use soroban_sdk::{contracterror, contracttype, Address, Env, IntoVal, Map, Symbol, Val, Vec};
 
@@ -55,6 +86,8 @@ pub enum RiskManagementError {
     GovernanceRequired = 12,
     /// Contract has already been initialized
     AlreadyInitialized = 13,
+    /// Borrow would exceed the global or per-asset daily borrow cap
+    DailyBorrowCapExceeded = 14,
 }
 /// Storage keys for risk management data
 #[contracttype]
@@ -73,6 +106,82 @@ pub enum RiskDataKey {
     /// Timelock for safety of sensitive parameter changes
     /// Value type: u64 (timestamp)
     ParameterChangeTimelock,
+    /// Per-market liquidation pause; stores the timestamp at which the pause expires
+    /// Value type: u64 (timestamp)
+    LiquidationPause(Option<Address>),
+    /// Protocol-wide cap on borrowed volume per window (absent = unlimited)
+    /// Value type: i128
+    GlobalDailyBorrowCap,
+    /// Per-asset cap on borrowed volume per window (absent = unlimited)
+    /// Value type: i128
+    AssetDailyBorrowCap(Option<Address>),
+    /// Protocol-wide borrowed volume in the current window
+    /// Value type: BorrowWindow
+    GlobalDailyBorrowed,
+    /// Per-asset borrowed volume in the current window
+    /// Value type: BorrowWindow
+    AssetDailyBorrowed(Option<Address>),
+    /// Health factor tiers for the dynamic close factor (absent = base close factor)
+    /// Value type: Vec<HealthCloseFactorTier>
+    HealthCloseFactorTiers,
+    /// Price used to value positions in liquidation checks (absent = spot)
+    /// Value type: LiquidationPriceSource
+    LiquidationPriceSource,
+    /// Protocol-owned accounts exempt from exit fees and withdrawal rate limits
+    /// Value type: Vec<Address>
+    ExitExemptions,
+    /// Hysteresis band above the liquidation threshold (basis points, absent = 0)
+    /// Value type: i128
+    LiquidationHysteresis,
+    /// Ledger in which a liquidation last found the borrower liquidatable
+    /// (temporary storage)
+    /// Value type: u32
+    LiquidatableLedger(Address),
+}
+
+/// Price source for liquidation checks
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum LiquidationPriceSource {
+    /// Latest oracle price
+    Spot,
+    /// Oracle TWAP over the given trailing window (seconds)
+    Twap(u64),
+}
+
+/// Close factor applied to positions below a health factor
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct HealthCloseFactorTier {
+    /// Tier applies to positions with a health factor below this value
+    /// (scaled by `HEALTH_FACTOR_SCALE`)
+    pub max_health_factor: i128,
+    /// Close factor for positions in this tier (in basis points)
+    pub close_factor: i128,
+}
+
+/// Borrowed volume accumulated within a single window
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BorrowWindow {
+    /// Timestamp at which the window started
+    pub window_start: u64,
+    /// Amount borrowed in this window
+    pub amount: i128,
+}
+
+/// Daily borrow limit status for status views
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DailyBorrowStatus {
+    /// Configured cap (None = unlimited)
+    pub cap: Option<i128>,
+    /// Amount borrowed in the current window
+    pub borrowed: i128,
+    /// Remaining capacity in the current window (None = unlimited)
+    pub remaining: Option<i128>,
+    /// Timestamp at which the current window ends and counters reset
+    pub window_end: u64,
 }
 
 /// Risk configuration parameters for pause switches
@@ -103,7 +212,23 @@ pub enum PauseOperation {
     All,
 }
 
+/// Maximum duration of a per-market liquidation pause (7 days)
+pub const MAX_LIQUIDATION_PAUSE_SECONDS: u64 = 7 * 24 * 60 * 60;
 
+/// Length of a daily borrow limit window (1 day)
+pub const DAILY_BORROW_WINDOW_SECONDS: u64 = 24 * 60 * 60;
+
+/// Health factor scale; 10000 = 1.0, i.e. exactly at the liquidation threshold
+pub const HEALTH_FACTOR_SCALE: i128 = stellarlend_math::HEALTH_FACTOR_SCALE;
+
+/// Maximum number of health factor close factor tiers
+pub const MAX_HEALTH_CLOSE_FACTOR_TIERS: u32 = 5;
+
+/// Maximum number of accounts on the exit exemption list
+pub const MAX_EXIT_EXEMPTIONS: u32 = 20;
+
+/// Maximum liquidation hysteresis band (1%)
+pub const MAX_LIQUIDATION_HYSTERESIS_BPS: i128 = 100;
 
 /// Initialize risk management system
 ///
@@ -176,6 +301,11 @@ pub fn require_admin(env: &Env, caller: &Address) -> Result<(), RiskManagementEr
     crate::admin::require_admin(env, caller).map_err(|_| RiskManagementError::Unauthorized)
 }
 
+/// Check if caller is admin or holds `role`
+pub fn require_role(env: &Env, caller: &Address, role: &str) -> Result<(), RiskManagementError> {
+    crate::admin::require_role(env, caller, role).map_err(|_| RiskManagementError::Unauthorized)
+}
+
 /// Get current risk configuration
 pub fn get_risk_config(env: &Env) -> Option<RiskConfig> {
     let config_key = RiskDataKey::RiskConfig;
@@ -186,13 +316,13 @@ pub fn get_risk_config(env: &Env) -> Option<RiskConfig> {
 
 
 
-/// Set pause switches (admin only)
+/// Set pause switches (admin or pauser)
 ///
 /// Updates pause switches for different operations.
 ///
 /// # Arguments
 /// * `env` - The Soroban environment
-/// * `caller` - The caller address (must be admin)
+/// * `caller` - The caller address (must be admin or pauser)
 /// * `operation` - The operation to pause/unpause (as Symbol)
 /// * `paused` - Whether to pause (true) or unpause (false)
 ///
@@ -200,7 +330,7 @@ pub fn get_risk_config(env: &Env) -> Option<RiskConfig> {
 /// Returns Ok(()) on success
 ///
 /// # Errors
-/// * `RiskManagementError::Unauthorized` - If caller is not admin
+/// * `RiskManagementError::Unauthorized` - If caller is not admin or pauser
 pub fn set_pause_switch(
     env: &Env,
     caller: Address,
@@ -208,8 +338,21 @@ pub fn set_pause_switch(
     paused: bool,
 ) -> Result<(), RiskManagementError> {
     // Check admin
-    require_admin(env, &caller)?;
+    require_role(env, &caller, PAUSER)?;
+
+    apply_pause_switch(env, &caller, operation, paused)
+}
 
+/// Set a pause switch without an authorization check
+///
+/// Shared by [`set_pause_switch`] and governance execution; `actor` is
+/// reported in the event.
+pub(crate) fn apply_pause_switch(
+    env: &Env,
+    actor: &Address,
+    operation: Symbol,
+    paused: bool,
+) -> Result<(), RiskManagementError> {
     // Get current config
     let mut config = get_risk_config(env).ok_or(RiskManagementError::InvalidParameter)?;
 
@@ -224,16 +367,16 @@ pub fn set_pause_switch(
     env.storage().persistent().set(&config_key, &config);
 
     // Emit event
-    emit_pause_switch_updated_event(env, &caller, &operation, paused);
+    emit_pause_switch_updated_event(env, actor, &operation, paused);
 
     Ok(())
 }
 
-/// Set multiple pause switches at once (admin only)
+/// Set multiple pause switches at once (admin or pauser)
 ///
 /// # Arguments
 /// * `env` - The Soroban environment
-/// * `caller` - The caller address (must be admin)
+/// * `caller` - The caller address (must be admin or pauser)
 /// * `switches` - Map of operation symbols to pause states
 ///
 /// # Returns
@@ -244,7 +387,7 @@ pub fn set_pause_switches(
     switches: Map<Symbol, bool>,
 ) -> Result<(), RiskManagementError> {
     // Check admin
-    require_admin(env, &caller)?;
+    require_role(env, &caller, PAUSER)?;
 
     // Get current config
     let mut config = get_risk_config(env).ok_or(RiskManagementError::InvalidParameter)?;
@@ -298,13 +441,13 @@ pub fn check_operation_paused(env: &Env, operation: Symbol) -> bool {
     is_operation_paused(env, operation)
 }
 
-/// Set emergency pause (admin only)
+/// Set emergency pause (admin or pauser)
 ///
 /// Emergency pause stops all operations immediately.
 ///
 /// # Arguments
 /// * `env` - The Soroban environment
-/// * `caller` - The caller address (must be admin)
+/// * `caller` - The caller address (must be admin or pauser)
 /// * `paused` - Whether to enable (true) or disable (false) emergency pause
 ///
 /// # Returns
@@ -315,16 +458,22 @@ pub fn set_emergency_pause(
     paused: bool,
 ) -> Result<(), RiskManagementError> {
     // Check admin
-    require_admin(env, &caller)?;
+    require_role(env, &caller, PAUSER)?;
+
+    apply_emergency_pause(env, &caller, paused);
+    Ok(())
+}
 
+/// Set the emergency pause without an authorization check
+///
+/// Shared by [`set_emergency_pause`] and governance execution.
+pub(crate) fn apply_emergency_pause(env: &Env, actor: &Address, paused: bool) {
     // Set emergency pause
     let emergency_key = RiskDataKey::EmergencyPause;
     env.storage().persistent().set(&emergency_key, &paused);
 
     // Emit event
-    emit_emergency_pause_event(env, &caller, paused);
-
-    Ok(())
+    emit_emergency_pause_event(env, actor, paused);
 }
 
 /// Check if emergency pause is active
@@ -348,6 +497,106 @@ pub fn check_emergency_pause(env: &Env) -> Result<(), RiskManagementError> {
 
 
 
+/// Pause liquidations for a single market (admin or pauser)
+///
+/// The pause expires automatically once `duration_seconds` have elapsed so that
+/// a forgotten pause cannot leave bad debt accruing indefinitely. Calling this
+/// again while a pause is active replaces the previous expiry.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `caller` - The caller address (must be admin or pauser)
+/// * `asset` - The market to pause (None for native XLM)
+/// * `duration_seconds` - Pause length, in (0, MAX_LIQUIDATION_PAUSE_SECONDS]
+///
+/// # Returns
+/// Returns the timestamp at which the pause expires
+///
+/// # Errors
+/// * `RiskManagementError::Unauthorized` - If caller is not admin or pauser
+/// * `RiskManagementError::InvalidParameter` - If duration is zero or exceeds the maximum
+pub fn pause_asset_liquidations(
+    env: &Env,
+    caller: Address,
+    asset: Option<Address>,
+    duration_seconds: u64,
+) -> Result<u64, RiskManagementError> {
+    require_role(env, &caller, PAUSER)?;
+
+    if duration_seconds == 0 || duration_seconds > MAX_LIQUIDATION_PAUSE_SECONDS {
+        return Err(RiskManagementError::InvalidParameter);
+    }
+
+    let now = env.ledger().timestamp();
+    let expires_at = now
+        .checked_add(duration_seconds)
+        .ok_or(RiskManagementError::Overflow)?;
+
+    let key = RiskDataKey::LiquidationPause(asset.clone());
+    env.storage().persistent().set(&key, &expires_at);
+
+    emit_liquidation_pause_updated(
+        env,
+        LiquidationPauseUpdatedEvent {
+            actor: caller,
+            asset,
+            expires_at,
+            timestamp: now,
+        },
+    );
+
+    Ok(expires_at)
+}
+
+/// Lift a per-market liquidation pause before it expires (admin or pauser)
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `caller` - The caller address (must be admin or pauser)
+/// * `asset` - The market to unpause (None for native XLM)
+pub fn unpause_asset_liquidations(
+    env: &Env,
+    caller: Address,
+    asset: Option<Address>,
+) -> Result<(), RiskManagementError> {
+    require_role(env, &caller, PAUSER)?;
+
+    let key = RiskDataKey::LiquidationPause(asset.clone());
+    env.storage().persistent().remove(&key);
+
+    let now = env.ledger().timestamp();
+    emit_liquidation_pause_updated(
+        env,
+        LiquidationPauseUpdatedEvent {
+            actor: caller,
+            asset,
+            expires_at: now,
+            timestamp: now,
+        },
+    );
+
+    Ok(())
+}
+
+/// Get the expiry of an active per-market liquidation pause
+///
+/// Returns None if the market is not paused or the pause has already expired.
+pub fn get_asset_liquidation_pause_expiry(env: &Env, asset: Option<Address>) -> Option<u64> {
+    let key = RiskDataKey::LiquidationPause(asset);
+    let expires_at = env.storage().persistent().get::<RiskDataKey, u64>(&key)?;
+
+    if env.ledger().timestamp() < expires_at {
+        Some(expires_at)
+    } else {
+        None
+    }
+}
+
+/// Check if liquidations are currently paused for a single market
+pub fn is_asset_liquidation_paused(env: &Env, asset: Option<Address>) -> bool {
+    get_asset_liquidation_pause_expiry(env, asset).is_some()
+}
+
 /// Emit pause switch updated event
 fn emit_pause_switch_updated_event(env: &Env, caller: &Address, operation: &Symbol, paused: bool) {
     emit_pause_state_changed(
@@ -388,3 +637,580 @@ fn emit_emergency_pause_event(env: &Env, caller: &Address, paused: bool) {
         },
     );
 }
+
+/// Set or clear the protocol-wide daily borrow cap
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `caller` - The caller address (must be admin or risk admin)
+/// * `cap` - Maximum volume borrowable per window (None to disable)
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not admin or risk admin
+/// * `InvalidParameter` - Cap is negative
+pub fn set_global_daily_borrow_cap(
+    env: &Env,
+    caller: Address,
+    cap: Option<i128>,
+) -> Result<(), RiskManagementError> {
+    require_role(env, &caller, RISK_ADMIN)?;
+    apply_global_daily_borrow_cap(env, cap)?;
+    emit_admin_action(
+        env,
+        AdminActionEvent {
+            actor: caller,
+            action: Symbol::new(env, "set_global_daily_cap"),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Set or clear the daily borrow cap for a single asset
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `caller` - The caller address (must be admin or risk admin)
+/// * `asset` - The asset (None for native XLM)
+/// * `cap` - Maximum volume borrowable per window (None to disable)
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not admin or risk admin
+/// * `InvalidParameter` - Cap is negative
+pub fn set_asset_daily_borrow_cap(
+    env: &Env,
+    caller: Address,
+    asset: Option<Address>,
+    cap: Option<i128>,
+) -> Result<(), RiskManagementError> {
+    require_role(env, &caller, RISK_ADMIN)?;
+    apply_asset_daily_borrow_cap(env, asset, cap)?;
+    emit_admin_action(
+        env,
+        AdminActionEvent {
+            actor: caller,
+            action: Symbol::new(env, "set_asset_daily_cap"),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Set or clear the protocol-wide daily borrow cap without an authorization
+/// check
+pub(crate) fn apply_global_daily_borrow_cap(
+    env: &Env,
+    cap: Option<i128>,
+) -> Result<(), RiskManagementError> {
+    set_daily_cap(env, RiskDataKey::GlobalDailyBorrowCap, cap)
+}
+
+/// Set or clear an asset's daily borrow cap without an authorization check
+pub(crate) fn apply_asset_daily_borrow_cap(
+    env: &Env,
+    asset: Option<Address>,
+    cap: Option<i128>,
+) -> Result<(), RiskManagementError> {
+    set_daily_cap(env, RiskDataKey::AssetDailyBorrowCap(asset), cap)
+}
+
+fn set_daily_cap(
+    env: &Env,
+    key: RiskDataKey,
+    cap: Option<i128>,
+) -> Result<(), RiskManagementError> {
+    match cap {
+        Some(cap) if cap < 0 => Err(RiskManagementError::InvalidParameter),
+        Some(cap) => {
+            env.storage().persistent().set(&key, &cap);
+            Ok(())
+        }
+        None => {
+            env.storage().persistent().remove(&key);
+            Ok(())
+        }
+    }
+}
+
+/// Start of the window containing the current ledger timestamp
+fn current_window_start(env: &Env) -> u64 {
+    let now = env.ledger().timestamp();
+    now - now % DAILY_BORROW_WINDOW_SECONDS
+}
+
+/// Volume borrowed in the current window for the given counter key
+fn window_borrowed(env: &Env, key: &RiskDataKey) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<RiskDataKey, BorrowWindow>(key)
+        .filter(|w| w.window_start == current_window_start(env))
+        .map(|w| w.amount)
+        .unwrap_or(0)
+}
+
+fn daily_status(env: &Env, cap_key: RiskDataKey, counter_key: RiskDataKey) -> DailyBorrowStatus {
+    let cap = env
+        .storage()
+        .persistent()
+        .get::<RiskDataKey, i128>(&cap_key);
+    let borrowed = window_borrowed(env, &counter_key);
+    DailyBorrowStatus {
+        cap,
+        borrowed,
+        remaining: cap.map(|c| c.saturating_sub(borrowed).max(0)),
+        window_end: current_window_start(env) + DAILY_BORROW_WINDOW_SECONDS,
+    }
+}
+
+/// Get the protocol-wide daily borrow limit status
+pub fn get_global_daily_borrow_status(env: &Env) -> DailyBorrowStatus {
+    daily_status(
+        env,
+        RiskDataKey::GlobalDailyBorrowCap,
+        RiskDataKey::GlobalDailyBorrowed,
+    )
+}
+
+/// Get the daily borrow limit status for an asset
+pub fn get_asset_daily_borrow_status(env: &Env, asset: Option<Address>) -> DailyBorrowStatus {
+    daily_status(
+        env,
+        RiskDataKey::AssetDailyBorrowCap(asset.clone()),
+        RiskDataKey::AssetDailyBorrowed(asset),
+    )
+}
+
+/// Check a borrow against the daily caps and record it in the current window
+///
+/// Called by the borrow path before any state is modified. Both the global
+/// and the per-asset counters are updated only if neither cap is exceeded.
+///
+/// # Errors
+/// * `DailyBorrowCapExceeded` - The borrow would exceed a daily cap
+/// * `Overflow` - Counter overflow
+pub fn record_daily_borrow(
+    env: &Env,
+    asset: Option<Address>,
+    amount: i128,
+) -> Result<(), RiskManagementError> {
+    let window_start = current_window_start(env);
+    let global = get_global_daily_borrow_status(env);
+    let per_asset = get_asset_daily_borrow_status(env, asset.clone());
+
+    let new_global = global
+        .borrowed
+        .checked_add(amount)
+        .ok_or(RiskManagementError::Overflow)?;
+    let new_asset = per_asset
+        .borrowed
+        .checked_add(amount)
+        .ok_or(RiskManagementError::Overflow)?;
+
+    if global.cap.is_some_and(|cap| new_global > cap)
+        || per_asset.cap.is_some_and(|cap| new_asset > cap)
+    {
+        return Err(RiskManagementError::DailyBorrowCapExceeded);
+    }
+
+    env.storage().persistent().set(
+        &RiskDataKey::GlobalDailyBorrowed,
+        &BorrowWindow {
+            window_start,
+            amount: new_global,
+        },
+    );
+    env.storage().persistent().set(
+        &RiskDataKey::AssetDailyBorrowed(asset),
+        &BorrowWindow {
+            window_start,
+            amount: new_asset,
+        },
+    );
+
+    Ok(())
+}
+
+/// Get the configured health factor close factor tiers (empty if none)
+pub fn get_health_close_factor_tiers(env: &Env) -> Vec<HealthCloseFactorTier> {
+    env.storage()
+        .persistent()
+        .get(&RiskDataKey::HealthCloseFactorTiers)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Set the health factor tiers for the dynamic close factor (admin or liquidation admin)
+///
+/// Tiers must be sorted by strictly increasing `max_health_factor`, the last
+/// tier must end at `HEALTH_FACTOR_SCALE` so every liquidatable position is
+/// covered, and close factors must not increase as health improves. An empty
+/// vector restores the base close factor.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `caller` - The caller address (must be admin or liquidation admin)
+/// * `tiers` - Health tiers, most severe (lowest `max_health_factor`) first
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not admin or liquidation admin
+/// * `InvalidCloseFactor` - A tier close factor is outside (0, 100%] or
+///   increases with health
+/// * `InvalidParameter` - Tiers are unsorted, too many, out of range, or do
+///   not end at `HEALTH_FACTOR_SCALE`
+pub fn set_health_close_factor_tiers(
+    env: &Env,
+    caller: Address,
+    tiers: Vec<HealthCloseFactorTier>,
+) -> Result<(), RiskManagementError> {
+    require_role(env, &caller, LIQUIDATION_ADMIN)?;
+
+    if tiers.len() > MAX_HEALTH_CLOSE_FACTOR_TIERS {
+        return Err(RiskManagementError::InvalidParameter);
+    }
+
+    let mut prev: Option<HealthCloseFactorTier> = None;
+    for tier in tiers.iter() {
+        if tier.max_health_factor <= 0 || tier.max_health_factor > HEALTH_FACTOR_SCALE {
+            return Err(RiskManagementError::InvalidParameter);
+        }
+        if tier.close_factor <= 0 || tier.close_factor > 10_000 {
+            return Err(RiskManagementError::InvalidCloseFactor);
+        }
+        if let Some(p) = prev {
+            if tier.max_health_factor <= p.max_health_factor {
+                return Err(RiskManagementError::InvalidParameter);
+            }
+            if tier.close_factor > p.close_factor {
+                return Err(RiskManagementError::InvalidCloseFactor);
+            }
+        }
+        prev = Some(tier);
+    }
+
+    if let Some(last) = prev {
+        if last.max_health_factor != HEALTH_FACTOR_SCALE {
+            return Err(RiskManagementError::InvalidParameter);
+        }
+    }
+
+    env.storage()
+        .persistent()
+        .set(&RiskDataKey::HealthCloseFactorTiers, &tiers);
+
+    emit_admin_action(
+        env,
+        AdminActionEvent {
+            actor: caller,
+            action: Symbol::new(env, "set_health_close_tiers"),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(())
+}
+
+/// Get the price source used for liquidation checks
+pub fn get_liquidation_price_source(env: &Env) -> LiquidationPriceSource {
+    env.storage()
+        .persistent()
+        .get(&RiskDataKey::LiquidationPriceSource)
+        .unwrap_or(LiquidationPriceSource::Spot)
+}
+
+/// Set the price source used for liquidation checks (admin or liquidation admin)
+///
+/// A TWAP source makes liquidations resistant to short-lived price
+/// manipulation at the cost of reacting more slowly to genuine moves.
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not admin or liquidation admin
+/// * `InvalidParameter` - TWAP window is zero
+pub fn set_liquidation_price_source(
+    env: &Env,
+    caller: Address,
+    source: LiquidationPriceSource,
+) -> Result<(), RiskManagementError> {
+    require_role(env, &caller, LIQUIDATION_ADMIN)?;
+
+    if source == LiquidationPriceSource::Twap(0) {
+        return Err(RiskManagementError::InvalidParameter);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&RiskDataKey::LiquidationPriceSource, &source);
+
+    emit_admin_action(
+        env,
+        AdminActionEvent {
+            actor: caller,
+            action: Symbol::new(env, "set_liq_price_source"),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(())
+}
+
+/// Get the liquidation hysteresis band (basis points)
+pub fn get_liquidation_hysteresis(env: &Env) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&RiskDataKey::LiquidationHysteresis)
+        .unwrap_or(0)
+}
+
+/// Set the liquidation hysteresis band (admin or liquidation admin)
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not admin or liquidation admin
+/// * `InvalidParameter` - Band is negative or above `MAX_LIQUIDATION_HYSTERESIS_BPS`
+pub fn set_liquidation_hysteresis(
+    env: &Env,
+    caller: Address,
+    band_bps: i128,
+) -> Result<(), RiskManagementError> {
+    require_role(env, &caller, LIQUIDATION_ADMIN)?;
+
+    if !(0..=MAX_LIQUIDATION_HYSTERESIS_BPS).contains(&band_bps) {
+        return Err(RiskManagementError::InvalidParameter);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&RiskDataKey::LiquidationHysteresis, &band_bps);
+
+    emit_admin_action(
+        env,
+        AdminActionEvent {
+            actor: caller,
+            action: Symbol::new(env, "set_liq_hysteresis"),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+
+    Ok(())
+}
+
+/// Record that a liquidation found the borrower liquidatable in this ledger
+pub(crate) fn mark_liquidatable(env: &Env, borrower: &Address) {
+    env.storage().temporary().set(
+        &RiskDataKey::LiquidatableLedger(borrower.clone()),
+        &env.ledger().sequence(),
+    );
+}
+
+/// Extra liquidation threshold (basis points) that applies to a borrower:
+/// the hysteresis band if they were found liquidatable in this ledger
+pub(crate) fn liquidation_hysteresis_for(env: &Env, borrower: &Address) -> i128 {
+    let marked: Option<u32> = env
+        .storage()
+        .temporary()
+        .get(&RiskDataKey::LiquidatableLedger(borrower.clone()));
+    if marked == Some(env.ledger().sequence()) {
+        get_liquidation_hysteresis(env)
+    } else {
+        0
+    }
+}
+
+/// Calculate a health factor from collateral and debt values
+///
+/// `collateral_value / debt_value / liquidation_threshold`, scaled by
+/// `HEALTH_FACTOR_SCALE`. Positions below `HEALTH_FACTOR_SCALE` are
+/// liquidatable. Returns `i128::MAX` when there is no debt.
+pub fn calculate_health_factor(
+    env: &Env,
+    collateral_value: i128,
+    debt_value: i128,
+) -> Result<i128, RiskManagementError> {
+    if debt_value <= 0 {
+        return Ok(i128::MAX);
+    }
+
+    let liquidation_threshold = crate::risk_params::get_liquidation_threshold(env)
+        .map_err(|_| RiskManagementError::InvalidParameter)?;
+
+    stellarlend_math::health_factor_from_ratio(collateral_value, debt_value, liquidation_threshold)
+        .ok_or(RiskManagementError::Overflow)
+}
+
+/// Get the close factor for a position with the given collateral and debt
+///
+/// Uses the health factor tier the position falls into, or the size-based
+/// close factor when no health tiers are configured. A matching position size
+/// tier still caps the result for large debts.
+pub fn get_close_factor_for_health(
+    env: &Env,
+    collateral_value: i128,
+    debt_value: i128,
+) -> Result<i128, RiskManagementError> {
+    let size_close_factor = crate::risk_params::get_close_factor_for_debt(env, debt_value)
+        .map_err(|_| RiskManagementError::InvalidParameter)?;
+
+    let tiers = get_health_close_factor_tiers(env);
+    if tiers.is_empty() {
+        return Ok(size_close_factor);
+    }
+
+    let health_factor = calculate_health_factor(env, collateral_value, debt_value)?;
+    let tier_close_factor = tiers
+        .iter()
+        .find(|tier| health_factor < tier.max_health_factor)
+        .map(|tier| tier.close_factor)
+        .unwrap_or(0);
+
+    let base_close_factor = crate::risk_params::get_close_factor(env)
+        .map_err(|_| RiskManagementError::InvalidParameter)?;
+    if size_close_factor < base_close_factor {
+        Ok(tier_close_factor.min(size_close_factor))
+    } else {
+        Ok(tier_close_factor)
+    }
+}
+
+/// Get the close factor currently applicable to a borrower's position
+///
+/// Values the position like `liquidate` does for native collateral and debt,
+/// including interest accrued since the last update. Returns 0 when the
+/// position is not liquidatable.
+pub fn get_dynamic_close_factor(
+    env: &Env,
+    borrower: &Address,
+) -> Result<i128, RiskManagementError> {
+    use crate::deposit::{DepositDataKey, Position};
+
+    let Some(position) = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, Position>(&DepositDataKey::Position(borrower.clone()))
+    else {
+        return Ok(0);
+    };
+
+    let pending_interest = if position.debt > 0 {
+        crate::interest_rate::preview_user_accrued_interest(
+            env,
+            borrower,
+            &None,
+            position.debt,
+            position.last_accrual_time,
+            env.ledger().timestamp(),
+        )
+        .map_err(|_| RiskManagementError::Overflow)?
+    } else {
+        0
+    };
+    let debt_value = position
+        .debt
+        .checked_add(position.borrow_interest)
+        .and_then(|d| d.checked_add(pending_interest))
+        .ok_or(RiskManagementError::Overflow)?;
+
+    let collateral_value = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, i128>(&DepositDataKey::CollateralBalance(borrower.clone()))
+        .unwrap_or(0);
+
+    if calculate_health_factor(env, collateral_value, debt_value)? >= HEALTH_FACTOR_SCALE {
+        return Ok(0);
+    }
+
+    get_close_factor_for_health(env, collateral_value, debt_value)
+}
+
+/// Add or remove an account on the exit exemption list (admin or risk admin)
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `caller` - The caller address (must be admin or risk admin)
+/// * `account` - Protocol-owned account (vault strategy, treasury)
+/// * `exempt` - Whether the account is exempt
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not admin or risk admin
+/// * `InvalidParameter` - The list already holds `MAX_EXIT_EXEMPTIONS` accounts
+pub fn set_exit_exemption(
+    env: &Env,
+    caller: Address,
+    account: Address,
+    exempt: bool,
+) -> Result<(), RiskManagementError> {
+    require_role(env, &caller, RISK_ADMIN)?;
+    apply_exit_exemption(env, account, exempt)
+}
+
+/// Update the exit exemption list without an authorization check
+///
+/// Shared by the admin entrypoint and governance proposal execution.
+pub(crate) fn apply_exit_exemption(
+    env: &Env,
+    account: Address,
+    exempt: bool,
+) -> Result<(), RiskManagementError> {
+    let mut exemptions = get_exit_exemptions(env);
+    match (exemptions.first_index_of(&account), exempt) {
+        (Some(_), true) | (None, false) => return Ok(()),
+        (Some(index), false) => {
+            exemptions.remove(index);
+        }
+        (None, true) => {
+            if exemptions.len() >= MAX_EXIT_EXEMPTIONS {
+                return Err(RiskManagementError::InvalidParameter);
+            }
+            exemptions.push_back(account.clone());
+        }
+    }
+    env.storage()
+        .persistent()
+        .set(&RiskDataKey::ExitExemptions, &exemptions);
+
+    emit_exit_exemption_updated(
+        env,
+        ExitExemptionUpdatedEvent {
+            account,
+            exempt,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Get the accounts on the exit exemption list
+pub fn get_exit_exemptions(env: &Env) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&RiskDataKey::ExitExemptions)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Whether an account is exempt from exit fees and withdrawal rate limits
+pub fn is_exit_exempt(env: &Env, account: &Address) -> bool {
+    get_exit_exemptions(env).contains(account)
+}
+
+/// Apply the exit exemption to a fee or rate limit check
+///
+/// Returns true if `account` is exempt, in which case the caller skips the
+/// fee or limit and an `ExitExemptionUsedEvent` records the waived fee
+/// (zero for a bypassed rate limit).
+pub(crate) fn use_exit_exemption(
+    env: &Env,
+    account: &Address,
+    operation: Symbol,
+    fee_waived: i128,
+) -> bool {
+    if !is_exit_exempt(env, account) {
+        return false;
+    }
+    emit_exit_exemption_used(
+        env,
+        ExitExemptionUsedEvent {
+            account: account.clone(),
+            operation,
+            fee_waived,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    true
+}
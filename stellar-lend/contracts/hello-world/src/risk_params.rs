@@ -29,6 +29,18 @@ pub enum RiskParamsError {
 pub enum RiskParamsDataKey {
     /// Risk configuration parameters
     RiskParamsConfig,
+    /// Position size tiers that reduce the close factor for large debts
+    CloseFactorTiers,
+}
+
+/// Close factor override applied to positions at or above a debt size
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct CloseFactorTier {
+    /// Minimum total debt (in base units) for this tier to apply
+    pub min_debt: i128,
+    /// Close factor for positions in this tier (in basis points)
+    pub close_factor: i128,
 }
 
 /// Risk parameters
@@ -62,6 +74,7 @@ const CLOSE_FACTOR_MAX: i128 = BASIS_POINTS_SCALE; // 100% maximum
 const LIQUIDATION_INCENTIVE_MIN: i128 = 0; // 0% minimum
 const LIQUIDATION_INCENTIVE_MAX: i128 = 5_000; // 50% maximum (safety limit)
 const MAX_PARAMETER_CHANGE_BPS: i128 = 1_000; // 10% maximum change per update
+const MAX_CLOSE_FACTOR_TIERS: u32 = 10;
 
 /// Initialize risk parameters
 ///
@@ -137,6 +150,44 @@ fn validate_risk_params(config: &RiskParams) -> Result<(), RiskParamsError> {
     Ok(())
 }
 
+/// Check proposed risk parameters against their absolute bounds
+///
+/// Ignores the current values and the per-update change limit, which
+/// [`set_risk_params`] enforces when the change is applied.
+pub(crate) fn validate_risk_params_bounds(
+    min_collateral_ratio: Option<i128>,
+    liquidation_threshold: Option<i128>,
+    close_factor: Option<i128>,
+    liquidation_incentive: Option<i128>,
+) -> Result<(), RiskParamsError> {
+    if let Some(mcr) = min_collateral_ratio {
+        if !(MIN_COLLATERAL_RATIO_MIN..=MIN_COLLATERAL_RATIO_MAX).contains(&mcr) {
+            return Err(RiskParamsError::InvalidParameter);
+        }
+    }
+    if let Some(lt) = liquidation_threshold {
+        if !(LIQUIDATION_THRESHOLD_MIN..=LIQUIDATION_THRESHOLD_MAX).contains(&lt) {
+            return Err(RiskParamsError::InvalidLiquidationThreshold);
+        }
+    }
+    if let (Some(mcr), Some(lt)) = (min_collateral_ratio, liquidation_threshold) {
+        if mcr < lt {
+            return Err(RiskParamsError::InvalidCollateralRatio);
+        }
+    }
+    if let Some(cf) = close_factor {
+        if !(CLOSE_FACTOR_MIN..=CLOSE_FACTOR_MAX).contains(&cf) {
+            return Err(RiskParamsError::InvalidCloseFactor);
+        }
+    }
+    if let Some(li) = liquidation_incentive {
+        if !(LIQUIDATION_INCENTIVE_MIN..=LIQUIDATION_INCENTIVE_MAX).contains(&li) {
+            return Err(RiskParamsError::InvalidLiquidationIncentive);
+        }
+    }
+    Ok(())
+}
+
 /// Validate parameter change doesn't exceed maximum allowed change
 fn validate_parameter_change(old_value: i128, new_value: i128) -> Result<(), RiskParamsError> {
     let change = if new_value > old_value {
@@ -238,6 +289,85 @@ pub fn get_close_factor(env: &Env) -> Result<i128, RiskParamsError> {
     Ok(config.close_factor)
 }
 
+/// Get configured close factor size tiers (empty if none)
+pub fn get_close_factor_tiers(env: &Env) -> Vec<CloseFactorTier> {
+    env.storage()
+        .persistent()
+        .get(&RiskParamsDataKey::CloseFactorTiers)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Set position size tiers for the close factor (admin only - caller check should be done by the contract)
+///
+/// Tiers must be sorted by strictly increasing `min_debt`, and each tier's
+/// close factor must be non-zero, no larger than the previous tier and no
+/// larger than the base close factor, so larger positions always unwind more
+/// gradually. An empty vector removes all tiers.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `tiers` - Size tiers, smallest `min_debt` first
+///
+/// # Errors
+/// * `RiskParamsError::InvalidCloseFactor` - A tier close factor is out of range
+/// * `RiskParamsError::InvalidParameter` - Tiers are unsorted, too many, or have a negative size
+pub fn set_close_factor_tiers(
+    env: &Env,
+    tiers: Vec<CloseFactorTier>,
+) -> Result<(), RiskParamsError> {
+    let base_close_factor = get_close_factor(env)?;
+
+    if tiers.len() > MAX_CLOSE_FACTOR_TIERS {
+        return Err(RiskParamsError::InvalidParameter);
+    }
+
+    let mut prev: Option<CloseFactorTier> = None;
+    for tier in tiers.iter() {
+        if tier.min_debt < 0 {
+            return Err(RiskParamsError::InvalidParameter);
+        }
+        if tier.close_factor <= CLOSE_FACTOR_MIN || tier.close_factor > base_close_factor {
+            return Err(RiskParamsError::InvalidCloseFactor);
+        }
+        if let Some(p) = prev {
+            if tier.min_debt <= p.min_debt {
+                return Err(RiskParamsError::InvalidParameter);
+            }
+            if tier.close_factor > p.close_factor {
+                return Err(RiskParamsError::InvalidCloseFactor);
+            }
+        }
+        prev = Some(tier);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&RiskParamsDataKey::CloseFactorTiers, &tiers);
+
+    let topics = (Symbol::new(env, "close_factor_tiers_updated"),);
+    env.events().publish(topics, tiers);
+
+    Ok(())
+}
+
+/// Get the close factor that applies to a position of the given debt size
+///
+/// Returns the close factor of the largest tier whose `min_debt` is at or
+/// below `debt_value`, or the base close factor if no tier applies. The
+/// result never exceeds the base close factor.
+pub fn get_close_factor_for_debt(env: &Env, debt_value: i128) -> Result<i128, RiskParamsError> {
+    let mut close_factor = get_close_factor(env)?;
+
+    for tier in get_close_factor_tiers(env).iter() {
+        if debt_value < tier.min_debt {
+            break;
+        }
+        close_factor = close_factor.min(tier.close_factor);
+    }
+
+    Ok(close_factor)
+}
+
 /// Get liquidation incentive
 pub fn get_liquidation_incentive(env: &Env) -> Result<i128, RiskParamsError> {
     let config = get_risk_params(env).ok_or(RiskParamsError::InvalidParameter)?;
@@ -246,7 +376,8 @@ pub fn get_liquidation_incentive(env: &Env) -> Result<i128, RiskParamsError> {
 
 /// Calculate maximum liquidatable amount
 ///
-/// Uses close factor to determine maximum debt that can be liquidated.
+/// Uses the close factor for the position's size tier to determine the
+/// maximum debt that can be liquidated.
 ///
 /// # Arguments
 /// * `env` - The Soroban environment
@@ -258,10 +389,10 @@ pub fn get_max_liquidatable_amount(
     env: &Env,
     debt_value: i128,
 ) -> Result<i128, RiskParamsError> {
-    let config = get_risk_params(env).ok_or(RiskParamsError::InvalidParameter)?;
+    let close_factor = get_close_factor_for_debt(env, debt_value)?;
 
     // Calculate: debt * close_factor / BASIS_POINTS_SCALE
-    let max_amount = (debt_value * config.close_factor)
+    let max_amount = (debt_value * close_factor)
         .checked_div(BASIS_POINTS_SCALE)
         .ok_or(RiskParamsError::InvalidParameter)?; // Return generic error for overflow since we dropped Overflow variant
 
@@ -316,10 +447,14 @@ pub fn require_min_collateral_ratio(
 }
 
 /// Can be liquidated check
+///
+/// With a `borrower`, the liquidation hysteresis band is added to the
+/// threshold if a liquidation already found them liquidatable in this ledger.
 pub fn can_be_liquidated(
     env: &Env,
     collateral_value: i128,
     debt_value: i128,
+    borrower: Option<&Address>,
 ) -> Result<bool, RiskParamsError> {
     let config = get_risk_params(env).ok_or(RiskParamsError::InvalidParameter)?;
 
@@ -331,5 +466,8 @@ pub fn can_be_liquidated(
         .checked_div(debt_value)
         .ok_or(RiskParamsError::InvalidParameter)?;
 
-    Ok(ratio < config.liquidation_threshold)
+    let hysteresis = borrower
+        .map(|borrower| crate::risk_management::liquidation_hysteresis_for(env, borrower))
+        .unwrap_or(0);
+    Ok(ratio < config.liquidation_threshold + hysteresis)
 }
@@ -0,0 +1,674 @@
+//! # Safety Module
+//!
+//! Staking of the protocol token as a backstop for bad debt.
+//!
+//! ## Staking
+//! Stakers deposit the staking token (set once by the admin) and receive
+//! shares of the staked pool. Slashing lowers the pool without touching
+//! shares, so every staker loses in proportion to their shares.
+//!
+//! ## Cooldown
+//! Unstaking starts with [`start_cooldown`]. Once `cooldown_seconds` have
+//! passed, the staker may [`unstake`] during the following `unstake_window`
+//! seconds; after the window a new cooldown is needed. Staking more restarts
+//! a running cooldown, so fresh stake cannot skip it.
+//!
+//! ## Fee Share
+//! `fee_share_bps` of every protocol fee accrued by the `fees` module goes to
+//! stakers instead of the fee collector, tracked per fee asset as an index of
+//! fees per share. Stakers claim an asset's fees with
+//! [`claim_staking_fees`]. Fees accrued while nothing is staked all go to the
+//! collector.
+//!
+//! ## Slashing
+//! While an asset has an unresolved shortfall, the admin (governance) may
+//! slash up to `max_slash_bps` of the staked pool per call, sending the
+//! tokens to a recipient that uses them to cover the bad debt.
+//!
+//! ## Storage Layout
+//! - `StakingToken`, `Config`
+//! - `TotalStaked` / `TotalShares` — pool size and shares outstanding
+//! - `Shares(staker)`, `CooldownStart(staker)`
+//! - `FeeIndex(asset)` / `StakerFeeIndex(staker, asset)` — fees per share
+//! - `PendingFees(staker, asset)` — settled and unclaimed fees
+//! - `FeeAssets` — assets stakers have earned fees in
+
+use soroban_sdk::{contracterror, contracttype, token, Address, Env, Symbol, Vec};
+
+use crate::events::{
+    emit_admin_action, emit_safety_module_slashed, emit_stake_cooldown_started, emit_staked,
+    emit_staking_fees_claimed, emit_unstaked, AdminActionEvent, SafetyModuleSlashedEvent,
+    StakeCooldownStartedEvent, StakedEvent, StakingFeesClaimedEvent, UnstakedEvent,
+};
+
+/// Basis points scale
+const BPS_SCALE: i128 = 10_000;
+
+/// Scale of fee indices (1.0 = 1e18)
+const FEE_INDEX_SCALE: i128 = 1_000_000_000_000_000_000;
+
+/// Maximum share of protocol fees routed to stakers: 50%
+pub const MAX_STAKER_FEE_SHARE_BPS: i128 = 5_000;
+
+/// Maximum share of the pool a single slash may take: 50%
+pub const MAX_SLASH_BPS: i128 = 5_000;
+
+/// Errors that can occur in safety module operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum SafetyModuleError {
+    /// Caller is not the admin
+    Unauthorized = 1,
+    /// Amount is not positive
+    InvalidAmount = 2,
+    /// The staking token has not been set
+    StakingTokenNotSet = 3,
+    /// The staking token is already set
+    StakingTokenAlreadySet = 4,
+    /// Configuration values out of range
+    InvalidConfig = 5,
+    /// Unstaking more than the staker's stake
+    InsufficientStake = 6,
+    /// Unstaking without a started cooldown
+    CooldownNotStarted = 7,
+    /// The cooldown has not finished yet
+    CooldownActive = 8,
+    /// The unstake window after the cooldown has passed
+    UnstakeWindowExpired = 9,
+    /// The asset has no shortfall to slash for
+    NoShortfall = 10,
+    /// Slash above `max_slash_bps` of the pool
+    SlashTooLarge = 11,
+    /// No fees to claim for the asset
+    NothingToClaim = 12,
+    /// The native asset address is not configured
+    InvalidAsset = 13,
+    /// Overflow occurred during calculation
+    Overflow = 14,
+}
+
+/// Storage keys for the safety module
+#[contracttype]
+#[derive(Clone)]
+pub enum SafetyModuleDataKey {
+    /// Token that is staked
+    /// Value type: Address
+    StakingToken,
+    /// Safety module parameters
+    /// Value type: SafetyModuleConfig
+    Config,
+    /// Staking tokens in the pool
+    /// Value type: i128
+    TotalStaked,
+    /// Pool shares outstanding
+    /// Value type: i128
+    TotalShares,
+    /// Pool shares of a staker
+    /// Value type: i128
+    Shares(Address),
+    /// Timestamp a staker started their cooldown
+    /// Value type: u64
+    CooldownStart(Address),
+    /// Cumulative staker fees per share of an asset (scaled by 1e18)
+    /// Value type: i128
+    FeeIndex(Option<Address>),
+    /// Fee index of an asset at a staker's last settlement
+    /// Value type: i128
+    StakerFeeIndex(Address, Option<Address>),
+    /// Settled, unclaimed fees of a staker in an asset
+    /// Value type: i128
+    PendingFees(Address, Option<Address>),
+    /// Assets stakers have earned fees in
+    /// Value type: Vec<Option<Address>>
+    FeeAssets,
+}
+
+/// Safety module parameters
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SafetyModuleConfig {
+    /// Wait between starting a cooldown and unstaking
+    pub cooldown_seconds: u64,
+    /// Time after the cooldown during which unstaking is allowed
+    pub unstake_window: u64,
+    /// Maximum share of the pool a single slash may take (bps)
+    pub max_slash_bps: i128,
+    /// Share of protocol fees routed to stakers (bps)
+    pub fee_share_bps: i128,
+}
+
+impl Default for SafetyModuleConfig {
+    fn default() -> Self {
+        Self {
+            cooldown_seconds: 10 * 86_400,
+            unstake_window: 2 * 86_400,
+            max_slash_bps: 3_000,
+            fee_share_bps: 0,
+        }
+    }
+}
+
+/// A staker's position in the safety module
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakeInfo {
+    /// Pool shares held
+    pub shares: i128,
+    /// Staking tokens the shares are worth
+    pub amount: i128,
+    /// Timestamp the cooldown was started, if any
+    pub cooldown_start: Option<u64>,
+    /// Earliest time the staker may unstake, if a cooldown is running
+    pub unstake_from: Option<u64>,
+    /// Latest time the staker may unstake, if a cooldown is running
+    pub unstake_until: Option<u64>,
+}
+
+/// Safety module pool totals
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SafetyModuleState {
+    /// Staking tokens in the pool
+    pub total_staked: i128,
+    /// Pool shares outstanding
+    pub total_shares: i128,
+    /// Most a single slash can take right now
+    pub max_slashable: i128,
+}
+
+/// Get the staking token
+pub fn get_staking_token(env: &Env) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get::<SafetyModuleDataKey, Address>(&SafetyModuleDataKey::StakingToken)
+}
+
+/// Set the staking token (admin only, once)
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not the admin
+/// * `StakingTokenAlreadySet` - A staking token is already set
+pub fn set_staking_token(
+    env: &Env,
+    caller: Address,
+    token: Address,
+) -> Result<(), SafetyModuleError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| SafetyModuleError::Unauthorized)?;
+    if get_staking_token(env).is_some() {
+        return Err(SafetyModuleError::StakingTokenAlreadySet);
+    }
+    env.storage()
+        .persistent()
+        .set(&SafetyModuleDataKey::StakingToken, &token);
+
+    emit_admin_action(
+        env,
+        AdminActionEvent {
+            actor: caller,
+            action: Symbol::new(env, "set_staking_token"),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Get the safety module parameters
+pub fn get_config(env: &Env) -> SafetyModuleConfig {
+    env.storage()
+        .persistent()
+        .get::<SafetyModuleDataKey, SafetyModuleConfig>(&SafetyModuleDataKey::Config)
+        .unwrap_or_default()
+}
+
+/// Set the safety module parameters (admin only)
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not the admin
+/// * `InvalidConfig` - Zero cooldown or window, or a share above its maximum
+pub fn set_config(
+    env: &Env,
+    caller: Address,
+    config: SafetyModuleConfig,
+) -> Result<(), SafetyModuleError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| SafetyModuleError::Unauthorized)?;
+    if config.cooldown_seconds == 0
+        || config.unstake_window == 0
+        || !(0..=MAX_SLASH_BPS).contains(&config.max_slash_bps)
+        || !(0..=MAX_STAKER_FEE_SHARE_BPS).contains(&config.fee_share_bps)
+    {
+        return Err(SafetyModuleError::InvalidConfig);
+    }
+    env.storage()
+        .persistent()
+        .set(&SafetyModuleDataKey::Config, &config);
+
+    emit_admin_action(
+        env,
+        AdminActionEvent {
+            actor: caller,
+            action: Symbol::new(env, "set_safety_module_config"),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Get the pool totals
+pub fn get_state(env: &Env) -> SafetyModuleState {
+    let total_staked = get_total_staked(env);
+    SafetyModuleState {
+        total_staked,
+        total_shares: get_total_shares(env),
+        max_slashable: total_staked.saturating_mul(get_config(env).max_slash_bps) / BPS_SCALE,
+    }
+}
+
+/// Get a staker's position
+pub fn get_stake(env: &Env, staker: &Address) -> StakeInfo {
+    let shares = get_shares(env, staker);
+    let cooldown_start = get_cooldown_start(env, staker);
+    let config = get_config(env);
+    let unstake_from = cooldown_start.map(|start| start.saturating_add(config.cooldown_seconds));
+    StakeInfo {
+        shares,
+        amount: shares_to_amount(env, shares),
+        cooldown_start,
+        unstake_from,
+        unstake_until: unstake_from.map(|from| from.saturating_add(config.unstake_window)),
+    }
+}
+
+/// Get a staker's claimable fees in an asset
+pub fn get_pending_fees(env: &Env, staker: &Address, asset: &Option<Address>) -> i128 {
+    get_stored_pending(env, staker, asset).saturating_add(unsettled_fees(env, staker, asset))
+}
+
+/// Stake tokens into the safety module
+///
+/// Restarts a running cooldown.
+///
+/// # Returns
+/// The shares minted
+///
+/// # Errors
+/// * `InvalidAmount` - Amount is not positive
+/// * `StakingTokenNotSet` - No staking token is set
+pub fn stake(env: &Env, staker: Address, amount: i128) -> Result<i128, SafetyModuleError> {
+    staker.require_auth();
+    if amount <= 0 {
+        return Err(SafetyModuleError::InvalidAmount);
+    }
+    let staking_token = get_staking_token(env).ok_or(SafetyModuleError::StakingTokenNotSet)?;
+
+    let total_staked = get_total_staked(env);
+    let total_shares = get_total_shares(env);
+    let shares = if total_shares == 0 || total_staked == 0 {
+        amount
+    } else {
+        amount
+            .checked_mul(total_shares)
+            .ok_or(SafetyModuleError::Overflow)?
+            / total_staked
+    };
+    if shares <= 0 {
+        return Err(SafetyModuleError::InvalidAmount);
+    }
+
+    token::Client::new(env, &staking_token).transfer(
+        &staker,
+        &env.current_contract_address(),
+        &amount,
+    );
+    settle_all(env, &staker);
+    set_shares(env, &staker, get_shares(env, &staker) + shares);
+    let storage = env.storage().persistent();
+    storage.set(
+        &SafetyModuleDataKey::TotalStaked,
+        &total_staked
+            .checked_add(amount)
+            .ok_or(SafetyModuleError::Overflow)?,
+    );
+    storage.set(&SafetyModuleDataKey::TotalShares, &(total_shares + shares));
+    if storage.has(&SafetyModuleDataKey::CooldownStart(staker.clone())) {
+        storage.set(
+            &SafetyModuleDataKey::CooldownStart(staker.clone()),
+            &env.ledger().timestamp(),
+        );
+    }
+
+    emit_staked(
+        env,
+        StakedEvent {
+            staker,
+            amount,
+            shares,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(shares)
+}
+
+/// Start the unstaking cooldown
+///
+/// # Errors
+/// * `InsufficientStake` - The staker has nothing staked
+pub fn start_cooldown(env: &Env, staker: Address) -> Result<(), SafetyModuleError> {
+    staker.require_auth();
+    if get_shares(env, &staker) == 0 {
+        return Err(SafetyModuleError::InsufficientStake);
+    }
+    let now = env.ledger().timestamp();
+    env.storage()
+        .persistent()
+        .set(&SafetyModuleDataKey::CooldownStart(staker.clone()), &now);
+
+    emit_stake_cooldown_started(
+        env,
+        StakeCooldownStartedEvent {
+            staker,
+            unstake_from: now.saturating_add(get_config(env).cooldown_seconds),
+            timestamp: now,
+        },
+    );
+    Ok(())
+}
+
+/// Withdraw staked tokens after the cooldown
+///
+/// Ends the cooldown; unstaking again needs a new one.
+///
+/// # Returns
+/// The shares burned
+///
+/// # Errors
+/// * `InvalidAmount` - Amount is not positive
+/// * `CooldownNotStarted` - No cooldown was started
+/// * `CooldownActive` - The cooldown has not finished
+/// * `UnstakeWindowExpired` - The window after the cooldown has passed
+/// * `InsufficientStake` - Amount is more than the stake is worth
+pub fn unstake(env: &Env, staker: Address, amount: i128) -> Result<i128, SafetyModuleError> {
+    staker.require_auth();
+    if amount <= 0 {
+        return Err(SafetyModuleError::InvalidAmount);
+    }
+    let staking_token = get_staking_token(env).ok_or(SafetyModuleError::StakingTokenNotSet)?;
+
+    let cooldown_start =
+        get_cooldown_start(env, &staker).ok_or(SafetyModuleError::CooldownNotStarted)?;
+    let config = get_config(env);
+    let now = env.ledger().timestamp();
+    let unstake_from = cooldown_start.saturating_add(config.cooldown_seconds);
+    if now < unstake_from {
+        return Err(SafetyModuleError::CooldownActive);
+    }
+    if now > unstake_from.saturating_add(config.unstake_window) {
+        return Err(SafetyModuleError::UnstakeWindowExpired);
+    }
+
+    // Burn shares rounded up so the pool never pays out more than it holds
+    let total_staked = get_total_staked(env);
+    let total_shares = get_total_shares(env);
+    let staker_shares = get_shares(env, &staker);
+    if total_staked == 0 || amount > shares_to_amount(env, staker_shares) {
+        return Err(SafetyModuleError::InsufficientStake);
+    }
+    let numerator = amount
+        .checked_mul(total_shares)
+        .ok_or(SafetyModuleError::Overflow)?;
+    let shares = ((numerator + total_staked - 1) / total_staked).min(staker_shares);
+
+    settle_all(env, &staker);
+    set_shares(env, &staker, staker_shares - shares);
+    let storage = env.storage().persistent();
+    storage.set(&SafetyModuleDataKey::TotalStaked, &(total_staked - amount));
+    storage.set(&SafetyModuleDataKey::TotalShares, &(total_shares - shares));
+    storage.remove(&SafetyModuleDataKey::CooldownStart(staker.clone()));
+
+    token::Client::new(env, &staking_token).transfer(
+        &env.current_contract_address(),
+        &staker,
+        &amount,
+    );
+
+    emit_unstaked(
+        env,
+        UnstakedEvent {
+            staker,
+            amount,
+            shares,
+            timestamp: now,
+        },
+    );
+    Ok(shares)
+}
+
+/// Slash the staked pool to cover an asset's shortfall (admin/governance only)
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not the admin
+/// * `InvalidAmount` - Amount is not positive
+/// * `NoShortfall` - The asset has no unresolved bad debt
+/// * `SlashTooLarge` - Amount exceeds `max_slash_bps` of the pool
+pub fn slash(
+    env: &Env,
+    caller: Address,
+    asset: Option<Address>,
+    amount: i128,
+    recipient: Address,
+) -> Result<(), SafetyModuleError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| SafetyModuleError::Unauthorized)?;
+    if amount <= 0 {
+        return Err(SafetyModuleError::InvalidAmount);
+    }
+    let shortfall = crate::bad_debt::get_bad_debt(env, asset.clone());
+    if shortfall <= 0 {
+        return Err(SafetyModuleError::NoShortfall);
+    }
+    if amount > get_state(env).max_slashable {
+        return Err(SafetyModuleError::SlashTooLarge);
+    }
+    let staking_token = get_staking_token(env).ok_or(SafetyModuleError::StakingTokenNotSet)?;
+
+    let total_staked = get_total_staked(env) - amount;
+    env.storage()
+        .persistent()
+        .set(&SafetyModuleDataKey::TotalStaked, &total_staked);
+    token::Client::new(env, &staking_token).transfer(
+        &env.current_contract_address(),
+        &recipient,
+        &amount,
+    );
+
+    emit_safety_module_slashed(
+        env,
+        SafetyModuleSlashedEvent {
+            caller,
+            asset,
+            shortfall,
+            amount,
+            recipient,
+            total_staked,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Claim a staker's fees in an asset
+///
+/// # Returns
+/// The amount claimed
+///
+/// # Errors
+/// * `NothingToClaim` - No fees for the asset
+/// * `InvalidAsset` - The native asset address is not configured
+pub fn claim_staking_fees(
+    env: &Env,
+    staker: Address,
+    asset: Option<Address>,
+) -> Result<i128, SafetyModuleError> {
+    staker.require_auth();
+    settle(env, &staker, &asset);
+    let amount = get_stored_pending(env, &staker, &asset);
+    if amount <= 0 {
+        return Err(SafetyModuleError::NothingToClaim);
+    }
+    let token_address =
+        crate::deposit::token_address(env, &asset).ok_or(SafetyModuleError::InvalidAsset)?;
+    env.storage()
+        .persistent()
+        .remove(&SafetyModuleDataKey::PendingFees(
+            staker.clone(),
+            asset.clone(),
+        ));
+    token::Client::new(env, &token_address).transfer(
+        &env.current_contract_address(),
+        &staker,
+        &amount,
+    );
+
+    emit_staking_fees_claimed(
+        env,
+        StakingFeesClaimedEvent {
+            staker,
+            asset,
+            amount,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(amount)
+}
+
+/// Take the stakers' share of a newly accrued protocol fee
+///
+/// Called by the fees module before crediting the collector.
+///
+/// # Returns
+/// The part of `fee` distributed to stakers
+pub(crate) fn distribute_fees(env: &Env, asset: &Option<Address>, fee: i128) -> i128 {
+    let total_shares = get_total_shares(env);
+    if fee <= 0 || total_shares <= 0 {
+        return 0;
+    }
+    let share = fee.saturating_mul(get_config(env).fee_share_bps) / BPS_SCALE;
+    if share <= 0 {
+        return 0;
+    }
+
+    let index = get_fee_index(env, asset)
+        .saturating_add(share.saturating_mul(FEE_INDEX_SCALE) / total_shares);
+    let storage = env.storage().persistent();
+    storage.set(&SafetyModuleDataKey::FeeIndex(asset.clone()), &index);
+    let mut assets = get_fee_assets(env);
+    if !assets.contains(asset) {
+        assets.push_back(asset.clone());
+        storage.set(&SafetyModuleDataKey::FeeAssets, &assets);
+    }
+    share
+}
+
+/// Settle a staker's fees in every asset before their shares change
+fn settle_all(env: &Env, staker: &Address) {
+    for asset in get_fee_assets(env).iter() {
+        settle(env, staker, &asset);
+    }
+}
+
+/// Move a staker's fees earned since their last settlement into
+/// `PendingFees`
+fn settle(env: &Env, staker: &Address, asset: &Option<Address>) {
+    let earned = unsettled_fees(env, staker, asset);
+    let storage = env.storage().persistent();
+    if earned > 0 {
+        storage.set(
+            &SafetyModuleDataKey::PendingFees(staker.clone(), asset.clone()),
+            &get_stored_pending(env, staker, asset).saturating_add(earned),
+        );
+    }
+    storage.set(
+        &SafetyModuleDataKey::StakerFeeIndex(staker.clone(), asset.clone()),
+        &get_fee_index(env, asset),
+    );
+}
+
+fn unsettled_fees(env: &Env, staker: &Address, asset: &Option<Address>) -> i128 {
+    let staker_index = env
+        .storage()
+        .persistent()
+        .get::<SafetyModuleDataKey, i128>(&SafetyModuleDataKey::StakerFeeIndex(
+            staker.clone(),
+            asset.clone(),
+        ))
+        .unwrap_or(0);
+    get_shares(env, staker).saturating_mul(get_fee_index(env, asset) - staker_index)
+        / FEE_INDEX_SCALE
+}
+
+fn shares_to_amount(env: &Env, shares: i128) -> i128 {
+    let total_shares = get_total_shares(env);
+    if total_shares == 0 {
+        return 0;
+    }
+    shares.saturating_mul(get_total_staked(env)) / total_shares
+}
+
+fn get_total_staked(env: &Env) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<SafetyModuleDataKey, i128>(&SafetyModuleDataKey::TotalStaked)
+        .unwrap_or(0)
+}
+
+fn get_total_shares(env: &Env) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<SafetyModuleDataKey, i128>(&SafetyModuleDataKey::TotalShares)
+        .unwrap_or(0)
+}
+
+fn get_shares(env: &Env, staker: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<SafetyModuleDataKey, i128>(&SafetyModuleDataKey::Shares(staker.clone()))
+        .unwrap_or(0)
+}
+
+fn set_shares(env: &Env, staker: &Address, shares: i128) {
+    let key = SafetyModuleDataKey::Shares(staker.clone());
+    if shares > 0 {
+        env.storage().persistent().set(&key, &shares);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+fn get_cooldown_start(env: &Env, staker: &Address) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get::<SafetyModuleDataKey, u64>(&SafetyModuleDataKey::CooldownStart(staker.clone()))
+}
+
+fn get_fee_index(env: &Env, asset: &Option<Address>) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<SafetyModuleDataKey, i128>(&SafetyModuleDataKey::FeeIndex(asset.clone()))
+        .unwrap_or(0)
+}
+
+fn get_fee_assets(env: &Env) -> Vec<Option<Address>> {
+    env.storage()
+        .persistent()
+        .get::<SafetyModuleDataKey, Vec<Option<Address>>>(&SafetyModuleDataKey::FeeAssets)
+        .unwrap_or(Vec::new(env))
+}
+
+fn get_stored_pending(env: &Env, staker: &Address, asset: &Option<Address>) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<SafetyModuleDataKey, i128>(&SafetyModuleDataKey::PendingFees(
+            staker.clone(),
+            asset.clone(),
+        ))
+        .unwrap_or(0)
+}
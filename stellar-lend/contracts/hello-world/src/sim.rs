@@ -0,0 +1,130 @@
+//! # Simulation Mode
+//!
+//! Deterministic time and price control for long-horizon integration tests.
+//! Compiled only under `cfg(any(test, feature = "sim"))`; release builds do
+//! not export these entrypoints. Build the wasm with `--features sim` to use
+//! them against a local network.
+//!
+//! ## Time
+//! A contract cannot move the ledger clock, so `sim_advance_time` ages the
+//! protocol's time-stamped state instead: the global borrow index and the
+//! price feeds of the listed assets are shifted back by the given number of
+//! seconds. The next call then observes that much elapsed time, so borrow
+//! interest compounds and prices go stale exactly as if the ledger had moved.
+//!
+//! ## Prices
+//! `sim_set_price` writes an asset's primary price feed directly, bypassing
+//! pause switches, sanity bounds and the deviation limit, so crash scenarios
+//! can be set up in a single step.
+
+use soroban_sdk::{contracterror, contractimpl, Address, Env, Vec};
+
+use crate::interest_rate::{accrue_borrow_index, BorrowIndex, InterestRateDataKey};
+use crate::oracle::{CachedPrice, OracleDataKey, PriceFeed};
+use crate::HelloContract;
+
+/// Errors that can occur in simulation entrypoints
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum SimError {
+    /// Caller is not the admin
+    Unauthorized = 1,
+    /// Price must be positive
+    InvalidPrice = 2,
+    /// Borrow index could not be advanced
+    Overflow = 3,
+}
+
+#[contractimpl]
+impl HelloContract {
+    /// Simulate `seconds` of elapsed time (admin only, simulation builds)
+    ///
+    /// Settles the borrow index to now, then backdates it and the price
+    /// feeds and cached prices of `assets` by `seconds`. Timestamps cannot go
+    /// below zero, so at most the current ledger time can be simulated.
+    ///
+    /// # Returns
+    /// The borrow index as of the simulated time
+    pub fn sim_advance_time(
+        env: Env,
+        caller: Address,
+        seconds: u64,
+        assets: Vec<Address>,
+    ) -> Result<i128, SimError> {
+        crate::admin::require_admin(&env, &caller).map_err(|_| SimError::Unauthorized)?;
+
+        accrue_borrow_index(&env).map_err(|_| SimError::Overflow)?;
+        let index_key = InterestRateDataKey::BorrowIndex;
+        if let Some(mut state) = env
+            .storage()
+            .persistent()
+            .get::<InterestRateDataKey, BorrowIndex>(&index_key)
+        {
+            state.last_update = state.last_update.saturating_sub(seconds);
+            env.storage().persistent().set(&index_key, &state);
+        }
+
+        for asset in assets.iter() {
+            for key in [
+                OracleDataKey::PriceFeed(asset.clone()),
+                OracleDataKey::FallbackFeed(asset.clone()),
+            ] {
+                if let Some(mut feed) = env
+                    .storage()
+                    .persistent()
+                    .get::<OracleDataKey, PriceFeed>(&key)
+                {
+                    feed.last_updated = feed.last_updated.saturating_sub(seconds);
+                    env.storage().persistent().set(&key, &feed);
+                }
+            }
+
+            let cache_key = OracleDataKey::PriceCache(asset.clone());
+            if let Some(mut cached) = env
+                .storage()
+                .persistent()
+                .get::<OracleDataKey, CachedPrice>(&cache_key)
+            {
+                cached.cached_at = cached.cached_at.saturating_sub(seconds);
+                env.storage().persistent().set(&cache_key, &cached);
+            }
+        }
+
+        crate::interest_rate::get_borrow_index(&env).map_err(|_| SimError::Overflow)
+    }
+
+    /// Set an asset's primary price feed without validation (admin only,
+    /// simulation builds)
+    ///
+    /// The price is timestamped now, recorded in the TWAP history, and any
+    /// cached price for the asset is dropped.
+    pub fn sim_set_price(
+        env: Env,
+        caller: Address,
+        asset: Address,
+        price: i128,
+        decimals: u32,
+    ) -> Result<(), SimError> {
+        crate::admin::require_admin(&env, &caller).map_err(|_| SimError::Unauthorized)?;
+        if price <= 0 {
+            return Err(SimError::InvalidPrice);
+        }
+
+        let timestamp = env.ledger().timestamp();
+        env.storage().persistent().set(
+            &OracleDataKey::PriceFeed(asset.clone()),
+            &PriceFeed {
+                price,
+                last_updated: timestamp,
+                oracle: caller,
+                decimals,
+            },
+        );
+        crate::oracle::record_observation(&env, &asset, price, timestamp);
+        env.storage()
+            .persistent()
+            .remove(&OracleDataKey::PriceCache(asset));
+        Ok(())
+    }
+}
@@ -0,0 +1,325 @@
+//! # Snapshot Module
+//!
+//! Read-only export views that let auditors and the indexer capture the full
+//! contract state deterministically, in bounded pages, and replay events
+//! against it to verify accounting.
+//!
+//! Soroban storage cannot be enumerated, so this module keeps an append-only
+//! index of every address that has ever held a position. Every path that
+//! writes a position indexes its user: legacy deposits (direct, `*_for` and
+//! isolated position accounts), borrows and repayments, cross-asset position
+//! updates, and newly opened isolated position accounts. The export views
+//! then walk that index (and the asset lists) in insertion order.
+//!
+//! ## Export Sections
+//! - `export_protocol_snapshot` — global configuration, aggregate counters,
+//!   the supply share pool and the governance configuration
+//! - `export_user_positions(offset, limit)` — per-user legacy position,
+//!   collateral balance, supply shares and non-empty cross-asset positions
+//! - `export_asset_configs(offset, limit)` — cross-asset market configurations
+//! - `export_asset_ledgers(offset, limit)` — per-asset supplied and borrowed
+//!   totals, protocol reserves and accrued protocol fees
+//!
+//! ## Not Covered
+//! Governance proposals and votes, reward and rate-mode accounting, stable
+//! rate debt splits and module-specific state (flash loans, bridges, vesting,
+//! the safety module) are not part of the snapshot; read them through their
+//! own views, e.g. `gov_get_proposals`.
+//!
+//! ## Storage Layout
+//! - `UserCount` — number of indexed users
+//! - `User(index)` — address at a given index
+//! - `Indexed(user)` — marker preventing duplicate index entries
+//!
+//! ## Invariants
+//! - Index entries are never removed or reordered, so a page at a given
+//!   offset is stable across calls.
+//! - Page size is capped at `MAX_EXPORT_PAGE_SIZE` to bound read costs.
+
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+use crate::cross_asset::{
+    get_asset_config_by_address, get_asset_list, get_user_asset_position, AssetConfig, AssetKey,
+    AssetPosition,
+};
+use crate::deposit::{
+    get_asset_ledger, get_ledger_assets, get_supply_pool, get_supply_shares, AssetLedger,
+    DepositDataKey, Position, ProtocolAnalytics, SupplyPool,
+};
+use crate::interest_rate::{get_interest_rate_config, InterestRateConfig};
+use crate::risk_management::is_emergency_paused;
+use crate::risk_params::{get_risk_params, RiskParams};
+use crate::types::GovernanceConfig;
+
+/// Maximum number of entries returned by a single export page
+pub const MAX_EXPORT_PAGE_SIZE: u32 = 50;
+
+/// Storage keys for the snapshot user index
+#[contracttype]
+#[derive(Clone)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub enum SnapshotDataKey {
+    /// Number of indexed users
+    /// Value type: u32
+    UserCount,
+    /// Indexed user address by insertion order
+    /// Value type: Address
+    User(u32),
+    /// Marker set once a user has been indexed
+    /// Value type: bool
+    Indexed(Address),
+}
+
+/// Global protocol state captured at a single ledger
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProtocolSnapshot {
+    /// Ledger sequence at which the snapshot was taken
+    pub ledger_sequence: u32,
+    /// Ledger timestamp at which the snapshot was taken
+    pub timestamp: u64,
+    /// Super admin address, if set
+    pub admin: Option<Address>,
+    /// Whether the global emergency pause is active
+    pub emergency_paused: bool,
+    /// Current risk parameters, if initialized
+    pub risk_params: Option<RiskParams>,
+    /// Current interest rate model, if initialized
+    pub interest_rate_config: Option<InterestRateConfig>,
+    /// Aggregate protocol analytics
+    pub protocol_analytics: ProtocolAnalytics,
+    /// Aggregate supply share accounting
+    pub supply_pool: SupplyPool,
+    /// Governance configuration, if initialized
+    pub governance_config: Option<GovernanceConfig>,
+    /// Number of users in the snapshot index
+    pub user_count: u32,
+    /// Number of registered cross-asset markets
+    pub asset_count: u32,
+    /// Number of assets with a ledger
+    pub ledger_asset_count: u32,
+}
+
+/// A single user's positions as exported
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct UserSnapshotEntry {
+    /// Position of the user in the snapshot index
+    pub index: u32,
+    /// User address
+    pub user: Address,
+    /// Raw collateral balance
+    pub collateral_balance: i128,
+    /// Stored legacy position, if any
+    pub position: Option<Position>,
+    /// Interest-bearing supply shares
+    pub supply_shares: i128,
+    /// Non-empty cross-asset positions, in asset registration order
+    pub asset_positions: Vec<UserAssetSnapshotEntry>,
+}
+
+/// A user's position in a single cross-asset market as exported
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct UserAssetSnapshotEntry {
+    /// Asset key
+    pub asset: AssetKey,
+    /// Stored position
+    pub position: AssetPosition,
+}
+
+/// A single cross-asset market configuration as exported
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct AssetSnapshotEntry {
+    /// Position of the asset in the asset list
+    pub index: u32,
+    /// Asset key
+    pub asset: AssetKey,
+    /// Market configuration
+    pub config: AssetConfig,
+}
+
+/// A single asset ledger as exported
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct AssetLedgerSnapshotEntry {
+    /// Position of the asset in the ledger asset list
+    pub index: u32,
+    /// Supplied and borrowed totals and protocol reserves
+    pub ledger: AssetLedger,
+    /// Protocol fees accrued and not yet claimed
+    pub accrued_fees: i128,
+}
+
+/// Record a user in the snapshot index if not already present
+///
+/// Called from state-changing entrypoints the first time an address
+/// creates a position.
+pub fn index_user(env: &Env, user: &Address) {
+    let marker = SnapshotDataKey::Indexed(user.clone());
+    if env.storage().persistent().has(&marker) {
+        return;
+    }
+
+    let count = get_user_count(env);
+    env.storage()
+        .persistent()
+        .set(&SnapshotDataKey::User(count), user);
+    env.storage()
+        .persistent()
+        .set(&SnapshotDataKey::UserCount, &(count + 1));
+    env.storage().persistent().set(&marker, &true);
+}
+
+/// Number of users in the snapshot index
+pub fn get_user_count(env: &Env) -> u32 {
+    env.storage()
+        .persistent()
+        .get::<SnapshotDataKey, u32>(&SnapshotDataKey::UserCount)
+        .unwrap_or(0)
+}
+
+/// Export global protocol state
+pub fn export_protocol_snapshot(env: &Env) -> ProtocolSnapshot {
+    let protocol_analytics = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, ProtocolAnalytics>(&DepositDataKey::ProtocolAnalytics)
+        .unwrap_or(ProtocolAnalytics {
+            total_deposits: 0,
+            total_borrows: 0,
+            total_value_locked: 0,
+        });
+
+    ProtocolSnapshot {
+        ledger_sequence: env.ledger().sequence(),
+        timestamp: env.ledger().timestamp(),
+        admin: crate::admin::get_admin(env),
+        emergency_paused: is_emergency_paused(env),
+        risk_params: get_risk_params(env),
+        interest_rate_config: get_interest_rate_config(env),
+        protocol_analytics,
+        supply_pool: get_supply_pool(env),
+        governance_config: crate::governance::get_config(env),
+        user_count: get_user_count(env),
+        asset_count: get_asset_list(env).len(),
+        ledger_asset_count: get_ledger_assets(env).len(),
+    }
+}
+
+/// Export a page of user positions in index order
+///
+/// Each entry carries the user's legacy position, supply shares and every
+/// non-empty cross-asset position.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `offset` - Index of the first user to return
+/// * `limit` - Maximum number of entries (capped at `MAX_EXPORT_PAGE_SIZE`)
+pub fn export_user_positions(env: &Env, offset: u32, limit: u32) -> Vec<UserSnapshotEntry> {
+    let mut page = Vec::new(env);
+    let assets = get_asset_list(env);
+    let count = get_user_count(env);
+    let end = offset
+        .saturating_add(limit.min(MAX_EXPORT_PAGE_SIZE))
+        .min(count);
+
+    let mut index = offset;
+    while index < end {
+        if let Some(user) = env
+            .storage()
+            .persistent()
+            .get::<SnapshotDataKey, Address>(&SnapshotDataKey::User(index))
+        {
+            let collateral_balance = env
+                .storage()
+                .persistent()
+                .get::<DepositDataKey, i128>(&DepositDataKey::CollateralBalance(user.clone()))
+                .unwrap_or(0);
+            let position = env
+                .storage()
+                .persistent()
+                .get::<DepositDataKey, Position>(&DepositDataKey::Position(user.clone()));
+
+            let mut asset_positions = Vec::new(env);
+            for asset in assets.iter() {
+                let position = get_user_asset_position(env, &user, asset.to_option());
+                if position.collateral != 0
+                    || position.debt_principal != 0
+                    || position.accrued_interest != 0
+                {
+                    asset_positions.push_back(UserAssetSnapshotEntry { asset, position });
+                }
+            }
+
+            page.push_back(UserSnapshotEntry {
+                index,
+                supply_shares: get_supply_shares(env, &user),
+                user,
+                collateral_balance,
+                position,
+                asset_positions,
+            });
+        }
+        index += 1;
+    }
+
+    page
+}
+
+/// Export a page of cross-asset market configurations in registration order
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `offset` - Index of the first asset to return
+/// * `limit` - Maximum number of entries (capped at `MAX_EXPORT_PAGE_SIZE`)
+pub fn export_asset_configs(env: &Env, offset: u32, limit: u32) -> Vec<AssetSnapshotEntry> {
+    let mut page = Vec::new(env);
+    let assets = get_asset_list(env);
+    let end = offset
+        .saturating_add(limit.min(MAX_EXPORT_PAGE_SIZE))
+        .min(assets.len());
+
+    let mut index = offset;
+    while index < end {
+        let asset = assets.get(index).unwrap();
+        if let Ok(config) = get_asset_config_by_address(env, asset.to_option()) {
+            page.push_back(AssetSnapshotEntry {
+                index,
+                asset,
+                config,
+            });
+        }
+        index += 1;
+    }
+
+    page
+}
+
+/// Export a page of asset ledgers in first-seen order
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `offset` - Index of the first asset to return
+/// * `limit` - Maximum number of entries (capped at `MAX_EXPORT_PAGE_SIZE`)
+pub fn export_asset_ledgers(env: &Env, offset: u32, limit: u32) -> Vec<AssetLedgerSnapshotEntry> {
+    let mut page = Vec::new(env);
+    let assets = get_ledger_assets(env);
+    let end = offset
+        .saturating_add(limit.min(MAX_EXPORT_PAGE_SIZE))
+        .min(assets.len());
+
+    let mut index = offset;
+    while index < end {
+        let asset = assets.get(index).unwrap();
+        page.push_back(AssetLedgerSnapshotEntry {
+            index,
+            accrued_fees: crate::fees::get_accrued_fees(env, &asset),
+            ledger: get_asset_ledger(env, &asset),
+        });
+        index += 1;
+    }
+
+    page
+}
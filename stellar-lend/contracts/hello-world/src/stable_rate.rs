@@ -0,0 +1,391 @@
+//! # Stable Rate Borrowing
+//!
+//! Borrowers choose a rate mode for every borrow. Variable debt follows the
+//! market borrow rate as utilization moves; stable debt locks the stable
+//! rate at borrow time, which is the market rate plus a configured premium.
+//!
+//! ## Accounting
+//! A position's `debt` stays the total principal, so collateral, health and
+//! liquidation checks treat both modes alike. The stable share and its
+//! locked rate are tracked here; the rest of the debt is variable.
+//!
+//! - Interest on the variable share accrues through the borrow index and
+//!   honors user class rate caps. The stable share accrues at its locked
+//!   rate over elapsed time.
+//! - Repayments and liquidations reduce variable debt first: the stable
+//!   share is capped at the position's principal whenever it is read, and
+//!   brought down to it at the next borrow.
+//! - A further stable borrow blends into the locked rate, weighted by
+//!   principal.
+//!
+//! ## Rebalancing
+//! When the current stable rate and a borrower's locked rate differ by more
+//! than the rebalance threshold, anyone can call `rebalance_stable_rate` to
+//! reset the lock to the current stable rate. Interest up to the rebalance
+//! is still charged at the old rate: the difference is carried as an
+//! adjustment settled by the next accrual.
+
+use soroban_sdk::{contracterror, contracttype, Address, Env, Symbol};
+
+use crate::constants::BPS_SCALE;
+use crate::deposit::{DepositDataKey, Position};
+use crate::events::{
+    emit_admin_action, emit_stable_borrow, emit_stable_rate_rebalanced, AdminActionEvent,
+    StableBorrowEvent, StableRateRebalancedEvent,
+};
+use crate::interest_rate::{calculate_accrued_interest_for_year, InterestRateError};
+
+/// Default premium of the stable rate over the variable rate (2%)
+const DEFAULT_PREMIUM_BPS: i128 = 200;
+
+/// Default divergence that allows a rebalance (5%)
+const DEFAULT_REBALANCE_THRESHOLD_BPS: i128 = 500;
+
+/// Errors that can occur in stable rate operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum StableRateError {
+    /// Caller is not the admin
+    Unauthorized = 1,
+    /// Premium or threshold is out of range
+    InvalidConfig = 2,
+    /// Stable-rate borrowing is not enabled
+    StableRateDisabled = 3,
+    /// The user has no stable debt
+    NoStableDebt = 4,
+    /// The locked rate is within the rebalance threshold of the current one
+    WithinThreshold = 5,
+    /// The variable rate is not configured
+    RateUnavailable = 6,
+    /// Overflow occurred during calculation
+    Overflow = 7,
+}
+
+/// Rate mode chosen for a borrow
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RateMode {
+    /// Follows the market borrow rate
+    Variable,
+    /// Locks the current stable rate
+    Stable,
+}
+
+/// Stable rate settings
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StableRateConfig {
+    /// Whether new stable borrows are accepted
+    pub enabled: bool,
+    /// Stable rate premium over the variable rate, in basis points
+    pub premium_bps: i128,
+    /// Divergence between the locked and current stable rate that allows a
+    /// rebalance, in basis points
+    pub rebalance_threshold_bps: i128,
+}
+
+/// A borrower's stable debt
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StableBorrow {
+    /// Stable share of the position's principal
+    pub principal: i128,
+    /// Locked annual rate in basis points
+    pub rate_bps: i128,
+    /// When the rate was last locked
+    pub locked_at: u64,
+    /// Interest owed before the last rebalance beyond what the locked rate
+    /// charges for the same time (negative if less), settled at the next accrual
+    pub rebalance_adjustment: i128,
+}
+
+/// A position's principal split by rate mode
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DebtByMode {
+    pub variable_principal: i128,
+    pub stable_principal: i128,
+    /// Locked rate of the stable share (0 without stable debt)
+    pub stable_rate_bps: i128,
+}
+
+/// Storage keys for stable rate data
+#[contracttype]
+#[derive(Clone)]
+pub enum StableRateDataKey {
+    /// Stable rate settings (absent = disabled, default premium and threshold)
+    /// Value type: StableRateConfig
+    Config,
+    /// A borrower's stable debt
+    /// Value type: StableBorrow
+    Borrow(Address),
+}
+
+/// Get the stable rate settings
+pub fn get_stable_rate_config(env: &Env) -> StableRateConfig {
+    env.storage()
+        .persistent()
+        .get(&StableRateDataKey::Config)
+        .unwrap_or(StableRateConfig {
+            enabled: false,
+            premium_bps: DEFAULT_PREMIUM_BPS,
+            rebalance_threshold_bps: DEFAULT_REBALANCE_THRESHOLD_BPS,
+        })
+}
+
+/// Set the stable rate settings (admin only)
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not admin
+/// * `InvalidConfig` - Premium or threshold is negative or above 100%
+pub fn set_stable_rate_config(
+    env: &Env,
+    caller: Address,
+    config: StableRateConfig,
+) -> Result<(), StableRateError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| StableRateError::Unauthorized)?;
+    if !(0..=BPS_SCALE).contains(&config.premium_bps)
+        || !(0..=BPS_SCALE).contains(&config.rebalance_threshold_bps)
+    {
+        return Err(StableRateError::InvalidConfig);
+    }
+    env.storage()
+        .persistent()
+        .set(&StableRateDataKey::Config, &config);
+    emit_admin_action(
+        env,
+        AdminActionEvent {
+            actor: caller,
+            action: Symbol::new(env, "set_stable_rate_config"),
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(())
+}
+
+/// Current stable rate: the variable borrow rate plus the premium
+pub fn get_stable_borrow_rate(env: &Env) -> Result<i128, StableRateError> {
+    let variable = crate::interest_rate::calculate_borrow_rate(env)
+        .map_err(|_| StableRateError::RateUnavailable)?;
+    variable
+        .checked_add(get_stable_rate_config(env).premium_bps)
+        .ok_or(StableRateError::Overflow)
+}
+
+/// Position principal, 0 without a position
+fn position_principal(env: &Env, user: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get::<DepositDataKey, Position>(&DepositDataKey::Position(user.clone()))
+        .map(|p| p.debt)
+        .unwrap_or(0)
+}
+
+/// Stored stable debt with its principal capped at `principal`
+fn capped_stable_borrow(env: &Env, user: &Address, principal: i128) -> Option<StableBorrow> {
+    let mut stable: StableBorrow = env
+        .storage()
+        .persistent()
+        .get(&StableRateDataKey::Borrow(user.clone()))?;
+    stable.principal = stable.principal.min(principal.max(0));
+    Some(stable)
+}
+
+/// Get a borrower's stable debt, capped at the position's principal
+pub fn get_stable_borrow(env: &Env, user: &Address) -> Option<StableBorrow> {
+    capped_stable_borrow(env, user, position_principal(env, user)).filter(|s| s.principal > 0)
+}
+
+/// Get a position's principal split into variable and stable debt
+pub fn get_debt_by_mode(env: &Env, user: &Address) -> DebtByMode {
+    let principal = position_principal(env, user);
+    let (stable_principal, stable_rate_bps) = match get_stable_borrow(env, user) {
+        Some(stable) => (stable.principal, stable.rate_bps),
+        None => (0, 0),
+    };
+    DebtByMode {
+        variable_principal: principal - stable_principal,
+        stable_principal,
+        stable_rate_bps,
+    }
+}
+
+/// Track a new borrow by rate mode
+///
+/// Called by borrows before the position's principal grows from
+/// `previous_debt`. Brings the stable share down to `previous_debt` and,
+/// for a stable borrow, adds `amount` at the current stable rate.
+pub(crate) fn record_borrow(
+    env: &Env,
+    user: &Address,
+    asset: &Option<Address>,
+    previous_debt: i128,
+    amount: i128,
+    rate_mode: RateMode,
+) -> Result<(), StableRateError> {
+    let key = StableRateDataKey::Borrow(user.clone());
+    let existing = capped_stable_borrow(env, user, previous_debt).filter(|s| s.principal > 0);
+
+    if rate_mode == RateMode::Variable {
+        match existing {
+            Some(stable) => env.storage().persistent().set(&key, &stable),
+            None => env.storage().persistent().remove(&key),
+        }
+        return Ok(());
+    }
+
+    if !get_stable_rate_config(env).enabled {
+        return Err(StableRateError::StableRateDisabled);
+    }
+    let now = env.ledger().timestamp();
+    let current_rate = get_stable_borrow_rate(env)?;
+    let stable = match existing {
+        Some(stable) => {
+            // Blend the locked rate with the new borrow, weighted by principal
+            let principal = stable
+                .principal
+                .checked_add(amount)
+                .ok_or(StableRateError::Overflow)?;
+            let rate_bps = stable
+                .principal
+                .checked_mul(stable.rate_bps)
+                .and_then(|v| v.checked_add(amount.checked_mul(current_rate)?))
+                .and_then(|v| v.checked_div(principal))
+                .ok_or(StableRateError::Overflow)?;
+            StableBorrow {
+                principal,
+                rate_bps,
+                locked_at: now,
+                rebalance_adjustment: stable.rebalance_adjustment,
+            }
+        }
+        None => StableBorrow {
+            principal: amount,
+            rate_bps: current_rate,
+            locked_at: now,
+            rebalance_adjustment: 0,
+        },
+    };
+    env.storage().persistent().set(&key, &stable);
+
+    emit_stable_borrow(
+        env,
+        StableBorrowEvent {
+            user: user.clone(),
+            asset: asset.clone(),
+            amount,
+            stable_principal: stable.principal,
+            rate_bps: stable.rate_bps,
+            timestamp: now,
+        },
+    );
+    Ok(())
+}
+
+/// Stable share of `principal` and the interest it accrues over the period
+///
+/// Includes any pending rebalance adjustment; `settle` clears it once the
+/// interest is charged.
+pub(crate) fn stable_interest(
+    env: &Env,
+    user: &Address,
+    principal: i128,
+    last_accrual_time: u64,
+    current_time: u64,
+    settle: bool,
+) -> Result<(i128, i128), InterestRateError> {
+    let mut stable = match capped_stable_borrow(env, user, principal) {
+        Some(stable) if stable.principal > 0 => stable,
+        _ => return Ok((0, 0)),
+    };
+    let interest = calculate_accrued_interest_for_year(
+        stable.principal,
+        last_accrual_time,
+        current_time,
+        stable.rate_bps,
+        crate::constants::seconds_per_year(env),
+    )?
+    .checked_add(stable.rebalance_adjustment)
+    .ok_or(InterestRateError::Overflow)?
+    .max(0);
+
+    if settle && stable.rebalance_adjustment != 0 {
+        stable.rebalance_adjustment = 0;
+        env.storage()
+            .persistent()
+            .set(&StableRateDataKey::Borrow(user.clone()), &stable);
+    }
+    Ok((stable.principal, interest))
+}
+
+/// Reset a borrower's locked rate to the current stable rate
+///
+/// Anyone can call this once the locked rate and the current stable rate
+/// differ by more than the rebalance threshold, in either direction.
+///
+/// # Returns
+/// The new locked rate
+///
+/// # Errors
+/// * `NoStableDebt` - The user has no stable debt
+/// * `WithinThreshold` - The rates differ by no more than the threshold
+pub fn rebalance_stable_rate(
+    env: &Env,
+    caller: Address,
+    user: Address,
+) -> Result<i128, StableRateError> {
+    let position = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, Position>(&DepositDataKey::Position(user.clone()))
+        .ok_or(StableRateError::NoStableDebt)?;
+    let mut stable = capped_stable_borrow(env, &user, position.debt)
+        .filter(|s| s.principal > 0)
+        .ok_or(StableRateError::NoStableDebt)?;
+
+    let current_rate = get_stable_borrow_rate(env)?;
+    let divergence = (stable.rate_bps - current_rate).abs();
+    if divergence <= get_stable_rate_config(env).rebalance_threshold_bps {
+        return Err(StableRateError::WithinThreshold);
+    }
+
+    // Keep charging the old rate for the time since the last accrual
+    let now = env.ledger().timestamp();
+    let seconds_per_year = crate::constants::seconds_per_year(env);
+    let interest_at = |rate_bps: i128| {
+        calculate_accrued_interest_for_year(
+            stable.principal,
+            position.last_accrual_time,
+            now,
+            rate_bps,
+            seconds_per_year,
+        )
+        .map_err(|_| StableRateError::Overflow)
+    };
+    let adjustment = interest_at(stable.rate_bps)?
+        .checked_sub(interest_at(current_rate)?)
+        .and_then(|v| v.checked_add(stable.rebalance_adjustment))
+        .ok_or(StableRateError::Overflow)?;
+
+    let old_rate_bps = stable.rate_bps;
+    stable.rate_bps = current_rate;
+    stable.locked_at = now;
+    stable.rebalance_adjustment = adjustment;
+    env.storage()
+        .persistent()
+        .set(&StableRateDataKey::Borrow(user.clone()), &stable);
+
+    emit_stable_rate_rebalanced(
+        env,
+        StableRateRebalancedEvent {
+            user,
+            caller,
+            stable_principal: stable.principal,
+            old_rate_bps,
+            new_rate_bps: current_rate,
+            timestamp: now,
+        },
+    );
+    Ok(current_rate)
+}
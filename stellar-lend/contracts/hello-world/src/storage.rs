@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, Vec};
+use soroban_sdk::{contracttype, Address, Env, IntoVal, Symbol, TryFromVal, Val, Vec};
 
 #[derive(Clone)]
 #[contracttype]
@@ -16,6 +16,20 @@ pub enum GovernanceDataKey {
 
     RecoveryRequest,
     RecoveryApprovals,
+
+    /// Token lock contract whose balance cannot vote
+    VestingContract,
+
+    /// Whether votes come from checkpointed deposits instead of live balances
+    CheckpointedVoting,
+    /// Vote tokens an account has deposited for delegation: i128
+    VotingDeposit(Address),
+    /// Account an account's deposit votes through (absent = itself)
+    Delegate(Address),
+    /// Number of vote checkpoints of a delegate: u32
+    CheckpointCount(Address),
+    /// A delegate's votes from a point in time: VoteCheckpoint
+    Checkpoint(Address, u32),
 }
 
 #[derive(Clone)]
@@ -24,3 +38,120 @@ pub struct GuardianConfig {
     pub guardians: Vec<Address>,
     pub threshold: u32,
 }
+
+// ============================================================================
+// Versioned storage namespaces
+// ============================================================================
+
+/// Module prefix and layout version for namespaced storage keys
+///
+/// Entries written through this layer are stored under
+/// `(prefix, version, module_key)` rather than the bare module key, so two
+/// modules, or two layouts of the same module, never collide on a reused key.
+/// A change to the shape of a module's stored values bumps `version`; the
+/// upgrade's migration then moves entries to the new layout explicitly.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct StorageNamespace {
+    /// Module prefix (at most 32 characters of `[a-zA-Z0-9_]`)
+    pub prefix: &'static str,
+    /// Layout version of the module's stored values
+    pub version: u32,
+}
+
+/// Configuration key-value store (`config.rs`)
+pub const CONFIG_NAMESPACE: StorageNamespace = StorageNamespace {
+    prefix: "config",
+    version: 1,
+};
+
+/// Network constants registry (`constants.rs`)
+pub const CONSTANTS_NAMESPACE: StorageNamespace = StorageNamespace {
+    prefix: "constants",
+    version: 1,
+};
+
+/// Liquidation auctions (`liquidate.rs`)
+pub const LIQUIDATION_NAMESPACE: StorageNamespace = StorageNamespace {
+    prefix: "liquidate",
+    version: 1,
+};
+
+/// Storage key of an entry in a versioned namespace
+pub type NamespacedKey<K> = (Symbol, u32, K);
+
+impl StorageNamespace {
+    /// Build the namespaced storage key for a module key
+    pub fn key<K>(&self, env: &Env, key: K) -> NamespacedKey<K> {
+        (Symbol::new(env, self.prefix), self.version, key)
+    }
+}
+
+/// Read a persistent value from a namespace
+///
+/// Falls back to the bare (pre-namespacing) key, so entries written before a
+/// module adopted namespacing stay readable until they are next written.
+pub fn read<K, V>(env: &Env, namespace: &StorageNamespace, key: &K) -> Option<V>
+where
+    K: IntoVal<Env, Val> + Clone,
+    V: TryFromVal<Env, Val>,
+{
+    let storage = env.storage().persistent();
+    storage
+        .get(&namespace.key(env, key.clone()))
+        .or_else(|| storage.get(key))
+}
+
+/// Write a persistent value to a namespace
+///
+/// Any legacy entry under the bare key is removed so it cannot resurface
+/// through the read fallback after a later `remove`.
+pub fn write<K, V>(env: &Env, namespace: &StorageNamespace, key: &K, value: &V)
+where
+    K: IntoVal<Env, Val> + Clone,
+    V: IntoVal<Env, Val>,
+{
+    let storage = env.storage().persistent();
+    storage.set(&namespace.key(env, key.clone()), value);
+    if storage.has(key) {
+        storage.remove(key);
+    }
+}
+
+/// Check whether a namespace holds a value, including under the legacy key
+pub fn has<K>(env: &Env, namespace: &StorageNamespace, key: &K) -> bool
+where
+    K: IntoVal<Env, Val> + Clone,
+{
+    let storage = env.storage().persistent();
+    storage.has(&namespace.key(env, key.clone())) || storage.has(key)
+}
+
+/// Remove a value from a namespace, including any legacy entry
+pub fn remove<K>(env: &Env, namespace: &StorageNamespace, key: &K)
+where
+    K: IntoVal<Env, Val> + Clone,
+{
+    let storage = env.storage().persistent();
+    storage.remove(&namespace.key(env, key.clone()));
+    storage.remove(key);
+}
+
+/// Move a legacy entry under its namespaced key
+///
+/// Used by upgrade migrations to rewrite entries eagerly instead of waiting
+/// for the next write. Returns `true` if a legacy entry was moved.
+pub fn migrate_legacy<K, V>(env: &Env, namespace: &StorageNamespace, key: &K) -> bool
+where
+    K: IntoVal<Env, Val> + Clone,
+    V: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    let storage = env.storage().persistent();
+    match storage.get::<K, V>(key) {
+        Some(value) => {
+            storage.set(&namespace.key(env, key.clone()), &value);
+            storage.remove(key);
+            true
+        }
+        None => false,
+    }
+}
@@ -0,0 +1,292 @@
+//! # Interest Subsidy Campaigns
+//!
+//! Governance can run a time-boxed campaign that lowers the borrow rate of a
+//! target asset by a fixed number of basis points, paid for by the treasury.
+//!
+//! ## Funding
+//! An executed `ProposalType::SubsidyCampaign` proposal moves the campaign
+//! budget out of the asset's protocol reserve (the treasury's claimable
+//! balance). The budget is a hard limit: the campaign never draws more.
+//!
+//! ## Accrual
+//! Whenever interest accrues on debt in the target asset, the part of the
+//! accrual period inside the campaign window is discounted by
+//! `principal * discount_bps * seconds / (10000 * seconds_per_year)`,
+//! capped at the interest itself and at the remaining budget. The discount
+//! is drawn from the budget and never charged to the borrower. Once the
+//! budget is spent, borrowers pay the full rate again.
+//!
+//! ## Closing
+//! After the window ends or the budget runs out, anyone can close the
+//! campaign, which returns the unspent budget to the protocol reserve and
+//! frees the asset for a new campaign. Only one campaign per asset can be
+//! open at a time.
+
+use soroban_sdk::{contracterror, contracttype, Address, Env};
+
+use crate::deposit::DepositDataKey;
+use crate::events::{
+    emit_subsidy_campaign_closed, emit_subsidy_campaign_created, SubsidyCampaignClosedEvent,
+    SubsidyCampaignCreatedEvent,
+};
+
+/// Basis points scale
+const BPS_SCALE: i128 = 10_000;
+
+/// Errors that can occur in subsidy campaign operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum SubsidyCampaignError {
+    /// Discount, window or budget is invalid
+    InvalidTerms = 1,
+    /// The asset already has an open campaign
+    CampaignActive = 2,
+    /// The asset's protocol reserve cannot fund the budget
+    InsufficientReserve = 3,
+    /// The asset has no open campaign
+    CampaignNotFound = 4,
+    /// The campaign window has not ended and budget remains
+    CampaignNotEnded = 5,
+    /// Overflow occurred during calculation
+    Overflow = 6,
+}
+
+/// Storage keys for subsidy campaigns
+#[contracttype]
+#[derive(Clone)]
+pub enum SubsidyCampaignDataKey {
+    /// Id assigned to the next campaign
+    /// Value type: u64
+    NextId,
+    /// A campaign by id, kept after closing
+    /// Value type: SubsidyCampaign
+    Campaign(u64),
+    /// Id of the open campaign on an asset
+    /// Value type: u64
+    AssetCampaign(Option<Address>),
+}
+
+/// Terms of a campaign, as proposed to governance
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubsidyCampaignTerms {
+    /// Debt asset whose borrow rate is lowered (None for native XLM)
+    pub asset: Option<Address>,
+    /// Borrow rate reduction in basis points
+    pub discount_bps: i128,
+    /// Window start; a start already passed at execution means "now"
+    pub start: u64,
+    /// Window end
+    pub end: u64,
+    /// Most the campaign can spend, taken from the asset's protocol reserve
+    pub budget: i128,
+}
+
+/// A funded campaign and its spending
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubsidyCampaign {
+    pub id: u64,
+    pub asset: Option<Address>,
+    pub discount_bps: i128,
+    pub start: u64,
+    pub end: u64,
+    pub budget: i128,
+    /// Subsidy drawn so far
+    pub spent: i128,
+    pub closed: bool,
+}
+
+/// Get a campaign by id
+pub fn get_subsidy_campaign(env: &Env, id: u64) -> Option<SubsidyCampaign> {
+    env.storage()
+        .persistent()
+        .get(&SubsidyCampaignDataKey::Campaign(id))
+}
+
+/// Get the open campaign on an asset, if any
+pub fn get_asset_subsidy_campaign(env: &Env, asset: &Option<Address>) -> Option<SubsidyCampaign> {
+    let id: u64 = env
+        .storage()
+        .persistent()
+        .get(&SubsidyCampaignDataKey::AssetCampaign(asset.clone()))?;
+    get_subsidy_campaign(env, id)
+}
+
+/// Check the terms that do not depend on protocol state
+pub(crate) fn validate_terms(terms: &SubsidyCampaignTerms) -> Result<(), SubsidyCampaignError> {
+    if terms.discount_bps <= 0
+        || terms.discount_bps > BPS_SCALE
+        || terms.end <= terms.start
+        || terms.budget <= 0
+    {
+        return Err(SubsidyCampaignError::InvalidTerms);
+    }
+    Ok(())
+}
+
+/// Fund and open a campaign (executed governance proposals only)
+///
+/// # Returns
+/// The campaign id
+///
+/// # Errors
+/// * `InvalidTerms` - Terms are invalid or the window has already ended
+/// * `CampaignActive` - The asset already has an open campaign
+/// * `InsufficientReserve` - The protocol reserve is below the budget
+pub(crate) fn apply_subsidy_campaign(
+    env: &Env,
+    terms: SubsidyCampaignTerms,
+) -> Result<u64, SubsidyCampaignError> {
+    validate_terms(&terms)?;
+    let now = env.ledger().timestamp();
+    if terms.end <= now {
+        return Err(SubsidyCampaignError::InvalidTerms);
+    }
+    let asset_key = SubsidyCampaignDataKey::AssetCampaign(terms.asset.clone());
+    if env.storage().persistent().has(&asset_key) {
+        return Err(SubsidyCampaignError::CampaignActive);
+    }
+
+    let reserve_key = DepositDataKey::ProtocolReserve(terms.asset.clone());
+    let reserve: i128 = env.storage().persistent().get(&reserve_key).unwrap_or(0);
+    if reserve < terms.budget {
+        return Err(SubsidyCampaignError::InsufficientReserve);
+    }
+    env.storage()
+        .persistent()
+        .set(&reserve_key, &(reserve - terms.budget));
+
+    let id: u64 = env
+        .storage()
+        .persistent()
+        .get(&SubsidyCampaignDataKey::NextId)
+        .unwrap_or(0);
+    let campaign = SubsidyCampaign {
+        id,
+        asset: terms.asset,
+        discount_bps: terms.discount_bps,
+        start: terms.start.max(now),
+        end: terms.end,
+        budget: terms.budget,
+        spent: 0,
+        closed: false,
+    };
+    env.storage()
+        .persistent()
+        .set(&SubsidyCampaignDataKey::NextId, &(id + 1));
+    env.storage()
+        .persistent()
+        .set(&SubsidyCampaignDataKey::Campaign(id), &campaign);
+    env.storage().persistent().set(&asset_key, &id);
+
+    emit_subsidy_campaign_created(
+        env,
+        SubsidyCampaignCreatedEvent {
+            id,
+            asset: campaign.asset,
+            discount_bps: campaign.discount_bps,
+            start: campaign.start,
+            end: campaign.end,
+            budget: campaign.budget,
+        },
+    );
+    Ok(id)
+}
+
+/// Close the open campaign on an asset once it has ended or run out
+///
+/// Anyone can call this; the unspent budget returns to the asset's protocol
+/// reserve.
+///
+/// # Returns
+/// The amount returned to the reserve
+pub fn close_subsidy_campaign(
+    env: &Env,
+    asset: Option<Address>,
+) -> Result<i128, SubsidyCampaignError> {
+    let asset_key = SubsidyCampaignDataKey::AssetCampaign(asset.clone());
+    let mut campaign =
+        get_asset_subsidy_campaign(env, &asset).ok_or(SubsidyCampaignError::CampaignNotFound)?;
+    let refund = campaign.budget - campaign.spent;
+    if env.ledger().timestamp() < campaign.end && refund > 0 {
+        return Err(SubsidyCampaignError::CampaignNotEnded);
+    }
+
+    let reserve_key = DepositDataKey::ProtocolReserve(asset.clone());
+    let reserve: i128 = env.storage().persistent().get(&reserve_key).unwrap_or(0);
+    let new_reserve = reserve
+        .checked_add(refund)
+        .ok_or(SubsidyCampaignError::Overflow)?;
+    env.storage().persistent().set(&reserve_key, &new_reserve);
+
+    campaign.closed = true;
+    env.storage()
+        .persistent()
+        .set(&SubsidyCampaignDataKey::Campaign(campaign.id), &campaign);
+    env.storage().persistent().remove(&asset_key);
+
+    emit_subsidy_campaign_closed(
+        env,
+        SubsidyCampaignClosedEvent {
+            id: campaign.id,
+            asset,
+            spent: campaign.spent,
+            refunded: refund,
+        },
+    );
+    Ok(refund)
+}
+
+/// Discount newly accrued interest on `asset` debt by the open campaign
+///
+/// Called wherever borrower interest accrues, with the accrual period and
+/// the interest computed for it. Draws the discount from the campaign
+/// budget and returns the interest left to charge.
+pub(crate) fn apply_campaign_subsidy(
+    env: &Env,
+    asset: &Option<Address>,
+    principal: i128,
+    from: u64,
+    to: u64,
+    interest: i128,
+) -> i128 {
+    if interest <= 0 || principal <= 0 {
+        return interest;
+    }
+    let mut campaign = match get_asset_subsidy_campaign(env, asset) {
+        Some(campaign) => campaign,
+        None => return interest,
+    };
+    let start = from.max(campaign.start);
+    let end = to.min(campaign.end);
+    let remaining = campaign.budget - campaign.spent;
+    if end <= start || remaining <= 0 {
+        return interest;
+    }
+
+    let seconds_per_year = crate::constants::seconds_per_year(env) as i128;
+    let subsidy = principal
+        .checked_mul(campaign.discount_bps)
+        .and_then(|v| v.checked_mul((end - start) as i128))
+        .and_then(|v| v.checked_div(BPS_SCALE * seconds_per_year))
+        .unwrap_or(0)
+        .min(interest)
+        .min(remaining);
+    if subsidy <= 0 {
+        return interest;
+    }
+
+    campaign.spent += subsidy;
+    env.storage()
+        .persistent()
+        .set(&SubsidyCampaignDataKey::Campaign(campaign.id), &campaign);
+    crate::financials::record(
+        env,
+        crate::financials::FinancialItem::SubsidiesPaid,
+        asset,
+        subsidy,
+    );
+    interest - subsidy
+}
@@ -0,0 +1,105 @@
+//! Accounting currency tests.
+//!
+//! # Coverage
+//! - Prices are rescaled from feed decimals to 8 decimals (USD default)
+//! - An XLM numeraire expresses prices and values in native units
+//! - `convert_amount` converts between assets through the numeraire
+//! - Admin-only configuration and unpriced assets/numeraires are rejected
+//! - Configuration changes need the admin's authorization
+
+use crate::accounting::{AccountingCurrency, AccountingError, ACCOUNTING_SCALE};
+use crate::tests::test_helpers::{assert_authorized, setup_env_with_native_asset};
+use soroban_sdk::{testutils::Address as _, Address, Symbol};
+
+#[test]
+fn test_default_currency_rescales_feed_decimals() {
+    let (env, _id, client, admin, _user, native) = setup_env_with_native_asset();
+    let oracle = Address::generate(&env);
+    let asset = Address::generate(&env);
+    // $1.50 quoted with 2 decimals, $0.10 with 8
+    client.update_price_feed(&admin, &asset, &150, &2, &oracle);
+    client.update_price_feed(&admin, &native, &10_000_000, &8, &oracle);
+
+    let currency = client.get_accounting_currency();
+    assert_eq!(currency.code, Symbol::new(&env, "USD"));
+    assert_eq!(currency.numeraire_asset, None);
+    assert_eq!(
+        client.get_normalized_price(&Some(asset.clone())),
+        150_000_000
+    );
+    assert_eq!(client.get_normalized_price(&None), 10_000_000);
+    assert_eq!(client.to_accounting_value(&Some(asset), &1_000), 1_500);
+}
+
+#[test]
+fn test_xlm_numeraire_values_in_native_units() {
+    let (env, id, client, admin, _user, native) = setup_env_with_native_asset();
+    let oracle = Address::generate(&env);
+    let asset = Address::generate(&env);
+    client.update_price_feed(&admin, &asset, &150, &2, &oracle);
+    client.update_price_feed(&admin, &native, &10_000_000, &8, &oracle);
+
+    client.set_accounting_currency(
+        &admin,
+        &AccountingCurrency {
+            code: Symbol::new(&env, "XLM"),
+            numeraire_asset: Some(native.clone()),
+        },
+    );
+    assert_authorized(&env, &admin, &id, "set_accounting_currency");
+
+    // 1 token = $1.50 = 15 XLM
+    assert_eq!(
+        client.get_normalized_price(&Some(asset.clone())),
+        15 * ACCOUNTING_SCALE
+    );
+    assert_eq!(client.get_normalized_price(&None), ACCOUNTING_SCALE);
+    assert_eq!(client.get_normalized_price(&Some(native)), ACCOUNTING_SCALE);
+    assert_eq!(client.to_accounting_value(&None, &700), 700);
+}
+
+#[test]
+fn test_convert_amount_between_assets() {
+    let (env, _id, client, admin, _user, native) = setup_env_with_native_asset();
+    let oracle = Address::generate(&env);
+    let asset = Address::generate(&env);
+    client.update_price_feed(&admin, &asset, &150, &2, &oracle);
+    client.update_price_feed(&admin, &native, &10_000_000, &8, &oracle);
+
+    assert_eq!(
+        client.convert_amount(&Some(asset.clone()), &None, &1_000),
+        15_000
+    );
+    assert_eq!(
+        client.convert_amount(&None, &Some(asset.clone()), &15_000),
+        1_000
+    );
+    assert_eq!(
+        client.convert_amount(&Some(asset.clone()), &Some(asset), &42),
+        42
+    );
+}
+
+#[test]
+fn test_configuration_and_unpriced_assets_rejected() {
+    let (env, _id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let unpriced = Address::generate(&env);
+    let currency = AccountingCurrency {
+        code: Symbol::new(&env, "EUR"),
+        numeraire_asset: Some(unpriced.clone()),
+    };
+
+    assert_eq!(
+        client.try_set_accounting_currency(&Address::generate(&env), &currency),
+        Err(Ok(AccountingError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_accounting_currency(&admin, &currency),
+        Err(Ok(AccountingError::PriceUnavailable))
+    );
+    assert_eq!(
+        client.try_get_normalized_price(&Some(unpriced)),
+        Err(Ok(AccountingError::PriceUnavailable))
+    );
+    assert_eq!(client.get_accounting_currency().numeraire_asset, None);
+}
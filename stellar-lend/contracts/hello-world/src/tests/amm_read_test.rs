@@ -0,0 +1,170 @@
+//! AMM read client tests.
+//!
+//! # Coverage
+//! - Reads of max slippage, enabled protocols and pool health from a deployed AMM
+//! - Swap preflight accepts viable swaps and rejects each failing limit up front
+//! - AMM contract errors propagate through the client
+//! - Admin-only registration, authorized by the admin, and the unconfigured
+//!   state
+
+use crate::tests::test_helpers::assert_authorized;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env, Symbol, Vec};
+use stellarlend_amm::{
+    AmmContract, AmmContractClient, AmmError, AmmProtocolConfig, AmmSettings, TokenPair,
+};
+
+struct Setup<'a> {
+    client: HelloContractClient<'a>,
+    amm: AmmContractClient<'a>,
+    amm_admin: Address,
+    protocol: Address,
+    token: Option<Address>,
+}
+
+/// Core contract wired to a deployed AMM with one enabled protocol (XLM/token pool)
+fn setup(e: &Env) -> Setup<'_> {
+    e.mock_all_auths();
+    let id = e.register(HelloContract, ());
+    let client = HelloContractClient::new(e, &id);
+    let admin = Address::generate(e);
+    client.initialize(&admin);
+
+    let amm = AmmContractClient::new(e, &e.register(AmmContract, ()));
+    let amm_admin = Address::generate(e);
+    amm.initialize_amm_settings(&amm_admin, &100, &1_000, &10_000);
+
+    let protocol = Address::generate(e);
+    let token = Some(Address::generate(e));
+    let mut supported_pairs = Vec::new(e);
+    supported_pairs.push_back(TokenPair {
+        token_a: None,
+        token_b: token.clone(),
+        pool_address: Address::generate(e),
+    });
+    amm.add_amm_protocol(
+        &amm_admin,
+        &AmmProtocolConfig {
+            protocol_address: protocol.clone(),
+            protocol_name: Symbol::new(e, "TestAMM"),
+            enabled: true,
+            fee_tier: 30,
+            min_swap_amount: 1_000,
+            max_swap_amount: 1_000_000,
+            supported_pairs,
+        },
+    );
+
+    client.set_amm_contract(&admin, &amm.address);
+    Setup {
+        client,
+        amm,
+        amm_admin,
+        protocol,
+        token,
+    }
+}
+
+#[test]
+fn test_reads_amm_settings_through_client() {
+    let e = Env::default();
+    let s = setup(&e);
+
+    assert_eq!(s.client.get_amm_contract(), Some(s.amm.address.clone()));
+    assert_eq!(s.client.get_amm_max_slippage(), 1_000);
+    let protocols = s.client.get_amm_supported_protocols();
+    assert_eq!(protocols.len(), 1);
+    assert_eq!(protocols.get(0).unwrap(), s.protocol);
+
+    let health = s.client.get_amm_pool_health(&s.protocol, &None, &s.token);
+    assert!(health.healthy);
+    assert_eq!(health.fee_tier, 30);
+}
+
+#[test]
+fn test_preflight_accepts_viable_swap() {
+    let e = Env::default();
+    let s = setup(&e);
+
+    let health = s
+        .client
+        .preflight_amm_swap(&s.protocol, &None, &s.token, &5_000, &500);
+    assert!(health.healthy);
+    assert!(health.pool_address.is_some());
+}
+
+#[test]
+fn test_preflight_rejects_failing_limits() {
+    let e = Env::default();
+    let s = setup(&e);
+    let other_token = Some(Address::generate(&e));
+
+    let cases = [
+        (s.token.clone(), 5_000, 1_001, AmmError::SlippageExceeded),
+        (other_token, 5_000, 100, AmmError::InvalidTokenPair),
+        (s.token.clone(), 999, 100, AmmError::InvalidSwapParams),
+        (s.token.clone(), 1_000_001, 100, AmmError::MaxInputExceeded),
+    ];
+    for (token_out, amount, slippage, expected) in cases {
+        assert_eq!(
+            s.client
+                .try_preflight_amm_swap(&s.protocol, &None, &token_out, &amount, &slippage),
+            Err(Ok(expected))
+        );
+    }
+
+    // Paused swaps are caught before the swap is attempted
+    s.amm.update_amm_settings(
+        &s.amm_admin,
+        &AmmSettings {
+            default_slippage: 100,
+            max_slippage: 1_000,
+            swap_enabled: false,
+            liquidity_enabled: true,
+            auto_swap_threshold: 10_000,
+            max_price_impact_bps: 0,
+        },
+    );
+    assert_eq!(
+        s.client
+            .try_preflight_amm_swap(&s.protocol, &None, &s.token, &5_000, &100),
+        Err(Ok(AmmError::SwapPaused))
+    );
+}
+
+#[test]
+fn test_amm_errors_propagate() {
+    let e = Env::default();
+    let s = setup(&e);
+
+    assert_eq!(
+        s.client
+            .try_get_amm_pool_health(&Address::generate(&e), &None, &s.token),
+        Err(Ok(AmmError::UnsupportedProtocol))
+    );
+}
+
+#[test]
+fn test_registration_and_unconfigured_state() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let id = e.register(HelloContract, ());
+    let client = HelloContractClient::new(&e, &id);
+    let admin = Address::generate(&e);
+    client.initialize(&admin);
+
+    assert_eq!(client.get_amm_contract(), None);
+    assert_eq!(
+        client.try_get_amm_max_slippage(),
+        Err(Ok(AmmError::NotConfigured))
+    );
+    assert_eq!(
+        client.try_set_amm_contract(&Address::generate(&e), &Address::generate(&e)),
+        Err(Ok(AmmError::Unauthorized))
+    );
+
+    let amm = Address::generate(&e);
+    client.set_amm_contract(&admin, &amm);
+    assert_authorized(&e, &admin, &id, "set_amm_contract");
+    assert_eq!(client.get_amm_contract(), Some(amm));
+}
@@ -81,6 +81,13 @@ fn test_amm_full_lifecycle() {
     let amount_out = client.amm_swap(&user, &swap_params);
     assert_eq!(amount_out, 9900); // 1% slippage in mock
 
+    let stats = client.get_amm_pair_stats(&Some(token_b.clone()), &None);
+    assert_eq!(stats.swap_count, 1);
+    assert_eq!(stats.volume_a, 10000);
+    assert_eq!(stats.volume_b, 9900);
+    assert_eq!(stats.fees_a, 30);
+    assert_eq!(stats.avg_slippage_bps, 100);
+
     // 4. Test Add Liquidity
     let lib_params = LiquidityParams {
         protocol: protocol_addr.clone(),
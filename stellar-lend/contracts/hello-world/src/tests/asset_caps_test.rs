@@ -0,0 +1,129 @@
+//! Per-asset supply/borrow cap and utilization buffer tests.
+//!
+//! # Coverage
+//! - Core `deposit_collateral` respects the asset's supply cap
+//! - Core `borrow_asset` respects the asset's borrow cap
+//! - The utilization buffer keeps a share of supply unborrowed
+//! - Withdrawals and repayments release cap headroom
+//! - `ca_get_remaining_caps` reports headroom; invalid buffers are rejected
+
+use super::test_helpers::setup_env_with_native_asset;
+use crate::borrow::{borrow_asset, BorrowError};
+use crate::cross_asset::{initialize, initialize_asset, AssetConfig, CrossAssetError};
+use crate::deposit::{deposit_collateral, DepositError};
+use crate::repay::repay_debt;
+use crate::withdraw::withdraw_collateral;
+use crate::HelloContractClient;
+use soroban_sdk::{testutils::Address as _, token, Address, Env};
+
+/// Returns (env, contract, client, user, native asset)
+fn setup() -> (Env, Address, HelloContractClient<'static>, Address, Address) {
+    let (env, contract_id, client, admin, user, native) = setup_env_with_native_asset();
+
+    env.as_contract(&contract_id, || {
+        initialize(&env, admin.clone()).unwrap();
+        let config = AssetConfig {
+            asset: None,
+            collateral_factor: 7500,
+            liquidation_threshold: 8000,
+            reserve_factor: 1000,
+            max_supply: 10_000,
+            max_borrow: 3_000,
+            can_collateralize: true,
+            can_borrow: true,
+            price: 10_000_000,
+            price_updated_at: env.ledger().timestamp(),
+            is_isolated: false,
+            isolated_debt_ceiling: 0,
+        };
+        initialize_asset(&env, None, config).unwrap();
+    });
+
+    (env, contract_id, client, user, native)
+}
+
+#[test]
+fn test_core_deposit_respects_supply_cap() {
+    let (env, id, client, user, _native) = setup();
+    env.as_contract(&id, || {
+        deposit_collateral(&env, user.clone(), None, 8_000).unwrap();
+        assert_eq!(
+            deposit_collateral(&env, user.clone(), None, 3_000),
+            Err(DepositError::SupplyCapExceeded)
+        );
+    });
+
+    let caps = client.ca_get_remaining_caps(&None);
+    assert_eq!(caps.total_supply, 8_000);
+    assert_eq!(caps.remaining_supply, 2_000);
+}
+
+#[test]
+fn test_core_borrow_respects_borrow_cap() {
+    let (env, id, client, user, _native) = setup();
+    env.as_contract(&id, || {
+        deposit_collateral(&env, user.clone(), None, 10_000).unwrap();
+        borrow_asset(&env, user.clone(), None, 3_000).unwrap();
+        assert_eq!(
+            borrow_asset(&env, user.clone(), None, 1),
+            Err(BorrowError::BorrowCapExceeded)
+        );
+    });
+
+    let caps = client.ca_get_remaining_caps(&None);
+    assert_eq!(caps.total_borrow, 3_000);
+    assert_eq!(caps.remaining_borrow, 0);
+}
+
+#[test]
+fn test_utilization_buffer_reserves_liquidity() {
+    let (env, id, client, user, _native) = setup();
+    client.ca_set_utilization_buffer(&None, &5_000);
+
+    env.as_contract(&id, || {
+        deposit_collateral(&env, user.clone(), None, 4_000).unwrap();
+        assert_eq!(
+            borrow_asset(&env, user.clone(), None, 2_001),
+            Err(BorrowError::UtilizationBufferExceeded)
+        );
+        borrow_asset(&env, user.clone(), None, 1_500).unwrap();
+    });
+
+    let caps = client.ca_get_remaining_caps(&None);
+    assert_eq!(caps.utilization_buffer_bps, 5_000);
+    assert_eq!(caps.remaining_borrow, 500);
+}
+
+#[test]
+fn test_withdraw_and_repay_release_headroom() {
+    let (env, id, client, user, native) = setup();
+    let native_client = token::StellarAssetClient::new(&env, &native);
+    native_client.mint(&user, &1_000);
+    native_client.approve(&user, &id, &1_000, &(env.ledger().sequence() + 100));
+
+    env.as_contract(&id, || {
+        deposit_collateral(&env, user.clone(), None, 10_000).unwrap();
+        borrow_asset(&env, user.clone(), None, 3_000).unwrap();
+        repay_debt(&env, user.clone(), None, 1_000).unwrap();
+        withdraw_collateral(&env, user.clone(), None, 2_000).unwrap();
+    });
+
+    let caps = client.ca_get_remaining_caps(&None);
+    assert_eq!(caps.remaining_borrow, 1_000);
+    assert_eq!(caps.remaining_supply, 2_000);
+    assert!(client.assert_invariants().holds);
+}
+
+#[test]
+fn test_invalid_buffer_rejected() {
+    let (env, _id, client, _user, _native) = setup();
+
+    assert_eq!(
+        client.try_ca_set_utilization_buffer(&None, &5_001),
+        Err(Ok(CrossAssetError::AssetNotConfigured))
+    );
+    assert_eq!(
+        client.try_ca_get_remaining_caps(&Some(Address::generate(&env))),
+        Err(Ok(CrossAssetError::AssetNotConfigured))
+    );
+}
@@ -0,0 +1,106 @@
+//! Per-asset accounting ledger tests.
+//!
+//! # Coverage
+//! - Core deposits, borrows, repayments and withdrawals update the ledger of
+//!   the asset they move, not one aggregate
+//! - The ledger reports the asset's current reserve balance
+//! - The protocol report values each ledger at its oracle price and derives
+//!   protocol totals and utilization from those values
+//! - Assets without a price are listed but left out of the totals
+
+use super::test_helpers::setup_env_with_native_asset;
+use crate::borrow::borrow_asset;
+use crate::deposit::deposit_collateral;
+use crate::repay::repay_debt;
+use crate::withdraw::withdraw_collateral;
+use crate::HelloContractClient;
+use soroban_sdk::{testutils::Address as _, token, Address, Env};
+
+struct Setup {
+    env: Env,
+    client: HelloContractClient<'static>,
+    admin: Address,
+    native: Address,
+    token: Address,
+}
+
+/// Native: 10_000 supplied, 2_000 borrowed. Token: 3_000 supplied.
+fn setup() -> Setup {
+    let (env, id, client, admin, user, native) = setup_env_with_native_asset();
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let expiration = env.ledger().sequence() + 100;
+    token::StellarAssetClient::new(&env, &native).mint(&user, &1_000);
+    token::Client::new(&env, &native).approve(&user, &id, &1_000, &expiration);
+    token::StellarAssetClient::new(&env, &token).mint(&user, &4_000);
+    token::Client::new(&env, &token).approve(&user, &id, &4_000, &expiration);
+
+    env.as_contract(&id, || {
+        deposit_collateral(&env, user.clone(), None, 10_000).unwrap();
+        deposit_collateral(&env, user.clone(), Some(token.clone()), 4_000).unwrap();
+        borrow_asset(&env, user.clone(), None, 3_000).unwrap();
+        repay_debt(&env, user.clone(), None, 1_000).unwrap();
+        withdraw_collateral(&env, user.clone(), Some(token.clone()), 1_000).unwrap();
+    });
+
+    Setup {
+        env,
+        client,
+        admin,
+        native,
+        token,
+    }
+}
+
+fn set_price(s: &Setup, asset: &Address, price: i128) {
+    s.client
+        .update_price_feed(&s.admin, asset, &price, &8, &Address::generate(&s.env));
+}
+
+#[test]
+fn test_ledger_tracks_each_asset() {
+    let s = setup();
+
+    let native = s.client.get_asset_ledger(&None);
+    assert_eq!(native.asset, None);
+    assert_eq!((native.supplied, native.borrowed), (10_000, 2_000));
+    assert_eq!(native.reserves, s.client.get_reserve_balance(&None));
+
+    let token = s.client.get_asset_ledger(&Some(s.token.clone()));
+    assert_eq!((token.supplied, token.borrowed), (3_000, 0));
+
+    let unknown = s.client.get_asset_ledger(&Some(Address::generate(&s.env)));
+    assert_eq!((unknown.supplied, unknown.borrowed), (0, 0));
+}
+
+#[test]
+fn test_report_values_ledgers_at_oracle_prices() {
+    let s = setup();
+    set_price(&s, &s.native, 200_000_000);
+
+    // The token has no price yet: listed, but not in the totals
+    let report = s.client.get_protocol_report();
+    assert_eq!(report.assets.len(), 2);
+    let native = report.assets.get(0).unwrap();
+    assert!(native.priced);
+    assert_eq!(native.utilization_rate, 2_000);
+    assert_eq!(
+        (native.supplied_value, native.borrowed_value),
+        (20_000, 4_000)
+    );
+    let token = report.assets.get(1).unwrap();
+    assert_eq!(token.asset, Some(s.token.clone()));
+    assert!(!token.priced);
+    assert_eq!(token.supplied, 3_000);
+    assert_eq!(token.supplied_value, 0);
+    assert_eq!(report.total_supplied_value, 20_000);
+    assert_eq!(report.value_utilization_rate, 2_000);
+
+    set_price(&s, &s.token, 50_000_000);
+    let report = s.client.get_protocol_report();
+    assert!(report.assets.get(1).unwrap().priced);
+    assert_eq!(report.total_supplied_value, 21_500);
+    assert_eq!(report.total_borrowed_value, 4_000);
+    assert_eq!(report.value_utilization_rate, 4_000 * 10_000 / 21_500);
+}
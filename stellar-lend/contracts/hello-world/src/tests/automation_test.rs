@@ -0,0 +1,177 @@
+//! Automation job tests.
+//!
+//! # Coverage
+//! - Keepers run due jobs and are paid the job reward from the keeper pool
+//! - Jobs cannot run again before their interval; they still run unpaid when
+//!   the pool is dry
+//! - Contract targets call another contract with the stored arguments, and a
+//!   failing call fails the job
+//! - Job and global pauses, removal, validation and admin-only registration,
+//!   authorized by the admin
+
+use crate::automation::{AutomationError, JobTarget};
+use crate::tests::test_helpers::{assert_authorized, setup_env_with_native_asset};
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env, IntoVal, Symbol, Val, Vec,
+};
+use stellarlend_testutils::advance_time;
+
+/// Counts its ticks; `fail` always panics
+#[contract]
+pub struct TickContract;
+
+#[contractimpl]
+impl TickContract {
+    pub fn tick(env: Env, step: u32) -> u32 {
+        let key = Symbol::new(&env, "ticks");
+        let ticks = env.storage().instance().get(&key).unwrap_or(0u32) + step;
+        env.storage().instance().set(&key, &ticks);
+        ticks
+    }
+
+    pub fn fail(_env: Env) {
+        panic!("job failed");
+    }
+}
+
+#[test]
+fn test_keeper_paid_per_due_execution() {
+    let (env, _id, client, admin, _user, native) = setup_env_with_native_asset();
+    let keeper = Address::generate(&env);
+    StellarAssetClient::new(&env, &native).mint(&admin, &100);
+    client.fund_keeper_pool(&admin, &100);
+
+    let job = client.register_job(
+        &admin,
+        &JobTarget::AccrueInterest,
+        &Vec::new(&env),
+        &3_600,
+        &50,
+    );
+    assert_eq!(client.execute_job(&keeper, &job), 50);
+    assert_eq!(
+        client.try_execute_job(&keeper, &job),
+        Err(Ok(AutomationError::JobNotDue))
+    );
+
+    advance_time(&env, 3_600);
+    assert_eq!(client.execute_job(&keeper, &job), 50);
+
+    // The pool is dry: the job still runs, unpaid
+    advance_time(&env, 3_600);
+    assert_eq!(client.execute_job(&keeper, &job), 0);
+    assert_eq!(TokenClient::new(&env, &native).balance(&keeper), 100);
+    assert_eq!(client.get_keeper_pool_balance(), 0);
+
+    let record = client.get_job(&job).unwrap();
+    assert_eq!(record.executions, 3);
+    assert_eq!(record.last_executed, env.ledger().timestamp());
+    assert_eq!(record.last_keeper, Some(keeper));
+}
+
+#[test]
+fn test_contract_target_calls_with_stored_args() {
+    let (env, id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let keeper = Address::generate(&env);
+    let ticker = env.register(TickContract, ());
+
+    let mut args: Vec<Val> = Vec::new(&env);
+    args.push_back(5u32.into_val(&env));
+    let tick = client.register_job(
+        &admin,
+        &JobTarget::Contract(ticker.clone(), Symbol::new(&env, "tick")),
+        &args,
+        &60,
+        &0,
+    );
+    client.execute_job(&keeper, &tick);
+    advance_time(&env, 60);
+    client.execute_job(&keeper, &tick);
+    let ticks: u32 = env.as_contract(&ticker, || {
+        env.storage()
+            .instance()
+            .get(&Symbol::new(&env, "ticks"))
+            .unwrap()
+    });
+    assert_eq!(ticks, 10);
+
+    let fail = client.register_job(
+        &admin,
+        &JobTarget::Contract(ticker, Symbol::new(&env, "fail")),
+        &Vec::new(&env),
+        &60,
+        &0,
+    );
+    assert_eq!(
+        client.try_execute_job(&keeper, &fail),
+        Err(Ok(AutomationError::JobFailed))
+    );
+    assert_eq!(client.get_job(&fail).unwrap().executions, 0);
+
+    assert_eq!(
+        client.try_register_job(
+            &admin,
+            &JobTarget::Contract(id, Symbol::new(&env, "poke_rates")),
+            &Vec::new(&env),
+            &60,
+            &0,
+        ),
+        Err(Ok(AutomationError::InvalidJob))
+    );
+}
+
+#[test]
+fn test_pause_remove_and_admin_controls() {
+    let (env, id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let keeper = Address::generate(&env);
+    let job = client.register_job(&admin, &JobTarget::AccrueInterest, &Vec::new(&env), &60, &0);
+    assert_authorized(&env, &admin, &id, "register_job");
+
+    client.set_job_paused(&admin, &job, &true);
+    assert_authorized(&env, &admin, &id, "set_job_paused");
+    assert_eq!(
+        client.try_execute_job(&keeper, &job),
+        Err(Ok(AutomationError::JobPaused))
+    );
+    client.set_job_paused(&admin, &job, &false);
+    client.set_automation_paused(&admin, &true);
+    assert_authorized(&env, &admin, &id, "set_automation_paused");
+    assert!(client.is_automation_paused());
+    assert_eq!(
+        client.try_execute_job(&keeper, &job),
+        Err(Ok(AutomationError::AutomationPaused))
+    );
+    client.set_automation_paused(&admin, &false);
+    client.execute_job(&keeper, &job);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_register_job(
+            &stranger,
+            &JobTarget::AccrueInterest,
+            &Vec::new(&env),
+            &60,
+            &0,
+        ),
+        Err(Ok(AutomationError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_job_paused(&stranger, &job, &true),
+        Err(Ok(AutomationError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_register_job(&admin, &JobTarget::AccrueInterest, &Vec::new(&env), &0, &0,),
+        Err(Ok(AutomationError::InvalidJob))
+    );
+
+    client.remove_job(&admin, &job);
+    assert_authorized(&env, &admin, &id, "remove_job");
+    assert_eq!(client.get_jobs().len(), 0);
+    assert_eq!(
+        client.try_execute_job(&keeper, &job),
+        Err(Ok(AutomationError::JobNotFound))
+    );
+}
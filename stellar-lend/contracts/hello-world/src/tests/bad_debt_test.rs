@@ -0,0 +1,198 @@
+//! Bad debt and insurance reserve tests.
+//!
+//! # Coverage
+//! - Liquidating a borrower's last collateral books the remaining debt as bad debt
+//! - A slice of accrued interest funds the insurance reserve
+//! - Socialization draws on insurance first, then lowers the supply exchange rate
+//! - Losses beyond the supply pool stay recorded as a shortfall
+//! - Admin-only configuration and resolution
+
+use crate::bad_debt::{BadDebtError, BadDebtResolution};
+use crate::deposit::{
+    accrue_supply_interest, get_supply_pool, DepositDataKey, Position, SupplyPool,
+    EXCHANGE_RATE_SCALE,
+};
+use crate::liquidate::liquidate;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+fn setup(e: &Env) -> (Address, Address, HelloContractClient<'_>) {
+    e.mock_all_auths();
+    let id = e.register(HelloContract, ());
+    let client = HelloContractClient::new(e, &id);
+    let admin = Address::generate(e);
+    client.initialize(&admin);
+    (id, admin, client)
+}
+
+/// Write a native position directly
+fn set_position(e: &Env, id: &Address, user: &Address, collateral: i128, debt: i128) {
+    e.as_contract(id, || {
+        e.storage().persistent().set(
+            &DepositDataKey::CollateralBalance(user.clone()),
+            &collateral,
+        );
+        e.storage().persistent().set(
+            &DepositDataKey::Position(user.clone()),
+            &Position {
+                collateral,
+                debt,
+                borrow_interest: 0,
+                last_accrual_time: e.ledger().timestamp(),
+            },
+        );
+    });
+}
+
+fn set_supply_pool(e: &Env, id: &Address, underlying: i128) {
+    e.as_contract(id, || {
+        e.storage().persistent().set(
+            &DepositDataKey::SupplyPool,
+            &SupplyPool {
+                total_shares: underlying,
+                total_underlying: underlying,
+            },
+        );
+    });
+}
+
+fn supply_underlying(e: &Env, id: &Address) -> i128 {
+    e.as_contract(id, || get_supply_pool(e).total_underlying)
+}
+
+fn get_position(e: &Env, id: &Address, user: &Address) -> Position {
+    e.as_contract(id, || {
+        e.storage()
+            .persistent()
+            .get(&DepositDataKey::Position(user.clone()))
+            .unwrap()
+    })
+}
+
+/// Liquidate an underwater native position: all 500 collateral is seized
+/// against 500 of the 1_000 debt, leaving 500 of bad debt
+fn create_bad_debt(e: &Env, id: &Address) {
+    let liquidator = Address::generate(e);
+    let borrower = Address::generate(e);
+    set_position(e, id, &borrower, 500, 1_000);
+    e.as_contract(id, || {
+        liquidate(e, liquidator.clone(), borrower.clone(), None, None, 500).unwrap();
+    });
+}
+
+#[test]
+fn test_exhausted_collateral_records_bad_debt() {
+    let e = Env::default();
+    let (id, _admin, client) = setup(&e);
+    let liquidator = Address::generate(&e);
+    let borrower = Address::generate(&e);
+    set_position(&e, &id, &borrower, 500, 1_000);
+
+    let (debt_liquidated, collateral_seized, _) = e.as_contract(&id, || {
+        liquidate(&e, liquidator.clone(), borrower.clone(), None, None, 500).unwrap()
+    });
+    assert_eq!((debt_liquidated, collateral_seized), (500, 500));
+
+    let position = get_position(&e, &id, &borrower);
+    assert_eq!(position.collateral, 0);
+    assert_eq!(position.debt, 0);
+    assert_eq!(client.get_bad_debt(&None), 500);
+}
+
+#[test]
+fn test_partial_liquidation_records_no_bad_debt() {
+    let e = Env::default();
+    let (id, _admin, client) = setup(&e);
+    let liquidator = Address::generate(&e);
+    let borrower = Address::generate(&e);
+    set_position(&e, &id, &borrower, 1_000, 1_000);
+
+    e.as_contract(&id, || {
+        liquidate(&e, liquidator.clone(), borrower.clone(), None, None, 500).unwrap();
+    });
+
+    assert_eq!(get_position(&e, &id, &borrower).debt, 500);
+    assert_eq!(client.get_bad_debt(&None), 0);
+}
+
+#[test]
+fn test_interest_slice_funds_insurance() {
+    let e = Env::default();
+    let (id, admin, client) = setup(&e);
+    set_supply_pool(&e, &id, 10_000);
+    client.set_insurance_factor(&admin, &2_000);
+    assert_eq!(client.get_insurance_factor(), 2_000);
+
+    // 10% reserve factor, 20% insurance, 70% to suppliers
+    e.as_contract(&id, || accrue_supply_interest(&e, &None, 1_000));
+    assert_eq!(client.get_insurance_reserve(), 200);
+    assert_eq!(supply_underlying(&e, &id), 10_700);
+}
+
+#[test]
+fn test_socialize_uses_insurance_then_suppliers() {
+    let e = Env::default();
+    let (id, admin, client) = setup(&e);
+    set_supply_pool(&e, &id, 10_000);
+    client.set_insurance_factor(&admin, &2_000);
+    e.as_contract(&id, || accrue_supply_interest(&e, &None, 1_000));
+    create_bad_debt(&e, &id);
+
+    let rate_before = client.get_exchange_rate();
+    let resolution = client.socialize_bad_debt(&admin, &None);
+    assert_eq!(
+        resolution,
+        BadDebtResolution {
+            covered_by_insurance: 200,
+            socialized: 300,
+        }
+    );
+
+    assert_eq!(client.get_bad_debt(&None), 0);
+    assert_eq!(client.get_insurance_reserve(), 0);
+    assert_eq!(supply_underlying(&e, &id), 10_400);
+    let rate_after = client.get_exchange_rate();
+    assert!(rate_after < rate_before);
+    assert_eq!(rate_after, 10_400 * EXCHANGE_RATE_SCALE / 10_000);
+}
+
+#[test]
+fn test_loss_beyond_supply_pool_stays_recorded() {
+    let e = Env::default();
+    let (id, admin, client) = setup(&e);
+    set_supply_pool(&e, &id, 200);
+    create_bad_debt(&e, &id);
+
+    let resolution = client.socialize_bad_debt(&admin, &None);
+    assert_eq!(resolution.covered_by_insurance, 0);
+    assert_eq!(resolution.socialized, 200);
+    assert_eq!(supply_underlying(&e, &id), 0);
+    assert_eq!(client.get_bad_debt(&None), 300);
+}
+
+#[test]
+fn test_admin_only_and_validation() {
+    let e = Env::default();
+    let (id, admin, client) = setup(&e);
+    let stranger = Address::generate(&e);
+
+    assert_eq!(
+        client.try_socialize_bad_debt(&admin, &None),
+        Err(Ok(BadDebtError::NoBadDebt))
+    );
+    assert_eq!(
+        client.try_set_insurance_factor(&admin, &5_001),
+        Err(Ok(BadDebtError::InvalidInsuranceFactor))
+    );
+    assert_eq!(
+        client.try_set_insurance_factor(&stranger, &1_000),
+        Err(Ok(BadDebtError::Unauthorized))
+    );
+
+    create_bad_debt(&e, &id);
+    assert_eq!(
+        client.try_socialize_bad_debt(&stranger, &None),
+        Err(Ok(BadDebtError::Unauthorized))
+    );
+    assert_eq!(client.get_bad_debt(&None), 500);
+}
@@ -0,0 +1,142 @@
+//! Batch operation tests.
+//!
+//! # Coverage
+//! - Deposit + borrow and repay + withdraw run in one call, in order
+//! - A successful batch emits one aggregated `BatchExecutedEvent`
+//! - A failing action rolls back the whole batch
+//! - Batch size bounds
+
+use crate::batch::{BatchError, ProtocolAction, MAX_BATCH_ACTIONS};
+use crate::tests::test_helpers::setup_env_with_native_asset;
+use soroban_sdk::{
+    contracttype, testutils::Events, token::StellarAssetClient, vec, Address, Env, TryFromVal, Vec,
+};
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestBatchExecutedEvent {
+    pub user: Address,
+    pub actions: u32,
+    pub deposited: i128,
+    pub borrowed: i128,
+    pub repaid: i128,
+    pub withdrawn: i128,
+    pub timestamp: u64,
+}
+
+fn batch_events(env: &Env) -> Vec<TestBatchExecutedEvent> {
+    let mut events = Vec::new(env);
+    for (_contract, _topics, data) in env.events().all().iter() {
+        if let Ok(event) = TestBatchExecutedEvent::try_from_val(env, &data) {
+            events.push_back(event);
+        }
+    }
+    events
+}
+
+#[test]
+fn test_deposit_and_borrow_in_one_batch() {
+    let (env, _id, client, _admin, user, _native) = setup_env_with_native_asset();
+    let results = client.execute_batch(
+        &user,
+        &vec![
+            &env,
+            ProtocolAction::Deposit(None, 20_000),
+            ProtocolAction::Borrow(None, 5_000),
+        ],
+    );
+    assert_eq!(results, vec![&env, 20_000, 5_000]);
+
+    let events = batch_events(&env);
+    assert_eq!(events.len(), 1);
+    assert_eq!(
+        events.get_unchecked(0),
+        TestBatchExecutedEvent {
+            user: user.clone(),
+            actions: 2,
+            deposited: 20_000,
+            borrowed: 5_000,
+            repaid: 0,
+            withdrawn: 0,
+            timestamp: env.ledger().timestamp(),
+        }
+    );
+
+    let position = client.get_user_report(&user).position;
+    assert_eq!(position.collateral, 20_000);
+    assert_eq!(position.debt, 5_000);
+}
+
+#[test]
+fn test_repay_and_withdraw_in_one_batch() {
+    let (env, id, client, _admin, user, native) = setup_env_with_native_asset();
+    client.deposit_collateral(&user, &None, &20_000);
+    client.borrow_asset(&user, &None, &5_000);
+    let token = StellarAssetClient::new(&env, &native);
+    token.mint(&user, &5_000);
+    token.approve(&user, &id, &5_000, &(env.ledger().sequence() + 100));
+
+    let results = client.execute_batch(
+        &user,
+        &vec![
+            &env,
+            ProtocolAction::Repay(None, 5_000),
+            ProtocolAction::Withdraw(None, 20_000),
+        ],
+    );
+    assert_eq!(results, vec![&env, 0, 0]);
+
+    let event = batch_events(&env).get_unchecked(0);
+    assert_eq!(event.repaid, 5_000);
+    assert_eq!(event.withdrawn, 20_000);
+    let position = client.get_user_report(&user).position;
+    assert_eq!(position.collateral, 0);
+    assert_eq!(position.debt, 0);
+}
+
+#[test]
+fn test_failing_action_rolls_back_batch() {
+    let (env, _id, client, _admin, user, _native) = setup_env_with_native_asset();
+    // Borrowing the full deposit breaks the collateral ratio
+    assert_eq!(
+        client.try_execute_batch(
+            &user,
+            &vec![
+                &env,
+                ProtocolAction::Deposit(None, 10_000),
+                ProtocolAction::Borrow(None, 10_000),
+            ],
+        ),
+        Err(Ok(BatchError::BorrowFailed))
+    );
+    assert_eq!(client.get_user_report(&user).position.collateral, 0);
+
+    assert_eq!(
+        client.try_execute_batch(&user, &vec![&env, ProtocolAction::Withdraw(None, 1)]),
+        Err(Ok(BatchError::WithdrawFailed))
+    );
+}
+
+#[test]
+fn test_batch_size_bounds() {
+    let (env, _id, client, _admin, user, _native) = setup_env_with_native_asset();
+    assert_eq!(
+        client.try_execute_batch(&user, &Vec::new(&env)),
+        Err(Ok(BatchError::EmptyBatch))
+    );
+
+    let mut actions = Vec::new(&env);
+    for _ in 0..=MAX_BATCH_ACTIONS {
+        actions.push_back(ProtocolAction::Deposit(None, 100));
+    }
+    assert_eq!(
+        client.try_execute_batch(&user, &actions),
+        Err(Ok(BatchError::TooManyActions))
+    );
+
+    actions.pop_back();
+    assert_eq!(
+        client.execute_batch(&user, &actions).len(),
+        MAX_BATCH_ACTIONS
+    );
+}
@@ -0,0 +1,121 @@
+//! Bridge fee quote tests.
+//!
+//! # Coverage
+//! - Quotes split the fee between the protocol and relayers
+//! - Deposits deduct exactly the quoted fee and emit its breakdown
+//! - Relayer shares are bounded and need a registered bridge
+//! - Quotes for unknown bridges, unsupported assets and zero amounts fail
+
+use crate::bridge::{BridgeError, BridgeFeeQuote};
+use crate::cross_asset::{initialize as init_cross_asset, initialize_asset, AssetConfig};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events},
+    Address, Env, IntoVal, TryFromVal, Val, Vec,
+};
+
+const NETWORK: u32 = 1;
+
+fn setup() -> (Env, HelloContractClient<'static>, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let asset = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        init_cross_asset(&env, admin.clone()).unwrap();
+        let config = AssetConfig {
+            asset: Some(asset.clone()),
+            collateral_factor: 7500,
+            liquidation_threshold: 8000,
+            reserve_factor: 1000,
+            max_supply: 0,
+            max_borrow: 0,
+            can_collateralize: true,
+            can_borrow: true,
+            price: 10_000_000,
+            price_updated_at: env.ledger().timestamp(),
+            is_isolated: false,
+            isolated_debt_ceiling: 0,
+        };
+        initialize_asset(&env, Some(asset.clone()), config).unwrap();
+    });
+
+    client.register_bridge(&admin, &NETWORK, &Address::generate(&env), &100); // 1% fee
+
+    (env, client, admin, asset)
+}
+
+#[test]
+fn test_quote_matches_deposit() {
+    let (env, client, admin, asset) = setup();
+    let user = Address::generate(&env);
+    client.set_bridge_relayer_share(&admin, &NETWORK, &2_500);
+
+    let quote = client.quote_bridge_fee(&NETWORK, &Some(asset.clone()), &10_000);
+    assert_eq!(
+        quote,
+        BridgeFeeQuote {
+            amount: 10_000,
+            total_fee: 100,
+            protocol_fee: 75,
+            relayer_fee: 25,
+            net_amount: 9_900,
+        }
+    );
+
+    assert_eq!(
+        client.bridge_deposit(&user, &NETWORK, &Some(asset), &10_000),
+        quote.net_amount
+    );
+    let topics: Vec<Val> =
+        (symbol_short!("bridge"), symbol_short!("deposit"), NETWORK).into_val(&env);
+    let breakdown = env
+        .events()
+        .all()
+        .iter()
+        .find(|(_contract, event_topics, _data)| *event_topics == topics)
+        .map(|(_contract, _topics, data)| {
+            <(Address, i128, i128, i128, i128)>::try_from_val(&env, &data).unwrap()
+        });
+    assert_eq!(breakdown, Some((user, 9_900, 100, 75, 25)));
+}
+
+#[test]
+fn test_relayer_share_bounds() {
+    let (_env, client, admin, asset) = setup();
+    assert_eq!(client.get_bridge_relayer_share(&NETWORK), 0);
+    assert_eq!(
+        client.try_set_bridge_relayer_share(&admin, &NETWORK, &10_001),
+        Err(Ok(BridgeError::InvalidFee))
+    );
+    assert_eq!(
+        client.try_set_bridge_relayer_share(&admin, &99, &1_000),
+        Err(Ok(BridgeError::BridgeNotFound))
+    );
+
+    // Without a relayer share the protocol keeps the whole fee
+    let quote = client.quote_bridge_fee(&NETWORK, &Some(asset), &5_000);
+    assert_eq!((quote.protocol_fee, quote.relayer_fee), (50, 0));
+}
+
+#[test]
+fn test_invalid_quotes_rejected() {
+    let (env, client, _admin, asset) = setup();
+    assert_eq!(
+        client.try_quote_bridge_fee(&99, &Some(asset.clone()), &1_000),
+        Err(Ok(BridgeError::BridgeNotFound))
+    );
+    assert_eq!(
+        client.try_quote_bridge_fee(&NETWORK, &Some(Address::generate(&env)), &1_000),
+        Err(Ok(BridgeError::AssetNotSupported))
+    );
+    assert_eq!(
+        client.try_quote_bridge_fee(&NETWORK, &Some(asset), &0),
+        Err(Ok(BridgeError::InvalidAmount))
+    );
+}
@@ -0,0 +1,154 @@
+//! Inbound bridge message queue tests.
+//!
+//! # Coverage
+//! - Only the registered bridge contract can enqueue; nonces are sequential
+//! - Messages are applied in nonce order, net of the bridge fee
+//! - `max` bounds each batch and leftovers stay pending
+//! - A failing message is marked `Failed` and skipped without blocking the queue
+//! - Invalid batch sizes and unknown bridges are rejected
+
+use crate::bridge::{BridgeError, BridgeMessageStatus, MAX_MESSAGES_PER_BATCH};
+use crate::cross_asset::{initialize as init_cross_asset, initialize_asset, AssetConfig};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+const NETWORK: u32 = 1;
+
+fn setup() -> (Env, HelloContractClient<'static>, Address, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let bridge = Address::generate(&env);
+    let asset = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        init_cross_asset(&env, admin.clone()).unwrap();
+        let config = AssetConfig {
+            asset: Some(asset.clone()),
+            collateral_factor: 7500,
+            liquidation_threshold: 8000,
+            reserve_factor: 1000,
+            max_supply: 25_000,
+            max_borrow: 0,
+            can_collateralize: true,
+            can_borrow: true,
+            price: 10_000_000,
+            price_updated_at: env.ledger().timestamp(),
+            is_isolated: false,
+            isolated_debt_ceiling: 0,
+        };
+        initialize_asset(&env, Some(asset.clone()), config).unwrap();
+    });
+
+    client.register_bridge(&admin, &NETWORK, &bridge, &100); // 1% fee
+
+    (env, client, admin, bridge, asset)
+}
+
+#[test]
+fn test_enqueue_assigns_sequential_nonces() {
+    let (env, client, _admin, bridge, asset) = setup();
+    let user = Address::generate(&env);
+
+    let first =
+        client.enqueue_bridge_message(&bridge, &NETWORK, &user, &Some(asset.clone()), &1_000);
+    let second = client.enqueue_bridge_message(&bridge, &NETWORK, &user, &Some(asset), &2_000);
+
+    assert_eq!(first, 0);
+    assert_eq!(second, 1);
+    let state = client.get_bridge_queue_state(&NETWORK);
+    assert_eq!(state.head, 0);
+    assert_eq!(state.tail, 2);
+    assert_eq!(
+        client.get_bridge_message(&NETWORK, &1).unwrap().status,
+        BridgeMessageStatus::Pending
+    );
+}
+
+#[test]
+fn test_only_bridge_contract_can_enqueue() {
+    let (env, client, _admin, _bridge, asset) = setup();
+    let impostor = Address::generate(&env);
+
+    assert_eq!(
+        client.try_enqueue_bridge_message(&impostor, &NETWORK, &impostor, &Some(asset), &1_000),
+        Err(Ok(BridgeError::NotAuthorized))
+    );
+}
+
+#[test]
+fn test_process_in_order_with_batch_limit() {
+    let (env, client, _admin, bridge, asset) = setup();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.enqueue_bridge_message(&bridge, &NETWORK, &alice, &Some(asset.clone()), &10_000);
+    client.enqueue_bridge_message(&bridge, &NETWORK, &bob, &Some(asset.clone()), &5_000);
+    client.enqueue_bridge_message(&bridge, &NETWORK, &alice, &Some(asset.clone()), &1_000);
+
+    let result = client.process_bridge_messages(&NETWORK, &2);
+    assert_eq!(result.processed, 2);
+    assert_eq!(result.failed, 0);
+    assert_eq!(result.remaining, 1);
+    assert_eq!(
+        client.get_bridge_message(&NETWORK, &2).unwrap().status,
+        BridgeMessageStatus::Pending
+    );
+
+    let result = client.process_bridge_messages(&NETWORK, &10);
+    assert_eq!(result.processed, 1);
+    assert_eq!(result.remaining, 0);
+
+    let alice_position = client.get_user_asset_position(&alice, &Some(asset.clone()));
+    let bob_position = client.get_user_asset_position(&bob, &Some(asset));
+    assert_eq!(alice_position.collateral, 9_900 + 990);
+    assert_eq!(bob_position.collateral, 4_950);
+}
+
+#[test]
+fn test_failed_message_is_skipped() {
+    let (env, client, _admin, bridge, asset) = setup();
+    let user = Address::generate(&env);
+
+    // Second message breaches the 25,000 supply cap
+    client.enqueue_bridge_message(&bridge, &NETWORK, &user, &Some(asset.clone()), &10_000);
+    client.enqueue_bridge_message(&bridge, &NETWORK, &user, &Some(asset.clone()), &20_000);
+    client.enqueue_bridge_message(&bridge, &NETWORK, &user, &Some(asset.clone()), &1_000);
+
+    let result = client.process_bridge_messages(&NETWORK, &10);
+    assert_eq!(result.processed, 2);
+    assert_eq!(result.failed, 1);
+    assert_eq!(result.remaining, 0);
+
+    assert_eq!(
+        client.get_bridge_message(&NETWORK, &1).unwrap().status,
+        BridgeMessageStatus::Failed
+    );
+    assert_eq!(
+        client.get_bridge_message(&NETWORK, &2).unwrap().status,
+        BridgeMessageStatus::Processed
+    );
+    let position = client.get_user_asset_position(&user, &Some(asset));
+    assert_eq!(position.collateral, 9_900 + 990);
+}
+
+#[test]
+fn test_invalid_batch_size_rejected() {
+    let (_env, client, _admin, _bridge, _asset) = setup();
+
+    assert_eq!(
+        client.try_process_bridge_messages(&NETWORK, &0),
+        Err(Ok(BridgeError::InvalidBatchSize))
+    );
+    assert_eq!(
+        client.try_process_bridge_messages(&NETWORK, &(MAX_MESSAGES_PER_BATCH + 1)),
+        Err(Ok(BridgeError::InvalidBatchSize))
+    );
+    assert_eq!(
+        client.try_process_bridge_messages(&99, &1),
+        Err(Ok(BridgeError::BridgeNotFound))
+    );
+}
@@ -0,0 +1,178 @@
+//! Bridge outflow rate limit tests.
+//!
+//! # Coverage
+//! - Withdrawals over the network or per-asset epoch cap are rejected
+//! - Utilization views report usage, remaining headroom and the epoch end
+//! - Filling a cap pauses outflows until the admin resumes them
+//! - Usage resets at the next epoch
+//! - Only the admin sets caps or pauses outflows; negative caps are rejected
+
+use crate::bridge::{BridgeError, BRIDGE_EPOCH_SECONDS};
+use crate::cross_asset::{initialize as init_cross_asset, initialize_asset, AssetConfig};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env};
+use stellarlend_testutils::{advance_time, set_time};
+
+const NETWORK: u32 = 1;
+const PRICE: i128 = 10_000_000;
+
+/// Fee-free bridge and a user holding 100_000 of each of two assets
+fn setup() -> (
+    Env,
+    HelloContractClient<'static>,
+    Address,
+    Address,
+    Address,
+    Address,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_time(&env, BRIDGE_EPOCH_SECONDS);
+
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let asset_a = Address::generate(&env);
+    let asset_b = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        init_cross_asset(&env, admin.clone()).unwrap();
+        for asset in [&asset_a, &asset_b] {
+            let config = AssetConfig {
+                asset: Some(asset.clone()),
+                collateral_factor: 7500,
+                liquidation_threshold: 8000,
+                reserve_factor: 1000,
+                max_supply: 1_000_000,
+                max_borrow: 0,
+                can_collateralize: true,
+                can_borrow: true,
+                price: PRICE,
+                price_updated_at: env.ledger().timestamp(),
+                is_isolated: false,
+                isolated_debt_ceiling: 0,
+            };
+            initialize_asset(&env, Some(asset.clone()), config).unwrap();
+        }
+    });
+
+    client.register_bridge(&admin, &NETWORK, &Address::generate(&env), &0);
+    client.bridge_deposit(&user, &NETWORK, &Some(asset_a.clone()), &100_000);
+    client.bridge_deposit(&user, &NETWORK, &Some(asset_b.clone()), &100_000);
+
+    (env, client, admin, user, asset_a, asset_b)
+}
+
+#[test]
+fn test_network_cap_rejects_excess_and_reports_utilization() {
+    let (_env, client, admin, user, asset_a, asset_b) = setup();
+    client.set_bridge_outflow_cap(&admin, &NETWORK, &Some(10_000));
+
+    client.bridge_withdraw(&user, &NETWORK, &Some(asset_a.clone()), &3_000);
+    client.bridge_withdraw(&user, &NETWORK, &Some(asset_b.clone()), &1_000);
+
+    let utilization = client.get_bridge_utilization(&NETWORK);
+    assert_eq!(utilization.cap, Some(10_000));
+    assert_eq!(utilization.used, 4_000);
+    assert_eq!(utilization.remaining, Some(6_000));
+    assert_eq!(utilization.utilization_bps, 4_000);
+    assert_eq!(utilization.window_end, 2 * BRIDGE_EPOCH_SECONDS);
+
+    assert_eq!(
+        client.try_bridge_withdraw(&user, &NETWORK, &Some(asset_a.clone()), &6_001),
+        Err(Ok(BridgeError::RateLimitExceeded))
+    );
+    assert_eq!(client.get_bridge_utilization(&NETWORK).used, 4_000);
+    assert_eq!(
+        client
+            .get_bridge_asset_utilization(&NETWORK, &Some(asset_a))
+            .used,
+        3_000
+    );
+    assert!(!client.is_bridge_outflow_paused(&NETWORK));
+}
+
+#[test]
+fn test_asset_cap_applies_to_that_asset_only() {
+    let (_env, client, admin, user, asset_a, asset_b) = setup();
+    client.set_bridge_asset_outflow_cap(&admin, &NETWORK, &Some(asset_a.clone()), &Some(5_000));
+
+    assert_eq!(
+        client.try_bridge_withdraw(&user, &NETWORK, &Some(asset_a.clone()), &5_001),
+        Err(Ok(BridgeError::RateLimitExceeded))
+    );
+    client.bridge_withdraw(&user, &NETWORK, &Some(asset_b.clone()), &20_000);
+    client.bridge_withdraw(&user, &NETWORK, &Some(asset_a.clone()), &2_000);
+
+    let utilization = client.get_bridge_asset_utilization(&NETWORK, &Some(asset_a));
+    assert_eq!(utilization.used, 2_000);
+    assert_eq!(utilization.remaining, Some(3_000));
+    let unlimited = client.get_bridge_asset_utilization(&NETWORK, &Some(asset_b));
+    assert_eq!(unlimited.cap, None);
+    assert_eq!(unlimited.remaining, None);
+    assert_eq!(unlimited.utilization_bps, 0);
+}
+
+#[test]
+fn test_filling_cap_pauses_outflows_until_resumed() {
+    let (env, client, admin, user, asset_a, asset_b) = setup();
+    client.set_bridge_outflow_cap(&admin, &NETWORK, &Some(10_000));
+
+    client.bridge_withdraw(&user, &NETWORK, &Some(asset_a.clone()), &10_000);
+    assert!(client.is_bridge_outflow_paused(&NETWORK));
+    assert_eq!(
+        client.try_bridge_withdraw(&user, &NETWORK, &Some(asset_a.clone()), &1),
+        Err(Ok(BridgeError::OutflowsPaused))
+    );
+
+    // A new epoch resets usage but not the pause
+    advance_time(&env, BRIDGE_EPOCH_SECONDS);
+    client.update_asset_price(&Some(asset_a.clone()), &PRICE);
+    client.update_asset_price(&Some(asset_b), &PRICE);
+    assert_eq!(client.get_bridge_utilization(&NETWORK).used, 0);
+    assert_eq!(
+        client.try_bridge_withdraw(&user, &NETWORK, &Some(asset_a.clone()), &1),
+        Err(Ok(BridgeError::OutflowsPaused))
+    );
+
+    client.set_bridge_outflows_paused(&admin, &NETWORK, &false);
+    client.bridge_withdraw(&user, &NETWORK, &Some(asset_a), &4_000);
+    assert_eq!(client.get_bridge_utilization(&NETWORK).used, 4_000);
+}
+
+#[test]
+fn test_rate_limit_admin_checks() {
+    let (_env, client, admin, user, asset_a, _asset_b) = setup();
+
+    assert_eq!(
+        client.try_set_bridge_outflow_cap(&user, &NETWORK, &Some(1_000)),
+        Err(Ok(BridgeError::NotAuthorized))
+    );
+    assert_eq!(
+        client.try_set_bridge_outflow_cap(&admin, &NETWORK, &Some(-1)),
+        Err(Ok(BridgeError::InvalidRateLimit))
+    );
+    assert_eq!(
+        client.try_set_bridge_asset_outflow_cap(&admin, &99, &Some(asset_a.clone()), &Some(1)),
+        Err(Ok(BridgeError::BridgeNotFound))
+    );
+    assert_eq!(
+        client.try_set_bridge_outflows_paused(&user, &NETWORK, &true),
+        Err(Ok(BridgeError::NotAuthorized))
+    );
+
+    // A manual pause blocks withdrawals even without caps
+    client.set_bridge_outflows_paused(&admin, &NETWORK, &true);
+    assert_eq!(
+        client.try_bridge_withdraw(&user, &NETWORK, &Some(asset_a.clone()), &1),
+        Err(Ok(BridgeError::OutflowsPaused))
+    );
+
+    // Clearing a cap makes the bridge unlimited again
+    client.set_bridge_outflows_paused(&admin, &NETWORK, &false);
+    client.set_bridge_outflow_cap(&admin, &NETWORK, &Some(1_000));
+    client.set_bridge_outflow_cap(&admin, &NETWORK, &None);
+    client.bridge_withdraw(&user, &NETWORK, &Some(asset_a), &5_000);
+    assert_eq!(client.get_bridge_utilization(&NETWORK).cap, None);
+}
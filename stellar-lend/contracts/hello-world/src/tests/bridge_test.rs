@@ -100,6 +100,8 @@ fn test_bridge_deposit_withdraw() {
             can_borrow: true,
             price: 1_000_000,
             price_updated_at: env.ledger().timestamp(),
+            is_isolated: false,
+            isolated_debt_ceiling: 0,
         };
         initialize_asset(&env, Some(asset.clone()), config).unwrap();
     });
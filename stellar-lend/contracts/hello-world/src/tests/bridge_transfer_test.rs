@@ -0,0 +1,164 @@
+//! Outbound bridge transfer lifecycle tests.
+//!
+//! # Coverage
+//! - `bridge_withdraw` creates a pending transfer listed per bridge and per user
+//! - Only the bridge's relayer completes a transfer; it then leaves the pending list
+//! - The user refunds an uncompleted transfer after the timeout, restoring collateral
+//! - Closed transfers cannot change state again
+//! - User history keeps the most recent `MAX_USER_TRANSFER_HISTORY` transfers
+
+use crate::bridge::{
+    BridgeError, BridgeTransferStatus, BRIDGE_TRANSFER_TIMEOUT, MAX_USER_TRANSFER_HISTORY,
+};
+use crate::cross_asset::{initialize as init_cross_asset, initialize_asset, AssetConfig};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env};
+use stellarlend_testutils::{advance_time, set_time};
+
+const NETWORK: u32 = 1;
+
+/// Bridge with a 1% fee and a user holding 99_000 collateral
+fn setup() -> (Env, HelloContractClient<'static>, Address, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let relayer = Address::generate(&env);
+    let user = Address::generate(&env);
+    let asset = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        init_cross_asset(&env, admin.clone()).unwrap();
+        let config = AssetConfig {
+            asset: Some(asset.clone()),
+            collateral_factor: 7500,
+            liquidation_threshold: 8000,
+            reserve_factor: 1000,
+            max_supply: 1_000_000,
+            max_borrow: 0,
+            can_collateralize: true,
+            can_borrow: true,
+            price: 10_000_000,
+            price_updated_at: env.ledger().timestamp(),
+            is_isolated: false,
+            isolated_debt_ceiling: 0,
+        };
+        initialize_asset(&env, Some(asset.clone()), config).unwrap();
+    });
+
+    client.register_bridge(&admin, &NETWORK, &relayer, &100);
+    client.bridge_deposit(&user, &NETWORK, &Some(asset.clone()), &100_000);
+
+    (env, client, relayer, user, asset)
+}
+
+#[test]
+fn test_relayer_completes_pending_transfer() {
+    let (env, client, relayer, user, asset) = setup();
+
+    assert_eq!(
+        client.bridge_withdraw(&user, &NETWORK, &Some(asset.clone()), &5_000),
+        4_950
+    );
+    client.bridge_withdraw(&user, &NETWORK, &Some(asset.clone()), &1_000);
+
+    let pending = client.list_pending_transfers(&NETWORK);
+    assert_eq!(pending.len(), 2);
+    let transfer = pending.get(0).unwrap();
+    assert_eq!(transfer.id, 1);
+    assert_eq!(transfer.user, user);
+    assert_eq!(transfer.asset, Some(asset));
+    assert_eq!(transfer.quote.net_amount, 4_950);
+    assert_eq!(transfer.status, BridgeTransferStatus::Pending);
+    assert_eq!(transfer.refundable_at, 1_000 + BRIDGE_TRANSFER_TIMEOUT);
+
+    assert_eq!(
+        client.try_complete_bridge_transfer(&user, &1),
+        Err(Ok(BridgeError::NotAuthorized))
+    );
+    assert_eq!(client.complete_bridge_transfer(&relayer, &1), 4_950);
+
+    let pending = client.list_pending_transfers(&NETWORK);
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending.get(0).unwrap().id, 2);
+
+    // History keeps closed transfers alongside pending ones
+    let history = client.get_user_transfers(&user);
+    assert_eq!(history.len(), 2);
+    assert_eq!(
+        history.get(0).unwrap().status,
+        BridgeTransferStatus::Completed
+    );
+    assert!(client
+        .get_user_transfers(&Address::generate(&env))
+        .is_empty());
+}
+
+#[test]
+fn test_user_refunds_expired_transfer() {
+    let (env, client, relayer, user, asset) = setup();
+    let position = || {
+        client
+            .get_user_asset_position(&user, &Some(asset.clone()))
+            .collateral
+    };
+    let before = position();
+    client.bridge_withdraw(&user, &NETWORK, &Some(asset.clone()), &5_000);
+    assert_eq!(position(), before - 5_000);
+
+    assert_eq!(
+        client.try_refund_bridge_transfer(&user, &1),
+        Err(Ok(BridgeError::TransferNotExpired))
+    );
+    advance_time(&env, BRIDGE_TRANSFER_TIMEOUT);
+    assert_eq!(
+        client.try_refund_bridge_transfer(&relayer, &1),
+        Err(Ok(BridgeError::NotAuthorized))
+    );
+
+    // The gross amount comes back; no fee is taken
+    assert_eq!(client.refund_bridge_transfer(&user, &1), 5_000);
+    assert_eq!(position(), before);
+    assert_eq!(
+        client.get_bridge_transfer(&1).unwrap().status,
+        BridgeTransferStatus::Refunded
+    );
+    assert!(client.list_pending_transfers(&NETWORK).is_empty());
+
+    assert_eq!(
+        client.try_complete_bridge_transfer(&relayer, &1),
+        Err(Ok(BridgeError::TransferNotPending))
+    );
+    assert_eq!(
+        client.try_refund_bridge_transfer(&user, &1),
+        Err(Ok(BridgeError::TransferNotPending))
+    );
+    assert_eq!(
+        client.try_refund_bridge_transfer(&user, &2),
+        Err(Ok(BridgeError::TransferNotFound))
+    );
+}
+
+#[test]
+fn test_user_history_keeps_most_recent_transfers() {
+    let (_env, client, relayer, user, asset) = setup();
+
+    for _ in 0..=MAX_USER_TRANSFER_HISTORY {
+        client.bridge_withdraw(&user, &NETWORK, &Some(asset.clone()), &100);
+    }
+    for id in 1..=u64::from(MAX_USER_TRANSFER_HISTORY) + 1 {
+        client.complete_bridge_transfer(&relayer, &id);
+    }
+
+    let history = client.get_user_transfers(&user);
+    assert_eq!(history.len(), MAX_USER_TRANSFER_HISTORY);
+    assert_eq!(history.get(0).unwrap().id, 2);
+    assert_eq!(
+        history.last().unwrap().id,
+        u64::from(MAX_USER_TRANSFER_HISTORY) + 1
+    );
+    assert!(client.list_pending_transfers(&NETWORK).is_empty());
+}
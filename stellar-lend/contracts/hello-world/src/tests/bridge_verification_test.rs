@@ -0,0 +1,323 @@
+//! Bridge message verification and withdrawal challenge window tests.
+//!
+//! # Coverage
+//! - Validator set registration and its validation
+//! - Inbound messages wait in the queue until they reach the attestation threshold
+//! - Attestations from validators removed from the set stop counting
+//! - Direct bridge deposits are refused once a validator set exists
+//! - Withdrawals are held for the challenge window, then completed by the relayer
+//! - Guardians can veto a pending withdrawal, restoring the user's collateral
+
+use crate::bridge::{BridgeError, BridgeMessageStatus, BridgeTransferStatus, MAX_CHALLENGE_WINDOW};
+use crate::cross_asset::{initialize as init_cross_asset, initialize_asset, AssetConfig};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, vec, Address, Env};
+use stellarlend_testutils::{advance_time, set_time};
+
+const NETWORK: u32 = 1;
+const WINDOW: u64 = 1_800;
+
+struct Setup<'a> {
+    env: Env,
+    client: HelloContractClient<'a>,
+    admin: Address,
+    bridge: Address,
+    guardian: Address,
+    asset: Address,
+    user: Address,
+}
+
+/// Bridge with a 1% fee, one guardian and a user holding 99_000 collateral
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let bridge = Address::generate(&env);
+    let asset = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        init_cross_asset(&env, admin.clone()).unwrap();
+        let config = AssetConfig {
+            asset: Some(asset.clone()),
+            collateral_factor: 7500,
+            liquidation_threshold: 8000,
+            reserve_factor: 1000,
+            max_supply: 1_000_000,
+            max_borrow: 0,
+            can_collateralize: true,
+            can_borrow: true,
+            price: 10_000_000,
+            price_updated_at: env.ledger().timestamp(),
+            is_isolated: false,
+            isolated_debt_ceiling: 0,
+        };
+        initialize_asset(&env, Some(asset.clone()), config).unwrap();
+    });
+
+    let vote_token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    client.gov_initialize(
+        &admin,
+        &vote_token,
+        &None,
+        &None,
+        &None,
+        &None,
+        &None,
+        &None,
+    );
+    let guardian = Address::generate(&env);
+    client.gov_add_guardian(&admin, &guardian);
+
+    client.register_bridge(&admin, &NETWORK, &bridge, &100);
+    client.bridge_deposit(&user, &NETWORK, &Some(asset.clone()), &100_000);
+
+    Setup {
+        env,
+        client,
+        admin,
+        bridge,
+        guardian,
+        asset,
+        user,
+    }
+}
+
+fn collateral(s: &Setup) -> i128 {
+    s.client
+        .get_user_asset_position(&s.user, &Some(s.asset.clone()))
+        .collateral
+}
+
+#[test]
+fn test_register_validators_validation() {
+    let s = setup();
+    let (v1, v2) = (Address::generate(&s.env), Address::generate(&s.env));
+
+    for (validators, threshold) in [
+        (vec![&s.env, v1.clone(), v2.clone()], 0),
+        (vec![&s.env, v1.clone(), v2.clone()], 3),
+        (vec![&s.env, v1.clone(), v1.clone()], 1),
+    ] {
+        assert_eq!(
+            s.client
+                .try_register_bridge_validators(&s.admin, &NETWORK, &validators, &threshold),
+            Err(Ok(BridgeError::InvalidValidatorSet))
+        );
+    }
+    let validators = vec![&s.env, v1.clone(), v2.clone()];
+    assert_eq!(
+        s.client
+            .try_register_bridge_validators(&s.admin, &99, &validators, &1),
+        Err(Ok(BridgeError::BridgeNotFound))
+    );
+    assert_eq!(
+        s.client
+            .try_register_bridge_validators(&v1, &NETWORK, &validators, &1),
+        Err(Ok(BridgeError::NotAuthorized))
+    );
+
+    s.client
+        .register_bridge_validators(&s.admin, &NETWORK, &validators, &2);
+    let set = s.client.get_bridge_validators(&NETWORK).unwrap();
+    assert_eq!(set.validators, validators);
+    assert_eq!(set.threshold, 2);
+
+    // An empty set with a zero threshold removes it
+    s.client
+        .register_bridge_validators(&s.admin, &NETWORK, &vec![&s.env], &0);
+    assert_eq!(s.client.get_bridge_validators(&NETWORK), None);
+}
+
+#[test]
+fn test_messages_wait_for_attestation_threshold() {
+    let s = setup();
+    let (v1, v2, v3) = (
+        Address::generate(&s.env),
+        Address::generate(&s.env),
+        Address::generate(&s.env),
+    );
+    s.client.register_bridge_validators(
+        &s.admin,
+        &NETWORK,
+        &vec![&s.env, v1.clone(), v2.clone(), v3],
+        &2,
+    );
+
+    // Direct deposits now have to go through attested messages
+    assert_eq!(
+        s.client
+            .try_bridge_deposit(&s.user, &NETWORK, &Some(s.asset.clone()), &1_000),
+        Err(Ok(BridgeError::AttestationRequired))
+    );
+
+    let nonce = s.client.enqueue_bridge_message(
+        &s.bridge,
+        &NETWORK,
+        &s.user,
+        &Some(s.asset.clone()),
+        &10_000,
+    );
+    let result = s.client.process_bridge_messages(&NETWORK, &10);
+    assert_eq!((result.processed, result.remaining), (0, 1));
+
+    assert_eq!(
+        s.client
+            .try_attest_bridge_message(&s.admin, &NETWORK, &nonce),
+        Err(Ok(BridgeError::NotValidator))
+    );
+    assert_eq!(
+        s.client.try_attest_bridge_message(&v1, &NETWORK, &7),
+        Err(Ok(BridgeError::MessageNotFound))
+    );
+    assert_eq!(s.client.attest_bridge_message(&v1, &NETWORK, &nonce), 1);
+    assert_eq!(
+        s.client.try_attest_bridge_message(&v1, &NETWORK, &nonce),
+        Err(Ok(BridgeError::AlreadyAttested))
+    );
+    let result = s.client.process_bridge_messages(&NETWORK, &10);
+    assert_eq!((result.processed, result.remaining), (0, 1));
+
+    assert_eq!(s.client.attest_bridge_message(&v2, &NETWORK, &nonce), 2);
+    let before = collateral(&s);
+    let result = s.client.process_bridge_messages(&NETWORK, &10);
+    assert_eq!((result.processed, result.remaining), (1, 0));
+    assert_eq!(collateral(&s), before + 9_900);
+    assert_eq!(
+        s.client
+            .get_bridge_message(&NETWORK, &nonce)
+            .unwrap()
+            .status,
+        BridgeMessageStatus::Processed
+    );
+    assert_eq!(
+        s.client.try_attest_bridge_message(&v2, &NETWORK, &nonce),
+        Err(Ok(BridgeError::MessageNotPending))
+    );
+}
+
+#[test]
+fn test_removed_validator_attestations_stop_counting() {
+    let s = setup();
+    let (v1, v2, v3) = (
+        Address::generate(&s.env),
+        Address::generate(&s.env),
+        Address::generate(&s.env),
+    );
+    s.client.register_bridge_validators(
+        &s.admin,
+        &NETWORK,
+        &vec![&s.env, v1.clone(), v2.clone()],
+        &2,
+    );
+    let nonce = s.client.enqueue_bridge_message(
+        &s.bridge,
+        &NETWORK,
+        &s.user,
+        &Some(s.asset.clone()),
+        &10_000,
+    );
+    s.client.attest_bridge_message(&v1, &NETWORK, &nonce);
+    s.client.attest_bridge_message(&v2, &NETWORK, &nonce);
+
+    // Rotating v1 out leaves the message one attestation short
+    s.client.register_bridge_validators(
+        &s.admin,
+        &NETWORK,
+        &vec![&s.env, v2.clone(), v3.clone()],
+        &2,
+    );
+    assert_eq!(s.client.get_bridge_attestations(&NETWORK, &nonce).len(), 2);
+    assert_eq!(s.client.process_bridge_messages(&NETWORK, &10).processed, 0);
+
+    assert_eq!(s.client.attest_bridge_message(&v3, &NETWORK, &nonce), 2);
+    assert_eq!(s.client.process_bridge_messages(&NETWORK, &10).processed, 1);
+}
+
+#[test]
+fn test_transfer_completed_after_challenge_window() {
+    let s = setup();
+    assert_eq!(
+        s.client
+            .try_set_bridge_challenge_window(&s.admin, &NETWORK, &(MAX_CHALLENGE_WINDOW + 1)),
+        Err(Ok(BridgeError::InvalidChallengeWindow))
+    );
+    s.client
+        .set_bridge_challenge_window(&s.admin, &NETWORK, &WINDOW);
+    assert_eq!(s.client.get_bridge_challenge_window(&NETWORK), WINDOW);
+
+    let before = collateral(&s);
+    let net = s
+        .client
+        .bridge_withdraw(&s.user, &NETWORK, &Some(s.asset.clone()), &5_000);
+    assert_eq!(net, 4_950);
+    assert_eq!(collateral(&s), before - 5_000);
+
+    let transfer = s.client.get_bridge_transfer(&1).unwrap();
+    assert_eq!(transfer.status, BridgeTransferStatus::Pending);
+    assert_eq!(transfer.challenge_ends_at, 1_000 + WINDOW);
+    assert_eq!(transfer.quote.net_amount, 4_950);
+
+    assert_eq!(
+        s.client.try_complete_bridge_transfer(&s.bridge, &1),
+        Err(Ok(BridgeError::ChallengeWindowOpen))
+    );
+    advance_time(&s.env, WINDOW);
+    assert_eq!(s.client.complete_bridge_transfer(&s.bridge, &1), 4_950);
+    assert_eq!(
+        s.client.get_bridge_transfer(&1).unwrap().status,
+        BridgeTransferStatus::Completed
+    );
+
+    assert_eq!(
+        s.client.try_complete_bridge_transfer(&s.bridge, &1),
+        Err(Ok(BridgeError::TransferNotPending))
+    );
+    assert_eq!(
+        s.client.try_complete_bridge_transfer(&s.bridge, &2),
+        Err(Ok(BridgeError::TransferNotFound))
+    );
+}
+
+#[test]
+fn test_guardian_vetoes_withdrawal_during_window() {
+    let s = setup();
+    s.client
+        .set_bridge_challenge_window(&s.admin, &NETWORK, &WINDOW);
+    let before = collateral(&s);
+    s.client
+        .bridge_withdraw(&s.user, &NETWORK, &Some(s.asset.clone()), &5_000);
+
+    assert_eq!(
+        s.client.try_veto_bridge_transfer(&s.user, &1),
+        Err(Ok(BridgeError::NotAuthorized))
+    );
+    s.client.veto_bridge_transfer(&s.guardian, &1);
+    assert_eq!(collateral(&s), before);
+    assert_eq!(
+        s.client.get_bridge_transfer(&1).unwrap().status,
+        BridgeTransferStatus::Vetoed
+    );
+
+    advance_time(&s.env, WINDOW);
+    assert_eq!(
+        s.client.try_complete_bridge_transfer(&s.bridge, &1),
+        Err(Ok(BridgeError::TransferNotPending))
+    );
+
+    // Once the window ends a withdrawal can no longer be vetoed
+    s.client
+        .bridge_withdraw(&s.user, &NETWORK, &Some(s.asset.clone()), &5_000);
+    advance_time(&s.env, WINDOW);
+    assert_eq!(
+        s.client.try_veto_bridge_transfer(&s.guardian, &2),
+        Err(Ok(BridgeError::ChallengeWindowClosed))
+    );
+    assert_eq!(s.client.complete_bridge_transfer(&s.bridge, &2), 4_950);
+}
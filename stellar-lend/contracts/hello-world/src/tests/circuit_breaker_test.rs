@@ -0,0 +1,256 @@
+//! Price deviation circuit breaker tests.
+//!
+//! # Coverage
+//! - An oracle price move above the limit within the window trips the breaker
+//!   and emits a circuit breaker tripped event
+//! - Moves against a previous price older than the window are ignored
+//! - A tripped breaker blocks core and cross-asset borrows of the asset
+//! - Cross-asset price updates are checked too
+//! - The admin or a guardian resets the breaker; configuration bounds and
+//!   the admin's authorization
+
+use crate::borrow::BorrowError;
+use crate::cross_asset::{AssetConfig, CrossAssetError};
+use crate::oracle::{
+    CircuitBreakerConfig, CircuitBreakerTrip, OracleError, MAX_CIRCUIT_BREAKER_WINDOW,
+};
+use crate::tests::test_helpers::assert_authorized;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    contracttype,
+    testutils::{Address as _, Events},
+    Address, Env, TryFromVal,
+};
+use stellarlend_testutils::{advance_time, set_time};
+
+const PRICE: i128 = 100_000_000;
+const CA_PRICE: i128 = 10_000_000;
+const WINDOW: u64 = 600;
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestCircuitBreakerTrippedEvent {
+    pub asset: Address,
+    pub previous_price: i128,
+    pub price: i128,
+    pub move_bps: i128,
+    pub timestamp: u64,
+}
+
+struct Setup<'a> {
+    client: HelloContractClient<'a>,
+    admin: Address,
+    guardian: Address,
+    oracle: Address,
+    asset: Address,
+}
+
+/// 3% breaker over 10 minutes on an asset priced at t = 1_000, plus a guardian
+fn setup(e: &Env) -> Setup<'_> {
+    e.mock_all_auths();
+    set_time(e, 1_000);
+    let id = e.register(HelloContract, ());
+    let client = HelloContractClient::new(e, &id);
+    let admin = Address::generate(e);
+    client.initialize(&admin);
+
+    let vote_token = e
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    client.gov_initialize(
+        &admin,
+        &vote_token,
+        &None,
+        &None,
+        &None,
+        &None,
+        &None,
+        &None,
+    );
+    let guardian = Address::generate(e);
+    client.gov_add_guardian(&admin, &guardian);
+
+    let s = Setup {
+        client,
+        admin,
+        guardian,
+        oracle: Address::generate(e),
+        asset: Address::generate(e),
+    };
+    s.client.set_circuit_breaker(
+        &s.admin,
+        &s.asset,
+        &Some(CircuitBreakerConfig {
+            max_move_bps: 300,
+            window_secs: WINDOW,
+        }),
+    );
+    assert_authorized(e, &s.admin, &s.client.address, "set_circuit_breaker");
+    push(&s, PRICE);
+    s
+}
+
+fn push(s: &Setup, price: i128) {
+    s.client
+        .update_price_feed(&s.admin, &s.asset, &price, &8, &s.oracle);
+}
+
+#[test]
+fn test_large_move_trips_breaker_and_blocks_borrows() {
+    let e = Env::default();
+    let s = setup(&e);
+    let user = Address::generate(&e);
+
+    // 3% exactly is within the limit
+    advance_time(&e, 60);
+    push(&s, PRICE * 103 / 100);
+    assert_eq!(s.client.get_circuit_breaker_trip(&s.asset), None);
+
+    advance_time(&e, 60);
+    push(&s, PRICE * 99 / 100);
+    let event = e
+        .events()
+        .all()
+        .iter()
+        .find_map(|(_contract, _topics, data)| {
+            TestCircuitBreakerTrippedEvent::try_from_val(&e, &data).ok()
+        });
+    // 103 -> 99 is a 3.88% move
+    assert_eq!(
+        event,
+        Some(TestCircuitBreakerTrippedEvent {
+            asset: s.asset.clone(),
+            previous_price: PRICE * 103 / 100,
+            price: PRICE * 99 / 100,
+            move_bps: 388,
+            timestamp: 1_120,
+        })
+    );
+    assert_eq!(
+        s.client.get_circuit_breaker_trip(&s.asset),
+        Some(CircuitBreakerTrip {
+            previous_price: PRICE * 103 / 100,
+            price: PRICE * 99 / 100,
+            move_bps: 388,
+            tripped_at: 1_120,
+        })
+    );
+    // The update itself applied
+    assert_eq!(s.client.get_price(&s.asset), PRICE * 99 / 100);
+    assert_eq!(
+        s.client
+            .try_borrow_asset(&user, &Some(s.asset.clone()), &100),
+        Err(Ok(BorrowError::CircuitBreakerTripped))
+    );
+
+    assert_eq!(
+        s.client
+            .try_reset_circuit_breaker(&Address::generate(&e), &s.asset),
+        Err(Ok(OracleError::Unauthorized))
+    );
+    s.client.reset_circuit_breaker(&s.guardian, &s.asset);
+    assert_eq!(s.client.get_circuit_breaker_trip(&s.asset), None);
+    assert_eq!(
+        s.client
+            .try_borrow_asset(&user, &Some(s.asset.clone()), &100),
+        Err(Ok(BorrowError::InsufficientCollateral))
+    );
+    assert_eq!(
+        s.client.try_reset_circuit_breaker(&s.admin, &s.asset),
+        Err(Ok(OracleError::CircuitBreakerNotTripped))
+    );
+}
+
+#[test]
+fn test_moves_outside_window_are_ignored() {
+    let e = Env::default();
+    let s = setup(&e);
+
+    advance_time(&e, WINDOW + 1);
+    push(&s, PRICE * 96 / 100);
+    assert_eq!(s.client.get_circuit_breaker_trip(&s.asset), None);
+
+    // Unmonitored assets never trip
+    s.client.set_circuit_breaker(&s.admin, &s.asset, &None);
+    advance_time(&e, 60);
+    push(&s, PRICE);
+    assert_eq!(s.client.get_circuit_breaker(&s.asset), None);
+    assert_eq!(s.client.get_circuit_breaker_trip(&s.asset), None);
+}
+
+#[test]
+fn test_cross_asset_price_update_trips_breaker() {
+    let e = Env::default();
+    let s = setup(&e);
+    let user = Address::generate(&e);
+    s.client.initialize_ca(&s.admin);
+    s.client.initialize_asset(
+        &Some(s.asset.clone()),
+        &AssetConfig {
+            asset: Some(s.asset.clone()),
+            collateral_factor: 7500,
+            liquidation_threshold: 8000,
+            reserve_factor: 1000,
+            max_supply: 0,
+            max_borrow: 0,
+            can_collateralize: true,
+            can_borrow: true,
+            price: CA_PRICE,
+            price_updated_at: 1_000,
+            is_isolated: false,
+            isolated_debt_ceiling: 0,
+        },
+    );
+    s.client
+        .cross_asset_deposit(&user, &Some(s.asset.clone()), &10_000);
+
+    advance_time(&e, 60);
+    s.client
+        .update_asset_price(&Some(s.asset.clone()), &(CA_PRICE * 90 / 100));
+    assert_eq!(
+        s.client
+            .get_circuit_breaker_trip(&s.asset)
+            .unwrap()
+            .move_bps,
+        1_000
+    );
+    assert_eq!(
+        s.client
+            .try_cross_asset_borrow(&user, &Some(s.asset.clone()), &1_000),
+        Err(Ok(CrossAssetError::CircuitBreakerTripped))
+    );
+
+    s.client.reset_circuit_breaker(&s.admin, &s.asset);
+    s.client
+        .cross_asset_borrow(&user, &Some(s.asset.clone()), &1_000);
+}
+
+#[test]
+fn test_circuit_breaker_config_checks() {
+    let e = Env::default();
+    let s = setup(&e);
+    let config = |max_move_bps, window_secs| {
+        Some(CircuitBreakerConfig {
+            max_move_bps,
+            window_secs,
+        })
+    };
+
+    for invalid in [
+        config(0, WINDOW),
+        config(10_001, WINDOW),
+        config(300, 0),
+        config(300, MAX_CIRCUIT_BREAKER_WINDOW + 1),
+    ] {
+        assert_eq!(
+            s.client
+                .try_set_circuit_breaker(&s.admin, &s.asset, &invalid),
+            Err(Ok(OracleError::InvalidCircuitBreakerConfig))
+        );
+    }
+    assert_eq!(
+        s.client
+            .try_set_circuit_breaker(&s.guardian, &s.asset, &config(300, WINDOW)),
+        Err(Ok(OracleError::Unauthorized))
+    );
+}
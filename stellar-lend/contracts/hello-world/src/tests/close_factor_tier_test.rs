@@ -0,0 +1,123 @@
+//! Position size-weighted close factor tests.
+//!
+//! # Coverage
+//! - Without tiers the base close factor applies to every position size
+//! - The largest matching tier determines the close factor
+//! - `get_max_liquidatable_amount` uses the tiered close factor
+//! - Tiers must be sorted, non-increasing and within the base close factor
+//! - Only the admin can configure tiers; an empty vector clears them
+
+use super::test_helpers::setup_env_with_native_asset;
+use crate::risk_management::RiskManagementError;
+use crate::risk_params::CloseFactorTier;
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, Vec};
+
+fn tiers(env: &Env) -> Vec<CloseFactorTier> {
+    vec![
+        env,
+        CloseFactorTier {
+            min_debt: 1_000_000,
+            close_factor: 2_500,
+        },
+        CloseFactorTier {
+            min_debt: 10_000_000,
+            close_factor: 1_000,
+        },
+    ]
+}
+
+#[test]
+fn test_base_close_factor_without_tiers() {
+    let (_env, _id, client, _admin, _user, _native) = setup_env_with_native_asset();
+
+    assert_eq!(client.get_close_factor_tiers().len(), 0);
+    assert_eq!(client.get_close_factor_for_debt(&100_000_000), 5_000);
+    assert_eq!(client.get_max_liquidatable_amount(&100_000_000), 50_000_000);
+}
+
+#[test]
+fn test_tiers_scale_close_factor_with_size() {
+    let (env, _id, client, admin, _user, _native) = setup_env_with_native_asset();
+    client.set_close_factor_tiers(&admin, &tiers(&env));
+
+    assert_eq!(client.get_close_factor_for_debt(&999_999), 5_000);
+    assert_eq!(client.get_close_factor_for_debt(&1_000_000), 2_500);
+    assert_eq!(client.get_close_factor_for_debt(&9_999_999), 2_500);
+    assert_eq!(client.get_close_factor_for_debt(&10_000_000), 1_000);
+
+    assert_eq!(client.get_max_liquidatable_amount(&500_000), 250_000);
+    assert_eq!(client.get_max_liquidatable_amount(&2_000_000), 500_000);
+    assert_eq!(client.get_max_liquidatable_amount(&20_000_000), 2_000_000);
+}
+
+#[test]
+fn test_empty_tiers_clear_configuration() {
+    let (env, _id, client, admin, _user, _native) = setup_env_with_native_asset();
+    client.set_close_factor_tiers(&admin, &tiers(&env));
+    client.set_close_factor_tiers(&admin, &Vec::new(&env));
+
+    assert_eq!(client.get_close_factor_for_debt(&20_000_000), 5_000);
+}
+
+#[test]
+fn test_invalid_tiers_rejected() {
+    let (env, _id, client, admin, _user, _native) = setup_env_with_native_asset();
+
+    // Above the base close factor
+    let above_base = vec![
+        &env,
+        CloseFactorTier {
+            min_debt: 1_000,
+            close_factor: 6_000,
+        },
+    ];
+    assert_eq!(
+        client.try_set_close_factor_tiers(&admin, &above_base),
+        Err(Ok(RiskManagementError::InvalidCloseFactor))
+    );
+
+    // Larger tier with a larger close factor
+    let increasing = vec![
+        &env,
+        CloseFactorTier {
+            min_debt: 1_000,
+            close_factor: 1_000,
+        },
+        CloseFactorTier {
+            min_debt: 2_000,
+            close_factor: 2_000,
+        },
+    ];
+    assert_eq!(
+        client.try_set_close_factor_tiers(&admin, &increasing),
+        Err(Ok(RiskManagementError::InvalidCloseFactor))
+    );
+
+    // Unsorted sizes
+    let unsorted = vec![
+        &env,
+        CloseFactorTier {
+            min_debt: 2_000,
+            close_factor: 2_000,
+        },
+        CloseFactorTier {
+            min_debt: 1_000,
+            close_factor: 1_000,
+        },
+    ];
+    assert_eq!(
+        client.try_set_close_factor_tiers(&admin, &unsorted),
+        Err(Ok(RiskManagementError::InvalidParameter))
+    );
+}
+
+#[test]
+fn test_non_admin_cannot_set_tiers() {
+    let (env, _id, client, _admin, _user, _native) = setup_env_with_native_asset();
+    let attacker = Address::generate(&env);
+
+    assert_eq!(
+        client.try_set_close_factor_tiers(&attacker, &tiers(&env)),
+        Err(Ok(RiskManagementError::Unauthorized))
+    );
+}
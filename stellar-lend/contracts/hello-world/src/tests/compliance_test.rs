@@ -0,0 +1,193 @@
+//! Compliance control tests.
+//!
+//! # Coverage
+//! - Denied accounts cannot deposit or borrow; required allowlists gate
+//!   unlisted accounts
+//! - Per-account supply and borrow caps bound the position after the operation
+//! - The compliance hook can veto operations and fails closed
+//! - Repayments are never gated
+//! - Configuration is admin only, needs the admin's authorization and is
+//!   validated
+
+use crate::borrow::BorrowError;
+use crate::compliance::{ComplianceConfig, ComplianceError, ComplianceStatus, UserPositionCaps};
+use crate::deposit::DepositError;
+use crate::tests::test_helpers::{assert_authorized, setup_env_with_native_asset};
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env, Symbol,
+};
+
+/// Compliance contract allowing everything up to a per-operation amount, or trapping
+#[contract]
+pub struct MockComplianceHook;
+
+#[contractimpl]
+impl MockComplianceHook {
+    /// Largest amount allowed; negative makes the hook trap
+    pub fn set_limit(env: Env, limit: i128) {
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "limit"), &limit);
+    }
+
+    pub fn check_allowed(
+        env: Env,
+        _user: Address,
+        _op: Symbol,
+        _asset: Option<Address>,
+        amount: i128,
+    ) -> bool {
+        let limit: i128 = env
+            .storage()
+            .instance()
+            .get(&Symbol::new(&env, "limit"))
+            .unwrap_or(i128::MAX);
+        if limit < 0 {
+            panic!("hook failure");
+        }
+        amount <= limit
+    }
+}
+
+#[test]
+fn test_denylist_and_required_allowlist() {
+    let (env, id, client, admin, user, native) = setup_env_with_native_asset();
+    client.deposit_collateral(&user, &None, &10_000);
+    client.borrow_asset(&user, &None, &1_000);
+
+    client.set_compliance_status(&admin, &user, &Some(ComplianceStatus::Denied));
+    assert_authorized(&env, &admin, &id, "set_compliance_status");
+    assert_eq!(
+        client.try_deposit_collateral(&user, &None, &1_000),
+        Err(Ok(DepositError::ComplianceRejected))
+    );
+    assert_eq!(
+        client.try_borrow_asset(&user, &None, &1_000),
+        Err(Ok(BorrowError::ComplianceRejected))
+    );
+    // A denied account can still unwind
+    StellarAssetClient::new(&env, &native).mint(&user, &500);
+    TokenClient::new(&env, &native).approve(&user, &id, &500, &(env.ledger().sequence() + 100));
+    client.repay_debt(&user, &None, &500);
+
+    client.set_compliance_config(
+        &admin,
+        &ComplianceConfig {
+            allowlist_required: true,
+            hook: None,
+        },
+    );
+    assert_authorized(&env, &admin, &id, "set_compliance_config");
+    client.set_compliance_status(&admin, &user, &None);
+    let other = Address::generate(&env);
+    assert_eq!(
+        client.try_deposit_collateral(&other, &None, &1_000),
+        Err(Ok(DepositError::ComplianceRejected))
+    );
+    client.set_compliance_status(&admin, &user, &Some(ComplianceStatus::Allowed));
+    client.deposit_collateral(&user, &None, &1_000);
+    assert_eq!(
+        client.get_compliance_status(&user),
+        Some(ComplianceStatus::Allowed)
+    );
+}
+
+#[test]
+fn test_position_caps() {
+    let (env, id, client, admin, user, _native) = setup_env_with_native_asset();
+    client.set_user_position_caps(
+        &admin,
+        &user,
+        &Some(UserPositionCaps {
+            max_supply: 10_000,
+            max_borrow: 4_000,
+        }),
+    );
+    assert_authorized(&env, &admin, &id, "set_user_position_caps");
+
+    client.deposit_collateral(&user, &None, &10_000);
+    assert_eq!(
+        client.try_deposit_collateral(&user, &None, &1),
+        Err(Ok(DepositError::ComplianceRejected))
+    );
+    client.borrow_asset(&user, &None, &4_000);
+    assert_eq!(
+        client.try_borrow_asset(&user, &None, &1),
+        Err(Ok(BorrowError::ComplianceRejected))
+    );
+
+    // A zero cap is unlimited
+    client.set_user_position_caps(
+        &admin,
+        &user,
+        &Some(UserPositionCaps {
+            max_supply: 0,
+            max_borrow: 4_000,
+        }),
+    );
+    client.deposit_collateral(&user, &None, &1);
+}
+
+#[test]
+fn test_hook_vetoes_and_fails_closed() {
+    let (env, _id, client, admin, user, _native) = setup_env_with_native_asset();
+    let hook = env.register(MockComplianceHook, ());
+    let hook_client = MockComplianceHookClient::new(&env, &hook);
+    hook_client.set_limit(&5_000);
+    client.set_compliance_config(
+        &admin,
+        &ComplianceConfig {
+            allowlist_required: false,
+            hook: Some(hook),
+        },
+    );
+
+    client.deposit_collateral(&user, &None, &5_000);
+    assert_eq!(
+        client.try_deposit_collateral(&user, &None, &5_001),
+        Err(Ok(DepositError::ComplianceRejected))
+    );
+    client.borrow_asset(&user, &None, &1_000);
+
+    hook_client.set_limit(&-1);
+    assert_eq!(
+        client.try_borrow_asset(&user, &None, &1_000),
+        Err(Ok(BorrowError::ComplianceRejected))
+    );
+}
+
+#[test]
+fn test_configuration_checks() {
+    let (env, _id, client, admin, user, _native) = setup_env_with_native_asset();
+    let outsider = Address::generate(&env);
+
+    assert_eq!(
+        client.try_set_compliance_status(&outsider, &user, &Some(ComplianceStatus::Denied)),
+        Err(Ok(ComplianceError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_compliance_config(
+            &outsider,
+            &ComplianceConfig {
+                allowlist_required: true,
+                hook: None,
+            }
+        ),
+        Err(Ok(ComplianceError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_user_position_caps(
+            &admin,
+            &user,
+            &Some(UserPositionCaps {
+                max_supply: -1,
+                max_borrow: 0,
+            })
+        ),
+        Err(Ok(ComplianceError::InvalidCap))
+    );
+    assert_eq!(client.get_user_position_caps(&user), None);
+}
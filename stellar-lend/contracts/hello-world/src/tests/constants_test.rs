@@ -0,0 +1,121 @@
+//! Network constants registry tests.
+//!
+//! # Coverage
+//! - Mainnet values apply when the contract is initialized without overrides
+//! - An accelerated year length speeds up interest accrual
+//! - Default caps apply to assets without their own configuration
+//! - Invalid overrides and repeated initialization are rejected
+
+use crate::constants::{NetworkConstants, DEFAULT_SECONDS_PER_YEAR, SECONDS_PER_DAY};
+use crate::deposit::{deposit_collateral, DepositDataKey, DepositError, ProtocolAnalytics};
+use crate::interest_rate::{
+    calculate_accrued_interest, calculate_borrow_rate, calculate_user_accrued_interest,
+};
+use crate::risk_management::RiskManagementError;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+fn setup() -> (Env, Address, HelloContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(&env, &contract_id);
+    (env, contract_id, client)
+}
+
+fn testnet() -> NetworkConstants {
+    NetworkConstants {
+        seconds_per_year: SECONDS_PER_DAY,
+        price_staleness_seconds: 600,
+        default_supply_cap: 5_000,
+        default_borrow_cap: 0,
+    }
+}
+
+#[test]
+fn test_mainnet_defaults_without_overrides() {
+    let (env, _id, client) = setup();
+    client.initialize(&Address::generate(&env));
+
+    let constants = client.get_network_constants();
+    assert_eq!(constants, NetworkConstants::mainnet());
+    assert_eq!(constants.seconds_per_year, DEFAULT_SECONDS_PER_YEAR);
+}
+
+#[test]
+fn test_accelerated_year_speeds_up_accrual() {
+    let (env, id, client) = setup();
+    let user = Address::generate(&env);
+    client.initialize_with_constants(&Address::generate(&env), &testnet());
+    assert_eq!(client.get_network_constants(), testnet());
+
+    env.as_contract(&id, || {
+        env.storage().persistent().set(
+            &DepositDataKey::ProtocolAnalytics,
+            &ProtocolAnalytics {
+                total_deposits: 1_000_000,
+                total_borrows: 500_000,
+                total_value_locked: 1_000_000,
+            },
+        );
+
+        // One testnet day accrues a full mainnet year of interest
+        let rate = calculate_borrow_rate(&env).unwrap();
+        let full_year =
+            calculate_accrued_interest(100_000, 0, DEFAULT_SECONDS_PER_YEAR, rate).unwrap();
+        let one_day =
+            calculate_user_accrued_interest(&env, &user, &None, 100_000, 0, SECONDS_PER_DAY)
+                .unwrap();
+        assert!(full_year > 0);
+        assert_eq!(one_day, full_year);
+    });
+}
+
+#[test]
+fn test_default_supply_cap_for_unconfigured_asset() {
+    let (env, id, client) = setup();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    client.initialize_with_constants(&admin, &testnet());
+
+    env.as_contract(&id, || {
+        deposit_collateral(&env, user.clone(), None, 4_000).unwrap();
+        assert_eq!(
+            deposit_collateral(&env, user.clone(), None, 2_000),
+            Err(DepositError::SupplyCapExceeded)
+        );
+    });
+}
+
+#[test]
+fn test_invalid_overrides_rejected() {
+    let (env, _id, client) = setup();
+    let admin = Address::generate(&env);
+
+    let mut zero_year = testnet();
+    zero_year.seconds_per_year = 0;
+    assert_eq!(
+        client.try_initialize_with_constants(&admin, &zero_year),
+        Err(Ok(RiskManagementError::InvalidParameter))
+    );
+
+    let mut negative_cap = testnet();
+    negative_cap.default_supply_cap = -1;
+    assert_eq!(
+        client.try_initialize_with_constants(&admin, &negative_cap),
+        Err(Ok(RiskManagementError::InvalidParameter))
+    );
+}
+
+#[test]
+fn test_overrides_only_at_initialization() {
+    let (env, _id, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    assert_eq!(
+        client.try_initialize_with_constants(&admin, &testnet()),
+        Err(Ok(RiskManagementError::Unauthorized))
+    );
+    assert_eq!(client.get_network_constants(), NetworkConstants::mainnet());
+}
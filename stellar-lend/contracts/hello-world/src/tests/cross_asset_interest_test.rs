@@ -0,0 +1,171 @@
+//! Cross-asset interest accrual tests.
+//!
+//! # Coverage
+//! - Assets without a rate model accrue no interest
+//! - Borrow rate follows the kinked curve below and above the kink
+//! - Debt grows with elapsed time and is settled on the next touch
+//! - Position summary includes pending interest without writing it
+//! - Repayment settles interest before principal
+//! - Invalid rate models and unconfigured assets are rejected
+
+use crate::cross_asset::{
+    cross_asset_borrow, cross_asset_deposit, cross_asset_repay, get_asset_borrow_rate,
+    get_user_asset_position, get_user_position_summary, initialize, initialize_asset,
+    set_asset_rate_model, update_asset_price, AssetConfig, AssetRateModel, CrossAssetError,
+};
+use crate::HelloContract;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+const YEAR: u64 = 365 * 86_400;
+
+fn setup() -> (Env, Address, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        initialize(&env, admin.clone()).unwrap();
+        let config = AssetConfig {
+            asset: None,
+            collateral_factor: 7500,
+            liquidation_threshold: 8000,
+            reserve_factor: 1000,
+            max_supply: 0,
+            max_borrow: 0,
+            can_collateralize: true,
+            can_borrow: true,
+            price: 10_000_000,
+            price_updated_at: env.ledger().timestamp(),
+            is_isolated: false,
+            isolated_debt_ceiling: 0,
+        };
+        initialize_asset(&env, None, config).unwrap();
+    });
+
+    (env, contract_id, admin, user)
+}
+
+fn rate_model() -> AssetRateModel {
+    AssetRateModel {
+        base_rate_bps: 200,
+        kink_utilization_bps: 8000,
+        multiplier_bps: 1000,
+        jump_multiplier_bps: 10_000,
+    }
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|li| li.timestamp += seconds);
+    update_asset_price(env, None, 10_000_000).unwrap();
+}
+
+#[test]
+fn test_no_rate_model_accrues_nothing() {
+    let (env, id, _admin, user) = setup();
+    env.as_contract(&id, || {
+        cross_asset_deposit(&env, user.clone(), None, 1_000_000).unwrap();
+        cross_asset_borrow(&env, user.clone(), None, 400_000).unwrap();
+        assert_eq!(get_asset_borrow_rate(&env, None), 0);
+
+        advance(&env, YEAR);
+        let position = cross_asset_repay(&env, user.clone(), None, 0).unwrap();
+        assert_eq!(position.accrued_interest, 0);
+        assert_eq!(position.debt_principal, 400_000);
+    });
+}
+
+#[test]
+fn test_borrow_rate_follows_kinked_curve() {
+    let (env, id, _admin, user) = setup();
+    env.as_contract(&id, || {
+        set_asset_rate_model(&env, None, rate_model()).unwrap();
+        cross_asset_deposit(&env, user.clone(), None, 1_000_000).unwrap();
+        assert_eq!(get_asset_borrow_rate(&env, None), 200);
+
+        // 40% utilization: 200 + 4000 * 1000 / 8000
+        cross_asset_borrow(&env, user.clone(), None, 400_000).unwrap();
+        assert_eq!(get_asset_borrow_rate(&env, None), 700);
+
+        // Above a 20% kink: 200 + 1000 + 2000 * 10000 / 8000
+        let mut model = rate_model();
+        model.kink_utilization_bps = 2000;
+        set_asset_rate_model(&env, None, model).unwrap();
+        assert_eq!(get_asset_borrow_rate(&env, None), 3700);
+    });
+}
+
+#[test]
+fn test_debt_accrues_over_time() {
+    let (env, id, _admin, user) = setup();
+    env.as_contract(&id, || {
+        set_asset_rate_model(&env, None, rate_model()).unwrap();
+        cross_asset_deposit(&env, user.clone(), None, 1_000_000).unwrap();
+        cross_asset_borrow(&env, user.clone(), None, 400_000).unwrap();
+
+        advance(&env, YEAR);
+
+        // Summary reflects pending interest but does not persist it
+        let summary = get_user_position_summary(&env, &user).unwrap();
+        assert_eq!(summary.total_debt_value, 428_000);
+        let stored = get_user_asset_position(&env, &user, None);
+        assert_eq!(stored.accrued_interest, 0);
+
+        // Borrowing again settles the elapsed interest first
+        let position = cross_asset_borrow(&env, user.clone(), None, 10_000).unwrap();
+        assert_eq!(position.accrued_interest, 28_000);
+        assert_eq!(position.debt_principal, 410_000);
+        assert_eq!(position.last_updated, env.ledger().timestamp());
+    });
+}
+
+#[test]
+fn test_repay_settles_interest_first() {
+    let (env, id, _admin, user) = setup();
+    env.as_contract(&id, || {
+        set_asset_rate_model(&env, None, rate_model()).unwrap();
+        cross_asset_deposit(&env, user.clone(), None, 1_000_000).unwrap();
+        cross_asset_borrow(&env, user.clone(), None, 400_000).unwrap();
+
+        advance(&env, YEAR);
+
+        let position = cross_asset_repay(&env, user.clone(), None, 30_000).unwrap();
+        assert_eq!(position.accrued_interest, 0);
+        assert_eq!(position.debt_principal, 398_000);
+
+        // Full repayment clears principal and leaves no residual interest
+        let position = cross_asset_repay(&env, user.clone(), None, i128::MAX).unwrap();
+        assert_eq!(position.debt_principal, 0);
+        assert_eq!(position.accrued_interest, 0);
+    });
+}
+
+#[test]
+fn test_invalid_rate_model_rejected() {
+    let (env, id, _admin, _user) = setup();
+    env.as_contract(&id, || {
+        let mut model = rate_model();
+        model.kink_utilization_bps = 0;
+        assert_eq!(
+            set_asset_rate_model(&env, None, model),
+            Err(CrossAssetError::InvalidRateModel)
+        );
+
+        let mut model = rate_model();
+        model.multiplier_bps = -1;
+        assert_eq!(
+            set_asset_rate_model(&env, None, model),
+            Err(CrossAssetError::InvalidRateModel)
+        );
+
+        let unknown = Address::generate(&env);
+        assert_eq!(
+            set_asset_rate_model(&env, Some(unknown), rate_model()),
+            Err(CrossAssetError::AssetNotConfigured)
+        );
+    });
+}
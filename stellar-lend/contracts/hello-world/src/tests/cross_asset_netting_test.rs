@@ -0,0 +1,164 @@
+//! Cross-asset portfolio margin netting tests.
+//!
+//! # Coverage
+//! - Without pairs the summary reports no netting benefit
+//! - A registered pair reduces weighted debt and raises the health factor
+//! - The benefit is bounded by the offset, the factor and the per-pair cap
+//! - Netting extends borrow capacity; removing the pair withdraws it
+//! - Invalid factors, caps and unknown assets are rejected
+
+use crate::cross_asset::{
+    cross_asset_borrow, cross_asset_deposit, get_netting_pairs, get_user_position_summary,
+    initialize, initialize_asset, remove_netting_pair, set_netting_pair, AssetConfig,
+    CrossAssetError, MAX_NETTING_FACTOR_BPS,
+};
+use crate::HelloContract;
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+fn asset_config(env: &Env, asset: &Address) -> AssetConfig {
+    AssetConfig {
+        asset: Some(asset.clone()),
+        collateral_factor: 7500,
+        liquidation_threshold: 8000,
+        reserve_factor: 1000,
+        max_supply: 0,
+        max_borrow: 0,
+        can_collateralize: true,
+        can_borrow: true,
+        price: 10_000_000,
+        price_updated_at: env.ledger().timestamp(),
+        is_isolated: false,
+        isolated_debt_ceiling: 0,
+    }
+}
+
+/// Returns (env, contract, user, usdc, yield-bearing usdc)
+fn setup() -> (Env, Address, Address, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let usdc = Address::generate(&env);
+    let yusdc = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        initialize(&env, admin.clone()).unwrap();
+        initialize_asset(&env, Some(usdc.clone()), asset_config(&env, &usdc)).unwrap();
+        initialize_asset(&env, Some(yusdc.clone()), asset_config(&env, &yusdc)).unwrap();
+        cross_asset_deposit(&env, user.clone(), Some(yusdc.clone()), 1_000).unwrap();
+    });
+
+    (env, contract_id, user, usdc, yusdc)
+}
+
+#[test]
+fn test_no_pairs_no_benefit() {
+    let (env, id, user, usdc, _yusdc) = setup();
+    env.as_contract(&id, || {
+        cross_asset_borrow(&env, user.clone(), Some(usdc), 700).unwrap();
+
+        let summary = get_user_position_summary(&env, &user).unwrap();
+        assert_eq!(summary.netting_benefit, 0);
+        assert_eq!(summary.weighted_debt_value, 700);
+    });
+}
+
+#[test]
+fn test_pair_reduces_weighted_debt() {
+    let (env, id, user, usdc, yusdc) = setup();
+    env.as_contract(&id, || {
+        cross_asset_borrow(&env, user.clone(), Some(usdc.clone()), 700).unwrap();
+        set_netting_pair(&env, Some(yusdc), Some(usdc), 5_000, 1_000_000).unwrap();
+
+        // Offset = min(1000, 700) = 700, half of it netted
+        let summary = get_user_position_summary(&env, &user).unwrap();
+        assert_eq!(summary.netting_benefit, 350);
+        assert_eq!(summary.weighted_debt_value, 350);
+        assert_eq!(summary.total_debt_value, 700);
+        assert_eq!(summary.health_factor, 800 * 10_000 / 350);
+    });
+}
+
+#[test]
+fn test_benefit_capped_per_pair() {
+    let (env, id, user, usdc, yusdc) = setup();
+    env.as_contract(&id, || {
+        cross_asset_borrow(&env, user.clone(), Some(usdc.clone()), 700).unwrap();
+        set_netting_pair(&env, Some(yusdc), Some(usdc), 5_000, 100).unwrap();
+
+        let summary = get_user_position_summary(&env, &user).unwrap();
+        assert_eq!(summary.netting_benefit, 100);
+        assert_eq!(summary.weighted_debt_value, 600);
+    });
+}
+
+#[test]
+fn test_netting_extends_borrow_capacity() {
+    let (env, id, user, usdc, yusdc) = setup();
+    env.as_contract(&id, || {
+        assert_eq!(
+            cross_asset_borrow(&env, user.clone(), Some(usdc.clone()), 900),
+            Err(CrossAssetError::ExceedsBorrowCapacity)
+        );
+
+        set_netting_pair(
+            &env,
+            Some(yusdc.clone()),
+            Some(usdc.clone()),
+            5_000,
+            1_000_000,
+        )
+        .unwrap();
+        cross_asset_borrow(&env, user.clone(), Some(usdc.clone()), 900).unwrap();
+        assert_eq!(
+            get_user_position_summary(&env, &user)
+                .unwrap()
+                .netting_benefit,
+            450
+        );
+
+        remove_netting_pair(&env, Some(yusdc), Some(usdc)).unwrap();
+        assert_eq!(get_netting_pairs(&env).len(), 0);
+        assert!(
+            get_user_position_summary(&env, &user)
+                .unwrap()
+                .is_liquidatable
+        );
+    });
+}
+
+#[test]
+fn test_invalid_pairs_rejected() {
+    let (env, id, _user, usdc, yusdc) = setup();
+    env.as_contract(&id, || {
+        assert_eq!(
+            set_netting_pair(
+                &env,
+                Some(yusdc.clone()),
+                Some(usdc.clone()),
+                MAX_NETTING_FACTOR_BPS + 1,
+                1_000,
+            ),
+            Err(CrossAssetError::InvalidNettingPair)
+        );
+        assert_eq!(
+            set_netting_pair(&env, Some(yusdc.clone()), Some(usdc.clone()), 5_000, 0),
+            Err(CrossAssetError::InvalidNettingPair)
+        );
+        assert_eq!(
+            set_netting_pair(
+                &env,
+                Some(Address::generate(&env)),
+                Some(usdc.clone()),
+                5_000,
+                1
+            ),
+            Err(CrossAssetError::AssetNotConfigured)
+        );
+        assert_eq!(
+            remove_netting_pair(&env, Some(yusdc), Some(usdc)),
+            Err(CrossAssetError::InvalidNettingPair)
+        );
+    });
+}
@@ -6,7 +6,7 @@ use crate::{
 };
 use soroban_sdk::{
     contract, contractimpl, testutils::{Address as _, Ledger, MockAuth, MockAuthInvoke},
-    Address, Env, IntoVal, Symbol, Val, Vec,
+    Address, Bytes, Env, IntoVal, Symbol, Val, Vec,
 };
 use soroban_sdk::token::Client as TokenClient;
 use soroban_sdk::token::StellarAssetClient as StellarTokenClient;
@@ -16,12 +16,11 @@ use soroban_sdk::token::StellarAssetClient as StellarTokenClient;
 // ============================================================================
 
 /// Mock Flash Loan Receiver Contract
-/// This contract implements the `receive_flash_loan` method expected by a flash loan provider.
+/// This contract implements the `FlashLoanReceiver` interface expected by the flash loan provider.
 /// It can be configured to:
 /// 1. Repay the loan successfully
 /// 2. Fail to repay (steal funds)
 /// 3. Re-enter the provider contract
-/// 4. Panic
 #[contract]
 pub struct MockFlashLoanReceiver;
 
@@ -35,17 +34,17 @@ impl MockFlashLoanReceiver {
     }
 
     /// The callback method for flash loans
-    pub fn receive_flash_loan(env: Env, loan_amount: i128, fee: i128, asset: Address) -> bool {
+    pub fn execute_operation(env: Env, asset: Address, amount: i128, fee: i128, _params: Bytes) -> bool {
         let provider: Address = env.storage().instance().get(&Symbol::new(&env, "provider")).unwrap();
         let should_repay: bool = env.storage().instance().get(&Symbol::new(&env, "should_repay")).unwrap();
         let should_reenter: bool = env.storage().instance().get(&Symbol::new(&env, "should_reenter")).unwrap();
 
-        let total_debt = loan_amount + fee;
+        let total_debt = amount + fee;
         let token_client = TokenClient::new(&env, &asset);
 
         // Verify we received the funds
         let balance = token_client.balance(&env.current_contract_address());
-        if balance < loan_amount {
+        if balance < amount {
             panic!("Did not receive flash loan funds");
         }
 
@@ -53,31 +52,13 @@ impl MockFlashLoanReceiver {
             // Attempt to re-enter the provider
             // For example, try to deposit the borrowed funds
             let client = HelloContractClient::new(&env, &provider);
-            // This should fail due to re-entrancy guards or logic
-            let _ = client.try_deposit_collateral(&env.current_contract_address(), &Some(asset.clone()), &loan_amount);
+            // This should fail: the host rejects re-entry into the provider
+            let _ = client.try_deposit_collateral(&env.current_contract_address(), &Some(asset.clone()), &amount);
         }
 
         if should_repay {
-            // Approve provider to pull funds (if using transfer_from) or transfer directly
-            // The protocol expects us to call `repay_flash_loan`? 
-            // OR if the protocol logic was "call callback then pull funds", we would just approve.
-            // Based on current implementation (which is broken/manual), we simulate the user action.
-            
-            // Note: In the current broken implementation, the *User* calls repay.
-            // But a real flash loan should have the *Receiver* contract call repay or approve.
-            // We'll simulate a "Push" repayment here.
-            
-            let client = HelloContractClient::new(&env, &provider);
-            
-            // Increase allowance for the provider to pull funds (if that's how repay works)
-            // Or just transfer back if repay_flash_loan expects us to have sent it?
-            // Checking `repay_flash_loan` implementation:
-            // It calls `token_client.transfer_from(&env.current_contract_address(), &user, ...)`?
-            // No, `repay_flash_loan` usually transfers FROM the user TO the contract.
-            
-            // Let's assume we need to call repay_flash_loan
-            token_client.approve(&provider, &total_debt, &200);
-            client.repay_flash_loan(&env.current_contract_address(), &asset, &total_debt);
+            // Push principal + fee back; the provider checks its balance after we return
+            token_client.transfer(&env.current_contract_address(), &provider, &total_debt);
         }
 
         true
@@ -165,35 +146,21 @@ fn test_flash_loan_happy_path() {
     stellar_token_client.mint(&receiver_id, &100); 
 
     // Execute Flash Loan
-    // Note: The current implementation of `execute_flash_loan` DOES NOT call the callback.
-    // It expects the user to handle it. 
-    // This test verifies the CURRENT behavior, which effectively just transfers funds.
-    // If we want to test a "fixed" version, we'd need to modify the contract.
-    // For now, let's test the interactions as they exist.
-    
+    // The provider transfers the loan to the receiver, invokes `execute_operation`
+    // and checks that principal + fee came back before the call returns.
     let loan_amount = 1000i128;
-    
-    // Mocking the user calling the flash loan
-    // In the current implementation, 'user' receives the funds, not the callback contract automatically?
-    // Let's check `execute_flash_loan`:
-    // token_client.transfer(..., &user, &amount);
-    // So the 'user' gets the money. The 'callback' arg is just stored.
-    
-    // This confirms the vulnerability/design choice. 
-    // To test "Cross Contract", we'll simulate the user being a contract (the receiver).
-    
-    // Let's treat `receiver_id` as the `user`.
-    let total_repayment = client.execute_flash_loan(&receiver_id, &token_addr, &loan_amount, &receiver_id);
-    
-    // Verify receiver has funds
-    assert_eq!(token_client.balance(&receiver_id), 100 + 1000);
-    
-    // Now Receiver calls repay (simulating the atomic transaction requirement)
-    // The `repay_flash_loan` must be called.
-    client.repay_flash_loan(&receiver_id, &token_addr, &total_repayment);
-    
-    // Verify funds returned
+    let total_repayment = client.execute_flash_loan(
+        &user,
+        &token_addr,
+        &loan_amount,
+        &receiver_id,
+        &Bytes::new(&env),
+    );
+
+    // Verify the fee was paid out of the receiver's own balance
+    assert_eq!(total_repayment, 1001);
     assert_eq!(token_client.balance(&receiver_id), 100 - (total_repayment - loan_amount));
+    assert_eq!(token_client.balance(&protocol_id), 1_000_000_000 + 1);
 
     std::println!("Flash Loan Happy Path Budget Usage:");
     env.budget().print();
@@ -243,24 +210,31 @@ fn test_flash_loan_insufficient_liquidity() {
     
     // Try to borrow more than exists
     let too_much = 2_000_000_000i128;
-    client.execute_flash_loan(&user, &token_client.address, &too_much, &user);
+    client.execute_flash_loan(&user, &token_client.address, &too_much, &user, &Bytes::new(&env));
 }
 
 #[test]
-#[should_panic(expected = "Reentrancy")]
-fn test_flash_loan_reentrancy_block() {
+fn test_flash_loan_unpaid_reverts() {
     let env = Env::default();
     env.mock_all_auths();
-    let (client, _, _, user, token_client) = setup_protocol(&env);
-    
-    let amount = 1000i128;
-    
-    // Start loan 1
-    client.execute_flash_loan(&user, &token_client.address, &amount, &user);
-    
-    // Try start loan 2 before repaying loan 1
-    // This should fail with Reentrancy
-    client.execute_flash_loan(&user, &token_client.address, &amount, &user);
+    let (client, protocol_id, _, user, token_client) = setup_protocol(&env);
+
+    // Receiver keeps the funds instead of repaying
+    let receiver_id = env.register(MockFlashLoanReceiver, ());
+    MockFlashLoanReceiverClient::new(&env, &receiver_id).init(&protocol_id, &false, &false);
+
+    let result = client.try_execute_flash_loan(
+        &user,
+        &token_client.address,
+        &1000,
+        &receiver_id,
+        &Bytes::new(&env),
+    );
+    assert_eq!(result, Err(Ok(FlashLoanError::InsufficientRepayment)));
+
+    // The whole loan reverted
+    assert_eq!(token_client.balance(&receiver_id), 0);
+    assert_eq!(token_client.balance(&protocol_id), 1_000_000_000);
 }
 
 #[test]
@@ -0,0 +1,128 @@
+//! # Daily Borrow Limit Tests
+//!
+//! Covers the global and per-asset daily borrow caps:
+//! - Caps are admin-only, need the admin's authorization and reject negative
+//!   values
+//! - Borrows beyond the global or per-asset cap revert
+//! - Counters reset when a new window starts
+//! - Status views and the protocol report expose cap, usage and remaining capacity
+
+use super::test_helpers::{assert_authorized, setup_env_with_native_asset};
+use crate::borrow::{borrow_asset, BorrowError};
+use crate::risk_management::{
+    record_daily_borrow, RiskManagementError, DAILY_BORROW_WINDOW_SECONDS,
+};
+use soroban_sdk::{testutils::Address as _, testutils::Ledger, Address};
+
+#[test]
+fn test_set_caps_requires_admin_and_valid_value() {
+    let (env, id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let stranger = Address::generate(&env);
+
+    assert_eq!(
+        client.try_set_global_daily_borrow_cap(&stranger, &Some(1_000)),
+        Err(Ok(RiskManagementError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_asset_daily_borrow_cap(&admin, &None, &Some(-1)),
+        Err(Ok(RiskManagementError::InvalidParameter))
+    );
+
+    client.set_global_daily_borrow_cap(&admin, &Some(1_000));
+    assert_authorized(&env, &admin, &id, "set_global_daily_borrow_cap");
+    assert_eq!(client.get_global_daily_borrow_status().cap, Some(1_000));
+
+    client.set_global_daily_borrow_cap(&admin, &None);
+    assert_eq!(client.get_global_daily_borrow_status().cap, None);
+}
+
+#[test]
+fn test_global_cap_blocks_excess_borrow() {
+    let (env, id, client, admin, user, _native) = setup_env_with_native_asset();
+    client.deposit_collateral(&user, &None, &100_000);
+    client.set_global_daily_borrow_cap(&admin, &Some(5_000));
+
+    env.as_contract(&id, || {
+        borrow_asset(&env, user.clone(), None, 4_000).unwrap();
+        assert_eq!(
+            borrow_asset(&env, user.clone(), None, 1_001).unwrap_err(),
+            BorrowError::DailyBorrowCapExceeded
+        );
+        borrow_asset(&env, user.clone(), None, 1_000).unwrap();
+    });
+
+    let status = client.get_global_daily_borrow_status();
+    assert_eq!(status.borrowed, 5_000);
+    assert_eq!(status.remaining, Some(0));
+}
+
+#[test]
+fn test_asset_cap_is_independent_per_asset() {
+    let (env, id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let token_a = Address::generate(&env);
+    let token_b = Address::generate(&env);
+    client.set_asset_daily_borrow_cap(&admin, &Some(token_a.clone()), &Some(1_000));
+    assert_authorized(&env, &admin, &id, "set_asset_daily_borrow_cap");
+
+    env.as_contract(&id, || {
+        record_daily_borrow(&env, Some(token_a.clone()), 1_000).unwrap();
+        assert_eq!(
+            record_daily_borrow(&env, Some(token_a.clone()), 1),
+            Err(RiskManagementError::DailyBorrowCapExceeded)
+        );
+        record_daily_borrow(&env, Some(token_b.clone()), 50_000).unwrap();
+    });
+
+    assert_eq!(
+        client
+            .get_asset_daily_borrow_status(&Some(token_a))
+            .remaining,
+        Some(0)
+    );
+    assert_eq!(
+        client
+            .get_asset_daily_borrow_status(&Some(token_b))
+            .borrowed,
+        50_000
+    );
+    assert_eq!(client.get_global_daily_borrow_status().borrowed, 51_000);
+}
+
+#[test]
+fn test_counters_reset_in_new_window() {
+    let (env, id, client, admin, _user, _native) = setup_env_with_native_asset();
+    client.set_global_daily_borrow_cap(&admin, &Some(1_000));
+
+    env.as_contract(&id, || {
+        record_daily_borrow(&env, None, 1_000).unwrap();
+    });
+    let first_window_end = client.get_global_daily_borrow_status().window_end;
+
+    env.ledger().with_mut(|li| li.timestamp = first_window_end);
+
+    let status = client.get_global_daily_borrow_status();
+    assert_eq!(status.borrowed, 0);
+    assert_eq!(status.remaining, Some(1_000));
+    assert_eq!(
+        status.window_end,
+        first_window_end + DAILY_BORROW_WINDOW_SECONDS
+    );
+
+    env.as_contract(&id, || {
+        record_daily_borrow(&env, None, 1_000).unwrap();
+    });
+}
+
+#[test]
+fn test_protocol_report_includes_daily_borrow_status() {
+    let (env, id, client, admin, _user, _native) = setup_env_with_native_asset();
+    client.set_global_daily_borrow_cap(&admin, &Some(2_000));
+    env.as_contract(&id, || {
+        record_daily_borrow(&env, None, 500).unwrap();
+    });
+
+    let report = client.get_protocol_report();
+    assert_eq!(report.daily_borrow.cap, Some(2_000));
+    assert_eq!(report.daily_borrow.borrowed, 500);
+    assert_eq!(report.daily_borrow.remaining, Some(1_500));
+}
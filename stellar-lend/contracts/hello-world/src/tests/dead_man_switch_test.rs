@@ -0,0 +1,156 @@
+//! # Dead-man Switch Tests
+//!
+//! Covers admin inactivity handling:
+//! - Configuration is admin-only and enforces a minimum inactivity period
+//! - Heartbeats and other admin actions reset the inactivity timer
+//! - The backup admin can only take over after the switch triggers
+//! - Disabling the switch prevents takeover
+
+use crate::admin::{AdminError, DeadManSwitchConfig, MIN_INACTIVITY_PERIOD};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+fn setup(env: &Env) -> (HelloContractClient<'_>, Address, Address) {
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(env, &id);
+    let admin = Address::generate(env);
+    let backup = Address::generate(env);
+    client.initialize(&admin);
+    client.configure_dead_man_switch(
+        &admin,
+        &Some(DeadManSwitchConfig {
+            inactivity_period: MIN_INACTIVITY_PERIOD,
+            backup_admin: Some(backup.clone()),
+        }),
+    );
+    (client, admin, backup)
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|li| li.timestamp += seconds);
+}
+
+#[test]
+fn test_configure_requires_admin_and_min_period() {
+    let env = Env::default();
+    let (client, admin, backup) = setup(&env);
+
+    assert_eq!(
+        client.try_configure_dead_man_switch(
+            &backup,
+            &Some(DeadManSwitchConfig {
+                inactivity_period: MIN_INACTIVITY_PERIOD,
+                backup_admin: None,
+            }),
+        ),
+        Err(Ok(AdminError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_configure_dead_man_switch(
+            &admin,
+            &Some(DeadManSwitchConfig {
+                inactivity_period: MIN_INACTIVITY_PERIOD - 1,
+                backup_admin: None,
+            }),
+        ),
+        Err(Ok(AdminError::InvalidParameter))
+    );
+}
+
+#[test]
+fn test_backup_cannot_claim_while_admin_active() {
+    let env = Env::default();
+    let (client, _admin, backup) = setup(&env);
+
+    advance(&env, MIN_INACTIVITY_PERIOD - 1);
+    assert!(!client.is_admin_inactive());
+    assert_eq!(
+        client.try_claim_admin_on_inactivity(&backup),
+        Err(Ok(AdminError::DeadManSwitchNotTriggered))
+    );
+}
+
+#[test]
+fn test_heartbeat_resets_timer() {
+    let env = Env::default();
+    let (client, admin, backup) = setup(&env);
+
+    advance(&env, MIN_INACTIVITY_PERIOD - 10);
+    client.admin_heartbeat(&admin);
+    assert_eq!(
+        client.get_last_admin_activity(),
+        Some(1_000 + MIN_INACTIVITY_PERIOD - 10)
+    );
+
+    advance(&env, MIN_INACTIVITY_PERIOD - 1);
+    assert_eq!(
+        client.try_claim_admin_on_inactivity(&backup),
+        Err(Ok(AdminError::DeadManSwitchNotTriggered))
+    );
+}
+
+#[test]
+fn test_admin_action_counts_as_activity() {
+    let env = Env::default();
+    let (client, admin, _backup) = setup(&env);
+
+    advance(&env, MIN_INACTIVITY_PERIOD - 10);
+    client.grant_role(
+        &admin,
+        &soroban_sdk::Symbol::new(&env, "oracle_admin"),
+        &admin,
+    );
+
+    advance(&env, 20);
+    assert!(!client.is_admin_inactive());
+}
+
+#[test]
+fn test_backup_takes_over_after_inactivity() {
+    let env = Env::default();
+    let (client, admin, backup) = setup(&env);
+
+    advance(&env, MIN_INACTIVITY_PERIOD);
+    assert!(client.is_admin_inactive());
+    client.claim_admin_on_inactivity(&backup);
+
+    assert!(!client.is_admin_inactive());
+    assert_eq!(
+        client.try_admin_heartbeat(&admin),
+        Err(Ok(AdminError::Unauthorized))
+    );
+    client.admin_heartbeat(&backup);
+}
+
+#[test]
+fn test_only_configured_backup_can_claim() {
+    let env = Env::default();
+    let (client, _admin, _backup) = setup(&env);
+    let stranger = Address::generate(&env);
+
+    advance(&env, MIN_INACTIVITY_PERIOD);
+    assert_eq!(
+        client.try_claim_admin_on_inactivity(&stranger),
+        Err(Ok(AdminError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_disabled_switch_prevents_takeover() {
+    let env = Env::default();
+    let (client, admin, backup) = setup(&env);
+
+    client.configure_dead_man_switch(&admin, &None);
+    advance(&env, MIN_INACTIVITY_PERIOD * 2);
+
+    assert!(!client.is_admin_inactive());
+    assert_eq!(
+        client.try_claim_admin_on_inactivity(&backup),
+        Err(Ok(AdminError::DeadManSwitchNotConfigured))
+    );
+}
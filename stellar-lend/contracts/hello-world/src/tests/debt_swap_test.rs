@@ -0,0 +1,196 @@
+//! Debt swap tests.
+//!
+//! # Coverage
+//! - Swapping native debt into a token priced at half of XLM moves the debt
+//!   between the per-asset positions at oracle prices and charges the flash
+//!   loan fee to the reserve
+//! - Input validation, slippage bounds and missing AMM pairs
+//! - Debt and liquidity limits
+//! - Liquidatable positions cannot be refinanced
+
+use crate::cross_asset::AssetConfig;
+use crate::debt_swap::DebtSwapError;
+use crate::tests::test_helpers::setup_env_with_native_asset;
+use crate::HelloContractClient;
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env, Symbol, Vec,
+};
+use stellarlend_amm::{AmmProtocolConfig, TokenPair};
+
+const XLM_PRICE: i128 = 10_000_000;
+const TOKEN_PRICE: i128 = 5_000_000;
+
+fn list_asset(env: &Env, client: &HelloContractClient, asset: Option<Address>, price: i128) {
+    client.initialize_asset(
+        &asset,
+        &AssetConfig {
+            asset: asset.clone(),
+            collateral_factor: 7500,
+            liquidation_threshold: 8000,
+            reserve_factor: 1000,
+            max_supply: 0,
+            max_borrow: 0,
+            can_collateralize: true,
+            can_borrow: true,
+            price,
+            price_updated_at: env.ledger().timestamp(),
+            is_isolated: false,
+            isolated_debt_ceiling: 0,
+        },
+    );
+}
+
+/// List XLM ($1), a new token ($0.50) and a collateral asset ($1); deposit
+/// 100_000 collateral, borrow 50_000 XLM, give the contract 100_000 of the
+/// token to lend and register an AMM pool swapping the token against XLM
+/// (1% default slippage, 2% max). Returns (token, collateral asset)
+fn setup_swap(
+    env: &Env,
+    contract_id: &Address,
+    client: &HelloContractClient,
+    admin: &Address,
+    user: &Address,
+) -> (Address, Address) {
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let collateral = Address::generate(env);
+    client.initialize_ca(admin);
+    list_asset(env, client, None, XLM_PRICE);
+    list_asset(env, client, Some(token.clone()), TOKEN_PRICE);
+    list_asset(env, client, Some(collateral.clone()), XLM_PRICE);
+    client.cross_asset_deposit(user, &Some(collateral.clone()), &100_000);
+    client.cross_asset_borrow(user, &None, &50_000);
+    StellarAssetClient::new(env, &token).mint(contract_id, &100_000);
+
+    client.initialize_amm(admin, &100, &200, &10_000);
+    let mut supported_pairs = Vec::new(env);
+    supported_pairs.push_back(TokenPair {
+        token_a: None,
+        token_b: Some(token.clone()),
+        pool_address: Address::generate(env),
+    });
+    client.set_amm_pool(
+        admin,
+        &AmmProtocolConfig {
+            protocol_address: Address::generate(env),
+            protocol_name: Symbol::new(env, "TestAMM"),
+            enabled: true,
+            fee_tier: 30,
+            min_swap_amount: 1_000,
+            max_swap_amount: 1_000_000,
+            supported_pairs,
+        },
+    );
+    (token, collateral)
+}
+
+/// Mint the user the XLM the swap would deliver and approve the flash
+/// settlement
+fn fund_settlement(env: &Env, contract_id: &Address, native: &Address, user: &Address) {
+    let token = StellarAssetClient::new(env, native);
+    token.mint(user, &20_018);
+    token.approve(user, contract_id, &20_018, &(env.ledger().sequence() + 100));
+}
+
+#[test]
+fn test_swap_native_debt_into_token() {
+    let (env, id, client, admin, user, native) = setup_env_with_native_asset();
+    let (token, _collateral) = setup_swap(&env, &id, &client, &admin, &user);
+    fund_settlement(&env, &id, &native, &user);
+
+    // 9 bps flash fee on 20_000 → 20_018 XLM owed, worth 40_036 of the
+    // token at $0.50; 1% AMM slippage → 40_441 borrowed
+    let result = client.swap_debt(&user, &None, &Some(token.clone()), &20_000, &200);
+    assert_eq!(result.repaid, 20_000);
+    assert_eq!(result.flash_fee, 18);
+    assert_eq!(result.borrowed, 40_441);
+    assert!(result.health_after < result.health_before);
+    assert!(result.health_after >= 10_000);
+
+    assert_eq!(
+        client.get_user_asset_position(&user, &None).debt_principal,
+        30_000
+    );
+    assert_eq!(
+        client
+            .get_user_asset_position(&user, &Some(token.clone()))
+            .debt_principal,
+        40_441
+    );
+    // 30_000 XLM + 40_441 × $0.50 of debt against 80_000 of weighted collateral
+    let summary = client.get_user_position_summary(&user);
+    assert_eq!(summary.total_debt_value, 50_220);
+    assert_eq!(summary.health_factor, result.health_after);
+
+    assert_eq!(client.get_reserve_balance(&None), 18);
+    assert_eq!(TokenClient::new(&env, &native).balance(&user), 0);
+    assert_eq!(TokenClient::new(&env, &token).balance(&user), 40_441);
+}
+
+#[test]
+fn test_invalid_inputs_rejected() {
+    let (env, id, client, admin, user, native) = setup_env_with_native_asset();
+    let token = Some(setup_swap(&env, &id, &client, &admin, &user).0);
+
+    assert_eq!(
+        client.try_swap_debt(&user, &None, &token, &0, &200),
+        Err(Ok(DebtSwapError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_swap_debt(&user, &None, &None, &20_000, &200),
+        Err(Ok(DebtSwapError::SameAsset))
+    );
+    assert_eq!(
+        client.try_swap_debt(&user, &None, &token, &20_000, &10_000),
+        Err(Ok(DebtSwapError::SlippageExceeded))
+    );
+    // The AMM swaps at 1% slippage; the caller accepts 0.5%
+    assert_eq!(
+        client.try_swap_debt(&user, &None, &token, &20_000, &50),
+        Err(Ok(DebtSwapError::SlippageExceeded))
+    );
+
+    fund_settlement(&env, &id, &native, &user);
+    let unknown = Some(Address::generate(&env));
+    assert_eq!(
+        client.try_swap_debt(&user, &None, &unknown, &20_000, &200),
+        Err(Ok(DebtSwapError::UnsupportedPair))
+    );
+}
+
+#[test]
+fn test_debt_and_liquidity_limits() {
+    let (env, id, client, admin, user, _native) = setup_env_with_native_asset();
+    let (token, _collateral) = setup_swap(&env, &id, &client, &admin, &user);
+
+    assert_eq!(
+        client.try_swap_debt(&user, &None, &Some(token.clone()), &50_001, &200),
+        Err(Ok(DebtSwapError::InsufficientDebt))
+    );
+
+    // Drain the contract's token below the 40_441 the swap borrows
+    TokenClient::new(&env, &token).transfer(&id, &admin, &70_000);
+    assert_eq!(
+        client.try_swap_debt(&user, &None, &Some(token), &20_000, &200),
+        Err(Ok(DebtSwapError::InsufficientLiquidity))
+    );
+}
+
+#[test]
+fn test_liquidatable_position_rejected() {
+    let (env, id, client, admin, user, native) = setup_env_with_native_asset();
+    let (token, collateral) = setup_swap(&env, &id, &client, &admin, &user);
+    let token = Some(token);
+    fund_settlement(&env, &id, &native, &user);
+
+    // Collateral halves in price: 50_000 × 80% no longer covers 50_000 of debt
+    client.update_asset_price(&Some(collateral), &5_000_000);
+
+    assert_eq!(
+        client.try_swap_debt(&user, &None, &token, &20_000, &200),
+        Err(Ok(DebtSwapError::HealthCheckFailed))
+    );
+}
@@ -0,0 +1,188 @@
+//! Health factor-based dynamic close factor tests.
+//!
+//! # Coverage
+//! - Without health tiers the base close factor applies to liquidatable positions
+//! - Healthy or missing positions have a dynamic close factor of 0
+//! - More severe health factors unlock a larger close factor
+//! - `liquidate` enforces the dynamic close factor, including above the base
+//! - Position size tiers still cap the close factor for large debts
+//! - Tiers must be sorted, cover up to HF 1.0 and not increase with health
+//! - Setting tiers needs the caller's authorization
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::liquidate::{liquidate, LiquidationError};
+use crate::risk_management::{HealthCloseFactorTier, RiskManagementError};
+use crate::risk_params::CloseFactorTier;
+use crate::tests::test_helpers::assert_authorized;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, Vec};
+
+fn setup(e: &Env) -> (Address, Address, HelloContractClient<'_>) {
+    e.mock_all_auths();
+    let id = e.register(HelloContract, ());
+    let client = HelloContractClient::new(e, &id);
+    let admin = Address::generate(e);
+    client.initialize(&admin);
+    (id, admin, client)
+}
+
+fn tier(max_health_factor: i128, close_factor: i128) -> HealthCloseFactorTier {
+    HealthCloseFactorTier {
+        max_health_factor,
+        close_factor,
+    }
+}
+
+/// 100% below HF 0.8, 50% below 0.95, 25% below 1.0
+fn tiers(e: &Env) -> Vec<HealthCloseFactorTier> {
+    vec![
+        e,
+        tier(8_000, 10_000),
+        tier(9_500, 5_000),
+        tier(10_000, 2_500),
+    ]
+}
+
+/// Write a native position directly; the liquidation threshold is 105%
+fn set_position(e: &Env, id: &Address, user: &Address, collateral: i128, debt: i128) {
+    e.as_contract(id, || {
+        e.storage().persistent().set(
+            &DepositDataKey::CollateralBalance(user.clone()),
+            &collateral,
+        );
+        e.storage().persistent().set(
+            &DepositDataKey::Position(user.clone()),
+            &Position {
+                collateral,
+                debt,
+                borrow_interest: 0,
+                last_accrual_time: e.ledger().timestamp(),
+            },
+        );
+    });
+}
+
+#[test]
+fn test_base_close_factor_without_tiers() {
+    let e = Env::default();
+    let (id, _admin, client) = setup(&e);
+    let borrower = Address::generate(&e);
+    let healthy = Address::generate(&e);
+
+    assert_eq!(client.get_health_close_factor_tiers().len(), 0);
+    assert_eq!(client.get_dynamic_close_factor(&borrower), 0);
+
+    set_position(&e, &id, &borrower, 1_000, 1_000);
+    set_position(&e, &id, &healthy, 1_500, 1_000);
+    assert_eq!(client.get_dynamic_close_factor(&borrower), 5_000);
+    assert_eq!(client.get_dynamic_close_factor(&healthy), 0);
+}
+
+#[test]
+fn test_close_factor_scales_with_severity() {
+    let e = Env::default();
+    let (id, admin, client) = setup(&e);
+    let mild = Address::generate(&e);
+    let moderate = Address::generate(&e);
+    let severe = Address::generate(&e);
+    client.set_health_close_factor_tiers(&admin, &tiers(&e));
+
+    // HF ~0.952, ~0.857 and ~0.762
+    set_position(&e, &id, &mild, 1_000, 1_000);
+    set_position(&e, &id, &moderate, 900, 1_000);
+    set_position(&e, &id, &severe, 800, 1_000);
+
+    assert_eq!(client.get_dynamic_close_factor(&mild), 2_500);
+    assert_eq!(client.get_dynamic_close_factor(&moderate), 5_000);
+    assert_eq!(client.get_dynamic_close_factor(&severe), 10_000);
+}
+
+#[test]
+fn test_liquidate_enforces_dynamic_close_factor() {
+    let e = Env::default();
+    let (id, admin, client) = setup(&e);
+    let liquidator = Address::generate(&e);
+    let mild = Address::generate(&e);
+    let severe = Address::generate(&e);
+    client.set_health_close_factor_tiers(&admin, &tiers(&e));
+    set_position(&e, &id, &mild, 1_000, 1_000);
+    set_position(&e, &id, &severe, 800, 1_000);
+
+    e.as_contract(&id, || {
+        assert_eq!(
+            liquidate(&e, liquidator.clone(), mild.clone(), None, None, 251),
+            Err(LiquidationError::ExceedsCloseFactor)
+        );
+        let (debt_liquidated, _, _) =
+            liquidate(&e, liquidator.clone(), mild.clone(), None, None, 250).unwrap();
+        assert_eq!(debt_liquidated, 250);
+
+        // Severe positions can be closed beyond the 50% base close factor
+        let (debt_liquidated, _, _) =
+            liquidate(&e, liquidator.clone(), severe.clone(), None, None, 600).unwrap();
+        assert_eq!(debt_liquidated, 600);
+    });
+}
+
+#[test]
+fn test_size_tier_still_caps_close_factor() {
+    let e = Env::default();
+    let (id, admin, client) = setup(&e);
+    let severe = Address::generate(&e);
+    client.set_health_close_factor_tiers(&admin, &tiers(&e));
+    client.set_close_factor_tiers(
+        &admin,
+        &vec![
+            &e,
+            CloseFactorTier {
+                min_debt: 1_000,
+                close_factor: 2_000,
+            },
+        ],
+    );
+
+    set_position(&e, &id, &severe, 800, 1_000);
+    assert_eq!(client.get_dynamic_close_factor(&severe), 2_000);
+}
+
+#[test]
+fn test_invalid_tiers_rejected() {
+    let e = Env::default();
+    let (id, admin, client) = setup(&e);
+    let stranger = Address::generate(&e);
+
+    assert_eq!(
+        client.try_set_health_close_factor_tiers(&stranger, &tiers(&e)),
+        Err(Ok(RiskManagementError::Unauthorized))
+    );
+
+    let unsorted = vec![&e, tier(9_500, 5_000), tier(8_000, 10_000)];
+    assert_eq!(
+        client.try_set_health_close_factor_tiers(&admin, &unsorted),
+        Err(Ok(RiskManagementError::InvalidParameter))
+    );
+
+    let short = vec![&e, tier(8_000, 10_000), tier(9_500, 5_000)];
+    assert_eq!(
+        client.try_set_health_close_factor_tiers(&admin, &short),
+        Err(Ok(RiskManagementError::InvalidParameter))
+    );
+
+    let increasing = vec![&e, tier(8_000, 2_500), tier(10_000, 5_000)];
+    assert_eq!(
+        client.try_set_health_close_factor_tiers(&admin, &increasing),
+        Err(Ok(RiskManagementError::InvalidCloseFactor))
+    );
+
+    let zero = vec![&e, tier(10_000, 0)];
+    assert_eq!(
+        client.try_set_health_close_factor_tiers(&admin, &zero),
+        Err(Ok(RiskManagementError::InvalidCloseFactor))
+    );
+
+    // An empty vector clears the tiers
+    client.set_health_close_factor_tiers(&admin, &tiers(&e));
+    assert_authorized(&e, &admin, &id, "set_health_close_factor_tiers");
+    client.set_health_close_factor_tiers(&admin, &Vec::new(&e));
+    assert_eq!(client.get_health_close_factor_tiers().len(), 0);
+}
@@ -0,0 +1,152 @@
+//! Cross-asset e-mode tests.
+//!
+//! # Coverage
+//! - Category parameters replace asset thresholds in the health factor
+//! - E-mode unlocks borrows that the default parameters reject
+//! - Users in e-mode can only borrow category assets
+//! - Entering requires existing debt to be in the category
+//! - Leaving is rejected if it would make the position unhealthy
+//! - Invalid categories and unknown ids are rejected
+
+use crate::cross_asset::{
+    cross_asset_borrow, cross_asset_deposit, get_user_emode, get_user_position_summary, initialize,
+    initialize_asset, set_emode_category, set_user_emode, AssetConfig, AssetKey, CrossAssetError,
+    EModeCategory,
+};
+use crate::HelloContract;
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, Symbol};
+
+fn asset_config(env: &Env, asset: &Address) -> AssetConfig {
+    AssetConfig {
+        asset: Some(asset.clone()),
+        collateral_factor: 7500,
+        liquidation_threshold: 8000,
+        reserve_factor: 1000,
+        max_supply: 0,
+        max_borrow: 0,
+        can_collateralize: true,
+        can_borrow: true,
+        price: 10_000_000,
+        price_updated_at: env.ledger().timestamp(),
+        is_isolated: false,
+        isolated_debt_ceiling: 0,
+    }
+}
+
+fn stables(env: &Env, usdc: &Address, usdt: &Address) -> EModeCategory {
+    EModeCategory {
+        label: Symbol::new(env, "stables"),
+        collateral_factor: 9300,
+        liquidation_threshold: 9500,
+        assets: vec![
+            env,
+            AssetKey::Token(usdc.clone()),
+            AssetKey::Token(usdt.clone()),
+        ],
+    }
+}
+
+/// Returns (env, contract, user, usdc, usdt, volatile)
+fn setup() -> (Env, Address, Address, Address, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let usdc = Address::generate(&env);
+    let usdt = Address::generate(&env);
+    let volatile = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        initialize(&env, admin.clone()).unwrap();
+        for asset in [&usdc, &usdt, &volatile] {
+            initialize_asset(&env, Some(asset.clone()), asset_config(&env, asset)).unwrap();
+        }
+        set_emode_category(&env, 1, stables(&env, &usdc, &usdt)).unwrap();
+        cross_asset_deposit(&env, user.clone(), Some(usdc.clone()), 1_000).unwrap();
+    });
+
+    (env, contract_id, user, usdc, usdt, volatile)
+}
+
+#[test]
+fn test_emode_applies_category_threshold() {
+    let (env, id, user, _usdc, usdt, _volatile) = setup();
+    env.as_contract(&id, || {
+        assert_eq!(
+            cross_asset_borrow(&env, user.clone(), Some(usdt.clone()), 900),
+            Err(CrossAssetError::ExceedsBorrowCapacity)
+        );
+
+        set_user_emode(&env, user.clone(), 1).unwrap();
+        assert_eq!(get_user_emode(&env, &user), 1);
+        cross_asset_borrow(&env, user.clone(), Some(usdt), 900).unwrap();
+
+        let summary = get_user_position_summary(&env, &user).unwrap();
+        assert_eq!(summary.weighted_collateral_value, 950);
+        assert_eq!(summary.health_factor, 950 * 10_000 / 900);
+    });
+}
+
+#[test]
+fn test_emode_restricts_borrowable_assets() {
+    let (env, id, user, _usdc, _usdt, volatile) = setup();
+    env.as_contract(&id, || {
+        set_user_emode(&env, user.clone(), 1).unwrap();
+        assert_eq!(
+            cross_asset_borrow(&env, user.clone(), Some(volatile), 100),
+            Err(CrossAssetError::InvalidEMode)
+        );
+    });
+}
+
+#[test]
+fn test_enter_requires_debt_in_category() {
+    let (env, id, user, _usdc, _usdt, volatile) = setup();
+    env.as_contract(&id, || {
+        cross_asset_borrow(&env, user.clone(), Some(volatile), 100).unwrap();
+        assert_eq!(
+            set_user_emode(&env, user.clone(), 1),
+            Err(CrossAssetError::InvalidEMode)
+        );
+        assert_eq!(get_user_emode(&env, &user), 0);
+    });
+}
+
+#[test]
+fn test_leave_rejected_when_unhealthy() {
+    let (env, id, user, _usdc, usdt, _volatile) = setup();
+    env.as_contract(&id, || {
+        set_user_emode(&env, user.clone(), 1).unwrap();
+        cross_asset_borrow(&env, user.clone(), Some(usdt), 900).unwrap();
+
+        assert_eq!(
+            set_user_emode(&env, user.clone(), 0),
+            Err(CrossAssetError::UnhealthyPosition)
+        );
+        assert_eq!(get_user_emode(&env, &user), 1);
+    });
+}
+
+#[test]
+fn test_invalid_categories_rejected() {
+    let (env, id, user, usdc, usdt, _volatile) = setup();
+    env.as_contract(&id, || {
+        assert_eq!(
+            set_emode_category(&env, 0, stables(&env, &usdc, &usdt)),
+            Err(CrossAssetError::InvalidEMode)
+        );
+
+        let mut loose = stables(&env, &usdc, &usdt);
+        loose.liquidation_threshold = 9000;
+        assert_eq!(
+            set_emode_category(&env, 2, loose),
+            Err(CrossAssetError::InvalidEMode)
+        );
+
+        assert_eq!(
+            set_user_emode(&env, user.clone(), 7),
+            Err(CrossAssetError::InvalidEMode)
+        );
+    });
+}
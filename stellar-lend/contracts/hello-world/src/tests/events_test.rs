@@ -36,6 +36,7 @@ pub struct TestDepositEvent {
     pub asset: Option<Address>,
     pub amount: i128,
     pub timestamp: u64,
+    pub operation_id: u64,
 }
 
 #[contracttype]
@@ -45,6 +46,7 @@ pub struct TestWithdrawalEvent {
     pub asset: Option<Address>,
     pub amount: i128,
     pub timestamp: u64,
+    pub operation_id: u64,
 }
 
 #[contracttype]
@@ -54,6 +56,7 @@ pub struct TestBorrowEvent {
     pub asset: Option<Address>,
     pub amount: i128,
     pub timestamp: u64,
+    pub operation_id: u64,
 }
 
 #[contracttype]
@@ -63,6 +66,7 @@ pub struct TestRepayEvent {
     pub asset: Option<Address>,
     pub amount: i128,
     pub timestamp: u64,
+    pub operation_id: u64,
 }
 
 #[contracttype]
@@ -173,6 +177,7 @@ fn test_deposit_event_structure() {
                 asset: None,
                 amount: 1_000,
                 timestamp: 100,
+                operation_id: 1,
             },
         );
 
@@ -187,6 +192,7 @@ fn test_deposit_event_structure() {
         assert_eq!(decoded.asset, None, "Native XLM should have None asset");
         assert_eq!(decoded.amount, 1_000);
         assert_eq!(decoded.timestamp, 100);
+        assert_eq!(decoded.operation_id, 1);
     });
 }
 
@@ -208,6 +214,7 @@ fn test_withdrawal_event_structure() {
                 asset: Some(asset.clone()),
                 amount: 500,
                 timestamp: 200,
+                operation_id: 1,
             },
         );
 
@@ -221,6 +228,7 @@ fn test_withdrawal_event_structure() {
         assert_eq!(decoded.asset, Some(asset));
         assert_eq!(decoded.amount, 500);
         assert_eq!(decoded.timestamp, 200);
+        assert_eq!(decoded.operation_id, 1);
     });
 }
 
@@ -241,6 +249,7 @@ fn test_borrow_event_structure() {
                 asset: None,
                 amount: 5_000,
                 timestamp: 300,
+                operation_id: 1,
             },
         );
 
@@ -253,6 +262,7 @@ fn test_borrow_event_structure() {
         assert_eq!(decoded.user, user);
         assert_eq!(decoded.amount, 5_000);
         assert_eq!(decoded.timestamp, 300);
+        assert_eq!(decoded.operation_id, 1);
     });
 }
 
@@ -273,6 +283,7 @@ fn test_repay_event_structure() {
                 asset: None,
                 amount: 2_000,
                 timestamp: 400,
+                operation_id: 1,
             },
         );
 
@@ -285,6 +296,7 @@ fn test_repay_event_structure() {
         assert_eq!(decoded.user, user);
         assert_eq!(decoded.amount, 2_000);
         assert_eq!(decoded.timestamp, 400);
+        assert_eq!(decoded.operation_id, 1);
     });
 }
 
@@ -621,6 +633,7 @@ fn test_all_event_helpers_emit_one_event_each() {
                 asset: None,
                 amount: 1,
                 timestamp: 0,
+                operation_id: 1,
             },
         );
         emit_withdrawal(
@@ -630,6 +643,7 @@ fn test_all_event_helpers_emit_one_event_each() {
                 asset: None,
                 amount: 1,
                 timestamp: 0,
+                operation_id: 1,
             },
         );
         emit_borrow(
@@ -639,6 +653,7 @@ fn test_all_event_helpers_emit_one_event_each() {
                 asset: None,
                 amount: 1,
                 timestamp: 0,
+                operation_id: 1,
             },
         );
         emit_repay(
@@ -648,6 +663,7 @@ fn test_all_event_helpers_emit_one_event_each() {
                 asset: None,
                 amount: 1,
                 timestamp: 0,
+                operation_id: 1,
             },
         );
         emit_liquidation(
@@ -749,6 +765,7 @@ fn test_event_with_none_asset_native_xlm() {
                 asset: None,
                 amount: 0,
                 timestamp: 0,
+                operation_id: 1,
             },
         );
 
@@ -782,6 +799,7 @@ fn test_no_sensitive_data_in_deposit_event() {
                 asset: None,
                 amount: 1_000,
                 timestamp: 123,
+                operation_id: 1,
             },
         );
 
@@ -0,0 +1,124 @@
+//! Exit exemption list tests.
+//!
+//! # Coverage
+//! - Exempt accounts pay no bridge withdrawal fee; others still do
+//! - Governance `ExitExemption` proposals update the list
+//! - The list is admin only, needs the admin's authorization, is idempotent
+//!   and bounded
+
+use crate::cross_asset::AssetConfig;
+use crate::risk_management::{RiskManagementError, MAX_EXIT_EXEMPTIONS};
+use crate::tests::test_helpers::{assert_authorized, setup_env_with_native_asset};
+use crate::types::{ProposalType, VoteType};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::StellarAssetClient,
+    vec, Address, String,
+};
+
+#[test]
+fn test_exempt_account_pays_no_bridge_fee() {
+    let (env, _id, client, admin, user, _native) = setup_env_with_native_asset();
+    let asset = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    client.initialize_ca(&admin);
+    client.initialize_asset(
+        &Some(asset.clone()),
+        &AssetConfig {
+            asset: Some(asset.clone()),
+            collateral_factor: 7500,
+            liquidation_threshold: 8000,
+            reserve_factor: 1000,
+            max_supply: 0,
+            max_borrow: 0,
+            can_collateralize: true,
+            can_borrow: true,
+            price: 10_000_000,
+            price_updated_at: env.ledger().timestamp(),
+            is_isolated: false,
+            isolated_debt_ceiling: 0,
+        },
+    );
+    // 1% bridge fee
+    client.register_bridge(&admin, &1, &Address::generate(&env), &100);
+    client.bridge_deposit(&user, &1, &Some(asset.clone()), &10_000);
+    client.bridge_deposit(&treasury, &1, &Some(asset.clone()), &10_000);
+
+    client.set_exit_exemption(&admin, &treasury, &true);
+    assert_eq!(client.get_exit_exemptions(), vec![&env, treasury.clone()]);
+    assert_eq!(
+        client.bridge_withdraw(&treasury, &1, &Some(asset.clone()), &5_000),
+        5_000
+    );
+    assert_eq!(
+        client.bridge_withdraw(&user, &1, &Some(asset.clone()), &5_000),
+        4_950
+    );
+
+    client.set_exit_exemption(&admin, &treasury, &false);
+    assert_eq!(
+        client.bridge_withdraw(&treasury, &1, &Some(asset), &1_000),
+        990
+    );
+}
+
+#[test]
+fn test_governance_sets_exit_exemption() {
+    let (env, _id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let vote_token = env.register_stellar_asset_contract(admin.clone());
+    let proposer = Address::generate(&env);
+    let strategy = Address::generate(&env);
+    StellarAssetClient::new(&env, &vote_token).mint(&proposer, &1_000);
+    client.gov_initialize(
+        &admin,
+        &vote_token,
+        &Some(259200),
+        &Some(86400),
+        &Some(400),
+        &Some(100),
+        &Some(604800),
+        &Some(5000),
+    );
+
+    let proposal_id = client.gov_create_proposal(
+        &proposer,
+        &ProposalType::ExitExemption(strategy.clone(), true),
+        &String::from_str(&env, "Exempt vault strategy"),
+        &None,
+    );
+    env.ledger().with_mut(|li| li.timestamp += 1);
+    client.gov_vote(&proposer, &proposal_id, &VoteType::For);
+    env.ledger().with_mut(|li| li.timestamp += 259200);
+    client.gov_queue_proposal(&admin, &proposal_id);
+    env.ledger().with_mut(|li| li.timestamp += 86400);
+    client.gov_execute_proposal(&admin, &proposal_id);
+
+    assert_eq!(client.get_exit_exemptions(), vec![&env, strategy]);
+}
+
+#[test]
+fn test_exemption_list_management() {
+    let (env, id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let account = Address::generate(&env);
+    assert_eq!(
+        client.try_set_exit_exemption(&account, &account, &true),
+        Err(Ok(RiskManagementError::Unauthorized))
+    );
+
+    // Repeated updates are no-ops
+    client.set_exit_exemption(&admin, &account, &true);
+    assert_authorized(&env, &admin, &id, "set_exit_exemption");
+    client.set_exit_exemption(&admin, &account, &true);
+    assert_eq!(client.get_exit_exemptions().len(), 1);
+    client.set_exit_exemption(&admin, &account, &false);
+    client.set_exit_exemption(&admin, &account, &false);
+    assert_eq!(client.get_exit_exemptions().len(), 0);
+
+    for _ in 0..MAX_EXIT_EXEMPTIONS {
+        client.set_exit_exemption(&admin, &Address::generate(&env), &true);
+    }
+    assert_eq!(
+        client.try_set_exit_exemption(&admin, &account, &true),
+        Err(Ok(RiskManagementError::InvalidParameter))
+    );
+}
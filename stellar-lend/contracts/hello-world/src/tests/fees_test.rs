@@ -1,13 +1,26 @@
-use crate::{
-    deposit::AssetParams,
-    deposit::DepositDataKey,
-    flash_loan::{FlashLoanConfig, FlashLoanDataKey},
-    HelloContract, HelloContractClient,
-};
+//! Protocol fee and reserve tests.
+//!
+//! # Coverage
+//! - Borrow fees, the interest reserve factor and flash loan fees accrue to
+//!   protocol reserves; reserve claims are admin-only and bounded
+//! - Fees are off by default and need a collector to switch on
+//! - Withdrawal fees are withheld, accrue and are claimed by the collector
+//! - Origination fees accrue on borrows and are swept to the collector
+//! - Per-asset flash loan fees replace the base fee; discounts still apply
+//! - Fee rates are admin-only, bounded and need the admin's authorization
+
+use crate::deposit::{AssetParams, DepositDataKey};
+use crate::fees::{FeeConfig, FeeKind, FeesError};
+use crate::flash_loan::{get_asset_flash_loan_fee_bps, FlashLoanConfig};
+use crate::tests::test_helpers::{assert_authorized, setup_env_with_native_asset};
+use crate::{HelloContract, HelloContractClient};
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
-    Address, Env, Map, Symbol,
+    contracttype,
+    testutils::{Address as _, Events, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Bytes, Env, TryFromVal, Vec,
 };
+use stellarlend_testutils::{register_mock_receiver, register_mock_token, ReceiverBehavior};
 
 /// Helper function to create a test environment
 fn create_test_env() -> Env {
@@ -84,9 +97,9 @@ fn test_interest_reserve_factor() {
             .set(&DepositDataKey::AssetParams(asset.clone()), &params);
 
         let position = crate::deposit::Position {
-            collateral: 10000,
-            debt: 1000,
-            borrow_interest: 100,
+            collateral: 200_000,
+            debt: 100_000,
+            borrow_interest: 0,
             last_accrual_time: env.ledger().timestamp(),
         };
         env.storage()
@@ -94,10 +107,20 @@ fn test_interest_reserve_factor() {
             .set(&DepositDataKey::Position(user.clone()), &position);
     });
 
+    // One year at the 1% base rate accrues 1000 of interest; the default
+    // 10% reserve factor books 100 of it when the repayment accrues
+    env.ledger()
+        .with_mut(|li| li.timestamp += 365 * 24 * 60 * 60);
     client.repay_debt(&user, &Some(asset.clone()), &100);
 
     let reserve_balance = client.get_reserve_balance(&Some(asset.clone()));
-    assert_eq!(reserve_balance, 10);
+    assert_eq!(reserve_balance, 100);
+    assert_eq!(
+        client
+            .get_reserve_account(&Some(asset.clone()))
+            .interest_accrued,
+        100
+    );
 }
 
 #[test]
@@ -137,8 +160,11 @@ fn test_flash_loan_fee_collection() {
 
     let admin = Address::generate(&env);
     let user = Address::generate(&env);
-    let asset = Address::generate(&env);
-    let callback = Address::generate(&env);
+    let token = register_mock_token(&env);
+    let asset = token.address.clone();
+    let callback = register_mock_receiver(&env, &contract_id, ReceiverBehavior::Repay);
+    token.mint(&contract_id, 1_000);
+    token.mint(&callback, 10);
 
     client.initialize(&admin);
 
@@ -153,18 +179,12 @@ fn test_flash_loan_fee_collection() {
             &crate::flash_loan::FlashLoanDataKey::FlashLoanConfig,
             &config,
         );
-
-        // Mock some balance in the contract for the asset
-        // (In a real test we'd use token.mint, but here we're testing accounting)
     });
 
-    // Execute flash loan
-    // execute_flash_loan returns principal + fee
-    let total = client.execute_flash_loan(&user, &asset, &1000, &callback);
+    // Execute flash loan; the receiver repays principal + fee in the same call
+    let total = client.execute_flash_loan(&user, &asset, &1000, &callback, &Bytes::new(&env));
     assert_eq!(total, 1001); // 1000 + 1 (10 bps of 1000)
-
-    // Repay flash loan
-    client.repay_flash_loan(&user, &asset, &total);
+    assert_eq!(token.balance(&contract_id), 1_001);
 
     let reserve_balance = client.get_reserve_balance(&Some(asset));
     assert_eq!(reserve_balance, 1); // Fee should be in reserves
@@ -319,3 +339,164 @@ fn test_claim_reserves_exceeding_balance() {
 
     client.claim_reserves(&admin, &Some(asset), &admin, &1000);
 }
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestFeeAccruedEvent {
+    pub kind: FeeKind,
+    pub user: Address,
+    pub asset: Option<Address>,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+fn fee_accrued_event(env: &Env) -> Option<TestFeeAccruedEvent> {
+    env.events()
+        .all()
+        .iter()
+        .find_map(|(_contract, _topics, data)| TestFeeAccruedEvent::try_from_val(env, &data).ok())
+}
+
+fn fees(origination: i128, flash_loan: Option<i128>, withdrawal: i128) -> FeeConfig {
+    FeeConfig {
+        origination_fee_bps: origination,
+        flash_loan_fee_bps: flash_loan,
+        withdrawal_fee_bps: withdrawal,
+    }
+}
+
+#[test]
+fn test_fees_off_by_default() {
+    let (_env, _id, client, admin, user, _native) = setup_env_with_native_asset();
+    client.set_asset_fees(&admin, &None, &fees(200, None, 100));
+    assert!(!client.is_fee_switch_on());
+    assert_eq!(
+        client.try_set_fee_switch(&admin, &true),
+        Err(Ok(FeesError::CollectorNotSet))
+    );
+
+    client.deposit_collateral(&user, &None, &10_000);
+    client.borrow_asset(&user, &None, &1_000);
+    client.withdraw_collateral(&user, &None, &1_000);
+    assert_eq!(client.get_accrued_fees(&None), 0);
+}
+
+#[test]
+fn test_withdrawal_fee_claimed_by_collector() {
+    let (env, id, client, admin, user, _native) = setup_env_with_native_asset();
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let asset = Some(token.clone());
+    StellarAssetClient::new(&env, &token).mint(&user, &10_000);
+    TokenClient::new(&env, &token).approve(&user, &id, &10_000, &(env.ledger().sequence() + 100));
+    client.deposit_collateral(&user, &asset, &10_000);
+
+    let collector = Address::generate(&env);
+    client.set_fee_collector(&admin, &collector);
+    assert_authorized(&env, &admin, &id, "set_fee_collector");
+    client.set_fee_switch(&admin, &true);
+    assert_authorized(&env, &admin, &id, "set_fee_switch");
+    client.set_asset_fees(&admin, &asset, &fees(0, None, 100));
+    assert_authorized(&env, &admin, &id, "set_asset_fees");
+
+    client.withdraw_collateral(&user, &asset, &5_000);
+    assert_eq!(
+        fee_accrued_event(&env),
+        Some(TestFeeAccruedEvent {
+            kind: FeeKind::Withdrawal,
+            user: user.clone(),
+            asset: asset.clone(),
+            amount: 50,
+            timestamp: env.ledger().timestamp(),
+        })
+    );
+    assert_eq!(TokenClient::new(&env, &token).balance(&user), 4_950);
+    assert_eq!(client.get_accrued_fees(&asset), 50);
+
+    assert_eq!(
+        client.try_claim_fees(&user, &asset),
+        Err(Ok(FeesError::Unauthorized))
+    );
+    assert_eq!(client.claim_fees(&collector, &asset), 50);
+    assert_eq!(TokenClient::new(&env, &token).balance(&collector), 50);
+    assert_eq!(client.get_accrued_fees(&asset), 0);
+    assert_eq!(
+        client.try_claim_fees(&collector, &asset),
+        Err(Ok(FeesError::NothingToClaim))
+    );
+}
+
+#[test]
+fn test_origination_fee_swept_to_collector() {
+    let (env, id, client, admin, user, native) = setup_env_with_native_asset();
+    let collector = Address::generate(&env);
+    client.set_fee_collector(&admin, &collector);
+    client.set_fee_switch(&admin, &true);
+    client.set_asset_fees(&admin, &None, &fees(200, None, 0));
+
+    client.deposit_collateral(&user, &None, &10_000);
+    client.borrow_asset(&user, &None, &1_000);
+    assert_eq!(
+        fee_accrued_event(&env).map(|event| (event.kind, event.amount)),
+        Some((FeeKind::Origination, 20))
+    );
+    assert_eq!(client.get_user_report(&user).position.debt, 1_000);
+    assert_eq!(client.get_accrued_fees(&None), 20);
+
+    // Native transfers are skipped in unit tests; fund the fee balance
+    StellarAssetClient::new(&env, &native).mint(&id, &20);
+    let other = Some(Address::generate(&env));
+    assert_eq!(
+        client.sweep_fees(&Vec::from_array(&env, [None, other])),
+        Vec::from_array(&env, [20, 0])
+    );
+    assert_eq!(TokenClient::new(&env, &native).balance(&collector), 20);
+}
+
+#[test]
+fn test_asset_flash_loan_fee() {
+    let (env, id, client, admin, user, _native) = setup_env_with_native_asset();
+    let asset = Address::generate(&env);
+    let fee_bps = || {
+        env.as_contract(&id, || {
+            get_asset_flash_loan_fee_bps(&env, &user, &Some(asset.clone()))
+        })
+    };
+    client.set_asset_fees(&admin, &Some(asset.clone()), &fees(0, Some(30), 0));
+    assert_eq!(fee_bps(), 9);
+
+    client.set_fee_collector(&admin, &Address::generate(&env));
+    client.set_fee_switch(&admin, &true);
+    assert_eq!(fee_bps(), 30);
+    assert_eq!(client.get_flash_loan_fee_bps(&user), 9);
+
+    client.set_flash_loan_integrator(&admin, &user, &true);
+    assert_eq!(fee_bps(), 5);
+}
+
+#[test]
+fn test_fee_configuration_checks() {
+    let (env, _id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let outsider = Address::generate(&env);
+    assert_eq!(
+        client.try_set_asset_fees(&outsider, &None, &fees(100, None, 100)),
+        Err(Ok(FeesError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_fee_collector(&outsider, &outsider),
+        Err(Ok(FeesError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_asset_fees(&admin, &None, &fees(1_001, None, 0)),
+        Err(Ok(FeesError::InvalidFee))
+    );
+    assert_eq!(
+        client.try_set_asset_fees(&admin, &None, &fees(0, Some(-1), 0)),
+        Err(Ok(FeesError::InvalidFee))
+    );
+
+    client.set_asset_fees(&admin, &None, &fees(100, Some(20), 50));
+    assert_eq!(client.get_asset_fees(&None), fees(100, Some(20), 50));
+    assert_eq!(client.get_fee_collector(), None);
+}
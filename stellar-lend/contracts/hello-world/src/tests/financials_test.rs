@@ -0,0 +1,115 @@
+//! Protocol financial statement tests.
+//!
+//! # Coverage
+//! - Fees and rewards are booked per asset, with expenses reducing net revenue
+//! - Counters roll over to a new statement every 30-day period
+//! - Periods without activity report no assets
+
+use crate::fees::FeeConfig;
+use crate::financials::{AssetFinancials, FINANCIAL_PERIOD_SECONDS};
+use crate::rewards::RewardSide;
+use crate::tests::test_helpers::setup_env_with_native_asset;
+use crate::HelloContractClient;
+use soroban_sdk::{testutils::Address as _, token::StellarAssetClient, vec, Address, Env};
+use stellarlend_testutils::advance_time;
+
+/// Charge a 2% origination fee on native borrows
+fn enable_origination_fee(client: &HelloContractClient, env: &Env, admin: &Address) {
+    client.set_fee_collector(admin, &Address::generate(env));
+    client.set_fee_switch(admin, &true);
+    client.set_asset_fees(
+        admin,
+        &None,
+        &FeeConfig {
+            origination_fee_bps: 200,
+            flash_loan_fee_bps: None,
+            withdrawal_fee_bps: 0,
+        },
+    );
+}
+
+fn financials(asset: Option<Address>) -> AssetFinancials {
+    AssetFinancials {
+        asset,
+        interest_collected: 0,
+        flash_loan_fees: 0,
+        amm_fees: 0,
+        bridge_fees: 0,
+        protocol_fees: 0,
+        rewards_paid: 0,
+        subsidies_paid: 0,
+        keeper_reimbursements: 0,
+        net_revenue: 0,
+    }
+}
+
+#[test]
+fn test_revenue_and_expenses_per_asset() {
+    let (env, _id, client, admin, user, _native) = setup_env_with_native_asset();
+    enable_origination_fee(&client, &env, &admin);
+    let reward_token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    StellarAssetClient::new(&env, &reward_token).mint(&admin, &10_000);
+    client.set_reward_token(&admin, &reward_token);
+    client.fund_rewards(&admin, &10_000);
+    client.set_emission_rate(&admin, &None, &RewardSide::Supply, &10);
+
+    client.deposit_collateral(&user, &None, &10_000);
+    advance_time(&env, 100);
+    assert_eq!(client.claim_rewards(&user), 1_000);
+    client.borrow_asset(&user, &None, &1_000);
+
+    let statement = client.get_financials(&0);
+    assert_eq!(
+        statement.assets,
+        vec![
+            &env,
+            AssetFinancials {
+                rewards_paid: 1_000,
+                net_revenue: -1_000,
+                ..financials(Some(reward_token))
+            },
+            AssetFinancials {
+                protocol_fees: 20,
+                net_revenue: 20,
+                ..financials(None)
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_statements_roll_over_by_period() {
+    let (env, _id, client, admin, user, _native) = setup_env_with_native_asset();
+    let other = Address::generate(&env);
+    enable_origination_fee(&client, &env, &admin);
+    client.deposit_collateral(&user, &None, &100_000);
+    client.borrow_asset(&user, &None, &50_000);
+    assert_eq!(client.get_financial_period(), 0);
+
+    advance_time(&env, FINANCIAL_PERIOD_SECONDS);
+    assert_eq!(client.get_financial_period(), 1);
+    client.deposit_collateral(&other, &None, &10_000);
+    client.borrow_asset(&other, &None, &500);
+    // Borrowing again accrues the first borrower's interest in this period
+    client.borrow_asset(&user, &None, &100);
+
+    let previous = client.get_financials(&0);
+    assert_eq!(previous.assets.get_unchecked(0).protocol_fees, 1_000);
+
+    let current = client.get_financials(&1);
+    assert_eq!(
+        (current.start, current.end),
+        (FINANCIAL_PERIOD_SECONDS, 2 * FINANCIAL_PERIOD_SECONDS)
+    );
+    let native = current.assets.get_unchecked(0);
+    assert_eq!(native.protocol_fees, 10 + 2);
+    assert!(native.interest_collected > 0);
+    assert_eq!(
+        native.net_revenue,
+        native.protocol_fees + native.interest_collected
+    );
+
+    assert_eq!(client.get_financials(&5).assets.len(), 0);
+}
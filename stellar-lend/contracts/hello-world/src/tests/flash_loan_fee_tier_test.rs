@@ -0,0 +1,282 @@
+//! # Flash Loan Fee Tier Tests
+//!
+//! Covers tiered flash loan fees:
+//! - Registered integrators pay the integrator fee
+//! - Users reaching a volume tier in the current epoch pay the tier fee
+//! - Volume counters reset when a new epoch starts
+//! - The lowest applicable fee wins
+//! - Tier configuration validation and admin-only integrator registration
+//! - A `FlashLoanConfig` stored before fee tiers existed still loads, with
+//!   the default tiers
+//! - Integrator registration needs the admin's authorization
+
+use soroban_sdk::{
+    contracttype, testutils::Address as _, testutils::Ledger, token, Address, Bytes, Env, Vec,
+};
+use stellarlend_testutils::{register_mock_receiver, ReceiverBehavior};
+
+use crate::flash_loan::{
+    configure_flash_loan_fee_tiers, execute_flash_loan, get_flash_loan_epoch_volume,
+    get_flash_loan_fee_bps, get_flash_loan_fee_tiers, set_flash_loan_integrator, FlashLoanDataKey,
+    FlashLoanError, FlashLoanFeeTier, FlashLoanFeeTiers,
+};
+use crate::tests::test_helpers::assert_authorized;
+use crate::{HelloContract, HelloContractClient};
+
+const EPOCH: u64 = 86_400;
+
+fn setup() -> (Env, Address, Address, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(HelloContract, ());
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token_address = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    token::StellarAssetClient::new(&env, &token_address).mint(&contract_id, &100_000_000);
+
+    env.as_contract(&contract_id, || {
+        crate::admin::set_admin(&env, admin.clone(), None).unwrap();
+    });
+
+    (env, contract_id, admin, user, token_address)
+}
+
+/// Receiver that repays principal plus fee from its own balance
+fn repaying_receiver(env: &Env, contract_id: &Address, token_address: &Address) -> Address {
+    let receiver = register_mock_receiver(env, contract_id, ReceiverBehavior::Repay);
+    token::StellarAssetClient::new(env, token_address).mint(&receiver, &100_000);
+    receiver
+}
+
+/// `FlashLoanConfig` as stored before fee tiers were added
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+struct LegacyFlashLoanConfig {
+    fee_bps: i128,
+    max_amount: i128,
+    min_amount: i128,
+}
+
+fn tiered_config(env: &Env) -> FlashLoanFeeTiers {
+    let mut tiers = Vec::new(env);
+    tiers.push_back(FlashLoanFeeTier {
+        min_epoch_volume: 1_000_000,
+        fee_bps: 6,
+    });
+    tiers.push_back(FlashLoanFeeTier {
+        min_epoch_volume: 10_000_000,
+        fee_bps: 3,
+    });
+    FlashLoanFeeTiers {
+        integrator_fee_bps: 4,
+        volume_tiers: tiers,
+        epoch_duration: EPOCH,
+    }
+}
+
+#[test]
+fn test_integrator_pays_integrator_fee() {
+    let (env, contract_id, admin, user, token_address) = setup();
+    let callback = repaying_receiver(&env, &contract_id, &token_address);
+
+    let total = env.as_contract(&contract_id, || {
+        configure_flash_loan_fee_tiers(&env, admin.clone(), tiered_config(&env)).unwrap();
+        set_flash_loan_integrator(&env, admin.clone(), user.clone(), true).unwrap();
+        assert_eq!(get_flash_loan_fee_bps(&env, &user), 4);
+        execute_flash_loan(
+            &env,
+            user.clone(),
+            token_address.clone(),
+            1_000_000,
+            callback,
+            Bytes::new(&env),
+        )
+        .unwrap()
+    });
+
+    assert_eq!(total, 1_000_400);
+}
+
+#[test]
+fn test_volume_tier_applies_after_threshold() {
+    let (env, contract_id, admin, user, token_address) = setup();
+    let callback = repaying_receiver(&env, &contract_id, &token_address);
+
+    env.as_contract(&contract_id, || {
+        configure_flash_loan_fee_tiers(&env, admin.clone(), tiered_config(&env)).unwrap();
+        assert_eq!(get_flash_loan_fee_bps(&env, &user), 9);
+
+        let total = execute_flash_loan(
+            &env,
+            user.clone(),
+            token_address.clone(),
+            1_000_000,
+            callback,
+            Bytes::new(&env),
+        )
+        .unwrap();
+        assert_eq!(total, 1_000_900);
+        assert_eq!(get_flash_loan_epoch_volume(&env, &user), 1_000_000);
+        assert_eq!(get_flash_loan_fee_bps(&env, &user), 6);
+    });
+}
+
+#[test]
+fn test_volume_resets_on_new_epoch() {
+    let (env, contract_id, admin, user, token_address) = setup();
+    let callback = repaying_receiver(&env, &contract_id, &token_address);
+
+    env.as_contract(&contract_id, || {
+        configure_flash_loan_fee_tiers(&env, admin.clone(), tiered_config(&env)).unwrap();
+        execute_flash_loan(
+            &env,
+            user.clone(),
+            token_address.clone(),
+            12_000_000,
+            callback,
+            Bytes::new(&env),
+        )
+        .unwrap();
+        assert_eq!(get_flash_loan_fee_bps(&env, &user), 3);
+    });
+
+    env.ledger().with_mut(|li| li.timestamp += EPOCH);
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(get_flash_loan_epoch_volume(&env, &user), 0);
+        assert_eq!(get_flash_loan_fee_bps(&env, &user), 9);
+    });
+}
+
+#[test]
+fn test_lowest_applicable_fee_wins() {
+    let (env, contract_id, admin, user, token_address) = setup();
+    let callback = repaying_receiver(&env, &contract_id, &token_address);
+
+    env.as_contract(&contract_id, || {
+        configure_flash_loan_fee_tiers(&env, admin.clone(), tiered_config(&env)).unwrap();
+        set_flash_loan_integrator(&env, admin.clone(), user.clone(), true).unwrap();
+        execute_flash_loan(
+            &env,
+            user.clone(),
+            token_address.clone(),
+            12_000_000,
+            callback,
+            Bytes::new(&env),
+        )
+        .unwrap();
+
+        // Tier fee (3) beats integrator fee (4)
+        assert_eq!(get_flash_loan_fee_bps(&env, &user), 3);
+
+        set_flash_loan_integrator(&env, admin.clone(), user.clone(), false).unwrap();
+        assert_eq!(get_flash_loan_fee_bps(&env, &user), 3);
+    });
+}
+
+#[test]
+fn test_invalid_tier_config_rejected() {
+    let (env, contract_id, admin, _user, _token) = setup();
+
+    env.as_contract(&contract_id, || {
+        let mut config = tiered_config(&env);
+        config.epoch_duration = 0;
+        assert_eq!(
+            configure_flash_loan_fee_tiers(&env, admin.clone(), config).unwrap_err(),
+            FlashLoanError::InvalidAmount
+        );
+
+        let mut config = tiered_config(&env);
+        config.volume_tiers.push_back(FlashLoanFeeTier {
+            min_epoch_volume: 1,
+            fee_bps: 10_001,
+        });
+        assert_eq!(
+            configure_flash_loan_fee_tiers(&env, admin.clone(), config).unwrap_err(),
+            FlashLoanError::InvalidAmount
+        );
+    });
+}
+
+#[test]
+fn test_set_integrator_requires_admin() {
+    let (env, contract_id, _admin, user, _token) = setup();
+    let attacker = Address::generate(&env);
+
+    let result = env.as_contract(&contract_id, || {
+        set_flash_loan_integrator(&env, attacker, user.clone(), true)
+    });
+
+    assert_eq!(result.unwrap_err(), FlashLoanError::InvalidCallback);
+}
+
+#[test]
+fn test_set_integrator_requires_auth() {
+    let (env, contract_id, admin, user, _token) = setup();
+    let client = HelloContractClient::new(&env, &contract_id);
+
+    client.set_flash_loan_integrator(&admin, &user, &true);
+    assert_authorized(&env, &admin, &contract_id, "set_flash_loan_integrator");
+    assert_eq!(client.get_flash_loan_fee_bps(&user), 5);
+}
+
+#[test]
+fn test_legacy_stored_config_still_loads() {
+    let (env, contract_id, _admin, user, token_address) = setup();
+    let callback = repaying_receiver(&env, &contract_id, &token_address);
+
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().set(
+            &FlashLoanDataKey::FlashLoanConfig,
+            &LegacyFlashLoanConfig {
+                fee_bps: 20,
+                max_amount: 2_000_000,
+                min_amount: 1_000,
+            },
+        );
+    });
+
+    let client = HelloContractClient::new(&env, &contract_id);
+    assert_eq!(client.get_flash_loan_fee_bps(&user), 20);
+    assert_eq!(client.get_flash_loan_fee_tiers().integrator_fee_bps, 5);
+
+    // The stored limits and fee apply to new loans
+    assert_eq!(
+        client.try_execute_flash_loan(
+            &user,
+            &token_address,
+            &2_000_001,
+            &callback,
+            &Bytes::new(&env)
+        ),
+        Err(Ok(FlashLoanError::InvalidAmount))
+    );
+    let total = client.execute_flash_loan(
+        &user,
+        &token_address,
+        &1_000_000,
+        &callback,
+        &Bytes::new(&env),
+    );
+    assert_eq!(total, 1_002_000);
+}
+
+#[test]
+fn test_fee_tiers_configured_through_client() {
+    let (env, contract_id, admin, user, _token) = setup();
+    let client = HelloContractClient::new(&env, &contract_id);
+
+    client.configure_flash_loan_fee_tiers(&admin, &tiered_config(&env));
+    assert_authorized(&env, &admin, &contract_id, "configure_flash_loan_fee_tiers");
+    assert_eq!(client.get_flash_loan_fee_tiers(), tiered_config(&env));
+
+    client.set_flash_loan_integrator(&admin, &user, &true);
+    assert_eq!(client.get_flash_loan_fee_bps(&user), 4);
+    env.as_contract(&contract_id, || {
+        assert_eq!(get_flash_loan_fee_tiers(&env).epoch_duration, EPOCH);
+    });
+}
@@ -0,0 +1,123 @@
+//! # Flash Loan Isolation Tests
+//!
+//! Verifies the operation-scoped flash loan lock:
+//! - deposit, borrow and withdraw revert with `FlashLoanInProgress` while a
+//!   flash loan is outstanding, for the borrower and for any other address
+//! - the lock is released once an atomic loan is repaid
+//! - overlapping loans keep the lock until the last one is repaid
+//!
+//! Soroban forbids re-entering the lending contract from the receiver
+//! callback, so an outstanding loan is simulated by taking the lock directly.
+
+use soroban_sdk::{testutils::Address as _, token, Address, Bytes, Env};
+use stellarlend_testutils::{register_mock_receiver, ReceiverBehavior};
+
+use crate::borrow::{borrow_asset, BorrowError};
+use crate::deposit::{deposit_collateral, DepositError};
+use crate::flash_loan::execute_flash_loan;
+use crate::reentrancy::{enter_flash_loan, exit_flash_loan, is_flash_loan_locked};
+use crate::withdraw::{withdraw_collateral, WithdrawError};
+use crate::HelloContract;
+
+fn setup() -> (Env, Address, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(HelloContract, ());
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let token_address = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &token_address).mint(&contract_id, &10_000_000);
+
+    env.as_contract(&contract_id, || {
+        crate::admin::set_admin(&env, admin.clone(), None).unwrap();
+    });
+
+    (env, contract_id, user, token_address)
+}
+
+/// Hold the lock as an outstanding loan would
+fn open_loan(env: &Env, contract_id: &Address) {
+    env.as_contract(contract_id, || enter_flash_loan(env));
+}
+
+fn repay_loan(env: &Env, contract_id: &Address) {
+    env.as_contract(contract_id, || exit_flash_loan(env));
+}
+
+#[test]
+fn test_nested_calls_revert_during_flash_loan() {
+    let (env, contract_id, user, token_address) = setup();
+    let asset = Some(token_address.clone());
+    open_loan(&env, &contract_id);
+
+    env.as_contract(&contract_id, || {
+        assert!(is_flash_loan_locked(&env));
+        assert_eq!(
+            deposit_collateral(&env, user.clone(), asset.clone(), 100).unwrap_err(),
+            DepositError::FlashLoanInProgress
+        );
+        assert_eq!(
+            borrow_asset(&env, user.clone(), asset.clone(), 100).unwrap_err(),
+            BorrowError::FlashLoanInProgress
+        );
+        assert_eq!(
+            withdraw_collateral(&env, user.clone(), asset.clone(), 100).unwrap_err(),
+            WithdrawError::FlashLoanInProgress
+        );
+    });
+}
+
+#[test]
+fn test_other_addresses_also_locked_during_flash_loan() {
+    let (env, contract_id, _user, token_address) = setup();
+    let other = Address::generate(&env);
+    open_loan(&env, &contract_id);
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            deposit_collateral(&env, other.clone(), Some(token_address.clone()), 100).unwrap_err(),
+            DepositError::FlashLoanInProgress
+        );
+    });
+}
+
+#[test]
+fn test_lock_released_after_repayment() {
+    let (env, contract_id, user, token_address) = setup();
+    let receiver = register_mock_receiver(&env, &contract_id, ReceiverBehavior::Repay);
+    token::StellarAssetClient::new(&env, &token_address).mint(&receiver, &10_000);
+
+    env.as_contract(&contract_id, || {
+        execute_flash_loan(
+            &env,
+            user.clone(),
+            token_address.clone(),
+            1_000_000,
+            receiver,
+            Bytes::new(&env),
+        )
+        .unwrap();
+
+        assert!(!is_flash_loan_locked(&env));
+        // Deposit now proceeds past the lock (and fails later on its own checks, if at all)
+        let result = deposit_collateral(&env, user.clone(), Some(token_address.clone()), 100);
+        assert_ne!(result, Err(DepositError::FlashLoanInProgress));
+    });
+}
+
+#[test]
+fn test_overlapping_loans_hold_lock_until_last_repaid() {
+    let (env, contract_id, _user, _token_address) = setup();
+
+    open_loan(&env, &contract_id);
+    open_loan(&env, &contract_id);
+
+    repay_loan(&env, &contract_id);
+    env.as_contract(&contract_id, || assert!(is_flash_loan_locked(&env)));
+
+    repay_loan(&env, &contract_id);
+    env.as_contract(&contract_id, || assert!(!is_flash_loan_locked(&env)));
+}
@@ -0,0 +1,226 @@
+//! Governance execution of typed proposal payloads.
+//!
+//! # Coverage
+//! - Risk parameter, interest rate, pause and listing proposals are applied
+//!   on execution
+//! - Malformed payloads are rejected when the proposal is created
+//! - A payload rejected by the target module fails the execution
+
+use crate::cross_asset::AssetConfig;
+use crate::errors::GovernanceError;
+use crate::interest_rate::{get_interest_rate_config, InterestRateConfigUpdate};
+use crate::tests::test_helpers::setup_env_with_native_asset;
+use crate::types::{Action, ProposalType, VoteType};
+use crate::HelloContractClient;
+use soroban_sdk::{
+    testutils::Address as _, token::StellarAssetClient, Address, Env, String, Symbol, Vec,
+};
+use stellarlend_testutils::advance_time;
+
+/// Initialize governance with a single 1_000-token voter
+fn setup_governance(env: &Env, client: &HelloContractClient, admin: &Address) -> Address {
+    let vote_token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let proposer = Address::generate(env);
+    StellarAssetClient::new(env, &vote_token).mint(&proposer, &1_000);
+    client.gov_initialize(
+        admin,
+        &vote_token,
+        &Some(259200),
+        &Some(86400),
+        &Some(400),
+        &Some(100),
+        &Some(604800),
+        &Some(5000),
+    );
+    proposer
+}
+
+/// Create, pass and queue a proposal; returns its id once executable
+fn pass_proposal(
+    env: &Env,
+    client: &HelloContractClient,
+    admin: &Address,
+    proposer: &Address,
+    proposal_type: ProposalType,
+) -> u64 {
+    let proposal_id = client.gov_create_proposal(
+        proposer,
+        &proposal_type,
+        &String::from_str(env, "Parameter change"),
+        &None,
+    );
+    advance_time(env, 1);
+    client.gov_vote(proposer, &proposal_id, &VoteType::For);
+    advance_time(env, 259200);
+    client.gov_queue_proposal(admin, &proposal_id);
+    advance_time(env, 86400);
+    proposal_id
+}
+
+fn listing_config(env: &Env, asset: &Address) -> AssetConfig {
+    AssetConfig {
+        asset: Some(asset.clone()),
+        collateral_factor: 7500,
+        liquidation_threshold: 8000,
+        reserve_factor: 1000,
+        max_supply: 0,
+        max_borrow: 0,
+        can_collateralize: true,
+        can_borrow: true,
+        price: 10_000_000,
+        price_updated_at: env.ledger().timestamp(),
+        is_isolated: false,
+        isolated_debt_ceiling: 0,
+    }
+}
+
+#[test]
+fn test_parameter_proposals_applied() {
+    let (env, id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let proposer = setup_governance(&env, &client, &admin);
+
+    let proposal_id = pass_proposal(
+        &env,
+        &client,
+        &admin,
+        &proposer,
+        ProposalType::MinCollateralRatio(12_000),
+    );
+    client.gov_execute_proposal(&admin, &proposal_id);
+    assert_eq!(client.get_min_collateral_ratio(), 12_000);
+
+    let proposal_id = pass_proposal(
+        &env,
+        &client,
+        &admin,
+        &proposer,
+        ProposalType::InterestRateConfig(InterestRateConfigUpdate {
+            base_rate_bps: Some(300),
+            kink_utilization_bps: None,
+            multiplier_bps: None,
+            jump_multiplier_bps: None,
+            rate_floor_bps: None,
+            rate_ceiling_bps: None,
+            spread_bps: Some(150),
+        }),
+    );
+    client.gov_execute_proposal(&admin, &proposal_id);
+    let config = env.as_contract(&id, || get_interest_rate_config(&env).unwrap());
+    assert_eq!((config.base_rate_bps, config.spread_bps), (300, 150));
+}
+
+#[test]
+fn test_pause_and_listing_proposals_applied() {
+    let (env, _id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let proposer = setup_governance(&env, &client, &admin);
+    let asset = Address::generate(&env);
+    let pause_borrow = Symbol::new(&env, "pause_borrow");
+
+    let pause_id = pass_proposal(
+        &env,
+        &client,
+        &admin,
+        &proposer,
+        ProposalType::PauseSwitch(pause_borrow.clone(), true),
+    );
+    client.gov_execute_proposal(&admin, &pause_id);
+    assert!(client.is_operation_paused(&pause_borrow));
+
+    let emergency_id = pass_proposal(
+        &env,
+        &client,
+        &admin,
+        &proposer,
+        ProposalType::EmergencyPause(true),
+    );
+    client.gov_execute_proposal(&admin, &emergency_id);
+    assert!(client.is_emergency_paused());
+
+    let config = listing_config(&env, &asset);
+    let listing_id = pass_proposal(
+        &env,
+        &client,
+        &admin,
+        &proposer,
+        ProposalType::ListAsset(config.clone()),
+    );
+    client.gov_execute_proposal(&admin, &listing_id);
+    assert_eq!(client.get_asset_config(&Some(asset)), config);
+
+    // Listing the same asset again is rejected up front
+    assert_eq!(
+        client.try_gov_create_proposal(
+            &proposer,
+            &ProposalType::ListAsset(config),
+            &String::from_str(&env, "Relist"),
+            &None,
+        ),
+        Err(Ok(GovernanceError::InvalidAction))
+    );
+}
+
+#[test]
+fn test_malformed_payloads_rejected_at_creation() {
+    let (env, _id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let proposer = setup_governance(&env, &client, &admin);
+    let asset = Address::generate(&env);
+
+    let invalid = [
+        ProposalType::MinCollateralRatio(5_000),
+        ProposalType::RiskParams(None, None, None, None),
+        ProposalType::RiskParams(Some(12_000), Some(13_000), None, None),
+        ProposalType::ReserveFactor(None, 6_000),
+        ProposalType::InterestRateConfig(InterestRateConfigUpdate {
+            base_rate_bps: None,
+            kink_utilization_bps: Some(0),
+            multiplier_bps: None,
+            jump_multiplier_bps: None,
+            rate_floor_bps: None,
+            rate_ceiling_bps: None,
+            spread_bps: None,
+        }),
+        ProposalType::ListAsset(AssetConfig {
+            price: 0,
+            ..listing_config(&env, &asset)
+        }),
+        ProposalType::GenericAction(Action {
+            target: asset.clone(),
+            method: Symbol::new(&env, "noop"),
+            args: Vec::new(&env),
+            value: 0,
+        }),
+    ];
+    for proposal_type in invalid {
+        assert_eq!(
+            client.try_gov_create_proposal(
+                &proposer,
+                &proposal_type,
+                &String::from_str(&env, "Invalid"),
+                &None,
+            ),
+            Err(Ok(GovernanceError::InvalidAction))
+        );
+    }
+}
+
+#[test]
+fn test_rejected_change_fails_execution() {
+    let (env, _id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let proposer = setup_governance(&env, &client, &admin);
+
+    // In range, but more than the 10% per-update change limit from 110%
+    let proposal_id = pass_proposal(
+        &env,
+        &client,
+        &admin,
+        &proposer,
+        ProposalType::MinCollateralRatio(20_000),
+    );
+    assert_eq!(
+        client.try_gov_execute_proposal(&admin, &proposal_id),
+        Err(Ok(GovernanceError::ExecutionFailed))
+    );
+    assert_eq!(client.get_min_collateral_ratio(), 11_000);
+}
@@ -0,0 +1,224 @@
+//! Position hedging hook tests.
+//!
+//! # Coverage
+//! - Keepers execute a hedge once the health factor drops below the trigger,
+//!   selling the signed share of collateral and repaying debt
+//! - Cooldown, execution limit and expiry bound executions
+//! - Invalid instructions and AMM slippage above the signed bound are rejected
+//! - Users can cancel their instruction
+
+use crate::hedging::{HedgeError, HedgeInstruction, HedgeResult};
+use crate::tests::test_helpers::setup_env_with_native_asset;
+use crate::HelloContractClient;
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env, Symbol, Vec,
+};
+use stellarlend_amm::{AmmProtocolConfig, TokenPair};
+use stellarlend_testutils::advance_time;
+
+/// Deposit 100_000 XLM, borrow 50_000 (health factor 19_047), register an AMM
+/// pool swapping XLM against a stable token (1% slippage) and give the
+/// contract 100_000 of the token to front repayments
+fn setup_hedge(
+    env: &Env,
+    contract_id: &Address,
+    client: &HelloContractClient,
+    admin: &Address,
+    user: &Address,
+) -> Address {
+    client.deposit_collateral(user, &None, &100_000);
+    client.borrow_asset(user, &None, &50_000);
+
+    let stable = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    StellarAssetClient::new(env, &stable).mint(contract_id, &100_000);
+    client.initialize_amm(admin, &100, &200, &10_000);
+    let mut supported_pairs = Vec::new(env);
+    supported_pairs.push_back(TokenPair {
+        token_a: None,
+        token_b: Some(stable.clone()),
+        pool_address: Address::generate(env),
+    });
+    client.set_amm_pool(
+        admin,
+        &AmmProtocolConfig {
+            protocol_address: Address::generate(env),
+            protocol_name: Symbol::new(env, "TestAMM"),
+            enabled: true,
+            fee_tier: 30,
+            min_swap_amount: 1_000,
+            max_swap_amount: 1_000_000,
+            supported_pairs,
+        },
+    );
+    stable
+}
+
+/// Sell 10% of the collateral below a 2.0 health factor, 2% max slippage
+fn instruction(stable: &Address) -> HedgeInstruction {
+    HedgeInstruction {
+        collateral_asset: None,
+        hedge_asset: Some(stable.clone()),
+        trigger_health_factor: 20_000,
+        hedge_bps: 1_000,
+        max_slippage_bps: 200,
+        cooldown: 3_600,
+        max_executions: 3,
+        expires_at: 86_400,
+    }
+}
+
+/// Give the user the swap proceeds the test AMM does not deliver and approve
+/// the repayment plus settlement of `executions` hedges
+fn fund_settlement(
+    env: &Env,
+    contract_id: &Address,
+    stable: &Address,
+    user: &Address,
+    executions: i128,
+) {
+    StellarAssetClient::new(env, stable).mint(user, &(9_800 * executions));
+    TokenClient::new(env, stable).approve(
+        user,
+        contract_id,
+        &(2 * 9_800 * executions),
+        &(env.ledger().sequence() + 100),
+    );
+}
+
+#[test]
+fn test_hedge_executes_below_trigger() {
+    let (env, id, client, admin, user, _native) = setup_env_with_native_asset();
+    let keeper = Address::generate(&env);
+    let stable = setup_hedge(&env, &id, &client, &admin, &user);
+    fund_settlement(&env, &id, &stable, &user, 1);
+    client.set_hedge_instruction(&user, &instruction(&stable));
+
+    // 10_000 sold at 1% slippage; 9_800 (the 2% bound) fronted and repaid
+    let result = client.execute_hedge(&keeper, &user);
+    assert_eq!(
+        result,
+        HedgeResult {
+            collateral_sold: 10_000,
+            proceeds: 9_900,
+            debt_repaid: 9_800,
+            health_before: 19_047,
+            health_after: 21_321,
+        }
+    );
+    let position = client.get_user_report(&user).position;
+    assert_eq!((position.collateral, position.debt), (90_000, 40_200));
+    assert_eq!(TokenClient::new(&env, &stable).balance(&id), 100_000);
+
+    let hook = client.get_hedge_hook(&user).unwrap();
+    assert_eq!((hook.executions, hook.last_executed), (1, 0));
+    assert_eq!(
+        client.try_execute_hedge(&keeper, &user),
+        Err(Ok(HedgeError::CooldownActive))
+    );
+    // The position is now above the trigger
+    advance_time(&env, 3_600);
+    assert_eq!(
+        client.try_execute_hedge(&keeper, &user),
+        Err(Ok(HedgeError::NotTriggered))
+    );
+}
+
+#[test]
+fn test_execution_limit_and_expiry() {
+    let (env, id, client, admin, user, _native) = setup_env_with_native_asset();
+    let keeper = Address::generate(&env);
+    let stable = setup_hedge(&env, &id, &client, &admin, &user);
+    fund_settlement(&env, &id, &stable, &user, 2);
+    assert_eq!(
+        client.try_execute_hedge(&keeper, &user),
+        Err(Ok(HedgeError::NoInstruction))
+    );
+
+    client.set_hedge_instruction(
+        &user,
+        &HedgeInstruction {
+            trigger_health_factor: 50_000,
+            max_executions: 1,
+            ..instruction(&stable)
+        },
+    );
+    client.execute_hedge(&keeper, &user);
+    advance_time(&env, 3_600);
+    assert_eq!(
+        client.try_execute_hedge(&keeper, &user),
+        Err(Ok(HedgeError::ExecutionLimitReached))
+    );
+
+    // A replaced instruction starts over, until it expires
+    client.set_hedge_instruction(
+        &user,
+        &HedgeInstruction {
+            trigger_health_factor: 50_000,
+            ..instruction(&stable)
+        },
+    );
+    advance_time(&env, 86_400);
+    assert_eq!(
+        client.try_execute_hedge(&keeper, &user),
+        Err(Ok(HedgeError::Expired))
+    );
+
+    client.cancel_hedge_instruction(&user);
+    assert_eq!(client.get_hedge_hook(&user), None);
+}
+
+#[test]
+fn test_invalid_instructions_rejected() {
+    let (env, id, client, admin, user, _native) = setup_env_with_native_asset();
+    let keeper = Address::generate(&env);
+    let stable = setup_hedge(&env, &id, &client, &admin, &user);
+
+    for invalid in [
+        HedgeInstruction {
+            hedge_asset: None,
+            ..instruction(&stable)
+        },
+        HedgeInstruction {
+            trigger_health_factor: 10_000,
+            ..instruction(&stable)
+        },
+        HedgeInstruction {
+            hedge_bps: 10_001,
+            ..instruction(&stable)
+        },
+        HedgeInstruction {
+            max_executions: 0,
+            ..instruction(&stable)
+        },
+        HedgeInstruction {
+            expires_at: 0,
+            ..instruction(&stable)
+        },
+    ] {
+        assert_eq!(
+            client.try_set_hedge_instruction(&user, &invalid),
+            Err(Ok(HedgeError::InvalidInstruction))
+        );
+    }
+
+    // The AMM swaps at 1% slippage; the user signed for at most 0.5%
+    client.set_hedge_instruction(
+        &user,
+        &HedgeInstruction {
+            max_slippage_bps: 50,
+            ..instruction(&stable)
+        },
+    );
+    assert_eq!(
+        client.try_execute_hedge(&keeper, &user),
+        Err(Ok(HedgeError::SlippageExceeded))
+    );
+    assert_eq!(
+        client.try_cancel_hedge_instruction(&keeper),
+        Err(Ok(HedgeError::NoInstruction))
+    );
+}
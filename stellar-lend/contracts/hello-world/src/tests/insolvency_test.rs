@@ -0,0 +1,115 @@
+//! Insolvency detection tests.
+//!
+//! # Coverage
+//! - A position update that leaves collateral below debt flags the position
+//!   and emits `insolvency_detected` once
+//! - Flagged positions cannot borrow
+//! - The flag is lifted once the position is solvent again
+//! - Undercollateralized but solvent positions are not flagged
+
+use crate::bad_debt::InsolvencyRecord;
+use crate::borrow::BorrowError;
+use crate::deposit::{DepositDataKey, Position};
+use crate::tests::test_helpers::setup_env_with_native_asset;
+use soroban_sdk::{contracttype, testutils::Events, Address, Env, TryFromVal};
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestInsolvencyDetectedEvent {
+    pub user: Address,
+    pub collateral: i128,
+    pub debt: i128,
+    pub timestamp: u64,
+}
+
+fn insolvency_events(env: &Env) -> u32 {
+    env.events()
+        .all()
+        .iter()
+        .filter(|(_contract, _topics, data)| {
+            TestInsolvencyDetectedEvent::try_from_val(env, data).is_ok()
+        })
+        .count() as u32
+}
+
+/// Write a native position directly
+fn set_position(env: &Env, id: &Address, user: &Address, collateral: i128, debt: i128) {
+    env.as_contract(id, || {
+        env.storage().persistent().set(
+            &DepositDataKey::CollateralBalance(user.clone()),
+            &collateral,
+        );
+        env.storage().persistent().set(
+            &DepositDataKey::Position(user.clone()),
+            &Position {
+                collateral,
+                debt,
+                borrow_interest: 0,
+                last_accrual_time: env.ledger().timestamp(),
+            },
+        );
+    });
+}
+
+#[test]
+fn test_insolvent_position_flagged() {
+    let (env, id, client, _admin, user, _native) = setup_env_with_native_asset();
+    set_position(&env, &id, &user, 800, 1_000);
+
+    client.deposit_collateral(&user, &None, &100);
+    let event = env
+        .events()
+        .all()
+        .iter()
+        .find_map(|(_contract, _topics, data)| {
+            TestInsolvencyDetectedEvent::try_from_val(&env, &data).ok()
+        });
+    assert_eq!(
+        event,
+        Some(TestInsolvencyDetectedEvent {
+            user: user.clone(),
+            collateral: 900,
+            debt: 1_000,
+            timestamp: env.ledger().timestamp(),
+        })
+    );
+    assert_eq!(
+        client.get_insolvency(&user),
+        Some(InsolvencyRecord {
+            collateral: 900,
+            debt: 1_000,
+            detected_at: env.ledger().timestamp(),
+        })
+    );
+
+    // Already flagged: no second event
+    client.deposit_collateral(&user, &None, &50);
+    assert_eq!(insolvency_events(&env), 0);
+    assert_eq!(client.get_insolvency(&user).unwrap().collateral, 900);
+
+    assert_eq!(
+        client.try_borrow_asset(&user, &None, &10),
+        Err(Ok(BorrowError::PositionInsolvent))
+    );
+}
+
+#[test]
+fn test_flag_lifted_when_solvent() {
+    let (env, id, client, _admin, user, _native) = setup_env_with_native_asset();
+    set_position(&env, &id, &user, 800, 1_000);
+    client.deposit_collateral(&user, &None, &100);
+    assert!(client.get_insolvency(&user).is_some());
+
+    client.deposit_collateral(&user, &None, &100);
+    assert_eq!(client.get_insolvency(&user), None);
+}
+
+#[test]
+fn test_undercollateralized_position_not_flagged() {
+    let (env, id, client, _admin, user, _native) = setup_env_with_native_asset();
+    set_position(&env, &id, &user, 1_100, 1_000);
+
+    client.deposit_collateral(&user, &None, &10);
+    assert_eq!(insolvency_events(&env), 0);
+    assert_eq!(client.get_insolvency(&user), None);
+}
@@ -0,0 +1,159 @@
+//! Protocol invariant checker tests.
+//!
+//! # Coverage
+//! - Invariants hold after core and cross-asset deposits, borrows, interest
+//!   accrual, repayments and withdrawals
+//! - Each run emits an invariant report event
+//! - Drifted core debt and a negative reserve are reported as violations
+
+use super::test_helpers::setup_env_with_native_asset;
+use crate::borrow::borrow_asset;
+use crate::cross_asset::{initialize, initialize_asset, AssetConfig};
+use crate::deposit::{deposit_collateral, DepositDataKey, Position};
+use crate::invariants::InvariantViolation;
+use crate::repay::repay_debt;
+use crate::withdraw::withdraw_collateral;
+use crate::HelloContractClient;
+use soroban_sdk::{
+    contracttype,
+    testutils::{Address as _, Events},
+    token, vec, Address, Env, TryFromVal, Vec,
+};
+use stellarlend_testutils::advance_time;
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestInvariantReportEvent {
+    pub holds: bool,
+    pub markets: u32,
+    pub violations: Vec<InvariantViolation>,
+    pub timestamp: u64,
+}
+
+fn asset_config(asset: Option<Address>) -> AssetConfig {
+    AssetConfig {
+        asset,
+        collateral_factor: 7500,
+        liquidation_threshold: 8000,
+        reserve_factor: 1000,
+        max_supply: 0,
+        max_borrow: 0,
+        can_collateralize: true,
+        can_borrow: true,
+        price: 10_000_000,
+        price_updated_at: 0,
+        is_isolated: false,
+        isolated_debt_ceiling: 0,
+    }
+}
+
+/// Core user with 8_000 deposited and 2_000 borrowed of native, and the
+/// returned token listed for cross-asset lending
+fn setup() -> (Env, Address, HelloContractClient<'static>, Address, Address) {
+    let (env, id, client, admin, user, native) = setup_env_with_native_asset();
+    let token_asset = Address::generate(&env);
+    let native_client = token::StellarAssetClient::new(&env, &native);
+    native_client.mint(&user, &1_000);
+    native_client.approve(&user, &id, &1_000, &(env.ledger().sequence() + 100));
+
+    env.as_contract(&id, || {
+        initialize(&env, admin.clone()).unwrap();
+        initialize_asset(&env, None, asset_config(None)).unwrap();
+        initialize_asset(
+            &env,
+            Some(token_asset.clone()),
+            asset_config(Some(token_asset.clone())),
+        )
+        .unwrap();
+
+        deposit_collateral(&env, user.clone(), None, 10_000).unwrap();
+        borrow_asset(&env, user.clone(), None, 3_000).unwrap();
+        repay_debt(&env, user.clone(), None, 1_000).unwrap();
+        withdraw_collateral(&env, user.clone(), None, 2_000).unwrap();
+    });
+
+    (env, id, client, user, token_asset)
+}
+
+#[test]
+fn test_invariants_hold_after_core_and_cross_asset_flows() {
+    let (env, _id, client, _user, token_asset) = setup();
+    let ca_user = Address::generate(&env);
+    let asset = Some(token_asset.clone());
+
+    client.cross_asset_deposit(&ca_user, &asset, &10_000);
+    client.cross_asset_borrow(&ca_user, &asset, &5_000);
+    advance_time(&env, 30 * 86_400);
+    client.cross_asset_repay(&ca_user, &asset, &1_000);
+
+    let report = client.assert_invariants();
+    assert!(report.holds);
+    assert!(report.violations.is_empty());
+    assert!(report.debt_checked);
+    assert_eq!(report.borrowers_checked, 1);
+    assert_eq!(report.core_debt, 2_000);
+    assert_eq!(report.markets.len(), 2);
+
+    let native = report.markets.get(0).unwrap();
+    assert_eq!(native.asset, None);
+    assert_eq!((native.total_supply, native.total_borrows), (8_000, 2_000));
+    assert_eq!((native.position_collateral, native.position_debt), (0, 0));
+
+    let token = report.markets.get(1).unwrap();
+    assert_eq!(token.asset, asset);
+    assert_eq!(
+        (token.total_supply, token.position_collateral),
+        (10_000, 10_000)
+    );
+    assert_eq!(token.total_borrows, token.position_debt);
+    assert_eq!(report.total_borrows, 2_000 + token.position_debt);
+
+    let event = env
+        .events()
+        .all()
+        .iter()
+        .find_map(|(_contract, _topics, data)| {
+            TestInvariantReportEvent::try_from_val(&env, &data).ok()
+        });
+    assert_eq!(
+        event,
+        Some(TestInvariantReportEvent {
+            holds: true,
+            markets: 2,
+            violations: Vec::new(&env),
+            timestamp: report.checked_at,
+        })
+    );
+}
+
+#[test]
+fn test_drift_is_reported() {
+    let (env, id, client, user, _token_asset) = setup();
+
+    env.as_contract(&id, || {
+        let key = DepositDataKey::Position(user.clone());
+        let position: Position = env.storage().persistent().get(&key).unwrap();
+        env.storage().persistent().set(
+            &key,
+            &Position {
+                debt: position.debt + 500,
+                ..position
+            },
+        );
+        env.storage()
+            .persistent()
+            .set(&DepositDataKey::ProtocolReserve(None), &-1i128);
+    });
+
+    let report = client.assert_invariants();
+    assert!(!report.holds);
+    assert_eq!(report.core_debt, 2_500);
+    assert_eq!(
+        report.violations,
+        vec![
+            &env,
+            InvariantViolation::NegativeReserve(None),
+            InvariantViolation::DebtMismatch,
+        ]
+    );
+}
@@ -0,0 +1,152 @@
+//! Cross-asset isolation mode tests.
+//!
+//! # Coverage
+//! - Isolated collateral cannot be combined with other collateral, either way round
+//! - The position summary reports the isolated collateral asset
+//! - Borrows against isolated collateral are bounded by the shared debt ceiling
+//! - Repayments release isolated debt
+//! - Negative ceilings are rejected
+
+use crate::cross_asset::{
+    cross_asset_borrow, cross_asset_deposit, cross_asset_repay, get_isolated_debt,
+    get_user_asset_position, get_user_position_summary, initialize, initialize_asset,
+    set_asset_isolation, AssetConfig, AssetKey, CrossAssetError,
+};
+use crate::HelloContract;
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+fn asset_config(env: &Env, asset: &Address, is_isolated: bool) -> AssetConfig {
+    AssetConfig {
+        asset: Some(asset.clone()),
+        collateral_factor: 7500,
+        liquidation_threshold: 8000,
+        reserve_factor: 1000,
+        max_supply: 0,
+        max_borrow: 0,
+        can_collateralize: true,
+        can_borrow: true,
+        price: 10_000_000,
+        price_updated_at: env.ledger().timestamp(),
+        is_isolated,
+        isolated_debt_ceiling: if is_isolated { 500 } else { 0 },
+    }
+}
+
+/// Returns (env, contract, risky isolated asset, usdc, usdt)
+fn setup() -> (Env, Address, Address, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let admin = Address::generate(&env);
+    let risky = Address::generate(&env);
+    let usdc = Address::generate(&env);
+    let usdt = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        initialize(&env, admin.clone()).unwrap();
+        initialize_asset(&env, Some(risky.clone()), asset_config(&env, &risky, true)).unwrap();
+        initialize_asset(&env, Some(usdc.clone()), asset_config(&env, &usdc, false)).unwrap();
+        initialize_asset(&env, Some(usdt.clone()), asset_config(&env, &usdt, false)).unwrap();
+    });
+
+    (env, contract_id, risky, usdc, usdt)
+}
+
+#[test]
+fn test_isolated_collateral_cannot_be_mixed() {
+    let (env, id, risky, usdc, _usdt) = setup();
+    let isolated_user = Address::generate(&env);
+    let regular_user = Address::generate(&env);
+    env.as_contract(&id, || {
+        cross_asset_deposit(&env, isolated_user.clone(), Some(risky.clone()), 1_000).unwrap();
+        cross_asset_deposit(&env, isolated_user.clone(), Some(risky.clone()), 1_000).unwrap();
+        assert_eq!(
+            cross_asset_deposit(&env, isolated_user.clone(), Some(usdc.clone()), 1_000),
+            Err(CrossAssetError::IsolationModeViolation)
+        );
+
+        cross_asset_deposit(&env, regular_user.clone(), Some(usdc), 1_000).unwrap();
+        assert_eq!(
+            cross_asset_deposit(&env, regular_user.clone(), Some(risky), 1_000),
+            Err(CrossAssetError::IsolationModeViolation)
+        );
+    });
+}
+
+#[test]
+fn test_summary_reports_isolated_asset() {
+    let (env, id, risky, usdc, _usdt) = setup();
+    let isolated_user = Address::generate(&env);
+    let regular_user = Address::generate(&env);
+    env.as_contract(&id, || {
+        cross_asset_deposit(&env, isolated_user.clone(), Some(risky.clone()), 1_000).unwrap();
+        cross_asset_deposit(&env, regular_user.clone(), Some(usdc), 1_000).unwrap();
+
+        assert_eq!(
+            get_user_position_summary(&env, &isolated_user)
+                .unwrap()
+                .isolated_asset,
+            Some(AssetKey::Token(risky))
+        );
+        assert_eq!(
+            get_user_position_summary(&env, &regular_user)
+                .unwrap()
+                .isolated_asset,
+            None
+        );
+    });
+}
+
+#[test]
+fn test_debt_ceiling_shared_across_users() {
+    let (env, id, risky, _usdc, usdt) = setup();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    env.as_contract(&id, || {
+        cross_asset_deposit(&env, alice.clone(), Some(risky.clone()), 1_000).unwrap();
+        cross_asset_deposit(&env, bob.clone(), Some(risky.clone()), 1_000).unwrap();
+
+        cross_asset_borrow(&env, alice.clone(), Some(usdt.clone()), 400).unwrap();
+        assert_eq!(get_isolated_debt(&env, Some(risky.clone())), 400);
+
+        assert_eq!(
+            cross_asset_borrow(&env, bob.clone(), Some(usdt.clone()), 200),
+            Err(CrossAssetError::IsolatedDebtCeilingExceeded)
+        );
+        assert_eq!(
+            get_user_asset_position(&env, &bob, Some(usdt.clone())).debt_principal,
+            0
+        );
+
+        cross_asset_borrow(&env, bob.clone(), Some(usdt), 100).unwrap();
+        assert_eq!(get_isolated_debt(&env, Some(risky)), 500);
+    });
+}
+
+#[test]
+fn test_repay_releases_isolated_debt() {
+    let (env, id, risky, _usdc, usdt) = setup();
+    let user = Address::generate(&env);
+    env.as_contract(&id, || {
+        cross_asset_deposit(&env, user.clone(), Some(risky.clone()), 1_000).unwrap();
+        cross_asset_borrow(&env, user.clone(), Some(usdt.clone()), 500).unwrap();
+
+        cross_asset_repay(&env, user.clone(), Some(usdt.clone()), 300).unwrap();
+        assert_eq!(get_isolated_debt(&env, Some(risky.clone())), 200);
+
+        cross_asset_borrow(&env, user.clone(), Some(usdt), 300).unwrap();
+        assert_eq!(get_isolated_debt(&env, Some(risky)), 500);
+    });
+}
+
+#[test]
+fn test_negative_ceiling_rejected() {
+    let (env, id, risky, _usdc, _usdt) = setup();
+    env.as_contract(&id, || {
+        assert_eq!(
+            set_asset_isolation(&env, Some(risky.clone()), true, -1),
+            Err(CrossAssetError::AssetNotConfigured)
+        );
+        set_asset_isolation(&env, Some(risky), false, 0).unwrap();
+    });
+}
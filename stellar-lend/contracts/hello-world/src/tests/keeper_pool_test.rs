@@ -0,0 +1,154 @@
+//! Keeper sponsorship pool tests.
+//!
+//! # Coverage
+//! - Sponsored keepers are paid per maintenance call, up to their daily cap
+//! - The cap resets the next day and payouts stop when the pool runs dry
+//! - Unsponsored keepers can still run operations, unpaid
+//! - TTL bumps extend position entries and skip unknown accounts
+//! - Configuration and whitelist are admin-only, need the admin's
+//!   authorization and are validated
+
+use crate::deposit::DepositDataKey;
+use crate::keeper_pool::{
+    KeeperPoolConfig, KeeperPoolError, KEEPER_DAY_SECONDS, KEEPER_TTL_EXTEND_TO, MAX_TTL_BUMP_BATCH,
+};
+use crate::tests::test_helpers::{assert_authorized, setup_env_with_native_asset};
+use crate::HelloContractClient;
+use soroban_sdk::{
+    testutils::{storage::Persistent as _, Address as _},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Env, Vec,
+};
+use stellarlend_testutils::advance_time;
+
+/// Pay 10 per call with a daily cap of 25, funded with `funding`
+fn setup_pool(
+    env: &Env,
+    client: &HelloContractClient,
+    admin: &Address,
+    native: &Address,
+    funding: i128,
+) -> Address {
+    let keeper = Address::generate(env);
+    client.set_keeper_pool_config(
+        admin,
+        &KeeperPoolConfig {
+            reward_per_call: 10,
+            daily_cap: 25,
+        },
+    );
+    assert_authorized(env, admin, &client.address, "set_keeper_pool_config");
+    client.set_sponsored_keeper(admin, &keeper, &true);
+    assert_authorized(env, admin, &client.address, "set_sponsored_keeper");
+    StellarAssetClient::new(env, native).mint(admin, &funding);
+    client.fund_keeper_pool(admin, &funding);
+    keeper
+}
+
+#[test]
+fn test_sponsored_keeper_paid_up_to_daily_cap() {
+    let (env, _id, client, admin, _user, native) = setup_env_with_native_asset();
+    let keeper = setup_pool(&env, &client, &admin, &native, 1_000);
+    let xlm = TokenClient::new(&env, &native);
+
+    client.poke_rates(&keeper);
+    client.bump_ttl(&keeper, &Vec::new(&env));
+    // A third call would exceed the cap of 25
+    client.poke_rates(&keeper);
+    assert_eq!(xlm.balance(&keeper), 20);
+    assert_eq!(client.get_keeper_paid_today(&keeper), 20);
+    assert_eq!(client.get_keeper_pool_balance(), 980);
+
+    advance_time(&env, KEEPER_DAY_SECONDS);
+    assert_eq!(client.get_keeper_paid_today(&keeper), 0);
+    client.poke_rates(&keeper);
+    assert_eq!(xlm.balance(&keeper), 30);
+
+    let statement = client.get_financials(&0);
+    assert_eq!(statement.assets.get_unchecked(0).keeper_reimbursements, 30);
+    assert_eq!(statement.assets.get_unchecked(0).net_revenue, -30);
+}
+
+#[test]
+fn test_unpaid_when_unsponsored_or_pool_dry() {
+    let (env, _id, client, admin, _user, native) = setup_env_with_native_asset();
+    let keeper = setup_pool(&env, &client, &admin, &native, 15);
+    let xlm = TokenClient::new(&env, &native);
+
+    client.poke_rates(&keeper);
+    // 5 left in the pool, less than one reward
+    client.poke_rates(&keeper);
+    assert_eq!(xlm.balance(&keeper), 10);
+    assert_eq!(client.get_keeper_pool_balance(), 5);
+
+    let stranger = Address::generate(&env);
+    client.poke_rates(&stranger);
+    assert_eq!(xlm.balance(&stranger), 0);
+
+    client.set_sponsored_keeper(&admin, &keeper, &false);
+    assert!(!client.is_sponsored_keeper(&keeper));
+}
+
+#[test]
+fn test_bump_ttl_extends_positions() {
+    let (env, id, client, admin, user, native) = setup_env_with_native_asset();
+    let keeper = setup_pool(&env, &client, &admin, &native, 1_000);
+    client.deposit_collateral(&user, &None, &10_000);
+    let stranger = Address::generate(&env);
+
+    assert_eq!(
+        client.bump_ttl(&keeper, &vec![&env, user.clone(), stranger]),
+        1
+    );
+    env.as_contract(&id, || {
+        let ttl = env
+            .storage()
+            .persistent()
+            .get_ttl(&DepositDataKey::Position(user.clone()));
+        assert!(ttl >= KEEPER_TTL_EXTEND_TO - 1);
+    });
+
+    let mut users = Vec::new(&env);
+    for _ in 0..=MAX_TTL_BUMP_BATCH {
+        users.push_back(Address::generate(&env));
+    }
+    assert_eq!(
+        client.try_bump_ttl(&keeper, &users),
+        Err(Ok(KeeperPoolError::BatchTooLarge))
+    );
+}
+
+#[test]
+fn test_configuration_checks() {
+    let (env, _id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let outsider = Address::generate(&env);
+    let config = KeeperPoolConfig {
+        reward_per_call: 10,
+        daily_cap: 100,
+    };
+
+    assert_eq!(
+        client.try_set_keeper_pool_config(&outsider, &config),
+        Err(Ok(KeeperPoolError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_sponsored_keeper(&outsider, &outsider, &true),
+        Err(Ok(KeeperPoolError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_keeper_pool_config(
+            &admin,
+            &KeeperPoolConfig {
+                reward_per_call: 10,
+                daily_cap: 5,
+            }
+        ),
+        Err(Ok(KeeperPoolError::InvalidConfig))
+    );
+    assert_eq!(
+        client.try_fund_keeper_pool(&admin, &0),
+        Err(Ok(KeeperPoolError::InvalidAmount))
+    );
+    client.set_keeper_pool_config(&admin, &config);
+    assert_eq!(client.get_keeper_pool_config(), Some(config));
+}
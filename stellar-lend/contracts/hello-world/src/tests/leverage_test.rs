@@ -0,0 +1,192 @@
+//! Leveraged position tests.
+//!
+//! # Coverage
+//! - Opening loops borrow → deposit until the target leverage is reached
+//! - Closing unwinds the position completely
+//! - Loop swaps through the AMM when collateral differs from the borrow asset
+//! - Input validation and slippage bounds
+//! - A failing step rolls back the whole call
+
+use crate::deposit::DepositDataKey;
+use crate::leverage::{LeverageError, LeverageResult, LEVERAGE_SCALE};
+use crate::tests::test_helpers::setup_env_with_native_asset;
+use crate::HelloContractClient;
+use soroban_sdk::{
+    testutils::Address as _, token::StellarAssetClient, Address, Env, Map, Symbol, Vec,
+};
+use stellarlend_amm::{AmmProtocolConfig, TokenPair};
+
+/// Mint and approve native XLM the contract pulls on repayment
+fn fund_repayments(
+    env: &Env,
+    contract_id: &Address,
+    native: &Address,
+    user: &Address,
+    amount: i128,
+) {
+    let token = StellarAssetClient::new(env, native);
+    token.mint(user, &amount);
+    token.approve(user, contract_id, &amount, &(env.ledger().sequence() + 100));
+}
+
+/// Register a collateral token plus an AMM pool swapping it against native XLM
+/// (1% default slippage, 2% max, 10_000 auto-swap threshold)
+fn setup_amm(
+    env: &Env,
+    contract_id: &Address,
+    client: &HelloContractClient,
+    admin: &Address,
+    user: &Address,
+) -> Address {
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let token_admin = StellarAssetClient::new(env, &token);
+    token_admin.mint(user, &1_000_000);
+    token_admin.approve(
+        user,
+        contract_id,
+        &1_000_000,
+        &(env.ledger().sequence() + 100),
+    );
+
+    client.initialize_amm(admin, &100, &200, &10_000);
+    let mut supported_pairs = Vec::new(env);
+    supported_pairs.push_back(TokenPair {
+        token_a: None,
+        token_b: Some(token.clone()),
+        pool_address: Address::generate(env),
+    });
+    client.set_amm_pool(
+        admin,
+        &AmmProtocolConfig {
+            protocol_address: Address::generate(env),
+            protocol_name: Symbol::new(env, "TestAMM"),
+            enabled: true,
+            fee_tier: 30,
+            min_swap_amount: 1_000,
+            max_swap_amount: 1_000_000,
+            supported_pairs,
+        },
+    );
+    token
+}
+
+#[test]
+fn test_open_native_position_reaches_target_leverage() {
+    let (_env, _id, client, _admin, user, _native) = setup_env_with_native_asset();
+
+    // 110% minimum ratio: 1000 → +909 → +826 → +265
+    let result = client.open_leveraged_position(&user, &None, &None, &1_000, &30_000, &0);
+    assert_eq!(
+        result,
+        LeverageResult {
+            collateral: 3_000,
+            debt: 2_000,
+            leverage_bps: 30_000,
+            loops: 3,
+        }
+    );
+
+    let position = client.get_user_report(&user).position;
+    assert_eq!(position.collateral, 3_000);
+    assert_eq!(position.debt, 2_000);
+}
+
+#[test]
+fn test_close_native_position_unwinds_everything() {
+    let (env, id, client, _admin, user, native) = setup_env_with_native_asset();
+    client.open_leveraged_position(&user, &None, &None, &1_000, &30_000, &0);
+    fund_repayments(&env, &id, &native, &user, 2_000);
+
+    let result = client.close_leveraged_position(&user, &None, &None, &0);
+    assert_eq!(
+        result,
+        LeverageResult {
+            collateral: 0,
+            debt: 0,
+            leverage_bps: 0,
+            loops: 3,
+        }
+    );
+}
+
+#[test]
+fn test_open_and_close_with_amm_swaps() {
+    let (env, id, client, admin, user, native) = setup_env_with_native_asset();
+    let token = setup_amm(&env, &id, &client, &admin, &user);
+    let collateral = Some(token.clone());
+
+    // Borrowed XLM is swapped into the token at 1% slippage; the loop stops once
+    // the remaining step falls below the auto-swap threshold
+    let opened = client.open_leveraged_position(&user, &collateral, &None, &100_000, &20_000, &200);
+    assert_eq!(opened.loops, 2);
+    assert_eq!(opened.collateral, 199_899);
+    assert_eq!(opened.debt, 100_910);
+    assert!(opened.leverage_bps > 2 * LEVERAGE_SCALE);
+
+    fund_repayments(&env, &id, &native, &user, opened.debt);
+    let closed = client.close_leveraged_position(&user, &collateral, &None, &200);
+    assert_eq!(closed.collateral, 0);
+    assert_eq!(closed.debt, 0);
+}
+
+#[test]
+fn test_amm_slippage_above_tolerance_rejected() {
+    let (env, id, client, admin, user, _native) = setup_env_with_native_asset();
+    let token = setup_amm(&env, &id, &client, &admin, &user);
+
+    // The AMM swaps at 1% slippage; the caller accepts 0.5%
+    assert_eq!(
+        client.try_open_leveraged_position(&user, &Some(token), &None, &100_000, &20_000, &50),
+        Err(Ok(LeverageError::SlippageExceeded))
+    );
+    assert!(client.try_get_user_report(&user).is_err());
+}
+
+#[test]
+fn test_invalid_inputs_rejected() {
+    let (env, _id, client, _admin, user, _native) = setup_env_with_native_asset();
+    assert_eq!(
+        client.try_open_leveraged_position(&user, &None, &None, &0, &20_000, &0),
+        Err(Ok(LeverageError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_open_leveraged_position(&user, &None, &None, &1_000, &LEVERAGE_SCALE, &0),
+        Err(Ok(LeverageError::InvalidLeverage))
+    );
+    assert_eq!(
+        client.try_open_leveraged_position(&user, &None, &None, &1_000, &60_000, &0),
+        Err(Ok(LeverageError::InvalidLeverage))
+    );
+    assert_eq!(
+        client.try_open_leveraged_position(&user, &None, &None, &1_000, &20_000, &10_000),
+        Err(Ok(LeverageError::SlippageExceeded))
+    );
+
+    // Borrowed tokens other than native XLM cannot be auto-swapped
+    let token = Some(Address::generate(&env));
+    assert_eq!(
+        client.try_open_leveraged_position(&user, &None, &token, &1_000, &20_000, &100),
+        Err(Ok(LeverageError::UnsupportedPair))
+    );
+}
+
+#[test]
+fn test_failed_step_rolls_back_open() {
+    let (env, id, client, _admin, user, _native) = setup_env_with_native_asset();
+    env.as_contract(&id, || {
+        let mut pause_map = Map::new(&env);
+        pause_map.set(Symbol::new(&env, "pause_borrow"), true);
+        env.storage()
+            .persistent()
+            .set(&DepositDataKey::PauseSwitches, &pause_map);
+    });
+
+    assert_eq!(
+        client.try_open_leveraged_position(&user, &None, &None, &1_000, &20_000, &0),
+        Err(Ok(LeverageError::BorrowFailed))
+    );
+    // The initial deposit was rolled back with the failed borrow
+    assert!(client.try_get_user_report(&user).is_err());
+}
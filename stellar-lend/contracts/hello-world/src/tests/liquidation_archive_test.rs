@@ -0,0 +1,132 @@
+//! Liquidation archive tests.
+//!
+//! # Coverage
+//! - Each liquidation archives a receipt with parties, amounts and prices
+//! - Receipts are read in pages from a start id
+//! - The archive is bounded and prunes the oldest receipt first
+
+use crate::accounting::ACCOUNTING_SCALE;
+use crate::deposit::{DepositDataKey, Position};
+use crate::liquidate::{liquidate, LiquidationDataKey, LiquidationReceipt, MAX_ARCHIVED_RECEIPTS};
+use crate::storage::{self, LIQUIDATION_NAMESPACE};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+fn setup(e: &Env) -> (Address, HelloContractClient<'_>) {
+    e.mock_all_auths();
+    e.ledger().with_mut(|li| li.timestamp = 1_000);
+    let id = e.register(HelloContract, ());
+    let client = HelloContractClient::new(e, &id);
+    client.initialize(&Address::generate(e));
+    (id, client)
+}
+
+/// Liquidate `amount` of a fresh 10_000 / 10_000 native position
+fn liquidate_fresh(e: &Env, id: &Address, liquidator: &Address, amount: i128) -> Address {
+    let borrower = Address::generate(e);
+    e.as_contract(id, || {
+        e.storage().persistent().set(
+            &DepositDataKey::CollateralBalance(borrower.clone()),
+            &10_000_i128,
+        );
+        e.storage().persistent().set(
+            &DepositDataKey::Position(borrower.clone()),
+            &Position {
+                collateral: 10_000,
+                debt: 10_000,
+                borrow_interest: 0,
+                last_accrual_time: e.ledger().timestamp(),
+            },
+        );
+        liquidate(e, liquidator.clone(), borrower.clone(), None, None, amount).unwrap();
+    });
+    borrower
+}
+
+#[test]
+fn test_liquidation_archives_receipt() {
+    let e = Env::default();
+    let (id, client) = setup(&e);
+    let liquidator = Address::generate(&e);
+    assert_eq!(client.get_liquidation_receipt_count(), 0);
+
+    let borrower = liquidate_fresh(&e, &id, &liquidator, 1_000);
+
+    assert_eq!(client.get_liquidation_receipt_count(), 1);
+    // Unpriced native assets value at one accounting unit
+    assert_eq!(
+        client.get_liquidation_receipt(&0),
+        Some(LiquidationReceipt {
+            id: 0,
+            borrower,
+            liquidator,
+            debt_asset: None,
+            collateral_asset: None,
+            debt_repaid: 1_000,
+            collateral_seized: 1_100,
+            incentive_amount: 100,
+            debt_price: ACCOUNTING_SCALE,
+            collateral_price: ACCOUNTING_SCALE,
+            timestamp: 1_000,
+        })
+    );
+    assert_eq!(client.get_liquidation_receipt(&1), None);
+}
+
+#[test]
+fn test_receipts_are_paginated() {
+    let e = Env::default();
+    let (id, client) = setup(&e);
+    let liquidator = Address::generate(&e);
+    for _ in 0..5 {
+        liquidate_fresh(&e, &id, &liquidator, 500);
+    }
+
+    let first = client.get_liquidation_receipts(&0, &2);
+    assert_eq!(first.len(), 2);
+    assert_eq!(first.get(0).unwrap().id, 0);
+    assert_eq!(first.get(1).unwrap().id, 1);
+
+    let rest = client.get_liquidation_receipts(&2, &10);
+    assert_eq!(rest.len(), 3);
+    assert_eq!(rest.get(2).unwrap().id, 4);
+
+    assert_eq!(client.get_liquidation_receipts(&5, &10).len(), 0);
+    assert_eq!(client.get_liquidation_receipts(&0, &0).len(), 0);
+}
+
+#[test]
+fn test_archive_prunes_oldest_receipt() {
+    let e = Env::default();
+    let (id, client) = setup(&e);
+    let liquidator = Address::generate(&e);
+    liquidate_fresh(&e, &id, &liquidator, 500);
+
+    // Pretend the archive is full
+    e.as_contract(&id, || {
+        storage::write(
+            &e,
+            &LIQUIDATION_NAMESPACE,
+            &LiquidationDataKey::ReceiptCount,
+            &MAX_ARCHIVED_RECEIPTS,
+        );
+    });
+    liquidate_fresh(&e, &id, &liquidator, 500);
+
+    assert_eq!(
+        client.get_liquidation_receipt_count(),
+        MAX_ARCHIVED_RECEIPTS + 1
+    );
+    assert_eq!(client.get_liquidation_receipt(&0), None);
+    let newest = client
+        .get_liquidation_receipt(&MAX_ARCHIVED_RECEIPTS)
+        .unwrap();
+    assert_eq!(newest.debt_repaid, 500);
+
+    let page = client.get_liquidation_receipts(&MAX_ARCHIVED_RECEIPTS, &10);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().id, MAX_ARCHIVED_RECEIPTS);
+}
@@ -0,0 +1,268 @@
+//! Dutch-auction liquidation tests.
+//!
+//! # Coverage
+//! - Auctions only start on liquidatable positions, one per borrower
+//! - Fixed-incentive `liquidate` is rejected while an auction is open
+//! - The bid discount ramps from 0 to the maximum incentive and then caps
+//! - Settlement requires a healthy position or a finished ramp
+//! - Admin-only cancellation and config, authorized by the caller, with closed
+//!   auctions kept in history
+//! - Eligibility checks preview pending interest without drawing subsidy or
+//!   recording interest checkpoints
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::interest_rate::{calculate_borrow_rate, get_interest_checkpoint};
+use crate::liquidate::{liquidate, AuctionConfig, AuctionOutcome, LiquidationError};
+use crate::reserve::{get_subsidy_budget, get_subsidy_spent, ReserveDataKey};
+use crate::tests::test_helpers::assert_authorized;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, Symbol,
+};
+
+fn setup(e: &Env) -> (Address, Address, HelloContractClient<'_>) {
+    e.mock_all_auths();
+    e.ledger().with_mut(|li| li.timestamp = 1_000);
+    let id = e.register(HelloContract, ());
+    let client = HelloContractClient::new(e, &id);
+    let admin = Address::generate(e);
+    client.initialize(&admin);
+    client.set_auction_config(
+        &admin,
+        &AuctionConfig {
+            duration: 1_000,
+            max_incentive_bps: 1_000,
+        },
+    );
+    (id, admin, client)
+}
+
+/// Write a native position directly; below 105% collateralization is liquidatable
+fn set_position(e: &Env, id: &Address, user: &Address, collateral: i128, debt: i128) {
+    e.as_contract(id, || {
+        e.storage().persistent().set(
+            &DepositDataKey::CollateralBalance(user.clone()),
+            &collateral,
+        );
+        e.storage().persistent().set(
+            &DepositDataKey::Position(user.clone()),
+            &Position {
+                collateral,
+                debt,
+                borrow_interest: 0,
+                last_accrual_time: e.ledger().timestamp(),
+            },
+        );
+    });
+}
+
+fn advance(e: &Env, seconds: u64) {
+    e.ledger().with_mut(|li| li.timestamp += seconds);
+}
+
+#[test]
+fn test_start_requires_liquidatable_position() {
+    let e = Env::default();
+    let (id, _admin, client) = setup(&e);
+    let starter = Address::generate(&e);
+    let borrower = Address::generate(&e);
+
+    set_position(&e, &id, &borrower, 1_500, 1_000);
+    assert_eq!(
+        client.try_start_auction(&starter, &borrower, &None, &None),
+        Err(Ok(LiquidationError::NotLiquidatable))
+    );
+
+    set_position(&e, &id, &borrower, 1_000, 1_000);
+    let auction = client.start_auction(&starter, &borrower, &None, &None);
+    assert_eq!(auction.start_time, 1_000);
+    assert_eq!(auction.max_incentive_bps, 1_000);
+    assert_eq!(client.get_auction(&borrower), Some(auction));
+
+    assert_eq!(
+        client.try_start_auction(&starter, &borrower, &None, &None),
+        Err(Ok(LiquidationError::AuctionActive))
+    );
+}
+
+#[test]
+fn test_fixed_liquidation_blocked_during_auction() {
+    let e = Env::default();
+    let (id, _admin, client) = setup(&e);
+    let liquidator = Address::generate(&e);
+    let borrower = Address::generate(&e);
+
+    set_position(&e, &id, &borrower, 1_000, 1_000);
+    client.start_auction(&liquidator, &borrower, &None, &None);
+
+    let result = e.as_contract(&id, || {
+        liquidate(&e, liquidator.clone(), borrower.clone(), None, None, 100)
+    });
+    assert_eq!(result, Err(LiquidationError::AuctionActive));
+}
+
+#[test]
+fn test_discount_ramps_then_caps() {
+    let e = Env::default();
+    let (id, _admin, client) = setup(&e);
+    let liquidator = Address::generate(&e);
+    let borrower = Address::generate(&e);
+
+    set_position(&e, &id, &borrower, 1_000, 1_000);
+    client.start_auction(&liquidator, &borrower, &None, &None);
+
+    // No discount at the start of the ramp
+    assert_eq!(client.bid(&liquidator, &borrower, &100), (100, 100, 0));
+
+    // Half way through the ramp: 5%
+    advance(&e, 500);
+    assert_eq!(client.bid(&liquidator, &borrower, &100), (100, 105, 5));
+
+    // Past the ramp the discount stays at the 10% maximum
+    advance(&e, 1_500);
+    assert_eq!(client.bid(&liquidator, &borrower, &100), (100, 110, 10));
+
+    let auction = client.get_auction(&borrower).unwrap();
+    assert_eq!(auction.debt_repaid, 300);
+    assert_eq!(auction.collateral_seized, 315);
+}
+
+#[test]
+fn test_settle_after_ramp_or_recovery() {
+    let e = Env::default();
+    let (id, _admin, client) = setup(&e);
+    let keeper = Address::generate(&e);
+    let borrower = Address::generate(&e);
+    let other = Address::generate(&e);
+
+    set_position(&e, &id, &borrower, 1_000, 1_000);
+    client.start_auction(&keeper, &borrower, &None, &None);
+    assert_eq!(
+        client.try_settle_auction(&keeper, &borrower),
+        Err(Ok(LiquidationError::AuctionNotSettleable))
+    );
+
+    advance(&e, 1_000);
+    let record = client.settle_auction(&keeper, &borrower);
+    assert_eq!(record.outcome, AuctionOutcome::Settled);
+    assert_eq!(record.closed_at, 2_000);
+    assert_eq!(client.get_auction(&borrower), None);
+    assert_eq!(client.get_auction_history(&borrower).len(), 1);
+
+    // A position that recovered can be settled before the ramp ends
+    set_position(&e, &id, &other, 1_000, 1_000);
+    client.start_auction(&keeper, &other, &None, &None);
+    set_position(&e, &id, &other, 2_000, 1_000);
+    assert_eq!(
+        client.settle_auction(&keeper, &other).outcome,
+        AuctionOutcome::Settled
+    );
+
+    assert_eq!(
+        client.try_settle_auction(&keeper, &other),
+        Err(Ok(LiquidationError::AuctionNotFound))
+    );
+}
+
+#[test]
+fn test_cancel_is_admin_only() {
+    let e = Env::default();
+    let (id, admin, client) = setup(&e);
+    let stranger = Address::generate(&e);
+    let borrower = Address::generate(&e);
+
+    set_position(&e, &id, &borrower, 1_000, 1_000);
+    client.start_auction(&stranger, &borrower, &None, &None);
+
+    assert_eq!(
+        client.try_cancel_auction(&stranger, &borrower),
+        Err(Ok(LiquidationError::Unauthorized))
+    );
+
+    let record = client.cancel_auction(&admin, &borrower);
+    assert_authorized(&e, &admin, &id, "cancel_auction");
+    assert_eq!(record.outcome, AuctionOutcome::Cancelled);
+    assert_eq!(client.get_auction(&borrower), None);
+    assert_eq!(
+        client
+            .get_auction_history(&borrower)
+            .get(0)
+            .unwrap()
+            .outcome,
+        AuctionOutcome::Cancelled
+    );
+}
+
+#[test]
+fn test_config_validation() {
+    let e = Env::default();
+    let (id, admin, client) = setup(&e);
+    let stranger = Address::generate(&e);
+    let valid = AuctionConfig {
+        duration: 600,
+        max_incentive_bps: 500,
+    };
+
+    assert_eq!(
+        client.try_set_auction_config(&stranger, &valid),
+        Err(Ok(LiquidationError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_auction_config(
+            &admin,
+            &AuctionConfig {
+                duration: 0,
+                max_incentive_bps: 500,
+            }
+        ),
+        Err(Ok(LiquidationError::InvalidAuctionConfig))
+    );
+    assert_eq!(
+        client.try_set_auction_config(
+            &admin,
+            &AuctionConfig {
+                duration: 600,
+                max_incentive_bps: 5_001,
+            }
+        ),
+        Err(Ok(LiquidationError::InvalidAuctionConfig))
+    );
+
+    client.set_auction_config(&admin, &valid);
+    assert_authorized(&e, &admin, &id, "set_auction_config");
+    assert_eq!(client.get_auction_config(), valid);
+}
+
+#[test]
+fn test_eligibility_check_previews_interest() {
+    let e = Env::default();
+    let (id, admin, client) = setup(&e);
+    let starter = Address::generate(&e);
+    let borrower = Address::generate(&e);
+
+    // A zero rate cap with a funded budget subsidizes all of the interest
+    let class = Symbol::new(&e, "rwa");
+    client.set_class_rate_cap(&admin, &class, &Some(0));
+    client.set_user_class(&admin, &borrower, &Some(class));
+    e.as_contract(&id, || {
+        e.storage()
+            .persistent()
+            .set(&ReserveDataKey::ReserveBalance(None), &1_000_000i128);
+    });
+    client.allocate_subsidy_budget(&admin, &None, &100_000);
+
+    set_position(&e, &id, &borrower, 1_000, 1_000);
+    advance(&e, 365 * 86_400);
+    let checkpoint = e.as_contract(&id, || {
+        assert!(calculate_borrow_rate(&e).unwrap() > 0);
+        get_interest_checkpoint(&e, &borrower)
+    });
+
+    client.start_auction(&starter, &borrower, &None, &None);
+    e.as_contract(&id, || {
+        assert_eq!(get_subsidy_budget(&e, None), 100_000);
+        assert_eq!(get_subsidy_spent(&e, None), 0);
+        assert_eq!(get_interest_checkpoint(&e, &borrower), checkpoint);
+    });
+}
@@ -0,0 +1,136 @@
+//! Liquidation hysteresis tests.
+//!
+//! # Coverage
+//! - A position found liquidatable stays liquidatable within the ledger
+//!   until it clears the threshold plus the band
+//! - The band expires with the ledger and is off by default
+//! - The stateless `can_be_liquidated` view ignores the band
+//! - Band bounds, admin-only access and the admin's authorization
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::liquidate::{liquidate, LiquidationError};
+use crate::risk_management::{RiskManagementError, MAX_LIQUIDATION_HYSTERESIS_BPS};
+use crate::tests::test_helpers::assert_authorized;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+fn setup(e: &Env) -> (Address, Address, HelloContractClient<'_>) {
+    e.mock_all_auths();
+    let id = e.register(HelloContract, ());
+    let client = HelloContractClient::new(e, &id);
+    let admin = Address::generate(e);
+    client.initialize(&admin);
+    (id, admin, client)
+}
+
+/// Write a native position directly; below 105% collateralization is liquidatable
+fn set_position(e: &Env, id: &Address, user: &Address, collateral: i128, debt: i128) {
+    e.as_contract(id, || {
+        e.storage().persistent().set(
+            &DepositDataKey::CollateralBalance(user.clone()),
+            &collateral,
+        );
+        e.storage().persistent().set(
+            &DepositDataKey::Position(user.clone()),
+            &Position {
+                collateral,
+                debt,
+                borrow_interest: 0,
+                last_accrual_time: e.ledger().timestamp(),
+            },
+        );
+    });
+}
+
+fn try_liquidate(e: &Env, id: &Address, borrower: &Address) -> Result<(), LiquidationError> {
+    let liquidator = Address::generate(e);
+    e.as_contract(id, || {
+        liquidate(e, liquidator, borrower.clone(), None, None, 100).map(|_| ())
+    })
+}
+
+#[test]
+fn test_band_keeps_position_liquidatable_within_ledger() {
+    let e = Env::default();
+    let (id, admin, client) = setup(&e);
+    client.set_liquidation_hysteresis(&admin, &50);
+    let borrower = Address::generate(&e);
+
+    set_position(&e, &id, &borrower, 10_000, 10_000);
+    assert_eq!(try_liquidate(&e, &id, &borrower), Ok(()));
+
+    // Jitter lifts the ratio to 105.2%: inside the band, still liquidatable
+    set_position(&e, &id, &borrower, 10_520, 10_000);
+    assert_eq!(try_liquidate(&e, &id, &borrower), Ok(()));
+    // The stateless view applies the plain threshold
+    assert!(!client.can_be_liquidated(&10_520, &10_000));
+
+    // 105.5% clears the band
+    set_position(&e, &id, &borrower, 10_550, 10_000);
+    assert_eq!(
+        try_liquidate(&e, &id, &borrower),
+        Err(LiquidationError::NotLiquidatable)
+    );
+}
+
+#[test]
+fn test_band_expires_with_ledger() {
+    let e = Env::default();
+    let (id, admin, client) = setup(&e);
+    client.set_liquidation_hysteresis(&admin, &50);
+    let borrower = Address::generate(&e);
+
+    set_position(&e, &id, &borrower, 10_000, 10_000);
+    assert_eq!(try_liquidate(&e, &id, &borrower), Ok(()));
+
+    e.ledger().with_mut(|li| li.sequence_number += 1);
+    set_position(&e, &id, &borrower, 10_520, 10_000);
+    assert_eq!(
+        try_liquidate(&e, &id, &borrower),
+        Err(LiquidationError::NotLiquidatable)
+    );
+}
+
+#[test]
+fn test_no_band_by_default() {
+    let e = Env::default();
+    let (id, _admin, client) = setup(&e);
+    assert_eq!(client.get_liquidation_hysteresis(), 0);
+    let borrower = Address::generate(&e);
+
+    set_position(&e, &id, &borrower, 10_000, 10_000);
+    assert_eq!(try_liquidate(&e, &id, &borrower), Ok(()));
+    set_position(&e, &id, &borrower, 10_520, 10_000);
+    assert_eq!(
+        try_liquidate(&e, &id, &borrower),
+        Err(LiquidationError::NotLiquidatable)
+    );
+}
+
+#[test]
+fn test_set_liquidation_hysteresis_validation() {
+    let e = Env::default();
+    let (id, admin, client) = setup(&e);
+    assert_eq!(
+        client.try_set_liquidation_hysteresis(&admin, &(MAX_LIQUIDATION_HYSTERESIS_BPS + 1)),
+        Err(Ok(RiskManagementError::InvalidParameter))
+    );
+    assert_eq!(
+        client.try_set_liquidation_hysteresis(&admin, &-1),
+        Err(Ok(RiskManagementError::InvalidParameter))
+    );
+    assert_eq!(
+        client.try_set_liquidation_hysteresis(&Address::generate(&e), &10),
+        Err(Ok(RiskManagementError::Unauthorized))
+    );
+
+    client.set_liquidation_hysteresis(&admin, &MAX_LIQUIDATION_HYSTERESIS_BPS);
+    assert_authorized(&e, &admin, &id, "set_liquidation_hysteresis");
+    assert_eq!(
+        client.get_liquidation_hysteresis(),
+        MAX_LIQUIDATION_HYSTERESIS_BPS
+    );
+}
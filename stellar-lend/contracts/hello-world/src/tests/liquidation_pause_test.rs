@@ -0,0 +1,127 @@
+//! Per-market liquidation pause tests.
+//!
+//! # Coverage
+//! - Admin can pause liquidations for one market with a bounded duration
+//! - The pause expires automatically once the duration elapses
+//! - Admin can lift the pause early
+//! - Pausing and unpausing need the caller's authorization
+//! - Zero and over-limit durations are rejected
+//! - Non-admin callers cannot pause
+//! - Pausing one market does not affect other markets
+//! - `liquidate` rejects while either market involved is paused
+
+use crate::liquidate::{liquidate, LiquidationError};
+use crate::risk_management::{RiskManagementError, MAX_LIQUIDATION_PAUSE_SECONDS};
+use crate::tests::test_helpers::assert_authorized;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+fn setup(e: &Env) -> (Address, Address, HelloContractClient<'_>) {
+    e.mock_all_auths();
+    let id = e.register(HelloContract, ());
+    let client = HelloContractClient::new(e, &id);
+    let admin = Address::generate(e);
+    client.initialize(&admin);
+    (id, admin, client)
+}
+
+#[test]
+fn test_pause_sets_expiry() {
+    let e = Env::default();
+    let (_id, admin, client) = setup(&e);
+    let asset = Address::generate(&e);
+    e.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let expires_at = client.pause_asset_liquidations(&admin, &Some(asset.clone()), &600);
+
+    assert_eq!(expires_at, 1_600);
+    assert_eq!(client.get_liquidation_pause_expiry(&Some(asset)), Some(1_600));
+}
+
+#[test]
+fn test_pause_expires_automatically() {
+    let e = Env::default();
+    let (_id, admin, client) = setup(&e);
+    let asset = Address::generate(&e);
+    e.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    client.pause_asset_liquidations(&admin, &Some(asset.clone()), &600);
+
+    e.ledger().with_mut(|li| li.timestamp = 1_599);
+    assert!(client.get_liquidation_pause_expiry(&Some(asset.clone())).is_some());
+
+    e.ledger().with_mut(|li| li.timestamp = 1_600);
+    assert_eq!(client.get_liquidation_pause_expiry(&Some(asset)), None);
+}
+
+#[test]
+fn test_unpause_lifts_pause_early() {
+    let e = Env::default();
+    let (id, admin, client) = setup(&e);
+    let asset = Address::generate(&e);
+
+    client.pause_asset_liquidations(&admin, &Some(asset.clone()), &600);
+    assert_authorized(&e, &admin, &id, "pause_asset_liquidations");
+    client.unpause_asset_liquidations(&admin, &Some(asset.clone()));
+    assert_authorized(&e, &admin, &id, "unpause_asset_liquidations");
+
+    assert_eq!(client.get_liquidation_pause_expiry(&Some(asset)), None);
+}
+
+#[test]
+fn test_pause_rejects_invalid_duration() {
+    let e = Env::default();
+    let (_id, admin, client) = setup(&e);
+    let asset = Address::generate(&e);
+
+    let zero = client.try_pause_asset_liquidations(&admin, &Some(asset.clone()), &0);
+    assert_eq!(zero, Err(Ok(RiskManagementError::InvalidParameter)));
+
+    let too_long = client.try_pause_asset_liquidations(
+        &admin,
+        &Some(asset),
+        &(MAX_LIQUIDATION_PAUSE_SECONDS + 1),
+    );
+    assert_eq!(too_long, Err(Ok(RiskManagementError::InvalidParameter)));
+}
+
+#[test]
+fn test_pause_requires_admin() {
+    let e = Env::default();
+    let (_id, _admin, client) = setup(&e);
+    let stranger = Address::generate(&e);
+    let asset = Address::generate(&e);
+
+    let result = client.try_pause_asset_liquidations(&stranger, &Some(asset), &600);
+    assert_eq!(result, Err(Ok(RiskManagementError::Unauthorized)));
+}
+
+#[test]
+fn test_pause_is_isolated_per_market() {
+    let e = Env::default();
+    let (_id, admin, client) = setup(&e);
+    let paused = Address::generate(&e);
+    let other = Address::generate(&e);
+
+    client.pause_asset_liquidations(&admin, &Some(paused.clone()), &600);
+
+    assert!(client.get_liquidation_pause_expiry(&Some(paused)).is_some());
+    assert_eq!(client.get_liquidation_pause_expiry(&Some(other)), None);
+    assert_eq!(client.get_liquidation_pause_expiry(&None), None);
+}
+
+#[test]
+fn test_liquidate_rejected_while_market_paused() {
+    let e = Env::default();
+    let (id, admin, client) = setup(&e);
+    let liquidator = Address::generate(&e);
+    let borrower = Address::generate(&e);
+
+    client.pause_asset_liquidations(&admin, &None, &600);
+
+    let result = e.as_contract(&id, || liquidate(&e, liquidator, borrower, None, None, 100));
+    assert_eq!(result, Err(LiquidationError::LiquidationPaused));
+}
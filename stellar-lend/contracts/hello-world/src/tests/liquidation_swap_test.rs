@@ -0,0 +1,223 @@
+//! Swap liquidation tests.
+//!
+//! # Coverage
+//! - Seized collateral is swapped to the debt asset, the proceeds repay the
+//!   debt and the surplus stays with the liquidator
+//! - Swaps below `min_out` and pairs without an AMM pool revert the
+//!   liquidation
+//! - Debt and collateral in the same asset are rejected
+//! - Flash loan liquidations sell just enough collateral to repay the loan
+//!   plus fee and credit the fee to the reserve
+//! - A flash loan the seized collateral cannot cover reverts the liquidation
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::liquidate::{FlashLiquidationResult, LiquidationError, SwapLiquidationResult};
+use crate::tests::test_helpers::setup_env_with_native_asset;
+use crate::HelloContractClient;
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env, Symbol, Vec,
+};
+use stellarlend_amm::{AmmProtocolConfig, TokenPair};
+
+/// Open a `size` / `size` native-collateral position with debt in a new
+/// token and register an AMM pool for the pair (1% default slippage)
+fn setup_swap_liquidation(
+    env: &Env,
+    id: &Address,
+    client: &HelloContractClient,
+    admin: &Address,
+    borrower: &Address,
+    size: i128,
+) -> Address {
+    let debt_token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    env.as_contract(id, || {
+        env.storage()
+            .persistent()
+            .set(&DepositDataKey::CollateralBalance(borrower.clone()), &size);
+        env.storage().persistent().set(
+            &DepositDataKey::Position(borrower.clone()),
+            &Position {
+                collateral: size,
+                debt: size,
+                borrow_interest: 0,
+                last_accrual_time: env.ledger().timestamp(),
+            },
+        );
+    });
+
+    client.initialize_amm(admin, &100, &200, &10_000);
+    let mut supported_pairs = Vec::new(env);
+    supported_pairs.push_back(TokenPair {
+        token_a: None,
+        token_b: Some(debt_token.clone()),
+        pool_address: Address::generate(env),
+    });
+    client.set_amm_pool(
+        admin,
+        &AmmProtocolConfig {
+            protocol_address: Address::generate(env),
+            protocol_name: Symbol::new(env, "TestAMM"),
+            enabled: true,
+            fee_tier: 30,
+            min_swap_amount: 100,
+            max_swap_amount: 1_000_000,
+            supported_pairs,
+        },
+    );
+    debt_token
+}
+
+/// Mint the liquidator what the swap would deliver and approve the repayment
+fn fund_swap_proceeds(env: &Env, id: &Address, debt_token: &Address, liquidator: &Address) {
+    StellarAssetClient::new(env, debt_token).mint(liquidator, &1_089);
+    TokenClient::new(env, debt_token).approve(
+        liquidator,
+        id,
+        &1_000,
+        &(env.ledger().sequence() + 100),
+    );
+}
+
+#[test]
+fn test_liquidate_and_swap_routes_surplus_to_liquidator() {
+    let (env, id, client, admin, borrower, _native) = setup_env_with_native_asset();
+    let liquidator = Address::generate(&env);
+    let debt_token = setup_swap_liquidation(&env, &id, &client, &admin, &borrower, 10_000);
+    fund_swap_proceeds(&env, &id, &debt_token, &liquidator);
+
+    // 1_000 repaid seizes 1_100 with the 10% incentive; 1% slippage → 1_089
+    let result = client.liquidate_and_swap(
+        &liquidator,
+        &borrower,
+        &Some(debt_token.clone()),
+        &None,
+        &1_000,
+        &1_050,
+    );
+    assert_eq!(
+        result,
+        SwapLiquidationResult {
+            debt_liquidated: 1_000,
+            collateral_seized: 1_100,
+            incentive_amount: 100,
+            amount_out: 1_089,
+            surplus: 89,
+        }
+    );
+
+    let token = TokenClient::new(&env, &debt_token);
+    assert_eq!(token.balance(&liquidator), 89);
+    assert_eq!(token.balance(&id), 1_000);
+    let position = client.get_user_report(&borrower).position;
+    assert_eq!(position.debt, 9_000);
+    assert_eq!(position.collateral, 8_900);
+}
+
+#[test]
+fn test_liquidate_and_swap_rejects_bad_swaps() {
+    let (env, id, client, admin, borrower, _native) = setup_env_with_native_asset();
+    let liquidator = Address::generate(&env);
+    let debt_token = Some(setup_swap_liquidation(
+        &env, &id, &client, &admin, &borrower,
+    ));
+
+    assert_eq!(
+        client.try_liquidate_and_swap(&liquidator, &borrower, &debt_token, &None, &1_000, &1_090),
+        Err(Ok(LiquidationError::SlippageExceeded))
+    );
+    let unsupported = Some(Address::generate(&env));
+    assert_eq!(
+        client.try_liquidate_and_swap(&liquidator, &borrower, &unsupported, &None, &1_000, &0),
+        Err(Ok(LiquidationError::SwapFailed))
+    );
+    assert_eq!(
+        client.try_liquidate_and_swap(&liquidator, &borrower, &None, &None, &1_000, &0),
+        Err(Ok(LiquidationError::InvalidCollateralAsset))
+    );
+    assert_eq!(
+        client.try_liquidate_and_swap(&liquidator, &borrower, &debt_token, &None, &1_000, &-1),
+        Err(Ok(LiquidationError::InvalidAmount))
+    );
+
+    // Nothing was liquidated
+    assert_eq!(client.get_user_report(&borrower).position.debt, 10_000);
+}
+
+#[test]
+fn test_liquidate_with_flash_loan_sells_only_what_repays_the_loan() {
+    let (env, id, client, admin, borrower, _native) = setup_env_with_native_asset();
+    let liquidator = Address::generate(&env);
+    let debt_token = setup_swap_liquidation(&env, &id, &client, &admin, &borrower, 1_000_000);
+    let token = TokenClient::new(&env, &debt_token);
+    StellarAssetClient::new(&env, &debt_token).mint(&id, &100_000);
+
+    // 100_000 + 9 bps fee = 100_090 owed; at 1% slippage 101_102 collateral
+    // returns 100_090 (the swap proceeds are minted to the liquidator)
+    StellarAssetClient::new(&env, &debt_token).mint(&liquidator, &100_090);
+    token.approve(&liquidator, &id, &100_090, &(env.ledger().sequence() + 100));
+    let result = client.liquidate_with_flash_loan(
+        &liquidator,
+        &borrower,
+        &Some(debt_token.clone()),
+        &None,
+        &100_000,
+    );
+    assert_eq!(
+        result,
+        FlashLiquidationResult {
+            debt_liquidated: 100_000,
+            collateral_seized: 110_000,
+            incentive_amount: 10_000,
+            flash_fee: 90,
+            collateral_sold: 101_102,
+            amount_out: 100_090,
+            surplus: 0,
+        }
+    );
+
+    // The contract gets the repaid debt and the fee; the fee is reserved
+    assert_eq!(token.balance(&id), 200_090);
+    assert_eq!(token.balance(&liquidator), 0);
+    assert_eq!(client.get_reserve_balance(&Some(debt_token)), 90);
+    assert_eq!(client.get_user_report(&borrower).position.debt, 900_000);
+}
+
+#[test]
+fn test_liquidate_with_flash_loan_rejects_uncovered_loans() {
+    let (env, id, client, admin, borrower, _native) = setup_env_with_native_asset();
+    let liquidator = Address::generate(&env);
+    let debt_token = Some(setup_swap_liquidation(
+        &env, &id, &client, &admin, &borrower, 1_000_000,
+    ));
+
+    // Without liquidity the flash loan is refused
+    assert_eq!(
+        client.try_liquidate_with_flash_loan(&liquidator, &borrower, &debt_token, &None, &100_000),
+        Err(Ok(LiquidationError::FlashLoanFailed))
+    );
+
+    // With the incentive switched off the seized collateral cannot cover the
+    // fee and the slippage
+    StellarAssetClient::new(&env, debt_token.as_ref().unwrap()).mint(&id, &100_000);
+    env.as_contract(&id, || {
+        let mut params = crate::risk_params::get_risk_params(&env).unwrap();
+        params.liquidation_incentive = 0;
+        env.storage().persistent().set(
+            &crate::risk_params::RiskParamsDataKey::RiskParamsConfig,
+            &params,
+        );
+    });
+    assert_eq!(
+        client.try_liquidate_with_flash_loan(&liquidator, &borrower, &debt_token, &None, &100_000),
+        Err(Ok(LiquidationError::FlashLoanNotCovered))
+    );
+    assert_eq!(
+        client.try_liquidate_with_flash_loan(&liquidator, &borrower, &None, &None, &100_000),
+        Err(Ok(LiquidationError::InvalidCollateralAsset))
+    );
+    assert_eq!(client.get_user_report(&borrower).position.debt, 1_000_000);
+}
@@ -0,0 +1,154 @@
+//! Liquidator statistics and leaderboard tests.
+//!
+//! # Coverage
+//! - Liquidations accumulate volume, collateral and incentive per liquidator
+//! - Auction bids count toward the same statistics
+//! - The leaderboard ranks by debt repaid and re-ranks on new activity
+//! - The leaderboard view honors the requested limit and the size bound
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::liquidate::{liquidate, AuctionConfig, LiquidatorStats, MAX_LEADERBOARD_SIZE};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+fn setup(e: &Env) -> (Address, Address, HelloContractClient<'_>) {
+    e.mock_all_auths();
+    e.ledger().with_mut(|li| li.timestamp = 1_000);
+    let id = e.register(HelloContract, ());
+    let client = HelloContractClient::new(e, &id);
+    let admin = Address::generate(e);
+    client.initialize(&admin);
+    (id, admin, client)
+}
+
+/// Write a native position directly; below 105% collateralization is liquidatable
+fn set_position(e: &Env, id: &Address, user: &Address, collateral: i128, debt: i128) {
+    e.as_contract(id, || {
+        e.storage().persistent().set(
+            &DepositDataKey::CollateralBalance(user.clone()),
+            &collateral,
+        );
+        e.storage().persistent().set(
+            &DepositDataKey::Position(user.clone()),
+            &Position {
+                collateral,
+                debt,
+                borrow_interest: 0,
+                last_accrual_time: e.ledger().timestamp(),
+            },
+        );
+    });
+}
+
+/// Liquidate `amount` of a fresh 10_000 / 10_000 native position
+fn liquidate_fresh(e: &Env, id: &Address, liquidator: &Address, amount: i128) {
+    let borrower = Address::generate(e);
+    set_position(e, id, &borrower, 10_000, 10_000);
+    e.as_contract(id, || {
+        liquidate(e, liquidator.clone(), borrower, None, None, amount).unwrap();
+    });
+}
+
+#[test]
+fn test_liquidations_accumulate_stats() {
+    let e = Env::default();
+    let (id, _admin, client) = setup(&e);
+    let liquidator = Address::generate(&e);
+    assert_eq!(
+        client.get_liquidator_stats(&liquidator),
+        LiquidatorStats::default()
+    );
+
+    // 10% incentive on each liquidation
+    liquidate_fresh(&e, &id, &liquidator, 1_000);
+    e.ledger().with_mut(|li| li.timestamp = 2_000);
+    liquidate_fresh(&e, &id, &liquidator, 500);
+
+    assert_eq!(
+        client.get_liquidator_stats(&liquidator),
+        LiquidatorStats {
+            liquidation_count: 2,
+            debt_repaid: 1_500,
+            collateral_seized: 1_650,
+            incentive_earned: 150,
+            last_liquidation: 2_000,
+        }
+    );
+}
+
+#[test]
+fn test_auction_bids_count_toward_stats() {
+    let e = Env::default();
+    let (id, admin, client) = setup(&e);
+    let liquidator = Address::generate(&e);
+    let borrower = Address::generate(&e);
+    client.set_auction_config(
+        &admin,
+        &AuctionConfig {
+            duration: 1_000,
+            max_incentive_bps: 1_000,
+        },
+    );
+
+    set_position(&e, &id, &borrower, 1_000, 1_000);
+    client.start_auction(&liquidator, &borrower, &None, &None);
+    e.ledger().with_mut(|li| li.timestamp += 500);
+    client.bid(&liquidator, &borrower, &100);
+
+    let stats = client.get_liquidator_stats(&liquidator);
+    assert_eq!(stats.liquidation_count, 1);
+    assert_eq!(stats.debt_repaid, 100);
+    assert_eq!(stats.incentive_earned, 5);
+}
+
+#[test]
+fn test_leaderboard_ranks_by_debt_repaid() {
+    let e = Env::default();
+    let (id, _admin, client) = setup(&e);
+    let first = Address::generate(&e);
+    let second = Address::generate(&e);
+    let third = Address::generate(&e);
+
+    liquidate_fresh(&e, &id, &first, 300);
+    liquidate_fresh(&e, &id, &second, 1_000);
+    liquidate_fresh(&e, &id, &third, 500);
+
+    let board = client.get_liquidator_leaderboard(&10);
+    assert_eq!(board.len(), 3);
+    assert_eq!(board.get(0).unwrap().liquidator, second);
+    assert_eq!(board.get(1).unwrap().liquidator, third);
+    assert_eq!(board.get(2).unwrap().liquidator, first);
+    assert_eq!(board.get(0).unwrap().stats.debt_repaid, 1_000);
+
+    // New activity moves a liquidator up
+    liquidate_fresh(&e, &id, &first, 1_000);
+    let board = client.get_liquidator_leaderboard(&2);
+    assert_eq!(board.len(), 2);
+    assert_eq!(board.get(0).unwrap().liquidator, first);
+    assert_eq!(board.get(1).unwrap().liquidator, second);
+}
+
+#[test]
+fn test_leaderboard_is_bounded() {
+    let e = Env::default();
+    let (id, _admin, client) = setup(&e);
+
+    let mut smallest = None;
+    for i in 0..=MAX_LEADERBOARD_SIZE {
+        let liquidator = Address::generate(&e);
+        liquidate_fresh(&e, &id, &liquidator, 100 + i as i128);
+        if i == 0 {
+            smallest = Some(liquidator);
+        }
+    }
+
+    let board = client.get_liquidator_leaderboard(&u32::MAX);
+    assert_eq!(board.len(), MAX_LEADERBOARD_SIZE);
+    let smallest = smallest.unwrap();
+    assert!(board.iter().all(|entry| entry.liquidator != smallest));
+    // Liquidators that fall off the board still keep their stats
+    assert_eq!(client.get_liquidator_stats(&smallest).debt_repaid, 100);
+}
@@ -0,0 +1,137 @@
+//! Daily metric history tests.
+//!
+//! # Coverage
+//! - Ledger changes refresh the current day's bucket; quiet days have none
+//! - A keeper rate poke records the day's metrics, rates included
+//! - `get_metric_history` returns one point per recorded day in range
+//! - The ring buffer drops days older than `METRIC_HISTORY_DAYS`
+//! - Range validation
+
+use super::test_helpers::setup_env_with_native_asset;
+use crate::borrow::borrow_asset;
+use crate::deposit::deposit_collateral;
+use crate::metric_history::{
+    Metric, MetricHistoryError, MetricPoint, METRIC_DAY_SECONDS, METRIC_HISTORY_DAYS,
+};
+use crate::HelloContractClient;
+use soroban_sdk::{testutils::Address as _, vec, Address, Env};
+use stellarlend_testutils::set_time;
+
+struct Setup {
+    env: Env,
+    id: Address,
+    client: HelloContractClient<'static>,
+    admin: Address,
+    user: Address,
+    native: Address,
+    oracle: Address,
+}
+
+fn setup() -> Setup {
+    let (env, id, client, admin, user, native) = setup_env_with_native_asset();
+    let s = Setup {
+        oracle: Address::generate(&env),
+        env,
+        id,
+        client,
+        admin,
+        user,
+        native,
+    };
+    go_to_day(&s, 0);
+    s
+}
+
+/// Move into `day` and refresh the native price (1.0) so it is not stale
+fn go_to_day(s: &Setup, day: u64) {
+    set_time(&s.env, day * METRIC_DAY_SECONDS + 100);
+    s.client
+        .update_price_feed(&s.admin, &s.native, &100_000_000, &8, &s.oracle);
+}
+
+fn point(day: u64, value: i128) -> MetricPoint {
+    MetricPoint { day, value }
+}
+
+#[test]
+fn test_ledger_changes_record_daily_buckets() {
+    let s = setup();
+    let e = &s.env;
+    e.as_contract(&s.id, || {
+        deposit_collateral(e, s.user.clone(), None, 10_000).unwrap();
+    });
+    go_to_day(&s, 2);
+    e.as_contract(&s.id, || {
+        borrow_asset(e, s.user.clone(), None, 2_000).unwrap();
+    });
+
+    assert_eq!(
+        s.client.get_metric_history(&Metric::Tvl, &0, &2),
+        vec![e, point(0, 10_000), point(2, 10_000)]
+    );
+    assert_eq!(
+        s.client.get_metric_history(&Metric::Borrows, &0, &2),
+        vec![e, point(0, 0), point(2, 2_000)]
+    );
+    assert_eq!(
+        s.client.get_metric_history(&Metric::Utilization, &2, &2),
+        vec![e, point(2, 2_000)]
+    );
+    assert_eq!(s.client.get_daily_metrics(&1), None);
+    assert_eq!(
+        s.client.get_daily_metrics(&2).unwrap().updated_at,
+        2 * METRIC_DAY_SECONDS + 100
+    );
+}
+
+#[test]
+fn test_keeper_poke_records_quiet_days() {
+    let s = setup();
+    let e = &s.env;
+    let keeper = Address::generate(e);
+    e.as_contract(&s.id, || {
+        deposit_collateral(e, s.user.clone(), None, 10_000).unwrap();
+        borrow_asset(e, s.user.clone(), None, 4_000).unwrap();
+    });
+
+    go_to_day(&s, 3);
+    assert_eq!(s.client.get_metric_day(), 3);
+    s.client.poke_rates(&keeper);
+    let metrics = s.client.get_daily_metrics(&3).unwrap();
+    assert_eq!((metrics.tvl, metrics.borrows), (10_000, 4_000));
+    assert_eq!(metrics.borrow_rate_bps, s.client.get_borrow_rate());
+    assert_eq!(metrics.supply_rate_bps, s.client.get_supply_rate());
+    assert_eq!(
+        s.client.get_metric_history(&Metric::BorrowRate, &3, &3),
+        vec![e, point(3, metrics.borrow_rate_bps)]
+    );
+
+    // The slot of day 3 is reused once the buffer wraps
+    go_to_day(&s, 3 + METRIC_HISTORY_DAYS);
+    s.client.poke_rates(&keeper);
+    assert_eq!(s.client.get_daily_metrics(&3), None);
+    assert_eq!(
+        s.client
+            .get_metric_history(&Metric::Tvl, &4, &(3 + METRIC_HISTORY_DAYS)),
+        vec![e, point(3 + METRIC_HISTORY_DAYS, 10_000)]
+    );
+}
+
+#[test]
+fn test_metric_history_range_checks() {
+    let s = setup();
+
+    assert_eq!(
+        s.client.try_get_metric_history(&Metric::Tvl, &5, &4),
+        Err(Ok(MetricHistoryError::InvalidRange))
+    );
+    assert_eq!(
+        s.client
+            .try_get_metric_history(&Metric::Tvl, &0, &METRIC_HISTORY_DAYS),
+        Err(Ok(MetricHistoryError::InvalidRange))
+    );
+    assert!(s
+        .client
+        .get_metric_history(&Metric::Tvl, &0, &(METRIC_HISTORY_DAYS - 1))
+        .is_empty());
+}
@@ -26,3 +26,85 @@ pub mod recovery_test;
 pub mod multisig_test;
 pub mod multisig_governance_execution_test;
 pub mod cross_contract_test;
+pub mod liquidation_pause_test;
+pub mod snapshot_test;
+pub mod rate_cap_test;
+pub mod flash_loan_fee_tier_test;
+pub mod flash_loan_isolation_test;
+pub mod daily_borrow_limit_test;
+pub mod interest_accrued_event_test;
+pub mod dead_man_switch_test;
+pub mod cross_asset_interest_test;
+pub mod close_factor_tier_test;
+pub mod bridge_queue_test;
+pub mod supply_shares_test;
+pub mod cross_asset_netting_test;
+pub mod emode_test;
+pub mod isolation_mode_test;
+pub mod oracle_bond_test;
+pub mod asset_caps_test;
+pub mod constants_test;
+pub mod liquidation_auction_test;
+pub mod dynamic_close_factor_test;
+pub mod storage_namespace_test;
+pub mod bad_debt_test;
+pub mod liquidator_stats_test;
+pub mod operations_test;
+pub mod amm_read_test;
+pub mod twap_test;
+pub mod oracle_feeder_test;
+pub mod sep40_oracle_test;
+pub mod borrow_index_test;
+pub mod sim_test;
+pub mod reserve_factor_test;
+pub mod exit_exemption_test;
+pub mod notifications_test;
+pub mod receipt_token_test;
+pub mod accounting_test;
+pub mod liquidation_archive_test;
+pub mod batch_test;
+pub mod liquidation_hysteresis_test;
+pub mod leverage_test;
+pub mod risk_hook_test;
+pub mod debt_swap_test;
+pub mod oracle_failover_test;
+pub mod on_behalf_test;
+pub mod param_bundle_test;
+pub mod price_override_test;
+pub mod fees_test;
+pub mod rate_projection_test;
+pub mod rewards_test;
+pub mod insolvency_test;
+pub mod safety_module_test;
+pub mod bridge_fee_quote_test;
+pub mod vesting_voting_test;
+pub mod governance_execution_test;
+pub mod position_archive_test;
+pub mod vote_delegation_test;
+pub mod smart_wallet_test;
+pub mod financials_test;
+pub mod voting_strategy_test;
+pub mod keeper_pool_test;
+pub mod hedging_test;
+pub mod operational_roles_test;
+pub mod upgrade_test;
+pub mod subsidy_campaign_test;
+pub mod unit_conversion_test;
+pub mod compliance_test;
+pub mod rate_curve_test;
+pub mod stable_rate_test;
+pub mod withdraw_liquidity_test;
+pub mod liquidatable_positions_test;
+pub mod liquidation_swap_test;
+pub mod automation_test;
+pub mod operators_test;
+pub mod positions_test;
+pub mod bridge_verification_test;
+pub mod bridge_rate_limit_test;
+pub mod bridge_transfer_test;
+pub mod remote_collateral_test;
+pub mod price_heartbeat_test;
+pub mod circuit_breaker_test;
+pub mod invariants_test;
+pub mod asset_ledger_test;
+pub mod metric_history_test;
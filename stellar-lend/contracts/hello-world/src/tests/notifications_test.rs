@@ -0,0 +1,169 @@
+//! Borrower notification tests.
+//!
+//! # Coverage
+//! - Crossing into the warning, critical or liquidation imminent band emits
+//!   that band's event with the previous and new health factor
+//! - Events carry the borrower's contact hint, if registered
+//! - Updates within a band, and healthy positions, emit nothing
+//! - Thresholds are admin-configurable, authorized and validated
+//! - Hints can be set, replaced and cleared
+
+use crate::notifications::{
+    HealthNotificationThresholds, NotificationError, HEALTH_CRITICAL_FACTOR, HEALTH_WARNING_FACTOR,
+    LIQUIDATION_IMMINENT_FACTOR,
+};
+use crate::tests::test_helpers::{assert_authorized, setup_env_with_native_asset};
+use soroban_sdk::{
+    contracttype,
+    testutils::{Address as _, Events},
+    Address, BytesN, Env, Symbol, TryFromVal, Vec,
+};
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct TestHealthEvent {
+    pub user: Address,
+    pub previous_health_factor: i128,
+    pub health_factor: i128,
+    pub contact_hint: Option<BytesN<32>>,
+    pub timestamp: u64,
+}
+
+/// Health notifications in the events of the last invocation, with their topic
+fn health_events(env: &Env) -> Vec<(Symbol, TestHealthEvent)> {
+    let mut events = Vec::new(env);
+    for (_contract, topics, data) in env.events().all().iter() {
+        let Some(Ok(topic)) = topics.get(0).map(|t| Symbol::try_from_val(env, &t)) else {
+            continue;
+        };
+        if let Ok(event) = TestHealthEvent::try_from_val(env, &data) {
+            events.push_back((topic, event));
+        }
+    }
+    events
+}
+
+#[test]
+fn test_critical_crossing_carries_hint_and_factors() {
+    let (env, _id, client, _admin, user, _native) = setup_env_with_native_asset();
+    let hint = BytesN::from_array(&env, &[7; 32]);
+    client.set_contact_hint(&user, &hint);
+
+    // 110% collateral ratio is the borrow limit; health is 1.1 / 1.05
+    client.deposit_collateral(&user, &None, &11_000);
+    client.borrow_asset(&user, &None, &10_000);
+
+    let events = health_events(&env);
+    assert_eq!(events.len(), 1);
+    let (topic, event) = events.get_unchecked(0);
+    assert_eq!(topic, Symbol::new(&env, "health_critical"));
+    assert_eq!(event.user, user);
+    assert_eq!(event.previous_health_factor, i128::MAX);
+    assert!(event.health_factor < HEALTH_CRITICAL_FACTOR);
+    assert!(event.health_factor >= LIQUIDATION_IMMINENT_FACTOR);
+    assert_eq!(event.contact_hint, Some(hint));
+}
+
+#[test]
+fn test_recovery_into_warning_band() {
+    let (env, _id, client, _admin, user, _native) = setup_env_with_native_asset();
+    client.deposit_collateral(&user, &None, &11_000);
+    client.borrow_asset(&user, &None, &10_000);
+    let critical = health_events(&env).get_unchecked(0).1.health_factor;
+
+    // Health 1.14 / 1.05 is back above critical but still below warning
+    client.deposit_collateral(&user, &None, &400);
+    let events = health_events(&env);
+    assert_eq!(events.len(), 1);
+    let (topic, event) = events.get_unchecked(0);
+    assert_eq!(topic, Symbol::new(&env, "health_warning"));
+    assert_eq!(event.previous_health_factor, critical);
+    assert!(event.health_factor < HEALTH_WARNING_FACTOR);
+    assert_eq!(event.contact_hint, None);
+
+    // Staying within the band, then recovering, emits nothing
+    client.deposit_collateral(&user, &None, &100);
+    assert_eq!(health_events(&env).len(), 0);
+    client.deposit_collateral(&user, &None, &5_000);
+    assert_eq!(health_events(&env).len(), 0);
+}
+
+#[test]
+fn test_liquidation_imminent_with_custom_thresholds() {
+    let (env, id, client, admin, user, _native) = setup_env_with_native_asset();
+    let thresholds = HealthNotificationThresholds {
+        warning: 20_000,
+        critical: 15_000,
+        liquidation_imminent: 11_000,
+    };
+    client.set_health_notify_thresholds(&admin, &thresholds);
+    assert_authorized(&env, &admin, &id, "set_health_notify_thresholds");
+    assert_eq!(client.get_health_notify_thresholds(), thresholds);
+
+    client.deposit_collateral(&user, &None, &11_000);
+    client.borrow_asset(&user, &None, &10_000);
+
+    let events = health_events(&env);
+    assert_eq!(events.len(), 1);
+    assert_eq!(
+        events.get_unchecked(0).0,
+        Symbol::new(&env, "liquidation_imminent")
+    );
+}
+
+#[test]
+fn test_healthy_position_emits_no_event() {
+    let (env, _id, client, _admin, user, _native) = setup_env_with_native_asset();
+    client.set_contact_hint(&user, &BytesN::from_array(&env, &[1; 32]));
+    client.deposit_collateral(&user, &None, &20_000);
+    client.borrow_asset(&user, &None, &5_000);
+    assert_eq!(health_events(&env).len(), 0);
+}
+
+#[test]
+fn test_threshold_validation() {
+    let (env, _id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let valid = HealthNotificationThresholds {
+        warning: 12_000,
+        critical: 11_000,
+        liquidation_imminent: 10_000,
+    };
+    assert_eq!(
+        client.try_set_health_notify_thresholds(&Address::generate(&env), &valid),
+        Err(Ok(NotificationError::Unauthorized))
+    );
+
+    let mut unordered = valid.clone();
+    unordered.critical = 12_000;
+    assert_eq!(
+        client.try_set_health_notify_thresholds(&admin, &unordered),
+        Err(Ok(NotificationError::InvalidThresholds))
+    );
+
+    let mut liquidatable = valid.clone();
+    liquidatable.liquidation_imminent = 9_999;
+    assert_eq!(
+        client.try_set_health_notify_thresholds(&admin, &liquidatable),
+        Err(Ok(NotificationError::InvalidThresholds))
+    );
+
+    assert_eq!(
+        client.get_health_notify_thresholds().warning,
+        HEALTH_WARNING_FACTOR
+    );
+}
+
+#[test]
+fn test_set_and_clear_contact_hint() {
+    let (env, _id, client, _admin, user, _native) = setup_env_with_native_asset();
+    assert_eq!(client.get_contact_hint(&user), None);
+
+    client.set_contact_hint(&user, &BytesN::from_array(&env, &[1; 32]));
+    let replacement = BytesN::from_array(&env, &[2; 32]);
+    client.set_contact_hint(&user, &replacement);
+    assert_eq!(client.get_contact_hint(&user), Some(replacement));
+    assert_eq!(client.get_contact_hint(&Address::generate(&env)), None);
+
+    client.clear_contact_hint(&user);
+    assert_eq!(client.get_contact_hint(&user), None);
+}
@@ -0,0 +1,124 @@
+//! On-behalf-of deposit and repay tests.
+//!
+//! # Coverage
+//! - `deposit_collateral_for` pulls tokens from the payer and credits the user
+//! - `repay_debt_for` pulls tokens from the payer and repays the user's debt
+//! - Only the payer authorizes; the beneficiary's authorization is not needed
+//! - Payer balance and debt checks apply
+
+use crate::deposit::DepositError;
+use crate::repay::RepayError;
+use crate::tests::test_helpers::setup_env_with_native_asset;
+use soroban_sdk::{
+    contracttype,
+    testutils::{Address as _, Events},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env, Symbol, TryFromVal,
+};
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestOnBehalfEvent {
+    pub payer: Address,
+    pub user: Address,
+    pub operation: Symbol,
+    pub asset: Option<Address>,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+fn on_behalf_event(env: &Env) -> Option<TestOnBehalfEvent> {
+    env.events()
+        .all()
+        .iter()
+        .find_map(|(_contract, _topics, data)| TestOnBehalfEvent::try_from_val(env, &data).ok())
+}
+
+/// Only `address` authorized the last invocation
+fn only_authorized(env: &Env, address: &Address) -> bool {
+    let auths = env.auths();
+    !auths.is_empty() && auths.iter().all(|(signer, _)| signer == address)
+}
+
+fn fund(env: &Env, token: &Address, contract_id: &Address, payer: &Address, amount: i128) {
+    StellarAssetClient::new(env, token).mint(payer, &amount);
+    TokenClient::new(env, token).approve(
+        payer,
+        contract_id,
+        &amount,
+        &(env.ledger().sequence() + 100),
+    );
+}
+
+#[test]
+fn test_deposit_for_credits_user() {
+    let (env, id, client, admin, user, _native) = setup_env_with_native_asset();
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let payer = Address::generate(&env);
+    fund(&env, &token, &id, &payer, 5_000);
+
+    let balance = client.deposit_collateral_for(&payer, &user, &Some(token.clone()), &5_000);
+    assert_eq!(balance, 5_000);
+    assert!(only_authorized(&env, &payer));
+    assert_eq!(
+        on_behalf_event(&env),
+        Some(TestOnBehalfEvent {
+            payer: payer.clone(),
+            user: user.clone(),
+            operation: Symbol::new(&env, "deposit"),
+            asset: Some(token.clone()),
+            amount: 5_000,
+            timestamp: env.ledger().timestamp(),
+        })
+    );
+
+    assert_eq!(client.get_user_report(&user).position.collateral, 5_000);
+    assert!(client.try_get_user_report(&payer).is_err());
+    assert_eq!(TokenClient::new(&env, &token).balance(&payer), 0);
+    assert_eq!(TokenClient::new(&env, &token).balance(&id), 5_000);
+}
+
+#[test]
+fn test_repay_for_reduces_user_debt() {
+    let (env, id, client, _admin, user, native) = setup_env_with_native_asset();
+    client.deposit_collateral(&user, &None, &10_000);
+    client.borrow_asset(&user, &None, &4_000);
+    let payer = Address::generate(&env);
+    fund(&env, &native, &id, &payer, 1_500);
+
+    let (remaining, interest, principal) = client.repay_debt_for(&payer, &user, &None, &1_500);
+    assert_eq!((remaining, interest, principal), (2_500, 0, 1_500));
+    assert!(only_authorized(&env, &payer));
+    assert_eq!(
+        on_behalf_event(&env).map(|event| (event.operation, event.amount)),
+        Some((Symbol::new(&env, "repay"), 1_500))
+    );
+
+    assert_eq!(client.get_user_report(&user).position.debt, 2_500);
+    assert_eq!(TokenClient::new(&env, &native).balance(&payer), 0);
+}
+
+#[test]
+fn test_payer_checks() {
+    let (env, id, client, admin, user, native) = setup_env_with_native_asset();
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let payer = Address::generate(&env);
+    fund(&env, &token, &id, &payer, 1_000);
+
+    // The user's own balance does not count
+    StellarAssetClient::new(&env, &token).mint(&user, &5_000);
+    assert_eq!(
+        client.try_deposit_collateral_for(&payer, &user, &Some(token), &5_000),
+        Err(Ok(DepositError::InsufficientBalance))
+    );
+
+    fund(&env, &native, &id, &payer, 1_000);
+    assert_eq!(
+        client.try_repay_debt_for(&payer, &user, &None, &1_000),
+        Err(Ok(RepayError::NoDebt))
+    );
+}
@@ -0,0 +1,112 @@
+//! Operational role enforcement tests.
+//!
+//! # Coverage
+//! - Each role unlocks its own operations and no others
+//! - The super admin keeps access to every role-gated operation
+//! - Revoking a role removes access and role membership
+//! - Role-gated calls require the caller's authorization
+//! - Oracle feeders push prices for any asset
+
+use crate::admin::{LIQUIDATION_ADMIN, ORACLE_FEEDER, PAUSER, RISK_ADMIN, TREASURER};
+use crate::risk_management::RiskManagementError;
+use crate::tests::test_helpers::{assert_authorized, setup_env_with_native_asset};
+use crate::HelloContractClient;
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, Symbol};
+
+fn grant(env: &Env, client: &HelloContractClient, admin: &Address, role: &str) -> Address {
+    let account = Address::generate(env);
+    client.grant_role(admin, &Symbol::new(env, role), &account);
+    account
+}
+
+#[test]
+fn test_roles_unlock_their_operations_only() {
+    let (env, _id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let pauser = grant(&env, &client, &admin, PAUSER);
+    let risk_admin = grant(&env, &client, &admin, RISK_ADMIN);
+    let liquidation_admin = grant(&env, &client, &admin, LIQUIDATION_ADMIN);
+    let treasurer = grant(&env, &client, &admin, TREASURER);
+
+    client.set_emergency_pause(&pauser, &true);
+    assert!(client.is_emergency_paused());
+    client.set_emergency_pause(&admin, &false);
+    client.set_global_daily_borrow_cap(&risk_admin, &Some(1_000));
+    client.set_liquidation_hysteresis(&liquidation_admin, &50);
+    assert_eq!(client.get_liquidation_hysteresis(), 50);
+    // Authorization passes; the zero amount is what fails
+    assert_eq!(
+        client.try_claim_reserves(&treasurer, &None, &treasurer, &0),
+        Err(Ok(RiskManagementError::InvalidParameter))
+    );
+
+    assert_eq!(
+        client.try_set_emergency_pause(&risk_admin, &true),
+        Err(Ok(RiskManagementError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_global_daily_borrow_cap(&pauser, &None),
+        Err(Ok(RiskManagementError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_liquidation_hysteresis(&treasurer, &0),
+        Err(Ok(RiskManagementError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_claim_reserves(&liquidation_admin, &None, &treasurer, &0),
+        Err(Ok(RiskManagementError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_role_gated_calls_require_caller_auth() {
+    let (env, id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let risk_admin = grant(&env, &client, &admin, RISK_ADMIN);
+    let pauser = grant(&env, &client, &admin, PAUSER);
+
+    client.set_risk_params(&risk_admin, &Some(12_100), &None, &None, &None);
+    assert_authorized(&env, &risk_admin, &id, "set_risk_params");
+    client.set_emergency_pause(&pauser, &true);
+    assert_authorized(&env, &pauser, &id, "set_emergency_pause");
+    client.set_emergency_pause(&admin, &false);
+    assert_authorized(&env, &admin, &id, "set_emergency_pause");
+    client.grant_role(&admin, &Symbol::new(&env, TREASURER), &pauser);
+    assert_authorized(&env, &admin, &id, "grant_role");
+}
+
+#[test]
+fn test_revoked_role_loses_access() {
+    let (env, _id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let role = Symbol::new(&env, PAUSER);
+    let first = grant(&env, &client, &admin, PAUSER);
+    let second = grant(&env, &client, &admin, PAUSER);
+    // Granting twice does not duplicate the member
+    client.grant_role(&admin, &role, &first);
+    assert_eq!(
+        client.get_role_members(&role),
+        vec![&env, first.clone(), second.clone()]
+    );
+
+    client.revoke_role(&admin, &role, &first);
+    assert!(!client.has_role(&role, &first));
+    assert!(client.has_role(&role, &second));
+    assert_eq!(client.get_role_members(&role), vec![&env, second]);
+    assert_eq!(
+        client.try_set_emergency_pause(&first, &true),
+        Err(Ok(RiskManagementError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_oracle_feeder_pushes_prices() {
+    let (env, _id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let feeder = grant(&env, &client, &admin, ORACLE_FEEDER);
+    let asset = Address::generate(&env);
+
+    client.update_price_feed(&feeder, &asset, &150, &2, &feeder);
+    assert_eq!(client.get_price(&asset), 150);
+
+    let stranger = Address::generate(&env);
+    assert!(client
+        .try_update_price_feed(&stranger, &asset, &160, &2, &stranger)
+        .is_err());
+}
@@ -0,0 +1,153 @@
+//! Operation id tests.
+//!
+//! # Coverage
+//! - Deposits, borrows, repayments and withdrawals get increasing ids
+//! - The `*_with_id` entrypoints return the id to the caller
+//! - Each user's latest operation is kept as a receipt
+//! - The id is embedded in the operation's event
+//! - Failed operations do not consume an id
+
+use crate::deposit::DepositDataKey;
+use crate::operations::{OperationKind, OperationReceipt};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Events},
+    Address, Env, Map, Symbol, TryFromVal, Val,
+};
+use stellarlend_testutils::register_mock_token;
+
+fn setup(e: &Env) -> (Address, HelloContractClient<'_>) {
+    e.mock_all_auths();
+    let id = e.register(HelloContract, ());
+    let client = HelloContractClient::new(e, &id);
+    client.initialize(&Address::generate(e));
+    (id, client)
+}
+
+/// `operation_id` carried by any event of the last invocation
+fn emitted_operation_id(e: &Env) -> Option<u64> {
+    let key = Symbol::new(e, "operation_id");
+    e.events().all().iter().find_map(|(_, _, data)| {
+        let fields = Map::<Symbol, Val>::try_from_val(e, &data).ok()?;
+        u64::try_from_val(e, &fields.get(key.clone())?).ok()
+    })
+}
+
+#[test]
+fn test_operations_get_increasing_ids() {
+    let e = Env::default();
+    let (id, client) = setup(&e);
+    let user = Address::generate(&e);
+    let native = register_mock_token(&e);
+    e.as_contract(&id, || {
+        e.storage()
+            .persistent()
+            .set(&DepositDataKey::NativeAssetAddress, &native.address);
+    });
+    assert_eq!(client.get_operation_count(), 0);
+    assert_eq!(client.get_last_operation(&user), None);
+
+    client.deposit_collateral(&user, &None, &1_000);
+    assert_eq!(client.get_last_operation(&user).unwrap().operation_id, 1);
+
+    client.borrow_asset(&user, &None, &400);
+    assert_eq!(client.get_last_operation(&user).unwrap().operation_id, 2);
+
+    native.mint_and_approve(&user, &id, 400);
+    client.repay_debt(&user, &None, &400);
+    let receipt = client.get_last_operation(&user).unwrap();
+    assert_eq!(receipt.operation_id, 3);
+    assert_eq!(receipt.kind, OperationKind::Repay);
+
+    client.withdraw_collateral(&user, &None, &300);
+    assert_eq!(
+        client.get_last_operation(&user),
+        Some(OperationReceipt {
+            operation_id: 4,
+            kind: OperationKind::Withdraw,
+            user: user.clone(),
+            asset: None,
+            amount: 300,
+            timestamp: e.ledger().timestamp(),
+        })
+    );
+    assert_eq!(client.get_operation_count(), 4);
+}
+
+#[test]
+fn test_with_id_entrypoints_return_ids() {
+    let e = Env::default();
+    let (id, client) = setup(&e);
+    let user = Address::generate(&e);
+    let native = register_mock_token(&e);
+    e.as_contract(&id, || {
+        e.storage()
+            .persistent()
+            .set(&DepositDataKey::NativeAssetAddress, &native.address);
+    });
+
+    assert_eq!(
+        client.deposit_collateral_with_id(&user, &None, &1_000),
+        (1_000, 1)
+    );
+    assert_eq!(client.borrow_asset_with_id(&user, &None, &400), (400, 2));
+
+    native.mint_and_approve(&user, &id, 400);
+    let (remaining, _, _, operation_id) = client.repay_debt_with_id(&user, &None, &400);
+    assert_eq!((remaining, operation_id), (0, 3));
+
+    assert_eq!(
+        client.withdraw_collateral_with_id(&user, &None, &300),
+        (700, 4)
+    );
+    assert_eq!(emitted_operation_id(&e), Some(4));
+    assert_eq!(client.get_last_operation(&user).unwrap().operation_id, 4);
+}
+
+#[test]
+fn test_ids_are_shared_across_users() {
+    let e = Env::default();
+    let (_id, client) = setup(&e);
+    let alice = Address::generate(&e);
+    let bob = Address::generate(&e);
+
+    client.deposit_collateral(&alice, &None, &1_000);
+    client.deposit_collateral(&bob, &None, &1_000);
+    client.deposit_collateral(&alice, &None, &500);
+
+    let alice_receipt = client.get_last_operation(&alice).unwrap();
+    assert_eq!(alice_receipt.operation_id, 3);
+    assert_eq!(alice_receipt.kind, OperationKind::Deposit);
+    assert_eq!(alice_receipt.amount, 500);
+    assert_eq!(client.get_last_operation(&bob).unwrap().operation_id, 2);
+}
+
+#[test]
+fn test_event_carries_operation_id() {
+    let e = Env::default();
+    let (_id, client) = setup(&e);
+    let user = Address::generate(&e);
+
+    client.deposit_collateral(&user, &None, &1_000);
+    assert_eq!(emitted_operation_id(&e), Some(1));
+
+    client.borrow_asset(&user, &None, &100);
+    assert_eq!(emitted_operation_id(&e), Some(2));
+}
+
+#[test]
+fn test_failed_operation_does_not_consume_id() {
+    let e = Env::default();
+    let (_id, client) = setup(&e);
+    let user = Address::generate(&e);
+
+    client.deposit_collateral(&user, &None, &1_000);
+    assert!(client
+        .try_withdraw_collateral(&user, &None, &5_000)
+        .is_err());
+    assert!(client.try_deposit_collateral(&user, &None, &0).is_err());
+    assert_eq!(client.get_operation_count(), 1);
+
+    client.deposit_collateral(&user, &None, &100);
+    assert_eq!(client.get_last_operation(&user).unwrap().operation_id, 2);
+}
@@ -0,0 +1,184 @@
+//! Position operator tests.
+//!
+//! # Coverage
+//! - Operators run only the actions their permission bitmask allows
+//! - Only the operator authorizes; funds come from and go to the user
+//! - Invalid bitmasks and operators are rejected; zero revokes an operator
+//! - Failing actions surface as the matching operator error
+
+use crate::batch::ProtocolAction;
+use crate::operators::{
+    OperatorError, PERMISSION_ALL, PERMISSION_BORROW, PERMISSION_DEPOSIT, PERMISSION_REPAY,
+    PERMISSION_WITHDRAW,
+};
+use crate::tests::test_helpers::setup_env_with_native_asset;
+use soroban_sdk::{
+    contracttype,
+    testutils::{Address as _, Events},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env, Symbol, TryFromVal,
+};
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestOperatorActionEvent {
+    pub operator: Address,
+    pub user: Address,
+    pub operation: Symbol,
+    pub asset: Option<Address>,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+fn operator_action_event(env: &Env) -> Option<TestOperatorActionEvent> {
+    env.events()
+        .all()
+        .iter()
+        .find_map(|(_contract, _topics, data)| {
+            TestOperatorActionEvent::try_from_val(env, &data).ok()
+        })
+}
+
+/// Only `address` authorized the last invocation
+fn only_authorized(env: &Env, address: &Address) -> bool {
+    let auths = env.auths();
+    !auths.is_empty() && auths.iter().all(|(signer, _)| signer == address)
+}
+
+fn fund(env: &Env, token: &Address, contract_id: &Address, user: &Address, amount: i128) {
+    StellarAssetClient::new(env, token).mint(user, &amount);
+    TokenClient::new(env, token).approve(
+        user,
+        contract_id,
+        &amount,
+        &(env.ledger().sequence() + 100),
+    );
+}
+
+#[test]
+fn test_operator_manages_collateral_with_user_funds() {
+    let (env, id, client, admin, user, _native) = setup_env_with_native_asset();
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let operator = Address::generate(&env);
+    fund(&env, &token, &id, &user, 5_000);
+    client.set_position_operator(
+        &user,
+        &operator,
+        &(PERMISSION_DEPOSIT | PERMISSION_WITHDRAW),
+    );
+    assert_eq!(
+        client.get_position_operator(&user, &operator),
+        PERMISSION_DEPOSIT | PERMISSION_WITHDRAW
+    );
+
+    let deposit = ProtocolAction::Deposit(Some(token.clone()), 5_000);
+    assert_eq!(
+        client.execute_as_operator(&operator, &user, &deposit),
+        5_000
+    );
+    assert!(only_authorized(&env, &operator));
+    assert_eq!(
+        operator_action_event(&env),
+        Some(TestOperatorActionEvent {
+            operator: operator.clone(),
+            user: user.clone(),
+            operation: Symbol::new(&env, "deposit"),
+            asset: Some(token.clone()),
+            amount: 5_000,
+            timestamp: env.ledger().timestamp(),
+        })
+    );
+    assert_eq!(client.get_user_report(&user).position.collateral, 5_000);
+
+    // Withdrawals are paid to the user, not the operator
+    let withdraw = ProtocolAction::Withdraw(Some(token.clone()), 2_000);
+    assert_eq!(
+        client.execute_as_operator(&operator, &user, &withdraw),
+        3_000
+    );
+    assert_eq!(TokenClient::new(&env, &token).balance(&user), 2_000);
+    assert_eq!(TokenClient::new(&env, &token).balance(&operator), 0);
+}
+
+#[test]
+fn test_operator_limited_to_granted_permissions() {
+    let (env, id, client, _admin, user, native) = setup_env_with_native_asset();
+    client.deposit_collateral(&user, &None, &10_000);
+    let operator = Address::generate(&env);
+    client.set_position_operator(&user, &operator, &(PERMISSION_BORROW | PERMISSION_REPAY));
+
+    assert_eq!(
+        client.execute_as_operator(&operator, &user, &ProtocolAction::Borrow(None, 4_000)),
+        4_000
+    );
+    fund(&env, &native, &id, &user, 1_500);
+    assert_eq!(
+        client.execute_as_operator(&operator, &user, &ProtocolAction::Repay(None, 1_500)),
+        2_500
+    );
+    assert_eq!(TokenClient::new(&env, &native).balance(&user), 0);
+    assert_eq!(client.get_user_report(&user).position.debt, 2_500);
+
+    for action in [
+        ProtocolAction::Deposit(None, 1_000),
+        ProtocolAction::Withdraw(None, 1_000),
+    ] {
+        assert_eq!(
+            client.try_execute_as_operator(&operator, &user, &action),
+            Err(Ok(OperatorError::Unauthorized))
+        );
+    }
+
+    // Permissions are per user
+    let other = Address::generate(&env);
+    assert_eq!(
+        client.try_execute_as_operator(&operator, &other, &ProtocolAction::Borrow(None, 100)),
+        Err(Ok(OperatorError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_set_position_operator_validation_and_revoke() {
+    let (env, _id, client, _admin, user, _native) = setup_env_with_native_asset();
+    let operator = Address::generate(&env);
+
+    assert_eq!(
+        client.try_set_position_operator(&user, &operator, &(PERMISSION_ALL + 1)),
+        Err(Ok(OperatorError::InvalidPermissions))
+    );
+    assert_eq!(
+        client.try_set_position_operator(&user, &user, &PERMISSION_ALL),
+        Err(Ok(OperatorError::InvalidOperator))
+    );
+
+    client.set_position_operator(&user, &operator, &PERMISSION_ALL);
+    client.set_position_operator(&user, &operator, &0);
+    assert_eq!(client.get_position_operator(&user, &operator), 0);
+    assert_eq!(
+        client.try_execute_as_operator(&operator, &user, &ProtocolAction::Deposit(None, 100)),
+        Err(Ok(OperatorError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_failing_action_maps_to_operator_error() {
+    let (env, _id, client, _admin, user, _native) = setup_env_with_native_asset();
+    client.deposit_collateral(&user, &None, &1_000);
+    let operator = Address::generate(&env);
+    client.set_position_operator(&user, &operator, &PERMISSION_ALL);
+
+    assert_eq!(
+        client.try_execute_as_operator(&operator, &user, &ProtocolAction::Withdraw(None, 1_001)),
+        Err(Ok(OperatorError::WithdrawFailed))
+    );
+    assert_eq!(
+        client.try_execute_as_operator(&operator, &user, &ProtocolAction::Repay(None, 100)),
+        Err(Ok(OperatorError::RepayFailed))
+    );
+    assert_eq!(
+        client.try_execute_as_operator(&operator, &user, &ProtocolAction::Borrow(None, 0)),
+        Err(Ok(OperatorError::BorrowFailed))
+    );
+}
@@ -0,0 +1,201 @@
+//! Oracle updater bond and rate-limit tests.
+//!
+//! # Coverage
+//! - Bonds cannot be posted before a policy is configured
+//! - Non-admin updaters need the minimum bond to push prices
+//! - Repeated pushes for an asset are rate limited per updater
+//! - Bonds stay locked for the dispute window after the last push
+//! - Governance can slash a bond to a recipient; others cannot
+//! - Policy changes and slashing need the admin's authorization
+//! - The bond token cannot change while bonds are outstanding
+
+use crate::oracle::{update_price_feed, OracleError, UpdaterPolicy};
+use crate::tests::test_helpers::assert_authorized;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env,
+};
+use stellarlend_testutils::{advance_time, register_mock_token};
+
+const PRICE: i128 = 100_000_000;
+
+struct Setup {
+    env: Env,
+    contract_id: Address,
+    client: HelloContractClient<'static>,
+    admin: Address,
+    oracle: Address,
+    asset: Address,
+    bond_token: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let asset = Address::generate(&env);
+    let bond_token = register_mock_token(&env);
+    bond_token.mint(&oracle, 10_000);
+
+    client.initialize(&admin);
+    client.set_primary_oracle(&admin, &asset, &oracle);
+    client.update_price_feed(&admin, &asset, &PRICE, &8, &oracle);
+
+    Setup {
+        env,
+        contract_id,
+        client,
+        admin,
+        oracle,
+        asset,
+        bond_token: bond_token.address,
+    }
+}
+
+fn policy(bond_token: &Address) -> UpdaterPolicy {
+    UpdaterPolicy {
+        bond_token: bond_token.clone(),
+        min_bond: 1_000,
+        min_update_interval: 60,
+        bond_lock_seconds: 3_600,
+    }
+}
+
+fn push(s: &Setup) -> Result<i128, OracleError> {
+    s.env.as_contract(&s.contract_id, || {
+        update_price_feed(
+            &s.env,
+            s.oracle.clone(),
+            s.asset.clone(),
+            PRICE,
+            8,
+            s.oracle.clone(),
+        )
+    })
+}
+
+#[test]
+fn test_bond_requires_policy() {
+    let s = setup();
+    assert_eq!(
+        s.client.try_post_oracle_bond(&s.oracle, &1_000),
+        Err(Ok(OracleError::BondNotConfigured))
+    );
+}
+
+#[test]
+fn test_unbonded_updater_rejected() {
+    let s = setup();
+    s.client
+        .set_updater_policy(&s.admin, &policy(&s.bond_token));
+
+    assert_eq!(push(&s), Err(OracleError::InsufficientBond));
+
+    assert_eq!(s.client.post_oracle_bond(&s.oracle, &1_000), 1_000);
+    assert_eq!(push(&s), Ok(PRICE));
+    assert_eq!(
+        token::Client::new(&s.env, &s.bond_token).balance(&s.contract_id),
+        1_000
+    );
+}
+
+#[test]
+fn test_pushes_are_rate_limited() {
+    let s = setup();
+    s.client
+        .set_updater_policy(&s.admin, &policy(&s.bond_token));
+    s.client.post_oracle_bond(&s.oracle, &1_000);
+
+    push(&s).unwrap();
+    advance_time(&s.env, 59);
+    assert_eq!(push(&s), Err(OracleError::UpdateRateLimited));
+
+    advance_time(&s.env, 1);
+    assert_eq!(push(&s), Ok(PRICE));
+
+    // Admin pushes are exempt
+    s.client
+        .update_price_feed(&s.admin, &s.asset, &PRICE, &8, &s.oracle);
+}
+
+#[test]
+fn test_bond_locked_after_push() {
+    let s = setup();
+    s.client
+        .set_updater_policy(&s.admin, &policy(&s.bond_token));
+    s.client.post_oracle_bond(&s.oracle, &1_500);
+    push(&s).unwrap();
+
+    assert_eq!(
+        s.client.try_withdraw_oracle_bond(&s.oracle, &500),
+        Err(Ok(OracleError::BondLocked))
+    );
+
+    advance_time(&s.env, 3_600);
+    assert_eq!(s.client.withdraw_oracle_bond(&s.oracle, &500), 1_000);
+    assert_eq!(
+        token::Client::new(&s.env, &s.bond_token).balance(&s.oracle),
+        9_000
+    );
+    assert_eq!(
+        s.client.try_withdraw_oracle_bond(&s.oracle, &1_001),
+        Err(Ok(OracleError::InsufficientBond))
+    );
+}
+
+#[test]
+fn test_governance_slashes_bond() {
+    let s = setup();
+    let treasury = Address::generate(&s.env);
+    s.client
+        .set_updater_policy(&s.admin, &policy(&s.bond_token));
+    assert_authorized(&s.env, &s.admin, &s.contract_id, "set_updater_policy");
+    s.client.post_oracle_bond(&s.oracle, &1_000);
+
+    assert_eq!(
+        s.client
+            .try_slash_oracle_bond(&s.oracle, &s.oracle, &1_000, &s.oracle),
+        Err(Ok(OracleError::Unauthorized))
+    );
+
+    assert_eq!(
+        s.client
+            .slash_oracle_bond(&s.admin, &s.oracle, &600, &treasury),
+        600
+    );
+    assert_authorized(&s.env, &s.admin, &s.contract_id, "slash_oracle_bond");
+    assert_eq!(s.client.get_oracle_bond(&s.oracle), 400);
+    assert_eq!(
+        token::Client::new(&s.env, &s.bond_token).balance(&treasury),
+        600
+    );
+    assert_eq!(push(&s), Err(OracleError::InsufficientBond));
+
+    // Slashing is capped at the remaining bond
+    assert_eq!(
+        s.client
+            .slash_oracle_bond(&s.admin, &s.oracle, &5_000, &treasury),
+        400
+    );
+}
+
+#[test]
+fn test_bond_token_fixed_while_bonded() {
+    let s = setup();
+    s.client
+        .set_updater_policy(&s.admin, &policy(&s.bond_token));
+    s.client.post_oracle_bond(&s.oracle, &1_000);
+
+    let other_token = Address::generate(&s.env);
+    assert_eq!(
+        s.client
+            .try_set_updater_policy(&s.admin, &policy(&other_token)),
+        Err(Ok(OracleError::InvalidOracle))
+    );
+}
@@ -0,0 +1,204 @@
+//! Oracle failover policy tests.
+//!
+//! # Coverage
+//! - Assets walk primary → fallback → TWAP → frozen as sources go stale
+//! - Each change of source is recorded and emits a transition event
+//! - A recovered primary source takes over again
+//! - Disabled steps are skipped
+//! - Only the admin sets policies, with their authorization; fallback steps
+//!   need a fallback oracle
+
+use crate::oracle::{FailoverPolicy, FailoverState, OracleError, PriceSource};
+use crate::tests::test_helpers::assert_authorized;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    contracttype,
+    testutils::{Address as _, Events},
+    Address, Env, TryFromVal, Vec,
+};
+use stellarlend_testutils::{advance_time, set_time};
+
+const PRIMARY_PRICE: i128 = 100_000_000;
+const FALLBACK_PRICE: i128 = 101_000_000;
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestOracleFailoverEvent {
+    pub asset: Address,
+    pub from: PriceSource,
+    pub to: PriceSource,
+    pub timestamp: u64,
+}
+
+fn failover_events(env: &Env) -> Vec<TestOracleFailoverEvent> {
+    let mut events = Vec::new(env);
+    for (_contract, _topics, data) in env.events().all().iter() {
+        if let Ok(event) = TestOracleFailoverEvent::try_from_val(env, &data) {
+            events.push_back(event);
+        }
+    }
+    events
+}
+
+struct Setup<'a> {
+    client: HelloContractClient<'a>,
+    admin: Address,
+    asset: Address,
+    fallback: Address,
+}
+
+/// Push a primary price at t = 10_000 and register a fallback oracle
+fn setup(e: &Env) -> Setup<'_> {
+    e.mock_all_auths();
+    set_time(e, 10_000);
+    let id = e.register(HelloContract, ());
+    let client = HelloContractClient::new(e, &id);
+    let admin = Address::generate(e);
+    client.initialize(&admin);
+
+    let asset = Address::generate(e);
+    client.update_price_feed(&admin, &asset, &PRIMARY_PRICE, &8, &Address::generate(e));
+    let fallback = Address::generate(e);
+    client.set_fallback_oracle(&admin, &asset, &fallback);
+    Setup {
+        client,
+        admin,
+        asset,
+        fallback,
+    }
+}
+
+fn source(s: &Setup) -> PriceSource {
+    s.client.get_failover_state(&s.asset).unwrap().source
+}
+
+#[test]
+fn test_walks_failover_chain() {
+    let e = Env::default();
+    let s = setup(&e);
+    s.client.set_failover_policy(
+        &s.admin,
+        &s.asset,
+        &Some(FailoverPolicy {
+            use_fallback: true,
+            twap_window_secs: 86_400,
+        }),
+    );
+    assert_eq!(s.client.get_price(&s.asset), PRIMARY_PRICE);
+    assert_eq!(source(&s), PriceSource::Primary);
+    assert_eq!(failover_events(&e).len(), 0);
+
+    // Primary goes stale (1 hour) while the fallback is fresh
+    advance_time(&e, 3_000);
+    s.client
+        .update_price_feed(&s.fallback, &s.asset, &FALLBACK_PRICE, &8, &s.fallback);
+    advance_time(&e, 601);
+    assert_eq!(s.client.get_price(&s.asset), FALLBACK_PRICE);
+    assert_eq!(
+        failover_events(&e),
+        Vec::from_array(
+            &e,
+            [TestOracleFailoverEvent {
+                asset: s.asset.clone(),
+                from: PriceSource::Primary,
+                to: PriceSource::Fallback,
+                timestamp: 13_601,
+            }]
+        )
+    );
+    assert_eq!(
+        s.client.get_failover_state(&s.asset),
+        Some(FailoverState {
+            source: PriceSource::Fallback,
+            since: 13_601,
+        })
+    );
+
+    // Fallback stale too: the day-long TWAP of the primary history prices it
+    advance_time(&e, 3_000);
+    assert_eq!(s.client.get_price(&s.asset), PRIMARY_PRICE);
+    assert_eq!(source(&s), PriceSource::Twap);
+
+    // Nothing left inside the TWAP window: the asset freezes
+    advance_time(&e, 86_400);
+    assert!(s.client.try_get_price(&s.asset).is_err());
+    assert_eq!(
+        s.client.refresh_failover_state(&s.asset).source,
+        PriceSource::Frozen
+    );
+
+    // A fresh primary price recovers the asset
+    s.client.update_price_feed(
+        &s.admin,
+        &s.asset,
+        &PRIMARY_PRICE,
+        &8,
+        &Address::generate(&e),
+    );
+    assert_eq!(s.client.get_price(&s.asset), PRIMARY_PRICE);
+    let last = failover_events(&e).last().unwrap();
+    assert_eq!(last.from, PriceSource::Frozen);
+    assert_eq!(last.to, PriceSource::Primary);
+    assert_eq!(source(&s), PriceSource::Primary);
+}
+
+#[test]
+fn test_disabled_steps_are_skipped() {
+    let e = Env::default();
+    let s = setup(&e);
+    s.client.set_failover_policy(
+        &s.admin,
+        &s.asset,
+        &Some(FailoverPolicy {
+            use_fallback: false,
+            twap_window_secs: 0,
+        }),
+    );
+    s.client
+        .update_price_feed(&s.fallback, &s.asset, &FALLBACK_PRICE, &8, &s.fallback);
+
+    // The fresh fallback is ignored once the primary goes stale
+    advance_time(&e, 3_601);
+    assert!(s.client.try_get_price(&s.asset).is_err());
+    assert_eq!(
+        s.client.refresh_failover_state(&s.asset).source,
+        PriceSource::Frozen
+    );
+}
+
+#[test]
+fn test_policy_configuration() {
+    let e = Env::default();
+    let s = setup(&e);
+    let policy = FailoverPolicy {
+        use_fallback: true,
+        twap_window_secs: 0,
+    };
+
+    let outsider = Address::generate(&e);
+    assert_eq!(
+        s.client
+            .try_set_failover_policy(&outsider, &s.asset, &Some(policy.clone())),
+        Err(Ok(OracleError::Unauthorized))
+    );
+    let unconfigured = Address::generate(&e);
+    assert_eq!(
+        s.client
+            .try_set_failover_policy(&s.admin, &unconfigured, &Some(policy.clone())),
+        Err(Ok(OracleError::FallbackNotConfigured))
+    );
+    assert_eq!(
+        s.client.try_refresh_failover_state(&s.asset),
+        Err(Ok(OracleError::AssetNotSupported))
+    );
+
+    s.client
+        .set_failover_policy(&s.admin, &s.asset, &Some(policy.clone()));
+    assert_authorized(&e, &s.admin, &s.client.address, "set_failover_policy");
+    assert_eq!(s.client.get_failover_policy(&s.asset), Some(policy));
+    assert_eq!(source(&s), PriceSource::Primary);
+
+    s.client.set_failover_policy(&s.admin, &s.asset, &None);
+    assert_eq!(s.client.get_failover_policy(&s.asset), None);
+    assert_eq!(s.client.get_failover_state(&s.asset), None);
+}
@@ -0,0 +1,197 @@
+//! Multi-feeder oracle aggregation tests.
+//!
+//! # Coverage
+//! - `get_price` returns the median of fresh feeder submissions (odd and even counts)
+//! - Stale submissions are ignored; no fresh submission means no price
+//! - Feeders spreading beyond `max_deviation_bps` dispute the price and block borrows
+//! - Only the admin resolves disputes; disputed medians stay out of the TWAP history
+//! - Feeder registration limits and submission authorization
+//! - Registering feeders and resolving disputes need the admin's authorization
+
+use crate::borrow::BorrowError;
+use crate::oracle::{OracleError, PriceDispute, MAX_PRICE_FEEDERS};
+use crate::tests::test_helpers::assert_authorized;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+use stellarlend_testutils::advance_time;
+
+const PRICE: i128 = 100_000_000;
+
+struct Setup<'a> {
+    client: HelloContractClient<'a>,
+    admin: Address,
+    asset: Address,
+}
+
+fn setup(e: &Env) -> Setup<'_> {
+    e.mock_all_auths();
+    e.ledger().with_mut(|li| li.timestamp = 1_000);
+    let id = e.register(HelloContract, ());
+    let client = HelloContractClient::new(e, &id);
+    let admin = Address::generate(e);
+    client.initialize(&admin);
+    Setup {
+        client,
+        admin,
+        asset: Address::generate(e),
+    }
+}
+
+/// Register `n` feeders for the setup asset
+fn add_feeders(e: &Env, s: &Setup, n: u32) -> soroban_sdk::Vec<Address> {
+    let mut feeders = soroban_sdk::Vec::new(e);
+    for _ in 0..n {
+        let feeder = Address::generate(e);
+        s.client.add_price_feeder(&s.admin, &s.asset, &feeder);
+        assert_authorized(e, &s.admin, &s.client.address, "add_price_feeder");
+        feeders.push_back(feeder);
+    }
+    feeders
+}
+
+#[test]
+fn test_price_is_median_of_feeders() {
+    let e = Env::default();
+    let s = setup(&e);
+    let feeders = add_feeders(&e, &s, 4);
+
+    s.client
+        .submit_feeder_price(&feeders.get(0).unwrap(), &s.asset, &PRICE);
+    s.client
+        .submit_feeder_price(&feeders.get(1).unwrap(), &s.asset, &(PRICE * 102 / 100));
+    let median =
+        s.client
+            .submit_feeder_price(&feeders.get(2).unwrap(), &s.asset, &(PRICE * 101 / 100));
+    assert_eq!(median, PRICE * 101 / 100);
+    assert_eq!(s.client.get_price(&s.asset), PRICE * 101 / 100);
+
+    // Even count averages the two middle prices
+    s.client
+        .submit_feeder_price(&feeders.get(3).unwrap(), &s.asset, &(PRICE * 103 / 100));
+    assert_eq!(s.client.get_price(&s.asset), PRICE * 1015 / 1000);
+    assert_eq!(s.client.get_price_dispute(&s.asset), None);
+}
+
+#[test]
+fn test_stale_submissions_are_ignored() {
+    let e = Env::default();
+    let s = setup(&e);
+    let feeders = add_feeders(&e, &s, 3);
+
+    s.client
+        .submit_feeder_price(&feeders.get(0).unwrap(), &s.asset, &(PRICE * 104 / 100));
+    advance_time(&e, 3_000);
+    s.client
+        .submit_feeder_price(&feeders.get(1).unwrap(), &s.asset, &PRICE);
+    s.client
+        .submit_feeder_price(&feeders.get(2).unwrap(), &s.asset, &(PRICE * 102 / 100));
+
+    // First submission is now over an hour old
+    advance_time(&e, 700);
+    assert_eq!(s.client.get_price(&s.asset), PRICE * 101 / 100);
+
+    advance_time(&e, 3_600);
+    assert!(s.client.try_get_price(&s.asset).is_err());
+}
+
+#[test]
+fn test_feeder_disagreement_disputes_price_and_blocks_borrows() {
+    let e = Env::default();
+    let s = setup(&e);
+    let feeders = add_feeders(&e, &s, 2);
+    let user = Address::generate(&e);
+
+    s.client
+        .submit_feeder_price(&feeders.get(0).unwrap(), &s.asset, &PRICE);
+    assert_eq!(s.client.get_price_history(&s.asset).len(), 1);
+    advance_time(&e, 60);
+    // 6% spread against the default 5% limit
+    s.client
+        .submit_feeder_price(&feeders.get(1).unwrap(), &s.asset, &(PRICE * 106 / 100));
+
+    assert_eq!(
+        s.client.get_price_dispute(&s.asset),
+        Some(PriceDispute {
+            min_price: PRICE,
+            max_price: PRICE * 106 / 100,
+            deviation_bps: 600,
+            flagged_at: 1_060,
+        })
+    );
+    assert_eq!(s.client.get_price_history(&s.asset).len(), 1);
+    assert_eq!(
+        s.client
+            .try_borrow_asset(&user, &Some(s.asset.clone()), &100),
+        Err(Ok(BorrowError::PriceDisputed))
+    );
+
+    assert_eq!(
+        s.client
+            .try_resolve_price_dispute(&Address::generate(&e), &s.asset),
+        Err(Ok(OracleError::Unauthorized))
+    );
+
+    // Admin drops the deviating feeder and resolves
+    s.client
+        .remove_price_feeder(&s.admin, &s.asset, &feeders.get(1).unwrap());
+    s.client.resolve_price_dispute(&s.admin, &s.asset);
+    assert_authorized(&e, &s.admin, &s.client.address, "resolve_price_dispute");
+    assert_eq!(s.client.get_price_dispute(&s.asset), None);
+    assert_eq!(s.client.get_price(&s.asset), PRICE);
+    assert_eq!(
+        s.client
+            .try_borrow_asset(&user, &Some(s.asset.clone()), &100),
+        Err(Ok(BorrowError::InsufficientCollateral))
+    );
+    assert_eq!(
+        s.client.try_resolve_price_dispute(&s.admin, &s.asset),
+        Err(Ok(OracleError::NotDisputed))
+    );
+}
+
+#[test]
+fn test_feeder_registration_and_authorization() {
+    let e = Env::default();
+    let s = setup(&e);
+    let feeders = add_feeders(&e, &s, MAX_PRICE_FEEDERS);
+
+    assert_eq!(
+        s.client
+            .try_add_price_feeder(&s.admin, &s.asset, &Address::generate(&e)),
+        Err(Ok(OracleError::TooManyFeeders))
+    );
+    let first = feeders.get(0).unwrap();
+    s.client.remove_price_feeder(&s.admin, &s.asset, &first);
+    assert_eq!(
+        s.client.get_price_feeders(&s.asset).len(),
+        MAX_PRICE_FEEDERS - 1
+    );
+    assert_eq!(
+        s.client.try_remove_price_feeder(&s.admin, &s.asset, &first),
+        Err(Ok(OracleError::InvalidOracle))
+    );
+
+    let second = feeders.get(1).unwrap();
+    assert_eq!(
+        s.client.try_add_price_feeder(&s.admin, &s.asset, &second),
+        Err(Ok(OracleError::InvalidOracle))
+    );
+    assert_eq!(
+        s.client
+            .try_add_price_feeder(&Address::generate(&e), &s.asset, &first),
+        Err(Ok(OracleError::Unauthorized))
+    );
+
+    // Removed and unregistered feeders cannot submit
+    assert_eq!(
+        s.client.try_submit_feeder_price(&first, &s.asset, &PRICE),
+        Err(Ok(OracleError::Unauthorized))
+    );
+    assert_eq!(
+        s.client.try_submit_feeder_price(&second, &s.asset, &0),
+        Err(Ok(OracleError::InvalidPrice))
+    );
+}
@@ -0,0 +1,166 @@
+//! Parameter bundle diff-and-apply tests.
+//!
+//! # Coverage
+//! - Re-applying an exported bundle changes nothing
+//! - Only the differing values are written and reported, across risk,
+//!   interest rate, oracle and cap sections
+//! - A floor raised above the old rate ceiling applies in the right order
+//! - A rejected value rolls back the whole bundle
+//! - Admin-only, authorized once by the admin; negative caps and
+//!   unregistered assets are rejected
+
+use crate::cross_asset::{initialize, initialize_asset, AssetConfig};
+use crate::param_bundle::{ParameterBundle, ParameterBundleError, ParameterChange};
+use crate::tests::test_helpers::{assert_authorized, setup_env_with_native_asset};
+use crate::HelloContractClient;
+use soroban_sdk::{testutils::Address as _, Address, Env, Symbol, Vec};
+
+/// Register native XLM with the cross-asset module and export its bundle
+fn setup() -> (Env, HelloContractClient<'static>, Address, ParameterBundle) {
+    let (env, id, client, admin, _user, _native) = setup_env_with_native_asset();
+    env.as_contract(&id, || {
+        initialize(&env, admin.clone()).unwrap();
+        let config = AssetConfig {
+            asset: None,
+            collateral_factor: 7500,
+            liquidation_threshold: 8000,
+            reserve_factor: 1000,
+            max_supply: 10_000,
+            max_borrow: 3_000,
+            can_collateralize: true,
+            can_borrow: true,
+            price: 10_000_000,
+            price_updated_at: env.ledger().timestamp(),
+            is_isolated: false,
+            isolated_debt_ceiling: 0,
+        };
+        initialize_asset(&env, None, config).unwrap();
+    });
+    let bundle = client.get_parameter_bundle(&Vec::from_array(&env, [None]));
+    (env, client, admin, bundle)
+}
+
+fn change(env: &Env, parameter: &str, old: Option<i128>, new: Option<i128>) -> ParameterChange {
+    ParameterChange {
+        parameter: Symbol::new(env, parameter),
+        asset: None,
+        old_value: old,
+        new_value: new,
+    }
+}
+
+#[test]
+fn test_unchanged_bundle_is_noop() {
+    let (_env, client, admin, bundle) = setup();
+    assert_eq!(bundle.caps.assets.get(0).unwrap().max_supply, 10_000);
+    assert!(client.apply_parameter_bundle(&admin, &bundle).is_empty());
+}
+
+#[test]
+fn test_applies_only_differences() {
+    let (env, client, admin, current) = setup();
+    let mut bundle = current.clone();
+    bundle.risk.min_collateral_ratio = 11_500;
+    bundle.interest.spread_bps = 300;
+    bundle.oracle.max_staleness_seconds = 1_800;
+    bundle.caps.global_daily_borrow_cap = Some(1_000_000);
+    let mut asset = bundle.caps.assets.get(0).unwrap();
+    asset.max_supply = 20_000;
+    asset.daily_borrow_cap = Some(5_000);
+    bundle.caps.assets.set(0, asset);
+
+    let changes = client.apply_parameter_bundle(&admin, &bundle);
+    assert_authorized(&env, &admin, &client.address, "apply_parameter_bundle");
+    assert_eq!(
+        changes,
+        Vec::from_array(
+            &env,
+            [
+                change(&env, "min_collateral_ratio", Some(11_000), Some(11_500)),
+                change(&env, "spread_bps", Some(200), Some(300)),
+                change(
+                    &env,
+                    "max_staleness_seconds",
+                    Some(current.oracle.max_staleness_seconds as i128),
+                    Some(1_800)
+                ),
+                change(&env, "global_daily_borrow_cap", None, Some(1_000_000)),
+                change(&env, "max_supply", Some(10_000), Some(20_000)),
+                change(&env, "daily_borrow_cap", None, Some(5_000)),
+            ]
+        )
+    );
+    assert_eq!(
+        client.get_parameter_bundle(&Vec::from_array(&env, [None])),
+        bundle
+    );
+    assert_eq!(client.get_global_daily_borrow_status().cap, Some(1_000_000));
+}
+
+#[test]
+fn test_floor_above_old_ceiling() {
+    let (env, client, admin, mut bundle) = setup();
+    bundle.interest.rate_ceiling_bps = 3_000;
+    client.apply_parameter_bundle(&admin, &bundle);
+
+    bundle.interest.rate_floor_bps = 4_000;
+    bundle.interest.rate_ceiling_bps = 5_000;
+    assert_eq!(client.apply_parameter_bundle(&admin, &bundle).len(), 2);
+    let applied = client.get_parameter_bundle(&Vec::new(&env));
+    assert_eq!(applied.interest.rate_floor_bps, 4_000);
+    assert_eq!(applied.interest.rate_ceiling_bps, 5_000);
+}
+
+#[test]
+fn test_rejected_change_applies_nothing() {
+    let (env, client, admin, current) = setup();
+    let mut bundle = current.clone();
+    bundle.interest.spread_bps = 300;
+    bundle.caps.global_daily_borrow_cap = Some(1_000_000);
+    // More than the 10% allowed per update
+    bundle.risk.min_collateral_ratio = 15_000;
+
+    assert_eq!(
+        client.try_apply_parameter_bundle(&admin, &bundle),
+        Err(Ok(ParameterBundleError::ParameterChangeTooLarge))
+    );
+    assert_eq!(
+        client.get_parameter_bundle(&Vec::from_array(&env, [None])),
+        current
+    );
+
+    bundle.risk.min_collateral_ratio = current.risk.min_collateral_ratio;
+    bundle.interest.kink_utilization_bps = 0;
+    assert_eq!(
+        client.try_apply_parameter_bundle(&admin, &bundle),
+        Err(Ok(ParameterBundleError::InvalidInterestRate))
+    );
+    assert_eq!(client.get_global_daily_borrow_status().cap, None);
+}
+
+#[test]
+fn test_bundle_validation() {
+    let (env, client, admin, current) = setup();
+    let outsider = Address::generate(&env);
+    assert_eq!(
+        client.try_apply_parameter_bundle(&outsider, &current),
+        Err(Ok(ParameterBundleError::Unauthorized))
+    );
+
+    let mut bundle = current.clone();
+    let mut asset = bundle.caps.assets.get(0).unwrap();
+    asset.max_borrow = -1;
+    bundle.caps.assets.set(0, asset.clone());
+    assert_eq!(
+        client.try_apply_parameter_bundle(&admin, &bundle),
+        Err(Ok(ParameterBundleError::InvalidCap))
+    );
+
+    asset.max_borrow = 0;
+    asset.asset = Some(Address::generate(&env));
+    bundle.caps.assets.set(0, asset);
+    assert_eq!(
+        client.try_apply_parameter_bundle(&admin, &bundle),
+        Err(Ok(ParameterBundleError::AssetNotConfigured))
+    );
+}
@@ -0,0 +1,191 @@
+//! Idle position archive tests.
+//!
+//! # Coverage
+//! - Debt-free positions idle for `ARCHIVE_IDLE_SECONDS` move to the archive
+//!   and are restored intact
+//! - Positions with debt or recent activity are skipped
+//! - Restoring merges with collateral deposited after the archive
+//! - Sweeps are bounded and restoring needs an archive
+//! - Isolated position accounts are archived like any position and restored
+//!   by their owner
+
+use crate::archive::{ArchiveError, ArchivedPosition, ARCHIVE_IDLE_SECONDS, MAX_ARCHIVE_BATCH};
+use crate::batch::ProtocolAction;
+use crate::deposit::{DepositDataKey, Position};
+use crate::positions::PositionError;
+use crate::tests::test_helpers::{assert_authorized, setup_env_with_native_asset};
+use crate::HelloContractClient;
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Env, Vec,
+};
+use stellarlend_testutils::advance_time;
+
+/// Deposit `amount` of a fresh token for `user`; returns the asset
+fn deposit(
+    env: &Env,
+    id: &Address,
+    client: &HelloContractClient,
+    admin: &Address,
+    user: &Address,
+    amount: i128,
+) -> Option<Address> {
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    StellarAssetClient::new(env, &token).mint(user, &amount);
+    TokenClient::new(env, &token).approve(user, id, &amount, &(env.ledger().sequence() + 100));
+    let asset = Some(token);
+    client.deposit_collateral(user, &asset, &amount);
+    asset
+}
+
+#[test]
+fn test_idle_position_archived_and_restored() {
+    let (env, id, client, admin, user, _native) = setup_env_with_native_asset();
+    let keeper = Address::generate(&env);
+    let asset = deposit(&env, &id, &client, &admin, &user, 10_000);
+    let deposited_at = env.ledger().timestamp();
+
+    advance_time(&env, ARCHIVE_IDLE_SECONDS - 1);
+    assert_eq!(
+        client.archive_idle_positions(&keeper, &vec![&env, user.clone()]),
+        0
+    );
+    advance_time(&env, 1);
+    assert_eq!(
+        client.archive_idle_positions(&keeper, &vec![&env, user.clone()]),
+        1
+    );
+
+    assert_eq!(
+        client.get_archived_position(&user),
+        Some(ArchivedPosition {
+            collateral: 10_000,
+            collateral_balance: 10_000,
+            supply_shares: 10_000,
+            last_active: deposited_at,
+            archived_at: env.ledger().timestamp(),
+        })
+    );
+    assert_eq!(client.get_supply_shares(&user), 0);
+    env.as_contract(&id, || {
+        assert!(!env
+            .storage()
+            .persistent()
+            .has(&DepositDataKey::Position(user.clone())));
+    });
+
+    let position = client.restore_position(&user);
+    assert_eq!(position.collateral, 10_000);
+    assert_eq!(client.get_archived_position(&user), None);
+    assert_eq!(client.get_supply_shares(&user), 10_000);
+    client.withdraw_collateral(&user, &asset, &4_000);
+}
+
+#[test]
+fn test_debt_and_active_positions_skipped() {
+    let (env, id, client, admin, user, _native) = setup_env_with_native_asset();
+    let keeper = Address::generate(&env);
+    let borrower = Address::generate(&env);
+    let never_deposited = Address::generate(&env);
+    deposit(&env, &id, &client, &admin, &user, 1_000);
+    env.as_contract(&id, || {
+        env.storage().persistent().set(
+            &DepositDataKey::Position(borrower.clone()),
+            &Position {
+                collateral: 5_000,
+                debt: 1_000,
+                borrow_interest: 0,
+                last_accrual_time: env.ledger().timestamp(),
+            },
+        );
+    });
+
+    advance_time(&env, ARCHIVE_IDLE_SECONDS);
+    deposit(&env, &id, &client, &admin, &user, 1_000);
+    assert_eq!(
+        client.archive_idle_positions(
+            &keeper,
+            &vec![&env, user.clone(), borrower.clone(), never_deposited]
+        ),
+        0
+    );
+    assert_eq!(client.get_archived_position(&borrower), None);
+}
+
+#[test]
+fn test_restore_merges_new_deposits() {
+    let (env, id, client, admin, user, _native) = setup_env_with_native_asset();
+    let keeper = Address::generate(&env);
+    deposit(&env, &id, &client, &admin, &user, 6_000);
+    advance_time(&env, ARCHIVE_IDLE_SECONDS);
+    client.archive_idle_positions(&keeper, &vec![&env, user.clone()]);
+
+    // Acting before restoring opens a fresh position alongside the archive
+    deposit(&env, &id, &client, &admin, &user, 2_000);
+    assert_eq!(client.get_supply_shares(&user), 2_000);
+
+    assert_eq!(client.restore_position(&user).collateral, 8_000);
+    assert_eq!(client.get_supply_shares(&user), 8_000);
+}
+
+#[test]
+fn test_archive_limits() {
+    let (env, _id, client, _admin, user, _native) = setup_env_with_native_asset();
+    let keeper = Address::generate(&env);
+    let mut users = Vec::new(&env);
+    for _ in 0..=MAX_ARCHIVE_BATCH {
+        users.push_back(Address::generate(&env));
+    }
+    assert_eq!(
+        client.try_archive_idle_positions(&keeper, &users),
+        Err(Ok(ArchiveError::BatchTooLarge))
+    );
+    assert_eq!(
+        client.try_restore_position(&user),
+        Err(Ok(ArchiveError::NotArchived))
+    );
+}
+
+#[test]
+fn test_position_account_restored_by_owner() {
+    let (env, id, client, _admin, owner, _native) = setup_env_with_native_asset();
+    let keeper = Address::generate(&env);
+    let position_id = client.open_position(&owner);
+    let account = client.get_position_account(&position_id);
+    client.execute_on_position(&owner, &position_id, &ProtocolAction::Deposit(None, 5_000));
+
+    advance_time(&env, ARCHIVE_IDLE_SECONDS);
+    assert_eq!(
+        client.archive_idle_positions(&keeper, &vec![&env, account.clone()]),
+        1
+    );
+    assert_eq!(
+        client.get_archived_position(&account).unwrap().collateral,
+        5_000
+    );
+
+    // Only the owner restores the account, which cannot authorize itself
+    assert_eq!(
+        client.try_restore_isolated_position(&Address::generate(&env), &position_id),
+        Err(Ok(PositionError::NotPositionOwner))
+    );
+    let position = client.restore_isolated_position(&owner, &position_id);
+    assert_authorized(&env, &owner, &id, "restore_isolated_position");
+    assert_eq!(position.collateral, 5_000);
+    assert_eq!(client.get_archived_position(&account), None);
+    assert_eq!(client.get_supply_shares(&account), 5_000);
+    assert_eq!(
+        client.try_restore_isolated_position(&owner, &position_id),
+        Err(Ok(PositionError::RestoreFailed))
+    );
+
+    // The restored account is operable again
+    let withdraw = ProtocolAction::Withdraw(None, 2_000);
+    assert_eq!(
+        client.execute_on_position(&owner, &position_id, &withdraw),
+        3_000
+    );
+}
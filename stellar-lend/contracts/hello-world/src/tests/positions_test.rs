@@ -0,0 +1,162 @@
+//! Isolated position tests.
+//!
+//! # Coverage
+//! - Positions live at their own accounts, isolated from each other and from
+//!   the owner's own position
+//! - The owner pays deposits and repayments and receives withdrawals
+//! - Transferring a position moves collateral and debt to the new owner
+//! - Ownership, recipient and position count checks
+//! - Position accounts are indexed as borrowers like any position
+
+use crate::batch::ProtocolAction;
+use crate::positions::{PositionError, MAX_POSITIONS_PER_USER};
+use crate::tests::test_helpers::setup_env_with_native_asset;
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Env,
+};
+
+fn fund(env: &Env, token: &Address, contract_id: &Address, owner: &Address, amount: i128) {
+    StellarAssetClient::new(env, token).mint(owner, &amount);
+    TokenClient::new(env, token).approve(
+        owner,
+        contract_id,
+        &amount,
+        &(env.ledger().sequence() + 100),
+    );
+}
+
+#[test]
+fn test_positions_are_isolated() {
+    let (env, _id, client, _admin, owner, _native) = setup_env_with_native_asset();
+    let first = client.open_position(&owner);
+    let second = client.open_position(&owner);
+    assert_eq!((first, second), (1, 2));
+    assert_eq!(client.get_user_positions(&owner), vec![&env, 1, 2]);
+    assert_eq!(client.get_position_owner(&first), Some(owner.clone()));
+
+    let first_account = client.get_position_account(&first);
+    let second_account = client.get_position_account(&second);
+    assert_ne!(first_account, second_account);
+    assert_ne!(first_account, owner);
+
+    let deposit = ProtocolAction::Deposit(None, 10_000);
+    assert_eq!(client.execute_on_position(&owner, &first, &deposit), 10_000);
+    let borrow = ProtocolAction::Borrow(None, 4_000);
+    assert_eq!(client.execute_on_position(&owner, &first, &borrow), 4_000);
+
+    let position = client.get_user_report(&first_account).position;
+    assert_eq!((position.collateral, position.debt), (10_000, 4_000));
+    assert!(client.try_get_user_report(&second_account).is_err());
+    assert!(client.try_get_user_report(&owner).is_err());
+
+    // The empty position has nothing to borrow against
+    assert_eq!(
+        client.try_execute_on_position(&owner, &second, &borrow),
+        Err(Ok(PositionError::BorrowFailed))
+    );
+
+    // The borrowing position account is indexed for liquidation scans
+    assert_eq!(client.get_borrower_count(), 1);
+}
+
+#[test]
+fn test_position_funds_flow_through_owner() {
+    let (env, id, client, admin, owner, _native) = setup_env_with_native_asset();
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let position_id = client.open_position(&owner);
+    let account = client.get_position_account(&position_id);
+    fund(&env, &token, &id, &owner, 5_000);
+
+    let deposit = ProtocolAction::Deposit(Some(token.clone()), 5_000);
+    assert_eq!(
+        client.execute_on_position(&owner, &position_id, &deposit),
+        5_000
+    );
+    assert_eq!(TokenClient::new(&env, &token).balance(&owner), 0);
+
+    let withdraw = ProtocolAction::Withdraw(Some(token.clone()), 2_000);
+    assert_eq!(
+        client.execute_on_position(&owner, &position_id, &withdraw),
+        3_000
+    );
+    assert_eq!(TokenClient::new(&env, &token).balance(&owner), 2_000);
+    assert_eq!(TokenClient::new(&env, &token).balance(&account), 0);
+}
+
+#[test]
+fn test_transfer_position_moves_collateral_and_debt() {
+    let (env, id, client, _admin, seller, native) = setup_env_with_native_asset();
+    let buyer = Address::generate(&env);
+    let position_id = client.open_position(&seller);
+    let account = client.get_position_account(&position_id);
+    client.execute_on_position(
+        &seller,
+        &position_id,
+        &ProtocolAction::Deposit(None, 10_000),
+    );
+    client.execute_on_position(&seller, &position_id, &ProtocolAction::Borrow(None, 4_000));
+
+    client.transfer_position(&seller, &position_id, &buyer);
+    assert_eq!(client.get_position_owner(&position_id), Some(buyer.clone()));
+    assert!(client.get_user_positions(&seller).is_empty());
+    assert_eq!(client.get_user_positions(&buyer), vec![&env, position_id]);
+    assert_eq!(client.get_position_account(&position_id), account);
+
+    assert_eq!(
+        client.try_execute_on_position(&seller, &position_id, &ProtocolAction::Withdraw(None, 1)),
+        Err(Ok(PositionError::NotPositionOwner))
+    );
+    assert_eq!(
+        client.try_transfer_position(&seller, &position_id, &seller),
+        Err(Ok(PositionError::NotPositionOwner))
+    );
+
+    // The buyer now carries the debt and repays it from their own wallet
+    fund(&env, &native, &id, &buyer, 1_500);
+    assert_eq!(
+        client.execute_on_position(&buyer, &position_id, &ProtocolAction::Repay(None, 1_500)),
+        2_500
+    );
+    assert_eq!(TokenClient::new(&env, &native).balance(&buyer), 0);
+    assert_eq!(client.get_user_report(&account).position.debt, 2_500);
+}
+
+#[test]
+fn test_position_checks() {
+    let (env, id, client, _admin, owner, _native) = setup_env_with_native_asset();
+    let deposit = ProtocolAction::Deposit(None, 100);
+    assert_eq!(
+        client.try_execute_on_position(&owner, &1, &deposit),
+        Err(Ok(PositionError::PositionNotFound))
+    );
+
+    let position_id = client.open_position(&owner);
+    assert_eq!(
+        client.try_transfer_position(&owner, &position_id, &owner),
+        Err(Ok(PositionError::InvalidRecipient))
+    );
+    assert_eq!(
+        client.try_transfer_position(&owner, &position_id, &id),
+        Err(Ok(PositionError::InvalidRecipient))
+    );
+
+    for _ in 1..MAX_POSITIONS_PER_USER {
+        client.open_position(&owner);
+    }
+    assert_eq!(
+        client.try_open_position(&owner),
+        Err(Ok(PositionError::TooManyPositions))
+    );
+
+    // A full recipient cannot take another position
+    let other = Address::generate(&env);
+    let other_position = client.open_position(&other);
+    assert_eq!(
+        client.try_transfer_position(&other, &other_position, &owner),
+        Err(Ok(PositionError::TooManyPositions))
+    );
+}
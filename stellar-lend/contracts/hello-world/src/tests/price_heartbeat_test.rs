@@ -0,0 +1,242 @@
+//! Oracle heartbeat and degraded mode tests.
+//!
+//! # Coverage
+//! - A missed heartbeat degrades the asset and emits a price stale event once
+//! - The next fresh update ends degraded mode
+//! - Degraded assets cannot be borrowed
+//! - Liquidations value degraded collateral at the buffered last good price
+//! - Heartbeat configuration bounds, admin checks and authorization
+
+use crate::borrow::BorrowError;
+use crate::deposit::{DepositDataKey, Position};
+use crate::liquidate::{liquidate, LiquidationError};
+use crate::oracle::{DegradedState, HeartbeatConfig, OracleError, MAX_HEARTBEAT_SAFETY_BUFFER_BPS};
+use crate::tests::test_helpers::assert_authorized;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    contracttype,
+    testutils::{Address as _, Events},
+    Address, Env, TryFromVal,
+};
+use stellarlend_testutils::{advance_time, set_time};
+
+const PRICE: i128 = 100_000_000;
+const HEARTBEAT: u64 = 600;
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestPriceStaleEvent {
+    pub asset: Address,
+    pub last_price: i128,
+    pub last_updated: u64,
+    pub timestamp: u64,
+}
+
+struct Setup<'a> {
+    id: Address,
+    client: HelloContractClient<'a>,
+    admin: Address,
+    oracle: Address,
+    asset: Address,
+}
+
+/// Price the asset at t = 1_000 under a 600s heartbeat with a 10% buffer
+fn setup(e: &Env) -> Setup<'_> {
+    e.mock_all_auths();
+    set_time(e, 1_000);
+    let id = e.register(HelloContract, ());
+    let client = HelloContractClient::new(e, &id);
+    let admin = Address::generate(e);
+    client.initialize(&admin);
+
+    let s = Setup {
+        id,
+        client,
+        admin,
+        oracle: Address::generate(e),
+        asset: Address::generate(e),
+    };
+    s.client.set_price_heartbeat(
+        &s.admin,
+        &s.asset,
+        &Some(HeartbeatConfig {
+            heartbeat_secs: HEARTBEAT,
+            safety_buffer_bps: 1_000,
+        }),
+    );
+    assert_authorized(e, &s.admin, &s.id, "set_price_heartbeat");
+    push(&s, &s.asset, PRICE);
+    s
+}
+
+fn push(s: &Setup, asset: &Address, price: i128) {
+    s.client
+        .update_price_feed(&s.admin, asset, &price, &8, &s.oracle);
+}
+
+fn stale_events(e: &Env) -> u32 {
+    e.events()
+        .all()
+        .iter()
+        .filter(|(_contract, _topics, data)| TestPriceStaleEvent::try_from_val(e, data).is_ok())
+        .count() as u32
+}
+
+#[test]
+fn test_missed_heartbeat_degrades_until_next_update() {
+    let e = Env::default();
+    let s = setup(&e);
+
+    advance_time(&e, HEARTBEAT);
+    assert!(!s.client.check_price_heartbeat(&s.asset));
+    assert_eq!(s.client.get_degraded_state(&s.asset), None);
+
+    advance_time(&e, 1);
+    assert!(s.client.check_price_heartbeat(&s.asset));
+    let event = e
+        .events()
+        .all()
+        .iter()
+        .find_map(|(_contract, _topics, data)| TestPriceStaleEvent::try_from_val(&e, &data).ok());
+    assert_eq!(
+        event,
+        Some(TestPriceStaleEvent {
+            asset: s.asset.clone(),
+            last_price: PRICE,
+            last_updated: 1_000,
+            timestamp: 1_601,
+        })
+    );
+    assert_eq!(
+        s.client.get_degraded_state(&s.asset),
+        Some(DegradedState {
+            last_price: PRICE,
+            last_updated: 1_000,
+            since: 1_601,
+        })
+    );
+
+    // Already degraded: no further event
+    advance_time(&e, 60);
+    assert!(s.client.check_price_heartbeat(&s.asset));
+    assert_eq!(stale_events(&e), 0);
+    assert_eq!(s.client.get_degraded_state(&s.asset).unwrap().since, 1_601);
+
+    push(&s, &s.asset, PRICE);
+    assert_eq!(s.client.get_degraded_state(&s.asset), None);
+    assert!(!s.client.check_price_heartbeat(&s.asset));
+}
+
+#[test]
+fn test_degraded_asset_blocks_borrows() {
+    let e = Env::default();
+    let s = setup(&e);
+    let user = Address::generate(&e);
+
+    advance_time(&e, HEARTBEAT + 1);
+    assert_eq!(
+        s.client
+            .try_borrow_asset(&user, &Some(s.asset.clone()), &100),
+        Err(Ok(BorrowError::PriceDegraded))
+    );
+
+    push(&s, &s.asset, PRICE);
+    assert_eq!(
+        s.client
+            .try_borrow_asset(&user, &Some(s.asset.clone()), &100),
+        Err(Ok(BorrowError::InsufficientCollateral))
+    );
+
+    // Unmonitored assets never degrade
+    let other = Address::generate(&e);
+    assert!(!s.client.check_price_heartbeat(&other));
+}
+
+#[test]
+fn test_liquidation_buffers_degraded_collateral() {
+    let e = Env::default();
+    let s = setup(&e);
+    let debt_asset = Address::generate(&e);
+    let borrower = Address::generate(&e);
+    let liquidator = Address::generate(&e);
+    push(&s, &debt_asset, PRICE);
+
+    // 107.5% collateralized at the last good price
+    e.as_contract(&s.id, || {
+        e.storage().persistent().set(
+            &DepositDataKey::CollateralBalance(borrower.clone()),
+            &10_000i128,
+        );
+        e.storage().persistent().set(
+            &DepositDataKey::Position(borrower.clone()),
+            &Position {
+                collateral: 10_000,
+                debt: 9_300,
+                borrow_interest: 0,
+                last_accrual_time: e.ledger().timestamp(),
+            },
+        );
+    });
+
+    let try_liquidate = || {
+        e.as_contract(&s.id, || {
+            liquidate(
+                &e,
+                liquidator.clone(),
+                borrower.clone(),
+                Some(debt_asset.clone()),
+                Some(s.asset.clone()),
+                1_000,
+            )
+        })
+    };
+    assert_eq!(try_liquidate(), Err(LiquidationError::NotLiquidatable));
+
+    // The spot price is still within the staleness limit, but the heartbeat
+    // was missed: collateral counts at 90% of the last good price
+    advance_time(&e, HEARTBEAT + 1);
+    push(&s, &debt_asset, PRICE);
+    assert_eq!(s.client.get_price(&s.asset), PRICE);
+    assert!(try_liquidate().is_ok());
+    assert_eq!(
+        s.client.get_degraded_state(&s.asset).unwrap().since,
+        1_000 + HEARTBEAT + 1
+    );
+}
+
+#[test]
+fn test_heartbeat_config_checks() {
+    let e = Env::default();
+    let s = setup(&e);
+    let config = |heartbeat_secs, safety_buffer_bps| {
+        Some(HeartbeatConfig {
+            heartbeat_secs,
+            safety_buffer_bps,
+        })
+    };
+
+    for invalid in [
+        config(0, 1_000),
+        config(HEARTBEAT, -1),
+        config(HEARTBEAT, MAX_HEARTBEAT_SAFETY_BUFFER_BPS + 1),
+    ] {
+        assert_eq!(
+            s.client
+                .try_set_price_heartbeat(&s.admin, &s.asset, &invalid),
+            Err(Ok(OracleError::InvalidHeartbeatConfig))
+        );
+    }
+    assert_eq!(
+        s.client
+            .try_set_price_heartbeat(&Address::generate(&e), &s.asset, &config(60, 0)),
+        Err(Ok(OracleError::Unauthorized))
+    );
+
+    // Removing the heartbeat ends degraded mode
+    advance_time(&e, HEARTBEAT + 1);
+    assert!(s.client.check_price_heartbeat(&s.asset));
+    s.client.set_price_heartbeat(&s.admin, &s.asset, &None);
+    assert_eq!(s.client.get_price_heartbeat(&s.asset), None);
+    assert_eq!(s.client.get_degraded_state(&s.asset), None);
+    assert!(!s.client.check_price_heartbeat(&s.asset));
+}
@@ -0,0 +1,185 @@
+//! Emergency price override tests.
+//!
+//! # Coverage
+//! - A guardian override prices an asset whose feeds went stale
+//! - Overrides stop applying at their expiry
+//! - Override price and expiry bounds relative to the last oracle price
+//! - Only the admin or a guardian may set or clear overrides
+
+use crate::oracle::{OracleError, PriceOverride, MAX_PRICE_OVERRIDE_DURATION};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    contracttype,
+    testutils::{Address as _, Events},
+    Address, Env, TryFromVal,
+};
+use stellarlend_testutils::{advance_time, set_time};
+
+const ORACLE_PRICE: i128 = 100_000_000;
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestPriceOverrideSetEvent {
+    pub asset: Address,
+    pub actor: Address,
+    pub price: i128,
+    pub reference_price: i128,
+    pub expires_at: u64,
+    pub timestamp: u64,
+}
+
+struct Setup<'a> {
+    client: HelloContractClient<'a>,
+    admin: Address,
+    guardian: Address,
+    asset: Address,
+}
+
+/// Push an oracle price at t = 10_000 and register a single guardian
+fn setup(e: &Env) -> Setup<'_> {
+    e.mock_all_auths();
+    set_time(e, 10_000);
+    let id = e.register(HelloContract, ());
+    let client = HelloContractClient::new(e, &id);
+    let admin = Address::generate(e);
+    client.initialize(&admin);
+
+    let vote_token = e
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    client.gov_initialize(
+        &admin,
+        &vote_token,
+        &None,
+        &None,
+        &None,
+        &None,
+        &None,
+        &None,
+    );
+    let guardian = Address::generate(e);
+    client.gov_add_guardian(&admin, &guardian);
+
+    let asset = Address::generate(e);
+    client.update_price_feed(&admin, &asset, &ORACLE_PRICE, &8, &Address::generate(e));
+    Setup {
+        client,
+        admin,
+        guardian,
+        asset,
+    }
+}
+
+#[test]
+fn test_override_prices_stale_asset() {
+    let e = Env::default();
+    let s = setup(&e);
+    advance_time(&e, 3_601);
+    assert!(s.client.try_get_price(&s.asset).is_err());
+
+    let expires_at = 13_601 + 3_600;
+    let price_override =
+        s.client
+            .set_price_override(&s.guardian, &s.asset, &90_000_000, &expires_at);
+    assert_eq!(
+        price_override,
+        PriceOverride {
+            price: 90_000_000,
+            reference_price: ORACLE_PRICE,
+            expires_at,
+            set_by: s.guardian.clone(),
+            set_at: 13_601,
+        }
+    );
+    let event = e
+        .events()
+        .all()
+        .iter()
+        .find_map(|(_contract, _topics, data)| {
+            TestPriceOverrideSetEvent::try_from_val(&e, &data).ok()
+        });
+    assert_eq!(
+        event,
+        Some(TestPriceOverrideSetEvent {
+            asset: s.asset.clone(),
+            actor: s.guardian.clone(),
+            price: 90_000_000,
+            reference_price: ORACLE_PRICE,
+            expires_at,
+            timestamp: 13_601,
+        })
+    );
+    assert_eq!(s.client.get_price(&s.asset), 90_000_000);
+
+    // The override lapses at its expiry and the stale feed applies again
+    advance_time(&e, 3_600);
+    assert_eq!(s.client.get_price_override(&s.asset), None);
+    assert!(s.client.try_get_price(&s.asset).is_err());
+}
+
+#[test]
+fn test_override_bounds() {
+    let e = Env::default();
+    let s = setup(&e);
+    let expires_at = 10_000 + 3_600;
+
+    assert_eq!(
+        s.client
+            .try_set_price_override(&s.admin, &s.asset, &120_000_001, &expires_at),
+        Err(Ok(OracleError::OverrideOutOfBounds))
+    );
+    assert_eq!(
+        s.client
+            .try_set_price_override(&s.admin, &s.asset, &79_999_999, &expires_at),
+        Err(Ok(OracleError::OverrideOutOfBounds))
+    );
+    assert_eq!(
+        s.client
+            .try_set_price_override(&s.admin, &s.asset, &ORACLE_PRICE, &10_000),
+        Err(Ok(OracleError::InvalidOverrideExpiry))
+    );
+    assert_eq!(
+        s.client.try_set_price_override(
+            &s.admin,
+            &s.asset,
+            &ORACLE_PRICE,
+            &(10_000 + MAX_PRICE_OVERRIDE_DURATION + 1)
+        ),
+        Err(Ok(OracleError::InvalidOverrideExpiry))
+    );
+    let unpriced = Address::generate(&e);
+    assert_eq!(
+        s.client
+            .try_set_price_override(&s.admin, &unpriced, &ORACLE_PRICE, &expires_at),
+        Err(Ok(OracleError::AssetNotSupported))
+    );
+
+    // Exactly at the 20% bound
+    s.client
+        .set_price_override(&s.admin, &s.asset, &80_000_000, &expires_at);
+    assert_eq!(s.client.get_price(&s.asset), 80_000_000);
+}
+
+#[test]
+fn test_override_authority() {
+    let e = Env::default();
+    let s = setup(&e);
+    let expires_at = 10_000 + 3_600;
+    let outsider = Address::generate(&e);
+
+    assert_eq!(
+        s.client
+            .try_set_price_override(&outsider, &s.asset, &ORACLE_PRICE, &expires_at),
+        Err(Ok(OracleError::Unauthorized))
+    );
+    s.client
+        .set_price_override(&s.admin, &s.asset, &95_000_000, &expires_at);
+    assert_eq!(
+        s.client.try_clear_price_override(&outsider, &s.asset),
+        Err(Ok(OracleError::Unauthorized))
+    );
+
+    s.client.clear_price_override(&s.guardian, &s.asset);
+    assert_eq!(s.client.get_price_override(&s.asset), None);
+    assert_eq!(s.client.get_price(&s.asset), ORACLE_PRICE);
+}
@@ -0,0 +1,230 @@
+//! # Borrow APR Cap Tests
+//!
+//! Tests for per-user-class borrow APR caps and the treasury subsidy budget.
+//!
+//! ## Coverage
+//! - Class assignment and cap configuration (admin only, authorized, bounds)
+//! - Uncapped borrowers accrue at the market rate
+//! - Capped borrowers accrue at the cap while the budget covers the gap
+//! - Partial coverage when the budget runs out
+//! - Subsidy budget allocation is bounded by the asset's reserves
+//! - Non-native budgets are funded from and drawn for their own asset only
+
+use crate::deposit::{DepositDataKey, ProtocolAnalytics};
+use crate::interest_rate::{
+    calculate_accrued_interest, calculate_borrow_rate, calculate_user_accrued_interest,
+    InterestRateError,
+};
+use crate::reserve::{get_subsidy_budget, get_subsidy_spent, ReserveDataKey, ReserveError};
+use crate::tests::test_helpers::assert_authorized;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env, Symbol};
+
+const SECONDS_PER_YEAR: u64 = 365 * 86400;
+
+fn setup() -> (Env, Address, Address, HelloContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    // 50% utilization so the market rate is well above the class cap
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().set(
+            &DepositDataKey::ProtocolAnalytics,
+            &ProtocolAnalytics {
+                total_deposits: 1_000_000,
+                total_borrows: 500_000,
+                total_value_locked: 1_000_000,
+            },
+        );
+    });
+
+    (env, contract_id, admin, client)
+}
+
+fn seed_native_reserve(env: &Env, contract_id: &Address, amount: i128) {
+    seed_reserve(env, contract_id, None, amount);
+}
+
+fn seed_reserve(env: &Env, contract_id: &Address, asset: Option<Address>, amount: i128) {
+    env.as_contract(contract_id, || {
+        env.storage()
+            .persistent()
+            .set(&ReserveDataKey::ReserveBalance(asset), &amount);
+    });
+}
+
+#[test]
+fn test_set_class_cap_requires_admin_and_bounds() {
+    let (env, id, admin, client) = setup();
+    let class = Symbol::new(&env, "rwa");
+    let stranger = Address::generate(&env);
+
+    assert_eq!(
+        client.try_set_class_rate_cap(&stranger, &class, &Some(200)),
+        Err(Ok(InterestRateError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_class_rate_cap(&admin, &class, &Some(10_001)),
+        Err(Ok(InterestRateError::InvalidParameter))
+    );
+
+    let user = Address::generate(&env);
+    client.set_class_rate_cap(&admin, &class, &Some(200));
+    assert_authorized(&env, &admin, &id, "set_class_rate_cap");
+    assert_eq!(client.get_user_rate_cap(&user), None);
+
+    client.set_user_class(&admin, &user, &Some(class.clone()));
+    assert_authorized(&env, &admin, &id, "set_user_class");
+    assert_eq!(client.get_user_rate_cap(&user), Some(200));
+
+    client.set_class_rate_cap(&admin, &class, &None);
+    assert_eq!(client.get_user_rate_cap(&user), None);
+}
+
+#[test]
+fn test_uncapped_user_accrues_market_rate() {
+    let (env, contract_id, _admin, _client) = setup();
+    let user = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        let rate = calculate_borrow_rate(&env).unwrap();
+        let expected = calculate_accrued_interest(100_000, 0, SECONDS_PER_YEAR, rate).unwrap();
+        let charged =
+            calculate_user_accrued_interest(&env, &user, &None, 100_000, 0, SECONDS_PER_YEAR)
+                .unwrap();
+        assert_eq!(charged, expected);
+        assert_eq!(get_subsidy_spent(&env, None), 0);
+    });
+}
+
+#[test]
+fn test_capped_user_accrues_at_cap_with_subsidy() {
+    let (env, contract_id, admin, client) = setup();
+    let user = Address::generate(&env);
+    let class = Symbol::new(&env, "rwa");
+    seed_native_reserve(&env, &contract_id, 1_000_000);
+
+    client.set_class_rate_cap(&admin, &class, &Some(200));
+    client.set_user_class(&admin, &user, &Some(class));
+    client.allocate_subsidy_budget(&admin, &None, &100_000);
+
+    env.as_contract(&contract_id, || {
+        let rate = calculate_borrow_rate(&env).unwrap();
+        assert!(rate > 200);
+        let market = calculate_accrued_interest(100_000, 0, SECONDS_PER_YEAR, rate).unwrap();
+        let capped = calculate_accrued_interest(100_000, 0, SECONDS_PER_YEAR, 200).unwrap();
+
+        let charged =
+            calculate_user_accrued_interest(&env, &user, &None, 100_000, 0, SECONDS_PER_YEAR)
+                .unwrap();
+        assert_eq!(charged, capped);
+        assert_eq!(get_subsidy_spent(&env, None), market - capped);
+        assert_eq!(get_subsidy_budget(&env, None), 100_000 - (market - capped));
+    });
+}
+
+#[test]
+fn test_exhausted_budget_charges_uncovered_gap() {
+    let (env, contract_id, admin, client) = setup();
+    let user = Address::generate(&env);
+    let class = Symbol::new(&env, "rwa");
+    seed_native_reserve(&env, &contract_id, 10);
+
+    client.set_class_rate_cap(&admin, &class, &Some(0));
+    client.set_user_class(&admin, &user, &Some(class));
+    client.allocate_subsidy_budget(&admin, &None, &10);
+
+    env.as_contract(&contract_id, || {
+        let rate = calculate_borrow_rate(&env).unwrap();
+        let market = calculate_accrued_interest(100_000, 0, SECONDS_PER_YEAR, rate).unwrap();
+
+        let charged =
+            calculate_user_accrued_interest(&env, &user, &None, 100_000, 0, SECONDS_PER_YEAR)
+                .unwrap();
+        assert_eq!(charged, market - 10);
+        assert_eq!(get_subsidy_budget(&env, None), 0);
+    });
+}
+
+#[test]
+fn test_allocate_subsidy_budget_bounded_by_reserves() {
+    let (env, contract_id, admin, client) = setup();
+    seed_native_reserve(&env, &contract_id, 500);
+
+    assert_eq!(
+        client.try_allocate_subsidy_budget(&admin, &None, &501),
+        Err(Ok(ReserveError::InsufficientReserve))
+    );
+    assert_eq!(
+        client.try_allocate_subsidy_budget(&admin, &None, &0),
+        Err(Ok(ReserveError::InvalidAmount))
+    );
+
+    assert_eq!(client.allocate_subsidy_budget(&admin, &None, &300), 300);
+    assert_eq!(client.get_subsidy_budget(&None), 300);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_allocate_subsidy_budget(&stranger, &None, &100),
+        Err(Ok(ReserveError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_non_native_budget_uses_asset_reserve() {
+    let (env, contract_id, admin, client) = setup();
+    let user = Address::generate(&env);
+    let class = Symbol::new(&env, "rwa");
+    let asset = Some(Address::generate(&env));
+    seed_reserve(&env, &contract_id, asset.clone(), 1_000_000);
+
+    // The native reserve is empty, so only the asset's reserve can fund it
+    assert_eq!(
+        client.try_allocate_subsidy_budget(&admin, &None, &100_000),
+        Err(Ok(ReserveError::InsufficientReserve))
+    );
+    assert_eq!(
+        client.allocate_subsidy_budget(&admin, &asset, &100_000),
+        100_000
+    );
+    assert_eq!(client.get_subsidy_budget(&asset), 100_000);
+    assert_eq!(client.get_subsidy_budget(&None), 0);
+
+    client.set_class_rate_cap(&admin, &class, &Some(200));
+    client.set_user_class(&admin, &user, &Some(class));
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            env.storage()
+                .persistent()
+                .get::<ReserveDataKey, i128>(&ReserveDataKey::ReserveBalance(asset.clone())),
+            Some(900_000)
+        );
+
+        let rate = calculate_borrow_rate(&env).unwrap();
+        let market = calculate_accrued_interest(100_000, 0, SECONDS_PER_YEAR, rate).unwrap();
+        let capped = calculate_accrued_interest(100_000, 0, SECONDS_PER_YEAR, 200).unwrap();
+
+        // Native debt finds no native budget and pays the market rate
+        let charged =
+            calculate_user_accrued_interest(&env, &user, &None, 100_000, 0, SECONDS_PER_YEAR)
+                .unwrap();
+        assert_eq!(charged, market);
+
+        // Debt in the asset is subsidized from the asset's budget
+        let charged =
+            calculate_user_accrued_interest(&env, &user, &asset, 100_000, 0, SECONDS_PER_YEAR)
+                .unwrap();
+        assert_eq!(charged, capped);
+        assert_eq!(get_subsidy_spent(&env, asset.clone()), market - capped);
+        assert_eq!(
+            get_subsidy_budget(&env, asset.clone()),
+            100_000 - (market - capped)
+        );
+        assert_eq!(get_subsidy_spent(&env, None), 0);
+    });
+}
@@ -0,0 +1,205 @@
+//! Per-asset rate curve tests.
+//!
+//! # Coverage
+//! - Linear, kinked and fixed curves price an asset's market through one dispatch
+//! - Clearing a curve falls back to the asset's cross-asset rate model
+//! - A two-slope kink drifts against utilization within its range
+//! - Invalid curves and non-admin callers are rejected; setting a curve needs
+//!   the admin's authorization
+
+use crate::cross_asset::{
+    cross_asset_borrow, cross_asset_deposit, cross_asset_repay, initialize, initialize_asset,
+    update_asset_price, AssetConfig, AssetRateModel,
+};
+use crate::interest_rate::{
+    InterestRateError, KinkedCurve, LinearCurve, RateCurve, TwoSlopeCurve, MAX_CURVE_RATE_BPS,
+};
+use crate::tests::test_helpers::{assert_authorized, setup_env_with_native_asset};
+use crate::HelloContractClient;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, Vec,
+};
+
+const DAY: u64 = 86_400;
+
+/// Configure the XLM cross-asset market and borrow `borrowed` out of 1,000,000
+fn open_market(env: &Env, id: &Address, admin: &Address, borrowed: i128) {
+    let user = Address::generate(env);
+    env.as_contract(id, || {
+        initialize(env, admin.clone()).unwrap();
+        let config = AssetConfig {
+            asset: None,
+            collateral_factor: 9000,
+            liquidation_threshold: 9500,
+            reserve_factor: 1000,
+            max_supply: 0,
+            max_borrow: 0,
+            can_collateralize: true,
+            can_borrow: true,
+            price: 10_000_000,
+            price_updated_at: env.ledger().timestamp(),
+            is_isolated: false,
+            isolated_debt_ceiling: 0,
+        };
+        initialize_asset(env, None, config).unwrap();
+        cross_asset_deposit(env, user.clone(), None, 1_000_000).unwrap();
+        if borrowed > 0 {
+            cross_asset_borrow(env, user, None, borrowed).unwrap();
+        }
+    });
+}
+
+/// Advance time and settle the market through a zero repayment
+fn settle_after(env: &Env, id: &Address, seconds: u64) {
+    env.ledger().with_mut(|li| li.timestamp += seconds);
+    env.as_contract(id, || {
+        update_asset_price(env, None, 10_000_000).unwrap();
+        let borrower = Address::generate(env);
+        let _ = cross_asset_repay(env, borrower, None, 0);
+    });
+}
+
+fn projected_rate(env: &Env, client: &HelloContractClient, utilization: i128) -> i128 {
+    client
+        .project_rates(&None, &Vec::from_array(env, [utilization]))
+        .get(0)
+        .unwrap()
+        .borrow_rate_bps
+}
+
+fn two_slope() -> TwoSlopeCurve {
+    TwoSlopeCurve {
+        base_rate_bps: 100,
+        slope1_bps: 1_000,
+        slope2_bps: 5_000,
+        min_kink_bps: 5_000,
+        max_kink_bps: 9_000,
+        kink_speed_bps: 1_000,
+    }
+}
+
+#[test]
+fn test_curve_variants_share_dispatch() {
+    let (env, id, client, admin, _user, _native) = setup_env_with_native_asset();
+    open_market(&env, &id, &admin, 400_000);
+    client.set_asset_rate_model(
+        &None,
+        &AssetRateModel {
+            base_rate_bps: 200,
+            kink_utilization_bps: 8_000,
+            multiplier_bps: 1_000,
+            jump_multiplier_bps: 10_000,
+        },
+    );
+    // 40% utilization on the legacy model: 200 + 4000 * 1000 / 8000
+    assert_eq!(client.get_asset_borrow_rate(&None), 700);
+
+    // Linear: 300 + 4000 * 2000 / 10000
+    let linear = RateCurve::Linear(LinearCurve {
+        base_rate_bps: 300,
+        slope_bps: 2_000,
+    });
+    client.set_asset_rate_curve(&admin, &None, &Some(linear.clone()));
+    assert_authorized(&env, &admin, &id, "set_asset_rate_curve");
+    assert_eq!(client.get_asset_rate_curve(&None), Some(linear));
+    assert_eq!(client.get_asset_borrow_rate(&None), 1_100);
+
+    // Kinked, above a 20% kink: 200 + 1000 + 2000 * 10000 / 8000
+    client.set_asset_rate_curve(
+        &admin,
+        &None,
+        &Some(RateCurve::Kinked(KinkedCurve {
+            base_rate_bps: 200,
+            kink_utilization_bps: 2_000,
+            multiplier_bps: 1_000,
+            jump_multiplier_bps: 10_000,
+        })),
+    );
+    assert_eq!(client.get_asset_borrow_rate(&None), 3_700);
+
+    // Fixed rate at any utilization
+    client.set_asset_rate_curve(&admin, &None, &Some(RateCurve::Fixed(450)));
+    assert_eq!(client.get_asset_borrow_rate(&None), 450);
+    assert_eq!(projected_rate(&env, &client, 0), 450);
+    assert_eq!(projected_rate(&env, &client, 10_000), 450);
+
+    client.set_asset_rate_curve(&admin, &None, &None);
+    assert_eq!(client.get_asset_rate_curve(&None), None);
+    assert_eq!(client.get_asset_borrow_rate(&None), 700);
+}
+
+#[test]
+fn test_two_slope_kink_drifts_against_utilization() {
+    let (env, id, client, admin, _user, _native) = setup_env_with_native_asset();
+    open_market(&env, &id, &admin, 800_000);
+    client.set_asset_rate_curve(&admin, &None, &Some(RateCurve::TwoSlope(two_slope())));
+
+    // Starts at the 70% midpoint: the rate at the kink is base + slope1
+    assert_eq!(projected_rate(&env, &client, 7_000), 1_100);
+
+    // 80% utilization sits above the kink, which drifts down 10% a day
+    settle_after(&env, &id, DAY);
+    assert_eq!(projected_rate(&env, &client, 6_000), 1_100);
+    // 100 + 5000 * 1000 / 6000
+    assert_eq!(projected_rate(&env, &client, 5_000), 933);
+
+    // Checkpoints shorter than a whole step accumulate instead of rounding
+    // to nothing: 120 seconds move the kink to 59.99%
+    for _ in 0..3 {
+        settle_after(&env, &id, 40);
+    }
+    // 100 + 1000 + 1 * 5000 / 4001
+    assert_eq!(projected_rate(&env, &client, 6_000), 1_101);
+
+    settle_after(&env, &id, DAY);
+    assert_eq!(projected_rate(&env, &client, 5_000), 1_100);
+
+    // The kink stops at its floor
+    settle_after(&env, &id, 10 * DAY);
+    assert_eq!(projected_rate(&env, &client, 5_000), 1_100);
+
+    // Reselecting the curve restarts at the midpoint
+    client.set_asset_rate_curve(&admin, &None, &Some(RateCurve::TwoSlope(two_slope())));
+    assert_eq!(projected_rate(&env, &client, 7_000), 1_100);
+}
+
+#[test]
+fn test_invalid_curves_rejected() {
+    let (env, id, client, admin, _user, _native) = setup_env_with_native_asset();
+    open_market(&env, &id, &admin, 0);
+
+    let mut inverted = two_slope();
+    inverted.min_kink_bps = 9_500;
+    let mut full_kink = two_slope();
+    full_kink.max_kink_bps = 10_000;
+    let invalid = [
+        RateCurve::Fixed(-1),
+        RateCurve::Fixed(MAX_CURVE_RATE_BPS + 1),
+        RateCurve::Linear(LinearCurve {
+            base_rate_bps: 100,
+            slope_bps: -1,
+        }),
+        RateCurve::Kinked(KinkedCurve {
+            base_rate_bps: 100,
+            kink_utilization_bps: 0,
+            multiplier_bps: 1_000,
+            jump_multiplier_bps: 1_000,
+        }),
+        RateCurve::TwoSlope(inverted),
+        RateCurve::TwoSlope(full_kink),
+    ];
+    for curve in invalid {
+        assert_eq!(
+            client.try_set_asset_rate_curve(&admin, &None, &Some(curve)),
+            Err(Ok(InterestRateError::InvalidParameter))
+        );
+    }
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_set_asset_rate_curve(&stranger, &None, &Some(RateCurve::Fixed(100))),
+        Err(Ok(InterestRateError::Unauthorized))
+    );
+    assert_eq!(client.get_asset_rate_curve(&None), None);
+}
@@ -0,0 +1,131 @@
+//! Interest rate projection tests.
+//!
+//! # Coverage
+//! - The core model is projected with its floor, ceiling and spread
+//! - Projections follow configuration changes and leave pool state untouched
+//! - Assets with a cross-asset rate model use that model and reserve factor
+//! - Out-of-range utilizations and oversized requests are rejected
+
+use crate::cross_asset::{initialize, initialize_asset, AssetConfig, AssetRateModel};
+use crate::interest_rate::{
+    update_interest_rate_config, InterestRateError, RateProjection, MAX_RATE_PROJECTION_POINTS,
+};
+use crate::tests::test_helpers::setup_env_with_native_asset;
+use soroban_sdk::{Env, Vec};
+
+fn rates(env: &Env, points: &[(i128, i128, i128)]) -> Vec<RateProjection> {
+    let mut projections = Vec::new(env);
+    for &(utilization_bps, borrow_rate_bps, supply_rate_bps) in points {
+        projections.push_back(RateProjection {
+            utilization_bps,
+            borrow_rate_bps,
+            supply_rate_bps,
+        });
+    }
+    projections
+}
+
+#[test]
+fn test_core_model_curve() {
+    let (env, id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let utilizations = Vec::from_array(&env, [0, 4_000, 8_000, 9_000, 10_000]);
+
+    // Defaults: 1% base, 80% kink, 20% / 100% slopes, 0.5%-100% bounds, 2% spread
+    assert_eq!(
+        client.project_rates(&None, &utilizations),
+        rates(
+            &env,
+            &[
+                (0, 100, 50),
+                (4_000, 1_100, 900),
+                (8_000, 2_100, 1_900),
+                (9_000, 7_100, 6_900),
+                (10_000, 10_000, 9_800),
+            ]
+        )
+    );
+    assert_eq!(client.get_borrow_rate(), 100);
+
+    env.as_contract(&id, || {
+        update_interest_rate_config(
+            &env,
+            admin.clone(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(5_000),
+            Some(100),
+        )
+        .unwrap();
+    });
+    assert_eq!(
+        client.project_rates(&None, &Vec::from_array(&env, [9_000, 10_000])),
+        rates(&env, &[(9_000, 5_000, 4_900), (10_000, 5_000, 4_900)])
+    );
+}
+
+#[test]
+fn test_asset_rate_model_curve() {
+    let (env, id, client, admin, _user, _native) = setup_env_with_native_asset();
+    env.as_contract(&id, || {
+        initialize(&env, admin.clone()).unwrap();
+        let config = AssetConfig {
+            asset: None,
+            collateral_factor: 7500,
+            liquidation_threshold: 8000,
+            reserve_factor: 1000,
+            max_supply: 0,
+            max_borrow: 0,
+            can_collateralize: true,
+            can_borrow: true,
+            price: 10_000_000,
+            price_updated_at: env.ledger().timestamp(),
+            is_isolated: false,
+            isolated_debt_ceiling: 0,
+        };
+        initialize_asset(&env, None, config).unwrap();
+    });
+    client.set_asset_rate_model(
+        &None,
+        &AssetRateModel {
+            base_rate_bps: 200,
+            kink_utilization_bps: 8_000,
+            multiplier_bps: 1_000,
+            jump_multiplier_bps: 5_000,
+        },
+    );
+
+    // Suppliers earn utilization × borrow rate, less the 10% reserve factor
+    assert_eq!(
+        client.project_rates(&None, &Vec::from_array(&env, [0, 5_000, 9_000])),
+        rates(
+            &env,
+            &[(0, 200, 0), (5_000, 825, 371), (9_000, 3_700, 2_997)]
+        )
+    );
+}
+
+#[test]
+fn test_invalid_projections_rejected() {
+    let (env, _id, client, _admin, _user, _native) = setup_env_with_native_asset();
+    assert_eq!(
+        client.try_project_rates(&None, &Vec::from_array(&env, [5_000, 10_001])),
+        Err(Ok(InterestRateError::InvalidParameter))
+    );
+    assert_eq!(
+        client.try_project_rates(&None, &Vec::from_array(&env, [-1])),
+        Err(Ok(InterestRateError::InvalidParameter))
+    );
+
+    let mut utilizations = Vec::new(&env);
+    for _ in 0..=MAX_RATE_PROJECTION_POINTS {
+        utilizations.push_back(0);
+    }
+    assert_eq!(
+        client.try_project_rates(&None, &utilizations),
+        Err(Ok(InterestRateError::InvalidParameter))
+    );
+    assert!(client.project_rates(&None, &Vec::new(&env)).is_empty());
+}
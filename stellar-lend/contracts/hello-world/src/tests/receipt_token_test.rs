@@ -0,0 +1,127 @@
+//! Receipt token tests.
+//!
+//! # Coverage
+//! - Deposits mint slTokens and withdrawals burn them
+//! - debtToken balances follow borrowed principal and cannot be transferred
+//! - Withdrawals need the slTokens for the amount withdrawn
+//! - Only tokens administered by the contract can be registered, with the
+//!   admin's authorization
+
+use crate::tests::test_helpers::{assert_authorized, setup_env_with_native_asset};
+use crate::tokenization::{ReceiptTokens, TokenizationError};
+use crate::withdraw::WithdrawError;
+use crate::HelloContractClient;
+use soroban_sdk::{testutils::Address as _, token::StellarAssetClient, Address, Env, String};
+use stellarlend_receipt_token::{ReceiptToken, ReceiptTokenClient, TokenError};
+
+fn deploy_token<'a>(
+    env: &Env,
+    admin: &Address,
+    symbol: &str,
+    transferable: bool,
+) -> ReceiptTokenClient<'a> {
+    let token = ReceiptTokenClient::new(env, &env.register(ReceiptToken, ()));
+    token.initialize(
+        admin,
+        &7,
+        &String::from_str(env, symbol),
+        &String::from_str(env, symbol),
+        &transferable,
+    );
+    token
+}
+
+fn register_tokens<'a>(
+    env: &Env,
+    client: &HelloContractClient,
+    admin: &Address,
+) -> (ReceiptTokenClient<'a>, ReceiptTokenClient<'a>) {
+    let sl_token = deploy_token(env, &client.address, "slXLM", true);
+    let debt_token = deploy_token(env, &client.address, "debtXLM", false);
+    client.set_receipt_tokens(
+        admin,
+        &ReceiptTokens {
+            supply_token: Some(sl_token.address.clone()),
+            debt_token: Some(debt_token.address.clone()),
+        },
+    );
+    assert_authorized(env, admin, &client.address, "set_receipt_tokens");
+    (sl_token, debt_token)
+}
+
+#[test]
+fn test_receipts_mirror_position() {
+    let (env, id, client, admin, user, native) = setup_env_with_native_asset();
+    let (sl_token, debt_token) = register_tokens(&env, &client, &admin);
+
+    client.deposit_collateral(&user, &None, &20_000);
+    assert_eq!(sl_token.balance(&user), 20_000);
+
+    client.borrow_asset(&user, &None, &5_000);
+    assert_eq!(debt_token.balance(&user), 5_000);
+
+    let native_token = StellarAssetClient::new(&env, &native);
+    native_token.mint(&user, &2_000);
+    native_token.approve(&user, &id, &2_000, &(env.ledger().sequence() + 100));
+    client.repay_debt(&user, &None, &2_000);
+    assert_eq!(
+        debt_token.balance(&user),
+        client.get_user_report(&user).position.debt
+    );
+
+    client.withdraw_collateral(&user, &None, &5_000);
+    assert_eq!(sl_token.balance(&user), 15_000);
+    assert_eq!(sl_token.total_supply(), 15_000);
+}
+
+#[test]
+fn test_debt_token_is_non_transferable() {
+    let (env, _id, client, admin, user, _native) = setup_env_with_native_asset();
+    let (_sl_token, debt_token) = register_tokens(&env, &client, &admin);
+    client.deposit_collateral(&user, &None, &20_000);
+    client.borrow_asset(&user, &None, &5_000);
+
+    assert_eq!(
+        debt_token.try_transfer(&user, &Address::generate(&env), &1_000),
+        Err(Ok(TokenError::NonTransferable))
+    );
+    assert_eq!(debt_token.balance(&user), 5_000);
+}
+
+#[test]
+fn test_withdraw_requires_receipts() {
+    let (env, _id, client, admin, user, _native) = setup_env_with_native_asset();
+    let (sl_token, _debt_token) = register_tokens(&env, &client, &admin);
+    let other = Address::generate(&env);
+    client.deposit_collateral(&user, &None, &10_000);
+
+    // Receipts moved to another protocol can no longer be redeemed here
+    sl_token.transfer(&user, &other, &8_000);
+    assert_eq!(
+        client.try_withdraw_collateral(&user, &None, &5_000),
+        Err(Ok(WithdrawError::InsufficientReceiptTokens))
+    );
+    client.withdraw_collateral(&user, &None, &2_000);
+    assert_eq!(sl_token.balance(&user), 0);
+    assert_eq!(sl_token.balance(&other), 8_000);
+}
+
+#[test]
+fn test_register_tokens_validation() {
+    let (env, _id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let foreign = deploy_token(&env, &Address::generate(&env), "x", true);
+    let tokens = ReceiptTokens {
+        supply_token: Some(foreign.address.clone()),
+        debt_token: None,
+    };
+
+    assert_eq!(
+        client.try_set_receipt_tokens(&admin, &tokens),
+        Err(Ok(TokenizationError::InvalidToken))
+    );
+    assert_eq!(
+        client.try_set_receipt_tokens(&Address::generate(&env), &ReceiptTokens::default()),
+        Err(Ok(TokenizationError::Unauthorized))
+    );
+    assert_eq!(client.get_receipt_tokens(), ReceiptTokens::default());
+}
@@ -0,0 +1,195 @@
+//! Cross-chain collateral accounting tests.
+//!
+//! # Coverage
+//! - Remote collateral reports flow through the bridge message queue
+//! - Nothing counts until the admin configures recognition
+//! - Recognized collateral counts at the liquidation threshold less the haircut
+//!   and backs local borrows
+//! - Stale attestations are de-recognized until the bridge reports again
+//! - Reports replace earlier ones per network and asset; zero releases them
+//! - Configuration and report validation
+
+use crate::bridge::{
+    BridgeError, BridgeMessageKind, BridgeMessageStatus, MAX_REMOTE_COLLATERAL_AGE,
+    MAX_REMOTE_COLLATERAL_ENTRIES,
+};
+use crate::cross_asset::{
+    initialize as init_cross_asset, initialize_asset, AssetConfig, CrossAssetError,
+};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env};
+use stellarlend_testutils::{advance_time, set_time};
+
+const NETWORK: u32 = 1;
+const MAX_AGE: u64 = 1_800;
+
+/// Fee-free bridge and an asset priced at 1.0 with an 80% liquidation threshold
+fn setup() -> (Env, HelloContractClient<'static>, Address, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+
+    let contract_id = env.register(HelloContract, ());
+    let client = HelloContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let bridge = Address::generate(&env);
+    let asset = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        init_cross_asset(&env, admin.clone()).unwrap();
+        let config = AssetConfig {
+            asset: Some(asset.clone()),
+            collateral_factor: 7500,
+            liquidation_threshold: 8000,
+            reserve_factor: 1000,
+            max_supply: 0,
+            max_borrow: 0,
+            can_collateralize: true,
+            can_borrow: true,
+            price: 10_000_000,
+            price_updated_at: env.ledger().timestamp(),
+            is_isolated: false,
+            isolated_debt_ceiling: 0,
+        };
+        initialize_asset(&env, Some(asset.clone()), config).unwrap();
+    });
+    client.register_bridge(&admin, &NETWORK, &bridge, &0);
+
+    (env, client, admin, bridge, asset)
+}
+
+/// Report `amount` locked remotely for `user` and process the message
+fn report(
+    client: &HelloContractClient,
+    bridge: &Address,
+    user: &Address,
+    asset: &Address,
+    amount: i128,
+) -> u64 {
+    let nonce =
+        client.enqueue_remote_collateral(bridge, &NETWORK, user, &Some(asset.clone()), &amount);
+    client.process_bridge_messages(&NETWORK, &10);
+    nonce
+}
+
+#[test]
+fn test_remote_collateral_backs_local_borrows() {
+    let (env, client, admin, bridge, asset) = setup();
+    let user = Address::generate(&env);
+
+    let nonce = report(&client, &bridge, &user, &asset, 10_000);
+    let message = client.get_bridge_message(&NETWORK, &nonce).unwrap();
+    assert_eq!(message.kind, BridgeMessageKind::RemoteCollateral);
+    assert_eq!(message.status, BridgeMessageStatus::Processed);
+    let entry = client.get_remote_collateral(&user).get(0).unwrap();
+    assert_eq!(
+        (entry.network_id, entry.amount, entry.attested_at),
+        (NETWORK, 10_000, 1_000)
+    );
+
+    // Not recognized until configured
+    assert_eq!(
+        client
+            .get_user_position_summary(&user)
+            .remote_collateral_value,
+        0
+    );
+
+    // 10_000 at an 80% threshold less a 25% haircut
+    client.set_remote_collateral_config(&admin, &2_500, &MAX_AGE);
+    let summary = client.get_user_position_summary(&user);
+    assert_eq!(summary.remote_collateral_value, 6_000);
+    assert_eq!(summary.weighted_collateral_value, 6_000);
+    assert_eq!(summary.total_collateral_value, 0);
+    assert_eq!(summary.borrow_capacity, 6_000);
+
+    client.cross_asset_borrow(&user, &Some(asset.clone()), &5_000);
+    assert_eq!(
+        client.try_cross_asset_borrow(&user, &Some(asset), &2_000),
+        Err(Ok(CrossAssetError::ExceedsBorrowCapacity))
+    );
+}
+
+#[test]
+fn test_stale_attestation_is_derecognized() {
+    let (env, client, admin, bridge, asset) = setup();
+    let user = Address::generate(&env);
+    client.set_remote_collateral_config(&admin, &2_500, &MAX_AGE);
+    report(&client, &bridge, &user, &asset, 10_000);
+    client.cross_asset_borrow(&user, &Some(asset.clone()), &5_000);
+    assert!(!client.get_user_position_summary(&user).is_liquidatable);
+
+    advance_time(&env, MAX_AGE + 1);
+    let summary = client.get_user_position_summary(&user);
+    assert_eq!(summary.remote_collateral_value, 0);
+    assert!(summary.is_liquidatable);
+    // The entry is kept, only no longer counted
+    assert_eq!(client.get_remote_collateral(&user).len(), 1);
+
+    // A fresh report restores it
+    report(&client, &bridge, &user, &asset, 10_000);
+    let summary = client.get_user_position_summary(&user);
+    assert_eq!(summary.remote_collateral_value, 6_000);
+    assert!(!summary.is_liquidatable);
+}
+
+#[test]
+fn test_reports_replace_and_release_entries() {
+    let (env, client, _admin, bridge, asset) = setup();
+    let user = Address::generate(&env);
+
+    report(&client, &bridge, &user, &asset, 10_000);
+    report(&client, &bridge, &user, &asset, 4_000);
+    let entries = client.get_remote_collateral(&user);
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries.get(0).unwrap().amount, 4_000);
+
+    report(&client, &bridge, &user, &asset, 0);
+    assert!(client.get_remote_collateral(&user).is_empty());
+
+    // One entry per (network, asset); past the limit the report fails
+    for _ in 0..MAX_REMOTE_COLLATERAL_ENTRIES {
+        report(&client, &bridge, &user, &Address::generate(&env), 1_000);
+    }
+    let nonce = report(&client, &bridge, &user, &asset, 1_000);
+    assert_eq!(
+        client.get_bridge_message(&NETWORK, &nonce).unwrap().status,
+        BridgeMessageStatus::Failed
+    );
+    assert_eq!(
+        client.get_remote_collateral(&user).len(),
+        MAX_REMOTE_COLLATERAL_ENTRIES
+    );
+}
+
+#[test]
+fn test_remote_collateral_validation() {
+    let (env, client, admin, bridge, asset) = setup();
+    let user = Address::generate(&env);
+
+    for (haircut, max_age) in [
+        (-1, MAX_AGE),
+        (10_001, MAX_AGE),
+        (2_500, 0),
+        (2_500, MAX_REMOTE_COLLATERAL_AGE + 1),
+    ] {
+        assert_eq!(
+            client.try_set_remote_collateral_config(&admin, &haircut, &max_age),
+            Err(Ok(BridgeError::InvalidRemoteCollateralConfig))
+        );
+    }
+    assert_eq!(
+        client.try_set_remote_collateral_config(&user, &2_500, &MAX_AGE),
+        Err(Ok(BridgeError::NotAuthorized))
+    );
+    assert_eq!(client.get_remote_collateral_config(), None);
+
+    assert_eq!(
+        client.try_enqueue_remote_collateral(&bridge, &NETWORK, &user, &Some(asset.clone()), &-1),
+        Err(Ok(BridgeError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_enqueue_remote_collateral(&user, &NETWORK, &user, &Some(asset), &1_000),
+        Err(Ok(BridgeError::NotAuthorized))
+    );
+}
@@ -0,0 +1,168 @@
+//! Per-asset reserve factor tests.
+//!
+//! # Coverage
+//! - Borrow interest books the asset's reserve factor share at accrual
+//! - The cross-asset `AssetConfig.reserve_factor` is the stored factor
+//! - `claim_reserves` keeps per-asset claim accounting
+//! - Governance `ReserveFactor` proposals apply the factor
+//! - Factor bounds and admin-only access
+
+use crate::cross_asset::AssetConfig;
+use crate::deposit::DepositDataKey;
+use crate::reserve::ReserveError;
+use crate::risk_management::RiskManagementError;
+use crate::tests::test_helpers::setup_env_with_native_asset;
+use crate::types::{ProposalType, VoteType};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::StellarAssetClient,
+    Address, String,
+};
+
+const SECONDS_PER_YEAR: u64 = 365 * 86400;
+
+#[test]
+fn test_accrual_books_reserve_factor_share() {
+    let (env, id, client, admin, user, native) = setup_env_with_native_asset();
+    client.set_reserve_factor(&admin, &None, &2000);
+
+    client.deposit_collateral(&user, &None, &20_000);
+    client.borrow_asset(&user, &None, &5_000);
+    env.ledger().with_mut(|li| li.timestamp += SECONDS_PER_YEAR);
+    let interest = client.get_accrued_debt(&user) - client.get_user_report(&user).position.debt;
+    assert!(interest > 0);
+
+    let token = StellarAssetClient::new(&env, &native);
+    token.mint(&user, &1_000);
+    token.approve(&user, &id, &1_000, &(env.ledger().sequence() + 100));
+    client.repay_debt(&user, &None, &1_000);
+
+    let account = client.get_reserve_account(&None);
+    assert_eq!(account.interest_accrued, interest * 2000 / 10_000);
+    env.as_contract(&id, || {
+        assert_eq!(
+            env.storage()
+                .persistent()
+                .get::<DepositDataKey, i128>(&DepositDataKey::ProtocolReserve(None)),
+            Some(account.interest_accrued)
+        );
+    });
+}
+
+#[test]
+fn test_factor_is_stored_in_asset_config() {
+    let (env, _id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let asset = Address::generate(&env);
+    client.initialize_ca(&admin);
+    client.initialize_asset(
+        &Some(asset.clone()),
+        &AssetConfig {
+            asset: Some(asset.clone()),
+            collateral_factor: 7500,
+            liquidation_threshold: 8000,
+            reserve_factor: 1500,
+            max_supply: 0,
+            max_borrow: 0,
+            can_collateralize: true,
+            can_borrow: true,
+            price: 10_000_000,
+            price_updated_at: env.ledger().timestamp(),
+            is_isolated: false,
+            isolated_debt_ceiling: 0,
+        },
+    );
+    assert_eq!(client.get_reserve_factor(&Some(asset.clone())), 1500);
+
+    client.set_reserve_factor(&admin, &Some(asset.clone()), &3000);
+    assert_eq!(client.get_reserve_factor(&Some(asset.clone())), 3000);
+    assert_eq!(
+        client.get_asset_config(&Some(asset.clone())).reserve_factor,
+        3000
+    );
+}
+
+#[test]
+fn test_claim_tracks_per_asset_account() {
+    let (env, id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let asset = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    env.as_contract(&id, || {
+        env.storage().persistent().set(
+            &DepositDataKey::ProtocolReserve(Some(asset.clone())),
+            &1_000_i128,
+        );
+    });
+
+    env.ledger().with_mut(|li| li.timestamp = 5_000);
+    client.claim_reserves(&admin, &Some(asset.clone()), &treasury, &400);
+    assert_eq!(client.get_reserve_balance(&Some(asset.clone())), 600);
+    let account = client.get_reserve_account(&Some(asset.clone()));
+    assert_eq!(account.claimed, 400);
+    assert_eq!(account.last_claimed_at, 5_000);
+    // Other assets are untouched
+    assert_eq!(client.get_reserve_account(&None).claimed, 0);
+
+    assert_eq!(
+        client.try_claim_reserves(&admin, &Some(asset.clone()), &treasury, &0),
+        Err(Ok(RiskManagementError::InvalidParameter))
+    );
+    assert_eq!(
+        client.try_claim_reserves(&admin, &Some(asset.clone()), &treasury, &601),
+        Err(Ok(RiskManagementError::InvalidParameter))
+    );
+    assert_eq!(
+        client.try_claim_reserves(&treasury, &Some(asset), &treasury, &1),
+        Err(Ok(RiskManagementError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_governance_sets_reserve_factor() {
+    let (env, _id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let vote_token = env.register_stellar_asset_contract(admin.clone());
+    let proposer = Address::generate(&env);
+    StellarAssetClient::new(&env, &vote_token).mint(&proposer, &1_000);
+    client.gov_initialize(
+        &admin,
+        &vote_token,
+        &Some(259200),
+        &Some(86400),
+        &Some(400),
+        &Some(100),
+        &Some(604800),
+        &Some(5000),
+    );
+
+    let proposal_id = client.gov_create_proposal(
+        &proposer,
+        &ProposalType::ReserveFactor(None, 2500),
+        &String::from_str(&env, "Raise native reserve factor"),
+        &None,
+    );
+    env.ledger().with_mut(|li| li.timestamp += 1);
+    client.gov_vote(&proposer, &proposal_id, &VoteType::For);
+    env.ledger().with_mut(|li| li.timestamp += 259200);
+    client.gov_queue_proposal(&admin, &proposal_id);
+    env.ledger().with_mut(|li| li.timestamp += 86400);
+    client.gov_execute_proposal(&admin, &proposal_id);
+
+    assert_eq!(client.get_reserve_factor(&None), 2500);
+}
+
+#[test]
+fn test_set_reserve_factor_validation() {
+    let (env, _id, client, admin, _user, _native) = setup_env_with_native_asset();
+    assert_eq!(
+        client.try_set_reserve_factor(&admin, &None, &5001),
+        Err(Ok(ReserveError::InvalidReserveFactor))
+    );
+    assert_eq!(
+        client.try_set_reserve_factor(&admin, &None, &-1),
+        Err(Ok(ReserveError::InvalidReserveFactor))
+    );
+    assert_eq!(
+        client.try_set_reserve_factor(&Address::generate(&env), &None, &1000),
+        Err(Ok(ReserveError::Unauthorized))
+    );
+    assert_eq!(client.get_reserve_factor(&None), 1000);
+}
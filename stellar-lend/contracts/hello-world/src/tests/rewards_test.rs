@@ -0,0 +1,165 @@
+//! Liquidity mining rewards tests.
+//!
+//! # Coverage
+//! - Suppliers and borrowers accrue in proportion to their share over time
+//! - Withdrawals shrink a user's share from that moment on
+//! - Emissions stop once the funded budget is exhausted
+//! - Claims transfer the reward token and emit a claim event
+//! - Reward configuration is admin-only, needs the admin's authorization and
+//!   is validated
+
+use crate::rewards::{RewardSide, RewardsError};
+use crate::tests::test_helpers::{assert_authorized, setup_env_with_native_asset};
+use crate::HelloContractClient;
+use soroban_sdk::{
+    contracttype,
+    testutils::{Address as _, Events},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env, TryFromVal,
+};
+use stellarlend_testutils::advance_time;
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestRewardsClaimedEvent {
+    pub user: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+/// Register a reward token and fund the budget with `budget` from the admin
+fn fund(env: &Env, client: &HelloContractClient, admin: &Address, budget: i128) -> Address {
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    StellarAssetClient::new(env, &token).mint(admin, &budget);
+    client.set_reward_token(admin, &token);
+    assert_authorized(env, admin, &client.address, "set_reward_token");
+    assert_eq!(client.fund_rewards(admin, &budget), budget);
+    token
+}
+
+#[test]
+fn test_rewards_proportional_to_share() {
+    let (env, _id, client, admin, user, _native) = setup_env_with_native_asset();
+    let other = Address::generate(&env);
+    fund(&env, &client, &admin, 100_000);
+    client.set_emission_rate(&admin, &None, &RewardSide::Supply, &10);
+    client.set_emission_rate(&admin, &None, &RewardSide::Borrow, &4);
+
+    client.deposit_collateral(&user, &None, &1_000);
+    client.deposit_collateral(&other, &None, &3_000);
+    advance_time(&env, 100);
+    assert_eq!(client.get_pending_rewards(&user), 250);
+    assert_eq!(client.get_pending_rewards(&other), 750);
+
+    // The sole borrower takes the whole borrow-side emission
+    client.borrow_asset(&other, &None, &500);
+    advance_time(&env, 100);
+    assert_eq!(client.get_pending_rewards(&user), 500);
+    assert_eq!(client.get_pending_rewards(&other), 1_500 + 400);
+    assert_eq!(client.get_reward_budget(), 100_000 - 2_000 - 400);
+}
+
+#[test]
+fn test_withdrawal_reduces_share() {
+    let (env, _id, client, admin, user, _native) = setup_env_with_native_asset();
+    let other = Address::generate(&env);
+    fund(&env, &client, &admin, 100_000);
+    client.set_emission_rate(&admin, &None, &RewardSide::Supply, &10);
+
+    client.deposit_collateral(&user, &None, &1_000);
+    client.deposit_collateral(&other, &None, &1_000);
+    advance_time(&env, 100);
+    client.withdraw_collateral(&user, &None, &1_000);
+    advance_time(&env, 100);
+
+    assert_eq!(client.get_pending_rewards(&user), 500);
+    assert_eq!(client.get_pending_rewards(&other), 1_500);
+}
+
+#[test]
+fn test_emissions_capped_by_budget() {
+    let (env, _id, client, admin, user, _native) = setup_env_with_native_asset();
+    fund(&env, &client, &admin, 500);
+    client.set_emission_rate(&admin, &None, &RewardSide::Supply, &10);
+
+    client.deposit_collateral(&user, &None, &1_000);
+    advance_time(&env, 100);
+    assert_eq!(client.get_pending_rewards(&user), 500);
+    assert_eq!(client.claim_rewards(&user), 500);
+    assert_eq!(client.get_reward_budget(), 0);
+
+    advance_time(&env, 100);
+    assert_eq!(client.get_pending_rewards(&user), 0);
+}
+
+#[test]
+fn test_claim_transfers_rewards() {
+    let (env, _id, client, admin, user, _native) = setup_env_with_native_asset();
+    let token = fund(&env, &client, &admin, 10_000);
+    client.set_emission_rate(&admin, &None, &RewardSide::Supply, &3);
+
+    client.deposit_collateral(&user, &None, &1_000);
+    advance_time(&env, 50);
+    assert_eq!(client.claim_rewards(&user), 150);
+    let event = env
+        .events()
+        .all()
+        .iter()
+        .find_map(|(_contract, _topics, data)| {
+            TestRewardsClaimedEvent::try_from_val(&env, &data).ok()
+        });
+    assert_eq!(
+        event,
+        Some(TestRewardsClaimedEvent {
+            user: user.clone(),
+            token: token.clone(),
+            amount: 150,
+            timestamp: env.ledger().timestamp(),
+        })
+    );
+    assert_eq!(TokenClient::new(&env, &token).balance(&user), 150);
+    assert_eq!(
+        client.try_claim_rewards(&user),
+        Err(Ok(RewardsError::NothingToClaim))
+    );
+}
+
+#[test]
+fn test_reward_configuration_checks() {
+    let (env, _id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let outsider = Address::generate(&env);
+    assert_eq!(
+        client.try_fund_rewards(&admin, &100),
+        Err(Ok(RewardsError::RewardTokenNotSet))
+    );
+    assert_eq!(
+        client.try_set_emission_rate(&outsider, &None, &RewardSide::Supply, &1),
+        Err(Ok(RewardsError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_emission_rate(&admin, &None, &RewardSide::Supply, &-1),
+        Err(Ok(RewardsError::InvalidAmount))
+    );
+
+    let token = fund(&env, &client, &admin, 1_000);
+    assert_eq!(
+        client.try_set_reward_token(&admin, &token),
+        Err(Ok(RewardsError::RewardTokenAlreadySet))
+    );
+    assert_eq!(
+        client.try_fund_rewards(&outsider, &100),
+        Err(Ok(RewardsError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_fund_rewards(&admin, &0),
+        Err(Ok(RewardsError::InvalidAmount))
+    );
+
+    client.set_emission_rate(&admin, &None, &RewardSide::Borrow, &7);
+    assert_authorized(&env, &admin, &client.address, "set_emission_rate");
+    assert_eq!(client.get_emission_rate(&None, &RewardSide::Borrow), 7);
+    assert_eq!(client.get_emission_rate(&None, &RewardSide::Supply), 0);
+}
@@ -0,0 +1,307 @@
+//! External risk engine hook tests.
+//!
+//! # Coverage
+//! - Borrows and withdrawals at or above the threshold are sent to the engine,
+//!   which can approve or reject them
+//! - Operations below the threshold skip the engine
+//! - Engine failures follow the fail-open / fail-closed policy
+//! - Engines must be whitelisted by governance; de-whitelisting disables the hook
+//! - Configuration is admin only, needs the admin's authorization and is
+//!   validated
+
+use crate::borrow::BorrowError;
+use crate::risk_hook::{
+    RiskCheckRequest, RiskHookConfig, RiskHookDataKey, RiskHookError, RiskHookFailPolicy,
+};
+use crate::tests::test_helpers::{assert_authorized, setup_env_with_native_asset};
+use crate::types::{ProposalType, VoteType};
+use crate::withdraw::WithdrawError;
+use crate::HelloContractClient;
+use soroban_sdk::{
+    contract, contractimpl, contracttype,
+    testutils::{Address as _, Events, Ledger},
+    token::StellarAssetClient,
+    Address, Env, String, Symbol, TryFromVal, Vec,
+};
+
+/// Risk engine approving or rejecting every request, or trapping
+#[contract]
+pub struct MockRiskEngine;
+
+#[contractimpl]
+impl MockRiskEngine {
+    /// 0 = approve, 1 = reject, 2 = trap
+    pub fn set_mode(env: Env, mode: u32) {
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "mode"), &mode);
+    }
+
+    pub fn check_risk(env: Env, _request: RiskCheckRequest) -> bool {
+        let mode: u32 = env
+            .storage()
+            .instance()
+            .get(&Symbol::new(&env, "mode"))
+            .unwrap_or(0);
+        if mode == 2 {
+            panic!("engine failure");
+        }
+        mode == 0
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestRiskCheckEvent {
+    pub user: Address,
+    pub engine: Address,
+    pub operation: Symbol,
+    pub asset: Option<Address>,
+    pub amount: i128,
+    pub engine_failed: bool,
+    pub timestamp: u64,
+}
+
+fn risk_check_events(env: &Env) -> Vec<TestRiskCheckEvent> {
+    let mut events = Vec::new(env);
+    for (_contract, _topics, data) in env.events().all().iter() {
+        if let Ok(event) = TestRiskCheckEvent::try_from_val(env, &data) {
+            events.push_back(event);
+        }
+    }
+    events
+}
+
+/// Price XLM at $1 so amounts equal their accounting value, and register a
+/// whitelisted engine with a 10_000 threshold for both operations
+fn setup_hook(
+    env: &Env,
+    contract_id: &Address,
+    client: &HelloContractClient,
+    admin: &Address,
+    native: &Address,
+    mode: u32,
+    fail_policy: RiskHookFailPolicy,
+) -> Address {
+    client.update_price_feed(admin, native, &100_000_000, &8, &Address::generate(env));
+    let engine = env.register(MockRiskEngine, ());
+    MockRiskEngineClient::new(env, &engine).set_mode(&mode);
+    env.as_contract(contract_id, || {
+        env.storage()
+            .persistent()
+            .set(&RiskHookDataKey::Whitelisted(engine.clone()), &true);
+    });
+    client.set_risk_hook(
+        admin,
+        &Some(RiskHookConfig {
+            engine: engine.clone(),
+            borrow_threshold: 10_000,
+            withdraw_threshold: 10_000,
+            fail_policy,
+        }),
+    );
+    engine
+}
+
+#[test]
+fn test_approved_borrow_emits_risk_check() {
+    let (env, id, client, admin, user, native) = setup_env_with_native_asset();
+    let engine = setup_hook(
+        &env,
+        &id,
+        &client,
+        &admin,
+        &native,
+        0,
+        RiskHookFailPolicy::FailClosed,
+    );
+    client.deposit_collateral(&user, &None, &50_000);
+
+    client.borrow_asset(&user, &None, &10_000);
+    let events = risk_check_events(&env);
+    assert_eq!(events.len(), 1);
+    assert_eq!(
+        events.get_unchecked(0),
+        TestRiskCheckEvent {
+            user: user.clone(),
+            engine,
+            operation: Symbol::new(&env, "borrow"),
+            asset: None,
+            amount: 10_000,
+            engine_failed: false,
+            timestamp: env.ledger().timestamp(),
+        }
+    );
+}
+
+#[test]
+fn test_rejected_operations_fail() {
+    let (env, id, client, admin, user, native) = setup_env_with_native_asset();
+    client.deposit_collateral(&user, &None, &50_000);
+    setup_hook(
+        &env,
+        &id,
+        &client,
+        &admin,
+        &native,
+        1,
+        RiskHookFailPolicy::FailOpen,
+    );
+
+    assert_eq!(
+        client.try_borrow_asset(&user, &None, &10_000),
+        Err(Ok(BorrowError::RiskCheckRejected))
+    );
+    assert_eq!(
+        client.try_withdraw_collateral(&user, &None, &20_000),
+        Err(Ok(WithdrawError::RiskCheckRejected))
+    );
+}
+
+#[test]
+fn test_small_operations_skip_engine() {
+    let (env, id, client, admin, user, native) = setup_env_with_native_asset();
+    client.deposit_collateral(&user, &None, &50_000);
+    setup_hook(
+        &env,
+        &id,
+        &client,
+        &admin,
+        &native,
+        1,
+        RiskHookFailPolicy::FailClosed,
+    );
+
+    client.borrow_asset(&user, &None, &9_999);
+    assert_eq!(risk_check_events(&env).len(), 0);
+    client.withdraw_collateral(&user, &None, &9_999);
+    assert_eq!(risk_check_events(&env).len(), 0);
+}
+
+#[test]
+fn test_engine_failure_follows_policy() {
+    let (env, id, client, admin, user, native) = setup_env_with_native_asset();
+    client.deposit_collateral(&user, &None, &50_000);
+    let engine = setup_hook(
+        &env,
+        &id,
+        &client,
+        &admin,
+        &native,
+        2,
+        RiskHookFailPolicy::FailOpen,
+    );
+
+    client.borrow_asset(&user, &None, &10_000);
+    let events = risk_check_events(&env);
+    assert_eq!(events.len(), 1);
+    assert!(events.get_unchecked(0).engine_failed);
+
+    client.set_risk_hook(
+        &admin,
+        &Some(RiskHookConfig {
+            engine,
+            borrow_threshold: 10_000,
+            withdraw_threshold: 10_000,
+            fail_policy: RiskHookFailPolicy::FailClosed,
+        }),
+    );
+    assert_eq!(
+        client.try_borrow_asset(&user, &None, &10_000),
+        Err(Ok(BorrowError::RiskCheckRejected))
+    );
+}
+
+#[test]
+fn test_governance_whitelists_engine() {
+    let (env, id, client, admin, user, _native) = setup_env_with_native_asset();
+    let engine = env.register(MockRiskEngine, ());
+    MockRiskEngineClient::new(&env, &engine).set_mode(&1);
+    let config = RiskHookConfig {
+        engine: engine.clone(),
+        borrow_threshold: 0,
+        withdraw_threshold: 0,
+        fail_policy: RiskHookFailPolicy::FailClosed,
+    };
+    assert_eq!(
+        client.try_set_risk_hook(&admin, &Some(config.clone())),
+        Err(Ok(RiskHookError::EngineNotWhitelisted))
+    );
+
+    let vote_token = env.register_stellar_asset_contract(admin.clone());
+    let proposer = Address::generate(&env);
+    StellarAssetClient::new(&env, &vote_token).mint(&proposer, &1_000);
+    client.gov_initialize(
+        &admin,
+        &vote_token,
+        &Some(259200),
+        &Some(86400),
+        &Some(400),
+        &Some(100),
+        &Some(604800),
+        &Some(5000),
+    );
+    let execute = |whitelisted: bool| {
+        let proposal_id = client.gov_create_proposal(
+            &proposer,
+            &ProposalType::RiskEngine(engine.clone(), whitelisted),
+            &String::from_str(&env, "Risk engine"),
+            &None,
+        );
+        env.ledger().with_mut(|li| li.timestamp += 1);
+        client.gov_vote(&proposer, &proposal_id, &VoteType::For);
+        env.ledger().with_mut(|li| li.timestamp += 259200);
+        client.gov_queue_proposal(&admin, &proposal_id);
+        env.ledger().with_mut(|li| li.timestamp += 86400);
+        client.gov_execute_proposal(&admin, &proposal_id);
+    };
+
+    execute(true);
+    assert!(client.is_risk_engine_whitelisted(&engine));
+    client.set_risk_hook(&admin, &Some(config.clone()));
+    assert_authorized(&env, &admin, &id, "set_risk_hook");
+    assert_eq!(client.get_risk_hook(), Some(config));
+    client.deposit_collateral(&user, &None, &50_000);
+    assert_eq!(
+        client.try_borrow_asset(&user, &None, &1_000),
+        Err(Ok(BorrowError::RiskCheckRejected))
+    );
+
+    // Removing the engine from the whitelist disables the hook
+    execute(false);
+    assert!(!client.is_risk_engine_whitelisted(&engine));
+    client.borrow_asset(&user, &None, &1_000);
+}
+
+#[test]
+fn test_configuration_validated() {
+    let (env, id, client, admin, _user, native) = setup_env_with_native_asset();
+    let engine = setup_hook(
+        &env,
+        &id,
+        &client,
+        &admin,
+        &native,
+        0,
+        RiskHookFailPolicy::FailOpen,
+    );
+    let config = RiskHookConfig {
+        engine,
+        borrow_threshold: -1,
+        withdraw_threshold: 0,
+        fail_policy: RiskHookFailPolicy::FailOpen,
+    };
+
+    let outsider = Address::generate(&env);
+    assert_eq!(
+        client.try_set_risk_hook(&outsider, &None),
+        Err(Ok(RiskHookError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_risk_hook(&admin, &Some(config)),
+        Err(Ok(RiskHookError::InvalidThreshold))
+    );
+
+    client.set_risk_hook(&admin, &None);
+    assert_eq!(client.get_risk_hook(), None);
+}
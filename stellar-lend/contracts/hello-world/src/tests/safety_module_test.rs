@@ -0,0 +1,215 @@
+//! Safety module staking tests.
+//!
+//! # Coverage
+//! - Unstaking waits for the cooldown and must happen within the window
+//! - Slashing needs a shortfall, is capped per call and hits stakers pro rata
+//! - Stakers take their share of protocol fees and claim it
+//! - Configuration and slashing are admin-only, need the admin's
+//!   authorization and are bounded
+
+use crate::bad_debt::BadDebtDataKey;
+use crate::safety_module::{SafetyModuleConfig, SafetyModuleError};
+use crate::tests::test_helpers::{assert_authorized, setup_env_with_native_asset};
+use crate::HelloContractClient;
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+use stellarlend_testutils::advance_time;
+
+/// Register the staking token and give each staker 10_000
+fn setup_staking(
+    env: &Env,
+    client: &HelloContractClient,
+    admin: &Address,
+    stakers: &[&Address],
+) -> Address {
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    for staker in stakers {
+        StellarAssetClient::new(env, &token).mint(staker, &10_000);
+    }
+    client.set_staking_token(admin, &token);
+    token
+}
+
+#[test]
+fn test_unstake_cooldown_and_window() {
+    let (env, _id, client, admin, user, _native) = setup_env_with_native_asset();
+    let token = setup_staking(&env, &client, &admin, &[&user]);
+    let config = client.get_safety_module_config();
+
+    assert_eq!(client.stake(&user, &1_000), 1_000);
+    assert_eq!(
+        client.try_unstake(&user, &400),
+        Err(Ok(SafetyModuleError::CooldownNotStarted))
+    );
+    client.start_unstake_cooldown(&user);
+    let stake = client.get_stake(&user);
+    assert_eq!(stake.amount, 1_000);
+    assert_eq!(
+        stake.unstake_from,
+        Some(env.ledger().timestamp() + config.cooldown_seconds)
+    );
+
+    advance_time(&env, config.cooldown_seconds - 1);
+    assert_eq!(
+        client.try_unstake(&user, &400),
+        Err(Ok(SafetyModuleError::CooldownActive))
+    );
+    advance_time(&env, 1);
+    assert_eq!(
+        client.try_unstake(&user, &1_001),
+        Err(Ok(SafetyModuleError::InsufficientStake))
+    );
+    assert_eq!(client.unstake(&user, &400), 400);
+    assert_eq!(TokenClient::new(&env, &token).balance(&user), 9_400);
+    assert_eq!(client.get_stake(&user).cooldown_start, None);
+
+    // A cooldown left past its window must be restarted
+    client.start_unstake_cooldown(&user);
+    advance_time(&env, config.cooldown_seconds + config.unstake_window + 1);
+    assert_eq!(
+        client.try_unstake(&user, &100),
+        Err(Ok(SafetyModuleError::UnstakeWindowExpired))
+    );
+}
+
+#[test]
+fn test_slash_covers_shortfall_pro_rata() {
+    let (env, id, client, admin, user, _native) = setup_env_with_native_asset();
+    let other = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let token = setup_staking(&env, &client, &admin, &[&user, &other]);
+    client.stake(&user, &1_000);
+    client.stake(&other, &3_000);
+
+    assert_eq!(
+        client.try_slash_safety_module(&admin, &None, &500, &recipient),
+        Err(Ok(SafetyModuleError::NoShortfall))
+    );
+    env.as_contract(&id, || {
+        env.storage()
+            .persistent()
+            .set(&BadDebtDataKey::Shortfall(None), &5_000i128);
+    });
+
+    // Default cap: 30% of the 4_000 staked
+    assert_eq!(client.get_safety_module_state().max_slashable, 1_200);
+    assert_eq!(
+        client.try_slash_safety_module(&admin, &None, &1_201, &recipient),
+        Err(Ok(SafetyModuleError::SlashTooLarge))
+    );
+    assert_eq!(
+        client.try_slash_safety_module(&user, &None, &100, &recipient),
+        Err(Ok(SafetyModuleError::Unauthorized))
+    );
+    client.slash_safety_module(&admin, &None, &1_000, &recipient);
+    assert_authorized(&env, &admin, &id, "slash_safety_module");
+
+    assert_eq!(TokenClient::new(&env, &token).balance(&recipient), 1_000);
+    assert_eq!(client.get_safety_module_state().total_staked, 3_000);
+    assert_eq!(client.get_stake(&user).amount, 750);
+    assert_eq!(client.get_stake(&other).amount, 2_250);
+
+    // New stake buys shares at the slashed rate
+    assert_eq!(client.stake(&user, &750), 1_000);
+    assert_eq!(client.get_stake(&user).amount, 1_500);
+}
+
+#[test]
+fn test_stakers_share_protocol_fees() {
+    let (env, id, client, admin, user, _native) = setup_env_with_native_asset();
+    let staker = Address::generate(&env);
+    setup_staking(&env, &client, &admin, &[&staker]);
+    assert_authorized(&env, &admin, &id, "set_staking_token");
+    client.stake(&staker, &1_000);
+    client.set_safety_module_config(
+        &admin,
+        &SafetyModuleConfig {
+            fee_share_bps: 5_000,
+            ..client.get_safety_module_config()
+        },
+    );
+    assert_authorized(&env, &admin, &id, "set_safety_module_config");
+
+    let asset_token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let asset = Some(asset_token.clone());
+    StellarAssetClient::new(&env, &asset_token).mint(&user, &10_000);
+    TokenClient::new(&env, &asset_token).approve(
+        &user,
+        &id,
+        &10_000,
+        &(env.ledger().sequence() + 100),
+    );
+    client.deposit_collateral(&user, &asset, &10_000);
+    client.set_fee_collector(&admin, &Address::generate(&env));
+    client.set_fee_switch(&admin, &true);
+    client.set_asset_fees(
+        &admin,
+        &asset,
+        &crate::fees::FeeConfig {
+            origination_fee_bps: 0,
+            flash_loan_fee_bps: None,
+            withdrawal_fee_bps: 100,
+        },
+    );
+
+    // 50 withdrawal fee, split evenly with the collector
+    client.withdraw_collateral(&user, &asset, &5_000);
+    assert_eq!(client.get_accrued_fees(&asset), 25);
+    assert_eq!(client.get_pending_staking_fees(&staker, &asset), 25);
+    assert_eq!(client.claim_staking_fees(&staker, &asset), 25);
+    assert_eq!(TokenClient::new(&env, &asset_token).balance(&staker), 25);
+    assert_eq!(
+        client.try_claim_staking_fees(&staker, &asset),
+        Err(Ok(SafetyModuleError::NothingToClaim))
+    );
+}
+
+#[test]
+fn test_safety_module_configuration_checks() {
+    let (env, _id, client, admin, user, _native) = setup_env_with_native_asset();
+    assert_eq!(
+        client.try_stake(&user, &100),
+        Err(Ok(SafetyModuleError::StakingTokenNotSet))
+    );
+    let token = setup_staking(&env, &client, &admin, &[&user]);
+    assert_eq!(
+        client.try_set_staking_token(&admin, &token),
+        Err(Ok(SafetyModuleError::StakingTokenAlreadySet))
+    );
+
+    let config = client.get_safety_module_config();
+    assert_eq!(
+        client.try_set_safety_module_config(&user, &config),
+        Err(Ok(SafetyModuleError::Unauthorized))
+    );
+    for invalid in [
+        SafetyModuleConfig {
+            cooldown_seconds: 0,
+            ..config.clone()
+        },
+        SafetyModuleConfig {
+            max_slash_bps: 5_001,
+            ..config.clone()
+        },
+        SafetyModuleConfig {
+            fee_share_bps: -1,
+            ..config.clone()
+        },
+    ] {
+        assert_eq!(
+            client.try_set_safety_module_config(&admin, &invalid),
+            Err(Ok(SafetyModuleError::InvalidConfig))
+        );
+    }
+    assert_eq!(
+        client.try_start_unstake_cooldown(&user),
+        Err(Ok(SafetyModuleError::InsufficientStake))
+    );
+}
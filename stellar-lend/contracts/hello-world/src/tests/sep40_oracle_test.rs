@@ -0,0 +1,161 @@
+//! SEP-40 pull oracle adapter tests.
+//!
+//! # Coverage
+//! - Prices are pulled from the registered SEP-40 oracle and rescaled
+//! - Pulled prices are cached for the cache TTL
+//! - Stale, missing or failing pulls fall back to the pushed price feed
+//! - Only the admin registers sources, with their authorization; invalid
+//!   sources are rejected
+
+use crate::oracle::{OracleError, Sep40Asset, Sep40Source};
+use crate::tests::test_helpers::assert_authorized;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env, Symbol};
+use stellarlend_testutils::{
+    advance_time, register_mock_sep40_oracle, set_time, MockSep40OracleClient,
+    Sep40Asset as MockAsset,
+};
+
+/// 1.5 with the mock oracle's 14 decimals
+const SEP40_PRICE: i128 = 150_000_000_000_000;
+/// 1.5 with 8 decimals
+const PRICE: i128 = 150_000_000;
+
+struct Setup<'a> {
+    client: HelloContractClient<'a>,
+    admin: Address,
+    asset: Address,
+    sep40: MockSep40OracleClient<'a>,
+}
+
+fn setup(e: &Env) -> Setup<'_> {
+    e.mock_all_auths();
+    set_time(e, 10_000);
+    let id = e.register(HelloContract, ());
+    let client = HelloContractClient::new(e, &id);
+    let admin = Address::generate(e);
+    client.initialize(&admin);
+    let sep40 = MockSep40OracleClient::new(e, &register_mock_sep40_oracle(e, 14));
+    Setup {
+        client,
+        admin,
+        asset: Address::generate(e),
+        sep40,
+    }
+}
+
+fn register(s: &Setup, feed_asset: Sep40Asset) {
+    s.client.set_sep40_source(
+        &s.admin,
+        &s.asset,
+        &Some(Sep40Source {
+            oracle: s.sep40.address.clone(),
+            feed_asset,
+            price_decimals: 8,
+        }),
+    );
+}
+
+#[test]
+fn test_pulls_and_rescales_price() {
+    let e = Env::default();
+    let s = setup(&e);
+    s.sep40
+        .set_lastprice(&MockAsset::Stellar(s.asset.clone()), &SEP40_PRICE, &10_000);
+    register(&s, Sep40Asset::Stellar(s.asset.clone()));
+    assert_eq!(s.client.get_price(&s.asset), PRICE);
+
+    // Off-chain tickers are supported as feed assets
+    let ticker = Symbol::new(&e, "BTC");
+    s.sep40.set_lastprice(
+        &MockAsset::Other(ticker.clone()),
+        &(2 * SEP40_PRICE),
+        &10_000,
+    );
+    register(&s, Sep40Asset::Other(ticker));
+    assert_eq!(s.client.get_price(&s.asset), 2 * PRICE);
+}
+
+#[test]
+fn test_pulled_price_is_cached() {
+    let e = Env::default();
+    let s = setup(&e);
+    let feed = MockAsset::Stellar(s.asset.clone());
+    s.sep40.set_lastprice(&feed, &SEP40_PRICE, &10_000);
+    register(&s, Sep40Asset::Stellar(s.asset.clone()));
+    assert_eq!(s.client.get_price(&s.asset), PRICE);
+
+    // Default cache TTL is five minutes
+    s.sep40.set_lastprice(&feed, &(2 * SEP40_PRICE), &10_100);
+    advance_time(&e, 100);
+    assert_eq!(s.client.get_price(&s.asset), PRICE);
+    advance_time(&e, 201);
+    assert_eq!(s.client.get_price(&s.asset), 2 * PRICE);
+}
+
+#[test]
+fn test_falls_back_to_pushed_price() {
+    let e = Env::default();
+    let s = setup(&e);
+    let oracle = Address::generate(&e);
+    let pushed = PRICE * 2;
+    s.client
+        .update_price_feed(&s.admin, &s.asset, &pushed, &8, &oracle);
+
+    // No record for the feed asset
+    register(&s, Sep40Asset::Stellar(s.asset.clone()));
+    assert_eq!(s.client.get_price(&s.asset), pushed);
+
+    // Record older than the one hour staleness limit
+    s.sep40
+        .set_lastprice(&MockAsset::Stellar(s.asset.clone()), &SEP40_PRICE, &6_000);
+    register(&s, Sep40Asset::Stellar(s.asset.clone()));
+    assert_eq!(s.client.get_price(&s.asset), pushed);
+
+    // Oracle address that is not a SEP-40 contract
+    s.client.set_sep40_source(
+        &s.admin,
+        &s.asset,
+        &Some(Sep40Source {
+            oracle: Address::generate(&e),
+            feed_asset: Sep40Asset::Stellar(s.asset.clone()),
+            price_decimals: 8,
+        }),
+    );
+    assert_eq!(s.client.get_price(&s.asset), pushed);
+}
+
+#[test]
+fn test_source_registration_is_admin_only() {
+    let e = Env::default();
+    let s = setup(&e);
+    let source = Sep40Source {
+        oracle: s.sep40.address.clone(),
+        feed_asset: Sep40Asset::Stellar(s.asset.clone()),
+        price_decimals: 8,
+    };
+
+    assert_eq!(
+        s.client
+            .try_set_sep40_source(&Address::generate(&e), &s.asset, &Some(source.clone())),
+        Err(Ok(OracleError::Unauthorized))
+    );
+    assert_eq!(
+        s.client.try_set_sep40_source(
+            &s.admin,
+            &s.asset,
+            &Some(Sep40Source {
+                price_decimals: 19,
+                ..source.clone()
+            })
+        ),
+        Err(Ok(OracleError::InvalidOracle))
+    );
+
+    s.client
+        .set_sep40_source(&s.admin, &s.asset, &Some(source.clone()));
+    assert_authorized(&e, &s.admin, &s.client.address, "set_sep40_source");
+    assert_eq!(s.client.get_sep40_source(&s.asset), Some(source));
+    s.client.set_sep40_source(&s.admin, &s.asset, &None);
+    assert_eq!(s.client.get_sep40_source(&s.asset), None);
+}
@@ -0,0 +1,97 @@
+//! Simulation mode tests.
+//!
+//! # Coverage
+//! - `sim_advance_time` compounds borrow interest like a real ledger advance
+//! - Aged price feeds and caches go stale
+//! - `sim_set_price` bypasses the deviation limit
+//! - Entrypoints are admin only
+
+use crate::oracle::OracleError;
+use crate::sim::SimError;
+use crate::tests::test_helpers::setup_env_with_native_asset;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Vec,
+};
+
+const SECONDS_PER_YEAR: u64 = 365 * 86400;
+const PRICE: i128 = 100_000_000;
+/// Ledger time to start from; time can only be simulated back to zero
+const START: u64 = 1_700_000_000;
+
+#[test]
+fn test_advance_time_matches_ledger_advance() {
+    let (sim_env, _id, sim, admin, sim_user, _native) = setup_env_with_native_asset();
+    let (env, _id, client, _admin, user, _native) = setup_env_with_native_asset();
+    sim_env.ledger().with_mut(|li| li.timestamp = START);
+    env.ledger().with_mut(|li| li.timestamp = START);
+    for (c, u) in [(&sim, &sim_user), (&client, &user)] {
+        c.deposit_collateral(u, &None, &20_000);
+        c.borrow_asset(u, &None, &5_000);
+    }
+
+    env.ledger().with_mut(|li| li.timestamp += SECONDS_PER_YEAR);
+    let index = sim.sim_advance_time(&admin, &SECONDS_PER_YEAR, &Vec::new(&sim_env));
+
+    assert_eq!(sim_env.ledger().timestamp(), START);
+    assert_eq!(index, client.get_borrow_index());
+    assert_eq!(
+        sim.get_accrued_debt(&sim_user),
+        client.get_accrued_debt(&user)
+    );
+}
+
+#[test]
+fn test_advance_time_ages_prices() {
+    let (env, _id, client, admin, _user, _native) = setup_env_with_native_asset();
+    env.ledger().with_mut(|li| li.timestamp = START);
+    let asset = Address::generate(&env);
+    client.update_price_feed(&admin, &asset, &PRICE, &8, &Address::generate(&env));
+    assert_eq!(client.get_price(&asset), PRICE);
+
+    // Default staleness limit is one hour
+    client.sim_advance_time(&admin, &3_601, &vec![&env, asset.clone()]);
+    assert_eq!(
+        client.try_get_price(&asset),
+        Err(Ok(OracleError::StalePrice))
+    );
+}
+
+#[test]
+fn test_set_price_bypasses_validation() {
+    let (env, _id, client, admin, _user, _native) = setup_env_with_native_asset();
+    env.ledger().with_mut(|li| li.timestamp = START);
+    let asset = Address::generate(&env);
+    client.update_price_feed(&admin, &asset, &PRICE, &8, &Address::generate(&env));
+
+    // A 90% crash is beyond the deviation limit for pushed updates
+    assert!(client
+        .try_update_price_feed(&admin, &asset, &(PRICE / 10), &8, &admin)
+        .is_err());
+    client.sim_set_price(&admin, &asset, &(PRICE / 10), &8);
+    assert_eq!(client.get_price(&asset), PRICE / 10);
+    assert_eq!(
+        client.get_price_history(&asset).last().unwrap().price,
+        PRICE / 10
+    );
+}
+
+#[test]
+fn test_sim_entrypoints_are_admin_only() {
+    let (env, _id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let stranger = Address::generate(&env);
+    let asset = Address::generate(&env);
+
+    assert_eq!(
+        client.try_sim_advance_time(&stranger, &60, &Vec::new(&env)),
+        Err(Ok(SimError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_sim_set_price(&stranger, &asset, &PRICE, &8),
+        Err(Ok(SimError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_sim_set_price(&admin, &asset, &0, &8),
+        Err(Ok(SimError::InvalidPrice))
+    );
+}
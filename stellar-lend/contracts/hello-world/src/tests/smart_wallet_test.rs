@@ -0,0 +1,203 @@
+//! Custom account (smart wallet) compatibility tests.
+//!
+//! Auth is enforced here instead of mocked, so every operation really goes
+//! through the wallet's `__check_auth`.
+//!
+//! # Coverage
+//! - Deposits and borrows from a contract account are authorized by its
+//!   `__check_auth`
+//! - A batch needs exactly one authorization from the wallet
+//! - Delegated deposits authorize the paying wallet only
+//! - A wallet policy rejecting the call blocks the operation
+
+use crate::batch::ProtocolAction;
+use crate::tests::test_helpers::setup_env_with_native_asset;
+use soroban_sdk::{
+    auth::Context,
+    contract, contracterror, contractimpl, symbol_short,
+    testutils::{Address as _, MockAuthInvoke},
+    vec,
+    xdr::{
+        ScVal, SorobanAddressCredentials, SorobanAuthorizationEntry, SorobanAuthorizedInvocation,
+        SorobanCredentials,
+    },
+    Address, BytesN, Env, IntoVal, Val, Vec,
+};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum WalletError {
+    NotAllowed = 1,
+}
+
+/// Contract account that only authorizes calls to one allowed contract and
+/// counts the authorizations it grants
+#[contract]
+pub struct SmartWallet;
+
+#[contractimpl]
+impl SmartWallet {
+    pub fn __constructor(env: Env, allowed: Address) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("allowed"), &allowed);
+    }
+
+    pub fn checks(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("checks"))
+            .unwrap_or(0)
+    }
+
+    #[allow(non_snake_case)]
+    pub fn __check_auth(
+        env: Env,
+        _signature_payload: BytesN<32>,
+        _signature: Val,
+        auth_contexts: Vec<Context>,
+    ) -> Result<(), WalletError> {
+        let allowed: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("allowed"))
+            .unwrap();
+        for context in auth_contexts.iter() {
+            match context {
+                Context::Contract(call) if call.contract == allowed => {}
+                _ => return Err(WalletError::NotAllowed),
+            }
+        }
+        let checks = Self::checks(env.clone()) + 1;
+        env.storage()
+            .instance()
+            .set(&symbol_short!("checks"), &checks);
+        Ok(())
+    }
+}
+
+/// Provide the wallet's authorization for a single call to `contract`
+fn authorize(
+    env: &Env,
+    wallet: &Address,
+    nonce: i64,
+    contract: &Address,
+    fn_name: &str,
+    args: Vec<Val>,
+) {
+    let root_invocation: SorobanAuthorizedInvocation = (&MockAuthInvoke {
+        contract,
+        fn_name,
+        args,
+        sub_invokes: &[],
+    })
+        .into();
+    env.set_auths(&[SorobanAuthorizationEntry {
+        credentials: SorobanCredentials::Address(SorobanAddressCredentials {
+            address: wallet.into(),
+            nonce,
+            signature_expiration_ledger: env.ledger().sequence() + 100,
+            signature: ScVal::Void,
+        }),
+        root_invocation,
+    }]);
+}
+
+#[test]
+fn test_smart_wallet_deposit_and_borrow() {
+    let (env, id, client, _admin, _user, _native) = setup_env_with_native_asset();
+    let wallet = env.register(SmartWallet, (id.clone(),));
+    let wallet_client = SmartWalletClient::new(&env, &wallet);
+
+    authorize(
+        &env,
+        &wallet,
+        1,
+        &id,
+        "deposit_collateral",
+        (&wallet, None::<Address>, 20_000_i128).into_val(&env),
+    );
+    client.deposit_collateral(&wallet, &None, &20_000);
+
+    authorize(
+        &env,
+        &wallet,
+        2,
+        &id,
+        "borrow_asset",
+        (&wallet, None::<Address>, 5_000_i128).into_val(&env),
+    );
+    client.borrow_asset(&wallet, &None, &5_000);
+
+    assert_eq!(wallet_client.checks(), 2);
+    let position = client.get_user_report(&wallet).position;
+    assert_eq!((position.collateral, position.debt), (20_000, 5_000));
+}
+
+#[test]
+fn test_smart_wallet_batch_authorized_once() {
+    let (env, id, client, _admin, _user, _native) = setup_env_with_native_asset();
+    let wallet = env.register(SmartWallet, (id.clone(),));
+    let actions = vec![
+        &env,
+        ProtocolAction::Deposit(None, 20_000),
+        ProtocolAction::Borrow(None, 5_000),
+    ];
+
+    authorize(
+        &env,
+        &wallet,
+        1,
+        &id,
+        "execute_batch",
+        (&wallet, actions.clone()).into_val(&env),
+    );
+    assert_eq!(
+        client.execute_batch(&wallet, &actions),
+        vec![&env, 20_000, 5_000]
+    );
+    assert_eq!(SmartWalletClient::new(&env, &wallet).checks(), 1);
+}
+
+#[test]
+fn test_smart_wallet_delegated_deposit() {
+    let (env, id, client, _admin, _user, _native) = setup_env_with_native_asset();
+    let wallet = env.register(SmartWallet, (id.clone(),));
+    let beneficiary = Address::generate(&env);
+
+    authorize(
+        &env,
+        &wallet,
+        1,
+        &id,
+        "deposit_collateral_for",
+        (&wallet, &beneficiary, None::<Address>, 8_000_i128).into_val(&env),
+    );
+    client.deposit_collateral_for(&wallet, &beneficiary, &None, &8_000);
+
+    assert_eq!(SmartWalletClient::new(&env, &wallet).checks(), 1);
+    assert_eq!(
+        client.get_user_report(&beneficiary).position.collateral,
+        8_000
+    );
+}
+
+#[test]
+fn test_smart_wallet_policy_rejects_call() {
+    let (env, id, client, _admin, _user, _native) = setup_env_with_native_asset();
+    let wallet = env.register(SmartWallet, (Address::generate(&env),));
+
+    authorize(
+        &env,
+        &wallet,
+        1,
+        &id,
+        "deposit_collateral",
+        (&wallet, None::<Address>, 20_000_i128).into_val(&env),
+    );
+    assert!(client
+        .try_deposit_collateral(&wallet, &None, &20_000)
+        .is_err());
+    assert_eq!(SmartWalletClient::new(&env, &wallet).checks(), 0);
+}
@@ -0,0 +1,183 @@
+//! State snapshot export tests.
+//!
+//! # Coverage
+//! - Protocol snapshot reflects admin, pause state and aggregate analytics
+//! - Users are indexed once, in first-deposit order
+//! - Users entering through `*_for` deposits, cross-asset markets or
+//!   isolated positions are indexed too
+//! - User entries carry supply shares and cross-asset positions
+//! - Asset ledger pages carry supplied totals and reserves
+//! - User pages are bounded, stable and respect offset/limit
+//! - Page size is capped at `MAX_EXPORT_PAGE_SIZE`
+//! - Out-of-range offsets return an empty page
+
+use super::test_helpers::setup_env_with_native_asset;
+use crate::cross_asset::{AssetConfig, AssetKey};
+use crate::snapshot::{index_user, MAX_EXPORT_PAGE_SIZE};
+use soroban_sdk::{testutils::Address as _, Address};
+
+#[test]
+fn test_protocol_snapshot_reflects_state() {
+    let (env, _id, client, admin, user, _native) = setup_env_with_native_asset();
+    client.deposit_collateral(&user, &None, &1_000);
+
+    let snapshot = client.export_protocol_snapshot();
+
+    assert_eq!(snapshot.admin, Some(admin));
+    assert!(!snapshot.emergency_paused);
+    assert!(snapshot.risk_params.is_some());
+    assert!(snapshot.interest_rate_config.is_some());
+    assert_eq!(snapshot.protocol_analytics.total_deposits, 1_000);
+    assert_eq!(snapshot.user_count, 1);
+    assert_eq!(snapshot.timestamp, env.ledger().timestamp());
+}
+
+#[test]
+fn test_users_indexed_once_in_order() {
+    let (env, _id, client, _admin, user, _native) = setup_env_with_native_asset();
+    let second = Address::generate(&env);
+
+    client.deposit_collateral(&user, &None, &100);
+    client.deposit_collateral(&second, &None, &200);
+    client.deposit_collateral(&user, &None, &300);
+
+    let page = client.export_user_positions(&0, &10);
+    assert_eq!(page.len(), 2);
+
+    let first_entry = page.get(0).unwrap();
+    assert_eq!(first_entry.index, 0);
+    assert_eq!(first_entry.user, user);
+    assert_eq!(first_entry.collateral_balance, 400);
+    assert_eq!(first_entry.position.unwrap().collateral, 400);
+
+    let second_entry = page.get(1).unwrap();
+    assert_eq!(second_entry.index, 1);
+    assert_eq!(second_entry.user, second);
+    assert_eq!(second_entry.collateral_balance, 200);
+}
+
+#[test]
+fn test_on_behalf_deposit_indexes_beneficiary() {
+    let (env, _id, client, _admin, user, _native) = setup_env_with_native_asset();
+    let payer = Address::generate(&env);
+
+    client.deposit_collateral_for(&payer, &user, &None, &500);
+
+    let page = client.export_user_positions(&0, &10);
+    assert_eq!(page.len(), 1);
+    let entry = page.get(0).unwrap();
+    assert_eq!(entry.user, user);
+    assert_eq!(entry.collateral_balance, 500);
+    assert!(entry.supply_shares > 0);
+}
+
+#[test]
+fn test_cross_asset_position_indexes_user() {
+    let (env, _id, client, admin, user, _native) = setup_env_with_native_asset();
+    let asset = Address::generate(&env);
+    client.initialize_ca(&admin);
+    client.initialize_asset(
+        &Some(asset.clone()),
+        &AssetConfig {
+            asset: Some(asset.clone()),
+            collateral_factor: 7500,
+            liquidation_threshold: 8000,
+            reserve_factor: 1000,
+            max_supply: 0,
+            max_borrow: 0,
+            can_collateralize: true,
+            can_borrow: true,
+            price: 10_000_000,
+            price_updated_at: env.ledger().timestamp(),
+            is_isolated: false,
+            isolated_debt_ceiling: 0,
+        },
+    );
+
+    client.cross_asset_deposit(&user, &Some(asset.clone()), &1_000);
+
+    let page = client.export_user_positions(&0, &10);
+    assert_eq!(page.len(), 1);
+    let entry = page.get(0).unwrap();
+    assert_eq!(entry.user, user);
+    assert_eq!(entry.position, None);
+    assert_eq!(entry.asset_positions.len(), 1);
+    let asset_entry = entry.asset_positions.get(0).unwrap();
+    assert_eq!(asset_entry.asset, AssetKey::Token(asset));
+    assert_eq!(asset_entry.position.collateral, 1_000);
+}
+
+#[test]
+fn test_isolated_position_account_is_indexed() {
+    let (_env, _id, client, _admin, user, _native) = setup_env_with_native_asset();
+
+    let position_id = client.open_position(&user);
+
+    let page = client.export_user_positions(&0, &10);
+    assert_eq!(page.len(), 1);
+    assert_eq!(
+        page.get(0).unwrap().user,
+        client.get_position_account(&position_id)
+    );
+}
+
+#[test]
+fn test_asset_ledger_export() {
+    let (_env, _id, client, _admin, user, _native) = setup_env_with_native_asset();
+    client.deposit_collateral(&user, &None, &1_000);
+
+    let snapshot = client.export_protocol_snapshot();
+    assert_eq!(snapshot.ledger_asset_count, 1);
+    assert_eq!(snapshot.supply_pool.total_underlying, 1_000);
+
+    let page = client.export_asset_ledgers(&0, &10);
+    assert_eq!(page.len(), 1);
+    let entry = page.get(0).unwrap();
+    assert_eq!(entry.index, 0);
+    assert_eq!(entry.ledger.asset, None);
+    assert_eq!(entry.ledger.supplied, 1_000);
+    assert_eq!(entry.ledger.reserves, 0);
+    assert_eq!(entry.accrued_fees, 0);
+    assert_eq!(client.export_asset_ledgers(&1, &10).len(), 0);
+}
+
+#[test]
+fn test_user_pages_respect_offset_and_limit() {
+    let (env, _id, client, _admin, _user, _native) = setup_env_with_native_asset();
+    for _ in 0..5 {
+        client.deposit_collateral(&Address::generate(&env), &None, &10);
+    }
+
+    let first = client.export_user_positions(&0, &2);
+    let second = client.export_user_positions(&2, &2);
+    let last = client.export_user_positions(&4, &2);
+
+    assert_eq!(first.len(), 2);
+    assert_eq!(second.len(), 2);
+    assert_eq!(last.len(), 1);
+    assert_eq!(second.get(0).unwrap().index, 2);
+    assert_eq!(last.get(0).unwrap().index, 4);
+}
+
+#[test]
+fn test_user_page_size_is_capped() {
+    let (env, id, client, _admin, _user, _native) = setup_env_with_native_asset();
+    env.as_contract(&id, || {
+        for _ in 0..(MAX_EXPORT_PAGE_SIZE + 5) {
+            index_user(&env, &Address::generate(&env));
+        }
+    });
+
+    let page = client.export_user_positions(&0, &(MAX_EXPORT_PAGE_SIZE + 5));
+    assert_eq!(page.len(), MAX_EXPORT_PAGE_SIZE);
+    assert_eq!(client.export_protocol_snapshot().user_count, MAX_EXPORT_PAGE_SIZE + 5);
+}
+
+#[test]
+fn test_out_of_range_offset_returns_empty_page() {
+    let (_env, _id, client, _admin, user, _native) = setup_env_with_native_asset();
+    client.deposit_collateral(&user, &None, &100);
+
+    assert_eq!(client.export_user_positions(&5, &10).len(), 0);
+    assert_eq!(client.export_asset_configs(&0, &10).len(), 0);
+}
@@ -0,0 +1,180 @@
+//! Stable rate borrowing tests.
+//!
+//! # Coverage
+//! - Stable borrows lock the current stable rate and accrue at it while the
+//!   market rate moves
+//! - Repayments reduce variable debt before stable debt
+//! - Rebalancing is refused within the threshold, resets the lock beyond it
+//!   and still charges the old rate up to the rebalance
+//! - Stable borrows are refused while disabled; invalid settings and
+//!   non-admin callers are rejected
+//! - Configuration changes need the admin's authorization
+
+use crate::borrow::BorrowError;
+use crate::deposit::{DepositDataKey, Position};
+use crate::stable_rate::{RateMode, StableRateConfig, StableRateError};
+use crate::tests::test_helpers::{assert_authorized, setup_env_with_native_asset};
+use crate::HelloContractClient;
+use soroban_sdk::{testutils::Address as _, Address, Env};
+use stellarlend_testutils::advance_time;
+
+const YEAR: u64 = 31_536_000;
+
+fn enable_stable(client: &HelloContractClient, admin: &Address) {
+    client.set_stable_rate_config(
+        admin,
+        &StableRateConfig {
+            enabled: true,
+            premium_bps: 200,
+            rebalance_threshold_bps: 500,
+        },
+    );
+}
+
+/// Move the market borrow rate by `adjustment_bps`
+fn adjust_market_rate(env: &Env, id: &Address, admin: &Address, adjustment_bps: i128) {
+    env.as_contract(id, || {
+        crate::interest_rate::set_emergency_rate_adjustment(env, admin.clone(), adjustment_bps)
+            .unwrap();
+    });
+}
+
+fn position(env: &Env, id: &Address, user: &Address) -> Position {
+    env.as_contract(id, || {
+        env.storage()
+            .persistent()
+            .get(&DepositDataKey::Position(user.clone()))
+            .unwrap()
+    })
+}
+
+#[test]
+fn test_stable_borrow_accrues_at_locked_rate() {
+    let (env, id, client, admin, user, _native) = setup_env_with_native_asset();
+    enable_stable(&client, &admin);
+    assert_authorized(&env, &admin, &id, "set_stable_rate_config");
+    client.deposit_collateral(&user, &None, &100_000);
+
+    let locked = client.get_stable_borrow_rate();
+    assert_eq!(locked, client.get_borrow_rate() + 200);
+    client.borrow_asset_with_rate_mode(&user, &None, &10_000, &RateMode::Stable);
+    let debt = client.get_debt_by_mode(&user);
+    assert_eq!(debt.stable_principal, 10_000);
+    assert_eq!(debt.variable_principal, 0);
+    assert_eq!(debt.stable_rate_bps, locked);
+
+    // The market rate rises but the lock holds: a year on 10_000 at `locked`
+    adjust_market_rate(&env, &id, &admin, 3_000);
+    advance_time(&env, YEAR);
+    client.borrow_asset(&user, &None, &1);
+    assert_eq!(position(&env, &id, &user).borrow_interest, locked);
+
+    // A second stable borrow blends the locked rate by principal
+    let current = client.get_stable_borrow_rate();
+    client.borrow_asset_with_rate_mode(&user, &None, &10_000, &RateMode::Stable);
+    let debt = client.get_debt_by_mode(&user);
+    assert_eq!(debt.stable_principal, 20_000);
+    assert_eq!(debt.variable_principal, 1);
+    assert_eq!(debt.stable_rate_bps, (locked + current) / 2);
+}
+
+#[test]
+fn test_repay_reduces_variable_debt_first() {
+    let (_env, _id, client, admin, user, _native) = setup_env_with_native_asset();
+    enable_stable(&client, &admin);
+    client.deposit_collateral(&user, &None, &100_000);
+    client.borrow_asset(&user, &None, &5_000);
+    client.borrow_asset_with_rate_mode(&user, &None, &5_000, &RateMode::Stable);
+
+    client.repay_debt(&user, &None, &3_000);
+    let debt = client.get_debt_by_mode(&user);
+    assert_eq!(debt.variable_principal, 2_000);
+    assert_eq!(debt.stable_principal, 5_000);
+
+    client.repay_debt(&user, &None, &4_000);
+    let debt = client.get_debt_by_mode(&user);
+    assert_eq!(debt.variable_principal, 0);
+    assert_eq!(debt.stable_principal, 3_000);
+
+    // A later variable borrow does not restore the repaid stable debt
+    client.borrow_asset(&user, &None, &1_000);
+    let debt = client.get_debt_by_mode(&user);
+    assert_eq!(debt.variable_principal, 1_000);
+    assert_eq!(debt.stable_principal, 3_000);
+}
+
+#[test]
+fn test_rebalance_beyond_threshold() {
+    let (env, id, client, admin, user, _native) = setup_env_with_native_asset();
+    enable_stable(&client, &admin);
+    client.deposit_collateral(&user, &None, &100_000);
+    client.borrow_asset_with_rate_mode(&user, &None, &10_000, &RateMode::Stable);
+    let old_rate = client.get_debt_by_mode(&user).stable_rate_bps;
+
+    // The borrow itself moves utilization, but not beyond the threshold
+    let keeper = Address::generate(&env);
+    assert!(client.get_stable_borrow_rate() > old_rate);
+    assert_eq!(
+        client.try_rebalance_stable_rate(&keeper, &user),
+        Err(Ok(StableRateError::WithinThreshold))
+    );
+
+    adjust_market_rate(&env, &id, &admin, 1_000);
+    advance_time(&env, YEAR);
+    let new_rate = client.get_stable_borrow_rate();
+    assert!(new_rate - old_rate > 500);
+    assert_eq!(client.rebalance_stable_rate(&keeper, &user), new_rate);
+    assert_eq!(client.get_debt_by_mode(&user).stable_rate_bps, new_rate);
+
+    // The first year is charged at the old rate, the second at the new one
+    advance_time(&env, YEAR);
+    client.borrow_asset(&user, &None, &1);
+    assert_eq!(
+        position(&env, &id, &user).borrow_interest,
+        old_rate + new_rate
+    );
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_rebalance_stable_rate(&keeper, &stranger),
+        Err(Ok(StableRateError::NoStableDebt))
+    );
+}
+
+#[test]
+fn test_disabled_and_invalid_settings_rejected() {
+    let (env, _id, client, admin, user, _native) = setup_env_with_native_asset();
+    client.deposit_collateral(&user, &None, &100_000);
+    assert!(!client.get_stable_rate_config().enabled);
+    assert_eq!(
+        client.try_borrow_asset_with_rate_mode(&user, &None, &1_000, &RateMode::Stable),
+        Err(Ok(BorrowError::StableRateDisabled))
+    );
+
+    for (premium_bps, rebalance_threshold_bps) in [(-1, 500), (10_001, 500), (200, -1)] {
+        assert_eq!(
+            client.try_set_stable_rate_config(
+                &admin,
+                &StableRateConfig {
+                    enabled: true,
+                    premium_bps,
+                    rebalance_threshold_bps,
+                },
+            ),
+            Err(Ok(StableRateError::InvalidConfig))
+        );
+    }
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_set_stable_rate_config(
+            &stranger,
+            &StableRateConfig {
+                enabled: true,
+                premium_bps: 200,
+                rebalance_threshold_bps: 500,
+            },
+        ),
+        Err(Ok(StableRateError::Unauthorized))
+    );
+}
@@ -0,0 +1,227 @@
+//! Interest subsidy campaign tests.
+//!
+//! # Coverage
+//! - Executed proposals fund a campaign from the asset's protocol reserve
+//! - Accrual discounts borrower interest by the campaign rate until the
+//!   budget is spent, then charges the full rate again
+//! - Closing returns the unspent budget and is refused while the campaign runs
+//! - Invalid terms are rejected at creation; an unfunded or duplicate
+//!   campaign fails execution
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::errors::GovernanceError;
+use crate::subsidy_campaign::{SubsidyCampaignError, SubsidyCampaignTerms};
+use crate::tests::test_helpers::setup_env_with_native_asset;
+use crate::types::{ProposalType, VoteType};
+use crate::HelloContractClient;
+use soroban_sdk::{testutils::Address as _, token::StellarAssetClient, Address, Env, String};
+use stellarlend_testutils::advance_time;
+
+const YEAR: u64 = 31_536_000;
+
+/// Initialize governance with a single 1_000-token voter
+fn setup_governance(env: &Env, client: &HelloContractClient, admin: &Address) -> Address {
+    let vote_token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let proposer = Address::generate(env);
+    StellarAssetClient::new(env, &vote_token).mint(&proposer, &1_000);
+    client.gov_initialize(
+        admin,
+        &vote_token,
+        &Some(259200),
+        &Some(86400),
+        &Some(400),
+        &Some(100),
+        &Some(604800),
+        &Some(5000),
+    );
+    proposer
+}
+
+/// Create, pass and queue a proposal; returns its id once executable
+fn pass_proposal(
+    env: &Env,
+    client: &HelloContractClient,
+    admin: &Address,
+    proposer: &Address,
+    proposal_type: ProposalType,
+) -> u64 {
+    let proposal_id = client.gov_create_proposal(
+        proposer,
+        &proposal_type,
+        &String::from_str(env, "Subsidy campaign"),
+        &None,
+    );
+    advance_time(env, 1);
+    client.gov_vote(proposer, &proposal_id, &VoteType::For);
+    advance_time(env, 259200);
+    client.gov_queue_proposal(admin, &proposal_id);
+    advance_time(env, 86400);
+    proposal_id
+}
+
+fn set_native_reserve(env: &Env, contract_id: &Address, amount: i128) {
+    env.as_contract(contract_id, || {
+        env.storage()
+            .persistent()
+            .set(&DepositDataKey::ProtocolReserve(None), &amount);
+    });
+}
+
+fn native_reserve(env: &Env, contract_id: &Address) -> i128 {
+    env.as_contract(contract_id, || {
+        env.storage()
+            .persistent()
+            .get(&DepositDataKey::ProtocolReserve(None))
+            .unwrap_or(0)
+    })
+}
+
+/// 5% off native borrows for two years, with a budget of `budget`
+fn terms(budget: i128) -> SubsidyCampaignTerms {
+    SubsidyCampaignTerms {
+        asset: None,
+        discount_bps: 500,
+        start: 0,
+        end: 2 * YEAR,
+        budget,
+    }
+}
+
+#[test]
+fn test_campaign_discounts_interest_until_budget_spent() {
+    let (env, id, client, admin, user, _native) = setup_env_with_native_asset();
+    let proposer = setup_governance(&env, &client, &admin);
+    set_native_reserve(&env, &id, 10_000);
+
+    let proposal_id = pass_proposal(
+        &env,
+        &client,
+        &admin,
+        &proposer,
+        ProposalType::SubsidyCampaign(terms(2_000)),
+    );
+    client.gov_execute_proposal(&admin, &proposal_id);
+    assert_eq!(native_reserve(&env, &id), 8_000);
+    let campaign = client.get_asset_subsidy_campaign(&None).unwrap();
+    // A start already passed opens the campaign at execution
+    assert_eq!(campaign.start, env.ledger().timestamp());
+
+    client.deposit_collateral(&user, &None, &100_000);
+    client.borrow_asset(&user, &None, &50_000);
+
+    // Half a year at 5% off 50_000
+    advance_time(&env, YEAR / 2);
+    client.borrow_asset(&user, &None, &1);
+    assert_eq!(
+        client.get_asset_subsidy_campaign(&None).unwrap().spent,
+        1_250
+    );
+    let position: Position = env.as_contract(&id, || {
+        env.storage()
+            .persistent()
+            .get(&DepositDataKey::Position(user.clone()))
+            .unwrap()
+    });
+    assert!(position.borrow_interest > 0);
+
+    // Only 750 of the next 1_250 is left
+    advance_time(&env, YEAR / 2);
+    client.borrow_asset(&user, &None, &1);
+    let campaign = client.get_asset_subsidy_campaign(&None).unwrap();
+    assert_eq!(campaign.spent, 2_000);
+
+    // A spent campaign can be closed before its window ends
+    assert_eq!(client.close_subsidy_campaign(&None), 0);
+    assert_eq!(client.get_asset_subsidy_campaign(&None), None);
+    assert!(client.get_subsidy_campaign(&campaign.id).unwrap().closed);
+}
+
+#[test]
+fn test_close_returns_unspent_budget() {
+    let (env, id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let proposer = setup_governance(&env, &client, &admin);
+    set_native_reserve(&env, &id, 10_000);
+
+    let proposal_id = pass_proposal(
+        &env,
+        &client,
+        &admin,
+        &proposer,
+        ProposalType::SubsidyCampaign(terms(2_000)),
+    );
+    client.gov_execute_proposal(&admin, &proposal_id);
+
+    // A second campaign on the same asset cannot open
+    let duplicate_id = pass_proposal(
+        &env,
+        &client,
+        &admin,
+        &proposer,
+        ProposalType::SubsidyCampaign(terms(1_000)),
+    );
+    assert_eq!(
+        client.try_gov_execute_proposal(&admin, &duplicate_id),
+        Err(Ok(GovernanceError::ExecutionFailed))
+    );
+
+    assert_eq!(
+        client.try_close_subsidy_campaign(&None),
+        Err(Ok(SubsidyCampaignError::CampaignNotEnded))
+    );
+    advance_time(&env, 2 * YEAR);
+    assert_eq!(client.close_subsidy_campaign(&None), 2_000);
+    assert_eq!(native_reserve(&env, &id), 10_000);
+    assert_eq!(
+        client.try_close_subsidy_campaign(&None),
+        Err(Ok(SubsidyCampaignError::CampaignNotFound))
+    );
+}
+
+#[test]
+fn test_invalid_or_unfunded_campaigns_rejected() {
+    let (env, id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let proposer = setup_governance(&env, &client, &admin);
+
+    for invalid in [
+        SubsidyCampaignTerms {
+            discount_bps: 0,
+            ..terms(1_000)
+        },
+        SubsidyCampaignTerms {
+            discount_bps: 10_001,
+            ..terms(1_000)
+        },
+        SubsidyCampaignTerms {
+            start: 2 * YEAR,
+            ..terms(1_000)
+        },
+        terms(0),
+    ] {
+        assert_eq!(
+            client.try_gov_create_proposal(
+                &proposer,
+                &ProposalType::SubsidyCampaign(invalid),
+                &String::from_str(&env, "Invalid"),
+                &None,
+            ),
+            Err(Ok(GovernanceError::InvalidAction))
+        );
+    }
+
+    // The reserve cannot fund the budget
+    set_native_reserve(&env, &id, 500);
+    let proposal_id = pass_proposal(
+        &env,
+        &client,
+        &admin,
+        &proposer,
+        ProposalType::SubsidyCampaign(terms(1_000)),
+    );
+    assert_eq!(
+        client.try_gov_execute_proposal(&admin, &proposal_id),
+        Err(Ok(GovernanceError::ExecutionFailed))
+    );
+    assert_eq!(native_reserve(&env, &id), 500);
+}
@@ -0,0 +1,92 @@
+//! Supply share accounting tests.
+//!
+//! # Coverage
+//! - First deposits mint shares 1:1 at the initial exchange rate
+//! - Accrued borrow interest (net of reserve factor) raises the exchange rate
+//! - Later depositors receive fewer shares for the same amount
+//! - Withdrawals redeem shares, including interest earned
+//! - Collateral recorded before share accounting is migrated on first touch
+
+use super::test_helpers::setup_env_with_native_asset;
+use crate::deposit::{accrue_supply_interest, DepositDataKey, EXCHANGE_RATE_SCALE};
+use soroban_sdk::{testutils::Address as _, Address};
+
+#[test]
+fn test_first_deposit_mints_one_to_one() {
+    let (_env, _id, client, _admin, user, _native) = setup_env_with_native_asset();
+
+    client.deposit_collateral(&user, &None, &10_000);
+
+    assert_eq!(client.get_exchange_rate(), EXCHANGE_RATE_SCALE);
+    assert_eq!(client.get_supply_shares(&user), 10_000);
+    assert_eq!(client.get_underlying_balance(&user), 10_000);
+}
+
+#[test]
+fn test_interest_raises_exchange_rate() {
+    let (env, id, client, _admin, user, _native) = setup_env_with_native_asset();
+    client.deposit_collateral(&user, &None, &10_000);
+
+    // 1,000 of borrow interest, 10% retained as reserve
+    env.as_contract(&id, || accrue_supply_interest(&env, &None, 1_000));
+
+    assert_eq!(client.get_exchange_rate(), 1_090_000_000);
+    assert_eq!(client.get_supply_shares(&user), 10_000);
+    assert_eq!(client.get_underlying_balance(&user), 10_900);
+}
+
+#[test]
+fn test_later_depositor_receives_fewer_shares() {
+    let (env, id, client, _admin, user, _native) = setup_env_with_native_asset();
+    let late = Address::generate(&env);
+    client.deposit_collateral(&user, &None, &10_000);
+    env.as_contract(&id, || accrue_supply_interest(&env, &None, 1_000));
+
+    client.deposit_collateral(&late, &None, &10_900);
+
+    assert_eq!(client.get_supply_shares(&late), 10_000);
+    assert_eq!(client.get_underlying_balance(&late), 10_900);
+    assert_eq!(client.get_exchange_rate(), 1_090_000_000);
+}
+
+#[test]
+fn test_withdraw_redeems_interest() {
+    let (env, id, client, _admin, user, _native) = setup_env_with_native_asset();
+    client.deposit_collateral(&user, &None, &10_000);
+    env.as_contract(&id, || accrue_supply_interest(&env, &None, 1_000));
+
+    let remaining = client.withdraw_collateral(&user, &None, &10_900);
+
+    assert_eq!(remaining, 0);
+    assert_eq!(client.get_supply_shares(&user), 0);
+    assert_eq!(client.get_underlying_balance(&user), 0);
+}
+
+#[test]
+fn test_partial_withdraw_burns_proportional_shares() {
+    let (env, id, client, _admin, user, _native) = setup_env_with_native_asset();
+    client.deposit_collateral(&user, &None, &10_000);
+    env.as_contract(&id, || accrue_supply_interest(&env, &None, 1_000));
+
+    let remaining = client.withdraw_collateral(&user, &None, &5_450);
+
+    assert_eq!(remaining, 5_450);
+    assert_eq!(client.get_supply_shares(&user), 5_000);
+    assert_eq!(client.get_exchange_rate(), 1_090_000_000);
+}
+
+#[test]
+fn test_legacy_collateral_migrated_on_deposit() {
+    let (env, id, client, _admin, user, _native) = setup_env_with_native_asset();
+    env.as_contract(&id, || {
+        env.storage().persistent().set(
+            &DepositDataKey::CollateralBalance(user.clone()),
+            &4_000_i128,
+        );
+    });
+
+    client.deposit_collateral(&user, &None, &1_000);
+
+    assert_eq!(client.get_supply_shares(&user), 5_000);
+    assert_eq!(client.get_underlying_balance(&user), 5_000);
+}
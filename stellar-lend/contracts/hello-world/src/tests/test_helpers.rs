@@ -2,7 +2,10 @@
 //! performs deposit/borrow/repay with asset = None, so that NativeAssetAddress is set.
 
 use crate::{HelloContract, HelloContractClient};
-use soroban_sdk::{testutils::Address as _, Address, Env};
+use soroban_sdk::{
+    testutils::{Address as _, AuthorizedFunction},
+    Address, Env, Symbol,
+};
 
 /// Full setup: env, contract, client, admin, user, and native asset address set.
 /// Use this for tests that call deposit_collateral/borrow_asset/repay_debt with None.
@@ -25,3 +28,20 @@ pub fn setup_env_with_native_asset() -> (
     client.set_native_asset_address(&admin, &native_asset);
     (env, contract_id, client, admin, user, native_asset)
 }
+
+/// Assert that the last contract call required `address` to authorize
+/// `function` on `contract`
+pub fn assert_authorized(env: &Env, address: &Address, contract: &Address, function: &str) {
+    let function = Symbol::new(env, function);
+    let authorized = env.auths().iter().any(|(auth_address, invocation)| {
+        auth_address == address
+            && matches!(
+                &invocation.function,
+                AuthorizedFunction::Contract((id, name, _)) if id == contract && *name == function
+            )
+    });
+    assert!(
+        authorized,
+        "call was not authorized by the expected address"
+    );
+}
@@ -0,0 +1,238 @@
+//! Oracle price history and TWAP tests.
+//!
+//! # Coverage
+//! - Primary-feed updates are recorded oldest first and wrap at capacity
+//! - Updates within one ledger collapse into a single observation
+//! - The TWAP weights prices by duration within the trailing window
+//! - Capacity changes keep the most recent observations; bounds are enforced
+//!   and the admin's authorization is required
+//! - Missing history, zero windows and stale history are rejected
+//! - Liquidation checks use the TWAP when configured, resisting a price spike
+//! - Choosing the liquidation price source needs the caller's authorization
+
+use crate::deposit::{DepositDataKey, Position};
+use crate::liquidate::{liquidate, LiquidationError};
+use crate::oracle::{OracleError, PriceObservation, MAX_PRICE_HISTORY_CAPACITY};
+use crate::risk_management::{LiquidationPriceSource, RiskManagementError};
+use crate::tests::test_helpers::assert_authorized;
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env, Vec,
+};
+use stellarlend_testutils::advance_time;
+
+const PRICE: i128 = 100_000_000;
+
+struct Setup<'a> {
+    id: Address,
+    client: HelloContractClient<'a>,
+    admin: Address,
+    oracle: Address,
+    asset: Address,
+}
+
+fn setup(e: &Env) -> Setup<'_> {
+    e.mock_all_auths();
+    e.ledger().with_mut(|li| li.timestamp = 1_000);
+    let id = e.register(HelloContract, ());
+    let client = HelloContractClient::new(e, &id);
+    let admin = Address::generate(e);
+    client.initialize(&admin);
+    Setup {
+        id,
+        client,
+        admin,
+        oracle: Address::generate(e),
+        asset: Address::generate(e),
+    }
+}
+
+fn push(s: &Setup, asset: &Address, price: i128) {
+    s.client
+        .update_price_feed(&s.admin, asset, &price, &8, &s.oracle);
+}
+
+fn prices(e: &Env, s: &Setup) -> Vec<i128> {
+    let mut prices = Vec::new(e);
+    for observation in s.client.get_price_history(&s.asset).iter() {
+        prices.push_back(observation.price);
+    }
+    prices
+}
+
+#[test]
+fn test_history_records_and_wraps() {
+    let e = Env::default();
+    let s = setup(&e);
+    s.client.set_price_history_capacity(&s.admin, &3);
+
+    push(&s, &s.asset, PRICE);
+    advance_time(&e, 60);
+    push(&s, &s.asset, PRICE + 1_000);
+    assert_eq!(
+        s.client.get_price_history(&s.asset).get(1).unwrap(),
+        PriceObservation {
+            price: PRICE + 1_000,
+            timestamp: 1_060,
+        }
+    );
+
+    for i in 2..5 {
+        advance_time(&e, 60);
+        push(&s, &s.asset, PRICE + i * 1_000);
+    }
+    assert_eq!(
+        prices(&e, &s),
+        vec![&e, PRICE + 2_000, PRICE + 3_000, PRICE + 4_000]
+    );
+}
+
+#[test]
+fn test_same_ledger_updates_collapse() {
+    let e = Env::default();
+    let s = setup(&e);
+
+    push(&s, &s.asset, PRICE);
+    push(&s, &s.asset, PRICE + 1_000);
+    assert_eq!(prices(&e, &s), vec![&e, PRICE + 1_000]);
+}
+
+#[test]
+fn test_twap_weights_by_duration() {
+    let e = Env::default();
+    let s = setup(&e);
+
+    push(&s, &s.asset, PRICE);
+    advance_time(&e, 300);
+    push(&s, &s.asset, PRICE + 4_000_000);
+    advance_time(&e, 100);
+
+    // 300s at PRICE, 100s at PRICE + 4%
+    assert_eq!(s.client.get_twap(&s.asset, &400), PRICE + 1_000_000);
+    // A window that starts mid-way only counts the overlapping part
+    assert_eq!(s.client.get_twap(&s.asset, &200), PRICE + 2_000_000);
+    // History shorter than the window averages over what is recorded
+    assert_eq!(s.client.get_twap(&s.asset, &3_600), PRICE + 1_000_000);
+    // An observation made this ledger has no weight yet
+    push(&s, &s.asset, PRICE + 8_000_000);
+    assert_eq!(s.client.get_twap(&s.asset, &200), PRICE + 2_000_000);
+}
+
+#[test]
+fn test_capacity_change_keeps_latest() {
+    let e = Env::default();
+    let s = setup(&e);
+    assert_eq!(s.client.get_price_history_capacity(), 24);
+
+    for i in 0..4 {
+        push(&s, &s.asset, PRICE + i * 1_000);
+        advance_time(&e, 60);
+    }
+    s.client.set_price_history_capacity(&s.admin, &2);
+    assert_authorized(&e, &s.admin, &s.id, "set_price_history_capacity");
+    push(&s, &s.asset, PRICE + 4_000);
+    assert_eq!(prices(&e, &s), vec![&e, PRICE + 3_000, PRICE + 4_000]);
+
+    for capacity in [0, MAX_PRICE_HISTORY_CAPACITY + 1] {
+        assert_eq!(
+            s.client.try_set_price_history_capacity(&s.admin, &capacity),
+            Err(Ok(OracleError::InvalidHistoryConfig))
+        );
+    }
+    assert_eq!(
+        s.client
+            .try_set_price_history_capacity(&Address::generate(&e), &10),
+        Err(Ok(OracleError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_twap_errors() {
+    let e = Env::default();
+    let s = setup(&e);
+
+    assert_eq!(
+        s.client.try_get_twap(&s.asset, &600),
+        Err(Ok(OracleError::InsufficientHistory))
+    );
+
+    push(&s, &s.asset, PRICE);
+    assert_eq!(
+        s.client.try_get_twap(&s.asset, &0),
+        Err(Ok(OracleError::InvalidHistoryConfig))
+    );
+
+    // Default staleness limit is one hour
+    advance_time(&e, 3_601);
+    assert_eq!(
+        s.client.try_get_twap(&s.asset, &600),
+        Err(Ok(OracleError::StalePrice))
+    );
+}
+
+#[test]
+fn test_liquidation_uses_twap_source() {
+    let e = Env::default();
+    let s = setup(&e);
+    let debt_asset = Address::generate(&e);
+    let borrower = Address::generate(&e);
+    let liquidator = Address::generate(&e);
+    assert_eq!(
+        s.client.get_liquidation_price_source(),
+        LiquidationPriceSource::Spot
+    );
+
+    push(&s, &debt_asset, PRICE);
+    push(&s, &s.asset, PRICE);
+
+    // 107.5% collateralized at the steady price
+    e.as_contract(&s.id, || {
+        e.storage().persistent().set(
+            &DepositDataKey::CollateralBalance(borrower.clone()),
+            &10_000i128,
+        );
+        e.storage().persistent().set(
+            &DepositDataKey::Position(borrower.clone()),
+            &Position {
+                collateral: 10_000,
+                debt: 9_300,
+                borrow_interest: 0,
+                last_accrual_time: e.ledger().timestamp(),
+            },
+        );
+    });
+
+    // A short-lived 5% drop pushes the spot ratio below the threshold
+    advance_time(&e, 3_000);
+    push(&s, &s.asset, PRICE * 95 / 100);
+    advance_time(&e, 10);
+
+    let try_liquidate = || {
+        e.as_contract(&s.id, || {
+            liquidate(
+                &e,
+                liquidator.clone(),
+                borrower.clone(),
+                Some(debt_asset.clone()),
+                Some(s.asset.clone()),
+                1_000,
+            )
+        })
+    };
+
+    s.client
+        .set_liquidation_price_source(&s.admin, &LiquidationPriceSource::Twap(3_600));
+    assert_authorized(&e, &s.admin, &s.id, "set_liquidation_price_source");
+    assert_eq!(try_liquidate(), Err(LiquidationError::NotLiquidatable));
+
+    s.client
+        .set_liquidation_price_source(&s.admin, &LiquidationPriceSource::Spot);
+    assert!(try_liquidate().is_ok());
+
+    assert_eq!(
+        s.client
+            .try_set_liquidation_price_source(&s.admin, &LiquidationPriceSource::Twap(0)),
+        Err(Ok(RiskManagementError::InvalidParameter))
+    );
+}
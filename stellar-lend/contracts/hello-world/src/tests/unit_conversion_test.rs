@@ -0,0 +1,109 @@
+//! Unit conversion view tests.
+//!
+//! # Coverage
+//! - `to_value` / `from_value` use the listed price and round toward zero
+//! - Conversions match the valuations in the position summary
+//! - Unlisted assets and stale prices are rejected
+//! - Basis point helpers round like the contract
+
+use crate::cross_asset::{
+    cross_asset_deposit, get_user_position_summary, initialize, initialize_asset, AssetConfig,
+    CrossAssetError,
+};
+use crate::{HelloContract, HelloContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env};
+use stellarlend_testutils::advance_time;
+
+/// Returns (env, contract, asset) with the asset listed at 1.5 USD
+fn setup() -> (Env, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(HelloContract, ());
+    let admin = Address::generate(&env);
+    let asset = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        initialize(&env, admin.clone()).unwrap();
+        initialize_asset(
+            &env,
+            Some(asset.clone()),
+            AssetConfig {
+                asset: Some(asset.clone()),
+                collateral_factor: 7500,
+                liquidation_threshold: 8000,
+                reserve_factor: 1000,
+                max_supply: 0,
+                max_borrow: 0,
+                can_collateralize: true,
+                can_borrow: true,
+                price: 15_000_000,
+                price_updated_at: env.ledger().timestamp(),
+                is_isolated: false,
+                isolated_debt_ceiling: 0,
+            },
+        )
+        .unwrap();
+    });
+
+    (env, contract_id, asset)
+}
+
+#[test]
+fn test_conversions_round_toward_zero() {
+    let (env, id, asset) = setup();
+    let client = HelloContractClient::new(&env, &id);
+    let asset = Some(asset);
+
+    // 7 * 1.5 = 10.5
+    assert_eq!(client.to_value(&asset, &7), 10);
+    assert_eq!(client.to_value(&asset, &-7), -10);
+    // 10 / 1.5 = 6.67
+    assert_eq!(client.from_value(&asset, &10), 6);
+    assert_eq!(client.from_value(&asset, &15), 10);
+}
+
+#[test]
+fn test_conversions_match_position_valuation() {
+    let (env, id, asset) = setup();
+    let client = HelloContractClient::new(&env, &id);
+    let user = Address::generate(&env);
+    env.as_contract(&id, || {
+        cross_asset_deposit(&env, user.clone(), Some(asset.clone()), 7).unwrap();
+    });
+
+    let summary = env.as_contract(&id, || get_user_position_summary(&env, &user).unwrap());
+    let value = client.to_value(&Some(asset), &7);
+    assert_eq!(summary.total_collateral_value, value);
+    assert_eq!(
+        summary.weighted_collateral_value,
+        client.apply_bps(&value, &8000)
+    );
+}
+
+#[test]
+fn test_unlisted_and_stale_rejected() {
+    let (env, id, asset) = setup();
+    let client = HelloContractClient::new(&env, &id);
+
+    assert_eq!(
+        client.try_to_value(&Some(Address::generate(&env)), &100),
+        Err(Ok(CrossAssetError::AssetNotConfigured))
+    );
+    advance_time(&env, 3_601);
+    assert_eq!(
+        client.try_from_value(&Some(asset), &100),
+        Err(Ok(CrossAssetError::PriceStale))
+    );
+}
+
+#[test]
+fn test_bps_helpers() {
+    let (env, id, _asset) = setup();
+    let client = HelloContractClient::new(&env, &id);
+
+    // 999 * 33.33% = 332.97
+    assert_eq!(client.apply_bps(&999, &3_333), 332);
+    // 2 / 3 = 66.67%
+    assert_eq!(client.ratio_bps(&2, &3), 6_666);
+    assert_eq!(client.ratio_bps(&-2, &3), -6_666);
+}
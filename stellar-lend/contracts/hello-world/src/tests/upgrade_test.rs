@@ -0,0 +1,110 @@
+//! Contract upgrade and storage migration tests.
+//!
+//! # Coverage
+//! - Fresh deployments start at the current storage version
+//! - `migrate` brings unversioned storage up to date, once
+//! - Admin upgrades must be scheduled and wait out the timelock with the same hash
+//! - Scheduling, cancelling, upgrading and migrating are admin-only and need
+//!   the admin's authorization
+
+use crate::tests::test_helpers::{assert_authorized, setup_env_with_native_asset};
+use crate::upgrade::{
+    ContractVersion, PendingUpgrade, UpgradeDataKey, UpgradeError, CONTRACT_VERSION,
+    UPGRADE_TIMELOCK_SECONDS,
+};
+use soroban_sdk::{testutils::Address as _, Address, BytesN};
+use stellarlend_testutils::advance_time;
+
+#[test]
+fn test_migrate_unversioned_storage() {
+    let (env, id, client, admin, _user, _native) = setup_env_with_native_asset();
+    assert_eq!(
+        client.get_contract_version(),
+        ContractVersion {
+            code_version: CONTRACT_VERSION,
+            storage_version: CONTRACT_VERSION,
+        }
+    );
+    assert_eq!(
+        client.try_migrate(&admin),
+        Err(Ok(UpgradeError::AlreadyMigrated))
+    );
+
+    // A deployment from before storage was versioned
+    env.as_contract(&id, || {
+        env.storage()
+            .persistent()
+            .remove(&UpgradeDataKey::StorageVersion);
+    });
+    assert_eq!(client.get_contract_version().storage_version, 0);
+    assert_eq!(client.migrate(&admin), CONTRACT_VERSION);
+    assert_authorized(&env, &admin, &id, "migrate");
+    assert_eq!(
+        client.get_contract_version().storage_version,
+        CONTRACT_VERSION
+    );
+}
+
+#[test]
+fn test_upgrade_requires_schedule_and_timelock() {
+    let (env, id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let wasm_hash = BytesN::from_array(&env, &[1; 32]);
+    let other_hash = BytesN::from_array(&env, &[2; 32]);
+
+    assert_eq!(
+        client.try_upgrade_wasm(&admin, &wasm_hash),
+        Err(Ok(UpgradeError::NoPendingUpgrade))
+    );
+    let eta = client.schedule_upgrade(&admin, &wasm_hash);
+    assert_authorized(&env, &admin, &id, "schedule_upgrade");
+    assert_eq!(eta, UPGRADE_TIMELOCK_SECONDS);
+    assert_eq!(
+        client.get_pending_upgrade(),
+        Some(PendingUpgrade {
+            wasm_hash: wasm_hash.clone(),
+            eta,
+        })
+    );
+    assert_eq!(
+        client.try_upgrade_wasm(&admin, &wasm_hash),
+        Err(Ok(UpgradeError::TimelockNotElapsed))
+    );
+    advance_time(&env, UPGRADE_TIMELOCK_SECONDS);
+    assert_eq!(
+        client.try_upgrade_wasm(&admin, &other_hash),
+        Err(Ok(UpgradeError::HashMismatch))
+    );
+
+    client.cancel_upgrade(&admin);
+    assert_authorized(&env, &admin, &id, "cancel_upgrade");
+    assert_eq!(client.get_pending_upgrade(), None);
+    assert_eq!(
+        client.try_cancel_upgrade(&admin),
+        Err(Ok(UpgradeError::NoPendingUpgrade))
+    );
+}
+
+#[test]
+fn test_upgrade_operations_admin_only() {
+    let (env, _id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let outsider = Address::generate(&env);
+    let wasm_hash = BytesN::from_array(&env, &[1; 32]);
+    client.schedule_upgrade(&admin, &wasm_hash);
+
+    assert_eq!(
+        client.try_schedule_upgrade(&outsider, &wasm_hash),
+        Err(Ok(UpgradeError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_cancel_upgrade(&outsider),
+        Err(Ok(UpgradeError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_upgrade_wasm(&outsider, &wasm_hash),
+        Err(Ok(UpgradeError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_migrate(&outsider),
+        Err(Ok(UpgradeError::Unauthorized))
+    );
+}
@@ -0,0 +1,72 @@
+//! Governance voting power with vested tokens.
+//!
+//! # Coverage
+//! - Vested, unclaimed grants count towards a beneficiary's voting power
+//! - Unvested tokens held by the vesting contract cannot vote
+//! - Only the governance admin registers the vesting contract
+
+use crate::errors::GovernanceError;
+use crate::tests::test_helpers::setup_env_with_native_asset;
+use crate::types::{ProposalType, VoteType};
+use soroban_sdk::{testutils::Address as _, token::StellarAssetClient, Address, String};
+use stellarlend_testutils::{advance_time, set_time};
+use stellarlend_vesting::{VestingContract, VestingContractClient};
+
+const YEAR: u64 = 365 * 86_400;
+
+#[test]
+fn test_vested_tokens_vote_and_locked_tokens_do_not() {
+    let (env, _id, client, admin, _user, _native) = setup_env_with_native_asset();
+    set_time(&env, 2 * YEAR);
+    let vote_token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let proposer = Address::generate(&env);
+    let beneficiary = Address::generate(&env);
+    StellarAssetClient::new(&env, &vote_token).mint(&proposer, &1_000);
+    StellarAssetClient::new(&env, &vote_token).mint(&admin, &40_000);
+    client.gov_initialize(
+        &admin,
+        &vote_token,
+        &Some(259200),
+        &Some(86400),
+        &Some(400),
+        &Some(100),
+        &Some(604800),
+        &Some(5000),
+    );
+
+    // Four-year linear grant started a year ago: a quarter has vested
+    let vesting_id = env.register(VestingContract, ());
+    let vesting = VestingContractClient::new(&env, &vesting_id);
+    vesting.initialize(&admin, &vote_token);
+    vesting.create_grant(&beneficiary, &40_000, &YEAR, &0, &(4 * YEAR), &false);
+
+    assert_eq!(
+        client.try_gov_set_vesting_contract(&proposer, &vesting_id),
+        Err(Ok(GovernanceError::Unauthorized))
+    );
+    client.gov_set_vesting_contract(&admin, &vesting_id);
+    assert_eq!(client.gov_get_vesting_contract(), Some(vesting_id.clone()));
+
+    let proposal_id = client.gov_create_proposal(
+        &proposer,
+        &ProposalType::ExitExemption(Address::generate(&env), true),
+        &String::from_str(&env, "Exempt vault strategy"),
+        &None,
+    );
+    advance_time(&env, 1);
+
+    client.gov_vote(&beneficiary, &proposal_id, &VoteType::For);
+    assert_eq!(
+        client
+            .gov_get_vote(&proposal_id, &beneficiary)
+            .unwrap()
+            .voting_power,
+        10_000
+    );
+    assert_eq!(
+        client.try_gov_vote(&vesting_id, &proposal_id, &VoteType::For),
+        Err(Ok(GovernanceError::NoVotingPower))
+    );
+}
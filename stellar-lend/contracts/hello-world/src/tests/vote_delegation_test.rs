@@ -0,0 +1,143 @@
+//! Governance vote delegation tests.
+//!
+//! # Coverage
+//! - Deposits, withdrawals and delegation checkpoint the delegates' votes
+//! - Prior votes are looked up by timestamp and only for the past
+//! - With checkpointed voting, votes are snapshotted before proposal
+//!   creation, so tokens moved afterwards cannot vote again
+//! - Checkpointed voting is admin-only and deposits must be positive
+
+use crate::errors::GovernanceError;
+use crate::tests::test_helpers::setup_env_with_native_asset;
+use crate::types::{ProposalType, VoteType};
+use crate::HelloContractClient;
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env, String,
+};
+use stellarlend_testutils::{advance_time, set_time};
+
+/// Initialize governance and mint `amount` vote tokens to each holder
+fn setup_governance(
+    env: &Env,
+    client: &HelloContractClient,
+    admin: &Address,
+    holders: &[&Address],
+    amount: i128,
+) -> Address {
+    let vote_token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    for holder in holders {
+        StellarAssetClient::new(env, &vote_token).mint(holder, &amount);
+    }
+    client.gov_initialize(
+        admin,
+        &vote_token,
+        &Some(259200),
+        &Some(86400),
+        &Some(400),
+        &Some(100),
+        &Some(604800),
+        &Some(5000),
+    );
+    vote_token
+}
+
+#[test]
+fn test_delegation_checkpoints_votes() {
+    let (env, id, client, admin, alice, _native) = setup_env_with_native_asset();
+    let bob = Address::generate(&env);
+    set_time(&env, 1_000);
+    let vote_token = setup_governance(&env, &client, &admin, &[&alice], 1_000);
+
+    client.gov_deposit_votes(&alice, &1_000);
+    assert_eq!(TokenClient::new(&env, &vote_token).balance(&id), 1_000);
+    let deposited_at = env.ledger().timestamp();
+    advance_time(&env, 10);
+
+    client.gov_delegate(&alice, &bob);
+    let delegated_at = env.ledger().timestamp();
+    assert_eq!(client.gov_get_delegate(&alice), bob);
+    assert_eq!(client.gov_get_current_votes(&alice), 0);
+    assert_eq!(client.gov_get_current_votes(&bob), 1_000);
+    assert_eq!(
+        client.try_gov_get_prior_votes(&bob, &delegated_at),
+        Err(Ok(GovernanceError::VotesNotYetDetermined))
+    );
+
+    advance_time(&env, 10);
+    client.gov_withdraw_votes(&alice, &400);
+    assert_eq!(client.gov_get_voting_deposit(&alice), 600);
+    assert_eq!(client.gov_get_current_votes(&bob), 600);
+    advance_time(&env, 1);
+
+    assert_eq!(client.gov_get_prior_votes(&alice, &(deposited_at - 1)), 0);
+    assert_eq!(
+        client.gov_get_prior_votes(&alice, &(delegated_at - 1)),
+        1_000
+    );
+    assert_eq!(client.gov_get_prior_votes(&alice, &delegated_at), 0);
+    assert_eq!(client.gov_get_prior_votes(&bob, &(delegated_at + 5)), 1_000);
+    assert_eq!(client.gov_get_prior_votes(&bob, &(delegated_at + 10)), 600);
+
+    // Delegating back to oneself takes the votes back
+    client.gov_delegate(&alice, &alice);
+    assert_eq!(client.gov_get_current_votes(&alice), 600);
+    assert_eq!(client.gov_get_current_votes(&bob), 0);
+}
+
+#[test]
+fn test_votes_snapshot_at_proposal_creation() {
+    let (env, _id, client, admin, alice, _native) = setup_env_with_native_asset();
+    let carol = Address::generate(&env);
+    let vote_token = setup_governance(&env, &client, &admin, &[&alice], 1_000);
+    client.gov_set_checkpointed_voting(&admin, &true);
+    assert!(client.gov_is_checkpointed_voting());
+
+    client.gov_deposit_votes(&alice, &1_000);
+    advance_time(&env, 1);
+    let proposal_id = client.gov_create_proposal(
+        &alice,
+        &ProposalType::EmergencyPause(true),
+        &String::from_str(&env, "Emergency pause"),
+        &None,
+    );
+
+    // Alice moves her tokens to Carol after the proposal was created
+    client.gov_withdraw_votes(&alice, &1_000);
+    TokenClient::new(&env, &vote_token).transfer(&alice, &carol, &1_000);
+    client.gov_deposit_votes(&carol, &1_000);
+    advance_time(&env, 1);
+
+    assert_eq!(
+        client.try_gov_vote(&carol, &proposal_id, &VoteType::For),
+        Err(Ok(GovernanceError::NoVotingPower))
+    );
+    client.gov_vote(&alice, &proposal_id, &VoteType::For);
+    assert_eq!(
+        client.gov_get_proposal(&proposal_id).unwrap().for_votes,
+        1_000
+    );
+}
+
+#[test]
+fn test_delegation_checks() {
+    let (env, _id, client, admin, alice, _native) = setup_env_with_native_asset();
+    setup_governance(&env, &client, &admin, &[&alice], 1_000);
+
+    assert_eq!(
+        client.try_gov_set_checkpointed_voting(&alice, &true),
+        Err(Ok(GovernanceError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_gov_deposit_votes(&alice, &0),
+        Err(Ok(GovernanceError::InvalidAmount))
+    );
+    client.gov_deposit_votes(&alice, &100);
+    assert_eq!(
+        client.try_gov_withdraw_votes(&alice, &101),
+        Err(Ok(GovernanceError::InsufficientVotingDeposit))
+    );
+}
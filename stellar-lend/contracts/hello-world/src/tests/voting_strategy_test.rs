@@ -0,0 +1,146 @@
+//! Governance voting strategy tests.
+//!
+//! # Coverage
+//! - Quadratic and one-address-one-vote proposals count votes in their units
+//! - Proposals keep the strategy they were created with
+//! - Per-strategy quorum is enforced when queueing
+//! - Strategy configuration is admin-only and validated
+
+use crate::errors::GovernanceError;
+use crate::tests::test_helpers::setup_env_with_native_asset;
+use crate::types::{ProposalType, VoteType, VotingStrategy};
+use crate::HelloContractClient;
+use soroban_sdk::{
+    testutils::Address as _, token::StellarAssetClient, Address, Env, String, Symbol,
+};
+use stellarlend_testutils::advance_time;
+
+/// Initialize governance; `whale` holds 10_000 vote tokens and `minnow` 100
+fn setup_governance(
+    env: &Env,
+    client: &HelloContractClient,
+    admin: &Address,
+) -> (Address, Address) {
+    let vote_token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let whale = Address::generate(env);
+    let minnow = Address::generate(env);
+    StellarAssetClient::new(env, &vote_token).mint(&whale, &10_000);
+    StellarAssetClient::new(env, &vote_token).mint(&minnow, &100);
+    client.gov_initialize(
+        admin,
+        &vote_token,
+        &Some(259200),
+        &Some(86400),
+        &Some(400),
+        &Some(100),
+        &Some(604800),
+        &Some(5000),
+    );
+    (whale, minnow)
+}
+
+fn propose(
+    env: &Env,
+    client: &HelloContractClient,
+    proposer: &Address,
+    proposal_type: ProposalType,
+) -> u64 {
+    let proposal_id = client.gov_create_proposal(
+        proposer,
+        &proposal_type,
+        &String::from_str(env, "Strategy test"),
+        &None,
+    );
+    advance_time(env, 1);
+    proposal_id
+}
+
+#[test]
+fn test_votes_counted_by_strategy() {
+    let (env, _id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let (whale, minnow) = setup_governance(&env, &client, &admin);
+    client.gov_set_voting_strategy(
+        &admin,
+        &Some(Symbol::new(&env, "emergency_pause")),
+        &VotingStrategy::Quadratic,
+    );
+    client.gov_set_voting_strategy(&admin, &None, &VotingStrategy::OneAddressOneVote);
+    assert_eq!(
+        client.gov_get_voting_strategy(&ProposalType::EmergencyPause(true)),
+        VotingStrategy::Quadratic
+    );
+
+    let quadratic = propose(&env, &client, &whale, ProposalType::EmergencyPause(true));
+    let one_vote = propose(
+        &env,
+        &client,
+        &whale,
+        ProposalType::MinCollateralRatio(12_000),
+    );
+    // Changing the default later does not affect existing proposals
+    client.gov_set_voting_strategy(&admin, &None, &VotingStrategy::TokenWeighted);
+
+    for proposal_id in [quadratic, one_vote] {
+        client.gov_vote(&whale, &proposal_id, &VoteType::For);
+        client.gov_vote(&minnow, &proposal_id, &VoteType::Against);
+    }
+
+    let proposal = client.gov_get_proposal(&quadratic).unwrap();
+    assert_eq!(proposal.voting_strategy, VotingStrategy::Quadratic);
+    assert_eq!((proposal.for_votes, proposal.against_votes), (100, 10));
+    let proposal = client.gov_get_proposal(&one_vote).unwrap();
+    assert_eq!(proposal.voting_strategy, VotingStrategy::OneAddressOneVote);
+    assert_eq!((proposal.for_votes, proposal.against_votes), (1, 1));
+}
+
+#[test]
+fn test_strategy_quorum_enforced() {
+    let (env, _id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let (whale, minnow) = setup_governance(&env, &client, &admin);
+    client.gov_set_voting_strategy(&admin, &None, &VotingStrategy::OneAddressOneVote);
+    client.gov_set_strategy_quorum(&admin, &VotingStrategy::OneAddressOneVote, &2);
+
+    let lonely = propose(&env, &client, &whale, ProposalType::EmergencyPause(true));
+    client.gov_vote(&whale, &lonely, &VoteType::For);
+    let popular = propose(&env, &client, &whale, ProposalType::EmergencyPause(true));
+    client.gov_vote(&whale, &popular, &VoteType::For);
+    client.gov_vote(&minnow, &popular, &VoteType::For);
+    advance_time(&env, 259200);
+
+    let outcome = client.gov_queue_proposal(&admin, &lonely);
+    assert!(!outcome.succeeded);
+    assert!(!outcome.quorum_reached);
+    assert_eq!(outcome.quorum_required, 2);
+
+    let outcome = client.gov_queue_proposal(&admin, &popular);
+    assert!(outcome.succeeded);
+}
+
+#[test]
+fn test_strategy_configuration_checks() {
+    let (env, _id, client, admin, _user, _native) = setup_env_with_native_asset();
+    let (whale, _minnow) = setup_governance(&env, &client, &admin);
+
+    assert_eq!(
+        client.try_gov_set_voting_strategy(&whale, &None, &VotingStrategy::Quadratic),
+        Err(Ok(GovernanceError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_gov_set_voting_strategy(
+            &admin,
+            &Some(Symbol::new(&env, "unknown_kind")),
+            &VotingStrategy::Quadratic
+        ),
+        Err(Ok(GovernanceError::UnknownProposalKind))
+    );
+    assert_eq!(
+        client.try_gov_set_strategy_quorum(&admin, &VotingStrategy::Quadratic, &-1),
+        Err(Ok(GovernanceError::InvalidQuorum))
+    );
+    assert_eq!(
+        client.gov_get_voting_strategy(&ProposalType::EmergencyPause(true)),
+        VotingStrategy::TokenWeighted
+    );
+}
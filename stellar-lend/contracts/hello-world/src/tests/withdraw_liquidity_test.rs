@@ -0,0 +1,93 @@
+//! Utilization-aware withdraw guard tests.
+//!
+//! # Coverage
+//! - Withdrawals cannot take supply that outstanding borrows have claim on
+//! - `get_max_withdraw` is bounded by the collateral ratio and by liquidity
+//! - Cross-asset withdrawals share the liquidity guard
+
+use crate::cross_asset::{
+    cross_asset_borrow, cross_asset_deposit, cross_asset_withdraw, initialize, initialize_asset,
+    record_borrow_change, AssetConfig, CrossAssetError,
+};
+use crate::tests::test_helpers::setup_env_with_native_asset;
+use crate::withdraw::{withdraw_collateral, WithdrawError};
+use soroban_sdk::{testutils::Address as _, Address};
+
+#[test]
+fn test_withdraw_limited_to_available_liquidity() {
+    let (env, id, client, _admin, lender, _native) = setup_env_with_native_asset();
+    client.deposit_collateral(&lender, &None, &1_000);
+    assert_eq!(client.get_available_liquidity(&None), 1_000);
+    assert_eq!(client.get_max_withdraw(&lender, &None), 1_000);
+
+    // Borrowers elsewhere have claim on 800 of the supply
+    env.as_contract(&id, || record_borrow_change(&env, None, 800));
+    assert_eq!(client.get_available_liquidity(&None), 200);
+    assert_eq!(client.get_max_withdraw(&lender, &None), 200);
+
+    env.as_contract(&id, || {
+        assert_eq!(
+            withdraw_collateral(&env, lender.clone(), None, 201),
+            Err(WithdrawError::InsufficientLiquidity)
+        );
+        assert_eq!(
+            withdraw_collateral(&env, lender.clone(), None, 200),
+            Ok(800)
+        );
+    });
+    assert_eq!(client.get_available_liquidity(&None), 0);
+    assert_eq!(client.get_max_withdraw(&lender, &None), 0);
+}
+
+#[test]
+fn test_max_withdraw_keeps_collateral_ratio() {
+    let (env, id, client, _admin, user, _native) = setup_env_with_native_asset();
+    client.deposit_collateral(&user, &None, &1_500);
+    client.borrow_asset(&user, &None, &600);
+
+    // 600 of debt needs 900 of collateral at 150%; 900 is still available
+    assert_eq!(client.get_available_liquidity(&None), 900);
+    assert_eq!(client.get_max_withdraw(&user, &None), 600);
+
+    env.as_contract(&id, || {
+        assert_eq!(
+            withdraw_collateral(&env, user.clone(), None, 601),
+            Err(WithdrawError::InsufficientCollateralRatio)
+        );
+        assert_eq!(withdraw_collateral(&env, user.clone(), None, 600), Ok(900));
+    });
+    assert_eq!(client.get_max_withdraw(&user, &None), 0);
+}
+
+#[test]
+fn test_cross_asset_withdraw_limited_to_available_liquidity() {
+    let (env, id, _client, admin, _user, _native) = setup_env_with_native_asset();
+    let user = Address::generate(&env);
+    env.as_contract(&id, || {
+        initialize(&env, admin.clone()).unwrap();
+        let config = AssetConfig {
+            asset: None,
+            collateral_factor: 9000,
+            liquidation_threshold: 9500,
+            reserve_factor: 1000,
+            max_supply: 0,
+            max_borrow: 0,
+            can_collateralize: true,
+            can_borrow: true,
+            price: 10_000_000,
+            price_updated_at: env.ledger().timestamp(),
+            is_isolated: false,
+            isolated_debt_ceiling: 0,
+        };
+        initialize_asset(&env, None, config).unwrap();
+        cross_asset_deposit(&env, user.clone(), None, 1_000_000).unwrap();
+        cross_asset_borrow(&env, user.clone(), None, 100_000).unwrap();
+        record_borrow_change(&env, None, 300_000);
+
+        assert_eq!(
+            cross_asset_withdraw(&env, user.clone(), None, 600_001),
+            Err(CrossAssetError::InsufficientLiquidity)
+        );
+        assert!(cross_asset_withdraw(&env, user.clone(), None, 600_000).is_ok());
+    });
+}
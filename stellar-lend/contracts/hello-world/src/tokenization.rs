@@ -0,0 +1,163 @@
+//! # Receipt Tokenization
+//!
+//! Optional SEP-41 tokens that mirror positions so other Soroban protocols
+//! can build on them:
+//!
+//! - **slToken** mirrors a user's collateral. It is minted as collateral grows
+//!   (deposits, supply interest) and burned as it shrinks. Receipts are
+//!   transferable, so a withdrawal requires the user to still hold receipts
+//!   for the amount leaving the position.
+//! - **debtToken** mirrors a borrower's principal. It is non-transferable, so
+//!   balances always match positions exactly.
+//!
+//! Both tokens are deployed separately (see the `receipt-token` contract) with
+//! this contract as their admin, then registered with `set_receipt_tokens`.
+//! Balances are synced whenever a position is updated; users whose position
+//! predates registration receive their receipts on their next update.
+//!
+//! Liquidation burns seized receipts on a best-effort basis: receipts the
+//! borrower moved away are left outstanding rather than blocking the
+//! liquidation.
+
+use soroban_sdk::{contractclient, contracterror, contracttype, Address, Env};
+
+use crate::deposit::Position;
+
+/// Interface of the receipt token contract used by the protocol
+#[contractclient(name = "ReceiptTokenClient")]
+pub trait ReceiptTokenInterface {
+    /// Admin allowed to mint and claw back
+    fn admin(env: Env) -> Address;
+    /// Balance of `id`
+    fn balance(env: Env, id: Address) -> i128;
+    /// Mint `amount` to `to` (admin only)
+    fn mint(env: Env, to: Address, amount: i128);
+    /// Burn `amount` from `from` (admin only)
+    fn clawback(env: Env, from: Address, amount: i128);
+}
+
+/// Errors that can occur in tokenization operations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum TokenizationError {
+    /// Caller is not the admin
+    Unauthorized = 1,
+    /// Token is not administered by this contract
+    InvalidToken = 2,
+    /// User holds fewer receipts than the collateral being withdrawn
+    InsufficientReceipts = 3,
+}
+
+/// Registered receipt tokens
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReceiptTokens {
+    /// slToken mirroring collateral (None = not issued)
+    pub supply_token: Option<Address>,
+    /// debtToken mirroring principal (None = not issued)
+    pub debt_token: Option<Address>,
+}
+
+/// Storage keys for tokenization data
+#[contracttype]
+#[derive(Clone)]
+pub enum TokenizationDataKey {
+    /// Registered receipt tokens
+    /// Value type: ReceiptTokens
+    ReceiptTokens,
+    /// slTokens issued to a user and not yet burned (token, user)
+    /// Value type: i128
+    SupplyIssued(Address, Address),
+}
+
+/// Register or replace the receipt tokens (admin only)
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not admin
+/// * `InvalidToken` - A token is not administered by this contract
+pub fn set_receipt_tokens(
+    env: &Env,
+    caller: Address,
+    tokens: ReceiptTokens,
+) -> Result<(), TokenizationError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| TokenizationError::Unauthorized)?;
+    for token in tokens.supply_token.iter().chain(tokens.debt_token.iter()) {
+        if ReceiptTokenClient::new(env, token).admin() != env.current_contract_address() {
+            return Err(TokenizationError::InvalidToken);
+        }
+    }
+    env.storage()
+        .persistent()
+        .set(&TokenizationDataKey::ReceiptTokens, &tokens);
+    Ok(())
+}
+
+/// Get the registered receipt tokens
+pub fn get_receipt_tokens(env: &Env) -> ReceiptTokens {
+    env.storage()
+        .persistent()
+        .get(&TokenizationDataKey::ReceiptTokens)
+        .unwrap_or_default()
+}
+
+/// Bring a user's slToken balance in line with their collateral
+///
+/// Mints the growth since the last sync, or burns the reduction. When `strict`
+/// (withdrawals), a user holding too few receipts is rejected; otherwise only
+/// the receipts they hold are burned.
+///
+/// # Errors
+/// * `InsufficientReceipts` - Strict sync and the user holds too few receipts
+pub(crate) fn sync_supply_receipts(
+    env: &Env,
+    user: &Address,
+    collateral: i128,
+    strict: bool,
+) -> Result<(), TokenizationError> {
+    let Some(token) = get_receipt_tokens(env).supply_token else {
+        return Ok(());
+    };
+    let client = ReceiptTokenClient::new(env, &token);
+    let issued_key = TokenizationDataKey::SupplyIssued(token, user.clone());
+    let issued: i128 = env.storage().persistent().get(&issued_key).unwrap_or(0);
+    let target = collateral.max(0);
+
+    if target > issued {
+        client.mint(user, &(target - issued));
+    } else if target < issued {
+        let to_burn = issued - target;
+        let held = client.balance(user);
+        if held < to_burn && strict {
+            return Err(TokenizationError::InsufficientReceipts);
+        }
+        let burned = to_burn.min(held);
+        if burned > 0 {
+            client.clawback(user, &burned);
+        }
+    }
+    env.storage().persistent().set(&issued_key, &target);
+    Ok(())
+}
+
+/// Bring a borrower's debtToken balance in line with their principal
+pub(crate) fn sync_debt_token(env: &Env, user: &Address, debt: i128) {
+    let Some(token) = get_receipt_tokens(env).debt_token else {
+        return;
+    };
+    let client = ReceiptTokenClient::new(env, &token);
+    let balance = client.balance(user);
+    let target = debt.max(0);
+    if target > balance {
+        client.mint(user, &(target - balance));
+    } else if target < balance {
+        client.clawback(user, &(balance - target));
+    }
+}
+
+/// Sync both receipt tokens after a position update
+pub(crate) fn sync_position(env: &Env, user: &Address, position: &Position) {
+    // Non-strict sync cannot fail
+    let _ = sync_supply_receipts(env, user, position.collateral, false);
+    sync_debt_token(env, user, position.debt);
+}
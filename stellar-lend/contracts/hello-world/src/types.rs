@@ -1,4 +1,7 @@
-use soroban_sdk::{contracttype, Address, Bytes, String, Symbol, Vec};
+use crate::cross_asset::AssetConfig;
+use crate::interest_rate::InterestRateConfigUpdate;
+use crate::subsidy_campaign::SubsidyCampaignTerms;
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, Map, String, Symbol, Vec};
 
 // ========================================================================
 // Proposal Types
@@ -25,6 +28,18 @@ pub enum VoteType {
     Abstain,
 }
 
+/// How a voter's voting power converts into votes
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum VotingStrategy {
+    /// One vote per token
+    TokenWeighted,
+    /// Square root of the token amount
+    Quadratic,
+    /// One vote per address with any voting power
+    OneAddressOneVote,
+}
+
 /// Proposal type for protocol parameter changes
 #[derive(Clone, Debug, PartialEq)]
 #[contracttype]
@@ -37,8 +52,23 @@ pub enum ProposalType {
     PauseSwitch(Symbol, bool),
     /// Emergency pause
     EmergencyPause(bool),
-    /// Generic action for future extensions
+    /// Set an asset's reserve factor (asset, bps)
+    ReserveFactor(Option<Address>, i128),
+    /// Add or remove an exit fee exemption (account, exempt)
+    ExitExemption(Address, bool),
+    /// Add or remove an external risk engine from the whitelist (engine, whitelisted)
+    RiskEngine(Address, bool),
+    /// Update the interest rate model
+    InterestRateConfig(InterestRateConfigUpdate),
+    /// List a new cross-asset market (keyed by `AssetConfig::asset`)
+    ListAsset(AssetConfig),
+    /// Generic action for future extensions; not executable yet, so proposals
+    /// of this type are rejected
     GenericAction(Action),
+    /// Replace the contract Wasm with the uploaded code of this hash
+    UpgradeContract(BytesN<32>),
+    /// Fund a borrow rate subsidy campaign from the asset's protocol reserve
+    SubsidyCampaign(SubsidyCampaignTerms),
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -58,6 +88,8 @@ pub struct Proposal {
     pub abstain_votes: i128,
     pub total_voting_power: i128,
     pub created_at: u64,
+    /// Fixed when the proposal is created
+    pub voting_strategy: VotingStrategy,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -70,6 +102,14 @@ pub struct VoteInfo {
     pub timestamp: u64,
 }
 
+/// Votes delegated to an account from `timestamp` on
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct VoteCheckpoint {
+    pub timestamp: u64,
+    pub votes: i128,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 #[contracttype]
 pub struct ProposalOutcome {
@@ -106,6 +146,12 @@ pub struct GovernanceConfig {
     pub vote_token: Address,            // Token used for voting
     pub timelock_duration: u64,         // Max time before expiration
     pub default_voting_threshold: i128, // Default 50% in basis points
+    /// Strategy of proposal kinds without an override
+    pub voting_strategy: VotingStrategy,
+    /// Strategy per proposal kind (see `governance::proposal_kind`)
+    pub strategy_overrides: Map<Symbol, VotingStrategy>,
+    /// Minimum votes cast for quorum, in each strategy's units
+    pub strategy_quorums: Map<VotingStrategy, i128>,
 }
 
 // ========================================================================
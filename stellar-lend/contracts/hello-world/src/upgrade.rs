@@ -0,0 +1,239 @@
+//! # Contract Upgrades and Storage Migration
+//!
+//! Replaces the contract's Wasm and brings persistent storage up to the
+//! layout the new code expects.
+//!
+//! ## Upgrading
+//! Code can only change after a delay the community can react to:
+//! - Through governance, with a `ProposalType::UpgradeContract` proposal,
+//!   which already waits out voting and the execution timelock.
+//! - Through the admin, who first calls [`schedule_upgrade`] and may run
+//!   [`upgrade_wasm`] with the same hash once [`UPGRADE_TIMELOCK_SECONDS`]
+//!   have passed. A scheduled upgrade can be cancelled at any time.
+//!
+//! ## Migrating
+//! Each release sets [`CONTRACT_VERSION`] to the storage layout it expects
+//! and adds a step to [`run_migration`] for the new version. After the Wasm
+//! is swapped, the admin calls [`migrate`], which runs every step between
+//! the stored layout version and `CONTRACT_VERSION` in order, recording the
+//! version after each. Fresh deployments start at `CONTRACT_VERSION`.
+
+use soroban_sdk::{contracterror, contracttype, Address, BytesN, Env};
+
+use crate::events::{
+    emit_contract_upgraded, emit_storage_migrated, emit_upgrade_scheduled, ContractUpgradedEvent,
+    StorageMigratedEvent, UpgradeScheduledEvent,
+};
+
+/// Storage layout version this code expects
+pub const CONTRACT_VERSION: u32 = 1;
+
+/// Delay between scheduling and applying an admin upgrade (48 hours)
+pub const UPGRADE_TIMELOCK_SECONDS: u64 = 48 * 60 * 60;
+
+/// Errors that can occur during upgrades and migrations
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum UpgradeError {
+    /// Caller is not the admin
+    Unauthorized = 1,
+    /// No upgrade is scheduled
+    NoPendingUpgrade = 2,
+    /// The hash differs from the scheduled upgrade
+    HashMismatch = 3,
+    /// The upgrade timelock has not elapsed
+    TimelockNotElapsed = 4,
+    /// Storage is already at `CONTRACT_VERSION`
+    AlreadyMigrated = 5,
+    /// A migration step failed
+    MigrationFailed = 6,
+}
+
+/// Storage keys for upgrades
+#[contracttype]
+#[derive(Clone)]
+pub enum UpgradeDataKey {
+    /// Layout version of persistent storage (absent before versioning: 0)
+    /// Value type: u32
+    StorageVersion,
+    /// Admin upgrade waiting out the timelock
+    /// Value type: PendingUpgrade
+    PendingUpgrade,
+}
+
+/// Admin upgrade waiting out the timelock
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingUpgrade {
+    pub wasm_hash: BytesN<32>,
+    /// Earliest time the upgrade can be applied
+    pub eta: u64,
+}
+
+/// Code and storage versions of the contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractVersion {
+    /// Storage layout version the running code expects
+    pub code_version: u32,
+    /// Layout version persistent storage is in
+    pub storage_version: u32,
+}
+
+/// Get the code and storage versions
+///
+/// A `storage_version` below `code_version` means [`migrate`] is due.
+pub fn get_contract_version(env: &Env) -> ContractVersion {
+    ContractVersion {
+        code_version: CONTRACT_VERSION,
+        storage_version: get_storage_version(env),
+    }
+}
+
+fn get_storage_version(env: &Env) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&UpgradeDataKey::StorageVersion)
+        .unwrap_or(0)
+}
+
+/// Record a fresh deployment as already at `CONTRACT_VERSION`
+pub(crate) fn init_storage_version(env: &Env) {
+    env.storage()
+        .persistent()
+        .set(&UpgradeDataKey::StorageVersion, &CONTRACT_VERSION);
+}
+
+/// Get the admin upgrade waiting out the timelock, if any
+pub fn get_pending_upgrade(env: &Env) -> Option<PendingUpgrade> {
+    env.storage()
+        .persistent()
+        .get(&UpgradeDataKey::PendingUpgrade)
+}
+
+/// Schedule an upgrade to `wasm_hash` (admin only)
+///
+/// Replaces any upgrade already scheduled and restarts the timelock.
+///
+/// # Returns
+/// The earliest time the upgrade can be applied
+pub fn schedule_upgrade(
+    env: &Env,
+    caller: Address,
+    wasm_hash: BytesN<32>,
+) -> Result<u64, UpgradeError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| UpgradeError::Unauthorized)?;
+
+    let eta = env.ledger().timestamp() + UPGRADE_TIMELOCK_SECONDS;
+    env.storage().persistent().set(
+        &UpgradeDataKey::PendingUpgrade,
+        &PendingUpgrade {
+            wasm_hash: wasm_hash.clone(),
+            eta,
+        },
+    );
+    emit_upgrade_scheduled(
+        env,
+        UpgradeScheduledEvent {
+            actor: caller,
+            wasm_hash,
+            eta,
+        },
+    );
+    Ok(eta)
+}
+
+/// Cancel the scheduled upgrade (admin only)
+pub fn cancel_upgrade(env: &Env, caller: Address) -> Result<(), UpgradeError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| UpgradeError::Unauthorized)?;
+    if get_pending_upgrade(env).is_none() {
+        return Err(UpgradeError::NoPendingUpgrade);
+    }
+    env.storage()
+        .persistent()
+        .remove(&UpgradeDataKey::PendingUpgrade);
+    Ok(())
+}
+
+/// Apply the scheduled upgrade once its timelock has elapsed (admin only)
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not the admin
+/// * `NoPendingUpgrade` - No upgrade is scheduled
+/// * `HashMismatch` - `wasm_hash` is not the scheduled hash
+/// * `TimelockNotElapsed` - Called before the scheduled time
+pub fn upgrade_wasm(env: &Env, caller: Address, wasm_hash: BytesN<32>) -> Result<(), UpgradeError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| UpgradeError::Unauthorized)?;
+    let pending = get_pending_upgrade(env).ok_or(UpgradeError::NoPendingUpgrade)?;
+    if pending.wasm_hash != wasm_hash {
+        return Err(UpgradeError::HashMismatch);
+    }
+    if env.ledger().timestamp() < pending.eta {
+        return Err(UpgradeError::TimelockNotElapsed);
+    }
+    env.storage()
+        .persistent()
+        .remove(&UpgradeDataKey::PendingUpgrade);
+    apply_upgrade(env, wasm_hash);
+    Ok(())
+}
+
+/// Swap the contract's Wasm; callers enforce the delay
+pub(crate) fn apply_upgrade(env: &Env, wasm_hash: BytesN<32>) {
+    env.deployer()
+        .update_current_contract_wasm(wasm_hash.clone());
+    emit_contract_upgraded(
+        env,
+        ContractUpgradedEvent {
+            wasm_hash,
+            previous_version: CONTRACT_VERSION,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+}
+
+/// Bring persistent storage up to `CONTRACT_VERSION` (admin only)
+///
+/// # Returns
+/// The new storage version
+///
+/// # Errors
+/// * `Unauthorized` - Caller is not the admin
+/// * `AlreadyMigrated` - Storage is already at `CONTRACT_VERSION`
+/// * `MigrationFailed` - A step failed; no version is recorded
+pub fn migrate(env: &Env, caller: Address) -> Result<u32, UpgradeError> {
+    crate::admin::require_admin(env, &caller).map_err(|_| UpgradeError::Unauthorized)?;
+    let from_version = get_storage_version(env);
+    if from_version >= CONTRACT_VERSION {
+        return Err(UpgradeError::AlreadyMigrated);
+    }
+
+    for version in (from_version + 1)..=CONTRACT_VERSION {
+        run_migration(env, version)?;
+        env.storage()
+            .persistent()
+            .set(&UpgradeDataKey::StorageVersion, &version);
+    }
+    emit_storage_migrated(
+        env,
+        StorageMigratedEvent {
+            from_version,
+            to_version: CONTRACT_VERSION,
+            timestamp: env.ledger().timestamp(),
+        },
+    );
+    Ok(CONTRACT_VERSION)
+}
+
+/// Transform storage from layout `version - 1` to `version`
+///
+/// Add an arm per release that changes a stored layout; steps must be
+/// idempotent so a partially migrated deployment can be re-run.
+fn run_migration(_env: &Env, version: u32) -> Result<(), UpgradeError> {
+    match version {
+        // First versioned layout: existing entries are already in it
+        1 => Ok(()),
+        _ => Err(UpgradeError::MigrationFailed),
+    }
+}
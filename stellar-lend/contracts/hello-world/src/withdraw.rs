@@ -6,6 +6,7 @@ use crate::deposit::{
     UserAnalytics,
 };
 use crate::events::{emit_withdrawal, WithdrawalEvent};
+use crate::operations::{record_operation, OperationKind};
 
 /// Errors that can occur during withdraw operations
 #[contracterror]
@@ -28,6 +29,14 @@ pub enum WithdrawError {
     Reentrancy = 7,
     /// Position would become undercollateralized
     Undercollateralized = 8,
+    /// Called while a flash loan is outstanding
+    FlashLoanInProgress = 9,
+    /// User holds fewer slTokens than the collateral being withdrawn
+    InsufficientReceiptTokens = 10,
+    /// The external risk engine rejected the withdrawal
+    RiskCheckRejected = 11,
+    /// Withdrawal would take supply that outstanding borrows have claim on
+    InsufficientLiquidity = 12,
 }
 
 // Minimum collateral ratio is now managed by the risk_params module
@@ -56,6 +65,20 @@ fn calculate_collateral_ratio(
     collateral_value.checked_mul(10000)?.checked_div(total_debt)
 }
 
+/// Collateral factor of the withdrawn asset
+/// Defaults to 100% for native XLM and assets without parameters
+fn get_collateral_factor(env: &Env, asset: Option<&Address>) -> i128 {
+    let params = asset.and_then(|asset_addr| {
+        let asset_params_key = DepositDataKey::AssetParams(asset_addr.clone());
+        env.storage()
+            .persistent()
+            .get::<DepositDataKey, AssetParams>(&asset_params_key)
+    });
+    params
+        .map(|params| params.collateral_factor)
+        .unwrap_or(10000)
+}
+
 /// Check if withdrawal would violate minimum collateral ratio
 fn validate_collateral_ratio_after_withdraw(
     env: &Env,
@@ -90,21 +113,7 @@ fn validate_collateral_ratio_after_withdraw(
         .ok_or(WithdrawError::InsufficientCollateral)?;
 
     // Get asset parameters for collateral factor
-    // Default collateral factor if asset params not found
-    let collateral_factor = if let Some(asset_addr) = asset {
-        let asset_params_key = DepositDataKey::AssetParams(asset_addr.clone());
-        if let Some(params) = env
-            .storage()
-            .persistent()
-            .get::<DepositDataKey, AssetParams>(&asset_params_key)
-        {
-            params.collateral_factor
-        } else {
-            10000 // Default 100% if not configured
-        }
-    } else {
-        10000 // Default 100% for native XLM
-    };
+    let collateral_factor = get_collateral_factor(env, asset);
 
     // Calculate total debt (debt + accrued interest)
     let _total_debt = position
@@ -132,11 +141,60 @@ fn validate_collateral_ratio_after_withdraw(
     Ok(())
 }
 
+/// Largest amount a user can withdraw now
+///
+/// The smallest of the user's collateral, the collateral left free by the
+/// minimum collateral ratio, and the asset's available liquidity. Pause
+/// switches and the external risk hook are not considered.
+///
+/// # Arguments
+/// * `env` - The Soroban environment
+/// * `user` - The address of the user
+/// * `asset` - The asset to withdraw (None for native XLM)
+pub fn get_max_withdraw(env: &Env, user: &Address, asset: Option<Address>) -> i128 {
+    let recorded_collateral = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, i128>(&DepositDataKey::CollateralBalance(user.clone()))
+        .unwrap_or(0);
+    let collateral = recorded_collateral.max(crate::deposit::get_underlying_balance(env, user));
+
+    let position = env
+        .storage()
+        .persistent()
+        .get::<DepositDataKey, Position>(&DepositDataKey::Position(user.clone()));
+    let free_collateral = match position {
+        Some(position) if position.debt > 0 || position.borrow_interest > 0 => {
+            let total_debt = position.debt.saturating_add(position.borrow_interest);
+            let min_ratio = crate::risk_params::get_min_collateral_ratio(env).unwrap_or(15000);
+            let collateral_factor = get_collateral_factor(env, asset.as_ref());
+            // Smallest collateral whose ratio stays at the minimum, rounded up
+            let required = total_debt
+                .checked_mul(min_ratio)
+                .map(|v| (v + 9_999) / 10_000)
+                .and_then(|value| value.checked_mul(10_000))
+                .filter(|_| collateral_factor > 0)
+                .map(|v| (v + collateral_factor - 1) / collateral_factor);
+            match required {
+                Some(required) => (collateral - required).max(0),
+                None => 0,
+            }
+        }
+        _ => collateral,
+    };
+
+    match crate::cross_asset::withdraw_liquidity_limit(env, asset) {
+        Some(limit) => free_collateral.min(limit),
+        None => free_collateral,
+    }
+}
+
 /// Withdraw collateral from the protocol
 ///
 /// Allows users to withdraw their deposited collateral, subject to:
 /// - Sufficient collateral balance
 /// - Minimum collateral ratio requirements
+/// - Available liquidity of the asset (supply not lent out)
 /// - Pause switch checks
 ///
 /// # Arguments
@@ -154,7 +212,9 @@ fn validate_collateral_ratio_after_withdraw(
 /// * `WithdrawError::InsufficientCollateral` - If user doesn't have enough collateral
 /// * `WithdrawError::WithdrawPaused` - If withdrawals are paused
 /// * `WithdrawError::InsufficientCollateralRatio` - If withdrawal would violate minimum ratio
+/// * `WithdrawError::InsufficientLiquidity` - If withdrawal would push utilization above 100%
 /// * `WithdrawError::Overflow` - If calculation overflow occurs
+/// * `WithdrawError::InsufficientReceiptTokens` - If the user no longer holds the slTokens for the amount
 ///
 /// # Security
 /// * Validates withdraw amount > 0
@@ -170,6 +230,17 @@ pub fn withdraw_collateral(
     user: Address,
     asset: Option<Address>,
     amount: i128,
+) -> Result<i128, WithdrawError> {
+    withdraw_collateral_to(env, user.clone(), user, asset, amount)
+}
+
+/// Withdraw collateral from `user`'s position and pay it to `recipient`
+pub(crate) fn withdraw_collateral_to(
+    env: &Env,
+    user: Address,
+    recipient: Address,
+    asset: Option<Address>,
+    amount: i128,
 ) -> Result<i128, WithdrawError> {
     // Validate amount
     if amount <= 0 {
@@ -179,6 +250,11 @@ pub fn withdraw_collateral(
     // Check for reentrancy
     let _guard = crate::reentrancy::ReentrancyGuard::new(env).map_err(|_| WithdrawError::Reentrancy)?;
 
+    // Flash loan receivers may not withdraw while the loan is outstanding
+    if crate::reentrancy::is_flash_loan_locked(env) {
+        return Err(WithdrawError::FlashLoanInProgress);
+    }
+
     // Check if withdrawals are paused
     let pause_switches_key = DepositDataKey::PauseSwitches;
     if let Some(pause_map) = env
@@ -193,6 +269,9 @@ pub fn withdraw_collateral(
         }
     }
 
+    // Settle borrow interest at the current utilization before it changes
+    crate::interest_rate::accrue_borrow_index(env).map_err(|_| WithdrawError::Overflow)?;
+
     // Get current timestamp
     let timestamp = env.ledger().timestamp();
 
@@ -204,13 +283,21 @@ pub fn withdraw_collateral(
         }
     }
 
-    // Get current collateral balance
+    // Get current collateral balance, including interest earned by supply shares
+    crate::deposit::sync_legacy_collateral(env, &user).map_err(|_| WithdrawError::Overflow)?;
     let collateral_key = DepositDataKey::CollateralBalance(user.clone());
-    let current_collateral = env
+    let recorded_collateral = env
         .storage()
         .persistent()
         .get::<DepositDataKey, i128>(&collateral_key)
         .unwrap_or(0);
+    let current_collateral =
+        recorded_collateral.max(crate::deposit::get_underlying_balance(env, &user));
+    if current_collateral > recorded_collateral {
+        env.storage()
+            .persistent()
+            .set(&collateral_key, &current_collateral);
+    }
 
     // Check sufficient collateral
     if current_collateral < amount {
@@ -220,15 +307,39 @@ pub fn withdraw_collateral(
     // Validate collateral ratio after withdrawal
     validate_collateral_ratio_after_withdraw(env, &user, amount, asset.as_ref())?;
 
+    // Leave the supply that outstanding borrows of the asset have claim on
+    crate::cross_asset::check_withdraw_liquidity(env, asset.clone(), amount)
+        .map_err(|_| WithdrawError::InsufficientLiquidity)?;
+
+    // Large withdrawals may be vetoed by the external risk engine
+    crate::risk_hook::check_operation(env, &user, Symbol::new(env, "withdraw"), &asset, amount)
+        .map_err(|_| WithdrawError::RiskCheckRejected)?;
+
     // Calculate new collateral balance
     let new_collateral = current_collateral
         .checked_sub(amount)
         .ok_or(WithdrawError::Overflow)?;
 
-    // Update storage
+    // Burn the slTokens for the withdrawn collateral; receipts moved elsewhere
+    // cannot be redeemed here
+    crate::tokenization::sync_supply_receipts(env, &user, new_collateral, true)
+        .map_err(|_| WithdrawError::InsufficientReceiptTokens)?;
+
+    // Update storage and redeem the shares backing the withdrawn amount
     env.storage()
         .persistent()
         .set(&collateral_key, &new_collateral);
+    crate::deposit::redeem_supply_shares(env, &user, amount)
+        .map_err(|_| WithdrawError::Overflow)?;
+    crate::cross_asset::record_supply_change(env, asset.clone(), -amount);
+    crate::deposit::update_asset_ledger(env, &asset, -amount, 0);
+    crate::rewards::record_balance_change(
+        env,
+        &user,
+        &asset,
+        crate::rewards::RewardSide::Supply,
+        -amount,
+    );
 
     // Get or update user position
     let position_key = DepositDataKey::Position(user.clone());
@@ -249,14 +360,18 @@ pub fn withdraw_collateral(
     position.last_accrual_time = timestamp;
     env.storage().persistent().set(&position_key, &position);
 
+    // Withhold the protocol withdrawal fee
+    let fee = crate::fees::charge_fee(env, crate::fees::FeeKind::Withdrawal, &user, &asset, amount)
+        .map_err(|_| WithdrawError::Overflow)?;
+
     // Handle asset transfer
     if let Some(ref asset_addr) = asset {
-        // Transfer tokens from contract to user
+        // Transfer tokens from contract to the recipient
         let token_client = soroban_sdk::token::Client::new(env, asset_addr);
         token_client.transfer(
             &env.current_contract_address(), // from (this contract)
-            &user,                           // to (user)
-            &amount,
+            &recipient,                      // to (recipient)
+            &(amount - fee),
         );
     } else {
         // Native XLM withdrawal - in Soroban, native assets are handled differently
@@ -285,6 +400,14 @@ pub fn withdraw_collateral(
     })?;
 
     // Emit withdraw event
+    let operation_id = record_operation(
+        env,
+        OperationKind::Withdraw,
+        &user,
+        &asset,
+        amount,
+        timestamp,
+    );
     emit_withdrawal(
         env,
         WithdrawalEvent {
@@ -292,6 +415,7 @@ pub fn withdraw_collateral(
             asset: asset.clone(),
             amount,
             timestamp,
+            operation_id,
         },
     );
 
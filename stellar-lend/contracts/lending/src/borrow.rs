@@ -5,13 +5,14 @@
 //!
 //! ## Interest Model
 //! Uses a fixed 5% APY simple interest model:
-//! `interest = principal * 500bps * time_elapsed / seconds_per_year`
+//! `interest = principal * 500bps * time_elapsed / seconds_per_year`,
+//! computed with the `stellarlend-math` crate shared with the core contract.
 //!
 //! ## Collateral Requirements
 //! Minimum collateral ratio is 150% (15,000 basis points).
 
 use crate::pause::{self, PauseType};
-use soroban_sdk::{contracterror, contractevent, contracttype, Address, Env, I256};
+use soroban_sdk::{contracterror, contractevent, contracttype, Address, Env};
 
 /// Errors that can occur during borrow operations.
 #[contracterror]
@@ -36,6 +37,12 @@ pub enum BorrowError {
     BelowMinimumBorrow = 8,
     /// Repay amount exceeds current debt
     RepayAmountTooHigh = 9,
+    /// Position is healthy or cannot be valued, so it cannot be liquidated
+    PositionHealthy = 10,
+    /// Liquidation repays more than the close factor allows
+    ExceedsCloseFactor = 11,
+    /// The core contract's risk configuration could not be read
+    CoreUnavailable = 12,
 }
 
 /// Storage keys for protocol-wide data.
@@ -248,8 +255,28 @@ pub fn repay(env: &Env, user: Address, asset: Address, amount: i128) -> Result<(
         return Err(BorrowError::AssetNotSupported);
     }
 
-    // First repay interest, then principal
-    let accrued_interest = calculate_interest(env, &debt_position);
+    settle_debt(env, &user, &mut debt_position, amount)?;
+
+    RepayEvent {
+        user,
+        asset,
+        amount,
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+
+    Ok(())
+}
+
+/// Accrue interest on `debt_position`, then pay `amount` toward interest
+/// first and principal second, and save it
+pub(crate) fn settle_debt(
+    env: &Env,
+    user: &Address,
+    debt_position: &mut DebtPosition,
+    amount: i128,
+) -> Result<(), BorrowError> {
+    let accrued_interest = calculate_interest(env, debt_position);
     debt_position.interest_accrued = debt_position
         .interest_accrued
         .checked_add(accrued_interest)
@@ -280,16 +307,7 @@ pub fn repay(env: &Env, user: Address, asset: Address, amount: i128) -> Result<(
         set_total_debt(env, new_total);
     }
 
-    save_debt_position(env, &user, &debt_position);
-
-    RepayEvent {
-        user,
-        asset,
-        amount,
-        timestamp: env.ledger().timestamp(),
-    }
-    .publish(env);
-
+    save_debt_position(env, user, debt_position);
     Ok(())
 }
 
@@ -313,23 +331,20 @@ pub(crate) fn calculate_interest(env: &Env, position: &DebtPosition) -> i128 {
         return 0;
     }
 
-    let current_time = env.ledger().timestamp();
-    let time_elapsed = current_time.saturating_sub(position.last_update);
-
-    let borrowed_256 = I256::from_i128(env, position.borrowed_amount);
-    let rate_256 = I256::from_i128(env, INTEREST_RATE_PER_YEAR);
-    let time_256 = I256::from_i128(env, time_elapsed as i128);
-
-    let interest_256 = borrowed_256
-        .mul(&rate_256)
-        .mul(&time_256)
-        .div(&I256::from_i128(env, 10000))
-        .div(&I256::from_i128(env, SECONDS_PER_YEAR as i128));
-
-    interest_256.to_i128().unwrap_or(i128::MAX)
+    let time_elapsed = env
+        .ledger()
+        .timestamp()
+        .saturating_sub(position.last_update);
+    stellarlend_math::simple_interest(
+        position.borrowed_amount,
+        INTEREST_RATE_PER_YEAR,
+        time_elapsed,
+        SECONDS_PER_YEAR,
+    )
+    .unwrap_or(i128::MAX)
 }
 
-fn get_debt_position(env: &Env, user: &Address) -> DebtPosition {
+pub(crate) fn get_debt_position(env: &Env, user: &Address) -> DebtPosition {
     env.storage()
         .persistent()
         .get(&BorrowDataKey::BorrowUserDebt(user.clone()))
@@ -357,7 +372,7 @@ fn get_collateral_position(env: &Env, user: &Address) -> BorrowCollateral {
         })
 }
 
-fn save_collateral_position(env: &Env, user: &Address, position: &BorrowCollateral) {
+pub(crate) fn save_collateral_position(env: &Env, user: &Address, position: &BorrowCollateral) {
     env.storage()
         .persistent()
         .set(&BorrowDataKey::BorrowUserCollateral(user.clone()), position);
@@ -4,6 +4,7 @@ use soroban_sdk::{contract, contractimpl, Address, Bytes, Env, Val, Vec};
 mod borrow;
 mod deposit;
 mod flash_loan;
+mod liquidate;
 mod pause;
 mod token_receiver;
 mod withdraw;
@@ -24,6 +25,10 @@ use flash_loan::{
     flash_loan as flash_loan_logic, set_flash_loan_fee_bps as set_flash_loan_fee_logic,
     FlashLoanError,
 };
+use liquidate::{
+    get_core_contract as get_core_contract_logic, liquidate as liquidate_logic,
+    set_core_contract as set_core_contract_logic,
+};
 use pause::{is_paused, set_pause as set_pause_logic, PauseType};
 use token_receiver::receive as receive_logic;
 
@@ -49,6 +54,8 @@ mod deposit_test;
 #[cfg(test)]
 mod flash_loan_test;
 #[cfg(test)]
+mod liquidate_test;
+#[cfg(test)]
 mod pause_test;
 #[cfg(test)]
 mod token_receiver_test;
@@ -155,21 +162,41 @@ impl LendingContract {
         borrow_deposit(&env, user, asset, amount)
     }
 
-    /// Liquidate a position
+    /// Liquidate part of an unhealthy position
+    ///
+    /// Uses the close factor and incentive of the linked core contract.
+    /// Returns the collateral seized, incentive included.
     pub fn liquidate(
         env: Env,
         liquidator: Address,
-        _borrower: Address,
-        _debt_asset: Address,
-        _collateral_asset: Address,
-        _amount: i128,
-    ) -> Result<(), BorrowError> {
+        borrower: Address,
+        debt_asset: Address,
+        collateral_asset: Address,
+        amount: i128,
+    ) -> Result<i128, BorrowError> {
         liquidator.require_auth();
         if is_paused(&env, PauseType::Liquidation) {
             return Err(BorrowError::ProtocolPaused);
         }
-        // Stub implementation, or call borrow::liquidate if it exists
-        Ok(())
+        liquidate_logic(
+            &env,
+            liquidator,
+            borrower,
+            debt_asset,
+            collateral_asset,
+            amount,
+        )
+    }
+
+    /// Link the core contract whose close factor and liquidation incentive
+    /// liquidations use (admin only)
+    pub fn set_core_contract(env: Env, admin: Address, core: Address) -> Result<(), BorrowError> {
+        set_core_contract_logic(&env, &admin, core)
+    }
+
+    /// Get the linked core contract, if any
+    pub fn get_core_contract(env: Env) -> Option<Address> {
+        get_core_contract_logic(&env)
     }
 
     /// Get user's debt position
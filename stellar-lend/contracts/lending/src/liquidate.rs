@@ -0,0 +1,192 @@
+//! # Liquidation (Simplified Lending)
+//!
+//! Lets a liquidator repay part of an unhealthy borrower's debt in exchange
+//! for the borrower's collateral plus a liquidation incentive.
+//!
+//! ## Risk Configuration
+//! The close factor and liquidation incentive are read from the core
+//! (hello-world) contract once the admin links it with `set_core_contract`,
+//! so both contracts liquidate under the same parameters. The close factor is
+//! queried for the position being liquidated (`get_close_factor_for_position`),
+//! so the core's health factor and position size tiers apply here too. Until
+//! a core contract is linked, the defaults below apply.
+//! Health factors and close amounts use the `stellarlend-math` crate shared
+//! with the core contract.
+//!
+//! ## Accounting
+//! Like `repay`, liquidation only updates the borrow module's bookkeeping:
+//! the repaid amount settles interest first and principal second, and the
+//! seized collateral is removed from the borrower's collateral position.
+
+use soroban_sdk::{contractevent, contracttype, Address, Env, IntoVal, Symbol, Vec};
+
+use crate::borrow::{
+    get_admin, get_debt_position, get_oracle, get_user_collateral, save_collateral_position,
+    settle_debt, BorrowError,
+};
+use crate::views::{get_asset_price, get_health_factor, HEALTH_FACTOR_SCALE, PRICE_SCALE};
+use stellarlend_math::{close_amount, mul_div, BPS_SCALE};
+
+/// Close factor used until a core contract is linked (50%)
+pub const DEFAULT_CLOSE_FACTOR_BPS: i128 = 5000;
+
+/// Liquidation incentive used until a core contract is linked (10%)
+pub const DEFAULT_LIQUIDATION_INCENTIVE_BPS: i128 = 1000;
+
+/// Storage keys for liquidation settings.
+#[contracttype]
+#[derive(Clone)]
+pub enum LiquidationDataKey {
+    /// Core contract supplying the risk configuration (optional)
+    CoreContract,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct LiquidationEvent {
+    pub liquidator: Address,
+    pub borrower: Address,
+    pub debt_asset: Address,
+    pub collateral_asset: Address,
+    pub debt_repaid: i128,
+    pub collateral_seized: i128,
+    pub timestamp: u64,
+}
+
+/// Liquidate part of an unhealthy position
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `liquidator` - The liquidator's address
+/// * `borrower` - The borrower being liquidated
+/// * `debt_asset` - The borrower's debt asset
+/// * `collateral_asset` - The borrower's collateral asset
+/// * `amount` - Debt to repay, at most the close factor share of the debt
+///
+/// # Returns
+/// The collateral seized, incentive included
+///
+/// # Errors
+/// * `InvalidAmount` - Amount is zero or negative
+/// * `AssetNotSupported` - Assets do not match the borrower's position
+/// * `PositionHealthy` - Health factor is at or above 1.0, or cannot be computed
+/// * `ExceedsCloseFactor` - Amount exceeds the close factor share of the debt
+/// * `CoreUnavailable` - The linked core contract's configuration could not be read
+pub fn liquidate(
+    env: &Env,
+    liquidator: Address,
+    borrower: Address,
+    debt_asset: Address,
+    collateral_asset: Address,
+    amount: i128,
+) -> Result<i128, BorrowError> {
+    if amount <= 0 {
+        return Err(BorrowError::InvalidAmount);
+    }
+
+    let mut debt_position = get_debt_position(env, &borrower);
+    let mut collateral = get_user_collateral(env, &borrower);
+    if debt_position.asset != debt_asset || collateral.asset != collateral_asset {
+        return Err(BorrowError::AssetNotSupported);
+    }
+
+    let health_factor = get_health_factor(env, &borrower);
+    if health_factor <= 0 || health_factor >= HEALTH_FACTOR_SCALE {
+        return Err(BorrowError::PositionHealthy);
+    }
+
+    let (close_factor, incentive) = get_risk_config(
+        env,
+        crate::views::get_collateral_value(env, &borrower),
+        crate::views::get_debt_value(env, &borrower),
+    )?;
+    let total_debt = crate::views::get_debt_balance(env, &borrower);
+    let max_repay = close_amount(total_debt, close_factor).ok_or(BorrowError::Overflow)?;
+    if amount > max_repay {
+        return Err(BorrowError::ExceedsCloseFactor);
+    }
+
+    // Value the repayment in collateral, plus the incentive
+    let oracle = get_oracle(env).ok_or(BorrowError::PositionHealthy)?;
+    let debt_price = get_asset_price(env, &oracle, &debt_asset);
+    let collateral_price = get_asset_price(env, &oracle, &collateral_asset);
+    if debt_price <= 0 || collateral_price <= 0 {
+        return Err(BorrowError::PositionHealthy);
+    }
+    let repaid_value = mul_div(amount, debt_price, PRICE_SCALE).ok_or(BorrowError::Overflow)?;
+    let seized_value =
+        mul_div(repaid_value, BPS_SCALE + incentive, BPS_SCALE).ok_or(BorrowError::Overflow)?;
+    let seized = mul_div(seized_value, PRICE_SCALE, collateral_price)
+        .ok_or(BorrowError::Overflow)?
+        .min(collateral.amount);
+
+    settle_debt(env, &borrower, &mut debt_position, amount)?;
+    collateral.amount -= seized;
+    save_collateral_position(env, &borrower, &collateral);
+
+    LiquidationEvent {
+        liquidator,
+        borrower,
+        debt_asset,
+        collateral_asset,
+        debt_repaid: amount,
+        collateral_seized: seized,
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+
+    Ok(seized)
+}
+
+/// Link the core contract whose risk configuration liquidations use (admin only)
+pub fn set_core_contract(env: &Env, admin: &Address, core: Address) -> Result<(), BorrowError> {
+    let current = get_admin(env).ok_or(BorrowError::Unauthorized)?;
+    if *admin != current {
+        return Err(BorrowError::Unauthorized);
+    }
+    admin.require_auth();
+    env.storage()
+        .persistent()
+        .set(&LiquidationDataKey::CoreContract, &core);
+    Ok(())
+}
+
+/// Returns the linked core contract, if any
+pub fn get_core_contract(env: &Env) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&LiquidationDataKey::CoreContract)
+}
+
+/// Returns (close factor, liquidation incentive) in basis points for a
+/// position with the given collateral and debt value
+///
+/// Read from the linked core contract, or the defaults when none is linked.
+/// The core returns a close factor of 0 for positions its health tiers do not
+/// consider liquidatable.
+pub fn get_risk_config(
+    env: &Env,
+    collateral_value: i128,
+    debt_value: i128,
+) -> Result<(i128, i128), BorrowError> {
+    let Some(core) = get_core_contract(env) else {
+        return Ok((DEFAULT_CLOSE_FACTOR_BPS, DEFAULT_LIQUIDATION_INCENTIVE_BPS));
+    };
+    let close_factor = match env.try_invoke_contract::<i128, soroban_sdk::Error>(
+        &core,
+        &Symbol::new(env, "get_close_factor_for_position"),
+        (collateral_value, debt_value).into_val(env),
+    ) {
+        Ok(Ok(value)) if (0..=BPS_SCALE).contains(&value) => value,
+        _ => return Err(BorrowError::CoreUnavailable),
+    };
+    let incentive = match env.try_invoke_contract::<i128, soroban_sdk::Error>(
+        &core,
+        &Symbol::new(env, "get_liquidation_incentive"),
+        Vec::new(env),
+    ) {
+        Ok(Ok(value)) if (0..=BPS_SCALE).contains(&value) => value,
+        _ => return Err(BorrowError::CoreUnavailable),
+    };
+    Ok((close_factor, incentive))
+}
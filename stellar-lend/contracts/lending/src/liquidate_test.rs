@@ -0,0 +1,153 @@
+//! Tests for liquidation: health checks, close factor, collateral seizure and
+//! the risk configuration read from the core contract.
+
+use super::*;
+use soroban_sdk::{contract, contractimpl, testutils::Address as _, Address, Env};
+use stellarlend_testutils::{register_mock_oracle, MockOracleClient, PRICE_SCALE};
+
+/// Core contract stand-in exposing the risk configuration getters, with a
+/// smaller close factor for positions of 10_000 debt value or more
+#[contract]
+pub struct MockCore;
+
+#[contractimpl]
+impl MockCore {
+    pub fn get_close_factor_for_position(
+        _env: Env,
+        _collateral_value: i128,
+        debt_value: i128,
+    ) -> i128 {
+        if debt_value >= 10_000 {
+            2500
+        } else {
+            5000
+        }
+    }
+
+    pub fn get_liquidation_incentive(_env: Env) -> i128 {
+        500
+    }
+}
+
+/// Borrower with 10_000 debt against 20_000 collateral, collateral price
+/// halved so the health factor is 0.8
+fn setup_unhealthy(
+    env: &Env,
+) -> (
+    LendingContractClient<'_>,
+    Address,
+    Address,
+    Address,
+    Address,
+) {
+    let contract_id = env.register(LendingContract, ());
+    let client = LendingContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    let borrower = Address::generate(env);
+    let asset = Address::generate(env);
+    let collateral_asset = Address::generate(env);
+    client.initialize(&admin, &1_000_000_000, &1000);
+    client.borrow(&borrower, &asset, &10_000, &collateral_asset, &20_000);
+
+    let oracle_id = register_mock_oracle(env);
+    let oracle = MockOracleClient::new(env, &oracle_id);
+    oracle.set_default_price(&PRICE_SCALE);
+    oracle.set_price(&collateral_asset, &(PRICE_SCALE / 2));
+    client.set_oracle(&admin, &oracle_id);
+    assert_eq!(client.get_health_factor(&borrower), 8000);
+    (client, admin, borrower, asset, collateral_asset)
+}
+
+#[test]
+fn test_liquidate_with_default_risk_config() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _admin, borrower, asset, collateral_asset) = setup_unhealthy(&env);
+    let liquidator = Address::generate(&env);
+
+    // 50% close factor caps the repayment at 5_000
+    assert_eq!(
+        client.try_liquidate(&liquidator, &borrower, &asset, &collateral_asset, &5_001),
+        Err(Ok(BorrowError::ExceedsCloseFactor))
+    );
+
+    // 5_000 of debt plus 10% is 5_500 of value, 11_000 collateral at half price
+    let seized = client.liquidate(&liquidator, &borrower, &asset, &collateral_asset, &5_000);
+    assert_eq!(seized, 11_000);
+    assert_eq!(client.get_debt_balance(&borrower), 5_000);
+    assert_eq!(client.get_collateral_balance(&borrower), 9_000);
+}
+
+#[test]
+fn test_liquidate_uses_core_risk_config() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, borrower, asset, collateral_asset) = setup_unhealthy(&env);
+    let core = env.register(MockCore, ());
+    client.set_core_contract(&admin, &core);
+    assert_eq!(client.get_core_contract(), Some(core));
+    let liquidator = Address::generate(&env);
+
+    assert_eq!(
+        client.try_liquidate(&liquidator, &borrower, &asset, &collateral_asset, &2_501),
+        Err(Ok(BorrowError::ExceedsCloseFactor))
+    );
+    let seized = client.liquidate(&liquidator, &borrower, &asset, &collateral_asset, &2_500);
+    assert_eq!(seized, 5_250);
+    assert_eq!(client.get_collateral_balance(&borrower), 14_750);
+
+    // The close factor is queried for the position: at 7_500 debt it falls
+    // into the core's 50% tier
+    assert_eq!(
+        client.try_liquidate(&liquidator, &borrower, &asset, &collateral_asset, &3_751),
+        Err(Ok(BorrowError::ExceedsCloseFactor))
+    );
+    client.liquidate(&liquidator, &borrower, &asset, &collateral_asset, &3_750);
+    assert_eq!(client.get_debt_balance(&borrower), 3_750);
+}
+
+#[test]
+fn test_liquidate_fails_when_core_unavailable() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, borrower, asset, collateral_asset) = setup_unhealthy(&env);
+    client.set_core_contract(&admin, &Address::generate(&env));
+
+    assert_eq!(
+        client.try_liquidate(&admin, &borrower, &asset, &collateral_asset, &1_000),
+        Err(Ok(BorrowError::CoreUnavailable))
+    );
+}
+
+#[test]
+fn test_liquidate_rejects_healthy_and_mismatched_positions() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _admin, borrower, asset, collateral_asset) = setup_unhealthy(&env);
+    let liquidator = Address::generate(&env);
+
+    assert_eq!(
+        client.try_liquidate(&liquidator, &borrower, &collateral_asset, &asset, &1_000),
+        Err(Ok(BorrowError::AssetNotSupported))
+    );
+    assert_eq!(
+        client.try_liquidate(&liquidator, &borrower, &asset, &collateral_asset, &0),
+        Err(Ok(BorrowError::InvalidAmount))
+    );
+
+    // Another borrower at full collateral price is healthy
+    let healthy = Address::generate(&env);
+    let other_collateral = Address::generate(&env);
+    client.borrow(&healthy, &asset, &10_000, &other_collateral, &20_000);
+    assert_eq!(
+        client.try_liquidate(&liquidator, &healthy, &asset, &other_collateral, &1_000),
+        Err(Ok(BorrowError::PositionHealthy))
+    );
+
+    // Only the admin links a core contract
+    assert_eq!(
+        client.try_set_core_contract(&liquidator, &Address::generate(&env)),
+        Err(Ok(BorrowError::Unauthorized))
+    );
+    assert_eq!(client.get_core_contract(), None);
+}
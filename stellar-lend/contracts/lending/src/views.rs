@@ -9,7 +9,7 @@
 //! - Collateral and debt values depend on the oracle; ensure the oracle is correct and trusted.
 //! - Health factor uses the admin-set liquidation threshold consistently.
 
-use soroban_sdk::{contracttype, Address, Env, IntoVal, Symbol, I256};
+use soroban_sdk::{contracttype, Address, Env, IntoVal, Symbol};
 
 use crate::borrow::{
     get_liquidation_threshold_bps, get_oracle, get_user_collateral, get_user_debt,
@@ -17,10 +17,10 @@ use crate::borrow::{
 };
 
 /// Scale for oracle price (1e8 = one unit). Value = amount * price / PRICE_SCALE.
-const PRICE_SCALE: i128 = 100_000_000;
+pub(crate) const PRICE_SCALE: i128 = 100_000_000;
 
 /// Health factor scale: 10000 = 1.0 (healthy). Below 10000 = liquidatable.
-pub const HEALTH_FACTOR_SCALE: i128 = 10000;
+pub const HEALTH_FACTOR_SCALE: i128 = stellarlend_math::HEALTH_FACTOR_SCALE;
 
 /// Sentinel health factor when user has no debt (position is healthy).
 pub const HEALTH_FACTOR_NO_DEBT: i128 = 100_000_000;
@@ -52,7 +52,7 @@ pub struct UserPositionSummary {
 /// # Security
 /// This is read-only; no state is modified. Oracle is trusted (admin-configured).
 #[inline]
-fn get_asset_price(env: &Env, oracle: &Address, asset: &Address) -> i128 {
+pub(crate) fn get_asset_price(env: &Env, oracle: &Address, asset: &Address) -> i128 {
     env.invoke_contract(
         oracle,
         &Symbol::new(env, "price"),
@@ -74,11 +74,7 @@ pub(crate) fn collateral_value(env: &Env, collateral: &BorrowCollateral) -> i128
     if price <= 0 {
         return 0;
     }
-    let amount_256 = I256::from_i128(env, collateral.amount);
-    let price_256 = I256::from_i128(env, price);
-    let scale_256 = I256::from_i128(env, PRICE_SCALE);
-    let val_256 = amount_256.mul(&price_256).div(&scale_256);
-    val_256.to_i128().unwrap_or(0)
+    stellarlend_math::mul_div(collateral.amount, price, PRICE_SCALE).unwrap_or(0)
 }
 
 /// Computes debt value in common unit (total debt * price / PRICE_SCALE).
@@ -99,11 +95,7 @@ pub(crate) fn debt_value(env: &Env, position: &DebtPosition) -> i128 {
     if price <= 0 {
         return 0;
     }
-    let debt_256 = I256::from_i128(env, total_debt);
-    let price_256 = I256::from_i128(env, price);
-    let scale_256 = I256::from_i128(env, PRICE_SCALE);
-    let val_256 = debt_256.mul(&price_256).div(&scale_256);
-    val_256.to_i128().unwrap_or(0)
+    stellarlend_math::mul_div(total_debt, price, PRICE_SCALE).unwrap_or(0)
 }
 
 /// Computes health factor from collateral value, debt value, and liquidation threshold.
@@ -111,6 +103,7 @@ pub(crate) fn debt_value(env: &Env, position: &DebtPosition) -> i128 {
 /// Formula: `health_factor = (collateral_value * liquidation_threshold_bps / 10000) * HEALTH_FACTOR_SCALE / debt_value`
 /// So 10000 = 1.0; above 10000 is healthy, below is liquidatable.
 ///
+/// Computed with the `stellarlend-math` crate shared with the core contract.
 /// Returns `HEALTH_FACTOR_NO_DEBT` when debt is zero (position is healthy).
 /// Returns 0 when oracle is not set but user has debt (cannot compute).
 #[inline]
@@ -129,16 +122,12 @@ pub(crate) fn compute_health_factor(
     let Some(_) = get_oracle(env) else {
         return 0;
     };
-    let bps = get_liquidation_threshold_bps(env);
-    let collat_256 = I256::from_i128(env, collateral_value);
-    let bps_256 = I256::from_i128(env, bps);
-    let hf_scale_256 = I256::from_i128(env, HEALTH_FACTOR_SCALE);
-    let debt_256 = I256::from_i128(env, debt_value);
-
-    let weighted_collateral = collat_256.mul(&bps_256).div(&I256::from_i128(env, 10000));
-
-    let hf_256 = weighted_collateral.mul(&hf_scale_256).div(&debt_256);
-    hf_256.to_i128().unwrap_or(0)
+    stellarlend_math::health_factor(
+        collateral_value,
+        debt_value,
+        get_liquidation_threshold_bps(env),
+    )
+    .unwrap_or(0)
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
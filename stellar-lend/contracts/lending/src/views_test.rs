@@ -6,18 +6,14 @@ use soroban_sdk::{
     testutils::{Address as _, Ledger},
     Address, Env,
 };
+use stellarlend_testutils::{register_mock_oracle, MockOracleClient, PRICE_SCALE};
 use views::{HEALTH_FACTOR_NO_DEBT, HEALTH_FACTOR_SCALE};
 
-/// Mock oracle contract: returns fixed price (1.0 with 8 decimals) for any asset.
-#[contract]
-pub struct MockOracle;
-
-#[contractimpl]
-impl MockOracle {
-    /// Returns price with 8 decimals (100_000_000 = 1.0).
-    pub fn price(_env: Env, _asset: Address) -> i128 {
-        100_000_000
-    }
+/// Shared mock oracle returning a fixed price (1.0 with 8 decimals) for any asset.
+fn register_oracle(env: &Env) -> Address {
+    let oracle_id = register_mock_oracle(env);
+    MockOracleClient::new(env, &oracle_id).set_default_price(&PRICE_SCALE);
+    oracle_id
 }
 
 fn setup(
@@ -50,7 +46,7 @@ fn setup_with_oracle(
     Address,
 ) {
     let (client, admin, user, asset, collateral_asset) = setup(env);
-    let oracle_id = env.register(MockOracle, ());
+    let oracle_id = register_oracle(env);
     client.set_oracle(&admin, &oracle_id);
     (client, admin, user, asset, collateral_asset, oracle_id)
 }
@@ -258,7 +254,7 @@ fn test_set_oracle_unauthorized() {
     let env = Env::default();
     env.mock_all_auths();
     let (client, _admin, user, _asset, _collateral_asset) = setup(&env);
-    let oracle_id = env.register(MockOracle, ());
+    let oracle_id = register_oracle(&env);
     let result = client.try_set_oracle(&user, &oracle_id);
     assert_eq!(result, Err(Ok(BorrowError::Unauthorized)));
 }
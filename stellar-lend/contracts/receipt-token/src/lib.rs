@@ -0,0 +1,7 @@
+#![no_std]
+mod token;
+
+pub use token::{ReceiptToken, ReceiptTokenClient, TokenError};
+
+#[cfg(test)]
+mod test;
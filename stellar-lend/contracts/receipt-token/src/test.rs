@@ -0,0 +1,119 @@
+#![cfg(test)]
+
+use crate::token::*;
+use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+fn setup(transferable: bool) -> (Env, ReceiptTokenClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let id = env.register(ReceiptToken, ());
+    let client = ReceiptTokenClient::new(&env, &id);
+    let admin = Address::generate(&env);
+    client.initialize(
+        &admin,
+        &7,
+        &String::from_str(&env, "StellarLend XLM"),
+        &String::from_str(&env, "slXLM"),
+        &transferable,
+    );
+    (env, client, admin)
+}
+
+#[test]
+fn initialize_sets_metadata() {
+    let (env, client, admin) = setup(true);
+    assert_eq!(client.admin(), admin);
+    assert_eq!(client.decimals(), 7);
+    assert_eq!(client.name(), String::from_str(&env, "StellarLend XLM"));
+    assert_eq!(client.symbol(), String::from_str(&env, "slXLM"));
+    assert!(client.transferable());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn initialize_twice_panics() {
+    let (env, client, admin) = setup(true);
+    client.initialize(
+        &admin,
+        &7,
+        &String::from_str(&env, "x"),
+        &String::from_str(&env, "x"),
+        &true,
+    );
+}
+
+#[test]
+fn mint_and_clawback_track_supply() {
+    let (env, client, _admin) = setup(true);
+    let user = Address::generate(&env);
+    client.mint(&user, &1_000);
+    client.clawback(&user, &400);
+    assert_eq!(client.balance(&user), 600);
+    assert_eq!(client.total_supply(), 600);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #4)")]
+fn clawback_above_balance_panics() {
+    let (env, client, _admin) = setup(true);
+    let user = Address::generate(&env);
+    client.mint(&user, &100);
+    client.clawback(&user, &101);
+}
+
+#[test]
+fn transfers_and_allowances() {
+    let (env, client, _admin) = setup(true);
+    let (a, b, spender) = (
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    );
+    client.mint(&a, &1_000);
+    client.transfer(&a, &b, &300);
+    assert_eq!(client.balance(&a), 700);
+    assert_eq!(client.balance(&b), 300);
+
+    client.approve(&a, &spender, &200, &(env.ledger().sequence() + 100));
+    client.transfer_from(&spender, &a, &b, &150);
+    assert_eq!(client.allowance(&a, &spender), 50);
+    client.burn_from(&spender, &a, &50);
+    client.burn(&b, &450);
+    assert_eq!(client.balance(&a), 500);
+    assert_eq!(client.balance(&b), 0);
+    assert_eq!(client.total_supply(), 500);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #5)")]
+fn transfer_from_above_allowance_panics() {
+    let (env, client, _admin) = setup(true);
+    let (a, spender) = (Address::generate(&env), Address::generate(&env));
+    client.mint(&a, &1_000);
+    client.approve(&a, &spender, &100, &(env.ledger().sequence() + 100));
+    client.transfer_from(&spender, &a, &spender, &101);
+}
+
+#[test]
+fn non_transferable_rejects_holder_actions() {
+    let (env, client, _admin) = setup(false);
+    let (a, b) = (Address::generate(&env), Address::generate(&env));
+    client.mint(&a, &1_000);
+
+    assert_eq!(
+        client.try_transfer(&a, &b, &1),
+        Err(Ok(TokenError::NonTransferable.into()))
+    );
+    assert_eq!(
+        client.try_approve(&a, &b, &1, &(env.ledger().sequence() + 100)),
+        Err(Ok(TokenError::NonTransferable.into()))
+    );
+    assert_eq!(
+        client.try_burn(&a, &1),
+        Err(Ok(TokenError::NonTransferable.into()))
+    );
+
+    // The admin still controls supply
+    client.clawback(&a, &1_000);
+    assert_eq!(client.total_supply(), 0);
+}
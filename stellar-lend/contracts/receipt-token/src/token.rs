@@ -0,0 +1,368 @@
+//! # Receipt Token
+//!
+//! SEP-41 token whose supply is controlled by the lending protocol. Two
+//! instances are deployed per market:
+//!
+//! - **slToken** (`transferable = true`): minted on deposit, burned on
+//!   withdrawal. A freely transferable claim receipt other Soroban protocols
+//!   can hold or price.
+//! - **debtToken** (`transferable = false`): mirrors a borrower's principal.
+//!   Transfers, approvals and holder burns are rejected so debt can never be
+//!   moved to another account.
+//!
+//! Only the admin (the lending contract) can `mint` and `clawback`. Events
+//! follow the SEP-41 topic layout.
+
+use soroban_sdk::{
+    contract, contracterror, contractevent, contractimpl, contracttype, panic_with_error, Address,
+    Env, String,
+};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum TokenError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    NegativeAmount = 3,
+    InsufficientBalance = 4,
+    InsufficientAllowance = 5,
+    NonTransferable = 6,
+    InvalidExpiration = 7,
+    Overflow = 8,
+}
+
+#[contracttype]
+#[derive(Clone)]
+enum DataKey {
+    /// Lending contract allowed to mint and claw back
+    Admin,
+    /// Decimals, name and symbol
+    Metadata,
+    /// Whether holders may transfer, approve and burn
+    Transferable,
+    /// Sum of all balances
+    TotalSupply,
+    Balance(Address),
+    Allowance(Address, Address),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenMetadata {
+    pub decimal: u32,
+    pub name: String,
+    pub symbol: String,
+}
+
+#[contracttype]
+#[derive(Clone)]
+struct AllowanceValue {
+    amount: i128,
+    expiration_ledger: u32,
+}
+
+#[contractevent(data_format = "single-value")]
+#[derive(Clone, Debug)]
+pub struct Transfer {
+    #[topic]
+    pub from: Address,
+    #[topic]
+    pub to: Address,
+    pub amount: i128,
+}
+
+#[contractevent(data_format = "vec")]
+#[derive(Clone, Debug)]
+pub struct Approve {
+    #[topic]
+    pub from: Address,
+    #[topic]
+    pub spender: Address,
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+#[contractevent(data_format = "single-value")]
+#[derive(Clone, Debug)]
+pub struct Mint {
+    #[topic]
+    pub to: Address,
+    pub amount: i128,
+}
+
+#[contractevent(data_format = "single-value")]
+#[derive(Clone, Debug)]
+pub struct Burn {
+    #[topic]
+    pub from: Address,
+    pub amount: i128,
+}
+
+#[contractevent(data_format = "single-value")]
+#[derive(Clone, Debug)]
+pub struct Clawback {
+    #[topic]
+    pub from: Address,
+    pub amount: i128,
+}
+
+#[contract]
+pub struct ReceiptToken;
+
+#[contractimpl]
+impl ReceiptToken {
+    /// Set the admin and metadata; callable once
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        decimal: u32,
+        name: String,
+        symbol: String,
+        transferable: bool,
+    ) {
+        let storage = env.storage().instance();
+        if storage.has(&DataKey::Admin) {
+            panic_with_error!(&env, TokenError::AlreadyInitialized);
+        }
+        storage.set(&DataKey::Admin, &admin);
+        storage.set(
+            &DataKey::Metadata,
+            &TokenMetadata {
+                decimal,
+                name,
+                symbol,
+            },
+        );
+        storage.set(&DataKey::Transferable, &transferable);
+    }
+
+    /// Mint `amount` to `to` (admin only)
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        read_admin(&env).require_auth();
+        check_amount(&env, amount);
+        add_balance(&env, &to, amount);
+        add_supply(&env, amount);
+        Mint { to, amount }.publish(&env);
+    }
+
+    /// Burn `amount` from `from` without its authorization (admin only)
+    pub fn clawback(env: Env, from: Address, amount: i128) {
+        read_admin(&env).require_auth();
+        check_amount(&env, amount);
+        sub_balance(&env, &from, amount);
+        add_supply(&env, -amount);
+        Clawback { from, amount }.publish(&env);
+    }
+
+    pub fn admin(env: Env) -> Address {
+        read_admin(&env)
+    }
+
+    pub fn transferable(env: Env) -> bool {
+        is_transferable(&env)
+    }
+
+    pub fn total_supply(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalSupply)
+            .unwrap_or(0)
+    }
+
+    // ── SEP-41 ────────────────────────────────────────────────────────────
+
+    pub fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        read_allowance(&env, &from, &spender).amount
+    }
+
+    pub fn approve(
+        env: Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32,
+    ) {
+        from.require_auth();
+        require_transferable(&env);
+        check_amount(&env, amount);
+        if amount > 0 && expiration_ledger < env.ledger().sequence() {
+            panic_with_error!(&env, TokenError::InvalidExpiration);
+        }
+
+        let key = DataKey::Allowance(from.clone(), spender.clone());
+        env.storage().temporary().set(
+            &key,
+            &AllowanceValue {
+                amount,
+                expiration_ledger,
+            },
+        );
+        if amount > 0 {
+            let live_for = expiration_ledger - env.ledger().sequence();
+            env.storage()
+                .temporary()
+                .extend_ttl(&key, live_for, live_for);
+        }
+        Approve {
+            from,
+            spender,
+            amount,
+            expiration_ledger,
+        }
+        .publish(&env);
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        read_balance(&env, &id)
+    }
+
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        from.require_auth();
+        require_transferable(&env);
+        check_amount(&env, amount);
+        sub_balance(&env, &from, amount);
+        add_balance(&env, &to, amount);
+        Transfer { from, to, amount }.publish(&env);
+    }
+
+    pub fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        spender.require_auth();
+        require_transferable(&env);
+        check_amount(&env, amount);
+        spend_allowance(&env, &from, &spender, amount);
+        sub_balance(&env, &from, amount);
+        add_balance(&env, &to, amount);
+        Transfer { from, to, amount }.publish(&env);
+    }
+
+    pub fn burn(env: Env, from: Address, amount: i128) {
+        from.require_auth();
+        require_transferable(&env);
+        check_amount(&env, amount);
+        sub_balance(&env, &from, amount);
+        add_supply(&env, -amount);
+        Burn { from, amount }.publish(&env);
+    }
+
+    pub fn burn_from(env: Env, spender: Address, from: Address, amount: i128) {
+        spender.require_auth();
+        require_transferable(&env);
+        check_amount(&env, amount);
+        spend_allowance(&env, &from, &spender, amount);
+        sub_balance(&env, &from, amount);
+        add_supply(&env, -amount);
+        Burn { from, amount }.publish(&env);
+    }
+
+    pub fn decimals(env: Env) -> u32 {
+        metadata(&env).decimal
+    }
+
+    pub fn name(env: Env) -> String {
+        metadata(&env).name
+    }
+
+    pub fn symbol(env: Env) -> String {
+        metadata(&env).symbol
+    }
+}
+
+fn read_admin(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .unwrap_or_else(|| panic_with_error!(env, TokenError::NotInitialized))
+}
+
+fn metadata(env: &Env) -> TokenMetadata {
+    env.storage()
+        .instance()
+        .get(&DataKey::Metadata)
+        .unwrap_or_else(|| panic_with_error!(env, TokenError::NotInitialized))
+}
+
+fn is_transferable(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&DataKey::Transferable)
+        .unwrap_or(false)
+}
+
+fn require_transferable(env: &Env) {
+    if !is_transferable(env) {
+        panic_with_error!(env, TokenError::NonTransferable);
+    }
+}
+
+fn check_amount(env: &Env, amount: i128) {
+    if amount < 0 {
+        panic_with_error!(env, TokenError::NegativeAmount);
+    }
+}
+
+fn read_balance(env: &Env, id: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Balance(id.clone()))
+        .unwrap_or(0)
+}
+
+fn add_balance(env: &Env, id: &Address, amount: i128) {
+    let balance = read_balance(env, id)
+        .checked_add(amount)
+        .unwrap_or_else(|| panic_with_error!(env, TokenError::Overflow));
+    env.storage()
+        .persistent()
+        .set(&DataKey::Balance(id.clone()), &balance);
+}
+
+fn sub_balance(env: &Env, id: &Address, amount: i128) {
+    let balance = read_balance(env, id);
+    if balance < amount {
+        panic_with_error!(env, TokenError::InsufficientBalance);
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::Balance(id.clone()), &(balance - amount));
+}
+
+fn add_supply(env: &Env, delta: i128) {
+    let supply: i128 = env
+        .storage()
+        .instance()
+        .get(&DataKey::TotalSupply)
+        .unwrap_or(0);
+    let supply = supply
+        .checked_add(delta)
+        .unwrap_or_else(|| panic_with_error!(env, TokenError::Overflow));
+    env.storage().instance().set(&DataKey::TotalSupply, &supply);
+}
+
+fn read_allowance(env: &Env, from: &Address, spender: &Address) -> AllowanceValue {
+    let expired = AllowanceValue {
+        amount: 0,
+        expiration_ledger: 0,
+    };
+    env.storage()
+        .temporary()
+        .get::<DataKey, AllowanceValue>(&DataKey::Allowance(from.clone(), spender.clone()))
+        .filter(|a| a.expiration_ledger >= env.ledger().sequence())
+        .unwrap_or(expired)
+}
+
+fn spend_allowance(env: &Env, from: &Address, spender: &Address, amount: i128) {
+    let allowance = read_allowance(env, from, spender);
+    if allowance.amount < amount {
+        panic_with_error!(env, TokenError::InsufficientAllowance);
+    }
+    if amount > 0 {
+        env.storage().temporary().set(
+            &DataKey::Allowance(from.clone(), spender.clone()),
+            &AllowanceValue {
+                amount: allowance.amount - amount,
+                expiration_ledger: allowance.expiration_ledger,
+            },
+        );
+    }
+}
@@ -0,0 +1,7 @@
+#![no_std]
+mod vesting;
+
+pub use vesting::{Grant, VestingContract, VestingContractClient, VestingError};
+
+#[cfg(test)]
+mod test;
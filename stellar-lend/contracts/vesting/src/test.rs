@@ -0,0 +1,120 @@
+#![cfg(test)]
+
+use crate::vesting::*;
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+use stellarlend_testutils::{advance_time, set_time};
+
+const START: u64 = 1_000;
+const YEAR: u64 = 365 * 86_400;
+
+fn setup() -> (
+    Env,
+    VestingContractClient<'static>,
+    Address,
+    TokenClient<'static>,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_time(&env, START);
+    let admin = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    StellarAssetClient::new(&env, &token).mint(&admin, &1_000_000);
+
+    let id = env.register(VestingContract, ());
+    let client = VestingContractClient::new(&env, &id);
+    client.initialize(&admin, &token);
+    let token = TokenClient::new(&env, &token);
+    (env, client, admin, token)
+}
+
+#[test]
+fn linear_vesting_after_cliff() {
+    let (env, client, _admin, token) = setup();
+    let beneficiary = Address::generate(&env);
+    let id = client.create_grant(&beneficiary, &48_000, &START, &YEAR, &(4 * YEAR), &false);
+    assert_eq!(token.balance(&client.address), 48_000);
+
+    advance_time(&env, YEAR - 1);
+    assert_eq!(client.releasable(&beneficiary), 0);
+    assert_eq!(
+        client.try_claim(&beneficiary),
+        Err(Ok(VestingError::NothingToClaim))
+    );
+
+    advance_time(&env, 1);
+    assert_eq!(client.vested(&id), 12_000);
+    assert_eq!(client.claim(&beneficiary), 12_000);
+    assert_eq!(client.locked(&beneficiary), 36_000);
+
+    advance_time(&env, 3 * YEAR);
+    assert_eq!(client.claim(&beneficiary), 36_000);
+    assert_eq!(token.balance(&beneficiary), 48_000);
+    assert_eq!(client.grant(&id).claimed, 48_000);
+}
+
+#[test]
+fn pure_cliff_vests_at_once() {
+    let (env, client, _admin, _token) = setup();
+    let beneficiary = Address::generate(&env);
+    client.create_grant(&beneficiary, &10_000, &START, &YEAR, &YEAR, &false);
+
+    advance_time(&env, YEAR - 1);
+    assert_eq!(client.releasable(&beneficiary), 0);
+    advance_time(&env, 1);
+    assert_eq!(client.releasable(&beneficiary), 10_000);
+}
+
+#[test]
+fn revoke_returns_unvested() {
+    let (env, client, admin, token) = setup();
+    let beneficiary = Address::generate(&env);
+    let fixed = client.create_grant(&beneficiary, &1_000, &START, &0, &YEAR, &false);
+    let id = client.create_grant(&beneficiary, &40_000, &START, &0, &(4 * YEAR), &true);
+    assert_eq!(client.grant_ids(&beneficiary).len(), 2);
+    assert_eq!(
+        client.try_revoke(&fixed),
+        Err(Ok(VestingError::NotRevocable))
+    );
+
+    advance_time(&env, YEAR);
+    assert_eq!(client.revoke(&id), 30_000);
+    assert_eq!(token.balance(&admin), 1_000_000 - 1_000 - 10_000);
+    assert_eq!(
+        client.try_revoke(&id),
+        Err(Ok(VestingError::AlreadyRevoked))
+    );
+
+    // Vesting stops at revocation; what vested stays claimable
+    advance_time(&env, YEAR);
+    assert_eq!(client.vested(&id), 10_000);
+    assert_eq!(client.claim(&beneficiary), 11_000);
+}
+
+#[test]
+fn invalid_grants_rejected() {
+    let (env, client, admin, token) = setup();
+    let beneficiary = Address::generate(&env);
+    assert_eq!(
+        client.try_initialize(&admin, &token.address),
+        Err(Ok(VestingError::AlreadyInitialized))
+    );
+    assert_eq!(
+        client.try_create_grant(&beneficiary, &0, &START, &0, &YEAR, &false),
+        Err(Ok(VestingError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_create_grant(&beneficiary, &100, &START, &0, &0, &false),
+        Err(Ok(VestingError::InvalidSchedule))
+    );
+    assert_eq!(
+        client.try_create_grant(&beneficiary, &100, &START, &(YEAR + 1), &YEAR, &false),
+        Err(Ok(VestingError::InvalidSchedule))
+    );
+    assert_eq!(client.try_grant(&7), Err(Ok(VestingError::GrantNotFound)));
+}
@@ -0,0 +1,358 @@
+//! # Vesting
+//!
+//! Token lock for team and treasury allocations of the governance token.
+//!
+//! The admin funds each grant up front; the tokens stay in this contract
+//! until they vest and the beneficiary claims them. A beneficiary may hold
+//! several grants.
+//!
+//! ## Schedules
+//! A grant vests nothing before `start + cliff_seconds`, then the share of
+//! `total_amount` that `duration_seconds` after `start` has elapsed, and
+//! everything from `start + duration_seconds` on:
+//! - **Linear with cliff**: `0 < cliff_seconds < duration_seconds`
+//! - **Pure linear**: `cliff_seconds == 0`
+//! - **Pure cliff**: `cliff_seconds == duration_seconds`
+//!
+//! ## Revocation
+//! Revocable grants can be revoked by the admin. Vesting stops at the
+//! revocation time: the amount vested by then stays claimable and the rest
+//! returns to the admin.
+//!
+//! ## Governance
+//! Locked tokens are held by this contract, so they never count towards a
+//! beneficiary's token balance. The lending protocol's governance counts
+//! vested, unclaimed tokens through [`VestingContract::releasable`] and
+//! ignores this contract as a voter, so unvested tokens cannot vote.
+
+use soroban_sdk::{
+    contract, contracterror, contractevent, contractimpl, contracttype, token, Address, Env, Vec,
+};
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum VestingError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidAmount = 3,
+    InvalidSchedule = 4,
+    GrantNotFound = 5,
+    NotRevocable = 6,
+    AlreadyRevoked = 7,
+    NothingToClaim = 8,
+    Overflow = 9,
+}
+
+#[contracttype]
+#[derive(Clone)]
+enum DataKey {
+    /// Funds and revokes grants
+    Admin,
+    /// Token being vested
+    Token,
+    /// Id the next grant receives
+    NextGrantId,
+    Grant(u64),
+    /// Grant ids of a beneficiary
+    BeneficiaryGrants(Address),
+}
+
+/// A vesting grant
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Grant {
+    pub id: u64,
+    pub beneficiary: Address,
+    /// Tokens granted
+    pub total_amount: i128,
+    /// Tokens already claimed
+    pub claimed: i128,
+    /// Vesting start time
+    pub start: u64,
+    /// Time after `start` before anything vests
+    pub cliff_seconds: u64,
+    /// Time after `start` at which everything has vested
+    pub duration_seconds: u64,
+    pub revocable: bool,
+    /// Time the grant was revoked; vesting stops there
+    pub revoked_at: Option<u64>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct GrantCreated {
+    #[topic]
+    pub beneficiary: Address,
+    pub grant_id: u64,
+    pub total_amount: i128,
+    pub start: u64,
+    pub cliff_seconds: u64,
+    pub duration_seconds: u64,
+    pub revocable: bool,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct Claimed {
+    #[topic]
+    pub beneficiary: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct GrantRevoked {
+    #[topic]
+    pub beneficiary: Address,
+    pub grant_id: u64,
+    pub vested: i128,
+    pub returned: i128,
+}
+
+#[contract]
+pub struct VestingContract;
+
+#[contractimpl]
+impl VestingContract {
+    /// Set the admin and the vested token; callable once
+    pub fn initialize(env: Env, admin: Address, token: Address) -> Result<(), VestingError> {
+        let storage = env.storage().instance();
+        if storage.has(&DataKey::Admin) {
+            return Err(VestingError::AlreadyInitialized);
+        }
+        storage.set(&DataKey::Admin, &admin);
+        storage.set(&DataKey::Token, &token);
+        Ok(())
+    }
+
+    /// Lock `total_amount` from the admin in a new grant (admin only)
+    ///
+    /// # Returns
+    /// The grant id
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - Amount is not positive
+    /// * `InvalidSchedule` - Zero duration or a cliff past the duration
+    pub fn create_grant(
+        env: Env,
+        beneficiary: Address,
+        total_amount: i128,
+        start: u64,
+        cliff_seconds: u64,
+        duration_seconds: u64,
+        revocable: bool,
+    ) -> Result<u64, VestingError> {
+        let admin = Self::admin(env.clone())?;
+        admin.require_auth();
+        if total_amount <= 0 {
+            return Err(VestingError::InvalidAmount);
+        }
+        if duration_seconds == 0 || cliff_seconds > duration_seconds {
+            return Err(VestingError::InvalidSchedule);
+        }
+
+        token::Client::new(&env, &Self::token(env.clone())?).transfer(
+            &admin,
+            env.current_contract_address(),
+            &total_amount,
+        );
+
+        let id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextGrantId)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextGrantId, &(id + 1));
+        let grant = Grant {
+            id,
+            beneficiary: beneficiary.clone(),
+            total_amount,
+            claimed: 0,
+            start,
+            cliff_seconds,
+            duration_seconds,
+            revocable,
+            revoked_at: None,
+        };
+        env.storage().persistent().set(&DataKey::Grant(id), &grant);
+        let mut ids = Self::grant_ids(env.clone(), beneficiary.clone());
+        ids.push_back(id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::BeneficiaryGrants(beneficiary.clone()), &ids);
+
+        GrantCreated {
+            beneficiary,
+            grant_id: id,
+            total_amount,
+            start,
+            cliff_seconds,
+            duration_seconds,
+            revocable,
+        }
+        .publish(&env);
+        Ok(id)
+    }
+
+    /// Transfer everything vested and unclaimed across the beneficiary's
+    /// grants
+    ///
+    /// # Returns
+    /// The amount claimed
+    pub fn claim(env: Env, beneficiary: Address) -> Result<i128, VestingError> {
+        beneficiary.require_auth();
+        let now = env.ledger().timestamp();
+        let mut amount: i128 = 0;
+        for id in Self::grant_ids(env.clone(), beneficiary.clone()).iter() {
+            let mut grant = Self::grant(env.clone(), id)?;
+            let releasable = vested_at(&grant, now) - grant.claimed;
+            if releasable > 0 {
+                grant.claimed += releasable;
+                amount = amount
+                    .checked_add(releasable)
+                    .ok_or(VestingError::Overflow)?;
+                env.storage().persistent().set(&DataKey::Grant(id), &grant);
+            }
+        }
+        if amount == 0 {
+            return Err(VestingError::NothingToClaim);
+        }
+
+        token::Client::new(&env, &Self::token(env.clone())?).transfer(
+            &env.current_contract_address(),
+            &beneficiary,
+            &amount,
+        );
+        Claimed {
+            beneficiary,
+            amount,
+        }
+        .publish(&env);
+        Ok(amount)
+    }
+
+    /// Stop a revocable grant and return its unvested tokens to the admin
+    /// (admin only)
+    ///
+    /// # Returns
+    /// The amount returned
+    pub fn revoke(env: Env, grant_id: u64) -> Result<i128, VestingError> {
+        let admin = Self::admin(env.clone())?;
+        admin.require_auth();
+        let mut grant = Self::grant(env.clone(), grant_id)?;
+        if !grant.revocable {
+            return Err(VestingError::NotRevocable);
+        }
+        if grant.revoked_at.is_some() {
+            return Err(VestingError::AlreadyRevoked);
+        }
+
+        let now = env.ledger().timestamp();
+        let vested = vested_at(&grant, now);
+        let returned = grant.total_amount - vested;
+        grant.revoked_at = Some(now);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Grant(grant_id), &grant);
+        if returned > 0 {
+            token::Client::new(&env, &Self::token(env.clone())?).transfer(
+                &env.current_contract_address(),
+                &admin,
+                &returned,
+            );
+        }
+
+        GrantRevoked {
+            beneficiary: grant.beneficiary,
+            grant_id,
+            vested,
+            returned,
+        }
+        .publish(&env);
+        Ok(returned)
+    }
+
+    pub fn admin(env: Env) -> Result<Address, VestingError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(VestingError::NotInitialized)
+    }
+
+    pub fn token(env: Env) -> Result<Address, VestingError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Token)
+            .ok_or(VestingError::NotInitialized)
+    }
+
+    pub fn grant(env: Env, grant_id: u64) -> Result<Grant, VestingError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Grant(grant_id))
+            .ok_or(VestingError::GrantNotFound)
+    }
+
+    pub fn grant_ids(env: Env, beneficiary: Address) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BeneficiaryGrants(beneficiary))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Tokens of a grant vested so far (claimed or not)
+    pub fn vested(env: Env, grant_id: u64) -> Result<i128, VestingError> {
+        let grant = Self::grant(env.clone(), grant_id)?;
+        Ok(vested_at(&grant, env.ledger().timestamp()))
+    }
+
+    /// Tokens the beneficiary could claim now
+    pub fn releasable(env: Env, beneficiary: Address) -> i128 {
+        let now = env.ledger().timestamp();
+        Self::grants_of(&env, &beneficiary)
+            .iter()
+            .map(|grant| vested_at(&grant, now) - grant.claimed)
+            .sum()
+    }
+
+    /// Tokens of the beneficiary still locked (not yet vested, excluding
+    /// revoked remainders)
+    pub fn locked(env: Env, beneficiary: Address) -> i128 {
+        let now = env.ledger().timestamp();
+        Self::grants_of(&env, &beneficiary)
+            .iter()
+            .filter(|grant| grant.revoked_at.is_none())
+            .map(|grant| grant.total_amount - vested_at(&grant, now))
+            .sum()
+    }
+}
+
+impl VestingContract {
+    fn grants_of(env: &Env, beneficiary: &Address) -> Vec<Grant> {
+        let mut grants = Vec::new(env);
+        for id in Self::grant_ids(env.clone(), beneficiary.clone()).iter() {
+            if let Some(grant) = env.storage().persistent().get(&DataKey::Grant(id)) {
+                grants.push_back(grant);
+            }
+        }
+        grants
+    }
+}
+
+/// Amount of a grant vested at `now`, frozen at its revocation time
+fn vested_at(grant: &Grant, now: u64) -> i128 {
+    let at = grant
+        .revoked_at
+        .map_or(now, |revoked_at| now.min(revoked_at));
+    if at < grant.start.saturating_add(grant.cliff_seconds) {
+        return 0;
+    }
+    let elapsed = at - grant.start;
+    if elapsed >= grant.duration_seconds {
+        return grant.total_amount;
+    }
+    grant.total_amount * elapsed as i128 / grant.duration_seconds as i128
+}
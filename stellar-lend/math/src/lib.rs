@@ -0,0 +1,135 @@
+//! # StellarLend Math
+//!
+//! Fixed-point math shared by the core (hello-world) and lending contracts,
+//! so both compute interest, health factors and close amounts the same way.
+//!
+//! - [`mul_div`]: `a * b / denominator` with a 256-bit intermediate, so large
+//!   balances do not overflow before the division brings them back in range.
+//! - [`simple_interest`]: interest on a principal at an annual rate in basis
+//!   points over an elapsed time.
+//! - [`health_factor`]: collateral weighted by a liquidation threshold (the
+//!   lending contract's convention, e.g. 8000 = 80%) over debt.
+//! - [`health_factor_from_ratio`]: collateral ratio over a minimum ratio (the
+//!   core contract's convention, e.g. 10500 = 105%).
+//! - [`close_amount`]: the share of a debt a single liquidation may repay.
+//!
+//! Every function is pure and returns `None` when the result does not fit an
+//! `i128` or a denominator is zero; callers map that to their own overflow
+//! error. Divisions truncate toward zero.
+
+#![no_std]
+
+#[cfg(test)]
+mod test;
+
+/// Basis point scale: 10000 = 100%
+pub const BPS_SCALE: i128 = 10_000;
+
+/// Health factor scale: 10000 = 1.0; positions below it are liquidatable
+pub const HEALTH_FACTOR_SCALE: i128 = 10_000;
+
+const LOW_MASK: u128 = u64::MAX as u128;
+
+/// `a * b / denominator`, computed with a 256-bit intermediate product
+pub fn mul_div(a: i128, b: i128, denominator: i128) -> Option<i128> {
+    if denominator == 0 {
+        return None;
+    }
+    let negative = (a < 0) ^ (b < 0) ^ (denominator < 0);
+    let (hi, lo) = mul_wide(a.unsigned_abs(), b.unsigned_abs());
+    let quotient = div_wide(hi, lo, denominator.unsigned_abs())?;
+    let quotient = i128::try_from(quotient).ok()?;
+    Some(if negative { -quotient } else { quotient })
+}
+
+/// Interest on `principal` at `rate_bps` per year over `elapsed` seconds
+///
+/// `principal * rate_bps * elapsed / (BPS_SCALE * seconds_per_year)`
+pub fn simple_interest(
+    principal: i128,
+    rate_bps: i128,
+    elapsed: u64,
+    seconds_per_year: u64,
+) -> Option<i128> {
+    let rate_time = rate_bps.checked_mul(elapsed as i128)?;
+    let denominator = BPS_SCALE.checked_mul(seconds_per_year as i128)?;
+    mul_div(principal, rate_time, denominator)
+}
+
+/// Health factor of collateral weighted by a liquidation threshold
+///
+/// `(collateral_value * liquidation_threshold_bps / BPS_SCALE) *
+/// HEALTH_FACTOR_SCALE / debt_value`. `None` when there is no debt.
+pub fn health_factor(
+    collateral_value: i128,
+    debt_value: i128,
+    liquidation_threshold_bps: i128,
+) -> Option<i128> {
+    if debt_value <= 0 {
+        return None;
+    }
+    let weighted = mul_div(collateral_value, liquidation_threshold_bps, BPS_SCALE)?;
+    mul_div(weighted, HEALTH_FACTOR_SCALE, debt_value)
+}
+
+/// Health factor of a collateral ratio over a minimum ratio
+///
+/// `collateral_value * HEALTH_FACTOR_SCALE * BPS_SCALE /
+/// (debt_value * min_ratio_bps)`. `None` when there is no debt.
+pub fn health_factor_from_ratio(
+    collateral_value: i128,
+    debt_value: i128,
+    min_ratio_bps: i128,
+) -> Option<i128> {
+    if debt_value <= 0 {
+        return None;
+    }
+    mul_div(
+        collateral_value,
+        HEALTH_FACTOR_SCALE * BPS_SCALE,
+        debt_value.checked_mul(min_ratio_bps)?,
+    )
+}
+
+/// Most of `debt` a single liquidation may repay under `close_factor_bps`
+pub fn close_amount(debt: i128, close_factor_bps: i128) -> Option<i128> {
+    mul_div(debt, close_factor_bps, BPS_SCALE)
+}
+
+/// Full 256-bit product of `a` and `b` as (high, low) halves
+fn mul_wide(a: u128, b: u128) -> (u128, u128) {
+    let (a_hi, a_lo) = (a >> 64, a & LOW_MASK);
+    let (b_hi, b_lo) = (b >> 64, b & LOW_MASK);
+    let lo_lo = a_lo * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_lo = a_hi * b_lo;
+    let hi_hi = a_hi * b_hi;
+
+    let middle = (lo_lo >> 64) + (lo_hi & LOW_MASK) + (hi_lo & LOW_MASK);
+    let low = (lo_lo & LOW_MASK) | (middle << 64);
+    let high = hi_hi + (lo_hi >> 64) + (hi_lo >> 64) + (middle >> 64);
+    (high, low)
+}
+
+/// (high, low) / `divisor`, or None when the quotient exceeds 128 bits
+fn div_wide(high: u128, low: u128, divisor: u128) -> Option<u128> {
+    if high == 0 {
+        return Some(low / divisor);
+    }
+    if high >= divisor {
+        return None;
+    }
+    // Long division, one bit of the low half at a time
+    let mut remainder = high;
+    let mut quotient = 0u128;
+    for bit in (0..128).rev() {
+        let carry = remainder >> 127;
+        remainder = (remainder << 1) | ((low >> bit) & 1);
+        quotient <<= 1;
+        if carry == 1 || remainder >= divisor {
+            remainder = remainder.wrapping_sub(divisor);
+            quotient |= 1;
+        }
+    }
+    Some(quotient)
+}
@@ -0,0 +1,60 @@
+use super::*;
+
+const SECONDS_PER_YEAR: u64 = 31_536_000;
+
+#[test]
+fn test_mul_div_wide_intermediate() {
+    assert_eq!(mul_div(6, 7, 2), Some(21));
+    assert_eq!(mul_div(-6, 7, 4), Some(-10));
+    assert_eq!(mul_div(1, 1, 0), None);
+
+    // The product overflows i128 but the quotient fits
+    assert_eq!(mul_div(i128::MAX, 10, 10), Some(i128::MAX));
+    assert_eq!(
+        mul_div(10i128.pow(30), 10i128.pow(12), 10i128.pow(20)),
+        Some(10i128.pow(22))
+    );
+    assert_eq!(mul_div(i128::MAX, 3, 2), None);
+}
+
+#[test]
+fn test_simple_interest() {
+    // 5% of 10_000 over a year
+    assert_eq!(
+        simple_interest(10_000, 500, SECONDS_PER_YEAR, SECONDS_PER_YEAR),
+        Some(500)
+    );
+    assert_eq!(simple_interest(10_000, 500, 0, SECONDS_PER_YEAR), Some(0));
+    assert_eq!(simple_interest(10_000, 500, 1, 0), None);
+
+    // 10^30 at 5% for three years needs more than 128 bits in between
+    let interest = simple_interest(10i128.pow(30), 500, 3 * SECONDS_PER_YEAR, SECONDS_PER_YEAR);
+    assert_eq!(interest, Some(15 * 10i128.pow(28)));
+    assert_eq!(
+        simple_interest(i128::MAX, 10_000, 100 * SECONDS_PER_YEAR, SECONDS_PER_YEAR),
+        None
+    );
+}
+
+#[test]
+fn test_health_factors() {
+    // 1_000 collateral at an 80% threshold against 800 debt is exactly 1.0
+    assert_eq!(health_factor(1_000, 800, 8_000), Some(HEALTH_FACTOR_SCALE));
+    assert_eq!(health_factor(1_000, 0, 8_000), None);
+    assert_eq!(health_factor(i128::MAX / 2, 1, 8_000), None);
+
+    // A 105% collateral ratio against a 105% minimum is exactly 1.0
+    assert_eq!(
+        health_factor_from_ratio(1_050, 1_000, 10_500),
+        Some(HEALTH_FACTOR_SCALE)
+    );
+    assert_eq!(health_factor_from_ratio(2_100, 1_000, 10_500), Some(20_000));
+    assert_eq!(health_factor_from_ratio(1_000, 0, 10_500), None);
+}
+
+#[test]
+fn test_close_amount() {
+    assert_eq!(close_amount(1_000, 5_000), Some(500));
+    assert_eq!(close_amount(999, 5_000), Some(499));
+    assert_eq!(close_amount(i128::MAX, BPS_SCALE), Some(i128::MAX));
+}
@@ -0,0 +1,97 @@
+//! Mock flash loan receiver contract.
+
+use soroban_sdk::{contract, contractimpl, contracttype, token, Address, Bytes, Env};
+
+/// How the mock receiver handles a flash loan
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReceiverBehavior {
+    /// Return principal plus fee and report success
+    Repay,
+    /// Return only the principal and report success
+    RepayPrincipal,
+    /// Keep the funds and report success
+    Keep,
+    /// Return principal plus fee but report failure
+    Reject,
+}
+
+#[contracttype]
+#[derive(Clone)]
+enum MockReceiverKey {
+    /// Lending contract that funds the loans
+    Provider,
+    /// Configured behavior
+    Behavior,
+    /// `params` of the last `execute_operation` call
+    LastParams,
+}
+
+/// Flash loan receiver with scripted behavior
+///
+/// Implements `execute_operation(asset, amount, fee, params) -> bool`, the
+/// callback the lending contract invokes after transferring the loan.
+/// Repayments are pushed back to the provider with a plain token transfer,
+/// so the receiver must hold enough extra balance to cover the fee.
+#[contract]
+pub struct MockFlashLoanReceiver;
+
+#[contractimpl]
+impl MockFlashLoanReceiver {
+    /// Set the lending contract and the behavior for subsequent loans
+    pub fn init(env: Env, provider: Address, behavior: ReceiverBehavior) {
+        env.storage()
+            .instance()
+            .set(&MockReceiverKey::Provider, &provider);
+        env.storage()
+            .instance()
+            .set(&MockReceiverKey::Behavior, &behavior);
+    }
+
+    /// Flash loan callback
+    pub fn execute_operation(
+        env: Env,
+        asset: Address,
+        amount: i128,
+        fee: i128,
+        params: Bytes,
+    ) -> bool {
+        let storage = env.storage().instance();
+        let provider: Address = storage
+            .get(&MockReceiverKey::Provider)
+            .expect("mock receiver: not initialized");
+        let behavior: ReceiverBehavior = storage.get(&MockReceiverKey::Behavior).unwrap();
+        storage.set(&MockReceiverKey::LastParams, &params);
+
+        let repayment = match behavior {
+            ReceiverBehavior::Repay | ReceiverBehavior::Reject => amount + fee,
+            ReceiverBehavior::RepayPrincipal => amount,
+            ReceiverBehavior::Keep => 0,
+        };
+        if repayment > 0 {
+            token::Client::new(&env, &asset).transfer(
+                &env.current_contract_address(),
+                &provider,
+                &repayment,
+            );
+        }
+
+        behavior != ReceiverBehavior::Reject
+    }
+
+    /// `params` passed to the last callback, if any
+    pub fn last_params(env: Env) -> Option<Bytes> {
+        env.storage().instance().get(&MockReceiverKey::LastParams)
+    }
+}
+
+/// Register a mock receiver for loans from `provider`
+pub fn register_mock_receiver(
+    env: &Env,
+    provider: &Address,
+    behavior: ReceiverBehavior,
+) -> Address {
+    let receiver = env.register(MockFlashLoanReceiver, ());
+    MockFlashLoanReceiverClient::new(env, &receiver).init(provider, &behavior);
+    receiver
+}
@@ -0,0 +1,38 @@
+//! # StellarLend Test Utilities
+//!
+//! Shared mocks and helpers for the contract test suites (hello-world,
+//! lending, amm, bridge), so each suite does not have to stub token and
+//! oracle behavior on its own.
+//!
+//! - [`MockToken`]: a Stellar Asset Contract registered in the test env with
+//!   mint/approve/balance helpers.
+//! - [`MockOracle`]: a price oracle contract exposing `price(asset) -> i128`
+//!   (8 decimals), the interface the lending contract reads from.
+//! - [`MockSep40Oracle`]: a SEP-40 (Reflector-style) oracle exposing
+//!   `lastprice(asset)` and `decimals()` with test-controlled timestamps.
+//! - [`MockFlashLoanReceiver`]: a flash loan receiver implementing
+//!   `execute_operation` with scripted repayment behavior.
+//! - [`advance_time`] / [`set_time`]: ledger timestamp helpers.
+//!
+//! This crate is only meant to be used as a `dev-dependency`.
+
+#![no_std]
+
+mod flash_loan;
+mod oracle;
+mod sep40;
+mod time;
+mod token;
+
+#[cfg(test)]
+mod test;
+
+pub use flash_loan::{
+    register_mock_receiver, MockFlashLoanReceiver, MockFlashLoanReceiverClient, ReceiverBehavior,
+};
+pub use oracle::{register_mock_oracle, set_price, MockOracle, MockOracleClient, PRICE_SCALE};
+pub use sep40::{
+    register_mock_sep40_oracle, MockSep40Oracle, MockSep40OracleClient, Sep40Asset, Sep40PriceData,
+};
+pub use time::{advance_time, set_time};
+pub use token::{register_mock_token, MockToken};
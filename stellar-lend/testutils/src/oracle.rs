@@ -0,0 +1,72 @@
+//! Mock price oracle contract.
+
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+
+/// Fixed-point scale of mock oracle prices (8 decimals, 100_000_000 = 1.0)
+pub const PRICE_SCALE: i128 = 100_000_000;
+
+#[contracttype]
+#[derive(Clone)]
+enum MockOracleKey {
+    /// Price for a specific asset
+    Price(Address),
+    /// Ledger timestamp of the last `set_price` for an asset
+    UpdatedAt(Address),
+    /// Price returned for assets without their own price
+    DefaultPrice,
+}
+
+/// Oracle returning prices set by the test
+///
+/// Implements `price(asset) -> i128`, the interface read by the lending
+/// contract. Unpriced assets fall back to the default price and panic when
+/// none is set, so a missing setup step fails loudly.
+#[contract]
+pub struct MockOracle;
+
+#[contractimpl]
+impl MockOracle {
+    /// Set the price of `asset` (8 decimals)
+    pub fn set_price(env: Env, asset: Address, price: i128) {
+        env.storage()
+            .instance()
+            .set(&MockOracleKey::Price(asset.clone()), &price);
+        env.storage()
+            .instance()
+            .set(&MockOracleKey::UpdatedAt(asset), &env.ledger().timestamp());
+    }
+
+    /// Set the price returned for assets without their own price
+    pub fn set_default_price(env: Env, price: i128) {
+        env.storage()
+            .instance()
+            .set(&MockOracleKey::DefaultPrice, &price);
+    }
+
+    /// Price of `asset` (8 decimals)
+    pub fn price(env: Env, asset: Address) -> i128 {
+        env.storage()
+            .instance()
+            .get(&MockOracleKey::Price(asset))
+            .or_else(|| env.storage().instance().get(&MockOracleKey::DefaultPrice))
+            .expect("mock oracle: price not set")
+    }
+
+    /// Ledger timestamp of the last `set_price` for `asset` (0 if never set)
+    pub fn last_updated(env: Env, asset: Address) -> u64 {
+        env.storage()
+            .instance()
+            .get(&MockOracleKey::UpdatedAt(asset))
+            .unwrap_or(0)
+    }
+}
+
+/// Register a mock oracle with no prices set
+pub fn register_mock_oracle(env: &Env) -> Address {
+    env.register(MockOracle, ())
+}
+
+/// Set the price of `asset` on a registered mock oracle
+pub fn set_price(env: &Env, oracle: &Address, asset: &Address, price: i128) {
+    MockOracleClient::new(env, oracle).set_price(asset, &price);
+}
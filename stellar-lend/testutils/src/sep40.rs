@@ -0,0 +1,75 @@
+//! Mock SEP-40 price oracle contract.
+
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Symbol};
+
+/// SEP-40 asset identifier
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Sep40Asset {
+    /// Stellar asset contract address
+    Stellar(Address),
+    /// Off-chain asset ticker
+    Other(Symbol),
+}
+
+/// SEP-40 price record
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Sep40PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+enum MockSep40Key {
+    /// Decimals reported by `decimals()`
+    Decimals,
+    /// Latest record for an asset
+    Price(Sep40Asset),
+}
+
+/// SEP-40 oracle (e.g. Reflector) returning records set by the test
+///
+/// Implements `decimals() -> u32` and `lastprice(asset) -> Option<PriceData>`.
+/// Records carry an explicit timestamp so tests can simulate stale feeds.
+#[contract]
+pub struct MockSep40Oracle;
+
+#[contractimpl]
+impl MockSep40Oracle {
+    /// Set the decimals reported for all prices
+    pub fn init(env: Env, decimals: u32) {
+        env.storage()
+            .instance()
+            .set(&MockSep40Key::Decimals, &decimals);
+    }
+
+    /// Set the latest record for `asset`
+    pub fn set_lastprice(env: Env, asset: Sep40Asset, price: i128, timestamp: u64) {
+        env.storage().instance().set(
+            &MockSep40Key::Price(asset),
+            &Sep40PriceData { price, timestamp },
+        );
+    }
+
+    /// Decimals of all prices
+    pub fn decimals(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&MockSep40Key::Decimals)
+            .expect("mock sep40 oracle: not initialized")
+    }
+
+    /// Latest record for `asset`, if any
+    pub fn lastprice(env: Env, asset: Sep40Asset) -> Option<Sep40PriceData> {
+        env.storage().instance().get(&MockSep40Key::Price(asset))
+    }
+}
+
+/// Register a mock SEP-40 oracle reporting prices with `decimals`
+pub fn register_mock_sep40_oracle(env: &Env, decimals: u32) -> Address {
+    let oracle = env.register(MockSep40Oracle, ());
+    MockSep40OracleClient::new(env, &oracle).init(&decimals);
+    oracle
+}
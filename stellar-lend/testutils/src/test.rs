@@ -0,0 +1,85 @@
+use super::*;
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+#[test]
+fn test_mock_token_mint_and_approve() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let token = register_mock_token(&env);
+    let user = Address::generate(&env);
+    let spender = Address::generate(&env);
+
+    token.mint_and_approve(&user, &spender, 1_000);
+
+    assert_eq!(token.balance(&user), 1_000);
+    assert_eq!(token.client.allowance(&user, &spender), 1_000);
+}
+
+#[test]
+fn test_mock_oracle_prices() {
+    let env = Env::default();
+    set_time(&env, 500);
+    let oracle = register_mock_oracle(&env);
+    let client = MockOracleClient::new(&env, &oracle);
+    let priced = Address::generate(&env);
+    let unpriced = Address::generate(&env);
+
+    set_price(&env, &oracle, &priced, 2 * PRICE_SCALE);
+    client.set_default_price(&PRICE_SCALE);
+
+    assert_eq!(client.price(&priced), 2 * PRICE_SCALE);
+    assert_eq!(client.price(&unpriced), PRICE_SCALE);
+    assert_eq!(client.last_updated(&priced), 500);
+    assert_eq!(client.last_updated(&unpriced), 0);
+}
+
+#[test]
+#[should_panic]
+fn test_mock_oracle_panics_without_price() {
+    let env = Env::default();
+    let oracle = register_mock_oracle(&env);
+    MockOracleClient::new(&env, &oracle).price(&Address::generate(&env));
+}
+
+#[test]
+fn test_advance_time() {
+    let env = Env::default();
+    set_time(&env, 1_000);
+    advance_time(&env, 60);
+    assert_eq!(env.ledger().timestamp(), 1_060);
+}
+
+#[test]
+fn test_mock_receiver_repays_provider() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let token = register_mock_token(&env);
+    let provider = Address::generate(&env);
+    let receiver = register_mock_receiver(&env, &provider, ReceiverBehavior::Repay);
+    let client = MockFlashLoanReceiverClient::new(&env, &receiver);
+    token.mint(&receiver, 1_010);
+
+    let params = soroban_sdk::Bytes::from_slice(&env, b"swap");
+    assert!(client.execute_operation(&token.address, &1_000, &10, &params));
+    assert_eq!(token.balance(&provider), 1_010);
+    assert_eq!(client.last_params(), Some(params));
+}
+
+#[test]
+fn test_mock_sep40_oracle_records() {
+    let env = Env::default();
+    let oracle = register_mock_sep40_oracle(&env, 14);
+    let client = MockSep40OracleClient::new(&env, &oracle);
+    let asset = Sep40Asset::Stellar(Address::generate(&env));
+
+    assert_eq!(client.decimals(), 14);
+    assert_eq!(client.lastprice(&asset), None);
+    client.set_lastprice(&asset, &(2 * PRICE_SCALE), &700);
+    assert_eq!(
+        client.lastprice(&asset),
+        Some(Sep40PriceData {
+            price: 2 * PRICE_SCALE,
+            timestamp: 700,
+        })
+    );
+}
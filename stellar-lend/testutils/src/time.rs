@@ -0,0 +1,13 @@
+//! Ledger time helpers.
+
+use soroban_sdk::{testutils::Ledger, Env};
+
+/// Move the ledger timestamp forward by `seconds`
+pub fn advance_time(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|li| li.timestamp += seconds);
+}
+
+/// Set the ledger timestamp
+pub fn set_time(env: &Env, timestamp: u64) {
+    env.ledger().with_mut(|li| li.timestamp = timestamp);
+}
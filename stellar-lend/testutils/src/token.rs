@@ -0,0 +1,56 @@
+//! Mock token backed by the built-in Stellar Asset Contract.
+
+use soroban_sdk::{testutils::Address as _, token, Address, Env};
+
+/// A token registered in the test environment with its issuer
+pub struct MockToken<'a> {
+    env: Env,
+    /// Token contract address
+    pub address: Address,
+    /// Issuer allowed to mint
+    pub admin: Address,
+    /// SEP-41 client for transfers, balances and allowances
+    pub client: token::Client<'a>,
+    /// Admin client for minting
+    pub admin_client: token::StellarAssetClient<'a>,
+}
+
+impl MockToken<'_> {
+    /// Mint `amount` to `to`
+    pub fn mint(&self, to: &Address, amount: i128) {
+        self.admin_client.mint(to, &amount);
+    }
+
+    /// Approve `spender` to move `amount` from `from` for the next 1000 ledgers
+    pub fn approve(&self, from: &Address, spender: &Address, amount: i128) {
+        let expiration = self.env.ledger().sequence() + 1_000;
+        self.client.approve(from, spender, &amount, &expiration);
+    }
+
+    /// Mint `amount` to `to` and approve `spender` to move it
+    pub fn mint_and_approve(&self, to: &Address, spender: &Address, amount: i128) {
+        self.mint(to, amount);
+        self.approve(to, spender, amount);
+    }
+
+    /// Balance of `id`
+    pub fn balance(&self, id: &Address) -> i128 {
+        self.client.balance(id)
+    }
+}
+
+/// Register a new token with a freshly generated issuer
+pub fn register_mock_token<'a>(env: &Env) -> MockToken<'a> {
+    let admin = Address::generate(env);
+    let address = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+
+    MockToken {
+        env: env.clone(),
+        client: token::Client::new(env, &address),
+        admin_client: token::StellarAssetClient::new(env, &address),
+        address,
+        admin,
+    }
+}